@@ -2,14 +2,24 @@ use anyhow::{Context, Result};
 use clap::Subcommand;
 use std::sync::Arc;
 
-use retrochat_core::database::DatabaseManager;
+use retrochat_core::database::{
+    DatabaseManager, MessageRepository, SessionSummaryRepository, ToolOperationRepository,
+};
 use retrochat_core::env::{apis as env_vars, llm as env_llm};
-use retrochat_core::models::OperationStatus;
+use retrochat_core::models::{
+    AnalyticsRequest, OperationStatus, SessionComparison, SessionOutcome,
+};
 use retrochat_core::services::{
+    analytics::{ComparisonWinner, ThrashKind},
     google_ai::{GoogleAiClient, GoogleAiConfig},
-    llm::{LlmClientFactory, LlmConfig, LlmProvider},
-    AnalyticsRequestService,
+    llm::{diagnostics::diagnose_providers, LlmClientFactory, LlmConfig, LlmProvider},
+    AnalyticsRequestService, AnalyticsService, ReworkDetector,
 };
+use retrochat_core::utils::locale::Language;
+use retrochat_core::utils::time_parser;
+
+/// Costliest turns listed under an analysis
+const MAX_TURNS_SHOWN: usize = 5;
 
 #[derive(Subcommand)]
 pub enum AnalyticsCommands {
@@ -77,15 +87,12 @@ pub async fn handle_execute_command(
     let db_path = retrochat_core::database::config::get_default_db_path()?;
     let db_manager = Arc::new(DatabaseManager::new(&db_path).await?);
 
-    // Determine LLM provider from --provider flag or environment variable
+    // Determine LLM provider from --provider flag, environment variable or config file
     let llm_provider: LlmProvider = if let Some(p) = provider.as_deref() {
         p.parse::<LlmProvider>()
             .map_err(|e| anyhow::anyhow!("{e}"))?
-    } else if let Ok(p) = std::env::var(env_llm::RETROCHAT_LLM_PROVIDER) {
-        p.parse::<LlmProvider>()
-            .map_err(|e| anyhow::anyhow!("{e}"))?
     } else {
-        LlmProvider::GoogleAi
+        retrochat_core::config::get_llm_provider()?.unwrap_or(LlmProvider::GoogleAi)
     };
 
     // Build LLM config
@@ -101,6 +108,10 @@ pub async fn handle_execute_command(
     };
 
     // Apply model if specified
+    let model = match model {
+        Some(model) => Some(model),
+        None => retrochat_core::config::get_llm_model()?,
+    };
     if let Some(m) = model {
         config = config.with_model(m);
     }
@@ -268,8 +279,16 @@ async fn show_session_results(service: &AnalyticsRequestService, session_id: &st
         .list_analyses(Some(session_id.to_string()), None)
         .await
         .map_err(|e| anyhow::anyhow!("Failed to list analyses: {e}"))?;
+    // Comparisons are filed under their first session; list the others too
+    let compared_as_second: Vec<SessionComparison> = service
+        .list_comparisons(session_id)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to list comparisons: {e}"))?
+        .into_iter()
+        .filter(|comparison| comparison.other_session_id == session_id)
+        .collect();
 
-    if requests.is_empty() {
+    if requests.is_empty() && compared_as_second.is_empty() {
         println!("No analysis found for session: {session_id}");
         println!("Run 'retrochat analytics execute {session_id}' to analytics this session");
         return Ok(());
@@ -288,16 +307,14 @@ async fn show_session_results(service: &AnalyticsRequestService, session_id: &st
                 // TODO: 좀더 좋은 방식으로 구현해야 합니다
                 println!("{}", serde_json::to_string_pretty(&analysis)?);
             }
-            None => {
-                println!("Request {} - Status: {:?}", request.id, request.status);
-                if let Some(error) = &request.error_message {
-                    println!("Error: {error}");
-                }
-                println!();
-            }
+            None => print_comparison_or_status(service, &request).await?,
         }
     }
 
+    for comparison in &compared_as_second {
+        print_comparison(comparison);
+    }
+
     Ok(())
 }
 
@@ -325,15 +342,68 @@ async fn show_all_results(service: &AnalyticsRequestService) -> Result<()> {
             Some(analysis) => {
                 println!("{}", serde_json::to_string_pretty(&analysis)?);
             }
-            None => {
-                println!("Request {} - Status: {:?}", request.id, request.status);
-                if let Some(error) = &request.error_message {
-                    println!("Error: {error}");
-                }
-                println!();
+            None => print_comparison_or_status(service, &request).await?,
+        }
+    }
+
+    Ok(())
+}
+
+/// Print a request's comparison, or its status if it has no result to show
+async fn print_comparison_or_status(
+    service: &AnalyticsRequestService,
+    request: &AnalyticsRequest,
+) -> Result<()> {
+    let comparison = service
+        .get_comparison_result(&request.id)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to get comparison: {e}"))?;
+    match comparison {
+        Some(comparison) if comparison.result.is_some() => print_comparison(&comparison),
+        _ => {
+            println!("Request {} - Status: {:?}", request.id, request.status);
+            if let Some(error) = &request.error_message {
+                println!("Error: {error}");
             }
+            println!();
         }
     }
+    Ok(())
+}
+
+/// Handle the analysis compare command
+pub async fn handle_compare_command(
+    session_id: String,
+    other_session_id: String,
+    provider: Option<String>,
+    model: Option<String>,
+) -> Result<()> {
+    let db_path = retrochat_core::database::config::get_default_db_path()?;
+    let db_manager = Arc::new(DatabaseManager::new(&db_path).await?);
+    let llm_client = super::summarize::create_llm_client(provider, model)?;
+    let service = AnalyticsRequestService::new_with_llm(db_manager, llm_client);
+
+    let request = service
+        .create_comparison_request(session_id, other_session_id, None)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to create comparison request: {e}"))?;
+
+    print!("Comparing sessions... ");
+    std::io::Write::flush(&mut std::io::stdout()).unwrap();
+
+    if let Err(e) = service.execute_analysis(request.id.clone()).await {
+        println!("✗ Comparison failed: {e}");
+        return Err(anyhow::anyhow!("Comparison failed: {e}"));
+    }
+    println!("✓ Comparison completed");
+
+    if let Some(comparison) = service
+        .get_comparison_result(&request.id)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to get comparison result: {e}"))?
+    {
+        print_comparison(&comparison);
+    }
 
     Ok(())
 }
@@ -413,6 +483,23 @@ async fn show_historical_status(service: &AnalyticsRequestService) -> Result<()>
         if let Some(completed_at) = request.completed_at {
             println!("  Completed: {completed_at}");
         }
+        if let Ok(Some(metrics)) = service.get_retry_metrics(&request.id).await {
+            println!(
+                "  LLM calls: {} ({} retried, {:.1}s backing off)",
+                metrics.attempts,
+                metrics.retries,
+                metrics.wait_ms as f64 / 1000.0
+            );
+        }
+        if let Ok(Some(metrics)) = service.get_parse_metrics(&request.id).await {
+            println!(
+                "  Responses parsed: {}/{} ({} repaired, {} unparseable)",
+                metrics.parsed + metrics.repaired,
+                metrics.total(),
+                metrics.repaired,
+                metrics.failed
+            );
+        }
         if let Some(error) = &request.error_message {
             println!("  Error: {error}");
         }
@@ -523,6 +610,214 @@ async fn list_cancellable_requests(service: &AnalyticsRequestService) -> Result<
     Ok(())
 }
 
+pub async fn handle_rework_command(limit: usize, threshold: Option<f32>) -> Result<()> {
+    let db_path = retrochat_core::database::config::get_default_db_path()?;
+    let db_manager = Arc::new(DatabaseManager::new(&db_path).await?);
+
+    let mut detector = ReworkDetector::new(db_manager);
+    if let Some(threshold) = threshold {
+        if !(0.0..=1.0).contains(&threshold) {
+            anyhow::bail!("Threshold must be between 0.0 and 1.0, got {threshold}");
+        }
+        detector = detector.with_threshold(threshold);
+    }
+
+    let repeated = detector.find_repeated_prompts(limit).await?;
+
+    if repeated.is_empty() {
+        println!("No repeated asks found across sessions");
+        return Ok(());
+    }
+
+    println!("=== Most Repeated Asks ===");
+    println!();
+
+    for (index, prompt) in repeated.iter().enumerate() {
+        let preview: String = prompt.prompt.chars().take(100).collect();
+        let ellipsis = if prompt.prompt.chars().count() > 100 {
+            "..."
+        } else {
+            ""
+        };
+        println!("{}. {}{}", index + 1, preview.replace('\n', " "), ellipsis);
+        println!(
+            "   Asked {} times across {} sessions ({} → {})",
+            prompt.occurrences,
+            prompt.session_ids.len(),
+            Language::current().format_date(prompt.first_seen.date_naive()),
+            Language::current().format_date(prompt.last_seen.date_naive())
+        );
+        println!();
+    }
+
+    Ok(())
+}
+
+pub async fn handle_recompute_scores_command() -> Result<()> {
+    let db_path = retrochat_core::database::config::get_default_db_path()?;
+    let db_manager = Arc::new(DatabaseManager::new(&db_path).await?);
+    let service = AnalyticsService::new(db_manager);
+    let report = service.recompute_rubric_summaries().await?;
+
+    if report.analyses == 0 {
+        println!("No analyses with rubric scores");
+        return Ok(());
+    }
+    println!(
+        "Recomputed {} analyses, {} changed",
+        report.analyses, report.updated
+    );
+    for rubric in &report.unmatched {
+        println!("⚠ analysis.rubric_weights.{rubric} matches no scored rubric");
+    }
+    Ok(())
+}
+
+pub async fn handle_workflows_command() -> Result<()> {
+    let db_path = retrochat_core::database::config::get_default_db_path()?;
+    let db_manager = DatabaseManager::new(&db_path).await?;
+    let stats = MessageRepository::new(&db_manager).workflow_stats().await?;
+
+    if stats.is_empty() {
+        println!("No slash commands or hook runs found");
+        println!(
+            "Sessions imported before they were recorded need `retrochat sync claude --overwrite`"
+        );
+        return Ok(());
+    }
+
+    let outcomes = SessionSummaryRepository::new(&db_manager)
+        .get_outcomes()
+        .await?;
+    let completed = outcomes
+        .iter()
+        .filter(|(_, outcome)| *outcome == SessionOutcome::Completed)
+        .count();
+
+    let format_rate = |rate: Option<f64>| {
+        rate.map(|r| format!("{:.0}%", r * 100.0))
+            .unwrap_or_else(|| "-".to_string())
+    };
+
+    println!("=== Workflows ===");
+    println!();
+    println!(
+        "{:<8} {:<24} {:>7} {:>7} {:>9} {:>10}",
+        "KIND", "NAME", "FIRED", "FAILED", "SESSIONS", "COMPLETED"
+    );
+    for workflow in &stats {
+        println!(
+            "{:<8} {:<24} {:>7} {:>7} {:>9} {:>10}",
+            workflow.kind,
+            workflow.name,
+            workflow.fired,
+            workflow.failures,
+            workflow.sessions,
+            format_rate(workflow.completion_rate())
+        );
+    }
+    println!();
+    let baseline = (!outcomes.is_empty()).then(|| completed as f64 / outcomes.len() as f64);
+    println!(
+        "Baseline: {} of {} summarized sessions completed",
+        format_rate(baseline),
+        outcomes.len()
+    );
+
+    Ok(())
+}
+
+pub async fn handle_mcp_command(since: Option<String>) -> Result<()> {
+    let since = since
+        .map(|spec| time_parser::parse_time_spec(&spec))
+        .transpose()?;
+
+    let db_path = retrochat_core::database::config::get_default_db_path()?;
+    let db_manager = DatabaseManager::new(&db_path).await?;
+    let stats = ToolOperationRepository::new(&db_manager)
+        .get_mcp_server_stats(since)
+        .await?;
+
+    if stats.is_empty() {
+        println!("No MCP tool calls found");
+        return Ok(());
+    }
+
+    println!("=== MCP Servers ===");
+    println!();
+    println!(
+        "{:<28} {:>8} {:>8} {:>10} {:>6} {:>9}",
+        "SERVER", "CALLS", "FAILED", "FAIL RATE", "TOOLS", "SESSIONS"
+    );
+    for server in &stats {
+        println!(
+            "{:<28} {:>8} {:>8} {:>9.1}% {:>6} {:>9}",
+            server.server,
+            server.calls,
+            server.failures,
+            server.failure_rate() * 100.0,
+            server.tools,
+            server.sessions
+        );
+    }
+
+    Ok(())
+}
+
+pub async fn handle_doctor_command(skip_generation: bool) -> Result<()> {
+    let default_provider = retrochat_core::config::get_llm_provider()
+        .ok()
+        .flatten()
+        .unwrap_or(LlmProvider::GoogleAi);
+
+    println!("=== LLM Provider Health ===");
+    if !skip_generation {
+        println!("Sending a short test prompt to each available provider...");
+    }
+    println!();
+
+    let results = diagnose_providers(!skip_generation).await;
+
+    for diagnosis in &results {
+        let mark = if diagnosis.is_healthy() { "✓" } else { "✗" };
+        let default_marker = if diagnosis.provider == default_provider {
+            " (default)"
+        } else {
+            ""
+        };
+        println!("{mark} {}{default_marker}", diagnosis.provider);
+        println!("   Status: {}", diagnosis.status);
+        if let Some(model) = &diagnosis.model {
+            println!("   Model: {model}");
+        }
+        if let Some(latency) = diagnosis.latency {
+            println!("   Latency: {:.1}s", latency.as_secs_f64());
+        }
+        if let Some(error) = &diagnosis.error {
+            println!("   Error: {error}");
+        }
+        if let Some(suggestion) = &diagnosis.suggestion {
+            println!("   Fix: {suggestion}");
+        }
+        println!();
+    }
+
+    let default_healthy = results
+        .iter()
+        .any(|d| d.provider == default_provider && d.is_healthy());
+    if !results.iter().any(|d| d.is_healthy()) {
+        anyhow::bail!("No LLM provider is usable; analysis will fail until one is fixed");
+    }
+    if !default_healthy {
+        println!(
+            "The default provider ({default_provider}) is not usable; pass --provider to `analysis run`, set {} or run `retrochat config set llm.provider <provider>`",
+            env_llm::RETROCHAT_LLM_PROVIDER
+        );
+    }
+
+    Ok(())
+}
+
 // =============================================================================
 // Print Functions
 // =============================================================================
@@ -537,8 +832,8 @@ async fn print_unified_analysis(analysis: &retrochat_core::models::Analytics) ->
     // Session Info
     println!("📋 Session: {}", analysis.session_id);
     println!(
-        "🕐 Generated: {}",
-        analysis.generated_at.format("%Y-%m-%d %H:%M:%S UTC")
+        "🕐 Generated: {} UTC",
+        Language::current().format_datetime_secs(&analysis.generated_at)
     );
     if let Some(model) = &analysis.model_used {
         println!("🤖 Model: {model}");
@@ -592,14 +887,43 @@ fn print_metric_quantitative(
         },
         metrics.file_changes.net_code_growth
     );
+    if !metrics.file_changes.languages.is_empty() {
+        println!("     • By Language:");
+        for language in &metrics.file_changes.languages {
+            println!(
+                "         {:<16} {} file{}, +{} -{}",
+                language.language,
+                language.files_touched,
+                if language.files_touched == 1 { "" } else { "s" },
+                language.lines_added,
+                language.lines_removed
+            );
+        }
+    }
     println!();
 
+    if !metrics.impact.is_empty() {
+        print_impact(&metrics.impact);
+    }
+
     // Time Metrics
     println!("  ⏰ Time Consumption:");
     println!(
         "     • Total Session Time: {:.1} minutes",
         metrics.time_metrics.total_session_time_minutes
     );
+    if let Some(active) = metrics.time_metrics.active_time_minutes {
+        println!(
+            "     • Active Time: {:.1} minutes ({} idle gap{})",
+            active,
+            metrics.time_metrics.idle_gaps,
+            if metrics.time_metrics.idle_gaps == 1 {
+                ""
+            } else {
+                "s"
+            }
+        );
+    }
     if !metrics.time_metrics.peak_hours.is_empty() {
         let peak_hours: Vec<String> = metrics
             .time_metrics
@@ -609,6 +933,19 @@ fn print_metric_quantitative(
             .collect();
         println!("     • Peak Hours: {}", peak_hours.join(", "));
     }
+    if metrics.time_metrics.compactions > 0 {
+        let segments: Vec<String> = metrics
+            .time_metrics
+            .segment_minutes
+            .iter()
+            .map(|m| format!("{m:.1}"))
+            .collect();
+        println!(
+            "     • Compactions: {} (segments: {} minutes)",
+            metrics.time_metrics.compactions,
+            segments.join(" / ")
+        );
+    }
     println!();
 
     // Token Metrics
@@ -629,6 +966,25 @@ fn print_metric_quantitative(
         "     • Token Efficiency: {:.2}%",
         metrics.token_metrics.token_efficiency * 100.0
     );
+    if metrics.token_metrics.segment_token_efficiency.len() > 1 {
+        println!(
+            "     • Peak Context Tokens: {}",
+            metrics.token_metrics.peak_context_tokens
+        );
+    }
+    let cached = metrics.token_metrics.cache_creation_input_tokens
+        + metrics.token_metrics.cache_read_input_tokens;
+    if cached > 0 {
+        println!(
+            "     • Cache Writes / Reads: {} / {}",
+            metrics.token_metrics.cache_creation_input_tokens,
+            metrics.token_metrics.cache_read_input_tokens
+        );
+        println!(
+            "     • Cache Hit Ratio: {:.2}%",
+            metrics.token_metrics.cache_hit_ratio * 100.0
+        );
+    }
     println!();
 
     // Tool Usage
@@ -648,10 +1004,20 @@ fn print_metric_quantitative(
             * 100.0;
         println!("     • Success Rate: {:.1}%", success_rate);
     }
-    println!(
-        "     • Avg Execution Time: {:.1}ms",
-        metrics.tool_usage.average_execution_time_ms
-    );
+    if metrics.tool_usage.timed_operations > 0 {
+        println!(
+            "     • Execution Time: avg {:.0}ms, p50 {:.0}ms, p95 {:.0}ms ({} timed call{})",
+            metrics.tool_usage.average_execution_time_ms,
+            metrics.tool_usage.p50_execution_time_ms,
+            metrics.tool_usage.p95_execution_time_ms,
+            metrics.tool_usage.timed_operations,
+            if metrics.tool_usage.timed_operations == 1 {
+                ""
+            } else {
+                "s"
+            }
+        );
+    }
     if !metrics.tool_usage.tool_distribution.is_empty() {
         println!("     • Tool Distribution:");
         let mut tools: Vec<_> = metrics.tool_usage.tool_distribution.iter().collect();
@@ -663,7 +1029,58 @@ fn print_metric_quantitative(
             println!("       ... and {} more tools", tools.len() - 10);
         }
     }
+    if !metrics.tool_usage.thrash_segments.is_empty() {
+        println!("     • Loops and Thrashing:");
+        for segment in &metrics.tool_usage.thrash_segments {
+            let count = match segment.kind {
+                ThrashKind::RepeatedCall => format!("{}x", segment.occurrences),
+                ThrashKind::EditRevert => format!("{} reverts", segment.occurrences),
+            };
+            let target = segment
+                .target
+                .as_deref()
+                .map(|t| format!(" {t}"))
+                .unwrap_or_default();
+            println!(
+                "       - {}: {}{target} ({count} over {:.1} min from {})",
+                segment.kind,
+                segment.tool_name,
+                (segment.ended_at - segment.started_at).num_seconds() as f64 / 60.0,
+                Language::current().format_datetime_secs(&segment.started_at)
+            );
+        }
+    }
     println!();
+
+    // Rework
+    println!("  🔁 Rework:");
+    println!("     • Repeated Asks: {:.1}%", metrics.rework_rate * 100.0);
+    println!();
+
+    // Costliest turns
+    if !metrics.turns.is_empty() {
+        let mut turns: Vec<_> = metrics.turns.iter().collect();
+        turns.sort_by_key(|t| std::cmp::Reverse(t.total_tokens));
+        println!("  💬 Turns ({} total, by tokens):", metrics.turns.len());
+        for turn in turns.iter().take(MAX_TURNS_SHOWN) {
+            println!(
+                "     • #{} {} tokens, {} tool calls, {:.1} min, {} files: {}",
+                turn.turn_number,
+                turn.total_tokens,
+                turn.tool_calls,
+                turn.duration_minutes,
+                turn.files_touched.len(),
+                turn.prompt_preview
+            );
+        }
+        if turns.len() > MAX_TURNS_SHOWN {
+            println!(
+                "       ... and {} more turns",
+                turns.len() - MAX_TURNS_SHOWN
+            );
+        }
+        println!();
+    }
 }
 
 fn print_ai_quantitative(ai_quant: &retrochat_core::services::analytics::AIQuantitativeOutput) {
@@ -688,6 +1105,18 @@ fn print_ai_quantitative(ai_quant: &retrochat_core::services::analytics::AIQuant
             "     Rubrics Evaluated: {} (version: {})",
             summary.rubrics_evaluated, summary.rubrics_version
         );
+        if summary.is_weighted() {
+            let weights = ai_quant
+                .rubric_scores
+                .iter()
+                .filter_map(|score| {
+                    let weight = summary.weights.get(&score.rubric_id)?;
+                    Some(format!("{} ×{weight}", score.rubric_name))
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            println!("     Weights: {weights}");
+        }
         println!();
     }
 
@@ -713,6 +1142,45 @@ fn print_ai_quantitative(ai_quant: &retrochat_core::services::analytics::AIQuant
     }
 }
 
+fn print_impact(impact: &retrochat_core::services::analytics::ImpactReport) {
+    let signed = |n: i64| {
+        if n >= 0 {
+            format!("+{n}")
+        } else {
+            n.to_string()
+        }
+    };
+    println!("  🗂️  Impact by Repository:");
+    for repository in &impact.repositories {
+        println!(
+            "     • {} ({} net, +{} -{})",
+            repository.root.as_deref().unwrap_or("(no repository)"),
+            signed(repository.net_lines),
+            repository.lines_added,
+            repository.lines_removed
+        );
+        for file in &repository.files {
+            let survived = match file.survived {
+                Some(true) => "  ✓ committed",
+                Some(false) => "  ✗ not committed",
+                None => "",
+            };
+            println!(
+                "         {:<40} {:>6}  ({} edit{}){}",
+                file.path,
+                signed(file.net_lines),
+                file.edits,
+                if file.edits == 1 { "" } else { "s" },
+                survived
+            );
+        }
+    }
+    if !impact.git_correlated {
+        println!("     (enable analysis.git_correlation to check which changes were committed)");
+    }
+    println!();
+}
+
 fn print_ai_qualitative(ai_qual: &retrochat_core::services::analytics::AIQualitativeOutput) {
     println!("┌──────────────────────────────────────────────────────────────────────────────┐");
     println!("│  💡 AI Qualitative Analysis                                                  │");
@@ -753,6 +1221,85 @@ fn print_ai_qualitative(ai_qual: &retrochat_core::services::analytics::AIQualita
     }
 }
 
+fn print_comparison(comparison: &SessionComparison) {
+    let Some(result) = &comparison.result else {
+        return;
+    };
+
+    println!();
+    println!("╔══════════════════════════════════════════════════════════════════════════════╗");
+    println!("║                          ⚖️  Session Comparison                               ║");
+    println!("╚══════════════════════════════════════════════════════════════════════════════╝");
+    println!();
+
+    println!("📋 First:  {}", comparison.session_id);
+    println!("📋 Second: {}", comparison.other_session_id);
+    if let Some(generated_at) = &comparison.generated_at {
+        println!(
+            "🕐 Generated: {} UTC",
+            Language::current().format_datetime_secs(generated_at)
+        );
+    }
+    if let Some(model) = &comparison.model_used {
+        println!("🤖 Model: {model}");
+    }
+    if let Some(duration) = comparison.analysis_duration_ms {
+        println!("⏱️  Analysis Duration: {duration}ms");
+    }
+    println!();
+
+    println!("  {}", result.summary);
+    println!();
+    let winner = match result.winner {
+        ComparisonWinner::First => format!("first session ({})", comparison.session_id),
+        ComparisonWinner::Second => format!("second session ({})", comparison.other_session_id),
+        ComparisonWinner::Tie => "tie".to_string(),
+    };
+    println!("  🏆 Better overall: {winner}");
+    println!();
+
+    println!("  {:<20} {:>14} {:>14}", "Metric", "First", "Second");
+    for metric in &result.metrics {
+        println!(
+            "  {:<20} {:>14} {:>14}",
+            metric.name,
+            format_compared_value(metric.first),
+            format_compared_value(metric.second)
+        );
+    }
+    println!();
+
+    for (title, dimension) in [
+        ("Approach", &result.approach),
+        ("Efficiency", &result.efficiency),
+        ("Failure Points", &result.failure_points),
+    ] {
+        println!("  • {title}:");
+        if !dimension.verdict.is_empty() {
+            println!("    📝 {}", dimension.verdict);
+        }
+        for (side, items) in [("First", &dimension.first), ("Second", &dimension.second)] {
+            if items.is_empty() {
+                continue;
+            }
+            println!("    {side}:");
+            for item in items {
+                println!("    - {item}");
+            }
+        }
+        println!();
+    }
+}
+
+/// Whole numbers as they are, others to one decimal
+fn format_compared_value(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{value}")
+    } else {
+        format!("{value:.1}")
+    }
+}
+
 fn generate_score_bar(score: f64, max_score: f64) -> String {
     let percentage = if max_score > 0.0 {
         score / max_score
@@ -1,7 +1,8 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use console::style;
+use std::path::PathBuf;
 
-use retrochat_core::config::Config;
+use retrochat_core::config::{validate_config_text, Config};
 
 /// Handle config get command
 pub async fn handle_config_get(key: String) -> Result<()> {
@@ -86,3 +87,40 @@ pub async fn handle_config_path() -> Result<()> {
     println!("{}", config_path.display());
     Ok(())
 }
+
+/// Handle config validate command
+pub async fn handle_config_validate(file: Option<String>) -> Result<()> {
+    let path = match file {
+        Some(file) => PathBuf::from(file),
+        None => Config::get_config_path()?,
+    };
+    if !path.exists() {
+        println!(
+            "{} No config file at {}; defaults apply",
+            style("✓").green(),
+            style(path.display()).dim()
+        );
+        return Ok(());
+    }
+
+    let contents = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+    let issues = validate_config_text(&contents);
+    if issues.is_empty() {
+        println!(
+            "{} {} is valid",
+            style("✓").green(),
+            style(path.display()).cyan()
+        );
+        return Ok(());
+    }
+
+    for issue in &issues {
+        let location = match issue.line {
+            Some(line) => format!("{}:{line}", path.display()),
+            None => path.display().to_string(),
+        };
+        println!("{} {}", style(location).red(), issue.message);
+    }
+    anyhow::bail!("Found {} problem(s) in the config file", issues.len());
+}
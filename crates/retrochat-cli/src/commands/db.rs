@@ -0,0 +1,280 @@
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+
+use retrochat_core::database::connection::find_migration_backups;
+use retrochat_core::database::encryption::{self, keychain, PassphraseSource};
+use retrochat_core::database::{DatabaseManager, MigrationManager, MigrationPlan};
+use retrochat_core::env::database as env_database;
+
+/// Open the database without migrating, so these commands work even when
+/// migrating fails
+async fn open_database() -> Result<(PathBuf, DatabaseManager)> {
+    let db_path = retrochat_core::database::config::get_default_db_path()?;
+    let db_manager = DatabaseManager::open_without_migrations(&db_path).await?;
+    Ok((db_path, db_manager))
+}
+
+/// Handle the db status command
+pub async fn handle_status_command(verbose: bool) -> Result<()> {
+    let (db_path, db_manager) = open_database().await?;
+    let migrations = MigrationManager::new(db_manager.pool().clone());
+    let status = migrations.get_migration_status().await?;
+
+    println!("Database: {}", db_path.display());
+    if db_manager.is_encrypted() {
+        println!("Encrypted: yes (SQLCipher)");
+    }
+    println!(
+        "Schema version: {} (latest: {})",
+        migrations.get_current_version().await?,
+        migrations.get_latest_version()
+    );
+
+    let pending = status.iter().filter(|s| !s.applied).count();
+    let unknown = status.iter().filter(|s| s.unknown).count();
+    let mismatched = status.iter().filter(|s| s.checksum_mismatch).count();
+    if pending > 0 {
+        println!("{pending} pending migrations; apply them with: retrochat db migrate");
+    }
+    if unknown > 0 {
+        println!(
+            "{unknown} migrations were applied by a newer RetroChat; upgrade to use this database"
+        );
+    }
+    if mismatched > 0 {
+        println!("{mismatched} applied migrations differ from the ones in this build");
+    }
+
+    println!();
+    for entry in &status {
+        if !verbose && entry.applied && !entry.checksum_mismatch && !entry.unknown {
+            continue;
+        }
+        let state = if entry.unknown {
+            "unknown"
+        } else if entry.checksum_mismatch {
+            "changed"
+        } else if entry.applied {
+            "applied"
+        } else {
+            "pending"
+        };
+        let mut line = format!("  {:03}  {:<8} {}", entry.version, state, entry.description);
+        if verbose {
+            if let Some(applied_at) = entry.applied_at {
+                line.push_str(&format!("  ({}", applied_at.format("%Y-%m-%d %H:%M UTC")));
+                if let Some(ms) = entry.execution_ms {
+                    line.push_str(&format!(", {ms} ms"));
+                }
+                line.push(')');
+            }
+            if entry.reversible {
+                line.push_str("  [reversible]");
+            }
+        }
+        println!("{line}");
+    }
+    if !verbose && pending == 0 && unknown == 0 && mismatched == 0 {
+        println!("  Up to date. Use --verbose to list every migration.");
+    }
+
+    let backups = db_manager.migration_backups();
+    if verbose && !backups.is_empty() {
+        println!();
+        println!("Pre-migration backups:");
+        for backup in &backups {
+            println!("  {}", backup.display());
+        }
+    }
+    Ok(())
+}
+
+/// Handle the db migrate command
+pub async fn handle_migrate_command(dry_run: bool) -> Result<()> {
+    let (_, db_manager) = open_database().await?;
+    let migrations = MigrationManager::new(db_manager.pool().clone());
+    let pending = migrations.pending_migrations().await?;
+    if pending.is_empty() {
+        println!("Database is up to date.");
+        return Ok(());
+    }
+
+    if dry_run {
+        println!("Would apply {} migrations:", pending.len());
+        print_plans(&pending);
+        return Ok(());
+    }
+
+    let current = migrations.get_current_version().await?;
+    if current > 0 {
+        let backup = db_manager.migration_backup_path(current);
+        migrations.backup(&backup).await?;
+        println!("Backed up the database to {}", backup.display());
+    }
+    for plan in &pending {
+        println!("  {:03}  {}", plan.version, plan.description);
+    }
+    migrations.migrate_to_latest().await?;
+    println!("Applied {} migrations.", pending.len());
+    Ok(())
+}
+
+/// Handle the db rollback command
+pub async fn handle_rollback_command(version: i64, dry_run: bool) -> Result<()> {
+    let (_, db_manager) = open_database().await?;
+    let migrations = MigrationManager::new(db_manager.pool().clone());
+    let plan = migrations.rollback_plan(version).await?;
+    if plan.is_empty() {
+        println!("Database is already at or below migration {version}.");
+        return Ok(());
+    }
+
+    if dry_run {
+        println!("Would revert {} migrations:", plan.len());
+        print_plans(&plan);
+        return Ok(());
+    }
+
+    let backup = db_manager.migration_backup_path(migrations.get_current_version().await?);
+    migrations.backup(&backup).await?;
+    println!("Backed up the database to {}", backup.display());
+    for entry in migrations.rollback_to(version).await? {
+        println!("  Reverted {:03}  {}", entry.version, entry.description);
+    }
+    println!("Database is at migration {version}.");
+    println!("Note: RetroChat re-applies newer migrations the next time it opens the database.");
+    Ok(())
+}
+
+/// Handle the db restore command
+pub async fn handle_restore_command(backup: Option<String>) -> Result<()> {
+    let db_path = retrochat_core::database::config::get_default_db_path()?;
+    let backup = match backup {
+        Some(path) => PathBuf::from(path),
+        None => find_migration_backups(&db_path)
+            .into_iter()
+            .next()
+            .context("No pre-migration backups found; pass a backup file")?,
+    };
+
+    DatabaseManager::restore_backup(&db_path, &backup)?;
+    println!("Restored {} from {}", db_path.display(), backup.display());
+    Ok(())
+}
+
+/// Handle the db encrypt command
+pub async fn handle_encrypt_command(store_in_keychain: bool) -> Result<()> {
+    let db_path = retrochat_core::database::config::get_default_db_path()?;
+    if encryption::is_encrypted(&db_path)? {
+        anyhow::bail!(
+            "The database is already encrypted; use `retrochat db rekey` to change the passphrase"
+        );
+    }
+    if !encryption::is_supported().await {
+        anyhow::bail!("This build of RetroChat can't encrypt databases; rebuild it with `--features sqlcipher`");
+    }
+    // Make sure the schema is current before rewriting the file
+    DatabaseManager::new(&db_path).await?.close().await?;
+
+    let from_env = std::env::var(env_database::DB_PASSPHRASE)
+        .ok()
+        .filter(|p| !p.is_empty());
+    let passphrase = match from_env {
+        Some(passphrase) => passphrase,
+        None => prompt_new_passphrase()?,
+    };
+
+    println!("Encrypting {}...", db_path.display());
+    encryption::reencrypt(&db_path, None, Some(&passphrase)).await?;
+    println!("Database encrypted.");
+    if store_in_keychain {
+        keychain::store(&passphrase)?;
+        println!("Stored the passphrase in the OS keychain.");
+    } else if std::env::var(env_database::DB_PASSPHRASE).is_err() {
+        println!(
+            "Set {} or run `retrochat db rekey --keychain` so RetroChat can open it.",
+            env_database::DB_PASSPHRASE
+        );
+    }
+
+    let backups = find_migration_backups(&db_path);
+    if !backups.is_empty() {
+        println!();
+        println!("These pre-migration backups are still unencrypted; delete them if you don't need them:");
+        for backup in &backups {
+            println!("  {}", backup.display());
+        }
+    }
+    Ok(())
+}
+
+/// Handle the db decrypt command
+pub async fn handle_decrypt_command() -> Result<()> {
+    let db_path = retrochat_core::database::config::get_default_db_path()?;
+    if !encryption::is_encrypted(&db_path)? {
+        anyhow::bail!("The database is not encrypted");
+    }
+    let (passphrase, source) = current_passphrase()?;
+
+    encryption::reencrypt(&db_path, Some(&passphrase), None).await?;
+    if source == Some(PassphraseSource::Keychain) {
+        keychain::delete()?;
+    }
+    println!("Decrypted {}", db_path.display());
+    Ok(())
+}
+
+/// Handle the db rekey command
+pub async fn handle_rekey_command(store_in_keychain: bool) -> Result<()> {
+    let db_path = retrochat_core::database::config::get_default_db_path()?;
+    if !encryption::is_encrypted(&db_path)? {
+        anyhow::bail!("The database is not encrypted; use `retrochat db encrypt`");
+    }
+    let (current, source) = current_passphrase()?;
+    println!("Choose the new passphrase.");
+    let new = prompt_new_passphrase()?;
+
+    encryption::reencrypt(&db_path, Some(&current), Some(&new)).await?;
+    println!("Changed the database passphrase.");
+    if store_in_keychain || source == Some(PassphraseSource::Keychain) {
+        keychain::store(&new)?;
+        println!("Stored the new passphrase in the OS keychain.");
+    }
+    if source == Some(PassphraseSource::Environment) {
+        println!(
+            "Update {} to the new passphrase.",
+            env_database::DB_PASSPHRASE
+        );
+    }
+    Ok(())
+}
+
+/// The passphrase of the encrypted database, prompting when neither the
+/// environment nor the keychain has it
+fn current_passphrase() -> Result<(String, Option<PassphraseSource>)> {
+    if let Some((passphrase, source)) = encryption::resolve_passphrase()? {
+        return Ok((passphrase, Some(source)));
+    }
+    let passphrase = inquire::Password::new("Database passphrase:")
+        .without_confirmation()
+        .prompt()?;
+    Ok((passphrase, None))
+}
+
+fn prompt_new_passphrase() -> Result<String> {
+    let passphrase = inquire::Password::new("Database passphrase:")
+        .with_custom_confirmation_message("Repeat the passphrase:")
+        .prompt()?;
+    if passphrase.is_empty() {
+        anyhow::bail!("The passphrase cannot be empty");
+    }
+    Ok(passphrase)
+}
+
+fn print_plans(plans: &[MigrationPlan]) {
+    for plan in plans {
+        println!();
+        println!("-- {:03} {}", plan.version, plan.description);
+        println!("{}", plan.sql.trim_end());
+    }
+}
@@ -0,0 +1,106 @@
+use anyhow::Result;
+
+use retrochat_core::database::{DatabaseManager, ParserWarningRepository};
+use retrochat_core::models::ParserWarning;
+use retrochat_core::services::{CheckStatus, DoctorReport, DoctorService};
+
+/// Handle the doctor command
+pub async fn handle_doctor_command(skip_generation: bool, json: bool) -> Result<()> {
+    let db_path = retrochat_core::database::config::get_default_db_path()?;
+    if !json && !skip_generation {
+        println!("Checking RetroChat (sending a short test prompt to each LLM provider)...");
+        println!();
+    }
+
+    let report = DoctorService::new(db_path)
+        .with_llm_probe(!skip_generation)
+        .run()
+        .await;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&report);
+    }
+
+    if report.has_errors() {
+        anyhow::bail!("{} checks failed", report.count(CheckStatus::Error));
+    }
+    Ok(())
+}
+
+/// Handle `doctor --parsers`: summarize format drift met during import
+pub async fn handle_parsers_command(json: bool, clear: bool) -> Result<()> {
+    let db_path = retrochat_core::database::config::get_default_db_path()?;
+    let db_manager = DatabaseManager::new(&db_path).await?;
+    let repo = ParserWarningRepository::new(&db_manager);
+
+    if clear {
+        let cleared = repo.clear(None).await?;
+        println!("Cleared {cleared} parser warnings");
+        return Ok(());
+    }
+
+    let warnings = repo.list(None).await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&warnings)?);
+    } else {
+        print_parser_warnings(&warnings);
+    }
+    Ok(())
+}
+
+fn print_parser_warnings(warnings: &[ParserWarning]) {
+    if warnings.is_empty() {
+        println!("The parsers recognized every field and type they met during import");
+        return;
+    }
+
+    let mut provider = None;
+    for warning in warnings {
+        if provider != Some(warning.provider.as_str()) {
+            if provider.is_some() {
+                println!();
+            }
+            println!("{}:", warning.provider);
+            provider = Some(warning.provider.as_str());
+        }
+        println!(
+            "  {} ({}): {} times in {} imports, last seen {}",
+            warning.location,
+            warning.kind,
+            warning.occurrences,
+            warning.file_count,
+            warning.last_seen_at.format("%Y-%m-%d %H:%M")
+        );
+        if let Some(path) = &warning.last_file_path {
+            println!("    in {path}");
+        }
+    }
+    println!();
+    println!(
+        "These may be new in the providers' log formats; if data is missing, upgrade RetroChat \
+         or report them. `retrochat doctor --parsers --clear` forgets them."
+    );
+}
+
+fn print_report(report: &DoctorReport) {
+    for check in &report.checks {
+        let mark = match check.status {
+            CheckStatus::Ok => "✓",
+            CheckStatus::Warning => "!",
+            CheckStatus::Error => "✗",
+        };
+        println!("{mark} {}: {}", check.name, check.detail);
+        if let Some(fix) = &check.fix {
+            println!("   Fix: {fix}");
+        }
+    }
+    println!();
+    println!(
+        "{} ok, {} warnings, {} errors",
+        report.count(CheckStatus::Ok),
+        report.count(CheckStatus::Warning),
+        report.count(CheckStatus::Error)
+    );
+}
@@ -0,0 +1,106 @@
+use anyhow::{Context, Result};
+use std::time::Duration;
+
+use retrochat_core::database::DatabaseManager;
+use retrochat_core::models::{Event, EventKind};
+use retrochat_core::services::EventFeed;
+
+/// How often `--follow` checks for commits by other processes
+const FOLLOW_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Events read per query with `--since`
+const PAGE_SIZE: i64 = 500;
+
+async fn create_feed() -> Result<EventFeed> {
+    let db_path = retrochat_core::database::config::get_default_db_path()?;
+    let db_manager = DatabaseManager::new(&db_path).await?;
+    Ok(EventFeed::new(&db_manager))
+}
+
+/// Handle the events tail command
+pub async fn handle_tail_command(
+    follow: bool,
+    since: Option<i64>,
+    kinds: Vec<String>,
+    limit: i64,
+    json: bool,
+) -> Result<()> {
+    let kinds = kinds
+        .iter()
+        .map(|kind| kind.parse::<EventKind>().map_err(anyhow::Error::msg))
+        .collect::<Result<Vec<_>>>()
+        .with_context(|| {
+            let known: Vec<&str> = EventKind::ALL.iter().map(EventKind::as_str).collect();
+            format!("Known kinds: {}", known.join(", "))
+        })?;
+    let feed = create_feed().await?;
+    let print = |event: &Event| -> Result<()> {
+        if kinds.is_empty() || kinds.contains(&event.kind) {
+            print_event(event, json)?;
+        }
+        Ok(())
+    };
+
+    let mut cursor = match since {
+        Some(mut cursor) => loop {
+            let events = feed.since(cursor, PAGE_SIZE).await?;
+            for event in &events {
+                print(event)?;
+                cursor = event.id;
+            }
+            if (events.len() as i64) < PAGE_SIZE {
+                break cursor;
+            }
+        },
+        None => {
+            let events = feed.recent(limit).await?;
+            for event in &events {
+                print(event)?;
+            }
+            match events.last() {
+                Some(event) => event.id,
+                None => feed.latest_cursor().await?,
+            }
+        }
+    };
+
+    if !follow {
+        return Ok(());
+    }
+    let mut events = feed.subscribe(cursor, FOLLOW_POLL_INTERVAL).await?;
+    while let Some(event) = events.recv().await {
+        print(&event)?;
+        cursor = event.id;
+    }
+    anyhow::bail!("Stopped following the event log after event {cursor}")
+}
+
+fn print_event(event: &Event, json: bool) -> Result<()> {
+    if json {
+        println!("{}", serde_json::to_string(event)?);
+        return Ok(());
+    }
+
+    let mut line = format!(
+        "{:>6}  {}  {:<18}",
+        event.id,
+        event.created_at.format("%Y-%m-%d %H:%M:%S"),
+        event.kind.as_str()
+    );
+    if let Some(session_id) = &event.session_id {
+        line.push_str(&format!("  {}", &session_id[..session_id.len().min(8)]));
+    }
+    if let Some(details) = event.payload.as_object().filter(|d| !d.is_empty()) {
+        let details: Vec<String> = details
+            .iter()
+            .filter(|(_, value)| !value.is_null())
+            .map(|(key, value)| match value.as_str() {
+                Some(text) => format!("{key}={text}"),
+                None => format!("{key}={value}"),
+            })
+            .collect();
+        line.push_str(&format!("  {}", details.join(" ")));
+    }
+    println!("{line}");
+    Ok(())
+}
@@ -0,0 +1,187 @@
+use anyhow::{Context, Result};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use retrochat_core::database::DatabaseManager;
+use retrochat_core::services::experiments::{format_average, format_rate};
+use retrochat_core::services::{ExperimentService, QueryService};
+use retrochat_core::utils::filter_expr::FilterExpr;
+
+async fn open_database() -> Result<Arc<DatabaseManager>> {
+    let db_path = retrochat_core::database::config::get_default_db_path()?;
+    Ok(Arc::new(DatabaseManager::new(&db_path).await?))
+}
+
+async fn create_service() -> Result<ExperimentService> {
+    Ok(ExperimentService::new(open_database().await?, None))
+}
+
+fn parse_session_ids(ids: &[String]) -> Result<Vec<Uuid>> {
+    ids.iter()
+        .map(|id| Uuid::parse_str(id.trim()).with_context(|| format!("Invalid session ID: {id}")))
+        .collect()
+}
+
+/// Handle the experiments create command
+pub async fn handle_create_command(name: String, description: Option<String>) -> Result<()> {
+    let experiment = create_service().await?.create(&name, description).await?;
+    println!("Created experiment '{}'", experiment.name);
+    println!(
+        "Add sessions with: retrochat experiments add {} <cohort> <session-id>...",
+        experiment.name
+    );
+    Ok(())
+}
+
+/// Handle the experiments add command
+pub async fn handle_add_command(
+    name: String,
+    cohort: String,
+    session_ids: Vec<String>,
+    filter: Option<String>,
+) -> Result<()> {
+    let db_manager = open_database().await?;
+    let mut ids = parse_session_ids(&session_ids)?;
+    if let Some(filter) = filter {
+        let expression = FilterExpr::parse(&filter)?;
+        if expression.is_empty() {
+            anyhow::bail!("Filter expression cannot be empty");
+        }
+        let sessions = QueryService::with_database(db_manager.clone())
+            .filter_sessions(&expression)
+            .await?;
+        ids.extend(sessions.into_iter().map(|session| session.id));
+    }
+    ids.sort();
+    ids.dedup();
+    if ids.is_empty() {
+        anyhow::bail!("No sessions to add; pass session IDs or a --filter that matches sessions");
+    }
+
+    let added = ExperimentService::new(db_manager, None)
+        .add_sessions(&name, &cohort, &ids)
+        .await?;
+    println!(
+        "Added {added} sessions to cohort '{}' of '{name}' ({} already there)",
+        cohort.trim(),
+        ids.len() as u64 - added
+    );
+    Ok(())
+}
+
+/// Handle the experiments remove command
+pub async fn handle_remove_command(name: String, session_ids: Vec<String>) -> Result<()> {
+    let ids = parse_session_ids(&session_ids)?;
+    let removed = create_service().await?.remove_sessions(&name, &ids).await?;
+    println!("Removed {removed} sessions from '{name}'");
+    Ok(())
+}
+
+/// Handle the experiments list command
+pub async fn handle_list_command() -> Result<()> {
+    let experiments = create_service().await?.list().await?;
+    if experiments.is_empty() {
+        println!("No experiments. Create one with: retrochat experiments create <name>");
+        return Ok(());
+    }
+
+    println!(
+        "{:<24} {:>8}  {:<10}  DESCRIPTION",
+        "NAME", "SESSIONS", "CREATED"
+    );
+    for (experiment, sessions) in experiments {
+        println!(
+            "{:<24} {:>8}  {:<10}  {}",
+            experiment.name,
+            sessions,
+            experiment.created_at.format("%Y-%m-%d"),
+            experiment.description.as_deref().unwrap_or("")
+        );
+    }
+    Ok(())
+}
+
+/// Handle the experiments show command
+pub async fn handle_show_command(name: String, sessions: bool, json: bool) -> Result<()> {
+    let service = create_service().await?;
+    let overview = service.overview(&name).await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&overview)?);
+        return Ok(());
+    }
+
+    println!("Experiment: {}", overview.experiment.name);
+    if let Some(description) = &overview.experiment.description {
+        println!("{description}");
+    }
+    println!();
+    if overview.cohorts.is_empty() {
+        println!("No sessions yet. Add some with: retrochat experiments add {name} <cohort> <session-id>...");
+        return Ok(());
+    }
+
+    println!(
+        "{:<16} {:>8} {:>9} {:>10} {:>8} {:>9} {:>8} {:>9}  PROVIDERS",
+        "COHORT",
+        "SESSIONS",
+        "AVG MSGS",
+        "AVG TOKENS",
+        "AVG MIN",
+        "AVG TOOLS",
+        "FAILED",
+        "COMPLETED"
+    );
+    for stats in &overview.cohorts {
+        let providers = stats
+            .providers
+            .iter()
+            .map(|(provider, sessions)| format!("{provider} {sessions}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        println!(
+            "{:<16} {:>8} {:>9} {:>10} {:>8} {:>9} {:>8} {:>9}  {providers}",
+            stats.cohort,
+            stats.sessions,
+            format_average(stats.avg_messages()),
+            format_average(stats.avg_tokens()),
+            format_average(stats.avg_minutes()),
+            format_average(stats.avg_tool_uses()),
+            format_rate(stats.tool_failure_rate()),
+            format_rate(stats.completion_rate()),
+        );
+    }
+    println!();
+    println!(
+        "Completion rates only count sessions with a summary (`retrochat summarize sessions`)."
+    );
+
+    if sessions {
+        println!();
+        for member in service.sessions(&name).await? {
+            println!("{:<16} {}", member.cohort, member.session_id);
+        }
+    }
+    Ok(())
+}
+
+/// Handle the experiments report command
+pub async fn handle_report_command(
+    name: String,
+    provider: Option<String>,
+    model: Option<String>,
+) -> Result<()> {
+    let llm_client = super::summarize::create_llm_client(provider, model)?;
+    let service = ExperimentService::new(open_database().await?, Some(llm_client));
+
+    let report = service.report(&name).await?;
+    println!();
+    println!("{}", report.markdown);
+    Ok(())
+}
+
+/// Handle the experiments delete command
+pub async fn handle_delete_command(name: String) -> Result<()> {
+    create_service().await?.delete(&name).await?;
+    println!("Deleted experiment '{name}'");
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use anyhow::{Context, Result};
+use uuid::Uuid;
+
+use retrochat_core::database::{
+    ChatSessionRepository, DatabaseManager, MessageRepository, MessageRevisionRepository,
+};
+use retrochat_core::models::MessageRevision;
+
+/// Handle the history command
+pub async fn handle_history_command(id: String) -> Result<()> {
+    let uuid = Uuid::parse_str(id.trim()).with_context(|| format!("Invalid ID: {id}"))?;
+    let db_path = retrochat_core::database::config::get_default_db_path()?;
+    let db_manager = DatabaseManager::new(&db_path).await?;
+    let revision_repo = MessageRevisionRepository::new(&db_manager);
+
+    if MessageRepository::new(&db_manager)
+        .get_by_id(&uuid)
+        .await?
+        .is_some()
+    {
+        let revisions = revision_repo.get_by_message(&uuid).await?;
+        if revisions.is_empty() {
+            println!("Message {uuid} is unchanged since import");
+        }
+        for revision in &revisions {
+            println!("{}", format_revision(revision));
+        }
+        return Ok(());
+    }
+
+    if ChatSessionRepository::new(&db_manager)
+        .get_by_id(&uuid)
+        .await?
+        .is_none()
+    {
+        anyhow::bail!("No message or session with ID {uuid}");
+    }
+    let revisions = revision_repo.get_by_session(&uuid).await?;
+    if revisions.is_empty() {
+        println!("No message in session {uuid} was changed since import");
+    }
+    for revision in &revisions {
+        println!("{}  {}", revision.message_id, format_revision(revision));
+    }
+    Ok(())
+}
+
+fn format_revision(revision: &MessageRevision) -> String {
+    let mut line = format!(
+        "{}  {:<9}  {}: {} -> {}",
+        revision.created_at.format("%Y-%m-%d %H:%M"),
+        revision.source,
+        revision.field,
+        revision.before.as_deref().unwrap_or("-"),
+        revision.after.as_deref().unwrap_or("-"),
+    );
+    if let Some(reason) = &revision.reason {
+        line.push_str(&format!("  ({reason})"));
+    }
+    line
+}
@@ -6,21 +6,27 @@ use crate::commands::help;
 use retrochat_core::database::DatabaseManager;
 use retrochat_core::models::provider::config::{ClaudeCodeConfig, CodexConfig, GeminiCliConfig};
 use retrochat_core::models::Provider;
-use retrochat_core::services::ImportService;
+use retrochat_core::services::{ImportPreview, ImportService, SessionChange};
+use retrochat_core::utils::locale::Language;
 
 pub async fn handle_import_command(
     path: Option<String>,
     providers: Vec<Provider>,
     overwrite: bool,
+    dry_run: bool,
 ) -> Result<()> {
+    if dry_run {
+        println!("Dry run: nothing will be written to the database\n");
+    }
+
     // Check if user provided a path
     if let Some(path_str) = path {
-        return import_path(path_str, overwrite).await;
+        return import_path(path_str, overwrite, dry_run).await;
     }
 
     // Check if any providers are specified
     if !providers.is_empty() {
-        return import_providers(providers, overwrite).await;
+        return import_providers(providers, overwrite, dry_run).await;
     }
 
     // No arguments provided - show help message
@@ -28,7 +34,77 @@ pub async fn handle_import_command(
     Err(anyhow::anyhow!("No import source specified"))
 }
 
-async fn import_path(path_str: String, overwrite: bool) -> Result<()> {
+/// Finish imports that an earlier run left incomplete; failures only produce a warning
+pub async fn resume_interrupted_imports() {
+    let result = match retrochat_core::database::config::get_default_db_path() {
+        Ok(db_path) if db_path.exists() => match DatabaseManager::new(&db_path).await {
+            Ok(db_manager) => {
+                ImportService::new(Arc::new(db_manager))
+                    .resume_interrupted_imports()
+                    .await
+            }
+            Err(e) => Err(e),
+        },
+        Ok(_) => return,
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(report) if !report.is_empty() => {
+            println!("Resuming interrupted imports:");
+            println!("  Incomplete sessions removed: {}", report.sessions_removed);
+            println!(
+                "  Files imported again: {} ({} sessions)",
+                report.files_resumed, report.sessions_imported
+            );
+            for error in &report.errors {
+                println!("  Skipped {error}");
+            }
+            println!();
+        }
+        Ok(_) => {}
+        Err(e) => eprintln!("Warning: failed to resume interrupted imports: {e}"),
+    }
+}
+
+/// Print files whose most recent import attempt failed
+pub async fn handle_import_report_command() -> Result<()> {
+    let db_path = retrochat_core::database::config::get_default_db_path()?;
+    let db_manager = Arc::new(DatabaseManager::new(&db_path).await?);
+    let import_service = ImportService::new(db_manager);
+
+    let errors = import_service
+        .get_import_errors(None)
+        .await
+        .context("Failed to load import errors")?;
+
+    println!();
+    println!("Import health report");
+    if errors.is_empty() {
+        println!("  No files are currently failing to import.");
+        return Ok(());
+    }
+
+    println!("  {} file(s) failed to import:", errors.len());
+    for error in &errors {
+        println!();
+        println!("  {}", error.file_path);
+        if let Some(provider) = &error.provider {
+            println!("    Provider:      {provider}");
+        }
+        println!("    Error:         {}", error.error_message);
+        println!(
+            "    Attempts:      {} (first {}, last {})",
+            error.attempt_count,
+            Language::current().format_datetime_secs(&error.first_attempt_at),
+            Language::current().format_datetime_secs(&error.last_attempt_at)
+        );
+    }
+
+    Ok(())
+}
+
+async fn import_path(path_str: String, overwrite: bool, dry_run: bool) -> Result<()> {
     let path = Path::new(&path_str);
 
     if !path.exists() {
@@ -36,9 +112,9 @@ async fn import_path(path_str: String, overwrite: bool) -> Result<()> {
     }
 
     if path.is_file() {
-        import_file(path_str, overwrite).await
+        import_file(path_str, overwrite, dry_run).await
     } else if path.is_dir() {
-        import_batch(path_str, overwrite).await
+        import_batch(path_str, overwrite, dry_run).await
     } else {
         Err(anyhow::anyhow!(
             "Path is neither a file nor a directory: {path_str}"
@@ -46,7 +122,7 @@ async fn import_path(path_str: String, overwrite: bool) -> Result<()> {
     }
 }
 
-async fn import_providers(providers: Vec<Provider>, overwrite: bool) -> Result<()> {
+async fn import_providers(providers: Vec<Provider>, overwrite: bool, dry_run: bool) -> Result<()> {
     // Expand "All" to all specific providers
     let expanded_providers = Provider::expand_all(providers);
 
@@ -61,7 +137,7 @@ async fn import_providers(providers: Vec<Provider>, overwrite: bool) -> Result<(
             Provider::ClaudeCode => {
                 println!("Importing from Claude Code directories...");
                 if let Err(e) = ClaudeCodeConfig::import_directories(overwrite, |path, ow| {
-                    Box::pin(import_batch(path, ow))
+                    Box::pin(import_batch(path, ow, dry_run))
                 })
                 .await
                 {
@@ -74,7 +150,7 @@ async fn import_providers(providers: Vec<Provider>, overwrite: bool) -> Result<(
             Provider::GeminiCLI => {
                 println!("Importing from Gemini directories...");
                 if let Err(e) = GeminiCliConfig::import_directories(overwrite, |path, ow| {
-                    Box::pin(import_batch(path, ow))
+                    Box::pin(import_batch(path, ow, dry_run))
                 })
                 .await
                 {
@@ -87,7 +163,7 @@ async fn import_providers(providers: Vec<Provider>, overwrite: bool) -> Result<(
             Provider::Codex => {
                 println!("Importing from Codex directories...");
                 if let Err(e) = CodexConfig::import_directories(overwrite, |path, ow| {
-                    Box::pin(import_batch(path, ow))
+                    Box::pin(import_batch(path, ow, dry_run))
                 })
                 .await
                 {
@@ -105,9 +181,12 @@ async fn import_providers(providers: Vec<Provider>, overwrite: bool) -> Result<(
                     if let Some(parent) = workspace_path.parent() {
                         let global_db = parent.join("globalStorage/state.vscdb");
                         if global_db.exists() {
-                            if let Err(e) =
-                                import_file(global_db.to_string_lossy().to_string(), overwrite)
-                                    .await
+                            if let Err(e) = import_file(
+                                global_db.to_string_lossy().to_string(),
+                                overwrite,
+                                dry_run,
+                            )
+                            .await
                             {
                                 eprintln!("Error importing Cursor global storage: {e}");
                             } else {
@@ -133,7 +212,11 @@ async fn import_providers(providers: Vec<Provider>, overwrite: bool) -> Result<(
     }
 }
 
-async fn import_file(file_path: String, overwrite: bool) -> Result<()> {
+async fn import_file(file_path: String, overwrite: bool, dry_run: bool) -> Result<()> {
+    if dry_run {
+        return preview_file(file_path, overwrite).await;
+    }
+
     let path = Path::new(&file_path);
 
     println!("Importing file: {}", path.display());
@@ -173,6 +256,10 @@ async fn import_file(file_path: String, overwrite: bool) -> Result<()> {
         "  - {} messages imported",
         import_response.messages_imported
     );
+    print_truncations(
+        import_response.messages_truncated,
+        import_response.tool_results_truncated,
+    );
 
     if !import_response.warnings.is_empty() {
         println!("Warnings:");
@@ -184,7 +271,23 @@ async fn import_file(file_path: String, overwrite: bool) -> Result<()> {
     Ok(())
 }
 
-async fn import_batch(directory: String, overwrite: bool) -> Result<()> {
+/// Report rows cut down by the `[import]` size limits
+fn print_truncations(messages_truncated: i32, tool_results_truncated: i32) {
+    if messages_truncated > 0 {
+        println!("  - {messages_truncated} oversized messages truncated (import.max_message_kb)");
+    }
+    if tool_results_truncated > 0 {
+        println!(
+            "  - {tool_results_truncated} oversized tool results truncated (import.max_tool_result_kb)"
+        );
+    }
+}
+
+async fn import_batch(directory: String, overwrite: bool, dry_run: bool) -> Result<()> {
+    if dry_run {
+        return preview_batch(directory, overwrite).await;
+    }
+
     let path = Path::new(&directory);
 
     println!("Batch importing from directory: {}", path.display());
@@ -247,6 +350,10 @@ async fn import_batch(directory: String, overwrite: bool) -> Result<()> {
         "  - {} messages imported",
         batch_response.total_messages_imported
     );
+    print_truncations(
+        batch_response.messages_truncated,
+        batch_response.tool_results_truncated,
+    );
 
     if batch_response.failed_imports > 0 {
         println!(
@@ -263,3 +370,114 @@ async fn import_batch(directory: String, overwrite: bool) -> Result<()> {
 
     Ok(())
 }
+
+async fn preview_file(file_path: String, overwrite: bool) -> Result<()> {
+    println!("Previewing file: {}", Path::new(&file_path).display());
+
+    let db_path = retrochat_core::database::config::get_default_db_path()?;
+    let db_manager = Arc::new(DatabaseManager::new(&db_path).await?);
+    let import_service = ImportService::new(db_manager);
+
+    let mut preview = ImportPreview::default();
+    preview.add_file(
+        import_service
+            .preview_file(&file_path)
+            .await
+            .with_context(|| format!("Failed to preview file: {file_path}"))?,
+    );
+
+    print_preview(&preview, overwrite);
+    Ok(())
+}
+
+async fn preview_batch(directory: String, overwrite: bool) -> Result<()> {
+    let path = Path::new(&directory);
+
+    println!("Previewing directory: {}", path.display());
+
+    let db_path = retrochat_core::database::config::get_default_db_path()?;
+    let db_manager = Arc::new(DatabaseManager::new(&db_path).await?);
+    let import_service = ImportService::new(db_manager);
+
+    let preview = import_service
+        .preview_batch(retrochat_core::services::BatchImportRequest {
+            directory_path: directory.clone(),
+            providers: None,
+            project_name: None,
+            overwrite_existing: Some(overwrite),
+            recursive: Some(true),
+        })
+        .await
+        .with_context(|| format!("Failed to preview directory: {}", path.display()))?;
+
+    print_preview(&preview, overwrite);
+    Ok(())
+}
+
+fn print_preview(preview: &ImportPreview, overwrite: bool) {
+    let updated_action = if overwrite {
+        "overwritten"
+    } else {
+        "skipped (use --overwrite to update)"
+    };
+
+    println!("\nDry run summary:");
+    println!("  - {} files scanned", preview.files.len());
+    println!(
+        "  - {} new sessions would be imported",
+        preview.new_sessions
+    );
+    println!(
+        "  - {} changed sessions would be {updated_action}",
+        preview.updated_sessions
+    );
+    println!(
+        "  - {} unchanged sessions would be skipped",
+        preview.unchanged_sessions
+    );
+    if preview.ignored_sessions > 0 {
+        println!(
+            "  - {} sessions match the ignore rules and would be skipped",
+            preview.ignored_sessions
+        );
+    }
+    if preview.failed_files > 0 {
+        println!("  - {} files failed to parse", preview.failed_files);
+    }
+
+    let changes: Vec<_> = preview
+        .files
+        .iter()
+        .flat_map(|file| file.sessions.iter().map(move |s| (file, s)))
+        .filter(|(_, s)| matches!(s.change, SessionChange::New | SessionChange::Updated))
+        .collect();
+    if !changes.is_empty() {
+        println!("\nChanges:");
+        for (file, session) in changes {
+            let messages = match session.previous_message_count {
+                Some(previous) => format!("{previous} -> {} messages", session.message_count),
+                None => format!("{} messages", session.message_count),
+            };
+            let marker = match session.change {
+                SessionChange::New => "+",
+                _ => "~",
+            };
+            println!(
+                "  {marker} {} [{}] {messages} ({})",
+                session.session_id, session.provider, file.file_path
+            );
+        }
+    }
+
+    let failures: Vec<_> = preview
+        .files
+        .iter()
+        .filter_map(|file| file.error.as_ref().map(|e| (file, e)))
+        .collect();
+    if !failures.is_empty() {
+        println!("\nFailures:");
+        for (file, error) in failures {
+            println!("  ! {}: {error}", file.file_path);
+        }
+    }
+}
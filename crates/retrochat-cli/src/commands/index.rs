@@ -0,0 +1,76 @@
+use anyhow::Result;
+
+use retrochat_core::database::DatabaseManager;
+use retrochat_core::services::{EmbeddingIndexService, IndexCoverage, IndexReport};
+use retrochat_core::utils::locale::Language;
+
+/// Handle the index command
+pub async fn handle_index_command(rebuild: bool, status: bool) -> Result<()> {
+    let db_path = retrochat_core::database::config::get_default_db_path()?;
+    let db_manager = DatabaseManager::new(&db_path).await?;
+    let service = EmbeddingIndexService::from_config(&db_manager)?;
+
+    if !status {
+        let report = if rebuild {
+            println!("Rebuilding the summary embedding index...");
+            service.rebuild().await?
+        } else {
+            service.index_pending().await?
+        };
+        print_report(&report);
+        println!();
+    }
+
+    print_coverage(&service.coverage().await?);
+    Ok(())
+}
+
+/// Embed new summaries after a sync; failures only produce a warning
+pub async fn index_after_sync() {
+    let result = match retrochat_core::database::config::get_default_db_path() {
+        Ok(db_path) => match DatabaseManager::new(&db_path).await {
+            Ok(db_manager) => match EmbeddingIndexService::from_config(&db_manager) {
+                Ok(service) => service.index_pending().await,
+                Err(e) => Err(e),
+            },
+            Err(e) => Err(e),
+        },
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(report) if report.embedded > 0 || report.removed > 0 => {
+            println!();
+            print_report(&report);
+        }
+        Ok(_) => {}
+        Err(e) => eprintln!("Warning: failed to update the embedding index: {e}"),
+    }
+}
+
+fn print_report(report: &IndexReport) {
+    println!("Embedding index updated:");
+    println!("  Summaries embedded: {}", report.embedded);
+    println!("  Already up to date: {}", report.unchanged);
+    println!("  Stale vectors removed: {}", report.removed);
+}
+
+fn print_coverage(coverage: &[IndexCoverage]) {
+    println!("Index coverage:");
+    for entry in coverage {
+        println!(
+            "  {:<8} {}/{} summaries ({:.0}%)",
+            entry.kind,
+            entry.indexed,
+            entry.total,
+            entry.percentage()
+        );
+        if let (Some(embedder), Some(dimensions)) = (&entry.embedder, entry.dimensions) {
+            let last_indexed = entry
+                .last_indexed_at
+                .map(|t| format!("{} UTC", Language::current().format_datetime(&t)))
+                .unwrap_or_else(|| "never".to_string());
+            println!("           {embedder} ({dimensions} dims), last indexed {last_indexed}");
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use anyhow::{Context, Result};
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use retrochat_core::logging::{LogLineFilter, LoggingConfig};
+
+/// How often `--follow` checks the log file for new lines
+const FOLLOW_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+fn log_path() -> Result<PathBuf> {
+    LoggingConfig::load()
+        .with_default_file()?
+        .file_path
+        .context("No log file configured")
+}
+
+/// Handle the logs tail command
+pub async fn handle_tail_command(
+    lines: usize,
+    follow: bool,
+    filter: Option<String>,
+    level: Option<String>,
+) -> Result<()> {
+    let min_level = level
+        .map(|level| {
+            level
+                .parse()
+                .map_err(|_| anyhow::anyhow!("Unknown level: {level}"))
+        })
+        .transpose()?;
+    let filter = LogLineFilter {
+        text: filter,
+        min_level,
+    };
+    let path = log_path()?;
+    if !path.exists() {
+        anyhow::bail!(
+            "No log file at {} yet; the TUI and `retrochat sync` write it",
+            path.display()
+        );
+    }
+
+    let mut position = print_last_lines(&path, lines, &filter)?;
+    if !follow {
+        return Ok(());
+    }
+
+    let mut partial = String::new();
+    loop {
+        tokio::time::sleep(FOLLOW_POLL_INTERVAL).await;
+        let Ok(mut file) = File::open(&path) else {
+            // Between the rename and the new file during rotation
+            continue;
+        };
+        let len = file.metadata()?.len();
+        if len < position {
+            // Rotated: the current file is a new one
+            position = 0;
+            partial.clear();
+        }
+        if len == position {
+            continue;
+        }
+        file.seek(SeekFrom::Start(position))?;
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes)?;
+        position += bytes.len() as u64;
+
+        partial.push_str(&String::from_utf8_lossy(&bytes));
+        while let Some(end) = partial.find('\n') {
+            let line: String = partial.drain(..=end).collect();
+            let line = line.trim_end();
+            if filter.matches(line) {
+                println!("{line}");
+            }
+        }
+    }
+}
+
+/// Print the last `count` matching lines and return the file length read
+fn print_last_lines(path: &Path, count: usize, filter: &LogLineFilter) -> Result<u64> {
+    let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut reader = BufReader::new(file);
+    let mut recent = VecDeque::with_capacity(count);
+    let mut position = 0;
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        let read = reader.read_until(b'\n', &mut buf)?;
+        // A last line without a newline is still being written
+        if read == 0 || buf.last() != Some(&b'\n') {
+            break;
+        }
+        position += read as u64;
+        let line = String::from_utf8_lossy(&buf).trim_end().to_string();
+        if count > 0 && filter.matches(&line) {
+            if recent.len() == count {
+                recent.pop_front();
+            }
+            recent.push_back(line);
+        }
+    }
+    for line in recent {
+        println!("{line}");
+    }
+    Ok(position)
+}
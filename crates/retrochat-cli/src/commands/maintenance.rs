@@ -0,0 +1,137 @@
+use anyhow::{Context, Result};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use retrochat_core::database::DatabaseManager;
+use retrochat_core::services::{
+    EmbeddingIndexService, MaintenanceReport, RetentionReport, RetentionService, SessionSelector,
+    VectorStorage,
+};
+use retrochat_core::utils::time_parser;
+
+async fn open_database() -> Result<Arc<DatabaseManager>> {
+    let db_path = retrochat_core::database::config::get_default_db_path()?;
+    Ok(Arc::new(DatabaseManager::new(&db_path).await?))
+}
+
+async fn create_service() -> Result<RetentionService> {
+    Ok(RetentionService::new(open_database().await?))
+}
+
+/// Handle the archive command
+pub async fn handle_archive_command(
+    session_ids: Vec<String>,
+    provider: Option<String>,
+    project: Option<String>,
+    before: Option<String>,
+    restore: bool,
+) -> Result<()> {
+    let session_ids = session_ids
+        .iter()
+        .map(|id| Uuid::parse_str(id).with_context(|| format!("Invalid session ID: {id}")))
+        .collect::<Result<Vec<_>>>()?;
+    let before = before
+        .map(|spec| time_parser::parse_time_spec(&spec))
+        .transpose()?;
+
+    let selector = SessionSelector {
+        session_ids,
+        provider,
+        project,
+        before,
+    };
+    if selector.is_empty() {
+        anyhow::bail!("Provide session IDs or a filter (--provider, --project, --before)");
+    }
+
+    let service = create_service().await?;
+    if restore {
+        let count = service.restore(&selector).await?;
+        println!("Restored {count} archived sessions");
+    } else {
+        let count = service.archive(&selector).await?;
+        println!("Archived {count} sessions");
+        if count > 0 {
+            println!("Use 'retrochat list --archived' to see them");
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle the maintenance command
+pub async fn handle_maintenance_command() -> Result<()> {
+    let db_manager = open_database().await?;
+    let report = RetentionService::new(db_manager.clone())
+        .run_maintenance()
+        .await?;
+
+    if report.is_empty() {
+        println!("Retention: no sessions are past the retention policy");
+    } else {
+        print_report(&report);
+    }
+
+    let index = EmbeddingIndexService::new(&db_manager);
+    println!();
+    print_vector_report(&index.maintain().await?);
+    print_storage(&index.storage().await?);
+    Ok(())
+}
+
+/// Apply the retention policy after a sync; failures only produce a warning
+pub async fn run_retention_after_sync() {
+    let result = match create_service().await {
+        Ok(service) => service.run_maintenance().await,
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(report) if !report.is_empty() => {
+            println!();
+            print_report(&report);
+        }
+        Ok(_) => {}
+        Err(e) => eprintln!("Warning: failed to apply retention policy: {e}"),
+    }
+}
+
+fn print_report(report: &RetentionReport) {
+    println!("Retention policy applied:");
+    println!("  Sessions archived: {}", report.archived_sessions);
+    println!("  Tool results purged: {}", report.purged_tool_results);
+}
+
+fn print_vector_report(report: &MaintenanceReport) {
+    println!("Vector store maintained:");
+    println!("  Orphaned vectors removed: {}", report.orphans_removed);
+    println!("  Malformed vectors removed: {}", report.malformed_removed);
+    println!(
+        "  Space reclaimed: {}",
+        format_bytes(report.reclaimed_bytes)
+    );
+}
+
+fn print_storage(storage: &[VectorStorage]) {
+    println!("Vector storage:");
+    for entry in storage {
+        println!(
+            "  {:<8} {} vectors, {}",
+            entry.kind,
+            entry.vectors,
+            format_bytes(entry.bytes)
+        );
+    }
+}
+
+pub(crate) fn format_bytes(bytes: i64) -> String {
+    const KB: f64 = 1024.0;
+    let bytes = bytes as f64;
+    if bytes < KB {
+        format!("{bytes} B")
+    } else if bytes < KB * KB {
+        format!("{:.1} KB", bytes / KB)
+    } else {
+        format!("{:.1} MB", bytes / (KB * KB))
+    }
+}
@@ -1,15 +1,39 @@
 pub mod analytics;
 pub mod config;
+pub mod db;
+pub mod doctor;
+pub mod events;
+pub mod experiments;
 pub mod help;
+pub mod history;
 pub mod import;
+pub mod index;
 pub mod init;
+pub mod logs;
+pub mod maintenance;
 pub mod query;
+pub mod quota;
+pub mod raw;
+pub mod remote;
+pub mod repair;
+pub mod serve;
+pub mod session;
 pub mod setup;
+pub mod share;
+pub mod sql;
+pub mod standup;
+pub mod stats;
 pub mod summarize;
+pub mod team;
+pub mod titles;
+pub mod tokens;
+pub mod topics;
+pub mod views;
 pub mod watch;
 
 use clap::{Parser, Subcommand};
 use retrochat_core::models::Provider;
+use retrochat_core::services::llm::LlmProvider;
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
@@ -29,6 +53,8 @@ pub enum Commands {
     ///   retrochat sync all                    # Import from all providers
     ///   retrochat sync claude -w --verbose    # Watch mode with detailed output
     ///   retrochat sync --path ~/.claude/projects
+    ///   retrochat sync all --report           # Import, then list files that failed to parse
+    ///   retrochat sync all --dry-run          # Show what would be imported without writing
     Sync {
         /// One or more providers to sync
         ///
@@ -51,42 +77,178 @@ pub enum Commands {
         /// Show detailed diff of changes (applies to watch mode)
         #[arg(short = 'v', long)]
         verbose: bool,
+
+        /// Report new, changed and unchanged sessions without writing anything
+        #[arg(long, conflicts_with = "watch")]
+        dry_run: bool,
+
+        /// Print the import error ledger (files that failed to parse)
+        ///
+        /// Without providers or a path, only the report is printed.
+        #[arg(long)]
+        report: bool,
+    },
+
+    /// Run the watch daemon as a background service
+    ///
+    /// Examples:
+    ///   retrochat watch install-service            # Keep all providers synced
+    ///   retrochat watch install-service claude codex
+    ///   retrochat watch uninstall-service
+    Watch {
+        #[command(subcommand)]
+        command: WatchCommands,
     },
 
     /// List sessions with optional filters
+    ///
+    /// Examples:
+    ///   retrochat list provider:claude project:retrochat
+    ///   retrochat list provider:claude -project:scratch
+    ///   retrochat list 'tokens>50000 since:"last week" tool:Bash(failed)'
+    ///   retrochat list --owner team
     List {
+        /// Filter expression (e.g. provider:claude 'tokens>50000' tool:Bash(failed))
+        #[arg(allow_hyphen_values = true)]
+        filter: Vec<String>,
         /// Filter by provider
         #[arg(long)]
         provider: Option<String>,
         /// Filter by project
         #[arg(long)]
         project: Option<String>,
+        /// Only sessions of: me, team, or a teammate's name
+        #[arg(long)]
+        owner: Option<String>,
         /// Page number (default: 1)
         #[arg(short, long)]
         page: Option<i32>,
         /// Page size (default: 20)
         #[arg(short = 's', long)]
         page_size: Option<i32>,
+        /// Include archived sessions
+        #[arg(long)]
+        archived: bool,
+    },
+
+    /// Archive sessions so they no longer appear in session lists
+    ///
+    /// Archived sessions are kept in the database and can be restored.
+    ///
+    /// Examples:
+    ///   retrochat archive SESSION_ID [SESSION_ID...]
+    ///   retrochat archive --before "180 days ago"
+    ///   retrochat archive --project old-prototype --provider claude
+    ///   retrochat archive SESSION_ID --restore
+    Archive {
+        /// Session IDs to archive
+        session_ids: Vec<String>,
+        /// Only sessions from this provider
+        #[arg(long)]
+        provider: Option<String>,
+        /// Only sessions from this project
+        #[arg(long)]
+        project: Option<String>,
+        /// Only sessions that ended before this time (e.g., "180 days ago", "2024-01-01")
+        #[arg(long)]
+        before: Option<String>,
+        /// Restore matching archived sessions instead
+        #[arg(long)]
+        restore: bool,
     },
 
+    /// Apply the retention policy from the config file
+    ///
+    /// Configure it with:
+    ///   retrochat config set retention.archive_after_days 180
+    ///   retrochat config set retention.purge_tool_results_after_days 365
+    ///
+    /// The policy also runs automatically after each sync. Maintenance then
+    /// drops embeddings of deleted sessions and summaries, compacts the
+    /// database and reports how much space the vectors take.
+    Maintenance,
+
     /// Show detailed information about a session
+    ///
+    /// Accepts a session ID or a `retrochat://session/<id>?seq=<n>` link as
+    /// printed by `retrochat search`. With a message sequence number, shows
+    /// the messages around it instead of the start of the session.
+    ///
+    /// Examples:
+    ///   retrochat show SESSION_ID
+    ///   retrochat show SESSION_ID --at 42
+    ///   retrochat show "retrochat://session/SESSION_ID?seq=42"
     Show {
-        /// Session ID to view
+        /// Session ID or retrochat:// link to view
         session_id: String,
+
+        /// Sequence number of the message to jump to
+        #[arg(long)]
+        at: Option<u32>,
     },
 
     /// Export a session transcript to JSON file
+    ///
+    /// Examples:
+    ///   retrochat export-session SESSION_ID -o session.json
+    ///   retrochat export-session SESSION_ID --format claude-jsonl --cwd ~/code/app
     ExportSession {
         /// Session ID to export
         session_id: String,
         /// Output file path (prints to stdout if not specified)
         #[arg(short, long)]
         output: Option<String>,
+        /// Output format: transcript (default), markdown, or claude-jsonl
+        ///
+        /// claude-jsonl rebuilds a Claude Code conversation file (best effort)
+        /// that can be resumed with `claude --resume`.
+        #[arg(long, short = 'f', default_value = "transcript")]
+        format: String,
+        /// Working directory recorded in claude-jsonl output (default: current directory)
+        #[arg(long)]
+        cwd: Option<String>,
+        /// Copy the export to the system clipboard instead of printing it
+        #[arg(long, conflicts_with = "output")]
+        clipboard: bool,
+    },
+
+    /// Share a session as a standalone, anonymized HTML page
+    ///
+    /// The home directory, login name, email addresses and credentials are
+    /// replaced before rendering. With --gist the page is uploaded as a
+    /// secret GitHub gist (token from GITHUB_TOKEN or
+    /// `retrochat config set github-token`).
+    ///
+    /// Examples:
+    ///   retrochat share SESSION_ID
+    ///   retrochat share SESSION_ID -o fix.html --redact "Acme Corp"
+    ///   retrochat share SESSION_ID --gist
+    Share {
+        /// Session ID to share
+        session_id: String,
+        /// Output file path (default: retrochat-<id>.html; with --gist, only written if given)
+        #[arg(short, long)]
+        output: Option<String>,
+        /// Upload as a secret GitHub gist and print its URL
+        #[arg(long)]
+        gist: bool,
+        /// Keep paths, names and credentials as stored
+        #[arg(long)]
+        no_anonymize: bool,
+        /// Extra terms to redact, e.g. a company or host name (repeatable)
+        #[arg(long, conflicts_with = "no_anonymize")]
+        redact: Vec<String>,
+        /// Include the assistant's thinking blocks
+        #[arg(long)]
+        thinking: bool,
     },
 
     /// Search messages by content
+    ///
+    /// Filter terms in the query narrow down the sessions searched, e.g.
+    ///   retrochat search "migration error provider:claude tool:Bash(failed)"
     Search {
-        /// Search query
+        /// Search query, optionally with filter terms
         query: String,
         /// Maximum number of results (default: 20)
         #[arg(short, long)]
@@ -99,6 +261,148 @@ pub enum Commands {
         until: Option<String>,
     },
 
+    /// Run a read-only SQL query against the database
+    ///
+    /// Only single SELECT/WITH/EXPLAIN statements are accepted, and they run on a
+    /// read-only connection.
+    ///
+    /// Examples:
+    ///   retrochat query --schema
+    ///   retrochat query "SELECT provider, COUNT(*) FROM chat_sessions GROUP BY provider"
+    ///   retrochat query "SELECT tool_name, COUNT(*) AS n FROM tool_operations GROUP BY 1 ORDER BY n DESC" --format json
+    Query {
+        /// SQL statement to run
+        #[arg(required_unless_present = "schema")]
+        sql: Option<String>,
+        /// Print the tables and views available to queries
+        #[arg(long, conflicts_with = "sql")]
+        schema: bool,
+        /// Output format: table (default) or json
+        #[arg(long, short = 'f', default_value = "table")]
+        format: String,
+        /// Maximum number of rows to return
+        #[arg(long, short = 'n', default_value = "1000")]
+        limit: usize,
+    },
+
+    /// Embed turn and session summaries for semantic search
+    ///
+    /// New summaries are indexed automatically after summarization and sync;
+    /// run this to catch up, rebuild after changing embedders, or check coverage.
+    ///
+    /// Examples:
+    ///   retrochat index
+    ///   retrochat index --rebuild
+    ///   retrochat index --status
+    Index {
+        /// Drop every stored vector and embed all summaries again
+        #[arg(long, conflicts_with = "status")]
+        rebuild: bool,
+        /// Only report index coverage
+        #[arg(long)]
+        status: bool,
+    },
+
+    /// Per-message token estimates for providers that do not report usage
+    ///
+    /// Messages are estimated at import time; backfill fills in messages
+    /// imported earlier.
+    ///
+    /// Examples:
+    ///   retrochat tokens backfill
+    ///   retrochat tokens accuracy
+    Tokens {
+        #[command(subcommand)]
+        command: TokensCommands,
+    },
+
+    /// The provider JSON kept behind imported messages and tool calls
+    ///
+    /// Only kept when `import.raw_payloads` is on. Strip deletes it; rehydrate
+    /// re-reads the source logs and restores it for rows already imported.
+    ///
+    /// Examples:
+    ///   retrochat config set import.raw_payloads true
+    ///   retrochat raw status
+    ///   retrochat raw strip --session SESSION_ID
+    ///   retrochat raw rehydrate
+    Raw {
+        #[command(subcommand)]
+        command: RawCommands,
+    },
+
+    /// Usage quotas: thresholds, current usage and recent alerts
+    ///
+    /// Quotas are set with `retrochat config set quota.<name> <limit>` and
+    /// checked after every sync and analysis run.
+    ///
+    /// Examples:
+    ///   retrochat config set quota.daily_tokens 2000000
+    ///   retrochat config set quota.weekly_analysis_usd 5
+    ///   retrochat quota
+    Quota,
+
+    /// Saved filter expressions ("smart views")
+    ///
+    /// Examples:
+    ///   retrochat views save big-failures "tokens>100k outcome:abandoned"
+    ///   retrochat views run big-failures
+    Views {
+        #[command(subcommand)]
+        command: ViewsCommands,
+    },
+
+    /// Sessions grouped into topics by summary similarity
+    ///
+    /// Topics are re-clustered after a sync once they are a week old; new
+    /// topics found that way are labelled with title keywords.
+    ///
+    /// Examples:
+    ///   retrochat topics refresh
+    ///   retrochat topics list
+    ///   retrochat topics show "database migrations"
+    Topics {
+        #[command(subcommand)]
+        command: TopicsCommands,
+    },
+
+    /// Fix messages an importer got wrong
+    ///
+    /// Examples:
+    ///   retrochat repair roles <SESSION_ID> --dry-run
+    ///   retrochat repair roles <SESSION_ID> --no-llm
+    Repair {
+        #[command(subcommand)]
+        command: RepairCommands,
+    },
+
+    /// Fix sessions a parser joined or split wrongly
+    ///
+    /// Summaries and embeddings of the edited sessions are dropped; run
+    /// `retrochat summarize` and `retrochat index` again afterwards.
+    ///
+    /// Examples:
+    ///   retrochat session split <SESSION_ID> --at-sequence 42
+    ///   retrochat session merge <SESSION_ID> <OTHER_SESSION_ID>
+    Session {
+        #[command(subcommand)]
+        command: SessionCommands,
+    },
+
+    /// Show the changes made to a message after import
+    ///
+    /// Role repairs, session splits and merges, and redactions are recorded
+    /// with the value before and after. Given a session ID, lists the changes
+    /// to all of its messages.
+    ///
+    /// Examples:
+    ///   retrochat history <MESSAGE_ID>
+    ///   retrochat history <SESSION_ID>
+    History {
+        /// Message or session ID
+        id: String,
+    },
+
     /// AI-powered session analysis
     Analysis {
         #[command(subcommand)]
@@ -111,11 +415,72 @@ pub enum Commands {
         command: SummarizeCommands,
     },
 
+    /// Generate session titles
+    Titles {
+        #[command(subcommand)]
+        command: TitlesCommands,
+    },
+
+    /// Write a markdown standup of a day's sessions: what was worked on,
+    /// what shipped and what is blocked
+    ///
+    /// Examples:
+    ///   retrochat standup
+    ///   retrochat standup --date 2024-10-01
+    ///   retrochat standup --no-llm
+    Standup {
+        /// Day to report on (e.g., "yesterday", "today", "2024-10-01")
+        #[arg(long, default_value = "yesterday")]
+        date: String,
+        /// Print the plain activity list instead of asking the LLM
+        #[arg(long)]
+        no_llm: bool,
+        /// LLM provider: google-ai (default), claude-code, gemini-cli
+        #[arg(long, short = 'P', conflicts_with = "no_llm")]
+        provider: Option<String>,
+        /// Model identifier (provider-specific)
+        #[arg(long, short = 'm', conflicts_with = "no_llm")]
+        model: Option<String>,
+    },
+
+    /// Conversation quality signals that need no LLM: user corrections,
+    /// tool errors, tool retry loops and prompt lengths
+    ///
+    /// Signals are computed at import; sessions imported earlier are
+    /// filled in on first use.
+    ///
+    /// Examples:
+    ///   retrochat stats
+    ///   retrochat stats --since "last month" --project retrochat
+    ///   retrochat stats --json
+    ///   retrochat stats --languages --since "last month"
+    Stats {
+        /// Only sessions started since (e.g., "7 days ago", "2024-10-01")
+        #[arg(long)]
+        since: Option<String>,
+        /// Only sessions in this project
+        #[arg(long, short = 'p')]
+        project: Option<String>,
+        /// Show files touched and lines changed per language instead
+        #[arg(long)]
+        languages: bool,
+        /// Print JSON instead of a summary
+        #[arg(long)]
+        json: bool,
+    },
+
     /// Export chat history
     Export {
-        /// Output format: compact (default) or jsonl
+        /// Output format: compact (default), jsonl, template (see --template-file), or sqlite
+        /// (standalone database of whole sessions)
         #[arg(long, short = 'f', default_value = "compact")]
         format: String,
+        /// Handlebars template to render with --format template
+        #[arg(long)]
+        template_file: Option<String>,
+        /// Group the compact timeline under headers: day, session, or project
+        #[arg(long)]
+        group_by: Option<String>,
         /// Messages since this time (e.g., "7 days ago", "2024-10-01", "yesterday")
         #[arg(long)]
         since: Option<String>,
@@ -143,22 +508,218 @@ pub enum Commands {
         /// Number of characters to show from the end (default: 200)
         #[arg(long, default_value = "200")]
         truncate_tail: usize,
-        /// Output file path (optional, prints to stdout if not specified)
+        /// Output file path (stdout if not specified or "-")
         #[arg(short, long)]
         output: Option<String>,
+        /// Gzip the output
+        #[arg(long)]
+        compress: bool,
         /// Exclude tool use and tool result messages
         #[arg(long)]
         no_tool: bool,
+        /// Only export these sessions (comma-separated or repeated IDs)
+        #[arg(long = "session", visible_alias = "sessions", value_delimiter = ',')]
+        sessions: Vec<String>,
+        /// Only export sessions of this project
+        #[arg(long)]
+        project: Option<String>,
+        /// Only export sessions matching a filter expression (e.g. "project:retrochat tool:Bash")
+        #[arg(long)]
+        filter: Option<String>,
+        /// Only export sessions matching a saved view (see `retrochat views`)
+        #[arg(long)]
+        view: Option<String>,
     },
 
-    /// Interactive setup wizard for first-time users
-    Setup,
+    /// Setup wizard for first-time users, or scripted setup without prompts
+    ///
+    /// Examples:
+    ///   retrochat setup
+    ///   retrochat setup --non-interactive --providers claude,codex
+    ///   retrochat setup --non-interactive --llm google_ai --api-key-env GOOGLE_AI_API_KEY
+    Setup {
+        /// Detect, import and configure without prompts (for bootstrap scripts)
+        #[arg(long)]
+        non_interactive: bool,
+
+        /// Providers to import (default: every provider with chat history)
+        #[arg(long, value_enum, value_delimiter = ',', requires = "non_interactive")]
+        providers: Vec<Provider>,
+
+        /// LLM provider for analyses: google-ai, claude-code or gemini-cli
+        #[arg(long, requires = "non_interactive")]
+        llm: Option<LlmProvider>,
+
+        /// Environment variable holding the Google AI API key to save
+        #[arg(long, value_name = "VAR", requires = "non_interactive")]
+        api_key_env: Option<String>,
+
+        /// Save the LLM provider without sending a test prompt
+        #[arg(long, requires = "llm")]
+        skip_llm_check: bool,
+    },
 
     /// Manage configuration settings
     Config {
         #[command(subcommand)]
         command: ConfigCommands,
     },
+
+    /// Inspect and repair the database schema
+    ///
+    /// Pending migrations are applied automatically when RetroChat opens the
+    /// database, after backing it up. These commands work even when that
+    /// fails.
+    ///
+    /// Examples:
+    ///   retrochat db status --verbose
+    ///   retrochat db migrate --dry-run
+    ///   retrochat db rollback 28
+    ///   retrochat db restore
+    ///   retrochat db encrypt --keychain
+    Db {
+        #[command(subcommand)]
+        command: DbCommands,
+    },
+
+    /// Check the installation and print how to fix what is wrong
+    ///
+    /// Looks at database integrity, the schema version, provider data
+    /// directories, LLM providers, the vector index, unrecognized log
+    /// formats and free disk space.
+    ///
+    /// Examples:
+    ///   retrochat doctor
+    ///   retrochat doctor --skip-generation --json
+    ///   retrochat doctor --parsers
+    Doctor {
+        /// Only check LLM credentials, without sending a test prompt
+        #[arg(long)]
+        skip_generation: bool,
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+        /// List log fields and types the parsers did not recognize during import
+        #[arg(long)]
+        parsers: bool,
+        /// With --parsers, forget the recorded warnings
+        #[arg(long, requires = "parsers")]
+        clear: bool,
+    },
+
+    /// Sync history between machines through S3, WebDAV or a shared folder
+    ///
+    /// Sessions are pushed as encrypted bundles; set the passphrase with
+    /// `retrochat config set sync.passphrase` or RETROCHAT_SYNC_PASSPHRASE
+    /// on every machine.
+    ///
+    /// Examples:
+    ///   retrochat remote add nas https://nas.local/remote.php/dav/files/me/retrochat --username me --password secret
+    ///   retrochat remote add r2 s3://history/retrochat --endpoint https://<account>.r2.cloudflarestorage.com
+    ///   retrochat remote push
+    ///   retrochat remote pull nas
+    Remote {
+        #[command(subcommand)]
+        command: RemoteCommands,
+    },
+
+    /// Log of changes made by any RetroChat process
+    ///
+    /// Imports, analyses, archiving, saved views, topic refreshes and quota
+    /// alerts are recorded in order; each event has an increasing ID.
+    ///
+    /// Examples:
+    ///   retrochat events tail
+    ///   retrochat events tail --follow --kind session_imported
+    ///   retrochat events tail --since 120 --json
+    Events {
+        #[command(subcommand)]
+        command: EventsCommands,
+    },
+
+    /// Serve an HTTP API, e.g. to upload session files to a home server
+    ///
+    /// `POST /api/import` takes multipart uploads of .json/.jsonl session
+    /// files. Set a token with `retrochat config set serve.token` or
+    /// RETROCHAT_SERVE_TOKEN; it is required to listen beyond localhost.
+    ///
+    /// Examples:
+    ///   retrochat serve
+    ///   retrochat serve --host 0.0.0.0 --port 8080
+    Serve {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+        /// Port to listen on
+        #[arg(long, default_value_t = 7878)]
+        port: u16,
+    },
+
+    /// Read RetroChat's own log (~/.retrochat/logs/retrochat.log)
+    ///
+    /// The TUI and `retrochat sync` log there. Levels, JSON output, rotation
+    /// and per-module levels are set under `logging.*` in the config, e.g.
+    /// `retrochat config set logging.modules.retrochat_core::services::watch_service debug`.
+    ///
+    /// Examples:
+    ///   retrochat logs tail
+    ///   retrochat logs tail --follow --filter watch_service
+    ///   retrochat logs tail -n 200 --level warn
+    Logs {
+        #[command(subcommand)]
+        command: LogsCommands,
+    },
+
+    /// Import teammates' exports as read-only sessions
+    ///
+    /// A teammate runs `retrochat export --format sqlite -o me.db` and shares
+    /// the file. Their sessions are tagged with their name; filter with
+    /// `owner:me`, `owner:team` or `owner:<name>`.
+    ///
+    /// Examples:
+    ///   retrochat team import alice.db --owner alice
+    ///   retrochat team stats
+    ///   retrochat list --owner team
+    ///   retrochat team remove alice
+    Team {
+        #[command(subcommand)]
+        command: TeamCommands,
+    },
+
+    /// A/B experiments: compare cohorts of sessions, e.g. one model against another
+    ///
+    /// Sessions are put into named cohorts by ID or filter expression. Show
+    /// aggregates each cohort's metrics; report has the LLM compare them.
+    ///
+    /// Examples:
+    ///   retrochat experiments create models --description "Sonnet vs Codex"
+    ///   retrochat experiments add models sonnet --filter "provider:claude project:retrochat"
+    ///   retrochat experiments add models gpt-5-codex SESSION_ID SESSION_ID
+    ///   retrochat experiments show models
+    ///   retrochat experiments report models
+    Experiments {
+        #[command(subcommand)]
+        command: ExperimentsCommands,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum WatchCommands {
+    /// Install and start a user service running `retrochat sync --watch`
+    ///
+    /// Writes a systemd user unit on Linux or a launchd agent on macOS. The
+    /// service starts at login; run this again after changing providers or
+    /// moving the retrochat binary.
+    InstallService {
+        /// Providers to watch (default: all)
+        #[arg(value_enum)]
+        providers: Vec<Provider>,
+        /// Print the unit file or plist instead of installing it
+        #[arg(long)]
+        print: bool,
+    },
+    /// Stop the service and remove it
+    UninstallService,
 }
 
 #[derive(Subcommand)]
@@ -195,6 +756,27 @@ pub enum AnalysisCommands {
         all: bool,
     },
 
+    /// Compare two sessions head to head: approach, efficiency, failure points
+    ///
+    /// The comparison is stored as an analysis of the first session and
+    /// listed by `retrochat analysis show` for either session.
+    ///
+    /// Examples:
+    ///   retrochat analysis compare <SESSION_ID> <OTHER_SESSION_ID>
+    ///   retrochat analysis compare <SESSION_ID> <OTHER_SESSION_ID> -P claude-code
+    Compare {
+        /// First session ID
+        session_id: String,
+        /// Second session ID
+        other_session_id: String,
+        /// LLM provider: google-ai (default), claude-code, gemini-cli
+        #[arg(long, short = 'P')]
+        provider: Option<String>,
+        /// Model identifier (provider-specific)
+        #[arg(long, short = 'm')]
+        model: Option<String>,
+    },
+
     /// Show analysis request status
     Status {
         /// Show all active operations
@@ -216,6 +798,50 @@ pub enum AnalysisCommands {
         #[arg(long)]
         all: bool,
     },
+
+    /// List the most repeated asks across sessions (rework)
+    Rework {
+        /// Maximum number of repeated prompts to show (default: 10)
+        #[arg(short, long, default_value = "10")]
+        limit: usize,
+        /// Similarity threshold between 0.0 and 1.0 (default: 0.9)
+        #[arg(short, long)]
+        threshold: Option<f32>,
+    },
+
+    /// Count slash commands and hook runs, and how their sessions ended
+    ///
+    /// Completion rates come from session summaries; compare them with the
+    /// baseline to see which workflows go with sessions that get finished.
+    Workflows,
+
+    /// Recompute overall rubric scores of existing analyses with the current weights
+    ///
+    /// Weights are set per rubric ID or name; the rubric scores themselves
+    /// are kept, so no LLM is called.
+    ///
+    /// Examples:
+    ///   retrochat config set "analysis.rubric_weights.Quality Verification" 2
+    ///   retrochat analysis recompute-scores
+    RecomputeScores,
+
+    /// Rank MCP servers by how often their tools are called and fail
+    ///
+    /// Examples:
+    ///   retrochat analysis mcp
+    ///   retrochat analysis mcp --since "last month"
+    Mcp {
+        /// Only count calls after this date (e.g. 2024-06-01, "last week")
+        #[arg(long)]
+        since: Option<String>,
+    },
+
+    /// Check which LLM providers are configured and working
+    Doctor {
+        /// Only check credentials and binaries, without a test generation
+        #[arg(long)]
+        skip_generation: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -254,6 +880,175 @@ pub enum SummarizeCommands {
     Status,
 }
 
+#[derive(Subcommand)]
+pub enum TitlesCommands {
+    /// Generate short descriptive titles with an LLM
+    ///
+    /// Titles are saved as they are generated, so an interrupted run picks up
+    /// where it stopped. Sessions that already have an LLM title are skipped.
+    ///
+    /// Examples:
+    ///   retrochat titles generate                  # Replace heuristic titles too
+    ///   retrochat titles generate --missing-only   # Only untitled sessions
+    ///   retrochat titles generate -P claude-code --batch-size 2 --delay-ms 5000
+    Generate {
+        /// Only title sessions without any title (keep heuristic titles)
+        #[arg(long)]
+        missing_only: bool,
+        /// LLM provider: google-ai (default), claude-code, gemini-cli
+        #[arg(long, short = 'P')]
+        provider: Option<String>,
+        /// Model identifier (provider-specific)
+        #[arg(long, short = 'm')]
+        model: Option<String>,
+        /// Number of sessions sent to the LLM at once (default: 5)
+        #[arg(long, default_value = "5")]
+        batch_size: usize,
+        /// Pause between batches in milliseconds (default: 1000)
+        #[arg(long, default_value = "1000")]
+        delay_ms: u64,
+        /// Maximum number of sessions to title in this run
+        #[arg(short, long)]
+        limit: Option<usize>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ViewsCommands {
+    /// Save a filter expression under a name, replacing any view with that name
+    Save {
+        /// View name
+        name: String,
+        /// Filter expression (e.g. "provider:claude tokens>100k")
+        expression: String,
+    },
+    /// List saved views
+    List,
+    /// List the sessions matching a saved view
+    Run {
+        /// View name
+        name: String,
+        /// Page number (default: 1)
+        #[arg(short, long)]
+        page: Option<i32>,
+        /// Page size (default: 20)
+        #[arg(short = 's', long)]
+        page_size: Option<i32>,
+    },
+    /// Delete a saved view
+    Delete {
+        /// View name
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum RepairCommands {
+    /// Re-infer who wrote each message, for sessions imported with every
+    /// message as User
+    ///
+    /// Roles are guessed from message types and phrasing; messages that
+    /// can't be placed that way are classified by the LLM.
+    Roles {
+        /// Session ID
+        session_id: String,
+        /// Only show the changes, without saving them
+        #[arg(long)]
+        dry_run: bool,
+        /// Only use the heuristics
+        #[arg(long)]
+        no_llm: bool,
+        /// LLM provider: google-ai (default), claude-code, gemini-cli
+        #[arg(long, short = 'P', conflicts_with = "no_llm")]
+        provider: Option<String>,
+        /// Model identifier (provider-specific)
+        #[arg(long, short = 'm', conflicts_with = "no_llm")]
+        model: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum SessionCommands {
+    /// Move the messages from a sequence number on into a new session
+    Split {
+        /// Session ID
+        session_id: String,
+        /// Sequence number of the first message of the new session
+        #[arg(long)]
+        at_sequence: u32,
+    },
+    /// Append the later of two sessions to the earlier one
+    Merge {
+        /// Session ID
+        session_id: String,
+        /// Session ID of the other session
+        other_session_id: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TokensCommands {
+    /// Estimate tokens for messages that have no estimate yet
+    Backfill {
+        /// Re-estimate every message, not only those without an estimate
+        #[arg(long)]
+        force: bool,
+    },
+    /// Compare estimates with the usage reported by providers
+    Accuracy,
+}
+
+#[derive(Subcommand)]
+pub enum RawCommands {
+    /// Show whether raw payloads are kept and how much space they take
+    Status,
+    /// Delete stored raw payloads
+    Strip {
+        /// Only this session (default: all sessions)
+        #[arg(long)]
+        session: Option<String>,
+    },
+    /// Restore raw payloads from the source logs
+    Rehydrate {
+        /// Only this session (default: all sessions)
+        #[arg(long)]
+        session: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TopicsCommands {
+    /// List topics, largest first
+    List,
+    /// List the sessions in a topic
+    Show {
+        /// Topic ID or label
+        topic: String,
+        /// Page number (default: 1)
+        #[arg(short, long)]
+        page: Option<i32>,
+        /// Page size (default: 20)
+        #[arg(short = 's', long)]
+        page_size: Option<i32>,
+    },
+    /// Cluster sessions into topics again, keeping the labels of topics that
+    /// did not change
+    Refresh {
+        /// Number of topics (default: picked from the number of sessions)
+        #[arg(long)]
+        clusters: Option<usize>,
+        /// Label new topics with title keywords instead of asking the LLM
+        #[arg(long)]
+        no_llm: bool,
+        /// LLM provider: google-ai (default), claude-code, gemini-cli
+        #[arg(long, short = 'P', conflicts_with = "no_llm")]
+        provider: Option<String>,
+        /// Model identifier (provider-specific)
+        #[arg(long, short = 'm', conflicts_with = "no_llm")]
+        model: Option<String>,
+    },
+}
+
 #[derive(Subcommand)]
 pub enum ConfigCommands {
     /// Get a configuration value
@@ -277,6 +1072,230 @@ pub enum ConfigCommands {
     List,
     /// Show the path to the config file
     Path,
+    /// Check the config file for syntax errors, wrong types, unknown keys and
+    /// invalid values
+    Validate {
+        /// Config file to check (default: ~/.retrochat/config.toml)
+        #[arg(long)]
+        file: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum DbCommands {
+    /// Show the schema version and any pending or unknown migrations
+    Status {
+        /// List every migration with when it was applied
+        #[arg(long, short = 'v')]
+        verbose: bool,
+    },
+    /// Apply pending migrations
+    Migrate {
+        /// Print the SQL that would run instead of running it
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Revert migrations newer than VERSION with their down scripts
+    Rollback {
+        /// Migration to roll back to
+        version: i64,
+        /// Print the SQL that would run instead of running it
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Replace the database with a backup (the newest pre-migration backup by default)
+    Restore {
+        /// Backup file to restore
+        backup: Option<String>,
+    },
+    /// Encrypt the database with a passphrase (needs a build with SQLCipher)
+    ///
+    /// The passphrase is read from RETROCHAT_DB_PASSPHRASE or prompted for.
+    /// Every later run needs it from that variable or the OS keychain.
+    Encrypt {
+        /// Store the passphrase in the OS keychain
+        #[arg(long)]
+        keychain: bool,
+    },
+    /// Decrypt the database back to plain SQLite
+    Decrypt,
+    /// Change the passphrase of an encrypted database
+    Rekey {
+        /// Store the new passphrase in the OS keychain
+        #[arg(long)]
+        keychain: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum RemoteCommands {
+    /// Add a remote, or replace the one with the same name
+    Add {
+        /// Name to refer to the remote by
+        name: String,
+        /// s3://bucket/prefix, an https:// WebDAV folder, or a local folder
+        url: String,
+        /// S3-compatible endpoint (MinIO, R2, ...) instead of AWS
+        #[arg(long)]
+        endpoint: Option<String>,
+        /// S3 region (default: us-east-1 or AWS_REGION)
+        #[arg(long)]
+        region: Option<String>,
+        /// S3 access key (default: AWS_ACCESS_KEY_ID)
+        #[arg(long)]
+        access_key_id: Option<String>,
+        /// S3 secret key (default: AWS_SECRET_ACCESS_KEY)
+        #[arg(long)]
+        secret_access_key: Option<String>,
+        /// WebDAV user name
+        #[arg(long)]
+        username: Option<String>,
+        /// WebDAV password
+        #[arg(long)]
+        password: Option<String>,
+    },
+    /// Remove a remote; its bundles are left in place
+    Remove {
+        /// Remote name
+        name: String,
+    },
+    /// List remotes with their sync status
+    List,
+    /// Upload sessions the remote doesn't have yet
+    Push {
+        /// Remote name (optional when only one is configured)
+        name: Option<String>,
+    },
+    /// Download and merge bundles pushed by other machines
+    Pull {
+        /// Remote name (optional when only one is configured)
+        name: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TeamCommands {
+    /// Import a teammate's sqlite export; newer exports update grown sessions
+    Import {
+        /// Snapshot made with `retrochat export --format sqlite`
+        file: String,
+        /// Teammate the sessions belong to
+        #[arg(long)]
+        owner: String,
+    },
+    /// Usage, provider mix and tool failure rates per teammate
+    Stats {
+        /// Print JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+    /// Delete every session imported for a teammate
+    Remove {
+        /// Teammate name
+        owner: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ExperimentsCommands {
+    /// Create an experiment
+    Create {
+        /// Experiment name
+        name: String,
+        /// What is being compared
+        #[arg(long, short = 'd')]
+        description: Option<String>,
+    },
+    /// Put sessions into a cohort; sessions in another cohort move over
+    Add {
+        /// Experiment name
+        name: String,
+        /// Cohort name (e.g. sonnet, gpt-5-codex)
+        cohort: String,
+        /// Session IDs
+        session_ids: Vec<String>,
+        /// Also add every session matching this filter expression
+        #[arg(long)]
+        filter: Option<String>,
+    },
+    /// Take sessions out of an experiment
+    Remove {
+        /// Experiment name
+        name: String,
+        /// Session IDs
+        #[arg(required = true)]
+        session_ids: Vec<String>,
+    },
+    /// List experiments
+    List,
+    /// Aggregate metrics per cohort
+    Show {
+        /// Experiment name
+        name: String,
+        /// Also list each cohort's sessions
+        #[arg(long)]
+        sessions: bool,
+        /// Print JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+    /// Have the LLM write a comparison of the cohorts
+    Report {
+        /// Experiment name
+        name: String,
+        /// LLM provider: google-ai (default), claude-code, gemini-cli
+        #[arg(long, short = 'P')]
+        provider: Option<String>,
+        /// Model identifier (provider-specific)
+        #[arg(long, short = 'm')]
+        model: Option<String>,
+    },
+    /// Delete an experiment; its sessions are kept
+    Delete {
+        /// Experiment name
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum EventsCommands {
+    /// Print recent events, optionally waiting for new ones
+    Tail {
+        /// Keep running and print events as they are recorded
+        #[arg(long, short = 'f')]
+        follow: bool,
+        /// Print every event after this ID instead of the last few
+        #[arg(long)]
+        since: Option<i64>,
+        /// Only print events of these kinds (e.g. session_imported, analysis_failed)
+        #[arg(long, value_delimiter = ',')]
+        kind: Vec<String>,
+        /// Number of recent events to print
+        #[arg(long, short = 'n', default_value = "20")]
+        limit: i64,
+        /// Print one JSON object per line
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum LogsCommands {
+    /// Print the end of the log, optionally waiting for new lines
+    Tail {
+        /// Number of lines to print
+        #[arg(long, short = 'n', default_value = "50")]
+        lines: usize,
+        /// Keep running and print lines as they are written
+        #[arg(long, short = 'f')]
+        follow: bool,
+        /// Only print lines containing this text (case-insensitive), e.g. a module name
+        #[arg(long)]
+        filter: Option<String>,
+        /// Only print lines at this level or more severe (error, warn, info, debug)
+        #[arg(long)]
+        level: Option<String>,
+    },
 }
 
 /// Route and execute CLI commands
@@ -291,30 +1310,113 @@ pub async fn run_command(command: Commands) -> anyhow::Result<()> {
             overwrite,
             watch,
             verbose,
+            dry_run,
+            report,
         } => {
             if watch {
-                self::watch::handle_watch_command(path, providers, verbose, false).await
+                self::watch::handle_watch_command(path, providers, verbose, true).await
+            } else if report && path.is_none() && providers.is_empty() {
+                self::import::handle_import_report_command().await
             } else {
-                self::import::handle_import_command(path, providers, overwrite).await
+                if !dry_run {
+                    self::import::resume_interrupted_imports().await;
+                }
+                let result =
+                    self::import::handle_import_command(path, providers, overwrite, dry_run).await;
+                if result.is_ok() && !dry_run {
+                    self::maintenance::run_retention_after_sync().await;
+                    self::index::index_after_sync().await;
+                    self::topics::topics_after_sync().await;
+                    self::quota::check_quotas().await;
+                }
+                if report {
+                    self::import::handle_import_report_command().await?;
+                }
+                result
             }
         }
+        Commands::Watch { command } => match command {
+            WatchCommands::InstallService { providers, print } => {
+                self::watch::handle_install_service_command(providers, print)
+            }
+            WatchCommands::UninstallService => self::watch::handle_uninstall_service_command(),
+        },
 
         // ═══════════════════════════════════════════════════
         // Session Management
         // ═══════════════════════════════════════════════════
         Commands::List {
+            filter,
             provider,
             project,
+            owner,
             page,
             page_size,
-        } => self::query::handle_sessions_command(page, page_size, provider, project).await,
+            archived,
+        } => {
+            self::query::handle_sessions_command(
+                page,
+                page_size,
+                provider,
+                project,
+                owner,
+                archived,
+                join_filter(filter),
+            )
+            .await
+        }
+
+        Commands::Archive {
+            session_ids,
+            provider,
+            project,
+            before,
+            restore,
+        } => {
+            self::maintenance::handle_archive_command(
+                session_ids,
+                provider,
+                project,
+                before,
+                restore,
+            )
+            .await
+        }
+
+        Commands::Maintenance => self::maintenance::handle_maintenance_command().await,
+
+        Commands::Show { session_id, at } => {
+            self::query::handle_session_detail_command(session_id, at).await
+        }
 
-        Commands::Show { session_id } => {
-            self::query::handle_session_detail_command(session_id).await
+        Commands::ExportSession {
+            session_id,
+            output,
+            format,
+            cwd,
+            clipboard,
+        } => {
+            self::query::handle_export_session_command(session_id, output, format, cwd, clipboard)
+                .await
         }
 
-        Commands::ExportSession { session_id, output } => {
-            self::query::handle_export_session_command(session_id, output).await
+        Commands::Share {
+            session_id,
+            output,
+            gist,
+            no_anonymize,
+            redact,
+            thinking,
+        } => {
+            self::share::handle_share_command(self::share::ShareParams {
+                session_id,
+                output,
+                gist,
+                no_anonymize,
+                redact,
+                thinking,
+            })
+            .await
         }
 
         Commands::Search {
@@ -324,6 +1426,81 @@ pub async fn run_command(command: Commands) -> anyhow::Result<()> {
             until,
         } => self::query::handle_search_command(query, limit, since, until).await,
 
+        Commands::Query {
+            sql,
+            schema,
+            format,
+            limit,
+        } => self::sql::handle_query_command(sql, schema, format, limit).await,
+
+        Commands::Index { rebuild, status } => {
+            self::index::handle_index_command(rebuild, status).await
+        }
+
+        Commands::Views { command } => match command {
+            ViewsCommands::Save { name, expression } => {
+                self::views::handle_save_command(name, expression).await
+            }
+            ViewsCommands::List => self::views::handle_list_command().await,
+            ViewsCommands::Run {
+                name,
+                page,
+                page_size,
+            } => self::views::handle_run_command(name, page, page_size).await,
+            ViewsCommands::Delete { name } => self::views::handle_delete_command(name).await,
+        },
+        Commands::Tokens { command } => match command {
+            TokensCommands::Backfill { force } => {
+                self::tokens::handle_backfill_command(force).await
+            }
+            TokensCommands::Accuracy => self::tokens::handle_accuracy_command().await,
+        },
+        Commands::Raw { command } => match command {
+            RawCommands::Status => self::raw::handle_status_command().await,
+            RawCommands::Strip { session } => self::raw::handle_strip_command(session).await,
+            RawCommands::Rehydrate { session } => {
+                self::raw::handle_rehydrate_command(session).await
+            }
+        },
+        Commands::Quota => self::quota::handle_quota_command().await,
+        Commands::History { id } => self::history::handle_history_command(id).await,
+        Commands::Repair { command } => match command {
+            RepairCommands::Roles {
+                session_id,
+                dry_run,
+                no_llm,
+                provider,
+                model,
+            } => {
+                self::repair::handle_roles_command(session_id, dry_run, no_llm, provider, model)
+                    .await
+            }
+        },
+        Commands::Session { command } => match command {
+            SessionCommands::Split {
+                session_id,
+                at_sequence,
+            } => self::session::handle_split_command(session_id, at_sequence).await,
+            SessionCommands::Merge {
+                session_id,
+                other_session_id,
+            } => self::session::handle_merge_command(session_id, other_session_id).await,
+        },
+        Commands::Topics { command } => match command {
+            TopicsCommands::List => self::topics::handle_list_command().await,
+            TopicsCommands::Show {
+                topic,
+                page,
+                page_size,
+            } => self::topics::handle_show_command(topic, page, page_size).await,
+            TopicsCommands::Refresh {
+                clusters,
+                no_llm,
+                provider,
+                model,
+            } => self::topics::handle_refresh_command(clusters, no_llm, provider, model).await,
+        },
+
         // ═══════════════════════════════════════════════════
         // AI Analysis
         // ═══════════════════════════════════════════════════
@@ -336,7 +1513,7 @@ pub async fn run_command(command: Commands) -> anyhow::Result<()> {
                 all,
                 background,
             } => {
-                self::analytics::handle_execute_command(
+                let result = self::analytics::handle_execute_command(
                     session_id,
                     provider,
                     model,
@@ -344,13 +1521,32 @@ pub async fn run_command(command: Commands) -> anyhow::Result<()> {
                     all,
                     background,
                 )
-                .await
+                .await;
+                if result.is_ok() && !background {
+                    self::quota::check_quotas().await;
+                }
+                result
             }
 
             AnalysisCommands::Show { session_id, all } => {
                 self::analytics::handle_show_command(session_id, all).await
             }
 
+            AnalysisCommands::Compare {
+                session_id,
+                other_session_id,
+                provider,
+                model,
+            } => {
+                self::analytics::handle_compare_command(
+                    session_id,
+                    other_session_id,
+                    provider,
+                    model,
+                )
+                .await
+            }
+
             AnalysisCommands::Status {
                 all,
                 watch,
@@ -360,6 +1556,22 @@ pub async fn run_command(command: Commands) -> anyhow::Result<()> {
             AnalysisCommands::Cancel { request_id, all } => {
                 self::analytics::handle_cancel_command(request_id, all).await
             }
+
+            AnalysisCommands::Rework { limit, threshold } => {
+                self::analytics::handle_rework_command(limit, threshold).await
+            }
+
+            AnalysisCommands::Workflows => self::analytics::handle_workflows_command().await,
+
+            AnalysisCommands::RecomputeScores => {
+                self::analytics::handle_recompute_scores_command().await
+            }
+
+            AnalysisCommands::Mcp { since } => self::analytics::handle_mcp_command(since).await,
+
+            AnalysisCommands::Doctor { skip_generation } => {
+                self::analytics::handle_doctor_command(skip_generation).await
+            }
         },
 
         // ═══════════════════════════════════════════════════
@@ -381,11 +1593,47 @@ pub async fn run_command(command: Commands) -> anyhow::Result<()> {
             SummarizeCommands::Status => self::summarize::handle_summarize_status().await,
         },
 
+        Commands::Titles { command } => match command {
+            TitlesCommands::Generate {
+                missing_only,
+                provider,
+                model,
+                batch_size,
+                delay_ms,
+                limit,
+            } => {
+                self::titles::handle_titles_generate(self::titles::TitlesGenerateParams {
+                    missing_only,
+                    provider,
+                    model,
+                    batch_size,
+                    delay_ms,
+                    limit,
+                })
+                .await
+            }
+        },
+
+        Commands::Standup {
+            date,
+            no_llm,
+            provider,
+            model,
+        } => self::standup::handle_standup_command(date, no_llm, provider, model).await,
+        Commands::Stats {
+            since,
+            project,
+            languages,
+            json,
+        } => self::stats::handle_stats_command(since, project, languages, json).await,
+
         // ═══════════════════════════════════════════════════
         // Export
         // ═══════════════════════════════════════════════════
         Commands::Export {
             format,
+            template_file,
+            group_by,
             since,
             until,
             provider,
@@ -396,11 +1644,30 @@ pub async fn run_command(command: Commands) -> anyhow::Result<()> {
             truncate_head,
             truncate_tail,
             output,
+            compress,
             no_tool,
+            sessions,
+            project,
+            filter,
+            view,
         } => {
-            // TODO: Handle output file if specified
-            if output.is_some() {
-                eprintln!("Warning: --output option is not yet implemented. Printing to stdout.");
+            if format == "sqlite" {
+                if compress {
+                    anyhow::bail!("--compress does not apply to the sqlite format");
+                }
+                return self::query::handle_snapshot_export_command(
+                    self::query::SnapshotExportParams {
+                        sessions,
+                        since,
+                        until,
+                        provider,
+                        project,
+                        filter,
+                        view,
+                        output,
+                    },
+                )
+                .await;
             }
 
             self::query::handle_timeline_command(self::query::TimelineParams {
@@ -409,12 +1676,20 @@ pub async fn run_command(command: Commands) -> anyhow::Result<()> {
                 provider,
                 role,
                 format,
+                template_file,
+                group_by,
                 limit,
                 reverse,
                 no_truncate,
                 truncate_head,
                 truncate_tail,
                 no_tool,
+                filter,
+                project,
+                sessions,
+                view,
+                output,
+                compress,
             })
             .await
         }
@@ -422,7 +1697,25 @@ pub async fn run_command(command: Commands) -> anyhow::Result<()> {
         // ═══════════════════════════════════════════════════
         // Setup & Configuration
         // ═══════════════════════════════════════════════════
-        Commands::Setup => self::setup::run_setup_wizard().await,
+        Commands::Setup {
+            non_interactive,
+            providers,
+            llm,
+            api_key_env,
+            skip_llm_check,
+        } => {
+            if non_interactive {
+                self::setup::run_non_interactive_setup(self::setup::NonInteractiveSetupParams {
+                    providers,
+                    llm,
+                    api_key_env,
+                    skip_llm_check,
+                })
+                .await
+            } else {
+                self::setup::run_setup_wizard().await
+            }
+        }
 
         Commands::Config { command } => match command {
             ConfigCommands::Get { key } => self::config::handle_config_get(key).await,
@@ -430,6 +1723,168 @@ pub async fn run_command(command: Commands) -> anyhow::Result<()> {
             ConfigCommands::Unset { key } => self::config::handle_config_unset(key).await,
             ConfigCommands::List => self::config::handle_config_list().await,
             ConfigCommands::Path => self::config::handle_config_path().await,
+            ConfigCommands::Validate { file } => self::config::handle_config_validate(file).await,
         },
+        Commands::Doctor {
+            skip_generation,
+            json,
+            parsers,
+            clear,
+        } => {
+            if parsers {
+                self::doctor::handle_parsers_command(json, clear).await
+            } else {
+                self::doctor::handle_doctor_command(skip_generation, json).await
+            }
+        }
+        Commands::Db { command } => match command {
+            DbCommands::Status { verbose } => self::db::handle_status_command(verbose).await,
+            DbCommands::Migrate { dry_run } => self::db::handle_migrate_command(dry_run).await,
+            DbCommands::Rollback { version, dry_run } => {
+                self::db::handle_rollback_command(version, dry_run).await
+            }
+            DbCommands::Restore { backup } => self::db::handle_restore_command(backup).await,
+            DbCommands::Encrypt { keychain } => self::db::handle_encrypt_command(keychain).await,
+            DbCommands::Decrypt => self::db::handle_decrypt_command().await,
+            DbCommands::Rekey { keychain } => self::db::handle_rekey_command(keychain).await,
+        },
+        Commands::Remote { command } => match command {
+            RemoteCommands::Add {
+                name,
+                url,
+                endpoint,
+                region,
+                access_key_id,
+                secret_access_key,
+                username,
+                password,
+            } => {
+                self::remote::handle_add_command(retrochat_core::config::RemoteSettings {
+                    name,
+                    url,
+                    endpoint,
+                    region,
+                    access_key_id,
+                    secret_access_key,
+                    username,
+                    password,
+                })
+                .await
+            }
+            RemoteCommands::Remove { name } => self::remote::handle_remove_command(name).await,
+            RemoteCommands::List => self::remote::handle_list_command().await,
+            RemoteCommands::Push { name } => self::remote::handle_push_command(name).await,
+            RemoteCommands::Pull { name } => self::remote::handle_pull_command(name).await,
+        },
+        Commands::Team { command } => match command {
+            TeamCommands::Import { file, owner } => {
+                self::team::handle_import_command(file, owner).await
+            }
+            TeamCommands::Stats { json } => self::team::handle_stats_command(json).await,
+            TeamCommands::Remove { owner } => self::team::handle_remove_command(owner).await,
+        },
+        Commands::Experiments { command } => match command {
+            ExperimentsCommands::Create { name, description } => {
+                self::experiments::handle_create_command(name, description).await
+            }
+            ExperimentsCommands::Add {
+                name,
+                cohort,
+                session_ids,
+                filter,
+            } => self::experiments::handle_add_command(name, cohort, session_ids, filter).await,
+            ExperimentsCommands::Remove { name, session_ids } => {
+                self::experiments::handle_remove_command(name, session_ids).await
+            }
+            ExperimentsCommands::List => self::experiments::handle_list_command().await,
+            ExperimentsCommands::Show {
+                name,
+                sessions,
+                json,
+            } => self::experiments::handle_show_command(name, sessions, json).await,
+            ExperimentsCommands::Report {
+                name,
+                provider,
+                model,
+            } => self::experiments::handle_report_command(name, provider, model).await,
+            ExperimentsCommands::Delete { name } => {
+                self::experiments::handle_delete_command(name).await
+            }
+        },
+        Commands::Serve { host, port } => self::serve::handle_serve_command(host, port).await,
+        Commands::Events { command } => match command {
+            EventsCommands::Tail {
+                follow,
+                since,
+                kind,
+                limit,
+                json,
+            } => self::events::handle_tail_command(follow, since, kind, limit, json).await,
+        },
+        Commands::Logs { command } => match command {
+            LogsCommands::Tail {
+                lines,
+                follow,
+                filter,
+                level,
+            } => self::logs::handle_tail_command(lines, follow, filter, level).await,
+        },
+    }
+}
+
+/// Join filter words given as separate arguments into one expression
+fn join_filter(words: Vec<String>) -> Option<String> {
+    (!words.is_empty()).then(|| words.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_list_filter(args: &[&str]) -> Option<String> {
+        let cli = Cli::try_parse_from(["retrochat", "list"].iter().chain(args)).unwrap();
+        match cli.command {
+            Some(Commands::List { filter, .. }) => join_filter(filter),
+            _ => panic!("Expected List command"),
+        }
+    }
+
+    #[test]
+    fn test_list_accepts_negated_filter_terms() {
+        assert_eq!(
+            parse_list_filter(&["-project:foo"]).as_deref(),
+            Some("-project:foo")
+        );
+        assert_eq!(
+            parse_list_filter(&["provider:claude", "-tool:Bash(failed)"]).as_deref(),
+            Some("provider:claude -tool:Bash(failed)")
+        );
+    }
+
+    #[test]
+    fn test_list_still_parses_flags() {
+        let cli = Cli::try_parse_from([
+            "retrochat",
+            "list",
+            "--page",
+            "2",
+            "-s",
+            "5",
+            "-project:foo",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::List {
+                filter,
+                page,
+                page_size,
+                ..
+            }) => {
+                assert_eq!(page, Some(2));
+                assert_eq!(page_size, Some(5));
+                assert_eq!(filter, vec!["-project:foo".to_string()]);
+            }
+            _ => panic!("Expected List command"),
+        }
     }
 }
@@ -1,15 +1,34 @@
 use anyhow::Result;
+use chrono::{DateTime, Utc};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use indicatif::{ProgressBar, ProgressStyle};
 use retrochat_core::database::{
-    ChatSessionRepository, DatabaseManager, MessageRepository, ToolOperationRepository,
+    ChatSessionRepository, DatabaseManager, MessageRepository, SavedViewRepository,
+    SessionTagRepository, ToolOperationRepository,
 };
-use retrochat_core::models::Message;
+use retrochat_core::models::{ChatSession, Message};
 use retrochat_core::services::analytics::build_session_transcript;
+use retrochat_core::services::provider_export::{claude_project_dir_name, export_claude_jsonl};
 use retrochat_core::services::{
-    QueryService, SearchRequest, SessionDetailRequest, SessionsQueryRequest,
+    export_sqlite_snapshot, render_markdown_transcript, render_template_export, QueryService,
+    SearchRequest, SessionDetailRequest, SessionSelector, SessionsQueryRequest,
 };
+use retrochat_core::utils::clipboard;
+use retrochat_core::utils::deep_link::DeepLink;
+use retrochat_core::utils::filter_expr::FilterExpr;
+use retrochat_core::utils::locale::Language;
 use retrochat_core::utils::time_parser;
+use std::collections::{HashMap, HashSet};
+use std::io::{BufWriter, Write};
 use std::sync::Arc;
 
+/// Related sessions listed at the bottom of `retrochat show`
+const RELATED_SESSION_LIMIT: usize = 5;
+
+/// Messages shown on either side of the target of `retrochat show --at`
+const JUMP_CONTEXT_MESSAGES: usize = 3;
+
 /// Parameters for timeline command to avoid clippy::too_many_arguments
 pub struct TimelineParams {
     pub since: Option<String>,
@@ -17,12 +36,32 @@ pub struct TimelineParams {
     pub provider: Option<String>,
     pub role: Option<String>,
     pub format: String,
+    pub template_file: Option<String>,
+    pub group_by: Option<String>,
     pub limit: Option<i32>,
     pub reverse: bool,
     pub no_truncate: bool,
     pub truncate_head: usize,
     pub truncate_tail: usize,
     pub no_tool: bool,
+    pub filter: Option<String>,
+    pub project: Option<String>,
+    pub sessions: Vec<String>,
+    pub view: Option<String>,
+    pub output: Option<String>,
+    pub compress: bool,
+}
+
+/// Parameters for exporting a SQLite snapshot
+pub struct SnapshotExportParams {
+    pub sessions: Vec<String>,
+    pub since: Option<String>,
+    pub until: Option<String>,
+    pub provider: Option<String>,
+    pub project: Option<String>,
+    pub filter: Option<String>,
+    pub view: Option<String>,
+    pub output: Option<String>,
 }
 
 pub async fn handle_sessions_command(
@@ -30,6 +69,9 @@ pub async fn handle_sessions_command(
     page_size: Option<i32>,
     provider: Option<String>,
     project: Option<String>,
+    owner: Option<String>,
+    include_archived: bool,
+    filter: Option<String>,
 ) -> Result<()> {
     let db_path = retrochat_core::database::config::get_default_db_path()?;
     let db_manager = DatabaseManager::new(&db_path).await?;
@@ -46,7 +88,15 @@ pub async fn handle_sessions_command(
             date_range: None,
             min_messages: None,
             max_messages: None,
+            include_archived,
+            expression: filter,
+            topic: None,
+            owner,
+            providers: None,
+            projects: None,
+            topics: None,
         }),
+        after: None,
     };
 
     let response = query_service.query_sessions(request).await?;
@@ -60,14 +110,24 @@ pub async fn handle_sessions_command(
 
     for session in response.sessions {
         println!("Session: {}", session.session_id);
+        if let Some(title) = &session.title {
+            println!("  Title: {title}");
+        }
         println!("  Provider: {}", session.provider);
+        if let Some(owner) = &session.owner {
+            println!("  Owner: {owner}");
+        }
         println!(
             "  Project: {}",
             session.project.unwrap_or_else(|| "None".to_string())
         );
         println!("  Messages: {}", session.message_count);
         println!("  Tokens: {}", session.total_tokens.unwrap_or(0));
+        println!("  Tool calls: {}", session.tool_op_count);
         println!("  Start: {}", session.start_time);
+        if let Some(seconds) = session.duration_seconds {
+            println!("  Duration: {}m {}s", seconds / 60, seconds % 60);
+        }
         println!("  Preview: {}", session.first_message_preview);
         println!();
     }
@@ -75,15 +135,23 @@ pub async fn handle_sessions_command(
     Ok(())
 }
 
-pub async fn handle_session_detail_command(session_id: String) -> Result<()> {
+pub async fn handle_session_detail_command(session_id: String, at: Option<u32>) -> Result<()> {
+    let (session_id, at) = if DeepLink::is_link(&session_id) {
+        let link: DeepLink = session_id.parse()?;
+        (link.session_id.to_string(), at.or(link.sequence))
+    } else {
+        (session_id, at)
+    };
+
     let db_path = retrochat_core::database::config::get_default_db_path()?;
-    let db_manager = DatabaseManager::new(&db_path).await?;
-    let query_service = QueryService::with_database(Arc::new(db_manager));
+    let db_manager = Arc::new(DatabaseManager::new(&db_path).await?);
+    let query_service = QueryService::with_database(db_manager.clone());
 
+    // Jumping to a message needs the whole session to find it
     let request = SessionDetailRequest {
         session_id,
         include_content: Some(true),
-        message_limit: Some(50),
+        message_limit: if at.is_some() { None } else { Some(50) },
         message_offset: Some(0),
     };
     let response = query_service.get_session_detail(request).await?;
@@ -109,15 +177,71 @@ pub async fn handle_session_detail_command(session_id: String) -> Result<()> {
             .map(|t| t.to_rfc3339())
             .unwrap_or_else(|| "N/A".to_string())
     );
+    let tags = SessionTagRepository::new(&db_manager)
+        .get_by_session(&response.session.id)
+        .await?;
+    if !tags.is_empty() {
+        println!("  Tags: {}", tags.join(", "));
+    }
     println!();
 
-    println!("Messages:");
-    for (i, message) in response.messages.iter().enumerate() {
-        println!("  {}: [{}] {}", i + 1, message.role, message.content);
-        if i >= 9 {
-            // Show only first 10 messages
-            println!("  ... and {} more messages", response.messages.len() - 10);
-            break;
+    if let Some(sequence) = at {
+        let target = response
+            .messages
+            .iter()
+            .position(|message| message.sequence_number == sequence)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Session {} has no message with sequence number {sequence}",
+                    response.session.id
+                )
+            })?;
+        let start = target.saturating_sub(JUMP_CONTEXT_MESSAGES);
+        let end = (target + JUMP_CONTEXT_MESSAGES + 1).min(response.messages.len());
+
+        println!("Messages {}-{}:", start + 1, end);
+        if start > 0 {
+            println!("  ... {start} earlier messages");
+        }
+        for (i, message) in response.messages[start..end].iter().enumerate() {
+            let marker = if start + i == target { ">" } else { " " };
+            println!(
+                "{marker} {}: [{}] {}",
+                message.sequence_number, message.role, message.content
+            );
+        }
+        if end < response.messages.len() {
+            println!("  ... {} later messages", response.messages.len() - end);
+        }
+    } else {
+        println!("Messages:");
+        for (i, message) in response.messages.iter().enumerate() {
+            println!("  {}: [{}] {}", i + 1, message.role, message.content);
+            if i >= 9 {
+                // Show only first 10 messages
+                println!(
+                    "  ... and {} more messages",
+                    response.total_message_count as usize - 10
+                );
+                break;
+            }
+        }
+    }
+
+    let related = query_service
+        .get_related_sessions(&response.session.id.to_string(), RELATED_SESSION_LIMIT)
+        .await?;
+    if !related.is_empty() {
+        println!();
+        println!("Related sessions:");
+        for session in related {
+            println!(
+                "  {} [{}] {} ({:.0}% similar)",
+                session.session_id,
+                session.project.as_deref().unwrap_or("No project"),
+                session.title.as_deref().unwrap_or("Untitled"),
+                session.score * 100.0
+            );
         }
     }
 
@@ -135,29 +259,12 @@ pub async fn handle_search_command(
     let query_service = QueryService::with_database(Arc::new(db_manager));
 
     // Parse time specifications if provided
-    let date_range = if since.is_some() || until.is_some() {
-        let start_date = if let Some(since_str) = since {
-            time_parser::parse_time_spec(&since_str)?.to_rfc3339()
-        } else {
-            // Use a very old date as default start
-            chrono::DateTime::parse_from_rfc3339("1970-01-01T00:00:00Z")?
-                .with_timezone(&chrono::Utc)
-                .to_rfc3339()
-        };
-
-        let end_date = if let Some(until_str) = until {
-            time_parser::parse_time_spec(&until_str)?.to_rfc3339()
-        } else {
-            // Use now as default end
-            chrono::Utc::now().to_rfc3339()
-        };
-
-        Some(retrochat_core::services::DateRange {
-            start_date,
-            end_date,
-        })
-    } else {
-        None
+    let date_range = match time_parser::parse_time_range(since.as_deref(), until.as_deref())? {
+        (None, None) => None,
+        (from, to) => Some(retrochat_core::services::DateRange {
+            start_date: from.unwrap_or(chrono::DateTime::UNIX_EPOCH).to_rfc3339(),
+            end_date: to.unwrap_or_else(chrono::Utc::now).to_rfc3339(),
+        }),
     };
 
     let request = SearchRequest {
@@ -193,6 +300,7 @@ pub async fn handle_search_command(
             result.message_role, result.timestamp
         );
         println!("  Content: {}", result.content_snippet);
+        println!("  Link: {}", result.link);
         println!();
     }
 
@@ -200,80 +308,415 @@ pub async fn handle_search_command(
 }
 
 pub async fn handle_timeline_command(params: TimelineParams) -> Result<()> {
-    // Parse time specifications
-    let from = if let Some(since_str) = params.since {
-        Some(time_parser::parse_time_spec(&since_str)?)
-    } else {
-        None
+    let template_file = match (params.format.as_str(), params.template_file) {
+        ("template", Some(path)) => Some(path),
+        ("template", None) => anyhow::bail!("--format template needs --template-file"),
+        (_, Some(_)) => anyhow::bail!("--template-file only applies to --format template"),
+        (_, None) => None,
     };
-
-    let to = if let Some(until_str) = params.until {
-        Some(time_parser::parse_time_spec(&until_str)?)
-    } else {
-        None
+    let grouping = params
+        .group_by
+        .as_deref()
+        .map(TimelineGrouping::parse)
+        .transpose()?;
+    if grouping.is_some() && matches!(params.format.as_str(), "jsonl" | "template") {
+        anyhow::bail!("--group-by only applies to the compact format");
+    }
+    let compact = CompactStyle {
+        truncate: !params.no_truncate,
+        head_chars: params.truncate_head,
+        tail_chars: params.truncate_tail,
     };
 
+    // Parse time specifications
+    let (from, to) =
+        time_parser::parse_time_range(params.since.as_deref(), params.until.as_deref())?;
+
     // Get database and repository
     let db_path = retrochat_core::database::config::get_default_db_path()?;
     let db_manager = DatabaseManager::new(&db_path).await?;
     let message_repo = retrochat_core::database::message_repo::MessageRepository::new(&db_manager);
 
-    // Query messages
-    let messages = message_repo
-        .get_by_time_range(
-            from,
-            to,
-            params.provider.as_deref(),
-            params.role.as_deref(),
-            params.limit.map(|l| l as i64),
-            params.reverse,
+    // Sessions allowed by the session selection and filter expression, if any
+    let selector = SessionSelector {
+        session_ids: parse_session_ids(&params.sessions)?,
+        provider: None,
+        project: params.project,
+        before: None,
+    };
+    let expression = resolve_filter(
+        &db_manager,
+        params.view.as_deref(),
+        params.filter.as_deref(),
+    )
+    .await?;
+    let allowed_sessions = if selector.is_empty() && expression.is_none() {
+        None
+    } else {
+        let sessions = match &expression {
+            Some(expression) => {
+                QueryService::with_database(Arc::new(db_manager.clone()))
+                    .filter_sessions(expression)
+                    .await?
+            }
+            None => ChatSessionRepository::new(&db_manager).get_all().await?,
+        };
+        Some(
+            sessions
+                .into_iter()
+                .filter(|session| selector.matches(session))
+                .map(|session| session.id)
+                .collect::<HashSet<_>>(),
         )
-        .await?;
+    };
 
-    // Format output
-    match params.format.as_str() {
-        "jsonl" => format_jsonl(&messages, params.no_tool),
-        _ => format_compact(
-            &messages,
-            !params.no_truncate,
-            params.truncate_head,
-            params.truncate_tail,
-            params.no_tool,
-        ),
-    }
+    let mut sink = ExportSink::open(params.output.as_deref(), params.compress)?;
+    let written = if template_file.is_some() || grouping.is_some() {
+        // Templates and groups need every message before the first line
+        let mut messages = message_repo
+            .get_by_time_range(
+                from,
+                to,
+                params.provider.as_deref(),
+                params.role.as_deref(),
+                params
+                    .limit
+                    .filter(|_| allowed_sessions.is_none())
+                    .map(|l| l as i64),
+                params.reverse,
+            )
+            .await?;
+        if let Some(allowed) = &allowed_sessions {
+            messages.retain(|msg| allowed.contains(&msg.session_id));
+            if let Some(limit) = params.limit {
+                messages.truncate(limit.max(0) as usize);
+            }
+        }
+        if params.no_tool {
+            messages.retain(|msg| !is_tool_message(&msg.content));
+        }
+
+        if let Some(template_file) = template_file {
+            let rendered = render_template_export(
+                &db_manager,
+                std::path::Path::new(&template_file),
+                &messages,
+            )
+            .await?;
+            sink.write_all(rendered.as_bytes())?;
+        } else if let Some(grouping) = grouping {
+            let session_repo = ChatSessionRepository::new(&db_manager);
+            let session_ids: HashSet<_> = messages.iter().map(|msg| msg.session_id).collect();
+            let mut sessions = HashMap::new();
+            for session_id in session_ids {
+                if let Some(session) = session_repo.get_by_id(&session_id).await? {
+                    sessions.insert(session_id, session);
+                }
+            }
 
+            for (index, group) in group_timeline(&messages, grouping, &sessions)
+                .iter()
+                .enumerate()
+            {
+                if index > 0 {
+                    writeln!(sink)?;
+                }
+                writeln!(sink, "{}", group.header(grouping))?;
+                for msg in &group.messages {
+                    write_compact_line(&mut sink, msg, &compact)?;
+                }
+            }
+        }
+        messages.len() as u64
+    } else {
+        // Everything else is written as it is read, so memory stays flat
+        let progress = match sink.path() {
+            Some(_) => {
+                let mut total = message_repo
+                    .count_by_time_range(
+                        from,
+                        to,
+                        params.provider.as_deref(),
+                        params.role.as_deref(),
+                    )
+                    .await? as u64;
+                if let Some(limit) = params.limit {
+                    total = total.min(limit.max(0) as u64);
+                }
+                let pb = ProgressBar::new(total);
+                pb.set_style(
+                    ProgressStyle::default_bar()
+                        .template(
+                            "{spinner:.green} [{bar:40.cyan/blue}] {pos}/{len} messages ({eta})",
+                        )?
+                        .progress_chars("━━╸"),
+                );
+                pb
+            }
+            None => ProgressBar::hidden(),
+        };
+
+        let limit = params.limit.map(|limit| limit.max(0) as u64);
+        let jsonl = params.format == "jsonl";
+        let mut taken = 0u64;
+        let mut written = 0u64;
+        if limit != Some(0) {
+            message_repo
+                .for_each_in_time_range(
+                    from,
+                    to,
+                    params.provider.as_deref(),
+                    params.role.as_deref(),
+                    params.reverse,
+                    |msg| {
+                        if allowed_sessions
+                            .as_ref()
+                            .is_some_and(|allowed| !allowed.contains(&msg.session_id))
+                        {
+                            return Ok(true);
+                        }
+                        taken += 1;
+                        progress.inc(1);
+
+                        if !(params.no_tool && is_tool_message(&msg.content)) {
+                            let result = if jsonl {
+                                write_jsonl_line(&mut sink, &msg)
+                            } else {
+                                write_compact_line(&mut sink, &msg, &compact)
+                            };
+                            if !keep_writing(result)? {
+                                return Ok(false);
+                            }
+                            written += 1;
+                        }
+                        Ok(limit.is_none_or(|limit| taken < limit))
+                    },
+                )
+                .await?;
+        }
+        progress.finish_and_clear();
+        written
+    };
+
+    let path = sink.path().map(str::to_string);
+    keep_writing(sink.finish())?;
+    if let Some(path) = path {
+        eprintln!("Exported {written} messages to {path}");
+    }
     Ok(())
 }
 
-fn format_compact(
-    messages: &[Message],
+/// How the compact format shortens long messages
+struct CompactStyle {
     truncate: bool,
     head_chars: usize,
     tail_chars: usize,
-    no_tool: bool,
-) {
-    for msg in messages {
-        // Filter out tool messages if no_tool is enabled
-        if no_tool && is_tool_message(&msg.content) {
-            continue;
-        }
+}
+
+/// Where export output goes: stdout or a file, optionally gzipped
+enum ExportSink {
+    Plain(BufWriter<Box<dyn Write>>, Option<String>),
+    Gzip(GzEncoder<BufWriter<Box<dyn Write>>>, Option<String>),
+}
 
-        let content = if truncate {
-            truncate_message(&msg.content, head_chars, tail_chars)
+impl ExportSink {
+    /// `None` and `-` mean stdout
+    fn open(output: Option<&str>, compress: bool) -> Result<Self> {
+        let path = output.filter(|path| *path != "-").map(str::to_string);
+        let inner: Box<dyn Write> = match &path {
+            Some(path) => Box::new(
+                std::fs::File::create(path)
+                    .map_err(|e| anyhow::anyhow!("Failed to create {path}: {e}"))?,
+            ),
+            None => Box::new(std::io::stdout()),
+        };
+        let writer = BufWriter::new(inner);
+        Ok(if compress {
+            Self::Gzip(GzEncoder::new(writer, Compression::default()), path)
         } else {
-            msg.content.clone()
+            Self::Plain(writer, path)
+        })
+    }
+
+    /// The file written to, if not stdout
+    fn path(&self) -> Option<&str> {
+        match self {
+            Self::Plain(_, path) | Self::Gzip(_, path) => path.as_deref(),
+        }
+    }
+
+    /// Flush everything, including the gzip trailer
+    fn finish(self) -> std::io::Result<()> {
+        match self {
+            Self::Plain(mut writer, _) => writer.flush(),
+            Self::Gzip(encoder, _) => encoder.finish()?.flush(),
+        }
+    }
+}
+
+impl Write for ExportSink {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Plain(writer, _) => writer.write(buf),
+            Self::Gzip(encoder, _) => encoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Self::Plain(writer, _) => writer.flush(),
+            Self::Gzip(encoder, _) => encoder.flush(),
+        }
+    }
+}
+
+/// Whether to go on after a write; a closed pipe (e.g. `| head`) just stops
+fn keep_writing(result: std::io::Result<()>) -> Result<bool> {
+    match result {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => Ok(false),
+        Err(e) => Err(anyhow::anyhow!("Failed to write export: {e}")),
+    }
+}
+
+/// How `--group-by` splits the compact timeline
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TimelineGrouping {
+    Day,
+    Session,
+    Project,
+}
+
+impl TimelineGrouping {
+    fn parse(value: &str) -> Result<Self> {
+        match value {
+            "day" => Ok(Self::Day),
+            "session" => Ok(Self::Session),
+            "project" => Ok(Self::Project),
+            other => {
+                anyhow::bail!("Unknown grouping: {other}. Valid options: day, session, project")
+            }
+        }
+    }
+}
+
+/// Timeline messages shown under one header
+struct TimelineGroup {
+    title: String,
+    messages: Vec<Message>,
+}
+
+impl TimelineGroup {
+    /// Title with message and session counts and the time spent
+    ///
+    /// The time is the span of each session's messages in the group added up,
+    /// so idle time between sessions does not count.
+    fn header(&self, grouping: TimelineGrouping) -> String {
+        let mut spans: HashMap<uuid::Uuid, (DateTime<Utc>, DateTime<Utc>)> = HashMap::new();
+        for msg in &self.messages {
+            spans
+                .entry(msg.session_id)
+                .and_modify(|(first, last)| {
+                    *first = (*first).min(msg.timestamp);
+                    *last = (*last).max(msg.timestamp);
+                })
+                .or_insert((msg.timestamp, msg.timestamp));
+        }
+        let seconds: i64 = spans
+            .values()
+            .map(|(first, last)| (*last - *first).num_seconds())
+            .sum();
+
+        let mut header = format!("== {} · {} messages", self.title, self.messages.len());
+        if grouping != TimelineGrouping::Session {
+            header.push_str(&format!(" · {} sessions", spans.len()));
+        }
+        header.push_str(&format!(" · {} ==", format_span(seconds)));
+        header
+    }
+}
+
+/// Split messages into groups, in the order each group first appears
+fn group_timeline(
+    messages: &[Message],
+    grouping: TimelineGrouping,
+    sessions: &HashMap<uuid::Uuid, ChatSession>,
+) -> Vec<TimelineGroup> {
+    let mut groups: Vec<TimelineGroup> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+
+    for msg in messages {
+        let session = sessions.get(&msg.session_id);
+        let (key, title) = match grouping {
+            TimelineGrouping::Day => {
+                let day = Language::current().format_date(msg.timestamp.date_naive());
+                (day.clone(), day)
+            }
+            TimelineGrouping::Session => {
+                let id = msg.session_id.to_string();
+                let title = match session {
+                    Some(session) => format!(
+                        "{} ({}, {})",
+                        session.title.as_deref().unwrap_or(&id[..8]),
+                        session.provider,
+                        session.project_name.as_deref().unwrap_or("no project")
+                    ),
+                    None => id[..8].to_string(),
+                };
+                (id, title)
+            }
+            TimelineGrouping::Project => {
+                let project = session
+                    .and_then(|session| session.project_name.clone())
+                    .unwrap_or_else(|| "(no project)".to_string());
+                (project.clone(), project)
+            }
         };
 
-        let preview = content.replace('\n', " ");
-        println!(
-            "{} [{:9}] {}",
-            msg.timestamp.format("%m-%d %H:%M"),
-            msg.role.to_string(),
-            preview
-        );
+        let position = *positions.entry(key).or_insert_with(|| {
+            groups.push(TimelineGroup {
+                title,
+                messages: Vec::new(),
+            });
+            groups.len() - 1
+        });
+        groups[position].messages.push(msg.clone());
+    }
+
+    groups
+}
+
+/// Compact duration such as `2h 05m`, `45m` or `<1m`
+fn format_span(seconds: i64) -> String {
+    let minutes = seconds / 60;
+    match (minutes / 60, minutes % 60) {
+        (0, 0) => "<1m".to_string(),
+        (0, minutes) => format!("{minutes}m"),
+        (hours, minutes) => format!("{hours}h {minutes:02}m"),
     }
 }
 
+fn write_compact_line(
+    out: &mut impl Write,
+    msg: &Message,
+    style: &CompactStyle,
+) -> std::io::Result<()> {
+    let content = if style.truncate {
+        truncate_message(&msg.content, style.head_chars, style.tail_chars)
+    } else {
+        msg.content.clone()
+    };
+
+    let preview = content.replace('\n', " ");
+    writeln!(
+        out,
+        "{} [{:9}] {}",
+        Language::current().format_month_day_time(&msg.timestamp),
+        msg.role.to_string(),
+        preview
+    )
+}
+
 fn truncate_message(content: &str, head_chars: usize, tail_chars: usize) -> String {
     let chars: Vec<char> = content.chars().collect();
     let total_chars = chars.len();
@@ -290,16 +733,10 @@ fn truncate_message(content: &str, head_chars: usize, tail_chars: usize) -> Stri
     format!("{head} [...] {tail}")
 }
 
-fn format_jsonl(messages: &[Message], no_tool: bool) {
-    for msg in messages {
-        // Filter out tool messages if no_tool is enabled
-        if no_tool && is_tool_message(&msg.content) {
-            continue;
-        }
-
-        if let Ok(json) = serde_json::to_string(msg) {
-            println!("{json}");
-        }
+fn write_jsonl_line(out: &mut impl Write, msg: &Message) -> std::io::Result<()> {
+    match serde_json::to_string(msg) {
+        Ok(json) => writeln!(out, "{json}"),
+        Err(_) => Ok(()),
     }
 }
 
@@ -315,7 +752,16 @@ fn is_tool_message(content: &str) -> bool {
 pub async fn handle_export_session_command(
     session_id: String,
     output: Option<String>,
+    format: String,
+    cwd: Option<String>,
+    clipboard: bool,
 ) -> Result<()> {
+    if !matches!(format.as_str(), "transcript" | "markdown" | "claude-jsonl") {
+        anyhow::bail!(
+            "Unknown export format: {format}. Valid options: transcript, markdown, claude-jsonl"
+        );
+    }
+
     let db_path = retrochat_core::database::config::get_default_db_path()?;
     let db_manager = DatabaseManager::new(&db_path).await?;
 
@@ -338,20 +784,157 @@ pub async fn handle_export_session_command(
     let messages = message_repo.get_by_session(&session_uuid).await?;
     let tool_operations = tool_op_repo.get_by_session(&session_uuid).await?;
 
-    // Build the session transcript
-    let transcript = build_session_transcript(&messages, &tool_operations, &session)?;
+    let mut resume_cwd = None;
+    let content = match format.as_str() {
+        "claude-jsonl" => {
+            let cwd = match cwd {
+                Some(cwd) => cwd,
+                None => std::env::current_dir()?.to_string_lossy().to_string(),
+            };
+            let jsonl = export_claude_jsonl(&session, &messages, &tool_operations, &cwd)?;
+            resume_cwd = Some(cwd);
+            jsonl
+        }
+        "markdown" => render_markdown_transcript(&session, &messages, &tool_operations),
+        _ => build_session_transcript(&messages, &tool_operations, &session)?,
+    };
 
-    // Output to file or stdout
-    if let Some(output_path) = output {
-        std::fs::write(&output_path, &transcript)?;
+    if clipboard {
+        clipboard::copy(&content)?;
+        println!("Session copied to the clipboard");
+    } else if let Some(output_path) = output {
+        std::fs::write(&output_path, &content)?;
         println!("Session exported to: {output_path}");
+        if let Some(cwd) = resume_cwd {
+            println!(
+                "To resume it, copy it to ~/.claude/projects/{}/{}.jsonl and run `claude --resume {}` from {cwd}",
+                claude_project_dir_name(&cwd),
+                session.id,
+                session.id
+            );
+        }
+    } else if content.ends_with('\n') {
+        print!("{content}");
     } else {
-        println!("{transcript}");
+        println!("{content}");
+    }
+
+    Ok(())
+}
+
+/// Export whole sessions to a standalone SQLite database
+pub async fn handle_snapshot_export_command(params: SnapshotExportParams) -> Result<()> {
+    let output = params
+        .output
+        .ok_or_else(|| anyhow::anyhow!("--output is required for the sqlite format"))?;
+
+    let session_ids = parse_session_ids(&params.sessions)?;
+    let (since, until) =
+        time_parser::parse_time_range(params.since.as_deref(), params.until.as_deref())?;
+
+    let selector = SessionSelector {
+        session_ids,
+        provider: params.provider,
+        project: params.project,
+        before: None,
+    };
+
+    let db_path = retrochat_core::database::config::get_default_db_path()?;
+    let db_manager = DatabaseManager::new(&db_path).await?;
+
+    let expression = resolve_filter(
+        &db_manager,
+        params.view.as_deref(),
+        params.filter.as_deref(),
+    )
+    .await?;
+    if selector.is_empty() && since.is_none() && until.is_none() && expression.is_none() {
+        anyhow::bail!(
+            "Select sessions with --session, --project, --view, --filter, --provider, --since or --until"
+        );
     }
 
+    let allowed_sessions = match &expression {
+        Some(expression) => Some(
+            QueryService::with_database(Arc::new(db_manager.clone()))
+                .filter_sessions(expression)
+                .await?
+                .into_iter()
+                .map(|session| session.id)
+                .collect::<HashSet<_>>(),
+        ),
+        None => None,
+    };
+
+    let selected: Vec<_> = ChatSessionRepository::new(&db_manager)
+        .get_all()
+        .await?
+        .into_iter()
+        .filter(|session| selector.matches(session))
+        .filter(|session| {
+            allowed_sessions
+                .as_ref()
+                .is_none_or(|allowed| allowed.contains(&session.id))
+        })
+        .filter(|session| since.is_none_or(|since| session.start_time >= since))
+        .filter(|session| until.is_none_or(|until| session.start_time <= until))
+        .map(|session| session.id)
+        .collect();
+    if selected.is_empty() {
+        anyhow::bail!("No sessions match the selection");
+    }
+
+    let report =
+        export_sqlite_snapshot(&db_manager, &selected, std::path::Path::new(&output)).await?;
+
+    println!("Snapshot written to: {output}");
+    println!("  Sessions: {}", report.sessions);
+    println!("  Messages: {}", report.messages);
+    println!("  Tool operations: {}", report.tool_operations);
+    println!("  Analytics results: {}", report.analytics);
+    println!("  Summaries: {}", report.summaries);
+    println!("Open it with: RETROCHAT_DB={output} retrochat list");
     Ok(())
 }
 
+fn parse_session_ids(ids: &[String]) -> Result<Vec<uuid::Uuid>> {
+    ids.iter()
+        .map(|id| {
+            uuid::Uuid::parse_str(id.trim())
+                .map_err(|e| anyhow::anyhow!("Invalid session ID format: {id}: {e}"))
+        })
+        .collect()
+}
+
+/// The --filter expression, narrowed further by the saved view named by --view
+async fn resolve_filter(
+    db_manager: &DatabaseManager,
+    view: Option<&str>,
+    filter: Option<&str>,
+) -> Result<Option<FilterExpr>> {
+    let view_expression = match view {
+        Some(name) => Some(
+            SavedViewRepository::new(db_manager)
+                .get_by_name(name)
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("No saved view named '{name}'"))?
+                .expression,
+        ),
+        None => None,
+    };
+
+    // Terms of an expression all have to match, so joining them intersects
+    let combined = [view_expression.as_deref(), filter]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if combined.trim().is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(FilterExpr::parse(&combined)?))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -383,4 +966,113 @@ mod tests {
         assert!(!is_tool_message("Here is [Tool Use: something]"));
         assert!(!is_tool_message("Text before [Tool Result]"));
     }
+
+    #[test]
+    fn test_group_timeline_by_day_and_project() {
+        use chrono::TimeZone;
+        use retrochat_core::models::{MessageRole, Provider};
+
+        let mut first = ChatSession::new(
+            Provider::ClaudeCode,
+            "/tmp/a.jsonl".to_string(),
+            "a".to_string(),
+            Utc::now(),
+        );
+        first.project_name = Some("retrochat".to_string());
+        let second = ChatSession::new(
+            Provider::Codex,
+            "/tmp/b.jsonl".to_string(),
+            "b".to_string(),
+            Utc::now(),
+        );
+        let at = |day: u32, hour: u32, minute: u32| {
+            Utc.with_ymd_and_hms(2024, 10, day, hour, minute, 0)
+                .unwrap()
+        };
+        let messages = vec![
+            Message::new(first.id, MessageRole::User, "a".into(), at(1, 9, 0), 1),
+            Message::new(second.id, MessageRole::User, "b".into(), at(1, 9, 30), 1),
+            Message::new(
+                first.id,
+                MessageRole::Assistant,
+                "c".into(),
+                at(1, 11, 5),
+                2,
+            ),
+            Message::new(
+                second.id,
+                MessageRole::Assistant,
+                "d".into(),
+                at(2, 8, 0),
+                2,
+            ),
+        ];
+        let sessions = HashMap::from([(first.id, first.clone()), (second.id, second.clone())]);
+
+        let days = group_timeline(&messages, TimelineGrouping::Day, &sessions);
+        assert_eq!(days.len(), 2);
+        assert_eq!(
+            days[0].header(TimelineGrouping::Day),
+            "== 2024-10-01 · 3 messages · 2 sessions · 2h 05m =="
+        );
+        assert_eq!(
+            days[1].header(TimelineGrouping::Day),
+            "== 2024-10-02 · 1 messages · 1 sessions · <1m =="
+        );
+
+        let projects = group_timeline(&messages, TimelineGrouping::Project, &sessions);
+        let titles: Vec<_> = projects.iter().map(|group| group.title.as_str()).collect();
+        assert_eq!(titles, ["retrochat", "(no project)"]);
+        assert_eq!(projects[1].messages.len(), 2);
+
+        assert!(TimelineGrouping::parse("week").is_err());
+    }
+
+    #[test]
+    fn test_export_sink_gzip_round_trip() {
+        use std::io::Read;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("export.jsonl.gz");
+        let path_str = path.to_str().unwrap();
+
+        let mut sink = ExportSink::open(Some(path_str), true).unwrap();
+        assert_eq!(sink.path(), Some(path_str));
+        writeln!(sink, "first").unwrap();
+        writeln!(sink, "second").unwrap();
+        sink.finish().unwrap();
+
+        let mut decoded = String::new();
+        flate2::read::GzDecoder::new(std::fs::File::open(&path).unwrap())
+            .read_to_string(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, "first\nsecond\n");
+
+        assert!(ExportSink::open(Some("-"), false).unwrap().path().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_filter_combines_view_and_filter() {
+        let db_manager = DatabaseManager::open_in_memory().await.unwrap();
+        SavedViewRepository::new(&db_manager)
+            .save("claude", "provider:claude")
+            .await
+            .unwrap();
+
+        assert!(resolve_filter(&db_manager, None, None)
+            .await
+            .unwrap()
+            .is_none());
+        let combined = resolve_filter(&db_manager, Some("claude"), Some("project:retrochat"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            combined,
+            FilterExpr::parse("provider:claude project:retrochat").unwrap()
+        );
+        assert!(resolve_filter(&db_manager, Some("missing"), None)
+            .await
+            .is_err());
+    }
 }
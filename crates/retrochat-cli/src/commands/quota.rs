@@ -0,0 +1,101 @@
+use anyhow::Result;
+use chrono::Utc;
+
+use retrochat_core::config::Config;
+use retrochat_core::database::DatabaseManager;
+use retrochat_core::services::{notify_desktop, QuotaService};
+use retrochat_core::utils::locale::Language;
+
+/// Events shown by `retrochat quota`
+const RECENT_EVENTS: i64 = 10;
+
+async fn create_service() -> Result<QuotaService> {
+    let db_path = retrochat_core::database::config::get_default_db_path()?;
+    let db_manager = DatabaseManager::new(&db_path).await?;
+    Ok(QuotaService::new(&db_manager))
+}
+
+/// Handle the quota command: thresholds, current usage and recent alerts
+pub async fn handle_quota_command() -> Result<()> {
+    let config = Config::load()?;
+    let service = create_service().await?;
+    let now = Utc::now();
+
+    if config.quota.has_thresholds() {
+        println!("Usage quotas:");
+        for status in service.status(&config.quota, now).await? {
+            println!(
+                "  {:<30} {:>12} / {:<12} {:>5.0}%{}",
+                status.kind.config_key(),
+                status.kind.format_amount(status.used),
+                status.kind.format_amount(status.threshold),
+                status.percentage(),
+                if status.exceeded() { "  EXCEEDED" } else { "" }
+            );
+        }
+    } else {
+        let usage = service.usage(now).await?;
+        println!("No quotas set. Current usage:");
+        println!("  Tokens today: {}", usage.daily_tokens);
+        println!(
+            "  Analysis this week: {} tokens, ${:.2}",
+            usage.weekly_analysis_tokens, usage.weekly_analysis_usd
+        );
+        println!();
+        println!("Set one with e.g.: retrochat config set quota.daily_tokens 2000000");
+    }
+
+    let events = service.events(RECENT_EVENTS).await?;
+    if !events.is_empty() {
+        println!();
+        println!("Recent alerts:");
+        for event in &events {
+            println!(
+                "  {} UTC  {}",
+                Language::current().format_datetime(&event.created_at),
+                event.message()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Check the configured quotas after an import or analysis, warning about
+/// thresholds crossed for the first time today or this week; failures only
+/// produce a warning
+pub async fn check_quotas() {
+    let config = match Config::load() {
+        Ok(config) if config.quota.has_thresholds() => config,
+        Ok(_) => return,
+        Err(e) => {
+            eprintln!("Warning: failed to check usage quotas: {e}");
+            return;
+        }
+    };
+
+    let events = match create_service().await {
+        Ok(service) => service.check(&config.quota, Utc::now()).await,
+        Err(e) => Err(e),
+    };
+    let events = match events {
+        Ok(events) => events,
+        Err(e) => {
+            eprintln!("Warning: failed to check usage quotas: {e}");
+            return;
+        }
+    };
+
+    for event in &events {
+        let message = event.message();
+        tracing::warn!(quota = %event.kind, "Usage quota exceeded: {message}");
+        eprintln!();
+        eprintln!("Warning: usage quota exceeded: {message}");
+        eprintln!(
+            "  Raise it with: retrochat config set {} <limit>",
+            event.kind.config_key()
+        );
+        if config.quota.notify.unwrap_or(true) {
+            notify_desktop("RetroChat usage quota exceeded", &message);
+        }
+    }
+}
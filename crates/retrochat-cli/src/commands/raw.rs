@@ -0,0 +1,79 @@
+use anyhow::{Context, Result};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use retrochat_core::config::Config;
+use retrochat_core::database::DatabaseManager;
+use retrochat_core::services::RawPayloadService;
+
+use super::maintenance::format_bytes;
+
+async fn create_service() -> Result<RawPayloadService> {
+    let db_path = retrochat_core::database::config::get_default_db_path()?;
+    let db_manager = DatabaseManager::new(&db_path).await?;
+    Ok(RawPayloadService::new(Arc::new(db_manager)))
+}
+
+fn parse_session_id(session: Option<String>) -> Result<Option<Uuid>> {
+    session
+        .map(|id| Uuid::parse_str(&id).with_context(|| format!("Invalid session ID: {id}")))
+        .transpose()
+}
+
+/// Handle the raw status command
+pub async fn handle_status_command() -> Result<()> {
+    let keep = Config::load()
+        .map(|config| config.import.keep_raw_payloads())
+        .unwrap_or(false);
+    println!("Raw payload retention: {}", if keep { "on" } else { "off" });
+
+    let stats = create_service().await?.status().await?;
+    if stats.sessions == 0 {
+        println!("No raw payloads are stored.");
+        if !keep {
+            println!("Enable with `retrochat config set import.raw_payloads true`, then `retrochat raw rehydrate`.");
+        }
+        return Ok(());
+    }
+
+    println!("Sessions:   {}", stats.sessions);
+    println!("Messages:   {}", stats.messages);
+    println!("Tool calls: {}", stats.tool_calls);
+    println!("Size:       {}", format_bytes(stats.bytes as i64));
+    Ok(())
+}
+
+/// Handle the raw strip command
+pub async fn handle_strip_command(session: Option<String>) -> Result<()> {
+    let session_id = parse_session_id(session)?;
+    let deleted = create_service().await?.strip(session_id.as_ref()).await?;
+    println!("Deleted {deleted} raw payloads");
+    if deleted > 0 {
+        println!("Run `retrochat maintenance` to reclaim the space on disk");
+    }
+    Ok(())
+}
+
+/// Handle the raw rehydrate command
+pub async fn handle_rehydrate_command(session: Option<String>) -> Result<()> {
+    let session_id = parse_session_id(session)?;
+    let report = create_service()
+        .await?
+        .rehydrate(session_id.as_ref())
+        .await?;
+
+    println!(
+        "Restored {} raw payloads for {} sessions",
+        report.payloads, report.sessions
+    );
+    if !report.missing_files.is_empty() {
+        println!(
+            "Skipped {} source files that are missing or unreadable:",
+            report.missing_files.len()
+        );
+        for file in &report.missing_files {
+            println!("  {file}");
+        }
+    }
+    Ok(())
+}
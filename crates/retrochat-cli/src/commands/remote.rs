@@ -0,0 +1,126 @@
+use anyhow::Result;
+use std::sync::Arc;
+
+use retrochat_core::config::{Config, RemoteSettings};
+use retrochat_core::database::DatabaseManager;
+use retrochat_core::services::{open_storage, RemoteSyncService};
+use retrochat_core::utils::locale::Language;
+
+async fn create_service() -> Result<RemoteSyncService> {
+    let db_path = retrochat_core::database::config::get_default_db_path()?;
+    let db_manager = Arc::new(DatabaseManager::new(&db_path).await?);
+    Ok(RemoteSyncService::new(db_manager))
+}
+
+/// Handle the remote add command
+pub async fn handle_add_command(remote: RemoteSettings) -> Result<()> {
+    let name = remote.name.trim();
+    if name.is_empty() || name.contains(char::is_whitespace) {
+        anyhow::bail!("Remote name must be non-empty and contain no spaces");
+    }
+    // Reject URLs that no storage understands before saving them
+    let kind = open_storage(&remote)?.kind();
+
+    let mut config = Config::load()?;
+    let replaced = config.sync.find_remote(&remote.name).is_some();
+    let name = remote.name.clone();
+    config.sync.add_remote(remote);
+    config.save()?;
+
+    let verb = if replaced { "Updated" } else { "Added" };
+    println!("{verb} {kind} remote '{name}'");
+    if config.sync.passphrase().is_err() {
+        println!(
+            "Set a passphrase before pushing: retrochat config set sync.passphrase <passphrase>"
+        );
+    }
+    Ok(())
+}
+
+/// Handle the remote remove command
+pub async fn handle_remove_command(name: String) -> Result<()> {
+    let mut config = Config::load()?;
+    if !config.sync.remove_remote(&name) {
+        anyhow::bail!("No remote named {name}; see `retrochat remote list`");
+    }
+    config.save()?;
+    create_service().await?.forget(&name).await?;
+    println!("Removed remote '{name}' (bundles already uploaded were left in place)");
+    Ok(())
+}
+
+/// Handle the remote list command
+pub async fn handle_list_command() -> Result<()> {
+    let config = Config::load()?;
+    if config.sync.remotes.is_empty() {
+        println!("No remotes. Add one with: retrochat remote add <name> <url>");
+        return Ok(());
+    }
+
+    let service = create_service().await?;
+    let format_time = |time: Option<chrono::DateTime<chrono::Utc>>| {
+        time.map(|t| Language::current().format_datetime(&t.with_timezone(&chrono::Local)))
+            .unwrap_or_else(|| "never".to_string())
+    };
+    for remote in &config.sync.remotes {
+        let kind = open_storage(remote)
+            .map(|storage| storage.kind())
+            .unwrap_or("invalid");
+        let summary = service.summary(&remote.name).await?;
+        let pending = service.pending_sessions(&remote.name).await?;
+        println!("{} ({kind}) {}", remote.name, remote.url);
+        println!(
+            "  last push: {} ({} bundles), last pull: {} ({} bundles), {pending} sessions to push",
+            format_time(summary.last_push),
+            summary.pushed_bundles,
+            format_time(summary.last_pull),
+            summary.pulled_bundles,
+        );
+    }
+    Ok(())
+}
+
+/// Handle the remote push command
+pub async fn handle_push_command(name: Option<String>) -> Result<()> {
+    let config = Config::load()?;
+    let remote = config.sync.resolve_remote(name.as_deref())?;
+    let passphrase = config.sync.passphrase()?;
+    let storage = open_storage(remote)?;
+
+    let report = create_service()
+        .await?
+        .push(&remote.name, storage.as_ref(), &passphrase)
+        .await?;
+    match report.bundle {
+        Some(bundle) => println!(
+            "Pushed {} sessions to '{}' as {bundle} ({:.1} KB)",
+            report.sessions,
+            remote.name,
+            report.bytes as f64 / 1024.0
+        ),
+        None => println!("'{}' is up to date; nothing to push", remote.name),
+    }
+    Ok(())
+}
+
+/// Handle the remote pull command
+pub async fn handle_pull_command(name: Option<String>) -> Result<()> {
+    let config = Config::load()?;
+    let remote = config.sync.resolve_remote(name.as_deref())?;
+    let passphrase = config.sync.passphrase()?;
+    let storage = open_storage(remote)?;
+
+    let report = create_service()
+        .await?
+        .pull(&remote.name, storage.as_ref(), &passphrase)
+        .await?;
+    if report.bundles == 0 {
+        println!("No new bundles on '{}'", remote.name);
+        return Ok(());
+    }
+    println!(
+        "Merged {} bundles from '{}': {} sessions added, {} replaced by longer copies, {} kept",
+        report.bundles, remote.name, report.added, report.replaced, report.kept
+    );
+    Ok(())
+}
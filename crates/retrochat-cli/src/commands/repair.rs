@@ -0,0 +1,60 @@
+use anyhow::{Context, Result};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use retrochat_core::database::DatabaseManager;
+use retrochat_core::services::RoleRepairService;
+
+/// Handle the repair roles command
+pub async fn handle_roles_command(
+    session_id: String,
+    dry_run: bool,
+    no_llm: bool,
+    provider: Option<String>,
+    model: Option<String>,
+) -> Result<()> {
+    let session_id = Uuid::parse_str(session_id.trim())
+        .with_context(|| format!("Invalid session ID: {session_id}"))?;
+    let llm_client = if no_llm {
+        None
+    } else {
+        Some(
+            super::summarize::create_llm_client(provider, model).context(
+                "No LLM available to classify messages; pass --no-llm for heuristics only",
+            )?,
+        )
+    };
+
+    let db_path = retrochat_core::database::config::get_default_db_path()?;
+    let db_manager = Arc::new(DatabaseManager::new(&db_path).await?);
+    let report = RoleRepairService::new(db_manager, llm_client)
+        .repair(&session_id, dry_run)
+        .await?;
+
+    if report.changes.is_empty() {
+        println!("All message roles look right");
+    } else {
+        for change in &report.changes {
+            println!(
+                "#{:<5} {:>9} -> {:<9} ({})  {}",
+                change.sequence_number, change.from, change.to, change.reason, change.excerpt
+            );
+        }
+        println!();
+        if report.applied {
+            println!("Changed {} message roles", report.changes.len());
+        } else {
+            println!(
+                "Would change {} message roles; run without --dry-run to save",
+                report.changes.len()
+            );
+        }
+    }
+    if report.undecided > 0 {
+        println!(
+            "{} messages could not be placed and were left as they are",
+            report.undecided
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,473 @@
+//! GraphQL endpoint (`POST /api/graphql`), built with the `graphql` feature
+//!
+//! Sessions, messages, tool operations and analyses with filtering and
+//! pagination, for dashboards that want nested queries the REST routes
+//! don't offer. `GET /api/graphql` returns the schema as SDL.
+
+use async_graphql::{
+    ComplexObject, Context, EmptyMutation, EmptySubscription, InputObject, Json, Object, Result,
+    Schema, SimpleObject, ID,
+};
+use std::sync::Arc;
+
+use retrochat_core::database::DatabaseManager;
+use retrochat_core::models::{Analytics, ChatSession, Message};
+use retrochat_core::services::{
+    QueryService, SessionCursor, SessionDetailRequest, SessionFilters, SessionSummary,
+    SessionsQueryRequest, ToolOperationRecord, ToolOperationsQueryRequest,
+};
+
+/// Page size when `first` is not given
+const DEFAULT_PAGE_SIZE: i32 = 20;
+
+/// Largest page a query can ask for
+const MAX_PAGE_SIZE: i32 = 100;
+
+/// Deepest nesting a query may use
+const MAX_QUERY_DEPTH: usize = 8;
+
+pub type RetrochatSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+pub fn build_schema(db_manager: Arc<DatabaseManager>) -> RetrochatSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(QueryService::with_database(db_manager.clone()))
+        .data(db_manager)
+        .limit_depth(MAX_QUERY_DEPTH)
+        .finish()
+}
+
+fn page_size(first: Option<i32>) -> i32 {
+    first.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE)
+}
+
+/// Which sessions to list; every given field must match
+#[derive(Debug, Default, InputObject)]
+pub struct SessionFilter {
+    provider: Option<String>,
+    project: Option<String>,
+    min_messages: Option<i32>,
+    max_messages: Option<i32>,
+    /// Topic ID or label
+    topic: Option<String>,
+    /// `me`, `team` or a teammate's name
+    owner: Option<String>,
+    /// Filter expression as accepted by `retrochat list --filter`, e.g.
+    /// `since:"last week" tool:Bash(failed)`
+    expression: Option<String>,
+    #[graphql(default)]
+    include_archived: bool,
+}
+
+impl From<SessionFilter> for SessionFilters {
+    fn from(filter: SessionFilter) -> Self {
+        Self {
+            provider: filter.provider,
+            project: filter.project,
+            date_range: None,
+            min_messages: filter.min_messages,
+            max_messages: filter.max_messages,
+            include_archived: filter.include_archived,
+            expression: filter.expression,
+            topic: filter.topic,
+            owner: filter.owner,
+            providers: None,
+            projects: None,
+            topics: None,
+        }
+    }
+}
+
+#[derive(Debug, SimpleObject)]
+#[graphql(complex)]
+pub struct Session {
+    id: ID,
+    provider: String,
+    project: Option<String>,
+    title: Option<String>,
+    /// RFC 3339
+    start_time: String,
+    end_time: Option<String>,
+    message_count: i32,
+    total_tokens: Option<i32>,
+    tool_op_count: i32,
+    duration_seconds: Option<i32>,
+    /// Only filled in session listings
+    first_message_preview: Option<String>,
+    owner: Option<String>,
+}
+
+impl From<SessionSummary> for Session {
+    fn from(summary: SessionSummary) -> Self {
+        Self {
+            id: ID(summary.session_id),
+            provider: summary.provider,
+            project: summary.project,
+            title: summary.title,
+            start_time: summary.start_time,
+            end_time: Some(summary.end_time),
+            message_count: summary.message_count,
+            total_tokens: summary.total_tokens,
+            tool_op_count: summary.tool_op_count,
+            duration_seconds: summary.duration_seconds,
+            first_message_preview: Some(summary.first_message_preview),
+            owner: summary.owner,
+        }
+    }
+}
+
+impl From<ChatSession> for Session {
+    fn from(session: ChatSession) -> Self {
+        Self {
+            id: ID(session.id.to_string()),
+            provider: session.provider.to_string(),
+            project: session.project_name,
+            title: session.title,
+            start_time: session.start_time.to_rfc3339(),
+            end_time: session.end_time.map(|t| t.to_rfc3339()),
+            message_count: session.message_count as i32,
+            total_tokens: session.token_count.map(|t| t as i32),
+            tool_op_count: session.tool_op_count as i32,
+            duration_seconds: session.duration_seconds.map(|d| d as i32),
+            first_message_preview: None,
+            owner: session.owner,
+        }
+    }
+}
+
+#[ComplexObject]
+impl Session {
+    /// Messages in conversation order
+    async fn messages(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<i32>,
+        offset: Option<i32>,
+    ) -> Result<MessagePage> {
+        session_messages(ctx, self.id.to_string(), first, offset).await
+    }
+
+    /// Tool calls, newest first
+    async fn tool_operations(
+        &self,
+        ctx: &Context<'_>,
+        tool_name: Option<String>,
+        success: Option<bool>,
+        first: Option<i32>,
+        page: Option<i32>,
+    ) -> Result<ToolOperationPage> {
+        tool_operations(
+            ctx,
+            ToolOperationsQueryRequest {
+                tool_name,
+                success,
+                file_path: None,
+                session_id: Some(self.id.to_string()),
+                since: None,
+                until: None,
+                page,
+                page_size: Some(page_size(first)),
+            },
+        )
+        .await
+    }
+
+    /// Latest completed analysis, as stored
+    async fn analytics(&self, ctx: &Context<'_>) -> Result<Option<Json<Analytics>>> {
+        latest_analytics(ctx, &self.id).await
+    }
+}
+
+#[derive(Debug, SimpleObject)]
+pub struct SessionPage {
+    sessions: Vec<Session>,
+    /// Matching sessions on all pages; 0 when paging with `after`
+    total_count: i32,
+    /// Pass as `after` to get the next page
+    next_cursor: Option<String>,
+}
+
+#[derive(Debug, SimpleObject)]
+pub struct ChatMessage {
+    id: ID,
+    role: String,
+    content: String,
+    timestamp: String,
+    sequence_number: i32,
+    message_type: String,
+    token_count: Option<i32>,
+    tool_operation_id: Option<ID>,
+}
+
+impl From<Message> for ChatMessage {
+    fn from(message: Message) -> Self {
+        Self {
+            id: ID(message.id.to_string()),
+            role: message.role.to_string(),
+            content: message.content,
+            timestamp: message.timestamp.to_rfc3339(),
+            sequence_number: message.sequence_number as i32,
+            message_type: message.message_type.to_string(),
+            token_count: message
+                .token_count
+                .or(message.estimated_tokens)
+                .map(|t| t as i32),
+            tool_operation_id: message.tool_operation_id.map(|id| ID(id.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, SimpleObject)]
+pub struct MessagePage {
+    messages: Vec<ChatMessage>,
+    total_count: i32,
+    has_more: bool,
+}
+
+#[derive(Debug, SimpleObject)]
+pub struct ToolOperation {
+    id: ID,
+    session_id: ID,
+    tool_name: String,
+    mcp_server: Option<String>,
+    timestamp: String,
+    success: Option<bool>,
+    file_path: Option<String>,
+    lines_added: Option<i32>,
+    lines_removed: Option<i32>,
+    command: Option<String>,
+    exit_code: Option<i32>,
+    result_summary: Option<String>,
+    duration_ms: Option<i64>,
+}
+
+impl From<ToolOperationRecord> for ToolOperation {
+    fn from(record: ToolOperationRecord) -> Self {
+        Self {
+            id: ID(record.id),
+            session_id: ID(record.session_id),
+            tool_name: record.tool_name,
+            mcp_server: record.mcp_server,
+            timestamp: record.timestamp,
+            success: record.success,
+            file_path: record.file_path,
+            lines_added: record.lines_added,
+            lines_removed: record.lines_removed,
+            command: record.command,
+            exit_code: record.exit_code,
+            result_summary: record.result_summary,
+            duration_ms: record.duration_ms,
+        }
+    }
+}
+
+#[derive(Debug, SimpleObject)]
+pub struct ToolOperationPage {
+    operations: Vec<ToolOperation>,
+    total_count: i32,
+    total_pages: i32,
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Sessions, newest first
+    async fn sessions(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<SessionFilter>,
+        first: Option<i32>,
+        page: Option<i32>,
+        #[graphql(
+            desc = "`nextCursor` of the previous page; faster than `page` deep into the listing"
+        )]
+        after: Option<String>,
+    ) -> Result<SessionPage> {
+        let after = after
+            .as_deref()
+            .map(str::parse::<SessionCursor>)
+            .transpose()?;
+        let response = ctx
+            .data::<QueryService>()?
+            .query_sessions(SessionsQueryRequest {
+                page,
+                page_size: Some(page_size(first)),
+                sort_by: None,
+                sort_order: None,
+                filters: filter.map(SessionFilters::from),
+                after,
+            })
+            .await?;
+        Ok(SessionPage {
+            sessions: response.sessions.into_iter().map(Session::from).collect(),
+            total_count: response.total_count,
+            next_cursor: response.next_cursor.map(|cursor| cursor.to_string()),
+        })
+    }
+
+    async fn session(&self, ctx: &Context<'_>, id: ID) -> Result<Option<Session>> {
+        let Ok(id) = id.parse() else {
+            return Ok(None);
+        };
+        let session = retrochat_core::database::ChatSessionRepository::new(
+            ctx.data::<Arc<DatabaseManager>>()?,
+        )
+        .get_by_id(&id)
+        .await?;
+        Ok(session.map(Session::from))
+    }
+
+    async fn messages(
+        &self,
+        ctx: &Context<'_>,
+        session_id: ID,
+        first: Option<i32>,
+        offset: Option<i32>,
+    ) -> Result<MessagePage> {
+        session_messages(ctx, session_id.to_string(), first, offset).await
+    }
+
+    /// Tool calls across sessions, newest first
+    #[allow(clippy::too_many_arguments)]
+    async fn tool_operations(
+        &self,
+        ctx: &Context<'_>,
+        session_id: Option<ID>,
+        tool_name: Option<String>,
+        success: Option<bool>,
+        file_path: Option<String>,
+        first: Option<i32>,
+        page: Option<i32>,
+    ) -> Result<ToolOperationPage> {
+        tool_operations(
+            ctx,
+            ToolOperationsQueryRequest {
+                tool_name,
+                success,
+                file_path,
+                session_id: session_id.map(|id| id.to_string()),
+                since: None,
+                until: None,
+                page,
+                page_size: Some(page_size(first)),
+            },
+        )
+        .await
+    }
+
+    /// Latest completed analysis of a session, as stored
+    async fn analytics(
+        &self,
+        ctx: &Context<'_>,
+        session_id: ID,
+    ) -> Result<Option<Json<Analytics>>> {
+        latest_analytics(ctx, &session_id).await
+    }
+}
+
+async fn session_messages(
+    ctx: &Context<'_>,
+    session_id: String,
+    first: Option<i32>,
+    offset: Option<i32>,
+) -> Result<MessagePage> {
+    let detail = ctx
+        .data::<QueryService>()?
+        .get_session_detail(SessionDetailRequest {
+            session_id,
+            include_content: Some(true),
+            message_limit: Some(page_size(first)),
+            message_offset: offset,
+        })
+        .await?;
+    Ok(MessagePage {
+        messages: detail.messages.into_iter().map(ChatMessage::from).collect(),
+        total_count: detail.total_message_count,
+        has_more: detail.has_more_messages,
+    })
+}
+
+async fn tool_operations(
+    ctx: &Context<'_>,
+    request: ToolOperationsQueryRequest,
+) -> Result<ToolOperationPage> {
+    let response = ctx
+        .data::<QueryService>()?
+        .query_tool_operations(request)
+        .await?;
+    Ok(ToolOperationPage {
+        operations: response
+            .operations
+            .into_iter()
+            .map(ToolOperation::from)
+            .collect(),
+        total_count: response.total_count,
+        total_pages: response.total_pages,
+    })
+}
+
+async fn latest_analytics(ctx: &Context<'_>, session_id: &ID) -> Result<Option<Json<Analytics>>> {
+    let analytics = ctx
+        .data::<QueryService>()?
+        .get_session_analytics(session_id)
+        .await?;
+    Ok(analytics
+        .and_then(|analytics| analytics.latest_analytics)
+        .map(Json))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use retrochat_core::database::{ChatSessionRepository, MessageRepository, ProjectRepository};
+    use retrochat_core::models::{MessageRole, Project, Provider};
+
+    #[tokio::test]
+    async fn test_nested_session_query() {
+        let db = Arc::new(DatabaseManager::open_in_memory().await.unwrap());
+        ProjectRepository::new(&db)
+            .create(&Project::new("retrochat".to_string()))
+            .await
+            .unwrap();
+        let session = ChatSession::new(
+            Provider::ClaudeCode,
+            "/tmp/session.jsonl".to_string(),
+            "hash".to_string(),
+            chrono::Utc::now(),
+        )
+        .with_project("retrochat".to_string());
+        ChatSessionRepository::new(&db)
+            .create(&session)
+            .await
+            .unwrap();
+        MessageRepository::new(&db)
+            .bulk_create(&[Message::new(
+                session.id,
+                MessageRole::User,
+                "Hello".to_string(),
+                chrono::Utc::now(),
+                1,
+            )])
+            .await
+            .unwrap();
+
+        let schema = build_schema(db);
+        let response = schema
+            .execute(
+                r#"{
+                    sessions(filter: { project: "retrochat" }, first: 500) {
+                        totalCount
+                        sessions { project messages { messages { content } } }
+                    }
+                }"#,
+            )
+            .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(data["sessions"]["totalCount"], 1);
+        assert_eq!(
+            data["sessions"]["sessions"][0]["messages"]["messages"][0]["content"],
+            "Hello"
+        );
+    }
+}
@@ -0,0 +1,586 @@
+//! HTTP API for running RetroChat on a home server
+//!
+//! Routes:
+//! - `POST /api/import`: multipart upload of `.json`/`.jsonl` session files
+//! - `GET /api/events`: the change feed as server-sent events, from
+//!   `?since=<id>` or `Last-Event-ID`, optionally limited with
+//!   `?kinds=a,b`; without either only new events are sent
+//! - `POST /api/graphql`: GraphQL queries, with the `graphql` feature (see
+//!   [`graphql`])
+//!
+//! Every `/api/` route needs `Authorization: Bearer <token>` once a token is
+//! set with `serve.token` or `RETROCHAT_SERVE_TOKEN`; without one the server
+//! only listens on localhost.
+
+use anyhow::{Context, Result};
+use hyper::body::HttpBody;
+use hyper::header::{AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE, WWW_AUTHENTICATE};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use serde::Serialize;
+use serde_json::json;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+
+use retrochat_core::config::Config;
+use retrochat_core::database::DatabaseManager;
+use retrochat_core::env::serve as env_serve;
+use retrochat_core::models::{Event, EventKind, Provider};
+use retrochat_core::parsers::ParserRegistry;
+use retrochat_core::services::{EventFeed, ImportService};
+
+#[cfg(feature = "graphql")]
+mod graphql;
+
+/// File extensions accepted by `POST /api/import`
+const UPLOAD_EXTENSIONS: &[&str] = &["json", "jsonl"];
+
+/// How often event streams check for commits by other processes
+const EVENT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Idle time after which an event stream sends a comment, so proxies don't
+/// close it
+const EVENT_KEEP_ALIVE: Duration = Duration::from_secs(15);
+
+/// Largest GraphQL request body accepted
+#[cfg(feature = "graphql")]
+const MAX_GRAPHQL_REQUEST_BYTES: usize = 64 * 1024;
+
+struct ServerState {
+    import_service: ImportService,
+    events: EventFeed,
+    /// Flips to true on Ctrl-C; event streams end so shutdown isn't held up
+    shutdown: watch::Receiver<bool>,
+    token: Option<String>,
+    max_upload_bytes: usize,
+    #[cfg(feature = "graphql")]
+    schema: graphql::RetrochatSchema,
+}
+
+/// Result of importing one uploaded file
+#[derive(Debug, Serialize)]
+struct UploadedFile {
+    file_name: String,
+    provider: Option<String>,
+    sessions_imported: i32,
+    messages_imported: i32,
+    messages_truncated: i32,
+    tool_results_truncated: i32,
+    warnings: Vec<String>,
+    error: Option<String>,
+}
+
+/// Response of `POST /api/import`
+#[derive(Debug, Serialize)]
+struct UploadReport {
+    files: Vec<UploadedFile>,
+    total_sessions_imported: i32,
+    total_messages_imported: i32,
+    failed_files: usize,
+}
+
+/// One part of a multipart/form-data body
+#[derive(Debug, PartialEq)]
+struct FormPart {
+    name: String,
+    file_name: Option<String>,
+    data: Vec<u8>,
+}
+
+/// Handle the serve command
+pub async fn handle_serve_command(host: String, port: u16) -> Result<()> {
+    let config = Config::load()?;
+    let token = config.serve.token();
+
+    let addr: SocketAddr = tokio::net::lookup_host((host.as_str(), port))
+        .await
+        .with_context(|| format!("Invalid address: {host}:{port}"))?
+        .next()
+        .with_context(|| format!("No address found for {host}"))?;
+    if token.is_none() && !addr.ip().is_loopback() {
+        anyhow::bail!(
+            "Set {} or `retrochat config set serve.token <token>` before listening on {addr}",
+            env_serve::SERVE_TOKEN
+        );
+    }
+
+    let db_path = retrochat_core::database::config::get_default_db_path()?;
+    let db_manager = Arc::new(DatabaseManager::new(&db_path).await?);
+    let (shutdown_sender, shutdown) = watch::channel(false);
+    let state = Arc::new(ServerState {
+        events: EventFeed::new(&db_manager),
+        shutdown,
+        #[cfg(feature = "graphql")]
+        schema: graphql::build_schema(db_manager.clone()),
+        import_service: ImportService::new(db_manager),
+        token,
+        max_upload_bytes: config.serve.max_upload_bytes(),
+    });
+
+    let make_service = make_service_fn(move |_| {
+        let state = state.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request| {
+                let state = state.clone();
+                async move { Ok::<_, Infallible>(route(&state, request).await) }
+            }))
+        }
+    });
+
+    let server = Server::try_bind(&addr)
+        .with_context(|| format!("Failed to listen on {addr}"))?
+        .serve(make_service);
+    println!("Listening on http://{addr} (Ctrl-C to stop)");
+    server
+        .with_graceful_shutdown(async move {
+            let _ = tokio::signal::ctrl_c().await;
+            let _ = shutdown_sender.send(true);
+        })
+        .await
+        .context("Server error")?;
+    Ok(())
+}
+
+async fn route(state: &ServerState, request: Request<Body>) -> Response<Body> {
+    let path = request.uri().path().to_string();
+    if path.starts_with("/api/") && !authorized(state, &request) {
+        let mut response = error_response(StatusCode::UNAUTHORIZED, "Missing or wrong token");
+        response
+            .headers_mut()
+            .insert(WWW_AUTHENTICATE, "Bearer".parse().unwrap());
+        return response;
+    }
+
+    let response = match (request.method(), path.as_str()) {
+        (&Method::POST, "/api/import") => import_upload(state, request).await,
+        (&Method::GET, "/api/events") => event_stream(state, &request).await,
+        #[cfg(feature = "graphql")]
+        (&Method::POST, "/api/graphql") => graphql_query(state, request).await,
+        #[cfg(feature = "graphql")]
+        (&Method::GET, "/api/graphql") => Response::builder()
+            .header(CONTENT_TYPE, "text/plain; charset=utf-8")
+            .body(Body::from(state.schema.sdl()))
+            .unwrap(),
+        _ => error_response(StatusCode::NOT_FOUND, "Not found"),
+    };
+    tracing::info!(path, status = response.status().as_u16(), "Handled request");
+    response
+}
+
+fn authorized(state: &ServerState, request: &Request<Body>) -> bool {
+    let Some(token) = &state.token else {
+        return true;
+    };
+    request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|given| constant_time_eq(given.trim().as_bytes(), token.as_bytes()))
+}
+
+/// Compare without stopping at the first difference, so response timing
+/// doesn't reveal how much of a guessed token was right
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+async fn import_upload(state: &ServerState, request: Request<Body>) -> Response<Body> {
+    let Some(boundary) = request
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(multipart_boundary)
+    else {
+        return error_response(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "Expected a multipart/form-data upload",
+        );
+    };
+
+    let declared_length = request
+        .headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<usize>().ok());
+    if declared_length.is_some_and(|length| length > state.max_upload_bytes) {
+        return too_large(state);
+    }
+
+    let body = match read_body(request.into_body(), state.max_upload_bytes).await {
+        Ok(Some(body)) => body,
+        Ok(None) => return too_large(state),
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, &format!("{e}")),
+    };
+    let parts = match parse_multipart(&body, &boundary) {
+        Ok(parts) => parts,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, &format!("{e:#}")),
+    };
+
+    let field = |name: &str| {
+        parts
+            .iter()
+            .find(|part| part.name == name && part.file_name.is_none())
+            .map(|part| String::from_utf8_lossy(&part.data).trim().to_string())
+    };
+    let provider = field("provider").filter(|p| !p.is_empty());
+    let overwrite = field("overwrite").is_some_and(|v| v == "true" || v == "1");
+
+    let mut files = Vec::new();
+    for part in parts.iter().filter(|part| part.file_name.is_some()) {
+        files.push(import_part(state, part, provider.as_deref(), overwrite).await);
+    }
+    if files.is_empty() {
+        return error_response(StatusCode::BAD_REQUEST, "No files in the upload");
+    }
+
+    let report = UploadReport {
+        total_sessions_imported: files.iter().map(|f| f.sessions_imported).sum(),
+        total_messages_imported: files.iter().map(|f| f.messages_imported).sum(),
+        failed_files: files.iter().filter(|f| f.error.is_some()).count(),
+        files,
+    };
+    json_response(StatusCode::OK, &report)
+}
+
+#[cfg(feature = "graphql")]
+async fn graphql_query(state: &ServerState, request: Request<Body>) -> Response<Body> {
+    let body = match read_body(request.into_body(), MAX_GRAPHQL_REQUEST_BYTES).await {
+        Ok(Some(body)) => body,
+        Ok(None) => {
+            return error_response(StatusCode::PAYLOAD_TOO_LARGE, "GraphQL request too large")
+        }
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, &format!("{e}")),
+    };
+    let query: async_graphql::Request = match serde_json::from_slice(&body) {
+        Ok(query) => query,
+        Err(e) => {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                &format!("Invalid GraphQL request: {e}"),
+            )
+        }
+    };
+    json_response(StatusCode::OK, &state.schema.execute(query).await)
+}
+
+async fn event_stream(state: &ServerState, request: &Request<Body>) -> Response<Body> {
+    let query = request.uri().query().unwrap_or_default();
+    let kinds = match query_param(query, "kinds") {
+        Some(kinds) => match parse_kinds(kinds) {
+            Ok(kinds) => kinds,
+            Err(e) => return error_response(StatusCode::BAD_REQUEST, &e),
+        },
+        None => Vec::new(),
+    };
+    // A reconnecting browser sends the id of the last event it got
+    let since = request
+        .headers()
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok())
+        .or_else(|| query_param(query, "since"))
+        .map(|cursor| cursor.trim().parse::<i64>());
+    let cursor = match since {
+        Some(Ok(cursor)) => cursor,
+        Some(Err(_)) => {
+            return error_response(StatusCode::BAD_REQUEST, "Event ids are integers");
+        }
+        None => match state.events.latest_cursor().await {
+            Ok(cursor) => cursor,
+            Err(e) => return error_response(StatusCode::INTERNAL_SERVER_ERROR, &format!("{e:#}")),
+        },
+    };
+
+    let mut events = match state.events.subscribe(cursor, EVENT_POLL_INTERVAL).await {
+        Ok(events) => events,
+        Err(e) => return error_response(StatusCode::INTERNAL_SERVER_ERROR, &format!("{e:#}")),
+    };
+    let mut shutdown = state.shutdown.clone();
+    let (mut sender, body) = Body::channel();
+    tokio::spawn(async move {
+        loop {
+            let frame = tokio::select! {
+                event = events.recv() => match event {
+                    Some(event) if kinds.is_empty() || kinds.contains(&event.kind) => {
+                        sse_frame(&event)
+                    }
+                    Some(_) => continue,
+                    None => return,
+                },
+                _ = tokio::time::sleep(EVENT_KEEP_ALIVE) => ": keep-alive\n\n".to_string(),
+                _ = shutdown.changed() => return,
+            };
+            // Fails once the client has gone; dropping `events` stops the feed
+            if sender.send_data(frame.into()).await.is_err() {
+                return;
+            }
+        }
+    });
+
+    Response::builder()
+        .header(CONTENT_TYPE, "text/event-stream")
+        .header("cache-control", "no-cache")
+        .body(body)
+        .unwrap()
+}
+
+/// Event kinds from a comma-separated list
+fn parse_kinds(kinds: &str) -> Result<Vec<EventKind>, String> {
+    kinds
+        .replace("%2C", ",")
+        .replace("%2c", ",")
+        .split(',')
+        .filter(|kind| !kind.is_empty())
+        .map(|kind| {
+            kind.parse::<EventKind>().map_err(|_| {
+                let known: Vec<&str> = EventKind::ALL.iter().map(EventKind::as_str).collect();
+                format!(
+                    "Unknown event kind '{kind}'; known kinds: {}",
+                    known.join(", ")
+                )
+            })
+        })
+        .collect()
+}
+
+/// One event in the `text/event-stream` format, named after its kind
+fn sse_frame(event: &Event) -> String {
+    let data = serde_json::to_string(event).unwrap_or_default();
+    format!("id: {}\nevent: {}\ndata: {data}\n\n", event.id, event.kind)
+}
+
+/// Value of `key` in a query string, undecoded
+fn query_param<'a>(query: &'a str, key: &str) -> Option<&'a str> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(name, _)| *name == key)
+        .map(|(_, value)| value)
+}
+
+async fn import_part(
+    state: &ServerState,
+    part: &FormPart,
+    provider: Option<&str>,
+    overwrite: bool,
+) -> UploadedFile {
+    // Only the base name is kept; client paths mean nothing on the server
+    let file_name = part
+        .file_name
+        .as_deref()
+        .and_then(|name| Path::new(name).file_name())
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut uploaded = UploadedFile {
+        file_name: file_name.clone(),
+        provider: None,
+        sessions_imported: 0,
+        messages_imported: 0,
+        messages_truncated: 0,
+        tool_results_truncated: 0,
+        warnings: Vec::new(),
+        error: None,
+    };
+
+    let extension = Path::new(&file_name)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase);
+    if !extension.is_some_and(|e| UPLOAD_EXTENSIONS.contains(&e.as_str())) {
+        uploaded.error = Some("Only .json and .jsonl files are accepted".to_string());
+        return uploaded;
+    }
+
+    let provider = match provider {
+        Some(name) => name.parse::<Provider>().ok(),
+        None => ParserRegistry::detect_provider_for_content(&file_name, &part.data),
+    };
+    let Some(provider) = provider else {
+        uploaded.error = Some("Unable to detect the file format".to_string());
+        return uploaded;
+    };
+    uploaded.provider = Some(provider.to_string());
+
+    match state
+        .import_service
+        .import_from_reader(provider, &file_name, part.data.as_slice(), overwrite)
+        .await
+    {
+        Ok(response) => {
+            uploaded.sessions_imported = response.sessions_imported;
+            uploaded.messages_imported = response.messages_imported;
+            uploaded.messages_truncated = response.messages_truncated;
+            uploaded.tool_results_truncated = response.tool_results_truncated;
+            uploaded.warnings = response.warnings;
+        }
+        Err(e) => uploaded.error = Some(format!("{e:#}")),
+    }
+    uploaded
+}
+
+/// Read a request body, or `None` once it grows past `limit`
+async fn read_body(mut body: Body, limit: usize) -> Result<Option<Vec<u8>>, hyper::Error> {
+    let mut bytes = Vec::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk?;
+        if bytes.len() + chunk.len() > limit {
+            return Ok(None);
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok(Some(bytes))
+}
+
+/// Boundary of a `multipart/form-data` content type
+fn multipart_boundary(content_type: &str) -> Option<String> {
+    let (mime, params) = content_type.split_once(';')?;
+    if !mime.trim().eq_ignore_ascii_case("multipart/form-data") {
+        return None;
+    }
+    params.split(';').find_map(|param| {
+        let (key, value) = param.split_once('=')?;
+        (key.trim().eq_ignore_ascii_case("boundary"))
+            .then(|| value.trim().trim_matches('"').to_string())
+            .filter(|boundary| !boundary.is_empty())
+    })
+}
+
+fn parse_multipart(body: &[u8], boundary: &str) -> Result<Vec<FormPart>> {
+    let delimiter = format!("--{boundary}");
+    let next_delimiter = format!("\r\n--{boundary}");
+    let mut position = find(body, delimiter.as_bytes(), 0)
+        .context("Upload has no multipart boundary")?
+        + delimiter.len();
+
+    let mut parts = Vec::new();
+    // `--` after a delimiter closes the body
+    while !body[position..].starts_with(b"--") {
+        let headers_start = if body[position..].starts_with(b"\r\n") {
+            position + 2
+        } else {
+            position
+        };
+        let headers_end = find(body, b"\r\n\r\n", headers_start)
+            .context("Multipart part has no end of headers")?;
+        let headers = String::from_utf8_lossy(&body[headers_start..headers_end]);
+        let data_start = headers_end + 4;
+        let data_end = find(body, next_delimiter.as_bytes(), data_start)
+            .context("Multipart part is not terminated")?;
+
+        let disposition = headers
+            .lines()
+            .find_map(|line| {
+                let (name, value) = line.split_once(':')?;
+                name.trim()
+                    .eq_ignore_ascii_case("content-disposition")
+                    .then_some(value)
+            })
+            .context("Multipart part has no Content-Disposition")?;
+        parts.push(FormPart {
+            name: disposition_param(disposition, "name").unwrap_or_default(),
+            file_name: disposition_param(disposition, "filename"),
+            data: body[data_start..data_end].to_vec(),
+        });
+        position = data_end + next_delimiter.len();
+    }
+    Ok(parts)
+}
+
+/// Value of a `key="value"` parameter of a Content-Disposition header
+fn disposition_param(disposition: &str, key: &str) -> Option<String> {
+    disposition.split(';').find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        (name.trim() == key).then(|| value.trim().trim_matches('"').to_string())
+    })
+}
+
+fn find(haystack: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+    haystack
+        .get(from..)?
+        .windows(needle.len())
+        .position(|window| window == needle)
+        .map(|index| from + index)
+}
+
+fn too_large(state: &ServerState) -> Response<Body> {
+    error_response(
+        StatusCode::PAYLOAD_TOO_LARGE,
+        &format!(
+            "Uploads are limited to {} MB (serve.max_upload_mb)",
+            state.max_upload_bytes / (1024 * 1024)
+        ),
+    )
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response<Body> {
+    json_response(status, &json!({ "error": message }))
+}
+
+fn json_response<T: Serialize>(status: StatusCode, value: &T) -> Response<Body> {
+    let body = serde_json::to_vec(value).unwrap_or_default();
+    Response::builder()
+        .status(status)
+        .header(CONTENT_TYPE, "application/json")
+        .body(Body::from(body))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_multipart_upload() {
+        let boundary = multipart_boundary("multipart/form-data; boundary=\"XyZ\"").unwrap();
+        assert_eq!(boundary, "XyZ");
+        assert_eq!(multipart_boundary("application/json"), None);
+
+        let body = concat!(
+            "--XyZ\r\n",
+            "Content-Disposition: form-data; name=\"provider\"\r\n\r\n",
+            "claude\r\n",
+            "--XyZ\r\n",
+            "Content-Disposition: form-data; name=\"file\"; filename=\"a.jsonl\"\r\n",
+            "Content-Type: application/octet-stream\r\n\r\n",
+            "{\"a\":1}\r\n{\"b\":2}\r\n",
+            "--XyZ--\r\n",
+        );
+        let parts = parse_multipart(body.as_bytes(), &boundary).unwrap();
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].name, "provider");
+        assert_eq!(parts[0].data, b"claude");
+        assert_eq!(parts[1].file_name.as_deref(), Some("a.jsonl"));
+        assert_eq!(parts[1].data, b"{\"a\":1}\r\n{\"b\":2}");
+
+        assert!(parse_multipart(b"--XyZ\r\nbroken", "XyZ").is_err());
+        assert!(constant_time_eq(b"secret", b"secret"));
+        assert!(!constant_time_eq(b"secret", b"secreT"));
+    }
+
+    #[test]
+    fn test_event_stream_frames() {
+        let query = "since=41&kinds=session_imported%2Canalysis_completed";
+        assert_eq!(query_param(query, "since"), Some("41"));
+        assert_eq!(query_param(query, "limit"), None);
+        assert_eq!(
+            parse_kinds(query_param(query, "kinds").unwrap()).unwrap(),
+            vec![EventKind::SessionImported, EventKind::AnalysisCompleted]
+        );
+        assert!(parse_kinds("session_imported,nope").is_err());
+
+        let event = Event {
+            id: 42,
+            kind: EventKind::SessionImported,
+            session_id: Some("abc".to_string()),
+            payload: json!({ "provider": "claude" }),
+            created_at: chrono::Utc::now(),
+        };
+        let frame = sse_frame(&event);
+        assert!(frame.starts_with("id: 42\nevent: session_imported\ndata: {"));
+        assert!(frame.ends_with("}\n\n"));
+    }
+}
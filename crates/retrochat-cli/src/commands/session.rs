@@ -0,0 +1,54 @@
+use anyhow::{Context, Result};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use retrochat_core::database::DatabaseManager;
+use retrochat_core::services::SessionEditService;
+
+async fn create_service() -> Result<SessionEditService> {
+    let db_path = retrochat_core::database::config::get_default_db_path()?;
+    Ok(SessionEditService::new(Arc::new(
+        DatabaseManager::new(&db_path).await?,
+    )))
+}
+
+fn parse_session_id(id: &str) -> Result<Uuid> {
+    Uuid::parse_str(id.trim()).with_context(|| format!("Invalid session ID: {id}"))
+}
+
+/// Handle the session split command
+pub async fn handle_split_command(session_id: String, at_sequence: u32) -> Result<()> {
+    let session_id = parse_session_id(&session_id)?;
+    let report = create_service()
+        .await?
+        .split(&session_id, at_sequence)
+        .await?;
+
+    println!(
+        "Split session {}: kept {} messages, moved {} into {}",
+        report.session_id, report.messages_kept, report.messages_moved, report.new_session_id
+    );
+    println!(
+        "Re-importing the file with --overwrite restores the original session next to \
+         the new one; archive {} if you do",
+        report.new_session_id
+    );
+    Ok(())
+}
+
+/// Handle the session merge command
+pub async fn handle_merge_command(session_id: String, other_session_id: String) -> Result<()> {
+    let report = create_service()
+        .await?
+        .merge(
+            &parse_session_id(&session_id)?,
+            &parse_session_id(&other_session_id)?,
+        )
+        .await?;
+
+    println!(
+        "Merged session {} into {} ({} messages moved)",
+        report.merged_session_id, report.session_id, report.messages_moved
+    );
+    Ok(())
+}
@@ -1,10 +1,15 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use console::{style, Emoji};
 use indicatif::{ProgressBar, ProgressStyle};
 use inquire::MultiSelect;
 use std::sync::Arc;
 
 use retrochat_core::database::{config, DatabaseManager};
+use retrochat_core::models::Provider;
+use retrochat_core::services::llm::diagnostics::{
+    error_suggestion, probe_provider, unavailable_suggestion,
+};
+use retrochat_core::services::llm::{LlmClientFactory, LlmProvider};
 use retrochat_core::services::{AutoDetectService, DetectedProvider, ImportService};
 
 static ROCKET: Emoji<'_, '_> = Emoji("🚀 ", "");
@@ -12,6 +17,8 @@ static SPARKLES: Emoji<'_, '_> = Emoji("✨ ", "");
 static CHECK: Emoji<'_, '_> = Emoji("✓ ", "[OK]");
 static CROSS: Emoji<'_, '_> = Emoji("✗ ", "[X]");
 
+const SKIP_OPTION: &str = "Skip for now";
+
 /// Run the interactive setup wizard for first-time users
 pub async fn run_setup_wizard() -> Result<()> {
     use inquire::Select;
@@ -26,12 +33,15 @@ pub async fn run_setup_wizard() -> Result<()> {
     // Step 1: API Key Setup (only once)
     setup_api_key_interactive();
 
-    // Step 2: Database Initialize
+    // Step 2: Analysis LLM provider (only once)
+    setup_llm_provider_interactive().await?;
+
+    // Step 3: Database Initialize
     setup_database_initialize().await?;
 
-    // Step 3: Scan and Import Loop
+    // Step 4: Scan and Import Loop
     loop {
-        let detected = scan_chat_histories();
+        let detected = scan_chat_histories().await;
         let valid_providers = AutoDetectService::valid_providers(&detected);
 
         if !valid_providers.is_empty() {
@@ -96,6 +106,74 @@ pub async fn run_setup_wizard() -> Result<()> {
     Ok(())
 }
 
+/// Options for `retrochat setup --non-interactive`
+pub struct NonInteractiveSetupParams {
+    pub providers: Vec<Provider>,
+    pub llm: Option<LlmProvider>,
+    pub api_key_env: Option<String>,
+    pub skip_llm_check: bool,
+}
+
+/// Run the setup steps without prompts, failing instead of asking
+pub async fn run_non_interactive_setup(params: NonInteractiveSetupParams) -> Result<()> {
+    config::ensure_config_dir()?;
+    let db_path = config::get_default_db_path()?;
+    DatabaseManager::new(&db_path).await?;
+    println!("{} Database ready: {}", CHECK, db_path.display());
+
+    let api_key = match &params.api_key_env {
+        Some(var) => Some(
+            std::env::var(var)
+                .ok()
+                .map(|key| key.trim().to_string())
+                .filter(|key| !key.is_empty())
+                .with_context(|| format!("Environment variable {var} is not set"))?,
+        ),
+        None => None,
+    };
+
+    if let Some(provider) = params.llm {
+        if !params.skip_llm_check {
+            if let Err(e) = probe_provider(provider, api_key.clone()).await {
+                anyhow::bail!(
+                    "Test generation with {provider} failed: {e}\n{}",
+                    error_suggestion(provider, &e)
+                );
+            }
+            println!("{CHECK} {provider} answered a test prompt");
+        }
+    }
+
+    let mut retrochat_config = retrochat_core::config::Config::load()?;
+    if let Some(api_key) = api_key {
+        retrochat_config.set("google-ai-api-key", api_key)?;
+        println!("{CHECK} Google AI API key saved");
+    }
+    if let Some(provider) = params.llm {
+        retrochat_config.set("llm.provider", provider.to_string())?;
+        println!("{CHECK} Analysis provider set to {provider}");
+    }
+    retrochat_config.save()?;
+
+    let detected = AutoDetectService::valid_providers(&AutoDetectService::scan_all());
+    let import_all = params.providers.is_empty() || params.providers.contains(&Provider::All);
+    for requested in &params.providers {
+        if *requested != Provider::All && !detected.iter().any(|d| d.provider == *requested) {
+            println!("{CROSS} No chat history found for {requested}");
+        }
+    }
+    let selected: Vec<DetectedProvider> = detected
+        .into_iter()
+        .filter(|d| import_all || params.providers.contains(&d.provider))
+        .collect();
+
+    if selected.is_empty() {
+        println!("{CROSS} Nothing to import");
+        return Ok(());
+    }
+    import_selected_providers(selected).await
+}
+
 /// Step 1: API Key Setup (interactive, only once)
 fn setup_api_key_interactive() {
     use inquire::{Select, Text};
@@ -218,7 +296,160 @@ fn setup_api_key_interactive() {
     }
 }
 
-/// Step 2: Database Initialize
+/// Step 2: Pick the LLM provider for analyses, check it with a test
+/// generation and save it as `llm.provider`
+async fn setup_llm_provider_interactive() -> Result<()> {
+    use inquire::{Confirm, Select};
+
+    if let Some(provider) = retrochat_core::config::get_llm_provider()? {
+        println!(
+            "{} Analysis provider is already configured: {}",
+            style("✓").green(),
+            style(provider).cyan()
+        );
+        println!();
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        style("────────────────────────────────────────────────────────────────────────────").dim()
+    );
+    println!(
+        "  {} {}",
+        style("🤖").bold(),
+        style("Analysis Provider (Optional)").bold().cyan()
+    );
+    println!(
+        "{}",
+        style("────────────────────────────────────────────────────────────────────────────").dim()
+    );
+    println!();
+    println!("Choose which LLM runs session analyses and summaries.");
+    println!();
+
+    let mut options = Vec::new();
+    for (provider, available, status) in LlmClientFactory::list_available().await {
+        // The key may live in config.toml or have been entered just now
+        let available = available
+            || (provider == LlmProvider::GoogleAi
+                && retrochat_core::config::has_google_ai_api_key());
+        let status = if available {
+            style(status).green()
+        } else {
+            style(unavailable_suggestion(provider)).dim()
+        };
+        println!(
+            "  {} {} - {}",
+            if available { CHECK } else { CROSS },
+            style(provider).bold(),
+            status
+        );
+        // Google AI can always be picked; the key is asked for below
+        if available || provider == LlmProvider::GoogleAi {
+            options.push(provider.to_string());
+        }
+    }
+    println!();
+    options.push(SKIP_OPTION.to_string());
+
+    loop {
+        let choice =
+            match Select::new("Which provider should run analyses?", options.clone()).prompt() {
+                Ok(choice) if choice != SKIP_OPTION => choice,
+                _ => {
+                    print_llm_provider_skipped();
+                    return Ok(());
+                }
+            };
+        let provider: LlmProvider = choice.parse().map_err(|e: String| anyhow::anyhow!(e))?;
+
+        let api_key = if provider == LlmProvider::GoogleAi
+            && !retrochat_core::config::has_google_ai_api_key()
+        {
+            match prompt_google_ai_api_key() {
+                Some(key) => Some(key),
+                None => continue,
+            }
+        } else {
+            None
+        };
+
+        let spinner = ProgressBar::new_spinner();
+        spinner.set_message(format!("Sending a test prompt to {provider}..."));
+        spinner.enable_steady_tick(std::time::Duration::from_millis(100));
+        let probe = probe_provider(provider, api_key.clone()).await;
+        spinner.finish_and_clear();
+
+        match probe {
+            Ok((model, latency)) => {
+                println!(
+                    "{} {} answered in {:.1}s",
+                    style("✓").green(),
+                    style(&model).cyan(),
+                    latency.as_secs_f64()
+                );
+            }
+            Err(e) => {
+                println!("{} Test generation failed: {}", style("✗").red(), e);
+                println!("  💡 {}", error_suggestion(provider, &e));
+                println!();
+                let save_anyway = Confirm::new("Save this provider anyway?")
+                    .with_default(false)
+                    .prompt()
+                    .unwrap_or(false);
+                if !save_anyway {
+                    continue;
+                }
+            }
+        }
+
+        let mut config = retrochat_core::config::Config::load()?;
+        config.set("llm.provider", provider.to_string())?;
+        if let Some(api_key) = api_key {
+            config.set("google-ai-api-key", api_key)?;
+        }
+        config.save()?;
+
+        println!("{} Analysis provider saved", style("✓").green());
+        println!("  {}", style("~/.retrochat/config.toml").dim());
+        println!();
+        return Ok(());
+    }
+}
+
+/// Ask for a Google AI API key; `None` if the prompt was left empty
+fn prompt_google_ai_api_key() -> Option<String> {
+    use inquire::Text;
+
+    println!(
+        "💡 Get your key: {}",
+        style("https://aistudio.google.com/app/apikey").underlined()
+    );
+    match Text::new("Enter your Google AI API key:")
+        .with_help_message("Paste your API key; it is tested before it is saved")
+        .prompt()
+    {
+        Ok(key) if !key.trim().is_empty() => Some(key.trim().to_string()),
+        _ => {
+            println!("{}", style("Cancelled.").yellow());
+            println!();
+            None
+        }
+    }
+}
+
+fn print_llm_provider_skipped() {
+    println!();
+    println!("{}", style("Skipped. Configure later with:").dim());
+    println!(
+        "  {}",
+        style("retrochat config set llm.provider claude-code").cyan()
+    );
+    println!();
+}
+
+/// Step 3: Database Initialize
 async fn setup_database_initialize() -> Result<()> {
     config::ensure_config_dir()?;
     let db_path = config::get_default_db_path()?;
@@ -253,8 +484,8 @@ async fn setup_database_initialize() -> Result<()> {
     Ok(())
 }
 
-/// Step 3: Scan chat histories
-fn scan_chat_histories() -> Vec<DetectedProvider> {
+/// Step 4: Scan chat histories
+async fn scan_chat_histories() -> Vec<DetectedProvider> {
     println!(
         "{}",
         style("────────────────────────────────────────────────────────────────────────────").dim()
@@ -271,13 +502,23 @@ fn scan_chat_histories() -> Vec<DetectedProvider> {
     println!();
     println!("Scanning for chat histories...");
 
-    let detected = AutoDetectService::scan_all();
+    let mut detected = AutoDetectService::scan_all();
+    if let Err(e) = estimate_unimported(&mut detected).await {
+        tracing::warn!(error = %e, "Failed to compare chat histories with the database");
+    }
     display_detected_providers(&detected);
 
     detected
 }
 
-/// Step 4: Configure provider paths (returns true if user completed, false if cancelled)
+/// Count files the database has not seen, so a re-run of the wizard only
+/// highlights what is new
+async fn estimate_unimported(detected: &mut [DetectedProvider]) -> Result<()> {
+    let db_manager = DatabaseManager::new(&config::get_default_db_path()?).await?;
+    AutoDetectService::estimate_unimported(&db_manager, detected).await
+}
+
+/// Step 5: Configure provider paths (returns true if user completed, false if cancelled)
 fn configure_provider_paths() -> Result<bool> {
     use inquire::{Select, Text};
 
@@ -362,7 +603,19 @@ fn display_detected_providers(detected: &[DetectedProvider]) {
     for provider in detected {
         let status_icon = if provider.is_valid { CHECK } else { CROSS };
         let status_text = if provider.is_valid {
-            style(format!("{} sessions", provider.estimated_sessions)).green()
+            let mut text = format!("{} sessions", provider.estimated_sessions);
+            if let Some(unimported) = provider.unimported_sessions {
+                text.push_str(&format!(" ({unimported} new)"));
+            }
+            if let Some(newest) = provider.newest_modified {
+                text.push_str(&format!(
+                    ", last active {}",
+                    newest
+                        .with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M")
+                ));
+            }
+            style(text).green()
         } else {
             style("Not found".to_string()).dim()
         };
@@ -0,0 +1,83 @@
+use anyhow::{Context, Result};
+use uuid::Uuid;
+
+use retrochat_core::config::get_github_token;
+use retrochat_core::database::{
+    ChatSessionRepository, DatabaseManager, MessageRepository, ToolOperationRepository,
+};
+use retrochat_core::env::apis as env_apis;
+use retrochat_core::services::{render_share_html, upload_gist, ShareOptions};
+use retrochat_core::utils::anonymize::Anonymizer;
+
+/// Options for `retrochat share`
+pub struct ShareParams {
+    pub session_id: String,
+    pub output: Option<String>,
+    pub gist: bool,
+    pub no_anonymize: bool,
+    pub redact: Vec<String>,
+    pub thinking: bool,
+}
+
+/// Handle the share command
+pub async fn handle_share_command(params: ShareParams) -> Result<()> {
+    let session_uuid = Uuid::parse_str(&params.session_id).context("Invalid session ID format")?;
+
+    // Resolve the token before rendering so a missing one fails fast
+    let token = if params.gist {
+        Some(get_github_token()?.with_context(|| {
+            format!(
+                "Uploading a gist needs a GitHub token with the `gist` scope; set {} or `retrochat config set github-token <token>`",
+                env_apis::GITHUB_TOKEN
+            )
+        })?)
+    } else {
+        None
+    };
+
+    let db_path = retrochat_core::database::config::get_default_db_path()?;
+    let db_manager = DatabaseManager::new(&db_path).await?;
+    let session = ChatSessionRepository::new(&db_manager)
+        .get_by_id(&session_uuid)
+        .await?
+        .with_context(|| format!("Session not found: {}", params.session_id))?;
+    let messages = MessageRepository::new(&db_manager)
+        .get_by_session(&session_uuid)
+        .await?;
+    let tool_operations = ToolOperationRepository::new(&db_manager)
+        .get_by_session(&session_uuid)
+        .await?;
+
+    let anonymizer = (!params.no_anonymize).then(|| {
+        params
+            .redact
+            .iter()
+            .fold(Anonymizer::for_current_user(), |anonymizer, term| {
+                anonymizer.with_term(term)
+            })
+    });
+    let options = ShareOptions {
+        anonymizer,
+        include_thinking: params.thinking,
+    };
+    let html = render_share_html(&session, &messages, &tool_operations, &options);
+    let file_name = format!("retrochat-{}.html", &session.id.to_string()[..8]);
+
+    if let Some(token) = token {
+        let upload = upload_gist(&token, &file_name, "RetroChat session transcript", &html).await?;
+        if let Some(output) = &params.output {
+            std::fs::write(output, &html).with_context(|| format!("Failed to write {output}"))?;
+        }
+        println!("{}", upload.html_url);
+        println!("Raw HTML: {}", upload.raw_url);
+        println!("The gist is secret but readable by anyone with the link; delete it on GitHub to unshare.");
+    } else {
+        let output = params.output.unwrap_or(file_name);
+        std::fs::write(&output, &html).with_context(|| format!("Failed to write {output}"))?;
+        println!("Transcript written to {output}");
+    }
+    if options.anonymizer.is_some() {
+        println!("Anonymization is best effort; skim the transcript before sharing it.");
+    }
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use anyhow::Result;
+use serde_json::Value;
+use std::sync::Arc;
+
+use retrochat_core::database::DatabaseManager;
+use retrochat_core::services::{SqlQueryResult, SqlQueryService};
+
+/// Widest a table cell is allowed to be before it is cut
+const MAX_CELL_WIDTH: usize = 60;
+
+/// Handle the query command
+pub async fn handle_query_command(
+    sql: Option<String>,
+    schema: bool,
+    format: String,
+    limit: usize,
+) -> Result<()> {
+    if !matches!(format.as_str(), "table" | "json") {
+        anyhow::bail!("Unknown format: {format}. Valid options: table, json");
+    }
+
+    let db_path = retrochat_core::database::config::get_default_db_path()?;
+    let db_manager = Arc::new(DatabaseManager::new(&db_path).await?);
+    let service = SqlQueryService::new(db_manager);
+
+    if schema {
+        let entries = service.schema().await?;
+        if format == "json" {
+            println!("{}", serde_json::to_string_pretty(&entries)?);
+        } else {
+            for entry in entries {
+                println!("{};\n", entry.sql.trim());
+            }
+        }
+        return Ok(());
+    }
+
+    let sql = sql.ok_or_else(|| anyhow::anyhow!("Provide a SQL statement or --schema"))?;
+    let result = service.run(&sql, limit).await?;
+
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    } else {
+        print_table(&result);
+    }
+    Ok(())
+}
+
+fn print_table(result: &SqlQueryResult) {
+    if result.columns.is_empty() {
+        println!("(no rows)");
+        return;
+    }
+
+    let cells: Vec<Vec<String>> = result
+        .rows
+        .iter()
+        .map(|row| row.iter().map(format_cell).collect())
+        .collect();
+    let widths: Vec<usize> = result
+        .columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            cells
+                .iter()
+                .map(|row| row[i].chars().count())
+                .chain(std::iter::once(column.chars().count()))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let print_row = |values: &[String]| {
+        let line: Vec<String> = values
+            .iter()
+            .zip(&widths)
+            .map(|(value, width)| format!("{value:<width$}"))
+            .collect();
+        println!("{}", line.join(" | ").trim_end());
+    };
+
+    print_row(&result.columns);
+    println!(
+        "{}",
+        widths
+            .iter()
+            .map(|width| "-".repeat(*width))
+            .collect::<Vec<_>>()
+            .join("-+-")
+    );
+    for row in &cells {
+        print_row(row);
+    }
+
+    println!();
+    if result.truncated {
+        println!(
+            "{} rows shown (more available, raise --limit to see them)",
+            result.rows.len()
+        );
+    } else {
+        println!("{} rows", result.rows.len());
+    }
+}
+
+fn format_cell(value: &Value) -> String {
+    let text = match value {
+        Value::Null => "NULL".to_string(),
+        Value::String(s) => s.replace('\n', " "),
+        other => other.to_string(),
+    };
+    if text.chars().count() > MAX_CELL_WIDTH {
+        let cut: String = text.chars().take(MAX_CELL_WIDTH - 3).collect();
+        format!("{cut}...")
+    } else {
+        text
+    }
+}
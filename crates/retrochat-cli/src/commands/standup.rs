@@ -0,0 +1,40 @@
+use anyhow::{Context, Result};
+use std::sync::Arc;
+
+use retrochat_core::database::DatabaseManager;
+use retrochat_core::services::StandupService;
+use retrochat_core::utils::time_parser;
+
+/// Handle the standup command
+pub async fn handle_standup_command(
+    date: String,
+    no_llm: bool,
+    provider: Option<String>,
+    model: Option<String>,
+) -> Result<()> {
+    let date = time_parser::parse_time_spec(&date)
+        .with_context(|| format!("Invalid --date: {date}"))?
+        .date_naive();
+
+    let llm_client = if no_llm {
+        None
+    } else {
+        Some(
+            super::summarize::create_llm_client(provider, model)
+                .context("No LLM available to write the standup; pass --no-llm for a plain list")?,
+        )
+    };
+
+    let db_path = retrochat_core::database::config::get_default_db_path()?;
+    let db_manager = Arc::new(DatabaseManager::new(&db_path).await?);
+    let standup = StandupService::new(db_manager, llm_client)
+        .generate(date)
+        .await?;
+
+    print!("{}", standup.markdown);
+    if !no_llm && !standup.written_by_llm && !standup.facts.is_empty() {
+        eprintln!();
+        eprintln!("Note: the LLM request failed, so this is the plain activity list.");
+    }
+    Ok(())
+}
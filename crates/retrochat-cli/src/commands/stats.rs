@@ -0,0 +1,172 @@
+use anyhow::{Context, Result};
+
+use retrochat_core::database::{
+    DatabaseManager, MessageRepository, SessionQualityRepository, ToolOperationRepository,
+};
+use retrochat_core::models::session_quality::{LONG_PROMPT_CHARS, SHORT_PROMPT_CHARS};
+use retrochat_core::models::{QualitySummary, SessionQuality};
+use retrochat_core::services::analytics::calculate_language_metrics;
+use retrochat_core::utils::time_parser;
+
+/// Sessions listed under "most corrected"
+const MAX_SESSIONS_SHOWN: usize = 5;
+
+/// Handle the stats command
+pub async fn handle_stats_command(
+    since: Option<String>,
+    project: Option<String>,
+    languages: bool,
+    json: bool,
+) -> Result<()> {
+    let since = since
+        .as_deref()
+        .map(|spec| {
+            time_parser::parse_time_spec(spec).with_context(|| format!("Invalid --since: {spec}"))
+        })
+        .transpose()?;
+
+    let db_path = retrochat_core::database::config::get_default_db_path()?;
+    let db_manager = DatabaseManager::new(&db_path).await?;
+    let quality_repo = SessionQualityRepository::new(&db_manager);
+
+    let backfilled = backfill(&db_manager, &quality_repo).await?;
+    if backfilled > 0 && !json {
+        eprintln!("Computed quality signals for {backfilled} sessions imported earlier");
+    }
+
+    let sessions = quality_repo.list(since, project.as_deref()).await?;
+    if languages {
+        return print_languages(&db_manager, &sessions, json).await;
+    }
+    let summary = QualitySummary::from_sessions(&sessions);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&summary)?);
+        return Ok(());
+    }
+
+    if sessions.is_empty() {
+        println!("No sessions found.");
+        return Ok(());
+    }
+
+    let percent = |rate: f64| format!("{:.1}%", rate * 100.0);
+    println!("Conversation quality across {} sessions", summary.sessions);
+    println!();
+    println!("  Prompts:              {}", summary.user_prompts);
+    println!(
+        "  Corrections:          {} ({} of follow-up prompts)",
+        summary.user_corrections,
+        percent(summary.correction_rate())
+    );
+    println!(
+        "  Tool errors:          {} of {} ({})",
+        summary.tool_errors,
+        summary.tool_errors + summary.tool_successes,
+        percent(summary.tool_error_ratio())
+    );
+    println!(
+        "  Tool retry loops:     {} in {} sessions",
+        summary.tool_retry_loops, summary.sessions_with_retry_loops
+    );
+    println!(
+        "  Prompt lengths:       {} short (≤{SHORT_PROMPT_CHARS} chars), {} medium, {} long (>{LONG_PROMPT_CHARS} chars)",
+        summary.short_prompts, summary.medium_prompts, summary.long_prompts
+    );
+
+    let mut corrected: Vec<&SessionQuality> = sessions
+        .iter()
+        .filter(|quality| quality.user_corrections > 0 || quality.tool_retry_loops > 0)
+        .collect();
+    corrected.sort_by_key(|quality| {
+        std::cmp::Reverse((quality.user_corrections, quality.tool_retry_loops))
+    });
+    if !corrected.is_empty() {
+        println!();
+        println!("Most corrected sessions:");
+        for quality in corrected.iter().take(MAX_SESSIONS_SHOWN) {
+            println!(
+                "  {}  {} corrections, {} retry loops, {} tool errors",
+                quality.session_id,
+                quality.user_corrections,
+                quality.tool_retry_loops,
+                quality.tool_errors
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Files touched and lines changed per language across `sessions`
+async fn print_languages(
+    db_manager: &DatabaseManager,
+    sessions: &[SessionQuality],
+    json: bool,
+) -> Result<()> {
+    let tool_operation_repo = ToolOperationRepository::new(db_manager);
+    let mut operations = Vec::new();
+    for quality in sessions {
+        operations.extend(
+            tool_operation_repo
+                .get_by_session(&quality.session_id)
+                .await?,
+        );
+    }
+    let languages = calculate_language_metrics(&operations);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&languages)?);
+        return Ok(());
+    }
+    if languages.is_empty() {
+        println!("No file changes found.");
+        return Ok(());
+    }
+
+    println!(
+        "File changes by language across {} sessions",
+        sessions.len()
+    );
+    println!();
+    println!(
+        "  {:<18} {:>7} {:>9} {:>9}",
+        "Language", "Files", "Added", "Removed"
+    );
+    for language in &languages {
+        println!(
+            "  {:<18} {:>7} {:>9} {:>9}",
+            language.language,
+            language.files_touched,
+            format!("+{}", language.lines_added),
+            format!("-{}", language.lines_removed)
+        );
+    }
+    Ok(())
+}
+
+/// Compute signals for sessions imported before they existed
+async fn backfill(
+    db_manager: &DatabaseManager,
+    quality_repo: &SessionQualityRepository,
+) -> Result<usize> {
+    let missing = quality_repo.sessions_without_quality().await?;
+    if missing.is_empty() {
+        return Ok(0);
+    }
+
+    let message_repo = MessageRepository::new(db_manager);
+    let tool_operation_repo = ToolOperationRepository::new(db_manager);
+    let _write_guard = db_manager.write_lock().await?;
+    for session_id in &missing {
+        let messages = message_repo.get_by_session(session_id).await?;
+        let operations = tool_operation_repo.get_by_session(session_id).await?;
+        quality_repo
+            .upsert(&SessionQuality::from_messages(
+                *session_id,
+                &messages,
+                &operations,
+            ))
+            .await?;
+    }
+    Ok(missing.len())
+}
@@ -8,23 +8,23 @@ use retrochat_core::services::llm::{LlmClientFactory, LlmConfig, LlmProvider};
 use retrochat_core::services::{SessionSummarizer, TurnDetector, TurnSummarizer};
 
 /// Create an LLM client based on provider/model flags or environment variables
-fn create_llm_client(
+pub(crate) fn create_llm_client(
     provider: Option<String>,
     model: Option<String>,
 ) -> Result<Arc<dyn retrochat_core::services::llm::LlmClient>> {
-    // Determine provider from flag, env var, or default
+    // Determine provider from flag, env var, config file, or default
     let llm_provider: LlmProvider = if let Some(p) = provider.as_deref() {
         p.parse::<LlmProvider>()
             .map_err(|e| anyhow::anyhow!("{e}"))?
-    } else if let Ok(p) = std::env::var(env_llm::RETROCHAT_LLM_PROVIDER) {
-        p.parse::<LlmProvider>()
-            .map_err(|e| anyhow::anyhow!("{e}"))?
     } else {
-        LlmProvider::GoogleAi
+        retrochat_core::config::get_llm_provider()?.unwrap_or(LlmProvider::GoogleAi)
     };
 
-    // Determine model from flag or env var
-    let model_name = model.or_else(|| std::env::var(env_llm::RETROCHAT_LLM_MODEL).ok());
+    // Determine model from flag, env var or config file
+    let model_name = match model {
+        Some(model) => Some(model),
+        None => retrochat_core::config::get_llm_model()?,
+    };
 
     // Build config based on provider
     let mut config = match llm_provider {
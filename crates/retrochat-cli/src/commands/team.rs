@@ -0,0 +1,92 @@
+use anyhow::Result;
+use std::path::Path;
+use std::sync::Arc;
+
+use retrochat_core::database::DatabaseManager;
+use retrochat_core::services::{OwnerStats, TeamService};
+
+async fn create_service() -> Result<TeamService> {
+    let db_path = retrochat_core::database::config::get_default_db_path()?;
+    let db_manager = Arc::new(DatabaseManager::new(&db_path).await?);
+    Ok(TeamService::new(db_manager))
+}
+
+/// Handle the team import command
+pub async fn handle_import_command(file: String, owner: String) -> Result<()> {
+    let file = Path::new(&file);
+    if !file.exists() {
+        anyhow::bail!("File not found: {}", file.display());
+    }
+    let report = create_service().await?.import(file, &owner).await?;
+    println!(
+        "Imported {}'s export: {} sessions added, {} updated, {} kept",
+        owner.trim(),
+        report.added,
+        report.updated,
+        report.kept
+    );
+    if report.added + report.updated > 0 {
+        println!("Filter them with `owner:{}` or `owner:team`", owner.trim());
+    }
+    Ok(())
+}
+
+/// Handle the team remove command
+pub async fn handle_remove_command(owner: String) -> Result<()> {
+    let removed = create_service().await?.remove(&owner).await?;
+    if removed == 0 {
+        anyhow::bail!("No sessions imported for {owner}; see `retrochat team stats`");
+    }
+    println!("Removed {removed} sessions imported for {owner}");
+    Ok(())
+}
+
+/// Handle the team stats command
+pub async fn handle_stats_command(json: bool) -> Result<()> {
+    let stats = create_service().await?.stats().await?;
+    let teammates: Vec<&OwnerStats> = stats.iter().filter(|row| row.owner.is_some()).collect();
+    let team_total = OwnerStats::combine(Some("team".to_string()), teammates.iter().copied());
+
+    if json {
+        let mut rows = stats.clone();
+        if !teammates.is_empty() {
+            rows.push(team_total);
+        }
+        println!("{}", serde_json::to_string_pretty(&rows)?);
+        return Ok(());
+    }
+
+    if teammates.is_empty() {
+        println!("No teammates' sessions. Import an export with: retrochat team import <file> --owner <name>");
+    }
+    println!(
+        "{:<16} {:>8} {:>9} {:>12} {:>9} {:>8}  PROVIDERS",
+        "OWNER", "SESSIONS", "MESSAGES", "TOKENS", "TOOL USES", "FAILED"
+    );
+    let mut rows: Vec<&OwnerStats> = stats.iter().collect();
+    if teammates.len() > 1 {
+        rows.push(&team_total);
+    }
+    for row in rows {
+        let failure_rate = row
+            .tool_failure_rate()
+            .map(|rate| format!("{:.1}%", rate * 100.0))
+            .unwrap_or_else(|| "-".to_string());
+        let providers = row
+            .providers
+            .iter()
+            .map(|(provider, sessions)| format!("{provider} {sessions}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        println!(
+            "{:<16} {:>8} {:>9} {:>12} {:>9} {:>8}  {providers}",
+            row.owner.as_deref().unwrap_or("me"),
+            row.sessions,
+            row.messages,
+            row.tokens,
+            row.tool_uses,
+            failure_rate
+        );
+    }
+    Ok(())
+}
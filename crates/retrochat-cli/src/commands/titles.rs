@@ -0,0 +1,55 @@
+use anyhow::Result;
+use std::sync::Arc;
+use std::time::Duration;
+
+use retrochat_core::database::DatabaseManager;
+use retrochat_core::services::{SessionTitler, TitleBackfillOptions};
+
+use super::summarize::create_llm_client;
+
+/// Parameters for the titles generate command
+pub struct TitlesGenerateParams {
+    pub missing_only: bool,
+    pub provider: Option<String>,
+    pub model: Option<String>,
+    pub batch_size: usize,
+    pub delay_ms: u64,
+    pub limit: Option<usize>,
+}
+
+/// Handle the titles generate command
+pub async fn handle_titles_generate(params: TitlesGenerateParams) -> Result<()> {
+    let db_path = retrochat_core::database::config::get_default_db_path()?;
+    let db_manager = Arc::new(DatabaseManager::new(&db_path).await?);
+
+    let llm_client = create_llm_client(params.provider, params.model)?;
+    let titler = SessionTitler::new(&db_manager, llm_client);
+
+    let options = TitleBackfillOptions {
+        missing_only: params.missing_only,
+        batch_size: params.batch_size,
+        delay: Duration::from_millis(params.delay_ms),
+        limit: params.limit,
+    };
+
+    let report = titler
+        .backfill(&options, |session_id, result| match result {
+            Ok(title) => println!("{session_id}  {title}"),
+            Err(e) => println!("{session_id}  FAILED: {e}"),
+        })
+        .await?;
+
+    if report.candidates == 0 {
+        println!("No sessions need a title");
+        return Ok(());
+    }
+
+    println!(
+        "\nCompleted: {} titled, {} errors",
+        report.titled, report.failed
+    );
+    if report.failed > 0 {
+        println!("Run the command again to retry failed sessions");
+    }
+    Ok(())
+}
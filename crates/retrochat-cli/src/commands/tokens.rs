@@ -0,0 +1,53 @@
+use anyhow::Result;
+
+use retrochat_core::database::DatabaseManager;
+use retrochat_core::services::TokenEstimationService;
+
+async fn create_service() -> Result<TokenEstimationService> {
+    let db_path = retrochat_core::database::config::get_default_db_path()?;
+    let db_manager = DatabaseManager::new(&db_path).await?;
+    Ok(TokenEstimationService::new(&db_manager))
+}
+
+/// Handle the tokens backfill command
+pub async fn handle_backfill_command(force: bool) -> Result<()> {
+    let report = create_service().await?.backfill(force).await?;
+    if report.messages_estimated == 0 {
+        println!("Every message already has a token estimate.");
+        return Ok(());
+    }
+    println!(
+        "Estimated tokens for {} messages in {} sessions.",
+        report.messages_estimated, report.sessions_updated
+    );
+    Ok(())
+}
+
+/// Handle the tokens accuracy command
+pub async fn handle_accuracy_command() -> Result<()> {
+    let accuracy = create_service().await?.accuracy().await?;
+    if accuracy.is_empty() {
+        println!("No messages have both reported usage and an estimate to compare.");
+        println!("Run `retrochat tokens backfill` if older messages have no estimate.");
+        return Ok(());
+    }
+
+    println!(
+        "{:<16} {:>9} {:>12} {:>12} {:>8} {:>8}",
+        "Provider", "Messages", "Reported", "Estimated", "Ratio", "Error"
+    );
+    for entry in &accuracy {
+        println!(
+            "{:<16} {:>9} {:>12} {:>12} {:>8.2} {:>7.1}%",
+            entry.provider,
+            entry.messages,
+            entry.reported_tokens,
+            entry.estimated_tokens,
+            entry.ratio(),
+            entry.error_percentage()
+        );
+    }
+    println!();
+    println!("Ratio is estimated / reported tokens; error is the per-message difference.");
+    Ok(())
+}
@@ -0,0 +1,140 @@
+use anyhow::{Context, Result};
+use chrono::Duration;
+
+use retrochat_core::database::DatabaseManager;
+use retrochat_core::services::{TopicReport, TopicService};
+use retrochat_core::utils::locale::Language;
+
+/// Topics older than this are re-clustered after a sync
+const TOPIC_REFRESH_DAYS: i64 = 7;
+
+async fn open_database() -> Result<DatabaseManager> {
+    let db_path = retrochat_core::database::config::get_default_db_path()?;
+    DatabaseManager::new(&db_path).await
+}
+
+/// Handle the topics list command
+pub async fn handle_list_command() -> Result<()> {
+    let db_manager = open_database().await?;
+    let topics = TopicService::new(&db_manager, None).list().await?;
+    if topics.is_empty() {
+        println!("No topics yet. Build them with: retrochat topics refresh");
+        println!("(sessions need summaries in the embedding index; see `retrochat index`)");
+        return Ok(());
+    }
+
+    let width = topics
+        .iter()
+        .map(|t| t.label.chars().count())
+        .max()
+        .unwrap_or(0);
+    for topic in &topics {
+        println!(
+            "{:>4}  {:<width$}  {:>4} sessions",
+            topic.id, topic.label, topic.session_count
+        );
+    }
+    println!();
+    println!(
+        "Clustered {} UTC",
+        Language::current().format_datetime(&topics[0].clustered_at)
+    );
+    Ok(())
+}
+
+/// Handle the topics show command
+pub async fn handle_show_command(
+    topic: String,
+    page: Option<i32>,
+    page_size: Option<i32>,
+) -> Result<()> {
+    let db_manager = open_database().await?;
+    let topic = TopicService::new(&db_manager, None)
+        .find(&topic)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("No topic with ID or label '{topic}'"))?;
+
+    println!("Topic {}: {}", topic.id, topic.label);
+    if let Some(description) = &topic.description {
+        println!("  {description}");
+    }
+    println!(
+        "  {} sessions, labelled by {}",
+        topic.session_count, topic.label_source
+    );
+    println!();
+
+    super::query::handle_sessions_command(
+        page,
+        page_size,
+        None,
+        None,
+        None,
+        false,
+        Some(format!("topic:{}", topic.id)),
+    )
+    .await
+}
+
+/// Handle the topics refresh command
+pub async fn handle_refresh_command(
+    clusters: Option<usize>,
+    no_llm: bool,
+    provider: Option<String>,
+    model: Option<String>,
+) -> Result<()> {
+    if clusters == Some(0) {
+        anyhow::bail!("--clusters must be at least 1");
+    }
+
+    let llm_client = if no_llm {
+        None
+    } else {
+        Some(
+            super::summarize::create_llm_client(provider, model).context(
+                "No LLM available for topic labels; pass --no-llm to use title keywords",
+            )?,
+        )
+    };
+
+    let db_manager = open_database().await?;
+    let service = TopicService::new(&db_manager, llm_client);
+    println!("Clustering sessions into topics...");
+    let report = service.refresh(clusters).await?;
+    print_report(&report);
+    Ok(())
+}
+
+/// Re-cluster stale topics after a sync, labelling new topics with title
+/// keywords; failures only produce a warning
+pub async fn topics_after_sync() {
+    let result = match open_database().await {
+        Ok(db_manager) => {
+            TopicService::new(&db_manager, None)
+                .refresh_if_stale(Duration::days(TOPIC_REFRESH_DAYS))
+                .await
+        }
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(Some(report)) if report.topics > 0 => {
+            println!();
+            print_report(&report);
+        }
+        Ok(_) => {}
+        Err(e) => eprintln!("Warning: failed to refresh topics: {e}"),
+    }
+}
+
+fn print_report(report: &TopicReport) {
+    if report.topics == 0 {
+        println!("Not enough indexed session summaries to cluster; run `retrochat index` first.");
+        return;
+    }
+    println!("Topics updated:");
+    println!("  Topics: {}", report.topics);
+    println!("  Sessions assigned: {}", report.sessions);
+    println!("  Kept from last run: {}", report.carried_over);
+    println!("  New, labelled by LLM: {}", report.labeled_by_llm);
+}
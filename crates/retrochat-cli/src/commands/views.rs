@@ -0,0 +1,79 @@
+use anyhow::Result;
+
+use retrochat_core::database::{DatabaseManager, SavedViewRepository};
+use retrochat_core::utils::filter_expr::FilterExpr;
+
+async fn create_repo() -> Result<SavedViewRepository> {
+    let db_path = retrochat_core::database::config::get_default_db_path()?;
+    let db_manager = DatabaseManager::new(&db_path).await?;
+    Ok(SavedViewRepository::new(&db_manager))
+}
+
+/// Handle the views save command
+pub async fn handle_save_command(name: String, expression: String) -> Result<()> {
+    let name = name.trim();
+    if name.is_empty() {
+        anyhow::bail!("View name cannot be empty");
+    }
+    if FilterExpr::parse(&expression)?.is_empty() {
+        anyhow::bail!("Filter expression cannot be empty");
+    }
+
+    let view = create_repo().await?.save(name, expression.trim()).await?;
+    println!("Saved view '{}': {}", view.name, view.expression);
+    Ok(())
+}
+
+/// Handle the views list command
+pub async fn handle_list_command() -> Result<()> {
+    let views = create_repo().await?.list().await?;
+    if views.is_empty() {
+        println!("No saved views. Create one with: retrochat views save <name> <expression>");
+        return Ok(());
+    }
+
+    let width = views
+        .iter()
+        .map(|v| v.name.chars().count())
+        .max()
+        .unwrap_or(0);
+    for view in views {
+        println!("{:<width$}  {}", view.name, view.expression);
+    }
+    Ok(())
+}
+
+/// Handle the views run command
+pub async fn handle_run_command(
+    name: String,
+    page: Option<i32>,
+    page_size: Option<i32>,
+) -> Result<()> {
+    let view = create_repo()
+        .await?
+        .get_by_name(&name)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("No saved view named '{name}'"))?;
+
+    println!("View '{}': {}", view.name, view.expression);
+    super::query::handle_sessions_command(
+        page,
+        page_size,
+        None,
+        None,
+        None,
+        false,
+        Some(view.expression),
+    )
+    .await
+}
+
+/// Handle the views delete command
+pub async fn handle_delete_command(name: String) -> Result<()> {
+    if create_repo().await?.delete(&name).await? {
+        println!("Deleted view '{name}'");
+        Ok(())
+    } else {
+        anyhow::bail!("No saved view named '{name}'")
+    }
+}
@@ -1,8 +1,17 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use crossterm::style::{Color, Stylize};
+use std::sync::Arc;
 
+use retrochat_core::config::Config;
+use retrochat_core::database::DatabaseManager;
+use retrochat_core::env::database as db_env;
 use retrochat_core::models::Provider;
-use retrochat_core::services::{collect_provider_paths, watch_paths_for_changes};
+use retrochat_core::services::background_service::{self, ServiceManager, WatchServiceSpec};
+use retrochat_core::services::import_ignore;
+use retrochat_core::services::{
+    collect_configured_targets, collect_provider_paths, watch_paths_for_changes, IgnoreRules,
+    ImportService, WatchTarget,
+};
 
 pub async fn handle_watch_command(
     path: Option<String>,
@@ -10,32 +19,108 @@ pub async fn handle_watch_command(
     verbose: bool,
     import: bool,
 ) -> Result<()> {
-    if import {
-        println!(
-            "{} {} {}",
-            "ℹ️".with(Color::Blue),
-            "Note:".with(Color::Blue).bold(),
-            "Auto-import feature is not yet implemented".with(Color::DarkGrey)
-        );
-    }
+    let config = Config::load()?;
 
-    // Collect paths to watch
-    let watch_paths = if let Some(path) = path {
-        vec![path]
-    } else if providers.is_empty() {
-        return Err(anyhow::anyhow!(
-            "Please specify either --path or one or more providers to watch"
-        ));
+    // Collect paths to watch: an explicit path, or provider defaults plus `watch.paths`
+    let watch_targets = if let Some(path) = path {
+        vec![WatchTarget::new(path)]
     } else {
-        collect_provider_paths(&providers)?
+        let mut targets: Vec<WatchTarget> = collect_provider_paths(&providers)?
+            .into_iter()
+            .map(WatchTarget::new)
+            .collect();
+        targets.extend(collect_configured_targets(&config.watch));
+        targets
     };
 
-    if watch_paths.is_empty() {
+    if watch_targets.is_empty() {
         return Err(anyhow::anyhow!(
-            "No paths to watch. Please specify --path or valid providers."
+            "No paths to watch. Please specify --path, valid providers, or configure watch.paths."
         ));
     }
 
+    // Files kept out of imports are not worth watching either
+    let mut ignore_globs = config.watch.ignore.clone();
+    ignore_globs.extend(import_ignore::path_globs(&config)?);
+    let ignore = IgnoreRules::new(&ignore_globs)?;
+
+    let importer = if import {
+        let db_path = retrochat_core::database::config::get_default_db_path()?;
+        let db_manager = Arc::new(DatabaseManager::new(&db_path).await?);
+        Some(Arc::new(ImportService::new(db_manager)))
+    } else {
+        None
+    };
+
     // Start watching
-    watch_paths_for_changes(watch_paths, verbose).await
+    watch_paths_for_changes(watch_targets, ignore, verbose, importer).await
+}
+
+pub fn handle_install_service_command(providers: Vec<Provider>, print: bool) -> Result<()> {
+    let manager = ServiceManager::current()?;
+    let program = std::env::current_exe().context("Failed to locate the retrochat binary")?;
+    let spec = WatchServiceSpec::new(program, &providers)?;
+
+    if print {
+        match manager {
+            ServiceManager::Systemd => print!("{}", spec.systemd_unit()),
+            ServiceManager::Launchd => print!("{}", spec.launchd_plist()),
+        }
+        return Ok(());
+    }
+
+    let path = background_service::install(manager, &spec)?;
+
+    println!(
+        "{} {}",
+        "✓".with(Color::Green),
+        format!("Installed the watch service ({manager})").with(Color::Green)
+    );
+    println!("  File:    {}", path.display());
+    println!(
+        "  Command: {} {}",
+        spec.program.display(),
+        spec.args.join(" ")
+    );
+    if !spec.env.is_empty() {
+        let names: Vec<&str> = spec.env.iter().map(|(name, _)| name.as_str()).collect();
+        println!("  Environment: {}", names.join(", "));
+    }
+    match manager {
+        ServiceManager::Systemd => println!(
+            "  Logs:    journalctl --user -u {}",
+            background_service::SYSTEMD_UNIT_NAME
+        ),
+        ServiceManager::Launchd => println!("  Logs:    {}", spec.log_path.display()),
+    }
+
+    if std::env::var_os(db_env::DB_PASSPHRASE).is_some() {
+        println!(
+            "\n{} {}",
+            "⚠️".with(Color::Yellow),
+            format!(
+                "{} is not copied into the service; it reads the passphrase from the OS keychain.",
+                db_env::DB_PASSPHRASE
+            )
+            .with(Color::Yellow)
+        );
+    }
+
+    Ok(())
+}
+
+pub fn handle_uninstall_service_command() -> Result<()> {
+    let manager = ServiceManager::current()?;
+
+    match background_service::uninstall(manager)? {
+        Some(path) => println!(
+            "{} {} {}",
+            "✓".with(Color::Green),
+            "Removed the watch service:".with(Color::Green),
+            path.display()
+        ),
+        None => println!("The watch service is not installed."),
+    }
+
+    Ok(())
 }
@@ -1,5 +1,4 @@
 use clap::Parser;
-use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::runtime::Runtime;
 
@@ -9,35 +8,33 @@ use commands::{Cli, Commands};
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
 
-    // Configure logging based on command
+    // Configure logging based on command: [logging] in config.toml, then
+    // RETROCHAT_LOG_* environment variables
+    let logging_config = retrochat_core::logging::LoggingConfig::load();
     let logging_config = match &cli.command {
         None => {
             // For TUI (default): log to file only, no stdout
             // Use same directory as DB (~/.retrochat/logs)
-            let config_dir = retrochat_core::database::config::get_config_dir()
-                .unwrap_or_else(|_| PathBuf::from("."));
-            let log_dir = config_dir.join("logs");
-            std::fs::create_dir_all(&log_dir)?;
-
-            let log_file = log_dir.join(format!(
-                "retrochat-{}.log",
-                chrono::Local::now().format("%Y%m%d")
-            ));
-
-            retrochat_core::logging::LoggingConfig::from_env()
+            logging_config
                 .with_stdout(false) // Critical: disable stdout for TUI
-                .with_file(log_file)
+                .with_default_file()?
+        }
+        Some(Commands::Sync { .. }) => {
+            // Imports and watch mode also keep a log for `retrochat logs tail`
+            logging_config.with_default_file()?
         }
         Some(Commands::List { .. })
         | Some(Commands::Show { .. })
         | Some(Commands::Search { .. })
-        | Some(Commands::Export { .. }) => {
+        | Some(Commands::Export { .. })
+        | Some(Commands::Logs { .. })
+        | Some(Commands::Doctor { .. }) => {
             // For query/output commands: disable stdout to keep output clean
-            retrochat_core::logging::LoggingConfig::from_env().with_stdout(false)
+            logging_config.with_stdout(false)
         }
         _ => {
             // For other CLI commands: stdout is safe
-            retrochat_core::logging::LoggingConfig::from_env()
+            logging_config
         }
     };
 
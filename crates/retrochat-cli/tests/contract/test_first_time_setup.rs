@@ -96,7 +96,15 @@ async fn test_first_time_setup_workflow() {
                 date_range: None,
                 min_messages: None,
                 max_messages: None,
+                include_archived: false,
+                expression: None,
+                topic: None,
+                owner: None,
+                providers: None,
+                projects: None,
+                topics: None,
             }),
+            after: None,
         })
         .await;
 
@@ -180,6 +188,7 @@ async fn test_query_service_basic_functionality() {
             sort_by: None,
             sort_order: None,
             filters: None,
+            after: None,
         })
         .await;
 
@@ -198,7 +207,15 @@ async fn test_query_service_basic_functionality() {
                 date_range: None,
                 min_messages: Some(1),
                 max_messages: Some(100),
+                include_archived: false,
+                expression: None,
+                topic: None,
+                owner: None,
+                providers: None,
+                projects: None,
+                topics: None,
             }),
+            after: None,
         })
         .await;
 
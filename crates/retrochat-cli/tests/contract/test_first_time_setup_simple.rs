@@ -69,7 +69,15 @@ async fn test_first_time_setup_basic() {
             date_range: None,
             min_messages: None,
             max_messages: None,
+            include_archived: false,
+            expression: None,
+            topic: None,
+            owner: None,
+            providers: None,
+            projects: None,
+            topics: None,
         }),
+        after: None,
     }).await;
 
     assert!(sessions_result.is_ok());
@@ -101,6 +109,7 @@ async fn test_query_service_basic() {
         sort_by: None,
         sort_order: None,
         filters: None,
+        after: None,
     }).await;
 
     assert!(sessions_result.is_ok());
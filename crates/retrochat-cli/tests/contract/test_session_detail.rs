@@ -60,7 +60,15 @@ async fn test_session_detail_basic_workflow() {
                 date_range: None,
                 min_messages: None,
                 max_messages: None,
+                include_archived: false,
+                expression: None,
+                topic: None,
+                owner: None,
+                providers: None,
+                projects: None,
+                topics: None,
             }),
+            after: None,
         })
         .await;
 
@@ -135,7 +143,15 @@ async fn test_session_filtering_and_pagination() {
                 date_range: None,
                 min_messages: Some(1),
                 max_messages: None,
+                include_archived: false,
+                expression: None,
+                topic: None,
+                owner: None,
+                providers: None,
+                projects: None,
+                topics: None,
             }),
+            after: None,
         })
         .await;
 
@@ -149,6 +165,7 @@ async fn test_session_filtering_and_pagination() {
             sort_by: Some("created_at".to_string()),
             sort_order: Some("asc".to_string()),
             filters: None,
+            after: None,
         })
         .await;
 
@@ -34,6 +34,7 @@ async fn test_session_detail_basic() {
             sort_by: None,
             sort_order: None,
             filters: None,
+            after: None,
         }).await;
 
         assert!(sessions_result.is_ok());
@@ -70,7 +71,15 @@ async fn test_session_detail_with_filters() {
             date_range: None,
             min_messages: None,
             max_messages: None,
+            include_archived: false,
+            expression: None,
+            topic: None,
+            owner: None,
+            providers: None,
+            projects: None,
+            topics: None,
         }),
+        after: None,
     }).await;
 
     assert!(sessions_result.is_ok());
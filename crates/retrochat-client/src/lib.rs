@@ -0,0 +1,304 @@
+//! Typed client API for RetroChat
+//!
+//! A small, stable facade over `retrochat-core` for Rust tools that want to
+//! read the agent history retrochat has imported, or analyze a session,
+//! without depending on the CLI, TUI or desktop app or on repository
+//! internals:
+//!
+//! ```no_run
+//! # async fn run() -> anyhow::Result<()> {
+//! use retrochat_client::{ListOptions, RetrochatClient};
+//!
+//! let client = RetrochatClient::open_default().await?;
+//! let page = client
+//!     .list(ListOptions {
+//!         filter: Some("since:\"last week\" tool:Bash(failed)".to_string()),
+//!         ..Default::default()
+//!     })
+//!     .await?;
+//! for session in page.sessions {
+//!     let detail = client.get(&session.session_id).await?;
+//!     println!("{}: {} messages", session.session_id, detail.map_or(0, |d| d.messages.len()));
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use retrochat_core::database::{ChatSessionRepository, ToolOperationRepository};
+use retrochat_core::services::{
+    AnalyticsRequestService, QueryService, SearchRequest, SessionCursor, SessionDetailRequest,
+    SessionFilters, SessionsQueryRequest,
+};
+
+pub use retrochat_core::database::DatabaseManager;
+pub use retrochat_core::models::message::MessageType;
+pub use retrochat_core::models::{
+    Analytics, Attachment, ChatSession, Message, MessageRole, Provider, ToolOperation,
+};
+pub use retrochat_core::services::llm::{LlmClient, LlmClientFactory, LlmConfig, LlmProvider};
+pub use retrochat_core::services::{SearchResult, SessionSummary};
+
+/// Sessions per page when no limit is given
+const DEFAULT_PAGE_SIZE: u32 = 50;
+
+/// Which sessions [`RetrochatClient::list`] returns
+#[derive(Debug, Clone, Default)]
+pub struct ListOptions {
+    /// Filter expression as accepted by `retrochat list --filter`
+    pub filter: Option<String>,
+    pub provider: Option<String>,
+    pub project: Option<String>,
+    /// Include archived sessions
+    pub include_archived: bool,
+    /// Sessions per page (50 by default)
+    pub limit: Option<u32>,
+    /// [`SessionPage::next_cursor`] of the previous page
+    pub after: Option<String>,
+}
+
+/// One page of sessions, newest first
+#[derive(Debug)]
+pub struct SessionPage {
+    pub sessions: Vec<SessionSummary>,
+    /// Matching sessions on all pages; 0 when paging with a cursor
+    pub total_count: u32,
+    /// Pass as [`ListOptions::after`] to get the next page; `None` on the last one
+    pub next_cursor: Option<String>,
+}
+
+/// Which messages [`RetrochatClient::search`] returns
+#[derive(Debug, Clone, Default)]
+pub struct SearchOptions {
+    /// Only these providers (all when empty)
+    pub providers: Vec<String>,
+    /// Only these projects (all when empty)
+    pub projects: Vec<String>,
+    /// Results returned (50 by default)
+    pub limit: Option<u32>,
+}
+
+/// A session with everything recorded for it
+#[derive(Debug, Clone)]
+pub struct SessionDetail {
+    pub session: ChatSession,
+    /// In conversation order
+    pub messages: Vec<Message>,
+    pub tool_operations: Vec<ToolOperation>,
+    pub attachments: Vec<Attachment>,
+}
+
+/// Read and analyze the retrochat database
+#[derive(Clone)]
+pub struct RetrochatClient {
+    db_manager: Arc<DatabaseManager>,
+    query_service: Arc<QueryService>,
+}
+
+impl RetrochatClient {
+    /// Open the database the `retrochat` CLI uses
+    /// (`RETROCHAT_DB` or the default data directory)
+    pub async fn open_default() -> Result<Self> {
+        let db_path = retrochat_core::database::config::get_default_db_path()?;
+        Self::open(db_path).await
+    }
+
+    /// Open a database file, creating and migrating it if needed
+    pub async fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let db_manager = DatabaseManager::new(path)
+            .await
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        Ok(Self::with_database(Arc::new(db_manager)))
+    }
+
+    /// Use an already opened database
+    pub fn with_database(db_manager: Arc<DatabaseManager>) -> Self {
+        Self {
+            query_service: Arc::new(QueryService::with_database(db_manager.clone())),
+            db_manager,
+        }
+    }
+
+    /// Sessions matching `options`, newest first
+    pub async fn list(&self, options: ListOptions) -> Result<SessionPage> {
+        let after = options
+            .after
+            .as_deref()
+            .map(str::parse::<SessionCursor>)
+            .transpose()?;
+        let response = self
+            .query_service
+            .query_sessions(SessionsQueryRequest {
+                page: None,
+                page_size: Some(options.limit.unwrap_or(DEFAULT_PAGE_SIZE) as i32),
+                sort_by: None,
+                sort_order: None,
+                filters: Some(SessionFilters {
+                    provider: options.provider,
+                    project: options.project,
+                    date_range: None,
+                    min_messages: None,
+                    max_messages: None,
+                    include_archived: options.include_archived,
+                    expression: options.filter,
+                    topic: None,
+                    owner: None,
+                    providers: None,
+                    projects: None,
+                    topics: None,
+                }),
+                after,
+            })
+            .await?;
+        Ok(SessionPage {
+            sessions: response.sessions,
+            total_count: response.total_count.max(0) as u32,
+            next_cursor: response.next_cursor.map(|cursor| cursor.to_string()),
+        })
+    }
+
+    /// Messages matching a full-text `query`, best matches first
+    pub async fn search(&self, query: &str, options: SearchOptions) -> Result<Vec<SearchResult>> {
+        let non_empty = |values: Vec<String>| (!values.is_empty()).then_some(values);
+        let response = self
+            .query_service
+            .search_messages(SearchRequest {
+                query: query.to_string(),
+                providers: non_empty(options.providers),
+                projects: non_empty(options.projects),
+                date_range: None,
+                search_type: None,
+                page: None,
+                page_size: Some(options.limit.unwrap_or(DEFAULT_PAGE_SIZE) as i32),
+            })
+            .await?;
+        Ok(response.results)
+    }
+
+    /// A session with its messages, tool calls and attachments, or `None`
+    /// if there is no such session
+    pub async fn get(&self, session_id: &str) -> Result<Option<SessionDetail>> {
+        let id = Uuid::parse_str(session_id)
+            .with_context(|| format!("Invalid session ID: {session_id}"))?;
+        if ChatSessionRepository::new(&self.db_manager)
+            .get_by_id(&id)
+            .await?
+            .is_none()
+        {
+            return Ok(None);
+        }
+
+        let detail = self
+            .query_service
+            .get_session_detail(SessionDetailRequest {
+                session_id: session_id.to_string(),
+                include_content: Some(true),
+                message_limit: None,
+                message_offset: None,
+            })
+            .await?;
+        let tool_operations = ToolOperationRepository::new(&self.db_manager)
+            .get_by_session(&id)
+            .await?;
+        Ok(Some(SessionDetail {
+            session: detail.session,
+            messages: detail.messages,
+            tool_operations,
+            attachments: detail.attachments,
+        }))
+    }
+
+    /// Latest completed analysis of a session
+    pub async fn analytics(&self, session_id: &str) -> Result<Option<Analytics>> {
+        Ok(self
+            .query_service
+            .get_session_analytics(session_id)
+            .await?
+            .and_then(|analytics| analytics.latest_analytics))
+    }
+
+    /// Analyze a session with `llm_client` and store the result, as
+    /// `retrochat analysis run` does
+    ///
+    /// Fails if the session is already being analyzed, or hasn't changed
+    /// since its last analysis (see [`RetrochatClient::analytics`]).
+    pub async fn analyze(
+        &self,
+        session_id: &str,
+        llm_client: Arc<dyn LlmClient>,
+    ) -> Result<Analytics> {
+        let service = AnalyticsRequestService::new_with_llm(self.db_manager.clone(), llm_client);
+        let request = service
+            .create_analysis_request(session_id.to_string(), None, None)
+            .await
+            .map_err(|e| anyhow::anyhow!("{e}"))?;
+        service
+            .execute_analysis(request.id.clone())
+            .await
+            .map_err(|e| anyhow::anyhow!("Analysis failed: {e}"))?;
+        service
+            .get_analysis_result(request.id)
+            .await
+            .map_err(|e| anyhow::anyhow!("{e}"))?
+            .context("The analysis finished without a result")
+    }
+
+    /// The underlying database, for anything the client doesn't cover
+    pub fn database(&self) -> &Arc<DatabaseManager> {
+        &self.db_manager
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use retrochat_core::database::MessageRepository;
+
+    #[tokio::test]
+    async fn test_list_and_get() {
+        let db = Arc::new(DatabaseManager::open_in_memory().await.unwrap());
+        let session = ChatSession::new(
+            Provider::ClaudeCode,
+            "/tmp/session.jsonl".to_string(),
+            "hash".to_string(),
+            chrono::Utc::now(),
+        );
+        ChatSessionRepository::new(&db)
+            .create(&session)
+            .await
+            .unwrap();
+        MessageRepository::new(&db)
+            .bulk_create(&[Message::new(
+                session.id,
+                MessageRole::User,
+                "Hello".to_string(),
+                chrono::Utc::now(),
+                1,
+            )])
+            .await
+            .unwrap();
+
+        let client = RetrochatClient::with_database(db);
+        let page = client.list(ListOptions::default()).await.unwrap();
+        assert_eq!(page.sessions.len(), 1);
+        assert_eq!(page.total_count, 1);
+
+        let detail = client.get(&session.id.to_string()).await.unwrap().unwrap();
+        assert_eq!(detail.messages[0].content, "Hello");
+        assert!(client
+            .get(&Uuid::new_v4().to_string())
+            .await
+            .unwrap()
+            .is_none());
+        assert!(client
+            .analytics(&session.id.to_string())
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,19 @@
+-- Migration: 019_add_import_errors.sql
+-- Description: Add an error ledger for files that failed to import
+
+-- =============================================================================
+-- Table: import_errors
+-- Purpose: One row per file whose last import attempt failed
+-- Lifecycle: Upserted on each failed attempt, deleted once the file imports
+-- =============================================================================
+CREATE TABLE IF NOT EXISTS import_errors (
+    id TEXT PRIMARY KEY,
+    file_path TEXT NOT NULL UNIQUE,
+    provider TEXT,
+    error_message TEXT NOT NULL,            -- Error from the most recent attempt
+    attempt_count INTEGER NOT NULL DEFAULT 1,
+    first_attempt_at TEXT NOT NULL,
+    last_attempt_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_import_errors_last_attempt_at ON import_errors(last_attempt_at);
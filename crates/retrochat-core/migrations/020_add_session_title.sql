@@ -0,0 +1,10 @@
+-- Migration: 020_add_session_title.sql
+-- Description: Store a human-readable session title separately from project_name
+
+ALTER TABLE chat_sessions ADD COLUMN title TEXT;
+
+-- Cursor sessions used to carry their chat title in project_name. Copy it over so
+-- existing sessions keep a title; re-importing them fixes project_name as well.
+UPDATE chat_sessions
+SET title = project_name
+WHERE provider = 'Cursor Client' AND project_name IS NOT NULL;
@@ -0,0 +1,14 @@
+-- Migration: 021_add_session_title_source.sql
+-- Description: Record where each session title came from (parser, heuristic or llm)
+
+ALTER TABLE chat_sessions ADD COLUMN title_source TEXT
+    CHECK (title_source IS NULL OR title_source IN ('parser', 'heuristic', 'llm'));
+
+-- Titles copied over by 020 came from Cursor chat names
+UPDATE chat_sessions
+SET title_source = 'parser'
+WHERE title IS NOT NULL AND provider = 'Cursor Client';
+
+UPDATE chat_sessions
+SET title_source = 'heuristic'
+WHERE title IS NOT NULL AND title_source IS NULL;
@@ -0,0 +1,4 @@
+-- Migration: 022_add_saved_views.down.sql
+-- Description: Revert 022_add_saved_views.sql
+
+DROP TABLE IF EXISTS saved_views;
@@ -0,0 +1,15 @@
+-- Migration: 022_add_saved_views.sql
+-- Description: Add named, reusable session filter expressions
+
+-- =============================================================================
+-- Table: saved_views
+-- Purpose: Saved searches ("smart views") over the session list
+-- Lifecycle: Created or replaced by name, deleted on request
+-- =============================================================================
+CREATE TABLE IF NOT EXISTS saved_views (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    expression TEXT NOT NULL,               -- Filter expression, e.g. "provider:claude tokens>100k"
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
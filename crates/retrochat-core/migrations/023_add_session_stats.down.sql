@@ -0,0 +1,6 @@
+-- Migration: 023_add_session_stats.down.sql
+-- Description: Revert 023_add_session_stats.sql
+
+-- message_count and token_count predate 023 and keep their backfilled values
+ALTER TABLE chat_sessions DROP COLUMN duration_seconds;
+ALTER TABLE chat_sessions DROP COLUMN tool_op_count;
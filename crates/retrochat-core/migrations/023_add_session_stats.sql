@@ -0,0 +1,25 @@
+-- Migration: 023_add_session_stats.sql
+-- Description: Cache per-session statistics on chat_sessions
+
+-- Session lists read these columns directly instead of aggregating messages
+-- on every request. The importer refreshes them after writing a session's
+-- messages (see ChatSessionRepository::refresh_stats).
+--   message_count    - stored messages
+--   token_count      - provider-reported total, or the sum of message tokens
+--   tool_op_count    - distinct tool operations linked from messages
+--   duration_seconds - end_time - start_time
+ALTER TABLE chat_sessions ADD COLUMN tool_op_count INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE chat_sessions ADD COLUMN duration_seconds INTEGER;
+
+-- Backfill existing sessions
+UPDATE chat_sessions SET
+    message_count = (SELECT COUNT(*) FROM messages WHERE session_id = chat_sessions.id),
+    token_count = COALESCE(
+        token_count,
+        (SELECT SUM(token_count) FROM messages WHERE session_id = chat_sessions.id)
+    ),
+    tool_op_count = (
+        SELECT COUNT(DISTINCT tool_operation_id) FROM messages
+        WHERE session_id = chat_sessions.id
+    ),
+    duration_seconds = CAST(ROUND((julianday(end_time) - julianday(start_time)) * 86400) AS INTEGER);
@@ -0,0 +1,4 @@
+-- Migration: 024_add_session_keyset_index.down.sql
+-- Description: Revert 024_add_session_keyset_index.sql
+
+DROP INDEX IF EXISTS idx_chat_sessions_start_time_id;
@@ -0,0 +1,6 @@
+-- Migration: 024_add_session_keyset_index.sql
+-- Description: Index sessions by (start_time, id) for keyset pagination
+
+-- Session listings page through (start_time, id) with row-value comparisons;
+-- the id breaks ties between sessions that started at the same moment
+CREATE INDEX IF NOT EXISTS idx_chat_sessions_start_time_id ON chat_sessions(start_time, id);
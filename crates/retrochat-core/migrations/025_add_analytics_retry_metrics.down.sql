@@ -0,0 +1,6 @@
+-- Migration: 025_add_analytics_retry_metrics.down.sql
+-- Description: Revert 025_add_analytics_retry_metrics.sql
+
+ALTER TABLE analytics_requests DROP COLUMN llm_retry_wait_ms;
+ALTER TABLE analytics_requests DROP COLUMN llm_retries;
+ALTER TABLE analytics_requests DROP COLUMN llm_attempts;
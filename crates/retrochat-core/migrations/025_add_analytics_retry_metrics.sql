@@ -0,0 +1,10 @@
+-- Migration: 025_add_analytics_retry_metrics.sql
+-- Description: Record LLM retry metrics on analysis requests
+
+-- Filled in when an analysis finishes (successfully or not):
+--   llm_attempts      - LLM requests sent, including retries
+--   llm_retries       - requests that were retried after a retryable error
+--   llm_retry_wait_ms - time spent backing off between attempts
+ALTER TABLE analytics_requests ADD COLUMN llm_attempts INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE analytics_requests ADD COLUMN llm_retries INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE analytics_requests ADD COLUMN llm_retry_wait_ms INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,5 @@
+-- Migration: 026_add_llm_cache.down.sql
+-- Description: Revert 026_add_llm_cache.sql
+
+DROP INDEX IF EXISTS idx_llm_cache_last_used_at;
+DROP TABLE IF EXISTS llm_cache;
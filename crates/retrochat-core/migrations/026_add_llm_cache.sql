@@ -0,0 +1,19 @@
+-- Migration: 026_add_llm_cache.sql
+-- Description: Cache LLM responses by request content
+
+-- Keyed by a SHA-256 of the provider, model and request (prompt, system
+-- prompt and sampling parameters), so re-running an analysis over an
+-- unchanged transcript reuses the earlier responses instead of paying for
+-- them again. Entries expire after a TTL and the least recently used ones
+-- are evicted once the cache outgrows its size limit.
+CREATE TABLE IF NOT EXISTS llm_cache (
+    key TEXT PRIMARY KEY,
+    provider TEXT NOT NULL,
+    model TEXT NOT NULL,
+    response TEXT NOT NULL,
+    size_bytes INTEGER NOT NULL,
+    created_at TEXT NOT NULL,
+    last_used_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_llm_cache_last_used_at ON llm_cache(last_used_at);
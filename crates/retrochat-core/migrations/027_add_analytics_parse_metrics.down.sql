@@ -0,0 +1,6 @@
+-- Migration: 027_add_analytics_parse_metrics.down.sql
+-- Description: Revert 027_add_analytics_parse_metrics.sql
+
+ALTER TABLE analytics_requests DROP COLUMN llm_parse_failures;
+ALTER TABLE analytics_requests DROP COLUMN llm_parse_repaired;
+ALTER TABLE analytics_requests DROP COLUMN llm_parsed;
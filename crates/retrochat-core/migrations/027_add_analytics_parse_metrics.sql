@@ -0,0 +1,10 @@
+-- Migration: 027_add_analytics_parse_metrics.sql
+-- Description: Record structured-output parse outcomes on analysis requests
+
+-- Filled in when an analysis finishes (successfully or not):
+--   llm_parsed         - responses that were valid JSON as returned
+--   llm_parse_repaired - responses that parsed only after JSON repair
+--   llm_parse_failures - responses with no usable JSON
+ALTER TABLE analytics_requests ADD COLUMN llm_parsed INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE analytics_requests ADD COLUMN llm_parse_repaired INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE analytics_requests ADD COLUMN llm_parse_failures INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,6 @@
+-- Migration: 028_add_vector_index.down.sql
+-- Description: Revert 028_add_vector_index.sql
+
+DROP TABLE IF EXISTS vector_index_state;
+DROP INDEX IF EXISTS idx_summary_embeddings_session;
+DROP TABLE IF EXISTS summary_embeddings;
@@ -0,0 +1,28 @@
+-- Migration: 028_add_vector_index.sql
+-- Description: Store embeddings of turn and session summaries
+
+-- One vector per summary. content_hash is a SHA-256 of the text that was
+-- embedded, so the indexer only re-embeds summaries whose text changed.
+-- Vectors are little-endian f32 arrays.
+CREATE TABLE IF NOT EXISTS summary_embeddings (
+    kind TEXT NOT NULL CHECK (kind IN ('turn', 'session')),
+    source_id TEXT NOT NULL,
+    session_id TEXT NOT NULL,
+    content_hash TEXT NOT NULL,
+    vector BLOB NOT NULL,
+    indexed_at TEXT NOT NULL,
+    PRIMARY KEY (kind, source_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_summary_embeddings_session ON summary_embeddings(session_id);
+
+-- One row per kind: which embedder produced the stored vectors and when the
+-- index was last brought up to date. A different embedder or dimension
+-- count invalidates every vector of that kind.
+CREATE TABLE IF NOT EXISTS vector_index_state (
+    kind TEXT PRIMARY KEY CHECK (kind IN ('turn', 'session')),
+    embedder TEXT NOT NULL,
+    dimensions INTEGER NOT NULL,
+    last_indexed_at TEXT,
+    last_rebuilt_at TEXT
+);
@@ -0,0 +1,6 @@
+-- Migration: 029_add_topics.down.sql
+-- Description: Revert 029_add_topics.sql
+
+DROP INDEX IF EXISTS idx_session_topics_topic;
+DROP TABLE IF EXISTS session_topics;
+DROP TABLE IF EXISTS topics;
@@ -0,0 +1,26 @@
+-- Migration: 029_add_topics.sql
+-- Description: Store topic clusters of sessions and each session's topic
+
+-- =============================================================================
+-- Table: topics
+-- Purpose: Clusters of sessions with similar summaries, with a readable label
+-- Lifecycle: Replaced as a whole by each clustering run; IDs and labels of
+--            clusters that barely changed are carried over
+-- =============================================================================
+CREATE TABLE IF NOT EXISTS topics (
+    id INTEGER PRIMARY KEY,
+    label TEXT NOT NULL,
+    description TEXT,
+    label_source TEXT NOT NULL CHECK (label_source IN ('llm', 'keywords')),
+    session_count INTEGER NOT NULL DEFAULT 0,
+    clustered_at TEXT NOT NULL
+);
+
+-- One topic per session; sessions without an indexed summary have none
+CREATE TABLE IF NOT EXISTS session_topics (
+    session_id TEXT PRIMARY KEY REFERENCES chat_sessions(id) ON DELETE CASCADE,
+    topic_id INTEGER NOT NULL REFERENCES topics(id) ON DELETE CASCADE,
+    similarity REAL NOT NULL                -- Cosine similarity to the topic centroid
+);
+
+CREATE INDEX IF NOT EXISTS idx_session_topics_topic ON session_topics(topic_id);
@@ -0,0 +1,6 @@
+-- Migration: 030_add_message_token_estimates.down.sql
+-- Description: Revert 030_add_message_token_estimates.sql
+
+-- Session token totals computed from estimates are left as they are; the
+-- next import or `refresh_stats` recomputes them from reported usage
+ALTER TABLE messages DROP COLUMN estimated_tokens;
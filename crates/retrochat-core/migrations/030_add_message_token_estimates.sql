@@ -0,0 +1,8 @@
+-- Migration: 030_add_message_token_estimates.sql
+-- Description: Store an estimated token count on every message
+
+-- token_count keeps only provider-reported usage; estimated_tokens is filled
+-- at import time from a per-provider heuristic (utils::token_estimate) so
+-- token metrics also work for providers that report nothing. Existing rows
+-- are filled by `retrochat tokens backfill`.
+ALTER TABLE messages ADD COLUMN estimated_tokens INTEGER CHECK (estimated_tokens >= 0);
@@ -0,0 +1,7 @@
+-- Migration: 031_add_quota_events.down.sql
+-- Description: Revert 031_add_quota_events.sql
+
+DROP INDEX IF EXISTS idx_quota_events_created;
+DROP TABLE IF EXISTS quota_events;
+ALTER TABLE analytics_requests DROP COLUMN llm_cost_usd;
+ALTER TABLE analytics_requests DROP COLUMN llm_tokens;
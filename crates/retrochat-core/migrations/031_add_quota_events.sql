@@ -0,0 +1,26 @@
+-- Migration: 031_add_quota_events.sql
+-- Description: Record LLM usage of analysis requests and usage quota alerts
+
+-- Filled in when an analysis finishes (successfully or not):
+--   llm_tokens   - tokens reported by the provider across all calls
+--   llm_cost_usd - cost reported by the provider (Claude Code only)
+ALTER TABLE analytics_requests ADD COLUMN llm_tokens INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE analytics_requests ADD COLUMN llm_cost_usd REAL NOT NULL DEFAULT 0;
+
+-- =============================================================================
+-- Table: quota_events
+-- Purpose: Usage thresholds from the [quota] config section that were crossed
+-- Lifecycle: One row per quota and period, written the first time a check
+--            finds usage over the threshold
+-- =============================================================================
+CREATE TABLE IF NOT EXISTS quota_events (
+    id INTEGER PRIMARY KEY,
+    kind TEXT NOT NULL CHECK (kind IN ('daily_tokens', 'weekly_analysis_tokens', 'weekly_analysis_usd')),
+    period_start TEXT NOT NULL,             -- Start of the UTC day or ISO week
+    threshold REAL NOT NULL,
+    observed REAL NOT NULL,                 -- Usage when the threshold was first crossed
+    created_at TEXT NOT NULL,
+    UNIQUE (kind, period_start)
+);
+
+CREATE INDEX IF NOT EXISTS idx_quota_events_created ON quota_events(created_at);
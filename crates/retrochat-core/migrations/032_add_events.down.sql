@@ -0,0 +1,6 @@
+-- Migration: 032_add_events.down.sql
+-- Description: Revert 032_add_events.sql
+
+DROP INDEX IF EXISTS idx_events_created;
+DROP INDEX IF EXISTS idx_events_session;
+DROP TABLE IF EXISTS events;
@@ -0,0 +1,22 @@
+-- Migration: 032_add_events.sql
+-- Description: Append-only log of changes made by any RetroChat process
+
+-- =============================================================================
+-- Table: events
+-- Purpose: Change feed for keeping the CLI, TUI, desktop app and other
+--          consumers in sync; readers remember the last id they saw and ask
+--          for newer rows
+-- Lifecycle: Appended by services as they change data; never updated
+-- =============================================================================
+-- kind has no CHECK constraint so newer builds can add kinds without
+-- rebuilding the table; readers skip kinds they don't know
+CREATE TABLE IF NOT EXISTS events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,   -- Cursor; never reused
+    kind TEXT NOT NULL,                     -- e.g. session_imported, analysis_completed
+    session_id TEXT,                        -- Session the event is about, if any
+    payload TEXT NOT NULL DEFAULT '{}',     -- JSON details, depending on kind
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_events_session ON events(session_id);
+CREATE INDEX IF NOT EXISTS idx_events_created ON events(created_at);
@@ -0,0 +1,5 @@
+-- Migration: 033_add_remote_sync.down.sql
+-- Description: Revert 033_add_remote_sync.sql
+
+DROP TABLE IF EXISTS remote_bundles;
+DROP TABLE IF EXISTS remote_sessions;
@@ -0,0 +1,32 @@
+-- Migration: 033_add_remote_sync.sql
+-- Description: Track what has been pushed to and pulled from sync remotes
+
+-- =============================================================================
+-- Table: remote_sessions
+-- Purpose: The message count of each session as the remote last saw it; a
+--          session is pushed again when its local count differs
+-- Lifecycle: Upserted after every push, and for every session in a pulled
+--            bundle; removed with `retrochat remote remove`
+-- =============================================================================
+CREATE TABLE IF NOT EXISTS remote_sessions (
+    remote TEXT NOT NULL,                   -- Remote name from the [sync] config
+    session_id TEXT NOT NULL,               -- No foreign key: deleting a session locally
+                                            -- doesn't delete it remotely
+    message_count INTEGER NOT NULL,
+    synced_at TEXT NOT NULL,
+    PRIMARY KEY (remote, session_id)
+);
+
+-- =============================================================================
+-- Table: remote_bundles
+-- Purpose: Bundles uploaded by this machine or already merged from a remote,
+--          so pulls only download new ones
+-- =============================================================================
+CREATE TABLE IF NOT EXISTS remote_bundles (
+    remote TEXT NOT NULL,
+    name TEXT NOT NULL,                     -- File name in the remote's bundles/ folder
+    direction TEXT NOT NULL CHECK (direction IN ('push', 'pull')),
+    sessions INTEGER NOT NULL,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (remote, name)
+);
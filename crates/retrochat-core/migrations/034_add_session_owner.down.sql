@@ -0,0 +1,5 @@
+-- Migration: 034_add_session_owner.down.sql
+-- Description: Revert 034_add_session_owner.sql
+
+DROP INDEX IF EXISTS idx_chat_sessions_owner;
+ALTER TABLE chat_sessions DROP COLUMN owner;
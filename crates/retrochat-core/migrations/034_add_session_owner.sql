@@ -0,0 +1,8 @@
+-- Migration: 034_add_session_owner.sql
+-- Description: Track which teammate a session was imported from
+
+-- NULL for the user's own sessions; the teammate's name for sessions merged
+-- from their exports with `retrochat team import`, which are read-only
+ALTER TABLE chat_sessions ADD COLUMN owner TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_chat_sessions_owner ON chat_sessions(owner);
@@ -0,0 +1,4 @@
+-- Migration: 035_add_import_journal.down.sql
+-- Description: Revert 035_add_import_journal.sql
+
+DROP TABLE IF EXISTS import_journal;
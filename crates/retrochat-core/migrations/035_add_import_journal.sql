@@ -0,0 +1,15 @@
+-- Migration: 035_add_import_journal.sql
+-- Description: Track files whose import has started but not finished
+
+-- =============================================================================
+-- Table: import_journal
+-- Purpose: Crash-safe imports; a row is written before a file's sessions are
+--          written and removed once they all are. Rows left behind by an
+--          interrupted import are re-imported on the next sync
+-- Lifecycle: Inserted and deleted by the import service
+-- =============================================================================
+CREATE TABLE IF NOT EXISTS import_journal (
+    file_path TEXT PRIMARY KEY,
+    provider TEXT,
+    started_at TEXT NOT NULL
+);
@@ -0,0 +1,5 @@
+-- Migration: 036_add_message_usage.down.sql
+-- Description: Revert 036_add_message_usage.sql
+
+DROP INDEX IF EXISTS idx_message_usage_session;
+DROP TABLE IF EXISTS message_usage;
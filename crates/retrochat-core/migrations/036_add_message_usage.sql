@@ -0,0 +1,24 @@
+-- Migration: 036_add_message_usage.sql
+-- Description: Provider-reported token usage and billing details per message
+
+-- =============================================================================
+-- Table: message_usage
+-- Purpose: Usage blocks from provider logs (currently Claude Code), kept
+--          apart from messages.token_count so prompt cache reads and writes
+--          can be analyzed
+-- Lifecycle: Written at import; replaced with the session's messages
+-- =============================================================================
+CREATE TABLE IF NOT EXISTS message_usage (
+    message_id TEXT PRIMARY KEY,
+    session_id TEXT NOT NULL,
+    model TEXT,
+    input_tokens INTEGER NOT NULL DEFAULT 0,
+    output_tokens INTEGER NOT NULL DEFAULT 0,
+    cache_creation_input_tokens INTEGER NOT NULL DEFAULT 0,
+    cache_read_input_tokens INTEGER NOT NULL DEFAULT 0,
+    service_tier TEXT,                      -- standard, priority, batch
+    FOREIGN KEY (message_id) REFERENCES messages(id) ON DELETE CASCADE,
+    FOREIGN KEY (session_id) REFERENCES chat_sessions(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_message_usage_session ON message_usage(session_id);
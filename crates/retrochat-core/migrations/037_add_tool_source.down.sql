@@ -0,0 +1,5 @@
+-- Migration: 037_add_tool_source.down.sql
+-- Description: Revert 037_add_tool_source.sql
+
+DROP INDEX IF EXISTS idx_tool_operations_source;
+ALTER TABLE tool_operations DROP COLUMN tool_source;
@@ -0,0 +1,21 @@
+-- Migration: 037_add_tool_source.sql
+-- Description: Classify tool operations by where the tool comes from
+
+-- 'builtin' for the agent's own tools, 'mcp:<server>' for MCP tools, which
+-- agents name mcp__<server>__<tool> (Claude Code) or <server>__<tool>
+ALTER TABLE tool_operations ADD COLUMN tool_source TEXT NOT NULL DEFAULT 'builtin';
+
+UPDATE tool_operations
+SET tool_source = 'mcp:' || substr(tool_name, 6, instr(substr(tool_name, 6), '__') - 1)
+WHERE tool_name LIKE 'mcp\_\_%\_\_%' ESCAPE '\'
+  AND instr(substr(tool_name, 6), '__') > 1
+  AND length(tool_name) > 6 + instr(substr(tool_name, 6), '__');
+
+UPDATE tool_operations
+SET tool_source = 'mcp:' || substr(tool_name, 1, instr(tool_name, '__') - 1)
+WHERE tool_source = 'builtin'
+  AND tool_name NOT LIKE 'mcp\_\_%' ESCAPE '\'
+  AND instr(tool_name, '__') > 1
+  AND length(tool_name) > instr(tool_name, '__') + 1;
+
+CREATE INDEX IF NOT EXISTS idx_tool_operations_source ON tool_operations(tool_source);
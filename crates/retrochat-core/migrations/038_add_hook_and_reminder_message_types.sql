@@ -0,0 +1,58 @@
+-- Migration: 038_add_hook_and_reminder_message_types.sql
+-- Description: Add 'hook_event' and 'system_reminder' to the message_type CHECK
+--              constraint, for hook runs and context injected by Claude Code
+
+-- SQLite cannot alter a CHECK constraint, so the table is recreated
+
+-- message_usage rows would be removed by ON DELETE CASCADE when the old
+-- table is dropped, so keep a copy
+CREATE TEMP TABLE message_usage_backup AS SELECT * FROM message_usage;
+
+CREATE TABLE messages_new (
+    id TEXT PRIMARY KEY,
+    session_id TEXT NOT NULL,
+    role TEXT NOT NULL CHECK (role IN ('User', 'Assistant', 'System')),
+    content TEXT NOT NULL CHECK (length(content) > 0),
+    timestamp TEXT NOT NULL,
+    token_count INTEGER CHECK (token_count >= 0),
+    metadata TEXT,   -- JSON object
+    sequence_number INTEGER NOT NULL,
+    message_type TEXT NOT NULL DEFAULT 'simple_message' CHECK (message_type IN ('tool_request', 'tool_result', 'thinking', 'slash_command', 'hook_event', 'system_reminder', 'simple_message')),
+    tool_operation_id TEXT,
+    estimated_tokens INTEGER CHECK (estimated_tokens >= 0),
+    FOREIGN KEY (session_id) REFERENCES chat_sessions(id) ON DELETE CASCADE,
+    FOREIGN KEY (tool_operation_id) REFERENCES tool_operations(id) ON DELETE SET NULL,
+    UNIQUE(session_id, sequence_number)
+);
+
+-- Keep rowids so the external-content FTS index stays valid
+INSERT INTO messages_new (rowid, id, session_id, role, content, timestamp, token_count, metadata, sequence_number, message_type, tool_operation_id, estimated_tokens)
+SELECT rowid, id, session_id, role, content, timestamp, token_count, metadata, sequence_number, message_type, tool_operation_id, estimated_tokens
+FROM messages;
+
+DROP TABLE messages;
+ALTER TABLE messages_new RENAME TO messages;
+
+INSERT INTO message_usage SELECT * FROM message_usage_backup;
+DROP TABLE message_usage_backup;
+
+CREATE INDEX IF NOT EXISTS idx_messages_session_id ON messages(session_id);
+CREATE INDEX IF NOT EXISTS idx_messages_role ON messages(role);
+CREATE INDEX IF NOT EXISTS idx_messages_timestamp ON messages(timestamp);
+CREATE INDEX IF NOT EXISTS idx_messages_sequence ON messages(session_id, sequence_number);
+CREATE INDEX IF NOT EXISTS idx_messages_message_type ON messages(message_type);
+CREATE INDEX IF NOT EXISTS idx_messages_tool_operation ON messages(tool_operation_id);
+
+-- Triggers are dropped with the old table
+CREATE TRIGGER messages_fts_insert AFTER INSERT ON messages BEGIN
+    INSERT INTO messages_fts(rowid, content) VALUES (NEW.rowid, NEW.content);
+END;
+
+CREATE TRIGGER messages_fts_delete AFTER DELETE ON messages BEGIN
+    INSERT INTO messages_fts(messages_fts, rowid, content) VALUES('delete', OLD.rowid, OLD.content);
+END;
+
+CREATE TRIGGER messages_fts_update AFTER UPDATE ON messages BEGIN
+    INSERT INTO messages_fts(messages_fts, rowid, content) VALUES('delete', OLD.rowid, OLD.content);
+    INSERT INTO messages_fts(rowid, content) VALUES (NEW.rowid, NEW.content);
+END;
@@ -0,0 +1,4 @@
+-- Migration: 039_add_session_segments.down.sql
+-- Description: Revert 039_add_session_segments.sql
+
+DROP TABLE IF EXISTS session_segments;
@@ -0,0 +1,22 @@
+-- Migration: 039_add_session_segments.sql
+-- Description: Context segments of a session, split at compactions
+
+-- =============================================================================
+-- Table: session_segments
+-- Purpose: Ranges of messages that shared one context window. Claude Code
+--          compaction replaces the conversation with a summary, so token
+--          accounting restarts at each segment
+-- Lifecycle: Written at import; replaced with the session's messages
+-- =============================================================================
+CREATE TABLE IF NOT EXISTS session_segments (
+    session_id TEXT NOT NULL,
+    segment_index INTEGER NOT NULL,         -- 0 before the first compaction
+    start_sequence INTEGER NOT NULL,
+    end_sequence INTEGER NOT NULL,
+    started_at TEXT NOT NULL,
+    ended_at TEXT NOT NULL,
+    trigger TEXT,                           -- auto, manual, or NULL if unknown
+    pre_compaction_tokens INTEGER,
+    PRIMARY KEY (session_id, segment_index),
+    FOREIGN KEY (session_id) REFERENCES chat_sessions(id) ON DELETE CASCADE
+);
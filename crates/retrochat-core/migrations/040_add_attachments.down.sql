@@ -0,0 +1,6 @@
+-- Migration: 040_add_attachments.down.sql
+-- Description: Revert 040_add_attachments.sql
+
+DROP INDEX IF EXISTS idx_attachments_message;
+DROP INDEX IF EXISTS idx_attachments_session;
+DROP TABLE IF EXISTS attachments;
@@ -0,0 +1,24 @@
+-- Migration: 040_add_attachments.sql
+-- Description: Images and documents attached to messages
+
+-- =============================================================================
+-- Table: attachments
+-- Purpose: Metadata of pasted images and documents. The bytes are only kept
+--          (under ~/.retrochat/attachments) when attachments.store_blobs is on
+-- Lifecycle: Written at import; replaced with the session's messages
+-- =============================================================================
+CREATE TABLE IF NOT EXISTS attachments (
+    id TEXT PRIMARY KEY,
+    message_id TEXT NOT NULL,
+    session_id TEXT NOT NULL,
+    kind TEXT NOT NULL,                     -- image, document
+    media_type TEXT,
+    size_bytes INTEGER NOT NULL DEFAULT 0,
+    sha256 TEXT,
+    file_path TEXT,                         -- saved blob, if any
+    FOREIGN KEY (message_id) REFERENCES messages(id) ON DELETE CASCADE,
+    FOREIGN KEY (session_id) REFERENCES chat_sessions(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_attachments_session ON attachments(session_id);
+CREATE INDEX IF NOT EXISTS idx_attachments_message ON attachments(message_id);
@@ -0,0 +1,5 @@
+-- Migration: 041_add_merged_sessions.down.sql
+-- Description: Revert 041_add_merged_sessions.sql
+
+DROP INDEX IF EXISTS idx_merged_sessions_into;
+DROP TABLE IF EXISTS merged_sessions;
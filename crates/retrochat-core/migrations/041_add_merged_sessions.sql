@@ -0,0 +1,17 @@
+-- Migration: 041_add_merged_sessions.sql
+-- Description: Remember sessions folded into another by `retrochat session merge`
+
+-- =============================================================================
+-- Table: merged_sessions
+-- Purpose: Sessions whose messages were moved into another session, so that
+--          importing their source file again doesn't bring them back
+-- Lifecycle: Written by a merge; dropped with the session merged into
+-- =============================================================================
+CREATE TABLE IF NOT EXISTS merged_sessions (
+    session_id TEXT PRIMARY KEY,            -- The merged session (no longer stored)
+    merged_into TEXT NOT NULL,
+    merged_at TEXT NOT NULL,
+    FOREIGN KEY (merged_into) REFERENCES chat_sessions(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_merged_sessions_into ON merged_sessions(merged_into);
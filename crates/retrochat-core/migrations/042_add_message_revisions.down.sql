@@ -0,0 +1,5 @@
+-- Migration: 042_add_message_revisions.down.sql
+-- Description: Revert 042_add_message_revisions.sql
+
+DROP INDEX IF EXISTS idx_message_revisions_message;
+DROP TABLE IF EXISTS message_revisions;
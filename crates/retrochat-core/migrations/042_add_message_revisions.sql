@@ -0,0 +1,22 @@
+-- Migration: 042_add_message_revisions.sql
+-- Description: Audit trail of changes made to stored messages
+
+-- =============================================================================
+-- Table: message_revisions
+-- Purpose: Before and after values of every message field changed after
+--          import, e.g. by `retrochat repair roles` or `retrochat session split`
+-- Lifecycle: Appended to by each change; dropped with the message
+-- =============================================================================
+CREATE TABLE IF NOT EXISTS message_revisions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    message_id TEXT NOT NULL,
+    field TEXT NOT NULL,                    -- role, content, position
+    before_value TEXT,
+    after_value TEXT,
+    source TEXT NOT NULL CHECK (source IN ('redaction', 'repair', 'manual')),
+    reason TEXT,                            -- e.g. "assistant phrasing", "session split"
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (message_id) REFERENCES messages(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_message_revisions_message ON message_revisions(message_id);
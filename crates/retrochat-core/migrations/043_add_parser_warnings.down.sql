@@ -0,0 +1,5 @@
+-- Migration: 043_add_parser_warnings.down.sql
+-- Description: Revert 043_add_parser_warnings.sql
+
+DROP INDEX IF EXISTS idx_parser_warnings_last_seen_at;
+DROP TABLE IF EXISTS parser_warnings;
@@ -0,0 +1,24 @@
+-- Migration: 043_add_parser_warnings.sql
+-- Description: Unrecognized log fields met by the parsers
+
+-- =============================================================================
+-- Table: parser_warnings
+-- Purpose: Keys and entry types a parser did not recognize, so changes to a
+--          provider's log format show up before data silently goes missing
+-- Lifecycle: Upserted on every import that meets one; cleared by
+--            `retrochat doctor --parsers --clear`
+-- =============================================================================
+CREATE TABLE IF NOT EXISTS parser_warnings (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    provider TEXT NOT NULL,
+    kind TEXT NOT NULL CHECK (kind IN ('unknown_key', 'unknown_type')),
+    location TEXT NOT NULL,                 -- e.g. entry.fooBar, message.content[].type=server_tool_use
+    occurrences INTEGER NOT NULL DEFAULT 0,
+    file_count INTEGER NOT NULL DEFAULT 0,  -- imports that met it
+    last_file_path TEXT,
+    first_seen_at TEXT NOT NULL,
+    last_seen_at TEXT NOT NULL,
+    UNIQUE (provider, kind, location)
+);
+
+CREATE INDEX IF NOT EXISTS idx_parser_warnings_last_seen_at ON parser_warnings(last_seen_at);
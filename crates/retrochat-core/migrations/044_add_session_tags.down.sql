@@ -0,0 +1,5 @@
+-- Migration: 044_add_session_tags.down.sql
+-- Description: Revert 044_add_session_tags.sql
+
+DROP INDEX IF EXISTS idx_session_tags_tag;
+DROP TABLE IF EXISTS session_tags;
@@ -0,0 +1,19 @@
+-- Migration: 044_add_session_tags.sql
+-- Description: Tags attached to whole sessions
+
+-- =============================================================================
+-- Table: session_tags
+-- Purpose: Labels for sessions, such as the `tags` listed in a project's
+--          `.retrochat.toml`
+-- Lifecycle: Replaced each time the session is imported; removed with the
+--            session
+-- =============================================================================
+CREATE TABLE IF NOT EXISTS session_tags (
+    session_id TEXT NOT NULL,
+    tag TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (session_id, tag),
+    FOREIGN KEY (session_id) REFERENCES chat_sessions(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_session_tags_tag ON session_tags(tag);
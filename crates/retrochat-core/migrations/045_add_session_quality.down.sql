@@ -0,0 +1,4 @@
+-- Migration: 045_add_session_quality.down.sql
+-- Description: Revert 045_add_session_quality.sql
+
+DROP TABLE IF EXISTS session_quality;
@@ -0,0 +1,24 @@
+-- Migration: 045_add_session_quality.sql
+-- Description: Conversation quality signals that need no LLM
+
+-- =============================================================================
+-- Table: session_quality
+-- Purpose: Metric-only quality signals per session: user corrections, tool
+--          retry loops, tool errors and prompt lengths
+-- Lifecycle: Written at import; replaced when the session is re-imported,
+--            removed with the session
+-- =============================================================================
+CREATE TABLE IF NOT EXISTS session_quality (
+    session_id TEXT PRIMARY KEY,
+    user_prompts INTEGER NOT NULL DEFAULT 0,
+    user_corrections INTEGER NOT NULL DEFAULT 0,
+    tool_retry_loops INTEGER NOT NULL DEFAULT 0,
+    tool_errors INTEGER NOT NULL DEFAULT 0,
+    tool_successes INTEGER NOT NULL DEFAULT 0,
+    short_prompts INTEGER NOT NULL DEFAULT 0,   -- at most 50 characters
+    medium_prompts INTEGER NOT NULL DEFAULT 0,
+    long_prompts INTEGER NOT NULL DEFAULT 0,    -- more than 500 characters
+    median_prompt_chars INTEGER NOT NULL DEFAULT 0,
+    computed_at TEXT NOT NULL,
+    FOREIGN KEY (session_id) REFERENCES chat_sessions(id) ON DELETE CASCADE
+);
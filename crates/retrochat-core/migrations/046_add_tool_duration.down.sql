@@ -0,0 +1,4 @@
+-- Migration: 046_add_tool_duration.down.sql
+-- Description: Revert 046_add_tool_duration.sql
+
+ALTER TABLE tool_operations DROP COLUMN duration_ms;
@@ -0,0 +1,6 @@
+-- Migration: 046_add_tool_duration.sql
+-- Description: How long each tool call took
+
+-- Milliseconds from the tool call to its result; NULL when the log has no
+-- separate timestamp for the result
+ALTER TABLE tool_operations ADD COLUMN duration_ms INTEGER;
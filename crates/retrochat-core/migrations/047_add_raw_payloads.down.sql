@@ -0,0 +1,4 @@
+-- Migration: 047_add_raw_payloads.down.sql
+-- Description: Revert 047_add_raw_payloads.sql
+
+DROP TABLE IF EXISTS raw_payloads;
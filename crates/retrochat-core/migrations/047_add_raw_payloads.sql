@@ -0,0 +1,21 @@
+-- Migration: 047_add_raw_payloads.sql
+-- Description: Byte-exact source JSON, kept only when import.raw_payloads is on
+
+-- =============================================================================
+-- Table: raw_payloads
+-- Purpose: The provider's original JSON for a message (one log entry) or a
+--          tool call (its tool_use and tool_result blocks), next to the
+--          normalized rows parsed from it
+-- Lifecycle: Written at import when enabled; removed with the session or by
+--            `retrochat raw strip`, restored from the logs by
+--            `retrochat raw rehydrate`
+-- =============================================================================
+CREATE TABLE IF NOT EXISTS raw_payloads (
+    session_id TEXT NOT NULL,
+    owner_kind TEXT NOT NULL,   -- 'message' or 'tool_call'
+    owner_id TEXT NOT NULL,     -- messages.id or tool_operations.tool_use_id
+    payload TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (session_id, owner_kind, owner_id),
+    FOREIGN KEY (session_id) REFERENCES chat_sessions(id) ON DELETE CASCADE
+);
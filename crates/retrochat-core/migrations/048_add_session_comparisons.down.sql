@@ -0,0 +1,6 @@
+-- Migration: 048_add_session_comparisons.down.sql
+-- Description: Revert 048_add_session_comparisons.sql
+
+DROP INDEX IF EXISTS idx_session_comparisons_other_session_id;
+DROP INDEX IF EXISTS idx_session_comparisons_session_id;
+DROP TABLE IF EXISTS session_comparisons;
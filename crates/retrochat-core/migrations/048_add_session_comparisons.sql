@@ -0,0 +1,27 @@
+-- Migration: 048_add_session_comparisons.sql
+-- Description: Head-to-head LLM comparisons of two sessions
+
+-- =============================================================================
+-- Table: session_comparisons
+-- Purpose: The pair of sessions behind a comparison request and, once the
+--          request completes, the LLM's structured comparison
+-- Lifecycle: Created with its analytics request; removed with the request or
+--            either session
+-- =============================================================================
+CREATE TABLE IF NOT EXISTS session_comparisons (
+    analytics_request_id TEXT PRIMARY KEY,
+    session_id TEXT NOT NULL,          -- first session, also the request's session
+    other_session_id TEXT NOT NULL,
+    result_json TEXT,                  -- NULL until the request completes
+    model_used TEXT,
+    analysis_duration_ms INTEGER,
+    generated_at TEXT,
+    FOREIGN KEY (analytics_request_id) REFERENCES analytics_requests(id) ON DELETE CASCADE,
+    FOREIGN KEY (session_id) REFERENCES chat_sessions(id) ON DELETE CASCADE,
+    FOREIGN KEY (other_session_id) REFERENCES chat_sessions(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_session_comparisons_session_id
+    ON session_comparisons(session_id);
+CREATE INDEX IF NOT EXISTS idx_session_comparisons_other_session_id
+    ON session_comparisons(other_session_id);
@@ -0,0 +1,6 @@
+-- Migration: 049_add_experiments.down.sql
+-- Description: Revert 049_add_experiments.sql
+
+DROP INDEX IF EXISTS idx_experiment_sessions_session_id;
+DROP TABLE IF EXISTS experiment_sessions;
+DROP TABLE IF EXISTS experiments;
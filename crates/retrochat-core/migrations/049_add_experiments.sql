@@ -0,0 +1,33 @@
+-- Migration: 049_add_experiments.sql
+-- Description: Named experiments grouping sessions into cohorts for A/B comparisons
+
+-- =============================================================================
+-- Table: experiments
+-- Purpose: A named comparison, e.g. one provider or model against another
+-- Lifecycle: Created by `retrochat experiments create`; removed by
+--            `retrochat experiments delete`
+-- =============================================================================
+CREATE TABLE IF NOT EXISTS experiments (
+    name TEXT PRIMARY KEY,
+    description TEXT,
+    created_at TEXT NOT NULL
+);
+
+-- =============================================================================
+-- Table: experiment_sessions
+-- Purpose: The cohort each session belongs to within an experiment
+-- Lifecycle: Added and removed by the user; removed with the experiment or
+--            the session. Unlike session_tags, never touched by imports
+-- =============================================================================
+CREATE TABLE IF NOT EXISTS experiment_sessions (
+    experiment_name TEXT NOT NULL,
+    session_id TEXT NOT NULL,
+    cohort TEXT NOT NULL,
+    added_at TEXT NOT NULL,
+    PRIMARY KEY (experiment_name, session_id),
+    FOREIGN KEY (experiment_name) REFERENCES experiments(name) ON DELETE CASCADE,
+    FOREIGN KEY (session_id) REFERENCES chat_sessions(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_experiment_sessions_session_id
+    ON experiment_sessions(session_id);
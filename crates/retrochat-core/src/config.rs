@@ -5,22 +5,834 @@
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 
 use crate::env::apis as env_apis;
+use crate::env::llm as env_llm;
+use crate::env::serve as env_serve;
+use crate::env::sync as env_sync;
+use crate::logging::LogRotation;
+use crate::services::embedding::EmbeddingProvider;
+use crate::services::llm::LlmProvider;
+use crate::utils::locale::Language;
+
+/// Idle threshold used when `analysis.idle_gap_minutes` is unset
+pub const DEFAULT_IDLE_GAP_MINUTES: u32 = 30;
+
+/// Largest message kept whole when `import.max_message_kb` is unset
+pub const DEFAULT_MAX_MESSAGE_KB: u64 = 1024;
+
+/// Largest tool result kept whole when `import.max_tool_result_kb` is unset
+pub const DEFAULT_MAX_TOOL_RESULT_KB: u64 = 512;
+
+/// Largest upload accepted by `retrochat serve` when `serve.max_upload_mb` is unset
+pub const DEFAULT_MAX_UPLOAD_MB: u64 = 50;
 
 /// Configuration structure matching config.toml
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Config {
     #[serde(default)]
     pub api: ApiConfig,
+    #[serde(default, skip_serializing_if = "WatchConfig::is_empty")]
+    pub watch: WatchConfig,
+    #[serde(default, skip_serializing_if = "ParsersConfig::is_empty")]
+    pub parsers: ParsersConfig,
+    #[serde(default, skip_serializing_if = "RetentionConfig::is_empty")]
+    pub retention: RetentionConfig,
+    #[serde(default, skip_serializing_if = "LlmSettings::is_empty")]
+    pub llm: LlmSettings,
+    #[serde(default, skip_serializing_if = "EmbeddingSettings::is_empty")]
+    pub embedding: EmbeddingSettings,
+    #[serde(default, skip_serializing_if = "QuotaConfig::is_empty")]
+    pub quota: QuotaConfig,
+    #[serde(default, skip_serializing_if = "SyncConfig::is_empty")]
+    pub sync: SyncConfig,
+    #[serde(default, skip_serializing_if = "ServeSettings::is_empty")]
+    pub serve: ServeSettings,
+    #[serde(default, skip_serializing_if = "AnalysisSettings::is_empty")]
+    pub analysis: AnalysisSettings,
+    #[serde(default, skip_serializing_if = "LoggingSettings::is_empty")]
+    pub logging: LoggingSettings,
+    #[serde(default, skip_serializing_if = "AttachmentSettings::is_empty")]
+    pub attachments: AttachmentSettings,
+    #[serde(default, skip_serializing_if = "IgnoreSettings::is_empty")]
+    pub ignore: IgnoreSettings,
+    #[serde(default, skip_serializing_if = "ImportSettings::is_empty")]
+    pub import: ImportSettings,
+    #[serde(default, skip_serializing_if = "TuiSettings::is_empty")]
+    pub tui: TuiSettings,
+    #[serde(default, skip_serializing_if = "TimeSettings::is_empty")]
+    pub time: TimeSettings,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ApiConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub google_ai_api_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub openai_api_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub voyage_api_key: Option<String>,
+    /// Token with the `gist` scope, used by `retrochat share --gist`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub github_token: Option<String>,
+}
+
+/// File watcher configuration (`[watch]` section)
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct WatchConfig {
+    /// Custom watch roots in addition to provider defaults
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<WatchPathConfig>,
+    /// Glob patterns for files to ignore (e.g. "**/*.tmp")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ignore: Vec<String>,
+}
+
+/// A custom watch root with an optional provider hint
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WatchPathConfig {
+    pub path: String,
+    /// Provider used to parse files under this root (e.g. "claude"), skipping detection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+}
+
+/// Parser plugin configuration (`[parsers]` section)
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct ParsersConfig {
+    /// External command parsers, declared as `[[parsers.external]]` tables
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub external: Vec<ExternalParserConfig>,
+    /// Parsing rules for MCP tools, declared as `[[parsers.mcp]]` tables
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mcp: Vec<McpToolRuleConfig>,
+}
+
+/// Maps a glob to a command that converts matching files to RetroChat's session JSON
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExternalParserConfig {
+    /// Provider name recorded on imported sessions (e.g. "aider")
+    pub name: String,
+    /// Glob matched against the full path or the file name (e.g. "**/*.aider.chat.md")
+    pub pattern: String,
+    /// Command to run; `{file}` is replaced by the file path, otherwise it is appended
+    pub command: String,
+}
+
+/// How to read calls to an MCP tool; paths are JSONPaths such as `$.issue.title`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct McpToolRuleConfig {
+    /// Tool name or glob (e.g. "mcp__github__*"); the first matching rule applies
+    pub tool: String,
+    /// Input field naming what the call acted on, shown before the result summary
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    /// Result field that must be truthy for the call to count as a success
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub success: Option<String>,
+    /// Result field used as the summary instead of the raw result text
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+}
+
+/// Retention policy (`[retention]` section), applied by `retrochat maintenance`
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct RetentionConfig {
+    /// Archive sessions that ended more than this many days ago
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archive_after_days: Option<u32>,
+    /// Drop raw tool output (keeping summaries) for sessions older than this many days
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub purge_tool_results_after_days: Option<u32>,
+}
+
+/// LLM provider settings (`[llm]` section)
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct LlmSettings {
+    /// Provider used for analyses and summaries unless one is passed in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    /// Model of that provider; each provider has a default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(default, skip_serializing_if = "LlmRetryConfig::is_empty")]
+    pub retry: LlmRetryConfig,
+    #[serde(default, skip_serializing_if = "LlmCacheSettings::is_empty")]
+    pub cache: LlmCacheSettings,
+}
+
+/// Response cache settings (`[llm.cache]`)
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct LlmCacheSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    /// Reuse cached responses for this many days
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl_days: Option<u32>,
+    /// Evict least recently used responses beyond this size
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_size_mb: Option<u64>,
+}
+
+/// Retry policy (`[llm.retry]`), with per-provider overrides in
+/// `[llm.retry.providers.<provider>]`
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct LlmRetryConfig {
+    #[serde(flatten)]
+    pub defaults: RetrySettings,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub providers: BTreeMap<String, RetrySettings>,
+}
+
+/// Retry settings; unset fields fall back to the next level up
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct RetrySettings {
+    /// Total attempts per request, including the first
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_attempts: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub initial_delay_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_delay_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multiplier: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jitter: Option<bool>,
+    /// Wait as long as a rate-limited server asks instead of backing off
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub respect_retry_after: Option<bool>,
+}
+
+/// Embedding backend for the summary index (`[embedding]` section)
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct EmbeddingSettings {
+    /// local, openai, google or voyage
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    /// Provider model; each API provider has a default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Vector size; required for models RetroChat doesn't know, and
+    /// requested from the API when set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dimensions: Option<usize>,
+}
+
+/// Usage alert thresholds (`[quota]` section), checked after imports and
+/// analyses
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct QuotaConfig {
+    /// Tokens across all imported sessions in one UTC day
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily_tokens: Option<u64>,
+    /// Tokens spent on LLM analyses in one week (Monday to Sunday, UTC)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weekly_analysis_tokens: Option<u64>,
+    /// Dollars spent on LLM analyses in one week; only providers that report
+    /// a cost (Claude Code) count towards it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weekly_analysis_usd: Option<f64>,
+    /// Show a desktop notification when a threshold is crossed (default true)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notify: Option<bool>,
+}
+
+/// LLM output settings (`[analysis]` section)
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct AnalysisSettings {
+    /// Language code analyses, summaries and titles are written in, e.g. ko
+    /// or ja; also picks the date format. Defaults to en
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Silence between messages, in minutes, counted as idle rather than
+    /// active time. Defaults to 30
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idle_gap_minutes: Option<u32>,
+    /// Check edited files against their git repositories to tell whether a
+    /// session's changes made it into a later commit. Defaults to false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_correlation: Option<bool>,
+    /// Weights of rubrics in the overall rubric score, by rubric ID or name,
+    /// e.g. `"Quality Verification" = 2.0`; unlisted rubrics keep the weight
+    /// from their definition (1.0) and 0 leaves a rubric out
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub rubric_weights: BTreeMap<String, f64>,
+}
+
+/// Log output settings (`[logging]` section); `RETROCHAT_LOG_LEVEL` and
+/// `RETROCHAT_LOG_FORMAT` take precedence
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct LoggingSettings {
+    /// error, warn, info (default), debug or trace
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<String>,
+    /// text (default) or json
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// never, hourly or daily (default)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rotation: Option<String>,
+    /// Rotate the log file past this size (default 10); 0 disables the limit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_size_mb: Option<u64>,
+    /// Rotated log files kept (default 7)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_files: Option<usize>,
+    /// Level overrides by target, e.g. `"retrochat_core::services::watch_service" = "debug"`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub modules: BTreeMap<String, String>,
+}
+
+/// Pasted image and document settings (`[attachments]` section)
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct AttachmentSettings {
+    /// Save attachment bytes under ~/.retrochat/attachments at import;
+    /// otherwise only their metadata is kept. Defaults to false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub store_blobs: Option<bool>,
+}
+
+/// Row size limits applied at import (`[import]` section); larger content
+/// is truncated and flagged rather than stored whole
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct ImportSettings {
+    /// Largest message body kept whole, in KB. Defaults to 1024; 0 disables
+    /// the limit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_message_kb: Option<u64>,
+    /// Largest raw tool result or command output kept whole, in KB.
+    /// Defaults to 512; 0 disables the limit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tool_result_kb: Option<u64>,
+    /// Keep each message's log entry and each tool call's JSON as written,
+    /// in the `raw_payloads` table. Defaults to false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_payloads: Option<bool>,
+}
+
+/// Terminal UI settings (`[tui]` section)
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct TuiSettings {
+    /// Capture the mouse for clicking and wheel scrolling; turn off to keep
+    /// the terminal's own text selection. Defaults to true
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mouse: Option<bool>,
+}
+
+/// Calendar settings for time expressions (`[time]` section)
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct TimeSettings {
+    /// First day of any sprint (YYYY-MM-DD); "this sprint" and "last sprint"
+    /// count from it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sprint_start: Option<String>,
+    /// Sprint length in days. Defaults to 14
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sprint_length_days: Option<u32>,
+}
+
+/// Sessions kept out of the database (`[ignore]` section), applied by
+/// imports and watch mode; more path globs can go in
+/// `~/.retrochat/.retrochatignore`, one per line
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct IgnoreSettings {
+    /// Globs matched against the session file path or file name
+    /// (e.g. "**/-tmp-*/**" for Claude Code sessions started in /tmp)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<String>,
+    /// Skip sessions with fewer messages than this
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_messages: Option<u32>,
+    /// Project names to skip; globs are allowed (e.g. "scratch-*")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub projects: Vec<String>,
+}
+
+/// Remote sync settings (`[sync]` section), managed by `retrochat remote`
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct SyncConfig {
+    /// Passphrase bundles are encrypted with; `RETROCHAT_SYNC_PASSPHRASE`
+    /// takes precedence
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub passphrase: Option<String>,
+    /// Remotes, declared as `[[sync.remotes]]` tables
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remotes: Vec<RemoteSettings>,
+}
+
+/// HTTP API settings (`[serve]` section)
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct ServeSettings {
+    /// Bearer token clients must send; `RETROCHAT_SERVE_TOKEN` takes
+    /// precedence. Required to listen on anything but localhost
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// Largest upload accepted by `POST /api/import`, in MB. Defaults to 50
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_upload_mb: Option<u64>,
+}
+
+/// Storage that sync bundles are pushed to and pulled from
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct RemoteSettings {
+    pub name: String,
+    /// `s3://bucket/prefix`, an `https://` WebDAV folder, or a local folder
+    pub url: String,
+    /// S3-compatible endpoint (e.g. MinIO or R2) instead of AWS
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+    /// S3 region (default us-east-1, or `AWS_REGION`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+    /// S3 credentials; `AWS_ACCESS_KEY_ID` and `AWS_SECRET_ACCESS_KEY` are
+    /// used when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access_key_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret_access_key: Option<String>,
+    /// WebDAV basic auth credentials
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+}
+
+impl SyncConfig {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    pub fn find_remote(&self, name: &str) -> Option<&RemoteSettings> {
+        self.remotes.iter().find(|r| r.name == name)
+    }
+
+    /// The named remote, or the only one when no name is given
+    pub fn resolve_remote(&self, name: Option<&str>) -> Result<&RemoteSettings> {
+        match name {
+            Some(name) => self
+                .find_remote(name)
+                .with_context(|| format!("No remote named {name}; see `retrochat remote list`")),
+            None => match self.remotes.as_slice() {
+                [remote] => Ok(remote),
+                [] => anyhow::bail!("No remotes configured; add one with `retrochat remote add`"),
+                _ => anyhow::bail!("Several remotes are configured; name the one to use"),
+            },
+        }
+    }
+
+    /// Add a remote, or replace the one with the same name
+    pub fn add_remote(&mut self, remote: RemoteSettings) {
+        match self.remotes.iter_mut().find(|r| r.name == remote.name) {
+            Some(existing) => *existing = remote,
+            None => self.remotes.push(remote),
+        }
+    }
+
+    /// Returns false if there was no remote with that name
+    pub fn remove_remote(&mut self, name: &str) -> bool {
+        let before = self.remotes.len();
+        self.remotes.retain(|r| r.name != name);
+        self.remotes.len() != before
+    }
+
+    /// Bundle passphrase with priority: environment variable > config file
+    pub fn passphrase(&self) -> Result<String> {
+        if let Ok(passphrase) = std::env::var(env_sync::SYNC_PASSPHRASE) {
+            if !passphrase.is_empty() {
+                return Ok(passphrase);
+            }
+        }
+        self.passphrase.clone().filter(|p| !p.is_empty()).with_context(|| {
+            format!(
+                "Sync bundles are encrypted; set {} or `retrochat config set sync.passphrase <passphrase>`",
+                env_sync::SYNC_PASSPHRASE
+            )
+        })
+    }
+}
+
+impl ServeSettings {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// API token with priority: environment variable > config file
+    pub fn token(&self) -> Option<String> {
+        std::env::var(env_serve::SERVE_TOKEN)
+            .ok()
+            .or_else(|| self.token.clone())
+            .filter(|token| !token.is_empty())
+    }
+
+    /// Largest upload accepted, in bytes
+    pub fn max_upload_bytes(&self) -> usize {
+        (self.max_upload_mb.unwrap_or(DEFAULT_MAX_UPLOAD_MB) as usize).saturating_mul(1024 * 1024)
+    }
+}
+
+impl EmbeddingSettings {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+impl LoggingSettings {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+impl AnalysisSettings {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Shortest silence between messages that counts as idle time
+    pub fn idle_gap(&self) -> chrono::Duration {
+        chrono::Duration::minutes(
+            self.idle_gap_minutes
+                .unwrap_or(DEFAULT_IDLE_GAP_MINUTES)
+                .into(),
+        )
+    }
+
+    pub fn git_correlation(&self) -> bool {
+        self.git_correlation.unwrap_or(false)
+    }
+}
+
+impl IgnoreSettings {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+impl TuiSettings {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Whether the TUI should capture the mouse
+    pub fn mouse_enabled(&self) -> bool {
+        self.mouse.unwrap_or(true)
+    }
+}
+
+impl TimeSettings {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+impl ImportSettings {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Largest message body kept whole, in bytes, or `None` for no limit
+    pub fn max_message_bytes(&self) -> Option<usize> {
+        kb_limit(self.max_message_kb.unwrap_or(DEFAULT_MAX_MESSAGE_KB))
+    }
+
+    /// Whether imports keep the source JSON next to the normalized rows
+    pub fn keep_raw_payloads(&self) -> bool {
+        self.raw_payloads.unwrap_or(false)
+    }
+
+    /// Largest tool result kept whole, in bytes, or `None` for no limit
+    pub fn max_tool_result_bytes(&self) -> Option<usize> {
+        kb_limit(
+            self.max_tool_result_kb
+                .unwrap_or(DEFAULT_MAX_TOOL_RESULT_KB),
+        )
+    }
+}
+
+fn kb_limit(kb: u64) -> Option<usize> {
+    (kb > 0).then(|| (kb as usize).saturating_mul(1024))
+}
+
+impl AttachmentSettings {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Directory attachment bytes are saved in, or `None` when storage is off
+    pub fn blob_dir(&self) -> Option<PathBuf> {
+        if self.store_blobs != Some(true) {
+            return None;
+        }
+        crate::database::config::get_config_dir()
+            .ok()
+            .map(|dir| dir.join("attachments"))
+    }
+}
+
+impl QuotaConfig {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Whether any threshold is set
+    pub fn has_thresholds(&self) -> bool {
+        self.daily_tokens.is_some()
+            || self.weekly_analysis_tokens.is_some()
+            || self.weekly_analysis_usd.is_some()
+    }
+}
+
+impl LlmSettings {
+    pub fn is_empty(&self) -> bool {
+        self.provider.is_none()
+            && self.model.is_none()
+            && self.retry.is_empty()
+            && self.cache.is_empty()
+    }
+}
+
+impl LlmCacheSettings {
+    pub fn is_empty(&self) -> bool {
+        self.enabled.is_none() && self.ttl_days.is_none() && self.max_size_mb.is_none()
+    }
+}
+
+impl LlmRetryConfig {
+    pub fn is_empty(&self) -> bool {
+        self.defaults.is_empty() && self.providers.is_empty()
+    }
+
+    /// Split the part of a key after `llm.retry.` into the provider (if any)
+    /// and the field, e.g. `claude-code.max_attempts`
+    fn parse_key(key: &str) -> Result<(Option<String>, &str)> {
+        match key.rsplit_once('.') {
+            Some((provider, field)) => {
+                let provider = provider
+                    .parse::<LlmProvider>()
+                    .map_err(|e| anyhow::anyhow!(e))?;
+                Ok((Some(provider.to_string()), field))
+            }
+            None => Ok((None, key)),
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<String> {
+        let (provider, field) = Self::parse_key(key).ok()?;
+        match provider {
+            Some(provider) => self.providers.get(&provider)?.get(field),
+            None => self.defaults.get(field),
+        }
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        let (provider, field) = Self::parse_key(key)?;
+        match provider {
+            Some(provider) => self
+                .providers
+                .entry(provider)
+                .or_default()
+                .set(field, value),
+            None => self.defaults.set(field, value),
+        }
+    }
+
+    fn unset(&mut self, key: &str) -> Result<()> {
+        let (provider, field) = Self::parse_key(key)?;
+        match provider {
+            Some(provider) => {
+                if let Some(settings) = self.providers.get_mut(&provider) {
+                    settings.set_field(field, None)?;
+                    if settings.is_empty() {
+                        self.providers.remove(&provider);
+                    }
+                }
+                Ok(())
+            }
+            None => self.defaults.set_field(field, None),
+        }
+    }
+
+    fn list(&self) -> Vec<(String, String)> {
+        let mut items: Vec<(String, String)> = self
+            .defaults
+            .list()
+            .into_iter()
+            .map(|(field, value)| (format!("llm.retry.{field}"), value))
+            .collect();
+        for (provider, settings) in &self.providers {
+            items.extend(
+                settings
+                    .list()
+                    .into_iter()
+                    .map(|(field, value)| (format!("llm.retry.{provider}.{field}"), value)),
+            );
+        }
+        items
+    }
+}
+
+impl RetrySettings {
+    const FIELDS: &'static [&'static str] = &[
+        "max_attempts",
+        "initial_delay_ms",
+        "max_delay_ms",
+        "multiplier",
+        "jitter",
+        "respect_retry_after",
+    ];
+
+    pub fn is_empty(&self) -> bool {
+        Self::FIELDS.iter().all(|field| self.get(field).is_none())
+    }
+
+    fn get(&self, field: &str) -> Option<String> {
+        match field {
+            "max_attempts" => self.max_attempts.map(|v| v.to_string()),
+            "initial_delay_ms" => self.initial_delay_ms.map(|v| v.to_string()),
+            "max_delay_ms" => self.max_delay_ms.map(|v| v.to_string()),
+            "multiplier" => self.multiplier.map(|v| v.to_string()),
+            "jitter" => self.jitter.map(|v| v.to_string()),
+            "respect_retry_after" => self.respect_retry_after.map(|v| v.to_string()),
+            _ => None,
+        }
+    }
+
+    fn set(&mut self, field: &str, value: &str) -> Result<()> {
+        self.set_field(field, Some(value.trim()))
+    }
+
+    /// Set (or with `None`, clear) a field from its string form
+    fn set_field(&mut self, field: &str, value: Option<&str>) -> Result<()> {
+        fn parse<T: std::str::FromStr>(field: &str, value: Option<&str>) -> Result<Option<T>> {
+            value
+                .map(|v| {
+                    v.parse()
+                        .map_err(|_| anyhow::anyhow!("Invalid value for {field}: {v}"))
+                })
+                .transpose()
+        }
+
+        match field {
+            "max_attempts" => {
+                let attempts: Option<u32> = parse(field, value)?;
+                if attempts == Some(0) {
+                    anyhow::bail!("max_attempts must be at least 1");
+                }
+                self.max_attempts = attempts;
+            }
+            "initial_delay_ms" => self.initial_delay_ms = parse(field, value)?,
+            "max_delay_ms" => self.max_delay_ms = parse(field, value)?,
+            "multiplier" => {
+                let multiplier: Option<f64> = parse(field, value)?;
+                if multiplier.is_some_and(|m| m < 1.0) {
+                    anyhow::bail!("multiplier must be at least 1.0");
+                }
+                self.multiplier = multiplier;
+            }
+            "jitter" => self.jitter = parse(field, value)?,
+            "respect_retry_after" => self.respect_retry_after = parse(field, value)?,
+            _ => anyhow::bail!(
+                "Unknown retry setting: {field}. Valid options: {}",
+                Self::FIELDS.join(", ")
+            ),
+        }
+        Ok(())
+    }
+
+    fn list(&self) -> Vec<(&'static str, String)> {
+        Self::FIELDS
+            .iter()
+            .filter_map(|field| self.get(field).map(|value| (*field, value)))
+            .collect()
+    }
+}
+
+impl RetentionConfig {
+    pub fn is_empty(&self) -> bool {
+        self.archive_after_days.is_none() && self.purge_tool_results_after_days.is_none()
+    }
+}
+
+impl ParsersConfig {
+    pub fn is_empty(&self) -> bool {
+        self.external.is_empty() && self.mcp.is_empty()
+    }
+
+    fn find_external(&self, name: &str) -> Option<&ExternalParserConfig> {
+        self.external.iter().find(|p| p.name == name)
+    }
+
+    fn find_mcp(&self, tool: &str) -> Option<&McpToolRuleConfig> {
+        self.mcp.iter().find(|r| r.tool == tool)
+    }
+}
+
+impl McpToolRuleConfig {
+    /// "subject=$.title success=$.ok" for `config get` and `config list`
+    fn describe(&self) -> String {
+        let fields = [
+            ("subject", &self.subject),
+            ("success", &self.success),
+            ("summary", &self.summary),
+        ];
+        let parts: Vec<String> = fields
+            .into_iter()
+            .filter_map(|(name, path)| path.as_ref().map(|path| format!("{name}={path}")))
+            .collect();
+        if parts.is_empty() {
+            "(no fields)".to_string()
+        } else {
+            parts.join(" ")
+        }
+    }
+}
+
+impl WatchConfig {
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty() && self.ignore.is_empty()
+    }
+
+    /// Parse `watch.paths` value: comma-separated `path` or `path=provider` entries
+    fn parse_paths(value: &str) -> Vec<WatchPathConfig> {
+        split_list(value)
+            .into_iter()
+            .map(|entry| match entry.rsplit_once('=') {
+                Some((path, provider)) if !provider.trim().is_empty() => WatchPathConfig {
+                    path: path.trim().to_string(),
+                    provider: Some(provider.trim().to_string()),
+                },
+                _ => WatchPathConfig {
+                    path: entry.trim_end_matches('=').to_string(),
+                    provider: None,
+                },
+            })
+            .collect()
+    }
+
+    fn format_paths(&self) -> Option<String> {
+        if self.paths.is_empty() {
+            return None;
+        }
+        let entries: Vec<String> = self
+            .paths
+            .iter()
+            .map(|p| match &p.provider {
+                Some(provider) => format!("{}={provider}", p.path),
+                None => p.path.clone(),
+            })
+            .collect();
+        Some(entries.join(","))
+    }
+
+    fn format_ignore(&self) -> Option<String> {
+        if self.ignore.is_empty() {
+            None
+        } else {
+            Some(self.ignore.join(","))
+        }
+    }
 }
 
 impl Config {
@@ -84,7 +896,78 @@ impl Config {
     pub fn get(&self, key: &str) -> Option<String> {
         match key {
             "google-ai-api-key" | "google_ai_api_key" => self.api.google_ai_api_key.clone(),
-            _ => None,
+            "openai-api-key" | "openai_api_key" => self.api.openai_api_key.clone(),
+            "voyage-api-key" | "voyage_api_key" => self.api.voyage_api_key.clone(),
+            "github-token" | "github_token" => self.api.github_token.clone(),
+            "watch.paths" => self.watch.format_paths(),
+            "watch.ignore" => self.watch.format_ignore(),
+            "retention.archive_after_days" => {
+                self.retention.archive_after_days.map(|d| d.to_string())
+            }
+            "retention.purge_tool_results_after_days" => self
+                .retention
+                .purge_tool_results_after_days
+                .map(|d| d.to_string()),
+            "llm.provider" => self.llm.provider.clone(),
+            "llm.model" => self.llm.model.clone(),
+            "llm.cache.enabled" => self.llm.cache.enabled.map(|v| v.to_string()),
+            "llm.cache.ttl_days" => self.llm.cache.ttl_days.map(|v| v.to_string()),
+            "llm.cache.max_size_mb" => self.llm.cache.max_size_mb.map(|v| v.to_string()),
+            _ if key.starts_with("llm.retry.") => self.llm.retry.get(&key["llm.retry.".len()..]),
+            "embedding.provider" => self.embedding.provider.clone(),
+            "embedding.model" => self.embedding.model.clone(),
+            "embedding.dimensions" => self.embedding.dimensions.map(|v| v.to_string()),
+            "quota.daily_tokens" => self.quota.daily_tokens.map(|v| v.to_string()),
+            "quota.weekly_analysis_tokens" => {
+                self.quota.weekly_analysis_tokens.map(|v| v.to_string())
+            }
+            "quota.weekly_analysis_usd" => self.quota.weekly_analysis_usd.map(|v| v.to_string()),
+            "quota.notify" => self.quota.notify.map(|v| v.to_string()),
+            "sync.passphrase" => self.sync.passphrase.clone(),
+            "serve.token" => self.serve.token.clone(),
+            "serve.max_upload_mb" => self.serve.max_upload_mb.map(|v| v.to_string()),
+            "analysis.language" => self.analysis.language.clone(),
+            "analysis.idle_gap_minutes" => self.analysis.idle_gap_minutes.map(|m| m.to_string()),
+            "analysis.git_correlation" => self.analysis.git_correlation.map(|v| v.to_string()),
+            "logging.level" => self.logging.level.clone(),
+            "logging.format" => self.logging.format.clone(),
+            "logging.rotation" => self.logging.rotation.clone(),
+            "logging.max_size_mb" => self.logging.max_size_mb.map(|v| v.to_string()),
+            "logging.max_files" => self.logging.max_files.map(|v| v.to_string()),
+            "attachments.store_blobs" => self.attachments.store_blobs.map(|v| v.to_string()),
+            "import.max_message_kb" => self.import.max_message_kb.map(|v| v.to_string()),
+            "import.max_tool_result_kb" => self.import.max_tool_result_kb.map(|v| v.to_string()),
+            "import.raw_payloads" => self.import.raw_payloads.map(|v| v.to_string()),
+            "tui.mouse" => self.tui.mouse.map(|v| v.to_string()),
+            "time.sprint_start" => self.time.sprint_start.clone(),
+            "time.sprint_length_days" => self.time.sprint_length_days.map(|d| d.to_string()),
+            "ignore.paths" => (!self.ignore.paths.is_empty()).then(|| self.ignore.paths.join(",")),
+            "ignore.min_messages" => self.ignore.min_messages.map(|v| v.to_string()),
+            "ignore.projects" => {
+                (!self.ignore.projects.is_empty()).then(|| self.ignore.projects.join(","))
+            }
+            _ if key.starts_with("logging.modules.") => self
+                .logging
+                .modules
+                .get(&key["logging.modules.".len()..])
+                .cloned(),
+            _ if key.starts_with("analysis.rubric_weights.") => self
+                .analysis
+                .rubric_weights
+                .get(&key["analysis.rubric_weights.".len()..])
+                .map(|weight| weight.to_string()),
+            _ if key.starts_with("sync.remotes.") => self
+                .sync
+                .find_remote(&key["sync.remotes.".len()..])
+                .map(|r| r.url.clone()),
+            _ if key.starts_with("parsers.mcp.") => self
+                .parsers
+                .find_mcp(&key["parsers.mcp.".len()..])
+                .map(McpToolRuleConfig::describe),
+            _ => key
+                .strip_prefix("parsers.external.")
+                .and_then(|name| self.parsers.find_external(name))
+                .map(|p| format!("{} => {}", p.pattern, p.command)),
         }
     }
 
@@ -94,6 +977,269 @@ impl Config {
             "google-ai-api-key" | "google_ai_api_key" => {
                 self.api.google_ai_api_key = Some(value);
             }
+            "openai-api-key" | "openai_api_key" => {
+                self.api.openai_api_key = Some(value);
+            }
+            "voyage-api-key" | "voyage_api_key" => {
+                self.api.voyage_api_key = Some(value);
+            }
+            "github-token" | "github_token" => {
+                self.api.github_token = Some(value);
+            }
+            "watch.paths" => {
+                self.watch.paths = WatchConfig::parse_paths(&value);
+            }
+            "watch.ignore" => {
+                for pattern in split_list(&value) {
+                    glob::Pattern::new(&pattern)
+                        .with_context(|| format!("Invalid ignore glob: {pattern}"))?;
+                }
+                self.watch.ignore = split_list(&value);
+            }
+            "retention.archive_after_days" => {
+                self.retention.archive_after_days = Some(parse_days(&value)?);
+            }
+            "retention.purge_tool_results_after_days" => {
+                self.retention.purge_tool_results_after_days = Some(parse_days(&value)?);
+            }
+            "llm.provider" => {
+                let provider: LlmProvider = value.parse().map_err(anyhow::Error::msg)?;
+                self.llm.provider = Some(provider.to_string());
+            }
+            "llm.model" => {
+                self.llm.model = Some(value.trim().to_string());
+            }
+            "llm.cache.enabled" => {
+                self.llm.cache.enabled = Some(
+                    value
+                        .trim()
+                        .parse()
+                        .with_context(|| format!("Expected true or false, got: {value}"))?,
+                );
+            }
+            "llm.cache.ttl_days" => {
+                self.llm.cache.ttl_days = Some(parse_days(&value)?);
+            }
+            "llm.cache.max_size_mb" => {
+                self.llm.cache.max_size_mb = Some(
+                    value
+                        .trim()
+                        .parse()
+                        .with_context(|| format!("Expected a size in MB, got: {value}"))?,
+                );
+            }
+            _ if key.starts_with("llm.retry.") => {
+                self.llm.retry.set(&key["llm.retry.".len()..], &value)?;
+            }
+            "embedding.provider" => {
+                let provider: EmbeddingProvider = value.parse().map_err(anyhow::Error::msg)?;
+                self.embedding.provider = Some(provider.to_string());
+            }
+            "embedding.model" => {
+                self.embedding.model = Some(value.trim().to_string());
+            }
+            "embedding.dimensions" => {
+                let dimensions: usize = value
+                    .trim()
+                    .parse()
+                    .with_context(|| format!("Expected a number of dimensions, got: {value}"))?;
+                if dimensions == 0 {
+                    anyhow::bail!("Embeddings need at least 1 dimension");
+                }
+                self.embedding.dimensions = Some(dimensions);
+            }
+            "quota.daily_tokens" => {
+                self.quota.daily_tokens = Some(parse_token_limit(&value)?);
+            }
+            "quota.weekly_analysis_tokens" => {
+                self.quota.weekly_analysis_tokens = Some(parse_token_limit(&value)?);
+            }
+            "quota.weekly_analysis_usd" => {
+                let usd: f64 = value
+                    .trim()
+                    .trim_start_matches('$')
+                    .parse()
+                    .with_context(|| format!("Expected an amount in USD, got: {value}"))?;
+                if !usd.is_finite() || usd <= 0.0 {
+                    anyhow::bail!("Spend limit must be more than $0");
+                }
+                self.quota.weekly_analysis_usd = Some(usd);
+            }
+            "quota.notify" => {
+                self.quota.notify = Some(
+                    value
+                        .trim()
+                        .parse()
+                        .with_context(|| format!("Expected true or false, got: {value}"))?,
+                );
+            }
+            "analysis.language" => {
+                let language: Language = value.parse().map_err(anyhow::Error::msg)?;
+                self.analysis.language = Some(language.to_string());
+            }
+            "analysis.idle_gap_minutes" => {
+                let minutes: u32 = value
+                    .trim()
+                    .parse()
+                    .with_context(|| format!("Expected a number of minutes, got: {value}"))?;
+                if minutes == 0 {
+                    anyhow::bail!("analysis.idle_gap_minutes must be at least 1");
+                }
+                self.analysis.idle_gap_minutes = Some(minutes);
+            }
+            "analysis.git_correlation" => {
+                self.analysis.git_correlation = Some(
+                    value
+                        .trim()
+                        .parse()
+                        .with_context(|| format!("Expected true or false, got: {value}"))?,
+                );
+            }
+            "logging.level" => {
+                self.logging.level = Some(parse_log_level(&value)?);
+            }
+            "logging.format" => {
+                let format = value.trim().to_lowercase();
+                if format != "text" && format != "json" {
+                    anyhow::bail!("Expected text or json, got: {value}");
+                }
+                self.logging.format = Some(format);
+            }
+            "logging.rotation" => {
+                let rotation: LogRotation = value.parse().map_err(anyhow::Error::msg)?;
+                self.logging.rotation = Some(rotation.to_string());
+            }
+            "logging.max_size_mb" => {
+                self.logging.max_size_mb = Some(
+                    value
+                        .trim()
+                        .parse()
+                        .with_context(|| format!("Expected a size in MB, got: {value}"))?,
+                );
+            }
+            "logging.max_files" => {
+                self.logging.max_files = Some(
+                    value
+                        .trim()
+                        .parse()
+                        .with_context(|| format!("Expected a number of files, got: {value}"))?,
+                );
+            }
+            "ignore.paths" | "ignore.projects" => {
+                let patterns = split_list(&value);
+                for pattern in &patterns {
+                    glob::Pattern::new(pattern)
+                        .with_context(|| format!("Invalid ignore glob: {pattern}"))?;
+                }
+                if key == "ignore.paths" {
+                    self.ignore.paths = patterns;
+                } else {
+                    self.ignore.projects = patterns;
+                }
+            }
+            "ignore.min_messages" => {
+                self.ignore.min_messages = Some(
+                    value
+                        .trim()
+                        .parse()
+                        .with_context(|| format!("Expected a number of messages, got: {value}"))?,
+                );
+            }
+            "import.max_message_kb" | "import.max_tool_result_kb" => {
+                let kb: u64 = value
+                    .trim()
+                    .parse()
+                    .with_context(|| format!("Expected a size in KB, got: {value}"))?;
+                if key == "import.max_message_kb" {
+                    self.import.max_message_kb = Some(kb);
+                } else {
+                    self.import.max_tool_result_kb = Some(kb);
+                }
+            }
+            "import.raw_payloads" => {
+                self.import.raw_payloads = Some(
+                    value
+                        .trim()
+                        .parse()
+                        .with_context(|| format!("Expected true or false, got: {value}"))?,
+                );
+            }
+            "attachments.store_blobs" => {
+                self.attachments.store_blobs = Some(
+                    value
+                        .trim()
+                        .parse()
+                        .with_context(|| format!("Expected true or false, got: {value}"))?,
+                );
+            }
+            "tui.mouse" => {
+                self.tui.mouse = Some(
+                    value
+                        .trim()
+                        .parse()
+                        .with_context(|| format!("Expected true or false, got: {value}"))?,
+                );
+            }
+            "time.sprint_start" => {
+                let date = chrono::NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+                    .with_context(|| format!("Expected a date (YYYY-MM-DD), got: {value}"))?;
+                self.time.sprint_start = Some(date.to_string());
+            }
+            "time.sprint_length_days" => {
+                self.time.sprint_length_days = Some(parse_days(&value)?);
+            }
+            _ if key.starts_with("logging.modules.") => {
+                let target = &key["logging.modules.".len()..];
+                if target.is_empty() {
+                    anyhow::bail!("Name a module, e.g. logging.modules.retrochat_core::parsers");
+                }
+                self.logging
+                    .modules
+                    .insert(target.to_string(), parse_log_level(&value)?);
+            }
+            _ if key.starts_with("analysis.rubric_weights.") => {
+                let rubric = &key["analysis.rubric_weights.".len()..];
+                if rubric.is_empty() {
+                    anyhow::bail!(
+                        "Name a rubric ID or name, e.g. analysis.rubric_weights.rubric_005"
+                    );
+                }
+                let weight: f64 = value
+                    .trim()
+                    .parse()
+                    .with_context(|| format!("Expected a weight such as 2 or 0.5, got: {value}"))?;
+                if !weight.is_finite() || weight < 0.0 {
+                    anyhow::bail!("Rubric weights must be 0 or more, got: {value}");
+                }
+                self.analysis
+                    .rubric_weights
+                    .insert(rubric.to_string(), weight);
+            }
+            "serve.token" => {
+                if value.trim().is_empty() {
+                    anyhow::bail!("The token can't be empty");
+                }
+                self.serve.token = Some(value);
+            }
+            "serve.max_upload_mb" => {
+                let mb: u64 = value
+                    .trim()
+                    .parse()
+                    .with_context(|| format!("Expected a size in MB, got: {value}"))?;
+                if mb == 0 {
+                    anyhow::bail!("serve.max_upload_mb must be at least 1");
+                }
+                self.serve.max_upload_mb = Some(mb);
+            }
+            "sync.passphrase" => {
+                if value.is_empty() {
+                    anyhow::bail!("The passphrase can't be empty");
+                }
+                self.sync.passphrase = Some(value);
+            }
+            _ if key.starts_with("sync.remotes.") => {
+                anyhow::bail!("Add remotes with `retrochat remote add <name> <url>`")
+            }
             _ => anyhow::bail!("Unknown config key: {key}"),
         }
         Ok(())
@@ -105,24 +1251,440 @@ impl Config {
             "google-ai-api-key" | "google_ai_api_key" => {
                 self.api.google_ai_api_key = None;
             }
-            _ => anyhow::bail!("Unknown config key: {key}"),
-        }
-        Ok(())
-    }
-
-    /// Get all config values as key-value pairs
-    pub fn list(&self) -> Vec<(String, String)> {
-        let mut items = Vec::new();
-
-        if let Some(ref key) = self.api.google_ai_api_key {
-            items.push(("google-ai-api-key".to_string(), mask_api_key(key)));
-        }
-
-        items
-    }
-}
-
-/// Get Google AI API key with priority: environment variable > config file
+            "openai-api-key" | "openai_api_key" => {
+                self.api.openai_api_key = None;
+            }
+            "voyage-api-key" | "voyage_api_key" => {
+                self.api.voyage_api_key = None;
+            }
+            "github-token" | "github_token" => {
+                self.api.github_token = None;
+            }
+            "watch.paths" => {
+                self.watch.paths.clear();
+            }
+            "watch.ignore" => {
+                self.watch.ignore.clear();
+            }
+            "retention.archive_after_days" => {
+                self.retention.archive_after_days = None;
+            }
+            "retention.purge_tool_results_after_days" => {
+                self.retention.purge_tool_results_after_days = None;
+            }
+            "llm.provider" => {
+                self.llm.provider = None;
+            }
+            "llm.model" => {
+                self.llm.model = None;
+            }
+            "llm.cache.enabled" => {
+                self.llm.cache.enabled = None;
+            }
+            "llm.cache.ttl_days" => {
+                self.llm.cache.ttl_days = None;
+            }
+            "llm.cache.max_size_mb" => {
+                self.llm.cache.max_size_mb = None;
+            }
+            _ if key.starts_with("llm.retry.") => {
+                self.llm.retry.unset(&key["llm.retry.".len()..])?;
+            }
+            "embedding.provider" => {
+                self.embedding.provider = None;
+            }
+            "embedding.model" => {
+                self.embedding.model = None;
+            }
+            "embedding.dimensions" => {
+                self.embedding.dimensions = None;
+            }
+            "quota.daily_tokens" => {
+                self.quota.daily_tokens = None;
+            }
+            "quota.weekly_analysis_tokens" => {
+                self.quota.weekly_analysis_tokens = None;
+            }
+            "quota.weekly_analysis_usd" => {
+                self.quota.weekly_analysis_usd = None;
+            }
+            "quota.notify" => {
+                self.quota.notify = None;
+            }
+            "analysis.language" => {
+                self.analysis.language = None;
+            }
+            "analysis.idle_gap_minutes" => {
+                self.analysis.idle_gap_minutes = None;
+            }
+            "analysis.git_correlation" => {
+                self.analysis.git_correlation = None;
+            }
+            "logging.level" => {
+                self.logging.level = None;
+            }
+            "logging.format" => {
+                self.logging.format = None;
+            }
+            "logging.rotation" => {
+                self.logging.rotation = None;
+            }
+            "logging.max_size_mb" => {
+                self.logging.max_size_mb = None;
+            }
+            "logging.max_files" => {
+                self.logging.max_files = None;
+            }
+            "attachments.store_blobs" => {
+                self.attachments.store_blobs = None;
+            }
+            "import.max_message_kb" => {
+                self.import.max_message_kb = None;
+            }
+            "import.max_tool_result_kb" => {
+                self.import.max_tool_result_kb = None;
+            }
+            "import.raw_payloads" => {
+                self.import.raw_payloads = None;
+            }
+            "tui.mouse" => {
+                self.tui.mouse = None;
+            }
+            "time.sprint_start" => {
+                self.time.sprint_start = None;
+            }
+            "time.sprint_length_days" => {
+                self.time.sprint_length_days = None;
+            }
+            "ignore.paths" => {
+                self.ignore.paths.clear();
+            }
+            "ignore.min_messages" => {
+                self.ignore.min_messages = None;
+            }
+            "ignore.projects" => {
+                self.ignore.projects.clear();
+            }
+            _ if key.starts_with("logging.modules.") => {
+                self.logging
+                    .modules
+                    .remove(&key["logging.modules.".len()..]);
+            }
+            _ if key.starts_with("analysis.rubric_weights.") => {
+                self.analysis
+                    .rubric_weights
+                    .remove(&key["analysis.rubric_weights.".len()..]);
+            }
+            "sync.passphrase" => {
+                self.sync.passphrase = None;
+            }
+            "serve.token" => {
+                self.serve.token = None;
+            }
+            "serve.max_upload_mb" => {
+                self.serve.max_upload_mb = None;
+            }
+            _ if key.starts_with("sync.remotes.") => {
+                let name = &key["sync.remotes.".len()..];
+                if !self.sync.remove_remote(name) {
+                    anyhow::bail!("No remote named {name}");
+                }
+            }
+            _ => anyhow::bail!("Unknown config key: {key}"),
+        }
+        Ok(())
+    }
+
+    /// Get all config values as key-value pairs
+    pub fn list(&self) -> Vec<(String, String)> {
+        let mut items = Vec::new();
+
+        if let Some(ref key) = self.api.google_ai_api_key {
+            items.push(("google-ai-api-key".to_string(), mask_api_key(key)));
+        }
+
+        if let Some(ref key) = self.api.openai_api_key {
+            items.push(("openai-api-key".to_string(), mask_api_key(key)));
+        }
+
+        if let Some(ref key) = self.api.voyage_api_key {
+            items.push(("voyage-api-key".to_string(), mask_api_key(key)));
+        }
+
+        if let Some(ref token) = self.api.github_token {
+            items.push(("github-token".to_string(), mask_api_key(token)));
+        }
+
+        if let Some(paths) = self.watch.format_paths() {
+            items.push(("watch.paths".to_string(), paths));
+        }
+
+        if let Some(ignore) = self.watch.format_ignore() {
+            items.push(("watch.ignore".to_string(), ignore));
+        }
+
+        for key in [
+            "retention.archive_after_days",
+            "retention.purge_tool_results_after_days",
+            "llm.provider",
+            "llm.model",
+            "llm.cache.enabled",
+            "llm.cache.ttl_days",
+            "llm.cache.max_size_mb",
+            "embedding.provider",
+            "embedding.model",
+            "embedding.dimensions",
+            "quota.daily_tokens",
+            "quota.weekly_analysis_tokens",
+            "quota.weekly_analysis_usd",
+            "quota.notify",
+            "analysis.language",
+            "analysis.idle_gap_minutes",
+            "analysis.git_correlation",
+            "logging.level",
+            "logging.format",
+            "logging.rotation",
+            "logging.max_size_mb",
+            "logging.max_files",
+            "attachments.store_blobs",
+            "import.max_message_kb",
+            "import.max_tool_result_kb",
+            "import.raw_payloads",
+            "serve.max_upload_mb",
+            "tui.mouse",
+            "time.sprint_start",
+            "time.sprint_length_days",
+            "ignore.paths",
+            "ignore.min_messages",
+            "ignore.projects",
+        ] {
+            if let Some(value) = self.get(key) {
+                items.push((key.to_string(), value));
+            }
+        }
+
+        items.extend(self.llm.retry.list());
+
+        for (target, level) in &self.logging.modules {
+            items.push((format!("logging.modules.{target}"), level.clone()));
+        }
+
+        for (rubric, weight) in &self.analysis.rubric_weights {
+            items.push((
+                format!("analysis.rubric_weights.{rubric}"),
+                weight.to_string(),
+            ));
+        }
+
+        for parser in &self.parsers.external {
+            items.push((
+                format!("parsers.external.{}", parser.name),
+                format!("{} => {}", parser.pattern, parser.command),
+            ));
+        }
+
+        for rule in &self.parsers.mcp {
+            items.push((format!("parsers.mcp.{}", rule.tool), rule.describe()));
+        }
+
+        if let Some(ref passphrase) = self.sync.passphrase {
+            items.push(("sync.passphrase".to_string(), mask_api_key(passphrase)));
+        }
+
+        if let Some(ref token) = self.serve.token {
+            items.push(("serve.token".to_string(), mask_api_key(token)));
+        }
+
+        for remote in &self.sync.remotes {
+            items.push((format!("sync.remotes.{}", remote.name), remote.url.clone()));
+        }
+
+        items
+    }
+
+    /// Check values that parse as TOML but that `set` would refuse (an unknown
+    /// provider, a bad glob, a zero retry count...), returning (key, error) pairs
+    pub fn validate(&self) -> Vec<(String, String)> {
+        self.list()
+            .into_iter()
+            .filter(|(key, _)| {
+                // Masked secrets and entries managed by other commands
+                !matches!(
+                    key.as_str(),
+                    "google-ai-api-key"
+                        | "openai-api-key"
+                        | "voyage-api-key"
+                        | "github-token"
+                        | "watch.paths"
+                        | "sync.passphrase"
+                        | "serve.token"
+                ) && !key.starts_with("parsers.external.")
+                    && !key.starts_with("parsers.mcp.")
+                    && !key.starts_with("sync.remotes.")
+            })
+            .filter_map(|(key, value)| {
+                let mut probe = self.clone();
+                probe
+                    .set(&key, value)
+                    .err()
+                    .map(|e| (key, format!("{e:#}")))
+            })
+            .collect()
+    }
+}
+
+/// A problem found in a config file; `line` is 1-based
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigIssue {
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+/// Value swapped in to probe whether a key is read at all: no setting
+/// accepts a datetime, so only unknown keys still deserialize
+const UNKNOWN_KEY_PROBE: &str = "1979-05-27T00:00:00Z";
+
+/// Validate config.toml contents, reporting syntax errors, type errors,
+/// unknown keys and invalid values with their line numbers
+pub fn validate_config_text(text: &str) -> Vec<ConfigIssue> {
+    if let Err(e) = text.parse::<toml::Table>() {
+        return vec![ConfigIssue {
+            line: e.span().map(|span| line_of(text, span.start)),
+            message: e.message().to_string(),
+        }];
+    }
+
+    let mut issues = Vec::new();
+    let mut lines: Vec<String> = text.lines().map(str::to_string).collect();
+
+    // Type errors: report one, blank its line and try again
+    let config = loop {
+        let current = lines.join("\n");
+        match toml::from_str::<Config>(&current) {
+            Ok(config) => break Some(config),
+            Err(e) => {
+                let line = e.span().map(|span| line_of(&current, span.start));
+                issues.push(ConfigIssue {
+                    line,
+                    message: e.message().to_string(),
+                });
+                match line {
+                    Some(line) if !lines[line - 1].trim().is_empty() => lines[line - 1].clear(),
+                    _ => break None,
+                }
+                if lines.join("\n").parse::<toml::Table>().is_err() {
+                    break None;
+                }
+            }
+        }
+    };
+
+    let Some(config) = config else {
+        return issues;
+    };
+
+    let mut section = String::new();
+    for (index, line) in lines.iter().enumerate() {
+        if let Some(header) = parse_table_header(line) {
+            section = header;
+            continue;
+        }
+        let Some(key) = parse_key_line(line) else {
+            continue;
+        };
+
+        let mut probe = lines.clone();
+        probe[index] = format!("{key} = {UNKNOWN_KEY_PROBE}");
+        let probe = probe.join("\n");
+        if probe.parse::<toml::Table>().is_ok() && toml::from_str::<Config>(&probe).is_ok() {
+            let path = if section.is_empty() {
+                key
+            } else {
+                format!("{section}.{key}")
+            };
+            issues.push(ConfigIssue {
+                line: Some(index + 1),
+                message: format!("unknown key `{path}`"),
+            });
+        }
+    }
+
+    for (key, error) in config.validate() {
+        issues.push(ConfigIssue {
+            line: find_key_line(&lines, &key),
+            message: format!("invalid value for `{key}`: {error}"),
+        });
+    }
+
+    issues.sort_by_key(|issue| issue.line);
+    issues
+}
+
+fn line_of(text: &str, offset: usize) -> usize {
+    text[..offset.min(text.len())].matches('\n').count() + 1
+}
+
+/// `[a.b]` or `[[a.b]]` → "a.b"
+fn parse_table_header(line: &str) -> Option<String> {
+    let line = line.trim();
+    let inner = line
+        .strip_prefix("[[")
+        .and_then(|rest| rest.split_once("]]"))
+        .or_else(|| line.strip_prefix('[').and_then(|rest| rest.split_once(']')))?
+        .0;
+    Some(
+        inner
+            .split('.')
+            .map(|part| part.trim().trim_matches('"'))
+            .collect::<Vec<_>>()
+            .join("."),
+    )
+}
+
+/// Key of a `key = value` line, with quotes around key parts removed
+fn parse_key_line(line: &str) -> Option<String> {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with('#') || trimmed.starts_with('[') {
+        return None;
+    }
+    let (key, _) = trimmed.split_once('=')?;
+    let key = key.trim();
+    if key.is_empty()
+        || !key
+            .chars()
+            .all(|c| c.is_alphanumeric() || "_-.\"': ".contains(c))
+    {
+        return None;
+    }
+    Some(
+        key.split('.')
+            .map(|part| part.trim().trim_matches('"').trim_matches('\''))
+            .collect::<Vec<_>>()
+            .join("."),
+    )
+}
+
+/// Line of a dotted config key such as "llm.retry.max_attempts"
+fn find_key_line(lines: &[String], key: &str) -> Option<usize> {
+    let mut section = String::new();
+    for (index, line) in lines.iter().enumerate() {
+        if let Some(header) = parse_table_header(line) {
+            section = header;
+        } else if let Some(line_key) = parse_key_line(line) {
+            let path = if section.is_empty() {
+                line_key
+            } else {
+                format!("{section}.{line_key}")
+            };
+            if path == key {
+                return Some(index + 1);
+            }
+        }
+    }
+    None
+}
+
+/// Get Google AI API key with priority: environment variable > config file
 pub fn get_google_ai_api_key() -> Result<Option<String>> {
     // Priority 1: Environment variable
     if let Ok(key) = std::env::var(env_apis::GOOGLE_AI_API_KEY) {
@@ -136,11 +1698,88 @@ pub fn get_google_ai_api_key() -> Result<Option<String>> {
     Ok(config.api.google_ai_api_key)
 }
 
+/// Get the default LLM provider with priority: environment variable > config file
+pub fn get_llm_provider() -> Result<Option<LlmProvider>> {
+    let value = match std::env::var(env_llm::RETROCHAT_LLM_PROVIDER) {
+        Ok(value) if !value.is_empty() => Some(value),
+        _ => Config::load()?.llm.provider,
+    };
+    value
+        .map(|value| value.parse().map_err(anyhow::Error::msg))
+        .transpose()
+}
+
+/// Get the default LLM model with priority: environment variable > config file
+pub fn get_llm_model() -> Result<Option<String>> {
+    if let Ok(model) = std::env::var(env_llm::RETROCHAT_LLM_MODEL) {
+        if !model.is_empty() {
+            return Ok(Some(model));
+        }
+    }
+
+    let config = Config::load()?;
+    Ok(config.llm.model.filter(|model| !model.is_empty()))
+}
+
+/// Get the GitHub token for gist uploads with priority: environment variable > config file
+pub fn get_github_token() -> Result<Option<String>> {
+    if let Ok(token) = std::env::var(env_apis::GITHUB_TOKEN) {
+        if !token.is_empty() {
+            return Ok(Some(token));
+        }
+    }
+
+    let config = Config::load()?;
+    Ok(config.api.github_token.filter(|token| !token.is_empty()))
+}
+
 /// Check if Google AI API key is configured (either in env or config file)
 pub fn has_google_ai_api_key() -> bool {
     get_google_ai_api_key().ok().flatten().is_some()
 }
 
+/// Split a comma-separated config value into trimmed, non-empty entries
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .collect()
+}
+
+/// Parse a positive number of days for a retention or TTL setting
+fn parse_days(value: &str) -> Result<u32> {
+    let days: u32 = value
+        .trim()
+        .parse()
+        .with_context(|| format!("Expected a number of days, got: {value}"))?;
+    if days == 0 {
+        anyhow::bail!("Period must be at least 1 day");
+    }
+    Ok(days)
+}
+
+/// Parse a positive token count for a quota; `_` separators are allowed
+fn parse_token_limit(value: &str) -> Result<u64> {
+    let tokens: u64 = value
+        .trim()
+        .replace('_', "")
+        .parse()
+        .with_context(|| format!("Expected a number of tokens, got: {value}"))?;
+    if tokens == 0 {
+        anyhow::bail!("Token limit must be at least 1");
+    }
+    Ok(tokens)
+}
+
+fn parse_log_level(value: &str) -> Result<String> {
+    let level: tracing::Level = value
+        .trim()
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Expected error, warn, info, debug or trace, got: {value}"))?;
+    Ok(level.as_str().to_lowercase())
+}
+
 /// Mask API key for display (show first 4 and last 4 characters)
 fn mask_api_key(key: &str) -> String {
     if key.len() <= 8 {
@@ -160,6 +1799,49 @@ mod tests {
         assert_eq!(mask_api_key("1234567890abcdef"), "1234...cdef");
     }
 
+    #[test]
+    fn test_validate_config_text() {
+        let text = r#"
+[api]
+google_ai_api_key = "key"
+
+[llm]
+provider = "chatgpt"
+modle = "gemini-2.5-flash"
+
+[retention]
+archive_after_days = "ninety"
+"#;
+        let issues = validate_config_text(text);
+        let lines: Vec<Option<usize>> = issues.iter().map(|issue| issue.line).collect();
+        assert_eq!(lines, vec![Some(6), Some(7), Some(10)]);
+        assert!(issues[0].message.contains("`llm.provider`"));
+        assert_eq!(issues[1].message, "unknown key `llm.modle`");
+        assert!(issues[2].message.contains("expected u32"));
+
+        let issues = validate_config_text("[llm\nprovider = 1");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, Some(1));
+
+        assert!(validate_config_text("[tui]\nmouse = false\n").is_empty());
+
+        // Everything `config set` writes validates cleanly
+        let mut config = Config::default();
+        for (key, value) in [
+            ("llm.provider", "claude"),
+            ("llm.retry.max_attempts", "5"),
+            ("llm.cache.ttl_days", "7"),
+            ("watch.paths", "~/chats=claude"),
+            ("logging.modules.retrochat_core::parsers", "debug"),
+            ("ignore.projects", "scratch-*"),
+            ("quota.weekly_analysis_usd", "5"),
+        ] {
+            config.set(key, value.to_string()).unwrap();
+        }
+        let text = toml::to_string_pretty(&config).unwrap();
+        assert_eq!(validate_config_text(&text), vec![]);
+    }
+
     #[test]
     fn test_config_set_get() {
         let mut config = Config::default();
@@ -175,4 +1857,425 @@ mod tests {
         config.unset("google-ai-api-key").unwrap();
         assert_eq!(config.get("google-ai-api-key"), None);
     }
+
+    #[test]
+    fn test_watch_config_set_get() {
+        let mut config = Config::default();
+
+        config
+            .set(
+                "watch.paths",
+                "~/logs/claude=claude, /data/sessions".to_string(),
+            )
+            .unwrap();
+        assert_eq!(
+            config.watch.paths[0],
+            WatchPathConfig {
+                path: "~/logs/claude".to_string(),
+                provider: Some("claude".to_string()),
+            }
+        );
+        assert_eq!(config.watch.paths[1].provider, None);
+        assert_eq!(
+            config.get("watch.paths"),
+            Some("~/logs/claude=claude,/data/sessions".to_string())
+        );
+
+        config
+            .set("watch.ignore", "**/*.tmp,**/backup/**".to_string())
+            .unwrap();
+        assert_eq!(config.watch.ignore.len(), 2);
+        assert!(config.set("watch.ignore", "[".to_string()).is_err());
+
+        let toml = toml::to_string_pretty(&config).unwrap();
+        let parsed: Config = toml::from_str(&toml).unwrap();
+        assert_eq!(parsed.watch, config.watch);
+
+        config.unset("watch.paths").unwrap();
+        assert_eq!(config.get("watch.paths"), None);
+    }
+
+    #[test]
+    fn test_analysis_language_set_get() {
+        let mut config = Config::default();
+
+        config
+            .set("analysis.language", "ko-KR".to_string())
+            .unwrap();
+        assert_eq!(config.get("analysis.language"), Some("ko".to_string()));
+        assert_eq!(Language::from_config(&config), Language::Korean);
+        assert!(config.set("analysis.language", "xx".to_string()).is_err());
+
+        config.unset("analysis.language").unwrap();
+        assert_eq!(Language::from_config(&config), Language::English);
+
+        assert_eq!(config.analysis.idle_gap(), chrono::Duration::minutes(30));
+        config
+            .set("analysis.idle_gap_minutes", "45".to_string())
+            .unwrap();
+        assert_eq!(config.analysis.idle_gap(), chrono::Duration::minutes(45));
+        assert!(config
+            .set("analysis.idle_gap_minutes", "0".to_string())
+            .is_err());
+
+        assert!(!config.analysis.git_correlation());
+        config
+            .set("analysis.git_correlation", "true".to_string())
+            .unwrap();
+        assert!(config.analysis.git_correlation());
+        assert!(config
+            .set("analysis.git_correlation", "yes".to_string())
+            .is_err());
+
+        config
+            .set(
+                "analysis.rubric_weights.Quality Verification",
+                "2".to_string(),
+            )
+            .unwrap();
+        assert_eq!(
+            config.get("analysis.rubric_weights.Quality Verification"),
+            Some("2".to_string())
+        );
+        assert!(config
+            .set("analysis.rubric_weights.rubric_001", "-1".to_string())
+            .is_err());
+        assert!(config.list().contains(&(
+            "analysis.rubric_weights.Quality Verification".to_string(),
+            "2".to_string()
+        )));
+        config
+            .unset("analysis.rubric_weights.Quality Verification")
+            .unwrap();
+        assert!(config.analysis.rubric_weights.is_empty());
+    }
+
+    #[test]
+    fn test_import_limits() {
+        let mut config = Config::default();
+        assert_eq!(config.import.max_message_bytes(), Some(1024 * 1024));
+
+        config
+            .set("import.max_tool_result_kb", "64".to_string())
+            .unwrap();
+        config
+            .set("import.max_message_kb", "0".to_string())
+            .unwrap();
+        assert_eq!(config.import.max_tool_result_bytes(), Some(64 * 1024));
+        assert_eq!(config.import.max_message_bytes(), None);
+        assert!(config
+            .set("import.max_message_kb", "1MB".to_string())
+            .is_err());
+
+        config.unset("import.max_message_kb").unwrap();
+        assert_eq!(config.get("import.max_message_kb"), None);
+
+        assert!(!config.import.keep_raw_payloads());
+        config
+            .set("import.raw_payloads", "true".to_string())
+            .unwrap();
+        assert!(config.import.keep_raw_payloads());
+        assert!(config
+            .set("import.raw_payloads", "all".to_string())
+            .is_err());
+    }
+
+    #[test]
+    fn test_attachment_settings() {
+        let mut config = Config::default();
+        assert_eq!(config.attachments.blob_dir(), None);
+
+        config
+            .set("attachments.store_blobs", "true".to_string())
+            .unwrap();
+        assert_eq!(config.get("attachments.store_blobs"), Some("true".into()));
+        assert!(config
+            .attachments
+            .blob_dir()
+            .is_some_and(|dir| dir.ends_with(".retrochat/attachments")));
+        assert!(config
+            .set("attachments.store_blobs", "maybe".to_string())
+            .is_err());
+
+        config.unset("attachments.store_blobs").unwrap();
+        assert!(config.attachments.is_empty());
+    }
+
+    #[test]
+    fn test_tui_settings() {
+        let mut config = Config::default();
+        assert!(config.tui.mouse_enabled());
+
+        config.set("tui.mouse", "false".to_string()).unwrap();
+        assert!(!config.tui.mouse_enabled());
+        assert!(config
+            .list()
+            .contains(&("tui.mouse".into(), "false".into())));
+        assert!(config.set("tui.mouse", "off".to_string()).is_err());
+
+        config.unset("tui.mouse").unwrap();
+        assert!(config.tui.is_empty());
+    }
+
+    #[test]
+    fn test_ignore_config_set_get() {
+        let mut config = Config::default();
+
+        config
+            .set("ignore.paths", "**/-tmp-*/**, **/vendor/**".to_string())
+            .unwrap();
+        config.set("ignore.min_messages", "3".to_string()).unwrap();
+        config
+            .set("ignore.projects", "scratch-*".to_string())
+            .unwrap();
+        assert_eq!(config.ignore.paths, vec!["**/-tmp-*/**", "**/vendor/**"]);
+        assert_eq!(config.get("ignore.min_messages"), Some("3".to_string()));
+        assert_eq!(config.get("ignore.projects"), Some("scratch-*".to_string()));
+        assert!(config.set("ignore.paths", "[".to_string()).is_err());
+        assert!(config
+            .set("ignore.min_messages", "few".to_string())
+            .is_err());
+
+        for key in ["ignore.paths", "ignore.min_messages", "ignore.projects"] {
+            config.unset(key).unwrap();
+        }
+        assert!(config.ignore.is_empty());
+    }
+
+    #[test]
+    fn test_external_parsers_from_toml() {
+        let config: Config = toml::from_str(
+            r#"
+            [[parsers.external]]
+            name = "aider"
+            pattern = "**/.aider.chat.history.md"
+            command = "aider-to-retrochat --input {file}"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.parsers.external.len(), 1);
+        assert_eq!(config.parsers.external[0].name, "aider");
+        assert_eq!(
+            config.get("parsers.external.aider"),
+            Some("**/.aider.chat.history.md => aider-to-retrochat --input {file}".to_string())
+        );
+        assert_eq!(config.get("parsers.external.missing"), None);
+        assert!(config
+            .list()
+            .iter()
+            .any(|(key, _)| key == "parsers.external.aider"));
+    }
+
+    #[test]
+    fn test_mcp_tool_rules_from_toml() {
+        let config: Config = toml::from_str(
+            r#"
+            [[parsers.mcp]]
+            tool = "mcp__github__create_issue"
+            subject = "$.title"
+            success = "$.number"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.parsers.mcp.len(), 1);
+        assert_eq!(config.parsers.mcp[0].summary, None);
+        assert_eq!(
+            config.get("parsers.mcp.mcp__github__create_issue"),
+            Some("subject=$.title success=$.number".to_string())
+        );
+        assert!(config.validate().is_empty());
+        assert!(config
+            .list()
+            .iter()
+            .any(|(key, _)| key == "parsers.mcp.mcp__github__create_issue"));
+    }
+
+    #[test]
+    fn test_retention_config_set_get() {
+        let mut config = Config::default();
+
+        config
+            .set("retention.archive_after_days", "180".to_string())
+            .unwrap();
+        assert_eq!(config.retention.archive_after_days, Some(180));
+        assert_eq!(
+            config.get("retention.archive_after_days"),
+            Some("180".to_string())
+        );
+        assert!(config
+            .set("retention.purge_tool_results_after_days", "0".to_string())
+            .is_err());
+        assert!(config
+            .set(
+                "retention.purge_tool_results_after_days",
+                "a year".to_string()
+            )
+            .is_err());
+
+        let toml = toml::to_string_pretty(&config).unwrap();
+        assert!(toml.contains("[retention]"));
+
+        config.unset("retention.archive_after_days").unwrap();
+        assert!(config.retention.is_empty());
+    }
+
+    #[test]
+    fn test_llm_retry_config_set_get() {
+        let mut config = Config::default();
+
+        config
+            .set("llm.retry.max_attempts", "5".to_string())
+            .unwrap();
+        config
+            .set("llm.retry.claude.jitter", "false".to_string())
+            .unwrap();
+        assert_eq!(config.get("llm.retry.max_attempts"), Some("5".to_string()));
+        assert_eq!(
+            config.get("llm.retry.claude-code.jitter"),
+            Some("false".to_string())
+        );
+        assert!(config
+            .set("llm.retry.max_attempts", "0".to_string())
+            .is_err());
+        assert!(config.set("llm.retry.backoff", "1".to_string()).is_err());
+        assert!(config
+            .set("llm.retry.openai.max_attempts", "2".to_string())
+            .is_err());
+
+        let toml = toml::to_string_pretty(&config).unwrap();
+        let parsed: Config = toml::from_str(&toml).unwrap();
+        assert_eq!(parsed.llm, config.llm);
+        assert!(config.list().contains(&(
+            "llm.retry.claude-code.jitter".to_string(),
+            "false".to_string()
+        )));
+
+        config.unset("llm.retry.claude-code.jitter").unwrap();
+        config.unset("llm.retry.max_attempts").unwrap();
+        assert!(config.llm.is_empty());
+
+        config.set("llm.provider", "claude".to_string()).unwrap();
+        assert_eq!(config.get("llm.provider"), Some("claude-code".to_string()));
+        assert!(config.set("llm.provider", "openai".to_string()).is_err());
+        config.set("llm.model", " sonnet ".to_string()).unwrap();
+        assert_eq!(config.get("llm.model"), Some("sonnet".to_string()));
+        config.unset("llm.provider").unwrap();
+        config.unset("llm.model").unwrap();
+        assert!(config.llm.is_empty());
+    }
+
+    #[test]
+    fn test_embedding_config_set_get() {
+        let mut config = Config::default();
+
+        config
+            .set("embedding.provider", "OpenAI".to_string())
+            .unwrap();
+        config
+            .set("embedding.dimensions", "512".to_string())
+            .unwrap();
+        config
+            .set("openai-api-key", "sk-test-1234567890".to_string())
+            .unwrap();
+        assert_eq!(config.get("embedding.provider"), Some("openai".to_string()));
+        assert_eq!(config.embedding.dimensions, Some(512));
+        assert!(config
+            .set("embedding.provider", "fastembed".to_string())
+            .is_err());
+        assert!(config.set("embedding.dimensions", "0".to_string()).is_err());
+
+        let toml = toml::to_string_pretty(&config).unwrap();
+        assert!(toml.contains("[embedding]"));
+        assert!(config
+            .list()
+            .contains(&("openai-api-key".to_string(), "sk-t...7890".to_string())));
+
+        config.unset("embedding.provider").unwrap();
+        config.unset("embedding.dimensions").unwrap();
+        assert!(config.embedding.is_empty());
+    }
+
+    #[test]
+    fn test_quota_config_set_get() {
+        let mut config = Config::default();
+
+        config
+            .set("quota.daily_tokens", "2_000_000".to_string())
+            .unwrap();
+        config
+            .set("quota.weekly_analysis_usd", "$5.50".to_string())
+            .unwrap();
+        config.set("quota.notify", "false".to_string()).unwrap();
+        assert_eq!(config.quota.daily_tokens, Some(2_000_000));
+        assert_eq!(
+            config.get("quota.weekly_analysis_usd"),
+            Some("5.5".to_string())
+        );
+        assert!(config.quota.has_thresholds());
+        assert!(config.set("quota.daily_tokens", "0".to_string()).is_err());
+        assert!(config
+            .set("quota.weekly_analysis_usd", "-1".to_string())
+            .is_err());
+
+        let toml = toml::to_string_pretty(&config).unwrap();
+        assert!(toml.contains("[quota]"));
+        assert!(config
+            .list()
+            .contains(&("quota.notify".to_string(), "false".to_string())));
+
+        for key in [
+            "quota.daily_tokens",
+            "quota.weekly_analysis_usd",
+            "quota.notify",
+        ] {
+            config.unset(key).unwrap();
+        }
+        assert!(config.quota.is_empty());
+    }
+
+    #[test]
+    fn test_sync_remotes_from_toml() {
+        let mut config: Config = toml::from_str(
+            r#"
+            [sync]
+            passphrase = "correct horse battery"
+
+            [[sync.remotes]]
+            name = "nas"
+            url = "https://nas.local/dav/retrochat"
+            username = "me"
+
+            [[sync.remotes]]
+            name = "r2"
+            url = "s3://history/retrochat"
+            endpoint = "https://account.r2.cloudflarestorage.com"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.get("sync.remotes.nas"),
+            Some("https://nas.local/dav/retrochat".to_string())
+        );
+        assert_eq!(config.sync.resolve_remote(Some("r2")).unwrap().name, "r2");
+        assert!(config.sync.resolve_remote(None).is_err());
+        assert!(config.sync.resolve_remote(Some("missing")).is_err());
+        assert!(config
+            .list()
+            .contains(&("sync.passphrase".to_string(), "corr...tery".to_string())));
+
+        config.unset("sync.remotes.r2").unwrap();
+        assert_eq!(config.sync.resolve_remote(None).unwrap().name, "nas");
+        assert!(config.set("sync.remotes.r2", "s3://x".to_string()).is_err());
+
+        config.sync.add_remote(RemoteSettings {
+            name: "nas".to_string(),
+            url: "/mnt/backup/retrochat".to_string(),
+            ..Default::default()
+        });
+        assert_eq!(config.sync.remotes.len(), 1);
+        assert_eq!(config.sync.remotes[0].username, None);
+    }
 }
@@ -1,10 +1,49 @@
 use anyhow::{Context, Result as AnyhowResult};
 use chrono::{DateTime, Utc};
-use sqlx::{Pool, Sqlite};
+use serde::Serialize;
+use sqlx::{Pool, Row, Sqlite};
 
 use super::connection::DatabaseManager;
 use crate::models::Analytics;
 
+/// Sessions, messages and tokens of the sessions started on one UTC day
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DailyUsagePoint {
+    /// `YYYY-MM-DD`
+    pub date: String,
+    pub sessions: i64,
+    pub messages: i64,
+    pub tokens: i64,
+}
+
+/// Calls of one tool, and how many of them failed
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ToolUsagePoint {
+    pub tool_name: String,
+    pub calls: i64,
+    pub failures: i64,
+}
+
+/// Tokens of one provider's sessions started on one UTC day
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TokenTrendPoint {
+    /// `YYYY-MM-DD`
+    pub date: String,
+    pub provider: String,
+    pub tokens: i64,
+}
+
+/// Average score of one rubric across the analyses generated on one UTC day
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RubricTrendPoint {
+    /// `YYYY-MM-DD`
+    pub date: String,
+    pub rubric_name: String,
+    /// Average percentage of the maximum score (0-100)
+    pub average_percentage: f64,
+    pub analyses: i64,
+}
+
 pub struct AnalyticsRepository {
     pool: Pool<Sqlite>,
 }
@@ -181,4 +220,299 @@ impl AnalyticsRepository {
             Ok(None)
         }
     }
+
+    /// Usage per day of the sessions started in the window, oldest first
+    pub async fn daily_usage(
+        &self,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+    ) -> AnyhowResult<Vec<DailyUsagePoint>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT substr(start_time, 1, 10) AS date,
+                   COUNT(*) AS sessions,
+                   COALESCE(SUM(message_count), 0) AS messages,
+                   COALESCE(SUM(token_count), 0) AS tokens
+            FROM chat_sessions
+            WHERE (?1 IS NULL OR start_time >= ?1)
+              AND (?2 IS NULL OR start_time <= ?2)
+            GROUP BY date
+            ORDER BY date
+            "#,
+        )
+        .bind(since.map(|t| t.to_rfc3339()))
+        .bind(until.map(|t| t.to_rfc3339()))
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch daily usage")?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(DailyUsagePoint {
+                    date: row.try_get("date")?,
+                    sessions: row.try_get("sessions")?,
+                    messages: row.try_get("messages")?,
+                    tokens: row.try_get("tokens")?,
+                })
+            })
+            .collect()
+    }
+
+    /// Calls per tool in the window, most used first
+    pub async fn tool_distribution(
+        &self,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+    ) -> AnyhowResult<Vec<ToolUsagePoint>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT tool_name,
+                   COUNT(*) AS calls,
+                   SUM(CASE WHEN success = 0 THEN 1 ELSE 0 END) AS failures
+            FROM tool_operations
+            WHERE (?1 IS NULL OR timestamp >= ?1)
+              AND (?2 IS NULL OR timestamp <= ?2)
+            GROUP BY tool_name
+            ORDER BY calls DESC, tool_name
+            "#,
+        )
+        .bind(since.map(|t| t.to_rfc3339()))
+        .bind(until.map(|t| t.to_rfc3339()))
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch tool distribution")?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(ToolUsagePoint {
+                    tool_name: row.try_get("tool_name")?,
+                    calls: row.try_get("calls")?,
+                    failures: row.try_get("failures")?,
+                })
+            })
+            .collect()
+    }
+
+    /// Tokens per day and provider of the sessions started in the window,
+    /// oldest first
+    pub async fn token_trend(
+        &self,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+    ) -> AnyhowResult<Vec<TokenTrendPoint>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT substr(start_time, 1, 10) AS date,
+                   provider,
+                   COALESCE(SUM(token_count), 0) AS tokens
+            FROM chat_sessions
+            WHERE (?1 IS NULL OR start_time >= ?1)
+              AND (?2 IS NULL OR start_time <= ?2)
+            GROUP BY date, provider
+            ORDER BY date, provider
+            "#,
+        )
+        .bind(since.map(|t| t.to_rfc3339()))
+        .bind(until.map(|t| t.to_rfc3339()))
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch token trend")?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(TokenTrendPoint {
+                    date: row.try_get("date")?,
+                    provider: row.try_get("provider")?,
+                    tokens: row.try_get("tokens")?,
+                })
+            })
+            .collect()
+    }
+
+    /// Average rubric scores per day of the analyses generated in the window,
+    /// oldest first
+    pub async fn rubric_trend(
+        &self,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+    ) -> AnyhowResult<Vec<RubricTrendPoint>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT substr(a.generated_at, 1, 10) AS date,
+                   json_extract(r.value, '$.rubric_name') AS rubric_name,
+                   AVG(json_extract(r.value, '$.score') * 100.0
+                       / json_extract(r.value, '$.max_score')) AS average_percentage,
+                   COUNT(DISTINCT a.id) AS analyses
+            FROM analytics a, json_each(a.ai_quantitative_output_json, '$.rubric_scores') r
+            WHERE json_extract(r.value, '$.max_score') > 0
+              AND (?1 IS NULL OR a.generated_at >= ?1)
+              AND (?2 IS NULL OR a.generated_at <= ?2)
+            GROUP BY date, rubric_name
+            ORDER BY date, rubric_name
+            "#,
+        )
+        .bind(since.map(|t| t.to_rfc3339()))
+        .bind(until.map(|t| t.to_rfc3339()))
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch rubric trend")?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(RubricTrendPoint {
+                    date: row.try_get("date")?,
+                    rubric_name: row.try_get("rubric_name")?,
+                    average_percentage: row.try_get("average_percentage")?,
+                    analyses: row.try_get("analyses")?,
+                })
+            })
+            .collect()
+    }
+
+    /// Rubric scores of every analysis, by analysis ID
+    pub async fn list_ai_quantitative_outputs(
+        &self,
+    ) -> AnyhowResult<Vec<(String, crate::services::analytics::AIQuantitativeOutput)>> {
+        let rows = sqlx::query("SELECT id, ai_quantitative_output_json FROM analytics ORDER BY id")
+            .fetch_all(&self.pool)
+            .await
+            .context("Failed to fetch rubric scores")?;
+
+        rows.iter()
+            .map(|row| {
+                let json: String = row.try_get("ai_quantitative_output_json")?;
+                let output = serde_json::from_str(&json)
+                    .context("Failed to deserialize ai_quantitative_output")?;
+                Ok((row.try_get("id")?, output))
+            })
+            .collect()
+    }
+
+    pub async fn update_ai_quantitative_output(
+        &self,
+        id: &str,
+        output: &crate::services::analytics::AIQuantitativeOutput,
+    ) -> AnyhowResult<()> {
+        let json =
+            serde_json::to_string(output).context("Failed to serialize ai_quantitative_output")?;
+        sqlx::query("UPDATE analytics SET ai_quantitative_output_json = ? WHERE id = ?")
+            .bind(json)
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .context("Failed to update rubric scores")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::ChatSessionRepository;
+    use crate::models::{ChatSession, Provider};
+    use chrono::TimeZone;
+
+    #[tokio::test]
+    async fn test_chart_series() {
+        let db = DatabaseManager::open_in_memory().await.unwrap();
+        let session_repo = ChatSessionRepository::new(&db);
+        let mut ids = Vec::new();
+        for (i, (provider, day, tokens)) in [
+            (Provider::ClaudeCode, 1, 100),
+            (Provider::ClaudeCode, 1, 50),
+            (Provider::Codex, 2, 10),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let mut session = ChatSession::new(
+                provider,
+                format!("/chart{i}.jsonl"),
+                format!("hash{i}"),
+                Utc.with_ymd_and_hms(2024, 3, day, 12, 0, 0).unwrap(),
+            );
+            session.message_count = 2;
+            session.token_count = Some(tokens);
+            session_repo.create(&session).await.unwrap();
+            ids.push(session.id.to_string());
+        }
+        let repo = AnalyticsRepository::new(&db);
+
+        let daily = repo.daily_usage(None, None).await.unwrap();
+        assert_eq!(
+            daily,
+            vec![
+                DailyUsagePoint {
+                    date: "2024-03-01".to_string(),
+                    sessions: 2,
+                    messages: 4,
+                    tokens: 150,
+                },
+                DailyUsagePoint {
+                    date: "2024-03-02".to_string(),
+                    sessions: 1,
+                    messages: 2,
+                    tokens: 10,
+                },
+            ]
+        );
+        let since = Utc.with_ymd_and_hms(2024, 3, 2, 0, 0, 0).unwrap();
+        assert_eq!(repo.daily_usage(Some(since), None).await.unwrap().len(), 1);
+
+        let trend = repo.token_trend(None, None).await.unwrap();
+        assert_eq!(trend.len(), 2);
+        assert_eq!(trend[1].provider, Provider::Codex.to_string());
+        assert_eq!(trend[1].tokens, 10);
+
+        assert!(repo.tool_distribution(None, None).await.unwrap().is_empty());
+
+        for (i, (day, score)) in [(1, 4.0), (1, 2.0), (2, 5.0)].into_iter().enumerate() {
+            let request_id = format!("request{i}");
+            sqlx::query(
+                "INSERT INTO analytics_requests (id, session_id, status) VALUES (?, ?, 'completed')",
+            )
+            .bind(&request_id)
+            .bind(&ids[0])
+            .execute(&repo.pool)
+            .await
+            .unwrap();
+            let quantitative = serde_json::json!({
+                "rubric_scores": [{
+                    "rubric_id": "clarity",
+                    "rubric_name": "Clarity",
+                    "score": score,
+                    "max_score": 5.0,
+                    "reasoning": "",
+                }],
+            });
+            sqlx::query(
+                r#"
+                INSERT INTO analytics (
+                    id, analytics_request_id, session_id, generated_at,
+                    qualitative_output_json, ai_quantitative_output_json,
+                    metric_quantitative_output_json
+                ) VALUES (?, ?, ?, ?, '{}', ?, '{}')
+                "#,
+            )
+            .bind(format!("analytics{i}"))
+            .bind(&request_id)
+            .bind(&ids[0])
+            .bind(
+                Utc.with_ymd_and_hms(2024, 3, day, 9, 0, 0)
+                    .unwrap()
+                    .to_rfc3339(),
+            )
+            .bind(quantitative.to_string())
+            .execute(&repo.pool)
+            .await
+            .unwrap();
+        }
+
+        let rubrics = repo.rubric_trend(None, None).await.unwrap();
+        assert_eq!(rubrics.len(), 2);
+        assert_eq!(rubrics[0].rubric_name, "Clarity");
+        assert_eq!(rubrics[0].analyses, 2);
+        assert!((rubrics[0].average_percentage - 60.0).abs() < 1e-9);
+        assert!((rubrics[1].average_percentage - 100.0).abs() < 1e-9);
+    }
 }
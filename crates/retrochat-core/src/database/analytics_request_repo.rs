@@ -1,8 +1,10 @@
 use chrono::{DateTime, Utc};
+use sqlx::Row;
 use std::sync::Arc;
 
 use crate::database::DatabaseManager;
 use crate::models::{AnalyticsRequest, OperationStatus};
+use crate::services::llm::{LlmUsage, ParseMetrics, RetryMetrics};
 
 #[derive(Clone)]
 pub struct AnalyticsRequestRepository {
@@ -73,6 +75,117 @@ impl AnalyticsRequestRepository {
         Ok(())
     }
 
+    /// Store the retry metrics of the LLM calls made for a request
+    pub async fn record_retry_metrics(
+        &self,
+        id: &str,
+        metrics: &RetryMetrics,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        sqlx::query(
+            r#"
+            UPDATE analytics_requests
+            SET llm_attempts = ?, llm_retries = ?, llm_retry_wait_ms = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(metrics.attempts as i64)
+        .bind(metrics.retries as i64)
+        .bind(metrics.wait_ms as i64)
+        .bind(id)
+        .execute(self.db_manager.pool())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Retry metrics of a request, or `None` if none were recorded
+    pub async fn get_retry_metrics(
+        &self,
+        id: &str,
+    ) -> Result<Option<RetryMetrics>, Box<dyn std::error::Error + Send + Sync>> {
+        let row = sqlx::query(
+            "SELECT llm_attempts, llm_retries, llm_retry_wait_ms FROM analytics_requests WHERE id = ?",
+        )
+        .bind(id)
+        .fetch_optional(self.db_manager.pool())
+        .await?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let attempts: i64 = row.try_get("llm_attempts")?;
+        if attempts == 0 {
+            return Ok(None);
+        }
+        Ok(Some(RetryMetrics {
+            attempts: attempts as u32,
+            retries: row.try_get::<i64, _>("llm_retries")? as u32,
+            wait_ms: row.try_get::<i64, _>("llm_retry_wait_ms")? as u64,
+        }))
+    }
+
+    /// Store the structured-output parse outcomes of a request
+    pub async fn record_parse_metrics(
+        &self,
+        id: &str,
+        metrics: &ParseMetrics,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        sqlx::query(
+            r#"
+            UPDATE analytics_requests
+            SET llm_parsed = ?, llm_parse_repaired = ?, llm_parse_failures = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(metrics.parsed as i64)
+        .bind(metrics.repaired as i64)
+        .bind(metrics.failed as i64)
+        .bind(id)
+        .execute(self.db_manager.pool())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Store the tokens and cost of the LLM calls made for a request
+    pub async fn record_usage(
+        &self,
+        id: &str,
+        usage: &LlmUsage,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        sqlx::query("UPDATE analytics_requests SET llm_tokens = ?, llm_cost_usd = ? WHERE id = ?")
+            .bind(usage.tokens as i64)
+            .bind(usage.cost_usd)
+            .bind(id)
+            .execute(self.db_manager.pool())
+            .await?;
+
+        Ok(())
+    }
+
+    /// Parse outcomes of a request, or `None` if nothing was parsed
+    pub async fn get_parse_metrics(
+        &self,
+        id: &str,
+    ) -> Result<Option<ParseMetrics>, Box<dyn std::error::Error + Send + Sync>> {
+        let row = sqlx::query(
+            "SELECT llm_parsed, llm_parse_repaired, llm_parse_failures FROM analytics_requests WHERE id = ?",
+        )
+        .bind(id)
+        .fetch_optional(self.db_manager.pool())
+        .await?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let metrics = ParseMetrics {
+            parsed: row.try_get::<i64, _>("llm_parsed")? as u32,
+            repaired: row.try_get::<i64, _>("llm_parse_repaired")? as u32,
+            failed: row.try_get::<i64, _>("llm_parse_failures")? as u32,
+        };
+        Ok((metrics.total() > 0).then_some(metrics))
+    }
+
     pub async fn find_by_id(
         &self,
         id: &str,
@@ -557,4 +670,76 @@ mod tests {
         let found = repo.find_by_session_id(&session_id).await.unwrap();
         assert_eq!(found.len(), 2);
     }
+
+    #[tokio::test]
+    async fn test_record_retry_metrics() {
+        let database = Database::new_in_memory().await.unwrap();
+        database.initialize().await.unwrap();
+
+        let session_repo = ChatSessionRepository::new(&database.manager);
+        let session = ChatSession::new(
+            Provider::ClaudeCode,
+            "/test/path".to_string(),
+            "test-hash".to_string(),
+            Utc::now(),
+        );
+        session_repo.create(&session).await.unwrap();
+
+        let repo = AnalyticsRequestRepository::new(Arc::new(database.manager));
+        let request = AnalyticsRequest::new(session.id.to_string(), None, None);
+        repo.create(&request).await.unwrap();
+
+        assert_eq!(repo.get_retry_metrics(&request.id).await.unwrap(), None);
+
+        let metrics = RetryMetrics {
+            attempts: 7,
+            retries: 2,
+            wait_ms: 1500,
+        };
+        repo.record_retry_metrics(&request.id, &metrics)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            repo.get_retry_metrics(&request.id).await.unwrap(),
+            Some(metrics)
+        );
+        // Rows read through the typed queries are unaffected by the new columns
+        assert!(repo.find_by_id(&request.id).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_record_parse_metrics() {
+        let database = Database::new_in_memory().await.unwrap();
+        database.initialize().await.unwrap();
+
+        let session_repo = ChatSessionRepository::new(&database.manager);
+        let session = ChatSession::new(
+            Provider::ClaudeCode,
+            "/test/path".to_string(),
+            "test-hash".to_string(),
+            Utc::now(),
+        );
+        session_repo.create(&session).await.unwrap();
+
+        let repo = AnalyticsRequestRepository::new(Arc::new(database.manager));
+        let request = AnalyticsRequest::new(session.id.to_string(), None, None);
+        repo.create(&request).await.unwrap();
+
+        assert_eq!(repo.get_parse_metrics(&request.id).await.unwrap(), None);
+
+        let metrics = ParseMetrics {
+            parsed: 9,
+            repaired: 2,
+            failed: 1,
+        };
+        repo.record_parse_metrics(&request.id, &metrics)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            repo.get_parse_metrics(&request.id).await.unwrap(),
+            Some(metrics)
+        );
+    }
 }
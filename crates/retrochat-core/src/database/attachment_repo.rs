@@ -0,0 +1,93 @@
+use anyhow::{Context, Result as AnyhowResult};
+use sqlx::{Pool, Row, Sqlite};
+use uuid::Uuid;
+
+use super::connection::DatabaseManager;
+use crate::models::Attachment;
+
+pub struct AttachmentRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl AttachmentRepository {
+    pub fn new(db: &DatabaseManager) -> Self {
+        Self {
+            pool: db.pool().clone(),
+        }
+    }
+
+    pub async fn bulk_create(&self, attachments: &[Attachment]) -> AnyhowResult<()> {
+        if attachments.is_empty() {
+            return Ok(());
+        }
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        for attachment in attachments {
+            sqlx::query(
+                r#"
+                INSERT OR REPLACE INTO attachments (
+                    id, message_id, session_id, kind, media_type, size_bytes, sha256, file_path
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(attachment.id.to_string())
+            .bind(attachment.message_id.to_string())
+            .bind(attachment.session_id.to_string())
+            .bind(&attachment.kind)
+            .bind(&attachment.media_type)
+            .bind(attachment.size_bytes as i64)
+            .bind(&attachment.sha256)
+            .bind(&attachment.file_path)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert attachment")?;
+        }
+
+        tx.commit().await.context("Failed to commit attachments")?;
+        Ok(())
+    }
+
+    /// A session's attachments in message order
+    pub async fn get_by_session(&self, session_id: &Uuid) -> AnyhowResult<Vec<Attachment>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT a.id, a.message_id, a.session_id, a.kind, a.media_type,
+                   a.size_bytes, a.sha256, a.file_path
+            FROM attachments a
+            JOIN messages m ON m.id = a.message_id
+            WHERE a.session_id = ?
+            ORDER BY m.sequence_number, a.rowid
+            "#,
+        )
+        .bind(session_id.to_string())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch attachments")?;
+
+        rows.iter()
+            .map(|row| {
+                let id: String = row.try_get("id")?;
+                let message_id: String = row.try_get("message_id")?;
+                let session_id: String = row.try_get("session_id")?;
+                Ok(Attachment {
+                    id: Uuid::parse_str(&id).context("Invalid attachment ID")?,
+                    message_id: Uuid::parse_str(&message_id)
+                        .context("Invalid message ID in attachments")?,
+                    session_id: Uuid::parse_str(&session_id)
+                        .context("Invalid session ID in attachments")?,
+                    kind: row.try_get("kind")?,
+                    media_type: row.try_get("media_type")?,
+                    size_bytes: row.try_get::<i64, _>("size_bytes")? as u64,
+                    sha256: row.try_get("sha256")?,
+                    file_path: row.try_get("file_path")?,
+                    data: None,
+                })
+            })
+            .collect()
+    }
+}
@@ -1,10 +1,11 @@
 use anyhow::{Context, Result as AnyhowResult};
 use chrono::{DateTime, NaiveDateTime, Utc};
 use sqlx::{sqlite::SqliteRow, Pool, Row, Sqlite};
+use std::collections::HashSet;
 use uuid::Uuid;
 
 use super::connection::DatabaseManager;
-use crate::models::{ChatSession, Provider, SessionState};
+use crate::models::{ChatSession, Provider, SessionState, TitleSource};
 
 fn parse_datetime(datetime_str: &str) -> Result<DateTime<Utc>, chrono::ParseError> {
     // Try RFC3339 format first
@@ -41,24 +42,29 @@ impl ChatSessionRepository {
         sqlx::query(
             r#"
             INSERT INTO chat_sessions (
-                id, provider, project_name, start_time, end_time,
-                message_count, token_count, file_path, file_hash,
-                created_at, updated_at, state
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                id, provider, project_name, title, title_source, start_time, end_time,
+                message_count, token_count, tool_op_count, duration_seconds, file_path, file_hash,
+                created_at, updated_at, state, owner
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(session.id.to_string())
         .bind(session.provider.to_string())
         .bind(session.project_name.as_ref())
+        .bind(session.title.as_ref())
+        .bind(session.title_source.map(|s| s.to_string()))
         .bind(session.start_time.to_rfc3339())
         .bind(session.end_time.map(|t| t.to_rfc3339()))
         .bind(session.message_count)
         .bind(session.token_count)
+        .bind(session.tool_op_count)
+        .bind(session.duration_seconds)
         .bind(&session.file_path)
         .bind(&session.file_hash)
         .bind(session.created_at.to_rfc3339())
         .bind(session.updated_at.to_rfc3339())
         .bind(session.state.to_string())
+        .bind(session.owner.as_ref())
         .execute(&self.pool)
         .await
         .context("Failed to create chat session")?;
@@ -69,9 +75,9 @@ impl ChatSessionRepository {
     pub async fn get_by_id(&self, id: &Uuid) -> AnyhowResult<Option<ChatSession>> {
         let row = sqlx::query(
             r#"
-            SELECT id, provider, project_name, start_time, end_time,
-                   message_count, token_count, file_path, file_hash,
-                   created_at, updated_at, state
+            SELECT id, provider, project_name, title, title_source, start_time, end_time,
+                   message_count, token_count, tool_op_count, duration_seconds, file_path, file_hash,
+                   created_at, updated_at, state, owner
             FROM chat_sessions WHERE id = ?
             "#,
         )
@@ -92,9 +98,9 @@ impl ChatSessionRepository {
     pub async fn get_all(&self) -> AnyhowResult<Vec<ChatSession>> {
         let rows = sqlx::query(
             r#"
-            SELECT id, provider, project_name, start_time, end_time,
-                   message_count, token_count, file_path, file_hash,
-                   created_at, updated_at, state
+            SELECT id, provider, project_name, title, title_source, start_time, end_time,
+                   message_count, token_count, tool_op_count, duration_seconds, file_path, file_hash,
+                   created_at, updated_at, state, owner
             FROM chat_sessions ORDER BY updated_at DESC
             "#,
         )
@@ -111,11 +117,47 @@ impl ChatSessionRepository {
         Ok(sessions)
     }
 
+    /// Up to `limit` sessions ordered by `(start_time, id)`, starting strictly
+    /// after the given position (keyset pagination)
+    pub async fn get_page_by_start_time(
+        &self,
+        after: Option<(DateTime<Utc>, Uuid)>,
+        descending: bool,
+        limit: i64,
+    ) -> AnyhowResult<Vec<ChatSession>> {
+        let (comparison, order) = if descending {
+            ("<", "DESC")
+        } else {
+            (">", "ASC")
+        };
+        let sql = format!(
+            r#"
+            SELECT id, provider, project_name, title, title_source, start_time, end_time,
+                   message_count, token_count, tool_op_count, duration_seconds, file_path, file_hash,
+                   created_at, updated_at, state, owner
+            FROM chat_sessions
+            WHERE ?1 IS NULL OR (start_time, id) {comparison} (?1, ?2)
+            ORDER BY start_time {order}, id {order}
+            LIMIT ?3
+            "#
+        );
+
+        let rows = sqlx::query(&sql)
+            .bind(after.map(|(start_time, _)| start_time.to_rfc3339()))
+            .bind(after.map(|(_, id)| id.to_string()))
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await
+            .context("Failed to fetch page of chat sessions")?;
+
+        rows.iter().map(|row| self.row_to_session(row)).collect()
+    }
+
     pub async fn update(&self, session: &ChatSession) -> AnyhowResult<()> {
         let result = sqlx::query(
             r#"
             UPDATE chat_sessions SET
-                provider = ?, project_name = ?, start_time = ?, end_time = ?,
+                provider = ?, project_name = ?, title = ?, title_source = ?, start_time = ?, end_time = ?,
                 message_count = ?, token_count = ?, file_path = ?, file_hash = ?,
                 updated_at = ?, state = ?
             WHERE id = ?
@@ -123,6 +165,8 @@ impl ChatSessionRepository {
         )
         .bind(session.provider.to_string())
         .bind(session.project_name.as_ref())
+        .bind(session.title.as_ref())
+        .bind(session.title_source.map(|s| s.to_string()))
         .bind(session.start_time.to_rfc3339())
         .bind(session.end_time.map(|t| t.to_rfc3339()))
         .bind(session.message_count)
@@ -143,6 +187,130 @@ impl ChatSessionRepository {
         Ok(())
     }
 
+    /// Replace a session's title and record where it came from
+    pub async fn update_title(
+        &self,
+        id: &Uuid,
+        title: &str,
+        source: TitleSource,
+    ) -> AnyhowResult<()> {
+        let result = sqlx::query(
+            "UPDATE chat_sessions SET title = ?, title_source = ?, updated_at = ? WHERE id = ?",
+        )
+        .bind(title)
+        .bind(source.to_string())
+        .bind(Utc::now().to_rfc3339())
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await
+        .context("Failed to update session title")?;
+
+        if result.rows_affected() == 0 {
+            return Err(anyhow::anyhow!("Chat session not found"));
+        }
+
+        Ok(())
+    }
+
+    /// IDs of sessions still waiting for an LLM title, newest first
+    ///
+    /// With `missing_only`, only sessions without any title are returned;
+    /// otherwise heuristic titles are candidates for replacement too.
+    /// Teammates' sessions are read-only and never returned.
+    pub async fn get_ids_needing_title(&self, missing_only: bool) -> AnyhowResult<Vec<Uuid>> {
+        let sql = if missing_only {
+            "SELECT id FROM chat_sessions WHERE title IS NULL AND owner IS NULL ORDER BY start_time DESC"
+        } else {
+            r#"
+            SELECT id FROM chat_sessions
+            WHERE owner IS NULL
+              AND (title IS NULL OR title_source IS NULL OR title_source = 'heuristic')
+            ORDER BY start_time DESC
+            "#
+        };
+
+        let ids: Vec<String> = sqlx::query_scalar(sql)
+            .fetch_all(&self.pool)
+            .await
+            .context("Failed to fetch sessions needing a title")?;
+
+        ids.iter()
+            .map(|id| Uuid::parse_str(id).context("Invalid session ID format"))
+            .collect()
+    }
+
+    /// Recompute the cached statistics columns from the session's stored messages
+    ///
+    /// The token total reported by the provider is kept when there is one;
+    /// otherwise reported message usage is summed, then message estimates.
+    pub async fn refresh_stats(&self, id: &Uuid) -> AnyhowResult<()> {
+        sqlx::query(
+            r#"
+            UPDATE chat_sessions SET
+                message_count = (SELECT COUNT(*) FROM messages WHERE session_id = ?1),
+                token_count = COALESCE(
+                    token_count,
+                    (SELECT SUM(token_count) FROM messages WHERE session_id = ?1),
+                    (SELECT SUM(estimated_tokens) FROM messages WHERE session_id = ?1)
+                ),
+                tool_op_count = (
+                    SELECT COUNT(DISTINCT tool_operation_id) FROM messages WHERE session_id = ?1
+                ),
+                duration_seconds = CAST(
+                    ROUND((julianday(end_time) - julianday(start_time)) * 86400) AS INTEGER
+                )
+            WHERE id = ?1
+            "#,
+        )
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await
+        .context("Failed to refresh session statistics")?;
+
+        Ok(())
+    }
+
+    /// Set the state of the given sessions, returning how many changed
+    pub async fn set_state_by_ids(&self, ids: &[Uuid], state: &SessionState) -> AnyhowResult<u64> {
+        let now = Utc::now().to_rfc3339();
+        let mut changed = 0;
+
+        for id in ids {
+            let result = sqlx::query(
+                "UPDATE chat_sessions SET state = ?, updated_at = ? WHERE id = ? AND state != ?",
+            )
+            .bind(state.to_string())
+            .bind(&now)
+            .bind(id.to_string())
+            .bind(state.to_string())
+            .execute(&self.pool)
+            .await
+            .context("Failed to update session state")?;
+            changed += result.rows_affected();
+        }
+
+        Ok(changed)
+    }
+
+    /// Archive every own session that ended before `cutoff`
+    pub async fn archive_ended_before(&self, cutoff: DateTime<Utc>) -> AnyhowResult<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE chat_sessions SET state = ?, updated_at = ?
+            WHERE state != ? AND owner IS NULL AND COALESCE(end_time, start_time) < ?
+            "#,
+        )
+        .bind(SessionState::Archived.to_string())
+        .bind(Utc::now().to_rfc3339())
+        .bind(SessionState::Archived.to_string())
+        .bind(cutoff.to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .context("Failed to archive old sessions")?;
+
+        Ok(result.rows_affected())
+    }
+
     pub async fn delete(&self, id: &Uuid) -> AnyhowResult<bool> {
         let result = sqlx::query("DELETE FROM chat_sessions WHERE id = ?")
             .bind(id.to_string())
@@ -153,12 +321,23 @@ impl ChatSessionRepository {
         Ok(result.rows_affected() > 0)
     }
 
+    /// Delete every session in `state`, along with their messages and tool operations
+    pub async fn delete_by_state(&self, state: &SessionState) -> AnyhowResult<u64> {
+        let result = sqlx::query("DELETE FROM chat_sessions WHERE state = ?")
+            .bind(state.to_string())
+            .execute(&self.pool)
+            .await
+            .context("Failed to delete chat sessions by state")?;
+
+        Ok(result.rows_affected())
+    }
+
     pub async fn get_by_provider(&self, provider: &Provider) -> AnyhowResult<Vec<ChatSession>> {
         let rows = sqlx::query(
             r#"
-            SELECT id, provider, project_name, start_time, end_time,
-                   message_count, token_count, file_path, file_hash,
-                   created_at, updated_at, state
+            SELECT id, provider, project_name, title, title_source, start_time, end_time,
+                   message_count, token_count, tool_op_count, duration_seconds, file_path, file_hash,
+                   created_at, updated_at, state, owner
             FROM chat_sessions WHERE provider = ? ORDER BY updated_at DESC
             "#,
         )
@@ -179,9 +358,9 @@ impl ChatSessionRepository {
     pub async fn get_by_project_name(&self, project_name: &str) -> AnyhowResult<Vec<ChatSession>> {
         let rows = sqlx::query(
             r#"
-            SELECT id, provider, project_name, start_time, end_time,
-                   message_count, token_count, file_path, file_hash,
-                   created_at, updated_at, state
+            SELECT id, provider, project_name, title, title_source, start_time, end_time,
+                   message_count, token_count, tool_op_count, duration_seconds, file_path, file_hash,
+                   created_at, updated_at, state, owner
             FROM chat_sessions WHERE project_name = ? ORDER BY updated_at DESC
             "#,
         )
@@ -199,12 +378,27 @@ impl ChatSessionRepository {
         Ok(sessions)
     }
 
+    /// All file hashes recorded for imported sessions, used to spot files
+    /// that have not been imported yet (or changed since their last import)
+    pub async fn known_file_hashes(&self) -> AnyhowResult<HashSet<String>> {
+        let rows = sqlx::query("SELECT DISTINCT file_hash FROM chat_sessions")
+            .fetch_all(&self.pool)
+            .await
+            .context("Failed to fetch session file hashes")?;
+
+        let mut hashes = HashSet::with_capacity(rows.len());
+        for row in rows {
+            hashes.insert(row.try_get::<String, _>("file_hash")?);
+        }
+        Ok(hashes)
+    }
+
     pub async fn get_by_file_hash(&self, file_hash: &str) -> AnyhowResult<Option<ChatSession>> {
         let row = sqlx::query(
             r#"
-            SELECT id, provider, project_name, start_time, end_time,
-                   message_count, token_count, file_path, file_hash,
-                   created_at, updated_at, state
+            SELECT id, provider, project_name, title, title_source, start_time, end_time,
+                   message_count, token_count, tool_op_count, duration_seconds, file_path, file_hash,
+                   created_at, updated_at, state, owner
             FROM chat_sessions WHERE file_hash = ?
             "#,
         )
@@ -222,6 +416,20 @@ impl ChatSessionRepository {
         }
     }
 
+    /// The session a merged session's messages were moved into, if it was merged
+    pub async fn get_merged_into(&self, id: &Uuid) -> AnyhowResult<Option<Uuid>> {
+        let merged_into: Option<String> =
+            sqlx::query_scalar("SELECT merged_into FROM merged_sessions WHERE session_id = ?")
+                .bind(id.to_string())
+                .fetch_optional(&self.pool)
+                .await
+                .context("Failed to look up merged session")?;
+
+        merged_into
+            .map(|id| Uuid::parse_str(&id).context("Invalid session ID in merged_sessions"))
+            .transpose()
+    }
+
     pub async fn count(&self) -> AnyhowResult<i64> {
         let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM chat_sessions")
             .fetch_one(&self.pool)
@@ -245,9 +453,9 @@ impl ChatSessionRepository {
     pub async fn get_recent_sessions(&self, limit: i64) -> AnyhowResult<Vec<ChatSession>> {
         let rows = sqlx::query(
             r#"
-            SELECT id, provider, project_name, start_time, end_time,
-                   message_count, token_count, file_path, file_hash,
-                   created_at, updated_at, state
+            SELECT id, provider, project_name, title, title_source, start_time, end_time,
+                   message_count, token_count, tool_op_count, duration_seconds, file_path, file_hash,
+                   created_at, updated_at, state, owner
             FROM chat_sessions ORDER BY updated_at DESC LIMIT ?
             "#,
         )
@@ -265,6 +473,37 @@ impl ChatSessionRepository {
         Ok(sessions)
     }
 
+    /// Get sessions whose activity overlaps the given time range, newest first
+    pub async fn get_by_time_range(
+        &self,
+        from: &DateTime<Utc>,
+        to: &DateTime<Utc>,
+    ) -> AnyhowResult<Vec<ChatSession>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, provider, project_name, title, title_source, start_time, end_time,
+                   message_count, token_count, tool_op_count, duration_seconds, file_path, file_hash,
+                   created_at, updated_at, state, owner
+            FROM chat_sessions
+            WHERE start_time <= ? AND COALESCE(end_time, start_time) >= ?
+            ORDER BY start_time DESC
+            "#,
+        )
+        .bind(to.to_rfc3339())
+        .bind(from.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch chat sessions by time range")?;
+
+        let mut sessions = Vec::new();
+        for row in rows {
+            let session = self.row_to_session(&row)?;
+            sessions.push(session);
+        }
+
+        Ok(sessions)
+    }
+
     /// Get histogram of active sessions within a time range
     ///
     /// Returns (timestamp, count) pairs for each time bucket.
@@ -342,15 +581,20 @@ impl ChatSessionRepository {
         let id_str: String = row.try_get("id")?;
         let provider_str: String = row.try_get("provider")?;
         let project_name: Option<String> = row.try_get("project_name")?;
+        let title: Option<String> = row.try_get("title")?;
+        let title_source: Option<String> = row.try_get("title_source")?;
         let start_time_str: String = row.try_get("start_time")?;
         let end_time_str: Option<String> = row.try_get("end_time")?;
         let message_count: i64 = row.try_get("message_count")?;
         let token_count: Option<i64> = row.try_get("token_count")?;
+        let tool_op_count: i64 = row.try_get("tool_op_count")?;
+        let duration_seconds: Option<i64> = row.try_get("duration_seconds")?;
         let file_path: String = row.try_get("file_path")?;
         let file_hash: String = row.try_get("file_hash")?;
         let created_at_str: String = row.try_get("created_at")?;
         let updated_at_str: String = row.try_get("updated_at")?;
         let state_str: String = row.try_get("state")?;
+        let owner: Option<String> = row.try_get("owner")?;
 
         let id = Uuid::parse_str(&id_str).context("Invalid session ID format")?;
 
@@ -388,15 +632,20 @@ impl ChatSessionRepository {
             id,
             provider,
             project_name,
+            title,
+            title_source: title_source.and_then(|s| s.parse::<TitleSource>().ok()),
             start_time,
             end_time,
             message_count: message_count as u32,
             token_count: token_count.map(|tc| tc as u32),
+            tool_op_count: tool_op_count as u32,
+            duration_seconds: duration_seconds.map(|d| d.max(0) as u32),
             file_path,
             file_hash,
             created_at,
             updated_at,
             state,
+            owner,
         })
     }
 }
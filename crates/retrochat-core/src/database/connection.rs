@@ -1,7 +1,17 @@
 use anyhow::{Context, Result as AnyhowResult};
-use sqlx::{sqlite::SqlitePool, Pool, Sqlite};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool};
+use sqlx::{Pool, Sqlite};
 use std::path::{Path, PathBuf};
-use tracing::{debug, info};
+use std::time::Duration;
+use tokio::sync::watch;
+use tracing::{debug, info, warn};
+
+use super::coordination::{self, WriteGuard, WriteLock, WRITE_LOCK_TIMEOUT};
+use super::encryption;
+use super::migrations::MigrationManager;
+
+/// Pre-migration backups kept next to the database; older ones are removed
+const KEPT_MIGRATION_BACKUPS: usize = 2;
 
 /// Get the default database path in the user's home directory
 pub fn get_default_db_path() -> AnyhowResult<PathBuf> {
@@ -9,15 +19,93 @@ pub fn get_default_db_path() -> AnyhowResult<PathBuf> {
     Ok(home_dir.join(".retrochat").join("retrochat.db"))
 }
 
+/// Pre-migration backups of the database at `db_path`, newest first
+pub fn find_migration_backups(db_path: &Path) -> Vec<PathBuf> {
+    let Some(dir) = db_path.parent() else {
+        return Vec::new();
+    };
+    let prefix = format!(
+        "{}.pre-migration-",
+        db_path.file_name().unwrap_or_default().to_string_lossy()
+    );
+    let mut backups: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|path| {
+                    path.file_name()
+                        .map(|name| name.to_string_lossy())
+                        .is_some_and(|name| name.starts_with(&prefix) && name.ends_with(".bak"))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    // Zero-padded versions sort by name
+    backups.sort_unstable_by(|a, b| b.cmp(a));
+    backups
+}
+
 #[derive(Clone)]
 pub struct DatabaseManager {
     db_path: PathBuf,
     pool: Pool<Sqlite>,
+    write_lock: WriteLock,
+    /// SQLCipher passphrase of an encrypted database
+    passphrase: Option<String>,
 }
 
 impl DatabaseManager {
     pub async fn new(db_path: impl AsRef<Path>) -> AnyhowResult<Self> {
-        let db_path = db_path.as_ref().to_path_buf();
+        let manager = Self::open_without_migrations(db_path).await?;
+
+        // Run migrations; another process starting at the same time waits
+        let guard = manager.write_lock().await?;
+        manager.run_migrations().await?;
+        drop(guard);
+
+        info!(
+            "SQLx database initialized at: {}",
+            manager.db_path.display()
+        );
+        Ok(manager)
+    }
+
+    /// Open an encrypted database with an explicit passphrase instead of the
+    /// one from the environment or keychain, applying pending migrations
+    pub async fn open_with_passphrase(
+        db_path: impl AsRef<Path>,
+        passphrase: &str,
+    ) -> AnyhowResult<Self> {
+        let manager = Self::open(db_path.as_ref(), Some(passphrase.to_string())).await?;
+        let guard = manager.write_lock().await?;
+        manager.run_migrations().await?;
+        drop(guard);
+        Ok(manager)
+    }
+
+    /// Open a database without applying pending migrations, so a database
+    /// whose migrations failed can still be inspected and repaired
+    pub async fn open_without_migrations(db_path: impl AsRef<Path>) -> AnyhowResult<Self> {
+        let db_path = db_path.as_ref();
+        let passphrase = if encryption::is_encrypted(db_path)? {
+            let (passphrase, source) = encryption::resolve_passphrase()?.with_context(|| {
+                format!(
+                    "{} is encrypted; set {} or store the passphrase in the OS keychain \
+                     with `retrochat db encrypt --keychain`",
+                    db_path.display(),
+                    crate::env::database::DB_PASSPHRASE
+                )
+            })?;
+            debug!("Using database passphrase from {source}");
+            Some(passphrase)
+        } else {
+            None
+        };
+        Self::open(db_path, passphrase).await
+    }
+
+    async fn open(db_path: &Path, passphrase: Option<String>) -> AnyhowResult<Self> {
+        let db_path = db_path.to_path_buf();
 
         // Ensure parent directory exists
         if let Some(parent) = db_path.parent() {
@@ -35,29 +123,34 @@ impl DatabaseManager {
             })?;
         }
 
-        // Check for potentially corrupted WAL files before connecting
+        // Check for potentially corrupted WAL files before connecting, while
+        // no other process can be writing to them
+        let write_lock = WriteLock::new(&db_path);
+        let guard = write_lock.acquire(WRITE_LOCK_TIMEOUT).await?;
         Self::check_and_cleanup_wal_files(&db_path)?;
 
-        // Create SQLite connection string
-        let database_url = format!("sqlite://{}", db_path.display());
-
         // Create connection pool with optimized settings
-        let pool = SqlitePool::connect(&database_url)
-            .await
-            .with_context(|| format!("Failed to connect to database at: {}", db_path.display()))?;
+        let pool =
+            SqlitePool::connect_with(encryption::connect_options(&db_path, passphrase.as_deref()))
+                .await
+                .with_context(|| {
+                    format!("Failed to connect to database at: {}", db_path.display())
+                })?;
 
-        let manager = Self { db_path, pool };
+        let manager = Self {
+            db_path,
+            pool,
+            write_lock,
+            passphrase,
+        };
+        if manager.passphrase.is_some() {
+            manager.check_passphrase().await?;
+        }
 
         // Optimize database for performance
         manager.optimize_for_performance().await?;
+        drop(guard);
 
-        // Run migrations
-        manager.run_migrations().await?;
-
-        info!(
-            "SQLx database initialized at: {}",
-            manager.db_path.display()
-        );
         Ok(manager)
     }
 
@@ -72,8 +165,9 @@ impl DatabaseManager {
             match (std::fs::metadata(&wal_path), std::fs::metadata(&shm_path)) {
                 (Ok(wal_meta), Ok(shm_meta)) => {
                     // If WAL file is suspiciously small (< 100 bytes) or SHM is wrong size
-                    // (should be 32KB for SQLite), these might be corrupted
-                    wal_meta.len() < 100 || shm_meta.len() != 32768
+                    // (a multiple of 32KB for SQLite), these might be corrupted. An empty
+                    // WAL is normal while another process has the database open.
+                    (wal_meta.len() > 0 && wal_meta.len() < 100) || shm_meta.len() % 32768 != 0
                 }
                 _ => false,
             }
@@ -114,9 +208,12 @@ impl DatabaseManager {
             .await
             .context("Failed to create in-memory database")?;
 
+        let db_path = PathBuf::from(":memory:");
         let manager = Self {
-            db_path: PathBuf::from(":memory:"),
+            write_lock: WriteLock::new(&db_path),
+            db_path,
             pool,
+            passphrase: None,
         };
 
         // Run migrations
@@ -126,6 +223,23 @@ impl DatabaseManager {
         Ok(manager)
     }
 
+    /// Fail early with a clear message when the database can't be decrypted
+    async fn check_passphrase(&self) -> AnyhowResult<()> {
+        let mut conn = self.pool.acquire().await?;
+        if encryption::cipher_version(&mut conn).await?.is_none() {
+            anyhow::bail!(
+                "{} is encrypted, but this build of RetroChat can't read encrypted databases; \
+                 rebuild it with `--features sqlcipher`",
+                self.db_path.display()
+            );
+        }
+        sqlx::query("SELECT COUNT(*) FROM sqlite_master")
+            .fetch_one(&mut *conn)
+            .await
+            .with_context(|| format!("Wrong passphrase for {}", self.db_path.display()))?;
+        Ok(())
+    }
+
     async fn optimize_for_performance(&self) -> AnyhowResult<()> {
         // Enable WAL mode for better concurrency
         sqlx::query("PRAGMA journal_mode = WAL")
@@ -156,21 +270,136 @@ impl DatabaseManager {
         Ok(())
     }
 
+    /// Apply pending migrations, backing up an existing database first
     async fn run_migrations(&self) -> AnyhowResult<()> {
-        // Run SQLx migrations
-        sqlx::migrate!("./migrations")
-            .run(&self.pool)
-            .await
-            .context("Failed to run database migrations")?;
+        let migrations = MigrationManager::new(self.pool.clone());
+        let backup = self.backup_before_migrating(&migrations).await?;
+
+        if let Err(e) = migrations.migrate_to_latest().await {
+            let hint = match &backup {
+                Some(path) => format!(
+                    "The database was backed up to {} before migrating. Inspect it with \
+                     `retrochat db status`, or restore the backup with `retrochat db restore {}`",
+                    path.display(),
+                    path.display()
+                ),
+                None => "Inspect it with `retrochat db status`".to_string(),
+            };
+            return Err(e.context(hint));
+        }
 
         info!("Database migrations completed successfully");
         Ok(())
     }
 
+    /// Copy the database aside if it has data and migrations are pending
+    async fn backup_before_migrating(
+        &self,
+        migrations: &MigrationManager,
+    ) -> AnyhowResult<Option<PathBuf>> {
+        if self.db_path == Path::new(":memory:") {
+            return Ok(None);
+        }
+        let current = migrations.get_current_version().await?;
+        if current == 0 || migrations.pending_migrations().await?.is_empty() {
+            return Ok(None);
+        }
+
+        let path = self.migration_backup_path(current);
+        migrations.backup(&path).await?;
+        info!("Backed up database before migrating to {}", path.display());
+        self.prune_migration_backups();
+        Ok(Some(path))
+    }
+
+    /// Where the backup taken before migrating from `version` is written
+    pub fn migration_backup_path(&self, version: i64) -> PathBuf {
+        let mut name = self.db_path.file_name().unwrap_or_default().to_os_string();
+        name.push(format!(".pre-migration-{version:03}.bak"));
+        self.db_path.with_file_name(name)
+    }
+
+    /// Pre-migration backups of this database, newest first
+    pub fn migration_backups(&self) -> Vec<PathBuf> {
+        find_migration_backups(&self.db_path)
+    }
+
+    /// Replace the database at `db_path` with a copy of `backup`
+    ///
+    /// No connection to the database may be open.
+    pub fn restore_backup(db_path: &Path, backup: &Path) -> AnyhowResult<()> {
+        if !backup.is_file() {
+            anyhow::bail!("Backup not found: {}", backup.display());
+        }
+        for stale in [
+            db_path.with_extension("db-wal"),
+            db_path.with_extension("db-shm"),
+        ] {
+            if stale.exists() {
+                std::fs::remove_file(&stale)
+                    .with_context(|| format!("Failed to remove {}", stale.display()))?;
+            }
+        }
+        std::fs::copy(backup, db_path).with_context(|| {
+            format!(
+                "Failed to restore {} from {}",
+                db_path.display(),
+                backup.display()
+            )
+        })?;
+        Ok(())
+    }
+
+    fn prune_migration_backups(&self) {
+        for old in self
+            .migration_backups()
+            .into_iter()
+            .skip(KEPT_MIGRATION_BACKUPS)
+        {
+            if let Err(e) = std::fs::remove_file(&old) {
+                warn!("Failed to remove old backup {}: {}", old.display(), e);
+            }
+        }
+    }
+
+    /// Take the database-wide write lock, waiting for writers in this and
+    /// other processes
+    ///
+    /// Hold it across multi-statement writes that readers shouldn't see half
+    /// done. The lock is not reentrant: don't take it again while holding it.
+    pub async fn write_lock(&self) -> AnyhowResult<WriteGuard> {
+        self.write_lock.acquire(WRITE_LOCK_TIMEOUT).await
+    }
+
+    /// Receive a notification whenever another connection or process commits
+    /// to the database, checking every `interval`
+    ///
+    /// The value is a change counter; watchers only need `changed()`. Polling
+    /// stops once every receiver is dropped.
+    pub async fn watch_changes(&self, interval: Duration) -> AnyhowResult<watch::Receiver<u64>> {
+        coordination::watch_data_version(&self.pool, interval).await
+    }
+
     pub fn pool(&self) -> &Pool<Sqlite> {
         &self.pool
     }
 
+    /// Options for opening another connection to this database outside the
+    /// pool, keyed like the pooled ones
+    pub fn connect_options(&self) -> SqliteConnectOptions {
+        encryption::connect_options(&self.db_path, self.passphrase.as_deref())
+    }
+
+    /// Whether the database is encrypted with SQLCipher
+    pub fn is_encrypted(&self) -> bool {
+        self.passphrase.is_some()
+    }
+
+    /// Path of the database file (`:memory:` for in-memory databases)
+    pub fn db_path(&self) -> &Path {
+        &self.db_path
+    }
+
     pub async fn close(self) -> AnyhowResult<()> {
         self.pool.close().await;
         Ok(())
@@ -191,3 +420,36 @@ impl Drop for DatabaseManager {
         debug!("SQLx database manager dropped");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_backup_before_migrating() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("retrochat.db");
+
+        let db = DatabaseManager::new(&db_path).await.unwrap();
+        assert!(db.migration_backups().is_empty());
+        let migrations = MigrationManager::new(db.pool().clone());
+        let latest = migrations.get_latest_version();
+        migrations.rollback_to(latest - 1).await.unwrap();
+        db.close().await.unwrap();
+
+        // Reopening applies the rolled-back migration after a backup
+        let db = DatabaseManager::new(&db_path).await.unwrap();
+        let backups = db.migration_backups();
+        assert_eq!(backups, vec![db.migration_backup_path(latest - 1)]);
+        db.close().await.unwrap();
+
+        let restored = dir.path().join("restored.db");
+        DatabaseManager::restore_backup(&restored, &backups[0]).unwrap();
+        let db = DatabaseManager::open_without_migrations(&restored)
+            .await
+            .unwrap();
+        let migrations = MigrationManager::new(db.pool().clone());
+        assert_eq!(migrations.get_current_version().await.unwrap(), latest - 1);
+        assert_eq!(migrations.pending_migrations().await.unwrap().len(), 1);
+    }
+}
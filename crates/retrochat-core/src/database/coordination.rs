@@ -0,0 +1,206 @@
+//! Coordination between processes sharing one database
+//!
+//! The watch daemon, TUI, desktop app and MCP server can all have the same
+//! database open. SQLite allows a single writer at a time, so multi-statement
+//! writes (importing a session, applying migrations, retention) take an
+//! advisory lock on `<db>.lock` first; other processes wait for it instead of
+//! failing with "database is locked" halfway through. Readers learn about
+//! other processes' writes by polling `PRAGMA data_version`.
+
+use anyhow::{Context, Result as AnyhowResult};
+use sqlx::{Pool, Sqlite};
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io::{Read, Seek, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{watch, Mutex, OwnedMutexGuard};
+use tracing::debug;
+
+/// How long a writer waits for another process before giving up
+pub const WRITE_LOCK_TIMEOUT: Duration = Duration::from_secs(60);
+const WRITE_LOCK_RETRY: Duration = Duration::from_millis(50);
+
+/// Exclusive right to write to the database, released on drop
+pub struct WriteGuard {
+    _local: OwnedMutexGuard<()>,
+    // Closing the file releases the advisory lock
+    _file: Option<File>,
+}
+
+/// Lock serializing writers across tasks of this process and across processes
+#[derive(Clone)]
+pub(crate) struct WriteLock {
+    /// `None` for in-memory databases, which only this process can see
+    path: Option<PathBuf>,
+    local: Arc<Mutex<()>>,
+}
+
+impl WriteLock {
+    pub(crate) fn new(db_path: &Path) -> Self {
+        let path = (db_path != Path::new(":memory:")).then(|| {
+            let mut name = db_path.file_name().unwrap_or_default().to_os_string();
+            name.push(".lock");
+            db_path.with_file_name(name)
+        });
+        Self {
+            path,
+            local: Arc::new(Mutex::new(())),
+        }
+    }
+
+    pub(crate) async fn acquire(&self, timeout: Duration) -> AnyhowResult<WriteGuard> {
+        let deadline = Instant::now() + timeout;
+        let local = tokio::time::timeout(timeout, self.local.clone().lock_owned())
+            .await
+            .context("Timed out waiting for another write in this process")?;
+
+        let Some(path) = &self.path else {
+            return Ok(WriteGuard {
+                _local: local,
+                _file: None,
+            });
+        };
+
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)
+            .with_context(|| format!("Failed to open lock file {}", path.display()))?;
+
+        loop {
+            match file.try_lock() {
+                Ok(()) => break,
+                Err(TryLockError::WouldBlock) if Instant::now() < deadline => {
+                    tokio::time::sleep(WRITE_LOCK_RETRY).await;
+                }
+                Err(TryLockError::WouldBlock) => {
+                    let mut holder = String::new();
+                    let _ = file.read_to_string(&mut holder);
+                    anyhow::bail!(
+                        "Timed out after {}s waiting for another RetroChat process (pid {}) to \
+                         finish writing to the database",
+                        timeout.as_secs(),
+                        holder.trim()
+                    );
+                }
+                Err(TryLockError::Error(e)) => {
+                    return Err(e).with_context(|| format!("Failed to lock {}", path.display()))
+                }
+            }
+        }
+
+        // Record the holder for the timeout message of other processes
+        let _ = file.set_len(0);
+        let _ = file.rewind();
+        let _ = write!(file, "{}", std::process::id());
+        debug!("Acquired database write lock");
+
+        Ok(WriteGuard {
+            _local: local,
+            _file: Some(file),
+        })
+    }
+}
+
+/// Poll `PRAGMA data_version` on a dedicated connection and bump the watched
+/// counter whenever another connection commits
+pub(crate) async fn watch_data_version(
+    pool: &Pool<Sqlite>,
+    interval: Duration,
+) -> AnyhowResult<watch::Receiver<u64>> {
+    // data_version only changes for commits made by *other* connections, so
+    // the watcher needs a connection of its own
+    let mut connection = pool
+        .acquire()
+        .await
+        .context("Failed to open a connection for change notifications")?
+        .detach();
+    let mut last: i64 = sqlx::query_scalar("PRAGMA data_version")
+        .fetch_one(&mut connection)
+        .await?;
+
+    let (sender, receiver) = watch::channel(0u64);
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            if sender.is_closed() {
+                break;
+            }
+            let version: i64 = match sqlx::query_scalar("PRAGMA data_version")
+                .fetch_one(&mut connection)
+                .await
+            {
+                Ok(version) => version,
+                Err(e) => {
+                    tracing::warn!(error = %e, "Stopped watching the database for changes");
+                    break;
+                }
+            };
+            if version != last {
+                last = version;
+                sender.send_modify(|changes| *changes += 1);
+            }
+        }
+    });
+
+    Ok(receiver)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::DatabaseManager;
+
+    #[tokio::test]
+    async fn test_write_lock_is_exclusive() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("retrochat.db");
+        // Two locks on one file behave like two processes
+        let first = WriteLock::new(&db_path);
+        let second = WriteLock::new(&db_path);
+
+        let guard = first.acquire(Duration::from_secs(1)).await.unwrap();
+        let error = second
+            .acquire(Duration::from_millis(200))
+            .await
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains(&std::process::id().to_string()));
+
+        drop(guard);
+        second.acquire(Duration::from_secs(1)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_watch_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("retrochat.db");
+        let reader = DatabaseManager::new(&db_path).await.unwrap();
+        let writer = DatabaseManager::new(&db_path).await.unwrap();
+
+        let mut changes = reader
+            .watch_changes(Duration::from_millis(20))
+            .await
+            .unwrap();
+        assert!(!changes.has_changed().unwrap());
+
+        sqlx::query(
+            "INSERT INTO saved_views (id, name, expression, created_at, updated_at) \
+             VALUES ('v', 'v', 'provider:claude', '', '')",
+        )
+        .execute(writer.pool())
+        .await
+        .unwrap();
+
+        tokio::time::timeout(Duration::from_secs(5), changes.changed())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(*changes.borrow_and_update(), 1);
+    }
+}
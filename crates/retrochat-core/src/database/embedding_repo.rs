@@ -0,0 +1,441 @@
+use anyhow::{Context, Result as AnyhowResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Row, Sqlite};
+use std::collections::HashMap;
+use std::fmt;
+
+use super::connection::DatabaseManager;
+
+/// What an embedding was computed from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmbeddingKind {
+    Turn,
+    Session,
+}
+
+impl EmbeddingKind {
+    pub const ALL: [EmbeddingKind; 2] = [EmbeddingKind::Turn, EmbeddingKind::Session];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EmbeddingKind::Turn => "turn",
+            EmbeddingKind::Session => "session",
+        }
+    }
+
+    /// Table holding the summaries of this kind
+    fn source_table(&self) -> &'static str {
+        match self {
+            EmbeddingKind::Turn => "turn_summaries",
+            EmbeddingKind::Session => "session_summaries",
+        }
+    }
+}
+
+impl fmt::Display for EmbeddingKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A stored summary embedding
+#[derive(Debug, Clone)]
+pub struct StoredEmbedding {
+    pub source_id: String,
+    pub session_id: String,
+    pub vector: Vec<f32>,
+}
+
+/// Which embedder built the vectors of one kind, and when
+#[derive(Debug, Clone, PartialEq)]
+pub struct VectorIndexState {
+    pub kind: EmbeddingKind,
+    pub embedder: String,
+    pub dimensions: usize,
+    pub last_indexed_at: Option<DateTime<Utc>>,
+    pub last_rebuilt_at: Option<DateTime<Utc>>,
+}
+
+pub struct EmbeddingRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl EmbeddingRepository {
+    pub fn new(db: &DatabaseManager) -> Self {
+        Self {
+            pool: db.pool().clone(),
+        }
+    }
+
+    /// Store the embedding of a summary, replacing any earlier one
+    pub async fn upsert(
+        &self,
+        kind: EmbeddingKind,
+        source_id: &str,
+        session_id: &str,
+        content_hash: &str,
+        vector: &[f32],
+    ) -> AnyhowResult<()> {
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO summary_embeddings (kind, source_id, session_id, content_hash, vector, indexed_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(kind.as_str())
+        .bind(source_id)
+        .bind(session_id)
+        .bind(content_hash)
+        .bind(encode_vector(vector))
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .context("Failed to store embedding")?;
+
+        Ok(())
+    }
+
+    /// Content hash of every stored embedding of `kind`, by source ID,
+    /// optionally limited to one session
+    pub async fn content_hashes(
+        &self,
+        kind: EmbeddingKind,
+        session_id: Option<&str>,
+    ) -> AnyhowResult<HashMap<String, String>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT source_id, content_hash FROM summary_embeddings
+            WHERE kind = ? AND (? IS NULL OR session_id = ?)
+            "#,
+        )
+        .bind(kind.as_str())
+        .bind(session_id)
+        .bind(session_id)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to read embedding hashes")?;
+
+        rows.iter()
+            .map(|row| Ok((row.try_get("source_id")?, row.try_get("content_hash")?)))
+            .collect()
+    }
+
+    /// All stored embeddings of `kind`
+    pub async fn list(&self, kind: EmbeddingKind) -> AnyhowResult<Vec<StoredEmbedding>> {
+        let rows = sqlx::query(
+            "SELECT source_id, session_id, vector FROM summary_embeddings WHERE kind = ?",
+        )
+        .bind(kind.as_str())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to read embeddings")?;
+
+        rows.iter()
+            .map(|row| {
+                let bytes: Vec<u8> = row.try_get("vector")?;
+                Ok(StoredEmbedding {
+                    source_id: row.try_get("source_id")?,
+                    session_id: row.try_get("session_id")?,
+                    vector: decode_vector(&bytes),
+                })
+            })
+            .collect()
+    }
+
+    /// Delete the embeddings of the given summaries
+    pub async fn delete(&self, kind: EmbeddingKind, source_ids: &[String]) -> AnyhowResult<u64> {
+        let mut tx = self.pool.begin().await?;
+        let mut deleted = 0;
+        for source_id in source_ids {
+            deleted +=
+                sqlx::query("DELETE FROM summary_embeddings WHERE kind = ? AND source_id = ?")
+                    .bind(kind.as_str())
+                    .bind(source_id)
+                    .execute(&mut *tx)
+                    .await
+                    .context("Failed to delete embedding")?
+                    .rows_affected();
+        }
+        tx.commit().await?;
+
+        Ok(deleted)
+    }
+
+    /// Delete every embedding of `kind`
+    pub async fn clear(&self, kind: EmbeddingKind) -> AnyhowResult<u64> {
+        let result = sqlx::query("DELETE FROM summary_embeddings WHERE kind = ?")
+            .bind(kind.as_str())
+            .execute(&self.pool)
+            .await
+            .context("Failed to clear embeddings")?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Number of summaries of `kind` that exist, and how many of them have an embedding
+    pub async fn coverage(&self, kind: EmbeddingKind) -> AnyhowResult<(i64, i64)> {
+        let sql = format!(
+            r#"
+            SELECT
+                COUNT(*) AS total,
+                COUNT(e.source_id) AS indexed
+            FROM {table} s
+            LEFT JOIN summary_embeddings e ON e.kind = ? AND e.source_id = s.id
+            "#,
+            table = kind.source_table()
+        );
+        let row = sqlx::query(&sql)
+            .bind(kind.as_str())
+            .fetch_one(&self.pool)
+            .await
+            .context("Failed to count indexed summaries")?;
+
+        Ok((row.try_get("total")?, row.try_get("indexed")?))
+    }
+
+    /// Number of embeddings whose session or summary no longer exists
+    pub async fn count_orphans(&self) -> AnyhowResult<i64> {
+        sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*) FROM summary_embeddings
+            WHERE session_id NOT IN (SELECT id FROM chat_sessions)
+               OR (kind = 'turn' AND source_id NOT IN (SELECT id FROM turn_summaries))
+               OR (kind = 'session' AND source_id NOT IN (SELECT id FROM session_summaries))
+            "#,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to count orphaned embeddings")
+    }
+
+    /// Number of vectors of `kind` that do not have `dimensions` components
+    pub async fn count_malformed(
+        &self,
+        kind: EmbeddingKind,
+        dimensions: usize,
+    ) -> AnyhowResult<i64> {
+        sqlx::query_scalar(
+            "SELECT COUNT(*) FROM summary_embeddings WHERE kind = ? AND LENGTH(vector) != ?",
+        )
+        .bind(kind.as_str())
+        .bind((dimensions * 4) as i64)
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to count malformed embeddings")
+    }
+
+    /// Delete embeddings whose session or summary no longer exists
+    pub async fn delete_orphans(&self) -> AnyhowResult<u64> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM summary_embeddings
+            WHERE session_id NOT IN (SELECT id FROM chat_sessions)
+               OR (kind = 'turn' AND source_id NOT IN (SELECT id FROM turn_summaries))
+               OR (kind = 'session' AND source_id NOT IN (SELECT id FROM session_summaries))
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to delete orphaned embeddings")?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Delete vectors of `kind` that do not have `dimensions` components
+    pub async fn delete_malformed(
+        &self,
+        kind: EmbeddingKind,
+        dimensions: usize,
+    ) -> AnyhowResult<u64> {
+        let result =
+            sqlx::query("DELETE FROM summary_embeddings WHERE kind = ? AND LENGTH(vector) != ?")
+                .bind(kind.as_str())
+                .bind((dimensions * 4) as i64)
+                .execute(&self.pool)
+                .await
+                .context("Failed to delete malformed embeddings")?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Number of stored vectors of `kind` and the bytes they take up
+    pub async fn storage(&self, kind: EmbeddingKind) -> AnyhowResult<(i64, i64)> {
+        let row = sqlx::query(
+            r#"
+            SELECT
+                COUNT(*) AS vectors,
+                COALESCE(SUM(LENGTH(vector) + LENGTH(content_hash) + LENGTH(source_id) + LENGTH(session_id)), 0) AS bytes
+            FROM summary_embeddings WHERE kind = ?
+            "#,
+        )
+        .bind(kind.as_str())
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to measure embedding storage")?;
+
+        Ok((row.try_get("vectors")?, row.try_get("bytes")?))
+    }
+
+    /// Bytes held by free pages of the database file, which the vector
+    /// store shares with everything else
+    pub async fn reclaimable_bytes(&self) -> AnyhowResult<i64> {
+        let free_pages: i64 = sqlx::query_scalar("PRAGMA freelist_count")
+            .fetch_one(&self.pool)
+            .await
+            .context("Failed to read free page count")?;
+        let page_size: i64 = sqlx::query_scalar("PRAGMA page_size")
+            .fetch_one(&self.pool)
+            .await
+            .context("Failed to read page size")?;
+
+        Ok(free_pages * page_size)
+    }
+
+    /// Rewrite the database file, returning free pages to the filesystem
+    pub async fn vacuum(&self) -> AnyhowResult<()> {
+        sqlx::query("VACUUM")
+            .execute(&self.pool)
+            .await
+            .context("Failed to compact the database")?;
+
+        Ok(())
+    }
+
+    pub async fn get_state(&self, kind: EmbeddingKind) -> AnyhowResult<Option<VectorIndexState>> {
+        let row = sqlx::query(
+            r#"
+            SELECT embedder, dimensions, last_indexed_at, last_rebuilt_at
+            FROM vector_index_state WHERE kind = ?
+            "#,
+        )
+        .bind(kind.as_str())
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to read vector index state")?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let last_indexed_at: Option<String> = row.try_get("last_indexed_at")?;
+        let last_rebuilt_at: Option<String> = row.try_get("last_rebuilt_at")?;
+
+        Ok(Some(VectorIndexState {
+            kind,
+            embedder: row.try_get("embedder")?,
+            dimensions: row.try_get::<i64, _>("dimensions")? as usize,
+            last_indexed_at: last_indexed_at
+                .as_deref()
+                .map(parse_timestamp)
+                .transpose()?,
+            last_rebuilt_at: last_rebuilt_at
+                .as_deref()
+                .map(parse_timestamp)
+                .transpose()?,
+        }))
+    }
+
+    pub async fn save_state(&self, state: &VectorIndexState) -> AnyhowResult<()> {
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO vector_index_state (kind, embedder, dimensions, last_indexed_at, last_rebuilt_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(state.kind.as_str())
+        .bind(&state.embedder)
+        .bind(state.dimensions as i64)
+        .bind(state.last_indexed_at.map(|t| t.to_rfc3339()))
+        .bind(state.last_rebuilt_at.map(|t| t.to_rfc3339()))
+        .execute(&self.pool)
+        .await
+        .context("Failed to save vector index state")?;
+
+        Ok(())
+    }
+}
+
+fn parse_timestamp(value: &str) -> AnyhowResult<DateTime<Utc>> {
+    Ok(DateTime::parse_from_rfc3339(value)
+        .context("Invalid vector index timestamp")?
+        .with_timezone(&Utc))
+}
+
+fn encode_vector(vector: &[f32]) -> Vec<u8> {
+    vector.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+fn decode_vector(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_upsert_list_and_delete() {
+        let db = DatabaseManager::open_in_memory().await.unwrap();
+        let repo = EmbeddingRepository::new(&db);
+
+        repo.upsert(EmbeddingKind::Turn, "t1", "s1", "h1", &[0.5, -1.0])
+            .await
+            .unwrap();
+        repo.upsert(EmbeddingKind::Turn, "t1", "s1", "h2", &[1.0, 0.25])
+            .await
+            .unwrap();
+        repo.upsert(EmbeddingKind::Turn, "t2", "s2", "h3", &[0.0, 1.0])
+            .await
+            .unwrap();
+
+        let hashes = repo
+            .content_hashes(EmbeddingKind::Turn, Some("s1"))
+            .await
+            .unwrap();
+        assert_eq!(hashes.len(), 1);
+        assert_eq!(hashes["t1"], "h2");
+
+        let stored = repo.list(EmbeddingKind::Turn).await.unwrap();
+        let t1 = stored.iter().find(|e| e.source_id == "t1").unwrap();
+        assert_eq!(t1.vector, vec![1.0, 0.25]);
+        assert!(repo.list(EmbeddingKind::Session).await.unwrap().is_empty());
+
+        assert_eq!(
+            repo.delete(EmbeddingKind::Turn, &["t2".to_string()])
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(repo.clear(EmbeddingKind::Turn).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_state_round_trip() {
+        let db = DatabaseManager::open_in_memory().await.unwrap();
+        let repo = EmbeddingRepository::new(&db);
+
+        assert_eq!(repo.get_state(EmbeddingKind::Session).await.unwrap(), None);
+
+        let state = VectorIndexState {
+            kind: EmbeddingKind::Session,
+            embedder: "hashing".to_string(),
+            dimensions: 256,
+            last_indexed_at: Some(
+                DateTime::parse_from_rfc3339("2024-05-01T10:00:00Z")
+                    .unwrap()
+                    .with_timezone(&Utc),
+            ),
+            last_rebuilt_at: None,
+        };
+        repo.save_state(&state).await.unwrap();
+
+        assert_eq!(
+            repo.get_state(EmbeddingKind::Session).await.unwrap(),
+            Some(state)
+        );
+    }
+}
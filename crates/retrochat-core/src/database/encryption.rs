@@ -0,0 +1,401 @@
+//! Encryption at rest with SQLCipher
+//!
+//! [`DatabaseManager`](super::DatabaseManager) reads the file header to tell
+//! whether a database is encrypted and, if it is, keys every connection with
+//! the passphrase from `RETROCHAT_DB_PASSPHRASE` or the OS keychain.
+//! Plaintext databases (new installs, snapshots, sync bundles) open without a
+//! passphrase as before. SQLCipher derives the page key from the passphrase
+//! with PBKDF2-HMAC-SHA512. Encrypted databases need a build with the
+//! `sqlcipher` feature; [`reencrypt`] converts between the two forms.
+
+use anyhow::{Context, Result as AnyhowResult};
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{ConnectOptions, Connection, SqliteConnection};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+use super::coordination::{WriteLock, WRITE_LOCK_TIMEOUT};
+use crate::env::database as env_database;
+
+/// First bytes of every plaintext SQLite database
+const SQLITE_HEADER: &[u8; 16] = b"SQLite format 3\0";
+
+/// Where a database passphrase came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PassphraseSource {
+    Environment,
+    Keychain,
+}
+
+impl std::fmt::Display for PassphraseSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PassphraseSource::Environment => write!(f, "{}", env_database::DB_PASSPHRASE),
+            PassphraseSource::Keychain => write!(f, "OS keychain"),
+        }
+    }
+}
+
+/// Whether the file at `db_path` is an encrypted database
+///
+/// Missing and empty files are new plaintext databases. A file that isn't a
+/// SQLite database at all also reads as encrypted; opening it then fails.
+pub fn is_encrypted(db_path: &Path) -> AnyhowResult<bool> {
+    let mut header = Vec::with_capacity(SQLITE_HEADER.len());
+    match std::fs::File::open(db_path) {
+        Ok(file) => {
+            file.take(SQLITE_HEADER.len() as u64)
+                .read_to_end(&mut header)
+                .with_context(|| format!("Failed to read {}", db_path.display()))?;
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e).with_context(|| format!("Failed to open {}", db_path.display())),
+    }
+    Ok(!header.is_empty() && header != SQLITE_HEADER)
+}
+
+/// Database passphrase with priority: environment variable > OS keychain
+pub fn resolve_passphrase() -> AnyhowResult<Option<(String, PassphraseSource)>> {
+    if let Ok(passphrase) = std::env::var(env_database::DB_PASSPHRASE) {
+        if !passphrase.is_empty() {
+            return Ok(Some((passphrase, PassphraseSource::Environment)));
+        }
+    }
+    Ok(keychain::get()?.map(|passphrase| (passphrase, PassphraseSource::Keychain)))
+}
+
+/// Connection options for the database at `db_path`, keyed when a
+/// passphrase is given
+pub fn connect_options(db_path: &Path, passphrase: Option<&str>) -> SqliteConnectOptions {
+    let options = SqliteConnectOptions::new().filename(db_path);
+    match passphrase {
+        // sqlx issues the key pragma before any other statement
+        Some(passphrase) => options.pragma("key", quote(passphrase)),
+        None => options,
+    }
+}
+
+fn quote(passphrase: &str) -> String {
+    format!("'{}'", passphrase.replace('\'', "''"))
+}
+
+/// SQLCipher version linked into this build, if any
+pub async fn cipher_version(conn: &mut SqliteConnection) -> AnyhowResult<Option<String>> {
+    // Plain SQLite ignores unknown pragmas and returns no rows
+    Ok(sqlx::query_scalar("PRAGMA cipher_version")
+        .fetch_optional(conn)
+        .await?)
+}
+
+/// Whether this build can read and write encrypted databases
+pub async fn is_supported() -> bool {
+    match SqliteConnectOptions::new()
+        .filename(":memory:")
+        .connect()
+        .await
+    {
+        Ok(mut conn) => cipher_version(&mut conn).await.ok().flatten().is_some(),
+        Err(_) => false,
+    }
+}
+
+fn unsupported_error() -> anyhow::Error {
+    anyhow::anyhow!(
+        "This build of RetroChat can't read or write encrypted databases; \
+         rebuild it with `--features sqlcipher`"
+    )
+}
+
+/// Rewrite the database at `db_path` under a different passphrase
+///
+/// `from` opens the current file and `to` keys the rewritten one; `None`
+/// means plaintext on either side, so this encrypts, decrypts and changes
+/// passphrases. The copy is checked before it replaces the original, and the
+/// original is gone afterwards. Other processes must not have the database
+/// open.
+pub async fn reencrypt(db_path: &Path, from: Option<&str>, to: Option<&str>) -> AnyhowResult<()> {
+    if !is_supported().await {
+        return Err(unsupported_error());
+    }
+    if !db_path.is_file() {
+        anyhow::bail!("Database not found: {}", db_path.display());
+    }
+
+    let write_lock = WriteLock::new(db_path);
+    let _guard = write_lock.acquire(WRITE_LOCK_TIMEOUT).await?;
+
+    let target = sibling(db_path, ".rekey");
+    if target.exists() {
+        std::fs::remove_file(&target)
+            .with_context(|| format!("Failed to remove {}", target.display()))?;
+    }
+
+    // ATTACH inherits the open flags, and the target doesn't exist yet
+    let mut conn = connect_options(db_path, from)
+        .create_if_missing(true)
+        .connect()
+        .await
+        .with_context(|| format!("Failed to open {}", db_path.display()))?;
+    let objects = count_objects(&mut conn)
+        .await
+        .context("Failed to read the database; is the passphrase right?")?;
+    sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)")
+        .execute(&mut conn)
+        .await?;
+
+    sqlx::query("ATTACH DATABASE ? AS rekeyed KEY ?")
+        .bind(target.to_string_lossy().to_string())
+        .bind(to.unwrap_or(""))
+        .execute(&mut conn)
+        .await
+        .context("Failed to create the rewritten database")?;
+    let exported = sqlx::query("SELECT sqlcipher_export('rekeyed')")
+        .execute(&mut conn)
+        .await
+        .context("Failed to copy the database");
+    sqlx::query("DETACH DATABASE rekeyed")
+        .execute(&mut conn)
+        .await?;
+    conn.close().await?;
+    if let Err(e) = exported {
+        let _ = std::fs::remove_file(&target);
+        return Err(e);
+    }
+
+    let mut check = connect_options(&target, to).connect().await?;
+    let integrity: String = sqlx::query_scalar("PRAGMA integrity_check")
+        .fetch_one(&mut check)
+        .await?;
+    let copied = count_objects(&mut check).await?;
+    check.close().await?;
+    if integrity != "ok" || copied != objects {
+        let _ = std::fs::remove_file(&target);
+        anyhow::bail!(
+            "The rewritten database failed verification ({integrity}); nothing was changed"
+        );
+    }
+
+    for stale in [sibling(db_path, "-wal"), sibling(db_path, "-shm")] {
+        if stale.exists() {
+            std::fs::remove_file(&stale)
+                .with_context(|| format!("Failed to remove {}", stale.display()))?;
+        }
+    }
+    std::fs::rename(&target, db_path)
+        .with_context(|| format!("Failed to replace {}", db_path.display()))?;
+    info!(
+        encrypted = to.is_some(),
+        "Rewrote database {}",
+        db_path.display()
+    );
+    Ok(())
+}
+
+async fn count_objects(conn: &mut SqliteConnection) -> AnyhowResult<i64> {
+    Ok(sqlx::query_scalar("SELECT COUNT(*) FROM sqlite_master")
+        .fetch_one(conn)
+        .await?)
+}
+
+fn sibling(db_path: &Path, suffix: &str) -> PathBuf {
+    let mut name = db_path.file_name().unwrap_or_default().to_os_string();
+    name.push(suffix);
+    db_path.with_file_name(name)
+}
+
+/// The database passphrase in the OS keychain
+///
+/// Uses the `security` tool on macOS and libsecret's `secret-tool` on Linux;
+/// other platforms fall back to the environment variable.
+pub mod keychain {
+    use anyhow::{Context, Result as AnyhowResult};
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    const SERVICE: &str = "retrochat";
+    const ACCOUNT: &str = "database";
+
+    /// The stored passphrase; `None` when there is none or no keychain
+    pub fn get() -> AnyhowResult<Option<String>> {
+        let output = if cfg!(target_os = "macos") {
+            Command::new("security")
+                .args(["find-generic-password", "-s", SERVICE, "-a", ACCOUNT, "-w"])
+                .output()
+        } else if cfg!(target_os = "linux") {
+            Command::new("secret-tool")
+                .args(["lookup", "service", SERVICE, "account", ACCOUNT])
+                .output()
+        } else {
+            return Ok(None);
+        };
+        match output {
+            Ok(output) if output.status.success() => {
+                let passphrase = String::from_utf8(output.stdout)
+                    .context("Keychain passphrase is not valid UTF-8")?;
+                let passphrase = passphrase.trim_end_matches(['\r', '\n']);
+                Ok((!passphrase.is_empty()).then(|| passphrase.to_string()))
+            }
+            Ok(_) => Ok(None),
+            Err(e) => {
+                tracing::debug!(error = %e, "OS keychain is not available");
+                Ok(None)
+            }
+        }
+    }
+
+    /// Store the passphrase, replacing any previous one
+    pub fn store(passphrase: &str) -> AnyhowResult<()> {
+        let status = if cfg!(target_os = "macos") {
+            Command::new("security")
+                .args([
+                    "add-generic-password",
+                    "-U",
+                    "-s",
+                    SERVICE,
+                    "-a",
+                    ACCOUNT,
+                    "-w",
+                    passphrase,
+                ])
+                .stdout(Stdio::null())
+                .status()
+                .context("Failed to run `security`")?
+        } else if cfg!(target_os = "linux") {
+            let mut child = Command::new("secret-tool")
+                .args([
+                    "store",
+                    "--label",
+                    "RetroChat database",
+                    "service",
+                    SERVICE,
+                    "account",
+                    ACCOUNT,
+                ])
+                .stdin(Stdio::piped())
+                .spawn()
+                .context("Failed to run `secret-tool`; is libsecret installed?")?;
+            child
+                .stdin
+                .take()
+                .context("Failed to open secret-tool input")?
+                .write_all(passphrase.as_bytes())?;
+            child.wait()?
+        } else {
+            anyhow::bail!(
+                "No supported keychain on this platform; set {} instead",
+                crate::env::database::DB_PASSPHRASE
+            );
+        };
+        if !status.success() {
+            anyhow::bail!("Failed to store the passphrase in the OS keychain");
+        }
+        Ok(())
+    }
+
+    /// Remove the stored passphrase, if any
+    pub fn delete() -> AnyhowResult<()> {
+        let result = if cfg!(target_os = "macos") {
+            Command::new("security")
+                .args(["delete-generic-password", "-s", SERVICE, "-a", ACCOUNT])
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+        } else if cfg!(target_os = "linux") {
+            Command::new("secret-tool")
+                .args(["clear", "service", SERVICE, "account", ACCOUNT])
+                .status()
+        } else {
+            return Ok(());
+        };
+        // Failing because nothing was stored is fine
+        result
+            .map(|_| ())
+            .context("Failed to access the OS keychain")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{ChatSessionRepository, DatabaseManager, MigrationManager};
+    use crate::models::{ChatSession, Provider};
+    use crate::services::snapshot_export::export_sqlite_snapshot;
+    use chrono::Utc;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_is_encrypted_reads_header() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("plain.db");
+        assert!(!is_encrypted(&path).unwrap());
+
+        DatabaseManager::new(&path)
+            .await
+            .unwrap()
+            .close()
+            .await
+            .unwrap();
+        assert!(!is_encrypted(&path).unwrap());
+
+        let garbled = dir.path().join("garbled.db");
+        std::fs::write(&garbled, [0x5au8; 64]).unwrap();
+        assert!(is_encrypted(&garbled).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_reencrypt_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("retrochat.db");
+        let db = DatabaseManager::new(&path).await.unwrap();
+        let session = ChatSession::new(
+            Provider::ClaudeCode,
+            "/secret.jsonl".to_string(),
+            "hash".to_string(),
+            Utc::now(),
+        );
+        ChatSessionRepository::new(&db)
+            .create(&session)
+            .await
+            .unwrap();
+        db.close().await.unwrap();
+
+        if !is_supported().await {
+            let error = reencrypt(&path, None, Some("pw")).await.unwrap_err();
+            assert!(error.to_string().contains("sqlcipher"));
+            return;
+        }
+
+        reencrypt(&path, None, Some("it's secret")).await.unwrap();
+        assert!(is_encrypted(&path).unwrap());
+        assert!(DatabaseManager::open_with_passphrase(&path, "wrong")
+            .await
+            .is_err());
+
+        let db = DatabaseManager::open_with_passphrase(&path, "it's secret")
+            .await
+            .unwrap();
+        assert_eq!(ChatSessionRepository::new(&db).count().await.unwrap(), 1);
+        // Backups stay encrypted; snapshots for export and sync don't
+        let backup = dir.path().join("backup.db");
+        MigrationManager::new(db.pool().clone())
+            .backup(&backup)
+            .await
+            .unwrap();
+        assert!(is_encrypted(&backup).unwrap());
+        let snapshot = dir.path().join("snapshot.db");
+        export_sqlite_snapshot(&db, &[session.id], &snapshot)
+            .await
+            .unwrap();
+        assert!(!is_encrypted(&snapshot).unwrap());
+        db.close().await.unwrap();
+
+        reencrypt(&path, Some("it's secret"), Some("new"))
+            .await
+            .unwrap();
+        reencrypt(&path, Some("new"), None).await.unwrap();
+        assert!(!is_encrypted(&path).unwrap());
+        let db = DatabaseManager::new(&path).await.unwrap();
+        assert_eq!(ChatSessionRepository::new(&db).count().await.unwrap(), 1);
+    }
+}
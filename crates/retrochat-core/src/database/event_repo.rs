@@ -0,0 +1,192 @@
+use anyhow::{Context, Result as AnyhowResult};
+use chrono::{DateTime, Utc};
+use sqlx::{sqlite::SqliteRow, Pool, Row, Sqlite};
+use tracing::{debug, warn};
+
+use super::connection::DatabaseManager;
+use crate::models::{Event, EventKind};
+
+/// The append-only change feed in the `events` table
+#[derive(Clone)]
+pub struct EventRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl EventRepository {
+    pub fn new(db: &DatabaseManager) -> Self {
+        Self {
+            pool: db.pool().clone(),
+        }
+    }
+
+    /// Append an event and return its id
+    pub async fn append(
+        &self,
+        kind: EventKind,
+        session_id: Option<&str>,
+        payload: &serde_json::Value,
+    ) -> AnyhowResult<i64> {
+        let id = sqlx::query_scalar(
+            r#"
+            INSERT INTO events (kind, session_id, payload, created_at)
+            VALUES (?, ?, ?, ?)
+            RETURNING id
+            "#,
+        )
+        .bind(kind.to_string())
+        .bind(session_id)
+        .bind(payload.to_string())
+        .bind(Utc::now().to_rfc3339())
+        .fetch_one(&self.pool)
+        .await
+        .with_context(|| format!("Failed to record {kind} event"))?;
+
+        Ok(id)
+    }
+
+    /// Append an event, only logging a failure; the change it describes has
+    /// already been made and shouldn't be reported as failed
+    pub async fn record(
+        &self,
+        kind: EventKind,
+        session_id: Option<&str>,
+        payload: serde_json::Value,
+    ) {
+        if let Err(e) = self.append(kind, session_id, &payload).await {
+            warn!(error = %e, "Failed to append to the event log");
+        }
+    }
+
+    /// Up to `limit` events after `cursor` (an event id, or 0 for the
+    /// start), oldest first
+    pub async fn since(&self, cursor: i64, limit: i64) -> AnyhowResult<Vec<Event>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, kind, session_id, payload, created_at
+            FROM events WHERE id > ? ORDER BY id LIMIT ?
+            "#,
+        )
+        .bind(cursor)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to read events")?;
+
+        rows_to_events(&rows)
+    }
+
+    /// The last `limit` events, oldest first
+    pub async fn recent(&self, limit: i64) -> AnyhowResult<Vec<Event>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM (
+                SELECT id, kind, session_id, payload, created_at
+                FROM events ORDER BY id DESC LIMIT ?
+            ) ORDER BY id
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to read events")?;
+
+        rows_to_events(&rows)
+    }
+
+    /// Id of the newest event, or 0 if there are none; a cursor that skips
+    /// everything recorded so far
+    pub async fn latest_id(&self) -> AnyhowResult<i64> {
+        let id: Option<i64> = sqlx::query_scalar("SELECT MAX(id) FROM events")
+            .fetch_one(&self.pool)
+            .await
+            .context("Failed to read the latest event")?;
+        Ok(id.unwrap_or(0))
+    }
+}
+
+/// Convert rows, skipping kinds added by a newer RetroChat
+fn rows_to_events(rows: &[SqliteRow]) -> AnyhowResult<Vec<Event>> {
+    let mut events = Vec::with_capacity(rows.len());
+    for row in rows {
+        let kind: String = row.try_get("kind")?;
+        let Ok(kind) = kind.parse() else {
+            debug!(kind, "Skipping unknown event kind");
+            continue;
+        };
+        let payload: String = row.try_get("payload")?;
+        let created_at: String = row.try_get("created_at")?;
+        events.push(Event {
+            id: row.try_get("id")?,
+            kind,
+            session_id: row.try_get("session_id")?,
+            payload: serde_json::from_str(&payload).unwrap_or(serde_json::Value::Null),
+            created_at: parse_timestamp(&created_at)?,
+        });
+    }
+    Ok(events)
+}
+
+fn parse_timestamp(value: &str) -> AnyhowResult<DateTime<Utc>> {
+    Ok(DateTime::parse_from_rfc3339(value)
+        .context("Invalid event timestamp")?
+        .with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_append_and_read_with_cursor() {
+        let db = DatabaseManager::open_in_memory().await.unwrap();
+        let repo = EventRepository::new(&db);
+        assert_eq!(repo.latest_id().await.unwrap(), 0);
+
+        let first = repo
+            .append(
+                EventKind::SessionImported,
+                Some("session-1"),
+                &json!({ "provider": "Claude Code" }),
+            )
+            .await
+            .unwrap();
+        repo.record(EventKind::ViewDeleted, None, json!({ "name": "old" }))
+            .await;
+        // Written by a newer build
+        sqlx::query(
+            "INSERT INTO events (kind, payload, created_at) VALUES ('from_the_future', '{}', ?)",
+        )
+        .bind(Utc::now().to_rfc3339())
+        .execute(db.pool())
+        .await
+        .unwrap();
+        let last = repo
+            .append(EventKind::TopicsRefreshed, None, &json!({}))
+            .await
+            .unwrap();
+        assert_eq!(repo.latest_id().await.unwrap(), last);
+
+        let all = repo.since(0, 100).await.unwrap();
+        let kinds: Vec<EventKind> = all.iter().map(|e| e.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                EventKind::SessionImported,
+                EventKind::ViewDeleted,
+                EventKind::TopicsRefreshed
+            ]
+        );
+        assert_eq!(all[0].id, first);
+        assert_eq!(all[0].session_id.as_deref(), Some("session-1"));
+        assert_eq!(all[0].payload["provider"], "Claude Code");
+
+        let after_first = repo.since(first, 1).await.unwrap();
+        assert_eq!(after_first.len(), 1);
+        assert_eq!(after_first[0].kind, EventKind::ViewDeleted);
+        assert!(repo.since(last, 100).await.unwrap().is_empty());
+
+        let recent = repo.recent(2).await.unwrap();
+        assert_eq!(recent.last().unwrap().id, last);
+    }
+}
@@ -0,0 +1,275 @@
+use anyhow::{Context, Result as AnyhowResult};
+use chrono::{DateTime, Utc};
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Pool, Row, Sqlite};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use super::connection::DatabaseManager;
+use crate::models::{CohortStats, Experiment, ExperimentSession};
+
+pub struct ExperimentRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl ExperimentRepository {
+    pub fn new(db: &DatabaseManager) -> Self {
+        Self {
+            pool: db.pool().clone(),
+        }
+    }
+
+    pub async fn create(&self, experiment: &Experiment) -> AnyhowResult<()> {
+        sqlx::query("INSERT INTO experiments (name, description, created_at) VALUES (?, ?, ?)")
+            .bind(&experiment.name)
+            .bind(&experiment.description)
+            .bind(experiment.created_at.to_rfc3339())
+            .execute(&self.pool)
+            .await
+            .context("Failed to create experiment")?;
+        Ok(())
+    }
+
+    pub async fn get(&self, name: &str) -> AnyhowResult<Option<Experiment>> {
+        let row =
+            sqlx::query("SELECT name, description, created_at FROM experiments WHERE name = ?")
+                .bind(name)
+                .fetch_optional(&self.pool)
+                .await
+                .context("Failed to fetch experiment")?;
+
+        row.as_ref().map(row_to_experiment).transpose()
+    }
+
+    /// All experiments with their session counts, newest first
+    pub async fn list(&self) -> AnyhowResult<Vec<(Experiment, i64)>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT e.name, e.description, e.created_at, COUNT(s.session_id) AS sessions
+            FROM experiments e
+            LEFT JOIN experiment_sessions s ON s.experiment_name = e.name
+            GROUP BY e.name
+            ORDER BY e.created_at DESC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list experiments")?;
+
+        rows.iter()
+            .map(|row| Ok((row_to_experiment(row)?, row.try_get("sessions")?)))
+            .collect()
+    }
+
+    /// Delete an experiment and its cohort assignments
+    pub async fn delete(&self, name: &str) -> AnyhowResult<bool> {
+        let result = sqlx::query("DELETE FROM experiments WHERE name = ?")
+            .bind(name)
+            .execute(&self.pool)
+            .await
+            .context("Failed to delete experiment")?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Put sessions into a cohort, moving them out of any other cohort of the
+    /// same experiment
+    pub async fn add_sessions(
+        &self,
+        name: &str,
+        cohort: &str,
+        session_ids: &[Uuid],
+    ) -> AnyhowResult<u64> {
+        let added_at = Utc::now().to_rfc3339();
+        let mut added = 0;
+        let mut tx = self.pool.begin().await?;
+        for session_id in session_ids {
+            let result = sqlx::query(
+                r#"
+                INSERT INTO experiment_sessions (experiment_name, session_id, cohort, added_at)
+                VALUES (?, ?, ?, ?)
+                ON CONFLICT (experiment_name, session_id) DO UPDATE
+                SET cohort = excluded.cohort, added_at = excluded.added_at
+                WHERE cohort != excluded.cohort
+                "#,
+            )
+            .bind(name)
+            .bind(session_id.to_string())
+            .bind(cohort)
+            .bind(&added_at)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to add session to experiment")?;
+            added += result.rows_affected();
+        }
+        tx.commit().await?;
+        Ok(added)
+    }
+
+    pub async fn remove_sessions(&self, name: &str, session_ids: &[Uuid]) -> AnyhowResult<u64> {
+        let mut removed = 0;
+        let mut tx = self.pool.begin().await?;
+        for session_id in session_ids {
+            let result = sqlx::query(
+                "DELETE FROM experiment_sessions WHERE experiment_name = ? AND session_id = ?",
+            )
+            .bind(name)
+            .bind(session_id.to_string())
+            .execute(&mut *tx)
+            .await
+            .context("Failed to remove session from experiment")?;
+            removed += result.rows_affected();
+        }
+        tx.commit().await?;
+        Ok(removed)
+    }
+
+    /// Sessions of an experiment by cohort, oldest additions first
+    pub async fn get_sessions(&self, name: &str) -> AnyhowResult<Vec<ExperimentSession>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT experiment_name, session_id, cohort, added_at
+            FROM experiment_sessions
+            WHERE experiment_name = ?
+            ORDER BY cohort, added_at, session_id
+            "#,
+        )
+        .bind(name)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch experiment sessions")?;
+
+        rows.iter().map(row_to_experiment_session).collect()
+    }
+
+    /// Aggregate metrics of each cohort, by cohort name
+    pub async fn cohort_stats(&self, name: &str) -> AnyhowResult<Vec<CohortStats>> {
+        let mut by_cohort: BTreeMap<String, CohortStats> = BTreeMap::new();
+
+        let rows = sqlx::query(
+            r#"
+            SELECT e.cohort, COUNT(*) AS sessions, SUM(s.message_count) AS messages,
+                   SUM(COALESCE(s.token_count, 0)) AS tokens,
+                   COUNT(s.duration_seconds) AS timed_sessions,
+                   COALESCE(SUM(s.duration_seconds), 0) AS duration_seconds
+            FROM experiment_sessions e
+            JOIN chat_sessions s ON s.id = e.session_id
+            WHERE e.experiment_name = ?
+            GROUP BY e.cohort
+            "#,
+        )
+        .bind(name)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to aggregate cohort sessions")?;
+        for row in rows {
+            let cohort: String = row.try_get("cohort")?;
+            by_cohort.insert(
+                cohort.clone(),
+                CohortStats {
+                    cohort,
+                    sessions: row.try_get("sessions")?,
+                    messages: row.try_get("messages")?,
+                    tokens: row.try_get("tokens")?,
+                    timed_sessions: row.try_get("timed_sessions")?,
+                    duration_seconds: row.try_get("duration_seconds")?,
+                    ..Default::default()
+                },
+            );
+        }
+
+        let rows = sqlx::query(
+            r#"
+            SELECT e.cohort, s.provider, COUNT(*) AS sessions
+            FROM experiment_sessions e
+            JOIN chat_sessions s ON s.id = e.session_id
+            WHERE e.experiment_name = ?
+            GROUP BY e.cohort, s.provider
+            ORDER BY sessions DESC, s.provider
+            "#,
+        )
+        .bind(name)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to count cohort providers")?;
+        for row in rows {
+            let cohort: String = row.try_get("cohort")?;
+            if let Some(stats) = by_cohort.get_mut(&cohort) {
+                stats
+                    .providers
+                    .push((row.try_get("provider")?, row.try_get("sessions")?));
+            }
+        }
+
+        let rows = sqlx::query(
+            r#"
+            SELECT e.cohort, COUNT(DISTINCT t.id) AS tool_uses,
+                   COUNT(DISTINCT CASE WHEN t.success = 0 THEN t.id END) AS failed
+            FROM experiment_sessions e
+            JOIN messages m ON m.session_id = e.session_id
+            JOIN tool_operations t ON t.id = m.tool_operation_id
+            WHERE e.experiment_name = ?
+            GROUP BY e.cohort
+            "#,
+        )
+        .bind(name)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to count cohort tool uses")?;
+        for row in rows {
+            let cohort: String = row.try_get("cohort")?;
+            if let Some(stats) = by_cohort.get_mut(&cohort) {
+                stats.tool_uses = row.try_get("tool_uses")?;
+                stats.failed_tool_uses = row.try_get("failed")?;
+            }
+        }
+
+        let rows = sqlx::query(
+            r#"
+            SELECT e.cohort, COUNT(*) AS summarized,
+                   COALESCE(SUM(ss.outcome = 'completed'), 0) AS completed
+            FROM experiment_sessions e
+            JOIN session_summaries ss ON ss.session_id = e.session_id
+            WHERE e.experiment_name = ? AND ss.outcome IS NOT NULL
+            GROUP BY e.cohort
+            "#,
+        )
+        .bind(name)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to count cohort outcomes")?;
+        for row in rows {
+            let cohort: String = row.try_get("cohort")?;
+            if let Some(stats) = by_cohort.get_mut(&cohort) {
+                stats.summarized = row.try_get("summarized")?;
+                stats.completed = row.try_get("completed")?;
+            }
+        }
+
+        Ok(by_cohort.into_values().collect())
+    }
+}
+
+fn parse_time(row: &SqliteRow, column: &str) -> AnyhowResult<DateTime<Utc>> {
+    let value: String = row.try_get(column)?;
+    Ok(DateTime::parse_from_rfc3339(&value)
+        .with_context(|| format!("Invalid {column} format"))?
+        .with_timezone(&Utc))
+}
+
+fn row_to_experiment(row: &SqliteRow) -> AnyhowResult<Experiment> {
+    Ok(Experiment {
+        name: row.try_get("name")?,
+        description: row.try_get("description")?,
+        created_at: parse_time(row, "created_at")?,
+    })
+}
+
+fn row_to_experiment_session(row: &SqliteRow) -> AnyhowResult<ExperimentSession> {
+    let session_id: String = row.try_get("session_id")?;
+    Ok(ExperimentSession {
+        experiment_name: row.try_get("experiment_name")?,
+        session_id: Uuid::parse_str(&session_id).context("Invalid session ID")?,
+        cohort: row.try_get("cohort")?,
+        added_at: parse_time(row, "added_at")?,
+    })
+}
@@ -0,0 +1,193 @@
+use anyhow::{Context, Result as AnyhowResult};
+use chrono::{DateTime, Utc};
+use sqlx::{sqlite::SqliteRow, Pool, Row, Sqlite};
+use uuid::Uuid;
+
+use super::connection::DatabaseManager;
+use crate::models::ImportError;
+
+pub struct ImportErrorRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl ImportErrorRepository {
+    pub fn new(db: &DatabaseManager) -> Self {
+        Self {
+            pool: db.pool().clone(),
+        }
+    }
+
+    /// Record a failed import attempt for a file
+    ///
+    /// The first failure inserts a row; later failures bump the attempt count
+    /// and replace the error message and last attempt time.
+    pub async fn record_failure(
+        &self,
+        file_path: &str,
+        provider: Option<&str>,
+        error_message: &str,
+    ) -> AnyhowResult<()> {
+        let now = Utc::now().to_rfc3339();
+
+        sqlx::query(
+            r#"
+            INSERT INTO import_errors (
+                id, file_path, provider, error_message,
+                attempt_count, first_attempt_at, last_attempt_at
+            ) VALUES (?, ?, ?, ?, 1, ?, ?)
+            ON CONFLICT(file_path) DO UPDATE SET
+                provider = COALESCE(excluded.provider, import_errors.provider),
+                error_message = excluded.error_message,
+                attempt_count = import_errors.attempt_count + 1,
+                last_attempt_at = excluded.last_attempt_at
+            "#,
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(file_path)
+        .bind(provider)
+        .bind(error_message)
+        .bind(&now)
+        .bind(&now)
+        .execute(&self.pool)
+        .await
+        .context("Failed to record import error")?;
+
+        Ok(())
+    }
+
+    /// Remove the ledger entry for a file that has since imported cleanly
+    pub async fn clear(&self, file_path: &str) -> AnyhowResult<bool> {
+        let result = sqlx::query("DELETE FROM import_errors WHERE file_path = ?")
+            .bind(file_path)
+            .execute(&self.pool)
+            .await
+            .context("Failed to clear import error")?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn get_by_file_path(&self, file_path: &str) -> AnyhowResult<Option<ImportError>> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, file_path, provider, error_message,
+                   attempt_count, first_attempt_at, last_attempt_at
+            FROM import_errors WHERE file_path = ?
+            "#,
+        )
+        .bind(file_path)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to fetch import error")?;
+
+        row.map(|row| self.row_to_import_error(&row)).transpose()
+    }
+
+    /// List recorded failures, most recent attempt first
+    pub async fn list(&self, limit: Option<i64>) -> AnyhowResult<Vec<ImportError>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, file_path, provider, error_message,
+                   attempt_count, first_attempt_at, last_attempt_at
+            FROM import_errors
+            ORDER BY last_attempt_at DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(limit.unwrap_or(-1))
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list import errors")?;
+
+        rows.iter()
+            .map(|row| self.row_to_import_error(row))
+            .collect()
+    }
+
+    pub async fn count(&self) -> AnyhowResult<i64> {
+        let row = sqlx::query("SELECT COUNT(*) as count FROM import_errors")
+            .fetch_one(&self.pool)
+            .await
+            .context("Failed to count import errors")?;
+
+        Ok(row.try_get("count")?)
+    }
+
+    fn row_to_import_error(&self, row: &SqliteRow) -> AnyhowResult<ImportError> {
+        let id_str: String = row.try_get("id")?;
+        let file_path: String = row.try_get("file_path")?;
+        let provider: Option<String> = row.try_get("provider")?;
+        let error_message: String = row.try_get("error_message")?;
+        let attempt_count: i64 = row.try_get("attempt_count")?;
+        let first_attempt_at_str: String = row.try_get("first_attempt_at")?;
+        let last_attempt_at_str: String = row.try_get("last_attempt_at")?;
+
+        let id = Uuid::parse_str(&id_str).context("Invalid import error ID format")?;
+
+        let first_attempt_at = DateTime::parse_from_rfc3339(&first_attempt_at_str)
+            .context("Invalid first_attempt_at timestamp format")?
+            .with_timezone(&Utc);
+
+        let last_attempt_at = DateTime::parse_from_rfc3339(&last_attempt_at_str)
+            .context("Invalid last_attempt_at timestamp format")?
+            .with_timezone(&Utc);
+
+        Ok(ImportError {
+            id,
+            file_path,
+            provider,
+            error_message,
+            attempt_count: attempt_count as u32,
+            first_attempt_at,
+            last_attempt_at,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_record_failure_upserts_by_file_path() {
+        let db = DatabaseManager::open_in_memory().await.unwrap();
+        let repo = ImportErrorRepository::new(&db);
+
+        repo.record_failure("/tmp/a.jsonl", Some("Claude Code"), "unexpected EOF")
+            .await
+            .unwrap();
+        repo.record_failure("/tmp/a.jsonl", None, "invalid JSON at line 3")
+            .await
+            .unwrap();
+
+        let error = repo
+            .get_by_file_path("/tmp/a.jsonl")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(error.attempt_count, 2);
+        assert_eq!(error.provider.as_deref(), Some("Claude Code"));
+        assert_eq!(error.error_message, "invalid JSON at line 3");
+        assert!(error.last_attempt_at >= error.first_attempt_at);
+        assert_eq!(repo.count().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_clear_removes_entry() {
+        let db = DatabaseManager::open_in_memory().await.unwrap();
+        let repo = ImportErrorRepository::new(&db);
+
+        repo.record_failure("/tmp/a.jsonl", None, "boom")
+            .await
+            .unwrap();
+        repo.record_failure("/tmp/b.jsonl", None, "boom")
+            .await
+            .unwrap();
+
+        assert!(repo.clear("/tmp/a.jsonl").await.unwrap());
+        assert!(!repo.clear("/tmp/a.jsonl").await.unwrap());
+
+        let remaining = repo.list(None).await.unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].file_path, "/tmp/b.jsonl");
+    }
+}
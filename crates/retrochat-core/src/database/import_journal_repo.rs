@@ -0,0 +1,70 @@
+use anyhow::{Context, Result as AnyhowResult};
+use chrono::Utc;
+use sqlx::{Pool, Row, Sqlite};
+
+use super::connection::DatabaseManager;
+
+/// A file whose import started but has not finished
+#[derive(Debug, Clone)]
+pub struct JournalEntry {
+    pub file_path: String,
+    pub provider: Option<String>,
+}
+
+/// Files being imported, so an interrupted import can be resumed
+pub struct ImportJournalRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl ImportJournalRepository {
+    pub fn new(db: &DatabaseManager) -> Self {
+        Self {
+            pool: db.pool().clone(),
+        }
+    }
+
+    /// Record that a file's sessions are about to be written
+    pub async fn begin(&self, file_path: &str, provider: Option<&str>) -> AnyhowResult<()> {
+        sqlx::query(
+            "INSERT OR REPLACE INTO import_journal (file_path, provider, started_at) VALUES (?, ?, ?)",
+        )
+        .bind(file_path)
+        .bind(provider)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .context("Failed to record import start")?;
+
+        Ok(())
+    }
+
+    /// Record that every session of a file has been written
+    pub async fn finish(&self, file_path: &str) -> AnyhowResult<()> {
+        sqlx::query("DELETE FROM import_journal WHERE file_path = ?")
+            .bind(file_path)
+            .execute(&self.pool)
+            .await
+            .context("Failed to record import end")?;
+
+        Ok(())
+    }
+
+    /// Files whose import was interrupted, oldest first
+    pub async fn list(&self) -> AnyhowResult<Vec<JournalEntry>> {
+        let rows = sqlx::query(
+            "SELECT file_path, provider FROM import_journal ORDER BY started_at, file_path",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list interrupted imports")?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(JournalEntry {
+                    file_path: row.try_get("file_path")?,
+                    provider: row.try_get("provider")?,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,181 @@
+use anyhow::{Context, Result as AnyhowResult};
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Row, Sqlite};
+
+use super::connection::DatabaseManager;
+
+pub struct LlmCacheRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl LlmCacheRepository {
+    pub fn new(db: &DatabaseManager) -> Self {
+        Self {
+            pool: db.pool().clone(),
+        }
+    }
+
+    /// Cached response for `key`, if it was stored at or after `not_before`
+    ///
+    /// A hit refreshes the entry's `last_used_at` so it survives eviction.
+    pub async fn get(&self, key: &str, not_before: DateTime<Utc>) -> AnyhowResult<Option<String>> {
+        let row = sqlx::query("SELECT response FROM llm_cache WHERE key = ? AND created_at >= ?")
+            .bind(key)
+            .bind(not_before.to_rfc3339())
+            .fetch_optional(&self.pool)
+            .await
+            .context("Failed to read LLM cache")?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        sqlx::query("UPDATE llm_cache SET last_used_at = ? WHERE key = ?")
+            .bind(Utc::now().to_rfc3339())
+            .bind(key)
+            .execute(&self.pool)
+            .await
+            .context("Failed to touch LLM cache entry")?;
+
+        Ok(Some(row.try_get("response")?))
+    }
+
+    /// Store a response, replacing any earlier entry for the same key
+    pub async fn put(
+        &self,
+        key: &str,
+        provider: &str,
+        model: &str,
+        response: &str,
+    ) -> AnyhowResult<()> {
+        let now = Utc::now().to_rfc3339();
+
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO llm_cache (key, provider, model, response, size_bytes, created_at, last_used_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(key)
+        .bind(provider)
+        .bind(model)
+        .bind(response)
+        .bind(response.len() as i64)
+        .bind(&now)
+        .bind(&now)
+        .execute(&self.pool)
+        .await
+        .context("Failed to write LLM cache entry")?;
+
+        Ok(())
+    }
+
+    /// Drop entries stored before `expired_before`, then evict the least
+    /// recently used entries until the cache fits in `max_size_bytes`
+    ///
+    /// Returns the number of entries removed.
+    pub async fn prune(
+        &self,
+        expired_before: DateTime<Utc>,
+        max_size_bytes: u64,
+    ) -> AnyhowResult<u64> {
+        let expired = sqlx::query("DELETE FROM llm_cache WHERE created_at < ?")
+            .bind(expired_before.to_rfc3339())
+            .execute(&self.pool)
+            .await
+            .context("Failed to expire LLM cache entries")?
+            .rows_affected();
+
+        let evicted = sqlx::query(
+            r#"
+            DELETE FROM llm_cache WHERE key IN (
+                SELECT key FROM (
+                    SELECT key, SUM(size_bytes) OVER (ORDER BY last_used_at DESC, key) AS running_size
+                    FROM llm_cache
+                )
+                WHERE running_size > ?
+            )
+            "#,
+        )
+        .bind(max_size_bytes as i64)
+        .execute(&self.pool)
+        .await
+        .context("Failed to evict LLM cache entries")?
+        .rows_affected();
+
+        Ok(expired + evicted)
+    }
+
+    /// Number of entries and their total size in bytes
+    pub async fn stats(&self) -> AnyhowResult<(u64, u64)> {
+        let row = sqlx::query(
+            "SELECT COUNT(*) AS entries, COALESCE(SUM(size_bytes), 0) AS size FROM llm_cache",
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to read LLM cache stats")?;
+
+        Ok((
+            row.try_get::<i64, _>("entries")? as u64,
+            row.try_get::<i64, _>("size")? as u64,
+        ))
+    }
+
+    pub async fn clear(&self) -> AnyhowResult<u64> {
+        let result = sqlx::query("DELETE FROM llm_cache")
+            .execute(&self.pool)
+            .await
+            .context("Failed to clear LLM cache")?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[tokio::test]
+    async fn test_get_respects_ttl() {
+        let db = DatabaseManager::open_in_memory().await.unwrap();
+        let repo = LlmCacheRepository::new(&db);
+
+        repo.put("k", "claude-code", "sonnet", "{}").await.unwrap();
+
+        let an_hour_ago = Utc::now() - Duration::hours(1);
+        assert_eq!(
+            repo.get("k", an_hour_ago).await.unwrap().as_deref(),
+            Some("{}")
+        );
+        let in_an_hour = Utc::now() + Duration::hours(1);
+        assert!(repo.get("k", in_an_hour).await.unwrap().is_none());
+        assert!(repo.get("missing", an_hour_ago).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_prune_evicts_least_recently_used() {
+        let db = DatabaseManager::open_in_memory().await.unwrap();
+        let repo = LlmCacheRepository::new(&db);
+        let long_ago = Utc::now() - Duration::days(1);
+
+        for key in ["a", "b", "c"] {
+            repo.put(key, "p", "m", "0123456789").await.unwrap();
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        }
+        // "a" is the oldest entry but was just used
+        repo.get("a", long_ago).await.unwrap();
+
+        let removed = repo.prune(long_ago, 20).await.unwrap();
+        assert_eq!(removed, 1);
+        assert!(repo.get("b", long_ago).await.unwrap().is_none());
+        assert!(repo.get("a", long_ago).await.unwrap().is_some());
+        assert_eq!(repo.stats().await.unwrap(), (2, 20));
+
+        // Everything is older than a cutoff in the future
+        repo.prune(Utc::now() + Duration::hours(1), 1_000)
+            .await
+            .unwrap();
+        assert_eq!(repo.stats().await.unwrap(), (0, 0));
+    }
+}
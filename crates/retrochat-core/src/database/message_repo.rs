@@ -1,11 +1,79 @@
 use anyhow::{Context, Result as AnyhowResult};
 use chrono::{DateTime, Utc};
-use sqlx::{sqlite::SqliteRow, Pool, Row, Sqlite};
+use futures::TryStreamExt;
+use sqlx::query::Query;
+use sqlx::sqlite::{SqliteArguments, SqliteRow};
+use sqlx::{Pool, Row, Sqlite};
 use std::str::FromStr;
 use uuid::Uuid;
 
 use super::connection::DatabaseManager;
 use crate::models::message::{Message, MessageRole};
+use crate::models::RevisionSource;
+
+/// Reported and estimated tokens of one provider's messages, over the
+/// messages that have both
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenAccuracy {
+    pub provider: String,
+    pub messages: i64,
+    pub reported_tokens: i64,
+    pub estimated_tokens: i64,
+    /// Sum of per-message |estimated - reported|
+    pub absolute_error: i64,
+}
+
+impl TokenAccuracy {
+    /// Estimated total as a share of the reported total (1.0 is exact)
+    pub fn ratio(&self) -> f64 {
+        if self.reported_tokens == 0 {
+            return 0.0;
+        }
+        self.estimated_tokens as f64 / self.reported_tokens as f64
+    }
+
+    /// Per-message error as a percentage of reported tokens
+    pub fn error_percentage(&self) -> f64 {
+        if self.reported_tokens == 0 {
+            return 0.0;
+        }
+        self.absolute_error as f64 / self.reported_tokens as f64 * 100.0
+    }
+}
+
+/// How often a slash command or hook fired, and how its sessions ended
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkflowStats {
+    /// "command" or "hook"
+    pub kind: String,
+    /// Command ("/compact") or hook event ("PostToolUse")
+    pub name: String,
+    pub fired: i64,
+    /// Hook runs that failed or blocked; always 0 for commands
+    pub failures: i64,
+    pub sessions: i64,
+    /// Sessions with a summary, and so a known outcome
+    pub summarized_sessions: i64,
+    pub completed_sessions: i64,
+}
+
+impl WorkflowStats {
+    /// Share of summarized sessions that were completed
+    pub fn completion_rate(&self) -> Option<f64> {
+        if self.summarized_sessions == 0 {
+            return None;
+        }
+        Some(self.completed_sessions as f64 / self.summarized_sessions as f64)
+    }
+}
+
+fn metadata_json(message: &Message) -> String {
+    message
+        .metadata
+        .as_ref()
+        .map(|metadata| metadata.to_string())
+        .unwrap_or_else(|| "{}".to_string())
+}
 
 pub struct MessageRepository {
     pool: Pool<Sqlite>,
@@ -22,9 +90,9 @@ impl MessageRepository {
         sqlx::query(
             r#"
             INSERT INTO messages (
-                id, session_id, role, content, timestamp, token_count,
+                id, session_id, role, content, timestamp, token_count, estimated_tokens,
                 metadata, sequence_number, message_type, tool_operation_id
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(message.id.to_string())
@@ -33,7 +101,8 @@ impl MessageRepository {
         .bind(&message.content)
         .bind(message.timestamp.to_rfc3339())
         .bind(message.token_count)
-        .bind("{}") // metadata
+        .bind(message.estimated_tokens)
+        .bind(metadata_json(message))
         .bind(message.sequence_number)
         .bind(message.message_type.to_string())
         .bind(message.tool_operation_id.map(|id| id.to_string()))
@@ -47,7 +116,7 @@ impl MessageRepository {
     pub async fn get_by_id(&self, id: &Uuid) -> AnyhowResult<Option<Message>> {
         let row = sqlx::query(
             r#"
-            SELECT id, session_id, role, content, timestamp, token_count,
+            SELECT id, session_id, role, content, timestamp, token_count, estimated_tokens,
                    metadata, sequence_number, message_type, tool_operation_id
             FROM messages
             WHERE id = ?
@@ -70,7 +139,7 @@ impl MessageRepository {
     pub async fn get_by_session_id(&self, session_id: &Uuid) -> AnyhowResult<Vec<Message>> {
         let rows = sqlx::query(
             r#"
-            SELECT id, session_id, role, content, timestamp, token_count,
+            SELECT id, session_id, role, content, timestamp, token_count, estimated_tokens,
                    metadata, sequence_number, message_type, tool_operation_id
             FROM messages
             WHERE session_id = ?
@@ -91,11 +160,58 @@ impl MessageRepository {
         Ok(messages)
     }
 
+    /// First message of a session by sequence number
+    pub async fn get_first_by_session(&self, session_id: &Uuid) -> AnyhowResult<Option<Message>> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, session_id, role, content, timestamp, token_count, estimated_tokens,
+                   metadata, sequence_number, message_type, tool_operation_id
+            FROM messages
+            WHERE session_id = ?
+            ORDER BY sequence_number ASC
+            LIMIT 1
+            "#,
+        )
+        .bind(session_id.to_string())
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to fetch first message of session")?;
+
+        row.map(|row| self.row_to_message(&row)).transpose()
+    }
+
     // Alias for backward compatibility
     pub async fn get_by_session(&self, session_id: &Uuid) -> AnyhowResult<Vec<Message>> {
         self.get_by_session_id(session_id).await
     }
 
+    /// One page of a session's messages, in sequence order
+    pub async fn get_page_by_session(
+        &self,
+        session_id: &Uuid,
+        offset: i64,
+        limit: i64,
+    ) -> AnyhowResult<Vec<Message>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, session_id, role, content, timestamp, token_count, estimated_tokens,
+                   metadata, sequence_number, message_type, tool_operation_id
+            FROM messages
+            WHERE session_id = ?
+            ORDER BY sequence_number ASC
+            LIMIT ? OFFSET ?
+            "#,
+        )
+        .bind(session_id.to_string())
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch page of session messages")?;
+
+        rows.iter().map(|row| self.row_to_message(row)).collect()
+    }
+
     pub async fn search_content(
         &self,
         query: &str,
@@ -106,7 +222,7 @@ impl MessageRepository {
         let rows = sqlx::query(
             r#"
             SELECT m.id, m.session_id, m.role, m.content, m.timestamp,
-                   m.token_count, m.metadata, m.sequence_number,
+                   m.token_count, m.estimated_tokens, m.metadata, m.sequence_number,
                    m.message_type, m.tool_operation_id
             FROM messages m
             JOIN messages_fts fts ON m.rowid = fts.rowid
@@ -141,7 +257,7 @@ impl MessageRepository {
 
         let mut sql = r#"
             SELECT m.id, m.session_id, m.role, m.content, m.timestamp,
-                   m.token_count, m.metadata, m.sequence_number,
+                   m.token_count, m.estimated_tokens, m.metadata, m.sequence_number,
                    m.message_type, m.tool_operation_id
             FROM messages m
             JOIN messages_fts fts ON m.rowid = fts.rowid
@@ -196,7 +312,7 @@ impl MessageRepository {
 
         let mut sql = r#"
             SELECT m.id, m.session_id, m.role, m.content, m.timestamp,
-                   m.token_count, m.metadata, m.sequence_number,
+                   m.token_count, m.estimated_tokens, m.metadata, m.sequence_number,
                    m.message_type, m.tool_operation_id
             FROM messages m
             JOIN messages_fts fts ON m.rowid = fts.rowid
@@ -287,15 +403,89 @@ impl MessageRepository {
         limit: Option<i64>,
         reverse: bool,
     ) -> AnyhowResult<Vec<Message>> {
+        let limit = limit.map(|limit| limit.max(0) as usize);
+        let mut messages = Vec::new();
+        if limit == Some(0) {
+            return Ok(messages);
+        }
+
+        self.for_each_in_time_range(from, to, provider, role, reverse, |message| {
+            messages.push(message);
+            Ok(limit.is_none_or(|limit| messages.len() < limit))
+        })
+        .await?;
+
+        Ok(messages)
+    }
+
+    /// Hand messages in a time range to `visit` one at a time, as they are read
+    ///
+    /// Used by exports too large to hold in memory. Reading stops when `visit`
+    /// returns `false`.
+    pub async fn for_each_in_time_range<F>(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        provider: Option<&str>,
+        role: Option<&str>,
+        reverse: bool,
+        mut visit: F,
+    ) -> AnyhowResult<()>
+    where
+        F: FnMut(Message) -> AnyhowResult<bool>,
+    {
         let mut sql = String::from(
             r#"
             SELECT m.id, m.session_id, m.role, m.content, m.timestamp,
-                   m.token_count, m.metadata, m.sequence_number,
+                   m.token_count, m.estimated_tokens, m.metadata, m.sequence_number,
                    m.message_type, m.tool_operation_id
             FROM messages m
             "#,
         );
+        sql.push_str(&Self::time_range_conditions(from, to, provider, role));
+        sql.push_str(" ORDER BY m.timestamp ");
+        sql.push_str(if reverse { "DESC" } else { "ASC" });
+
+        let query = Self::bind_time_range(sqlx::query(&sql), from, to, provider, role);
+        let mut rows = query.fetch(&self.pool);
+        while let Some(row) = rows
+            .try_next()
+            .await
+            .context("Failed to fetch messages by time range")?
+        {
+            if !visit(self.row_to_message(&row)?)? {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Number of messages in a time range
+    pub async fn count_by_time_range(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        provider: Option<&str>,
+        role: Option<&str>,
+    ) -> AnyhowResult<i64> {
+        let mut sql = String::from("SELECT COUNT(*) FROM messages m");
+        sql.push_str(&Self::time_range_conditions(from, to, provider, role));
+
+        let row = Self::bind_time_range(sqlx::query(&sql), from, to, provider, role)
+            .fetch_one(&self.pool)
+            .await
+            .context("Failed to count messages by time range")?;
 
+        Ok(row.try_get(0)?)
+    }
+
+    fn time_range_conditions(
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        provider: Option<&str>,
+        role: Option<&str>,
+    ) -> String {
         let mut conditions = Vec::new();
 
         if from.is_some() {
@@ -319,20 +509,20 @@ impl MessageRepository {
             conditions.push("m.role = ?");
         }
 
-        if !conditions.is_empty() {
-            sql.push_str(" WHERE ");
-            sql.push_str(&conditions.join(" AND "));
-        }
-
-        sql.push_str(" ORDER BY m.timestamp ");
-        sql.push_str(if reverse { "DESC" } else { "ASC" });
-
-        if let Some(lim) = limit {
-            sql.push_str(&format!(" LIMIT {lim}"));
+        if conditions.is_empty() {
+            String::new()
+        } else {
+            format!(" WHERE {}", conditions.join(" AND "))
         }
+    }
 
-        let mut query_builder = sqlx::query(&sql);
-
+    fn bind_time_range<'q>(
+        mut query_builder: Query<'q, Sqlite, SqliteArguments<'q>>,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        provider: Option<&'q str>,
+        role: Option<&'q str>,
+    ) -> Query<'q, Sqlite, SqliteArguments<'q>> {
         if let Some(from_time) = from {
             query_builder = query_builder.bind(from_time.to_rfc3339());
         }
@@ -349,10 +539,41 @@ impl MessageRepository {
             query_builder = query_builder.bind(r);
         }
 
+        query_builder
+    }
+
+    /// Get plain user prompts (no tool results or slash commands), newest first
+    ///
+    /// When `before` is set, only prompts with a timestamp strictly before it are returned.
+    pub async fn get_user_prompts(
+        &self,
+        before: Option<DateTime<Utc>>,
+        limit: i64,
+    ) -> AnyhowResult<Vec<Message>> {
+        let mut sql = String::from(
+            r#"
+            SELECT id, session_id, role, content, timestamp, token_count, estimated_tokens,
+                   metadata, sequence_number, message_type, tool_operation_id
+            FROM messages
+            WHERE role = 'User' AND message_type = 'simple_message'
+            "#,
+        );
+
+        if before.is_some() {
+            sql.push_str(" AND timestamp < ?");
+        }
+        sql.push_str(" ORDER BY timestamp DESC LIMIT ?");
+
+        let mut query_builder = sqlx::query(&sql);
+        if let Some(before_time) = before {
+            query_builder = query_builder.bind(before_time.to_rfc3339());
+        }
+
         let rows = query_builder
+            .bind(limit)
             .fetch_all(&self.pool)
             .await
-            .context("Failed to fetch messages by time range")?;
+            .context("Failed to fetch user prompts")?;
 
         let mut messages = Vec::new();
         for row in rows {
@@ -379,9 +600,9 @@ impl MessageRepository {
             sqlx::query(
                 r#"
                 INSERT INTO messages (
-                    id, session_id, role, content, timestamp, token_count,
+                    id, session_id, role, content, timestamp, token_count, estimated_tokens,
                     metadata, sequence_number, message_type, tool_operation_id
-                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
                 "#,
             )
             .bind(message.id.to_string())
@@ -390,7 +611,8 @@ impl MessageRepository {
             .bind(&message.content)
             .bind(message.timestamp.to_rfc3339())
             .bind(message.token_count)
-            .bind("{}") // metadata
+            .bind(message.estimated_tokens)
+            .bind(metadata_json(message))
             .bind(message.sequence_number)
             .bind(message.message_type.to_string())
             .bind(message.tool_operation_id.map(|id| id.to_string()))
@@ -405,6 +627,170 @@ impl MessageRepository {
         Ok(())
     }
 
+    /// Store token estimates, given as (message ID, estimate) pairs
+    pub async fn set_estimated_tokens(&self, estimates: &[(Uuid, u32)]) -> AnyhowResult<()> {
+        if estimates.is_empty() {
+            return Ok(());
+        }
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        for (id, estimate) in estimates {
+            sqlx::query("UPDATE messages SET estimated_tokens = ? WHERE id = ?")
+                .bind(estimate)
+                .bind(id.to_string())
+                .execute(&mut *tx)
+                .await
+                .context("Failed to store token estimate")?;
+        }
+
+        tx.commit()
+            .await
+            .context("Failed to commit token estimates")?;
+        Ok(())
+    }
+
+    /// Change message roles, given as (message ID, role, reason) triples,
+    /// recording each change in `message_revisions`
+    pub async fn set_roles(
+        &self,
+        roles: &[(Uuid, MessageRole, String)],
+        source: RevisionSource,
+    ) -> AnyhowResult<()> {
+        if roles.is_empty() {
+            return Ok(());
+        }
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+        let now = Utc::now().to_rfc3339();
+
+        for (id, role, reason) in roles {
+            sqlx::query(
+                r#"
+                INSERT INTO message_revisions (
+                    message_id, field, before_value, after_value, source, reason, created_at
+                )
+                SELECT id, 'role', role, ?, ?, ?, ? FROM messages WHERE id = ? AND role != ?
+                "#,
+            )
+            .bind(role.to_string())
+            .bind(source.to_string())
+            .bind(reason)
+            .bind(&now)
+            .bind(id.to_string())
+            .bind(role.to_string())
+            .execute(&mut *tx)
+            .await
+            .context("Failed to record message revision")?;
+
+            sqlx::query("UPDATE messages SET role = ? WHERE id = ?")
+                .bind(role.to_string())
+                .bind(id.to_string())
+                .execute(&mut *tx)
+                .await
+                .context("Failed to update message role")?;
+        }
+
+        tx.commit()
+            .await
+            .context("Failed to commit message roles")?;
+        Ok(())
+    }
+
+    /// Compare estimates with reported usage, per provider
+    pub async fn token_accuracy(&self) -> AnyhowResult<Vec<TokenAccuracy>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT s.provider,
+                   COUNT(*) AS messages,
+                   SUM(m.token_count) AS reported_tokens,
+                   SUM(m.estimated_tokens) AS estimated_tokens,
+                   SUM(ABS(m.estimated_tokens - m.token_count)) AS absolute_error
+            FROM messages m
+            JOIN chat_sessions s ON s.id = m.session_id
+            WHERE m.token_count IS NOT NULL AND m.estimated_tokens IS NOT NULL
+            GROUP BY s.provider
+            ORDER BY s.provider
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to compare token estimates")?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(TokenAccuracy {
+                    provider: row.try_get("provider")?,
+                    messages: row.try_get("messages")?,
+                    reported_tokens: row.try_get("reported_tokens")?,
+                    estimated_tokens: row.try_get("estimated_tokens")?,
+                    absolute_error: row.try_get("absolute_error")?,
+                })
+            })
+            .collect()
+    }
+
+    /// Slash commands and hooks across sessions, most fired first
+    pub async fn workflow_stats(&self) -> AnyhowResult<Vec<WorkflowStats>> {
+        let rows = sqlx::query(
+            r#"
+            WITH events AS (
+                SELECT 'command' AS kind,
+                       json_extract(metadata, '$.command') AS name,
+                       0 AS failed,
+                       session_id
+                FROM messages
+                WHERE message_type = 'slash_command'
+                UNION ALL
+                SELECT 'hook',
+                       json_extract(metadata, '$.hook.event'),
+                       json_extract(metadata, '$.hook.outcome') != 'success',
+                       session_id
+                FROM messages
+                WHERE message_type = 'hook_event'
+            )
+            SELECT e.kind, e.name,
+                   COUNT(*) AS fired,
+                   SUM(e.failed) AS failures,
+                   COUNT(DISTINCT e.session_id) AS sessions,
+                   COUNT(DISTINCT CASE WHEN ss.outcome IS NOT NULL THEN e.session_id END)
+                       AS summarized_sessions,
+                   COUNT(DISTINCT CASE WHEN ss.outcome = 'completed' THEN e.session_id END)
+                       AS completed_sessions
+            FROM events e
+            LEFT JOIN session_summaries ss ON ss.session_id = e.session_id
+            WHERE e.name IS NOT NULL
+            GROUP BY e.kind, e.name
+            ORDER BY fired DESC, e.name
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to count workflow events")?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(WorkflowStats {
+                    kind: row.try_get("kind")?,
+                    name: row.try_get("name")?,
+                    fired: row.try_get("fired")?,
+                    failures: row.try_get("failures")?,
+                    sessions: row.try_get("sessions")?,
+                    summarized_sessions: row.try_get("summarized_sessions")?,
+                    completed_sessions: row.try_get("completed_sessions")?,
+                })
+            })
+            .collect()
+    }
+
     /// Get histogram of user messages within a time range
     ///
     /// Returns (timestamp, count) pairs for each time bucket.
@@ -485,6 +871,7 @@ impl MessageRepository {
         let content: String = row.try_get("content")?;
         let timestamp_str: String = row.try_get("timestamp")?;
         let token_count: Option<i64> = row.try_get("token_count")?;
+        let estimated_tokens: Option<i64> = row.try_get("estimated_tokens")?;
         let sequence_number: i64 = row.try_get("sequence_number")?;
         let message_type_str: String = row.try_get("message_type")?;
         let tool_operation_id_str: Option<String> = row.try_get("tool_operation_id")?;
@@ -504,7 +891,10 @@ impl MessageRepository {
             None
         };
 
-        let metadata: Option<serde_json::Value> = serde_json::from_str("{}").ok();
+        let metadata_str: Option<String> = row.try_get("metadata").ok().flatten();
+        let metadata = metadata_str
+            .as_deref()
+            .and_then(|json| serde_json::from_str(json).ok());
 
         Ok(Message {
             id,
@@ -513,12 +903,122 @@ impl MessageRepository {
             content,
             timestamp,
             token_count: token_count.map(|tc| tc as u32),
+            estimated_tokens: estimated_tokens.map(|tc| tc as u32),
             metadata,
             sequence_number: sequence_number as u32,
             message_type,
             tool_operation_id,
             tool_uses: None,
             tool_results: None,
+            usage: None,
+            attachments: None,
+            raw: None,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{ChatSessionRepository, SessionSummaryRepository};
+    use crate::models::message::MessageType;
+    use crate::models::{ChatSession, GeneratedSessionSummary, Provider, SessionOutcome};
+
+    #[tokio::test]
+    async fn test_workflow_stats() {
+        let db = DatabaseManager::open_in_memory().await.unwrap();
+        let repo = MessageRepository::new(&db);
+        let session = ChatSession::new(
+            Provider::ClaudeCode,
+            "/test/file.jsonl".to_string(),
+            "test_hash".to_string(),
+            Utc::now(),
+        );
+        ChatSessionRepository::new(&db)
+            .create(&session)
+            .await
+            .unwrap();
+
+        let event = |sequence, message_type, metadata| {
+            Message::new(
+                session.id,
+                MessageRole::System,
+                "event".to_string(),
+                Utc::now(),
+                sequence,
+            )
+            .with_message_type(message_type)
+            .with_metadata(metadata)
+        };
+        let hook =
+            |outcome| serde_json::json!({ "hook": { "event": "PostToolUse", "outcome": outcome } });
+        let messages = [
+            event(
+                1,
+                MessageType::SlashCommand,
+                serde_json::json!({ "command": "/compact" }),
+            ),
+            event(2, MessageType::HookEvent, hook("success")),
+            event(3, MessageType::HookEvent, hook("failed")),
+            event(4, MessageType::HookEvent, hook("success")),
+        ];
+        repo.bulk_create(&messages).await.unwrap();
+        let summary = GeneratedSessionSummary::new(
+            session.id.to_string(),
+            "Title".to_string(),
+            "Summary".to_string(),
+        )
+        .with_outcome(SessionOutcome::Completed);
+        SessionSummaryRepository::new(&db)
+            .create(&summary)
+            .await
+            .unwrap();
+
+        let stored = repo.get_by_session(&session.id).await.unwrap();
+        assert_eq!(stored[0].metadata.as_ref().unwrap()["command"], "/compact");
+
+        let stats = repo.workflow_stats().await.unwrap();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].kind, "hook");
+        assert_eq!(stats[0].name, "PostToolUse");
+        assert_eq!(stats[0].fired, 3);
+        assert_eq!(stats[0].failures, 1);
+        assert_eq!(stats[0].completion_rate(), Some(1.0));
+        assert_eq!(stats[1].name, "/compact");
+        assert_eq!(stats[1].sessions, 1);
+    }
+
+    #[tokio::test]
+    async fn test_get_page_by_session() {
+        let db = DatabaseManager::open_in_memory().await.unwrap();
+        let repo = MessageRepository::new(&db);
+        let session = ChatSession::new(
+            Provider::ClaudeCode,
+            "/test/file.jsonl".to_string(),
+            "test_hash".to_string(),
+            Utc::now(),
+        );
+        ChatSessionRepository::new(&db)
+            .create(&session)
+            .await
+            .unwrap();
+        let messages: Vec<Message> = (1..=5)
+            .map(|sequence| {
+                Message::new(
+                    session.id,
+                    MessageRole::User,
+                    format!("message {sequence}"),
+                    Utc::now(),
+                    sequence,
+                )
+            })
+            .collect();
+        repo.bulk_create(&messages).await.unwrap();
+
+        let page = repo.get_page_by_session(&session.id, 2, 2).await.unwrap();
+        let sequences: Vec<u32> = page.iter().map(|m| m.sequence_number).collect();
+        assert_eq!(sequences, vec![3, 4]);
+        let last = repo.get_page_by_session(&session.id, 4, 10).await.unwrap();
+        assert_eq!(last.len(), 1);
+    }
+}
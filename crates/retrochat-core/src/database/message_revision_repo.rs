@@ -0,0 +1,81 @@
+use anyhow::{Context, Result as AnyhowResult};
+use chrono::{DateTime, Utc};
+use sqlx::{sqlite::SqliteRow, Pool, Row, Sqlite};
+use uuid::Uuid;
+
+use super::connection::DatabaseManager;
+use crate::models::{MessageRevision, RevisionSource};
+
+/// Read side of the `message_revisions` audit trail
+///
+/// Revisions are written by the code that changes a message, in the same
+/// transaction as the change.
+pub struct MessageRevisionRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl MessageRevisionRepository {
+    pub fn new(db: &DatabaseManager) -> Self {
+        Self {
+            pool: db.pool().clone(),
+        }
+    }
+
+    /// Changes to one message, oldest first
+    pub async fn get_by_message(&self, message_id: &Uuid) -> AnyhowResult<Vec<MessageRevision>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, message_id, field, before_value, after_value, source, reason, created_at
+            FROM message_revisions
+            WHERE message_id = ?
+            ORDER BY id
+            "#,
+        )
+        .bind(message_id.to_string())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch message revisions")?;
+
+        rows.iter().map(row_to_revision).collect()
+    }
+
+    /// Changes to the messages now in a session, oldest first
+    pub async fn get_by_session(&self, session_id: &Uuid) -> AnyhowResult<Vec<MessageRevision>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT r.id, r.message_id, r.field, r.before_value, r.after_value, r.source,
+                   r.reason, r.created_at
+            FROM message_revisions r
+            JOIN messages m ON m.id = r.message_id
+            WHERE m.session_id = ?
+            ORDER BY r.id
+            "#,
+        )
+        .bind(session_id.to_string())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch session revisions")?;
+
+        rows.iter().map(row_to_revision).collect()
+    }
+}
+
+fn row_to_revision(row: &SqliteRow) -> AnyhowResult<MessageRevision> {
+    let message_id: String = row.try_get("message_id")?;
+    let source: String = row.try_get("source")?;
+    let created_at: String = row.try_get("created_at")?;
+    Ok(MessageRevision {
+        id: row.try_get("id")?,
+        message_id: Uuid::parse_str(&message_id).context("Invalid message ID in revisions")?,
+        field: row.try_get("field")?,
+        before: row.try_get("before_value")?,
+        after: row.try_get("after_value")?,
+        source: source
+            .parse::<RevisionSource>()
+            .map_err(|e| anyhow::anyhow!(e))?,
+        reason: row.try_get("reason")?,
+        created_at: DateTime::parse_from_rfc3339(&created_at)
+            .context("Invalid revision time")?
+            .with_timezone(&Utc),
+    })
+}
@@ -0,0 +1,96 @@
+use anyhow::{Context, Result as AnyhowResult};
+use sqlx::{Pool, Row, Sqlite};
+use uuid::Uuid;
+
+use super::connection::DatabaseManager;
+use crate::models::MessageUsage;
+
+pub struct MessageUsageRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl MessageUsageRepository {
+    pub fn new(db: &DatabaseManager) -> Self {
+        Self {
+            pool: db.pool().clone(),
+        }
+    }
+
+    pub async fn bulk_create(&self, usage: &[MessageUsage]) -> AnyhowResult<()> {
+        if usage.is_empty() {
+            return Ok(());
+        }
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        for record in usage {
+            sqlx::query(
+                r#"
+                INSERT OR REPLACE INTO message_usage (
+                    message_id, session_id, model, input_tokens, output_tokens,
+                    cache_creation_input_tokens, cache_read_input_tokens, service_tier
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(record.message_id.to_string())
+            .bind(record.session_id.to_string())
+            .bind(&record.model)
+            .bind(record.input_tokens as i64)
+            .bind(record.output_tokens as i64)
+            .bind(record.cache_creation_input_tokens as i64)
+            .bind(record.cache_read_input_tokens as i64)
+            .bind(&record.service_tier)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert message usage")?;
+        }
+
+        tx.commit()
+            .await
+            .context("Failed to commit message usage")?;
+        Ok(())
+    }
+
+    pub async fn get_by_session(&self, session_id: &Uuid) -> AnyhowResult<Vec<MessageUsage>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT u.message_id, u.session_id, u.model, u.input_tokens, u.output_tokens,
+                   u.cache_creation_input_tokens, u.cache_read_input_tokens, u.service_tier
+            FROM message_usage u
+            JOIN messages m ON m.id = u.message_id
+            WHERE u.session_id = ?
+            ORDER BY m.sequence_number
+            "#,
+        )
+        .bind(session_id.to_string())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch message usage")?;
+
+        rows.iter()
+            .map(|row| {
+                let message_id: String = row.try_get("message_id")?;
+                let session_id: String = row.try_get("session_id")?;
+                Ok(MessageUsage {
+                    message_id: Uuid::parse_str(&message_id)
+                        .context("Invalid message ID in message_usage")?,
+                    session_id: Uuid::parse_str(&session_id)
+                        .context("Invalid session ID in message_usage")?,
+                    model: row.try_get("model")?,
+                    input_tokens: row.try_get::<i64, _>("input_tokens")? as u64,
+                    output_tokens: row.try_get::<i64, _>("output_tokens")? as u64,
+                    cache_creation_input_tokens: row
+                        .try_get::<i64, _>("cache_creation_input_tokens")?
+                        as u64,
+                    cache_read_input_tokens: row.try_get::<i64, _>("cache_read_input_tokens")?
+                        as u64,
+                    service_tier: row.try_get("service_tier")?,
+                })
+            })
+            .collect()
+    }
+}
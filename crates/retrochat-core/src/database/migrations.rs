@@ -1,6 +1,62 @@
+//! Schema migrations
+//!
+//! Migrations are the SQL files in `crates/retrochat-core/migrations`, embedded
+//! at build time and applied by SQLx, which records them in `_sqlx_migrations`.
+//! A migration `NNN_name.sql` can be reverted when a matching
+//! `NNN_name.down.sql` exists; older migrations have none, so rollbacks can
+//! only go back as far as the oldest down script.
+
 use anyhow::{Context, Result as AnyhowResult};
-use sqlx::{Pool, Sqlite};
-use tracing::{error, info, warn};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use sqlx::migrate::{Migration, Migrator};
+use sqlx::{Pool, Row, Sqlite};
+use std::collections::HashMap;
+use std::path::Path;
+use tracing::{info, warn};
+
+/// Every migration shipped with this build
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+/// One migration and whether it is applied to the database
+#[derive(Debug, Clone)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub description: String,
+    pub applied: bool,
+    pub applied_at: Option<DateTime<Utc>>,
+    pub execution_ms: Option<i64>,
+    /// A down script exists, so the migration can be rolled back
+    pub reversible: bool,
+    /// The applied SQL differs from the SQL shipped with this build
+    pub checksum_mismatch: bool,
+    /// Applied by a newer RetroChat; this build doesn't know the migration
+    pub unknown: bool,
+}
+
+/// A migration's SQL, as it would be run
+#[derive(Debug, Clone)]
+pub struct MigrationPlan {
+    pub version: i64,
+    pub description: String,
+    pub sql: String,
+}
+
+impl MigrationPlan {
+    fn from_migration(migration: &Migration) -> Self {
+        Self {
+            version: migration.version,
+            description: migration.description.to_string(),
+            sql: migration.sql.to_string(),
+        }
+    }
+}
+
+struct AppliedMigration {
+    description: String,
+    applied_at: Option<DateTime<Utc>>,
+    execution_ms: i64,
+    checksum: Vec<u8>,
+}
 
 pub struct MigrationManager {
     pool: Pool<Sqlite>,
@@ -11,204 +67,254 @@ impl MigrationManager {
         Self { pool }
     }
 
-    pub async fn get_current_version(&self) -> AnyhowResult<u32> {
-        // Check if schema_versions table exists
-        let count: i32 = sqlx::query_scalar(
-            "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='schema_versions'",
-        )
-        .fetch_one(&self.pool)
-        .await
-        .unwrap_or(0);
-
-        let table_exists = count > 0;
+    /// Highest applied migration, or 0 for a new database
+    pub async fn get_current_version(&self) -> AnyhowResult<i64> {
+        Ok(self
+            .applied_migrations()
+            .await?
+            .keys()
+            .copied()
+            .max()
+            .unwrap_or(0))
+    }
 
-        if !table_exists {
-            return Ok(0);
-        }
+    /// Highest migration shipped with this build
+    pub fn get_latest_version(&self) -> i64 {
+        up_migrations().map(|m| m.version).max().unwrap_or(0)
+    }
 
-        // Get the highest version number
-        let version: u32 =
-            sqlx::query_scalar("SELECT COALESCE(MAX(version), 0) FROM schema_versions")
-                .fetch_one(&self.pool)
-                .await
-                .unwrap_or(0);
+    /// Every known or applied migration, oldest first
+    pub async fn get_migration_status(&self) -> AnyhowResult<Vec<MigrationStatus>> {
+        let mut applied = self.applied_migrations().await?;
+        let mut status: Vec<MigrationStatus> = up_migrations()
+            .map(|migration| {
+                let record = applied.remove(&migration.version);
+                MigrationStatus {
+                    version: migration.version,
+                    description: migration.description.to_string(),
+                    applied: record.is_some(),
+                    applied_at: record.as_ref().and_then(|r| r.applied_at),
+                    execution_ms: record.as_ref().map(|r| r.execution_ms),
+                    reversible: down_migration(migration.version).is_some(),
+                    checksum_mismatch: record
+                        .as_ref()
+                        .is_some_and(|r| r.checksum != *migration.checksum),
+                    unknown: false,
+                }
+            })
+            .collect();
+
+        status.extend(
+            applied
+                .into_iter()
+                .map(|(version, record)| MigrationStatus {
+                    version,
+                    description: record.description,
+                    applied: true,
+                    applied_at: record.applied_at,
+                    execution_ms: Some(record.execution_ms),
+                    reversible: false,
+                    checksum_mismatch: false,
+                    unknown: true,
+                }),
+        );
+        status.sort_by_key(|s| s.version);
+        Ok(status)
+    }
 
-        Ok(version)
+    /// Migrations that `migrate_to_latest` would apply, in order
+    pub async fn pending_migrations(&self) -> AnyhowResult<Vec<MigrationPlan>> {
+        let applied = self.applied_migrations().await?;
+        Ok(up_migrations()
+            .filter(|m| !applied.contains_key(&m.version))
+            .map(MigrationPlan::from_migration)
+            .collect())
     }
 
+    /// Apply every pending migration; each runs in its own transaction
     pub async fn migrate_to_latest(&self) -> AnyhowResult<()> {
-        let current_version = self.get_current_version().await?;
-        let target_version = self.get_latest_version().await?;
-
-        self.migrate_to_version(target_version, current_version)
+        MIGRATOR
+            .run(&self.pool)
             .await
+            .context("Failed to run database migrations")?;
+        Ok(())
     }
 
-    pub async fn migrate_to_version(
-        &self,
-        target_version: u32,
-        from_version: u32,
-    ) -> AnyhowResult<()> {
-        if target_version == from_version {
-            info!("Database is already at version {}", target_version);
-            return Ok(());
-        }
-
-        if target_version > from_version {
-            self.migrate_up(from_version, target_version).await
-        } else {
-            self.migrate_down(from_version, target_version).await
-        }
+    /// Down scripts that `rollback_to(target)` would run, newest first
+    ///
+    /// Fails if an applied migration above `target` has no down script.
+    pub async fn rollback_plan(&self, target: i64) -> AnyhowResult<Vec<MigrationPlan>> {
+        let mut versions: Vec<i64> = self
+            .applied_migrations()
+            .await?
+            .into_keys()
+            .filter(|&version| version > target)
+            .collect();
+        versions.sort_unstable_by(|a, b| b.cmp(a));
+
+        versions
+            .into_iter()
+            .map(|version| {
+                down_migration(version)
+                    .map(MigrationPlan::from_migration)
+                    .with_context(|| {
+                        format!(
+                            "Migration {version} has no down script; the oldest version \
+                             this build can roll back to is {}",
+                            self.oldest_rollback_target()
+                        )
+                    })
+            })
+            .collect()
     }
 
-    async fn migrate_up(&self, from_version: u32, to_version: u32) -> AnyhowResult<()> {
-        info!(
-            "Migrating database from version {} to {}",
-            from_version, to_version
-        );
-
-        let mut tx = self.pool.begin().await?;
-
-        for version in (from_version + 1)..=to_version {
-            info!("Applying migration {}", version);
-
-            // SQLx migrations are handled by the migrate! macro
-            // This is just for tracking and logging
-            sqlx::query("INSERT INTO schema_versions (version) VALUES (?)")
-                .bind(version)
-                .execute(&mut *tx)
-                .await
-                .with_context(|| format!("Failed to record migration {version}"))?;
-
-            info!("Successfully applied migration {}", version);
+    /// Revert applied migrations newer than `target` with their down scripts
+    pub async fn rollback_to(&self, target: i64) -> AnyhowResult<Vec<MigrationPlan>> {
+        let plan = self.rollback_plan(target).await?;
+        if plan.is_empty() {
+            return Ok(plan);
         }
 
-        tx.commit().await?;
-        info!("Database migration completed successfully");
-        Ok(())
-    }
-
-    async fn migrate_down(&self, from_version: u32, to_version: u32) -> AnyhowResult<()> {
         warn!(
-            "Rolling back database from version {} to {}",
-            from_version, to_version
+            "Rolling back database to migration {} ({} migrations)",
+            target,
+            plan.len()
         );
+        MIGRATOR
+            .undo(&self.pool, target)
+            .await
+            .with_context(|| format!("Failed to roll back to migration {target}"))?;
+        info!("Database rolled back to migration {}", target);
+        Ok(plan)
+    }
 
-        let mut tx = self.pool.begin().await?;
-
-        for version in (to_version + 1..=from_version).rev() {
-            warn!("Rolling back migration {}", version);
-
-            // Remove migration record
-            sqlx::query("DELETE FROM schema_versions WHERE version = ?")
-                .bind(version)
-                .execute(&mut *tx)
-                .await
-                .with_context(|| format!("Failed to remove migration record {version}"))?;
-
-            warn!("Successfully rolled back migration {}", version);
+    /// Lowest version `rollback_to` accepts: just below the oldest migration
+    /// of the unbroken run of reversible migrations ending at the latest
+    fn oldest_rollback_target(&self) -> i64 {
+        let mut target = self.get_latest_version();
+        let mut versions: Vec<i64> = up_migrations().map(|m| m.version).collect();
+        versions.sort_unstable();
+        for version in versions.into_iter().rev() {
+            if down_migration(version).is_none() {
+                break;
+            }
+            target = version - 1;
         }
-
-        tx.commit().await?;
-        warn!("Database rollback completed");
-        Ok(())
+        target
     }
 
-    async fn get_latest_version(&self) -> AnyhowResult<u32> {
-        // For now, we'll use a hardcoded value
-        // In a real implementation, you might scan the migrations directory
-        Ok(5)
+    /// Write a consistent copy of the database to `path`
+    pub async fn backup(&self, path: &Path) -> AnyhowResult<()> {
+        if path.exists() {
+            std::fs::remove_file(path)
+                .with_context(|| format!("Failed to replace backup: {}", path.display()))?;
+        }
+        sqlx::query("VACUUM INTO ?")
+            .bind(path.to_string_lossy().to_string())
+            .execute(&self.pool)
+            .await
+            .with_context(|| format!("Failed to back up database to {}", path.display()))?;
+        Ok(())
     }
 
-    pub async fn get_migration_status(&self) -> AnyhowResult<Vec<MigrationStatus>> {
-        let current_version = self.get_current_version().await?;
-        let mut status = Vec::new();
-
-        let applied_versions: Vec<u32> = if current_version > 0 {
-            sqlx::query_scalar("SELECT version FROM schema_versions ORDER BY version")
-                .fetch_all(&self.pool)
-                .await?
-        } else {
-            Vec::new()
-        };
-
-        // For now, we only have one migration
-        let is_applied = applied_versions.contains(&1);
-        status.push(MigrationStatus {
-            version: 1,
-            description: "Initial schema creation".to_string(),
-            applied: is_applied,
-        });
+    async fn applied_migrations(&self) -> AnyhowResult<HashMap<i64, AppliedMigration>> {
+        let table_exists: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = '_sqlx_migrations'",
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        if table_exists == 0 {
+            return Ok(HashMap::new());
+        }
 
-        Ok(status)
+        let rows = sqlx::query(
+            r#"
+            SELECT version, description, installed_on, checksum, execution_time
+            FROM _sqlx_migrations WHERE success = TRUE
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to read applied migrations")?;
+
+        rows.iter()
+            .map(|row| {
+                let installed_on: Option<String> = row.try_get("installed_on")?;
+                let execution_time: i64 = row.try_get("execution_time")?;
+                Ok((
+                    row.try_get("version")?,
+                    AppliedMigration {
+                        description: row.try_get("description")?,
+                        applied_at: installed_on
+                            .and_then(|t| {
+                                NaiveDateTime::parse_from_str(&t, "%Y-%m-%d %H:%M:%S").ok()
+                            })
+                            .map(|t| t.and_utc()),
+                        // Recorded in nanoseconds; -1 when it was never measured
+                        execution_ms: execution_time.max(0) / 1_000_000,
+                        checksum: row.try_get("checksum")?,
+                    },
+                ))
+            })
+            .collect()
     }
+}
 
-    pub async fn reset_database(&self) -> AnyhowResult<()> {
-        warn!("Resetting database - all data will be lost!");
-
-        // Drop all tables
-        self.drop_all_tables().await?;
+fn up_migrations() -> impl Iterator<Item = &'static Migration> {
+    MIGRATOR
+        .iter()
+        .filter(|m| !m.migration_type.is_down_migration())
+}
 
-        // Recreate from scratch
-        self.migrate_to_latest().await?;
+fn down_migration(version: i64) -> Option<&'static Migration> {
+    MIGRATOR
+        .iter()
+        .find(|m| m.version == version && m.migration_type.is_down_migration())
+}
 
-        info!("Database reset completed");
-        Ok(())
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::DatabaseManager;
+
+    #[tokio::test]
+    async fn test_status_and_pending() {
+        let db = DatabaseManager::open_in_memory().await.unwrap();
+        let manager = MigrationManager::new(db.pool().clone());
+
+        let latest = manager.get_latest_version();
+        assert_eq!(manager.get_current_version().await.unwrap(), latest);
+        assert!(manager.pending_migrations().await.unwrap().is_empty());
+
+        let status = manager.get_migration_status().await.unwrap();
+        assert_eq!(status.len() as i64, latest);
+        assert!(status
+            .iter()
+            .all(|s| s.applied && !s.checksum_mismatch && !s.unknown));
+        assert!(!status[0].reversible);
+        assert!(status.last().unwrap().reversible);
     }
 
-    async fn drop_all_tables(&self) -> AnyhowResult<()> {
-        // Get all table names
-        let tables: Vec<String> = sqlx::query_scalar(
-            "SELECT name FROM sqlite_master WHERE type='table' AND name NOT LIKE 'sqlite_%'",
-        )
-        .fetch_all(&self.pool)
-        .await?;
+    #[tokio::test]
+    async fn test_rollback_and_reapply() {
+        let db = DatabaseManager::open_in_memory().await.unwrap();
+        let manager = MigrationManager::new(db.pool().clone());
+        let latest = manager.get_latest_version();
+        let oldest = manager.oldest_rollback_target();
+        assert!(oldest < latest);
 
-        // Drop each table
-        for table in tables {
-            sqlx::query(&format!("DROP TABLE IF EXISTS {table}"))
-                .execute(&self.pool)
-                .await?;
-        }
+        assert!(manager.rollback_plan(oldest - 1).await.is_err());
 
-        Ok(())
-    }
+        let plan = manager.rollback_to(oldest).await.unwrap();
+        assert_eq!(plan.len() as i64, latest - oldest);
+        assert_eq!(plan[0].version, latest);
+        assert_eq!(manager.get_current_version().await.unwrap(), oldest);
 
-    pub async fn validate_database(&self) -> AnyhowResult<bool> {
-        // Check if all required tables exist
-        let required_tables = [
-            "schema_versions",
-            "projects",
-            "chat_sessions",
-            "messages",
-            "llm_providers",
-            "messages_fts",
-        ];
-
-        for table in &required_tables {
-            let count: i32 = sqlx::query_scalar(
-                "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name = ?",
-            )
-            .bind(table)
-            .fetch_one(&self.pool)
-            .await?;
-
-            let exists = count > 0;
-
-            if !exists {
-                error!("Required table {} does not exist", table);
-                return Ok(false);
-            }
-        }
+        let pending = manager.pending_migrations().await.unwrap();
+        assert_eq!(pending.len(), plan.len());
+        assert!(pending[0].sql.contains("Migration:"));
 
-        info!("Database validation passed");
-        Ok(true)
+        manager.migrate_to_latest().await.unwrap();
+        assert_eq!(manager.get_current_version().await.unwrap(), latest);
     }
 }
-
-#[derive(Debug, Clone)]
-pub struct MigrationStatus {
-    pub version: u32,
-    pub description: String,
-    pub applied: bool,
-}
@@ -1,27 +1,70 @@
 pub mod analytics_repo;
 pub mod analytics_request_repo;
+pub mod attachment_repo;
 pub mod chat_session_repo;
 pub mod config;
 pub mod connection;
+pub mod coordination;
+pub mod embedding_repo;
+pub mod encryption;
+pub mod event_repo;
+pub mod experiment_repo;
+pub mod import_error_repo;
+pub mod import_journal_repo;
+pub mod llm_cache_repo;
 pub mod message_repo;
+pub mod message_revision_repo;
+pub mod message_usage_repo;
 pub mod migrations;
+pub mod parser_warning_repo;
 pub mod project_repo;
+pub mod quota_repo;
+pub mod raw_payload_repo;
+pub mod remote_sync_repo;
+pub mod saved_view_repo;
 pub mod schema;
+pub mod session_comparison_repo;
+pub mod session_quality_repo;
+pub mod session_segment_repo;
 pub mod session_summary_repo;
+pub mod session_tag_repo;
 pub mod tool_operation_repo;
+pub mod topic_repo;
 pub mod turn_summary_repo;
 
 // Main repositories (now using SQLx)
-pub use analytics_repo::AnalyticsRepository;
+pub use analytics_repo::{
+    AnalyticsRepository, DailyUsagePoint, RubricTrendPoint, TokenTrendPoint, ToolUsagePoint,
+};
 pub use analytics_request_repo::AnalyticsRequestRepository;
+pub use attachment_repo::AttachmentRepository;
 pub use chat_session_repo::ChatSessionRepository;
 pub use connection::DatabaseManager;
-pub use message_repo::MessageRepository;
-pub use migrations::{MigrationManager, MigrationStatus};
+pub use coordination::WriteGuard;
+pub use embedding_repo::{EmbeddingKind, EmbeddingRepository, StoredEmbedding, VectorIndexState};
+pub use event_repo::EventRepository;
+pub use experiment_repo::ExperimentRepository;
+pub use import_error_repo::ImportErrorRepository;
+pub use import_journal_repo::{ImportJournalRepository, JournalEntry};
+pub use llm_cache_repo::LlmCacheRepository;
+pub use message_repo::{MessageRepository, TokenAccuracy, WorkflowStats};
+pub use message_revision_repo::MessageRevisionRepository;
+pub use message_usage_repo::MessageUsageRepository;
+pub use migrations::{MigrationManager, MigrationPlan, MigrationStatus};
+pub use parser_warning_repo::ParserWarningRepository;
 pub use project_repo::ProjectRepository;
+pub use quota_repo::QuotaRepository;
+pub use raw_payload_repo::RawPayloadRepository;
+pub use remote_sync_repo::{BundleDirection, RemoteSyncRepository, RemoteSyncSummary};
+pub use saved_view_repo::SavedViewRepository;
 pub use schema::{create_schema, SCHEMA_VERSION};
+pub use session_comparison_repo::SessionComparisonRepository;
+pub use session_quality_repo::SessionQualityRepository;
+pub use session_segment_repo::SessionSegmentRepository;
 pub use session_summary_repo::SessionSummaryRepository;
-pub use tool_operation_repo::ToolOperationRepository;
+pub use session_tag_repo::SessionTagRepository;
+pub use tool_operation_repo::{McpServerStats, ToolOperationFilter, ToolOperationRepository};
+pub use topic_repo::{TopicAssignment, TopicRepository};
 pub use turn_summary_repo::TurnSummaryRepository;
 
 // Main database structure (now using SQLx by default)
@@ -79,6 +122,30 @@ impl Database {
         SessionSummaryRepository::new(&self.manager)
     }
 
+    pub fn import_error_repo(&self) -> ImportErrorRepository {
+        ImportErrorRepository::new(&self.manager)
+    }
+
+    pub fn saved_view_repo(&self) -> SavedViewRepository {
+        SavedViewRepository::new(&self.manager)
+    }
+
+    pub fn llm_cache_repo(&self) -> LlmCacheRepository {
+        LlmCacheRepository::new(&self.manager)
+    }
+
+    pub fn embedding_repo(&self) -> EmbeddingRepository {
+        EmbeddingRepository::new(&self.manager)
+    }
+
+    pub fn topic_repo(&self) -> TopicRepository {
+        TopicRepository::new(&self.manager)
+    }
+
+    pub fn event_repo(&self) -> EventRepository {
+        EventRepository::new(&self.manager)
+    }
+
     pub fn migration_manager(&self) -> MigrationManager {
         MigrationManager::new(self.manager.pool().clone())
     }
@@ -0,0 +1,167 @@
+use anyhow::{Context, Result as AnyhowResult};
+use chrono::{DateTime, Utc};
+use sqlx::{sqlite::SqliteRow, Pool, Row, Sqlite};
+
+use super::connection::DatabaseManager;
+use crate::models::{FormatDrift, ParserWarning};
+
+pub struct ParserWarningRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl ParserWarningRepository {
+    pub fn new(db: &DatabaseManager) -> Self {
+        Self {
+            pool: db.pool().clone(),
+        }
+    }
+
+    /// Add the drift met while importing one file
+    ///
+    /// Known warnings get their counts bumped and their last file and time
+    /// replaced.
+    pub async fn record(
+        &self,
+        provider: &str,
+        file_path: &str,
+        drift: &[FormatDrift],
+    ) -> AnyhowResult<()> {
+        if drift.is_empty() {
+            return Ok(());
+        }
+        let now = Utc::now().to_rfc3339();
+        let mut tx = self.pool.begin().await?;
+
+        for item in drift {
+            sqlx::query(
+                r#"
+                INSERT INTO parser_warnings (
+                    provider, kind, location, occurrences, file_count,
+                    last_file_path, first_seen_at, last_seen_at
+                ) VALUES (?, ?, ?, ?, 1, ?, ?, ?)
+                ON CONFLICT(provider, kind, location) DO UPDATE SET
+                    occurrences = parser_warnings.occurrences + excluded.occurrences,
+                    file_count = parser_warnings.file_count + 1,
+                    last_file_path = excluded.last_file_path,
+                    last_seen_at = excluded.last_seen_at
+                "#,
+            )
+            .bind(provider)
+            .bind(item.kind.to_string())
+            .bind(&item.location)
+            .bind(item.occurrences as i64)
+            .bind(file_path)
+            .bind(&now)
+            .bind(&now)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to record parser warning")?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// List recorded warnings by provider, most recently seen first
+    pub async fn list(&self, provider: Option<&str>) -> AnyhowResult<Vec<ParserWarning>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, provider, kind, location, occurrences, file_count,
+                   last_file_path, first_seen_at, last_seen_at
+            FROM parser_warnings
+            WHERE ? IS NULL OR provider = ?
+            ORDER BY provider, last_seen_at DESC, location
+            "#,
+        )
+        .bind(provider)
+        .bind(provider)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list parser warnings")?;
+
+        rows.iter().map(Self::row_to_warning).collect()
+    }
+
+    /// Forget recorded warnings, e.g. after upgrading the parsers
+    pub async fn clear(&self, provider: Option<&str>) -> AnyhowResult<u64> {
+        let result = sqlx::query("DELETE FROM parser_warnings WHERE ? IS NULL OR provider = ?")
+            .bind(provider)
+            .bind(provider)
+            .execute(&self.pool)
+            .await
+            .context("Failed to clear parser warnings")?;
+
+        Ok(result.rows_affected())
+    }
+
+    fn row_to_warning(row: &SqliteRow) -> AnyhowResult<ParserWarning> {
+        let kind: String = row.try_get("kind")?;
+        let first_seen_at: String = row.try_get("first_seen_at")?;
+        let last_seen_at: String = row.try_get("last_seen_at")?;
+        let occurrences: i64 = row.try_get("occurrences")?;
+        let file_count: i64 = row.try_get("file_count")?;
+
+        Ok(ParserWarning {
+            id: row.try_get("id")?,
+            provider: row.try_get("provider")?,
+            kind: kind.parse().map_err(anyhow::Error::msg)?,
+            location: row.try_get("location")?,
+            occurrences: occurrences as u64,
+            file_count: file_count as u64,
+            last_file_path: row.try_get("last_file_path")?,
+            first_seen_at: DateTime::parse_from_rfc3339(&first_seen_at)
+                .context("Invalid first_seen_at timestamp format")?
+                .with_timezone(&Utc),
+            last_seen_at: DateTime::parse_from_rfc3339(&last_seen_at)
+                .context("Invalid last_seen_at timestamp format")?
+                .with_timezone(&Utc),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::DriftKind;
+
+    fn drift(location: &str, occurrences: u32) -> FormatDrift {
+        FormatDrift {
+            kind: DriftKind::UnknownKey,
+            location: location.to_string(),
+            occurrences,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_accumulates_per_location() {
+        let db = DatabaseManager::open_in_memory().await.unwrap();
+        let repo = ParserWarningRepository::new(&db);
+
+        repo.record("Claude Code", "/tmp/a.jsonl", &[drift("entry.newField", 3)])
+            .await
+            .unwrap();
+        repo.record(
+            "Claude Code",
+            "/tmp/b.jsonl",
+            &[drift("entry.newField", 2), drift("entry.other", 1)],
+        )
+        .await
+        .unwrap();
+        repo.record("Codex", "/tmp/c.jsonl", &[drift("entry.newField", 1)])
+            .await
+            .unwrap();
+
+        let warnings = repo.list(Some("Claude Code")).await.unwrap();
+        assert_eq!(warnings.len(), 2);
+        let new_field = warnings
+            .iter()
+            .find(|w| w.location == "entry.newField")
+            .unwrap();
+        assert_eq!(new_field.occurrences, 5);
+        assert_eq!(new_field.file_count, 2);
+        assert_eq!(new_field.last_file_path.as_deref(), Some("/tmp/b.jsonl"));
+
+        assert_eq!(repo.clear(Some("Codex")).await.unwrap(), 1);
+        assert_eq!(repo.list(None).await.unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,199 @@
+use anyhow::{Context, Result as AnyhowResult};
+use chrono::{DateTime, Utc};
+use sqlx::{sqlite::SqliteRow, Pool, Row, Sqlite};
+
+use super::connection::DatabaseManager;
+use crate::models::QuotaEvent;
+
+/// Usage totals behind the quota checks, and the log of crossed thresholds
+pub struct QuotaRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl QuotaRepository {
+    pub fn new(db: &DatabaseManager) -> Self {
+        Self {
+            pool: db.pool().clone(),
+        }
+    }
+
+    /// Tokens of messages sent in `[from, to)`, reported or estimated
+    pub async fn tokens_between(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> AnyhowResult<u64> {
+        let tokens: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COALESCE(SUM(COALESCE(token_count, estimated_tokens, 0)), 0)
+            FROM messages
+            WHERE timestamp >= ? AND timestamp < ?
+            "#,
+        )
+        .bind(from.to_rfc3339())
+        .bind(to.to_rfc3339())
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to sum message tokens")?;
+
+        Ok(tokens.max(0) as u64)
+    }
+
+    /// Tokens and dollars spent on analyses started in `[from, to)`
+    pub async fn analysis_usage_between(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> AnyhowResult<(u64, f64)> {
+        let row = sqlx::query(
+            r#"
+            SELECT COALESCE(SUM(llm_tokens), 0) AS tokens, COALESCE(SUM(llm_cost_usd), 0.0) AS cost
+            FROM analytics_requests
+            WHERE started_at >= ? AND started_at < ?
+            "#,
+        )
+        .bind(from.to_rfc3339())
+        .bind(to.to_rfc3339())
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to sum analysis usage")?;
+
+        let tokens: i64 = row.try_get("tokens")?;
+        Ok((tokens.max(0) as u64, row.try_get("cost")?))
+    }
+
+    /// Log a crossed threshold; returns false if it was already logged for
+    /// the same period
+    pub async fn record(&self, event: &QuotaEvent) -> AnyhowResult<bool> {
+        let result = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO quota_events (kind, period_start, threshold, observed, created_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(event.kind.to_string())
+        .bind(event.period_start.to_rfc3339())
+        .bind(event.threshold)
+        .bind(event.observed)
+        .bind(event.created_at.to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .context("Failed to record quota event")?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Most recent events first
+    pub async fn list(&self, limit: i64) -> AnyhowResult<Vec<QuotaEvent>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT kind, period_start, threshold, observed, created_at
+            FROM quota_events ORDER BY created_at DESC, id DESC LIMIT ?
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list quota events")?;
+
+        rows.iter().map(row_to_event).collect()
+    }
+}
+
+fn row_to_event(row: &SqliteRow) -> AnyhowResult<QuotaEvent> {
+    let kind: String = row.try_get("kind")?;
+    let period_start: String = row.try_get("period_start")?;
+    let created_at: String = row.try_get("created_at")?;
+
+    Ok(QuotaEvent {
+        kind: kind.parse().map_err(anyhow::Error::msg)?,
+        period_start: parse_timestamp(&period_start)?,
+        threshold: row.try_get("threshold")?,
+        observed: row.try_get("observed")?,
+        created_at: parse_timestamp(&created_at)?,
+    })
+}
+
+fn parse_timestamp(value: &str) -> AnyhowResult<DateTime<Utc>> {
+    Ok(DateTime::parse_from_rfc3339(value)
+        .context("Invalid quota event timestamp")?
+        .with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{ChatSessionRepository, MessageRepository};
+    use crate::models::{ChatSession, Message, MessageRole, Provider, QuotaKind};
+    use chrono::{Duration, TimeZone};
+
+    #[tokio::test]
+    async fn test_tokens_and_events() {
+        let db = DatabaseManager::open_in_memory().await.unwrap();
+        let day = Utc.with_ymd_and_hms(2026, 3, 2, 0, 0, 0).unwrap();
+        let session = ChatSession::new(
+            Provider::ClaudeCode,
+            "/quota.jsonl".to_string(),
+            "hash".to_string(),
+            day,
+        );
+        ChatSessionRepository::new(&db)
+            .create(&session)
+            .await
+            .unwrap();
+
+        let mut estimated = Message::new(
+            session.id,
+            MessageRole::User,
+            "hi".to_string(),
+            day + Duration::hours(3),
+            1,
+        );
+        estimated.estimated_tokens = Some(40);
+        let reported = Message::new(
+            session.id,
+            MessageRole::Assistant,
+            "hello".to_string(),
+            day + Duration::hours(4),
+            2,
+        )
+        .with_token_count(60);
+        let next_day = Message::new(
+            session.id,
+            MessageRole::User,
+            "again".to_string(),
+            day + Duration::hours(25),
+            3,
+        )
+        .with_token_count(1000);
+        MessageRepository::new(&db)
+            .bulk_create(&[estimated, reported, next_day])
+            .await
+            .unwrap();
+
+        let repo = QuotaRepository::new(&db);
+        assert_eq!(
+            repo.tokens_between(day, day + Duration::days(1))
+                .await
+                .unwrap(),
+            100
+        );
+        assert_eq!(
+            repo.analysis_usage_between(day, day + Duration::days(7))
+                .await
+                .unwrap(),
+            (0, 0.0)
+        );
+
+        let event = QuotaEvent {
+            kind: QuotaKind::DailyTokens,
+            period_start: day,
+            threshold: 50.0,
+            observed: 100.0,
+            created_at: Utc::now(),
+        };
+        assert!(repo.record(&event).await.unwrap());
+        assert!(!repo.record(&event).await.unwrap());
+        assert_eq!(repo.list(10).await.unwrap(), vec![event]);
+    }
+}
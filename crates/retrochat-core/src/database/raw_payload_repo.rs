@@ -0,0 +1,136 @@
+use anyhow::{Context, Result as AnyhowResult};
+use chrono::{DateTime, Utc};
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Pool, Row, Sqlite};
+use uuid::Uuid;
+
+use super::connection::DatabaseManager;
+use crate::models::{RawPayload, RawPayloadKind, RawPayloadStats};
+
+pub struct RawPayloadRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl RawPayloadRepository {
+    pub fn new(db: &DatabaseManager) -> Self {
+        Self {
+            pool: db.pool().clone(),
+        }
+    }
+
+    /// Store payloads, replacing any already kept for the same owner
+    pub async fn bulk_create(&self, payloads: &[RawPayload]) -> AnyhowResult<()> {
+        if payloads.is_empty() {
+            return Ok(());
+        }
+
+        let mut tx = self.pool.begin().await?;
+        for payload in payloads {
+            sqlx::query(
+                r#"
+                INSERT OR REPLACE INTO raw_payloads (
+                    session_id, owner_kind, owner_id, payload, created_at
+                ) VALUES (?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(payload.session_id.to_string())
+            .bind(payload.kind.as_str())
+            .bind(&payload.owner_id)
+            .bind(&payload.payload)
+            .bind(payload.created_at.to_rfc3339())
+            .execute(&mut *tx)
+            .await
+            .context("Failed to save raw payload")?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn get(
+        &self,
+        session_id: &Uuid,
+        kind: RawPayloadKind,
+        owner_id: &str,
+    ) -> AnyhowResult<Option<RawPayload>> {
+        let row = sqlx::query(
+            r#"
+            SELECT session_id, owner_kind, owner_id, payload, created_at
+            FROM raw_payloads
+            WHERE session_id = ? AND owner_kind = ? AND owner_id = ?
+            "#,
+        )
+        .bind(session_id.to_string())
+        .bind(kind.as_str())
+        .bind(owner_id)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to fetch raw payload")?;
+
+        row.as_ref().map(row_to_payload).transpose()
+    }
+
+    pub async fn get_by_session(&self, session_id: &Uuid) -> AnyhowResult<Vec<RawPayload>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT session_id, owner_kind, owner_id, payload, created_at
+            FROM raw_payloads
+            WHERE session_id = ?
+            ORDER BY owner_kind, owner_id
+            "#,
+        )
+        .bind(session_id.to_string())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch raw payloads")?;
+
+        rows.iter().map(row_to_payload).collect()
+    }
+
+    /// Delete the payloads of one session, or of all sessions
+    pub async fn delete(&self, session_id: Option<&Uuid>) -> AnyhowResult<u64> {
+        let result = sqlx::query("DELETE FROM raw_payloads WHERE ?1 IS NULL OR session_id = ?1")
+            .bind(session_id.map(Uuid::to_string))
+            .execute(&self.pool)
+            .await
+            .context("Failed to delete raw payloads")?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn stats(&self) -> AnyhowResult<RawPayloadStats> {
+        let row = sqlx::query(
+            r#"
+            SELECT COUNT(DISTINCT session_id) AS sessions,
+                   COALESCE(SUM(owner_kind = 'message'), 0) AS messages,
+                   COALESCE(SUM(owner_kind = 'tool_call'), 0) AS tool_calls,
+                   COALESCE(SUM(LENGTH(CAST(payload AS BLOB))), 0) AS bytes
+            FROM raw_payloads
+            "#,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to count raw payloads")?;
+
+        Ok(RawPayloadStats {
+            sessions: row.try_get::<i64, _>("sessions")? as u64,
+            messages: row.try_get::<i64, _>("messages")? as u64,
+            tool_calls: row.try_get::<i64, _>("tool_calls")? as u64,
+            bytes: row.try_get::<i64, _>("bytes")? as u64,
+        })
+    }
+}
+
+fn row_to_payload(row: &SqliteRow) -> AnyhowResult<RawPayload> {
+    let session_id: String = row.try_get("session_id")?;
+    let kind: String = row.try_get("owner_kind")?;
+    let created_at: String = row.try_get("created_at")?;
+
+    Ok(RawPayload {
+        session_id: Uuid::parse_str(&session_id).context("Invalid session ID")?,
+        kind: kind.parse().map_err(anyhow::Error::msg)?,
+        owner_id: row.try_get("owner_id")?,
+        payload: row.try_get("payload")?,
+        created_at: DateTime::parse_from_rfc3339(&created_at)
+            .context("Invalid created_at format")?
+            .with_timezone(&Utc),
+    })
+}
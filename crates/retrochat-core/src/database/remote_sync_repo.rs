@@ -0,0 +1,232 @@
+use anyhow::{Context, Result as AnyhowResult};
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Row, Sqlite};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use super::connection::DatabaseManager;
+
+/// Whether a bundle was uploaded from here or merged from the remote
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BundleDirection {
+    Push,
+    Pull,
+}
+
+impl BundleDirection {
+    fn as_str(&self) -> &'static str {
+        match self {
+            BundleDirection::Push => "push",
+            BundleDirection::Pull => "pull",
+        }
+    }
+}
+
+/// Sync history with one remote
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RemoteSyncSummary {
+    pub pushed_bundles: i64,
+    pub pulled_bundles: i64,
+    pub last_push: Option<DateTime<Utc>>,
+    pub last_pull: Option<DateTime<Utc>>,
+}
+
+/// What each sync remote has seen, in `remote_sessions` and `remote_bundles`
+pub struct RemoteSyncRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl RemoteSyncRepository {
+    pub fn new(db: &DatabaseManager) -> Self {
+        Self {
+            pool: db.pool().clone(),
+        }
+    }
+
+    /// Sessions the remote hasn't seen, or has seen with a different message
+    /// count, with their current message count
+    pub async fn unsynced_sessions(&self, remote: &str) -> AnyhowResult<Vec<(Uuid, i64)>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT s.id, s.message_count
+            FROM chat_sessions s
+            LEFT JOIN remote_sessions r ON r.remote = ? AND r.session_id = s.id
+            WHERE r.session_id IS NULL OR r.message_count != s.message_count
+            ORDER BY s.start_time
+            "#,
+        )
+        .bind(remote)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to find sessions to push")?;
+
+        rows.iter()
+            .map(|row| {
+                let id: String = row.try_get("id")?;
+                Ok((Uuid::parse_str(&id)?, row.try_get("message_count")?))
+            })
+            .collect()
+    }
+
+    /// Remember that the remote has these sessions with these message counts
+    pub async fn mark_synced(&self, remote: &str, sessions: &[(Uuid, i64)]) -> AnyhowResult<()> {
+        let synced_at = Utc::now().to_rfc3339();
+        let mut tx = self.pool.begin().await?;
+        for (id, message_count) in sessions {
+            sqlx::query(
+                r#"
+                INSERT INTO remote_sessions (remote, session_id, message_count, synced_at)
+                VALUES (?, ?, ?, ?)
+                ON CONFLICT(remote, session_id) DO UPDATE SET
+                    message_count = excluded.message_count,
+                    synced_at = excluded.synced_at
+                "#,
+            )
+            .bind(remote)
+            .bind(id.to_string())
+            .bind(message_count)
+            .bind(&synced_at)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to record synced sessions")?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Names of bundles pushed to or merged from the remote
+    pub async fn known_bundles(&self, remote: &str) -> AnyhowResult<HashSet<String>> {
+        let names: Vec<String> =
+            sqlx::query_scalar("SELECT name FROM remote_bundles WHERE remote = ?")
+                .bind(remote)
+                .fetch_all(&self.pool)
+                .await
+                .context("Failed to list synced bundles")?;
+        Ok(names.into_iter().collect())
+    }
+
+    pub async fn record_bundle(
+        &self,
+        remote: &str,
+        name: &str,
+        direction: BundleDirection,
+        sessions: usize,
+    ) -> AnyhowResult<()> {
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO remote_bundles (remote, name, direction, sessions, created_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(remote)
+        .bind(name)
+        .bind(direction.as_str())
+        .bind(sessions as i64)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .context("Failed to record synced bundle")?;
+        Ok(())
+    }
+
+    pub async fn summary(&self, remote: &str) -> AnyhowResult<RemoteSyncSummary> {
+        let row = sqlx::query(
+            r#"
+            SELECT
+                COUNT(CASE WHEN direction = 'push' THEN 1 END) AS pushed,
+                COUNT(CASE WHEN direction = 'pull' THEN 1 END) AS pulled,
+                MAX(CASE WHEN direction = 'push' THEN created_at END) AS last_push,
+                MAX(CASE WHEN direction = 'pull' THEN created_at END) AS last_pull
+            FROM remote_bundles WHERE remote = ?
+            "#,
+        )
+        .bind(remote)
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to summarize sync history")?;
+
+        let parse = |value: Option<String>| {
+            value
+                .and_then(|v| DateTime::parse_from_rfc3339(&v).ok())
+                .map(|t| t.with_timezone(&Utc))
+        };
+        Ok(RemoteSyncSummary {
+            pushed_bundles: row.try_get("pushed")?,
+            pulled_bundles: row.try_get("pulled")?,
+            last_push: parse(row.try_get("last_push")?),
+            last_pull: parse(row.try_get("last_pull")?),
+        })
+    }
+
+    /// Drop the sync history of a removed remote
+    pub async fn forget(&self, remote: &str) -> AnyhowResult<()> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM remote_sessions WHERE remote = ?")
+            .bind(remote)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM remote_bundles WHERE remote = ?")
+            .bind(remote)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await.context("Failed to forget remote")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::ChatSessionRepository;
+    use crate::models::{ChatSession, Provider};
+
+    #[tokio::test]
+    async fn test_unsynced_sessions_and_bundles() {
+        let db = DatabaseManager::open_in_memory().await.unwrap();
+        let mut session = ChatSession::new(
+            Provider::ClaudeCode,
+            "/sync.jsonl".to_string(),
+            "hash".to_string(),
+            Utc::now(),
+        );
+        session.message_count = 3;
+        let sessions = ChatSessionRepository::new(&db);
+        sessions.create(&session).await.unwrap();
+
+        let repo = RemoteSyncRepository::new(&db);
+        assert_eq!(
+            repo.unsynced_sessions("nas").await.unwrap(),
+            vec![(session.id, 3)]
+        );
+
+        repo.mark_synced("nas", &[(session.id, 3)]).await.unwrap();
+        assert!(repo.unsynced_sessions("nas").await.unwrap().is_empty());
+        // Other remotes are tracked separately
+        assert_eq!(repo.unsynced_sessions("s3").await.unwrap().len(), 1);
+
+        session.message_count = 5;
+        sessions.update(&session).await.unwrap();
+        assert_eq!(
+            repo.unsynced_sessions("nas").await.unwrap(),
+            vec![(session.id, 5)]
+        );
+
+        repo.record_bundle("nas", "b1", BundleDirection::Push, 1)
+            .await
+            .unwrap();
+        repo.record_bundle("nas", "b2", BundleDirection::Pull, 4)
+            .await
+            .unwrap();
+        assert_eq!(repo.known_bundles("nas").await.unwrap().len(), 2);
+        let summary = repo.summary("nas").await.unwrap();
+        assert_eq!((summary.pushed_bundles, summary.pulled_bundles), (1, 1));
+        assert!(summary.last_push.is_some() && summary.last_pull.is_some());
+
+        repo.forget("nas").await.unwrap();
+        assert!(repo.known_bundles("nas").await.unwrap().is_empty());
+        assert_eq!(
+            repo.summary("nas").await.unwrap(),
+            RemoteSyncSummary::default()
+        );
+    }
+}
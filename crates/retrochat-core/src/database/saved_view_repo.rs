@@ -0,0 +1,163 @@
+use anyhow::{Context, Result as AnyhowResult};
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use sqlx::{sqlite::SqliteRow, Pool, Row, Sqlite};
+use uuid::Uuid;
+
+use super::connection::DatabaseManager;
+use super::event_repo::EventRepository;
+use crate::models::{EventKind, SavedView};
+
+pub struct SavedViewRepository {
+    pool: Pool<Sqlite>,
+    events: EventRepository,
+}
+
+impl SavedViewRepository {
+    pub fn new(db: &DatabaseManager) -> Self {
+        Self {
+            pool: db.pool().clone(),
+            events: EventRepository::new(db),
+        }
+    }
+
+    /// Save a view, replacing the expression of an existing view with the same name
+    pub async fn save(&self, name: &str, expression: &str) -> AnyhowResult<SavedView> {
+        let view = SavedView::new(name.to_string(), expression.to_string());
+
+        sqlx::query(
+            r#"
+            INSERT INTO saved_views (id, name, expression, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT(name) DO UPDATE SET
+                expression = excluded.expression,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(view.id.to_string())
+        .bind(&view.name)
+        .bind(&view.expression)
+        .bind(view.created_at.to_rfc3339())
+        .bind(view.updated_at.to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .context("Failed to save view")?;
+
+        let view = self
+            .get_by_name(name)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Saved view disappeared: {name}"))?;
+        self.events
+            .record(
+                EventKind::ViewSaved,
+                None,
+                json!({ "name": view.name, "expression": view.expression }),
+            )
+            .await;
+        Ok(view)
+    }
+
+    pub async fn get_by_name(&self, name: &str) -> AnyhowResult<Option<SavedView>> {
+        let row = sqlx::query(
+            "SELECT id, name, expression, created_at, updated_at FROM saved_views WHERE name = ?",
+        )
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to fetch saved view")?;
+
+        row.map(|row| self.row_to_saved_view(&row)).transpose()
+    }
+
+    /// List saved views by name
+    pub async fn list(&self) -> AnyhowResult<Vec<SavedView>> {
+        let rows = sqlx::query(
+            "SELECT id, name, expression, created_at, updated_at FROM saved_views ORDER BY name",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list saved views")?;
+
+        rows.iter().map(|row| self.row_to_saved_view(row)).collect()
+    }
+
+    pub async fn delete(&self, name: &str) -> AnyhowResult<bool> {
+        let result = sqlx::query("DELETE FROM saved_views WHERE name = ?")
+            .bind(name)
+            .execute(&self.pool)
+            .await
+            .context("Failed to delete saved view")?;
+
+        let deleted = result.rows_affected() > 0;
+        if deleted {
+            self.events
+                .record(EventKind::ViewDeleted, None, json!({ "name": name }))
+                .await;
+        }
+        Ok(deleted)
+    }
+
+    fn row_to_saved_view(&self, row: &SqliteRow) -> AnyhowResult<SavedView> {
+        let id_str: String = row.try_get("id")?;
+        let name: String = row.try_get("name")?;
+        let expression: String = row.try_get("expression")?;
+        let created_at_str: String = row.try_get("created_at")?;
+        let updated_at_str: String = row.try_get("updated_at")?;
+
+        let id = Uuid::parse_str(&id_str).context("Invalid saved view ID format")?;
+
+        let created_at = DateTime::parse_from_rfc3339(&created_at_str)
+            .context("Invalid created_at timestamp format")?
+            .with_timezone(&Utc);
+
+        let updated_at = DateTime::parse_from_rfc3339(&updated_at_str)
+            .context("Invalid updated_at timestamp format")?
+            .with_timezone(&Utc);
+
+        Ok(SavedView {
+            id,
+            name,
+            expression,
+            created_at,
+            updated_at,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_save_replaces_expression_by_name() {
+        let db = DatabaseManager::open_in_memory().await.unwrap();
+        let repo = SavedViewRepository::new(&db);
+
+        let first = repo.save("big", "tokens>100k").await.unwrap();
+        let second = repo
+            .save("big", "tokens>100k outcome:abandoned")
+            .await
+            .unwrap();
+        repo.save("claude", "provider:claude").await.unwrap();
+
+        assert_eq!(first.id, second.id);
+        assert_eq!(second.expression, "tokens>100k outcome:abandoned");
+        assert!(second.updated_at >= first.updated_at);
+
+        let views = repo.list().await.unwrap();
+        let names: Vec<_> = views.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, vec!["big", "claude"]);
+    }
+
+    #[tokio::test]
+    async fn test_delete_removes_view() {
+        let db = DatabaseManager::open_in_memory().await.unwrap();
+        let repo = SavedViewRepository::new(&db);
+
+        repo.save("big", "tokens>100k").await.unwrap();
+
+        assert!(repo.delete("big").await.unwrap());
+        assert!(!repo.delete("big").await.unwrap());
+        assert!(repo.get_by_name("big").await.unwrap().is_none());
+    }
+}
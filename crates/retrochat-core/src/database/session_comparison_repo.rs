@@ -0,0 +1,126 @@
+use anyhow::{Context, Result as AnyhowResult};
+use chrono::{DateTime, Utc};
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Pool, Row, Sqlite};
+
+use super::connection::DatabaseManager;
+use crate::models::SessionComparison;
+use crate::services::analytics::SessionComparisonOutput;
+
+pub struct SessionComparisonRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SessionComparisonRepository {
+    pub fn new(db: &DatabaseManager) -> Self {
+        Self {
+            pool: db.pool().clone(),
+        }
+    }
+
+    pub async fn create(&self, comparison: &SessionComparison) -> AnyhowResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO session_comparisons (
+                analytics_request_id, session_id, other_session_id
+            ) VALUES (?, ?, ?)
+            "#,
+        )
+        .bind(&comparison.analytics_request_id)
+        .bind(&comparison.session_id)
+        .bind(&comparison.other_session_id)
+        .execute(&self.pool)
+        .await
+        .context("Failed to insert session comparison")?;
+        Ok(())
+    }
+
+    /// Store the result of a completed comparison
+    pub async fn save_result(
+        &self,
+        analytics_request_id: &str,
+        result: &SessionComparisonOutput,
+        model_used: Option<&str>,
+        analysis_duration_ms: i64,
+    ) -> AnyhowResult<()> {
+        let result_json =
+            serde_json::to_string(result).context("Failed to serialize comparison result")?;
+        sqlx::query(
+            r#"
+            UPDATE session_comparisons
+            SET result_json = ?, model_used = ?, analysis_duration_ms = ?, generated_at = ?
+            WHERE analytics_request_id = ?
+            "#,
+        )
+        .bind(result_json)
+        .bind(model_used)
+        .bind(analysis_duration_ms)
+        .bind(Utc::now().to_rfc3339())
+        .bind(analytics_request_id)
+        .execute(&self.pool)
+        .await
+        .context("Failed to save comparison result")?;
+        Ok(())
+    }
+
+    pub async fn get_by_request_id(
+        &self,
+        analytics_request_id: &str,
+    ) -> AnyhowResult<Option<SessionComparison>> {
+        let row = sqlx::query(
+            r#"
+            SELECT analytics_request_id, session_id, other_session_id, result_json,
+                   model_used, analysis_duration_ms, generated_at
+            FROM session_comparisons
+            WHERE analytics_request_id = ?
+            "#,
+        )
+        .bind(analytics_request_id)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to fetch session comparison")?;
+
+        row.as_ref().map(row_to_comparison).transpose()
+    }
+
+    /// Comparisons involving a session on either side, newest first
+    pub async fn get_by_session(&self, session_id: &str) -> AnyhowResult<Vec<SessionComparison>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT c.analytics_request_id, c.session_id, c.other_session_id, c.result_json,
+                   c.model_used, c.analysis_duration_ms, c.generated_at
+            FROM session_comparisons c
+            JOIN analytics_requests r ON r.id = c.analytics_request_id
+            WHERE c.session_id = ?1 OR c.other_session_id = ?1
+            ORDER BY r.started_at DESC
+            "#,
+        )
+        .bind(session_id)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch session comparisons")?;
+
+        rows.iter().map(row_to_comparison).collect()
+    }
+}
+
+fn row_to_comparison(row: &SqliteRow) -> AnyhowResult<SessionComparison> {
+    let result_json: Option<String> = row.try_get("result_json")?;
+    let generated_at: Option<String> = row.try_get("generated_at")?;
+
+    Ok(SessionComparison {
+        analytics_request_id: row.try_get("analytics_request_id")?,
+        session_id: row.try_get("session_id")?,
+        other_session_id: row.try_get("other_session_id")?,
+        result: result_json
+            .map(|json| serde_json::from_str(&json))
+            .transpose()
+            .context("Failed to deserialize comparison result")?,
+        model_used: row.try_get("model_used")?,
+        analysis_duration_ms: row.try_get("analysis_duration_ms")?,
+        generated_at: generated_at
+            .map(|at| DateTime::parse_from_rfc3339(&at).map(|at| at.with_timezone(&Utc)))
+            .transpose()
+            .context("Invalid generated_at format")?,
+    })
+}
@@ -0,0 +1,173 @@
+use anyhow::{Context, Result as AnyhowResult};
+use chrono::{DateTime, Utc};
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Pool, Row, Sqlite};
+use uuid::Uuid;
+
+use super::connection::DatabaseManager;
+use crate::models::SessionQuality;
+
+const QUALITY_COLUMNS: &str = "q.session_id, q.user_prompts, q.user_corrections, \
+     q.tool_retry_loops, q.tool_errors, q.tool_successes, q.short_prompts, q.medium_prompts, \
+     q.long_prompts, q.median_prompt_chars, q.computed_at";
+
+pub struct SessionQualityRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SessionQualityRepository {
+    pub fn new(db: &DatabaseManager) -> Self {
+        Self {
+            pool: db.pool().clone(),
+        }
+    }
+
+    /// Store a session's signals, replacing earlier ones
+    pub async fn upsert(&self, quality: &SessionQuality) -> AnyhowResult<()> {
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO session_quality (
+                session_id, user_prompts, user_corrections, tool_retry_loops,
+                tool_errors, tool_successes, short_prompts, medium_prompts,
+                long_prompts, median_prompt_chars, computed_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(quality.session_id.to_string())
+        .bind(quality.user_prompts as i64)
+        .bind(quality.user_corrections as i64)
+        .bind(quality.tool_retry_loops as i64)
+        .bind(quality.tool_errors as i64)
+        .bind(quality.tool_successes as i64)
+        .bind(quality.short_prompts as i64)
+        .bind(quality.medium_prompts as i64)
+        .bind(quality.long_prompts as i64)
+        .bind(quality.median_prompt_chars as i64)
+        .bind(quality.computed_at.to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .context("Failed to save session quality")?;
+        Ok(())
+    }
+
+    pub async fn get_by_session(&self, session_id: &Uuid) -> AnyhowResult<Option<SessionQuality>> {
+        let row = sqlx::query(&format!(
+            "SELECT {QUALITY_COLUMNS} FROM session_quality q WHERE q.session_id = ?"
+        ))
+        .bind(session_id.to_string())
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to fetch session quality")?;
+
+        row.as_ref().map(row_to_quality).transpose()
+    }
+
+    /// Signals of sessions started since `since` in `project`, newest first
+    pub async fn list(
+        &self,
+        since: Option<DateTime<Utc>>,
+        project: Option<&str>,
+    ) -> AnyhowResult<Vec<SessionQuality>> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {QUALITY_COLUMNS}
+            FROM session_quality q
+            JOIN chat_sessions s ON s.id = q.session_id
+            WHERE (?1 IS NULL OR s.start_time >= ?1)
+              AND (?2 IS NULL OR s.project_name = ?2)
+            ORDER BY s.start_time DESC
+            "#
+        ))
+        .bind(since.map(|t| t.to_rfc3339()))
+        .bind(project)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list session quality")?;
+
+        rows.iter().map(row_to_quality).collect()
+    }
+
+    /// Sessions imported before quality signals were computed
+    pub async fn sessions_without_quality(&self) -> AnyhowResult<Vec<Uuid>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT s.id
+            FROM chat_sessions s
+            LEFT JOIN session_quality q ON q.session_id = s.id
+            WHERE q.session_id IS NULL
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to find sessions without quality signals")?;
+
+        rows.iter()
+            .map(|row| {
+                let id: String = row.try_get("id")?;
+                Uuid::parse_str(&id).context("Invalid session ID in chat_sessions")
+            })
+            .collect()
+    }
+}
+
+fn row_to_quality(row: &SqliteRow) -> AnyhowResult<SessionQuality> {
+    let count = |column: &str| -> AnyhowResult<u32> { Ok(row.try_get::<i64, _>(column)? as u32) };
+    let session_id: String = row.try_get("session_id")?;
+    let computed_at: String = row.try_get("computed_at")?;
+    Ok(SessionQuality {
+        session_id: Uuid::parse_str(&session_id)
+            .context("Invalid session ID in session_quality")?,
+        user_prompts: count("user_prompts")?,
+        user_corrections: count("user_corrections")?,
+        tool_retry_loops: count("tool_retry_loops")?,
+        tool_errors: count("tool_errors")?,
+        tool_successes: count("tool_successes")?,
+        short_prompts: count("short_prompts")?,
+        medium_prompts: count("medium_prompts")?,
+        long_prompts: count("long_prompts")?,
+        median_prompt_chars: count("median_prompt_chars")?,
+        computed_at: DateTime::parse_from_rfc3339(&computed_at)
+            .context("Invalid quality computation time")?
+            .with_timezone(&Utc),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::ChatSessionRepository;
+    use crate::models::{ChatSession, Provider};
+
+    #[tokio::test]
+    async fn test_upsert_and_list() {
+        let db = DatabaseManager::open_in_memory().await.unwrap();
+        let session = ChatSession::new(
+            Provider::ClaudeCode,
+            "/tmp/session.jsonl".to_string(),
+            "hash".to_string(),
+            Utc::now(),
+        );
+        ChatSessionRepository::new(&db)
+            .create(&session)
+            .await
+            .unwrap();
+
+        let repo = SessionQualityRepository::new(&db);
+        assert_eq!(
+            repo.sessions_without_quality().await.unwrap(),
+            vec![session.id]
+        );
+
+        let mut quality = SessionQuality::from_messages(session.id, &[], &[]);
+        quality.user_corrections = 2;
+        repo.upsert(&quality).await.unwrap();
+        quality.user_corrections = 3;
+        repo.upsert(&quality).await.unwrap();
+
+        let stored = repo.get_by_session(&session.id).await.unwrap().unwrap();
+        assert_eq!(stored.user_corrections, 3);
+        assert_eq!(repo.list(None, None).await.unwrap().len(), 1);
+        assert!(repo.list(None, Some("other")).await.unwrap().is_empty());
+        assert!(repo.sessions_without_quality().await.unwrap().is_empty());
+    }
+}
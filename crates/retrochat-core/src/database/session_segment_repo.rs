@@ -0,0 +1,106 @@
+use anyhow::{Context, Result as AnyhowResult};
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Row, Sqlite};
+use uuid::Uuid;
+
+use super::connection::DatabaseManager;
+use crate::models::SessionSegment;
+
+pub struct SessionSegmentRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SessionSegmentRepository {
+    pub fn new(db: &DatabaseManager) -> Self {
+        Self {
+            pool: db.pool().clone(),
+        }
+    }
+
+    /// Replace a session's segments, as after a re-import
+    pub async fn replace_for_session(
+        &self,
+        session_id: &Uuid,
+        segments: &[SessionSegment],
+    ) -> AnyhowResult<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        sqlx::query("DELETE FROM session_segments WHERE session_id = ?")
+            .bind(session_id.to_string())
+            .execute(&mut *tx)
+            .await
+            .context("Failed to delete session segments")?;
+
+        for segment in segments {
+            sqlx::query(
+                r#"
+                INSERT INTO session_segments (
+                    session_id, segment_index, start_sequence, end_sequence,
+                    started_at, ended_at, trigger, pre_compaction_tokens
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(session_id.to_string())
+            .bind(segment.segment_index as i64)
+            .bind(segment.start_sequence as i64)
+            .bind(segment.end_sequence as i64)
+            .bind(segment.started_at.to_rfc3339())
+            .bind(segment.ended_at.to_rfc3339())
+            .bind(&segment.trigger)
+            .bind(segment.pre_compaction_tokens.map(|t| t as i64))
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert session segment")?;
+        }
+
+        tx.commit()
+            .await
+            .context("Failed to commit session segments")?;
+        Ok(())
+    }
+
+    pub async fn get_by_session(&self, session_id: &Uuid) -> AnyhowResult<Vec<SessionSegment>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT session_id, segment_index, start_sequence, end_sequence,
+                   started_at, ended_at, trigger, pre_compaction_tokens
+            FROM session_segments
+            WHERE session_id = ?
+            ORDER BY segment_index
+            "#,
+        )
+        .bind(session_id.to_string())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch session segments")?;
+
+        rows.iter()
+            .map(|row| {
+                let session_id: String = row.try_get("session_id")?;
+                let started_at: String = row.try_get("started_at")?;
+                let ended_at: String = row.try_get("ended_at")?;
+                Ok(SessionSegment {
+                    session_id: Uuid::parse_str(&session_id)
+                        .context("Invalid session ID in session_segments")?,
+                    segment_index: row.try_get::<i64, _>("segment_index")? as u32,
+                    start_sequence: row.try_get::<i64, _>("start_sequence")? as u32,
+                    end_sequence: row.try_get::<i64, _>("end_sequence")? as u32,
+                    started_at: DateTime::parse_from_rfc3339(&started_at)
+                        .context("Invalid segment start")?
+                        .with_timezone(&Utc),
+                    ended_at: DateTime::parse_from_rfc3339(&ended_at)
+                        .context("Invalid segment end")?
+                        .with_timezone(&Utc),
+                    trigger: row.try_get("trigger")?,
+                    pre_compaction_tokens: row
+                        .try_get::<Option<i64>, _>("pre_compaction_tokens")?
+                        .map(|t| t as u64),
+                })
+            })
+            .collect()
+    }
+}
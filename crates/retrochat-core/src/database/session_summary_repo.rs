@@ -274,6 +274,25 @@ impl SessionSummaryRepository {
         rows.iter().map(Self::row_to_session_summary).collect()
     }
 
+    /// Outcome of every summarized session that has one
+    pub async fn get_outcomes(&self) -> AnyhowResult<Vec<(Uuid, SessionOutcome)>> {
+        let rows = sqlx::query(
+            "SELECT session_id, outcome FROM session_summaries WHERE outcome IS NOT NULL",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to load session outcomes")?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| {
+                let session_id: String = row.get("session_id");
+                let outcome: String = row.get("outcome");
+                Some((Uuid::parse_str(&session_id).ok()?, outcome.parse().ok()?))
+            })
+            .collect())
+    }
+
     /// Convert a database row to SessionSummary
     fn row_to_session_summary(row: &sqlx::sqlite::SqliteRow) -> AnyhowResult<SessionSummary> {
         let generated_at_str: String = row.get("generated_at");
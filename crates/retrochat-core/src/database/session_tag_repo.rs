@@ -0,0 +1,97 @@
+use anyhow::{Context, Result as AnyhowResult};
+use chrono::Utc;
+use sqlx::{Pool, Row, Sqlite};
+use uuid::Uuid;
+
+use super::connection::DatabaseManager;
+
+pub struct SessionTagRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SessionTagRepository {
+    pub fn new(db: &DatabaseManager) -> Self {
+        Self {
+            pool: db.pool().clone(),
+        }
+    }
+
+    /// Replace a session's tags
+    pub async fn set_tags(&self, session_id: &Uuid, tags: &[String]) -> AnyhowResult<()> {
+        let now = Utc::now().to_rfc3339();
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM session_tags WHERE session_id = ?")
+            .bind(session_id.to_string())
+            .execute(&mut *tx)
+            .await
+            .context("Failed to clear session tags")?;
+
+        for tag in tags {
+            sqlx::query(
+                "INSERT OR IGNORE INTO session_tags (session_id, tag, created_at) VALUES (?, ?, ?)",
+            )
+            .bind(session_id.to_string())
+            .bind(tag)
+            .bind(&now)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to save session tag")?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// A session's tags, sorted by name
+    pub async fn get_by_session(&self, session_id: &Uuid) -> AnyhowResult<Vec<String>> {
+        let rows = sqlx::query("SELECT tag FROM session_tags WHERE session_id = ? ORDER BY tag")
+            .bind(session_id.to_string())
+            .fetch_all(&self.pool)
+            .await
+            .context("Failed to fetch session tags")?;
+
+        rows.iter()
+            .map(|row| row.try_get("tag").map_err(Into::into))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::ChatSessionRepository;
+    use crate::models::{ChatSession, Provider};
+
+    #[tokio::test]
+    async fn test_set_tags_replaces_previous() {
+        let db = DatabaseManager::open_in_memory().await.unwrap();
+        let session = ChatSession::new(
+            Provider::ClaudeCode,
+            "/tmp/session.jsonl".to_string(),
+            "hash".to_string(),
+            Utc::now(),
+        );
+        ChatSessionRepository::new(&db)
+            .create(&session)
+            .await
+            .unwrap();
+
+        let repo = SessionTagRepository::new(&db);
+        repo.set_tags(&session.id, &["work".to_string(), "client-x".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(
+            repo.get_by_session(&session.id).await.unwrap(),
+            vec!["client-x", "work"]
+        );
+
+        repo.set_tags(&session.id, &["work".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(
+            repo.get_by_session(&session.id).await.unwrap(),
+            vec!["work"]
+        );
+    }
+}
@@ -1,10 +1,47 @@
 use anyhow::{Context, Result as AnyhowResult};
 use chrono::{DateTime, Utc};
+use serde::Serialize;
 use sqlx::{sqlite::SqliteRow, Pool, Row, Sqlite};
 use uuid::Uuid;
 
 use super::connection::DatabaseManager;
-use crate::models::ToolOperation;
+use crate::models::{ToolOperation, ToolSource};
+
+/// Filters for querying tool operations across sessions
+#[derive(Debug, Clone, Default)]
+pub struct ToolOperationFilter {
+    /// Exact tool name (case-insensitive), e.g. "Bash" or "Edit"
+    pub tool_name: Option<String>,
+    pub success: Option<bool>,
+    /// Substring of the affected file path
+    pub file_path: Option<String>,
+    pub session_id: Option<Uuid>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+/// How often one MCP server's tools were called, and how often they failed
+#[derive(Debug, Clone, Serialize)]
+pub struct McpServerStats {
+    pub server: String,
+    pub calls: i64,
+    pub failures: i64,
+    /// Calls whose outcome was recorded
+    pub completed: i64,
+    pub tools: i64,
+    pub sessions: i64,
+}
+
+impl McpServerStats {
+    /// Failed share of the calls with a recorded outcome
+    pub fn failure_rate(&self) -> f64 {
+        if self.completed > 0 {
+            self.failures as f64 / self.completed as f64
+        } else {
+            0.0
+        }
+    }
+}
 
 pub struct ToolOperationRepository {
     pool: Pool<Sqlite>,
@@ -41,16 +78,17 @@ impl ToolOperationRepository {
         sqlx::query(
             r#"
             INSERT INTO tool_operations (
-                id, tool_use_id, tool_name, timestamp,
+                id, tool_use_id, tool_name, tool_source, timestamp,
                 file_metadata, bash_metadata,
                 success, result_summary, raw_input, raw_result,
-                created_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                created_at, duration_ms
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(operation.id.to_string())
         .bind(&operation.tool_use_id)
         .bind(&operation.tool_name)
+        .bind(operation.tool_source.to_string())
         .bind(operation.timestamp.to_rfc3339())
         .bind(file_metadata_json)
         .bind(bash_metadata_json)
@@ -59,6 +97,7 @@ impl ToolOperationRepository {
         .bind(raw_input_json)
         .bind(raw_result_json)
         .bind(operation.created_at.to_rfc3339())
+        .bind(operation.duration_ms)
         .execute(&self.pool)
         .await
         .context("Failed to create tool operation")?;
@@ -97,16 +136,17 @@ impl ToolOperationRepository {
             sqlx::query(
                 r#"
                 INSERT INTO tool_operations (
-                    id, tool_use_id, tool_name, timestamp,
+                    id, tool_use_id, tool_name, tool_source, timestamp,
                     file_metadata, bash_metadata,
                     success, result_summary, raw_input, raw_result,
-                    created_at
-                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                    created_at, duration_ms
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
                 "#,
             )
             .bind(operation.id.to_string())
             .bind(&operation.tool_use_id)
             .bind(&operation.tool_name)
+            .bind(operation.tool_source.to_string())
             .bind(operation.timestamp.to_rfc3339())
             .bind(file_metadata_json)
             .bind(bash_metadata_json)
@@ -115,6 +155,7 @@ impl ToolOperationRepository {
             .bind(raw_input_json)
             .bind(raw_result_json)
             .bind(operation.created_at.to_rfc3339())
+            .bind(operation.duration_ms)
             .execute(&mut *tx)
             .await
             .context("Failed to create tool operation in bulk")?;
@@ -127,10 +168,10 @@ impl ToolOperationRepository {
     pub async fn get_by_id(&self, id: &Uuid) -> AnyhowResult<Option<ToolOperation>> {
         let row = sqlx::query(
             r#"
-            SELECT id, tool_use_id, tool_name, timestamp,
+            SELECT id, tool_use_id, tool_name, tool_source, timestamp,
                    file_metadata,
                    success, result_summary, raw_input, raw_result,
-                   created_at
+                   created_at, duration_ms
             FROM tool_operations
             WHERE id = ?
             "#,
@@ -152,10 +193,10 @@ impl ToolOperationRepository {
     pub async fn get_by_session(&self, session_id: &Uuid) -> AnyhowResult<Vec<ToolOperation>> {
         let rows = sqlx::query(
             r#"
-            SELECT t.id, t.tool_use_id, t.tool_name, t.timestamp,
+            SELECT DISTINCT t.id, t.tool_use_id, t.tool_name, t.tool_source, t.timestamp,
                    t.file_metadata,
                    t.success, t.result_summary, t.raw_input, t.raw_result,
-                   t.created_at
+                   t.created_at, t.duration_ms
             FROM tool_operations t
             JOIN messages m ON m.tool_operation_id = t.id
             WHERE m.session_id = ?
@@ -179,10 +220,10 @@ impl ToolOperationRepository {
     pub async fn get_by_message(&self, message_id: &Uuid) -> AnyhowResult<Vec<ToolOperation>> {
         let rows = sqlx::query(
             r#"
-            SELECT t.id, t.tool_use_id, t.tool_name, t.timestamp,
+            SELECT t.id, t.tool_use_id, t.tool_name, t.tool_source, t.timestamp,
                    t.file_metadata,
                    t.success, t.result_summary, t.raw_input, t.raw_result,
-                   t.created_at
+                   t.created_at, t.duration_ms
             FROM tool_operations t
             JOIN messages m ON m.tool_operation_id = t.id
             WHERE m.id = ?
@@ -207,10 +248,10 @@ impl ToolOperationRepository {
     pub async fn get_file_operations(&self, session_id: &Uuid) -> AnyhowResult<Vec<ToolOperation>> {
         let rows = sqlx::query(
             r#"
-            SELECT t.id, t.tool_use_id, t.tool_name, t.timestamp,
+            SELECT t.id, t.tool_use_id, t.tool_name, t.tool_source, t.timestamp,
                    t.file_metadata,
                    t.success, t.result_summary, t.raw_input, t.raw_result,
-                   t.created_at
+                   t.created_at, t.duration_ms
             FROM tool_operations t
             JOIN messages m ON m.tool_operation_id = t.id
             WHERE m.session_id = ? AND t.file_metadata IS NOT NULL
@@ -235,9 +276,10 @@ impl ToolOperationRepository {
     pub async fn get_file_history(&self, file_path: &str) -> AnyhowResult<Vec<ToolOperation>> {
         let rows = sqlx::query(
             r#"
-            SELECT id, tool_use_id, tool_name, timestamp,
+            SELECT id, tool_use_id, tool_name, tool_source, timestamp,
                    file_metadata,
-                   success, result_summary, raw_input, raw_result, created_at
+                   success, result_summary, raw_input, raw_result, created_at,
+                   duration_ms
             FROM tool_operations
             WHERE json_extract(file_metadata, '$.file_path') = ?
             ORDER BY timestamp ASC
@@ -287,6 +329,52 @@ impl ToolOperationRepository {
         Ok(stats)
     }
 
+    /// Calls per MCP server since `since`, most used first
+    ///
+    /// Operations linked to both their request and result message count once.
+    pub async fn get_mcp_server_stats(
+        &self,
+        since: Option<DateTime<Utc>>,
+    ) -> AnyhowResult<Vec<McpServerStats>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT substr(tool_source, 5) AS server,
+                   COUNT(*) AS calls,
+                   SUM(CASE WHEN success = 0 THEN 1 ELSE 0 END) AS failures,
+                   COUNT(success) AS completed,
+                   COUNT(DISTINCT tool_name) AS tools,
+                   COUNT(DISTINCT session_id) AS sessions
+            FROM (
+                SELECT DISTINCT t.id, t.tool_source, t.tool_name, t.success, m.session_id
+                FROM tool_operations t
+                JOIN messages m ON m.tool_operation_id = t.id
+                WHERE t.tool_source LIKE 'mcp:%'
+                  AND (? IS NULL OR t.timestamp >= ?)
+            )
+            GROUP BY tool_source
+            ORDER BY calls DESC, server
+            "#,
+        )
+        .bind(since.map(|t| t.to_rfc3339()))
+        .bind(since.map(|t| t.to_rfc3339()))
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch MCP server statistics")?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(McpServerStats {
+                    server: row.try_get("server")?,
+                    calls: row.try_get("calls")?,
+                    failures: row.try_get("failures")?,
+                    completed: row.try_get("completed")?,
+                    tools: row.try_get("tools")?,
+                    sessions: row.try_get("sessions")?,
+                })
+            })
+            .collect()
+    }
+
     /// Get file type statistics (code vs config vs other)
     pub async fn get_file_type_stats(&self, session_id: &Uuid) -> AnyhowResult<(i64, i64, i64)> {
         let row = sqlx::query(
@@ -383,6 +471,155 @@ impl ToolOperationRepository {
         Ok(results)
     }
 
+    /// Get tool operations within a time range, paired with their owning session ID
+    ///
+    /// An operation linked to both its request and result message is returned once.
+    pub async fn get_by_time_range(
+        &self,
+        from: &DateTime<Utc>,
+        to: &DateTime<Utc>,
+    ) -> AnyhowResult<Vec<(Uuid, ToolOperation)>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT DISTINCT t.id, t.tool_use_id, t.tool_name, t.tool_source, t.timestamp,
+                   t.file_metadata, t.bash_metadata,
+                   t.success, t.result_summary, t.raw_input, t.raw_result,
+                   t.created_at, t.duration_ms, m.session_id
+            FROM tool_operations t
+            JOIN messages m ON m.tool_operation_id = t.id
+            WHERE t.timestamp >= ? AND t.timestamp <= ?
+            ORDER BY t.timestamp ASC
+            "#,
+        )
+        .bind(from.to_rfc3339())
+        .bind(to.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch tool operations by time range")?;
+
+        let mut operations = Vec::new();
+        for row in rows {
+            let session_id_str: String = row.try_get("session_id")?;
+            let session_id =
+                Uuid::parse_str(&session_id_str).context("Invalid session ID format")?;
+            operations.push((session_id, self.row_to_tool_operation(&row)?));
+        }
+
+        Ok(operations)
+    }
+
+    /// Query tool operations matching the filter, newest first, paired with their session ID
+    pub async fn query(
+        &self,
+        filter: &ToolOperationFilter,
+        limit: i64,
+        offset: i64,
+    ) -> AnyhowResult<Vec<(Uuid, ToolOperation)>> {
+        let (where_clause, binds) = Self::filter_clause(filter);
+        let sql = format!(
+            r#"
+            SELECT DISTINCT t.id, t.tool_use_id, t.tool_name, t.tool_source, t.timestamp,
+                   t.file_metadata, t.bash_metadata,
+                   t.success, t.result_summary, t.raw_input, t.raw_result,
+                   t.created_at, t.duration_ms, m.session_id
+            FROM tool_operations t
+            JOIN messages m ON m.tool_operation_id = t.id
+            {where_clause}
+            ORDER BY t.timestamp DESC
+            LIMIT ? OFFSET ?
+            "#
+        );
+
+        let mut query_builder = sqlx::query(&sql);
+        for value in binds {
+            query_builder = query_builder.bind(value);
+        }
+
+        let rows = query_builder
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&self.pool)
+            .await
+            .context("Failed to query tool operations")?;
+
+        let mut operations = Vec::new();
+        for row in rows {
+            let session_id_str: String = row.try_get("session_id")?;
+            let session_id =
+                Uuid::parse_str(&session_id_str).context("Invalid session ID format")?;
+            operations.push((session_id, self.row_to_tool_operation(&row)?));
+        }
+
+        Ok(operations)
+    }
+
+    /// Count tool operations matching the filter
+    pub async fn count_matching(&self, filter: &ToolOperationFilter) -> AnyhowResult<i64> {
+        let (where_clause, binds) = Self::filter_clause(filter);
+        let sql = format!(
+            r#"
+            SELECT COUNT(DISTINCT t.id)
+            FROM tool_operations t
+            JOIN messages m ON m.tool_operation_id = t.id
+            {where_clause}
+            "#
+        );
+
+        let mut query_builder = sqlx::query_scalar::<_, i64>(&sql);
+        for value in binds {
+            query_builder = query_builder.bind(value);
+        }
+
+        query_builder
+            .fetch_one(&self.pool)
+            .await
+            .context("Failed to count tool operations")
+    }
+
+    fn filter_clause(filter: &ToolOperationFilter) -> (String, Vec<String>) {
+        let mut conditions = Vec::new();
+        let mut binds = Vec::new();
+
+        if let Some(tool_name) = &filter.tool_name {
+            conditions.push("t.tool_name = ? COLLATE NOCASE");
+            binds.push(tool_name.clone());
+        }
+
+        if let Some(success) = filter.success {
+            conditions.push(if success {
+                "t.success = 1"
+            } else {
+                "t.success = 0"
+            });
+        }
+
+        if let Some(file_path) = &filter.file_path {
+            conditions.push("json_extract(t.file_metadata, '$.file_path') LIKE ?");
+            binds.push(format!("%{file_path}%"));
+        }
+
+        if let Some(session_id) = &filter.session_id {
+            conditions.push("m.session_id = ?");
+            binds.push(session_id.to_string());
+        }
+
+        if let Some(from) = &filter.from {
+            conditions.push("t.timestamp >= ?");
+            binds.push(from.to_rfc3339());
+        }
+
+        if let Some(to) = &filter.to {
+            conditions.push("t.timestamp <= ?");
+            binds.push(to.to_rfc3339());
+        }
+
+        if conditions.is_empty() {
+            (String::new(), binds)
+        } else {
+            (format!("WHERE {}", conditions.join(" AND ")), binds)
+        }
+    }
+
     pub async fn delete_by_session(&self, session_id: &Uuid) -> AnyhowResult<u64> {
         let result = sqlx::query(
             r#"
@@ -403,6 +640,63 @@ impl ToolOperationRepository {
         Ok(result.rows_affected())
     }
 
+    /// Drop raw tool output for sessions that ended before `cutoff`
+    ///
+    /// Tool names, inputs, file metadata and result summaries are kept.
+    /// Teammates' sessions are read-only and left alone.
+    pub async fn purge_raw_results_before(&self, cutoff: DateTime<Utc>) -> AnyhowResult<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE tool_operations SET raw_result = NULL
+            WHERE raw_result IS NOT NULL AND id IN (
+                SELECT m.tool_operation_id
+                FROM messages m
+                JOIN chat_sessions s ON s.id = m.session_id
+                WHERE m.tool_operation_id IS NOT NULL
+                  AND s.owner IS NULL
+                  AND COALESCE(s.end_time, s.start_time) < ?
+            )
+            "#,
+        )
+        .bind(cutoff.to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .context("Failed to purge raw tool results")?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// IDs of sessions that used a tool (case-insensitive name), optionally
+    /// only counting successful or failed uses
+    pub async fn get_session_ids_using_tool(
+        &self,
+        tool_name: &str,
+        success: Option<bool>,
+    ) -> AnyhowResult<Vec<Uuid>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT DISTINCT m.session_id
+            FROM tool_operations t
+            JOIN messages m ON m.tool_operation_id = t.id
+            WHERE LOWER(t.tool_name) = LOWER(?)
+              AND (? IS NULL OR t.success = ?)
+            "#,
+        )
+        .bind(tool_name)
+        .bind(success)
+        .bind(success)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to find sessions using tool")?;
+
+        rows.iter()
+            .map(|row| {
+                let id: String = row.try_get("session_id")?;
+                Ok(Uuid::parse_str(&id)?)
+            })
+            .collect()
+    }
+
     pub async fn count_by_session(&self, session_id: &Uuid) -> AnyhowResult<i64> {
         let count: i64 = sqlx::query_scalar(
             r#"
@@ -424,6 +718,7 @@ impl ToolOperationRepository {
         let id_str: String = row.try_get("id")?;
         let tool_use_id: String = row.try_get("tool_use_id")?;
         let tool_name: String = row.try_get("tool_name")?;
+        let tool_source: String = row.try_get("tool_source")?;
         let timestamp_str: String = row.try_get("timestamp")?;
 
         let id = Uuid::parse_str(&id_str).context("Invalid tool operation ID format")?;
@@ -450,11 +745,13 @@ impl ToolOperationRepository {
         let created_at = DateTime::parse_from_rfc3339(&created_at_str)
             .context("Invalid created_at format")?
             .with_timezone(&Utc);
+        let duration_ms: Option<i64> = row.try_get("duration_ms").ok().flatten();
 
         Ok(ToolOperation {
             id,
             tool_use_id,
             tool_name,
+            tool_source: ToolSource::from(tool_source),
             timestamp,
             file_metadata,
             bash_metadata,
@@ -462,6 +759,7 @@ impl ToolOperationRepository {
             result_summary,
             raw_input,
             raw_result,
+            duration_ms,
             created_at,
         })
     }
@@ -578,4 +876,248 @@ mod tests {
         let operations = repo.get_by_session(&session_id).await.unwrap();
         assert_eq!(operations.len(), 3);
     }
+
+    #[tokio::test]
+    async fn test_query_with_filters() {
+        use crate::database::{ChatSessionRepository, MessageRepository};
+        use crate::models::{
+            BashMetadata, ChatSession, Message, MessageRole, Provider, SessionState,
+        };
+
+        let db = DatabaseManager::open_in_memory().await.unwrap();
+        let repo = ToolOperationRepository::new(&db);
+        let session_repo = ChatSessionRepository::new(&db);
+        let message_repo = MessageRepository::new(&db);
+
+        let session_id = Uuid::new_v4();
+        let mut session = ChatSession::new(
+            Provider::ClaudeCode,
+            "/test/file.jsonl".to_string(),
+            "test_hash".to_string(),
+            Utc::now(),
+        );
+        session.id = session_id;
+        session.set_state(SessionState::Imported);
+        session_repo.create(&session).await.unwrap();
+
+        let operations = [
+            ToolOperation::new("bash_ok".to_string(), "Bash".to_string(), Utc::now())
+                .with_bash_metadata(BashMetadata::new(
+                    "Build".to_string(),
+                    "cargo build".to_string(),
+                ))
+                .with_success(true),
+            ToolOperation::new("bash_fail".to_string(), "Bash".to_string(), Utc::now())
+                .with_bash_metadata(BashMetadata::new(
+                    "Test".to_string(),
+                    "cargo test".to_string(),
+                ))
+                .with_success(false),
+            ToolOperation::new("edit".to_string(), "Edit".to_string(), Utc::now())
+                .with_file_path("/repo/src/main.rs".to_string())
+                .with_success(true),
+        ];
+
+        for (i, operation) in operations.iter().enumerate() {
+            repo.create(operation).await.unwrap();
+            let message = Message::new(
+                session_id,
+                MessageRole::Assistant,
+                format!("test message {i}"),
+                Utc::now(),
+                (i + 1) as u32,
+            )
+            .with_message_type(crate::models::message::MessageType::ToolRequest)
+            .with_tool_operation(operation.id);
+            message_repo.create(&message).await.unwrap();
+        }
+
+        let failed_bash = ToolOperationFilter {
+            tool_name: Some("bash".to_string()),
+            success: Some(false),
+            ..Default::default()
+        };
+        let results = repo.query(&failed_bash, 10, 0).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, session_id);
+        assert_eq!(
+            results[0].1.bash_metadata.as_ref().unwrap().command,
+            "cargo test"
+        );
+        assert_eq!(repo.count_matching(&failed_bash).await.unwrap(), 1);
+
+        assert_eq!(
+            repo.get_session_ids_using_tool("bash", Some(false))
+                .await
+                .unwrap(),
+            vec![session_id]
+        );
+        assert!(repo
+            .get_session_ids_using_tool("Edit", Some(false))
+            .await
+            .unwrap()
+            .is_empty());
+
+        let by_path = ToolOperationFilter {
+            file_path: Some("src/main".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(repo.query(&by_path, 10, 0).await.unwrap().len(), 1);
+        assert_eq!(
+            repo.count_matching(&ToolOperationFilter::default())
+                .await
+                .unwrap(),
+            3
+        );
+
+        // Linking the result message as well must not duplicate the operation
+        let result_message = Message::new(
+            session_id,
+            MessageRole::User,
+            "tool result".to_string(),
+            Utc::now(),
+            4,
+        )
+        .with_message_type(crate::models::message::MessageType::ToolResult)
+        .with_tool_operation(operations[1].id);
+        message_repo.create(&result_message).await.unwrap();
+
+        assert_eq!(repo.query(&failed_bash, 10, 0).await.unwrap().len(), 1);
+        assert_eq!(repo.count_matching(&failed_bash).await.unwrap(), 1);
+        assert_eq!(
+            repo.query(&ToolOperationFilter::default(), 10, 0)
+                .await
+                .unwrap()
+                .len(),
+            3
+        );
+        assert_eq!(
+            repo.count_matching(&ToolOperationFilter::default())
+                .await
+                .unwrap(),
+            3
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mcp_server_stats() {
+        use crate::database::{ChatSessionRepository, MessageRepository};
+        use crate::models::{ChatSession, Message, MessageRole, Provider};
+
+        let db = DatabaseManager::open_in_memory().await.unwrap();
+        let repo = ToolOperationRepository::new(&db);
+        let session = ChatSession::new(
+            Provider::ClaudeCode,
+            "/test/file.jsonl".to_string(),
+            "test_hash".to_string(),
+            Utc::now(),
+        );
+        ChatSessionRepository::new(&db)
+            .create(&session)
+            .await
+            .unwrap();
+
+        let calls = [
+            ("mcp__github__create_issue", true),
+            ("mcp__github__list_prs", false),
+            ("mcp__github__list_prs", true),
+            ("mcp__sentry__get_issue", true),
+            ("Bash", false),
+        ];
+        for (i, (name, success)) in calls.iter().enumerate() {
+            let operation = ToolOperation::new(format!("toolu_{i}"), name.to_string(), Utc::now())
+                .with_success(*success);
+            repo.create(&operation).await.unwrap();
+            let message = Message::new(
+                session.id,
+                MessageRole::Assistant,
+                format!("call {i}"),
+                Utc::now(),
+                (i + 1) as u32,
+            )
+            .with_tool_operation(operation.id);
+            MessageRepository::new(&db).create(&message).await.unwrap();
+
+            // Claude Code links the tool result message to the operation too
+            let result = Message::new(
+                session.id,
+                MessageRole::User,
+                format!("result {i}"),
+                Utc::now(),
+                (i + 100) as u32,
+            )
+            .with_message_type(crate::models::message::MessageType::ToolResult)
+            .with_tool_operation(operation.id);
+            MessageRepository::new(&db).create(&result).await.unwrap();
+        }
+
+        let stats = repo.get_mcp_server_stats(None).await.unwrap();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].server, "github");
+        assert_eq!(stats[0].calls, 3);
+        assert_eq!(stats[0].tools, 2);
+        assert_eq!(stats[0].failures, 1);
+        assert_eq!(stats[0].sessions, 1);
+        assert!((stats[0].failure_rate() - 1.0 / 3.0).abs() < 1e-9);
+        assert_eq!(stats[1].server, "sentry");
+        assert_eq!(stats[1].failures, 0);
+
+        let future = Utc::now() + chrono::Duration::hours(1);
+        assert!(repo
+            .get_mcp_server_stats(Some(future))
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_by_time_range_returns_each_operation_once() {
+        use crate::database::{ChatSessionRepository, MessageRepository};
+        use crate::models::message::MessageType;
+        use crate::models::{ChatSession, Message, MessageRole, Provider};
+
+        let db = DatabaseManager::open_in_memory().await.unwrap();
+        let repo = ToolOperationRepository::new(&db);
+        let session = ChatSession::new(
+            Provider::ClaudeCode,
+            "/test/file.jsonl".to_string(),
+            "test_hash".to_string(),
+            Utc::now(),
+        );
+        ChatSessionRepository::new(&db)
+            .create(&session)
+            .await
+            .unwrap();
+
+        let operation = ToolOperation::new("toolu_1".to_string(), "Edit".to_string(), Utc::now())
+            .with_file_path("/repo/src/lib.rs".to_string())
+            .with_success(false);
+        repo.create(&operation).await.unwrap();
+        for (i, (role, message_type)) in [
+            (MessageRole::Assistant, MessageType::ToolRequest),
+            (MessageRole::User, MessageType::ToolResult),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let message = Message::new(
+                session.id,
+                role,
+                format!("message {i}"),
+                Utc::now(),
+                (i + 1) as u32,
+            )
+            .with_message_type(message_type)
+            .with_tool_operation(operation.id);
+            MessageRepository::new(&db).create(&message).await.unwrap();
+        }
+
+        let hour = chrono::Duration::hours(1);
+        let operations = repo
+            .get_by_time_range(&(Utc::now() - hour), &(Utc::now() + hour))
+            .await
+            .unwrap();
+        assert_eq!(operations.len(), 1);
+        assert_eq!(operations[0].0, session.id);
+    }
 }
@@ -0,0 +1,246 @@
+use anyhow::{Context, Result as AnyhowResult};
+use chrono::{DateTime, Utc};
+use sqlx::{sqlite::SqliteRow, Pool, Row, Sqlite};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use super::connection::DatabaseManager;
+use crate::models::Topic;
+
+/// A session's membership in a topic
+#[derive(Debug, Clone, PartialEq)]
+pub struct TopicAssignment {
+    pub session_id: Uuid,
+    pub topic_id: i64,
+    /// Cosine similarity to the topic centroid
+    pub similarity: f32,
+}
+
+pub struct TopicRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl TopicRepository {
+    pub fn new(db: &DatabaseManager) -> Self {
+        Self {
+            pool: db.pool().clone(),
+        }
+    }
+
+    /// Replace every topic and assignment with the result of a clustering run
+    pub async fn replace_all(
+        &self,
+        topics: &[Topic],
+        assignments: &[TopicAssignment],
+    ) -> AnyhowResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM session_topics")
+            .execute(&mut *tx)
+            .await
+            .context("Failed to clear topic assignments")?;
+        sqlx::query("DELETE FROM topics")
+            .execute(&mut *tx)
+            .await
+            .context("Failed to clear topics")?;
+
+        for topic in topics {
+            sqlx::query(
+                r#"
+                INSERT INTO topics (id, label, description, label_source, session_count, clustered_at)
+                VALUES (?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(topic.id)
+            .bind(&topic.label)
+            .bind(&topic.description)
+            .bind(topic.label_source.to_string())
+            .bind(topic.session_count)
+            .bind(topic.clustered_at.to_rfc3339())
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert topic")?;
+        }
+
+        for assignment in assignments {
+            sqlx::query(
+                "INSERT INTO session_topics (session_id, topic_id, similarity) VALUES (?, ?, ?)",
+            )
+            .bind(assignment.session_id.to_string())
+            .bind(assignment.topic_id)
+            .bind(assignment.similarity)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert topic assignment")?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// List topics, largest first
+    pub async fn list(&self) -> AnyhowResult<Vec<Topic>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, label, description, label_source, session_count, clustered_at
+            FROM topics ORDER BY session_count DESC, id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list topics")?;
+
+        rows.iter().map(row_to_topic).collect()
+    }
+
+    /// Find a topic by ID or by its label (case-insensitive)
+    pub async fn find(&self, key: &str) -> AnyhowResult<Option<Topic>> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, label, description, label_source, session_count, clustered_at
+            FROM topics
+            WHERE CAST(id AS TEXT) = ?1 OR label = ?1 COLLATE NOCASE
+            ORDER BY id
+            LIMIT 1
+            "#,
+        )
+        .bind(key.trim())
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to find topic")?;
+
+        row.as_ref().map(row_to_topic).transpose()
+    }
+
+    /// Members of every topic
+    pub async fn assignments(&self) -> AnyhowResult<Vec<TopicAssignment>> {
+        let rows = sqlx::query("SELECT session_id, topic_id, similarity FROM session_topics")
+            .fetch_all(&self.pool)
+            .await
+            .context("Failed to read topic assignments")?;
+
+        rows.iter()
+            .map(|row| {
+                let session_id: String = row.try_get("session_id")?;
+                Ok(TopicAssignment {
+                    session_id: Uuid::parse_str(&session_id)?,
+                    topic_id: row.try_get("topic_id")?,
+                    similarity: row.try_get::<f64, _>("similarity")? as f32,
+                })
+            })
+            .collect()
+    }
+
+    /// ID and label of the topic of every assigned session
+    pub async fn session_topics(&self) -> AnyhowResult<HashMap<Uuid, (i64, String)>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT st.session_id, t.id, t.label
+            FROM session_topics st JOIN topics t ON t.id = st.topic_id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to read session topics")?;
+
+        rows.iter()
+            .map(|row| {
+                let session_id: String = row.try_get("session_id")?;
+                Ok((
+                    Uuid::parse_str(&session_id)?,
+                    (row.try_get("id")?, row.try_get("label")?),
+                ))
+            })
+            .collect()
+    }
+
+    /// When the current topics were clustered, if they ever were
+    pub async fn last_clustered_at(&self) -> AnyhowResult<Option<DateTime<Utc>>> {
+        let value: Option<String> = sqlx::query_scalar("SELECT MAX(clustered_at) FROM topics")
+            .fetch_one(&self.pool)
+            .await
+            .context("Failed to read topic clustering time")?;
+
+        value.as_deref().map(parse_timestamp).transpose()
+    }
+}
+
+fn row_to_topic(row: &SqliteRow) -> AnyhowResult<Topic> {
+    let label_source: String = row.try_get("label_source")?;
+    let clustered_at: String = row.try_get("clustered_at")?;
+
+    Ok(Topic {
+        id: row.try_get("id")?,
+        label: row.try_get("label")?,
+        description: row.try_get("description")?,
+        label_source: label_source.parse().map_err(anyhow::Error::msg)?,
+        session_count: row.try_get("session_count")?,
+        clustered_at: parse_timestamp(&clustered_at)?,
+    })
+}
+
+fn parse_timestamp(value: &str) -> AnyhowResult<DateTime<Utc>> {
+    Ok(DateTime::parse_from_rfc3339(value)
+        .context("Invalid topic timestamp")?
+        .with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::ChatSessionRepository;
+    use crate::models::{ChatSession, Provider, TopicLabelSource};
+
+    #[tokio::test]
+    async fn test_replace_and_find_topics() {
+        let db = DatabaseManager::open_in_memory().await.unwrap();
+        let session = ChatSession::new(
+            Provider::ClaudeCode,
+            "/topic.jsonl".to_string(),
+            "hash".to_string(),
+            Utc::now(),
+        );
+        ChatSessionRepository::new(&db)
+            .create(&session)
+            .await
+            .unwrap();
+
+        let repo = TopicRepository::new(&db);
+        assert_eq!(repo.last_clustered_at().await.unwrap(), None);
+
+        let topic = Topic {
+            id: 7,
+            label: "Database migrations".to_string(),
+            description: None,
+            label_source: TopicLabelSource::Keywords,
+            session_count: 1,
+            clustered_at: Utc::now(),
+        };
+        repo.replace_all(
+            &[topic],
+            &[TopicAssignment {
+                session_id: session.id,
+                topic_id: 7,
+                similarity: 0.9,
+            }],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(repo.find("7").await.unwrap().unwrap().id, 7);
+        assert_eq!(
+            repo.find("database MIGRATIONS").await.unwrap().unwrap().id,
+            7
+        );
+        assert!(repo.find("8").await.unwrap().is_none());
+        assert_eq!(
+            repo.session_topics().await.unwrap()[&session.id],
+            (7, "Database migrations".to_string())
+        );
+        assert!(repo.last_clustered_at().await.unwrap().is_some());
+
+        repo.replace_all(&[], &[]).await.unwrap();
+        assert!(repo.list().await.unwrap().is_empty());
+        assert!(repo.assignments().await.unwrap().is_empty());
+    }
+}
@@ -127,6 +127,28 @@ impl TurnSummaryRepository {
         rows.iter().map(Self::row_to_turn_summary).collect()
     }
 
+    /// Get every turn summary, ordered by session and turn number
+    pub async fn list_all(&self) -> AnyhowResult<Vec<TurnSummary>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                id, session_id, turn_number,
+                start_sequence, end_sequence,
+                user_intent, assistant_action, summary,
+                turn_type, key_topics, decisions_made, code_concepts,
+                started_at, ended_at,
+                model_used, prompt_version, generated_at
+            FROM turn_summaries
+            ORDER BY session_id, turn_number ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list turn summaries")?;
+
+        rows.iter().map(Self::row_to_turn_summary).collect()
+    }
+
     /// Get a specific turn summary by session and turn number
     pub async fn get_by_session_and_turn(
         &self,
@@ -11,6 +11,9 @@ pub mod logging {
     /// Log file path for file-based logging
     pub const LOG_FILE: &str = "RETROCHAT_LOG_FILE";
 
+    /// Log line format: "text" (default) or "json"
+    pub const LOG_FORMAT: &str = "RETROCHAT_LOG_FORMAT";
+
     /// Disable colored output (follows the NO_COLOR standard)
     pub const NO_COLOR: &str = "NO_COLOR";
 }
@@ -31,6 +34,26 @@ pub mod providers {
 pub mod apis {
     /// Google AI API key for analysis
     pub const GOOGLE_AI_API_KEY: &str = "GOOGLE_AI_API_KEY";
+
+    /// OpenAI API key for the `openai` embedding provider
+    pub const OPENAI_API_KEY: &str = "OPENAI_API_KEY";
+
+    /// Voyage AI API key for the `voyage` embedding provider
+    pub const VOYAGE_API_KEY: &str = "VOYAGE_API_KEY";
+
+    /// GitHub token with the `gist` scope for `retrochat share --gist`
+    pub const GITHUB_TOKEN: &str = "GITHUB_TOKEN";
+}
+
+/// Remote sync configuration
+pub mod sync {
+    /// Passphrase sync bundles are encrypted with
+    pub const SYNC_PASSPHRASE: &str = "RETROCHAT_SYNC_PASSPHRASE";
+
+    /// S3 credentials and region, used when a remote doesn't set them
+    pub const AWS_ACCESS_KEY_ID: &str = "AWS_ACCESS_KEY_ID";
+    pub const AWS_SECRET_ACCESS_KEY: &str = "AWS_SECRET_ACCESS_KEY";
+    pub const AWS_REGION: &str = "AWS_REGION";
 }
 
 /// System environment variables
@@ -43,6 +66,9 @@ pub mod system {
 pub mod database {
     /// Database file path (overrides default ~/.retrochat/retrochat.db)
     pub const RETROCHAT_DB: &str = "RETROCHAT_DB";
+
+    /// Passphrase of an encrypted database (overrides the OS keychain)
+    pub const DB_PASSPHRASE: &str = "RETROCHAT_DB_PASSPHRASE";
 }
 
 /// LLM provider configuration
@@ -60,3 +86,21 @@ pub mod llm {
     /// Custom path to Gemini CLI binary (defaults to "gemini" in PATH)
     pub const GEMINI_CLI_PATH: &str = "GEMINI_CLI_PATH";
 }
+
+/// MCP server configuration
+pub mod mcp {
+    /// Maximum size of a single tool result in bytes
+    pub const MAX_RESPONSE_BYTES: &str = "RETROCHAT_MCP_MAX_RESPONSE_BYTES";
+
+    /// Maximum size of a single tool result in (approximate) tokens
+    pub const MAX_RESPONSE_TOKENS: &str = "RETROCHAT_MCP_MAX_RESPONSE_TOKENS";
+
+    /// Maximum length of a single string field before head/tail truncation
+    pub const MAX_FIELD_CHARS: &str = "RETROCHAT_MCP_MAX_FIELD_CHARS";
+}
+
+/// HTTP API configuration (`retrochat serve`)
+pub mod serve {
+    /// Bearer token clients must send to the API
+    pub const SERVE_TOKEN: &str = "RETROCHAT_SERVE_TOKEN";
+}
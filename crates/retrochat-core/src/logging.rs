@@ -1,17 +1,81 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, SecondsFormat, Utc};
+use serde_json::{Map, Value};
 use std::env;
-use std::path::PathBuf;
-use tracing::Level;
-use tracing_appender::rolling::{RollingFileAppender, Rotation};
-use tracing_subscriber::{fmt, Registry};
-
+use std::fmt as std_fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Mutex;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::{self, FmtContext, FormatEvent, FormatFields, MakeWriter};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Registry;
+
+use crate::config::{Config, LoggingSettings};
 use crate::env::logging as env_vars;
 
+/// Rotated log files kept by default
+const DEFAULT_MAX_FILES: usize = 7;
+
+/// Size a log file may reach before it is rotated, by default
+const DEFAULT_MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// When the log file is rotated by age
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogRotation {
+    Never,
+    Hourly,
+    #[default]
+    Daily,
+}
+
+impl LogRotation {
+    /// Key that changes when a new period starts
+    fn period(&self, time: DateTime<Local>) -> Option<String> {
+        match self {
+            LogRotation::Never => None,
+            LogRotation::Hourly => Some(time.format("%Y%m%d%H").to_string()),
+            LogRotation::Daily => Some(time.format("%Y%m%d").to_string()),
+        }
+    }
+}
+
+impl std_fmt::Display for LogRotation {
+    fn fmt(&self, f: &mut std_fmt::Formatter<'_>) -> std_fmt::Result {
+        f.write_str(match self {
+            LogRotation::Never => "never",
+            LogRotation::Hourly => "hourly",
+            LogRotation::Daily => "daily",
+        })
+    }
+}
+
+impl FromStr for LogRotation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "never" => Ok(LogRotation::Never),
+            "hourly" => Ok(LogRotation::Hourly),
+            "daily" => Ok(LogRotation::Daily),
+            _ => Err(format!(
+                "Unknown rotation: {s} (expected never, hourly or daily)"
+            )),
+        }
+    }
+}
+
 /// Simplified logging configuration for RetroChat
 #[derive(Debug, Clone)]
 pub struct LoggingConfig {
     /// Log level (error, warn, info, debug, trace)
     pub level: Level,
+    /// Level overrides for targets such as `retrochat_core::services::watch_service`
+    pub targets: Vec<(String, Level)>,
     /// Whether to log to stdout
     pub stdout: bool,
     /// Optional file path for logging
@@ -20,16 +84,26 @@ pub struct LoggingConfig {
     pub json_format: bool,
     /// Whether to use ANSI colors
     pub use_colors: bool,
+    /// Start a new log file every hour or day
+    pub rotation: LogRotation,
+    /// Start a new log file once it grows past this many bytes
+    pub max_file_size: Option<u64>,
+    /// Rotated log files kept next to the current one
+    pub max_files: usize,
 }
 
 impl Default for LoggingConfig {
     fn default() -> Self {
         Self {
             level: Level::INFO,
+            targets: Vec::new(),
             stdout: true,
             file_path: None,
             json_format: false,
             use_colors: true,
+            rotation: LogRotation::default(),
+            max_file_size: Some(DEFAULT_MAX_FILE_SIZE),
+            max_files: DEFAULT_MAX_FILES,
         }
     }
 }
@@ -46,6 +120,14 @@ impl LoggingConfig {
         self
     }
 
+    /// Override the level for one target (module path prefix)
+    pub fn with_target_level(mut self, target: impl Into<String>, level: Level) -> Self {
+        let target = target.into();
+        self.targets.retain(|(existing, _)| *existing != target);
+        self.targets.push((target, level));
+        self
+    }
+
     /// Enable/disable stdout logging
     pub fn with_stdout(mut self, enabled: bool) -> Self {
         self.stdout = enabled;
@@ -58,6 +140,14 @@ impl LoggingConfig {
         self
     }
 
+    /// Log to [`default_log_path`] unless a file was already chosen
+    pub fn with_default_file(mut self) -> Result<Self> {
+        if self.file_path.is_none() {
+            self.file_path = Some(default_log_path()?);
+        }
+        Ok(self)
+    }
+
     /// Enable/disable JSON format
     pub fn with_json_format(mut self, enabled: bool) -> Self {
         self.json_format = enabled;
@@ -70,43 +160,100 @@ impl LoggingConfig {
         self
     }
 
+    /// Set when the log file is rotated
+    pub fn with_rotation(mut self, rotation: LogRotation) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Rotate the log file once it exceeds `bytes`; `None` disables the limit
+    pub fn with_max_file_size(mut self, bytes: Option<u64>) -> Self {
+        self.max_file_size = bytes;
+        self
+    }
+
+    /// Keep at most `count` rotated log files
+    pub fn with_max_files(mut self, count: usize) -> Self {
+        self.max_files = count;
+        self
+    }
+
     /// Create config from environment variables
     pub fn from_env() -> Self {
+        Self::default().apply_env()
+    }
+
+    /// Create config from the `[logging]` section of the config file, then
+    /// environment variables
+    ///
+    /// Falls back to the defaults when the config file cannot be read, since
+    /// logging is set up before anything could report the error.
+    pub fn load() -> Self {
+        let config = Config::load().unwrap_or_default();
+        Self::from_settings(&config.logging).apply_env()
+    }
+
+    /// Create config from `[logging]` settings; invalid values are skipped
+    pub fn from_settings(settings: &LoggingSettings) -> Self {
         let mut config = Self::default();
+        if let Some(level) = settings.level.as_deref().and_then(|l| l.parse().ok()) {
+            config.level = level;
+        }
+        if let Some(format) = &settings.format {
+            config.json_format = format.eq_ignore_ascii_case("json");
+        }
+        if let Some(rotation) = settings.rotation.as_deref().and_then(|r| r.parse().ok()) {
+            config.rotation = rotation;
+        }
+        if let Some(mb) = settings.max_size_mb {
+            config.max_file_size = (mb > 0).then_some(mb * 1024 * 1024);
+        }
+        if let Some(count) = settings.max_files {
+            config.max_files = count;
+        }
+        for (target, level) in &settings.modules {
+            if let Ok(level) = level.parse() {
+                config = config.with_target_level(target.clone(), level);
+            }
+        }
+        config
+    }
 
-        // Set log level from environment
-        if let Ok(level_str) = env::var(env_vars::LOG_LEVEL) {
-            config.level = match level_str.to_lowercase().as_str() {
-                "error" => Level::ERROR,
-                "warn" => Level::WARN,
-                "info" => Level::INFO,
-                "debug" => Level::DEBUG,
-                "trace" => Level::TRACE,
-                _ => Level::INFO,
-            };
+    /// Override settings with `RETROCHAT_LOG_*` environment variables
+    fn apply_env(mut self) -> Self {
+        // Set log level from environment, e.g. "info,retrochat_core::parsers=debug"
+        if let Ok(directives) = env::var(env_vars::LOG_LEVEL) {
+            let (level, targets) = parse_directives(&directives);
+            if let Some(level) = level {
+                self.level = level;
+            }
+            for (target, level) in targets {
+                self = self.with_target_level(target, level);
+            }
         }
 
         // Set file path from environment
         if let Ok(file_path) = env::var(env_vars::LOG_FILE) {
-            config.file_path = Some(PathBuf::from(file_path));
+            self.file_path = Some(PathBuf::from(file_path));
+        }
+
+        if let Ok(format) = env::var(env_vars::LOG_FORMAT) {
+            self.json_format = format.eq_ignore_ascii_case("json");
         }
 
         // Disable colors if RETROCHAT_NO_COLOR is set
         if env::var(env_vars::NO_COLOR).is_ok() {
-            config.use_colors = false;
+            self.use_colors = false;
         }
 
-        config
+        self
     }
 
     /// Create a development config with debug logging
     pub fn development() -> Self {
         Self {
             level: Level::DEBUG,
-            stdout: true,
-            file_path: None,
-            json_format: false,
-            use_colors: true,
+            ..Self::default()
         }
     }
 
@@ -118,8 +265,43 @@ impl LoggingConfig {
             file_path: Some(PathBuf::from("/var/log/retrochat/app.log")),
             json_format: true,
             use_colors: false,
+            ..Self::default()
+        }
+    }
+}
+
+/// Log file used by the TUI and `retrochat sync` (~/.retrochat/logs/retrochat.log)
+pub fn default_log_path() -> Result<PathBuf> {
+    Ok(crate::database::config::get_config_dir()?
+        .join("logs")
+        .join("retrochat.log"))
+}
+
+/// Split `RETROCHAT_LOG_LEVEL` into a default level and per-target levels
+///
+/// Unknown levels are ignored, so a typo never stops RetroChat from starting.
+pub fn parse_directives(directives: &str) -> (Option<Level>, Vec<(String, Level)>) {
+    let mut default = None;
+    let mut targets = Vec::new();
+    for directive in directives
+        .split(',')
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+    {
+        match directive.split_once('=') {
+            Some((target, level)) => {
+                if let Ok(level) = level.trim().parse() {
+                    targets.push((target.trim().to_string(), level));
+                }
+            }
+            None => {
+                if let Ok(level) = directive.parse() {
+                    default = Some(level);
+                }
+            }
         }
     }
+    (default, targets)
 }
 
 /// Initialize logging with the given configuration
@@ -130,41 +312,58 @@ pub fn init_logging(config: LoggingConfig) -> Result<()> {
     let mut layers = Vec::new();
 
     // Create filter that suppresses sqlx slow query warnings
-    let filter = Targets::new()
-        .with_default(config.level)
-        .with_target("sqlx::query", Level::ERROR); // Only show ERROR and above for sqlx::query
+    let mut filter = Targets::new().with_default(config.level);
+    if !config
+        .targets
+        .iter()
+        .any(|(target, _)| target == "sqlx::query")
+    {
+        filter = filter.with_target("sqlx::query", Level::ERROR); // Only show ERROR and above for sqlx::query
+    }
+    let filter = filter.with_targets(config.targets.clone());
 
     // Add stdout layer only if enabled
     if config.stdout {
-        let stdout_layer = fmt::layer()
-            .with_ansi(config.use_colors)
-            .with_level(true)
-            .with_target(true)
-            .with_filter(filter.clone());
-        layers.push(stdout_layer.boxed());
+        let stdout_layer = if config.json_format {
+            fmt::layer()
+                .event_format(JsonFormat)
+                .with_filter(filter.clone())
+                .boxed()
+        } else {
+            fmt::layer()
+                .with_ansi(config.use_colors)
+                .with_level(true)
+                .with_target(true)
+                .with_filter(filter.clone())
+                .boxed()
+        };
+        layers.push(stdout_layer);
     }
 
     // Add file layer if path is set
     if let Some(path) = &config.file_path {
-        let parent_dir = path
-            .parent()
-            .ok_or_else(|| anyhow::anyhow!("Invalid log path"))?;
-        let file_name = path
-            .file_name()
-            .ok_or_else(|| anyhow::anyhow!("Invalid log filename"))?;
-
-        // Ensure parent directory exists
-        std::fs::create_dir_all(parent_dir)?;
-
-        let file_appender = RollingFileAppender::new(Rotation::DAILY, parent_dir, file_name);
-
-        let file_layer = fmt::layer()
-            .with_writer(file_appender)
-            .with_ansi(false)
-            .with_level(true)
-            .with_target(true)
-            .with_filter(filter);
-        layers.push(file_layer.boxed());
+        let writer = RotatingFileWriter::new(
+            path,
+            config.rotation,
+            config.max_file_size,
+            config.max_files,
+        )?;
+        let file_layer = if config.json_format {
+            fmt::layer()
+                .event_format(JsonFormat)
+                .with_writer(writer)
+                .with_filter(filter)
+                .boxed()
+        } else {
+            fmt::layer()
+                .with_writer(writer)
+                .with_ansi(false)
+                .with_level(true)
+                .with_target(true)
+                .with_filter(filter)
+                .boxed()
+        };
+        layers.push(file_layer);
     }
 
     // Initialize the subscriber with all layers
@@ -192,6 +391,272 @@ pub fn init_from_env() -> Result<()> {
     init_logging(LoggingConfig::from_env())
 }
 
+/// Writes each event as one JSON object per line
+///
+/// Fields: `timestamp` (RFC 3339, UTC), `level`, `target`, `message`, the
+/// event's other `fields`, and the names of enclosing `spans`.
+pub struct JsonFormat;
+
+impl<S, N> FormatEvent<S, N> for JsonFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> std_fmt::Result {
+        let metadata = event.metadata();
+        let mut visitor = JsonVisitor::default();
+        event.record(&mut visitor);
+        let mut fields = visitor.0;
+
+        let mut object = Map::new();
+        object.insert(
+            "timestamp".to_string(),
+            Utc::now()
+                .to_rfc3339_opts(SecondsFormat::Millis, true)
+                .into(),
+        );
+        object.insert("level".to_string(), metadata.level().as_str().into());
+        object.insert("target".to_string(), metadata.target().into());
+        if let Some(message) = fields.remove("message") {
+            object.insert("message".to_string(), message);
+        }
+        if !fields.is_empty() {
+            object.insert("fields".to_string(), Value::Object(fields));
+        }
+        if let Some(scope) = ctx.event_scope() {
+            let spans: Vec<Value> = scope.from_root().map(|span| span.name().into()).collect();
+            if !spans.is_empty() {
+                object.insert("spans".to_string(), Value::Array(spans));
+            }
+        }
+        writeln!(writer, "{}", Value::Object(object))
+    }
+}
+
+#[derive(Default)]
+struct JsonVisitor(Map<String, Value>);
+
+impl Visit for JsonVisitor {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std_fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{value:?}").into());
+    }
+}
+
+/// Log file that moves itself aside when a new hour/day starts or it grows
+/// too large
+///
+/// The current file keeps its name; rotated files get a timestamp suffix
+/// (`retrochat.log.20250101-120000.000`) and the oldest beyond `max_files`
+/// are deleted.
+pub struct RotatingFileWriter {
+    path: PathBuf,
+    rotation: LogRotation,
+    max_size: Option<u64>,
+    max_files: usize,
+    state: Mutex<RotatingState>,
+}
+
+struct RotatingState {
+    file: File,
+    size: u64,
+    period: Option<String>,
+}
+
+impl RotatingFileWriter {
+    pub fn new(
+        path: &Path,
+        rotation: LogRotation,
+        max_size: Option<u64>,
+        max_files: usize,
+    ) -> Result<Self> {
+        let parent_dir = path
+            .parent()
+            .ok_or_else(|| anyhow::anyhow!("Invalid log path"))?;
+        if path.file_name().is_none() {
+            anyhow::bail!("Invalid log filename");
+        }
+
+        // Ensure parent directory exists
+        fs::create_dir_all(parent_dir)?;
+
+        let state = Self::open(path, rotation)
+            .with_context(|| format!("Failed to open log file: {}", path.display()))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            rotation,
+            max_size,
+            max_files,
+            state: Mutex::new(state),
+        })
+    }
+
+    /// Open for appending; the period of an existing file is taken from its
+    /// modification time, so yesterday's log is rotated on the first write
+    fn open(path: &Path, rotation: LogRotation) -> io::Result<RotatingState> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let metadata = file.metadata()?;
+        let modified = metadata
+            .modified()
+            .map(DateTime::<Local>::from)
+            .unwrap_or_else(|_| Local::now());
+        Ok(RotatingState {
+            file,
+            size: metadata.len(),
+            period: rotation.period(modified),
+        })
+    }
+
+    fn needs_rotation(&self, state: &RotatingState, incoming: usize) -> bool {
+        if state.size == 0 {
+            return false;
+        }
+        if self.rotation.period(Local::now()) != state.period {
+            return true;
+        }
+        self.max_size
+            .is_some_and(|max| state.size + incoming as u64 > max)
+    }
+
+    fn rotate(&self, state: &mut RotatingState) -> io::Result<()> {
+        state.file.flush()?;
+        let suffix = Local::now().format("%Y%m%d-%H%M%S%.3f");
+        let mut rotated = self.path.as_os_str().to_owned();
+        rotated.push(format!(".{suffix}"));
+        fs::rename(&self.path, &rotated)?;
+        *state = Self::open(&self.path, self.rotation)?;
+        state.period = self.rotation.period(Local::now());
+        self.prune();
+        Ok(())
+    }
+
+    /// Delete the oldest rotated files beyond `max_files`
+    fn prune(&self) {
+        let mut rotated = rotated_log_files(&self.path);
+        if rotated.len() <= self.max_files {
+            return;
+        }
+        let excess = rotated.len() - self.max_files;
+        for path in rotated.drain(..excess) {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+/// Rotated siblings of `path`, oldest first
+pub fn rotated_log_files(path: &Path) -> Vec<PathBuf> {
+    let (Some(dir), Some(name)) = (path.parent(), path.file_name()) else {
+        return Vec::new();
+    };
+    let prefix = format!("{}.", name.to_string_lossy());
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with(&prefix))
+        .map(|entry| entry.path())
+        .collect();
+    // Timestamp suffixes sort chronologically
+    files.sort();
+    files
+}
+
+impl Write for &RotatingFileWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut state = self
+            .state
+            .lock()
+            .map_err(|_| io::Error::other("log file lock poisoned"))?;
+        if self.needs_rotation(&state, buf.len()) {
+            // Keep logging to the current file if it cannot be moved
+            let _ = self.rotate(&mut state);
+        }
+        let written = state.file.write(buf)?;
+        state.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.state
+            .lock()
+            .map_err(|_| io::Error::other("log file lock poisoned"))?
+            .file
+            .flush()
+    }
+}
+
+impl<'a> MakeWriter<'a> for RotatingFileWriter {
+    type Writer = &'a RotatingFileWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self
+    }
+}
+
+/// Which lines `retrochat logs tail` prints
+#[derive(Debug, Clone, Default)]
+pub struct LogLineFilter {
+    /// Case-insensitive text the line must contain
+    pub text: Option<String>,
+    /// Least severe level shown
+    pub min_level: Option<Level>,
+}
+
+impl LogLineFilter {
+    pub fn matches(&self, line: &str) -> bool {
+        if let Some(text) = &self.text {
+            if !line.to_lowercase().contains(&text.to_lowercase()) {
+                return false;
+            }
+        }
+        match (self.min_level, line_level(line)) {
+            // Lower levels are more verbose: TRACE > DEBUG > ... > ERROR
+            (Some(min_level), Some(level)) => level <= min_level,
+            (Some(_), None) => false,
+            (None, _) => true,
+        }
+    }
+}
+
+/// Level of a log line in either the text or the JSON format
+pub fn line_level(line: &str) -> Option<Level> {
+    if line.starts_with('{') {
+        return serde_json::from_str::<Value>(line)
+            .ok()?
+            .get("level")?
+            .as_str()?
+            .parse()
+            .ok();
+    }
+    // Text lines look like "2025-01-01T12:00:00.000000Z  INFO target: message"
+    line.split_whitespace().nth(1)?.parse().ok()
+}
+
 /// Log error with context
 pub fn log_error<E: std::fmt::Display>(error: &E, context: &str) {
     tracing::error!(error = %error, context = context, "Error occurred");
@@ -243,3 +708,131 @@ pub fn log_audit(event: &str, user: Option<&str>, resource: Option<&str>, action
         "Audit event"
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_directives() {
+        let (level, targets) =
+            parse_directives("warn, retrochat_core::services::watch_service=debug,sqlx=bogus");
+        assert_eq!(level, Some(Level::WARN));
+        assert_eq!(
+            targets,
+            vec![(
+                "retrochat_core::services::watch_service".to_string(),
+                Level::DEBUG
+            )]
+        );
+        assert_eq!(parse_directives("nonsense").0, None);
+    }
+
+    #[test]
+    fn test_from_settings() {
+        let mut settings = LoggingSettings {
+            level: Some("debug".to_string()),
+            format: Some("json".to_string()),
+            rotation: Some("hourly".to_string()),
+            max_size_mb: Some(0),
+            ..Default::default()
+        };
+        settings
+            .modules
+            .insert("retrochat_core::parsers".to_string(), "trace".to_string());
+
+        let config = LoggingConfig::from_settings(&settings);
+        assert_eq!(config.level, Level::DEBUG);
+        assert!(config.json_format);
+        assert_eq!(config.rotation, LogRotation::Hourly);
+        assert_eq!(config.max_file_size, None);
+        assert_eq!(
+            config.targets,
+            vec![("retrochat_core::parsers".to_string(), Level::TRACE)]
+        );
+    }
+
+    #[test]
+    fn test_rotating_writer_rotates_by_size() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("retrochat.log");
+        let writer = RotatingFileWriter::new(&path, LogRotation::Never, Some(10), 2).unwrap();
+
+        for line in [
+            "first line\n",
+            "second line\n",
+            "third line\n",
+            "fourth line\n",
+        ] {
+            (&writer).write_all(line.as_bytes()).unwrap();
+            // Rotated names have millisecond timestamps
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "fourth line\n");
+        let rotated = rotated_log_files(&path);
+        assert_eq!(rotated.len(), 2);
+        assert_eq!(fs::read_to_string(&rotated[1]).unwrap(), "third line\n");
+    }
+
+    #[test]
+    fn test_json_format() {
+        use tracing_subscriber::prelude::*;
+
+        #[derive(Clone, Default)]
+        struct Buffer(Arc<Mutex<Vec<u8>>>);
+        impl Write for Buffer {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.0.lock().unwrap().write(buf)
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let buffer = Buffer::default();
+        let output = buffer.clone();
+        let subscriber = Registry::default().with(
+            fmt::layer()
+                .event_format(JsonFormat)
+                .with_writer(move || buffer.clone()),
+        );
+        tracing::subscriber::with_default(subscriber, || {
+            let _span = tracing::info_span!("import").entered();
+            tracing::warn!(files = 3, path = "a.jsonl", "Skipped files");
+        });
+
+        let line = String::from_utf8(output.0.lock().unwrap().clone()).unwrap();
+        let value: Value = serde_json::from_str(line.trim()).unwrap();
+        assert_eq!(value["level"], "WARN");
+        assert_eq!(value["message"], "Skipped files");
+        assert_eq!(value["fields"]["files"], 3);
+        assert_eq!(value["fields"]["path"], "a.jsonl");
+        assert_eq!(value["spans"], serde_json::json!(["import"]));
+        assert_eq!(line_level(line.trim()), Some(Level::WARN));
+    }
+
+    #[test]
+    fn test_log_line_filter() {
+        let info =
+            "2025-01-01T12:00:00.000000Z  INFO retrochat_core::services::watch_service: Watching";
+        let error =
+            "2025-01-01T12:00:01.000000Z ERROR retrochat_core::services::import_service: Failed";
+
+        let filter = LogLineFilter {
+            text: None,
+            min_level: Some(Level::WARN),
+        };
+        assert!(!filter.matches(info));
+        assert!(filter.matches(error));
+
+        let filter = LogLineFilter {
+            text: Some("WATCH_SERVICE".to_string()),
+            min_level: None,
+        };
+        assert!(filter.matches(info));
+        assert!(!filter.matches(error));
+    }
+}
@@ -0,0 +1,124 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use uuid::Uuid;
+
+/// Image or document attached to a message
+///
+/// Only metadata is stored in the database. The bytes are written under
+/// `~/.retrochat/attachments` when `attachments.store_blobs` is on, named by
+/// their hash so a screenshot pasted twice is saved once.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Attachment {
+    pub id: Uuid,
+    pub message_id: Uuid,
+    pub session_id: Uuid,
+    /// `image` or `document`, as in the provider's content block
+    pub kind: String,
+    /// e.g. `image/png`
+    pub media_type: Option<String>,
+    pub size_bytes: u64,
+    /// SHA-256 of the decoded bytes; `None` when the log only links to them
+    pub sha256: Option<String>,
+    /// Saved copy of the bytes, when blob storage is on
+    pub file_path: Option<String>,
+    /// Decoded bytes (transient, only used during import)
+    #[serde(skip)]
+    pub data: Option<Vec<u8>>,
+}
+
+impl Attachment {
+    pub fn new(kind: &str, media_type: Option<String>, data: Option<Vec<u8>>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            kind: kind.to_string(),
+            media_type,
+            size_bytes: data.as_ref().map_or(0, |d| d.len() as u64),
+            sha256: data.as_ref().map(|d| hex::encode(Sha256::digest(d))),
+            data,
+            ..Default::default()
+        }
+    }
+
+    /// Short description used as the message text placeholder, e.g.
+    /// `Image: image/png, 12 KB`
+    pub fn label(&self) -> String {
+        let mut kind = self.kind.clone();
+        if let Some(first) = kind.get_mut(..1) {
+            first.make_ascii_uppercase();
+        }
+        let mut details = Vec::new();
+        if let Some(media_type) = &self.media_type {
+            details.push(media_type.clone());
+        }
+        if self.size_bytes > 0 {
+            details.push(format_size(self.size_bytes));
+        }
+        if details.is_empty() {
+            kind
+        } else {
+            format!("{kind}: {}", details.join(", "))
+        }
+    }
+
+    /// File extension for the saved blob
+    fn extension(&self) -> &str {
+        match self.media_type.as_deref() {
+            Some("image/png") => "png",
+            Some("image/jpeg") => "jpg",
+            Some("image/gif") => "gif",
+            Some("image/webp") => "webp",
+            Some("application/pdf") => "pdf",
+            Some("text/plain") => "txt",
+            _ => "bin",
+        }
+    }
+
+    /// Write the bytes under `dir` and record the path; no-op without bytes
+    pub fn save_blob(&mut self, dir: &Path) -> Result<()> {
+        let (Some(data), Some(sha256)) = (self.data.as_ref(), self.sha256.as_ref()) else {
+            return Ok(());
+        };
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+        let path = dir.join(format!("{sha256}.{}", self.extension()));
+        if !path.exists() {
+            std::fs::write(&path, data)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+        }
+        self.file_path = Some(path.to_string_lossy().to_string());
+        Ok(())
+    }
+}
+
+fn format_size(bytes: u64) -> String {
+    match bytes {
+        0..=1023 => format!("{bytes} B"),
+        1024..=1_048_575 => format!("{} KB", bytes / 1024),
+        _ => format!("{:.1} MB", bytes as f64 / 1_048_576.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_label_and_blob() {
+        let mut attachment = Attachment::new(
+            "image",
+            Some("image/png".to_string()),
+            Some(vec![0u8; 2048]),
+        );
+        assert_eq!(attachment.label(), "Image: image/png, 2 KB");
+        assert_eq!(Attachment::new("document", None, None).label(), "Document");
+
+        let dir = tempfile::tempdir().unwrap();
+        attachment.save_blob(dir.path()).unwrap();
+        let path = attachment.file_path.clone().unwrap();
+        assert!(path.ends_with(".png"));
+        assert!(path.contains(attachment.sha256.as_deref().unwrap()));
+        assert_eq!(std::fs::read(path).unwrap().len(), 2048);
+    }
+}
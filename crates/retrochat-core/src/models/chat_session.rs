@@ -9,6 +9,10 @@ pub enum SessionState {
     Created,
     Imported,
     Analyticsd,
+    /// Hidden from session lists; kept for search and export
+    Archived,
+    /// Written but not yet complete; left behind only by an interrupted import
+    Importing,
 }
 
 impl std::fmt::Display for SessionState {
@@ -17,6 +21,8 @@ impl std::fmt::Display for SessionState {
             SessionState::Created => write!(f, "created"),
             SessionState::Imported => write!(f, "imported"),
             SessionState::Analyticsd => write!(f, "analyticsd"),
+            SessionState::Archived => write!(f, "archived"),
+            SessionState::Importing => write!(f, "importing"),
         }
     }
 }
@@ -29,25 +35,73 @@ impl std::str::FromStr for SessionState {
             "created" => Ok(SessionState::Created),
             "imported" => Ok(SessionState::Imported),
             "analyticsd" => Ok(SessionState::Analyticsd),
+            "archived" => Ok(SessionState::Archived),
+            "importing" => Ok(SessionState::Importing),
             _ => Err(format!("Unknown session state: {s}")),
         }
     }
 }
 
+/// Where a session title came from
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TitleSource {
+    /// Supplied by the chat file itself (e.g. a Cursor chat name)
+    Parser,
+    /// Derived from the first user prompt at import time
+    Heuristic,
+    /// Generated by an LLM
+    Llm,
+}
+
+impl std::fmt::Display for TitleSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TitleSource::Parser => write!(f, "parser"),
+            TitleSource::Heuristic => write!(f, "heuristic"),
+            TitleSource::Llm => write!(f, "llm"),
+        }
+    }
+}
+
+impl std::str::FromStr for TitleSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parser" => Ok(TitleSource::Parser),
+            "heuristic" => Ok(TitleSource::Heuristic),
+            "llm" => Ok(TitleSource::Llm),
+            _ => Err(format!("Unknown title source: {s}")),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatSession {
     pub id: Uuid,
     pub provider: Provider,
     pub project_name: Option<String>,
+    /// Short human-readable title (e.g. the chat name or first user request)
+    pub title: Option<String>,
+    pub title_source: Option<TitleSource>,
     pub start_time: DateTime<Utc>,
     pub end_time: Option<DateTime<Utc>>,
     pub message_count: u32,
     pub token_count: Option<u32>,
+    /// Distinct tool operations in the session (cached, refreshed on import)
+    pub tool_op_count: u32,
+    /// Seconds between start and end time (cached, refreshed on import)
+    pub duration_seconds: Option<u32>,
     pub file_path: String,
     pub file_hash: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub state: SessionState,
+    /// Teammate the session was imported from; `None` for the user's own
+    /// sessions. Teammates' sessions are read-only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
 }
 
 impl ChatSession {
@@ -62,15 +116,20 @@ impl ChatSession {
             id: Uuid::new_v4(),
             provider,
             project_name: None,
+            title: None,
+            title_source: None,
             start_time,
             end_time: None,
             message_count: 0,
             token_count: None,
+            tool_op_count: 0,
+            duration_seconds: None,
             file_path,
             file_hash,
             created_at: now,
             updated_at: now,
             state: SessionState::Created,
+            owner: None,
         }
     }
 
@@ -79,8 +138,22 @@ impl ChatSession {
         self
     }
 
+    /// Set a title taken from the chat file itself
+    pub fn with_title(mut self, title: String) -> Self {
+        self.title = Some(title);
+        self.title_source = Some(TitleSource::Parser);
+        self
+    }
+
+    pub fn set_title(&mut self, title: String, source: TitleSource) {
+        self.title = Some(title);
+        self.title_source = Some(source);
+        self.updated_at = Utc::now();
+    }
+
     pub fn with_end_time(mut self, end_time: DateTime<Utc>) -> Self {
         self.end_time = Some(end_time);
+        self.duration_seconds = Some((end_time - self.start_time).num_seconds().max(0) as u32);
         self
     }
 
@@ -99,6 +172,31 @@ impl ChatSession {
         self.updated_at = Utc::now();
     }
 
+    pub fn is_archived(&self) -> bool {
+        self.state == SessionState::Archived
+    }
+
+    /// Whether the session is still being written by an import
+    pub fn is_importing(&self) -> bool {
+        self.state == SessionState::Importing
+    }
+
+    /// Whether the session was imported from a teammate's export
+    pub fn is_team(&self) -> bool {
+        self.owner.is_some()
+    }
+
+    /// Whether the session matches an owner filter: `me` for the user's own
+    /// sessions, `team` for all teammates' sessions, or a teammate's name
+    pub fn matches_owner(&self, filter: &str) -> bool {
+        match (filter.trim().to_lowercase().as_str(), &self.owner) {
+            ("me" | "mine", owner) => owner.is_none(),
+            ("team", owner) => owner.is_some(),
+            (name, Some(owner)) => owner.to_lowercase() == name,
+            (_, None) => false,
+        }
+    }
+
     pub fn duration(&self) -> Option<chrono::Duration> {
         self.end_time.map(|end| end - self.start_time)
     }
@@ -163,6 +261,25 @@ mod tests {
         assert!(!session.is_valid());
     }
 
+    #[test]
+    fn test_matches_owner() {
+        let mut session = ChatSession::new(
+            Provider::ClaudeCode,
+            "/path/to/chat.jsonl".to_string(),
+            "abc123".to_string(),
+            Utc::now(),
+        );
+        assert!(session.matches_owner("me"));
+        assert!(!session.matches_owner("team"));
+        assert!(!session.matches_owner("alice"));
+
+        session.owner = Some("Alice".to_string());
+        assert!(!session.matches_owner("me"));
+        assert!(session.matches_owner("team"));
+        assert!(session.matches_owner("alice"));
+        assert!(!session.matches_owner("bob"));
+    }
+
     #[test]
     fn test_provider_display() {
         assert_eq!(Provider::ClaudeCode.to_string(), "Claude Code");
@@ -0,0 +1,85 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// What happened, as recorded in the `events` table
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    /// A new session was imported
+    SessionImported,
+    /// An already imported session was imported again with new content
+    SessionReplaced,
+    /// Retention archived sessions
+    SessionsArchived,
+    /// Archived sessions were restored
+    SessionsRestored,
+    AnalysisCompleted,
+    AnalysisFailed,
+    /// A saved view was created or its expression changed
+    ViewSaved,
+    ViewDeleted,
+    /// Topic clusters were recomputed
+    TopicsRefreshed,
+    /// A usage quota was crossed for the first time in its period
+    QuotaExceeded,
+}
+
+impl EventKind {
+    pub const ALL: [EventKind; 10] = [
+        EventKind::SessionImported,
+        EventKind::SessionReplaced,
+        EventKind::SessionsArchived,
+        EventKind::SessionsRestored,
+        EventKind::AnalysisCompleted,
+        EventKind::AnalysisFailed,
+        EventKind::ViewSaved,
+        EventKind::ViewDeleted,
+        EventKind::TopicsRefreshed,
+        EventKind::QuotaExceeded,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventKind::SessionImported => "session_imported",
+            EventKind::SessionReplaced => "session_replaced",
+            EventKind::SessionsArchived => "sessions_archived",
+            EventKind::SessionsRestored => "sessions_restored",
+            EventKind::AnalysisCompleted => "analysis_completed",
+            EventKind::AnalysisFailed => "analysis_failed",
+            EventKind::ViewSaved => "view_saved",
+            EventKind::ViewDeleted => "view_deleted",
+            EventKind::TopicsRefreshed => "topics_refreshed",
+            EventKind::QuotaExceeded => "quota_exceeded",
+        }
+    }
+}
+
+impl std::fmt::Display for EventKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for EventKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        EventKind::ALL
+            .into_iter()
+            .find(|kind| kind.as_str() == s)
+            .ok_or_else(|| format!("Unknown event kind: {s}"))
+    }
+}
+
+/// One entry of the change feed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Event {
+    /// Increasing position in the feed; consumers resume after the last id
+    /// they handled
+    pub id: i64,
+    pub kind: EventKind,
+    pub session_id: Option<String>,
+    /// Kind-specific details, e.g. the provider of an imported session
+    pub payload: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
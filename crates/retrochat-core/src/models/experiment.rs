@@ -0,0 +1,81 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A named comparison between cohorts of sessions
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Experiment {
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Experiment {
+    pub fn new(name: String, description: Option<String>) -> Self {
+        Self {
+            name,
+            description,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// A session's place in an experiment
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExperimentSession {
+    pub experiment_name: String,
+    pub session_id: Uuid,
+    pub cohort: String,
+    pub added_at: DateTime<Utc>,
+}
+
+/// Aggregate metrics of one cohort
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct CohortStats {
+    pub cohort: String,
+    pub sessions: i64,
+    pub messages: i64,
+    pub tokens: i64,
+    /// Sessions with a known duration
+    pub timed_sessions: i64,
+    pub duration_seconds: i64,
+    pub tool_uses: i64,
+    pub failed_tool_uses: i64,
+    /// Sessions with a summarized outcome
+    pub summarized: i64,
+    pub completed: i64,
+    /// Sessions per provider, most used first
+    pub providers: Vec<(String, i64)>,
+}
+
+impl CohortStats {
+    pub fn avg_messages(&self) -> Option<f64> {
+        ratio(self.messages, self.sessions)
+    }
+
+    pub fn avg_tokens(&self) -> Option<f64> {
+        ratio(self.tokens, self.sessions)
+    }
+
+    pub fn avg_minutes(&self) -> Option<f64> {
+        ratio(self.duration_seconds, self.timed_sessions).map(|seconds| seconds / 60.0)
+    }
+
+    pub fn avg_tool_uses(&self) -> Option<f64> {
+        ratio(self.tool_uses, self.sessions)
+    }
+
+    /// Share of tool uses that failed, `None` without tool uses
+    pub fn tool_failure_rate(&self) -> Option<f64> {
+        ratio(self.failed_tool_uses, self.tool_uses)
+    }
+
+    /// Share of summarized sessions that completed, `None` before any summary
+    pub fn completion_rate(&self) -> Option<f64> {
+        ratio(self.completed, self.summarized)
+    }
+}
+
+fn ratio(total: i64, count: i64) -> Option<f64> {
+    (count > 0).then(|| total as f64 / count as f64)
+}
@@ -0,0 +1,16 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A file whose most recent import attempt failed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportError {
+    pub id: Uuid,
+    pub file_path: String,
+    pub provider: Option<String>,
+    /// Error from the most recent attempt
+    pub error_message: String,
+    pub attempt_count: u32,
+    pub first_attempt_at: DateTime<Utc>,
+    pub last_attempt_at: DateTime<Utc>,
+}
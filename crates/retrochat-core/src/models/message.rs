@@ -3,6 +3,8 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use uuid::Uuid;
 
+use super::{Attachment, MessageUsage};
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum MessageRole {
     User,
@@ -16,6 +18,10 @@ pub enum MessageType {
     ToolResult,
     Thinking,
     SlashCommand,
+    /// Output of a user-configured hook (e.g. a PostToolUse formatter)
+    HookEvent,
+    /// Context the agent injected into the conversation, not typed by the user
+    SystemReminder,
     #[default]
     SimpleMessage,
 }
@@ -33,6 +39,18 @@ pub struct SlashCommandData {
     pub stdout: Option<String>,
 }
 
+/// A hook run recorded in the session log, stored under `metadata.hook`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HookEventData {
+    /// Hook event, e.g. "PreToolUse", "PostToolUse" or "Stop"
+    pub event: String,
+    /// Tool matcher the hook was registered for, if any
+    pub matcher: Option<String>,
+    pub command: Option<String>,
+    /// "success", "failed" or "blocked"
+    pub outcome: String,
+}
+
 impl std::fmt::Display for MessageRole {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -63,6 +81,8 @@ impl std::fmt::Display for MessageType {
             MessageType::ToolResult => write!(f, "tool_result"),
             MessageType::Thinking => write!(f, "thinking"),
             MessageType::SlashCommand => write!(f, "slash_command"),
+            MessageType::HookEvent => write!(f, "hook_event"),
+            MessageType::SystemReminder => write!(f, "system_reminder"),
             MessageType::SimpleMessage => write!(f, "simple_message"),
         }
     }
@@ -77,6 +97,8 @@ impl std::str::FromStr for MessageType {
             "tool_result" => Ok(MessageType::ToolResult),
             "thinking" => Ok(MessageType::Thinking),
             "slash_command" => Ok(MessageType::SlashCommand),
+            "hook_event" => Ok(MessageType::HookEvent),
+            "system_reminder" => Ok(MessageType::SystemReminder),
             "simple_message" => Ok(MessageType::SimpleMessage),
             _ => Err(format!("Unknown message type: {s}")),
         }
@@ -126,7 +148,10 @@ pub struct Message {
     pub role: MessageRole,
     pub content: String,
     pub timestamp: DateTime<Utc>,
+    /// Provider-reported token usage
     pub token_count: Option<u32>,
+    /// Heuristic estimate (see [`crate::utils::token_estimate`])
+    pub estimated_tokens: Option<u32>,
     pub metadata: Option<Value>,
     pub sequence_number: u32,
     pub message_type: MessageType,
@@ -138,6 +163,15 @@ pub struct Message {
     pub tool_uses: Option<Vec<ToolUse>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_results: Option<Vec<ToolResult>>,
+    /// Provider-reported usage, stored separately by ImportService
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage: Option<MessageUsage>,
+    /// Images and documents, stored separately by ImportService
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attachments: Option<Vec<Attachment>>,
+    /// The log entry as written, kept by ImportService when raw payloads are on
+    #[serde(skip)]
+    pub raw: Option<String>,
 }
 
 impl Message {
@@ -155,12 +189,16 @@ impl Message {
             content,
             timestamp,
             token_count: None,
+            estimated_tokens: None,
             metadata: None,
             sequence_number,
             message_type: MessageType::default(),
             tool_operation_id: None,
             tool_uses: None,
             tool_results: None,
+            usage: None,
+            attachments: None,
+            raw: None,
         }
     }
 
@@ -169,11 +207,31 @@ impl Message {
         self
     }
 
+    pub fn with_estimated_tokens(mut self, estimated_tokens: u32) -> Self {
+        self.estimated_tokens = Some(estimated_tokens);
+        self
+    }
+
+    /// Reported token usage, or the estimate when the provider reports none
+    pub fn tokens(&self) -> Option<u32> {
+        self.token_count.or(self.estimated_tokens)
+    }
+
     pub fn with_metadata(mut self, metadata: Value) -> Self {
         self.metadata = Some(metadata);
         self
     }
 
+    /// Set one metadata key, keeping the others
+    pub fn insert_metadata(&mut self, key: &str, value: Value) {
+        match &mut self.metadata {
+            Some(Value::Object(map)) => {
+                map.insert(key.to_string(), value);
+            }
+            metadata => *metadata = Some(serde_json::json!({ key: value })),
+        }
+    }
+
     pub fn with_message_type(mut self, message_type: MessageType) -> Self {
         self.message_type = message_type;
         self
@@ -196,6 +254,23 @@ impl Message {
         self
     }
 
+    /// Set usage (transient field - only used during import)
+    pub fn with_usage(mut self, usage: MessageUsage) -> Self {
+        self.usage = Some(usage);
+        self
+    }
+
+    /// Set attachments (transient field - only used during import)
+    pub fn with_attachments(mut self, attachments: Vec<Attachment>) -> Self {
+        self.attachments = Some(attachments);
+        self
+    }
+
+    pub fn with_raw(mut self, raw: String) -> Self {
+        self.raw = Some(raw);
+        self
+    }
+
     pub fn is_valid(&self) -> bool {
         !self.content.is_empty()
     }
@@ -0,0 +1,53 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// What changed a stored message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RevisionSource {
+    /// Personal details or secrets were scrubbed from the content
+    Redaction,
+    /// An automatic fix, e.g. `retrochat repair roles`
+    Repair,
+    /// An edit the user asked for, e.g. `retrochat session split`
+    Manual,
+}
+
+impl std::fmt::Display for RevisionSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RevisionSource::Redaction => write!(f, "redaction"),
+            RevisionSource::Repair => write!(f, "repair"),
+            RevisionSource::Manual => write!(f, "manual"),
+        }
+    }
+}
+
+impl std::str::FromStr for RevisionSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "redaction" => Ok(RevisionSource::Redaction),
+            "repair" => Ok(RevisionSource::Repair),
+            "manual" => Ok(RevisionSource::Manual),
+            _ => Err(format!("Unknown revision source: {s}")),
+        }
+    }
+}
+
+/// One change to one field of a stored message
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MessageRevision {
+    pub id: i64,
+    pub message_id: Uuid,
+    /// `role`, `content`, or `position` (`<session id>#<sequence number>`)
+    pub field: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    pub source: RevisionSource,
+    /// Why the change was made, e.g. `assistant phrasing` or `session split`
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
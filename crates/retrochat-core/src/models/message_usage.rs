@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Token usage and billing details the provider reported for one message
+///
+/// Claude Code records these on every assistant message. Prompt tokens are
+/// split three ways: uncached input, input written to the prompt cache, and
+/// input read back from it (billed at a fraction of the input price).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct MessageUsage {
+    pub message_id: Uuid,
+    pub session_id: Uuid,
+    pub model: Option<String>,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_creation_input_tokens: u64,
+    pub cache_read_input_tokens: u64,
+    /// e.g. `standard`, `priority` or `batch`
+    pub service_tier: Option<String>,
+}
+
+impl MessageUsage {
+    /// Every prompt token, whether cached or not
+    pub fn prompt_tokens(&self) -> u64 {
+        self.input_tokens + self.cache_creation_input_tokens + self.cache_read_input_tokens
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prompt_tokens() {
+        let usage = MessageUsage {
+            input_tokens: 10,
+            output_tokens: 50,
+            cache_creation_input_tokens: 200,
+            cache_read_input_tokens: 790,
+            ..Default::default()
+        };
+        assert_eq!(usage.prompt_tokens(), 1000);
+    }
+}
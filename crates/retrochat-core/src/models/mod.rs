@@ -1,21 +1,49 @@
 pub mod analytics;
 pub mod analytics_request;
+pub mod attachment;
 pub mod bash_metadata;
 pub mod chat_session;
+pub mod event;
+pub mod experiment;
+pub mod import_error;
 pub mod message;
+pub mod message_revision;
+pub mod message_usage;
+pub mod parser_warning;
 pub mod project;
 pub mod provider;
+pub mod quota_event;
+pub mod raw_payload;
+pub mod saved_view;
+pub mod session_comparison;
+pub mod session_quality;
+pub mod session_segment;
 pub mod session_summary;
 pub mod tool_operation;
+pub mod topic;
 pub mod turn_summary;
 
 pub use analytics::Analytics;
 pub use analytics_request::{AnalyticsRequest, OperationStatus};
+pub use attachment::Attachment;
 pub use bash_metadata::BashMetadata;
-pub use chat_session::{ChatSession, SessionState};
+pub use chat_session::{ChatSession, SessionState, TitleSource};
+pub use event::{Event, EventKind};
+pub use experiment::{CohortStats, Experiment, ExperimentSession};
+pub use import_error::ImportError;
 pub use message::{Message, MessageRole, ToolCall, ToolResult, ToolUse};
+pub use message_revision::{MessageRevision, RevisionSource};
+pub use message_usage::MessageUsage;
+pub use parser_warning::{DriftKind, FormatDrift, ParserWarning};
 pub use project::Project;
 pub use provider::{ParserType, Provider, ProviderConfig, ProviderRegistry};
+pub use quota_event::{QuotaEvent, QuotaKind};
+pub use raw_payload::{RawPayload, RawPayloadKind, RawPayloadStats};
+pub use saved_view::SavedView;
+pub use session_comparison::SessionComparison;
+pub use session_quality::{QualitySummary, SessionQuality};
+pub use session_segment::SessionSegment;
 pub use session_summary::{SessionOutcome, SessionSummary as GeneratedSessionSummary};
-pub use tool_operation::ToolOperation;
+pub use tool_operation::{ToolOperation, ToolSource};
+pub use topic::{Topic, TopicLabelSource};
 pub use turn_summary::{DetectedTurn, TurnSummary, TurnType};
@@ -0,0 +1,57 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// What a parser did not recognize
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DriftKind {
+    /// A field the parser does not read or knowingly ignore
+    UnknownKey,
+    /// An entry or content block `type` the parser has no handling for
+    UnknownType,
+}
+
+impl std::fmt::Display for DriftKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DriftKind::UnknownKey => write!(f, "unknown_key"),
+            DriftKind::UnknownType => write!(f, "unknown_type"),
+        }
+    }
+}
+
+impl std::str::FromStr for DriftKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "unknown_key" => Ok(DriftKind::UnknownKey),
+            "unknown_type" => Ok(DriftKind::UnknownType),
+            _ => Err(format!("Unknown drift kind: {s}")),
+        }
+    }
+}
+
+/// Unrecognized structure met while parsing one file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FormatDrift {
+    pub kind: DriftKind,
+    /// Where it was found, e.g. `entry.fooBar` or `message.content[].type=server_tool_use`
+    pub location: String,
+    pub occurrences: u32,
+}
+
+/// Drift recorded across imports, as stored in `parser_warnings`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParserWarning {
+    pub id: i64,
+    pub provider: String,
+    pub kind: DriftKind,
+    pub location: String,
+    pub occurrences: u64,
+    /// Imports that met it
+    pub file_count: u64,
+    pub last_file_path: Option<String>,
+    pub first_seen_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+}
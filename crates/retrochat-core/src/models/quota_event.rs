@@ -0,0 +1,89 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A usage threshold from the `[quota]` config section
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum QuotaKind {
+    /// Tokens across all imported sessions in one UTC day
+    DailyTokens,
+    /// Tokens spent on LLM analyses in one week
+    WeeklyAnalysisTokens,
+    /// Dollars spent on LLM analyses in one week
+    WeeklyAnalysisUsd,
+}
+
+impl QuotaKind {
+    /// The `quota.*` config key setting this threshold
+    pub fn config_key(&self) -> &'static str {
+        match self {
+            QuotaKind::DailyTokens => "quota.daily_tokens",
+            QuotaKind::WeeklyAnalysisTokens => "quota.weekly_analysis_tokens",
+            QuotaKind::WeeklyAnalysisUsd => "quota.weekly_analysis_usd",
+        }
+    }
+
+    /// Human-readable description of what is measured
+    pub fn description(&self) -> &'static str {
+        match self {
+            QuotaKind::DailyTokens => "tokens today",
+            QuotaKind::WeeklyAnalysisTokens => "analysis tokens this week",
+            QuotaKind::WeeklyAnalysisUsd => "analysis spend this week",
+        }
+    }
+
+    /// Format an amount of this quota's unit
+    pub fn format_amount(&self, amount: f64) -> String {
+        match self {
+            QuotaKind::WeeklyAnalysisUsd => format!("${amount:.2}"),
+            _ => format!("{amount:.0}"),
+        }
+    }
+}
+
+impl std::fmt::Display for QuotaKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QuotaKind::DailyTokens => write!(f, "daily_tokens"),
+            QuotaKind::WeeklyAnalysisTokens => write!(f, "weekly_analysis_tokens"),
+            QuotaKind::WeeklyAnalysisUsd => write!(f, "weekly_analysis_usd"),
+        }
+    }
+}
+
+impl std::str::FromStr for QuotaKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "daily_tokens" => Ok(QuotaKind::DailyTokens),
+            "weekly_analysis_tokens" => Ok(QuotaKind::WeeklyAnalysisTokens),
+            "weekly_analysis_usd" => Ok(QuotaKind::WeeklyAnalysisUsd),
+            _ => Err(format!("Unknown quota: {s}")),
+        }
+    }
+}
+
+/// A quota threshold crossed during one day or week
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuotaEvent {
+    pub kind: QuotaKind,
+    /// Start of the UTC day or week the usage was counted over
+    pub period_start: DateTime<Utc>,
+    pub threshold: f64,
+    /// Usage when the check first found the threshold crossed
+    pub observed: f64,
+    pub created_at: DateTime<Utc>,
+}
+
+impl QuotaEvent {
+    /// One-line description, e.g. "2100000 tokens today (limit 2000000)"
+    pub fn message(&self) -> String {
+        format!(
+            "{} {} (limit {})",
+            self.kind.format_amount(self.observed),
+            self.kind.description(),
+            self.kind.format_amount(self.threshold)
+        )
+    }
+}
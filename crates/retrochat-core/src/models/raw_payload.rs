@@ -0,0 +1,74 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// What a raw payload was parsed into
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RawPayloadKind {
+    /// One log entry, keyed by the message ID
+    Message,
+    /// A tool_use block and its tool_result, keyed by the tool use ID
+    ToolCall,
+}
+
+impl RawPayloadKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RawPayloadKind::Message => "message",
+            RawPayloadKind::ToolCall => "tool_call",
+        }
+    }
+}
+
+impl std::fmt::Display for RawPayloadKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for RawPayloadKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "message" => Ok(RawPayloadKind::Message),
+            "tool_call" => Ok(RawPayloadKind::ToolCall),
+            _ => Err(format!("Unknown raw payload kind: {s}")),
+        }
+    }
+}
+
+/// The provider's original JSON behind a message or tool call
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RawPayload {
+    pub session_id: Uuid,
+    pub kind: RawPayloadKind,
+    /// Message ID or tool use ID, depending on `kind`
+    pub owner_id: String,
+    /// JSON text; for messages, the log line exactly as written
+    pub payload: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl RawPayload {
+    pub fn new(session_id: Uuid, kind: RawPayloadKind, owner_id: String, payload: String) -> Self {
+        Self {
+            session_id,
+            kind,
+            owner_id,
+            payload,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// Stored raw payloads, as reported by `retrochat raw status`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct RawPayloadStats {
+    pub sessions: u64,
+    pub messages: u64,
+    pub tool_calls: u64,
+    /// Size of the stored JSON text
+    pub bytes: u64,
+}
@@ -0,0 +1,32 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::services::analytics::SessionComparisonOutput;
+
+/// A comparison of two sessions, tracked by an analytics request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionComparison {
+    pub analytics_request_id: String,
+    /// First session; the request is filed under it
+    pub session_id: String,
+    pub other_session_id: String,
+    /// `None` until the request completes
+    pub result: Option<SessionComparisonOutput>,
+    pub model_used: Option<String>,
+    pub analysis_duration_ms: Option<i64>,
+    pub generated_at: Option<DateTime<Utc>>,
+}
+
+impl SessionComparison {
+    pub fn new(analytics_request_id: String, session_id: String, other_session_id: String) -> Self {
+        Self {
+            analytics_request_id,
+            session_id,
+            other_session_id,
+            result: None,
+            model_used: None,
+            analysis_duration_ms: None,
+            generated_at: None,
+        }
+    }
+}
@@ -0,0 +1,279 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use super::message::MessageType;
+use super::{Message, ToolOperation};
+
+/// Prompts up to this many characters count as short
+pub const SHORT_PROMPT_CHARS: usize = 50;
+
+/// Prompts over this many characters count as long
+pub const LONG_PROMPT_CHARS: usize = 500;
+
+/// Openings of a prompt that push back on the previous answer
+const CORRECTION_PREFIXES: &[&str] = &[
+    "no,",
+    "no.",
+    "no ",
+    "nope",
+    "wrong",
+    "undo",
+    "revert",
+    "stop,",
+    "stop.",
+    "actually,",
+    "instead,",
+];
+
+/// Phrases anywhere in a prompt that push back on the previous answer
+const CORRECTION_PHRASES: &[&str] = &[
+    "that's wrong",
+    "that is wrong",
+    "that's not",
+    "that is not what",
+    "not what i",
+    "doesn't work",
+    "does not work",
+    "didn't work",
+    "did not work",
+    "still fail",
+    "still broken",
+    "still not",
+    "still doesn't",
+    "you forgot",
+    "you missed",
+    "try again",
+];
+
+/// Conversation quality signals computed from a session's messages and tool
+/// calls alone, so they need no LLM
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SessionQuality {
+    pub session_id: Uuid,
+    /// Prompts the user typed, not counting tool results or slash commands
+    pub user_prompts: u32,
+    /// Prompts after the first that push back on the previous answer
+    pub user_corrections: u32,
+    /// Runs of an identical tool call repeated after it failed
+    pub tool_retry_loops: u32,
+    pub tool_errors: u32,
+    pub tool_successes: u32,
+    /// Prompts of at most [`SHORT_PROMPT_CHARS`] characters
+    pub short_prompts: u32,
+    pub medium_prompts: u32,
+    /// Prompts of more than [`LONG_PROMPT_CHARS`] characters
+    pub long_prompts: u32,
+    pub median_prompt_chars: u32,
+    pub computed_at: DateTime<Utc>,
+}
+
+impl SessionQuality {
+    pub fn from_messages(
+        session_id: Uuid,
+        messages: &[Message],
+        tool_operations: &[ToolOperation],
+    ) -> Self {
+        let prompts: Vec<&str> = messages
+            .iter()
+            .filter(|m| m.is_user_message() && m.message_type == MessageType::SimpleMessage)
+            .map(|m| m.content.trim())
+            .filter(|content| !content.is_empty())
+            .collect();
+
+        let user_corrections = prompts
+            .iter()
+            .skip(1)
+            .filter(|prompt| is_correction(prompt))
+            .count() as u32;
+
+        let mut lengths: Vec<usize> = prompts.iter().map(|p| p.chars().count()).collect();
+        lengths.sort_unstable();
+        let median_prompt_chars = lengths.get(lengths.len() / 2).copied().unwrap_or(0) as u32;
+        let short_prompts = lengths.iter().filter(|&&l| l <= SHORT_PROMPT_CHARS).count() as u32;
+        let long_prompts = lengths.iter().filter(|&&l| l > LONG_PROMPT_CHARS).count() as u32;
+
+        // Operations can be linked from both their request and result messages
+        let mut seen = HashSet::new();
+        let mut operations: Vec<&ToolOperation> = tool_operations
+            .iter()
+            .filter(|op| seen.insert(op.id))
+            .collect();
+        operations.sort_by_key(|op| op.timestamp);
+
+        Self {
+            session_id,
+            user_prompts: prompts.len() as u32,
+            user_corrections,
+            tool_retry_loops: count_retry_loops(&operations),
+            tool_errors: operations
+                .iter()
+                .filter(|op| op.success == Some(false))
+                .count() as u32,
+            tool_successes: operations
+                .iter()
+                .filter(|op| op.success == Some(true))
+                .count() as u32,
+            short_prompts,
+            medium_prompts: prompts.len() as u32 - short_prompts - long_prompts,
+            long_prompts,
+            median_prompt_chars,
+            computed_at: Utc::now(),
+        }
+    }
+
+    /// Share of follow-up prompts that were corrections
+    pub fn correction_rate(&self) -> f64 {
+        ratio(self.user_corrections, self.user_prompts.saturating_sub(1))
+    }
+
+    /// Share of tool calls with a known outcome that failed
+    pub fn tool_error_ratio(&self) -> f64 {
+        ratio(self.tool_errors, self.tool_errors + self.tool_successes)
+    }
+}
+
+/// Signals summed over a set of sessions
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct QualitySummary {
+    pub sessions: u32,
+    pub user_prompts: u32,
+    /// Prompts after each session's first, the ones that can be corrections
+    pub follow_up_prompts: u32,
+    pub user_corrections: u32,
+    pub tool_retry_loops: u32,
+    pub sessions_with_retry_loops: u32,
+    pub tool_errors: u32,
+    pub tool_successes: u32,
+    pub short_prompts: u32,
+    pub medium_prompts: u32,
+    pub long_prompts: u32,
+}
+
+impl QualitySummary {
+    pub fn from_sessions(sessions: &[SessionQuality]) -> Self {
+        let mut summary = Self::default();
+        for quality in sessions {
+            summary.sessions += 1;
+            summary.user_prompts += quality.user_prompts;
+            summary.follow_up_prompts += quality.user_prompts.saturating_sub(1);
+            summary.user_corrections += quality.user_corrections;
+            summary.tool_retry_loops += quality.tool_retry_loops;
+            summary.sessions_with_retry_loops += u32::from(quality.tool_retry_loops > 0);
+            summary.tool_errors += quality.tool_errors;
+            summary.tool_successes += quality.tool_successes;
+            summary.short_prompts += quality.short_prompts;
+            summary.medium_prompts += quality.medium_prompts;
+            summary.long_prompts += quality.long_prompts;
+        }
+        summary
+    }
+
+    pub fn correction_rate(&self) -> f64 {
+        ratio(self.user_corrections, self.follow_up_prompts)
+    }
+
+    pub fn tool_error_ratio(&self) -> f64 {
+        ratio(self.tool_errors, self.tool_errors + self.tool_successes)
+    }
+}
+
+/// Whether a prompt pushes back on the previous answer
+pub fn is_correction(prompt: &str) -> bool {
+    let lower = prompt.trim().to_lowercase();
+    CORRECTION_PREFIXES.iter().any(|p| lower.starts_with(p))
+        || CORRECTION_PHRASES.iter().any(|p| lower.contains(p))
+}
+
+/// Runs of consecutive identical calls (same tool and input) that started
+/// with a failure
+fn count_retry_loops(operations: &[&ToolOperation]) -> u32 {
+    let key = |op: &ToolOperation| {
+        (
+            op.tool_name.clone(),
+            op.raw_input.as_ref().map(|input| input.to_string()),
+        )
+    };
+
+    let mut loops = 0;
+    let mut in_loop = false;
+    for pair in operations.windows(2) {
+        let (previous, current) = (pair[0], pair[1]);
+        if previous.success == Some(false) && key(previous) == key(current) {
+            if !in_loop {
+                loops += 1;
+                in_loop = true;
+            }
+        } else {
+            in_loop = false;
+        }
+    }
+    loops
+}
+
+fn ratio(part: u32, whole: u32) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        part as f64 / whole as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::MessageRole;
+    use serde_json::json;
+
+    #[test]
+    fn test_quality_signals() {
+        let session_id = Uuid::new_v4();
+        let now = Utc::now();
+        let prompt = |content: &str, sequence| {
+            Message::new(
+                session_id,
+                MessageRole::User,
+                content.to_string(),
+                now,
+                sequence,
+            )
+        };
+        let messages = vec![
+            prompt("No, start by fixing the parser", 1),
+            prompt("Thanks", 2),
+            prompt("That's wrong, the test still fails", 3),
+            prompt(&"x".repeat(600), 4),
+        ];
+
+        let call = |success, minutes| {
+            ToolOperation::new(
+                "t".to_string(),
+                "Bash".to_string(),
+                now + chrono::Duration::minutes(minutes),
+            )
+            .with_raw_input(json!({ "command": "cargo test" }))
+            .with_success(success)
+        };
+        let operations = vec![call(false, 0), call(false, 1), call(true, 2), call(true, 3)];
+
+        let quality = SessionQuality::from_messages(session_id, &messages, &operations);
+        assert_eq!(quality.user_prompts, 4);
+        // The first prompt cannot correct anything
+        assert_eq!(quality.user_corrections, 1);
+        assert_eq!(quality.tool_retry_loops, 1);
+        assert_eq!(quality.tool_errors, 2);
+        assert_eq!(quality.tool_successes, 2);
+        assert_eq!(quality.short_prompts, 3);
+        assert_eq!(quality.long_prompts, 1);
+        assert!((quality.correction_rate() - 1.0 / 3.0).abs() < f64::EPSILON);
+        assert_eq!(quality.tool_error_ratio(), 0.5);
+
+        let summary = QualitySummary::from_sessions(&[quality.clone(), quality]);
+        assert_eq!(summary.follow_up_prompts, 6);
+        assert_eq!(summary.sessions_with_retry_loops, 2);
+
+        assert!(is_correction("it still doesn't compile"));
+        assert!(!is_correction("Now add a --verbose flag"));
+    }
+}
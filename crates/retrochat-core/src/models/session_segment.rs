@@ -0,0 +1,124 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::Message;
+
+/// Message metadata key parsers set on the first message after a compaction
+pub const COMPACTION_METADATA_KEY: &str = "compaction";
+
+/// Stretch of a session that ran in one continuous context
+///
+/// Context compaction replaces the conversation so far with a summary, so
+/// tokens before and after it do not share a context window. A session with
+/// no compactions is a single segment.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SessionSegment {
+    pub session_id: Uuid,
+    /// 0 for the first segment, then one more per compaction
+    pub segment_index: u32,
+    pub start_sequence: u32,
+    pub end_sequence: u32,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    /// How the compaction that opened this segment started (`auto` or
+    /// `manual`); `None` for the first segment or when the log does not say
+    pub trigger: Option<String>,
+    /// Context size just before that compaction, when the log records it
+    pub pre_compaction_tokens: Option<u64>,
+}
+
+impl SessionSegment {
+    /// Segments of a session from its messages, in sequence order
+    pub fn from_messages(session_id: Uuid, messages: &[Message]) -> Vec<Self> {
+        split_at_compactions(messages)
+            .into_iter()
+            .enumerate()
+            .map(|(index, segment)| {
+                let first = &segment[0];
+                let last = &segment[segment.len() - 1];
+                let compaction = (index > 0).then(|| compaction_of(first)).flatten();
+                Self {
+                    session_id,
+                    segment_index: index as u32,
+                    start_sequence: first.sequence_number,
+                    end_sequence: last.sequence_number,
+                    started_at: first.timestamp,
+                    ended_at: last.timestamp,
+                    trigger: compaction
+                        .and_then(|c| c.get("trigger"))
+                        .and_then(|t| t.as_str())
+                        .map(str::to_string),
+                    pre_compaction_tokens: compaction
+                        .and_then(|c| c.get("pre_tokens"))
+                        .and_then(|t| t.as_u64()),
+                }
+            })
+            .collect()
+    }
+
+    pub fn duration_minutes(&self) -> f64 {
+        (self.ended_at - self.started_at).num_seconds() as f64 / 60.0
+    }
+}
+
+fn compaction_of(message: &Message) -> Option<&serde_json::Value> {
+    message.metadata.as_ref()?.get(COMPACTION_METADATA_KEY)
+}
+
+/// Split messages (in sequence order) where a compaction reset the context
+pub fn split_at_compactions(messages: &[Message]) -> Vec<&[Message]> {
+    let mut segments = Vec::new();
+    let mut start = 0;
+    for (index, message) in messages.iter().enumerate() {
+        if index > start && compaction_of(message).is_some() {
+            segments.push(&messages[start..index]);
+            start = index;
+        }
+    }
+    if start < messages.len() {
+        segments.push(&messages[start..]);
+    }
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::MessageRole;
+    use chrono::Duration;
+
+    #[test]
+    fn test_segments_split_at_compactions() {
+        let session_id = Uuid::new_v4();
+        let start = Utc::now();
+        let mut messages: Vec<Message> = (1..=5)
+            .map(|i| {
+                let time = start + Duration::minutes(i as i64 * 10);
+                Message::new(session_id, MessageRole::User, "hi".into(), time, i)
+            })
+            .collect();
+        messages[3].insert_metadata(
+            COMPACTION_METADATA_KEY,
+            serde_json::json!({ "trigger": "auto", "pre_tokens": 155000 }),
+        );
+
+        let segments = SessionSegment::from_messages(session_id, &messages);
+        assert_eq!(segments.len(), 2);
+        assert_eq!(
+            (segments[0].start_sequence, segments[0].end_sequence),
+            (1, 3)
+        );
+        assert_eq!(segments[0].trigger, None);
+        assert_eq!(segments[0].duration_minutes(), 20.0);
+        assert_eq!(segments[1].segment_index, 1);
+        assert_eq!(
+            (segments[1].start_sequence, segments[1].end_sequence),
+            (4, 5)
+        );
+        assert_eq!(segments[1].trigger.as_deref(), Some("auto"));
+        assert_eq!(segments[1].pre_compaction_tokens, Some(155000));
+
+        assert!(SessionSegment::from_messages(session_id, &[]).is_empty());
+    }
+}
@@ -55,11 +55,74 @@ impl FileMetadata {
     }
 }
 
+/// Where a tool comes from: the agent itself or an MCP server
+///
+/// Agents name MCP tools `mcp__<server>__<tool>` (Claude Code) or
+/// `<server>__<tool>` (Gemini CLI). Stored as `builtin` or `mcp:<server>`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(into = "String", from = "String")]
+pub enum ToolSource {
+    #[default]
+    Builtin,
+    Mcp(String),
+}
+
+impl ToolSource {
+    pub fn classify(tool_name: &str) -> Self {
+        let name = tool_name.strip_prefix("mcp__").unwrap_or(tool_name);
+        match name.split_once("__") {
+            Some((server, tool)) if !server.is_empty() && !tool.is_empty() => {
+                ToolSource::Mcp(server.to_string())
+            }
+            _ => ToolSource::Builtin,
+        }
+    }
+
+    pub fn mcp_server(&self) -> Option<&str> {
+        match self {
+            ToolSource::Builtin => None,
+            ToolSource::Mcp(server) => Some(server),
+        }
+    }
+}
+
+impl std::fmt::Display for ToolSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ToolSource::Builtin => write!(f, "builtin"),
+            ToolSource::Mcp(server) => write!(f, "mcp:{server}"),
+        }
+    }
+}
+
+impl From<&str> for ToolSource {
+    fn from(value: &str) -> Self {
+        match value.strip_prefix("mcp:") {
+            Some(server) if !server.is_empty() => ToolSource::Mcp(server.to_string()),
+            _ => ToolSource::Builtin,
+        }
+    }
+}
+
+impl From<String> for ToolSource {
+    fn from(value: String) -> Self {
+        ToolSource::from(value.as_str())
+    }
+}
+
+impl From<ToolSource> for String {
+    fn from(source: ToolSource) -> Self {
+        source.to_string()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolOperation {
     pub id: Uuid,
     pub tool_use_id: String,
     pub tool_name: String,
+    #[serde(default)]
+    pub tool_source: ToolSource,
     pub timestamp: DateTime<Utc>,
 
     // File-related metadata (None for non-file tools)
@@ -75,6 +138,9 @@ pub struct ToolOperation {
     pub result_summary: Option<String>,
     pub raw_input: Option<Value>,
     pub raw_result: Option<Value>,
+    /// Milliseconds from the call to its result, when the log times both
+    #[serde(default)]
+    pub duration_ms: Option<i64>,
 
     pub created_at: DateTime<Utc>,
 }
@@ -84,6 +150,7 @@ impl ToolOperation {
         Self {
             id: Uuid::new_v4(),
             tool_use_id,
+            tool_source: ToolSource::classify(&tool_name),
             tool_name,
             timestamp,
             file_metadata: None,
@@ -92,6 +159,7 @@ impl ToolOperation {
             result_summary: None,
             raw_input: None,
             raw_result: None,
+            duration_ms: None,
             created_at: Utc::now(),
         }
     }
@@ -232,6 +300,12 @@ impl ToolOperation {
         self
     }
 
+    /// Builder method: set how long the call took
+    pub fn with_duration_ms(mut self, duration_ms: i64) -> Self {
+        self.duration_ms = Some(duration_ms);
+        self
+    }
+
     /// Count lines in a string by counting newline characters
     pub fn count_lines(text: &str) -> i32 {
         if text.is_empty() {
@@ -275,6 +349,24 @@ impl ToolOperation {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_classify_tool_source() {
+        let github = ToolSource::Mcp("github".to_string());
+        assert_eq!(ToolSource::classify("mcp__github__create_issue"), github);
+        assert_eq!(ToolSource::classify("github__create_issue"), github);
+        assert_eq!(
+            ToolSource::classify("mcp__claude_ai_Linear__list_issues"),
+            ToolSource::Mcp("claude_ai_Linear".to_string())
+        );
+        assert_eq!(ToolSource::classify("Bash"), ToolSource::Builtin);
+        assert_eq!(ToolSource::classify("read_file"), ToolSource::Builtin);
+        assert_eq!(ToolSource::classify("mcp__broken"), ToolSource::Builtin);
+
+        assert_eq!(github.to_string(), "mcp:github");
+        assert_eq!(ToolSource::from("mcp:github"), github);
+        assert_eq!(ToolSource::from("builtin"), ToolSource::Builtin);
+    }
+
     #[test]
     fn test_count_lines_empty() {
         assert_eq!(ToolOperation::count_lines(""), 0);
@@ -0,0 +1,44 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Where a topic label came from
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TopicLabelSource {
+    /// Generated by an LLM from the sessions closest to the cluster centre
+    Llm,
+    /// The most frequent words of the member sessions' titles
+    Keywords,
+}
+
+impl std::fmt::Display for TopicLabelSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TopicLabelSource::Llm => write!(f, "llm"),
+            TopicLabelSource::Keywords => write!(f, "keywords"),
+        }
+    }
+}
+
+impl std::str::FromStr for TopicLabelSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "llm" => Ok(TopicLabelSource::Llm),
+            "keywords" => Ok(TopicLabelSource::Keywords),
+            _ => Err(format!("Unknown topic label source: {s}")),
+        }
+    }
+}
+
+/// A cluster of sessions about the same subject
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Topic {
+    pub id: i64,
+    pub label: String,
+    pub description: Option<String>,
+    pub label_source: TopicLabelSource,
+    pub session_count: i64,
+    pub clustered_at: DateTime<Utc>,
+}
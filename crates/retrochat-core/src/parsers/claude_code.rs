@@ -1,18 +1,25 @@
 use anyhow::{anyhow, Context, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
 use chrono::{DateTime, Utc};
 use lazy_static::lazy_static;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
 use std::fs::File;
-use std::io::{BufRead, BufReader, Lines};
+use std::io::{BufRead, BufReader, Cursor, Lines};
 use std::path::Path;
+use std::sync::{Mutex, PoisonError};
+use tokio::io::AsyncRead;
 use uuid::Uuid;
 
-use crate::models::message::{MessageType, SlashCommandData, ToolResult, ToolUse};
-use crate::models::{ChatSession, Message, MessageRole};
+use crate::models::message::{HookEventData, MessageType, SlashCommandData, ToolResult, ToolUse};
+use crate::models::session_segment::COMPACTION_METADATA_KEY;
+use crate::models::{Attachment, ChatSession, FormatDrift, Message, MessageRole, MessageUsage};
 use crate::models::{Provider, SessionState};
 
+use super::drift::DriftCollector;
 use super::project_inference::ProjectInference;
 
 lazy_static! {
@@ -25,8 +32,29 @@ lazy_static! {
         Regex::new(r"<command-args>(.*?)</command-args>").unwrap();
     static ref COMMAND_STDOUT_RE: Regex =
         Regex::new(r"(?s)<local-command-stdout>(.*?)</local-command-stdout>").unwrap();
+    /// Context blocks Claude Code injects into user turns
+    static ref INJECTED_BLOCK_RE: Regex = Regex::new(
+        r"(?s)<(system-reminder|user-prompt-submit-hook)>(.*?)</(?:system-reminder|user-prompt-submit-hook)>"
+    )
+    .unwrap();
+    /// Hook status lines, e.g. "PostToolUse:Edit [cargo fmt] completed successfully"
+    static ref HOOK_STATUS_RE: Regex =
+        Regex::new(r"(?s)^(\w+)(?::(\S+))? \[(.*?)\] (.*)$").unwrap();
 }
 
+/// Hook events Claude Code reports in `system` entries
+const HOOK_EVENTS: &[&str] = &[
+    "PreToolUse",
+    "PostToolUse",
+    "Notification",
+    "UserPromptSubmit",
+    "Stop",
+    "SubagentStop",
+    "PreCompact",
+    "SessionStart",
+    "SessionEnd",
+];
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ClaudeCodeMessage {
     pub uuid: String,
@@ -66,6 +94,275 @@ pub struct ClaudeCodeConversationEntry {
     /// Tool use result metadata (stdout, stderr, etc.) for tool_result messages
     #[serde(rename = "toolUseResult")]
     pub tool_use_result: Option<Value>,
+    /// Text of `system` entries, such as hook status lines
+    pub content: Option<Value>,
+    pub subtype: Option<String>,
+    /// Set on user entries Claude Code wrote itself
+    #[serde(rename = "isMeta")]
+    pub is_meta: Option<bool>,
+    #[serde(rename = "hookInfos")]
+    pub hook_infos: Option<Vec<Value>>,
+    #[serde(rename = "hookErrors")]
+    pub hook_errors: Option<Vec<Value>>,
+    #[serde(rename = "preventedContinuation")]
+    pub prevented_continuation: Option<bool>,
+    /// Set on `compact_boundary` system entries
+    #[serde(rename = "compactMetadata")]
+    pub compact_metadata: Option<Value>,
+    /// Set on the user entry holding the summary a compaction left behind
+    #[serde(rename = "isCompactSummary")]
+    pub is_compact_summary: Option<bool>,
+    /// The line this entry was read from
+    #[serde(skip)]
+    pub raw: Option<String>,
+}
+
+/// Conversation entry fields, read or knowingly ignored
+const ENTRY_KEYS: &[&str] = &[
+    "type",
+    "uuid",
+    "sessionId",
+    "timestamp",
+    "message",
+    "summary",
+    "leafUuid",
+    "parentUuid",
+    "logicalParentUuid",
+    "toolUseResult",
+    "content",
+    "subtype",
+    "level",
+    "isMeta",
+    "hookInfos",
+    "hookErrors",
+    "preventedContinuation",
+    "stopReason",
+    "hasOutput",
+    "toolUseID",
+    "compactMetadata",
+    "isCompactSummary",
+    "isSidechain",
+    "isApiErrorMessage",
+    "isVisibleInTranscriptOnly",
+    "userType",
+    "cwd",
+    "version",
+    "gitBranch",
+    "slug",
+    "agentId",
+    "requestId",
+    "messageId",
+    "thinkingMetadata",
+    "todos",
+    "error",
+    "snapshot",
+    "isSnapshotUpdate",
+    "operation",
+];
+
+const ENTRY_TYPES: &[&str] = &[
+    "user",
+    "assistant",
+    "system",
+    "summary",
+    "file-history-snapshot",
+    "queue-operation",
+];
+
+/// API message fields, read or knowingly ignored
+const MESSAGE_KEYS: &[&str] = &[
+    "id",
+    "type",
+    "role",
+    "model",
+    "content",
+    "usage",
+    "stop_reason",
+    "stop_sequence",
+    "container",
+    "context_management",
+];
+
+const CONTENT_BLOCK_TYPES: &[&str] = &[
+    "text",
+    "thinking",
+    "redacted_thinking",
+    "tool_use",
+    "tool_result",
+    "image",
+    "document",
+];
+
+/// Record entry fields and types this parser does not know about
+fn check_drift(drift: &mut DriftCollector, entry: &Value) {
+    let Some(object) = entry.as_object() else {
+        return;
+    };
+    drift.check_keys("entry", object, ENTRY_KEYS);
+    if let Some(entry_type) = object.get("type").and_then(Value::as_str) {
+        drift.check_type("entry.type", entry_type, ENTRY_TYPES);
+    }
+
+    let Some(message) = object.get("message").and_then(Value::as_object) else {
+        return;
+    };
+    drift.check_keys("message", message, MESSAGE_KEYS);
+    let blocks = message.get("content").and_then(Value::as_array);
+    for block_type in blocks
+        .into_iter()
+        .flatten()
+        .filter_map(|block| block.get("type").and_then(Value::as_str))
+    {
+        drift.check_type("message.content[].type", block_type, CONTENT_BLOCK_TYPES);
+    }
+}
+
+/// Opening of the summary message older Claude Code versions write after compacting
+const COMPACT_SUMMARY_PREFIX: &str = "This session is being continued from a previous conversation";
+
+/// Compaction recorded by a `compact_boundary` system entry
+fn compaction_of(entry: &ClaudeCodeConversationEntry) -> Value {
+    let metadata = entry.compact_metadata.as_ref();
+    serde_json::json!({
+        "trigger": metadata.and_then(|m| m.get("trigger")).and_then(Value::as_str),
+        "pre_tokens": metadata.and_then(|m| m.get("preTokens")).and_then(Value::as_u64),
+    })
+}
+
+/// Attachment described by an `image` or `document` content block
+///
+/// Inline bytes come base64-encoded (or as plain text for text documents);
+/// blocks that only link to a URL keep their media type.
+fn attachment_of(kind: &str, block: &serde_json::Map<String, Value>) -> Attachment {
+    let source = block.get("source");
+    let field = |name: &str| source.and_then(|s| s.get(name)).and_then(Value::as_str);
+    let data = match field("type") {
+        Some("base64") => field("data").and_then(|data| BASE64.decode(data).ok()),
+        Some("text") => field("data").map(|text| text.as_bytes().to_vec()),
+        _ => None,
+    };
+    Attachment::new(kind, field("media_type").map(str::to_string), data)
+}
+
+/// Record a pending compaction on the first message written after it
+fn mark_compaction(messages: &mut [Message], compaction: &mut Option<(usize, Value)>) {
+    if matches!(compaction, Some((index, _)) if *index < messages.len()) {
+        if let Some((index, value)) = compaction.take() {
+            messages[index].insert_metadata(COMPACTION_METADATA_KEY, value);
+        }
+    }
+}
+
+/// A message Claude Code added to a user entry
+struct InjectedBlock {
+    message_type: MessageType,
+    content: String,
+    metadata: Option<Value>,
+}
+
+/// Hook run described by a `system` entry, if it is one
+fn hook_event_of(entry: &ClaudeCodeConversationEntry) -> Option<(String, HookEventData)> {
+    let content = entry
+        .content
+        .as_ref()
+        .and_then(|c| c.as_str())
+        .unwrap_or_default();
+
+    if entry.subtype.as_deref() == Some("stop_hook_summary") {
+        let command = entry
+            .hook_infos
+            .iter()
+            .flatten()
+            .filter_map(|info| info.get("command").and_then(|c| c.as_str()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let errors = entry.hook_errors.as_ref().map_or(0, Vec::len);
+        let outcome = if entry.prevented_continuation == Some(true) {
+            "blocked"
+        } else if errors > 0 {
+            "failed"
+        } else {
+            "success"
+        };
+        let text = if content.is_empty() {
+            format!("Stop hook {outcome}")
+        } else {
+            content.to_string()
+        };
+        let hook = HookEventData {
+            event: "Stop".to_string(),
+            matcher: None,
+            command: Some(command).filter(|c| !c.is_empty()),
+            outcome: outcome.to_string(),
+        };
+        return Some((text, hook));
+    }
+
+    let caps = HOOK_STATUS_RE.captures(content)?;
+    let event = caps.get(1)?.as_str();
+    if !HOOK_EVENTS.contains(&event) {
+        return None;
+    }
+    let status = caps.get(4).map_or("", |m| m.as_str()).to_lowercase();
+    let outcome = if status.contains("block") && !status.contains("non-blocking") {
+        "blocked"
+    } else if status.contains("fail") || status.contains("error") {
+        "failed"
+    } else {
+        "success"
+    };
+    let hook = HookEventData {
+        event: event.to_string(),
+        matcher: caps.get(2).map(|m| m.as_str().to_string()),
+        command: caps.get(3).map(|m| m.as_str().to_string()),
+        outcome: outcome.to_string(),
+    };
+    Some((content.to_string(), hook))
+}
+
+/// Split system reminders and prompt hook output out of a user message
+///
+/// Returns what the user typed ("[No content]" if nothing) and the blocks.
+fn split_injected_blocks(content: &str) -> (String, Vec<InjectedBlock>) {
+    let mut blocks = Vec::new();
+    for caps in INJECTED_BLOCK_RE.captures_iter(content) {
+        let text = caps[2].trim().to_string();
+        if text.is_empty() {
+            continue;
+        }
+        let block = if &caps[1] == "system-reminder" {
+            InjectedBlock {
+                message_type: MessageType::SystemReminder,
+                content: text,
+                metadata: None,
+            }
+        } else {
+            let hook = HookEventData {
+                event: "UserPromptSubmit".to_string(),
+                matcher: None,
+                command: None,
+                outcome: "success".to_string(),
+            };
+            InjectedBlock {
+                message_type: MessageType::HookEvent,
+                content: text,
+                metadata: Some(serde_json::json!({ "hook": hook })),
+            }
+        };
+        blocks.push(block);
+    }
+    if blocks.is_empty() {
+        return (content.to_string(), blocks);
+    }
+
+    let rest = INJECTED_BLOCK_RE.replace_all(content, "");
+    let rest = rest.trim();
+    let rest = if rest.is_empty() {
+        "[No content]".to_string()
+    } else {
+        rest.to_string()
+    };
+    (rest, blocks)
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -74,19 +371,116 @@ pub struct ConversationMessage {
     pub content: Value,
     pub id: Option<String>,
     pub model: Option<String>,
+    pub usage: Option<ClaudeUsage>,
+}
+
+/// Token usage reported by the API for an assistant message
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ClaudeUsage {
+    pub input_tokens: Option<u64>,
+    pub output_tokens: Option<u64>,
+    pub cache_creation_input_tokens: Option<u64>,
+    pub cache_read_input_tokens: Option<u64>,
+    pub service_tier: Option<String>,
+}
+
+impl ClaudeUsage {
+    fn to_message_usage(&self, model: Option<&str>) -> MessageUsage {
+        MessageUsage {
+            model: model.map(str::to_string),
+            input_tokens: self.input_tokens.unwrap_or(0),
+            output_tokens: self.output_tokens.unwrap_or(0),
+            cache_creation_input_tokens: self.cache_creation_input_tokens.unwrap_or(0),
+            cache_read_input_tokens: self.cache_read_input_tokens.unwrap_or(0),
+            service_tier: self.service_tier.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+fn usage_of(message: &ConversationMessage) -> Option<MessageUsage> {
+    message
+        .usage
+        .as_ref()
+        .map(|usage| usage.to_message_usage(message.model.as_deref()))
+}
+
+/// Attaches usage to messages, once per API response
+///
+/// Claude Code writes one entry per content block (thinking, text, each
+/// tool call) and repeats the response's usage on every one of them, so
+/// usage is keyed by the API message id. The last entry carries the final
+/// output token count.
+#[derive(Default)]
+struct UsageTracker {
+    /// API message id -> index of the message holding its usage
+    targets: HashMap<String, usize>,
+    /// Usage of responses whose entries produced no message yet
+    pending: HashMap<String, MessageUsage>,
+}
+
+impl UsageTracker {
+    /// `first_new` is the index of the first message created for the entry
+    fn attach(
+        &mut self,
+        messages: &mut [Message],
+        first_new: usize,
+        api_id: Option<&str>,
+        usage: Option<MessageUsage>,
+    ) {
+        let usage = match (usage, api_id) {
+            (Some(usage), _) => usage,
+            (None, Some(id)) if first_new < messages.len() => match self.pending.remove(id) {
+                Some(usage) => usage,
+                None => return,
+            },
+            (None, _) => return,
+        };
+
+        if let Some(index) = api_id.and_then(|id| self.targets.get(id)) {
+            messages[*index].usage = Some(usage);
+        } else if first_new < messages.len() {
+            messages[first_new].usage = Some(usage);
+            if let Some(id) = api_id {
+                self.targets.insert(id.to_string(), first_new);
+                self.pending.remove(id);
+            }
+        } else if let Some(id) = api_id {
+            self.pending.insert(id.to_string(), usage);
+        }
+    }
 }
 
 pub struct ClaudeCodeParser {
     file_path: String,
+    source_hash: Option<String>,
+    drift: Mutex<DriftCollector>,
 }
 
 impl ClaudeCodeParser {
     pub fn new(file_path: impl AsRef<Path>) -> Self {
         Self {
             file_path: file_path.as_ref().to_string_lossy().to_string(),
+            source_hash: None,
+            drift: Mutex::default(),
         }
     }
 
+    /// Use a hash of content read from elsewhere instead of the file's
+    /// metadata, for sessions parsed with `parse_reader`
+    pub fn with_source_hash(mut self, hash: String) -> Self {
+        self.source_hash = Some(hash);
+        self
+    }
+
+    /// Unrecognized fields and types met by the last parse
+    pub fn take_drift(&self) -> Vec<FormatDrift> {
+        self.drift
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+    }
+
     pub async fn parse(&self) -> Result<(ChatSession, Vec<Message>)> {
         let file = File::open(&self.file_path)
             .with_context(|| format!("Failed to open file: {}", self.file_path))?;
@@ -95,6 +489,15 @@ impl ClaudeCodeParser {
         self.parse_from_reader(reader).await
     }
 
+    /// Parse a session from content that is not a file on disk, e.g. an upload
+    pub async fn parse_reader<R: AsyncRead + Unpin>(
+        &self,
+        reader: R,
+    ) -> Result<(ChatSession, Vec<Message>)> {
+        let content = super::read_source(reader).await?;
+        self.parse_lines(Cursor::new(content).lines()).await
+    }
+
     async fn parse_from_reader(
         &self,
         reader: BufReader<File>,
@@ -110,6 +513,7 @@ impl ClaudeCodeParser {
         let mut conversation_entries: Vec<ClaudeCodeConversationEntry> = Vec::new();
         let mut sessions: Vec<ClaudeCodeSession> = Vec::new();
         let mut is_conversation_format = false;
+        let mut drift = DriftCollector::default();
 
         for line in lines {
             let line = line.with_context(|| "Failed to read line from file")?;
@@ -118,11 +522,16 @@ impl ClaudeCodeParser {
                 continue;
             }
 
+            let value: Value = serde_json::from_str(&line)
+                .map_err(|_| anyhow!("Failed to parse line as JSON: {line}"))?;
+
             // Try to parse as conversation format first
-            if let Ok(entry) = serde_json::from_str::<ClaudeCodeConversationEntry>(&line) {
+            if let Ok(mut entry) = ClaudeCodeConversationEntry::deserialize(&value) {
+                check_drift(&mut drift, &value);
+                entry.raw = Some(line);
                 conversation_entries.push(entry);
                 is_conversation_format = true;
-            } else if let Ok(session) = serde_json::from_str::<ClaudeCodeSession>(&line) {
+            } else if let Ok(session) = ClaudeCodeSession::deserialize(&value) {
                 if is_conversation_format {
                     return Err(anyhow!(
                         "Mixed format detected: cannot mix conversation and session formats"
@@ -133,6 +542,7 @@ impl ClaudeCodeParser {
                 return Err(anyhow!("Failed to parse line as JSON: {line}"));
             }
         }
+        *self.drift.lock().unwrap_or_else(PoisonError::into_inner) = drift;
 
         if is_conversation_format {
             self.parse_conversation_format(conversation_entries)
@@ -221,12 +631,45 @@ impl ClaudeCodeParser {
 
         // Convert conversation entries to messages
         let mut messages = Vec::new();
-        let mut total_tokens = 0u32;
         let mut sequence = 1;
+        let mut usage_tracker = UsageTracker::default();
+        // Compaction waiting for the first message after it, by that message's index
+        let mut compaction: Option<(usize, Value)> = None;
 
         for entry in &entries {
+            mark_compaction(&mut messages, &mut compaction);
+
+            if entry.entry_type == "system" {
+                if entry.subtype.as_deref() == Some("compact_boundary") {
+                    compaction = Some((messages.len(), compaction_of(entry)));
+                } else if let Some((content, hook)) = hook_event_of(entry) {
+                    let timestamp = entry
+                        .timestamp
+                        .as_ref()
+                        .and_then(|ts| self.parse_timestamp(ts).ok())
+                        .unwrap_or(start_time);
+                    let mut message = Message::new(
+                        session_id,
+                        MessageRole::System,
+                        content,
+                        timestamp,
+                        sequence,
+                    )
+                    .with_message_type(MessageType::HookEvent)
+                    .with_metadata(serde_json::json!({ "hook": hook }));
+                    message.raw = entry.raw.clone();
+                    if let Some(id) = entry.uuid.as_ref().and_then(|u| Uuid::parse_str(u).ok()) {
+                        message.id = id;
+                    }
+                    messages.push(message);
+                    sequence += 1;
+                }
+                continue;
+            }
+
             if let Some(conv_message) = &entry.message {
                 if conv_message.role == "user" || conv_message.role == "assistant" {
+                    let first_new = messages.len();
                     let message_id = entry
                         .uuid
                         .as_ref()
@@ -245,8 +688,14 @@ impl ClaudeCodeParser {
                         .and_then(|ts| self.parse_timestamp(ts).ok())
                         .unwrap_or(start_time);
 
-                    let (content, tool_uses, mut tool_results, thinking_content, is_slash_command) =
-                        self.extract_tools_and_content(&conv_message.content);
+                    let (
+                        content,
+                        tool_uses,
+                        mut tool_results,
+                        thinking_content,
+                        is_slash_command,
+                        attachments,
+                    ) = self.extract_tools_and_content(&conv_message.content);
 
                     // If there's thinking content, create a separate message for it first
                     if let Some(thinking_text) = thinking_content {
@@ -259,14 +708,6 @@ impl ClaudeCodeParser {
                         )
                         .with_message_type(MessageType::Thinking);
 
-                        let thinking_tokens = (thinking_text.len() / 4) as u32;
-                        let thinking_message = if thinking_tokens > 0 {
-                            total_tokens += thinking_tokens;
-                            thinking_message.with_token_count(thinking_tokens)
-                        } else {
-                            thinking_message
-                        };
-
                         messages.push(thinking_message);
                         sequence += 1;
                     }
@@ -279,49 +720,95 @@ impl ClaudeCodeParser {
                         }
                     }
 
-                    // Skip messages with no meaningful content and no tools and no slash command
-                    if content == "[No content]"
-                        && tool_uses.is_empty()
-                        && tool_results.is_empty()
-                        && !is_slash_command
-                    {
-                        continue;
+                    let is_compact_summary = role == MessageRole::User
+                        && (entry.is_compact_summary == Some(true)
+                            || content.starts_with(COMPACT_SUMMARY_PREFIX));
+                    if is_compact_summary && compaction.is_none() {
+                        // Older logs have no boundary entry, only the summary
+                        let unknown = serde_json::json!({ "trigger": null, "pre_tokens": null });
+                        compaction = Some((messages.len(), unknown));
                     }
 
-                    let mut message = Message::new(session_id, role, content, timestamp, sequence);
+                    // Reminders and hook output Claude Code added to the user's turn
+                    let (content, injected) = if role != MessageRole::User || is_slash_command {
+                        (content, Vec::new())
+                    } else if (entry.is_meta == Some(true) || is_compact_summary)
+                        && content != "[No content]"
+                    {
+                        let block = InjectedBlock {
+                            message_type: MessageType::SystemReminder,
+                            content,
+                            metadata: None,
+                        };
+                        ("[No content]".to_string(), vec![block])
+                    } else {
+                        split_injected_blocks(&content)
+                    };
 
-                    message.id = message_id;
+                    // Skip messages with no meaningful content and no tools and no slash command
+                    let is_empty = content == "[No content]"
+                        && tool_uses.is_empty()
+                        && tool_results.is_empty()
+                        && !is_slash_command;
+                    if !is_empty {
+                        let mut message =
+                            Message::new(session_id, role, content, timestamp, sequence);
+
+                        message.id = message_id;
+                        message.raw = entry.raw.clone();
+
+                        // Set message type for slash commands
+                        if is_slash_command {
+                            let command = message.content.lines().next().unwrap_or_default();
+                            let metadata = serde_json::json!({ "command": command });
+                            message = message
+                                .with_message_type(MessageType::SlashCommand)
+                                .with_metadata(metadata);
+                        }
 
-                    // Set message type for slash commands
-                    if is_slash_command {
-                        message = message.with_message_type(MessageType::SlashCommand);
-                    }
+                        // Attach tool uses and results if any
+                        if !tool_uses.is_empty() {
+                            message = message.with_tool_uses(tool_uses);
+                        }
+                        if !tool_results.is_empty() {
+                            message = message.with_tool_results(tool_results);
+                        }
+                        if !attachments.is_empty() {
+                            message = message.with_attachments(attachments);
+                        }
 
-                    // Attach tool uses and results if any
-                    if !tool_uses.is_empty() {
-                        message = message.with_tool_uses(tool_uses);
-                    }
-                    if !tool_results.is_empty() {
-                        message = message.with_tool_results(tool_results);
+                        messages.push(message);
+                        sequence += 1;
                     }
 
-                    // Estimate token count based on content length
-                    let estimated_tokens = (message.content.len() / 4) as u32;
-                    if estimated_tokens > 0 {
-                        message = message.with_token_count(estimated_tokens);
-                        total_tokens += estimated_tokens;
+                    for block in injected {
+                        let mut message = Message::new(
+                            session_id,
+                            MessageRole::System,
+                            block.content,
+                            timestamp,
+                            sequence,
+                        )
+                        .with_message_type(block.message_type);
+                        if let Some(metadata) = block.metadata {
+                            message = message.with_metadata(metadata);
+                        }
+                        messages.push(message);
+                        sequence += 1;
                     }
 
-                    messages.push(message);
-                    sequence += 1;
+                    usage_tracker.attach(
+                        &mut messages,
+                        first_new,
+                        conv_message.id.as_deref(),
+                        usage_of(conv_message),
+                    );
                 }
             }
         }
+        mark_compaction(&mut messages, &mut compaction);
 
         chat_session.message_count = messages.len() as u32;
-        if total_tokens > 0 {
-            chat_session = chat_session.with_token_count(total_tokens);
-        }
 
         chat_session.set_state(SessionState::Imported);
 
@@ -382,9 +869,17 @@ impl ClaudeCodeParser {
     fn extract_tools_and_content(
         &self,
         value: &Value,
-    ) -> (String, Vec<ToolUse>, Vec<ToolResult>, Option<String>, bool) {
+    ) -> (
+        String,
+        Vec<ToolUse>,
+        Vec<ToolResult>,
+        Option<String>,
+        bool,
+        Vec<Attachment>,
+    ) {
         let mut tool_uses = Vec::new();
         let mut tool_results = Vec::new();
+        let mut attachments = Vec::new();
         let mut thinking_content: Option<String> = None;
 
         let content = match value {
@@ -469,6 +964,14 @@ impl ClaudeCodeParser {
                             continue;
                         }
 
+                        // Handle pasted images and documents
+                        if let Some(kind @ ("image" | "document")) = item_type {
+                            let attachment = attachment_of(kind, obj);
+                            content_parts.push(format!("[{}]", attachment.label()));
+                            attachments.push(attachment);
+                            continue;
+                        }
+
                         // Handle text content
                         if let Some(text) = obj.get("text").and_then(|v| v.as_str()) {
                             content_parts.push(text.to_string());
@@ -507,6 +1010,7 @@ impl ClaudeCodeParser {
             tool_results,
             thinking_content,
             slash_command.is_some(),
+            attachments,
         )
     }
 
@@ -590,7 +1094,7 @@ impl ClaudeCodeParser {
             _ => return Err(anyhow!("Unknown message role: {}", claude_message.role)),
         };
 
-        let (content, tool_uses, tool_results, _thinking_content, is_slash_command) =
+        let (content, tool_uses, tool_results, _thinking_content, is_slash_command, attachments) =
             self.extract_tools_and_content(&claude_message.content);
         // Note: thinking_content is ignored for legacy format
 
@@ -612,11 +1116,8 @@ impl ClaudeCodeParser {
         if !tool_results.is_empty() {
             message = message.with_tool_results(tool_results);
         }
-
-        // Estimate token count based on content length
-        let estimated_tokens = (message.content.len() / 4) as u32; // Rough estimate: 4 chars per token
-        if estimated_tokens > 0 {
-            message = message.with_token_count(estimated_tokens);
+        if !attachments.is_empty() {
+            message = message.with_attachments(attachments);
         }
 
         if let Some(metadata) = &claude_message.metadata {
@@ -659,6 +1160,10 @@ impl ClaudeCodeParser {
         use std::collections::hash_map::DefaultHasher;
         use std::hash::{Hash, Hasher};
 
+        if let Some(hash) = &self.source_hash {
+            return Ok(hash.clone());
+        }
+
         let metadata = std::fs::metadata(&self.file_path)
             .with_context(|| format!("Failed to get file metadata: {}", self.file_path))?;
 
@@ -768,6 +1273,7 @@ impl ClaudeCodeParser {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::SessionSegment;
     use std::io::Write;
     use tempfile::NamedTempFile;
 
@@ -791,6 +1297,178 @@ mod tests {
         assert_eq!(messages[1].role, MessageRole::Assistant);
     }
 
+    #[tokio::test]
+    async fn test_parse_usage_once_per_response() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        let usage = |output: u64| {
+            format!(
+                r#"{{"input_tokens":4,"output_tokens":{output},"cache_creation_input_tokens":1200,"cache_read_input_tokens":15000,"service_tier":"standard"}}"#
+            )
+        };
+        let lines = [
+            r#"{"type":"user","uuid":"550e8400-e29b-41d4-a716-446655440001","sessionId":"550e8400-e29b-41d4-a716-446655440000","timestamp":"2024-01-01T10:00:00Z","message":{"role":"user","content":"Fix the bug"}}"#.to_string(),
+            format!(
+                r#"{{"type":"assistant","uuid":"550e8400-e29b-41d4-a716-446655440002","sessionId":"550e8400-e29b-41d4-a716-446655440000","timestamp":"2024-01-01T10:00:01Z","message":{{"role":"assistant","id":"msg_1","model":"claude-sonnet-4","content":[{{"type":"text","text":"Looking"}}],"usage":{}}}}}"#,
+                usage(8)
+            ),
+            format!(
+                r#"{{"type":"assistant","uuid":"550e8400-e29b-41d4-a716-446655440003","sessionId":"550e8400-e29b-41d4-a716-446655440000","timestamp":"2024-01-01T10:00:02Z","message":{{"role":"assistant","id":"msg_1","model":"claude-sonnet-4","content":[{{"type":"tool_use","id":"toolu_1","name":"Read","input":{{"file_path":"src/main.rs"}}}}],"usage":{}}}}}"#,
+                usage(120)
+            ),
+        ];
+        temp_file.write_all(lines.join("\n").as_bytes()).unwrap();
+
+        let parser = ClaudeCodeParser::new(temp_file.path());
+        let (_, messages) = parser.parse().await.unwrap();
+
+        assert_eq!(messages.len(), 3);
+        assert!(messages[0].usage.is_none());
+        assert!(messages[2].usage.is_none());
+        let usage = messages[1].usage.as_ref().unwrap();
+        assert_eq!(usage.output_tokens, 120);
+        assert_eq!(usage.cache_read_input_tokens, 15000);
+        assert_eq!(usage.cache_creation_input_tokens, 1200);
+        assert_eq!(usage.model.as_deref(), Some("claude-sonnet-4"));
+        assert_eq!(usage.service_tier.as_deref(), Some("standard"));
+    }
+
+    #[tokio::test]
+    async fn test_parse_hooks_and_system_reminders() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        let lines = [
+            r#"{"type":"user","uuid":"550e8400-e29b-41d4-a716-446655440001","sessionId":"550e8400-e29b-41d4-a716-446655440000","timestamp":"2024-01-01T10:00:00Z","message":{"role":"user","content":"<command-name>/compact</command-name>\n<command-message>compact</command-message>\n<command-args></command-args>"}}"#,
+            r#"{"type":"user","uuid":"550e8400-e29b-41d4-a716-446655440002","sessionId":"550e8400-e29b-41d4-a716-446655440000","timestamp":"2024-01-01T10:00:01Z","isMeta":true,"message":{"role":"user","content":"Caveat: The messages below were generated by the user while running local commands."}}"#,
+            r#"{"type":"user","uuid":"550e8400-e29b-41d4-a716-446655440003","sessionId":"550e8400-e29b-41d4-a716-446655440000","timestamp":"2024-01-01T10:00:02Z","message":{"role":"user","content":"Fix the bug\n<system-reminder>The task list is empty.</system-reminder>"}}"#,
+            r#"{"type":"system","uuid":"550e8400-e29b-41d4-a716-446655440004","sessionId":"550e8400-e29b-41d4-a716-446655440000","timestamp":"2024-01-01T10:00:03Z","content":"PostToolUse:Edit [cargo fmt] failed with non-blocking status code 1: error","level":"warning"}"#,
+            r#"{"type":"system","uuid":"550e8400-e29b-41d4-a716-446655440005","sessionId":"550e8400-e29b-41d4-a716-446655440000","timestamp":"2024-01-01T10:00:04Z","subtype":"stop_hook_summary","hookInfos":[{"command":"./check.sh"}],"hookErrors":[],"preventedContinuation":false}"#,
+            r#"{"type":"system","uuid":"550e8400-e29b-41d4-a716-446655440006","sessionId":"550e8400-e29b-41d4-a716-446655440000","timestamp":"2024-01-01T10:00:05Z","content":"Conversation compacted"}"#,
+        ];
+        temp_file.write_all(lines.join("\n").as_bytes()).unwrap();
+
+        let parser = ClaudeCodeParser::new(temp_file.path());
+        let (_, messages) = parser.parse().await.unwrap();
+        let types: Vec<_> = messages.iter().map(|m| m.message_type.clone()).collect();
+        assert_eq!(
+            types,
+            vec![
+                MessageType::SlashCommand,
+                MessageType::SystemReminder,
+                MessageType::SimpleMessage,
+                MessageType::SystemReminder,
+                MessageType::HookEvent,
+                MessageType::HookEvent,
+            ]
+        );
+
+        assert_eq!(
+            messages[0].metadata.as_ref().unwrap()["command"],
+            "/compact"
+        );
+        assert_eq!(messages[1].role, MessageRole::System);
+        assert_eq!(messages[2].content, "Fix the bug");
+        assert_eq!(messages[3].content, "The task list is empty.");
+
+        let hook: HookEventData =
+            serde_json::from_value(messages[4].metadata.as_ref().unwrap()["hook"].clone()).unwrap();
+        assert_eq!(hook.event, "PostToolUse");
+        assert_eq!(hook.matcher.as_deref(), Some("Edit"));
+        assert_eq!(hook.command.as_deref(), Some("cargo fmt"));
+        assert_eq!(hook.outcome, "failed");
+
+        let stop = &messages[5].metadata.as_ref().unwrap()["hook"];
+        assert_eq!(stop["event"], "Stop");
+        assert_eq!(stop["outcome"], "success");
+    }
+
+    #[tokio::test]
+    async fn test_parse_compaction_boundaries() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        let lines = [
+            r#"{"type":"user","uuid":"550e8400-e29b-41d4-a716-446655440001","sessionId":"550e8400-e29b-41d4-a716-446655440000","timestamp":"2024-01-01T10:00:00Z","message":{"role":"user","content":"Refactor the parser"}}"#,
+            r#"{"type":"system","uuid":"550e8400-e29b-41d4-a716-446655440002","sessionId":"550e8400-e29b-41d4-a716-446655440000","timestamp":"2024-01-01T11:00:00Z","subtype":"compact_boundary","content":"Conversation compacted","compactMetadata":{"trigger":"auto","preTokens":155000}}"#,
+            r#"{"type":"user","uuid":"550e8400-e29b-41d4-a716-446655440003","sessionId":"550e8400-e29b-41d4-a716-446655440000","timestamp":"2024-01-01T11:00:01Z","isCompactSummary":true,"message":{"role":"user","content":"This session is being continued from a previous conversation. Summary: parser refactor"}}"#,
+            r#"{"type":"assistant","uuid":"550e8400-e29b-41d4-a716-446655440004","sessionId":"550e8400-e29b-41d4-a716-446655440000","timestamp":"2024-01-01T11:00:05Z","message":{"role":"assistant","content":"Continuing."}}"#,
+            r#"{"type":"user","uuid":"550e8400-e29b-41d4-a716-446655440005","sessionId":"550e8400-e29b-41d4-a716-446655440000","timestamp":"2024-01-01T12:00:00Z","message":{"role":"user","content":"This session is being continued from a previous conversation that ran out of context."}}"#,
+        ];
+        temp_file.write_all(lines.join("\n").as_bytes()).unwrap();
+
+        let parser = ClaudeCodeParser::new(temp_file.path());
+        let (session, messages) = parser.parse().await.unwrap();
+        assert_eq!(messages.len(), 4);
+        assert_eq!(messages[1].message_type, MessageType::SystemReminder);
+        assert_eq!(
+            messages[1].metadata.as_ref().unwrap()["compaction"]["pre_tokens"],
+            155000
+        );
+        assert_eq!(messages[3].message_type, MessageType::SystemReminder);
+
+        let segments = SessionSegment::from_messages(session.id, &messages);
+        assert_eq!(segments.len(), 3);
+        assert_eq!(segments[1].trigger.as_deref(), Some("auto"));
+        assert_eq!(segments[1].pre_compaction_tokens, Some(155000));
+        // The older summary-only format has no boundary details
+        assert_eq!(segments[2].trigger, None);
+        assert_eq!(segments[2].start_sequence, messages[3].sequence_number);
+    }
+
+    #[tokio::test]
+    async fn test_parse_image_attachments() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        // "iVBORw0KGgo=" is the 8-byte PNG signature
+        let line = r#"{"type":"user","uuid":"550e8400-e29b-41d4-a716-446655440001","sessionId":"550e8400-e29b-41d4-a716-446655440000","timestamp":"2024-01-01T10:00:00Z","message":{"role":"user","content":[{"type":"image","source":{"type":"base64","media_type":"image/png","data":"iVBORw0KGgo="}},{"type":"text","text":"What is wrong here?"}]}}"#;
+        temp_file.write_all(line.as_bytes()).unwrap();
+
+        let parser = ClaudeCodeParser::new(temp_file.path());
+        let (_, messages) = parser.parse().await.unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(
+            messages[0].content,
+            "[Image: image/png, 8 B] What is wrong here?"
+        );
+
+        let attachments = messages[0].attachments.as_ref().unwrap();
+        assert_eq!(attachments.len(), 1);
+        assert_eq!(attachments[0].kind, "image");
+        assert_eq!(attachments[0].size_bytes, 8);
+        assert_eq!(
+            attachments[0].data.as_deref(),
+            Some(&b"\x89PNG\r\n\x1a\n"[..])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parse_records_format_drift() {
+        use crate::models::DriftKind;
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        let lines = [
+            r#"{"type":"user","uuid":"550e8400-e29b-41d4-a716-446655440001","sessionId":"550e8400-e29b-41d4-a716-446655440000","timestamp":"2024-01-01T10:00:00Z","cwd":"/home/dev","message":{"role":"user","content":"Search the docs"}}"#,
+            r#"{"type":"assistant","uuid":"550e8400-e29b-41d4-a716-446655440002","sessionId":"550e8400-e29b-41d4-a716-446655440000","timestamp":"2024-01-01T10:00:05Z","branchLabel":"main","message":{"role":"assistant","content":[{"type":"server_tool_use","id":"srv_1","name":"web_search"},{"type":"text","text":"Found it"}]}}"#,
+        ];
+        temp_file.write_all(lines.join("\n").as_bytes()).unwrap();
+
+        let parser = ClaudeCodeParser::new(temp_file.path());
+        let (_, messages) = parser.parse().await.unwrap();
+        assert_eq!(messages.len(), 2);
+
+        let drift = parser.take_drift();
+        let found: Vec<(DriftKind, &str)> = drift
+            .iter()
+            .map(|d| (d.kind, d.location.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (DriftKind::UnknownKey, "entry.branchLabel"),
+                (
+                    DriftKind::UnknownType,
+                    "message.content[].type=server_tool_use"
+                ),
+            ]
+        );
+        assert!(parser.take_drift().is_empty());
+    }
+
     #[test]
     fn test_is_valid_file() {
         use std::fs;
@@ -3,15 +3,79 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::fs::File;
-use std::io::{BufRead, BufReader, Lines};
+use std::io::{BufRead, BufReader, Cursor, Lines};
 use std::path::Path;
+use std::sync::{Mutex, PoisonError};
+use tokio::io::AsyncRead;
 use uuid::Uuid;
 
-use crate::models::{ChatSession, Message, MessageRole};
+use crate::models::{ChatSession, FormatDrift, Message, MessageRole};
 use crate::models::{Provider, SessionState};
 
+use super::drift::DriftCollector;
 use super::project_inference::ProjectInference;
 
+/// Rollout event types, handled or knowingly ignored
+const EVENT_TYPES: &[&str] = &[
+    "session_meta",
+    "event_msg",
+    "response_item",
+    "turn_context",
+    "compacted",
+];
+
+const EVENT_MSG_TYPES: &[&str] = &[
+    "user_message",
+    "agent_message",
+    "token_count",
+    "agent_reasoning",
+    "agent_reasoning_raw_content",
+    "agent_reasoning_section_break",
+    "task_started",
+    "task_complete",
+    "turn_aborted",
+    "turn_diff",
+    "plan_update",
+    "exec_command_begin",
+    "exec_command_end",
+    "patch_apply_begin",
+    "patch_apply_end",
+    "mcp_tool_call_begin",
+    "mcp_tool_call_end",
+    "web_search_begin",
+    "web_search_end",
+    "entered_review_mode",
+    "exited_review_mode",
+    "background_event",
+    "stream_error",
+    "error",
+];
+
+const RESPONSE_ITEM_TYPES: &[&str] = &[
+    "message",
+    "reasoning",
+    "function_call",
+    "function_call_output",
+    "custom_tool_call",
+    "custom_tool_call_output",
+    "local_shell_call",
+    "web_search_call",
+    "ghost_snapshot",
+];
+
+/// Record event and payload types this parser does not know about
+fn check_drift(drift: &mut DriftCollector, event: &CodexEvent) {
+    drift.check_type("event.type", &event.event_type, EVENT_TYPES);
+    let known = match event.event_type.as_str() {
+        "event_msg" => EVENT_MSG_TYPES,
+        "response_item" => RESPONSE_ITEM_TYPES,
+        _ => return,
+    };
+    if let Some(payload_type) = event.payload.get("type").and_then(Value::as_str) {
+        drift.check_type(&format!("{}.type", event.event_type), payload_type, known);
+    }
+}
+
 // ===== New Event-based Format Structures =====
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -87,15 +151,34 @@ pub struct TotalTokenUsage {
 
 pub struct CodexParser {
     file_path: String,
+    source_hash: Option<String>,
+    drift: Mutex<DriftCollector>,
 }
 
 impl CodexParser {
     pub fn new(file_path: impl AsRef<Path>) -> Self {
         Self {
             file_path: file_path.as_ref().to_string_lossy().to_string(),
+            source_hash: None,
+            drift: Mutex::default(),
         }
     }
 
+    /// Use a hash of content read from elsewhere instead of the file's
+    /// metadata, for sessions parsed with `parse_reader`
+    pub fn with_source_hash(mut self, hash: String) -> Self {
+        self.source_hash = Some(hash);
+        self
+    }
+
+    /// Unrecognized event types met by the last parse
+    pub fn take_drift(&self) -> Vec<FormatDrift> {
+        self.drift
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+    }
+
     pub async fn parse(&self) -> Result<(ChatSession, Vec<Message>)> {
         let file = File::open(&self.file_path)
             .with_context(|| format!("Failed to open file: {}", self.file_path))?;
@@ -104,6 +187,15 @@ impl CodexParser {
         self.parse_from_reader(reader).await
     }
 
+    /// Parse a session from content that is not a file on disk, e.g. an upload
+    pub async fn parse_reader<R: AsyncRead + Unpin>(
+        &self,
+        reader: R,
+    ) -> Result<(ChatSession, Vec<Message>)> {
+        let content = super::read_source(reader).await?;
+        self.parse_lines(Cursor::new(content).lines()).await
+    }
+
     async fn parse_from_reader(
         &self,
         reader: BufReader<File>,
@@ -120,6 +212,7 @@ impl CodexParser {
         let mut messages: Vec<(String, MessageRole, String)> = Vec::new(); // (timestamp, role, content)
         let mut legacy_messages: Vec<(MessageRole, String)> = Vec::new(); // For legacy messages without timestamps
         let mut total_tokens: Option<u32> = None;
+        let mut drift = DriftCollector::default();
 
         for line in lines {
             let line = line.with_context(|| "Failed to read line from file")?;
@@ -140,6 +233,7 @@ impl CodexParser {
                 if json_value.get("timestamp").is_some() && json_value.get("payload").is_some() {
                     // New event-based format
                     if let Ok(event) = serde_json::from_value::<CodexEvent>(json_value.clone()) {
+                        check_drift(&mut drift, &event);
                         match event.event_type.as_str() {
                             "session_meta" => {
                                 if let Ok(meta) =
@@ -252,6 +346,7 @@ impl CodexParser {
             }
             // Ignore other line types (record_type: state, etc.)
         }
+        *self.drift.lock().unwrap_or_else(PoisonError::into_inner) = drift;
 
         // If no session metadata found, return error
         let meta = session_meta.ok_or_else(|| anyhow!("No session_meta found in Codex file"))?;
@@ -396,6 +491,10 @@ impl CodexParser {
         use std::collections::hash_map::DefaultHasher;
         use std::hash::{Hash, Hasher};
 
+        if let Some(hash) = &self.source_hash {
+            return Ok(hash.clone());
+        }
+
         let metadata = std::fs::metadata(&self.file_path)
             .with_context(|| format!("Failed to get file metadata: {}", self.file_path))?;
 
@@ -19,6 +19,7 @@ use uuid::Uuid;
 
 use crate::models::{ChatSession, Message, MessageRole};
 use crate::models::{Provider, SessionState};
+use crate::utils::wsl;
 
 /// Composer message type constants
 const MESSAGE_TYPE_USER: i64 = 1;
@@ -196,6 +197,18 @@ impl CursorClientParser {
 
         #[cfg(target_os = "linux")]
         {
+            // Inside WSL, Cursor runs on Windows and keeps chats there; the
+            // WSL side only has the remote server
+            if wsl::is_wsl() {
+                let windows_storage = wsl::bridged_home_dirs()
+                    .into_iter()
+                    .map(|home| home.join("AppData/Roaming/Cursor/User/workspaceStorage"))
+                    .find(|path| path.is_dir());
+                if windows_storage.is_some() {
+                    return windows_storage;
+                }
+            }
+
             // Check if running in remote/SSH environment
             if std::env::var("SSH_CONNECTION").is_ok()
                 || std::env::var("SSH_CLIENT").is_ok()
@@ -402,7 +415,7 @@ impl CursorClientParser {
             }
 
             // Sort by timestamp
-            bubbles.sort_by(|a, b| a.timestamp.unwrap_or(0).cmp(&b.timestamp.unwrap_or(0)));
+            bubbles.sort_by_key(|b| b.timestamp.unwrap_or(0));
 
             let session_id = Uuid::new_v4();
             let first_bubble = bubbles.first();
@@ -430,7 +443,7 @@ impl CursorClientParser {
                 start_time,
             );
             session.id = Uuid::parse_str(&chat_id).unwrap_or(session_id);
-            session = session.with_project(title);
+            session = session.with_title(title);
             if let Some(end) = end_time {
                 if end != start_time {
                     session = session.with_end_time(end);
@@ -544,6 +557,15 @@ impl CursorClientParser {
         if let Ok(content) = std::fs::read_to_string(&workspace_json) {
             if let Ok(data) = serde_json::from_str::<serde_json::Value>(&content) {
                 if let Some(folder) = data.get("folder").and_then(|f| f.as_str()) {
+                    // `file://` and WSL `vscode-remote://` URIs
+                    if let Some(name) = wsl::folder_uri_to_path(folder)
+                        .as_deref()
+                        .and_then(|path| path.to_str())
+                        .and_then(|path| path.rsplit(['/', '\\']).find(|s| !s.is_empty()))
+                    {
+                        return Some(name.to_string());
+                    }
+
                     // Extract folder name from path
                     let folder_path = folder.replace("file://", "");
                     return folder_path
@@ -592,7 +614,7 @@ impl CursorClientParser {
         // Try to get name or extract from first message
         if let Some(name) = &composer.name {
             if !name.is_empty() {
-                session = session.with_project(name.clone());
+                session = session.with_title(name.clone());
             }
         }
 
@@ -657,8 +679,8 @@ impl CursorClientParser {
                             Message::new(session_id, role, content, timestamp, (idx + 1) as u32);
                         messages.push(message);
 
-                        // Set project name from first user message if not set
-                        if session.project_name.is_none() && idx == 0 {
+                        // Set title from first user message if not set
+                        if session.title.is_none() && idx == 0 {
                             let first_line = messages[0].content.lines().next().unwrap_or("");
                             let title = if first_line.len() > 50 {
                                 format!("{}...", &first_line[..47])
@@ -666,7 +688,7 @@ impl CursorClientParser {
                                 first_line.to_string()
                             };
                             if !title.is_empty() {
-                                session = session.with_project(title);
+                                session = session.with_title(title);
                             }
                         }
                     }
@@ -760,7 +782,7 @@ impl CursorClientParser {
 
         if let Some(name) = &composer.name {
             if !name.is_empty() {
-                session = session.with_project(name.clone());
+                session = session.with_title(name.clone());
             }
         }
 
@@ -834,7 +856,7 @@ impl CursorClientParser {
 
         if let Some(title) = &tab.title {
             if !title.is_empty() {
-                session = session.with_project(title.clone());
+                session = session.with_title(title.clone());
             }
         }
 
@@ -1122,6 +1144,28 @@ mod tests {
         assert!(path.is_some());
     }
 
+    #[test]
+    fn test_get_workspace_folder_remote_uris() {
+        let temp_dir = TempDir::new().unwrap();
+        let parser = CursorClientParser::new(temp_dir.path());
+        let cases = [
+            ("vscode-remote://wsl%2Bubuntu/home/me/my%20app", "my app"),
+            ("file:///c%3A/src/app", "app"),
+            ("file:///home/me/project", "project"),
+        ];
+        for (uri, expected) in cases {
+            std::fs::write(
+                temp_dir.path().join("workspace.json"),
+                serde_json::json!({ "folder": uri }).to_string(),
+            )
+            .unwrap();
+            assert_eq!(
+                parser.get_workspace_folder(temp_dir.path()).as_deref(),
+                Some(expected)
+            );
+        }
+    }
+
     #[tokio::test]
     async fn test_parse_empty_workspace() {
         let temp_dir = TempDir::new().unwrap();
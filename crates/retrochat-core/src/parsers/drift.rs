@@ -0,0 +1,89 @@
+//! Format drift detection
+//!
+//! Providers change their log formats without notice. Parsers hand the raw
+//! JSON they read to a [`DriftCollector`] together with the keys and types
+//! they know about (including the ones they deliberately ignore); anything
+//! else is counted and stored in `parser_warnings` at import, where
+//! `retrochat doctor --parsers` reports it.
+
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+use crate::models::{DriftKind, FormatDrift};
+
+#[derive(Debug, Default)]
+pub struct DriftCollector {
+    seen: BTreeMap<(DriftKind, String), u32>,
+}
+
+impl DriftCollector {
+    /// Count the keys of `object` that are not in `known`
+    pub fn check_keys(&mut self, location: &str, object: &Map<String, Value>, known: &[&str]) {
+        for key in object.keys() {
+            if !known.contains(&key.as_str()) {
+                self.record(DriftKind::UnknownKey, format!("{location}.{key}"));
+            }
+        }
+    }
+
+    /// Count `value` as an unknown type at `location` when it is not in `known`
+    pub fn check_type(&mut self, location: &str, value: &str, known: &[&str]) {
+        if !known.contains(&value) {
+            self.record(DriftKind::UnknownType, format!("{location}={value}"));
+        }
+    }
+
+    fn record(&mut self, kind: DriftKind, location: String) {
+        *self.seen.entry((kind, location)).or_default() += 1;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+
+    /// Everything counted so far, leaving the collector empty
+    pub fn take(&mut self) -> Vec<FormatDrift> {
+        std::mem::take(&mut self.seen)
+            .into_iter()
+            .map(|((kind, location), occurrences)| FormatDrift {
+                kind,
+                location,
+                occurrences,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_collects_unknown_keys_and_types() {
+        let mut drift = DriftCollector::default();
+        let entry = json!({ "type": "user", "uuid": "a", "newField": 1 });
+        for _ in 0..2 {
+            drift.check_keys("entry", entry.as_object().unwrap(), &["type", "uuid"]);
+        }
+        drift.check_type("entry.type", "user", &["user"]);
+        drift.check_type("entry.type", "checkpoint", &["user"]);
+
+        assert_eq!(
+            drift.take(),
+            vec![
+                FormatDrift {
+                    kind: DriftKind::UnknownKey,
+                    location: "entry.newField".to_string(),
+                    occurrences: 2,
+                },
+                FormatDrift {
+                    kind: DriftKind::UnknownType,
+                    location: "entry.type=checkpoint".to_string(),
+                    occurrences: 1,
+                },
+            ]
+        );
+        assert!(drift.is_empty());
+    }
+}
@@ -0,0 +1,369 @@
+//! External command parser plugins
+//!
+//! Users can teach RetroChat new chat formats without forking the crate by
+//! declaring a `[[parsers.external]]` entry in `~/.retrochat/config.toml`:
+//!
+//! ```toml
+//! [[parsers.external]]
+//! name = "aider"
+//! pattern = "**/.aider.chat.history.md"
+//! command = "aider-to-retrochat {file}"
+//! ```
+//!
+//! Files matching `pattern` are handed to `command`, which must print an
+//! [`ExternalSessionDocument`] as JSON on stdout. The command is run directly
+//! (no shell), split on whitespace; `{file}` is replaced by the file path, or
+//! the path is appended as the last argument.
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::config::{Config, ExternalParserConfig};
+use crate::models::{ChatSession, Message, MessageRole, Provider};
+
+/// Maximum time an external parser may run for a single file
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Placeholder replaced by the file path in the configured command
+const FILE_PLACEHOLDER: &str = "{file}";
+
+/// Normalized session schema printed by external parser commands
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExternalSessionDocument {
+    pub sessions: Vec<ExternalSession>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExternalSession {
+    /// Stable session UUID, so re-imports update the same session
+    pub id: String,
+    #[serde(default)]
+    pub project: Option<String>,
+    /// Defaults to the first message timestamp
+    #[serde(default)]
+    pub start_time: Option<DateTime<Utc>>,
+    /// Defaults to the last message timestamp
+    #[serde(default)]
+    pub end_time: Option<DateTime<Utc>>,
+    pub messages: Vec<ExternalMessage>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExternalMessage {
+    /// "user", "assistant" or "system"
+    pub role: String,
+    pub content: String,
+    pub timestamp: DateTime<Utc>,
+    #[serde(default)]
+    pub token_count: Option<u32>,
+}
+
+/// External parsers declared in the user config, loaded once per process
+pub fn configured_parsers() -> &'static [ExternalParserConfig] {
+    static PARSERS: OnceLock<Vec<ExternalParserConfig>> = OnceLock::new();
+    PARSERS.get_or_init(|| {
+        Config::load()
+            .map(|config| config.parsers.external)
+            .unwrap_or_default()
+    })
+}
+
+/// Find the first configured external parser whose glob matches `path`
+pub fn find_parser_for_path(path: &Path) -> Option<&'static ExternalParserConfig> {
+    configured_parsers()
+        .iter()
+        .find(|config| matches_pattern(config, path))
+}
+
+/// Find a configured external parser by provider name
+pub fn find_parser_by_name(name: &str) -> Option<&'static ExternalParserConfig> {
+    configured_parsers()
+        .iter()
+        .find(|config| config.name == name)
+}
+
+/// Whether `path` matches the parser's glob, by full path or by file name
+pub fn matches_pattern(config: &ExternalParserConfig, path: &Path) -> bool {
+    matches_glob(&config.pattern, path)
+}
+
+/// Whether `path` matches `pattern`, by full path or by file name
+pub fn matches_glob(pattern: &str, path: &Path) -> bool {
+    let Ok(pattern) = glob::Pattern::new(pattern) else {
+        return false;
+    };
+    pattern.matches_path(path)
+        || path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|name| pattern.matches(name))
+}
+
+pub struct ExternalCommandParser {
+    file_path: PathBuf,
+    config: ExternalParserConfig,
+}
+
+impl ExternalCommandParser {
+    pub fn new(file_path: impl AsRef<Path>, config: ExternalParserConfig) -> Self {
+        Self {
+            file_path: file_path.as_ref().to_path_buf(),
+            config,
+        }
+    }
+
+    pub fn provider(&self) -> Provider {
+        Provider::Other(self.config.name.clone())
+    }
+
+    pub async fn parse(&self) -> Result<Vec<(ChatSession, Vec<Message>)>> {
+        let (program, args) = build_command(&self.config.command, &self.file_path)?;
+
+        let mut command = tokio::process::Command::new(&program);
+        command.args(&args).kill_on_drop(true);
+
+        let output = tokio::time::timeout(COMMAND_TIMEOUT, command.output())
+            .await
+            .map_err(|_| {
+                anyhow!(
+                    "External parser '{}' timed out after {}s",
+                    self.config.name,
+                    COMMAND_TIMEOUT.as_secs()
+                )
+            })?
+            .with_context(|| format!("Failed to run external parser command: {program}"))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow!(
+                "External parser '{}' exited with {}: {}",
+                self.config.name,
+                output.status,
+                stderr.trim()
+            ));
+        }
+
+        let document: ExternalSessionDocument = serde_json::from_slice(&output.stdout)
+            .with_context(|| {
+                format!(
+                    "External parser '{}' did not print a valid session document",
+                    self.config.name
+                )
+            })?;
+
+        convert_document(document, self.provider(), &self.file_path)
+    }
+
+    pub async fn parse_streaming<F>(&self, mut callback: F) -> Result<()>
+    where
+        F: FnMut(ChatSession, Message) -> Result<()>,
+    {
+        let sessions = self.parse().await?;
+
+        for (session, messages) in sessions {
+            for message in messages {
+                callback(session.clone(), message)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Convert a session document into sessions attributed to `provider`
+///
+/// Shared by every plugin mechanism that emits [`ExternalSessionDocument`].
+pub(crate) fn convert_document(
+    document: ExternalSessionDocument,
+    provider: Provider,
+    file_path: &Path,
+) -> Result<Vec<(ChatSession, Vec<Message>)>> {
+    let file_hash = calculate_file_hash(file_path)?;
+    let file_path = file_path.to_string_lossy().to_string();
+
+    document
+        .sessions
+        .into_iter()
+        .filter(|session| !session.messages.is_empty())
+        .map(|session| {
+            let session_id = Uuid::parse_str(&session.id)
+                .with_context(|| format!("Session id is not a UUID: {}", session.id))?;
+
+            let first = session.messages.first().map(|m| m.timestamp);
+            let last = session.messages.last().map(|m| m.timestamp);
+            let start_time = session.start_time.or(first).unwrap_or_else(Utc::now);
+
+            let mut chat_session = ChatSession::new(
+                provider.clone(),
+                file_path.clone(),
+                file_hash.clone(),
+                start_time,
+            );
+            chat_session.id = session_id;
+            if let Some(project) = session.project {
+                chat_session = chat_session.with_project(project);
+            }
+            if let Some(end_time) = session.end_time.or(last) {
+                chat_session = chat_session.with_end_time(end_time);
+            }
+
+            let mut messages = Vec::with_capacity(session.messages.len());
+            let mut total_tokens = 0u32;
+            for (index, message) in session.messages.into_iter().enumerate() {
+                let role = parse_role(&message.role)?;
+                let mut converted = Message::new(
+                    session_id,
+                    role,
+                    message.content,
+                    message.timestamp,
+                    index as u32 + 1,
+                );
+                if let Some(tokens) = message.token_count {
+                    total_tokens += tokens;
+                    converted = converted.with_token_count(tokens);
+                }
+                messages.push(converted);
+            }
+
+            chat_session.update_message_count(messages.len() as u32);
+            if total_tokens > 0 {
+                chat_session = chat_session.with_token_count(total_tokens);
+            }
+
+            Ok((chat_session, messages))
+        })
+        .collect()
+}
+
+fn calculate_file_hash(file_path: &Path) -> Result<String> {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let metadata = std::fs::metadata(file_path)
+        .with_context(|| format!("Failed to get file metadata: {}", file_path.display()))?;
+
+    let mut hasher = DefaultHasher::new();
+    file_path.hash(&mut hasher);
+    metadata.len().hash(&mut hasher);
+    if let Ok(modified) = metadata.modified() {
+        if let Ok(duration) = modified.duration_since(std::time::UNIX_EPOCH) {
+            duration.as_secs().hash(&mut hasher);
+        }
+    }
+
+    Ok(format!("{:x}", hasher.finish()))
+}
+
+/// Split the configured command into a program and arguments for `file_path`
+pub fn build_command(command: &str, file_path: &Path) -> Result<(String, Vec<String>)> {
+    let file = file_path.to_string_lossy();
+    let mut parts = command.split_whitespace().map(str::to_string);
+    let program = parts
+        .next()
+        .ok_or_else(|| anyhow!("External parser command is empty"))?;
+
+    let mut args: Vec<String> = parts.collect();
+    if args.iter().any(|arg| arg.contains(FILE_PLACEHOLDER)) {
+        for arg in &mut args {
+            *arg = arg.replace(FILE_PLACEHOLDER, &file);
+        }
+    } else {
+        args.push(file.to_string());
+    }
+
+    Ok((program, args))
+}
+
+fn parse_role(role: &str) -> Result<MessageRole> {
+    match role.to_lowercase().as_str() {
+        "user" | "human" => Ok(MessageRole::User),
+        "assistant" | "ai" | "model" => Ok(MessageRole::Assistant),
+        "system" => Ok(MessageRole::System),
+        other => Err(anyhow!(
+            "Unknown message role from external parser: {other}"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn config(pattern: &str, command: &str) -> ExternalParserConfig {
+        ExternalParserConfig {
+            name: "aider".to_string(),
+            pattern: pattern.to_string(),
+            command: command.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_build_command_substitutes_or_appends_file() {
+        let path = Path::new("/tmp/chat.md");
+
+        let (program, args) = build_command("convert --input {file} --json", path).unwrap();
+        assert_eq!(program, "convert");
+        assert_eq!(args, vec!["--input", "/tmp/chat.md", "--json"]);
+
+        let (_, args) = build_command("convert --json", path).unwrap();
+        assert_eq!(args, vec!["--json", "/tmp/chat.md"]);
+
+        assert!(build_command("   ", path).is_err());
+    }
+
+    #[test]
+    fn test_matches_pattern_by_path_or_file_name() {
+        let path = Path::new("/home/me/project/.aider.chat.history.md");
+
+        assert!(matches_pattern(
+            &config("**/.aider.chat.history.md", "x"),
+            path
+        ));
+        assert!(matches_pattern(&config(".aider.*.md", "x"), path));
+        assert!(!matches_pattern(&config("*.jsonl", "x"), path));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_parse_reads_session_document_from_stdout() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("session.custom");
+        fs::write(
+            &file_path,
+            r#"{
+                "sessions": [{
+                    "id": "550e8400-e29b-41d4-a716-446655440000",
+                    "project": "retrochat",
+                    "messages": [
+                        {"role": "user", "content": "Hello", "timestamp": "2024-01-01T00:00:00Z"},
+                        {"role": "assistant", "content": "Hi!", "timestamp": "2024-01-01T00:01:00Z", "token_count": 5}
+                    ]
+                }]
+            }"#,
+        )
+        .unwrap();
+
+        // `cat` echoes the file, which already holds a session document
+        let parser = ExternalCommandParser::new(&file_path, config("*.custom", "cat {file}"));
+        let sessions = parser.parse().await.unwrap();
+
+        assert_eq!(sessions.len(), 1);
+        let (session, messages) = &sessions[0];
+        assert_eq!(session.provider, Provider::Other("aider".to_string()));
+        assert_eq!(session.project_name.as_deref(), Some("retrochat"));
+        assert_eq!(session.message_count, 2);
+        assert_eq!(session.token_count, Some(5));
+        assert_eq!(session.end_time, Some(messages[1].timestamp));
+        assert_eq!(messages[0].role, MessageRole::User);
+
+        let failing = ExternalCommandParser::new(&file_path, config("*.custom", "false"));
+        assert!(failing.parse().await.is_err());
+    }
+}
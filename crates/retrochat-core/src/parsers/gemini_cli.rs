@@ -5,6 +5,7 @@ use serde_json::Value;
 use std::fs::File;
 use std::io::Read;
 use std::path::Path;
+use tokio::io::AsyncRead;
 use uuid::Uuid;
 
 use crate::models::message::MessageType;
@@ -110,6 +111,51 @@ pub struct GeminiSession {
     pub messages: Vec<GeminiSessionMessage>,
 }
 
+// Checkpoint structures: `checkpoint-<tag>.json`, written next to the `chats`
+// directory by `/chat save`, holds the raw model history including every
+// function call and response
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GeminiCheckpointContent {
+    pub role: String,
+    #[serde(default)]
+    pub parts: Vec<GeminiCheckpointPart>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GeminiCheckpointPart {
+    pub text: Option<String>,
+    #[serde(rename = "functionCall")]
+    pub function_call: Option<GeminiFunctionCall>,
+    #[serde(rename = "functionResponse")]
+    pub function_response: Option<GeminiCheckpointFunctionResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GeminiFunctionCall {
+    pub id: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    pub args: Value,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GeminiCheckpointFunctionResponse {
+    pub id: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    pub response: Value,
+}
+
+/// Function calls made in answer to one user prompt of a checkpoint
+#[derive(Debug)]
+struct CheckpointTurn<'a> {
+    prompt: String,
+    calls: Vec<(
+        &'a GeminiFunctionCall,
+        Option<&'a GeminiCheckpointFunctionResponse>,
+    )>,
+}
+
 // Array format structures (simple message list grouped by session)
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GeminiArrayMessage {
@@ -125,6 +171,7 @@ pub struct GeminiArrayMessage {
 
 pub struct GeminiCLIParser {
     file_path: String,
+    source_hash: Option<String>,
     use_memory_mapping: bool,
 }
 
@@ -132,10 +179,18 @@ impl GeminiCLIParser {
     pub fn new(file_path: impl AsRef<Path>) -> Self {
         Self {
             file_path: file_path.as_ref().to_string_lossy().to_string(),
+            source_hash: None,
             use_memory_mapping: false,
         }
     }
 
+    /// Use a hash of content read from elsewhere instead of the file's
+    /// metadata, for sessions parsed with `parse_reader`
+    pub fn with_source_hash(mut self, hash: String) -> Self {
+        self.source_hash = Some(hash);
+        self
+    }
+
     pub fn with_memory_mapping(mut self, use_mmap: bool) -> Self {
         self.use_memory_mapping = use_mmap;
         self
@@ -160,6 +215,16 @@ impl GeminiCLIParser {
         self.parse_content(&content).await
     }
 
+    /// Parse sessions from content that is not a file on disk, e.g. an upload
+    pub async fn parse_reader<R: AsyncRead + Unpin>(
+        &self,
+        reader: R,
+    ) -> Result<Vec<(ChatSession, Vec<Message>)>> {
+        let content = String::from_utf8(super::read_source(reader).await?)
+            .context("Session content is not valid UTF-8")?;
+        self.parse_content(&content).await
+    }
+
     async fn parse_with_mmap(&self) -> Result<Vec<(ChatSession, Vec<Message>)>> {
         // For now, fallback to standard parsing
         // TODO: Implement actual memory mapping using memmap2 crate
@@ -295,6 +360,8 @@ impl GeminiCLIParser {
             }
         }
 
+        self.merge_checkpoints(&mut converted_messages, session_id);
+
         chat_session.message_count = converted_messages.len() as u32;
         if total_tokens > 0 {
             chat_session = chat_session.with_token_count(total_tokens);
@@ -362,6 +429,8 @@ impl GeminiCLIParser {
             }
         }
 
+        self.merge_checkpoints(&mut messages, session_id);
+
         chat_session.message_count = messages.len() as u32;
         if total_tokens > 0 {
             chat_session = chat_session.with_token_count(total_tokens);
@@ -512,6 +581,189 @@ impl GeminiCLIParser {
         }
     }
 
+    /// Rename Gemini arguments to the ones RetroChat's tool parsers read, so
+    /// operations get file metadata (`read_file` takes `absolute_path`)
+    fn normalize_tool_input(gemini_name: &str, args: &Value) -> Value {
+        let mut input = args.clone();
+        if gemini_name == "read_file" {
+            if let Some(obj) = input.as_object_mut() {
+                if !obj.contains_key("file_path") {
+                    if let Some(path) = obj.get("absolute_path").cloned() {
+                        obj.insert("file_path".to_string(), path);
+                    }
+                }
+            }
+        }
+        input
+    }
+
+    /// Text content of a function response, as shown for session tool calls
+    fn function_response_content(response: &Value) -> String {
+        if let Some(output) = response.get("output") {
+            output.as_str().unwrap_or_default().to_string()
+        } else {
+            serde_json::to_string(response).unwrap_or_default()
+        }
+    }
+
+    /// `checkpoint-*.json` files of this session's project
+    ///
+    /// Session files live in `<project>/chats/`, checkpoints in `<project>/`;
+    /// both directories are searched so loose copies are found too.
+    fn checkpoint_paths(&self) -> Vec<std::path::PathBuf> {
+        let Some(dir) = Path::new(&self.file_path).parent() else {
+            return Vec::new();
+        };
+        let mut dirs = vec![dir];
+        if dir.file_name().is_some_and(|name| name == "chats") {
+            dirs.extend(dir.parent());
+        }
+
+        let mut paths: Vec<_> = dirs
+            .into_iter()
+            .filter_map(|dir| std::fs::read_dir(dir).ok())
+            .flatten()
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with("checkpoint-") && name.ends_with(".json"))
+            })
+            .collect();
+        paths.sort();
+        paths
+    }
+
+    fn load_checkpoints(&self) -> Vec<Vec<GeminiCheckpointContent>> {
+        self.checkpoint_paths()
+            .into_iter()
+            .filter_map(|path| {
+                let content = std::fs::read_to_string(&path).ok()?;
+                match serde_json::from_str(&content) {
+                    Ok(history) => Some(history),
+                    Err(e) => {
+                        tracing::warn!(path = %path.display(), error = %e, "Skipping unreadable Gemini checkpoint");
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Split a checkpoint history into user prompts and the calls that followed
+    fn checkpoint_turns(history: &[GeminiCheckpointContent]) -> Vec<CheckpointTurn<'_>> {
+        let mut turns: Vec<CheckpointTurn> = Vec::new();
+        for content in history {
+            let text: Vec<&str> = content
+                .parts
+                .iter()
+                .filter_map(|part| part.text.as_deref())
+                .collect();
+            if content.role == "user" && !text.is_empty() {
+                turns.push(CheckpointTurn {
+                    prompt: text.join("").trim().to_string(),
+                    calls: Vec::new(),
+                });
+                continue;
+            }
+            let Some(turn) = turns.last_mut() else {
+                continue;
+            };
+            for part in &content.parts {
+                if let Some(call) = &part.function_call {
+                    turn.calls.push((call, None));
+                }
+                if let Some(response) = &part.function_response {
+                    // Pair by id, or with the oldest unanswered call of that name
+                    let slot = turn.calls.iter_mut().find(|(call, answered)| {
+                        answered.is_none()
+                            && match (&call.id, &response.id) {
+                                (Some(call_id), Some(response_id)) => call_id == response_id,
+                                _ => call.name == response.name,
+                            }
+                    });
+                    if let Some((_, answered)) = slot {
+                        *answered = Some(response);
+                    }
+                }
+            }
+        }
+        turns
+    }
+
+    /// Add function calls recorded only in checkpoints to the session
+    ///
+    /// Checkpoint turns are matched to the session by prompt text. Calls the
+    /// session already has (same id, or same tool and arguments) are skipped;
+    /// the rest are attached to the last assistant reply to that prompt, so
+    /// import turns them into tool operations.
+    fn merge_checkpoints(&self, messages: &mut [Message], session_id: Uuid) {
+        for history in self.load_checkpoints() {
+            for (turn_index, turn) in Self::checkpoint_turns(&history).iter().enumerate() {
+                if turn.calls.is_empty() {
+                    continue;
+                }
+                let Some(start) = messages
+                    .iter()
+                    .position(|m| m.role == MessageRole::User && m.content.trim() == turn.prompt)
+                else {
+                    continue;
+                };
+                let end = messages[start + 1..]
+                    .iter()
+                    .position(|m| m.role == MessageRole::User)
+                    .map_or(messages.len(), |offset| start + 1 + offset);
+                let Some(target) = (start + 1..end).rev().find(|&i| {
+                    messages[i].role == MessageRole::Assistant
+                        && messages[i].message_type != MessageType::Thinking
+                }) else {
+                    continue;
+                };
+
+                for (call_index, (call, response)) in turn.calls.iter().enumerate() {
+                    let name = Self::normalize_tool_name(&call.name);
+                    let input = Self::normalize_tool_input(&call.name, &call.args);
+                    let known = messages[start..end].iter().any(|m| {
+                        m.tool_uses.iter().flatten().any(|tool_use| {
+                            call.id.as_ref() == Some(&tool_use.id)
+                                || (tool_use.name == name && tool_use.input == input)
+                        })
+                    });
+                    if known {
+                        continue;
+                    }
+
+                    let id = call.id.clone().unwrap_or_else(|| {
+                        format!("{session_id}-checkpoint-{turn_index}-{call_index}")
+                    });
+                    let message = &mut messages[target];
+                    message
+                        .tool_uses
+                        .get_or_insert_with(Vec::new)
+                        .push(ToolUse {
+                            id: id.clone(),
+                            name,
+                            input,
+                            raw: serde_json::to_value(call).unwrap_or(Value::Null),
+                        });
+                    if let Some(response) = response {
+                        let is_error = response.response.get("error").is_some();
+                        message
+                            .tool_results
+                            .get_or_insert_with(Vec::new)
+                            .push(ToolResult {
+                                tool_use_id: id,
+                                content: Self::function_response_content(&response.response),
+                                is_error,
+                                details: Some(response.response.clone()),
+                                raw: serde_json::to_value(response).unwrap_or(Value::Null),
+                            });
+                    }
+                }
+            }
+        }
+    }
+
     /// Extract tool operations from Gemini toolCalls
     /// Returns (tool_uses, tool_results)
     fn extract_tool_operations(
@@ -527,7 +779,7 @@ impl GeminiCLIParser {
             tool_uses.push(ToolUse {
                 id: tool_call.id.clone(),
                 name: normalized_name.clone(),
-                input: tool_call.args.clone(),
+                input: Self::normalize_tool_input(&tool_call.name, &tool_call.args),
                 raw: serde_json::to_value(tool_call).unwrap_or(Value::Null),
             });
 
@@ -536,12 +788,7 @@ impl GeminiCLIParser {
                 for func_response in results {
                     let response_data = &func_response.function_response;
 
-                    // Extract content from response
-                    let content = if let Some(output) = response_data.response.get("output") {
-                        output.as_str().unwrap_or_default().to_string()
-                    } else {
-                        serde_json::to_string(&response_data.response).unwrap_or_default()
-                    };
+                    let content = Self::function_response_content(&response_data.response);
 
                     // Check if it's an error based on status
                     let is_error = tool_call
@@ -860,6 +1107,10 @@ impl GeminiCLIParser {
         use std::collections::hash_map::DefaultHasher;
         use std::hash::{Hash, Hasher};
 
+        if let Some(hash) = &self.source_hash {
+            return Ok(hash.clone());
+        }
+
         let metadata = std::fs::metadata(&self.file_path)
             .with_context(|| format!("Failed to get file metadata: {}", self.file_path))?;
 
@@ -872,6 +1123,14 @@ impl GeminiCLIParser {
             }
         }
 
+        // Checkpoints are merged into the session, so a new one is a change too
+        for path in self.checkpoint_paths() {
+            if let Ok(metadata) = std::fs::metadata(&path) {
+                path.hash(&mut hasher);
+                metadata.len().hash(&mut hasher);
+            }
+        }
+
         Ok(format!("{:x}", hasher.finish()))
     }
 
@@ -1306,4 +1565,64 @@ mod tests {
         assert_eq!(messages[1].role, MessageRole::Assistant);
         assert_eq!(messages[1].message_type, MessageType::SimpleMessage);
     }
+
+    #[tokio::test]
+    async fn test_merge_checkpoint_tool_calls() {
+        use std::fs;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let chats_dir = temp_dir.path().join("chats");
+        fs::create_dir(&chats_dir).unwrap();
+        let file_path = chats_dir.join("session-test-checkpoint.json");
+
+        let session_data = r#"{
+            "sessionId": "test-session-checkpoint",
+            "startTime": "2024-01-01T10:00:00Z",
+            "lastUpdated": "2024-01-01T10:05:00Z",
+            "messages": [
+                {"id": "msg-1", "timestamp": "2024-01-01T10:00:00Z", "type": "user", "content": "Fix main.rs"},
+                {
+                    "id": "msg-2",
+                    "timestamp": "2024-01-01T10:01:00Z",
+                    "type": "gemini",
+                    "content": "Done.",
+                    "toolCalls": [
+                        {"id": "replace-1", "name": "replace", "args": {"file_path": "/src/main.rs", "old_string": "a", "new_string": "b"}, "status": "success"}
+                    ]
+                }
+            ]
+        }"#;
+        fs::write(&file_path, session_data).unwrap();
+
+        let checkpoint = r#"[
+            {"role": "user", "parts": [{"text": "This is the Gemini CLI. We are setting up the context for our chat."}]},
+            {"role": "model", "parts": [{"text": "Got it. Thanks for the context!"}]},
+            {"role": "user", "parts": [{"text": "Fix main.rs"}]},
+            {"role": "model", "parts": [{"functionCall": {"id": "read_file-1", "name": "read_file", "args": {"absolute_path": "/src/main.rs"}}}]},
+            {"role": "user", "parts": [{"functionResponse": {"id": "read_file-1", "name": "read_file", "response": {"output": "fn main() {}"}}}]},
+            {"role": "model", "parts": [{"functionCall": {"id": "replace-1", "name": "replace", "args": {"file_path": "/src/main.rs", "old_string": "a", "new_string": "b"}}}]},
+            {"role": "model", "parts": [{"text": "Done."}]}
+        ]"#;
+        fs::write(temp_dir.path().join("checkpoint-fix.json"), checkpoint).unwrap();
+
+        let parser = GeminiCLIParser::new(&file_path);
+        let sessions = parser.parse().await.unwrap();
+        let (_, messages) = &sessions[0];
+
+        let tool_uses = messages[1].tool_uses.as_ref().unwrap();
+        assert_eq!(
+            tool_uses.len(),
+            2,
+            "the replace call must not be duplicated"
+        );
+        assert_eq!(tool_uses[0].name, "Edit");
+        assert_eq!(tool_uses[1].name, "Read");
+        assert_eq!(tool_uses[1].input["file_path"], "/src/main.rs");
+
+        let tool_results = messages[1].tool_results.as_ref().unwrap();
+        assert_eq!(tool_results.len(), 1);
+        assert_eq!(tool_results[0].tool_use_id, "read_file-1");
+        assert_eq!(tool_results[0].content, "fn main() {}");
+    }
 }
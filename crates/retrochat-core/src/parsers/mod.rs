@@ -1,25 +1,37 @@
 pub mod claude_code;
 pub mod codex;
 pub mod cursor_client;
+pub mod drift;
+pub mod external;
 pub mod gemini_cli;
 pub mod project_inference;
+pub mod testkit;
+#[cfg(feature = "wasm-plugins")]
+pub mod wasm;
 
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, Context, Result};
 use std::path::Path;
+use tokio::io::{AsyncRead, AsyncReadExt};
 
 use crate::models::Provider;
-use crate::models::{ChatSession, Message};
+use crate::models::{ChatSession, FormatDrift, Message};
 
 pub use claude_code::ClaudeCodeParser;
 pub use codex::CodexParser;
 pub use cursor_client::CursorClientParser;
+pub use external::ExternalCommandParser;
 pub use gemini_cli::GeminiCLIParser;
+#[cfg(feature = "wasm-plugins")]
+pub use wasm::WasmPluginParser;
 
 pub enum ChatParser {
     ClaudeCode(ClaudeCodeParser),
     Codex(CodexParser),
     CursorClient(CursorClientParser),
     GeminiCLI(GeminiCLIParser),
+    External(ExternalCommandParser),
+    #[cfg(feature = "wasm-plugins")]
+    Wasm(WasmPluginParser),
 }
 
 impl ChatParser {
@@ -35,6 +47,9 @@ impl ChatParser {
             }
             ChatParser::CursorClient(parser) => parser.parse().await,
             ChatParser::GeminiCLI(parser) => parser.parse().await,
+            ChatParser::External(parser) => parser.parse().await,
+            #[cfg(feature = "wasm-plugins")]
+            ChatParser::Wasm(parser) => parser.parse().await,
         }
     }
 
@@ -47,6 +62,50 @@ impl ChatParser {
             ChatParser::Codex(parser) => parser.parse_streaming(callback).await,
             ChatParser::CursorClient(parser) => parser.parse_streaming(callback).await,
             ChatParser::GeminiCLI(parser) => parser.parse_streaming(callback).await,
+            ChatParser::External(parser) => parser.parse_streaming(callback).await,
+            #[cfg(feature = "wasm-plugins")]
+            ChatParser::Wasm(parser) => parser.parse_streaming(callback).await,
+        }
+    }
+
+    /// Parse sessions from content that is not a file on disk
+    ///
+    /// Only the Claude Code, Codex and Gemini parsers read streams; Cursor
+    /// databases and external parsers need a real file.
+    pub async fn parse_reader<R: AsyncRead + Unpin>(
+        &self,
+        reader: R,
+    ) -> Result<Vec<(ChatSession, Vec<Message>)>> {
+        match self {
+            ChatParser::ClaudeCode(parser) => Ok(vec![parser.parse_reader(reader).await?]),
+            ChatParser::Codex(parser) => Ok(vec![parser.parse_reader(reader).await?]),
+            ChatParser::GeminiCLI(parser) => parser.parse_reader(reader).await,
+            _ => Err(anyhow!(
+                "{} sessions can only be imported from files",
+                self.get_provider()
+            )),
+        }
+    }
+
+    /// Hash to store as the sessions' file hash when parsing a stream
+    pub fn with_source_hash(self, hash: String) -> Self {
+        match self {
+            ChatParser::ClaudeCode(parser) => ChatParser::ClaudeCode(parser.with_source_hash(hash)),
+            ChatParser::Codex(parser) => ChatParser::Codex(parser.with_source_hash(hash)),
+            ChatParser::GeminiCLI(parser) => ChatParser::GeminiCLI(parser.with_source_hash(hash)),
+            other => other,
+        }
+    }
+
+    /// Unrecognized log structure met by the last parse
+    ///
+    /// Only the Claude Code and Codex parsers track drift; the others report
+    /// nothing.
+    pub fn take_drift(&self) -> Vec<FormatDrift> {
+        match self {
+            ChatParser::ClaudeCode(parser) => parser.take_drift(),
+            ChatParser::Codex(parser) => parser.take_drift(),
+            _ => Vec::new(),
         }
     }
 
@@ -56,16 +115,50 @@ impl ChatParser {
             ChatParser::Codex(_) => Provider::Codex,
             ChatParser::CursorClient(_) => Provider::CursorClient,
             ChatParser::GeminiCLI(_) => Provider::GeminiCLI,
+            ChatParser::External(parser) => parser.provider(),
+            #[cfg(feature = "wasm-plugins")]
+            ChatParser::Wasm(parser) => parser.provider(),
         }
     }
 }
 
+/// Read a whole session source into memory
+pub(crate) async fn read_source<R: AsyncRead + Unpin>(mut reader: R) -> Result<Vec<u8>> {
+    let mut content = Vec::new();
+    reader
+        .read_to_end(&mut content)
+        .await
+        .context("Failed to read session content")?;
+    Ok(content)
+}
+
+/// File hash for content that did not come from a file on disk
+pub fn content_hash(source_name: &str, content: &[u8]) -> String {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = DefaultHasher::new();
+    source_name.hash(&mut hasher);
+    content.hash(&mut hasher);
+    format!("{:x}", hasher.finish())
+}
+
 pub struct ParserRegistry;
 
 impl ParserRegistry {
     pub fn detect_provider(file_path: impl AsRef<Path>) -> Option<Provider> {
         let path = file_path.as_ref();
 
+        // User-configured external parsers take precedence over built-in detection
+        if let Some(config) = external::find_parser_for_path(path) {
+            return Some(Provider::Other(config.name.clone()));
+        }
+
+        #[cfg(feature = "wasm-plugins")]
+        if let Some(plugin) = wasm::find_plugin_for_path(path) {
+            return Some(Provider::Other(plugin.name.clone()));
+        }
+
         // First check by file extension and content
         // is_valid_file() already includes filename filtering via accepts_filename()
         if ClaudeCodeParser::is_valid_file(path) {
@@ -118,15 +211,11 @@ impl ParserRegistry {
         // Check by file extension as last resort (with filename filtering)
         if let Some(extension) = path.extension().and_then(|e| e.to_str()) {
             match extension.to_lowercase().as_str() {
-                "jsonl" => {
-                    if ClaudeCodeParser::accepts_filename(path) {
-                        return Some(Provider::ClaudeCode);
-                    }
+                "jsonl" if ClaudeCodeParser::accepts_filename(path) => {
+                    return Some(Provider::ClaudeCode);
                 }
-                "json" => {
-                    if GeminiCLIParser::accepts_filename(path) {
-                        return Some(Provider::GeminiCLI);
-                    }
+                "json" if GeminiCLIParser::accepts_filename(path) => {
+                    return Some(Provider::GeminiCLI);
                 }
                 _ => {}
             }
@@ -135,6 +224,48 @@ impl ParserRegistry {
         None
     }
 
+    /// Detect the provider of content that is not a file on disk, from its
+    /// file name's extension and its first record
+    ///
+    /// Unlike [`Self::detect_provider`], file name patterns are not required,
+    /// since uploads are often renamed.
+    pub fn detect_provider_for_content(file_name: &str, content: &[u8]) -> Option<Provider> {
+        let extension = Path::new(file_name)
+            .extension()
+            .and_then(|e| e.to_str())?
+            .to_lowercase();
+        let text = std::str::from_utf8(content).ok()?;
+
+        match extension.as_str() {
+            "jsonl" => {
+                let first = text.lines().find(|line| !line.trim().is_empty())?;
+                let record: serde_json::Value = serde_json::from_str(first).ok()?;
+                let has = |key: &str| record.get(key).is_some();
+                if record.get("type").and_then(|t| t.as_str()) == Some("session_meta")
+                    || (has("id") && has("timestamp") && !has("type"))
+                {
+                    Some(Provider::Codex)
+                } else if (has("type") && (has("sessionId") || has("summary")))
+                    || (has("uuid") && has("chat_messages"))
+                {
+                    Some(Provider::ClaudeCode)
+                } else {
+                    None
+                }
+            }
+            "json" => {
+                let document: serde_json::Value = serde_json::from_str(text).ok()?;
+                let has = |key: &str| document.get(key).is_some();
+                (document.is_array()
+                    || (has("sessionId") && has("messages"))
+                    || has("conversations")
+                    || has("conversation"))
+                .then_some(Provider::GeminiCLI)
+            }
+            _ => None,
+        }
+    }
+
     pub fn create_parser(file_path: impl AsRef<Path>) -> Result<ChatParser> {
         let provider = Self::detect_provider(&file_path).ok_or_else(|| {
             anyhow!(
@@ -143,6 +274,14 @@ impl ParserRegistry {
             )
         })?;
 
+        Self::create_parser_for_provider(file_path, provider)
+    }
+
+    /// Create a parser for a known provider, skipping format detection
+    pub fn create_parser_for_provider(
+        file_path: impl AsRef<Path>,
+        provider: Provider,
+    ) -> Result<ChatParser> {
         match provider {
             Provider::ClaudeCode => Ok(ChatParser::ClaudeCode(ClaudeCodeParser::new(file_path))),
             Provider::Codex => Ok(ChatParser::Codex(CodexParser::new(file_path))),
@@ -153,10 +292,32 @@ impl ParserRegistry {
             Provider::All => Err(anyhow!(
                 "'All' is a CLI-only provider and cannot be used for parsing"
             )),
-            Provider::Other(name) => Err(anyhow!("Parser for {name} not implemented")),
+            Provider::Other(name) => match external::find_parser_by_name(&name) {
+                Some(config) => Ok(ChatParser::External(ExternalCommandParser::new(
+                    file_path,
+                    config.clone(),
+                ))),
+                None => Self::create_plugin_parser(file_path, &name),
+            },
+        }
+    }
+
+    #[cfg(feature = "wasm-plugins")]
+    fn create_plugin_parser(file_path: impl AsRef<Path>, name: &str) -> Result<ChatParser> {
+        match wasm::find_plugin_by_name(name) {
+            Some(plugin) => Ok(ChatParser::Wasm(WasmPluginParser::new(
+                file_path,
+                plugin.clone(),
+            ))),
+            None => Err(anyhow!("Parser for {name} not implemented")),
         }
     }
 
+    #[cfg(not(feature = "wasm-plugins"))]
+    fn create_plugin_parser(_file_path: impl AsRef<Path>, name: &str) -> Result<ChatParser> {
+        Err(anyhow!("Parser for {name} not implemented"))
+    }
+
     pub fn get_supported_extensions() -> Vec<&'static str> {
         vec!["jsonl", "json", "db", "vscdb"]
     }
@@ -177,6 +338,32 @@ impl ParserRegistry {
         parser.parse().await
     }
 
+    /// Parse a file, also returning its provider and the format drift met
+    pub async fn parse_file_with_drift(
+        file_path: impl AsRef<Path>,
+    ) -> Result<(Provider, Vec<(ChatSession, Vec<Message>)>, Vec<FormatDrift>)> {
+        let parser = Self::create_parser(&file_path)?;
+        let sessions = parser.parse().await?;
+        Ok((parser.get_provider(), sessions, parser.take_drift()))
+    }
+
+    /// Parse sessions of a known provider from a reader instead of a file
+    ///
+    /// `source_name` stands in for the file path: it is stored with the
+    /// sessions and used to infer their project. The file hash is taken from
+    /// the content, so importing the same bytes again is recognized.
+    pub async fn parse_reader<R: AsyncRead + Unpin>(
+        provider: Provider,
+        source_name: &str,
+        reader: R,
+    ) -> Result<(Vec<(ChatSession, Vec<Message>)>, Vec<FormatDrift>)> {
+        let content = read_source(reader).await?;
+        let parser = Self::create_parser_for_provider(source_name, provider)?
+            .with_source_hash(content_hash(source_name, &content));
+        let sessions = parser.parse_reader(content.as_slice()).await?;
+        Ok((sessions, parser.take_drift()))
+    }
+
     pub async fn parse_file_streaming<F>(file_path: impl AsRef<Path>, callback: F) -> Result<()>
     where
         F: FnMut(ChatSession, Message) -> Result<()>,
@@ -241,6 +428,27 @@ mod tests {
     use std::fs;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_detect_provider_for_content() {
+        let detect = |name: &str, content: &str| {
+            ParserRegistry::detect_provider_for_content(name, content.as_bytes())
+        };
+        assert_eq!(
+            detect("upload.jsonl", r#"{"type":"user","sessionId":"s1"}"#),
+            Some(Provider::ClaudeCode)
+        );
+        assert_eq!(
+            detect("upload.jsonl", r#"{"type":"session_meta","payload":{}}"#),
+            Some(Provider::Codex)
+        );
+        assert_eq!(
+            detect("upload.json", r#"{"sessionId":"s1","messages":[]}"#),
+            Some(Provider::GeminiCLI)
+        );
+        assert_eq!(detect("upload.txt", "{}"), None);
+        assert_eq!(detect("upload.jsonl", "not json"), None);
+    }
+
     #[test]
     fn test_detect_provider_by_extension() {
         let temp_dir = TempDir::new().unwrap();
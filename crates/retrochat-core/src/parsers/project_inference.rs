@@ -1,4 +1,4 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Utility for inferring project names from Claude Code's encoded directory patterns
 pub struct ProjectInference {
@@ -44,6 +44,18 @@ impl ProjectInference {
         None
     }
 
+    /// Directory the session ran in, when Claude Code's encoded project
+    /// directory name resolves to one that exists
+    pub fn infer_project_dir(&self) -> Option<PathBuf> {
+        let parent_name = Path::new(&self.file_path).parent()?.file_name()?.to_str()?;
+        if !parent_name.starts_with('-') {
+            return None;
+        }
+        self.resolve_original_path(parent_name)
+            .map(PathBuf::from)
+            .filter(|path| path.is_dir())
+    }
+
     /// Extract project name from encoded directory name when filesystem validation fails
     fn extract_project_name_from_encoded(&self, encoded_name: &str) -> Option<String> {
         let without_prefix = encoded_name.trim_start_matches('-');
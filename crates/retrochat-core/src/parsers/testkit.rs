@@ -0,0 +1,294 @@
+//! Conformance fixtures for parsers
+//!
+//! A fixture is a directory with one provider log file and `expected.json`:
+//! the provider name and the sessions the parser should produce, normalized so
+//! the same input always gives the same JSON (no generated IDs, file paths or
+//! hashes). The corpus lives under `tests/fixtures/parsers/<provider>/<case>/`
+//! and runs as part of `cargo test`.
+//!
+//! To add a case, drop the log file into a new directory and run the tests
+//! with `RETROCHAT_BLESS=1`; `expected.json` is written from the parser's
+//! current output, ready to be reviewed and committed. Do the same after an
+//! intended change to a parser.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use similar::TextDiff;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::ParserRegistry;
+use crate::models::{ChatSession, Message, Provider};
+
+/// Expected output file in each fixture directory
+pub const EXPECTED_FILE_NAME: &str = "expected.json";
+
+/// Set to rewrite `expected.json` files instead of comparing against them
+pub const BLESS_ENV: &str = "RETROCHAT_BLESS";
+
+/// Contents of `expected.json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Expected {
+    /// Provider the input is parsed as, e.g. `Claude Code`
+    pub provider: String,
+    pub sessions: Vec<NormalizedSession>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NormalizedSession {
+    pub project_name: Option<String>,
+    pub title: Option<String>,
+    pub start_time: String,
+    pub end_time: Option<String>,
+    pub message_count: u32,
+    pub token_count: Option<u32>,
+    pub messages: Vec<NormalizedMessage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NormalizedMessage {
+    pub sequence_number: u32,
+    pub role: String,
+    pub message_type: String,
+    pub timestamp: String,
+    pub content: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_count: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Value>,
+    /// Tool name and input of each tool call
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_uses: Vec<(String, Value)>,
+    /// Content of each tool result, prefixed with `error: ` for failures
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_results: Vec<String>,
+    /// Label of each attachment, e.g. `Image: image/png, 2 KB`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<String>,
+}
+
+impl NormalizedSession {
+    pub fn new(session: &ChatSession, messages: &[Message]) -> Self {
+        Self {
+            project_name: session.project_name.clone(),
+            title: session.title.clone(),
+            start_time: session.start_time.to_rfc3339(),
+            end_time: session.end_time.map(|t| t.to_rfc3339()),
+            message_count: session.message_count,
+            token_count: session.token_count,
+            messages: messages.iter().map(NormalizedMessage::new).collect(),
+        }
+    }
+}
+
+impl NormalizedMessage {
+    pub fn new(message: &Message) -> Self {
+        Self {
+            sequence_number: message.sequence_number,
+            role: message.role.to_string(),
+            message_type: message.message_type.to_string(),
+            timestamp: message.timestamp.to_rfc3339(),
+            content: message.content.clone(),
+            token_count: message.token_count,
+            metadata: message.metadata.clone(),
+            tool_uses: message
+                .tool_uses
+                .iter()
+                .flatten()
+                .map(|tool_use| (tool_use.name.clone(), tool_use.input.clone()))
+                .collect(),
+            tool_results: message
+                .tool_results
+                .iter()
+                .flatten()
+                .map(|result| {
+                    if result.is_error {
+                        format!("error: {}", result.content)
+                    } else {
+                        result.content.clone()
+                    }
+                })
+                .collect(),
+            attachments: message
+                .attachments
+                .iter()
+                .flatten()
+                .map(|attachment| attachment.label())
+                .collect(),
+        }
+    }
+}
+
+/// Outcome of running a fixture corpus
+#[derive(Debug, Default)]
+pub struct CorpusReport {
+    pub passed: usize,
+    /// Fixtures whose output differs, written or failed to parse, with a diff
+    /// or error message
+    pub failures: Vec<(PathBuf, String)>,
+    /// Fixtures whose `expected.json` was (re)written
+    pub blessed: Vec<PathBuf>,
+}
+
+/// Parse a fixture's input and compare it with `expected.json`
+///
+/// Returns a unified diff of the expected and actual output when they differ.
+/// When blessing, or when there is no `expected.json` yet, the file is written
+/// instead and `None` is returned.
+pub async fn check_fixture(dir: &Path, bless: bool) -> Result<Option<String>> {
+    let input = fixture_input(dir)?;
+    let expected_path = dir.join(EXPECTED_FILE_NAME);
+    let expected: Option<Expected> = if expected_path.exists() {
+        let contents = fs::read_to_string(&expected_path)
+            .with_context(|| format!("Failed to read {}", expected_path.display()))?;
+        Some(
+            serde_json::from_str(&contents)
+                .with_context(|| format!("Invalid {}", expected_path.display()))?,
+        )
+    } else {
+        None
+    };
+
+    let provider = match &expected {
+        Some(expected) => expected
+            .provider
+            .parse::<Provider>()
+            .map_err(|e| anyhow::anyhow!(e))?,
+        None => ParserRegistry::detect_provider(&input)
+            .with_context(|| format!("Unable to detect the provider of {}", input.display()))?,
+    };
+    let sessions = ParserRegistry::create_parser_for_provider(&input, provider.clone())?
+        .parse()
+        .await
+        .with_context(|| format!("Failed to parse {}", input.display()))?;
+    let actual = Expected {
+        provider: provider.to_string(),
+        sessions: sessions
+            .iter()
+            .map(|(session, messages)| NormalizedSession::new(session, messages))
+            .collect(),
+    };
+    let actual_json = serde_json::to_string_pretty(&actual)? + "\n";
+
+    match expected {
+        Some(expected) if !bless => {
+            if expected == actual {
+                return Ok(None);
+            }
+            let expected_json = serde_json::to_string_pretty(&expected)? + "\n";
+            Ok(Some(
+                TextDiff::from_lines(&expected_json, &actual_json)
+                    .unified_diff()
+                    .header(EXPECTED_FILE_NAME, "actual")
+                    .to_string(),
+            ))
+        }
+        _ => {
+            fs::write(&expected_path, actual_json)
+                .with_context(|| format!("Failed to write {}", expected_path.display()))?;
+            Ok(None)
+        }
+    }
+}
+
+/// Check every fixture under `root`; blesses them when [`BLESS_ENV`] is set
+pub async fn run_corpus(root: &Path) -> Result<CorpusReport> {
+    let bless = std::env::var_os(BLESS_ENV).is_some();
+    let mut report = CorpusReport::default();
+    for dir in fixture_dirs(root)? {
+        let had_expected = dir.join(EXPECTED_FILE_NAME).exists();
+        match check_fixture(&dir, bless).await {
+            Ok(None) if bless || !had_expected => report.blessed.push(dir),
+            Ok(None) => report.passed += 1,
+            Ok(Some(diff)) => report.failures.push((dir, diff)),
+            Err(e) => report.failures.push((dir, format!("{e:#}"))),
+        }
+    }
+    Ok(report)
+}
+
+/// The one input file of a fixture directory
+fn fixture_input(dir: &Path) -> Result<PathBuf> {
+    let mut inputs = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))? {
+        let path = entry?.path();
+        if path.is_file() && path.file_name() != Some(EXPECTED_FILE_NAME.as_ref()) {
+            inputs.push(path);
+        }
+    }
+    match inputs.len() {
+        1 => Ok(inputs.remove(0)),
+        n => anyhow::bail!(
+            "Fixture {} needs exactly one input file next to {EXPECTED_FILE_NAME}, found {n}",
+            dir.display()
+        ),
+    }
+}
+
+/// Directories under `root` that contain files, in path order
+fn fixture_dirs(root: &Path) -> Result<Vec<PathBuf>> {
+    let mut dirs = Vec::new();
+    let mut has_files = false;
+    let mut entries: Vec<PathBuf> = fs::read_dir(root)
+        .with_context(|| format!("Failed to read {}", root.display()))?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<std::io::Result<_>>()?;
+    entries.sort();
+    for path in entries {
+        if path.is_dir() {
+            dirs.extend(fixture_dirs(&path)?);
+        } else {
+            has_files = true;
+        }
+    }
+    if has_files {
+        dirs.insert(0, root.to_path_buf());
+    }
+    Ok(dirs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_parser_corpus() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/parsers");
+        let report = run_corpus(&root).await.unwrap();
+
+        for (dir, failure) in &report.failures {
+            eprintln!("--- {}\n{failure}", dir.display());
+        }
+        assert!(
+            report.failures.is_empty(),
+            "{} parser fixtures failed; rerun with {BLESS_ENV}=1 if the change is intended",
+            report.failures.len()
+        );
+        assert!(report.passed + report.blessed.len() > 0);
+    }
+
+    #[tokio::test]
+    async fn test_check_fixture_reports_diff() {
+        let source = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/parsers/claude_code/basic_conversation");
+        // Claude Code takes the project name from the directory, so keep it
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path().join("basic_conversation");
+        fs::create_dir(&dir).unwrap();
+        for entry in fs::read_dir(&source).unwrap() {
+            let path = entry.unwrap().path();
+            fs::copy(&path, dir.join(path.file_name().unwrap())).unwrap();
+        }
+        assert_eq!(check_fixture(&dir, false).await.unwrap(), None);
+
+        let expected_path = dir.join(EXPECTED_FILE_NAME);
+        let mut expected: Expected =
+            serde_json::from_str(&fs::read_to_string(&expected_path).unwrap()).unwrap();
+        expected.sessions[0].messages[0].content = "something else".to_string();
+        fs::write(&expected_path, serde_json::to_string(&expected).unwrap()).unwrap();
+
+        let diff = check_fixture(&dir, false).await.unwrap().unwrap();
+        assert!(diff.contains("-          \"content\": \"something else\""));
+    }
+}
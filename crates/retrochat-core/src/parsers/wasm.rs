@@ -0,0 +1,334 @@
+//! WASM parser plugins (requires the `wasm-plugins` feature)
+//!
+//! Plugins are core WebAssembly modules dropped into `~/.retrochat/plugins/`.
+//! The file stem becomes the provider name. A plugin gets no host imports and
+//! no filesystem: the host reads the target file and copies its bytes into the
+//! plugin's memory, so a plugin can only ever see the file it was asked to parse.
+//!
+//! ABI version 1 — a plugin must export:
+//!
+//! - `memory`
+//! - `retrochat_abi_version() -> i32` returning `1`
+//! - `retrochat_alloc(len: i32) -> i32` returning a buffer for the host to fill
+//! - `retrochat_file_pattern() -> i64` pointing at a UTF-8 glob of files it parses
+//! - `retrochat_parse(ptr: i32, len: i32) -> i64` pointing at UTF-8 JSON output
+//!
+//! Returned `i64` values pack a pointer and length as `(ptr << 32) | len`. The
+//! JSON output is an [`ExternalSessionDocument`], or `{"error": "..."}`.
+
+use anyhow::{anyhow, Context, Result};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use wasmtime::{Config, Engine, Instance, Memory, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+use super::external::{convert_document, matches_glob, ExternalSessionDocument};
+use crate::models::{ChatSession, Message, Provider};
+
+/// ABI version implemented by this host
+pub const ABI_VERSION: i32 = 1;
+
+/// Upper bound on instructions executed per call
+const FUEL_PER_CALL: u64 = 10_000_000_000;
+
+/// Upper bound on a plugin's linear memory
+const MAX_MEMORY_BYTES: usize = 512 * 1024 * 1024;
+
+/// A compiled plugin and the files it claims
+#[derive(Clone)]
+pub struct WasmPlugin {
+    pub name: String,
+    pub pattern: String,
+    pub path: PathBuf,
+    engine: Engine,
+    module: Module,
+}
+
+impl WasmPlugin {
+    /// Compile a plugin module and query its file pattern
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path)
+            .with_context(|| format!("Failed to read plugin: {}", path.display()))?;
+        let name = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .ok_or_else(|| anyhow!("Invalid plugin file name: {}", path.display()))?
+            .to_string();
+        Self::from_bytes(name, path.to_path_buf(), &bytes)
+    }
+
+    fn from_bytes(name: String, path: PathBuf, bytes: &[u8]) -> Result<Self> {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config)?;
+        let module = Module::new(&engine, bytes)
+            .with_context(|| format!("Failed to compile plugin: {name}"))?;
+
+        let mut plugin = Self {
+            name,
+            pattern: String::new(),
+            path,
+            engine,
+            module,
+        };
+
+        let mut session = plugin.instantiate()?;
+        let version = session
+            .instance
+            .get_typed_func::<(), i32>(&mut session.store, "retrochat_abi_version")?
+            .call(&mut session.store, ())?;
+        if version != ABI_VERSION {
+            return Err(anyhow!(
+                "Plugin {} uses ABI version {version}, expected {ABI_VERSION}",
+                plugin.name
+            ));
+        }
+
+        let packed = session
+            .instance
+            .get_typed_func::<(), i64>(&mut session.store, "retrochat_file_pattern")?
+            .call(&mut session.store, ())?;
+        plugin.pattern = String::from_utf8(session.read(packed)?)
+            .context("Plugin file pattern is not valid UTF-8")?;
+
+        Ok(plugin)
+    }
+
+    pub fn matches(&self, path: &Path) -> bool {
+        matches_glob(&self.pattern, path)
+    }
+
+    /// Run the plugin over a file's contents and return its JSON output
+    pub fn parse_bytes(&self, input: &[u8]) -> Result<Vec<u8>> {
+        let mut session = self.instantiate()?;
+
+        let len = i32::try_from(input.len()).context("File is too large for a WASM plugin")?;
+        let ptr = session
+            .instance
+            .get_typed_func::<i32, i32>(&mut session.store, "retrochat_alloc")?
+            .call(&mut session.store, len)?;
+        session
+            .memory
+            .write(&mut session.store, ptr as usize, input)
+            .context("Plugin returned an invalid input buffer")?;
+
+        let packed = session
+            .instance
+            .get_typed_func::<(i32, i32), i64>(&mut session.store, "retrochat_parse")?
+            .call(&mut session.store, (ptr, len))
+            .with_context(|| format!("Plugin {} failed while parsing", self.name))?;
+        session.read(packed)
+    }
+
+    fn instantiate(&self) -> Result<PluginSession> {
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(MAX_MEMORY_BYTES)
+            .build();
+        let mut store = Store::new(&self.engine, limits);
+        store.limiter(|limits| limits);
+        store.set_fuel(FUEL_PER_CALL)?;
+
+        // No imports are provided, which keeps plugins away from the host entirely
+        let instance = Instance::new(&mut store, &self.module, &[]).with_context(|| {
+            format!(
+                "Failed to instantiate plugin {} (plugins may not import host functions)",
+                self.name
+            )
+        })?;
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or_else(|| anyhow!("Plugin {} does not export memory", self.name))?;
+
+        Ok(PluginSession {
+            store,
+            instance,
+            memory,
+        })
+    }
+}
+
+struct PluginSession {
+    store: Store<StoreLimits>,
+    instance: Instance,
+    memory: Memory,
+}
+
+impl PluginSession {
+    /// Copy a packed `(ptr << 32) | len` region out of plugin memory
+    fn read(&self, packed: i64) -> Result<Vec<u8>> {
+        let ptr = (packed as u64 >> 32) as usize;
+        let len = (packed as u64 & 0xffff_ffff) as usize;
+        self.memory
+            .data(&self.store)
+            .get(ptr..ptr + len)
+            .map(<[u8]>::to_vec)
+            .ok_or_else(|| anyhow!("Plugin returned an out-of-bounds buffer"))
+    }
+}
+
+/// Directory plugins are discovered from (`~/.retrochat/plugins`)
+pub fn plugins_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".retrochat").join("plugins"))
+}
+
+/// Plugins found in the plugins directory, loaded once per process
+///
+/// Plugins that fail to load are skipped with a warning.
+pub fn installed_plugins() -> &'static [WasmPlugin] {
+    static PLUGINS: OnceLock<Vec<WasmPlugin>> = OnceLock::new();
+    PLUGINS.get_or_init(|| {
+        plugins_dir()
+            .map(|dir| load_plugins(&dir))
+            .unwrap_or_default()
+    })
+}
+
+/// Load every `*.wasm` module in `dir`
+pub fn load_plugins(dir: &Path) -> Vec<WasmPlugin> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("wasm"))
+        .collect();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .filter_map(|path| match WasmPlugin::load(&path) {
+            Ok(plugin) => Some(plugin),
+            Err(e) => {
+                tracing::warn!(error = %e, path = %path.display(), "Skipping WASM plugin");
+                None
+            }
+        })
+        .collect()
+}
+
+pub fn find_plugin_for_path(path: &Path) -> Option<&'static WasmPlugin> {
+    installed_plugins()
+        .iter()
+        .find(|plugin| plugin.matches(path))
+}
+
+pub fn find_plugin_by_name(name: &str) -> Option<&'static WasmPlugin> {
+    installed_plugins()
+        .iter()
+        .find(|plugin| plugin.name == name)
+}
+
+pub struct WasmPluginParser {
+    file_path: PathBuf,
+    plugin: WasmPlugin,
+}
+
+impl WasmPluginParser {
+    pub fn new(file_path: impl AsRef<Path>, plugin: WasmPlugin) -> Self {
+        Self {
+            file_path: file_path.as_ref().to_path_buf(),
+            plugin,
+        }
+    }
+
+    pub fn provider(&self) -> Provider {
+        Provider::Other(self.plugin.name.clone())
+    }
+
+    pub async fn parse(&self) -> Result<Vec<(ChatSession, Vec<Message>)>> {
+        let input = tokio::fs::read(&self.file_path)
+            .await
+            .with_context(|| format!("Failed to read file: {}", self.file_path.display()))?;
+
+        let plugin = self.plugin.clone();
+        let output = tokio::task::spawn_blocking(move || plugin.parse_bytes(&input)).await??;
+
+        let value: serde_json::Value = serde_json::from_slice(&output)
+            .with_context(|| format!("Plugin {} did not return valid JSON", self.plugin.name))?;
+        if let Some(error) = value.get("error").and_then(|e| e.as_str()) {
+            return Err(anyhow!("Plugin {} failed: {error}", self.plugin.name));
+        }
+        let document: ExternalSessionDocument =
+            serde_json::from_value(value).with_context(|| {
+                format!(
+                    "Plugin {} did not return a valid session document",
+                    self.plugin.name
+                )
+            })?;
+
+        convert_document(document, self.provider(), &self.file_path)
+    }
+
+    pub async fn parse_streaming<F>(&self, mut callback: F) -> Result<()>
+    where
+        F: FnMut(ChatSession, Message) -> Result<()>,
+    {
+        let sessions = self.parse().await?;
+
+        for (session, messages) in sessions {
+            for message in messages {
+                callback(session.clone(), message)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const DOCUMENT: &str = r#"{"sessions":[{"id":"550e8400-e29b-41d4-a716-446655440000","messages":[{"role":"user","content":"Hi","timestamp":"2024-01-01T00:00:00Z"}]}]}"#;
+
+    /// A plugin that claims `*.demo` files and always returns `DOCUMENT`
+    fn demo_plugin_wat(imports: &str) -> String {
+        let pattern = "*.demo";
+        format!(
+            r#"(module
+                {imports}
+                (memory (export "memory") 1)
+                (data (i32.const 16) "{pattern}")
+                (data (i32.const 64) "{document}")
+                (func (export "retrochat_abi_version") (result i32) i32.const 1)
+                (func (export "retrochat_alloc") (param i32) (result i32) i32.const 4096)
+                (func (export "retrochat_file_pattern") (result i64)
+                    i64.const {pattern_packed})
+                (func (export "retrochat_parse") (param i32 i32) (result i64)
+                    i64.const {document_packed}))"#,
+            document = DOCUMENT.replace('"', "\\\""),
+            pattern_packed = (16i64 << 32) | pattern.len() as i64,
+            document_packed = (64i64 << 32) | DOCUMENT.len() as i64,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_wasm_plugin_parses_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let plugin_path = temp_dir.path().join("demo.wasm");
+        std::fs::write(&plugin_path, demo_plugin_wat("")).unwrap();
+        let file_path = temp_dir.path().join("chat.demo");
+        std::fs::write(&file_path, "anything").unwrap();
+
+        let plugins = load_plugins(temp_dir.path());
+        assert_eq!(plugins.len(), 1);
+        assert_eq!(plugins[0].name, "demo");
+        assert!(plugins[0].matches(&file_path));
+
+        let parser = WasmPluginParser::new(&file_path, plugins[0].clone());
+        let sessions = parser.parse().await.unwrap();
+
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].0.provider, Provider::Other("demo".to_string()));
+        assert_eq!(sessions[0].1[0].content, "Hi");
+    }
+
+    #[test]
+    fn test_wasm_plugin_with_imports_is_rejected() {
+        let wat = demo_plugin_wat(r#"(import "wasi_snapshot_preview1" "fd_write" (func))"#);
+        let result = WasmPlugin::from_bytes("demo".to_string(), PathBuf::new(), wat.as_bytes());
+        assert!(result.is_err());
+    }
+}
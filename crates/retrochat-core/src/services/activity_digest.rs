@@ -0,0 +1,379 @@
+use anyhow::{Context, Result as AnyhowResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::database::{
+    ChatSessionRepository, DatabaseManager, MessageRepository, ToolOperationRepository,
+};
+use crate::models::message::MessageType;
+use crate::models::{Message, ToolOperation};
+
+/// Default number of entries kept in each digest section
+pub const DEFAULT_DIGEST_LIMIT: usize = 10;
+
+/// Maximum characters kept for prompt previews and failure details
+const PREVIEW_CHARS: usize = 120;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentActivityRequest {
+    pub since: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+    /// Maximum entries per section (sessions, projects, files, failures)
+    pub limit: Option<usize>,
+}
+
+/// Compact summary of what happened across sessions in a time window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentActivityDigest {
+    pub window_start: DateTime<Utc>,
+    pub window_end: DateTime<Utc>,
+    pub session_count: usize,
+    pub message_count: usize,
+    pub sessions: Vec<ActivitySession>,
+    pub projects: Vec<ActivityProject>,
+    pub file_changes: Vec<ActivityFileChange>,
+    pub failures: Vec<ActivityFailure>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivitySession {
+    pub session_id: String,
+    pub provider: String,
+    pub project: Option<String>,
+    pub start_time: DateTime<Utc>,
+    /// Messages of this session that fall inside the window
+    pub message_count: usize,
+    /// First user prompt inside the window
+    pub first_prompt: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityProject {
+    pub project: String,
+    pub session_count: usize,
+    pub message_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityFileChange {
+    pub file_path: String,
+    pub edit_count: usize,
+    pub lines_added: i64,
+    pub lines_removed: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityFailure {
+    pub session_id: String,
+    pub tool_name: String,
+    pub timestamp: DateTime<Utc>,
+    /// Failed command (for Bash) or the tool's result summary
+    pub detail: Option<String>,
+}
+
+/// Service for building recent-activity digests across all sessions
+pub struct ActivityDigestService {
+    db_manager: Arc<DatabaseManager>,
+}
+
+impl ActivityDigestService {
+    pub fn with_database(db_manager: Arc<DatabaseManager>) -> Self {
+        Self { db_manager }
+    }
+
+    pub async fn get_recent_activity(
+        &self,
+        request: RecentActivityRequest,
+    ) -> AnyhowResult<RecentActivityDigest> {
+        if request.since > request.until {
+            anyhow::bail!("Window start must be before window end");
+        }
+        let limit = request.limit.unwrap_or(DEFAULT_DIGEST_LIMIT);
+
+        let session_repo = ChatSessionRepository::new(&self.db_manager);
+        let message_repo = MessageRepository::new(&self.db_manager);
+        let tool_repo = ToolOperationRepository::new(&self.db_manager);
+
+        let sessions = session_repo
+            .get_by_time_range(&request.since, &request.until)
+            .await
+            .context("Failed to load sessions for activity digest")?;
+        let messages = message_repo
+            .get_by_time_range(
+                Some(request.since),
+                Some(request.until),
+                None,
+                None,
+                None,
+                false,
+            )
+            .await
+            .context("Failed to load messages for activity digest")?;
+        let operations = tool_repo
+            .get_by_time_range(&request.since, &request.until)
+            .await
+            .context("Failed to load tool operations for activity digest")?;
+
+        let mut message_counts: HashMap<Uuid, usize> = HashMap::new();
+        let mut first_prompts: HashMap<Uuid, &Message> = HashMap::new();
+        for message in &messages {
+            *message_counts.entry(message.session_id).or_insert(0) += 1;
+            if message.is_user_message() && message.message_type == MessageType::SimpleMessage {
+                first_prompts.entry(message.session_id).or_insert(message);
+            }
+        }
+
+        let mut projects: HashMap<String, ActivityProject> = HashMap::new();
+        let mut activity_sessions = Vec::with_capacity(sessions.len());
+        for session in &sessions {
+            let count = message_counts.get(&session.id).copied().unwrap_or(0);
+            if let Some(project) = &session.project_name {
+                let entry = projects
+                    .entry(project.clone())
+                    .or_insert_with(|| ActivityProject {
+                        project: project.clone(),
+                        session_count: 0,
+                        message_count: 0,
+                    });
+                entry.session_count += 1;
+                entry.message_count += count;
+            }
+
+            activity_sessions.push(ActivitySession {
+                session_id: session.id.to_string(),
+                provider: session.provider.to_string(),
+                project: session.project_name.clone(),
+                start_time: session.start_time,
+                message_count: count,
+                first_prompt: first_prompts
+                    .get(&session.id)
+                    .map(|m| truncate_preview(&m.content)),
+            });
+        }
+        activity_sessions.truncate(limit);
+
+        let mut projects: Vec<ActivityProject> = projects.into_values().collect();
+        projects.sort_by(|a, b| {
+            b.message_count
+                .cmp(&a.message_count)
+                .then_with(|| a.project.cmp(&b.project))
+        });
+        projects.truncate(limit);
+
+        Ok(RecentActivityDigest {
+            window_start: request.since,
+            window_end: request.until,
+            session_count: sessions.len(),
+            message_count: messages.len(),
+            sessions: activity_sessions,
+            projects,
+            file_changes: summarize_file_changes(&operations, limit),
+            failures: collect_failures(&operations, limit),
+        })
+    }
+}
+
+/// Aggregate Write/Edit operations per file, most edited first
+pub fn summarize_file_changes(
+    operations: &[(Uuid, ToolOperation)],
+    limit: usize,
+) -> Vec<ActivityFileChange> {
+    let mut changes: HashMap<String, ActivityFileChange> = HashMap::new();
+
+    for (_, operation) in operations {
+        if operation.tool_name != "Write" && operation.tool_name != "Edit" {
+            continue;
+        }
+        let Some(meta) = &operation.file_metadata else {
+            continue;
+        };
+
+        let entry = changes
+            .entry(meta.file_path.clone())
+            .or_insert_with(|| ActivityFileChange {
+                file_path: meta.file_path.clone(),
+                edit_count: 0,
+                lines_added: 0,
+                lines_removed: 0,
+            });
+        entry.edit_count += 1;
+        entry.lines_added += meta.lines_added.unwrap_or(0) as i64;
+        entry.lines_removed += meta.lines_removed.unwrap_or(0) as i64;
+    }
+
+    let mut changes: Vec<ActivityFileChange> = changes.into_values().collect();
+    changes.sort_by(|a, b| {
+        b.edit_count
+            .cmp(&a.edit_count)
+            .then_with(|| (b.lines_added + b.lines_removed).cmp(&(a.lines_added + a.lines_removed)))
+            .then_with(|| a.file_path.cmp(&b.file_path))
+    });
+    changes.truncate(limit);
+    changes
+}
+
+/// Collect the most recent failed tool operations
+pub fn collect_failures(
+    operations: &[(Uuid, ToolOperation)],
+    limit: usize,
+) -> Vec<ActivityFailure> {
+    operations
+        .iter()
+        .rev()
+        .filter(|(_, op)| op.success == Some(false))
+        .take(limit)
+        .map(|(session_id, op)| ActivityFailure {
+            session_id: session_id.to_string(),
+            tool_name: op.tool_name.clone(),
+            timestamp: op.timestamp,
+            detail: op
+                .bash_metadata
+                .as_ref()
+                .map(|bash| bash.command.clone())
+                .or_else(|| op.result_summary.clone())
+                .map(|text| truncate_preview(&text)),
+        })
+        .collect()
+}
+
+fn truncate_preview(text: &str) -> String {
+    let text = text.trim();
+    if text.chars().count() > PREVIEW_CHARS {
+        let truncated: String = text.chars().take(PREVIEW_CHARS - 3).collect();
+        format!("{truncated}...")
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::MessageRole;
+
+    fn ts(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn edit(file_path: &str, added: i32, removed: i32) -> (Uuid, ToolOperation) {
+        let mut operation = ToolOperation::new(
+            Uuid::new_v4().to_string(),
+            "Edit".to_string(),
+            ts("2024-01-01T00:00:00Z"),
+        )
+        .with_file_path(file_path.to_string());
+        if let Some(meta) = &mut operation.file_metadata {
+            meta.lines_added = Some(added);
+            meta.lines_removed = Some(removed);
+        }
+        (Uuid::new_v4(), operation)
+    }
+
+    #[test]
+    fn test_summarize_file_changes_orders_by_edit_count() {
+        let operations = vec![
+            edit("src/a.rs", 5, 1),
+            edit("src/b.rs", 2, 0),
+            edit("src/b.rs", 3, 3),
+        ];
+
+        let changes = summarize_file_changes(&operations, 10);
+
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].file_path, "src/b.rs");
+        assert_eq!(changes[0].edit_count, 2);
+        assert_eq!(changes[0].lines_added, 5);
+        assert_eq!(changes[0].lines_removed, 3);
+        assert_eq!(summarize_file_changes(&operations, 1).len(), 1);
+    }
+
+    #[test]
+    fn test_collect_failures_uses_result_summary() {
+        let session_id = Uuid::new_v4();
+        let failed = ToolOperation::new(
+            "tool-1".to_string(),
+            "Read".to_string(),
+            ts("2024-01-01T00:00:00Z"),
+        )
+        .with_success(false)
+        .with_result_summary("File does not exist".to_string());
+        let succeeded = ToolOperation::new(
+            "tool-2".to_string(),
+            "Read".to_string(),
+            ts("2024-01-01T00:01:00Z"),
+        )
+        .with_success(true);
+
+        let failures = collect_failures(&[(session_id, failed), (session_id, succeeded)], 10);
+
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].tool_name, "Read");
+        assert_eq!(failures[0].detail.as_deref(), Some("File does not exist"));
+    }
+
+    #[tokio::test]
+    async fn test_get_recent_activity_from_database() {
+        let db = Arc::new(DatabaseManager::open_in_memory().await.unwrap());
+        let message_repo = MessageRepository::new(&db);
+        sqlx::query("INSERT INTO projects (id, name) VALUES ('p1', 'retrochat')")
+            .execute(db.pool())
+            .await
+            .unwrap();
+
+        for (i, start) in ["2024-01-10T09:00:00Z", "2023-12-01T09:00:00Z"]
+            .iter()
+            .enumerate()
+        {
+            let session_id = Uuid::new_v4();
+            sqlx::query(
+                r#"
+                INSERT INTO chat_sessions (id, provider, project_name, start_time, end_time, message_count, file_path, file_hash, state)
+                VALUES (?, 'Claude Code', 'retrochat', ?, ?, 1, ?, ?, 'imported')
+                "#,
+            )
+            .bind(session_id.to_string())
+            .bind(*start)
+            .bind(*start)
+            .bind(format!("/test{i}.jsonl"))
+            .bind(format!("hash{i}"))
+            .execute(db.pool())
+            .await
+            .unwrap();
+
+            message_repo
+                .create(&Message::new(
+                    session_id,
+                    MessageRole::User,
+                    "Add a recent activity digest".to_string(),
+                    ts(start),
+                    1,
+                ))
+                .await
+                .unwrap();
+        }
+
+        let service = ActivityDigestService::with_database(db.clone());
+        let digest = service
+            .get_recent_activity(RecentActivityRequest {
+                since: ts("2024-01-08T00:00:00Z"),
+                until: ts("2024-01-15T00:00:00Z"),
+                limit: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(digest.session_count, 1);
+        assert_eq!(digest.message_count, 1);
+        assert_eq!(digest.projects.len(), 1);
+        assert_eq!(digest.projects[0].project, "retrochat");
+        assert_eq!(
+            digest.sessions[0].first_prompt.as_deref(),
+            Some("Add a recent activity digest")
+        );
+    }
+}
@@ -1,13 +1,73 @@
 use super::models::{
     AIQualitativeOutput, AIQuantitativeOutput, QualitativeEntry, QualitativeEntryList,
     QualitativeEntryOutput, QualitativeInput, Rubric, RubricEvaluationSummary, RubricList,
-    RubricScore,
+    RubricScore, RubricWeights,
 };
 use crate::models::message::MessageType;
 use crate::models::{Message, MessageRole};
+use crate::services::llm::structured::parse_structured_or;
 use crate::services::llm::{GenerateRequest, LlmClient};
+use crate::utils::locale::Language;
 use anyhow::Result;
 use regex::Regex;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+/// Maximum length of a qualitative entry's summary, in characters
+const MAX_SUMMARY_CHARS: usize = 100;
+
+/// Structured response for a single qualitative entry
+#[derive(Debug, Deserialize)]
+struct EntryResponse {
+    summary: String,
+    #[serde(default)]
+    items: Vec<String>,
+}
+
+/// Structured response for a rubric score
+#[derive(Debug, Deserialize)]
+struct RubricResponse {
+    score: f64,
+    #[serde(default)]
+    reasoning: String,
+}
+
+fn entry_response_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "summary": {
+                "type": "string",
+                "description": "One line (max 100 characters) summarizing the key finding"
+            },
+            "items": {
+                "type": "array",
+                "items": {
+                    "type": "string",
+                    "description": "One markdown line: **Observation title**: details"
+                }
+            }
+        },
+        "required": ["summary", "items"]
+    })
+}
+
+fn rubric_response_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "score": {
+                "type": "integer",
+                "description": "Score from 1 to 5"
+            },
+            "reasoning": {
+                "type": "string",
+                "description": "2-3 sentences with specific evidence from the session"
+            }
+        },
+        "required": ["score", "reasoning"]
+    })
+}
 
 // =============================================================================
 // AI Analysis Functions
@@ -17,6 +77,7 @@ pub async fn generate_qualitative_analysis_ai(
     qualitative_input: &QualitativeInput,
     llm_client: &dyn LlmClient,
     entries: Option<&QualitativeEntryList>,
+    language: Language,
 ) -> Result<AIQualitativeOutput> {
     // Use provided entries or load defaults
     let entry_list = match entries {
@@ -27,7 +88,7 @@ pub async fn generate_qualitative_analysis_ai(
     // Process all entry types sequentially (can't easily pass trait object to spawned tasks)
     let mut results = Vec::new();
     for entry in &entry_list.entries {
-        let result = generate_single_entry(qualitative_input, entry, llm_client).await;
+        let result = generate_single_entry(qualitative_input, entry, llm_client, language).await;
         results.push((entry.clone(), result));
     }
 
@@ -63,12 +124,14 @@ async fn generate_single_entry(
     qualitative_input: &QualitativeInput,
     entry: &QualitativeEntry,
     llm_client: &dyn LlmClient,
+    language: Language,
 ) -> Result<QualitativeEntryOutput> {
-    let prompt = build_single_entry_prompt(qualitative_input, entry);
+    let prompt = language.localize_prompt(build_single_entry_prompt(qualitative_input, entry));
 
     let request = GenerateRequest::new(prompt)
         .with_max_tokens(1024)
-        .with_temperature(0.7);
+        .with_temperature(0.7)
+        .with_response_schema(entry_response_schema());
 
     let response = llm_client
         .generate(request)
@@ -103,24 +166,16 @@ Each item should be a single, concise markdown line that captures one specific o
 
 ## Required Output Format
 
-Your response MUST follow this exact format:
-
-SHORT_SUMMARY: [A single line (max 100 characters) summarizing the key finding for {title}]
+Respond with a JSON object with these fields:
 
-ITEMS:
-1. **Observation title**: Brief description of the observation with specific details.
-2. **Another observation**: Another specific point with supporting evidence.
+- "summary": a single line (max 100 characters) summarizing the key finding for {title}
+- "items": a list of observations, each a single line of markdown text
 
 Example:
-SHORT_SUMMARY: User demonstrated strong debugging skills but could improve test coverage practices.
-
-ITEMS:
-1. **Clear problem articulation**: User explained the bug clearly with specific error messages.
-2. **Iterative approach**: User refined requirements based on initial results.
+{{"summary": "User demonstrated strong debugging skills but could improve test coverage practices.", "items": ["**Clear problem articulation**: User explained the bug clearly with specific error messages.", "**Iterative approach**: User refined requirements based on initial results."]}}
 
 Important:
-- SHORT_SUMMARY must be a single concise line (no more than 100 characters).
-- Return numbered list items under ITEMS section.
+- Return only the JSON object, with no text before or after it.
 - Each item must be a single line of markdown text.
 - Focus on specific, actionable observations from the session."#,
         title = entry.title.to_lowercase(),
@@ -134,11 +189,37 @@ Important:
 // =============================================================================
 
 /// Parse the LLM response for a single entry type
-/// Expects SHORT_SUMMARY and ITEMS sections
+///
+/// Expects a JSON object with `summary` and `items`; falls back to the
+/// older SHORT_SUMMARY/ITEMS text format when no JSON can be recovered.
 fn parse_entry_response(
     response_text: &str,
     entry: &QualitativeEntry,
 ) -> Result<QualitativeEntryOutput> {
+    let parsed = parse_structured_or(response_text, |text| {
+        parse_legacy_entry_response(text, &entry.key)
+    })
+    .unwrap_or(EntryResponse {
+        summary: String::new(),
+        items: Vec::new(),
+    });
+
+    Ok(QualitativeEntryOutput {
+        key: entry.key.clone(),
+        title: entry.title.clone(),
+        description: entry.description.clone(),
+        summary: truncate_summary(parsed.summary.trim()),
+        items: parsed
+            .items
+            .into_iter()
+            .map(|item| item.trim().to_string())
+            .filter(|item| !item.is_empty())
+            .collect(),
+    })
+}
+
+/// Parse the older SHORT_SUMMARY/ITEMS text format, or `None` if neither is present
+fn parse_legacy_entry_response(response_text: &str, entry_key: &str) -> Option<EntryResponse> {
     let mut items = Vec::new();
     let mut summary = String::new();
 
@@ -147,10 +228,6 @@ fn parse_entry_response(
     if let Some(caps) = summary_re.captures(response_text) {
         if let Some(summary_match) = caps.get(1) {
             summary = summary_match.as_str().trim().to_string();
-            // Truncate to 100 characters if needed
-            if summary.len() > 100 {
-                summary = format!("{}...", &summary[..97]);
-            }
         }
     }
 
@@ -190,7 +267,7 @@ fn parse_entry_response(
     if items.is_empty() {
         tracing::warn!(
             "No items parsed for entry {}, response: {}",
-            entry.key,
+            entry_key,
             response_text
         );
     }
@@ -198,18 +275,21 @@ fn parse_entry_response(
     if summary.is_empty() {
         tracing::warn!(
             "No summary parsed for entry {}, response: {}",
-            entry.key,
+            entry_key,
             response_text
         );
     }
 
-    Ok(QualitativeEntryOutput {
-        key: entry.key.clone(),
-        title: entry.title.clone(),
-        description: entry.description.clone(),
-        summary,
-        items,
-    })
+    (!summary.is_empty() || !items.is_empty()).then_some(EntryResponse { summary, items })
+}
+
+/// Cut a summary down to [`MAX_SUMMARY_CHARS`], marking the cut with an ellipsis
+fn truncate_summary(summary: &str) -> String {
+    if summary.chars().count() <= MAX_SUMMARY_CHARS {
+        return summary.to_string();
+    }
+    let kept: String = summary.chars().take(MAX_SUMMARY_CHARS - 3).collect();
+    format!("{kept}...")
 }
 
 // =============================================================================
@@ -311,27 +391,38 @@ fn build_rubric_judge_prompt(rubric: &Rubric, formatted_session: &str) -> String
 
 ## Required Output Format
 
-Respond EXACTLY in this format:
-SCORE: [1-5]
-REASONING: [Your 2-3 sentence explanation with specific evidence]
+Respond with only a JSON object with these fields:
+- "score": an integer from 1 to 5
+- "reasoning": your 2-3 sentence explanation with specific evidence
 
 Example:
-SCORE: 4
-REASONING: The user provided clear requirements by specifying the exact functionality needed and mentioning edge cases. They could improve by providing more context about the existing codebase structure."#,
+{{"score": 4, "reasoning": "The user provided clear requirements by specifying the exact functionality needed and mentioning edge cases. They could improve by providing more context about the existing codebase structure."}}"#,
         rubric_content = rubric.format_for_prompt(),
         session = formatted_session
     )
 }
 
 /// Parse the LLM response to extract score and reasoning
+///
+/// Expects a JSON object with `score` and `reasoning`; falls back to the
+/// older SCORE/REASONING text format when no JSON can be recovered.
 fn parse_rubric_score_response(response: &str) -> (Option<f64>, String) {
+    match parse_structured_or(response, parse_legacy_rubric_response) {
+        Some(parsed) => (
+            Some(parsed.score.clamp(1.0, 5.0)),
+            parsed.reasoning.trim().to_string(),
+        ),
+        None => (None, String::new()),
+    }
+}
+
+/// Parse the older SCORE/REASONING text format, or `None` without a score
+fn parse_legacy_rubric_response(response: &str) -> Option<RubricResponse> {
     // Extract score using regex
     let score_re = Regex::new(r"SCORE:\s*(\d+(?:\.\d+)?)").unwrap();
-    let score = score_re.captures(response).and_then(|caps| {
-        caps.get(1)
-            .and_then(|m| m.as_str().parse::<f64>().ok())
-            .map(|s| s.clamp(1.0, 5.0))
-    });
+    let score = score_re
+        .captures(response)
+        .and_then(|caps| caps.get(1).and_then(|m| m.as_str().parse::<f64>().ok()))?;
 
     // Extract reasoning using regex
     let reasoning_re = Regex::new(r"(?i)REASONING:\s*(.+?)(?:\n\n|\z)").unwrap();
@@ -340,7 +431,7 @@ fn parse_rubric_score_response(response: &str) -> (Option<f64>, String) {
         .and_then(|caps| caps.get(1).map(|m| m.as_str().trim().to_string()))
         .unwrap_or_default();
 
-    (score, reasoning)
+    Some(RubricResponse { score, reasoning })
 }
 
 /// Score a session against a single rubric
@@ -348,12 +439,14 @@ async fn score_rubric(
     rubric: &Rubric,
     formatted_session: &str,
     llm_client: &dyn LlmClient,
+    language: Language,
 ) -> Result<RubricScore> {
-    let prompt = build_rubric_judge_prompt(rubric, formatted_session);
+    let prompt = language.localize_prompt(build_rubric_judge_prompt(rubric, formatted_session));
 
     let request = GenerateRequest::new(prompt.clone())
         .with_max_tokens(512)
-        .with_temperature(0.3); // Lower temperature for more consistent scoring
+        .with_temperature(0.3) // Lower temperature for more consistent scoring
+        .with_response_schema(rubric_response_schema());
 
     let (score, reasoning) = match llm_client.generate(request).await {
         Ok(response) => {
@@ -366,13 +459,14 @@ async fn score_rubric(
                     rubric.id
                 );
                 let retry_prompt = format!(
-                    "{}\n\nIMPORTANT: Please respond EXACTLY in this format:\nSCORE: [1-5]\nREASONING: [your explanation]",
+                    "{}\n\nIMPORTANT: Respond with ONLY this JSON object and nothing else:\n{{\"score\": <1-5>, \"reasoning\": \"<your explanation>\"}}",
                     prompt
                 );
 
                 let retry_request = GenerateRequest::new(retry_prompt)
                     .with_max_tokens(512)
-                    .with_temperature(0.3);
+                    .with_temperature(0.3)
+                    .with_response_schema(rubric_response_schema());
 
                 match llm_client.generate(retry_request).await {
                     Ok(retry_response) => parse_rubric_score_response(&retry_response.text),
@@ -409,6 +503,7 @@ async fn score_rubric(
         score: final_score,
         max_score: 5.0,
         reasoning: final_reasoning,
+        rubric_weight: rubric.weight,
     })
 }
 
@@ -416,8 +511,11 @@ pub async fn generate_quantitative_analysis_ai(
     qualitative_input: &QualitativeInput,
     llm_client: &dyn LlmClient,
     rubrics: Option<&RubricList>,
+    weights: &RubricWeights,
+    language: Language,
 ) -> Result<AIQuantitativeOutput> {
-    return match score_all_rubrics(qualitative_input, llm_client, rubrics).await {
+    return match score_all_rubrics(qualitative_input, llm_client, rubrics, weights, language).await
+    {
         Ok((rubric_scores, rubric_summary)) => Ok(AIQuantitativeOutput {
             rubric_scores,
             rubric_summary: Some(rubric_summary),
@@ -434,6 +532,8 @@ async fn score_all_rubrics(
     qualitative_input: &QualitativeInput,
     llm_client: &dyn LlmClient,
     rubrics: Option<&RubricList>,
+    weights: &RubricWeights,
+    language: Language,
 ) -> Result<(Vec<RubricScore>, RubricEvaluationSummary)> {
     // Use provided rubrics or load defaults
     let rubric_list = match rubrics {
@@ -447,7 +547,7 @@ async fn score_all_rubrics(
     // Score all rubrics sequentially (can't easily pass trait object to spawned tasks)
     let mut results = Vec::new();
     for rubric in &rubric_list.rubrics {
-        let result = score_rubric(rubric, &formatted_session, llm_client).await;
+        let result = score_rubric(rubric, &formatted_session, llm_client, language).await;
         results.push((rubric.clone(), result));
     }
 
@@ -465,27 +565,83 @@ async fn score_all_rubrics(
                     score: 3.0,
                     max_score: 5.0,
                     reasoning: format!("Scoring error: {}", e),
+                    rubric_weight: rubric.weight,
                 });
             }
         }
     }
 
-    // Calculate summary
-    let total_score: f64 = scores.iter().map(|s| s.score).sum();
-    let max_score: f64 = scores.iter().map(|s| s.max_score).sum();
-    let percentage = if max_score > 0.0 {
-        (total_score / max_score) * 100.0
-    } else {
-        0.0
-    };
-
-    let summary = RubricEvaluationSummary {
-        total_score,
-        max_score,
-        percentage,
-        rubrics_evaluated: scores.len(),
-        rubrics_version: rubric_list.version.clone(),
-    };
+    let summary =
+        RubricEvaluationSummary::from_scores(&scores, weights, rubric_list.version.clone());
 
     Ok((scores, summary))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rubric_score_response_accepts_json_and_legacy_format() {
+        let (score, reasoning) = parse_rubric_score_response(
+            "Here is my evaluation:\n```json\n{\"score\": 7, \"reasoning\": \"Clear asks.\"}\n```",
+        );
+        assert_eq!(score, Some(5.0));
+        assert_eq!(reasoning, "Clear asks.");
+
+        let (score, reasoning) = parse_rubric_score_response("SCORE: 2\nREASONING: Vague asks.");
+        assert_eq!(score, Some(2.0));
+        assert_eq!(reasoning, "Vague asks.");
+    }
+
+    #[test]
+    fn test_rubric_summary_applies_weights() {
+        let score = |id: &str, name: &str, score: f64, rubric_weight: f64| RubricScore {
+            rubric_id: id.to_string(),
+            rubric_name: name.to_string(),
+            score,
+            max_score: 5.0,
+            reasoning: String::new(),
+            rubric_weight,
+        };
+        let scores = vec![
+            score("rubric_001", "Correctness", 5.0, 1.0),
+            score("rubric_002", "Style", 1.0, 1.0),
+            score("rubric_003", "Testing", 3.0, 0.0),
+        ];
+
+        let equal = RubricEvaluationSummary::from_scores(
+            &scores[..2],
+            &RubricWeights::default(),
+            "1.0".to_string(),
+        );
+        assert!((equal.percentage - 60.0).abs() < 1e-9);
+        assert!(!equal.is_weighted());
+
+        // Configured by name and by ID; the definition's weight of 0 applies
+        // to the rubric left out of the config
+        let weights = RubricWeights::new(
+            [
+                ("correctness".to_string(), 2.0),
+                ("rubric_002".to_string(), 0.5),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        let weighted = RubricEvaluationSummary::from_scores(&scores, &weights, "1.0".to_string());
+        assert!((weighted.total_score - 10.5).abs() < 1e-9);
+        assert!((weighted.max_score - 12.5).abs() < 1e-9);
+        assert!((weighted.percentage - 84.0).abs() < 1e-9);
+        assert_eq!(weighted.weights["rubric_001"], 2.0);
+        assert_eq!(weighted.weights["rubric_003"], 0.0);
+        assert!(weighted.is_weighted());
+    }
+
+    #[test]
+    fn test_truncate_summary_counts_characters() {
+        let summary = "é".repeat(120);
+        let truncated = truncate_summary(&summary);
+        assert_eq!(truncated.chars().count(), MAX_SUMMARY_CHARS);
+        assert!(truncated.ends_with("..."));
+    }
+}
@@ -0,0 +1,322 @@
+//! Head-to-head comparison of two sessions
+//!
+//! Both transcripts (and session summaries, when generated) go to the LLM in
+//! one request, together with the measured metrics of each session so that
+//! efficiency is judged on numbers rather than impressions.
+
+use super::models::MetricQuantitativeOutput;
+use crate::services::llm::structured::parse_structured;
+use crate::services::llm::{GenerateRequest, LlmClient};
+use crate::utils::locale::Language;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// Characters of each transcript sent to the LLM; longer ones lose their middle
+const MAX_TRANSCRIPT_CHARS: usize = 60_000;
+
+/// What is known about one side of a comparison
+#[derive(Debug, Clone)]
+pub struct ComparisonInput {
+    pub title: String,
+    /// Session summary, if one was generated
+    pub summary: Option<String>,
+    /// Session transcript as JSON (see [`super::QualitativeInput`])
+    pub transcript: String,
+    pub metrics: MetricQuantitativeOutput,
+}
+
+/// Which session came out ahead
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComparisonWinner {
+    First,
+    Second,
+    Tie,
+}
+
+impl std::fmt::Display for ComparisonWinner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ComparisonWinner::First => write!(f, "first"),
+            ComparisonWinner::Second => write!(f, "second"),
+            ComparisonWinner::Tie => write!(f, "tie"),
+        }
+    }
+}
+
+/// One aspect compared across both sessions
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ComparisonDimension {
+    /// Observations about the first session
+    #[serde(default)]
+    pub first: Vec<String>,
+    /// Observations about the second session
+    #[serde(default)]
+    pub second: Vec<String>,
+    /// Which session handled this aspect better, and why
+    #[serde(default)]
+    pub verdict: String,
+}
+
+/// A measured number of both sessions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComparedMetric {
+    pub name: String,
+    pub first: f64,
+    pub second: f64,
+}
+
+/// Structured result of comparing two sessions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionComparisonOutput {
+    /// Two or three sentences on how the sessions differ
+    pub summary: String,
+    pub approach: ComparisonDimension,
+    pub efficiency: ComparisonDimension,
+    pub failure_points: ComparisonDimension,
+    pub winner: ComparisonWinner,
+    /// Metrics of both sessions, measured rather than generated
+    pub metrics: Vec<ComparedMetric>,
+}
+
+/// Structured response for a comparison
+#[derive(Debug, Deserialize)]
+struct ComparisonResponse {
+    summary: String,
+    #[serde(default)]
+    approach: ComparisonDimension,
+    #[serde(default)]
+    efficiency: ComparisonDimension,
+    #[serde(default)]
+    failure_points: ComparisonDimension,
+    winner: ComparisonWinner,
+}
+
+fn dimension_schema(description: &str) -> Value {
+    json!({
+        "type": "object",
+        "description": description,
+        "properties": {
+            "first": {
+                "type": "array",
+                "items": { "type": "string", "description": "One markdown line about the first session" }
+            },
+            "second": {
+                "type": "array",
+                "items": { "type": "string", "description": "One markdown line about the second session" }
+            },
+            "verdict": {
+                "type": "string",
+                "description": "One sentence: which session did better here, and why"
+            }
+        },
+        "required": ["first", "second", "verdict"]
+    })
+}
+
+fn comparison_response_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "summary": {
+                "type": "string",
+                "description": "2-3 sentences on how the two sessions differ"
+            },
+            "approach": dimension_schema("How each session went about the task"),
+            "efficiency": dimension_schema("Tokens, time and tool calls spent for what was achieved"),
+            "failure_points": dimension_schema("Where each session went wrong or got stuck"),
+            "winner": {
+                "type": "string",
+                "enum": ["first", "second", "tie"]
+            }
+        },
+        "required": ["summary", "approach", "efficiency", "failure_points", "winner"]
+    })
+}
+
+/// Metrics shown next to each other, in display order
+pub fn compared_metrics(
+    first: &MetricQuantitativeOutput,
+    second: &MetricQuantitativeOutput,
+) -> Vec<ComparedMetric> {
+    let metric = |name: &str, value: fn(&MetricQuantitativeOutput) -> f64| ComparedMetric {
+        name: name.to_string(),
+        first: value(first),
+        second: value(second),
+    };
+    vec![
+        metric("Total tokens", |m| m.token_metrics.total_tokens_used as f64),
+        metric("Session minutes", |m| {
+            m.time_metrics.total_session_time_minutes
+        }),
+        metric("Active minutes", |m| {
+            m.time_metrics
+                .active_time_minutes
+                .unwrap_or(m.time_metrics.total_session_time_minutes)
+        }),
+        metric("Tool calls", |m| m.tool_usage.total_operations as f64),
+        metric("Failed tool calls", |m| {
+            m.tool_usage.failed_operations as f64
+        }),
+        metric("Files modified", |m| {
+            m.file_changes.total_files_modified as f64
+        }),
+        metric("Lines added", |m| m.file_changes.lines_added as f64),
+        metric("Lines removed", |m| m.file_changes.lines_removed as f64),
+    ]
+}
+
+pub async fn generate_session_comparison_ai(
+    first: &ComparisonInput,
+    second: &ComparisonInput,
+    llm_client: &dyn LlmClient,
+    language: Language,
+) -> Result<SessionComparisonOutput> {
+    let metrics = compared_metrics(&first.metrics, &second.metrics);
+    let prompt = language.localize_prompt(build_comparison_prompt(first, second, &metrics));
+
+    let request = GenerateRequest::new(prompt)
+        .with_max_tokens(2048)
+        .with_temperature(0.4)
+        .with_response_schema(comparison_response_schema());
+
+    let response = llm_client
+        .generate(request)
+        .await
+        .map_err(|e| anyhow::anyhow!("LLM generation failed: {e}"))?;
+    let parsed = parse_structured::<ComparisonResponse>(&response.text)
+        .ok_or_else(|| anyhow::anyhow!("Could not parse the comparison response"))?;
+
+    Ok(SessionComparisonOutput {
+        summary: parsed.summary.trim().to_string(),
+        approach: parsed.approach,
+        efficiency: parsed.efficiency,
+        failure_points: parsed.failure_points,
+        winner: parsed.winner,
+        metrics,
+    })
+}
+
+fn build_comparison_prompt(
+    first: &ComparisonInput,
+    second: &ComparisonInput,
+    metrics: &[ComparedMetric],
+) -> String {
+    let metric_table = metrics
+        .iter()
+        .map(|metric| {
+            format!(
+                "| {} | {} | {} |",
+                metric.name,
+                format_metric(metric.first),
+                format_metric(metric.second)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!(
+        r#"Compare the following two development sessions between a user and an AI coding assistant.
+
+## Measured Metrics
+
+| Metric | First session | Second session |
+|---|---|---|
+{metric_table}
+
+{first_section}
+
+{second_section}
+
+## Task
+
+Compare the sessions head to head on three aspects:
+
+- "approach": how each session went about the task (planning, exploration, order of changes, use of tools)
+- "efficiency": what each session achieved for the tokens, time and tool calls it spent; use the measured metrics
+- "failure_points": where each session went wrong, got stuck or needed correction
+
+For each aspect, list specific observations about the first and the second session and give a one-sentence verdict.
+Then pick the session that went better overall as "winner" ("first", "second" or "tie").
+
+## Required Output Format
+
+Respond with a JSON object with these fields:
+
+- "summary": 2-3 sentences on how the sessions differ
+- "approach", "efficiency", "failure_points": objects with "first" (list of markdown lines), "second" (list of markdown lines) and "verdict" (one sentence)
+- "winner": "first", "second" or "tie"
+
+Important:
+- Return only the JSON object, with no text before or after it.
+- Each observation must be a single line of markdown text backed by evidence from the transcript."#,
+        first_section = session_section("First Session", first),
+        second_section = session_section("Second Session", second),
+    )
+}
+
+/// Whole numbers as they are, others to one decimal
+fn format_metric(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{value}")
+    } else {
+        format!("{value:.1}")
+    }
+}
+
+fn session_section(heading: &str, input: &ComparisonInput) -> String {
+    let summary = input
+        .summary
+        .as_deref()
+        .map(|summary| format!("Summary: {summary}\n\n"))
+        .unwrap_or_default();
+    format!(
+        "## {heading}: {title}\n\n{summary}```json\n{transcript}\n```",
+        title = input.title,
+        transcript = shorten_transcript(&input.transcript, MAX_TRANSCRIPT_CHARS),
+    )
+}
+
+/// Keep the start and end of a long transcript, noting how much was left out
+fn shorten_transcript(transcript: &str, max_chars: usize) -> String {
+    let total = transcript.chars().count();
+    if total <= max_chars {
+        return transcript.to_string();
+    }
+    let half = max_chars / 2;
+    let head: String = transcript.chars().take(half).collect();
+    let tail: String = transcript.chars().skip(total - half).collect();
+    format!(
+        "{head}\n... [{} characters omitted] ...\n{tail}",
+        total - 2 * half
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shorten_transcript_keeps_both_ends() {
+        assert_eq!(shorten_transcript("short", 10), "short");
+
+        let shortened = shorten_transcript("aaaaébbbbbcccc", 8);
+        assert_eq!(shortened, "aaaa\n... [6 characters omitted] ...\ncccc");
+    }
+
+    #[test]
+    fn test_parse_comparison_response() {
+        let text = r#"{
+            "summary": "The second session planned first and finished sooner.",
+            "approach": {"first": ["**Trial and error**: edited before reading"], "second": [], "verdict": "Second read the code first."},
+            "efficiency": {"first": [], "second": [], "verdict": "Second used half the tokens."},
+            "failure_points": {"first": ["**Broken build**: left a type error"], "second": [], "verdict": "First broke the build."},
+            "winner": "second"
+        }"#;
+        let parsed = parse_structured::<ComparisonResponse>(text).unwrap();
+        assert_eq!(parsed.winner, ComparisonWinner::Second);
+        assert_eq!(parsed.approach.first.len(), 1);
+        assert_eq!(parsed.failure_points.verdict, "First broke the build.");
+    }
+}
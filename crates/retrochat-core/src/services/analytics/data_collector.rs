@@ -1,10 +1,11 @@
+use super::impact::build_impact_report;
 use super::metrics::{
     calculate_file_change_metrics, calculate_time_consumption_metrics,
-    calculate_token_consumption_metrics, calculate_tool_usage_metrics,
+    calculate_token_consumption_metrics, calculate_tool_usage_metrics, calculate_turn_metrics,
 };
 use super::models::{MetricQuantitativeOutput, QualitativeInput, SessionTranscript, SessionTurn};
 use crate::models::message::MessageType;
-use crate::models::{ChatSession, Message, MessageRole, ToolOperation};
+use crate::models::{ChatSession, Message, MessageRole, MessageUsage, ToolOperation};
 use anyhow::Result;
 use std::collections::HashMap;
 use uuid::Uuid;
@@ -23,10 +24,12 @@ pub async fn collect_quantitative_data(
     session: &ChatSession,
     messages: &[Message],
     tool_operations: &[ToolOperation],
+    usage: &[MessageUsage],
+    idle_gap: chrono::Duration,
 ) -> Result<MetricQuantitativeOutput> {
     let file_changes = calculate_file_change_metrics(tool_operations);
-    let time_metrics = calculate_time_consumption_metrics(session, messages);
-    let token_metrics = calculate_token_consumption_metrics(messages);
+    let time_metrics = calculate_time_consumption_metrics(session, messages, idle_gap);
+    let token_metrics = calculate_token_consumption_metrics(messages, usage);
     let tool_usage = calculate_tool_usage_metrics(tool_operations);
 
     Ok(MetricQuantitativeOutput {
@@ -34,6 +37,9 @@ pub async fn collect_quantitative_data(
         time_metrics,
         token_metrics,
         tool_usage,
+        rework_rate: 0.0,
+        turns: calculate_turn_metrics(messages, tool_operations),
+        impact: build_impact_report(tool_operations),
     })
 }
 
@@ -144,6 +150,8 @@ fn get_message_type_string(
         }
         MessageType::Thinking => "thinking".to_string(),
         MessageType::SlashCommand => "slash_command".to_string(),
+        MessageType::HookEvent => "hook_event".to_string(),
+        MessageType::SystemReminder => "system_reminder".to_string(),
         MessageType::SimpleMessage => "simple_message".to_string(),
     }
 }
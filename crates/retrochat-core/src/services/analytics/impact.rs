@@ -0,0 +1,320 @@
+//! Repository-aware impact report
+//!
+//! Files a session edited, grouped by the git repository they belong to,
+//! with net line deltas. A file's repository is the nearest ancestor with a
+//! `.git` entry on this machine, or else a directory the session ran git
+//! commands in (`cd <dir> && git ...`, `git -C <dir> ...`).
+//!
+//! With `analysis.git_correlation` enabled, [`correlate_with_git`] also
+//! checks each file against the repository: a change survived when a
+//! commit made after the session started touched the file and the text the
+//! session last wrote is still in `HEAD`.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+use crate::models::ToolOperation;
+
+/// Tools whose calls change files
+const EDIT_TOOLS: &[&str] = &["Edit", "MultiEdit", "Write"];
+
+/// Files a session changed, by repository
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ImpactReport {
+    /// Most lines changed first; files outside any known repository come last
+    pub repositories: Vec<RepositoryImpact>,
+    /// Whether `survived` was checked against git
+    #[serde(default)]
+    pub git_correlated: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RepositoryImpact {
+    /// Repository root; `None` for files outside any known repository
+    pub root: Option<String>,
+    pub lines_added: u64,
+    pub lines_removed: u64,
+    pub net_lines: i64,
+    /// Most lines changed first
+    pub files: Vec<FileImpact>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileImpact {
+    /// Relative to the repository root when there is one
+    pub path: String,
+    pub edits: u32,
+    pub lines_added: u64,
+    pub lines_removed: u64,
+    pub net_lines: i64,
+    /// Whether the change made it into a later commit; `None` unless git
+    /// correlation ran and could read the repository
+    #[serde(default)]
+    pub survived: Option<bool>,
+}
+
+impl ImpactReport {
+    pub fn is_empty(&self) -> bool {
+        self.repositories.is_empty()
+    }
+}
+
+/// Group the files `tool_operations` edited by repository
+pub fn build_impact_report(tool_operations: &[ToolOperation]) -> ImpactReport {
+    build_with(tool_operations, |dir| dir.join(".git").exists())
+}
+
+fn build_with(
+    tool_operations: &[ToolOperation],
+    is_repository: impl Fn(&Path) -> bool,
+) -> ImpactReport {
+    let command_roots = command_roots(tool_operations);
+    let mut checked: HashMap<PathBuf, Option<PathBuf>> = HashMap::new();
+    let mut root_of = |file: &Path| -> Option<PathBuf> {
+        let on_disk = file.ancestors().skip(1).find_map(|dir| {
+            checked
+                .entry(dir.to_path_buf())
+                .or_insert_with(|| is_repository(dir).then(|| dir.to_path_buf()))
+                .clone()
+        });
+        on_disk.or_else(|| {
+            command_roots
+                .iter()
+                .filter(|root| file.starts_with(root))
+                .max_by_key(|root| root.as_os_str().len())
+                .cloned()
+        })
+    };
+
+    // Operations can be linked from both their request and result messages
+    let mut seen = HashSet::new();
+    let mut by_root: BTreeMap<Option<PathBuf>, BTreeMap<String, FileImpact>> = BTreeMap::new();
+    for op in tool_operations.iter().filter(|op| seen.insert(op.id)) {
+        if !EDIT_TOOLS.contains(&op.tool_name.as_str()) {
+            continue;
+        }
+        let Some(metadata) = &op.file_metadata else {
+            continue;
+        };
+        let file = Path::new(&metadata.file_path);
+        let root = root_of(file);
+        let path = match root
+            .as_deref()
+            .and_then(|root| file.strip_prefix(root).ok())
+        {
+            Some(relative) => relative.to_string_lossy().to_string(),
+            None => metadata.file_path.clone(),
+        };
+
+        let impact = by_root
+            .entry(root)
+            .or_default()
+            .entry(path.clone())
+            .or_insert(FileImpact {
+                path,
+                edits: 0,
+                lines_added: 0,
+                lines_removed: 0,
+                net_lines: 0,
+                survived: None,
+            });
+        impact.edits += 1;
+        impact.lines_added += metadata.lines_added.unwrap_or(0).max(0) as u64;
+        impact.lines_removed += metadata.lines_removed.unwrap_or(0).max(0) as u64;
+        impact.net_lines = impact.lines_added as i64 - impact.lines_removed as i64;
+    }
+
+    let mut repositories: Vec<RepositoryImpact> = by_root
+        .into_iter()
+        .map(|(root, files)| {
+            let mut files: Vec<FileImpact> = files.into_values().collect();
+            files.sort_by_key(|file| std::cmp::Reverse(file.lines_added + file.lines_removed));
+            let lines_added = files.iter().map(|file| file.lines_added).sum();
+            let lines_removed = files.iter().map(|file| file.lines_removed).sum();
+            RepositoryImpact {
+                root: root.map(|root| root.to_string_lossy().to_string()),
+                lines_added,
+                lines_removed,
+                net_lines: lines_added as i64 - lines_removed as i64,
+                files,
+            }
+        })
+        .collect();
+    repositories.sort_by_key(|repository| {
+        (
+            repository.root.is_none(),
+            std::cmp::Reverse(repository.lines_added + repository.lines_removed),
+        )
+    });
+
+    ImpactReport {
+        repositories,
+        git_correlated: false,
+    }
+}
+
+/// Directories the session ran git commands in
+fn command_roots(tool_operations: &[ToolOperation]) -> Vec<PathBuf> {
+    let mut roots: Vec<PathBuf> = Vec::new();
+    for op in tool_operations {
+        let Some(command) = op.bash_metadata.as_ref().map(|bash| bash.command.as_str()) else {
+            continue;
+        };
+        let words: Vec<&str> = command.split_whitespace().collect();
+        for (i, pair) in words.windows(2).enumerate() {
+            let dir = match pair {
+                ["git", "-C"] => words.get(i + 2).copied(),
+                ["cd", dir] if command.contains("git ") => Some(*dir),
+                _ => None,
+            };
+            if let Some(dir) = dir.map(|dir| dir.trim_matches(['"', '\'', ';', '&'])) {
+                let dir = PathBuf::from(dir);
+                if dir.is_absolute() && !roots.contains(&dir) {
+                    roots.push(dir);
+                }
+            }
+        }
+    }
+    roots
+}
+
+/// Fill in `survived` for files in repositories that exist on this machine
+pub async fn correlate_with_git(
+    report: &mut ImpactReport,
+    tool_operations: &[ToolOperation],
+    since: DateTime<Utc>,
+) {
+    let written = last_written_text(tool_operations);
+    for repository in &mut report.repositories {
+        let Some(root) = &repository.root else {
+            continue;
+        };
+        if !Path::new(root).join(".git").exists() {
+            continue;
+        }
+        for file in &mut repository.files {
+            let absolute = Path::new(root).join(&file.path);
+            let text = written.get(absolute.to_string_lossy().as_ref());
+            file.survived = file_survived(root, &file.path, since, text.map(String::as_str)).await;
+        }
+    }
+    report.git_correlated = true;
+}
+
+async fn file_survived(
+    root: &str,
+    path: &str,
+    since: DateTime<Utc>,
+    text: Option<&str>,
+) -> Option<bool> {
+    let since = format!("--since={}", since.to_rfc3339());
+    let commits = git(root, &["log", "-1", "--format=%H", &since, "--", path]).await?;
+    if commits.trim().is_empty() {
+        return Some(false);
+    }
+    let Some(text) = text.filter(|text| !text.trim().is_empty()) else {
+        return Some(true);
+    };
+    // A file deleted since has no content at HEAD
+    let head = git(root, &["show", &format!("HEAD:{path}")])
+        .await
+        .unwrap_or_default();
+    Some(head.contains(text))
+}
+
+/// Stdout of a git command, or `None` if it could not run or failed
+async fn git(root: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(args)
+        .output()
+        .await
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Text each file was last given by an edit: the replacement of an edit,
+/// or the content of a write
+fn last_written_text(tool_operations: &[ToolOperation]) -> HashMap<String, String> {
+    let mut written = HashMap::new();
+    for op in tool_operations {
+        let (Some(metadata), Some(input)) = (&op.file_metadata, &op.raw_input) else {
+            continue;
+        };
+        let text = input
+            .get("new_string")
+            .or_else(|| input.get("content"))
+            .or_else(|| {
+                input
+                    .get("edits")
+                    .and_then(Value::as_array)
+                    .and_then(|edits| edits.last())
+                    .and_then(|edit| edit.get("new_string"))
+            })
+            .and_then(Value::as_str);
+        if let Some(text) = text {
+            written.insert(metadata.file_path.clone(), text.to_string());
+        }
+    }
+    written
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::bash_metadata::BashMetadata;
+
+    fn edit(path: &str, added: i32, removed: i32) -> ToolOperation {
+        let mut op = ToolOperation::new("t".to_string(), "Edit".to_string(), Utc::now())
+            .with_file_path(path.to_string());
+        let metadata = op.file_metadata.as_mut().unwrap();
+        metadata.lines_added = Some(added);
+        metadata.lines_removed = Some(removed);
+        op
+    }
+
+    #[test]
+    fn test_group_edits_by_repository() {
+        let git_command = ToolOperation::new("b".to_string(), "Bash".to_string(), Utc::now())
+            .with_bash_metadata(BashMetadata::new(
+                "BashCommand".to_string(),
+                "cd /work/api && git status".to_string(),
+            ));
+        let operations = vec![
+            edit("/work/app/src/main.rs", 10, 2),
+            edit("/work/app/src/main.rs", 1, 1),
+            edit("/work/app/README.md", 3, 0),
+            edit("/work/api/server.go", 1, 0),
+            edit("/tmp/scratch.txt", 1, 0),
+            ToolOperation::new("r".to_string(), "Read".to_string(), Utc::now())
+                .with_file_path("/work/app/Cargo.toml".to_string()),
+            git_command,
+        ];
+
+        let report = build_with(&operations, |dir| dir == Path::new("/work/app"));
+        let roots: Vec<Option<&str>> = report
+            .repositories
+            .iter()
+            .map(|repository| repository.root.as_deref())
+            .collect();
+        assert_eq!(roots, [Some("/work/app"), Some("/work/api"), None]);
+
+        let app = &report.repositories[0];
+        assert_eq!(
+            (app.lines_added, app.lines_removed, app.net_lines),
+            (14, 3, 11)
+        );
+        assert_eq!(app.files[0].path, "src/main.rs");
+        assert_eq!(app.files[0].edits, 2);
+        assert_eq!(app.files[0].survived, None);
+        assert_eq!(report.repositories[2].files[0].path, "/tmp/scratch.txt");
+    }
+}
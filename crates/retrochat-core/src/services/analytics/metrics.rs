@@ -1,10 +1,16 @@
-use chrono::Timelike;
-use std::collections::HashMap;
+use chrono::{Duration, Timelike};
+use std::collections::{BTreeSet, HashMap, HashSet};
 
 use super::models::{
-    FileChangeMetrics, TimeConsumptionMetrics, TokenConsumptionMetrics, ToolUsageMetrics,
+    FileChangeMetrics, LanguageChangeMetrics, TimeConsumptionMetrics, TokenConsumptionMetrics,
+    ToolUsageMetrics, TurnUsageMetrics,
 };
-use crate::models::{ChatSession, Message, MessageRole, ToolOperation};
+use super::thrash::detect_thrash_segments;
+use crate::models::session_segment::split_at_compactions;
+use crate::models::{ChatSession, Message, MessageRole, MessageUsage, ToolOperation};
+use crate::services::turn_detection::TurnDetector;
+use crate::utils::language::language_for_path;
+use crate::utils::session_title::{title_from_messages, truncate_title};
 
 // =============================================================================
 // File Change Metrics Calculation
@@ -46,16 +52,61 @@ pub fn calculate_file_change_metrics(tool_operations: &[ToolOperation]) -> FileC
         lines_added,
         lines_removed,
         net_code_growth,
+        languages: calculate_language_metrics(tool_operations),
     }
 }
 
+/// Files touched and lines changed per language, most lines changed first
+pub fn calculate_language_metrics(tool_operations: &[ToolOperation]) -> Vec<LanguageChangeMetrics> {
+    // Operations can be linked from both their request and result messages
+    let mut seen = HashSet::new();
+    let mut files: HashMap<&str, HashSet<&str>> = HashMap::new();
+    let mut by_language: HashMap<&str, LanguageChangeMetrics> = HashMap::new();
+
+    for op in tool_operations.iter().filter(|op| seen.insert(op.id)) {
+        let Some(metadata) = &op.file_metadata else {
+            continue;
+        };
+        let language = language_for_path(&metadata.file_path);
+        let entry = by_language
+            .entry(language)
+            .or_insert_with(|| LanguageChangeMetrics {
+                language: language.to_string(),
+                files_touched: 0,
+                lines_added: 0,
+                lines_removed: 0,
+            });
+        entry.lines_added += metadata.lines_added.unwrap_or(0).max(0) as u64;
+        entry.lines_removed += metadata.lines_removed.unwrap_or(0).max(0) as u64;
+        if files
+            .entry(language)
+            .or_default()
+            .insert(&metadata.file_path)
+        {
+            entry.files_touched += 1;
+        }
+    }
+
+    let mut languages: Vec<LanguageChangeMetrics> = by_language.into_values().collect();
+    languages.sort_by(|a, b| {
+        (b.lines_added + b.lines_removed)
+            .cmp(&(a.lines_added + a.lines_removed))
+            .then(b.files_touched.cmp(&a.files_touched))
+            .then(a.language.cmp(&b.language))
+    });
+    languages
+}
+
 // =============================================================================
 // Time Consumption Metrics Calculation
 // =============================================================================
 
+/// Time metrics for a session; silences between messages longer than
+/// `idle_gap` count toward wall time but not active time
 pub fn calculate_time_consumption_metrics(
     session: &ChatSession,
     messages: &[Message],
+    idle_gap: Duration,
 ) -> TimeConsumptionMetrics {
     let session_duration =
         if let (Some(end_time), Some(start_time)) = (session.end_time, Some(session.start_time)) {
@@ -81,9 +132,35 @@ pub fn calculate_time_consumption_metrics(
         }
     }
 
+    let mut timestamps: Vec<_> = messages.iter().map(|m| m.timestamp).collect();
+    timestamps.sort();
+    let mut active = Duration::zero();
+    let mut idle_gaps = 0u32;
+    for pair in timestamps.windows(2) {
+        let gap = pair[1] - pair[0];
+        if gap > idle_gap {
+            idle_gaps += 1;
+        } else {
+            active += gap;
+        }
+    }
+
+    let segments = split_at_compactions(messages);
+    let segment_minutes = segments
+        .iter()
+        .map(|segment| {
+            let (first, last) = (&segment[0], &segment[segment.len() - 1]);
+            (last.timestamp - first.timestamp).num_seconds() as f64 / 60.0
+        })
+        .collect();
+
     TimeConsumptionMetrics {
         total_session_time_minutes: session_duration,
+        active_time_minutes: Some(active.num_seconds() as f64 / 60.0),
+        idle_gaps,
         peak_hours,
+        compactions: segments.len().saturating_sub(1) as u32,
+        segment_minutes,
     }
 }
 
@@ -91,25 +168,53 @@ pub fn calculate_time_consumption_metrics(
 // Token Consumption Metrics Calculation
 // =============================================================================
 
-pub fn calculate_token_consumption_metrics(messages: &[Message]) -> TokenConsumptionMetrics {
+pub fn calculate_token_consumption_metrics(
+    messages: &[Message],
+    usage: &[MessageUsage],
+) -> TokenConsumptionMetrics {
     let mut total_tokens_used = 0u64;
     let mut input_tokens = 0u64;
     let mut output_tokens = 0u64;
+    let mut segment_token_efficiency = Vec::new();
+    let mut peak_context_tokens = 0u64;
 
-    for message in messages {
-        if let Some(tokens) = message.token_count {
-            total_tokens_used += tokens as u64;
+    // A compaction starts a fresh context, so each segment is measured alone
+    for segment in split_at_compactions(messages) {
+        let mut segment_total = 0u64;
+        let mut segment_output = 0u64;
+        for message in segment {
+            if let Some(tokens) = message.tokens() {
+                segment_total += tokens as u64;
 
-            match message.role {
-                MessageRole::User => input_tokens += tokens as u64,
-                MessageRole::Assistant => output_tokens += tokens as u64,
-                MessageRole::System => input_tokens += tokens as u64,
+                match message.role {
+                    MessageRole::User => input_tokens += tokens as u64,
+                    MessageRole::Assistant => {
+                        output_tokens += tokens as u64;
+                        segment_output += tokens as u64;
+                    }
+                    MessageRole::System => input_tokens += tokens as u64,
+                }
             }
         }
+        total_tokens_used += segment_total;
+        peak_context_tokens = peak_context_tokens.max(segment_total);
+        if segment_total > 0 {
+            segment_token_efficiency.push(segment_output as f64 / segment_total as f64);
+        }
     }
 
-    let token_efficiency = if total_tokens_used > 0 {
-        output_tokens as f64 / total_tokens_used as f64
+    let token_efficiency = if segment_token_efficiency.is_empty() {
+        0.0
+    } else {
+        segment_token_efficiency.iter().sum::<f64>() / segment_token_efficiency.len() as f64
+    };
+
+    let cache_creation_input_tokens: u64 =
+        usage.iter().map(|u| u.cache_creation_input_tokens).sum();
+    let cache_read_input_tokens: u64 = usage.iter().map(|u| u.cache_read_input_tokens).sum();
+    let prompt_tokens: u64 = usage.iter().map(MessageUsage::prompt_tokens).sum();
+    let cache_hit_ratio = if prompt_tokens > 0 {
+        cache_read_input_tokens as f64 / prompt_tokens as f64
     } else {
         0.0
     };
@@ -119,6 +224,11 @@ pub fn calculate_token_consumption_metrics(messages: &[Message]) -> TokenConsump
         input_tokens,
         output_tokens,
         token_efficiency,
+        segment_token_efficiency,
+        peak_context_tokens,
+        cache_creation_input_tokens,
+        cache_read_input_tokens,
+        cache_hit_ratio,
     }
 }
 
@@ -142,7 +252,20 @@ pub fn calculate_tool_usage_metrics(tool_operations: &[ToolOperation]) -> ToolUs
     }
 
     let total_operations = tool_operations.len() as u64;
-    let average_execution_time_ms = 0.0; // TODO: Calculate from execution times
+
+    // Operations can be linked from both their request and result messages
+    let mut seen = HashSet::new();
+    let mut durations: Vec<i64> = tool_operations
+        .iter()
+        .filter(|op| seen.insert(op.id))
+        .filter_map(|op| op.duration_ms)
+        .collect();
+    durations.sort_unstable();
+    let average_execution_time_ms = if durations.is_empty() {
+        0.0
+    } else {
+        durations.iter().sum::<i64>() as f64 / durations.len() as f64
+    };
 
     ToolUsageMetrics {
         total_operations,
@@ -150,5 +273,254 @@ pub fn calculate_tool_usage_metrics(tool_operations: &[ToolOperation]) -> ToolUs
         failed_operations,
         tool_distribution,
         average_execution_time_ms,
+        timed_operations: durations.len() as u64,
+        p50_execution_time_ms: percentile(&durations, 50),
+        p95_execution_time_ms: percentile(&durations, 95),
+        thrash_segments: detect_thrash_segments(tool_operations),
+    }
+}
+
+/// Nearest-rank percentile of sorted durations; 0 when there are none
+fn percentile(sorted: &[i64], p: usize) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = (p * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1] as f64
+}
+
+// =============================================================================
+// Per-Turn Metrics Calculation
+// =============================================================================
+
+/// Characters of the opening request kept in a turn's preview
+const TURN_PROMPT_PREVIEW_CHARS: usize = 60;
+
+/// Tokens, tool calls, duration and files of each turn, so the costly
+/// requests in a session stand out
+pub fn calculate_turn_metrics(
+    messages: &[Message],
+    tool_operations: &[ToolOperation],
+) -> Vec<TurnUsageMetrics> {
+    let operations: HashMap<_, _> = tool_operations.iter().map(|op| (op.id, op)).collect();
+
+    TurnDetector::detect_turns_from_messages(messages)
+        .into_iter()
+        .map(|turn| {
+            // Messages arrive in sequence order, so a turn is a contiguous run
+            let sequence = |m: &Message| m.sequence_number as i32;
+            let start = messages.partition_point(|m| sequence(m) < turn.start_sequence);
+            let end = messages.partition_point(|m| sequence(m) <= turn.end_sequence);
+            let turn_messages = &messages[start..end.max(start)];
+
+            let prompt_preview = title_from_messages(turn_messages)
+                .map(|title| truncate_title(&title, TURN_PROMPT_PREVIEW_CHARS))
+                .unwrap_or_default();
+
+            let total_tokens = turn_messages
+                .iter()
+                .filter_map(|m| m.tokens())
+                .map(|t| t as u64)
+                .sum();
+
+            let operation_ids: HashSet<_> = turn_messages
+                .iter()
+                .filter_map(|m| m.tool_operation_id)
+                .collect();
+            let files_touched: BTreeSet<String> = operation_ids
+                .iter()
+                .filter_map(|id| operations.get(id))
+                .filter_map(|op| op.file_metadata.as_ref())
+                .map(|metadata| metadata.file_path.clone())
+                .collect();
+
+            TurnUsageMetrics {
+                turn_number: turn.turn_number.max(0) as u32,
+                prompt_preview,
+                total_tokens,
+                tool_calls: operation_ids.len() as u64,
+                duration_minutes: (turn.ended_at - turn.started_at).num_seconds() as f64 / 60.0,
+                files_touched: files_touched.into_iter().collect(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_hit_ratio() {
+        let usage = |input, creation, read| MessageUsage {
+            input_tokens: input,
+            cache_creation_input_tokens: creation,
+            cache_read_input_tokens: read,
+            ..Default::default()
+        };
+        let metrics =
+            calculate_token_consumption_metrics(&[], &[usage(10, 290, 0), usage(20, 80, 600)]);
+
+        assert_eq!(metrics.cache_creation_input_tokens, 370);
+        assert_eq!(metrics.cache_read_input_tokens, 600);
+        assert!((metrics.cache_hit_ratio - 0.6).abs() < f64::EPSILON);
+
+        assert_eq!(
+            calculate_token_consumption_metrics(&[], &[]).cache_hit_ratio,
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_token_efficiency_per_segment() {
+        let session_id = uuid::Uuid::new_v4();
+        let now = chrono::Utc::now();
+        let message = |role, tokens, sequence| {
+            Message::new(session_id, role, String::new(), now, sequence).with_token_count(tokens)
+        };
+        let mut messages = vec![
+            message(MessageRole::User, 100, 1),
+            message(MessageRole::Assistant, 100, 2),
+            message(MessageRole::User, 10, 3),
+            message(MessageRole::Assistant, 30, 4),
+        ];
+        messages[2].insert_metadata(
+            crate::models::session_segment::COMPACTION_METADATA_KEY,
+            serde_json::json!({ "trigger": "manual" }),
+        );
+
+        let metrics = calculate_token_consumption_metrics(&messages, &[]);
+        assert_eq!(metrics.total_tokens_used, 240);
+        assert_eq!(metrics.peak_context_tokens, 200);
+        assert_eq!(metrics.segment_token_efficiency, vec![0.5, 0.75]);
+        assert!((metrics.token_efficiency - 0.625).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_idle_gaps_excluded_from_active_time() {
+        let start = chrono::Utc::now();
+        let mut session = ChatSession::new(
+            crate::models::Provider::ClaudeCode,
+            String::new(),
+            String::new(),
+            start,
+        );
+        // Two bursts of work with an overnight gap between them
+        let offsets = [0, 10, 25, 25 + 12 * 60, 25 + 12 * 60 + 20];
+        let messages: Vec<Message> = offsets
+            .iter()
+            .enumerate()
+            .map(|(i, minutes)| {
+                Message::new(
+                    session.id,
+                    MessageRole::User,
+                    String::new(),
+                    start + Duration::minutes(*minutes),
+                    i as u32 + 1,
+                )
+            })
+            .collect();
+        session.end_time = Some(messages[4].timestamp);
+
+        let metrics =
+            calculate_time_consumption_metrics(&session, &messages, Duration::minutes(30));
+        assert_eq!(metrics.total_session_time_minutes, 765.0);
+        assert_eq!(metrics.active_time_minutes, Some(45.0));
+        assert_eq!(metrics.idle_gaps, 1);
+
+        let metrics = calculate_time_consumption_metrics(&session, &messages, Duration::minutes(5));
+        assert_eq!(metrics.active_time_minutes, Some(0.0));
+        assert_eq!(metrics.idle_gaps, 4);
+    }
+
+    #[test]
+    fn test_turn_metrics() {
+        let session_id = uuid::Uuid::new_v4();
+        let start = chrono::Utc::now();
+        let edit = ToolOperation::new("t1".to_string(), "Edit".to_string(), start)
+            .with_file_path("src/lib.rs".to_string());
+        let message = |role, content: &str, minutes, sequence| {
+            Message::new(
+                session_id,
+                role,
+                content.to_string(),
+                start + Duration::minutes(minutes),
+                sequence,
+            )
+        };
+        let messages = vec![
+            message(MessageRole::User, "Fix the parser\nplease", 0, 1).with_token_count(10),
+            message(MessageRole::Assistant, "", 1, 2)
+                .with_message_type(crate::models::message::MessageType::ToolRequest)
+                .with_tool_operation(edit.id)
+                .with_token_count(500),
+            message(MessageRole::Assistant, "Done", 4, 3).with_token_count(40),
+            message(MessageRole::User, "Thanks", 10, 4).with_token_count(5),
+        ];
+
+        let turns = calculate_turn_metrics(&messages, &[edit]);
+        assert_eq!(turns.len(), 2);
+        assert_eq!(turns[0].prompt_preview, "Fix the parser");
+        assert_eq!(turns[0].total_tokens, 550);
+        assert_eq!(turns[0].tool_calls, 1);
+        assert_eq!(turns[0].duration_minutes, 4.0);
+        assert_eq!(turns[0].files_touched, vec!["src/lib.rs"]);
+        assert_eq!(turns[1].total_tokens, 5);
+        assert_eq!(turns[1].tool_calls, 0);
+    }
+
+    #[test]
+    fn test_language_breakdown() {
+        let edit = |path: &str, added, removed| {
+            let mut op =
+                ToolOperation::new("t".to_string(), "Edit".to_string(), chrono::Utc::now())
+                    .with_file_path(path.to_string());
+            let metadata = op.file_metadata.as_mut().unwrap();
+            metadata.lines_added = Some(added);
+            metadata.lines_removed = Some(removed);
+            op
+        };
+        let twice = edit("src/lib.rs", 5, 1);
+        let operations = vec![
+            edit("src/main.rs", 10, 2),
+            twice.clone(),
+            twice,
+            edit("web/app.tsx", 3, 0),
+            edit("README", 1, 0),
+        ];
+
+        let languages = calculate_language_metrics(&operations);
+        let names: Vec<&str> = languages.iter().map(|l| l.language.as_str()).collect();
+        assert_eq!(names, ["Rust", "TypeScript", "Other"]);
+        assert_eq!(
+            languages[0],
+            LanguageChangeMetrics {
+                language: "Rust".to_string(),
+                files_touched: 2,
+                lines_added: 15,
+                lines_removed: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn test_tool_latency_percentiles() {
+        let call = |duration_ms: Option<i64>| {
+            let mut op =
+                ToolOperation::new("t".to_string(), "Bash".to_string(), chrono::Utc::now());
+            op.duration_ms = duration_ms;
+            op
+        };
+        let mut operations: Vec<ToolOperation> = (1..=19).map(|i| call(Some(i * 100))).collect();
+        operations.push(call(Some(10_000)));
+        operations.push(operations[0].clone());
+        operations.push(call(None));
+
+        let metrics = calculate_tool_usage_metrics(&operations);
+        assert_eq!(metrics.timed_operations, 20);
+        assert_eq!(metrics.average_execution_time_ms, 1_450.0);
+        assert_eq!(metrics.p50_execution_time_ms, 1_000.0);
+        assert_eq!(metrics.p95_execution_time_ms, 1_900.0);
+        assert_eq!(percentile(&[], 95), 0.0);
     }
 }
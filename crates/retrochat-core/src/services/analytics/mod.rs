@@ -1,10 +1,16 @@
 pub mod ai_analysis;
+pub mod comparison;
 pub mod data_collector;
+pub mod impact;
 pub mod metrics;
 pub mod models;
+pub mod thrash;
 
 // Re-export commonly used types
 pub use ai_analysis::*;
+pub use comparison::*;
 pub use data_collector::*;
+pub use impact::*;
 pub use metrics::*;
 pub use models::*;
+pub use thrash::*;
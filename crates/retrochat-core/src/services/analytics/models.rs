@@ -1,7 +1,12 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 
+use crate::config::Config;
+
+use super::impact::ImpactReport;
+use super::thrash::ThrashSegment;
+
 // =============================================================================
 // Rubric Models (for LLM-as-a-judge evaluation)
 // =============================================================================
@@ -152,6 +157,9 @@ pub struct RubricScore {
     pub max_score: f64,
     /// LLM's reasoning for the score
     pub reasoning: String,
+    /// Weight from the rubric definition, before `analysis.rubric_weights`
+    #[serde(default = "default_weight")]
+    pub rubric_weight: f64,
 }
 
 impl RubricScore {
@@ -166,11 +174,11 @@ impl RubricScore {
 }
 
 /// Summary of all rubric evaluations
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct RubricEvaluationSummary {
-    /// Total score across all rubrics
+    /// Weighted total score across all rubrics
     pub total_score: f64,
-    /// Maximum possible score
+    /// Weighted maximum possible score
     pub max_score: f64,
     /// Percentage (0-100)
     pub percentage: f64,
@@ -178,6 +186,98 @@ pub struct RubricEvaluationSummary {
     pub rubrics_evaluated: usize,
     /// Version of rubrics used
     pub rubrics_version: String,
+    /// Weight applied to each rubric, by rubric ID; empty for results scored
+    /// before weights were recorded, which weighed every rubric equally
+    #[serde(default)]
+    pub weights: BTreeMap<String, f64>,
+}
+
+impl RubricEvaluationSummary {
+    /// Sum scores with each rubric's weight
+    pub fn from_scores(
+        scores: &[RubricScore],
+        weights: &RubricWeights,
+        rubrics_version: String,
+    ) -> Self {
+        let weights: BTreeMap<String, f64> = scores
+            .iter()
+            .map(|score| (score.rubric_id.clone(), weights.weight(score)))
+            .collect();
+        let weight = |score: &RubricScore| weights.get(&score.rubric_id).copied().unwrap_or(1.0);
+
+        let total_score: f64 = scores.iter().map(|s| s.score * weight(s)).sum();
+        let max_score: f64 = scores.iter().map(|s| s.max_score * weight(s)).sum();
+        let percentage = if max_score > 0.0 {
+            (total_score / max_score) * 100.0
+        } else {
+            0.0
+        };
+
+        Self {
+            total_score,
+            max_score,
+            percentage,
+            rubrics_evaluated: scores.len(),
+            rubrics_version,
+            weights,
+        }
+    }
+
+    /// Whether any rubric counted more or less than the others
+    pub fn is_weighted(&self) -> bool {
+        let mut weights = self.weights.values();
+        match weights.next() {
+            Some(first) => weights.any(|weight| weight != first),
+            None => false,
+        }
+    }
+}
+
+/// Rubric weights set with `analysis.rubric_weights`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RubricWeights {
+    /// By rubric ID or name
+    overrides: BTreeMap<String, f64>,
+}
+
+impl RubricWeights {
+    pub fn new(overrides: BTreeMap<String, f64>) -> Self {
+        Self { overrides }
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(config.analysis.rubric_weights.clone())
+    }
+
+    /// Weights from the config file, none if it cannot be read
+    pub fn load() -> Self {
+        match Config::load() {
+            Ok(config) => Self::from_config(&config),
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to load config, weighing rubrics equally");
+                Self::default()
+            }
+        }
+    }
+
+    pub fn overrides(&self) -> &BTreeMap<String, f64> {
+        &self.overrides
+    }
+
+    /// Configured weight of a rubric, by ID or else by name (ignoring case),
+    /// falling back to the weight from its definition
+    pub fn weight(&self, score: &RubricScore) -> f64 {
+        self.overrides
+            .get(&score.rubric_id)
+            .or_else(|| {
+                self.overrides
+                    .iter()
+                    .find(|(rubric, _)| rubric.eq_ignore_ascii_case(&score.rubric_name))
+                    .map(|(_, weight)| weight)
+            })
+            .copied()
+            .unwrap_or(score.rubric_weight)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -186,6 +286,29 @@ pub struct MetricQuantitativeOutput {
     pub time_metrics: TimeConsumptionMetrics,
     pub token_metrics: TokenConsumptionMetrics,
     pub tool_usage: ToolUsageMetrics,
+    /// Fraction of user prompts that near-duplicate a prompt from an earlier session
+    #[serde(default)]
+    pub rework_rate: f64,
+    /// Usage of each turn, in conversation order
+    #[serde(default)]
+    pub turns: Vec<TurnUsageMetrics>,
+    /// Edited files grouped by repository
+    #[serde(default)]
+    pub impact: ImpactReport,
+}
+
+/// Usage of one turn: a user request and everything up to the next one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TurnUsageMetrics {
+    /// Turn number; 0 when the session opens without a user request
+    pub turn_number: u32,
+    /// Start of the request that opened the turn
+    pub prompt_preview: String,
+    pub total_tokens: u64,
+    pub tool_calls: u64,
+    pub duration_minutes: f64,
+    /// Files read or edited by the turn's tool calls
+    pub files_touched: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -195,12 +318,39 @@ pub struct FileChangeMetrics {
     pub lines_added: u64,
     pub lines_removed: u64,
     pub net_code_growth: i64,
+    /// Breakdown by language, most lines changed first
+    #[serde(default)]
+    pub languages: Vec<LanguageChangeMetrics>,
+}
+
+/// File changes in one language (see [`crate::utils::language`])
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LanguageChangeMetrics {
+    pub language: String,
+    /// Distinct files read or edited
+    pub files_touched: u64,
+    pub lines_added: u64,
+    pub lines_removed: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TimeConsumptionMetrics {
+    /// Wall-clock time from the first to the last message
     pub total_session_time_minutes: f64,
+    /// Wall time minus idle gaps; `None` for analyses made before idle gaps
+    /// were detected
+    #[serde(default)]
+    pub active_time_minutes: Option<f64>,
+    /// Silences between messages longer than the idle threshold
+    #[serde(default)]
+    pub idle_gaps: u32,
     pub peak_hours: Vec<u32>,
+    /// Context compactions during the session
+    #[serde(default)]
+    pub compactions: u32,
+    /// Minutes spent in each context segment, split at compactions
+    #[serde(default)]
+    pub segment_minutes: Vec<f64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -208,7 +358,23 @@ pub struct TokenConsumptionMetrics {
     pub total_tokens_used: u64,
     pub input_tokens: u64,
     pub output_tokens: u64,
+    /// Mean output share across context segments
     pub token_efficiency: f64,
+    /// Output share within each context segment, split at compactions
+    #[serde(default)]
+    pub segment_token_efficiency: Vec<f64>,
+    /// Most tokens used within one context segment
+    #[serde(default)]
+    pub peak_context_tokens: u64,
+    /// Prompt tokens written to the provider's prompt cache
+    #[serde(default)]
+    pub cache_creation_input_tokens: u64,
+    /// Prompt tokens served from the provider's prompt cache
+    #[serde(default)]
+    pub cache_read_input_tokens: u64,
+    /// Share of prompt tokens served from the cache (0.0 to 1.0)
+    #[serde(default)]
+    pub cache_hit_ratio: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -217,7 +383,18 @@ pub struct ToolUsageMetrics {
     pub successful_operations: u64,
     pub failed_operations: u64,
     pub tool_distribution: HashMap<String, u64>,
+    /// Mean duration of calls the log timed; 0 when none were
     pub average_execution_time_ms: f64,
+    /// Calls with a recorded duration
+    #[serde(default)]
+    pub timed_operations: u64,
+    #[serde(default)]
+    pub p50_execution_time_ms: f64,
+    #[serde(default)]
+    pub p95_execution_time_ms: f64,
+    /// Loops of repeated calls and edit-revert cycles
+    #[serde(default)]
+    pub thrash_segments: Vec<ThrashSegment>,
 }
 
 // =============================================================================
@@ -0,0 +1,217 @@
+//! Agent loops and thrashing within a session
+//!
+//! Two patterns are flagged: the same tool call made over and over in a row
+//! (identical, or identical once whitespace is ignored), and edits that undo
+//! an earlier edit to the same file.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+use crate::models::ToolOperation;
+
+/// Consecutive identical calls that make a loop
+pub const MIN_REPEATED_CALLS: usize = 3;
+
+/// Pattern a thrash segment matched
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThrashKind {
+    /// The same call repeated back to back
+    RepeatedCall,
+    /// An edit reverted by a later edit, possibly several times
+    EditRevert,
+}
+
+impl std::fmt::Display for ThrashKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::RepeatedCall => write!(f, "repeated call"),
+            Self::EditRevert => write!(f, "edit-revert"),
+        }
+    }
+}
+
+/// Stretch of a session where the agent went in circles
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThrashSegment {
+    pub kind: ThrashKind,
+    pub tool_name: String,
+    /// File or command the calls worked on, when there is one
+    pub target: Option<String>,
+    /// Calls in the loop, or reverts for an edit-revert cycle
+    pub occurrences: u32,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+}
+
+/// Thrash segments of a session, in the order they started
+pub fn detect_thrash_segments(tool_operations: &[ToolOperation]) -> Vec<ThrashSegment> {
+    // Operations can be linked from both their request and result messages
+    let mut seen = HashSet::new();
+    let mut operations: Vec<&ToolOperation> = tool_operations
+        .iter()
+        .filter(|op| seen.insert(op.id))
+        .collect();
+    operations.sort_by_key(|op| op.timestamp);
+
+    let mut segments = repeated_calls(&operations);
+    segments.extend(edit_reverts(&operations));
+    segments.sort_by_key(|segment| segment.started_at);
+    segments
+}
+
+fn repeated_calls(operations: &[&ToolOperation]) -> Vec<ThrashSegment> {
+    let mut segments = Vec::new();
+    let mut start = 0;
+    for end in 1..=operations.len() {
+        let same = end < operations.len()
+            && call_signature(operations[end]) == call_signature(operations[start]);
+        if same {
+            continue;
+        }
+        let run = &operations[start..end];
+        if run.len() >= MIN_REPEATED_CALLS {
+            segments.push(ThrashSegment {
+                kind: ThrashKind::RepeatedCall,
+                tool_name: run[0].tool_name.clone(),
+                target: target_of(run[0]),
+                occurrences: run.len() as u32,
+                started_at: run[0].timestamp,
+                ended_at: run[run.len() - 1].timestamp,
+            });
+        }
+        start = end;
+    }
+    segments
+}
+
+fn edit_reverts(operations: &[&ToolOperation]) -> Vec<ThrashSegment> {
+    // (file, old, new) of every edit so far, to find the edit a later one undoes
+    let mut edits: HashMap<(String, String, String), &ToolOperation> = HashMap::new();
+    let mut by_file: HashMap<String, ThrashSegment> = HashMap::new();
+
+    for op in operations {
+        let Some(file) = target_of(op) else { continue };
+        for (old, new) in replacements(op) {
+            if let Some(original) = edits.get(&(file.clone(), new.clone(), old.clone())) {
+                let segment = by_file.entry(file.clone()).or_insert(ThrashSegment {
+                    kind: ThrashKind::EditRevert,
+                    tool_name: op.tool_name.clone(),
+                    target: Some(file.clone()),
+                    occurrences: 0,
+                    started_at: original.timestamp,
+                    ended_at: op.timestamp,
+                });
+                segment.occurrences += 1;
+                segment.started_at = segment.started_at.min(original.timestamp);
+                segment.ended_at = segment.ended_at.max(op.timestamp);
+            }
+            edits.insert((file.clone(), old, new), op);
+        }
+    }
+
+    by_file.into_values().collect()
+}
+
+/// Tool and input with whitespace runs collapsed, so calls that differ only
+/// in formatting match
+fn call_signature(op: &ToolOperation) -> (String, Option<String>) {
+    (
+        op.tool_name.clone(),
+        op.raw_input
+            .as_ref()
+            .map(|input| collapse_whitespace(&input.to_string())),
+    )
+}
+
+/// File path or command a call worked on
+fn target_of(op: &ToolOperation) -> Option<String> {
+    if let Some(metadata) = &op.file_metadata {
+        return Some(metadata.file_path.clone());
+    }
+    let input = op.raw_input.as_ref()?;
+    ["file_path", "path", "command"]
+        .iter()
+        .find_map(|key| input.get(key).and_then(Value::as_str))
+        .map(collapse_whitespace)
+}
+
+/// `(old, new)` text pairs of an edit call, including each part of a
+/// multi-edit
+fn replacements(op: &ToolOperation) -> Vec<(String, String)> {
+    let Some(input) = &op.raw_input else {
+        return Vec::new();
+    };
+    let pair = |value: &Value| {
+        let old = value.get("old_string")?.as_str()?;
+        let new = value.get("new_string")?.as_str()?;
+        (old != new).then(|| (old.to_string(), new.to_string()))
+    };
+    match input.get("edits").and_then(Value::as_array) {
+        Some(edits) => edits.iter().filter_map(pair).collect(),
+        None => pair(input).into_iter().collect(),
+    }
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use serde_json::json;
+
+    fn call(tool: &str, input: Value, minutes: i64) -> ToolOperation {
+        ToolOperation::new(
+            format!("t{minutes}"),
+            tool.to_string(),
+            Utc::now() + Duration::minutes(minutes),
+        )
+        .with_raw_input(input)
+    }
+
+    #[test]
+    fn test_repeated_calls() {
+        let operations = vec![
+            call("Bash", json!({ "command": "cargo test" }), 0),
+            call("Bash", json!({ "command": "cargo  test" }), 1),
+            call("Bash", json!({ "command": "cargo test" }), 2),
+            call("Read", json!({ "file_path": "src/lib.rs" }), 3),
+            call("Read", json!({ "file_path": "src/lib.rs" }), 4),
+        ];
+
+        let segments = detect_thrash_segments(&operations);
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].kind, ThrashKind::RepeatedCall);
+        assert_eq!(segments[0].occurrences, 3);
+        assert_eq!(segments[0].target.as_deref(), Some("cargo test"));
+    }
+
+    #[test]
+    fn test_edit_revert_cycles() {
+        let edit = |old: &str, new: &str, minutes| {
+            call(
+                "Edit",
+                json!({ "file_path": "src/lib.rs", "old_string": old, "new_string": new }),
+                minutes,
+            )
+        };
+        let operations = vec![
+            edit("a + b", "a - b", 0),
+            edit("a - b", "a + b", 1),
+            edit("a + b", "a - b", 2),
+            edit("fn x()", "fn y()", 3),
+        ];
+
+        let segments = detect_thrash_segments(&operations);
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].kind, ThrashKind::EditRevert);
+        assert_eq!(segments[0].occurrences, 2);
+        assert_eq!(segments[0].started_at, operations[0].timestamp);
+        assert_eq!(segments[0].ended_at, operations[2].timestamp);
+    }
+}
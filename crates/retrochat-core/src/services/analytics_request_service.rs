@@ -1,17 +1,31 @@
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde_json::json;
+use tokio_util::sync::CancellationToken;
 
 use crate::database::{
     AnalyticsRepository, AnalyticsRequestRepository, ChatSessionRepository, DatabaseManager,
+    EventRepository, SessionComparisonRepository,
 };
-use crate::models::{Analytics, AnalyticsRequest, OperationStatus};
+use crate::models::{Analytics, AnalyticsRequest, EventKind, OperationStatus, SessionComparison};
 use crate::services::analytics_service::AnalyticsService;
 use crate::services::google_ai::GoogleAiClient;
-use crate::services::llm::LlmClient;
+use crate::services::llm::{
+    track_parse_metrics, track_retry_metrics, track_usage, LlmClient, LlmError, LlmUsage,
+    ParseMetrics, RetryMetrics,
+};
+
+/// How often a running analysis checks whether another process cancelled it
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_secs(2);
 
 pub struct AnalyticsRequestService {
     analytics_service: AnalyticsService,
     request_repo: AnalyticsRequestRepository,
     db_manager: Arc<DatabaseManager>,
+    /// Cancellation tokens of analyses running in this process, by request ID
+    running: Mutex<HashMap<String, CancellationToken>>,
 }
 
 impl AnalyticsRequestService {
@@ -25,6 +39,7 @@ impl AnalyticsRequestService {
             analytics_service,
             request_repo,
             db_manager,
+            running: Mutex::new(HashMap::new()),
         }
     }
 
@@ -38,6 +53,7 @@ impl AnalyticsRequestService {
             analytics_service,
             request_repo,
             db_manager,
+            running: Mutex::new(HashMap::new()),
         }
     }
 
@@ -47,19 +63,16 @@ impl AnalyticsRequestService {
         created_by: Option<String>,
         custom_prompt: Option<String>,
     ) -> Result<AnalyticsRequest, Box<dyn std::error::Error + Send + Sync>> {
-        // Check if there's already an active request for this session
-        let existing_requests = self.request_repo.find_by_session_id(&session_id).await?;
-        for existing_request in &existing_requests {
-            match existing_request.status {
-                OperationStatus::Pending | OperationStatus::Running => {
-                    return Err(format!(
-                        "Session {} already has an active analysis request ({}). Please wait for it to complete or cancel it first.",
-                        session_id, existing_request.id
-                    ).into());
-                }
-                _ => {} // Allow creating new requests if existing ones are completed/failed/cancelled
-            }
-        }
+        let existing_requests = self.ensure_no_active_request(&session_id).await?;
+
+        // Comparisons filed under this session are not analyses of it
+        let comparison_ids: Vec<String> = self
+            .comparison_repo()
+            .get_by_session(&session_id)
+            .await?
+            .into_iter()
+            .map(|comparison| comparison.analytics_request_id)
+            .collect();
 
         // Dirty check: Check if session has been updated since last completed analysis
         if custom_prompt.is_none() {
@@ -68,6 +81,7 @@ impl AnalyticsRequestService {
             if let Some(latest_completed) = existing_requests
                 .iter()
                 .filter(|r| matches!(r.status, OperationStatus::Completed))
+                .filter(|r| !comparison_ids.contains(&r.id))
                 .max_by_key(|r| r.completed_at.as_ref())
             {
                 // Get the session to check its updated_at timestamp
@@ -104,6 +118,58 @@ impl AnalyticsRequestService {
         Ok(request)
     }
 
+    /// Create a request comparing two sessions, filed under the first one
+    pub async fn create_comparison_request(
+        &self,
+        session_id: String,
+        other_session_id: String,
+        created_by: Option<String>,
+    ) -> Result<AnalyticsRequest, Box<dyn std::error::Error + Send + Sync>> {
+        if session_id == other_session_id {
+            return Err("Cannot compare a session with itself".into());
+        }
+        let session_repo = ChatSessionRepository::new(&self.db_manager);
+        for id in [&session_id, &other_session_id] {
+            let uuid = uuid::Uuid::parse_str(id).map_err(|e| format!("Invalid session ID: {e}"))?;
+            if session_repo.get_by_id(&uuid).await?.is_none() {
+                return Err(format!("Session not found: {id}").into());
+            }
+        }
+        self.ensure_no_active_request(&session_id).await?;
+
+        let request = AnalyticsRequest::new(session_id.clone(), created_by, None);
+        self.request_repo.create(&request).await?;
+        self.comparison_repo()
+            .create(&SessionComparison::new(
+                request.id.clone(),
+                session_id,
+                other_session_id,
+            ))
+            .await?;
+
+        Ok(request)
+    }
+
+    /// The session's requests, failing if one of them is still pending or running
+    async fn ensure_no_active_request(
+        &self,
+        session_id: &str,
+    ) -> Result<Vec<AnalyticsRequest>, Box<dyn std::error::Error + Send + Sync>> {
+        let existing_requests = self.request_repo.find_by_session_id(session_id).await?;
+        if let Some(active) = existing_requests.iter().find(|r| r.is_active()) {
+            return Err(format!(
+                "Session {} already has an active analysis request ({}). Please wait for it to complete or cancel it first.",
+                session_id, active.id
+            )
+            .into());
+        }
+        Ok(existing_requests)
+    }
+
+    fn comparison_repo(&self) -> SessionComparisonRepository {
+        SessionComparisonRepository::new(&self.db_manager)
+    }
+
     pub async fn execute_analysis(
         &self,
         request_id: String,
@@ -123,6 +189,9 @@ impl AnalyticsRequestService {
             OperationStatus::Completed => {
                 return Err("Request is already completed".into());
             }
+            OperationStatus::Cancelled => {
+                return Err("Request was cancelled".into());
+            }
             _ => {}
         }
 
@@ -130,20 +199,54 @@ impl AnalyticsRequestService {
         request.mark_running();
         self.request_repo.update(&request).await?;
 
-        // Perform the analysis synchronously (blocking for CLI, but TUI will handle async)
-        match self.perform_analysis(&request).await {
-            Ok(analysis) => {
+        let token = CancellationToken::new();
+        self.running
+            .lock()
+            .unwrap()
+            .insert(request_id.clone(), token.clone());
+
+        // Perform the analysis synchronously (blocking for CLI, but TUI will handle async).
+        // On cancellation the analysis future is dropped, which aborts in-flight
+        // LLM calls (HTTP requests are dropped, CLI subprocesses killed).
+        let result = tokio::select! {
+            result = self.perform_request(&request) => result,
+            _ = self.wait_for_cancellation(&request_id, &token) => Err(LlmError::Cancelled.into()),
+        };
+        self.running.lock().unwrap().remove(&request_id);
+
+        if token.is_cancelled() {
+            // cancel_analysis has already recorded the cancelled status
+            tracing::info!(request_id = %request_id, "Analysis cancelled");
+            return Err(LlmError::Cancelled.into());
+        }
+
+        match result {
+            Ok(()) => {
                 // Mark request as completed
                 // Note: analysis results are now stored via analytics_service
                 request.mark_completed();
                 self.request_repo.update(&request).await?;
+                EventRepository::new(&self.db_manager)
+                    .record(
+                        EventKind::AnalysisCompleted,
+                        Some(&request.session_id),
+                        json!({ "request_id": request.id }),
+                    )
+                    .await;
 
-                Ok(analysis.session_id)
+                Ok(request.session_id)
             }
             Err(e) => {
                 // Mark request as failed with error message
                 request.mark_failed(e.to_string());
                 self.request_repo.update(&request).await?;
+                EventRepository::new(&self.db_manager)
+                    .record(
+                        EventKind::AnalysisFailed,
+                        Some(&request.session_id),
+                        json!({ "request_id": request.id, "error": e.to_string() }),
+                    )
+                    .await;
 
                 Err(e)
             }
@@ -166,6 +269,12 @@ impl AnalyticsRequestService {
                 request.mark_cancelled();
                 self.request_repo.update(&request).await?;
 
+                // Stop the work too if it is running in this process; runs in
+                // other processes notice the status change on their next poll
+                if let Some(token) = self.running.lock().unwrap().get(&request_id) {
+                    token.cancel();
+                }
+
                 Ok(())
             }
             _ => Err(format!("Cannot cancel request with status: {:?}", request.status).into()),
@@ -198,6 +307,16 @@ impl AnalyticsRequestService {
             return Ok(None);
         }
 
+        // Comparisons have no analysis to load or regenerate
+        if self
+            .comparison_repo()
+            .get_by_request_id(&request_id)
+            .await?
+            .is_some()
+        {
+            return Ok(None);
+        }
+
         // Try to load from database first
         let analytics_repo = AnalyticsRepository::new(&self.db_manager);
         if let Some(analytics) = analytics_repo
@@ -232,6 +351,38 @@ impl AnalyticsRequestService {
         }
     }
 
+    /// The comparison behind a request, or `None` if it is not a comparison
+    pub async fn get_comparison_result(
+        &self,
+        request_id: &str,
+    ) -> Result<Option<SessionComparison>, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(self.comparison_repo().get_by_request_id(request_id).await?)
+    }
+
+    /// Comparisons involving a session on either side, newest first
+    pub async fn list_comparisons(
+        &self,
+        session_id: &str,
+    ) -> Result<Vec<SessionComparison>, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(self.comparison_repo().get_by_session(session_id).await?)
+    }
+
+    /// Retry metrics of the LLM calls made for a finished request
+    pub async fn get_retry_metrics(
+        &self,
+        request_id: &str,
+    ) -> Result<Option<RetryMetrics>, Box<dyn std::error::Error + Send + Sync>> {
+        self.request_repo.get_retry_metrics(request_id).await
+    }
+
+    /// Structured-output parse outcomes of a finished request
+    pub async fn get_parse_metrics(
+        &self,
+        request_id: &str,
+    ) -> Result<Option<ParseMetrics>, Box<dyn std::error::Error + Send + Sync>> {
+        self.request_repo.get_parse_metrics(request_id).await
+    }
+
     pub async fn list_analyses(
         &self,
         session_id: Option<String>,
@@ -278,6 +429,69 @@ impl AnalyticsRequestService {
         Ok(requests_deleted)
     }
 
+    /// Resolve once `token` is cancelled, either directly or because the
+    /// request was marked cancelled in the database (e.g. by another process)
+    async fn wait_for_cancellation(&self, request_id: &str, token: &CancellationToken) {
+        loop {
+            tokio::select! {
+                _ = token.cancelled() => return,
+                _ = tokio::time::sleep(CANCEL_POLL_INTERVAL) => {
+                    if let Ok(Some(request)) = self.request_repo.find_by_id(request_id).await {
+                        if request.status == OperationStatus::Cancelled {
+                            token.cancel();
+                            return;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Run a request as an analysis or, if it was created as one, a comparison
+    async fn perform_request(
+        &self,
+        request: &AnalyticsRequest,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        match self
+            .comparison_repo()
+            .get_by_request_id(&request.id)
+            .await?
+        {
+            Some(comparison) => self.perform_comparison(request, &comparison).await,
+            None => self.perform_analysis(request).await.map(|_| ()),
+        }
+    }
+
+    async fn perform_comparison(
+        &self,
+        request: &AnalyticsRequest,
+        comparison: &SessionComparison,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let start_time = std::time::Instant::now();
+
+        let (((output, parse_metrics), retry_metrics), usage) =
+            track_usage(track_retry_metrics(track_parse_metrics(
+                self.analytics_service
+                    .compare_sessions(&comparison.session_id, &comparison.other_session_id),
+            )))
+            .await;
+        self.record_metrics(request, &parse_metrics, &retry_metrics, &usage)
+            .await;
+        let output = output?;
+
+        self.comparison_repo()
+            .save_result(
+                &request.id,
+                &output,
+                self.analytics_service.model_name(),
+                start_time.elapsed().as_millis() as i64,
+            )
+            .await
+            .map_err(|e| format!("Failed to save comparison: {e}"))?;
+
+        Ok(())
+    }
+
     async fn perform_analysis(
         &self,
         request: &AnalyticsRequest,
@@ -285,10 +499,15 @@ impl AnalyticsRequestService {
         let start_time = std::time::Instant::now();
 
         // Use analytics service to generate analysis
-        let mut analytics = self
-            .analytics_service
-            .analyze_session(&request.session_id, Some(request.id.clone()))
-            .await?;
+        let (((analytics, parse_metrics), retry_metrics), usage) =
+            track_usage(track_retry_metrics(track_parse_metrics(
+                self.analytics_service
+                    .analyze_session(&request.session_id, Some(request.id.clone())),
+            )))
+            .await;
+        self.record_metrics(request, &parse_metrics, &retry_metrics, &usage)
+            .await;
+        let mut analytics = analytics?;
 
         // Save analysis to database with timing info
         let analysis_duration_ms = start_time.elapsed().as_millis() as i64;
@@ -303,6 +522,33 @@ impl AnalyticsRequestService {
 
         Ok(analytics)
     }
+
+    /// Store the LLM call metrics of a request, logging (not failing) on error
+    async fn record_metrics(
+        &self,
+        request: &AnalyticsRequest,
+        parse_metrics: &ParseMetrics,
+        retry_metrics: &RetryMetrics,
+        usage: &LlmUsage,
+    ) {
+        if let Err(e) = self
+            .request_repo
+            .record_retry_metrics(&request.id, retry_metrics)
+            .await
+        {
+            tracing::warn!(error = %e, "Failed to record retry metrics");
+        }
+        if let Err(e) = self
+            .request_repo
+            .record_parse_metrics(&request.id, parse_metrics)
+            .await
+        {
+            tracing::warn!(error = %e, "Failed to record parse metrics");
+        }
+        if let Err(e) = self.request_repo.record_usage(&request.id, usage).await {
+            tracing::warn!(error = %e, "Failed to record LLM usage");
+        }
+    }
 }
 
 /// A cleanup handler that automatically cancels running analyze requests when dropped.
@@ -536,4 +782,187 @@ mod tests {
             Some("Custom analysis prompt".to_string())
         );
     }
+
+    struct DropFlag(Arc<std::sync::atomic::AtomicBool>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.store(true, std::sync::atomic::Ordering::SeqCst);
+        }
+    }
+
+    /// LLM client whose calls never finish, recording when they are dropped
+    struct HangingLlm {
+        dropped: Arc<std::sync::atomic::AtomicBool>,
+    }
+
+    #[async_trait::async_trait]
+    impl LlmClient for HangingLlm {
+        async fn generate(
+            &self,
+            _request: crate::services::llm::GenerateRequest,
+        ) -> Result<crate::services::llm::GenerateResponse, LlmError> {
+            let _flag = DropFlag(self.dropped.clone());
+            std::future::pending().await
+        }
+
+        fn provider_name(&self) -> &'static str {
+            "hanging"
+        }
+
+        fn model_name(&self) -> &str {
+            "hanging"
+        }
+
+        async fn health_check(&self) -> Result<(), LlmError> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cancel_stops_running_analysis() {
+        let database = Database::new_in_memory().await.unwrap();
+        database.initialize().await.unwrap();
+
+        let session_repo = crate::database::ChatSessionRepository::new(&database.manager);
+        let test_session = crate::models::ChatSession::new(
+            crate::models::Provider::ClaudeCode,
+            "/test/chat5.jsonl".to_string(),
+            "test_hash5".to_string(),
+            chrono::Utc::now(),
+        );
+        session_repo.create(&test_session).await.unwrap();
+
+        let dropped = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let service = Arc::new(AnalyticsRequestService::new_with_llm(
+            Arc::new(database.manager),
+            Arc::new(HangingLlm {
+                dropped: dropped.clone(),
+            }),
+        ));
+
+        let request = service
+            .create_analysis_request(test_session.id.to_string(), None, None)
+            .await
+            .unwrap();
+
+        let running = tokio::spawn({
+            let service = service.clone();
+            let request_id = request.id.clone();
+            async move { service.execute_analysis(request_id).await }
+        });
+
+        // Wait until the analysis is stuck in the LLM call
+        while service.running.lock().unwrap().is_empty() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        service.cancel_analysis(request.id.clone()).await.unwrap();
+
+        let result = tokio::time::timeout(Duration::from_secs(5), running)
+            .await
+            .expect("cancelled analysis should finish promptly")
+            .unwrap();
+        assert!(result.is_err());
+        assert!(dropped.load(std::sync::atomic::Ordering::SeqCst));
+        assert!(service.running.lock().unwrap().is_empty());
+
+        let status = service.get_analysis_status(request.id).await.unwrap();
+        assert_eq!(status.status, OperationStatus::Cancelled);
+    }
+
+    /// LLM client that always answers with the same text
+    struct FixedLlm(&'static str);
+
+    #[async_trait::async_trait]
+    impl LlmClient for FixedLlm {
+        async fn generate(
+            &self,
+            _request: crate::services::llm::GenerateRequest,
+        ) -> Result<crate::services::llm::GenerateResponse, LlmError> {
+            Ok(crate::services::llm::GenerateResponse {
+                text: self.0.to_string(),
+                ..Default::default()
+            })
+        }
+
+        fn provider_name(&self) -> &'static str {
+            "fixed"
+        }
+
+        fn model_name(&self) -> &str {
+            "fixed"
+        }
+
+        async fn health_check(&self) -> Result<(), LlmError> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_compare_sessions() {
+        let database = Database::new_in_memory().await.unwrap();
+        database.initialize().await.unwrap();
+
+        let session_repo = crate::database::ChatSessionRepository::new(&database.manager);
+        let mut sessions = Vec::new();
+        for n in 0..2 {
+            let session = crate::models::ChatSession::new(
+                crate::models::Provider::ClaudeCode,
+                format!("/test/compare{n}.jsonl"),
+                format!("compare_hash{n}"),
+                chrono::Utc::now(),
+            );
+            session_repo.create(&session).await.unwrap();
+            sessions.push(session.id.to_string());
+        }
+
+        let service = AnalyticsRequestService::new_with_llm(
+            Arc::new(database.manager),
+            Arc::new(FixedLlm(
+                r#"{"summary": "Both sessions were empty.", "approach": {"first": [], "second": [], "verdict": "Even."}, "efficiency": {"first": [], "second": [], "verdict": "Even."}, "failure_points": {"first": [], "second": [], "verdict": "Even."}, "winner": "tie"}"#,
+            )),
+        );
+
+        assert!(service
+            .create_comparison_request(sessions[0].clone(), sessions[0].clone(), None)
+            .await
+            .is_err());
+
+        let request = service
+            .create_comparison_request(sessions[0].clone(), sessions[1].clone(), None)
+            .await
+            .unwrap();
+        assert_eq!(request.session_id, sessions[0]);
+        service.execute_analysis(request.id.clone()).await.unwrap();
+
+        let comparison = service
+            .get_comparison_result(&request.id)
+            .await
+            .unwrap()
+            .unwrap();
+        let result = comparison.result.unwrap();
+        assert_eq!(
+            result.winner,
+            crate::services::analytics::ComparisonWinner::Tie
+        );
+        assert_eq!(result.metrics[0].name, "Total tokens");
+        assert_eq!(comparison.model_used.as_deref(), Some("fixed"));
+        assert!(service
+            .get_analysis_result(request.id.clone())
+            .await
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            service.list_comparisons(&sessions[1]).await.unwrap().len(),
+            1
+        );
+
+        // A finished comparison does not count as an analysis of the session
+        assert!(service
+            .create_analysis_request(sessions[0].clone(), None, None)
+            .await
+            .is_ok());
+    }
 }
@@ -1,34 +1,67 @@
 use super::google_ai::GoogleAiClient;
-use super::llm::{adapters::GoogleAiAdapter, LlmClient};
+use super::llm::{adapters::GoogleAiAdapter, CachingClient, LlmClient};
+use super::project_config::ProjectConfig;
+use super::rework_detection::ReworkDetector;
+use crate::config::Config;
 use crate::database::{
-    ChatSessionRepository, DatabaseManager, MessageRepository, ToolOperationRepository,
+    AnalyticsRepository, ChatSessionRepository, DatabaseManager, MessageRepository,
+    MessageUsageRepository, SessionSummaryRepository, ToolOperationRepository,
 };
 use anyhow::Result;
 use std::sync::Arc;
 
 // Import from analytics module
 use super::analytics::{
-    collect_qualitative_data, collect_quantitative_data, generate_qualitative_analysis_ai,
-    generate_quantitative_analysis_ai,
+    collect_qualitative_data, collect_quantitative_data, correlate_with_git,
+    generate_qualitative_analysis_ai, generate_quantitative_analysis_ai,
+    generate_session_comparison_ai, ComparisonInput, RubricEvaluationSummary, RubricWeights,
+    SessionComparisonOutput,
 };
 use crate::models::Analytics;
+use crate::utils::locale::Language;
+
+/// Result of recomputing stored rubric summaries with the current weights
+#[derive(Debug, Clone, Default)]
+pub struct RubricRecomputeReport {
+    /// Analyses with rubric scores
+    pub analyses: usize,
+    /// Analyses whose summary changed
+    pub updated: usize,
+    /// Configured rubrics that match no scored rubric, likely typos
+    pub unmatched: Vec<String>,
+}
 
 pub struct AnalyticsService {
     db_manager: Arc<DatabaseManager>,
     llm_client: Option<Arc<dyn LlmClient>>,
+    language: Language,
+    idle_gap: chrono::Duration,
+    git_correlation: bool,
+    rubric_weights: RubricWeights,
 }
 
 impl AnalyticsService {
     pub fn new(db_manager: Arc<DatabaseManager>) -> Self {
+        let config = Config::load().unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "Failed to load config, using analysis defaults");
+            Config::default()
+        });
         Self {
             db_manager,
             llm_client: None,
+            language: Language::from_config(&config),
+            idle_gap: config.analysis.idle_gap(),
+            git_correlation: config.analysis.git_correlation(),
+            rubric_weights: RubricWeights::from_config(&config),
         }
     }
 
     /// Set the LLM client (generic method for any provider)
+    ///
+    /// Responses are cached, so re-analyzing an unchanged session does not
+    /// call the provider again.
     pub fn with_llm_client(mut self, client: Arc<dyn LlmClient>) -> Self {
-        self.llm_client = Some(client);
+        self.llm_client = Some(CachingClient::wrap(client, &self.db_manager));
         self
     }
 
@@ -38,6 +71,17 @@ impl AnalyticsService {
         self.with_llm_client(Arc::new(adapter))
     }
 
+    /// Weigh rubrics with these weights instead of the configured ones
+    pub fn with_rubric_weights(mut self, weights: RubricWeights) -> Self {
+        self.rubric_weights = weights;
+        self
+    }
+
+    /// Model behind the LLM client, if one is set
+    pub fn model_name(&self) -> Option<&str> {
+        self.llm_client.as_ref().map(|client| client.model_name())
+    }
+
     // =============================================================================
     // Advanced Analytics (새로운 기능)
     // =============================================================================
@@ -67,10 +111,29 @@ impl AnalyticsService {
         // Get messages and tool operations
         let messages = message_repo.get_by_session(&session_uuid).await?;
         let tool_operations = tool_op_repo.get_by_session(&session_uuid).await?;
+        let usage = MessageUsageRepository::new(&self.db_manager)
+            .get_by_session(&session_uuid)
+            .await?;
 
         // Collect quantitative and qualitative data
-        let metric_quantitative_output =
-            collect_quantitative_data(&session, &messages, &tool_operations).await?;
+        let mut metric_quantitative_output =
+            collect_quantitative_data(&session, &messages, &tool_operations, &usage, self.idle_gap)
+                .await?;
+        metric_quantitative_output.rework_rate = ReworkDetector::new(self.db_manager.clone())
+            .rework_rate(&session, &messages)
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!("Failed to calculate rework rate: {}", e);
+                0.0
+            });
+        if self.git_correlation {
+            correlate_with_git(
+                &mut metric_quantitative_output.impact,
+                &tool_operations,
+                session.start_time,
+            )
+            .await;
+        }
         let qualitative_input =
             collect_qualitative_data(&tool_operations, &messages, &session).await?;
 
@@ -80,11 +143,33 @@ impl AnalyticsService {
             .as_ref()
             .ok_or_else(|| anyhow::anyhow!("LLM client is required for analysis"))?;
 
+        // A .retrochat.toml in the project can swap the rubrics, entries and language
+        let project_config = ProjectConfig::for_session(&self.db_manager, &session).await?;
+        let (rubrics, entries, language) = match &project_config {
+            Some(config) => (
+                config.rubrics()?,
+                config.qualitative_entries()?,
+                config.language(self.language),
+            ),
+            None => (None, None, self.language),
+        };
+
         // Run qualitative and quantitative analysis in parallel
         // try_join! cancels remaining futures immediately if one fails
         let (ai_qualitative_output, ai_quantitative_output) = tokio::try_join!(
-            generate_qualitative_analysis_ai(&qualitative_input, llm_client.as_ref(), None),
-            generate_quantitative_analysis_ai(&qualitative_input, llm_client.as_ref(), None)
+            generate_qualitative_analysis_ai(
+                &qualitative_input,
+                llm_client.as_ref(),
+                entries.as_ref(),
+                language
+            ),
+            generate_quantitative_analysis_ai(
+                &qualitative_input,
+                llm_client.as_ref(),
+                rubrics.as_ref(),
+                &self.rubric_weights,
+                language
+            )
         )?;
 
         // Create Analytics directly
@@ -98,4 +183,193 @@ impl AnalyticsService {
             None, // analysis_duration_ms - will be set later
         ))
     }
+
+    /// Compare two sessions head to head
+    pub async fn compare_sessions(
+        &self,
+        session_id: &str,
+        other_session_id: &str,
+    ) -> Result<SessionComparisonOutput> {
+        tracing::info!(session_id, other_session_id, "Starting session comparison");
+
+        let llm_client = self
+            .llm_client
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("LLM client is required for analysis"))?;
+
+        let (first, second) = tokio::try_join!(
+            self.comparison_input(session_id),
+            self.comparison_input(other_session_id)
+        )?;
+
+        generate_session_comparison_ai(&first, &second, llm_client.as_ref(), self.language).await
+    }
+
+    /// Recompute the overall rubric score of stored analyses with the current
+    /// weights; the per-rubric scores are kept as they are
+    pub async fn recompute_rubric_summaries(&self) -> Result<RubricRecomputeReport> {
+        let repo = AnalyticsRepository::new(&self.db_manager);
+        let _write_guard = self.db_manager.write_lock().await?;
+
+        let mut report = RubricRecomputeReport::default();
+        let mut matched = std::collections::HashSet::new();
+        for (id, mut output) in repo.list_ai_quantitative_outputs().await? {
+            if output.rubric_scores.is_empty() {
+                continue;
+            }
+            report.analyses += 1;
+            for score in &output.rubric_scores {
+                for rubric in self.rubric_weights.overrides().keys() {
+                    if rubric == &score.rubric_id || rubric.eq_ignore_ascii_case(&score.rubric_name)
+                    {
+                        matched.insert(rubric.clone());
+                    }
+                }
+            }
+
+            let version = output
+                .rubric_summary
+                .as_ref()
+                .map(|summary| summary.rubrics_version.clone())
+                .unwrap_or_else(|| "1.0".to_string());
+            let summary = RubricEvaluationSummary::from_scores(
+                &output.rubric_scores,
+                &self.rubric_weights,
+                version,
+            );
+            if output.rubric_summary.as_ref() != Some(&summary) {
+                output.rubric_summary = Some(summary);
+                repo.update_ai_quantitative_output(&id, &output).await?;
+                report.updated += 1;
+            }
+        }
+
+        report.unmatched = self
+            .rubric_weights
+            .overrides()
+            .keys()
+            .filter(|rubric| !matched.contains(*rubric))
+            .cloned()
+            .collect();
+        Ok(report)
+    }
+
+    /// Transcript, summary and metrics of one side of a comparison
+    async fn comparison_input(&self, session_id: &str) -> Result<ComparisonInput> {
+        let session_uuid = uuid::Uuid::parse_str(session_id)
+            .map_err(|e| anyhow::anyhow!("Invalid session ID format: {e}"))?;
+        let session = ChatSessionRepository::new(&self.db_manager)
+            .get_by_id(&session_uuid)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Session not found: {session_id}"))?;
+
+        let messages = MessageRepository::new(&self.db_manager)
+            .get_by_session(&session_uuid)
+            .await?;
+        let tool_operations = ToolOperationRepository::new(&self.db_manager)
+            .get_by_session(&session_uuid)
+            .await?;
+        let usage = MessageUsageRepository::new(&self.db_manager)
+            .get_by_session(&session_uuid)
+            .await?;
+        let summary = SessionSummaryRepository::new(&self.db_manager)
+            .get_by_session(&session_uuid)
+            .await?;
+
+        let metrics =
+            collect_quantitative_data(&session, &messages, &tool_operations, &usage, self.idle_gap)
+                .await?;
+        let qualitative_input =
+            collect_qualitative_data(&tool_operations, &messages, &session).await?;
+
+        Ok(ComparisonInput {
+            title: session
+                .title
+                .clone()
+                .unwrap_or_else(|| session_id.to_string()),
+            summary: summary.map(|summary| summary.summary),
+            transcript: qualitative_input.raw_session,
+            metrics,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ChatSession, Provider};
+    use chrono::Utc;
+
+    #[tokio::test]
+    async fn test_recompute_rubric_summaries() {
+        let db = Arc::new(DatabaseManager::open_in_memory().await.unwrap());
+        let session = ChatSession::new(
+            Provider::ClaudeCode,
+            "/weights.jsonl".to_string(),
+            "hash".to_string(),
+            Utc::now(),
+        );
+        ChatSessionRepository::new(&db)
+            .create(&session)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO analytics_requests (id, session_id, status) VALUES ('request', ?, 'completed')",
+        )
+        .bind(session.id.to_string())
+        .execute(db.pool())
+        .await
+        .unwrap();
+        // Scored before weights were recorded
+        let quantitative = serde_json::json!({
+            "rubric_scores": [
+                {"rubric_id": "rubric_001", "rubric_name": "Correctness", "score": 5.0, "max_score": 5.0, "reasoning": ""},
+                {"rubric_id": "rubric_002", "rubric_name": "Style", "score": 1.0, "max_score": 5.0, "reasoning": ""},
+            ],
+            "rubric_summary": {
+                "total_score": 6.0, "max_score": 10.0, "percentage": 60.0,
+                "rubrics_evaluated": 2, "rubrics_version": "1.0",
+            },
+        });
+        sqlx::query(
+            r#"
+            INSERT INTO analytics (
+                id, analytics_request_id, session_id, generated_at,
+                qualitative_output_json, ai_quantitative_output_json,
+                metric_quantitative_output_json
+            ) VALUES ('analytics', 'request', ?, ?, '{}', ?, '{}')
+            "#,
+        )
+        .bind(session.id.to_string())
+        .bind(Utc::now().to_rfc3339())
+        .bind(quantitative.to_string())
+        .execute(db.pool())
+        .await
+        .unwrap();
+
+        let weights = RubricWeights::new(
+            [("Correctness".to_string(), 3.0), ("Typo".to_string(), 2.0)]
+                .into_iter()
+                .collect(),
+        );
+        let service = AnalyticsService::new(db.clone()).with_rubric_weights(weights);
+
+        let report = service.recompute_rubric_summaries().await.unwrap();
+        assert_eq!(report.analyses, 1);
+        assert_eq!(report.updated, 1);
+        assert_eq!(report.unmatched, vec!["Typo".to_string()]);
+
+        let (_, output) = AnalyticsRepository::new(&db)
+            .list_ai_quantitative_outputs()
+            .await
+            .unwrap()
+            .remove(0);
+        let summary = output.rubric_summary.unwrap();
+        assert!((summary.percentage - 80.0).abs() < 1e-9);
+        assert_eq!(summary.weights["rubric_001"], 3.0);
+        assert_eq!(summary.weights["rubric_002"], 1.0);
+
+        let report = service.recompute_rubric_summaries().await.unwrap();
+        assert_eq!(report.updated, 0);
+    }
 }
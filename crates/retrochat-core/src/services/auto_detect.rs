@@ -1,14 +1,48 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
+use crate::database::{ChatSessionRepository, DatabaseManager};
 use crate::models::Provider;
+use crate::utils::wsl;
 
 /// Represents a detected provider with its location and session count estimate
 #[derive(Debug, Clone)]
 pub struct DetectedProvider {
     pub provider: Provider,
     pub paths: Vec<PathBuf>,
+    /// Number of session files found under `paths`
     pub estimated_sessions: usize,
     pub is_valid: bool,
+    /// Session files found under `paths`
+    pub files: Vec<PathBuf>,
+    /// Modification time of the most recently written session file
+    pub newest_modified: Option<DateTime<Utc>>,
+    /// Files not yet imported (or changed since import); `None` until
+    /// [`AutoDetectService::estimate_unimported`] has run
+    pub unimported_sessions: Option<usize>,
+}
+
+impl DetectedProvider {
+    fn from_scan(provider: Provider, paths: Vec<PathBuf>, patterns: &[&str]) -> Self {
+        let mut files = Vec::new();
+        let mut newest: Option<SystemTime> = None;
+        for path in &paths {
+            AutoDetectService::collect_files(path, patterns, &mut files, &mut newest);
+        }
+
+        Self {
+            provider,
+            paths,
+            estimated_sessions: files.len(),
+            is_valid: !files.is_empty(),
+            files,
+            newest_modified: newest.map(DateTime::<Utc>::from),
+            unimported_sessions: None,
+        }
+    }
 }
 
 /// Auto-detection service for LLM chat providers
@@ -24,37 +58,82 @@ impl AutoDetectService {
         ]
     }
 
-    /// Detect Claude Code installation
-    fn detect_claude_code() -> DetectedProvider {
-        let default_path = dirs::home_dir()
-            .map(|h| h.join(".claude").join("projects"))
-            .unwrap_or_default();
+    /// Scan all providers and count, per provider, the files whose content
+    /// hash is not yet recorded in the database
+    pub async fn scan_with_import_status(db: &DatabaseManager) -> Result<Vec<DetectedProvider>> {
+        let mut detected = Self::scan_all();
+        Self::estimate_unimported(db, &mut detected).await?;
+        Ok(detected)
+    }
 
-        let (is_valid, estimated_sessions) = Self::check_directory(&default_path, &["*.jsonl"]);
+    /// Fill in `unimported_sessions` by comparing each file's hash (computed the
+    /// same way the parsers do) against the hashes of imported sessions
+    pub async fn estimate_unimported(
+        db: &DatabaseManager,
+        detected: &mut [DetectedProvider],
+    ) -> Result<()> {
+        let known = ChatSessionRepository::new(db).known_file_hashes().await?;
+        for provider in detected.iter_mut() {
+            provider.unimported_sessions = Some(Self::count_unimported(&provider.files, &known));
+        }
+        Ok(())
+    }
 
-        DetectedProvider {
-            provider: Provider::ClaudeCode,
-            paths: vec![default_path],
-            estimated_sessions,
-            is_valid,
+    fn count_unimported(files: &[PathBuf], known: &HashSet<String>) -> usize {
+        files
+            .iter()
+            .filter(|file| match Self::file_hash(file) {
+                Some(hash) => !known.contains(&hash),
+                None => false,
+            })
+            .count()
+    }
+
+    /// Mirrors the parsers' `calculate_file_hash` (path, size and mtime)
+    fn file_hash(path: &Path) -> Option<String> {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let metadata = std::fs::metadata(path).ok()?;
+        let mut hasher = DefaultHasher::new();
+        path.to_string_lossy().to_string().hash(&mut hasher);
+        metadata.len().hash(&mut hasher);
+        if let Ok(modified) = metadata.modified() {
+            if let Ok(duration) = modified.duration_since(std::time::UNIX_EPOCH) {
+                duration.as_secs().hash(&mut hasher);
+            }
         }
+
+        Some(format!("{:x}", hasher.finish()))
     }
 
-    /// Detect Gemini CLI installation
-    fn detect_gemini() -> DetectedProvider {
+    /// `relative` under the home directory, plus under the homes on the
+    /// other side of the Windows/WSL boundary where it exists
+    fn home_paths(relative: &Path) -> Vec<PathBuf> {
         let default_path = dirs::home_dir()
-            .map(|h| h.join(".gemini").join("tmp"))
+            .map(|h| h.join(relative))
             .unwrap_or_default();
 
-        let (is_valid, estimated_sessions) =
-            Self::check_directory(&default_path, &["session-*.json"]);
+        let mut paths = vec![default_path];
+        paths.extend(
+            wsl::bridged_home_dirs()
+                .into_iter()
+                .map(|home| home.join(relative))
+                .filter(|path| path.is_dir()),
+        );
+        paths
+    }
 
-        DetectedProvider {
-            provider: Provider::GeminiCLI,
-            paths: vec![default_path],
-            estimated_sessions,
-            is_valid,
-        }
+    /// Detect Claude Code installation
+    fn detect_claude_code() -> DetectedProvider {
+        let paths = Self::home_paths(Path::new(".claude/projects"));
+        DetectedProvider::from_scan(Provider::ClaudeCode, paths, &["*.jsonl"])
+    }
+
+    /// Detect Gemini CLI installation
+    fn detect_gemini() -> DetectedProvider {
+        let paths = Self::home_paths(Path::new(".gemini/tmp"));
+        DetectedProvider::from_scan(Provider::GeminiCLI, paths, &["session-*.json"])
     }
 
     /// Detect Codex installation
@@ -64,58 +143,48 @@ impl AutoDetectService {
             .ok()
             .and_then(|p| p.split(':').next().map(PathBuf::from));
 
-        if let Some(path) = env_path {
-            let (is_valid, estimated_sessions) =
-                Self::check_directory(&path, &["*.json", "*.jsonl"]);
-            DetectedProvider {
-                provider: Provider::Codex,
-                paths: vec![path],
-                estimated_sessions,
-                is_valid,
-            }
-        } else {
-            DetectedProvider {
-                provider: Provider::Codex,
-                paths: vec![],
-                estimated_sessions: 0,
-                is_valid: false,
-            }
-        }
+        let paths = env_path.into_iter().collect();
+        DetectedProvider::from_scan(Provider::Codex, paths, &["*.json", "*.jsonl"])
     }
 
-    /// Check if directory exists and count potential session files (recursive)
-    fn check_directory(path: &Path, patterns: &[&str]) -> (bool, usize) {
-        if !path.exists() || !path.is_dir() {
-            return (false, 0);
+    /// Recursively collect files matching patterns, tracking the newest mtime
+    fn collect_files(
+        path: &Path,
+        patterns: &[&str],
+        files: &mut Vec<PathBuf>,
+        newest: &mut Option<SystemTime>,
+    ) {
+        if !path.is_dir() {
+            return;
         }
 
-        let mut count = 0;
-
-        // Try to count files matching patterns (recursive)
-        if let Ok(entries) = std::fs::read_dir(path) {
-            for entry in entries.flatten() {
-                if let Ok(file_type) = entry.file_type() {
-                    if file_type.is_file() {
-                        let file_name = entry.file_name();
-                        let file_name_str = file_name.to_string_lossy();
-
-                        for pattern in patterns {
-                            if Self::matches_pattern(&file_name_str, pattern) {
-                                count += 1;
-                                break;
-                            }
-                        }
-                    } else if file_type.is_dir() {
-                        // Recursively scan subdirectories
-                        let subdir = entry.path();
-                        let (_, subcount) = Self::check_directory(&subdir, patterns);
-                        count += subcount;
+        let Ok(entries) = std::fs::read_dir(path) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_file() {
+                let file_name = entry.file_name();
+                let file_name_str = file_name.to_string_lossy();
+                if !patterns
+                    .iter()
+                    .any(|pattern| Self::matches_pattern(&file_name_str, pattern))
+                {
+                    continue;
+                }
+
+                if let Ok(modified) = entry.metadata().and_then(|m| m.modified()) {
+                    if newest.is_none_or(|current| modified > current) {
+                        *newest = Some(modified);
                     }
                 }
+                files.push(entry.path());
+            } else if file_type.is_dir() {
+                Self::collect_files(&entry.path(), patterns, files, newest);
             }
         }
-
-        (count > 0, count)
     }
 
     /// Simple pattern matching (supports * wildcard)
@@ -138,6 +207,33 @@ impl AutoDetectService {
         detected.iter().map(|d| d.estimated_sessions).sum()
     }
 
+    /// Get total unimported session count, if it has been estimated
+    pub fn total_unimported(detected: &[DetectedProvider]) -> usize {
+        detected.iter().filter_map(|d| d.unimported_sessions).sum()
+    }
+
+    /// Summarize unimported files for prompts, e.g.
+    /// "You have 37 new Claude Code sessions and 2 new Codex sessions"
+    pub fn new_sessions_message(detected: &[DetectedProvider]) -> Option<String> {
+        let parts: Vec<String> = detected
+            .iter()
+            .filter_map(|d| match d.unimported_sessions {
+                Some(count) if count > 0 => Some(format!(
+                    "{count} new {} session{}",
+                    d.provider,
+                    if count == 1 { "" } else { "s" }
+                )),
+                _ => None,
+            })
+            .collect();
+
+        match parts.as_slice() {
+            [] => None,
+            [only] => Some(format!("You have {only}")),
+            [rest @ .., last] => Some(format!("You have {} and {last}", rest.join(", "))),
+        }
+    }
+
     /// Get valid providers only
     pub fn valid_providers(detected: &[DetectedProvider]) -> Vec<DetectedProvider> {
         detected.iter().filter(|d| d.is_valid).cloned().collect()
@@ -163,6 +259,49 @@ mod tests {
         assert!(AutoDetectService::matches_pattern("session.json", "*.json"));
     }
 
+    #[test]
+    fn test_collect_files_and_unimported_count() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let nested = temp_dir.path().join("project");
+        std::fs::create_dir(&nested).unwrap();
+        std::fs::write(nested.join("a.jsonl"), "{}").unwrap();
+        std::fs::write(temp_dir.path().join("b.jsonl"), "{}").unwrap();
+        std::fs::write(temp_dir.path().join("notes.txt"), "").unwrap();
+
+        let detected = DetectedProvider::from_scan(
+            Provider::ClaudeCode,
+            vec![temp_dir.path().to_path_buf()],
+            &["*.jsonl"],
+        );
+        assert!(detected.is_valid);
+        assert_eq!(detected.estimated_sessions, 2);
+        assert!(detected.newest_modified.is_some());
+
+        let imported = AutoDetectService::file_hash(&nested.join("a.jsonl")).unwrap();
+        let known = HashSet::from([imported]);
+        assert_eq!(
+            AutoDetectService::count_unimported(&detected.files, &known),
+            1
+        );
+    }
+
+    #[test]
+    fn test_new_sessions_message() {
+        let mut claude = DetectedProvider::from_scan(Provider::ClaudeCode, vec![], &["*.jsonl"]);
+        let mut codex = DetectedProvider::from_scan(Provider::Codex, vec![], &["*.jsonl"]);
+        assert_eq!(
+            AutoDetectService::new_sessions_message(&[claude.clone()]),
+            None
+        );
+
+        claude.unimported_sessions = Some(37);
+        codex.unimported_sessions = Some(1);
+        assert_eq!(
+            AutoDetectService::new_sessions_message(&[claude, codex]).as_deref(),
+            Some("You have 37 new Claude Code sessions and 1 new Codex session")
+        );
+    }
+
     #[test]
     fn test_scan_all() {
         let detected = AutoDetectService::scan_all();
@@ -0,0 +1,360 @@
+//! User-level service running the watch daemon in the background
+//!
+//! `retrochat watch install-service` writes a systemd user unit (Linux) or a
+//! launchd agent (macOS) that runs `retrochat sync <providers> --watch` at
+//! login and restarts it if it exits. Variables that change where retrochat
+//! looks for data are copied from the installing shell, because services do
+//! not inherit it. Secrets are not: an encrypted database is opened with the
+//! passphrase from the OS keychain.
+
+use anyhow::{bail, Context, Result};
+use clap::ValueEnum;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+use crate::database::config::get_config_dir;
+use crate::env;
+use crate::models::Provider;
+
+/// systemd unit file name
+pub const SYSTEMD_UNIT_NAME: &str = "retrochat-watch.service";
+
+/// launchd job label, also the plist file name
+pub const LAUNCHD_LABEL: &str = "com.wafflestudio.retrochat.watch";
+
+/// Variables copied into the service environment when set
+const FORWARDED_ENV: &[&str] = &[
+    env::database::RETROCHAT_DB,
+    env::providers::CLAUDE_DIRS,
+    env::providers::GEMINI_DIRS,
+    env::providers::CODEX_DIRS,
+    env::logging::LOG_LEVEL,
+    env::logging::LOG_FORMAT,
+    env::logging::LOG_FILE,
+    "PATH",
+];
+
+/// Service manager of the current platform
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceManager {
+    Systemd,
+    Launchd,
+}
+
+impl ServiceManager {
+    /// The service manager user services are installed with on this platform
+    pub fn current() -> Result<Self> {
+        if cfg!(target_os = "macos") {
+            Ok(Self::Launchd)
+        } else if cfg!(target_os = "linux") {
+            Ok(Self::Systemd)
+        } else {
+            bail!("Installing a service is only supported on Linux (systemd) and macOS (launchd)")
+        }
+    }
+
+    /// Where the unit file or plist is written
+    pub fn service_file_path(&self) -> Result<PathBuf> {
+        let home = dirs::home_dir().context("Could not find home directory")?;
+        Ok(match self {
+            Self::Systemd => dirs::config_dir()
+                .unwrap_or_else(|| home.join(".config"))
+                .join("systemd")
+                .join("user")
+                .join(SYSTEMD_UNIT_NAME),
+            Self::Launchd => home
+                .join("Library")
+                .join("LaunchAgents")
+                .join(format!("{LAUNCHD_LABEL}.plist")),
+        })
+    }
+}
+
+impl std::fmt::Display for ServiceManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Systemd => write!(f, "systemd"),
+            Self::Launchd => write!(f, "launchd"),
+        }
+    }
+}
+
+/// What the service runs
+#[derive(Debug, Clone, PartialEq)]
+pub struct WatchServiceSpec {
+    pub program: PathBuf,
+    pub args: Vec<String>,
+    pub env: Vec<(String, String)>,
+    /// Output file for launchd, which has no journal
+    pub log_path: PathBuf,
+}
+
+impl WatchServiceSpec {
+    /// Service running `program sync <providers> --watch` with the current
+    /// process's forwarded variables
+    pub fn new(program: PathBuf, providers: &[Provider]) -> Result<Self> {
+        let mut args = vec!["sync".to_string()];
+        if providers.is_empty() {
+            args.push("all".to_string());
+        }
+        args.extend(
+            providers
+                .iter()
+                .filter_map(|p| p.to_possible_value())
+                .map(|v| v.get_name().to_string()),
+        );
+        args.push("--watch".to_string());
+
+        let env = FORWARDED_ENV
+            .iter()
+            .filter_map(|name| {
+                std::env::var(name)
+                    .ok()
+                    .filter(|value| !value.is_empty())
+                    .map(|value| (name.to_string(), value))
+            })
+            .collect();
+
+        Ok(Self {
+            program,
+            args,
+            env,
+            log_path: get_config_dir()?.join("logs").join("watch-service.log"),
+        })
+    }
+
+    /// Contents of the systemd user unit
+    pub fn systemd_unit(&self) -> String {
+        let exec = std::iter::once(self.program.to_string_lossy().to_string())
+            .chain(self.args.iter().cloned())
+            .map(|arg| systemd_quote(&arg))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let mut unit = String::from(
+            "[Unit]\n\
+             Description=retrochat watch daemon (imports new chat sessions)\n\
+             \n\
+             [Service]\n\
+             Type=simple\n",
+        );
+        unit.push_str(&format!("ExecStart={exec}\n"));
+        for (name, value) in &self.env {
+            unit.push_str(&format!(
+                "Environment={}\n",
+                systemd_quote(&format!("{name}={value}"))
+            ));
+        }
+        unit.push_str(
+            "Environment=NO_COLOR=1\n\
+             Restart=on-failure\n\
+             RestartSec=10\n\
+             \n\
+             [Install]\n\
+             WantedBy=default.target\n",
+        );
+        unit
+    }
+
+    /// Contents of the launchd agent plist
+    pub fn launchd_plist(&self) -> String {
+        let mut plist = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n\
+             <plist version=\"1.0\">\n\
+             <dict>\n",
+        );
+        plist.push_str(&format!(
+            "    <key>Label</key>\n    <string>{LAUNCHD_LABEL}</string>\n"
+        ));
+        plist.push_str("    <key>ProgramArguments</key>\n    <array>\n");
+        for arg in std::iter::once(self.program.to_string_lossy().to_string())
+            .chain(self.args.iter().cloned())
+        {
+            plist.push_str(&format!("        <string>{}</string>\n", xml_escape(&arg)));
+        }
+        plist.push_str("    </array>\n");
+        plist.push_str("    <key>EnvironmentVariables</key>\n    <dict>\n");
+        for (name, value) in self
+            .env
+            .iter()
+            .map(|(n, v)| (n.as_str(), v.as_str()))
+            .chain(std::iter::once(("NO_COLOR", "1")))
+        {
+            plist.push_str(&format!(
+                "        <key>{}</key>\n        <string>{}</string>\n",
+                xml_escape(name),
+                xml_escape(value)
+            ));
+        }
+        plist.push_str("    </dict>\n");
+        let log_path = xml_escape(&self.log_path.to_string_lossy());
+        plist.push_str("    <key>RunAtLoad</key>\n    <true/>\n");
+        // Restart after a crash, not after a clean exit
+        plist.push_str(
+            "    <key>KeepAlive</key>\n    <dict>\n        <key>SuccessfulExit</key>\n        <false/>\n    </dict>\n",
+        );
+        for key in ["StandardOutPath", "StandardErrorPath"] {
+            plist.push_str(&format!(
+                "    <key>{key}</key>\n    <string>{log_path}</string>\n"
+            ));
+        }
+        plist.push_str("</dict>\n</plist>\n");
+        plist
+    }
+}
+
+/// Write the service file and start the service; returns the file written
+pub fn install(manager: ServiceManager, spec: &WatchServiceSpec) -> Result<PathBuf> {
+    let path = manager.service_file_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+
+    match manager {
+        ServiceManager::Systemd => {
+            fs::write(&path, spec.systemd_unit())
+                .with_context(|| format!("Failed to write unit file: {}", path.display()))?;
+            run("systemctl", &["--user", "daemon-reload"])?;
+            run(
+                "systemctl",
+                &["--user", "enable", "--now", SYSTEMD_UNIT_NAME],
+            )?;
+            // Pick up a changed unit when the service was already running
+            run("systemctl", &["--user", "restart", SYSTEMD_UNIT_NAME])?;
+        }
+        ServiceManager::Launchd => {
+            if let Some(parent) = spec.log_path.parent() {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+            }
+            let path_str = path.to_string_lossy().to_string();
+            if path.exists() {
+                // Unload the previous definition; it may not be loaded
+                let _ = run("launchctl", &["unload", &path_str]);
+            }
+            fs::write(&path, spec.launchd_plist())
+                .with_context(|| format!("Failed to write plist: {}", path.display()))?;
+            run("launchctl", &["load", "-w", &path_str])?;
+        }
+    }
+
+    Ok(path)
+}
+
+/// Stop the service and remove its file; returns the file removed, if any
+pub fn uninstall(manager: ServiceManager) -> Result<Option<PathBuf>> {
+    let path = manager.service_file_path()?;
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    match manager {
+        ServiceManager::Systemd => {
+            run(
+                "systemctl",
+                &["--user", "disable", "--now", SYSTEMD_UNIT_NAME],
+            )?;
+            fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove unit file: {}", path.display()))?;
+            run("systemctl", &["--user", "daemon-reload"])?;
+        }
+        ServiceManager::Launchd => {
+            run("launchctl", &["unload", "-w", &path.to_string_lossy()])?;
+            fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove plist: {}", path.display()))?;
+        }
+    }
+
+    Ok(Some(path))
+}
+
+fn run(program: &str, args: &[&str]) -> Result<()> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .with_context(|| format!("Failed to run {program}"))?;
+    if !output.status.success() {
+        bail!(
+            "{program} {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// Quote a word for a systemd `ExecStart=`/`Environment=` line
+fn systemd_quote(word: &str) -> String {
+    if !word.is_empty()
+        && !word
+            .chars()
+            .any(|c| c.is_whitespace() || matches!(c, '"' | '\'' | '\\' | '%' | '$'))
+    {
+        return word.to_string();
+    }
+    let escaped = word
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('%', "%%")
+        .replace('$', "$$");
+    format!("\"{escaped}\"")
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec() -> WatchServiceSpec {
+        WatchServiceSpec {
+            program: PathBuf::from("/opt/retro chat/retrochat"),
+            args: vec![
+                "sync".to_string(),
+                "claude".to_string(),
+                "--watch".to_string(),
+            ],
+            env: vec![("RETROCHAT_DB".to_string(), "/data/r&d.db".to_string())],
+            log_path: PathBuf::from("/home/me/.retrochat/logs/watch-service.log"),
+        }
+    }
+
+    #[test]
+    fn test_service_files() {
+        let unit = spec().systemd_unit();
+        assert!(unit.contains("ExecStart=\"/opt/retro chat/retrochat\" sync claude --watch\n"));
+        assert!(unit.contains("Environment=RETROCHAT_DB=/data/r&d.db\n"));
+        assert!(unit.contains("WantedBy=default.target"));
+
+        let plist = spec().launchd_plist();
+        assert!(plist.contains("<string>/opt/retro chat/retrochat</string>"));
+        assert!(plist.contains("<string>--watch</string>"));
+        assert!(
+            plist.contains("<key>RETROCHAT_DB</key>\n        <string>/data/r&amp;d.db</string>")
+        );
+        assert!(plist.contains(&format!("<string>{LAUNCHD_LABEL}</string>")));
+
+        assert_eq!(systemd_quote("100%"), "\"100%%\"");
+    }
+
+    #[test]
+    fn test_spec_arguments() {
+        let spec = WatchServiceSpec::new(PathBuf::from("retrochat"), &[]).unwrap();
+        assert_eq!(spec.args, vec!["sync", "all", "--watch"]);
+
+        let spec = WatchServiceSpec::new(
+            PathBuf::from("retrochat"),
+            &[Provider::ClaudeCode, Provider::Codex],
+        )
+        .unwrap();
+        assert_eq!(spec.args, vec!["sync", "claude", "codex", "--watch"]);
+    }
+}
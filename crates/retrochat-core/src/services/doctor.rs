@@ -0,0 +1,513 @@
+//! Self-diagnostics for `retrochat doctor`
+//!
+//! Each check looks at one part of the installation and, when something is
+//! off, says what to run to fix it. Checks never modify anything.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::database::{
+    DatabaseManager, EmbeddingKind, EmbeddingRepository, MigrationManager, ParserWarningRepository,
+};
+use crate::models::ProviderRegistry;
+use crate::services::embedding::configured_embedder;
+use crate::services::llm::diagnostics::diagnose_providers;
+
+/// Below this much free space, imports and migration backups may fail
+const LOW_DISK_SPACE_BYTES: u64 = 1024 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Ok,
+    Warning,
+    Error,
+}
+
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckStatus::Ok => write!(f, "ok"),
+            CheckStatus::Warning => write!(f, "warning"),
+            CheckStatus::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// Outcome of one check
+#[derive(Debug, Clone, Serialize)]
+pub struct DoctorCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+    /// What to run or change when the check did not pass
+    pub fix: Option<String>,
+}
+
+impl DoctorCheck {
+    fn ok(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: CheckStatus::Ok,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    fn warning(name: impl Into<String>, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: CheckStatus::Warning,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    fn error(name: impl Into<String>, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: CheckStatus::Error,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DoctorReport {
+    pub checks: Vec<DoctorCheck>,
+}
+
+impl DoctorReport {
+    pub fn has_errors(&self) -> bool {
+        self.checks.iter().any(|c| c.status == CheckStatus::Error)
+    }
+
+    pub fn count(&self, status: CheckStatus) -> usize {
+        self.checks.iter().filter(|c| c.status == status).count()
+    }
+}
+
+/// Runs every check against a database path
+pub struct DoctorService {
+    db_path: PathBuf,
+    probe_llm: bool,
+}
+
+impl DoctorService {
+    pub fn new(db_path: impl Into<PathBuf>) -> Self {
+        Self {
+            db_path: db_path.into(),
+            probe_llm: true,
+        }
+    }
+
+    /// Send a test prompt to each available LLM provider (on by default)
+    pub fn with_llm_probe(mut self, probe: bool) -> Self {
+        self.probe_llm = probe;
+        self
+    }
+
+    pub async fn run(&self) -> DoctorReport {
+        let mut report = DoctorReport::default();
+
+        if let Some(db_manager) = self.check_database_file(&mut report.checks).await {
+            report.checks.push(check_integrity(&db_manager).await);
+            let schema = check_schema(&db_manager).await;
+            let schema_ok = schema.status == CheckStatus::Ok;
+            report.checks.push(schema);
+            // The vector tables may not exist before migrating
+            if schema_ok {
+                report.checks.push(check_vector_index(&db_manager).await);
+                report.checks.push(check_parser_drift(&db_manager).await);
+            }
+        }
+
+        report.checks.extend(check_provider_directories());
+        report.checks.push(self.check_llm_providers().await);
+        report.checks.push(check_disk_space(&self.db_path));
+
+        report
+    }
+
+    async fn check_database_file(&self, checks: &mut Vec<DoctorCheck>) -> Option<DatabaseManager> {
+        let name = "Database";
+        let path = self.db_path.display();
+
+        if !self.db_path.exists() {
+            checks.push(DoctorCheck::warning(
+                name,
+                format!("No database at {path} yet"),
+                "Import your history with `retrochat sync` or `retrochat setup`",
+            ));
+            return None;
+        }
+
+        match std::fs::metadata(&self.db_path) {
+            Ok(metadata) if metadata.permissions().readonly() => {
+                checks.push(DoctorCheck::error(
+                    name,
+                    format!("{path} is read-only"),
+                    format!("Make it writable: chmod u+w {path}"),
+                ));
+                return None;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                checks.push(DoctorCheck::error(
+                    name,
+                    format!("Cannot read {path}: {e}"),
+                    format!("Check the permissions of {path}"),
+                ));
+                return None;
+            }
+        }
+
+        match DatabaseManager::open_without_migrations(&self.db_path).await {
+            Ok(db_manager) => {
+                let encrypted = if db_manager.is_encrypted() {
+                    " (encrypted)"
+                } else {
+                    ""
+                };
+                checks.push(DoctorCheck::ok(name, format!("{path}{encrypted}")));
+                Some(db_manager)
+            }
+            Err(e) => {
+                checks.push(DoctorCheck::error(
+                    name,
+                    format!("Cannot open {path}: {e:#}"),
+                    "Restore a backup with `retrochat db restore`",
+                ));
+                None
+            }
+        }
+    }
+
+    async fn check_llm_providers(&self) -> DoctorCheck {
+        let name = "LLM providers";
+        let results = diagnose_providers(self.probe_llm).await;
+        let healthy: Vec<String> = results
+            .iter()
+            .filter(|d| d.is_healthy())
+            .map(|d| d.provider.to_string())
+            .collect();
+
+        if !healthy.is_empty() {
+            return DoctorCheck::ok(name, format!("Usable: {}", healthy.join(", ")));
+        }
+        let fix = results
+            .iter()
+            .find_map(|d| d.suggestion.clone())
+            .unwrap_or_else(|| "Run `retrochat analysis doctor` for details".to_string());
+        DoctorCheck::warning(
+            name,
+            "No provider is usable; summaries and analyses will fail",
+            fix,
+        )
+    }
+}
+
+async fn check_integrity(db_manager: &DatabaseManager) -> DoctorCheck {
+    let name = "Integrity";
+    let result: Result<Vec<String>, _> = sqlx::query_scalar("PRAGMA quick_check")
+        .fetch_all(db_manager.pool())
+        .await;
+
+    match result {
+        Ok(rows) if rows.len() == 1 && rows[0] == "ok" => {
+            DoctorCheck::ok(name, "quick_check passed")
+        }
+        Ok(rows) => DoctorCheck::error(
+            name,
+            format!(
+                "quick_check found {} problems, first: {}",
+                rows.len(),
+                rows.first().map(String::as_str).unwrap_or_default()
+            ),
+            "Restore a backup with `retrochat db restore`, or re-import with `retrochat sync`",
+        ),
+        Err(e) => DoctorCheck::error(
+            name,
+            format!("quick_check failed: {e}"),
+            "Restore a backup with `retrochat db restore`",
+        ),
+    }
+}
+
+async fn check_schema(db_manager: &DatabaseManager) -> DoctorCheck {
+    let name = "Schema";
+    let migrations = MigrationManager::new(db_manager.pool().clone());
+    let (current, status) = match (
+        migrations.get_current_version().await,
+        migrations.get_migration_status().await,
+    ) {
+        (Ok(current), Ok(status)) => (current, status),
+        (Err(e), _) | (_, Err(e)) => {
+            return DoctorCheck::error(
+                name,
+                format!("Cannot read migrations: {e:#}"),
+                "Run `retrochat db status` for details",
+            )
+        }
+    };
+    let latest = migrations.get_latest_version();
+
+    let pending = status.iter().filter(|s| !s.applied).count();
+    let unknown = status.iter().filter(|s| s.unknown).count();
+    let mismatched = status.iter().filter(|s| s.checksum_mismatch).count();
+    let version = format!("version {current} (latest {latest})");
+
+    if unknown > 0 {
+        DoctorCheck::error(
+            name,
+            format!("{version}; {unknown} migrations come from a newer RetroChat"),
+            "Upgrade RetroChat to use this database",
+        )
+    } else if pending > 0 {
+        DoctorCheck::warning(
+            name,
+            format!("{version}; {pending} migrations pending"),
+            "Apply them with `retrochat db migrate`",
+        )
+    } else if mismatched > 0 {
+        DoctorCheck::warning(
+            name,
+            format!("{version}; {mismatched} applied migrations differ from this build"),
+            "Run `retrochat db status --verbose` to see which",
+        )
+    } else {
+        DoctorCheck::ok(name, version)
+    }
+}
+
+async fn check_vector_index(db_manager: &DatabaseManager) -> DoctorCheck {
+    let name = "Vector index";
+    match inspect_vector_index(db_manager).await {
+        Ok(check) => check,
+        Err(e) => DoctorCheck::error(
+            name,
+            format!("Cannot inspect the index: {e:#}"),
+            "Rebuild it with `retrochat index --rebuild`",
+        ),
+    }
+}
+
+async fn inspect_vector_index(db_manager: &DatabaseManager) -> anyhow::Result<DoctorCheck> {
+    let name = "Vector index";
+    let repo = EmbeddingRepository::new(db_manager);
+    let embedder = configured_embedder()?;
+
+    let mut stale_kinds = Vec::new();
+    let mut malformed = 0;
+    let mut missing = 0;
+    for kind in EmbeddingKind::ALL {
+        let (total, indexed) = repo.coverage(kind).await?;
+        missing += total - indexed;
+        if let Some(state) = repo.get_state(kind).await? {
+            if state.embedder != embedder.name() || state.dimensions != embedder.dimensions() {
+                stale_kinds.push(format!(
+                    "{} vectors from {} ({} dims)",
+                    kind.as_str(),
+                    state.embedder,
+                    state.dimensions
+                ));
+            }
+            malformed += repo.count_malformed(kind, state.dimensions).await?;
+        }
+    }
+    let orphans = repo.count_orphans().await?;
+
+    if !stale_kinds.is_empty() {
+        return Ok(DoctorCheck::error(
+            name,
+            format!(
+                "{}, but {} ({} dims) is configured",
+                stale_kinds.join(", "),
+                embedder.name(),
+                embedder.dimensions()
+            ),
+            "Rebuild it with `retrochat index --rebuild`",
+        ));
+    }
+    if orphans > 0 || malformed > 0 {
+        return Ok(DoctorCheck::warning(
+            name,
+            format!("{orphans} orphaned and {malformed} malformed vectors"),
+            "Clean them up with `retrochat maintenance`",
+        ));
+    }
+    if missing > 0 {
+        return Ok(DoctorCheck::warning(
+            name,
+            format!("{missing} summaries are not indexed"),
+            "Index them with `retrochat index`",
+        ));
+    }
+    Ok(DoctorCheck::ok(
+        name,
+        "Consistent with the configured embedder",
+    ))
+}
+
+async fn check_parser_drift(db_manager: &DatabaseManager) -> DoctorCheck {
+    let name = "Log formats";
+    let warnings = match ParserWarningRepository::new(db_manager).list(None).await {
+        Ok(warnings) => warnings,
+        Err(e) => {
+            return DoctorCheck::error(
+                name,
+                format!("Cannot read parser warnings: {e:#}"),
+                "Run `retrochat db status` for details",
+            )
+        }
+    };
+    if warnings.is_empty() {
+        return DoctorCheck::ok(name, "Every imported field was recognized");
+    }
+
+    let mut providers: Vec<&str> = warnings.iter().map(|w| w.provider.as_str()).collect();
+    providers.dedup();
+    DoctorCheck::warning(
+        name,
+        format!(
+            "{} unrecognized fields or types in {} logs; some data may not be imported",
+            warnings.len(),
+            providers.join(", ")
+        ),
+        "Run `retrochat doctor --parsers` for details, and upgrade RetroChat",
+    )
+}
+
+/// Import directories of each provider, with the variable that overrides them
+fn check_provider_directories() -> Vec<DoctorCheck> {
+    let registry = ProviderRegistry::global();
+    registry
+        .all_configs_with_names()
+        .into_iter()
+        .map(|(config, provider_name)| {
+            let name = format!("{provider_name} data");
+            let env_hint = config
+                .env_var_name()
+                .map(|var| format!("set {var} to its data directories (colon-separated)"))
+                .unwrap_or_else(|| "check where it stores its sessions".to_string());
+            check_directories(&name, &config.get_import_directories(), &env_hint)
+        })
+        .collect()
+}
+
+fn check_directories(name: &str, dirs: &[String], env_hint: &str) -> DoctorCheck {
+    let mut found = Vec::new();
+    for dir in dirs {
+        let path = Path::new(dir);
+        if !path.exists() {
+            continue;
+        }
+        if let Err(e) = std::fs::read_dir(path) {
+            return DoctorCheck::error(
+                name,
+                format!("{dir} is not readable: {e}"),
+                format!("Grant read access: chmod -R u+rX {dir}"),
+            );
+        }
+        found.push(dir.as_str());
+    }
+
+    if found.is_empty() {
+        DoctorCheck::warning(
+            name,
+            format!("Not found in {}", dirs.join(", ")),
+            format!("If it is installed, {env_hint}"),
+        )
+    } else {
+        DoctorCheck::ok(name, found.join(", "))
+    }
+}
+
+fn check_disk_space(db_path: &Path) -> DoctorCheck {
+    let name = "Disk space";
+    // The database may not exist yet; measure the closest existing ancestor
+    let Some(dir) = db_path.ancestors().find(|p| p.exists()) else {
+        return DoctorCheck::ok(name, "Not checked");
+    };
+
+    match free_space(dir) {
+        Some(free) if free < LOW_DISK_SPACE_BYTES => DoctorCheck::warning(
+            name,
+            format!("{} free on {}", format_bytes(free), dir.display()),
+            "Free up space; imports and migration backups need room to write",
+        ),
+        Some(free) => DoctorCheck::ok(
+            name,
+            format!("{} free on {}", format_bytes(free), dir.display()),
+        ),
+        None => DoctorCheck::ok(name, "Not checked on this platform"),
+    }
+}
+
+#[cfg(unix)]
+fn free_space(path: &Path) -> Option<u64> {
+    let stats = rustix::fs::statvfs(path).ok()?;
+    Some(stats.f_bavail.saturating_mul(stats.f_frsize))
+}
+
+#[cfg(not(unix))]
+fn free_space(_path: &Path) -> Option<u64> {
+    None
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const GB: f64 = 1024.0 * 1024.0 * 1024.0;
+    const MB: f64 = 1024.0 * 1024.0;
+    let bytes = bytes as f64;
+    if bytes >= GB {
+        format!("{:.1} GB", bytes / GB)
+    } else {
+        format!("{:.0} MB", bytes / MB)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_check_directories() {
+        let temp = TempDir::new().unwrap();
+        let existing = temp.path().to_string_lossy().to_string();
+        let missing = temp.path().join("missing").to_string_lossy().to_string();
+
+        let check = check_directories("Test data", &[missing.clone(), existing.clone()], "");
+        assert_eq!(check.status, CheckStatus::Ok);
+        assert_eq!(check.detail, existing);
+
+        let check = check_directories("Test data", &[missing], "set TEST_DIRS");
+        assert_eq!(check.status, CheckStatus::Warning);
+        assert!(check.fix.unwrap().contains("TEST_DIRS"));
+    }
+
+    #[tokio::test]
+    async fn test_database_checks() {
+        let temp = TempDir::new().unwrap();
+        let db_path = temp.path().join("retrochat.db");
+        let service = DoctorService::new(&db_path);
+
+        let mut checks = Vec::new();
+        assert!(service.check_database_file(&mut checks).await.is_none());
+        assert_eq!(checks[0].status, CheckStatus::Warning);
+
+        DatabaseManager::new(&db_path).await.unwrap();
+        let mut checks = Vec::new();
+        let db_manager = service.check_database_file(&mut checks).await.unwrap();
+        assert_eq!(checks[0].status, CheckStatus::Ok);
+        assert_eq!(check_integrity(&db_manager).await.status, CheckStatus::Ok);
+        assert_eq!(check_schema(&db_manager).await.status, CheckStatus::Ok);
+        assert_eq!(
+            check_parser_drift(&db_manager).await.status,
+            CheckStatus::Ok
+        );
+    }
+}
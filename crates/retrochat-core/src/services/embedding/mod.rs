@@ -0,0 +1,188 @@
+pub mod provider;
+pub mod remote;
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+pub use provider::{configured_embedder, create_embedder, EmbeddingProvider};
+pub use remote::ApiEmbedder;
+
+/// Default number of dimensions for the local hashing embedder
+pub const DEFAULT_EMBEDDING_DIMENSIONS: usize = 256;
+
+/// Produces fixed-size vector representations of text for similarity comparisons
+#[async_trait]
+pub trait Embedder: Send + Sync {
+    /// Identifier of the embedding backend (stored alongside vectors)
+    fn name(&self) -> &str;
+
+    /// Number of dimensions in every produced vector
+    fn dimensions(&self) -> usize;
+
+    /// Embed a single piece of text
+    async fn embed(&self, text: &str) -> Result<Vec<f32>>;
+
+    /// Embed multiple texts, preserving input order
+    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let mut vectors = Vec::with_capacity(texts.len());
+        for text in texts {
+            vectors.push(self.embed(text).await?);
+        }
+        Ok(vectors)
+    }
+}
+
+/// Local, dependency-free embedder based on feature hashing
+///
+/// Tokens (lowercased words) and adjacent word bigrams are hashed into a fixed
+/// number of buckets with a sign bit, then the vector is L2-normalized. This is
+/// not semantic, but it is deterministic, fast and good at spotting prompts that
+/// are reworded copies of each other.
+#[derive(Debug, Clone)]
+pub struct HashingEmbedder {
+    dimensions: usize,
+}
+
+impl HashingEmbedder {
+    pub fn new() -> Self {
+        Self::with_dimensions(DEFAULT_EMBEDDING_DIMENSIONS)
+    }
+
+    pub fn with_dimensions(dimensions: usize) -> Self {
+        Self {
+            dimensions: dimensions.max(1),
+        }
+    }
+
+    /// Synchronous embedding used by the async trait implementation
+    pub fn embed_text(&self, text: &str) -> Vec<f32> {
+        let mut vector = vec![0.0f32; self.dimensions];
+        let tokens = tokenize(text);
+
+        for token in &tokens {
+            self.add_feature(&mut vector, token);
+        }
+        for pair in tokens.windows(2) {
+            self.add_feature(&mut vector, &format!("{} {}", pair[0], pair[1]));
+        }
+
+        normalize(&mut vector);
+        vector
+    }
+
+    fn add_feature(&self, vector: &mut [f32], feature: &str) {
+        let hash = fnv1a(feature.as_bytes());
+        let index = (hash % self.dimensions as u64) as usize;
+        let sign = if (hash >> 63) & 1 == 0 { 1.0 } else { -1.0 };
+        vector[index] += sign;
+    }
+}
+
+impl Default for HashingEmbedder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Embedder for HashingEmbedder {
+    fn name(&self) -> &str {
+        "hashing"
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        Ok(self.embed_text(text))
+    }
+}
+
+/// Cosine similarity between two vectors (0.0 when either is empty or zero)
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.is_empty() || a.len() != b.len() {
+        return 0.0;
+    }
+
+    let mut dot = 0.0f32;
+    let mut norm_a = 0.0f32;
+    let mut norm_b = 0.0f32;
+    for (x, y) in a.iter().zip(b.iter()) {
+        dot += x * y;
+        norm_a += x * x;
+        norm_b += y * y;
+    }
+
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+
+    dot / (norm_a.sqrt() * norm_b.sqrt())
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_lowercase())
+        .collect()
+}
+
+fn normalize(vector: &mut [f32]) {
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        for v in vector.iter_mut() {
+            *v /= norm;
+        }
+    }
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identical_texts_have_similarity_one() {
+        let embedder = HashingEmbedder::new();
+        let a = embedder.embed_text("Fix the failing login test");
+        let b = embedder.embed_text("fix the failing LOGIN test!");
+
+        assert!((cosine_similarity(&a, &b) - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_unrelated_texts_have_low_similarity() {
+        let embedder = HashingEmbedder::new();
+        let a = embedder.embed_text("Add pagination to the sessions list");
+        let b = embedder.embed_text("Why does the docker build fail on arm64?");
+
+        assert!(cosine_similarity(&a, &b) < 0.5);
+    }
+
+    #[test]
+    fn test_cosine_similarity_edge_cases() {
+        assert_eq!(cosine_similarity(&[], &[]), 0.0);
+        assert_eq!(cosine_similarity(&[1.0, 0.0], &[1.0]), 0.0);
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 0.0]), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_embed_batch_preserves_order() {
+        let embedder = HashingEmbedder::with_dimensions(32);
+        let texts = vec!["first prompt".to_string(), "second prompt".to_string()];
+        let vectors = embedder.embed_batch(&texts).await.unwrap();
+
+        assert_eq!(vectors.len(), 2);
+        assert_eq!(vectors[0], embedder.embed_text("first prompt"));
+        assert_eq!(vectors[1].len(), 32);
+    }
+}
@@ -0,0 +1,199 @@
+//! Embedding backend selection
+//!
+//! The `[embedding]` config section picks the backend used for the summary
+//! index. The local hashing embedder needs no network or model download;
+//! the API backends move the work off machines with weak CPUs in exchange
+//! for an API key.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use super::remote::ApiEmbedder;
+use super::{Embedder, HashingEmbedder};
+use crate::config::{Config, EmbeddingSettings};
+use crate::env::apis as env_apis;
+
+/// Embedding backend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum EmbeddingProvider {
+    /// Local feature-hashing embedder
+    #[default]
+    Local,
+    OpenAi,
+    Google,
+    Voyage,
+}
+
+impl EmbeddingProvider {
+    /// Model used when `embedding.model` is not set
+    pub fn default_model(&self) -> Option<&'static str> {
+        match self {
+            EmbeddingProvider::Local => None,
+            EmbeddingProvider::OpenAi => Some("text-embedding-3-small"),
+            EmbeddingProvider::Google => Some("text-embedding-004"),
+            EmbeddingProvider::Voyage => Some("voyage-3.5-lite"),
+        }
+    }
+
+    /// Environment variable holding the API key
+    pub fn api_key_env(&self) -> Option<&'static str> {
+        match self {
+            EmbeddingProvider::Local => None,
+            EmbeddingProvider::OpenAi => Some(env_apis::OPENAI_API_KEY),
+            EmbeddingProvider::Google => Some(env_apis::GOOGLE_AI_API_KEY),
+            EmbeddingProvider::Voyage => Some(env_apis::VOYAGE_API_KEY),
+        }
+    }
+
+    /// API key with priority: environment variable > config file
+    fn api_key(&self, config: &Config) -> Option<String> {
+        if let Some(key) = self
+            .api_key_env()
+            .and_then(|var| std::env::var(var).ok())
+            .filter(|key| !key.is_empty())
+        {
+            return Some(key);
+        }
+        match self {
+            EmbeddingProvider::Local => None,
+            EmbeddingProvider::OpenAi => config.api.openai_api_key.clone(),
+            EmbeddingProvider::Google => config.api.google_ai_api_key.clone(),
+            EmbeddingProvider::Voyage => config.api.voyage_api_key.clone(),
+        }
+    }
+}
+
+impl std::str::FromStr for EmbeddingProvider {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('_', "-").as_str() {
+            "local" | "hashing" => Ok(EmbeddingProvider::Local),
+            "openai" | "open-ai" => Ok(EmbeddingProvider::OpenAi),
+            "google" | "google-ai" | "gemini" => Ok(EmbeddingProvider::Google),
+            "voyage" | "voyage-ai" | "voyageai" => Ok(EmbeddingProvider::Voyage),
+            _ => Err(format!(
+                "Unknown embedding provider: {s}. Valid options: local, openai, google, voyage"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for EmbeddingProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EmbeddingProvider::Local => write!(f, "local"),
+            EmbeddingProvider::OpenAi => write!(f, "openai"),
+            EmbeddingProvider::Google => write!(f, "google"),
+            EmbeddingProvider::Voyage => write!(f, "voyage"),
+        }
+    }
+}
+
+/// Vector size of well-known models at their default output size
+fn known_dimensions(model: &str) -> Option<usize> {
+    match model {
+        "text-embedding-3-small" | "text-embedding-ada-002" => Some(1536),
+        "text-embedding-3-large" | "gemini-embedding-001" => Some(3072),
+        "text-embedding-004" | "embedding-001" => Some(768),
+        "voyage-3.5" | "voyage-3.5-lite" | "voyage-3" | "voyage-3-large" | "voyage-code-3" => {
+            Some(1024)
+        }
+        "voyage-3-lite" => Some(512),
+        _ => None,
+    }
+}
+
+/// Build the embedder selected by `config`
+pub fn create_embedder(config: &Config) -> Result<Arc<dyn Embedder>> {
+    let settings: &EmbeddingSettings = &config.embedding;
+    let provider = match settings.provider.as_deref() {
+        Some(name) => name
+            .parse::<EmbeddingProvider>()
+            .map_err(anyhow::Error::msg)?,
+        None => EmbeddingProvider::default(),
+    };
+
+    let Some(default_model) = provider.default_model() else {
+        return Ok(Arc::new(match settings.dimensions {
+            Some(dimensions) => HashingEmbedder::with_dimensions(dimensions),
+            None => HashingEmbedder::new(),
+        }));
+    };
+
+    let model = settings
+        .model
+        .clone()
+        .unwrap_or_else(|| default_model.to_string());
+    let dimensions = settings
+        .dimensions
+        .or_else(|| known_dimensions(&model))
+        .with_context(|| format!("Unknown vector size for {model}; set embedding.dimensions"))?;
+    let api_key = provider.api_key(config).with_context(|| {
+        format!(
+            "The {provider} embedding provider needs an API key; set {} or `retrochat config set {provider}-api-key <key>`",
+            provider.api_key_env().unwrap_or_default()
+        )
+    })?;
+
+    Ok(Arc::new(ApiEmbedder::new(
+        provider,
+        model,
+        dimensions,
+        settings.dimensions.is_some(),
+        api_key,
+    )?))
+}
+
+/// Build the embedder selected in the config file
+pub fn configured_embedder() -> Result<Arc<dyn Embedder>> {
+    create_embedder(&Config::load()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::embedding::DEFAULT_EMBEDDING_DIMENSIONS;
+
+    #[test]
+    fn test_provider_parsing() {
+        assert_eq!(
+            "OpenAI".parse::<EmbeddingProvider>(),
+            Ok(EmbeddingProvider::OpenAi)
+        );
+        assert_eq!(
+            "voyage_ai".parse::<EmbeddingProvider>(),
+            Ok(EmbeddingProvider::Voyage)
+        );
+        assert_eq!(
+            "hashing".parse::<EmbeddingProvider>(),
+            Ok(EmbeddingProvider::Local)
+        );
+        assert!("fastembed".parse::<EmbeddingProvider>().is_err());
+        assert_eq!(EmbeddingProvider::Google.to_string(), "google");
+    }
+
+    #[test]
+    fn test_create_embedder_from_config() {
+        let mut config = Config::default();
+        let local = create_embedder(&config).unwrap();
+        assert_eq!(local.name(), "hashing");
+        assert_eq!(local.dimensions(), DEFAULT_EMBEDDING_DIMENSIONS);
+
+        config.embedding.provider = Some("voyage".to_string());
+        config.api.voyage_api_key = Some("pa-test".to_string());
+        let voyage = create_embedder(&config).unwrap();
+        assert_eq!(voyage.name(), "voyage/voyage-3.5-lite");
+        assert_eq!(voyage.dimensions(), 1024);
+
+        config.embedding.dimensions = Some(256);
+        assert_eq!(create_embedder(&config).unwrap().dimensions(), 256);
+
+        config.embedding.model = Some("voyage-experimental".to_string());
+        config.embedding.dimensions = None;
+        let err = create_embedder(&config).err().unwrap();
+        assert!(err.to_string().contains("embedding.dimensions"));
+    }
+}
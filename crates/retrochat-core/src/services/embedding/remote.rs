@@ -0,0 +1,288 @@
+//! Embedders backed by hosted embedding APIs
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+use super::provider::EmbeddingProvider;
+use super::Embedder;
+
+const OPENAI_EMBEDDINGS_URL: &str = "https://api.openai.com/v1/embeddings";
+const GOOGLE_API_BASE_URL: &str = "https://generativelanguage.googleapis.com/v1beta";
+const VOYAGE_EMBEDDINGS_URL: &str = "https://api.voyageai.com/v1/embeddings";
+
+/// Texts sent per request; below the smallest provider limit (Google's 100)
+const API_BATCH_SIZE: usize = 96;
+
+const REQUEST_TIMEOUT_SECS: u64 = 60;
+
+/// Embedder calling the OpenAI, Google or Voyage embeddings API
+pub struct ApiEmbedder {
+    provider: EmbeddingProvider,
+    model: String,
+    /// `provider/model`, stored with the vectors so a model switch re-embeds them
+    name: String,
+    dimensions: usize,
+    /// Ask the API for `dimensions` instead of the model's default size
+    request_dimensions: bool,
+    api_key: String,
+    client: Client,
+}
+
+impl ApiEmbedder {
+    pub fn new(
+        provider: EmbeddingProvider,
+        model: String,
+        dimensions: usize,
+        request_dimensions: bool,
+        api_key: String,
+    ) -> Result<Self> {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            .build()
+            .context("Failed to create HTTP client")?;
+
+        Ok(Self {
+            name: format!("{provider}/{model}"),
+            provider,
+            model,
+            dimensions,
+            request_dimensions,
+            api_key,
+            client,
+        })
+    }
+
+    fn request_body(&self, texts: &[String]) -> Value {
+        let dimensions = self.request_dimensions.then_some(self.dimensions);
+        match self.provider {
+            EmbeddingProvider::Google => {
+                let requests: Vec<Value> = texts
+                    .iter()
+                    .map(|text| {
+                        let mut request = json!({
+                            "model": format!("models/{}", self.model),
+                            "content": { "parts": [{ "text": text }] },
+                            "taskType": "SEMANTIC_SIMILARITY",
+                        });
+                        if let Some(dimensions) = dimensions {
+                            request["outputDimensionality"] = json!(dimensions);
+                        }
+                        request
+                    })
+                    .collect();
+                json!({ "requests": requests })
+            }
+            EmbeddingProvider::Voyage => {
+                let mut body = json!({
+                    "model": self.model,
+                    "input": texts,
+                    "input_type": "document",
+                });
+                if let Some(dimensions) = dimensions {
+                    body["output_dimension"] = json!(dimensions);
+                }
+                body
+            }
+            EmbeddingProvider::OpenAi | EmbeddingProvider::Local => {
+                let mut body = json!({ "model": self.model, "input": texts });
+                if let Some(dimensions) = dimensions {
+                    body["dimensions"] = json!(dimensions);
+                }
+                body
+            }
+        }
+    }
+
+    async fn embed_chunk(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let request = match self.provider {
+            EmbeddingProvider::Google => self
+                .client
+                .post(format!(
+                    "{GOOGLE_API_BASE_URL}/models/{}:batchEmbedContents",
+                    self.model
+                ))
+                .header("x-goog-api-key", &self.api_key),
+            EmbeddingProvider::Voyage => self
+                .client
+                .post(VOYAGE_EMBEDDINGS_URL)
+                .bearer_auth(&self.api_key),
+            EmbeddingProvider::OpenAi | EmbeddingProvider::Local => self
+                .client
+                .post(OPENAI_EMBEDDINGS_URL)
+                .bearer_auth(&self.api_key),
+        };
+
+        let response = request
+            .json(&self.request_body(texts))
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach the {} embeddings API", self.provider))?;
+
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .context("Failed to read embeddings response")?;
+        if !status.is_success() {
+            let detail: String = body.chars().take(300).collect();
+            if status.as_u16() == 401 || status.as_u16() == 403 {
+                anyhow::bail!(
+                    "The {} API key was rejected ({status}): {detail}",
+                    self.provider
+                );
+            }
+            anyhow::bail!(
+                "{} embeddings request failed ({status}): {detail}",
+                self.provider
+            );
+        }
+
+        let vectors = parse_response(self.provider, &body)?;
+        if vectors.len() != texts.len() {
+            anyhow::bail!(
+                "{} returned {} embeddings for {} texts",
+                self.provider,
+                vectors.len(),
+                texts.len()
+            );
+        }
+        if let Some(vector) = vectors.iter().find(|v| v.len() != self.dimensions) {
+            anyhow::bail!(
+                "{} returned {}-dimensional vectors, expected {}; set embedding.dimensions to match the model",
+                self.name,
+                vector.len(),
+                self.dimensions
+            );
+        }
+
+        Ok(vectors)
+    }
+}
+
+#[async_trait]
+impl Embedder for ApiEmbedder {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        let mut vectors = self.embed_chunk(&[text.to_string()]).await?;
+        Ok(vectors.remove(0))
+    }
+
+    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let mut vectors = Vec::with_capacity(texts.len());
+        for chunk in texts.chunks(API_BATCH_SIZE) {
+            vectors.extend(self.embed_chunk(chunk).await?);
+        }
+        Ok(vectors)
+    }
+}
+
+/// OpenAI and Voyage response body
+#[derive(Deserialize)]
+struct DataResponse {
+    data: Vec<DataItem>,
+}
+
+#[derive(Deserialize)]
+struct DataItem {
+    embedding: Vec<f32>,
+    #[serde(default)]
+    index: usize,
+}
+
+/// Google `batchEmbedContents` response body
+#[derive(Deserialize)]
+struct GoogleResponse {
+    embeddings: Vec<GoogleEmbedding>,
+}
+
+#[derive(Deserialize)]
+struct GoogleEmbedding {
+    values: Vec<f32>,
+}
+
+/// Vectors from a response body, in input order
+fn parse_response(provider: EmbeddingProvider, body: &str) -> Result<Vec<Vec<f32>>> {
+    match provider {
+        EmbeddingProvider::Google => {
+            let response: GoogleResponse =
+                serde_json::from_str(body).context("Invalid Google embeddings response")?;
+            Ok(response.embeddings.into_iter().map(|e| e.values).collect())
+        }
+        _ => {
+            let mut response: DataResponse = serde_json::from_str(body)
+                .with_context(|| format!("Invalid {provider} embeddings response"))?;
+            response.data.sort_by_key(|item| item.index);
+            Ok(response
+                .data
+                .into_iter()
+                .map(|item| item.embedding)
+                .collect())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_response_orders_by_index() {
+        let body = r#"{"object": "list", "data": [
+            {"object": "embedding", "index": 1, "embedding": [0.5, 0.5]},
+            {"object": "embedding", "index": 0, "embedding": [1.0, 0.0]}
+        ], "model": "text-embedding-3-small"}"#;
+        assert_eq!(
+            parse_response(EmbeddingProvider::OpenAi, body).unwrap(),
+            vec![vec![1.0, 0.0], vec![0.5, 0.5]]
+        );
+
+        let google = r#"{"embeddings": [{"values": [0.25, -0.75]}]}"#;
+        assert_eq!(
+            parse_response(EmbeddingProvider::Google, google).unwrap(),
+            vec![vec![0.25, -0.75]]
+        );
+
+        assert!(parse_response(EmbeddingProvider::Voyage, "{}").is_err());
+    }
+
+    #[test]
+    fn test_request_body_per_provider() {
+        let texts = vec!["fix the build".to_string()];
+
+        let google = ApiEmbedder::new(
+            EmbeddingProvider::Google,
+            "text-embedding-004".to_string(),
+            256,
+            true,
+            "key".to_string(),
+        )
+        .unwrap();
+        let body = google.request_body(&texts);
+        assert_eq!(body["requests"][0]["model"], "models/text-embedding-004");
+        assert_eq!(body["requests"][0]["outputDimensionality"], 256);
+        assert_eq!(google.name(), "google/text-embedding-004");
+
+        let openai = ApiEmbedder::new(
+            EmbeddingProvider::OpenAi,
+            "text-embedding-3-small".to_string(),
+            1536,
+            false,
+            "key".to_string(),
+        )
+        .unwrap();
+        let body = openai.request_body(&texts);
+        assert_eq!(body["input"][0], "fix the build");
+        assert!(body.get("dimensions").is_none());
+    }
+}
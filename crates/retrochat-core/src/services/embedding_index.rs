@@ -0,0 +1,562 @@
+//! Incremental embedding index over turn and session summaries
+//!
+//! Summaries are embedded once and stored with a hash of the embedded text,
+//! so indexing only does work for summaries that are new or were
+//! regenerated, and drops vectors whose summary is gone. Switching to an
+//! embedder with a different name or dimension count invalidates the stored
+//! vectors of every kind.
+
+use anyhow::{Context, Result as AnyhowResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::embedding::{configured_embedder, Embedder, HashingEmbedder};
+use super::session_similarity::summary_document;
+use crate::database::{
+    DatabaseManager, EmbeddingKind, EmbeddingRepository, SessionSummaryRepository,
+    TurnSummaryRepository, VectorIndexState,
+};
+use crate::models::TurnSummary;
+
+/// Summaries embedded per `embed_batch` call
+const EMBED_BATCH_SIZE: usize = 64;
+
+/// Session summaries loaded per page while indexing
+const SUMMARY_PAGE_SIZE: i64 = 1000;
+
+/// What an indexing run did
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexReport {
+    /// Summaries embedded because they were new or changed
+    pub embedded: usize,
+    /// Summaries whose stored vector was still current
+    pub unchanged: usize,
+    /// Vectors dropped because their summary no longer exists
+    pub removed: usize,
+}
+
+impl IndexReport {
+    fn add(&mut self, other: IndexReport) {
+        self.embedded += other.embedded;
+        self.unchanged += other.unchanged;
+        self.removed += other.removed;
+    }
+}
+
+/// How much of one kind of summary is indexed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexCoverage {
+    pub kind: EmbeddingKind,
+    pub indexed: i64,
+    pub total: i64,
+    pub embedder: Option<String>,
+    pub dimensions: Option<usize>,
+    pub last_indexed_at: Option<DateTime<Utc>>,
+}
+
+impl IndexCoverage {
+    pub fn percentage(&self) -> f64 {
+        if self.total == 0 {
+            100.0
+        } else {
+            self.indexed as f64 / self.total as f64 * 100.0
+        }
+    }
+}
+
+/// What a vector store maintenance run did
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MaintenanceReport {
+    /// Vectors dropped because their session or summary was deleted
+    pub orphans_removed: u64,
+    /// Vectors dropped because their size did not match the index
+    pub malformed_removed: u64,
+    /// Bytes returned to the filesystem by compaction
+    pub reclaimed_bytes: i64,
+}
+
+/// Space taken by the stored vectors of one kind
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VectorStorage {
+    pub kind: EmbeddingKind,
+    pub vectors: i64,
+    pub bytes: i64,
+}
+
+/// Text to embed for one summary
+struct Document {
+    source_id: String,
+    session_id: String,
+    text: String,
+}
+
+/// Service keeping summary embeddings in step with the summaries
+pub struct EmbeddingIndexService {
+    embedding_repo: EmbeddingRepository,
+    turn_summary_repo: TurnSummaryRepository,
+    session_summary_repo: SessionSummaryRepository,
+    embedder: Arc<dyn Embedder>,
+}
+
+impl EmbeddingIndexService {
+    pub fn new(db: &DatabaseManager) -> Self {
+        Self {
+            embedding_repo: EmbeddingRepository::new(db),
+            turn_summary_repo: TurnSummaryRepository::new(db),
+            session_summary_repo: SessionSummaryRepository::new(db),
+            embedder: Arc::new(HashingEmbedder::new()),
+        }
+    }
+
+    /// Index with the embedder selected in the `[embedding]` config section
+    pub fn from_config(db: &DatabaseManager) -> AnyhowResult<Self> {
+        Ok(Self::new(db).with_embedder(configured_embedder()?))
+    }
+
+    pub fn with_embedder(mut self, embedder: Arc<dyn Embedder>) -> Self {
+        self.embedder = embedder;
+        self
+    }
+
+    /// Embed every new or changed summary and drop vectors of deleted ones
+    pub async fn index_pending(&self) -> AnyhowResult<IndexReport> {
+        let mut report = IndexReport::default();
+        for kind in EmbeddingKind::ALL {
+            let documents = self.load_documents(kind, None).await?;
+            report.add(self.index_documents(kind, documents, None).await?);
+        }
+        Ok(report)
+    }
+
+    /// Like [`index_pending`](Self::index_pending), limited to one session's summaries
+    pub async fn index_session(&self, session_id: &Uuid) -> AnyhowResult<IndexReport> {
+        let mut report = IndexReport::default();
+        for kind in EmbeddingKind::ALL {
+            let documents = self.load_documents(kind, Some(session_id)).await?;
+            let scope = session_id.to_string();
+            report.add(self.index_documents(kind, documents, Some(&scope)).await?);
+        }
+        Ok(report)
+    }
+
+    /// Drop every stored vector and embed all summaries again
+    pub async fn rebuild(&self) -> AnyhowResult<IndexReport> {
+        let now = Utc::now();
+        for kind in EmbeddingKind::ALL {
+            self.embedding_repo.clear(kind).await?;
+            self.embedding_repo
+                .save_state(&VectorIndexState {
+                    kind,
+                    embedder: self.embedder.name().to_string(),
+                    dimensions: self.embedder.dimensions(),
+                    last_indexed_at: None,
+                    last_rebuilt_at: Some(now),
+                })
+                .await?;
+        }
+        self.index_pending().await
+    }
+
+    /// Indexed and total summaries of each kind
+    pub async fn coverage(&self) -> AnyhowResult<Vec<IndexCoverage>> {
+        let mut coverage = Vec::new();
+        for kind in EmbeddingKind::ALL {
+            let (total, indexed) = self.embedding_repo.coverage(kind).await?;
+            let state = self.embedding_repo.get_state(kind).await?;
+            coverage.push(IndexCoverage {
+                kind,
+                indexed,
+                total,
+                embedder: state.as_ref().map(|s| s.embedder.clone()),
+                dimensions: state.as_ref().map(|s| s.dimensions),
+                last_indexed_at: state.and_then(|s| s.last_indexed_at),
+            });
+        }
+        Ok(coverage)
+    }
+
+    /// Drop orphaned and malformed vectors, then compact the database file
+    pub async fn maintain(&self) -> AnyhowResult<MaintenanceReport> {
+        let mut report = MaintenanceReport {
+            orphans_removed: self.embedding_repo.delete_orphans().await?,
+            ..Default::default()
+        };
+        for kind in EmbeddingKind::ALL {
+            if let Some(state) = self.embedding_repo.get_state(kind).await? {
+                report.malformed_removed += self
+                    .embedding_repo
+                    .delete_malformed(kind, state.dimensions)
+                    .await?;
+            }
+        }
+
+        let reclaimable = self.embedding_repo.reclaimable_bytes().await?;
+        if reclaimable > 0 {
+            self.embedding_repo.vacuum().await?;
+            report.reclaimed_bytes = reclaimable - self.embedding_repo.reclaimable_bytes().await?;
+        }
+
+        Ok(report)
+    }
+
+    /// Stored vectors and their size, by kind
+    pub async fn storage(&self) -> AnyhowResult<Vec<VectorStorage>> {
+        let mut storage = Vec::new();
+        for kind in EmbeddingKind::ALL {
+            let (vectors, bytes) = self.embedding_repo.storage(kind).await?;
+            storage.push(VectorStorage {
+                kind,
+                vectors,
+                bytes,
+            });
+        }
+        Ok(storage)
+    }
+
+    async fn load_documents(
+        &self,
+        kind: EmbeddingKind,
+        session_id: Option<&Uuid>,
+    ) -> AnyhowResult<Vec<Document>> {
+        match kind {
+            EmbeddingKind::Turn => {
+                let summaries = match session_id {
+                    Some(id) => self.turn_summary_repo.get_by_session(id).await?,
+                    None => self.turn_summary_repo.list_all().await?,
+                };
+                Ok(summaries
+                    .into_iter()
+                    .map(|s| Document {
+                        text: turn_document(&s),
+                        source_id: s.id,
+                        session_id: s.session_id,
+                    })
+                    .collect())
+            }
+            EmbeddingKind::Session => {
+                let summaries = match session_id {
+                    Some(id) => self
+                        .session_summary_repo
+                        .get_by_session(id)
+                        .await?
+                        .into_iter()
+                        .collect(),
+                    None => {
+                        let mut all = Vec::new();
+                        loop {
+                            let page = self
+                                .session_summary_repo
+                                .list_all(None, SUMMARY_PAGE_SIZE, all.len() as i64)
+                                .await?;
+                            let done = (page.len() as i64) < SUMMARY_PAGE_SIZE;
+                            all.extend(page);
+                            if done {
+                                break all;
+                            }
+                        }
+                    }
+                };
+                Ok(summaries
+                    .into_iter()
+                    .map(|s| Document {
+                        text: summary_document(&s),
+                        source_id: s.id,
+                        session_id: s.session_id,
+                    })
+                    .collect())
+            }
+        }
+    }
+
+    async fn index_documents(
+        &self,
+        kind: EmbeddingKind,
+        documents: Vec<Document>,
+        session_id: Option<&str>,
+    ) -> AnyhowResult<IndexReport> {
+        self.invalidate_if_embedder_changed(kind).await?;
+
+        let stored = self.embedding_repo.content_hashes(kind, session_id).await?;
+        let mut report = IndexReport::default();
+
+        let current: HashSet<String> = documents.iter().map(|d| d.source_id.clone()).collect();
+        let orphans: Vec<String> = stored
+            .keys()
+            .filter(|id| !current.contains(*id))
+            .cloned()
+            .collect();
+        if !orphans.is_empty() {
+            report.removed = self.embedding_repo.delete(kind, &orphans).await? as usize;
+        }
+
+        let pending: Vec<(Document, String)> = documents
+            .into_iter()
+            .map(|d| {
+                let hash = content_hash(&d.text);
+                (d, hash)
+            })
+            .filter(|(d, hash)| stored.get(&d.source_id) != Some(hash))
+            .collect();
+        report.unchanged = current.len() - pending.len();
+
+        for batch in pending.chunks(EMBED_BATCH_SIZE) {
+            let texts: Vec<String> = batch.iter().map(|(d, _)| d.text.clone()).collect();
+            let vectors = self
+                .embedder
+                .embed_batch(&texts)
+                .await
+                .with_context(|| format!("Failed to embed {kind} summaries"))?;
+            for ((document, hash), vector) in batch.iter().zip(vectors) {
+                self.embedding_repo
+                    .upsert(
+                        kind,
+                        &document.source_id,
+                        &document.session_id,
+                        hash,
+                        &vector,
+                    )
+                    .await?;
+            }
+            report.embedded += batch.len();
+        }
+
+        let mut state = self.current_state(kind).await?;
+        state.last_indexed_at = Some(Utc::now());
+        self.embedding_repo.save_state(&state).await?;
+
+        Ok(report)
+    }
+
+    /// Clear the vectors of `kind` if they came from a different embedder
+    async fn invalidate_if_embedder_changed(&self, kind: EmbeddingKind) -> AnyhowResult<()> {
+        let Some(state) = self.embedding_repo.get_state(kind).await? else {
+            return Ok(());
+        };
+        if state.embedder == self.embedder.name() && state.dimensions == self.embedder.dimensions()
+        {
+            return Ok(());
+        }
+
+        tracing::info!(
+            kind = %kind,
+            from = %state.embedder,
+            to = %self.embedder.name(),
+            "Embedder changed, re-indexing summaries"
+        );
+        self.embedding_repo.clear(kind).await?;
+        self.embedding_repo
+            .save_state(&VectorIndexState {
+                kind,
+                embedder: self.embedder.name().to_string(),
+                dimensions: self.embedder.dimensions(),
+                last_indexed_at: None,
+                last_rebuilt_at: Some(Utc::now()),
+            })
+            .await
+    }
+
+    async fn current_state(&self, kind: EmbeddingKind) -> AnyhowResult<VectorIndexState> {
+        Ok(self
+            .embedding_repo
+            .get_state(kind)
+            .await?
+            .unwrap_or_else(|| VectorIndexState {
+                kind,
+                embedder: self.embedder.name().to_string(),
+                dimensions: self.embedder.dimensions(),
+                last_indexed_at: None,
+                last_rebuilt_at: None,
+            }))
+    }
+}
+
+fn turn_document(summary: &TurnSummary) -> String {
+    let mut parts = vec![
+        summary.user_intent.clone(),
+        summary.assistant_action.clone(),
+        summary.summary.clone(),
+    ];
+    if let Some(topics) = &summary.key_topics {
+        parts.push(topics.join(" "));
+    }
+    if let Some(concepts) = &summary.code_concepts {
+        parts.push(concepts.join(" "));
+    }
+    parts.join("\n")
+}
+
+fn content_hash(text: &str) -> String {
+    hex::encode(Sha256::digest(text.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::GeneratedSessionSummary;
+
+    async fn insert_session(db: &DatabaseManager) -> Uuid {
+        let session_id = Uuid::new_v4();
+        sqlx::query(
+            r#"
+            INSERT INTO chat_sessions (id, provider, project_name, start_time, end_time, message_count, file_path, file_hash, state)
+            VALUES (?, 'Claude Code', NULL, '2024-01-01T00:00:00Z', '2024-01-01T01:00:00Z', 1, ?, ?, 'imported')
+            "#,
+        )
+        .bind(session_id.to_string())
+        .bind(format!("/test-{session_id}.jsonl"))
+        .bind(session_id.to_string())
+        .execute(db.pool())
+        .await
+        .unwrap();
+        session_id
+    }
+
+    fn turn(session_id: &Uuid, turn_number: i32, summary: &str) -> TurnSummary {
+        TurnSummary::new(
+            session_id.to_string(),
+            turn_number,
+            0,
+            1,
+            "Fix the build".to_string(),
+            "Edited Cargo.toml".to_string(),
+            summary.to_string(),
+            Utc::now(),
+            Utc::now(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_index_pending_is_incremental() {
+        let db = DatabaseManager::open_in_memory().await.unwrap();
+        let session_id = insert_session(&db).await;
+        let turn_repo = TurnSummaryRepository::new(&db);
+        turn_repo
+            .create(&turn(&session_id, 0, "Pinned the sqlx version"))
+            .await
+            .unwrap();
+        SessionSummaryRepository::new(&db)
+            .create(&GeneratedSessionSummary::new(
+                session_id.to_string(),
+                "Build fix".to_string(),
+                "Fixed the build".to_string(),
+            ))
+            .await
+            .unwrap();
+
+        let service = EmbeddingIndexService::new(&db);
+        let first = service.index_pending().await.unwrap();
+        assert_eq!(first.embedded, 2);
+
+        let second = service.index_pending().await.unwrap();
+        assert_eq!(
+            second,
+            IndexReport {
+                embedded: 0,
+                unchanged: 2,
+                removed: 0,
+            }
+        );
+
+        // Re-summarizing replaces the turn summaries with new IDs
+        turn_repo.delete_by_session(&session_id).await.unwrap();
+        turn_repo
+            .create(&turn(&session_id, 0, "Pinned sqlx and fixed imports"))
+            .await
+            .unwrap();
+        let third = service.index_session(&session_id).await.unwrap();
+        assert_eq!(third.embedded, 1);
+        assert_eq!(third.removed, 1);
+
+        let coverage = service.coverage().await.unwrap();
+        assert!(coverage.iter().all(|c| c.indexed == 1 && c.total == 1));
+        assert!(coverage.iter().all(|c| c.last_indexed_at.is_some()));
+    }
+
+    #[tokio::test]
+    async fn test_embedder_change_reindexes() {
+        let db = DatabaseManager::open_in_memory().await.unwrap();
+        let session_id = insert_session(&db).await;
+        TurnSummaryRepository::new(&db)
+            .create(&turn(&session_id, 0, "Added a retry policy"))
+            .await
+            .unwrap();
+
+        EmbeddingIndexService::new(&db)
+            .index_pending()
+            .await
+            .unwrap();
+
+        let smaller = EmbeddingIndexService::new(&db)
+            .with_embedder(Arc::new(HashingEmbedder::with_dimensions(64)));
+        assert_eq!(smaller.index_pending().await.unwrap().embedded, 1);
+
+        let stored = EmbeddingRepository::new(&db)
+            .list(EmbeddingKind::Turn)
+            .await
+            .unwrap();
+        assert!(stored.iter().all(|e| e.vector.len() == 64));
+    }
+
+    #[tokio::test]
+    async fn test_maintain_removes_orphaned_and_malformed_vectors() {
+        let db = DatabaseManager::open_in_memory().await.unwrap();
+        let session_id = insert_session(&db).await;
+        let turn_repo = TurnSummaryRepository::new(&db);
+        for (number, text) in ["Pinned the sqlx version", "Bumped tokio"]
+            .iter()
+            .enumerate()
+        {
+            turn_repo
+                .create(&turn(&session_id, number as i32, text))
+                .await
+                .unwrap();
+        }
+
+        let service = EmbeddingIndexService::new(&db);
+        service.index_pending().await.unwrap();
+
+        let repo = EmbeddingRepository::new(&db);
+        let deleted_session = Uuid::new_v4().to_string();
+        repo.upsert(
+            EmbeddingKind::Turn,
+            "gone",
+            &deleted_session,
+            "h",
+            &[0.0; 256],
+        )
+        .await
+        .unwrap();
+        let first_turn = turn_repo.get_by_session(&session_id).await.unwrap()[0]
+            .id
+            .clone();
+        repo.upsert(
+            EmbeddingKind::Turn,
+            &first_turn,
+            &session_id.to_string(),
+            "h",
+            &[1.0; 8],
+        )
+        .await
+        .unwrap();
+
+        let report = service.maintain().await.unwrap();
+        assert_eq!(report.orphans_removed, 1);
+        assert_eq!(report.malformed_removed, 1);
+        assert!(report.reclaimed_bytes >= 0);
+
+        let storage = service.storage().await.unwrap();
+        let turns = storage
+            .iter()
+            .find(|s| s.kind == EmbeddingKind::Turn)
+            .unwrap();
+        assert_eq!(turns.vectors, 1);
+        assert!(turns.bytes >= 256 * 4);
+
+        // The malformed vector is embedded again on the next run
+        assert_eq!(service.index_pending().await.unwrap().embedded, 1);
+    }
+}
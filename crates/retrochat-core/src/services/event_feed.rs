@@ -0,0 +1,143 @@
+//! Change feed shared by every RetroChat process
+//!
+//! Services append to the `events` table as they change data: imports,
+//! analyses, retention, saved views, topics and quota alerts. Consumers keep
+//! the id of the last event they handled as a cursor and read newer events,
+//! either once or continuously with [`EventFeed::subscribe`], which wakes up
+//! when another connection commits instead of polling the table.
+
+use anyhow::Result as AnyhowResult;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+use crate::database::{DatabaseManager, EventRepository};
+use crate::models::Event;
+
+/// Events read per query while catching up
+const BATCH_SIZE: i64 = 500;
+
+/// Cursor-based reader of the `events` table
+#[derive(Clone)]
+pub struct EventFeed {
+    db_manager: DatabaseManager,
+    repo: EventRepository,
+}
+
+impl EventFeed {
+    pub fn new(db: &DatabaseManager) -> Self {
+        Self {
+            db_manager: db.clone(),
+            repo: EventRepository::new(db),
+        }
+    }
+
+    /// Up to `limit` events after `cursor`, oldest first
+    pub async fn since(&self, cursor: i64, limit: i64) -> AnyhowResult<Vec<Event>> {
+        self.repo.since(cursor, limit).await
+    }
+
+    /// The last `limit` events, oldest first
+    pub async fn recent(&self, limit: i64) -> AnyhowResult<Vec<Event>> {
+        self.repo.recent(limit).await
+    }
+
+    /// Cursor positioned after every event recorded so far
+    pub async fn latest_cursor(&self) -> AnyhowResult<i64> {
+        self.repo.latest_id().await
+    }
+
+    /// Stream every event after `cursor`, then new events as other
+    /// connections record them, checking for commits every `interval`
+    ///
+    /// The stream ends when the receiver is dropped or the database can no
+    /// longer be read.
+    pub async fn subscribe(
+        &self,
+        mut cursor: i64,
+        interval: Duration,
+    ) -> AnyhowResult<mpsc::Receiver<Event>> {
+        let mut changes = self.db_manager.watch_changes(interval).await?;
+        let repo = self.repo.clone();
+        let (sender, receiver) = mpsc::channel(BATCH_SIZE as usize);
+
+        tokio::spawn(async move {
+            loop {
+                // Catch up on everything after the cursor, in pages
+                loop {
+                    let events = match repo.since(cursor, BATCH_SIZE).await {
+                        Ok(events) => events,
+                        Err(e) => {
+                            tracing::warn!(error = %e, "Stopped following the event log");
+                            return;
+                        }
+                    };
+                    let done = (events.len() as i64) < BATCH_SIZE;
+                    for event in events {
+                        cursor = event.id;
+                        if sender.send(event).await.is_err() {
+                            return;
+                        }
+                    }
+                    if done {
+                        break;
+                    }
+                }
+
+                tokio::select! {
+                    changed = changes.changed() => {
+                        if changed.is_err() {
+                            return;
+                        }
+                    }
+                    _ = sender.closed() => return,
+                }
+            }
+        });
+
+        Ok(receiver)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::EventKind;
+    use serde_json::json;
+
+    async fn next(events: &mut mpsc::Receiver<Event>) -> Event {
+        tokio::time::timeout(Duration::from_secs(5), events.recv())
+            .await
+            .unwrap()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_catches_up_and_follows() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("retrochat.db");
+        let reader = DatabaseManager::new(&db_path).await.unwrap();
+        let writer = EventRepository::new(&DatabaseManager::new(&db_path).await.unwrap());
+
+        let skipped = writer
+            .append(EventKind::ViewSaved, None, &json!({ "name": "a" }))
+            .await
+            .unwrap();
+        writer
+            .append(EventKind::ViewDeleted, None, &json!({ "name": "a" }))
+            .await
+            .unwrap();
+
+        let feed = EventFeed::new(&reader);
+        let mut events = feed
+            .subscribe(skipped, Duration::from_millis(20))
+            .await
+            .unwrap();
+        assert_eq!(next(&mut events).await.kind, EventKind::ViewDeleted);
+
+        writer
+            .append(EventKind::TopicsRefreshed, None, &json!({}))
+            .await
+            .unwrap();
+        assert_eq!(next(&mut events).await.kind, EventKind::TopicsRefreshed);
+    }
+}
@@ -0,0 +1,512 @@
+//! A/B experiments across providers and models
+//!
+//! An experiment puts sessions into named cohorts (e.g. "sonnet" against
+//! "gpt-5-codex"). Cohorts are chosen by the user and, unlike session tags,
+//! survive re-imports. Each cohort's sessions are aggregated into metrics,
+//! and the report has the LLM compare the cohorts from those metrics and a
+//! sample of session summaries.
+
+use anyhow::{Context, Result as AnyhowResult};
+use serde::Serialize;
+use std::fmt::Write as _;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::database::{
+    ChatSessionRepository, DatabaseManager, ExperimentRepository, SessionSummaryRepository,
+};
+use crate::models::{CohortStats, Experiment, ExperimentSession, SessionOutcome};
+use crate::services::llm::{CachingClient, GenerateRequest, LlmClient};
+use crate::utils::locale::Language;
+
+/// Summarized sessions of each cohort shown to the LLM
+const REPORT_SAMPLE_SESSIONS: usize = 10;
+
+/// An experiment with the metrics of its cohorts
+#[derive(Debug, Clone, Serialize)]
+pub struct ExperimentOverview {
+    pub experiment: Experiment,
+    /// By cohort name
+    pub cohorts: Vec<CohortStats>,
+}
+
+/// A summarized session of a cohort, as shown to the LLM
+#[derive(Debug, Clone, Serialize)]
+pub struct CohortSample {
+    pub title: String,
+    pub summary: String,
+    pub outcome: Option<SessionOutcome>,
+}
+
+/// LLM-written comparison of an experiment's cohorts
+#[derive(Debug, Clone, Serialize)]
+pub struct ExperimentReport {
+    pub overview: ExperimentOverview,
+    pub markdown: String,
+}
+
+/// Service behind `retrochat experiments`
+pub struct ExperimentService {
+    db_manager: Arc<DatabaseManager>,
+    llm_client: Option<Arc<dyn LlmClient>>,
+    language: Language,
+}
+
+impl ExperimentService {
+    /// Without an LLM client, everything but reports is available
+    pub fn new(db_manager: Arc<DatabaseManager>, llm_client: Option<Arc<dyn LlmClient>>) -> Self {
+        let llm_client = llm_client.map(|client| CachingClient::wrap(client, &db_manager));
+        Self {
+            db_manager,
+            llm_client,
+            language: Language::load(),
+        }
+    }
+
+    fn repo(&self) -> ExperimentRepository {
+        ExperimentRepository::new(&self.db_manager)
+    }
+
+    pub async fn create(
+        &self,
+        name: &str,
+        description: Option<String>,
+    ) -> AnyhowResult<Experiment> {
+        let name = validate_name("Experiment", name)?;
+        if self.repo().get(&name).await?.is_some() {
+            anyhow::bail!("Experiment '{name}' already exists");
+        }
+        let description = description
+            .map(|description| description.trim().to_string())
+            .filter(|description| !description.is_empty());
+        let experiment = Experiment::new(name, description);
+        self.repo().create(&experiment).await?;
+        Ok(experiment)
+    }
+
+    /// Experiments with their session counts, newest first
+    pub async fn list(&self) -> AnyhowResult<Vec<(Experiment, i64)>> {
+        self.repo().list().await
+    }
+
+    pub async fn delete(&self, name: &str) -> AnyhowResult<()> {
+        if !self.repo().delete(name).await? {
+            anyhow::bail!("Experiment not found: {name}");
+        }
+        Ok(())
+    }
+
+    /// Put sessions into a cohort; sessions already in another cohort of the
+    /// experiment move over. Returns how many sessions were added or moved.
+    pub async fn add_sessions(
+        &self,
+        name: &str,
+        cohort: &str,
+        session_ids: &[Uuid],
+    ) -> AnyhowResult<u64> {
+        self.get(name).await?;
+        let cohort = validate_name("Cohort", cohort)?;
+
+        let session_repo = ChatSessionRepository::new(&self.db_manager);
+        for session_id in session_ids {
+            if session_repo.get_by_id(session_id).await?.is_none() {
+                anyhow::bail!("Session not found: {session_id}");
+            }
+        }
+
+        self.repo().add_sessions(name, &cohort, session_ids).await
+    }
+
+    /// Take sessions out of the experiment, returning how many were in it
+    pub async fn remove_sessions(&self, name: &str, session_ids: &[Uuid]) -> AnyhowResult<u64> {
+        self.get(name).await?;
+        self.repo().remove_sessions(name, session_ids).await
+    }
+
+    /// The experiment's sessions by cohort
+    pub async fn sessions(&self, name: &str) -> AnyhowResult<Vec<ExperimentSession>> {
+        self.get(name).await?;
+        self.repo().get_sessions(name).await
+    }
+
+    pub async fn overview(&self, name: &str) -> AnyhowResult<ExperimentOverview> {
+        let experiment = self.get(name).await?;
+        let cohorts = self.repo().cohort_stats(name).await?;
+        Ok(ExperimentOverview {
+            experiment,
+            cohorts,
+        })
+    }
+
+    /// Have the LLM compare the cohorts of an experiment
+    pub async fn report(&self, name: &str) -> AnyhowResult<ExperimentReport> {
+        let client = self
+            .llm_client
+            .as_ref()
+            .context("An LLM client is required for experiment reports")?;
+
+        let overview = self.overview(name).await?;
+        if overview.cohorts.len() < 2 {
+            anyhow::bail!(
+                "Experiment '{name}' needs sessions in at least two cohorts to compare, found {}",
+                overview.cohorts.len()
+            );
+        }
+
+        let mut samples = Vec::with_capacity(overview.cohorts.len());
+        for stats in &overview.cohorts {
+            samples.push((
+                stats.cohort.clone(),
+                self.samples(name, &stats.cohort).await?,
+            ));
+        }
+
+        let prompt = build_report_prompt(&overview, &samples);
+        let request = GenerateRequest::new(self.language.localize_prompt(prompt))
+            .with_max_tokens(1500)
+            .with_temperature(0.3);
+        let response = client
+            .generate(request)
+            .await
+            .map_err(|e| anyhow::anyhow!("LLM generation failed: {e}"))?;
+
+        let markdown = response
+            .text
+            .trim()
+            .trim_start_matches("```markdown")
+            .trim_matches('`')
+            .trim()
+            .to_string();
+        if markdown.is_empty() {
+            anyhow::bail!("LLM returned an empty report");
+        }
+
+        Ok(ExperimentReport { overview, markdown })
+    }
+
+    async fn get(&self, name: &str) -> AnyhowResult<Experiment> {
+        self.repo()
+            .get(name)
+            .await?
+            .with_context(|| format!("Experiment not found: {name}"))
+    }
+
+    /// Summaries of the cohort's most recently added sessions
+    async fn samples(&self, name: &str, cohort: &str) -> AnyhowResult<Vec<CohortSample>> {
+        let summary_repo = SessionSummaryRepository::new(&self.db_manager);
+        let mut samples = Vec::new();
+        for member in self
+            .repo()
+            .get_sessions(name)
+            .await?
+            .iter()
+            .rev()
+            .filter(|member| member.cohort == cohort)
+        {
+            if samples.len() == REPORT_SAMPLE_SESSIONS {
+                break;
+            }
+            if let Some(summary) = summary_repo.get_by_session(&member.session_id).await? {
+                samples.push(CohortSample {
+                    title: summary.title,
+                    summary: summary.summary,
+                    outcome: summary.outcome,
+                });
+            }
+        }
+        Ok(samples)
+    }
+}
+
+fn validate_name(kind: &str, name: &str) -> AnyhowResult<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        anyhow::bail!("{kind} name must not be empty");
+    }
+    Ok(name.to_string())
+}
+
+/// A rate as a percentage, `-` when unknown
+pub fn format_rate(rate: Option<f64>) -> String {
+    rate.map(|rate| format!("{:.1}%", rate * 100.0))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// An average to one decimal, `-` when unknown
+pub fn format_average(value: Option<f64>) -> String {
+    value
+        .map(|value| format!("{value:.1}"))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn build_report_prompt(
+    overview: &ExperimentOverview,
+    samples: &[(String, Vec<CohortSample>)],
+) -> String {
+    let mut metrics = String::from(
+        "| Cohort | Sessions | Providers | Avg messages | Avg tokens | Avg minutes | Avg tool calls | Tool failure rate | Completion rate |\n|---|---|---|---|---|---|---|---|---|\n",
+    );
+    for stats in &overview.cohorts {
+        let providers = stats
+            .providers
+            .iter()
+            .map(|(provider, sessions)| format!("{provider} {sessions}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let _ = writeln!(
+            metrics,
+            "| {} | {} | {providers} | {} | {} | {} | {} | {} | {} ({} summarized) |",
+            stats.cohort,
+            stats.sessions,
+            format_average(stats.avg_messages()),
+            format_average(stats.avg_tokens()),
+            format_average(stats.avg_minutes()),
+            format_average(stats.avg_tool_uses()),
+            format_rate(stats.tool_failure_rate()),
+            format_rate(stats.completion_rate()),
+            stats.summarized,
+        );
+    }
+
+    let mut sessions = String::new();
+    for (cohort, cohort_samples) in samples {
+        let _ = writeln!(sessions, "### Cohort: {cohort}\n");
+        if cohort_samples.is_empty() {
+            let _ = writeln!(sessions, "No summarized sessions.\n");
+            continue;
+        }
+        for sample in cohort_samples {
+            let outcome = sample
+                .outcome
+                .as_ref()
+                .map(|outcome| format!(" ({outcome})"))
+                .unwrap_or_default();
+            let _ = writeln!(
+                sessions,
+                "- **{}**{outcome}: {}",
+                sample.title, sample.summary
+            );
+        }
+        sessions.push('\n');
+    }
+
+    let description = overview
+        .experiment
+        .description
+        .as_deref()
+        .map(|description| format!("Description: {description}\n\n"))
+        .unwrap_or_default();
+
+    format!(
+        r#"Compare the cohorts of an experiment on AI coding assistant sessions. Each cohort is a group of sessions run with a different provider, model or setup.
+
+## Experiment: {name}
+
+{description}## Measured Metrics
+
+{metrics}
+## Sample Sessions
+
+{sessions}## Task
+
+Write a short markdown report with these sections:
+
+- `## Summary`: 2-3 sentences on how the cohorts differ and which one did better overall
+- `## Efficiency`: tokens, time and tool calls per session, using the measured metrics
+- `## Reliability`: tool failures and completion rates
+- `## Work Style`: differences in the kind of work and how it went, from the sample sessions
+- `## Caveats`: small cohorts, few summarized sessions or different kinds of tasks that make the comparison less certain
+
+Use at most five bullets per section and keep each bullet to one line. Quote the measured numbers rather than estimating them, and do not invent anything that is not above. Output only the markdown."#,
+        name = overview.experiment.name,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{MessageRepository, ToolOperationRepository};
+    use crate::models::{ChatSession, Message, MessageRole, Provider, ToolOperation};
+    use crate::services::llm::{GenerateResponse, LlmError};
+    use async_trait::async_trait;
+    use chrono::Utc;
+
+    struct FixedLlm(&'static str);
+
+    #[async_trait]
+    impl LlmClient for FixedLlm {
+        async fn generate(&self, _request: GenerateRequest) -> Result<GenerateResponse, LlmError> {
+            Ok(GenerateResponse {
+                text: self.0.to_string(),
+                ..Default::default()
+            })
+        }
+
+        fn provider_name(&self) -> &'static str {
+            "fixed"
+        }
+
+        fn model_name(&self) -> &str {
+            "fixed"
+        }
+
+        async fn health_check(&self) -> Result<(), LlmError> {
+            Ok(())
+        }
+    }
+
+    async fn add_session(db: &DatabaseManager, messages: u32, failed_tool: bool) -> Uuid {
+        let session = ChatSession::new(
+            Provider::ClaudeCode,
+            format!("/{}.jsonl", Uuid::new_v4()),
+            "hash".to_string(),
+            Utc::now(),
+        );
+        ChatSessionRepository::new(db)
+            .create(&session)
+            .await
+            .unwrap();
+        let mut operation =
+            ToolOperation::new("toolu_1".to_string(), "Bash".to_string(), Utc::now());
+        operation.success = Some(!failed_tool);
+        ToolOperationRepository::new(db)
+            .create(&operation)
+            .await
+            .unwrap();
+        let messages: Vec<Message> = (1..=messages)
+            .map(|n| {
+                let message = Message::new(
+                    session.id,
+                    MessageRole::Assistant,
+                    format!("message {n}"),
+                    Utc::now(),
+                    n,
+                );
+                if n == 1 {
+                    message.with_tool_operation(operation.id)
+                } else {
+                    message
+                }
+            })
+            .collect();
+        MessageRepository::new(db)
+            .bulk_create(&messages)
+            .await
+            .unwrap();
+        ChatSessionRepository::new(db)
+            .refresh_stats(&session.id)
+            .await
+            .unwrap();
+        session.id
+    }
+
+    #[tokio::test]
+    async fn test_cohorts_and_report() {
+        let db = Arc::new(DatabaseManager::open_in_memory().await.unwrap());
+        let service = ExperimentService::new(
+            db.clone(),
+            Some(Arc::new(FixedLlm(
+                "```markdown\n## Summary\nSonnet won.\n```",
+            ))),
+        );
+
+        service
+            .create("models", Some("Sonnet vs Codex".to_string()))
+            .await
+            .unwrap();
+        assert!(service.create(" models ", None).await.is_err());
+
+        let first = add_session(&db, 4, false).await;
+        let second = add_session(&db, 2, true).await;
+        let third = add_session(&db, 6, true).await;
+
+        assert_eq!(
+            service
+                .add_sessions("models", "sonnet", &[first, second])
+                .await
+                .unwrap(),
+            2
+        );
+        // Already there, then moved to the other cohort
+        assert_eq!(
+            service
+                .add_sessions("models", "sonnet", &[first])
+                .await
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            service
+                .add_sessions("models", "codex", &[second, third])
+                .await
+                .unwrap(),
+            2
+        );
+        assert!(service
+            .add_sessions("models", "codex", &[Uuid::new_v4()])
+            .await
+            .is_err());
+
+        let overview = service.overview("models").await.unwrap();
+        let cohorts: Vec<(&str, i64, i64, i64)> = overview
+            .cohorts
+            .iter()
+            .map(|stats| {
+                (
+                    stats.cohort.as_str(),
+                    stats.sessions,
+                    stats.messages,
+                    stats.failed_tool_uses,
+                )
+            })
+            .collect();
+        assert_eq!(cohorts, vec![("codex", 2, 8, 2), ("sonnet", 1, 4, 0)]);
+        assert_eq!(overview.cohorts[0].tool_failure_rate(), Some(1.0));
+        assert_eq!(overview.cohorts[0].completion_rate(), None);
+
+        let report = service.report("models").await.unwrap();
+        assert_eq!(report.markdown, "## Summary\nSonnet won.");
+
+        assert_eq!(
+            service.remove_sessions("models", &[first]).await.unwrap(),
+            1
+        );
+        assert!(service.report("models").await.is_err());
+
+        service.delete("models").await.unwrap();
+        assert!(service.list().await.unwrap().is_empty());
+        assert!(service.overview("models").await.is_err());
+    }
+
+    #[test]
+    fn test_report_prompt_lists_cohorts() {
+        let overview = ExperimentOverview {
+            experiment: Experiment::new("models".to_string(), None),
+            cohorts: vec![CohortStats {
+                cohort: "sonnet".to_string(),
+                sessions: 2,
+                messages: 10,
+                tool_uses: 4,
+                failed_tool_uses: 1,
+                summarized: 2,
+                completed: 1,
+                providers: vec![("Claude Code".to_string(), 2)],
+                ..Default::default()
+            }],
+        };
+        let samples = vec![(
+            "sonnet".to_string(),
+            vec![CohortSample {
+                title: "Fix login".to_string(),
+                summary: "Fixed the session cookie.".to_string(),
+                outcome: Some(SessionOutcome::Completed),
+            }],
+        )];
+
+        let prompt = build_report_prompt(&overview, &samples);
+        assert!(prompt.contains(
+            "| sonnet | 2 | Claude Code 2 | 5.0 | 0.0 | - | 2.0 | 25.0% | 50.0% (2 summarized) |"
+        ));
+        assert!(prompt.contains("- **Fix login** (completed): Fixed the session cookie."));
+    }
+}
@@ -4,10 +4,10 @@ use std::time::Duration;
 use tokio::sync::Semaphore;
 use tokio::time::timeout;
 
-use super::errors::{GoogleAiError, RetryError};
+use super::errors::GoogleAiError;
 use super::models::{GenerateContentRequest, GenerateContentResponse, GenerationConfig};
-use super::retry::{with_retry, RetryConfig};
 use crate::env::apis as env_vars;
+use crate::services::llm::retry::{retry, RetryConfig};
 
 #[derive(Debug, Clone)]
 pub struct GoogleAiConfig {
@@ -108,20 +108,12 @@ impl GoogleAiClient {
         &self,
         request: GenerateContentRequest,
     ) -> Result<GenerateContentResponse, GoogleAiError> {
-        let retry_config =
-            RetryConfig::new(self.config.max_retries).with_total_timeout(self.config.timeout);
-
-        with_retry(retry_config, || self.generate_content_once(request.clone()))
-            .await
-            .map_err(|retry_error| match retry_error {
-                RetryError::NonRetryable { source } => source,
-                RetryError::MaxAttemptsExceeded => GoogleAiError::RateLimitExceeded {
-                    message: "Maximum retry attempts exceeded".to_string(),
-                },
-                RetryError::TimeoutExceeded => GoogleAiError::Timeout {
-                    timeout_ms: self.config.timeout.as_millis() as u64,
-                },
-            })
+        let retry_config = RetryConfig::new(self.config.max_retries.max(1) as u32);
+
+        retry(&retry_config, || {
+            self.generate_content_once(request.clone())
+        })
+        .await
     }
 
     async fn generate_content_once(
@@ -135,6 +127,7 @@ impl GoogleAiClient {
                 .await
                 .map_err(|_| GoogleAiError::RateLimitExceeded {
                     message: "Rate limiter closed".to_string(),
+                    retry_after_secs: None,
                 })?;
 
         let url = format!(
@@ -183,12 +176,24 @@ impl GoogleAiClient {
 
             Ok(parsed_response)
         } else {
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse::<u64>().ok());
             let error_body = response
                 .text()
                 .await
                 .unwrap_or_else(|_| "Unable to read error response".to_string());
 
-            Err(GoogleAiError::from_status_and_body(status, &error_body))
+            let mut error = GoogleAiError::from_status_and_body(status, &error_body);
+            if let GoogleAiError::RateLimitExceeded {
+                retry_after_secs, ..
+            } = &mut error
+            {
+                *retry_after_secs = retry_after;
+            }
+            Err(error)
         }
     }
 
@@ -239,6 +244,10 @@ Provide:
         if let Some(max_tokens) = analysis_request.max_tokens {
             generation_config.max_output_tokens = Some(max_tokens);
         }
+        if let Some(schema) = &analysis_request.response_schema {
+            generation_config.response_mime_type = Some("application/json".to_string());
+            generation_config.response_schema = Some(to_gemini_schema(schema));
+        }
 
         let request = GenerateContentRequest::new(analysis_request.prompt)
             .with_generation_config(generation_config);
@@ -275,6 +284,37 @@ Provide:
     }
 }
 
+/// Convert a JSON schema to the OpenAPI subset Gemini accepts for `responseSchema`
+///
+/// Type names are upper-cased and keywords Gemini rejects are dropped.
+fn to_gemini_schema(schema: &serde_json::Value) -> serde_json::Value {
+    use serde_json::Value;
+
+    match schema {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .filter(|(key, _)| !matches!(key.as_str(), "$schema" | "additionalProperties"))
+                .map(|(key, value)| {
+                    let value = match (key.as_str(), value) {
+                        ("type", Value::String(t)) => Value::String(t.to_uppercase()),
+                        // Property names are not schemas, but their values are
+                        ("properties", Value::Object(props)) => Value::Object(
+                            props
+                                .iter()
+                                .map(|(name, prop)| (name.clone(), to_gemini_schema(prop)))
+                                .collect(),
+                        ),
+                        (_, value) => to_gemini_schema(value),
+                    };
+                    (key.clone(), value)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(to_gemini_schema).collect()),
+        other => other.clone(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -317,4 +357,29 @@ mod tests {
         assert!(!request.contents.is_empty());
         assert!(request.generation_config.is_some());
     }
+
+    #[test]
+    fn test_to_gemini_schema() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "additionalProperties": false,
+            "properties": {
+                "type": {"type": "string"},
+                "items": {"type": "array", "items": {"type": "string"}}
+            },
+            "required": ["type", "items"]
+        });
+
+        assert_eq!(
+            to_gemini_schema(&schema),
+            serde_json::json!({
+                "type": "OBJECT",
+                "properties": {
+                    "type": {"type": "STRING"},
+                    "items": {"type": "ARRAY", "items": {"type": "STRING"}}
+                },
+                "required": ["type", "items"]
+            })
+        );
+    }
 }
@@ -6,7 +6,11 @@ pub enum GoogleAiError {
     AuthenticationFailed { message: String },
 
     #[error("Rate limit exceeded: {message}")]
-    RateLimitExceeded { message: String },
+    RateLimitExceeded {
+        message: String,
+        /// Value of the Retry-After header, if the server sent one
+        retry_after_secs: Option<u64>,
+    },
 
     #[error("Request timeout after {timeout_ms}ms")]
     Timeout { timeout_ms: u64 },
@@ -89,8 +93,10 @@ impl GoogleAiError {
 
     pub fn retry_after_seconds(&self) -> Option<u64> {
         match self {
-            GoogleAiError::RateLimitExceeded { .. } => Some(60), // Wait 60 seconds for rate limits
-            GoogleAiError::Timeout { .. } => Some(5),            // Wait 5 seconds for timeouts
+            GoogleAiError::RateLimitExceeded {
+                retry_after_secs, ..
+            } => Some(retry_after_secs.unwrap_or(60)), // Wait 60 seconds unless told otherwise
+            GoogleAiError::Timeout { .. } => Some(5), // Wait 5 seconds for timeouts
             GoogleAiError::ServerError { .. } => Some(30), // Wait 30 seconds for server errors
             GoogleAiError::ServiceUnavailable { .. } => Some(120), // Wait 2 minutes for service issues
             _ => None,
@@ -152,7 +158,10 @@ impl GoogleAiError {
             match status_code {
                 401 => GoogleAiError::AuthenticationFailed { message },
                 403 => GoogleAiError::QuotaExceeded { message },
-                429 => GoogleAiError::RateLimitExceeded { message },
+                429 => GoogleAiError::RateLimitExceeded {
+                    message,
+                    retry_after_secs: None,
+                },
                 500..=599 => GoogleAiError::ServerError {
                     status: status_code,
                     message,
@@ -200,6 +209,7 @@ impl GoogleAiError {
             }
             429 => GoogleAiError::RateLimitExceeded {
                 message: error_message,
+                retry_after_secs: None,
             },
             503 => GoogleAiError::ServiceUnavailable {
                 message: error_message,
@@ -215,24 +225,17 @@ impl GoogleAiError {
     }
 }
 
-#[derive(Debug, Error)]
-pub enum RetryError {
-    #[error("Maximum retry attempts exceeded")]
-    MaxAttemptsExceeded,
-
-    #[error("Retry timeout exceeded")]
-    TimeoutExceeded,
-
-    #[error("Non-retryable error: {source}")]
-    NonRetryable { source: GoogleAiError },
-}
+impl crate::services::llm::retry::RetryableError for GoogleAiError {
+    fn is_retryable(&self) -> bool {
+        GoogleAiError::is_retryable(self)
+    }
 
-impl From<GoogleAiError> for RetryError {
-    fn from(error: GoogleAiError) -> Self {
-        if error.is_retryable() {
-            RetryError::MaxAttemptsExceeded
-        } else {
-            RetryError::NonRetryable { source: error }
+    fn server_retry_after(&self) -> Option<std::time::Duration> {
+        match self {
+            GoogleAiError::RateLimitExceeded {
+                retry_after_secs, ..
+            } => retry_after_secs.map(std::time::Duration::from_secs),
+            _ => None,
         }
     }
 }
@@ -1,12 +1,10 @@
 pub mod client;
 pub mod errors;
 pub mod models;
-pub mod retry;
 
 pub use client::{GoogleAiClient, GoogleAiConfig};
-pub use errors::{GoogleAiError, RetryError};
+pub use errors::GoogleAiError;
 pub use models::{
     AnalysisRequest, AnalysisResponse, Candidate, Content, GenerateContentRequest,
     GenerateContentResponse, GenerationConfig, Part, SafetyRating, SafetySetting, UsageMetadata,
 };
-pub use retry::{with_default_retry, with_retry, RetryConfig, RetryHandler, RetryMetrics};
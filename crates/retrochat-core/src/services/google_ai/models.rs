@@ -32,6 +32,11 @@ pub struct GenerationConfig {
     pub candidate_count: Option<u32>,
     #[serde(rename = "stopSequences")]
     pub stop_sequences: Option<Vec<String>>,
+    #[serde(rename = "responseMimeType", skip_serializing_if = "Option::is_none")]
+    pub response_mime_type: Option<String>,
+    /// OpenAPI-style schema the response is constrained to
+    #[serde(rename = "responseSchema", skip_serializing_if = "Option::is_none")]
+    pub response_schema: Option<serde_json::Value>,
 }
 
 impl Default for GenerationConfig {
@@ -43,6 +48,8 @@ impl Default for GenerationConfig {
             top_k: Some(40),
             candidate_count: Some(1),
             stop_sequences: None,
+            response_mime_type: None,
+            response_schema: None,
         }
     }
 }
@@ -150,6 +157,8 @@ pub struct AnalysisRequest {
     pub prompt: String,
     pub max_tokens: Option<u32>,
     pub temperature: Option<f32>,
+    /// JSON schema for the response; requests JSON output when set
+    pub response_schema: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
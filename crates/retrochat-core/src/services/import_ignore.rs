@@ -0,0 +1,166 @@
+//! Rules that keep sessions out of the database
+//!
+//! Set with `retrochat config set ignore.paths|ignore.min_messages|ignore.projects`,
+//! plus path globs listed in `~/.retrochat/.retrochatignore`. Imports skip
+//! matching files and sessions; watch mode skips matching files.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+use super::watch_service::IgnoreRules;
+use crate::config::Config;
+use crate::database::config::get_config_dir;
+use crate::models::ChatSession;
+
+/// File of extra path globs in the config directory
+pub const IGNORE_FILE_NAME: &str = ".retrochatignore";
+
+/// Compiled `[ignore]` settings
+#[derive(Debug, Clone, Default)]
+pub struct ImportIgnore {
+    paths: IgnoreRules,
+    projects: Vec<glob::Pattern>,
+    min_messages: u32,
+}
+
+impl ImportIgnore {
+    pub fn new(paths: &[String], projects: &[String], min_messages: u32) -> Result<Self> {
+        let projects = projects
+            .iter()
+            .map(|p| glob::Pattern::new(p).with_context(|| format!("Invalid ignore glob: {p}")))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            paths: IgnoreRules::new(paths)?,
+            projects,
+            min_messages,
+        })
+    }
+
+    pub fn from_config(config: &Config) -> Result<Self> {
+        Self::new(
+            &path_globs(config)?,
+            &config.ignore.projects,
+            config.ignore.min_messages.unwrap_or(0),
+        )
+    }
+
+    /// Rules from the config file; no rules (with a warning) if they cannot be read
+    pub fn load() -> Self {
+        match Config::load().and_then(|config| Self::from_config(&config)) {
+            Ok(ignore) => ignore,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to load ignore rules, importing everything");
+                Self::default()
+            }
+        }
+    }
+
+    /// Whether a session file matches an ignored path glob
+    pub fn ignores_path(&self, path: &Path) -> bool {
+        self.paths.is_ignored(path)
+    }
+
+    /// Why a parsed session is skipped, if it is
+    pub fn session_skip_reason(
+        &self,
+        session: &ChatSession,
+        message_count: usize,
+    ) -> Option<String> {
+        if self.ignores_path(Path::new(&session.file_path)) {
+            return Some("file matches ignore.paths".to_string());
+        }
+        if (message_count as u32) < self.min_messages {
+            return Some(format!(
+                "{message_count} messages, below ignore.min_messages ({})",
+                self.min_messages
+            ));
+        }
+        let project = session.project_name.as_deref()?;
+        self.projects
+            .iter()
+            .any(|pattern| pattern.matches(project))
+            .then(|| format!("project {project} matches ignore.projects"))
+    }
+}
+
+/// `ignore.paths` followed by the globs in the ignore file
+pub fn path_globs(config: &Config) -> Result<Vec<String>> {
+    let mut globs = config.ignore.paths.clone();
+    let file = get_config_dir()?.join(IGNORE_FILE_NAME);
+    if file.exists() {
+        let contents = fs::read_to_string(&file)
+            .with_context(|| format!("Failed to read {}", file.display()))?;
+        globs.extend(parse_ignore_file(&contents));
+    }
+    Ok(globs)
+}
+
+/// One glob per line; blank lines and `#` comments are skipped
+fn parse_ignore_file(contents: &str) -> Vec<String> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Provider;
+    use chrono::Utc;
+
+    fn session(file_path: &str, project: Option<&str>) -> ChatSession {
+        let mut session = ChatSession::new(
+            Provider::ClaudeCode,
+            file_path.to_string(),
+            "hash".to_string(),
+            Utc::now(),
+        );
+        session.project_name = project.map(str::to_string);
+        session
+    }
+
+    #[test]
+    fn test_parse_ignore_file() {
+        let contents = "# scratch work\n**/-tmp-*/**\n\n  vendor/**  \n";
+        assert_eq!(
+            parse_ignore_file(contents),
+            vec!["**/-tmp-*/**", "vendor/**"]
+        );
+    }
+
+    #[test]
+    fn test_session_skip_reason() {
+        let ignore =
+            ImportIgnore::new(&["**/-tmp-*/**".to_string()], &["scratch-*".to_string()], 3)
+                .unwrap();
+
+        let tmp = "/home/me/.claude/projects/-tmp-try/a.jsonl";
+        assert!(ignore.ignores_path(Path::new(tmp)));
+        assert!(ignore
+            .session_skip_reason(&session(tmp, None), 10)
+            .is_some());
+
+        let real = "/home/me/.claude/projects/-home-me-app/a.jsonl";
+        assert!(!ignore.ignores_path(Path::new(real)));
+        assert_eq!(
+            ignore.session_skip_reason(&session(real, Some("app")), 10),
+            None
+        );
+        assert!(ignore
+            .session_skip_reason(&session(real, Some("app")), 2)
+            .unwrap()
+            .contains("min_messages"));
+        assert!(ignore
+            .session_skip_reason(&session(real, Some("scratch-1")), 10)
+            .is_some());
+
+        assert_eq!(
+            ImportIgnore::default().session_skip_reason(&session(tmp, None), 0),
+            None
+        );
+    }
+}
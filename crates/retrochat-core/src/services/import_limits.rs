@@ -0,0 +1,186 @@
+//! Size limits for rows written at import
+//!
+//! Set with `retrochat config set import.max_message_kb|import.max_tool_result_kb`.
+//! A message or tool result over its limit keeps its start and end around a
+//! marker, and is flagged so the loss is visible later; one runaway tool
+//! output can otherwise add hundreds of megabytes to the database.
+
+use serde_json::{json, Value};
+
+use crate::config::Config;
+use crate::models::{Message, ToolOperation};
+
+/// Share of the kept text taken from the start; the rest comes from the end
+const HEAD_SHARE: f64 = 0.75;
+
+/// Compiled `[import]` size limits, in bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RowLimits {
+    pub max_message_bytes: Option<usize>,
+    pub max_tool_result_bytes: Option<usize>,
+}
+
+impl Default for RowLimits {
+    fn default() -> Self {
+        Self::from_config(&Config::default())
+    }
+}
+
+impl RowLimits {
+    /// No limits at all
+    pub fn unlimited() -> Self {
+        Self {
+            max_message_bytes: None,
+            max_tool_result_bytes: None,
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            max_message_bytes: config.import.max_message_bytes(),
+            max_tool_result_bytes: config.import.max_tool_result_bytes(),
+        }
+    }
+
+    /// Limits from the config file; the defaults (with a warning) if it cannot be read
+    pub fn load() -> Self {
+        match Config::load() {
+            Ok(config) => Self::from_config(&config),
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to load import limits, using defaults");
+                Self::default()
+            }
+        }
+    }
+
+    /// Truncate an oversized message body, flagging it in the metadata.
+    /// Returns whether anything was cut
+    pub fn truncate_message(&self, message: &mut Message) -> bool {
+        let Some(limit) = self.max_message_bytes else {
+            return false;
+        };
+        let original_bytes = message.content.len();
+        let Some(content) = truncate_text(&message.content, limit) else {
+            return false;
+        };
+        message.content = content;
+        message.insert_metadata("truncated", json!({ "original_bytes": original_bytes }));
+        true
+    }
+
+    /// Truncate an oversized raw result and command output of a tool call.
+    /// Returns whether anything was cut
+    pub fn truncate_tool_operation(&self, operation: &mut ToolOperation) -> bool {
+        let Some(limit) = self.max_tool_result_bytes else {
+            return false;
+        };
+        let mut truncated = false;
+
+        if let Some(result) = &operation.raw_result {
+            // Strings are measured as text; anything else as its JSON
+            let text = match result {
+                Value::String(text) => text.clone(),
+                other => other.to_string(),
+            };
+            if let Some(content) = truncate_text(&text, limit) {
+                operation.raw_result = Some(json!({
+                    "truncated": true,
+                    "original_bytes": text.len(),
+                    "content": content,
+                }));
+                truncated = true;
+            }
+        }
+
+        if let Some(bash) = &mut operation.bash_metadata {
+            for output in [&mut bash.stdout, &mut bash.stderr].into_iter().flatten() {
+                if let Some(content) = truncate_text(output, limit) {
+                    *output = content;
+                    truncated = true;
+                }
+            }
+        }
+
+        truncated
+    }
+}
+
+/// `text` cut to about `limit` bytes around a marker, or `None` if it fits
+fn truncate_text(text: &str, limit: usize) -> Option<String> {
+    if text.len() <= limit {
+        return None;
+    }
+    let head = floor_char_boundary(text, (limit as f64 * HEAD_SHARE) as usize);
+    let tail = ceil_char_boundary(text, text.len() - (limit - head));
+    Some(format!(
+        "{}\n\n[... {} bytes truncated by retrochat ...]\n\n{}",
+        &text[..head],
+        tail - head,
+        &text[tail..]
+    ))
+}
+
+fn floor_char_boundary(text: &str, mut index: usize) -> usize {
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+fn ceil_char_boundary(text: &str, mut index: usize) -> usize {
+    while !text.is_char_boundary(index) {
+        index += 1;
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::bash_metadata::BashMetadata;
+    use crate::models::MessageRole;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_truncate_oversized_rows() {
+        let limits = RowLimits {
+            max_message_bytes: Some(100),
+            max_tool_result_bytes: Some(100),
+        };
+
+        let mut message = Message::new(
+            Uuid::new_v4(),
+            MessageRole::Assistant,
+            format!("start{}é end", "x".repeat(1000)),
+            Utc::now(),
+            1,
+        );
+        assert!(limits.truncate_message(&mut message));
+        assert!(message.content.starts_with("start"));
+        assert!(message.content.ends_with("é end"));
+        assert!(message.content.len() < 200);
+        assert_eq!(
+            message.metadata.as_ref().unwrap()["truncated"]["original_bytes"],
+            1011
+        );
+
+        let mut short = message.clone();
+        short.content = "short".to_string();
+        assert!(!limits.truncate_message(&mut short));
+
+        let mut operation = ToolOperation::new("t".to_string(), "Bash".to_string(), Utc::now())
+            .with_bash_metadata(BashMetadata {
+                stdout: Some("y".repeat(5000)),
+                ..BashMetadata::new("other".to_string(), "yes".to_string())
+            });
+        operation.raw_result = Some(json!({ "content": "y".repeat(5000) }));
+        assert!(limits.truncate_tool_operation(&mut operation));
+        let result = operation.raw_result.unwrap();
+        assert_eq!(result["truncated"], true);
+        assert!(result["content"].as_str().unwrap().len() < 200);
+        assert!(operation.bash_metadata.unwrap().stdout.unwrap().len() < 200);
+
+        assert!(!RowLimits::unlimited().truncate_message(&mut message));
+    }
+}
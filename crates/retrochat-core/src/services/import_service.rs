@@ -1,26 +1,42 @@
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, Context, Result};
 use futures::future::join_all;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::UNIX_EPOCH;
+use tokio::io::{AsyncRead, AsyncReadExt};
 use tokio::sync::{mpsc, Semaphore};
 use tokio::task::JoinHandle;
 use tokio::time::Instant;
 use uuid::Uuid;
 
+use crate::config::Config;
 use crate::database::{
-    ChatSessionRepository, DatabaseManager, MessageRepository, ProjectRepository,
-    ToolOperationRepository,
+    AttachmentRepository, ChatSessionRepository, DatabaseManager, EventRepository,
+    ImportErrorRepository, ImportJournalRepository, MessageRepository, MessageUsageRepository,
+    ParserWarningRepository, ProjectRepository, RawPayloadRepository, SessionQualityRepository,
+    SessionSegmentRepository, SessionTagRepository, ToolOperationRepository,
 };
 use crate::models::bash_metadata::BashMetadata;
-use crate::models::ToolOperation;
+use crate::models::{
+    EventKind, FormatDrift, ImportError, Provider, SessionQuality, SessionSegment, SessionState,
+    TitleSource, ToolOperation,
+};
 use crate::parsers::ParserRegistry;
+use crate::services::import_ignore::ImportIgnore;
+use crate::services::import_limits::RowLimits;
+use crate::services::project_config::ProjectConfigCache;
+use crate::services::raw_payloads::collect_raw_payloads;
 use crate::tools::parsers::{
-    bash::BashParser, edit::EditParser, read::ReadParser, write::WriteParser, ToolData, ToolParser,
+    bash::BashParser, edit::EditParser, notebook_edit::NotebookEditParser, read::ReadParser,
+    write::WriteParser, ToolData, ToolParser,
 };
+use crate::tools::McpToolRules;
 use crate::utils::bash_utils;
+use crate::utils::session_title::title_from_messages;
+use crate::utils::token_estimate::estimate_tokens;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ScanRequest {
@@ -61,6 +77,12 @@ pub struct ImportFileResponse {
     pub import_duration_ms: i32,
     pub file_size_bytes: i64,
     pub warnings: Vec<String>,
+    /// Messages cut down to `import.max_message_kb`
+    #[serde(default)]
+    pub messages_truncated: i32,
+    /// Tool results cut down to `import.max_tool_result_kb`
+    #[serde(default)]
+    pub tool_results_truncated: i32,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -81,32 +103,175 @@ pub struct BatchImportResponse {
     pub total_messages_imported: i32,
     pub batch_duration_ms: i32,
     pub errors: Vec<String>,
+    #[serde(default)]
+    pub messages_truncated: i32,
+    #[serde(default)]
+    pub tool_results_truncated: i32,
+}
+
+/// What an import would do with a session that was found in a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionChange {
+    /// Not in the database yet
+    New,
+    /// Already imported, but the source file has changed since
+    Updated,
+    /// Already imported from an identical file
+    Unchanged,
+    /// Left out by the `[ignore]` rules
+    Ignored,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SessionPreview {
+    pub session_id: String,
+    pub provider: String,
+    pub project_name: Option<String>,
+    pub message_count: u32,
+    /// Message count of the stored copy, if the session was imported before
+    pub previous_message_count: Option<u32>,
+    pub change: SessionChange,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FilePreview {
+    pub file_path: String,
+    pub sessions: Vec<SessionPreview>,
+    /// Parse error, if the file could not be read
+    pub error: Option<String>,
+}
+
+/// Result of a dry-run import: nothing is written to the database
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ImportPreview {
+    pub files: Vec<FilePreview>,
+    pub new_sessions: i32,
+    pub updated_sessions: i32,
+    pub unchanged_sessions: i32,
+    #[serde(default)]
+    pub ignored_sessions: i32,
+    pub failed_files: i32,
+}
+
+impl ImportPreview {
+    /// Add a file's preview and update the totals
+    pub fn add_file(&mut self, file: FilePreview) {
+        if file.error.is_some() {
+            self.failed_files += 1;
+        }
+        for session in &file.sessions {
+            match session.change {
+                SessionChange::New => self.new_sessions += 1,
+                SessionChange::Updated => self.updated_sessions += 1,
+                SessionChange::Unchanged => self.unchanged_sessions += 1,
+                SessionChange::Ignored => self.ignored_sessions += 1,
+            }
+        }
+        self.files.push(file);
+    }
+}
+
+/// Outcome of cleaning up after imports that were interrupted
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ResumeReport {
+    /// Half-written sessions that were deleted
+    pub sessions_removed: u64,
+    /// Files that were imported again
+    pub files_resumed: i32,
+    pub sessions_imported: i32,
+    /// Files that no longer exist or failed to import again
+    pub errors: Vec<String>,
+}
+
+impl ResumeReport {
+    pub fn is_empty(&self) -> bool {
+        self.sessions_removed == 0 && self.files_resumed == 0 && self.errors.is_empty()
+    }
 }
 
 #[derive(Clone)]
 pub struct ImportService {
-    #[allow(dead_code)]
     db_manager: Arc<DatabaseManager>,
     max_concurrent_imports: usize,
+    /// Where attachment bytes are saved; `None` keeps only their metadata
+    attachment_dir: Option<PathBuf>,
+    /// Files and sessions to leave out
+    ignore: ImportIgnore,
+    /// Size past which messages and tool results are truncated
+    limits: RowLimits,
+    /// How to read the inputs and results of MCP tools
+    mcp_rules: McpToolRules,
+    /// Whether the provider's raw JSON is kept alongside normalized rows
+    keep_raw_payloads: bool,
+}
+
+/// What importing one file's sessions wrote
+#[derive(Debug, Default)]
+struct ImportedSessions {
+    sessions: i32,
+    messages: i32,
+    messages_truncated: i32,
+    tool_results_truncated: i32,
+    warnings: Vec<String>,
 }
 
 impl ImportService {
     pub fn new(db_manager: Arc<DatabaseManager>) -> Self {
         // Use number of CPU cores, with a reasonable max
         let max_concurrent = num_cpus::get().clamp(4, 16);
-        Self {
-            db_manager,
-            max_concurrent_imports: max_concurrent,
-        }
+        Self::with_concurrency(db_manager, max_concurrent)
     }
 
     pub fn with_concurrency(db_manager: Arc<DatabaseManager>, max_concurrent: usize) -> Self {
+        let config = Config::load().ok();
+        let attachment_dir = config
+            .as_ref()
+            .and_then(|config| config.attachments.blob_dir());
+        let keep_raw_payloads = config
+            .as_ref()
+            .is_some_and(|config| config.import.keep_raw_payloads());
         Self {
             db_manager,
             max_concurrent_imports: max_concurrent,
+            attachment_dir,
+            ignore: ImportIgnore::load(),
+            limits: RowLimits::load(),
+            mcp_rules: McpToolRules::load(),
+            keep_raw_payloads,
         }
     }
 
+    /// Replace the ignore rules read from the config file
+    pub fn with_ignore(mut self, ignore: ImportIgnore) -> Self {
+        self.ignore = ignore;
+        self
+    }
+
+    /// Replace the size limits read from the config file
+    pub fn with_limits(mut self, limits: RowLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Replace the MCP tool rules read from the config file
+    pub fn with_mcp_rules(mut self, mcp_rules: McpToolRules) -> Self {
+        self.mcp_rules = mcp_rules;
+        self
+    }
+
+    /// Override whether raw provider JSON is kept
+    pub fn with_raw_payloads(mut self, keep: bool) -> Self {
+        self.keep_raw_payloads = keep;
+        self
+    }
+
+    /// Override where attachment bytes are saved (`None` to keep none)
+    pub fn with_attachment_dir(mut self, dir: Option<PathBuf>) -> Self {
+        self.attachment_dir = dir;
+        self
+    }
+
     pub async fn scan_directory(&self, request: ScanRequest) -> Result<ScanResponse> {
         let start_time = Instant::now();
 
@@ -156,6 +321,9 @@ impl ImportService {
         let files = ParserRegistry::scan_directory(path, recursive, provider_filter.as_deref())?;
 
         for (file_path, provider) in files {
+            if self.ignore.ignores_path(&file_path) {
+                continue;
+            }
             let metadata = fs::metadata(&file_path)?;
             let file_size_bytes = metadata.len() as i64;
             let last_modified = metadata.modified()?.duration_since(UNIX_EPOCH)?.as_secs();
@@ -201,27 +369,76 @@ impl ImportService {
 
     /// Import sessions into the database
     ///
-    /// Returns (sessions_imported, messages_imported, warnings)
     async fn import_sessions(
         &self,
         sessions: Vec<(crate::models::ChatSession, Vec<crate::models::Message>)>,
         overwrite_existing: bool,
-    ) -> Result<(i32, i32, Vec<String>)> {
+    ) -> Result<ImportedSessions> {
+        let mut imported = ImportedSessions::default();
         let mut warnings = Vec::new();
-        let mut sessions_imported = 0;
-        let mut messages_imported = 0;
 
         let session_repo = ChatSessionRepository::new(&self.db_manager);
         let message_repo = MessageRepository::new(&self.db_manager);
+        let usage_repo = MessageUsageRepository::new(&self.db_manager);
+        let segment_repo = SessionSegmentRepository::new(&self.db_manager);
+        let attachment_repo = AttachmentRepository::new(&self.db_manager);
         let project_repo = ProjectRepository::new(&self.db_manager);
         let tool_operation_repo = ToolOperationRepository::new(&self.db_manager);
+        let event_repo = EventRepository::new(&self.db_manager);
+        let tag_repo = SessionTagRepository::new(&self.db_manager);
+        let quality_repo = SessionQualityRepository::new(&self.db_manager);
+        let raw_payload_repo = RawPayloadRepository::new(&self.db_manager);
+        let mut project_configs = ProjectConfigCache::default();
+
+        for (mut session, mut messages) in sessions {
+            if let Some(reason) = self.ignore.session_skip_reason(&session, messages.len()) {
+                tracing::debug!(session_id = %session.id, reason, "Skipping ignored session");
+                continue;
+            }
+
+            // Write each session as a unit, one writer at a time across processes
+            let _write_guard = self.db_manager.write_lock().await?;
+
+            // Sessions merged into another by hand stay merged
+            if let Ok(Some(merged_into)) = session_repo.get_merged_into(&session.id).await {
+                warnings.push(format!(
+                    "Session {} was merged into {merged_into}, skipping",
+                    session.id
+                ));
+                continue;
+            }
 
-        for (session, mut messages) in sessions {
             // Check if session already exists
-            let existing_session = session_repo.get_by_id(&session.id).await.ok().flatten();
+            let mut existing_session = session_repo.get_by_id(&session.id).await.ok().flatten();
+
+            // A half-written copy from an interrupted import is replaced as if absent
+            if existing_session.as_ref().is_some_and(|s| s.is_importing()) {
+                if let Err(e) = session_repo.delete(&session.id).await {
+                    warnings.push(format!(
+                        "Failed to delete incomplete session {}: {}",
+                        session.id, e
+                    ));
+                    continue;
+                }
+                existing_session = None;
+            }
+            let replaced = existing_session.is_some();
 
-            if existing_session.is_some() {
+            if let Some(existing) = existing_session {
+                if let Some(owner) = &existing.owner {
+                    warnings.push(format!(
+                        "Session {} was imported from {owner}'s export and is read-only, skipping",
+                        session.id
+                    ));
+                    continue;
+                }
                 if overwrite_existing {
+                    // LLM titles are expensive to regenerate, so keep them
+                    if existing.title_source == Some(TitleSource::Llm) {
+                        session.title = existing.title;
+                        session.title_source = existing.title_source;
+                    }
+
                     // Delete existing session and its messages
                     if let Err(e) = message_repo.delete_by_session(&session.id).await {
                         warnings.push(format!(
@@ -253,7 +470,44 @@ impl ImportService {
                 }
             }
 
-            // Insert session
+            // Redact what the project's .retrochat.toml asks for before anything is stored
+            let project_config = project_configs
+                .get(&self.db_manager, &session)
+                .await
+                .cloned();
+            let redactor = match project_config.as_ref().map(|config| config.redactor()) {
+                Some(Ok(redactor)) => redactor,
+                Some(Err(e)) => {
+                    warnings.push(format!("Ignoring project redaction rules: {e:#}"));
+                    None
+                }
+                None => None,
+            };
+            if let Some(redactor) = &redactor {
+                for message in &mut messages {
+                    message.content = redactor.redact(&message.content);
+                }
+                session.title = session.title.map(|title| redactor.redact(&title));
+            }
+
+            // Raw JSON is only carried as far as here unless it is to be kept
+            let raw_payloads = if self.keep_raw_payloads {
+                collect_raw_payloads(session.id, &mut messages, redactor.as_ref())
+            } else {
+                messages.iter_mut().for_each(|message| message.raw = None);
+                Vec::new()
+            };
+
+            // Fall back to a title derived from the first user prompt
+            if session.title.is_none() {
+                if let Some(title) = title_from_messages(&messages) {
+                    session.title = Some(title);
+                    session.title_source = Some(TitleSource::Heuristic);
+                }
+            }
+
+            // Insert session, marked as importing until its messages are written
+            let final_state = std::mem::replace(&mut session.state, SessionState::Importing);
             if let Err(e) = session_repo.create(&session).await {
                 warnings.push(format!("Failed to insert session {}: {}", session.id, e));
                 continue;
@@ -261,11 +515,11 @@ impl ImportService {
 
             // Extract and save tool operations FIRST (before messages)
             // Returns map of message_id -> (tool_operation_id, message_type)
-            let tool_op_links = match self
+            let (tool_op_links, tool_results_truncated) = match self
                 .extract_and_save_tool_operations(&tool_operation_repo, &messages)
                 .await
             {
-                Ok(links) => links,
+                Ok(saved) => saved,
                 Err(e) => {
                     warnings.push(format!(
                         "Failed to create tool operations for session {}: {}",
@@ -278,6 +532,9 @@ impl ImportService {
             };
 
             // Update messages with tool_operation_id and message_type
+            let mut usage = Vec::new();
+            let mut attachments = Vec::new();
+            let mut messages_truncated = 0;
             for message in &mut messages {
                 if let Some((tool_op_id, msg_type)) = tool_op_links.get(&message.id) {
                     message.tool_operation_id = Some(*tool_op_id);
@@ -286,6 +543,27 @@ impl ImportService {
                 // Clear transient fields before saving
                 message.tool_uses = None;
                 message.tool_results = None;
+                if let Some(mut record) = message.usage.take() {
+                    record.message_id = message.id;
+                    record.session_id = session.id;
+                    usage.push(record);
+                }
+                for mut attachment in message.attachments.take().unwrap_or_default() {
+                    attachment.message_id = message.id;
+                    attachment.session_id = session.id;
+                    if let Some(dir) = &self.attachment_dir {
+                        if let Err(e) = attachment.save_blob(dir) {
+                            warnings.push(format!("Failed to save attachment: {e}"));
+                        }
+                    }
+                    attachment.data = None;
+                    attachments.push(attachment);
+                }
+                if self.limits.truncate_message(message) {
+                    messages_truncated += 1;
+                }
+                message.estimated_tokens =
+                    Some(estimate_tokens(&message.content, &session.provider));
             }
 
             // Use bulk insert for messages
@@ -300,11 +578,113 @@ impl ImportService {
                 continue;
             }
 
-            sessions_imported += 1;
-            messages_imported += session_messages_imported;
+            if let Err(e) = raw_payload_repo.bulk_create(&raw_payloads).await {
+                warnings.push(format!(
+                    "Failed to save raw payloads for session {}: {}",
+                    session.id, e
+                ));
+            }
+
+            if let Err(e) = usage_repo.bulk_create(&usage).await {
+                warnings.push(format!(
+                    "Failed to save token usage for session {}: {}",
+                    session.id, e
+                ));
+            }
+
+            if let Err(e) = attachment_repo.bulk_create(&attachments).await {
+                warnings.push(format!(
+                    "Failed to save attachments for session {}: {}",
+                    session.id, e
+                ));
+            }
+
+            let segments = SessionSegment::from_messages(session.id, &messages);
+            if let Err(e) = segment_repo
+                .replace_for_session(&session.id, &segments)
+                .await
+            {
+                warnings.push(format!(
+                    "Failed to save context segments for session {}: {}",
+                    session.id, e
+                ));
+            }
+
+            let quality = match tool_operation_repo.get_by_session(&session.id).await {
+                Ok(operations) => {
+                    quality_repo
+                        .upsert(&SessionQuality::from_messages(
+                            session.id,
+                            &messages,
+                            &operations,
+                        ))
+                        .await
+                }
+                Err(e) => Err(e),
+            };
+            if let Err(e) = quality {
+                warnings.push(format!(
+                    "Failed to save quality signals for session {}: {}",
+                    session.id, e
+                ));
+            }
+
+            if let Err(e) = session_repo.refresh_stats(&session.id).await {
+                warnings.push(format!(
+                    "Failed to refresh statistics for session {}: {}",
+                    session.id, e
+                ));
+            }
+
+            if let Err(e) = session_repo
+                .set_state_by_ids(&[session.id], &final_state)
+                .await
+            {
+                warnings.push(format!(
+                    "Failed to complete import of session {}: {}",
+                    session.id, e
+                ));
+                let _ = session_repo.delete(&session.id).await;
+                continue;
+            }
+
+            if let Some(config) = project_config.filter(|config| !config.tags.is_empty()) {
+                if let Err(e) = tag_repo.set_tags(&session.id, &config.tags).await {
+                    warnings.push(format!("Failed to tag session {}: {}", session.id, e));
+                }
+            }
+
+            event_repo
+                .record(
+                    if replaced {
+                        EventKind::SessionReplaced
+                    } else {
+                        EventKind::SessionImported
+                    },
+                    Some(&session.id.to_string()),
+                    json!({
+                        "provider": session.provider.to_string(),
+                        "project": session.project_name,
+                        "messages": session_messages_imported,
+                    }),
+                )
+                .await;
+
+            if messages_truncated > 0 || tool_results_truncated > 0 {
+                warnings.push(format!(
+                    "Session {}: truncated {} oversized messages and {} tool results",
+                    session.id, messages_truncated, tool_results_truncated
+                ));
+            }
+
+            imported.sessions += 1;
+            imported.messages += session_messages_imported;
+            imported.messages_truncated += messages_truncated;
+            imported.tool_results_truncated += tool_results_truncated;
         }
 
-        Ok((sessions_imported, messages_imported, warnings))
+        imported.warnings = warnings;
+        Ok(imported)
     }
 
     pub async fn import_file(&self, request: ImportFileRequest) -> Result<ImportFileResponse> {
@@ -320,8 +700,21 @@ impl ImportService {
 
         let mut warnings = Vec::new();
 
+        if self.ignore.ignores_path(path) {
+            warnings.push("File matches ignore.paths, skipped".to_string());
+            return Ok(ImportFileResponse {
+                sessions_imported: 0,
+                messages_imported: 0,
+                import_duration_ms: start_time.elapsed().as_millis() as i32,
+                file_size_bytes,
+                warnings,
+                messages_truncated: 0,
+                tool_results_truncated: 0,
+            });
+        }
+
         // Detect provider if not provided (for validation)
-        let _provider = request.provider.unwrap_or_else(|| {
+        let provider = request.provider.unwrap_or_else(|| {
             self.detect_provider(
                 path.file_name().and_then(|n| n.to_str()).unwrap_or(""),
                 path.extension().and_then(|e| e.to_str()).unwrap_or(""),
@@ -329,25 +722,35 @@ impl ImportService {
         });
 
         // Parse the file using ParserRegistry
-        let sessions = match ParserRegistry::parse_file(path).await {
-            Ok(sessions) => sessions,
+        let sessions = match ParserRegistry::parse_file_with_drift(path).await {
+            Ok((parsed_provider, sessions, drift)) => {
+                self.record_drift(&parsed_provider.to_string(), &request.file_path, &drift)
+                    .await;
+                sessions
+            }
             Err(e) => {
                 let error_msg = e.to_string();
                 // Skip summary-only files silently (these are just metadata, not actual conversations)
                 if error_msg.contains("only summary entries") {
+                    self.clear_import_error(&request.file_path).await;
                     return Ok(ImportFileResponse {
                         sessions_imported: 0,
                         messages_imported: 0,
                         import_duration_ms: start_time.elapsed().as_millis() as i32,
                         file_size_bytes,
                         warnings: vec![],
+                        messages_truncated: 0,
+                        tool_results_truncated: 0,
                     });
                 }
-                warnings.push(format!("Failed to parse file: {error_msg}"));
+                self.record_import_error(&request.file_path, &provider, &error_msg)
+                    .await;
                 return Err(anyhow!("Failed to parse file: {error_msg}"));
             }
         };
 
+        self.clear_import_error(&request.file_path).await;
+
         if sessions.is_empty() {
             warnings.push("No sessions found in file".to_string());
             return Ok(ImportFileResponse {
@@ -356,24 +759,79 @@ impl ImportService {
                 import_duration_ms: start_time.elapsed().as_millis() as i32,
                 file_size_bytes,
                 warnings,
+                messages_truncated: 0,
+                tool_results_truncated: 0,
             });
         }
 
+        // Journal the file so an interrupted import is picked up again
+        let journal = ImportJournalRepository::new(&self.db_manager);
+        journal.begin(&request.file_path, Some(&provider)).await?;
+
         // Import sessions into database
-        let (sessions_imported, messages_imported, import_warnings) = self
+        let result = self
             .import_sessions(sessions, request.overwrite_existing.unwrap_or(false))
-            .await?;
+            .await;
+        journal.finish(&request.file_path).await?;
+        let imported = result?;
 
-        warnings.extend(import_warnings);
+        warnings.extend(imported.warnings);
 
         let import_duration_ms = start_time.elapsed().as_millis() as i32;
 
         Ok(ImportFileResponse {
-            sessions_imported,
-            messages_imported,
+            sessions_imported: imported.sessions,
+            messages_imported: imported.messages,
             import_duration_ms,
             file_size_bytes,
             warnings,
+            messages_truncated: imported.messages_truncated,
+            tool_results_truncated: imported.tool_results_truncated,
+        })
+    }
+
+    /// Import sessions from content that is not a file on disk, such as a
+    /// drag-dropped or uploaded log
+    ///
+    /// `source_name` stands in for the file path (a file name like
+    /// `<session-id>.jsonl` is enough). Parse failures are returned rather
+    /// than recorded in the import error ledger, and nothing is journaled,
+    /// since there is no file to retry.
+    pub async fn import_from_reader<R: AsyncRead + Unpin>(
+        &self,
+        provider: Provider,
+        source_name: &str,
+        mut reader: R,
+        overwrite_existing: bool,
+    ) -> Result<ImportFileResponse> {
+        let start_time = Instant::now();
+        let mut content = Vec::new();
+        reader
+            .read_to_end(&mut content)
+            .await
+            .context("Failed to read session content")?;
+        let file_size_bytes = content.len() as i64;
+
+        let provider_name = provider.to_string();
+        let (sessions, drift) =
+            ParserRegistry::parse_reader(provider, source_name, content.as_slice()).await?;
+        self.record_drift(&provider_name, source_name, &drift).await;
+
+        let mut warnings = Vec::new();
+        if sessions.is_empty() {
+            warnings.push("No sessions found in content".to_string());
+        }
+        let imported = self.import_sessions(sessions, overwrite_existing).await?;
+        warnings.extend(imported.warnings);
+
+        Ok(ImportFileResponse {
+            sessions_imported: imported.sessions,
+            messages_imported: imported.messages,
+            import_duration_ms: start_time.elapsed().as_millis() as i32,
+            file_size_bytes,
+            warnings,
+            messages_truncated: imported.messages_truncated,
+            tool_results_truncated: imported.tool_results_truncated,
         })
     }
 
@@ -398,6 +856,8 @@ impl ImportService {
                 total_messages_imported: 0,
                 batch_duration_ms: start_time.elapsed().as_millis() as i32,
                 errors: vec!["No files found for import".to_string()],
+                messages_truncated: 0,
+                tool_results_truncated: 0,
             });
         }
 
@@ -434,6 +894,8 @@ impl ImportService {
         let mut failed_imports = 0;
         let mut total_sessions_imported = 0;
         let mut total_messages_imported = 0;
+        let mut messages_truncated = 0;
+        let mut tool_results_truncated = 0;
         let mut errors = Vec::new();
 
         for result in results {
@@ -442,6 +904,8 @@ impl ImportService {
                     successful_imports += 1;
                     total_sessions_imported += import_response.sessions_imported;
                     total_messages_imported += import_response.messages_imported;
+                    messages_truncated += import_response.messages_truncated;
+                    tool_results_truncated += import_response.tool_results_truncated;
                 }
                 Ok((file_path, Err(e))) => {
                     failed_imports += 1;
@@ -465,16 +929,22 @@ impl ImportService {
             total_messages_imported,
             batch_duration_ms,
             errors,
+            messages_truncated,
+            tool_results_truncated,
         })
     }
 
     /// Extract tool operations from messages and save them to database
-    /// Returns a map of message_id -> (tool_operation_id, message_type)
+    /// Returns a map of message_id -> (tool_operation_id, message_type), and
+    /// how many tool results were truncated
     async fn extract_and_save_tool_operations(
         &self,
         tool_operation_repo: &ToolOperationRepository,
         messages: &[crate::models::Message],
-    ) -> Result<std::collections::HashMap<Uuid, (Uuid, crate::models::message::MessageType)>> {
+    ) -> Result<(
+        std::collections::HashMap<Uuid, (Uuid, crate::models::message::MessageType)>,
+        i32,
+    )> {
         use crate::models::message::MessageType;
         let mut message_links = std::collections::HashMap::new();
         let mut tool_operations = Vec::new();
@@ -483,7 +953,7 @@ impl ImportService {
         // This allows matching tool_results that are in separate messages from tool_uses
         let mut global_tool_results: std::collections::HashMap<
             String,
-            Vec<(&crate::models::message::ToolResult, Uuid)>,
+            Vec<(&crate::models::message::ToolResult, &crate::models::Message)>,
         > = std::collections::HashMap::new();
 
         for message in messages {
@@ -492,7 +962,7 @@ impl ImportService {
                     global_tool_results
                         .entry(tool_result.tool_use_id.clone())
                         .or_default()
-                        .push((tool_result, message.id));
+                        .push((tool_result, message));
                 }
             }
         }
@@ -508,12 +978,22 @@ impl ImportService {
                         .and_then(|vec| vec.first());
 
                     let tool_result = tool_result_data.map(|(tr, _)| *tr);
-                    let tool_result_message_id = tool_result_data.map(|(_, mid)| *mid);
+                    let tool_result_message = tool_result_data.map(|(_, m)| *m);
+                    let tool_result_message_id = tool_result_message.map(|m| m.id);
 
                     // Create base ToolOperation
                     let mut operation =
                         ToolOperation::from_tool_use(tool_use, tool_result, message.timestamp);
 
+                    // A result logged in its own message carries the time the tool finished
+                    if let Some(result_message) = tool_result_message.filter(|m| m.id != message.id)
+                    {
+                        let duration = result_message.timestamp - message.timestamp;
+                        if duration >= chrono::Duration::zero() {
+                            operation = operation.with_duration_ms(duration.num_milliseconds());
+                        }
+                    }
+
                     // Parse tool-specific data and extract metrics
                     match tool_use.name.as_str() {
                         "Edit" => {
@@ -556,6 +1036,19 @@ impl ImportService {
                                 }
                             }
                         }
+                        "NotebookEdit" => {
+                            let parser = NotebookEditParser;
+                            if let Ok(parsed) = parser.parse(tool_use) {
+                                if let ToolData::NotebookEdit(data) = parsed.data {
+                                    // Only an inserted cell is known to add all its lines
+                                    let lines_before = (data.edit_mode() == "insert").then_some(0);
+                                    operation = operation
+                                        .with_file_path(data.notebook_path.clone())
+                                        .with_file_type(data.is_code_cell(), false)
+                                        .with_line_metrics(lines_before, data.lines_after());
+                                }
+                            }
+                        }
                         "Bash" => {
                             let parser = BashParser;
                             if let Ok(parsed) = parser.parse(tool_use) {
@@ -657,8 +1150,9 @@ impl ImportService {
                             }
                         }
                         _ => {
-                            // For other tools (Task, etc.), just save the basic info
-                            // File-related fields will be None
+                            // For other tools (Task, Grep, etc.), just save the basic info;
+                            // configured MCP tools also get their subject and success
+                            operation = self.mcp_rules.apply(operation, tool_use, tool_result);
                         }
                     }
 
@@ -688,12 +1182,19 @@ impl ImportService {
             }
         }
 
+        let mut truncated = 0;
+        for operation in &mut tool_operations {
+            if self.limits.truncate_tool_operation(operation) {
+                truncated += 1;
+            }
+        }
+
         // Bulk create all tool operations
         if !tool_operations.is_empty() {
             tool_operation_repo.bulk_create(&tool_operations).await?;
         }
 
-        Ok(message_links)
+        Ok((message_links, truncated))
     }
 
     /// Import files with progress reporting
@@ -726,6 +1227,8 @@ impl ImportService {
                 total_messages_imported: 0,
                 batch_duration_ms: start_time.elapsed().as_millis() as i32,
                 errors: vec!["No files found for import".to_string()],
+                messages_truncated: 0,
+                tool_results_truncated: 0,
             });
         }
 
@@ -783,6 +1286,8 @@ impl ImportService {
         let mut failed_imports = 0;
         let mut total_sessions_imported = 0;
         let mut total_messages_imported = 0;
+        let mut messages_truncated = 0;
+        let mut tool_results_truncated = 0;
         let mut errors = Vec::new();
 
         for result in results {
@@ -791,6 +1296,8 @@ impl ImportService {
                     successful_imports += 1;
                     total_sessions_imported += import_response.sessions_imported;
                     total_messages_imported += import_response.messages_imported;
+                    messages_truncated += import_response.messages_truncated;
+                    tool_results_truncated += import_response.tool_results_truncated;
                 }
                 Ok((file_path, Err(e))) => {
                     failed_imports += 1;
@@ -814,9 +1321,181 @@ impl ImportService {
             total_messages_imported,
             batch_duration_ms,
             errors,
+            messages_truncated,
+            tool_results_truncated,
+        })
+    }
+
+    /// Clean up after imports that were interrupted by a crash or Ctrl-C
+    ///
+    /// Sessions still marked as importing are deleted, then every file whose
+    /// import never finished is imported again. Sessions that were completed
+    /// before the interruption are left alone.
+    pub async fn resume_interrupted_imports(&self) -> Result<ResumeReport> {
+        let mut report = ResumeReport::default();
+        let session_repo = ChatSessionRepository::new(&self.db_manager);
+        {
+            // Other processes only leave importing sessions behind mid-write
+            let _write_guard = self.db_manager.write_lock().await?;
+            report.sessions_removed = session_repo
+                .delete_by_state(&SessionState::Importing)
+                .await?;
+        }
+
+        let journal = ImportJournalRepository::new(&self.db_manager);
+        for entry in journal.list().await? {
+            if !Path::new(&entry.file_path).is_file() {
+                report
+                    .errors
+                    .push(format!("{}: file no longer exists", entry.file_path));
+                journal.finish(&entry.file_path).await?;
+                continue;
+            }
+
+            let request = ImportFileRequest {
+                file_path: entry.file_path.clone(),
+                provider: entry.provider,
+                project_name: None,
+                overwrite_existing: Some(false),
+            };
+            match self.import_file(request).await {
+                Ok(response) => {
+                    report.files_resumed += 1;
+                    report.sessions_imported += response.sessions_imported;
+                }
+                Err(e) => {
+                    report
+                        .errors
+                        .push(Self::format_import_error(&entry.file_path, &e));
+                    journal.finish(&entry.file_path).await?;
+                }
+            }
+        }
+
+        if report.sessions_removed > 0 || report.files_resumed > 0 {
+            tracing::info!(
+                sessions_removed = report.sessions_removed,
+                files_resumed = report.files_resumed,
+                "Resumed interrupted imports"
+            );
+        }
+        Ok(report)
+    }
+
+    /// Report what importing a file would do without writing anything
+    ///
+    /// Each parsed session is compared against the stored copy by file hash:
+    /// unknown sessions are new, sessions whose source hash differs are
+    /// updated, and the rest are unchanged.
+    pub async fn preview_file(&self, file_path: &str) -> Result<FilePreview> {
+        let path = Path::new(file_path);
+        if !path.exists() || !path.is_file() {
+            return Err(anyhow!("Invalid file path: {file_path}"));
+        }
+
+        let sessions = match ParserRegistry::parse_file(path).await {
+            Ok(sessions) => sessions,
+            Err(e) => {
+                let error_msg = e.to_string();
+                return Ok(FilePreview {
+                    file_path: file_path.to_string(),
+                    sessions: Vec::new(),
+                    // Summary-only files are skipped by a real import as well
+                    error: (!error_msg.contains("only summary entries"))
+                        .then(|| format!("Failed to parse file: {error_msg}")),
+                });
+            }
+        };
+
+        let session_repo = ChatSessionRepository::new(&self.db_manager);
+        let mut previews = Vec::with_capacity(sessions.len());
+        for (session, messages) in sessions {
+            let existing = session_repo.get_by_id(&session.id).await?;
+            let ignored = self
+                .ignore
+                .session_skip_reason(&session, messages.len())
+                .is_some();
+            let merged = session_repo.get_merged_into(&session.id).await?.is_some();
+            let change = match &existing {
+                _ if ignored => SessionChange::Ignored,
+                // Its messages already live in the session it was merged into
+                None if merged => SessionChange::Unchanged,
+                None => SessionChange::New,
+                Some(stored) if stored.file_hash == session.file_hash => SessionChange::Unchanged,
+                Some(_) => SessionChange::Updated,
+            };
+
+            previews.push(SessionPreview {
+                session_id: session.id.to_string(),
+                provider: session.provider.to_string(),
+                project_name: session.project_name.clone(),
+                message_count: session.message_count,
+                previous_message_count: existing.map(|s| s.message_count),
+                change,
+            });
+        }
+
+        Ok(FilePreview {
+            file_path: file_path.to_string(),
+            sessions: previews,
+            error: None,
         })
     }
 
+    /// Dry-run counterpart of [`ImportService::import_batch`]
+    pub async fn preview_batch(&self, request: BatchImportRequest) -> Result<ImportPreview> {
+        let scan_response = self
+            .scan_directory(ScanRequest {
+                directory_path: request.directory_path,
+                providers: request.providers,
+                recursive: request.recursive,
+            })
+            .await?;
+
+        let mut preview = ImportPreview::default();
+        for file in scan_response.files_found {
+            preview.add_file(self.preview_file(&file.file_path).await?);
+        }
+
+        Ok(preview)
+    }
+
+    /// List files whose most recent import attempt failed, newest first
+    pub async fn get_import_errors(&self, limit: Option<i64>) -> Result<Vec<ImportError>> {
+        ImportErrorRepository::new(&self.db_manager)
+            .list(limit)
+            .await
+    }
+
+    /// Add a parse failure to the import error ledger
+    ///
+    /// Ledger failures are logged rather than returned so they never mask the
+    /// original import error.
+    async fn record_import_error(&self, file_path: &str, provider: &str, error_msg: &str) {
+        let repo = ImportErrorRepository::new(&self.db_manager);
+        if let Err(e) = repo
+            .record_failure(file_path, Some(provider), error_msg)
+            .await
+        {
+            tracing::warn!(error = %e, file_path, "Failed to record import error");
+        }
+    }
+
+    /// Store unrecognized log structure met while parsing a file
+    async fn record_drift(&self, provider: &str, file_path: &str, drift: &[FormatDrift]) {
+        let repo = ParserWarningRepository::new(&self.db_manager);
+        if let Err(e) = repo.record(provider, file_path, drift).await {
+            tracing::warn!(error = %e, file_path, "Failed to record parser warnings");
+        }
+    }
+
+    async fn clear_import_error(&self, file_path: &str) {
+        let repo = ImportErrorRepository::new(&self.db_manager);
+        if let Err(e) = repo.clear(file_path).await {
+            tracing::warn!(error = %e, file_path, "Failed to clear import error");
+        }
+    }
+
     /// Format import error with file path and truncate long messages
     fn format_import_error(file_path: &str, error: &anyhow::Error) -> String {
         let error_str = error.to_string();
@@ -917,7 +1596,7 @@ mod tests {
             result.is_ok(),
             "Should successfully extract tool operations"
         );
-        let message_links = result.unwrap();
+        let (message_links, _) = result.unwrap();
 
         // Verify: Both messages should be linked to the same ToolOperation
         assert_eq!(
@@ -995,7 +1674,7 @@ mod tests {
             .await;
 
         assert!(result.is_ok());
-        let message_links = result.unwrap();
+        let (message_links, _) = result.unwrap();
 
         // Verify: Only one message should be linked (same message contains both)
         assert_eq!(message_links.len(), 1);
@@ -1011,4 +1690,280 @@ mod tests {
         assert_eq!(op.tool_name, "Write");
         assert_eq!(op.success, Some(true)); // Should have result data
     }
+
+    #[tokio::test]
+    async fn test_extract_tool_operations_applies_mcp_rules() {
+        let db = DatabaseManager::open_in_memory().await.unwrap();
+        let tool_operation_repo = ToolOperationRepository::new(&db);
+        let service = ImportService::new(Arc::new(db)).with_mcp_rules(McpToolRules::new(&[
+            crate::config::McpToolRuleConfig {
+                tool: "mcp__linear__*".to_string(),
+                subject: Some("$.title".to_string()),
+                success: Some("$.success".to_string()),
+                summary: Some("$.issue.identifier".to_string()),
+            },
+        ]));
+
+        let tool_use = ToolUse {
+            id: "toolu_mcp".to_string(),
+            name: "mcp__linear__create_issue".to_string(),
+            input: json!({"title": "Flaky sync test"}),
+            raw: json!({}),
+        };
+        let tool_result = ToolResult {
+            tool_use_id: "toolu_mcp".to_string(),
+            content: r#"{"success": true, "issue": {"identifier": "ENG-12"}}"#.to_string(),
+            is_error: false,
+            details: None,
+            raw: json!({}),
+        };
+        let msg = Message::new(
+            Uuid::new_v4(),
+            MessageRole::Assistant,
+            "Filing an issue".to_string(),
+            Utc::now(),
+            1,
+        )
+        .with_tool_uses(vec![tool_use])
+        .with_tool_results(vec![tool_result]);
+
+        let (message_links, _) = service
+            .extract_and_save_tool_operations(&tool_operation_repo, std::slice::from_ref(&msg))
+            .await
+            .unwrap();
+
+        let (tool_op_id, _) = message_links.get(&msg.id).unwrap();
+        let op = tool_operation_repo
+            .get_by_id(tool_op_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(op.success, Some(true));
+        assert_eq!(
+            op.result_summary.as_deref(),
+            Some("Flaky sync test: ENG-12")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parse_failure_is_recorded_in_import_errors() {
+        let db = Arc::new(DatabaseManager::open_in_memory().await.unwrap());
+        let service = ImportService::new(db.clone());
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("broken.jsonl");
+        fs::write(&file_path, "{not valid json\n").unwrap();
+        let file_path = file_path.to_string_lossy().to_string();
+
+        for _ in 0..2 {
+            let result = service
+                .import_file(ImportFileRequest {
+                    file_path: file_path.clone(),
+                    provider: None,
+                    project_name: None,
+                    overwrite_existing: None,
+                })
+                .await;
+            assert!(result.is_err());
+        }
+
+        let errors = service.get_import_errors(None).await.unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].file_path, file_path);
+        assert_eq!(errors[0].attempt_count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_preview_file_reports_new_unchanged_and_updated_sessions() {
+        let db = Arc::new(DatabaseManager::open_in_memory().await.unwrap());
+        let service = ImportService::new(db.clone());
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir
+            .path()
+            .join("550e8400-e29b-41d4-a716-446655440000.jsonl");
+        let line = |minute: u32, role: &str, content: &str| {
+            format!(
+                r#"{{"type":"conversation","sessionId":"550e8400-e29b-41d4-a716-446655440000","timestamp":"2024-01-01T00:0{minute}:00Z","message":{{"role":"{role}","content":"{content}"}}}}"#
+            )
+        };
+        let content = [line(0, "user", "Hello"), line(1, "assistant", "Hi there!")].join("\n");
+        fs::write(&file_path, &content).unwrap();
+        let file_path_str = file_path.to_string_lossy().to_string();
+
+        let preview = service.preview_file(&file_path_str).await.unwrap();
+        assert_eq!(preview.sessions.len(), 1);
+        assert_eq!(preview.sessions[0].change, SessionChange::New);
+        assert_eq!(
+            ChatSessionRepository::new(&db).count().await.unwrap(),
+            0,
+            "preview must not write"
+        );
+
+        service
+            .import_file(ImportFileRequest {
+                file_path: file_path_str.clone(),
+                provider: None,
+                project_name: None,
+                overwrite_existing: Some(false),
+            })
+            .await
+            .unwrap();
+
+        let preview = service.preview_file(&file_path_str).await.unwrap();
+        assert_eq!(preview.sessions[0].change, SessionChange::Unchanged);
+
+        fs::write(
+            &file_path,
+            format!("{content}\n{}", line(2, "user", "Thanks")),
+        )
+        .unwrap();
+
+        let preview = service.preview_file(&file_path_str).await.unwrap();
+        assert_eq!(preview.sessions[0].change, SessionChange::Updated);
+        assert_eq!(preview.sessions[0].previous_message_count, Some(2));
+        assert_eq!(preview.sessions[0].message_count, 3);
+    }
+
+    #[tokio::test]
+    async fn test_import_refreshes_cached_session_stats() {
+        let db = Arc::new(DatabaseManager::open_in_memory().await.unwrap());
+        let service = ImportService::new(db.clone());
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let session_id = "550e8400-e29b-41d4-a716-446655440001";
+        let file_path = temp_dir.path().join(format!("{session_id}.jsonl"));
+        let content = [
+            format!(
+                r#"{{"type":"user","sessionId":"{session_id}","uuid":"u1","timestamp":"2024-01-01T00:00:00Z","message":{{"role":"user","content":"List files"}}}}"#
+            ),
+            format!(
+                r#"{{"type":"assistant","sessionId":"{session_id}","uuid":"a1","timestamp":"2024-01-01T00:01:00Z","message":{{"role":"assistant","content":[{{"type":"tool_use","id":"toolu_1","name":"Bash","input":{{"command":"ls"}}}}]}}}}"#
+            ),
+            format!(
+                r#"{{"type":"user","sessionId":"{session_id}","uuid":"u2","timestamp":"2024-01-01T00:01:30Z","message":{{"role":"user","content":[{{"type":"tool_result","tool_use_id":"toolu_1","content":"src"}}]}}}}"#
+            ),
+        ]
+        .join("\n");
+        fs::write(&file_path, content).unwrap();
+
+        service
+            .import_file(ImportFileRequest {
+                file_path: file_path.to_string_lossy().to_string(),
+                provider: None,
+                project_name: None,
+                overwrite_existing: Some(false),
+            })
+            .await
+            .unwrap();
+
+        let session = ChatSessionRepository::new(&db)
+            .get_by_id(&Uuid::parse_str(session_id).unwrap())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(session.message_count, 3);
+        assert_eq!(session.tool_op_count, 1);
+        assert_eq!(session.duration_seconds, Some(90));
+    }
+
+    #[tokio::test]
+    async fn test_import_from_reader() {
+        let db = Arc::new(DatabaseManager::open_in_memory().await.unwrap());
+        let service = ImportService::new(db.clone());
+
+        let session_id = "550e8400-e29b-41d4-a716-446655440003";
+        let content = [
+            format!(
+                r#"{{"type":"user","sessionId":"{session_id}","uuid":"u1","timestamp":"2024-01-01T00:00:00Z","message":{{"role":"user","content":"Hello"}}}}"#
+            ),
+            format!(
+                r#"{{"type":"assistant","sessionId":"{session_id}","uuid":"a1","timestamp":"2024-01-01T00:01:00Z","message":{{"role":"assistant","content":"Hi there!"}}}}"#
+            ),
+        ]
+        .join("\n");
+
+        let response = service
+            .import_from_reader(
+                Provider::ClaudeCode,
+                &format!("{session_id}.jsonl"),
+                content.as_bytes(),
+                false,
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.sessions_imported, 1);
+        assert_eq!(response.messages_imported, 2);
+        assert_eq!(response.file_size_bytes, content.len() as i64);
+
+        let session = ChatSessionRepository::new(&db)
+            .get_by_id(&Uuid::parse_str(session_id).unwrap())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(session.file_path, format!("{session_id}.jsonl"));
+
+        assert!(service
+            .import_from_reader(Provider::CursorClient, "state.vscdb", &b""[..], false)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_resume_interrupted_import() {
+        let db = Arc::new(DatabaseManager::open_in_memory().await.unwrap());
+        let service = ImportService::new(db.clone());
+        let session_repo = ChatSessionRepository::new(&db);
+        let journal = ImportJournalRepository::new(&db);
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let session_id = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440002").unwrap();
+        let file_path = temp_dir.path().join(format!("{session_id}.jsonl"));
+        let content = [
+            format!(
+                r#"{{"type":"conversation","sessionId":"{session_id}","timestamp":"2024-01-01T00:00:00Z","message":{{"role":"user","content":"Hello"}}}}"#
+            ),
+            format!(
+                r#"{{"type":"conversation","sessionId":"{session_id}","timestamp":"2024-01-01T00:01:00Z","message":{{"role":"assistant","content":"Hi there!"}}}}"#
+            ),
+        ]
+        .join("\n");
+        fs::write(&file_path, content).unwrap();
+        let file_path = file_path.to_string_lossy().to_string();
+
+        service
+            .import_file(ImportFileRequest {
+                file_path: file_path.clone(),
+                provider: None,
+                project_name: None,
+                overwrite_existing: Some(false),
+            })
+            .await
+            .unwrap();
+        assert!(journal.list().await.unwrap().is_empty());
+
+        // Simulate a crash after the session row was written but before its messages
+        MessageRepository::new(&db)
+            .delete_by_session(&session_id)
+            .await
+            .unwrap();
+        session_repo
+            .set_state_by_ids(&[session_id], &SessionState::Importing)
+            .await
+            .unwrap();
+        journal.begin(&file_path, None).await.unwrap();
+
+        let report = service.resume_interrupted_imports().await.unwrap();
+        assert_eq!(report.sessions_removed, 1);
+        assert_eq!(report.files_resumed, 1);
+        assert_eq!(report.sessions_imported, 1);
+        assert!(journal.list().await.unwrap().is_empty());
+
+        let session = session_repo.get_by_id(&session_id).await.unwrap().unwrap();
+        assert!(!session.is_importing());
+        assert_eq!(session.message_count, 2);
+
+        let report = service.resume_interrupted_imports().await.unwrap();
+        assert!(report.is_empty());
+    }
 }
@@ -30,6 +30,9 @@ struct ClaudeCodeOutput {
     session_id: Option<String>,
     #[serde(default)]
     usage: Option<ClaudeUsage>,
+    /// Response to a `--json-schema` request
+    #[serde(default)]
+    structured_output: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -76,13 +79,14 @@ impl ClaudeCodeClient {
                 });
             }
 
-            let text =
-                output
-                    .result
-                    .or(output.content)
-                    .ok_or_else(|| LlmError::InvalidResponse {
-                        message: "No result or content in Claude Code output".to_string(),
-                    })?;
+            let text = output
+                .structured_output
+                .map(|value| value.to_string())
+                .or(output.result)
+                .or(output.content)
+                .ok_or_else(|| LlmError::InvalidResponse {
+                    message: "No result or content in Claude Code output".to_string(),
+                })?;
 
             let token_usage = output.usage.map(|u| TokenUsage {
                 input_tokens: u.input_tokens,
@@ -137,7 +141,7 @@ impl LlmClient for ClaudeCodeClient {
         // Using stdin instead of command-line argument to:
         // - Avoid OS argument length limits (typically 128KB-2MB)
         // - Handle special characters without escaping issues
-        let args = vec![
+        let mut args = vec![
             "-p", // Print mode, reads prompt from stdin
             "--output-format",
             "json",
@@ -148,6 +152,13 @@ impl LlmClient for ClaudeCodeClient {
             "user", // Only load user settings, skip project/local CLAUDE.md files
         ];
 
+        // Structured output: the CLI forces a final tool call matching the
+        // schema and returns its input as `structured_output`
+        let schema = request.response_schema.as_ref().map(|s| s.to_string());
+        if let Some(schema) = &schema {
+            args.extend(["--json-schema", schema.as_str()]);
+        }
+
         let result =
             run_cli_command_with_stdin(&self.cli_path, &args, &request.prompt, self.timeout_secs)
                 .await?;
@@ -245,6 +256,17 @@ mod tests {
         assert_eq!(response.text, "Hello, world!");
     }
 
+    #[test]
+    fn test_parse_structured_output() {
+        let config = LlmConfig::claude_code();
+        let client = ClaudeCodeClient::new(config).unwrap();
+
+        let json = r#"{"result": "Here is the score.", "structured_output": {"score": 4}}"#;
+        let response = client.parse_output(json, "").unwrap();
+
+        assert_eq!(response.text, r#"{"score":4}"#);
+    }
+
     #[test]
     fn test_parse_plain_text_output() {
         let config = LlmConfig::claude_code();
@@ -9,6 +9,7 @@ use serde::Deserialize;
 use crate::env::llm as env_llm;
 
 use super::super::errors::LlmError;
+use super::super::structured::schema_instruction;
 use super::super::subprocess::{check_cli_available, run_cli_command, run_cli_command_with_stdin};
 use super::super::traits::LlmClient;
 use super::super::types::{GenerateRequest, GenerateResponse, LlmConfig, TokenUsage};
@@ -167,9 +168,14 @@ impl LlmClient for GeminiCliClient {
             "none", // Disable extensions
         ];
 
+        // Gemini CLI has no schema option, so ask for the schema in the prompt
+        let prompt = match &request.response_schema {
+            Some(schema) => format!("{}{}", request.prompt, schema_instruction(schema)),
+            None => request.prompt,
+        };
+
         let result =
-            run_cli_command_with_stdin(&self.cli_path, &args, &request.prompt, self.timeout_secs)
-                .await?;
+            run_cli_command_with_stdin(&self.cli_path, &args, &prompt, self.timeout_secs).await?;
 
         if result.exit_code != 0 {
             return Err(LlmError::CliExecutionError {
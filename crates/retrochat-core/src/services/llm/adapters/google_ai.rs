@@ -32,9 +32,10 @@ impl GoogleAiAdapter {
             gai_config = gai_config.with_model(model.clone());
         }
 
+        // Retries are applied around the adapter by the LLM layer
         gai_config = gai_config
             .with_timeout(Duration::from_secs(config.timeout_secs))
-            .with_max_retries(config.max_retries);
+            .with_max_retries(1);
 
         let model_name = gai_config.model.clone();
         let client = GoogleAiClient::new(gai_config).map_err(LlmError::from)?;
@@ -56,6 +57,7 @@ impl LlmClient for GoogleAiAdapter {
             prompt: request.prompt,
             max_tokens: request.max_tokens,
             temperature: request.temperature,
+            response_schema: request.response_schema,
         };
 
         let response = self
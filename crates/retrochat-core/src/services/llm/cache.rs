@@ -0,0 +1,281 @@
+//! Content-addressed cache for LLM responses
+//!
+//! [`CachingClient`] stores each response in the `llm_cache` table under a
+//! SHA-256 of the provider, model and request, so repeating a request (e.g.
+//! re-running an analysis after a crash, or over an unchanged transcript)
+//! is answered from the database instead of the provider. Cache failures
+//! are logged and never fail the request.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::errors::LlmError;
+use super::traits::LlmClient;
+use super::types::{GenerateRequest, GenerateResponse};
+use crate::config::Config;
+use crate::database::{DatabaseManager, LlmCacheRepository};
+
+/// Cache policy, read from `[llm.cache]` in the config file
+#[derive(Debug, Clone, PartialEq)]
+pub struct CacheConfig {
+    pub enabled: bool,
+    /// Responses older than this are not reused
+    pub ttl: Duration,
+    /// Least recently used responses are evicted beyond this size
+    pub max_size_bytes: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ttl: Duration::from_secs(30 * 24 * 60 * 60),
+            max_size_bytes: 100 * 1024 * 1024,
+        }
+    }
+}
+
+impl CacheConfig {
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            ..Default::default()
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        let settings = &config.llm.cache;
+        let defaults = Self::default();
+        Self {
+            enabled: settings.enabled.unwrap_or(defaults.enabled),
+            ttl: settings
+                .ttl_days
+                .map(|days| Duration::from_secs(days as u64 * 24 * 60 * 60))
+                .unwrap_or(defaults.ttl),
+            max_size_bytes: settings
+                .max_size_mb
+                .map(|mb| mb * 1024 * 1024)
+                .unwrap_or(defaults.max_size_bytes),
+        }
+    }
+
+    /// Policy from the config file, or the defaults if it cannot be read
+    pub fn load() -> Self {
+        match Config::load() {
+            Ok(config) => Self::from_config(&config),
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to load LLM cache settings, using defaults");
+                Self::default()
+            }
+        }
+    }
+}
+
+/// [`LlmClient`] decorator answering repeated requests from the cache
+pub struct CachingClient {
+    inner: Arc<dyn LlmClient>,
+    repo: LlmCacheRepository,
+    config: CacheConfig,
+}
+
+impl CachingClient {
+    pub fn new(inner: Arc<dyn LlmClient>, db: &DatabaseManager, config: CacheConfig) -> Self {
+        Self {
+            inner,
+            repo: LlmCacheRepository::new(db),
+            config,
+        }
+    }
+
+    /// Wrap `inner` using the configured cache policy, unless caching is off
+    pub fn wrap(inner: Arc<dyn LlmClient>, db: &DatabaseManager) -> Arc<dyn LlmClient> {
+        let config = CacheConfig::load();
+        if config.enabled {
+            Arc::new(Self::new(inner, db, config))
+        } else {
+            inner
+        }
+    }
+
+    fn cache_key(&self, request: &GenerateRequest) -> String {
+        let mut hasher = Sha256::new();
+        for part in [
+            self.inner.provider_name(),
+            self.inner.model_name(),
+            request.system_prompt.as_deref().unwrap_or_default(),
+            &request
+                .max_tokens
+                .map(|t| t.to_string())
+                .unwrap_or_default(),
+            &request
+                .temperature
+                .map(|t| t.to_string())
+                .unwrap_or_default(),
+            &request
+                .response_schema
+                .as_ref()
+                .map(|s| s.to_string())
+                .unwrap_or_default(),
+            &request.prompt,
+        ] {
+            // Length-prefix each part so adjacent fields cannot run together
+            hasher.update((part.len() as u64).to_le_bytes());
+            hasher.update(part.as_bytes());
+        }
+        hex::encode(hasher.finalize())
+    }
+
+    async fn lookup(&self, key: &str) -> Option<GenerateResponse> {
+        let not_before =
+            Utc::now() - chrono::Duration::from_std(self.config.ttl).unwrap_or_default();
+        match self.repo.get(key, not_before).await {
+            Ok(Some(json)) => match serde_json::from_str(&json) {
+                Ok(response) => Some(response),
+                Err(e) => {
+                    tracing::warn!(error = %e, "Ignoring unreadable LLM cache entry");
+                    None
+                }
+            },
+            Ok(None) => None,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to read LLM cache");
+                None
+            }
+        }
+    }
+
+    async fn store(&self, key: &str, response: &GenerateResponse) -> anyhow::Result<()> {
+        let json = serde_json::to_string(response)?;
+        self.repo
+            .put(
+                key,
+                self.inner.provider_name(),
+                self.inner.model_name(),
+                &json,
+            )
+            .await?;
+
+        let expired_before =
+            Utc::now() - chrono::Duration::from_std(self.config.ttl).unwrap_or_default();
+        self.repo
+            .prune(expired_before, self.config.max_size_bytes)
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl LlmClient for CachingClient {
+    async fn generate(&self, request: GenerateRequest) -> Result<GenerateResponse, LlmError> {
+        let key = self.cache_key(&request);
+        if let Some(response) = self.lookup(&key).await {
+            tracing::debug!(provider = self.inner.provider_name(), "LLM cache hit");
+            return Ok(response);
+        }
+
+        let response = self.inner.generate(request).await?;
+        if let Err(e) = self.store(&key, &response).await {
+            tracing::warn!(error = %e, "Failed to write LLM cache");
+        }
+        Ok(response)
+    }
+
+    fn provider_name(&self) -> &'static str {
+        self.inner.provider_name()
+    }
+
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+
+    async fn health_check(&self) -> Result<(), LlmError> {
+        self.inner.health_check().await
+    }
+
+    fn estimate_tokens(&self, text: &str) -> u32 {
+        self.inner.estimate_tokens(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Client echoing the prompt and counting calls
+    struct CountingClient {
+        calls: AtomicU32,
+    }
+
+    #[async_trait]
+    impl LlmClient for CountingClient {
+        async fn generate(&self, request: GenerateRequest) -> Result<GenerateResponse, LlmError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(GenerateResponse {
+                text: format!("echo: {}", request.prompt),
+                ..Default::default()
+            })
+        }
+
+        fn provider_name(&self) -> &'static str {
+            "counting"
+        }
+
+        fn model_name(&self) -> &str {
+            "counting"
+        }
+
+        async fn health_check(&self) -> Result<(), LlmError> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_repeated_requests_are_served_from_cache() {
+        let db = DatabaseManager::open_in_memory().await.unwrap();
+        let inner = Arc::new(CountingClient {
+            calls: AtomicU32::new(0),
+        });
+        let client = CachingClient::new(inner.clone(), &db, CacheConfig::default());
+
+        let request = GenerateRequest::new("hello".to_string()).with_temperature(0.7);
+        let first = client.generate(request.clone()).await.unwrap();
+        let second = client.generate(request.clone()).await.unwrap();
+        assert_eq!(first.text, "echo: hello");
+        assert_eq!(second.text, first.text);
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+
+        // Any change to the request is a different entry
+        client
+            .generate(request.with_temperature(0.3))
+            .await
+            .unwrap();
+        client
+            .generate(GenerateRequest::new("hello!".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_expired_entries_are_not_reused() {
+        let db = DatabaseManager::open_in_memory().await.unwrap();
+        let inner = Arc::new(CountingClient {
+            calls: AtomicU32::new(0),
+        });
+        let config = CacheConfig {
+            ttl: Duration::ZERO,
+            ..Default::default()
+        };
+        let client = CachingClient::new(inner.clone(), &db, config);
+
+        let request = GenerateRequest::new("hello".to_string());
+        client.generate(request.clone()).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        client.generate(request).await.unwrap();
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,210 @@
+//! Health checks for LLM providers
+//!
+//! Used by `retrochat analysis doctor` to find out which providers can run
+//! an analysis before one fails halfway through.
+
+use std::time::{Duration, Instant};
+
+use crate::env::llm as env_llm;
+
+use super::errors::LlmError;
+use super::factory::LlmClientFactory;
+use super::retry::RetryConfig;
+use super::types::{GenerateRequest, LlmConfig, LlmProvider};
+
+/// Prompt for the test generation; short so the check costs next to nothing
+const PROBE_PROMPT: &str = "Reply with the single word: ok";
+
+/// Timeout for the test generation
+const PROBE_TIMEOUT_SECS: u64 = 60;
+
+/// Result of checking one provider
+#[derive(Debug, Clone)]
+pub struct ProviderDiagnosis {
+    pub provider: LlmProvider,
+    /// Credentials or binary were found
+    pub available: bool,
+    /// How the provider was found, or why it was not
+    pub status: String,
+    /// Model that answered the test generation
+    pub model: Option<String>,
+    /// Round trip of the test generation
+    pub latency: Option<Duration>,
+    /// Why the test generation failed
+    pub error: Option<String>,
+    /// What to do about an unavailable or failing provider
+    pub suggestion: Option<String>,
+}
+
+impl ProviderDiagnosis {
+    /// Available and, if a generation was attempted, it succeeded
+    pub fn is_healthy(&self) -> bool {
+        self.available && self.error.is_none()
+    }
+}
+
+/// Check every provider, running a tiny test generation against each
+/// available one unless `probe` is false
+pub async fn diagnose_providers(probe: bool) -> Vec<ProviderDiagnosis> {
+    let mut results = Vec::new();
+
+    for (provider, available, status) in LlmClientFactory::list_available().await {
+        let mut diagnosis = ProviderDiagnosis {
+            provider,
+            available,
+            status,
+            model: None,
+            latency: None,
+            error: None,
+            suggestion: None,
+        };
+
+        if !available {
+            diagnosis.suggestion = Some(unavailable_suggestion(provider));
+        } else if probe {
+            match probe_provider(provider, None).await {
+                Ok((model, latency)) => {
+                    diagnosis.model = Some(model);
+                    diagnosis.latency = Some(latency);
+                }
+                Err(e) => {
+                    diagnosis.suggestion = Some(error_suggestion(provider, &e));
+                    diagnosis.error = Some(e.to_string());
+                }
+            }
+        }
+
+        results.push(diagnosis);
+    }
+
+    results
+}
+
+/// Send the test prompt once (no retries) and time the answer, returning the
+/// model that replied. `api_key` overrides the configured Google AI key so a
+/// key can be tested before it is saved.
+pub async fn probe_provider(
+    provider: LlmProvider,
+    api_key: Option<String>,
+) -> Result<(String, Duration), LlmError> {
+    let mut config = match provider {
+        LlmProvider::GoogleAi => {
+            let api_key = api_key
+                .or_else(|| crate::config::get_google_ai_api_key().ok().flatten())
+                .ok_or_else(|| LlmError::ConfigurationError {
+                    message: "No Google AI API key configured".to_string(),
+                })?;
+            LlmConfig::google_ai(api_key)
+        }
+        LlmProvider::ClaudeCode => LlmConfig::claude_code(),
+        LlmProvider::GeminiCli => LlmConfig::gemini_cli(),
+    };
+    let cli_path_var = match provider {
+        LlmProvider::GoogleAi => None,
+        LlmProvider::ClaudeCode => Some(env_llm::CLAUDE_CODE_PATH),
+        LlmProvider::GeminiCli => Some(env_llm::GEMINI_CLI_PATH),
+    };
+    if let Some(path) = cli_path_var.and_then(|var| std::env::var(var).ok()) {
+        config = config.with_cli_path(path);
+    }
+    let config = config
+        .with_timeout(PROBE_TIMEOUT_SECS)
+        .with_retry(RetryConfig::no_retry());
+
+    let client = LlmClientFactory::create(config)?;
+    let request = GenerateRequest::new(PROBE_PROMPT.to_string()).with_max_tokens(16);
+
+    let started = Instant::now();
+    let response = client.generate(request).await?;
+    let latency = started.elapsed();
+
+    if response.text.trim().is_empty() {
+        return Err(LlmError::InvalidResponse {
+            message: "Empty response to test prompt".to_string(),
+        });
+    }
+
+    Ok((
+        response
+            .model_used
+            .unwrap_or_else(|| client.model_name().to_string()),
+        latency,
+    ))
+}
+
+/// Suggest how to make an unavailable provider usable
+pub fn unavailable_suggestion(provider: LlmProvider) -> String {
+    match provider {
+        LlmProvider::GoogleAi => {
+            "Set GOOGLE_AI_API_KEY or run `retrochat config set google-ai-api-key <key>`"
+                .to_string()
+        }
+        LlmProvider::ClaudeCode => {
+            "Install Claude Code (`npm install -g @anthropic-ai/claude-code`) or set CLAUDE_CODE_PATH"
+                .to_string()
+        }
+        LlmProvider::GeminiCli => {
+            "Install Gemini CLI (`npm install -g @google/gemini-cli`) or set GEMINI_CLI_PATH"
+                .to_string()
+        }
+    }
+}
+
+/// Suggest a fix for a failed test generation
+pub fn error_suggestion(provider: LlmProvider, error: &LlmError) -> String {
+    match error {
+        LlmError::AuthenticationFailed { .. } => match provider {
+            LlmProvider::GoogleAi => {
+                "The API key was rejected; create a new one at https://aistudio.google.com/apikey"
+                    .to_string()
+            }
+            LlmProvider::ClaudeCode => "Run `claude` once to log in".to_string(),
+            LlmProvider::GeminiCli => "Run `gemini` once to log in".to_string(),
+        },
+        LlmError::ConfigurationError { .. } => unavailable_suggestion(provider),
+        LlmError::CliBinaryNotFound { .. } => unavailable_suggestion(provider),
+        LlmError::RateLimitExceeded { .. } => {
+            "Rate limited; wait a minute or lower concurrency before analyzing".to_string()
+        }
+        LlmError::QuotaExceeded { .. } => {
+            "Quota exhausted; check your plan's usage limits".to_string()
+        }
+        LlmError::Timeout { .. } | LlmError::NetworkError { .. } => {
+            "Check your network connection and any proxy settings".to_string()
+        }
+        LlmError::CliExecutionError { .. } => {
+            "Run the CLI by hand to see the full error (it may need an update or a login)"
+                .to_string()
+        }
+        _ => "Try again; if it keeps failing, run with RUST_LOG=debug for details".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_suggestion_is_provider_specific() {
+        let auth = LlmError::AuthenticationFailed {
+            message: "denied".to_string(),
+        };
+        assert!(error_suggestion(LlmProvider::ClaudeCode, &auth).contains("claude"));
+        assert!(error_suggestion(LlmProvider::GoogleAi, &auth).contains("API key"));
+
+        let missing = LlmError::CliBinaryNotFound {
+            path: "gemini".to_string(),
+        };
+        assert!(error_suggestion(LlmProvider::GeminiCli, &missing).contains("GEMINI_CLI_PATH"));
+    }
+
+    #[tokio::test]
+    async fn test_diagnose_without_probe_reports_every_provider() {
+        let results = diagnose_providers(false).await;
+        assert_eq!(results.len(), 3);
+        for diagnosis in results {
+            assert!(diagnosis.latency.is_none());
+            assert_eq!(diagnosis.suggestion.is_some(), !diagnosis.available);
+        }
+    }
+}
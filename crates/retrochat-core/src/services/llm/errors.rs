@@ -15,7 +15,11 @@ pub enum LlmError {
     AuthenticationFailed { message: String },
 
     #[error("Rate limit exceeded: {message}")]
-    RateLimitExceeded { message: String },
+    RateLimitExceeded {
+        message: String,
+        /// Delay requested by the server (Retry-After), if it sent one
+        retry_after_secs: Option<u64>,
+    },
 
     #[error("Request timeout after {timeout_secs}s")]
     Timeout { timeout_secs: u64 },
@@ -49,6 +53,9 @@ pub enum LlmError {
 
     #[error("Provider unavailable: {message}")]
     ProviderUnavailable { message: String },
+
+    #[error("Request cancelled")]
+    Cancelled,
 }
 
 impl LlmError {
@@ -118,6 +125,7 @@ impl LlmError {
             LlmError::InvalidRequest { message } => {
                 format!("Invalid request: {message}")
             }
+            LlmError::Cancelled => "Request was cancelled.".to_string(),
         }
     }
 }
@@ -130,7 +138,13 @@ impl From<crate::services::google_ai::GoogleAiError> for LlmError {
             GoogleAiError::AuthenticationFailed { message } => {
                 LlmError::AuthenticationFailed { message }
             }
-            GoogleAiError::RateLimitExceeded { message } => LlmError::RateLimitExceeded { message },
+            GoogleAiError::RateLimitExceeded {
+                message,
+                retry_after_secs,
+            } => LlmError::RateLimitExceeded {
+                message,
+                retry_after_secs,
+            },
             GoogleAiError::Timeout { timeout_ms } => LlmError::Timeout {
                 timeout_secs: timeout_ms / 1000,
             },
@@ -160,7 +174,8 @@ mod tests {
     #[test]
     fn test_is_retryable() {
         assert!(LlmError::RateLimitExceeded {
-            message: "test".to_string()
+            message: "test".to_string(),
+            retry_after_secs: None,
         }
         .is_retryable());
         assert!(LlmError::Timeout { timeout_secs: 30 }.is_retryable());
@@ -188,7 +203,8 @@ mod tests {
     fn test_retry_after_secs() {
         assert_eq!(
             LlmError::RateLimitExceeded {
-                message: "test".to_string()
+                message: "test".to_string(),
+                retry_after_secs: None,
             }
             .retry_after_secs(),
             Some(60)
@@ -9,6 +9,7 @@ use crate::env::{apis as env_apis, llm as env_llm};
 
 use super::adapters::{ClaudeCodeClient, GeminiCliClient, GoogleAiAdapter};
 use super::errors::LlmError;
+use super::retry::{RetryConfig, RetryingClient};
 use super::subprocess::check_cli_available;
 use super::traits::LlmClient;
 use super::types::{LlmConfig, LlmProvider};
@@ -18,42 +19,45 @@ pub struct LlmClientFactory;
 
 impl LlmClientFactory {
     /// Create an LLM client based on configuration
+    ///
+    /// The client retries failed requests according to `config.retry`, or
+    /// the retry settings in the config file when that is unset.
     pub fn create(config: LlmConfig) -> Result<Arc<dyn LlmClient>, LlmError> {
-        match config.provider {
-            LlmProvider::GoogleAi => {
-                let adapter = GoogleAiAdapter::new(config)?;
-                Ok(Arc::new(adapter))
-            }
-            LlmProvider::ClaudeCode => {
-                let client = ClaudeCodeClient::new(config)?;
-                Ok(Arc::new(client))
-            }
-            LlmProvider::GeminiCli => {
-                let client = GeminiCliClient::new(config)?;
-                Ok(Arc::new(client))
-            }
-        }
+        let retry = config
+            .retry
+            .clone()
+            .unwrap_or_else(|| RetryConfig::load_for(config.provider));
+
+        let client: Arc<dyn LlmClient> = match config.provider {
+            LlmProvider::GoogleAi => Arc::new(GoogleAiAdapter::new(config)?),
+            LlmProvider::ClaudeCode => Arc::new(ClaudeCodeClient::new(config)?),
+            LlmProvider::GeminiCli => Arc::new(GeminiCliClient::new(config)?),
+        };
+
+        Ok(Arc::new(RetryingClient::new(client, retry)))
     }
 
     /// Create an LLM client from environment variables
     ///
     /// Environment variables checked:
     /// - RETROCHAT_LLM_PROVIDER: "google-ai" | "claude-code" | "gemini-cli"
-    /// - RETROCHAT_LLM_MODEL: Model identifier (provider-specific)
+    ///   (falls back to `llm.provider` in the config file)
+    /// - RETROCHAT_LLM_MODEL: Model identifier (provider-specific; falls back
+    ///   to `llm.model`)
     /// - GOOGLE_AI_API_KEY: API key for Google AI (if provider is google-ai)
     /// - CLAUDE_CODE_PATH: Custom path to Claude CLI binary
     /// - GEMINI_CLI_PATH: Custom path to Gemini CLI binary
     pub fn from_env() -> Result<Arc<dyn LlmClient>, LlmError> {
-        let provider = std::env::var(env_llm::RETROCHAT_LLM_PROVIDER)
+        let provider = crate::config::get_llm_provider()
             .ok()
-            .and_then(|s| s.parse::<LlmProvider>().ok())
+            .flatten()
             .unwrap_or(LlmProvider::GoogleAi);
 
         let mut config = LlmConfig {
             provider,
-            model: std::env::var(env_llm::RETROCHAT_LLM_MODEL).ok(),
+            model: crate::config::get_llm_model().ok().flatten(),
             timeout_secs: 300,
-            max_retries: 3,
+            retry: None,
             api_key: None,
             cli_path: None,
         };
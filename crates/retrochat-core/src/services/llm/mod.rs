@@ -30,14 +30,23 @@
 //! ```
 
 pub mod adapters;
+pub mod cache;
+pub mod diagnostics;
 mod errors;
 mod factory;
+pub mod retry;
+pub mod structured;
 pub mod subprocess;
 mod traits;
 mod types;
+pub mod usage;
 
 // Re-export main types
+pub use cache::{CacheConfig, CachingClient};
 pub use errors::LlmError;
 pub use factory::LlmClientFactory;
+pub use retry::{track_retry_metrics, RetryConfig, RetryMetrics, RetryingClient};
+pub use structured::{track_parse_metrics, ParseMetrics};
 pub use traits::LlmClient;
 pub use types::{GenerateRequest, GenerateResponse, LlmConfig, LlmProvider, TokenUsage};
+pub use usage::{track_usage, LlmUsage};
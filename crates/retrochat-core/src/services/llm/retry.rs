@@ -0,0 +1,438 @@
+//! Retry policy shared by all LLM providers
+//!
+//! Clients built by [`LlmClientFactory`](super::LlmClientFactory) are wrapped
+//! in [`RetryingClient`], which retries retryable errors with jittered
+//! exponential backoff. The policy is read from `[llm.retry]` in the config
+//! file and can be overridden per provider under
+//! `[llm.retry.providers.<provider>]`.
+//!
+//! Retries are counted into [`RetryMetrics`] for any future run through
+//! [`track_retry_metrics`], which is how analysis requests record them.
+
+use async_trait::async_trait;
+use backoff::{backoff::Backoff, ExponentialBackoff};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::errors::LlmError;
+use super::traits::LlmClient;
+use super::types::{GenerateRequest, GenerateResponse, LlmProvider};
+use crate::config::{Config, RetrySettings};
+
+/// Retry policy for LLM requests
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryConfig {
+    /// Total attempts, including the first one
+    pub max_attempts: u32,
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    pub multiplier: f64,
+    /// Randomize each delay by ±50% so concurrent callers spread out
+    pub jitter: bool,
+    /// Wait as long as the server asks (Retry-After) instead of backing off
+    pub respect_retry_after: bool,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            multiplier: 2.0,
+            jitter: true,
+            respect_retry_after: true,
+        }
+    }
+}
+
+impl RetryConfig {
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            ..Default::default()
+        }
+    }
+
+    /// A single attempt; errors are returned as-is
+    pub fn no_retry() -> Self {
+        Self::new(1)
+    }
+
+    pub fn with_initial_delay(mut self, delay: Duration) -> Self {
+        self.initial_delay = delay;
+        self
+    }
+
+    pub fn with_max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    pub fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Override fields that are set in `settings`
+    pub fn with_settings(mut self, settings: &RetrySettings) -> Self {
+        if let Some(max_attempts) = settings.max_attempts {
+            self.max_attempts = max_attempts.max(1);
+        }
+        if let Some(ms) = settings.initial_delay_ms {
+            self.initial_delay = Duration::from_millis(ms);
+        }
+        if let Some(ms) = settings.max_delay_ms {
+            self.max_delay = Duration::from_millis(ms);
+        }
+        if let Some(multiplier) = settings.multiplier {
+            self.multiplier = multiplier;
+        }
+        if let Some(jitter) = settings.jitter {
+            self.jitter = jitter;
+        }
+        if let Some(respect) = settings.respect_retry_after {
+            self.respect_retry_after = respect;
+        }
+        self
+    }
+
+    /// Policy for `provider` from a loaded config: defaults, then `[llm.retry]`,
+    /// then the provider's own section
+    pub fn from_config(config: &Config, provider: LlmProvider) -> Self {
+        let retry = &config.llm.retry;
+        let mut policy = Self::default().with_settings(&retry.defaults);
+        if let Some(settings) = retry.providers.get(&provider.to_string()) {
+            policy = policy.with_settings(settings);
+        }
+        policy
+    }
+
+    /// Policy for `provider` from the config file, or the defaults if it
+    /// cannot be read
+    pub fn load_for(provider: LlmProvider) -> Self {
+        match Config::load() {
+            Ok(config) => Self::from_config(&config, provider),
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to load retry settings, using defaults");
+                Self::default()
+            }
+        }
+    }
+
+    fn backoff(&self) -> ExponentialBackoff {
+        ExponentialBackoff {
+            current_interval: self.initial_delay,
+            initial_interval: self.initial_delay,
+            max_interval: self.max_delay,
+            multiplier: self.multiplier,
+            randomization_factor: if self.jitter { 0.5 } else { 0.0 },
+            max_elapsed_time: None,
+            ..Default::default()
+        }
+    }
+}
+
+/// Errors the retry loop knows how to classify
+pub trait RetryableError: std::fmt::Display {
+    fn is_retryable(&self) -> bool;
+
+    /// Delay the server explicitly asked for (e.g. a Retry-After header)
+    fn server_retry_after(&self) -> Option<Duration>;
+}
+
+impl RetryableError for LlmError {
+    fn is_retryable(&self) -> bool {
+        LlmError::is_retryable(self)
+    }
+
+    fn server_retry_after(&self) -> Option<Duration> {
+        match self {
+            LlmError::RateLimitExceeded {
+                retry_after_secs, ..
+            } => retry_after_secs.map(Duration::from_secs),
+            _ => None,
+        }
+    }
+}
+
+/// Retry counts for the LLM calls made while a tracked future ran
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetryMetrics {
+    /// Requests sent, including retries
+    pub attempts: u32,
+    pub retries: u32,
+    /// Time spent waiting between attempts
+    pub wait_ms: u64,
+}
+
+#[derive(Default)]
+struct MetricsRecorder {
+    attempts: AtomicU32,
+    retries: AtomicU32,
+    wait_ms: AtomicU64,
+}
+
+tokio::task_local! {
+    static RETRY_METRICS: Arc<MetricsRecorder>;
+}
+
+fn record(update: impl FnOnce(&MetricsRecorder)) {
+    let _ = RETRY_METRICS.try_with(|recorder| update(recorder));
+}
+
+/// Run `future`, counting the attempts and retries of every LLM call it makes
+///
+/// Calls must happen on the same task; work moved to `tokio::spawn` is not
+/// counted.
+pub async fn track_retry_metrics<F: Future>(future: F) -> (F::Output, RetryMetrics) {
+    let recorder = Arc::new(MetricsRecorder::default());
+    let output = RETRY_METRICS.scope(recorder.clone(), future).await;
+    let metrics = RetryMetrics {
+        attempts: recorder.attempts.load(Ordering::Relaxed),
+        retries: recorder.retries.load(Ordering::Relaxed),
+        wait_ms: recorder.wait_ms.load(Ordering::Relaxed),
+    };
+    (output, metrics)
+}
+
+/// Run `operation` until it succeeds, fails with a non-retryable error, or
+/// runs out of attempts; the last error is returned
+pub async fn retry<T, E, F, Fut>(config: &RetryConfig, mut operation: F) -> Result<T, E>
+where
+    E: RetryableError,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut backoff = config.backoff();
+    let mut attempt = 0;
+
+    loop {
+        attempt += 1;
+        record(|m| {
+            m.attempts.fetch_add(1, Ordering::Relaxed);
+        });
+
+        let error = match operation().await {
+            Ok(value) => return Ok(value),
+            Err(error) => error,
+        };
+        if !error.is_retryable() || attempt >= config.max_attempts {
+            return Err(error);
+        }
+
+        let delay = error
+            .server_retry_after()
+            .filter(|_| config.respect_retry_after)
+            .unwrap_or_else(|| backoff.next_backoff().unwrap_or(config.max_delay));
+
+        tracing::warn!(
+            attempt,
+            max_attempts = config.max_attempts,
+            delay_ms = delay.as_millis() as u64,
+            error = %error,
+            "LLM request failed, retrying"
+        );
+        record(|m| {
+            m.retries.fetch_add(1, Ordering::Relaxed);
+            m.wait_ms
+                .fetch_add(delay.as_millis() as u64, Ordering::Relaxed);
+        });
+
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// [`LlmClient`] decorator applying a [`RetryConfig`] to `generate`
+pub struct RetryingClient {
+    inner: Arc<dyn LlmClient>,
+    config: RetryConfig,
+}
+
+impl RetryingClient {
+    pub fn new(inner: Arc<dyn LlmClient>, config: RetryConfig) -> Self {
+        Self { inner, config }
+    }
+}
+
+#[async_trait]
+impl LlmClient for RetryingClient {
+    async fn generate(&self, request: GenerateRequest) -> Result<GenerateResponse, LlmError> {
+        let response = retry(&self.config, || self.inner.generate(request.clone())).await?;
+        super::usage::record_usage(&response);
+        Ok(response)
+    }
+
+    fn provider_name(&self) -> &'static str {
+        self.inner.provider_name()
+    }
+
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+
+    async fn health_check(&self) -> Result<(), LlmError> {
+        self.inner.health_check().await
+    }
+
+    fn estimate_tokens(&self, text: &str) -> u32 {
+        self.inner.estimate_tokens(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+
+    /// Client that replays scripted results
+    struct ScriptedClient {
+        results: Mutex<VecDeque<Result<GenerateResponse, LlmError>>>,
+    }
+
+    impl ScriptedClient {
+        fn new(results: Vec<Result<GenerateResponse, LlmError>>) -> Self {
+            Self {
+                results: Mutex::new(results.into()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl LlmClient for ScriptedClient {
+        async fn generate(&self, _request: GenerateRequest) -> Result<GenerateResponse, LlmError> {
+            self.results.lock().unwrap().pop_front().unwrap()
+        }
+
+        fn provider_name(&self) -> &'static str {
+            "scripted"
+        }
+
+        fn model_name(&self) -> &str {
+            "scripted"
+        }
+
+        async fn health_check(&self) -> Result<(), LlmError> {
+            Ok(())
+        }
+    }
+
+    fn fast_config(max_attempts: u32) -> RetryConfig {
+        RetryConfig::new(max_attempts)
+            .with_initial_delay(Duration::from_millis(1))
+            .with_jitter(false)
+    }
+
+    fn rate_limited(retry_after_secs: Option<u64>) -> Result<GenerateResponse, LlmError> {
+        Err(LlmError::RateLimitExceeded {
+            message: "slow down".to_string(),
+            retry_after_secs,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_retries_until_success_and_records_metrics() {
+        let client = RetryingClient::new(
+            Arc::new(ScriptedClient::new(vec![
+                rate_limited(None),
+                Err(LlmError::ServerError {
+                    message: "boom".to_string(),
+                }),
+                Ok(GenerateResponse {
+                    text: "ok".to_string(),
+                    ..Default::default()
+                }),
+            ])),
+            fast_config(3),
+        );
+
+        let (result, metrics) =
+            track_retry_metrics(client.generate(GenerateRequest::new("hi".to_string()))).await;
+
+        assert_eq!(result.unwrap().text, "ok");
+        assert_eq!(metrics.attempts, 3);
+        assert_eq!(metrics.retries, 2);
+    }
+
+    #[tokio::test]
+    async fn test_non_retryable_and_exhausted_errors_are_returned() {
+        let client = RetryingClient::new(
+            Arc::new(ScriptedClient::new(vec![Err(
+                LlmError::AuthenticationFailed {
+                    message: "bad key".to_string(),
+                },
+            )])),
+            fast_config(3),
+        );
+        let (result, metrics) =
+            track_retry_metrics(client.generate(GenerateRequest::new("hi".to_string()))).await;
+        assert!(matches!(result, Err(LlmError::AuthenticationFailed { .. })));
+        assert_eq!(metrics.retries, 0);
+
+        let client = RetryingClient::new(
+            Arc::new(ScriptedClient::new(vec![
+                rate_limited(None),
+                rate_limited(None),
+            ])),
+            fast_config(2),
+        );
+        let result = client
+            .generate(GenerateRequest::new("hi".to_string()))
+            .await;
+        assert!(matches!(result, Err(LlmError::RateLimitExceeded { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_server_retry_after_is_respected() {
+        let client = RetryingClient::new(
+            Arc::new(ScriptedClient::new(vec![
+                rate_limited(Some(1)),
+                Ok(GenerateResponse::default()),
+            ])),
+            fast_config(2),
+        );
+        let (_, metrics) =
+            track_retry_metrics(client.generate(GenerateRequest::new("hi".to_string()))).await;
+        assert_eq!(metrics.wait_ms, 1000);
+
+        let mut config = fast_config(2);
+        config.respect_retry_after = false;
+        let client = RetryingClient::new(
+            Arc::new(ScriptedClient::new(vec![
+                rate_limited(Some(1)),
+                Ok(GenerateResponse::default()),
+            ])),
+            config,
+        );
+        let (_, metrics) =
+            track_retry_metrics(client.generate(GenerateRequest::new("hi".to_string()))).await;
+        assert_eq!(metrics.wait_ms, 1);
+    }
+
+    #[test]
+    fn test_from_config_applies_provider_overrides() {
+        let config: Config = toml::from_str(
+            r#"
+            [llm.retry]
+            max_attempts = 5
+            jitter = false
+
+            [llm.retry.providers.claude-code]
+            max_attempts = 2
+            "#,
+        )
+        .unwrap();
+
+        let google = RetryConfig::from_config(&config, LlmProvider::GoogleAi);
+        assert_eq!(google.max_attempts, 5);
+        assert!(!google.jitter);
+
+        let claude = RetryConfig::from_config(&config, LlmProvider::ClaudeCode);
+        assert_eq!(claude.max_attempts, 2);
+        assert!(!claude.jitter);
+    }
+}
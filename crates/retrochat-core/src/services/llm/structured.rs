@@ -0,0 +1,294 @@
+//! Structured (JSON) output from LLM responses
+//!
+//! Requests carrying a JSON schema are constrained by the adapters where the
+//! provider supports it, but models still wrap JSON in prose, code fences or
+//! get cut off mid-object. [`parse_structured`] recovers what it can and
+//! counts the outcome into [`ParseMetrics`] for any future run through
+//! [`track_parse_metrics`], which is how analysis requests record them.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::future::Future;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+/// Outcomes of parsing structured responses while a tracked future ran
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParseMetrics {
+    /// Responses that were valid JSON as returned
+    pub parsed: u32,
+    /// Responses that needed repair or a fallback format before they parsed
+    pub repaired: u32,
+    /// Responses with no usable JSON
+    pub failed: u32,
+}
+
+impl ParseMetrics {
+    pub fn total(&self) -> u32 {
+        self.parsed + self.repaired + self.failed
+    }
+}
+
+#[derive(Default)]
+struct MetricsRecorder {
+    parsed: AtomicU32,
+    repaired: AtomicU32,
+    failed: AtomicU32,
+}
+
+tokio::task_local! {
+    static PARSE_METRICS: Arc<MetricsRecorder>;
+}
+
+fn record(counter: impl FnOnce(&MetricsRecorder) -> &AtomicU32) {
+    let _ = PARSE_METRICS.try_with(|recorder| counter(recorder).fetch_add(1, Ordering::Relaxed));
+}
+
+/// Run `future`, counting how every structured response it parses turned out
+///
+/// Like retry metrics, parsing must happen on the same task to be counted.
+pub async fn track_parse_metrics<F: Future>(future: F) -> (F::Output, ParseMetrics) {
+    let recorder = Arc::new(MetricsRecorder::default());
+    let output = PARSE_METRICS.scope(recorder.clone(), future).await;
+    let metrics = ParseMetrics {
+        parsed: recorder.parsed.load(Ordering::Relaxed),
+        repaired: recorder.repaired.load(Ordering::Relaxed),
+        failed: recorder.failed.load(Ordering::Relaxed),
+    };
+    (output, metrics)
+}
+
+/// Parse `text` as `T`, repairing the JSON if needed
+pub fn parse_structured<T: DeserializeOwned>(text: &str) -> Option<T> {
+    parse_structured_or(text, |_| None)
+}
+
+/// Parse `text` as `T`, handing it to `fallback` when no JSON can be recovered
+///
+/// A response only counts as failed when the fallback cannot read it either;
+/// one the fallback reads counts as repaired.
+pub fn parse_structured_or<T: DeserializeOwned>(
+    text: &str,
+    fallback: impl FnOnce(&str) -> Option<T>,
+) -> Option<T> {
+    if let Ok(value) = serde_json::from_str::<T>(text.trim()) {
+        record(|r| &r.parsed);
+        return Some(value);
+    }
+
+    match repair_json(text)
+        .and_then(|value| serde_json::from_value::<T>(value).ok())
+        .or_else(|| fallback(text))
+    {
+        Some(value) => {
+            record(|r| &r.repaired);
+            Some(value)
+        }
+        None => {
+            record(|r| &r.failed);
+            None
+        }
+    }
+}
+
+/// Recover a JSON value from a response that is not valid JSON as a whole
+///
+/// Handles code fences, prose before and after the JSON, trailing commas and
+/// output truncated before its closing brackets.
+pub fn repair_json(text: &str) -> Option<Value> {
+    let text = strip_code_fence(text);
+    let candidate = balanced_json(text)?;
+    let candidate = remove_trailing_commas(&candidate);
+    serde_json::from_str(&candidate).ok()
+}
+
+/// Instruction appended to prompts for providers without native schema support
+pub fn schema_instruction(schema: &Value) -> String {
+    format!(
+        "\n\nRespond with only a JSON value matching this JSON Schema, with no prose and no code fences:\n{schema}"
+    )
+}
+
+/// Contents of the first fenced code block, or `text` if there is none
+fn strip_code_fence(text: &str) -> &str {
+    let Some(start) = text.find("```") else {
+        return text;
+    };
+    let after = &text[start + 3..];
+    // Skip the language tag, if any
+    let body = match after.find('\n') {
+        Some(newline) => &after[newline + 1..],
+        None => after,
+    };
+    match body.find("```") {
+        Some(end) => &body[..end],
+        None => body,
+    }
+}
+
+/// The first JSON object or array in `text`, closed off if it was truncated
+fn balanced_json(text: &str) -> Option<String> {
+    let start = text.find(['{', '['])?;
+    let mut out = String::new();
+    let mut closers = Vec::new();
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for c in text[start..].chars() {
+        out.push(c);
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '{' => closers.push('}'),
+            '[' => closers.push(']'),
+            '}' | ']' => {
+                closers.pop();
+                if closers.is_empty() {
+                    return Some(out);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Truncated: close the open string and brackets
+    if in_string {
+        out.push('"');
+    }
+    let trimmed_len = out.trim_end().trim_end_matches(',').len();
+    out.truncate(trimmed_len);
+    while let Some(closer) = closers.pop() {
+        out.push(closer);
+    }
+    Some(out)
+}
+
+/// Drop commas directly before a closing bracket, outside strings
+fn remove_trailing_commas(json: &str) -> String {
+    let chars: Vec<char> = json.chars().collect();
+    let mut out = String::with_capacity(json.len());
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (i, &c) in chars.iter().enumerate() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+        } else if c == '"' {
+            in_string = true;
+        } else if c == ',' {
+            let next = chars[i + 1..].iter().find(|c| !c.is_whitespace());
+            if matches!(next, Some('}') | Some(']')) {
+                continue;
+            }
+        }
+        out.push(c);
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_repair_json() {
+        let fenced = "Here you go:\n```json\n{\"score\": 4, \"reasoning\": \"ok\"}\n```\nThanks!";
+        assert_eq!(
+            repair_json(fenced),
+            Some(json!({"score": 4, "reasoning": "ok"}))
+        );
+
+        let prose = "Sure. {\"items\": [\"a\", \"b\",], \"note\": \"x}y\"} Hope that helps.";
+        assert_eq!(
+            repair_json(prose),
+            Some(json!({"items": ["a", "b"], "note": "x}y"}))
+        );
+
+        let truncated = "{\"summary\": \"Good\", \"items\": [\"first\", \"sec";
+        assert_eq!(
+            repair_json(truncated),
+            Some(json!({"summary": "Good", "items": ["first", "sec"]}))
+        );
+
+        assert_eq!(repair_json("no json here"), None);
+    }
+
+    #[tokio::test]
+    async fn test_parse_structured_records_metrics() {
+        #[derive(Deserialize)]
+        struct Score {
+            score: u8,
+        }
+
+        let (scores, metrics) = track_parse_metrics(async {
+            [
+                "{\"score\": 3}",
+                "Score: {\"score\": 5}",
+                "I would rate this a four.",
+            ]
+            .iter()
+            .map(|text| parse_structured::<Score>(text).map(|s| s.score))
+            .collect::<Vec<_>>()
+        })
+        .await;
+
+        assert_eq!(scores, vec![Some(3), Some(5), None]);
+        assert_eq!(
+            metrics,
+            ParseMetrics {
+                parsed: 1,
+                repaired: 1,
+                failed: 1,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parse_structured_or_counts_fallback_as_repaired() {
+        #[derive(Deserialize)]
+        struct Score {
+            score: u8,
+        }
+
+        let legacy = |text: &str| {
+            text.strip_prefix("SCORE: ")
+                .and_then(|score| score.trim().parse().ok())
+                .map(|score| Score { score })
+        };
+        let (scores, metrics) = track_parse_metrics(async {
+            ["SCORE: 4", "I would rate this a four."]
+                .iter()
+                .map(|text| parse_structured_or(text, legacy).map(|s| s.score))
+                .collect::<Vec<_>>()
+        })
+        .await;
+
+        assert_eq!(scores, vec![Some(4), None]);
+        assert_eq!(
+            metrics,
+            ParseMetrics {
+                parsed: 0,
+                repaired: 1,
+                failed: 1,
+            }
+        );
+    }
+}
@@ -20,6 +20,9 @@ pub struct SubprocessResult {
 
 /// Run a CLI command with timeout and capture output
 ///
+/// The child is spawned with `kill_on_drop`, so dropping the returned future
+/// (e.g. when an analysis is cancelled) kills the process.
+///
 /// # Arguments
 /// * `command` - The command to execute (e.g., "claude", "gemini")
 /// * `args` - Command line arguments
@@ -73,6 +76,8 @@ pub async fn run_cli_command(
 
 /// Run a CLI command with stdin input, timeout, and capture output
 ///
+/// Like [`run_cli_command`], dropping the returned future kills the process.
+///
 /// This is preferred over passing prompts as command-line arguments to:
 /// - Avoid OS argument length limits (typically 128KB-2MB)
 /// - Handle special characters without escaping issues
@@ -20,6 +20,9 @@ pub trait LlmClient: Send + Sync {
     /// # Returns
     /// * `Ok(GenerateResponse)` - The generated text response with metadata
     /// * `Err(LlmError)` - Provider-specific or transport errors
+    ///
+    /// Dropping the returned future must stop the underlying work (abort the
+    /// HTTP request, kill the CLI subprocess); cancellation relies on it.
     async fn generate(&self, request: GenerateRequest) -> Result<GenerateResponse, LlmError>;
 
     /// Get the provider name for logging and debugging
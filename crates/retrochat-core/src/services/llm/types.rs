@@ -4,6 +4,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::retry::RetryConfig;
+
 /// LLM provider enumeration
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "kebab-case")]
@@ -56,6 +58,13 @@ pub struct GenerateRequest {
 
     /// System instruction or context (optional, not all providers support)
     pub system_prompt: Option<String>,
+
+    /// JSON schema the response must follow (optional)
+    ///
+    /// Providers that support constrained generation enforce it; the others
+    /// are asked for it in the prompt.
+    #[serde(default)]
+    pub response_schema: Option<serde_json::Value>,
 }
 
 impl GenerateRequest {
@@ -65,6 +74,7 @@ impl GenerateRequest {
             max_tokens: None,
             temperature: None,
             system_prompt: None,
+            response_schema: None,
         }
     }
 
@@ -82,6 +92,11 @@ impl GenerateRequest {
         self.system_prompt = Some(system_prompt);
         self
     }
+
+    pub fn with_response_schema(mut self, schema: serde_json::Value) -> Self {
+        self.response_schema = Some(schema);
+        self
+    }
 }
 
 /// Response from text generation
@@ -117,7 +132,9 @@ pub struct LlmConfig {
     pub provider: LlmProvider,
     pub model: Option<String>,
     pub timeout_secs: u64,
-    pub max_retries: usize,
+
+    /// Retry policy; `None` reads it from the config file
+    pub retry: Option<RetryConfig>,
 
     /// API key for remote providers (Google AI)
     pub api_key: Option<String>,
@@ -132,7 +149,7 @@ impl Default for LlmConfig {
             provider: LlmProvider::GoogleAi,
             model: None,
             timeout_secs: 300,
-            max_retries: 3,
+            retry: None,
             api_key: None,
             cli_path: None,
         }
@@ -180,8 +197,8 @@ impl LlmConfig {
         self
     }
 
-    pub fn with_max_retries(mut self, max_retries: usize) -> Self {
-        self.max_retries = max_retries;
+    pub fn with_retry(mut self, retry: RetryConfig) -> Self {
+        self.retry = Some(retry);
         self
     }
 }
@@ -0,0 +1,112 @@
+//! Token and cost accounting for LLM calls
+//!
+//! [`RetryingClient`](super::RetryingClient) reports every successful response
+//! here, so usage is counted once per call and cache hits cost nothing. Usage
+//! is summed for any future run through [`track_usage`], which is how analysis
+//! requests record what they spent.
+
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use super::types::GenerateResponse;
+
+/// Tokens and cost of the LLM calls made while a tracked future ran
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct LlmUsage {
+    /// Successful responses
+    pub requests: u32,
+    /// Tokens reported by the provider (input plus output)
+    pub tokens: u64,
+    /// Cost reported by the provider; only Claude Code reports one
+    pub cost_usd: f64,
+}
+
+impl LlmUsage {
+    fn add(&mut self, response: &GenerateResponse) {
+        self.requests += 1;
+        if let Some(usage) = &response.token_usage {
+            let tokens = usage
+                .total_tokens
+                .or(match (usage.input_tokens, usage.output_tokens) {
+                    (None, None) => None,
+                    (input, output) => Some(input.unwrap_or(0) + output.unwrap_or(0)),
+                });
+            self.tokens += u64::from(tokens.unwrap_or(0));
+        }
+        if let Some(cost) = response
+            .metadata
+            .as_ref()
+            .and_then(|metadata| metadata.get("cost_usd"))
+            .and_then(|cost| cost.as_f64())
+        {
+            self.cost_usd += cost;
+        }
+    }
+}
+
+tokio::task_local! {
+    static LLM_USAGE: Arc<Mutex<LlmUsage>>;
+}
+
+/// Count `response` into the usage of the enclosing [`track_usage`], if any
+pub(crate) fn record_usage(response: &GenerateResponse) {
+    let _ = LLM_USAGE.try_with(|usage| usage.lock().unwrap().add(response));
+}
+
+/// Run `future`, summing the usage of every LLM call it makes
+///
+/// Calls must happen on the same task; work moved to `tokio::spawn` is not
+/// counted.
+pub async fn track_usage<F: Future>(future: F) -> (F::Output, LlmUsage) {
+    let usage = Arc::new(Mutex::new(LlmUsage::default()));
+    let output = LLM_USAGE.scope(usage.clone(), future).await;
+    let usage = *usage.lock().unwrap();
+    (output, usage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::llm::TokenUsage;
+    use serde_json::json;
+
+    fn response(usage: TokenUsage, cost: Option<f64>) -> GenerateResponse {
+        GenerateResponse {
+            text: "ok".to_string(),
+            token_usage: Some(usage),
+            metadata: cost.map(|cost| json!({ "cost_usd": cost })),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_track_usage() {
+        let ((), usage) = track_usage(async {
+            record_usage(&response(
+                TokenUsage {
+                    input_tokens: Some(100),
+                    output_tokens: Some(20),
+                    total_tokens: None,
+                },
+                Some(0.25),
+            ));
+            record_usage(&response(
+                TokenUsage {
+                    total_tokens: Some(50),
+                    ..Default::default()
+                },
+                None,
+            ));
+            record_usage(&GenerateResponse::default());
+        })
+        .await;
+
+        assert_eq!(usage.requests, 3);
+        assert_eq!(usage.tokens, 170);
+        assert!((usage.cost_usd - 0.25).abs() < f64::EPSILON);
+
+        // Outside a tracked future recording is a no-op
+        record_usage(&response(TokenUsage::default(), Some(1.0)));
+    }
+}
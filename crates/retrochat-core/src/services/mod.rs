@@ -1,39 +1,111 @@
+pub mod activity_digest;
 pub mod analytics;
 pub mod analytics_request_service;
 pub mod analytics_service;
 pub mod auto_detect;
+pub mod background_service;
+pub mod doctor;
+pub mod embedding;
+pub mod embedding_index;
+pub mod event_feed;
+pub mod experiments;
 pub mod google_ai;
+pub mod import_ignore;
+pub mod import_limits;
 pub mod import_service;
 pub mod llm;
 pub mod parser_service;
+pub mod project_config;
+pub mod provider_export;
 pub mod query_service;
+pub mod quota;
+pub mod raw_payloads;
+pub mod remote_sync;
+pub mod retention;
+pub mod rework_detection;
+pub mod role_repair;
+pub mod session_edit;
+pub mod session_similarity;
 pub mod session_summarization;
+pub mod session_titling;
+pub mod share;
+pub mod snapshot_export;
+pub mod snapshot_merge;
+pub mod sql_query;
+pub mod standup;
+pub mod team;
+pub mod template_export;
+pub mod token_estimation;
+pub mod topic_clustering;
 pub mod turn_detection;
 pub mod turn_summarization;
 pub mod watch_service;
 
+pub use activity_digest::{
+    ActivityDigestService, ActivityFailure, ActivityFileChange, ActivityProject, ActivitySession,
+    RecentActivityDigest, RecentActivityRequest,
+};
 pub use analytics::{
-    AIQualitativeOutput, FileChangeMetrics, MetricQuantitativeOutput, QualitativeEntry,
-    QualitativeEntryList, QualitativeEvaluationSummary, QualitativeInput, SessionTranscript,
-    SessionTurn, TimeConsumptionMetrics, TokenConsumptionMetrics, ToolUsageMetrics,
+    AIQualitativeOutput, FileChangeMetrics, FileImpact, ImpactReport, LanguageChangeMetrics,
+    MetricQuantitativeOutput, QualitativeEntry, QualitativeEntryList, QualitativeEvaluationSummary,
+    QualitativeInput, RepositoryImpact, SessionTranscript, SessionTurn, TimeConsumptionMetrics,
+    TokenConsumptionMetrics, ToolUsageMetrics, TurnUsageMetrics,
 };
 pub use analytics_request_service::{AnalyticsRequestCleanupHandler, AnalyticsRequestService};
-pub use analytics_service::AnalyticsService;
+pub use analytics_service::{AnalyticsService, RubricRecomputeReport};
 pub use auto_detect::{AutoDetectService, DetectedProvider};
+pub use doctor::{CheckStatus, DoctorCheck, DoctorReport, DoctorService};
+pub use embedding::{
+    configured_embedder, cosine_similarity, create_embedder, ApiEmbedder, Embedder,
+    EmbeddingProvider, HashingEmbedder,
+};
+pub use embedding_index::{
+    EmbeddingIndexService, IndexCoverage, IndexReport, MaintenanceReport, VectorStorage,
+};
+pub use event_feed::EventFeed;
+pub use experiments::{CohortSample, ExperimentOverview, ExperimentReport, ExperimentService};
 pub use google_ai::{
     GenerateContentRequest, GenerateContentResponse, GoogleAiClient, GoogleAiConfig, GoogleAiError,
 };
+pub use import_ignore::ImportIgnore;
+pub use import_limits::RowLimits;
 pub use import_service::{
-    BatchImportRequest, BatchImportResponse, ChatFile, ImportFileRequest, ImportFileResponse,
-    ImportService, ScanRequest, ScanResponse,
+    BatchImportRequest, BatchImportResponse, ChatFile, FilePreview, ImportFileRequest,
+    ImportFileResponse, ImportPreview, ImportService, ResumeReport, ScanRequest, ScanResponse,
+    SessionChange, SessionPreview,
 };
 pub use parser_service::ParserService;
+pub use project_config::{ProjectConfig, ProjectConfigCache, Redactor};
 pub use query_service::{
-    DateRange, MessageGroup, QueryService, SearchRequest, SearchResponse, SearchResult,
-    SessionAnalytics, SessionDetailRequest, SessionDetailResponse, SessionFilters, SessionSummary,
-    SessionsQueryRequest, SessionsQueryResponse,
+    DateRange, FacetCount, FilterOption, FilterOptions, MessageGroup, QueryService, RelatedSession,
+    SearchFacets, SearchRequest, SearchResponse, SearchResult, SessionAnalytics, SessionCursor,
+    SessionDetailRequest, SessionDetailResponse, SessionFilters, SessionSummary,
+    SessionsQueryRequest, SessionsQueryResponse, ToolOperationRecord, ToolOperationsQueryRequest,
+    ToolOperationsQueryResponse,
 };
+pub use quota::{notify_desktop, QuotaService, QuotaStatus, QuotaUsage};
+pub use raw_payloads::{collect_raw_payloads, RawPayloadService, RehydrateReport};
+pub use remote_sync::{open_storage, PullReport, PushReport, RemoteStorage, RemoteSyncService};
+pub use retention::{RetentionReport, RetentionService, SessionSelector};
+pub use rework_detection::{RepeatedPrompt, ReworkDetector};
+pub use role_repair::{RoleChange, RoleRepairReport, RoleRepairService};
+pub use session_edit::{MergeReport, SessionEditService, SplitReport};
+pub use session_similarity::{SessionSimilarityService, SimilarSession};
 pub use session_summarization::SessionSummarizer;
+pub use session_titling::{SessionTitler, TitleBackfillOptions, TitleBackfillReport};
+pub use share::{
+    render_markdown_transcript, render_share_html, upload_gist, GistUpload, ShareOptions,
+};
+pub use snapshot_export::{export_sqlite_snapshot, SnapshotReport};
+pub use sql_query::{SchemaEntry, SqlQueryResult, SqlQueryService};
+pub use standup::{Standup, StandupFacts, StandupService, StandupSession};
+pub use team::{OwnerStats, TeamImportReport, TeamService};
+pub use template_export::{build_template_context, render_template_export};
+pub use token_estimation::{TokenBackfillReport, TokenEstimationService};
+pub use topic_clustering::{TopicReport, TopicService};
 pub use turn_detection::{TurnDetector, TurnMetrics};
 pub use turn_summarization::TurnSummarizer;
-pub use watch_service::{collect_provider_paths, detect_provider, watch_paths_for_changes};
+pub use watch_service::{
+    collect_configured_targets, collect_provider_paths, detect_provider, watch_paths_for_changes,
+    IgnoreRules, WatchTarget,
+};
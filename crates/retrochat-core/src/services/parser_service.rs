@@ -1,7 +1,7 @@
 use anyhow::{Context, Result};
 use std::path::Path;
 
-use crate::models::{ChatSession, Message};
+use crate::models::{ChatSession, Message, Provider};
 use crate::parsers::ParserRegistry;
 
 /// Service for parsing chat files into sessions and messages
@@ -18,12 +18,24 @@ impl ParserService {
     pub async fn parse_file(
         &self,
         file_path: impl AsRef<Path>,
+    ) -> Result<Vec<(ChatSession, Vec<Message>)>> {
+        self.parse_file_with_provider(file_path, None).await
+    }
+
+    /// Parse a file, using `provider` instead of format detection when given
+    pub async fn parse_file_with_provider(
+        &self,
+        file_path: impl AsRef<Path>,
+        provider: Option<Provider>,
     ) -> Result<Vec<(ChatSession, Vec<Message>)>> {
         let path = file_path.as_ref();
 
-        // Detect provider and create appropriate parser
-        let parser = ParserRegistry::create_parser(path)
-            .with_context(|| format!("Failed to create parser for: {}", path.display()))?;
+        // Detect provider (unless hinted) and create appropriate parser
+        let parser = match provider {
+            Some(provider) => ParserRegistry::create_parser_for_provider(path, provider),
+            None => ParserRegistry::create_parser(path),
+        }
+        .with_context(|| format!("Failed to create parser for: {}", path.display()))?;
 
         // Parse the file
         let sessions = parser
@@ -93,8 +105,6 @@ impl Default for ParserService {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::io::Write;
-    use tempfile::NamedTempFile;
 
     #[tokio::test]
     async fn test_parse_claude_code_file() {
@@ -0,0 +1,282 @@
+//! Per-project overrides from a `.retrochat.toml` in the project directory
+//!
+//! A session's project directory comes from Claude Code's encoded log
+//! directory or the project's recorded working directory; the nearest
+//! `.retrochat.toml` in it or a parent (below the home directory) applies:
+//!
+//! ```toml
+//! tags = ["client-x"]
+//!
+//! [analysis]
+//! rubrics = "retro/rubrics.json"                # instead of the built-in rubrics
+//! qualitative_entries = "retro/entries.json"   # instead of the built-in entries
+//! language = "ko"                               # instead of analysis.language
+//!
+//! [redaction]
+//! terms = ["Acme Corp"]
+//! patterns = ['ACME-\d{4}']
+//! ```
+//!
+//! Redaction and tags apply at import time, the analysis settings when a
+//! session is analyzed. Settings left out fall back to the global config.
+
+use anyhow::{Context, Result};
+use regex::{NoExpand, Regex};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use super::analytics::{QualitativeEntryList, RubricList};
+use crate::database::{DatabaseManager, ProjectRepository};
+use crate::models::ChatSession;
+use crate::parsers::project_inference::ProjectInference;
+use crate::utils::locale::Language;
+
+/// File looked for in project directories
+pub const PROJECT_CONFIG_FILE_NAME: &str = ".retrochat.toml";
+
+/// Replacement for redacted text
+const REDACTED: &str = "<redacted>";
+
+/// Contents of a `.retrochat.toml`
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+pub struct ProjectConfig {
+    /// Tags attached to every session of the project
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub analysis: ProjectAnalysisSettings,
+    #[serde(default)]
+    pub redaction: RedactionSettings,
+    /// Directory holding the file; relative paths are resolved against it
+    #[serde(skip)]
+    pub dir: PathBuf,
+}
+
+/// `[analysis]` section of a `.retrochat.toml`
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+pub struct ProjectAnalysisSettings {
+    /// Rubric set (JSON, same format as the built-in one)
+    pub rubrics: Option<String>,
+    /// Qualitative analysis entries (JSON, same format as the built-in one)
+    pub qualitative_entries: Option<String>,
+    /// Analysis output language
+    pub language: Option<String>,
+}
+
+/// `[redaction]` section of a `.retrochat.toml`
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+pub struct RedactionSettings {
+    /// Text replaced case-insensitively
+    #[serde(default)]
+    pub terms: Vec<String>,
+    /// Regular expressions replaced
+    #[serde(default)]
+    pub patterns: Vec<String>,
+}
+
+impl ProjectConfig {
+    /// Read a `.retrochat.toml`
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read project config: {}", path.display()))?;
+        let mut config: ProjectConfig = toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse project config: {}", path.display()))?;
+        config.dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        Ok(config)
+    }
+
+    /// The nearest `.retrochat.toml` in `dir` or its parents, stopping at the
+    /// home directory so a file there does not apply to every project
+    pub fn discover(dir: &Path) -> Result<Option<Self>> {
+        let home = dirs::home_dir();
+        for candidate in dir.ancestors() {
+            if home.as_deref() == Some(candidate) {
+                break;
+            }
+            let path = candidate.join(PROJECT_CONFIG_FILE_NAME);
+            if path.is_file() {
+                return Self::load(&path).map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    /// Directory a session ran in, if it can be told
+    pub async fn project_dir(db: &DatabaseManager, session: &ChatSession) -> Option<PathBuf> {
+        if let Some(dir) = ProjectInference::new(&session.file_path).infer_project_dir() {
+            return Some(dir);
+        }
+        let project = ProjectRepository::new(db)
+            .get_by_name(session.project_name.as_deref()?)
+            .await
+            .ok()??;
+        project.working_directory.filter(|dir| dir.is_dir())
+    }
+
+    /// Project config that applies to a session
+    pub async fn for_session(db: &DatabaseManager, session: &ChatSession) -> Result<Option<Self>> {
+        match Self::project_dir(db, session).await {
+            Some(dir) => Self::discover(&dir),
+            None => Ok(None),
+        }
+    }
+
+    /// Rubrics to score sessions with, if the project has its own
+    pub fn rubrics(&self) -> Result<Option<RubricList>> {
+        self.analysis
+            .rubrics
+            .as_ref()
+            .map(|path| {
+                let path = self.dir.join(path);
+                RubricList::from_json_file(&path)
+                    .with_context(|| format!("Failed to load rubrics: {}", path.display()))
+            })
+            .transpose()
+    }
+
+    /// Qualitative entries to analyze sessions with, if the project has its own
+    pub fn qualitative_entries(&self) -> Result<Option<QualitativeEntryList>> {
+        self.analysis
+            .qualitative_entries
+            .as_ref()
+            .map(|path| {
+                let path = self.dir.join(path);
+                QualitativeEntryList::from_json_file(&path).with_context(|| {
+                    format!("Failed to load qualitative entries: {}", path.display())
+                })
+            })
+            .transpose()
+    }
+
+    /// Analysis language, falling back to the global setting
+    pub fn language(&self, global: Language) -> Language {
+        match self
+            .analysis
+            .language
+            .as_deref()
+            .map(str::parse::<Language>)
+        {
+            Some(Ok(language)) => language,
+            Some(Err(e)) => {
+                tracing::warn!(
+                    error = %e,
+                    dir = %self.dir.display(),
+                    "Ignoring project analysis language"
+                );
+                global
+            }
+            None => global,
+        }
+    }
+
+    /// Compiled redaction rules; `None` when the project has none
+    pub fn redactor(&self) -> Result<Option<Redactor>> {
+        let mut rules = Vec::new();
+        for term in &self.redaction.terms {
+            let term = term.trim();
+            if !term.is_empty() {
+                rules.push(Regex::new(&format!("(?i){}", regex::escape(term)))?);
+            }
+        }
+        for pattern in &self.redaction.patterns {
+            rules.push(
+                Regex::new(pattern)
+                    .with_context(|| format!("Invalid redaction pattern: {pattern}"))?,
+            );
+        }
+        Ok((!rules.is_empty()).then_some(Redactor { rules }))
+    }
+}
+
+/// Replaces a project's redacted terms and patterns with `<redacted>`
+#[derive(Debug, Clone)]
+pub struct Redactor {
+    rules: Vec<Regex>,
+}
+
+impl Redactor {
+    pub fn redact(&self, text: &str) -> String {
+        let mut text = text.to_string();
+        for rule in &self.rules {
+            text = rule.replace_all(&text, NoExpand(REDACTED)).into_owned();
+        }
+        text
+    }
+}
+
+/// Project configs looked up during one import, keyed by session log directory
+#[derive(Default)]
+pub struct ProjectConfigCache {
+    by_dir: HashMap<(PathBuf, Option<String>), Option<ProjectConfig>>,
+}
+
+impl ProjectConfigCache {
+    /// Project config for a session, logging (not failing) on a broken file
+    pub async fn get(
+        &mut self,
+        db: &DatabaseManager,
+        session: &ChatSession,
+    ) -> Option<&ProjectConfig> {
+        let key = (
+            Path::new(&session.file_path)
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_default(),
+            session.project_name.clone(),
+        );
+        if !self.by_dir.contains_key(&key) {
+            let config = ProjectConfig::for_session(db, session)
+                .await
+                .unwrap_or_else(|e| {
+                    tracing::warn!(error = %e, "Ignoring project config");
+                    None
+                });
+            self.by_dir.insert(key.clone(), config);
+        }
+        self.by_dir.get(&key).and_then(Option::as_ref)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discover_and_redact() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let project = temp_dir.path().join("acme");
+        let nested = project.join("src");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(
+            project.join(PROJECT_CONFIG_FILE_NAME),
+            r#"
+tags = ["client-x"]
+
+[analysis]
+rubrics = "rubrics.json"
+language = "ko"
+
+[redaction]
+terms = ["Acme Corp"]
+patterns = ['ACME-\d{4}']
+"#,
+        )
+        .unwrap();
+
+        let config = ProjectConfig::discover(&nested).unwrap().unwrap();
+        assert_eq!(config.dir, project);
+        assert_eq!(config.tags, vec!["client-x"]);
+        assert_eq!(config.language(Language::English), Language::Korean);
+        assert!(config.rubrics().is_err(), "rubrics.json does not exist");
+
+        let redactor = config.redactor().unwrap().unwrap();
+        assert_eq!(
+            redactor.redact("Ticket ACME-1234 from acme corp"),
+            "Ticket <redacted> from <redacted>"
+        );
+
+        assert!(ProjectConfig::discover(temp_dir.path()).unwrap().is_none());
+        assert!(ProjectConfig::default().redactor().unwrap().is_none());
+    }
+}
@@ -0,0 +1,220 @@
+//! Reconstruct a session in its provider's native file format
+//!
+//! This is best effort: RetroChat keeps message text, tool inputs and tool
+//! result summaries, but not every field the provider originally wrote.
+//! Thinking blocks are dropped because they cannot be replayed without the
+//! provider's signature.
+
+use anyhow::Result;
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+use crate::models::message::MessageType;
+use crate::models::{ChatSession, Message, MessageRole, ToolOperation};
+
+/// Value written to the `version` field of exported entries
+const EXPORT_VERSION: &str = "retrochat-export";
+
+/// Build a Claude Code conversation JSONL for a stored session
+///
+/// `cwd` is recorded on every entry; Claude Code uses it to find the project
+/// a session belongs to when resuming.
+pub fn export_claude_jsonl(
+    session: &ChatSession,
+    messages: &[Message],
+    tool_operations: &[ToolOperation],
+    cwd: &str,
+) -> Result<String> {
+    let operations: HashMap<Uuid, &ToolOperation> =
+        tool_operations.iter().map(|op| (op.id, op)).collect();
+    let session_id = session.id.to_string();
+
+    let mut emitted_tool_uses = HashSet::new();
+    let mut parent_uuid: Option<String> = None;
+    let mut lines = Vec::new();
+
+    for message in messages {
+        let operation = message
+            .tool_operation_id
+            .and_then(|id| operations.get(&id).copied());
+
+        let (role, content, tool_use_result) = match (&message.message_type, operation) {
+            (MessageType::Thinking, _) => continue,
+            (_, _) if message.role == MessageRole::System => continue,
+            (MessageType::ToolRequest, Some(op)) => {
+                emitted_tool_uses.insert(op.tool_use_id.clone());
+                ("assistant", tool_use_content(message, op), None)
+            }
+            (MessageType::ToolResult, Some(op)) if emitted_tool_uses.contains(&op.tool_use_id) => {
+                ("user", tool_result_content(op), op.raw_result.clone())
+            }
+            _ => match message.role {
+                MessageRole::User => ("user", Value::String(message.content.clone()), None),
+                _ => (
+                    "assistant",
+                    json!([{ "type": "text", "text": message.content }]),
+                    None,
+                ),
+            },
+        };
+
+        let uuid = message.id.to_string();
+        let mut entry = json!({
+            "parentUuid": parent_uuid,
+            "isSidechain": false,
+            "userType": "external",
+            "cwd": cwd,
+            "sessionId": session_id,
+            "version": EXPORT_VERSION,
+            "type": role,
+            "message": { "role": role, "content": content },
+            "uuid": uuid,
+            "timestamp": message.timestamp.to_rfc3339(),
+        });
+        if let Some(result) = tool_use_result {
+            entry["toolUseResult"] = result;
+        }
+
+        lines.push(serde_json::to_string(&entry)?);
+        parent_uuid = Some(uuid);
+    }
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Directory name Claude Code uses for a project under `~/.claude/projects`
+pub fn claude_project_dir_name(cwd: &str) -> String {
+    cwd.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+/// Assistant content for a tool request: any accompanying text plus the tool_use block
+fn tool_use_content(message: &Message, op: &ToolOperation) -> Value {
+    let placeholder = format!("[Tool Use: {}]", op.tool_name);
+    let text = message.content.replace(&placeholder, "");
+    let text = text.trim();
+
+    let mut blocks = Vec::new();
+    if !text.is_empty() {
+        blocks.push(json!({ "type": "text", "text": text }));
+    }
+    blocks.push(json!({
+        "type": "tool_use",
+        "id": op.tool_use_id,
+        "name": op.tool_name,
+        "input": op.raw_input.clone().unwrap_or_else(|| json!({})),
+    }));
+    Value::Array(blocks)
+}
+
+fn tool_result_content(op: &ToolOperation) -> Value {
+    json!([{
+        "type": "tool_result",
+        "tool_use_id": op.tool_use_id,
+        "content": op.result_summary.clone().unwrap_or_default(),
+        "is_error": op.success == Some(false),
+    }])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Provider;
+    use crate::parsers::claude_code::ClaudeCodeParser;
+    use chrono::Utc;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_claude_project_dir_name() {
+        assert_eq!(
+            claude_project_dir_name("/Users/me/my.project"),
+            "-Users-me-my-project"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_export_claude_jsonl_round_trips_through_parser() {
+        let session = ChatSession::new(
+            Provider::ClaudeCode,
+            "/tmp/original.jsonl".to_string(),
+            "hash".to_string(),
+            Utc::now(),
+        );
+        let now = Utc::now();
+
+        let operation = ToolOperation::new("toolu_01".to_string(), "Bash".to_string(), now)
+            .with_raw_input(json!({ "command": "ls" }))
+            .with_success(true)
+            .with_result_summary("Cargo.toml\nsrc".to_string());
+
+        let mut tool_use = Message::new(
+            session.id,
+            MessageRole::Assistant,
+            "Let me look. [Tool Use: Bash]".to_string(),
+            now,
+            3,
+        )
+        .with_message_type(MessageType::ToolRequest);
+        tool_use.tool_operation_id = Some(operation.id);
+
+        let mut tool_result = Message::new(
+            session.id,
+            MessageRole::User,
+            "[Tool Result]".to_string(),
+            now,
+            4,
+        )
+        .with_message_type(MessageType::ToolResult);
+        tool_result.tool_operation_id = Some(operation.id);
+
+        let messages = vec![
+            Message::new(
+                session.id,
+                MessageRole::User,
+                "List files".to_string(),
+                now,
+                1,
+            ),
+            Message::new(
+                session.id,
+                MessageRole::Assistant,
+                "hmm".to_string(),
+                now,
+                2,
+            )
+            .with_message_type(MessageType::Thinking),
+            tool_use,
+            tool_result,
+            Message::new(
+                session.id,
+                MessageRole::Assistant,
+                "Done".to_string(),
+                now,
+                5,
+            ),
+        ];
+
+        let jsonl = export_claude_jsonl(&session, &messages, &[operation], "/work/app").unwrap();
+        assert_eq!(jsonl.lines().count(), 4);
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(format!("{}.jsonl", session.id));
+        std::fs::write(&path, jsonl).unwrap();
+
+        let (parsed_session, parsed_messages) = ClaudeCodeParser::new(&path).parse().await.unwrap();
+        assert_eq!(parsed_session.id, session.id);
+        assert_eq!(parsed_messages.len(), 4);
+        assert_eq!(parsed_messages[0].content, "List files");
+
+        let tool_uses = parsed_messages[1].tool_uses.as_ref().unwrap();
+        assert_eq!(tool_uses[0].id, "toolu_01");
+        assert_eq!(tool_uses[0].input, json!({ "command": "ls" }));
+
+        let tool_results = parsed_messages[2].tool_results.as_ref().unwrap();
+        assert_eq!(tool_results[0].content, "Cargo.toml\nsrc");
+        assert!(!tool_results[0].is_error);
+        assert_eq!(parsed_messages[3].content, "Done");
+    }
+}
@@ -1,11 +1,18 @@
 use crate::database::{
     AnalyticsRepository, AnalyticsRequestRepository, ChatSessionRepository, DatabaseManager,
+    EmbeddingKind, EmbeddingRepository, SessionSummaryRepository, ToolOperationFilter,
+    ToolOperationRepository, TopicRepository,
 };
-use crate::models::{Analytics, AnalyticsRequest, ChatSession, Message, OperationStatus};
-use anyhow::Result;
+use crate::models::{
+    Analytics, AnalyticsRequest, Attachment, ChatSession, Message, OperationStatus, ToolOperation,
+};
+use crate::services::session_similarity::rank_by_similarity;
+use crate::utils::deep_link::DeepLink;
+use crate::utils::filter_expr::{FilterClause, FilterContext, FilterExpr, FilterTerm};
+use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -112,6 +119,67 @@ pub struct SessionsQueryRequest {
     pub sort_by: Option<String>,
     pub sort_order: Option<String>,
     pub filters: Option<SessionFilters>,
+    /// Return the sessions following this position instead of a numbered page
+    ///
+    /// Keyset pagination stays fast however deep the listing goes, but it only
+    /// works when sorting by `start_time`, and the response carries no totals.
+    #[serde(default)]
+    pub after: Option<SessionCursor>,
+}
+
+/// Position in a session listing sorted by start time, for keyset pagination
+///
+/// Serialized as an opaque `<start_time>/<id>` string.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct SessionCursor {
+    pub start_time: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+impl SessionCursor {
+    pub fn for_session(session: &ChatSession) -> Self {
+        Self {
+            start_time: session.start_time,
+            id: session.id,
+        }
+    }
+}
+
+impl std::fmt::Display for SessionCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.start_time.to_rfc3339(), self.id)
+    }
+}
+
+impl std::str::FromStr for SessionCursor {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start_time, id) = s
+            .split_once('/')
+            .ok_or_else(|| anyhow::anyhow!("Invalid session cursor: {s}"))?;
+        Ok(Self {
+            start_time: DateTime::parse_from_rfc3339(start_time)
+                .with_context(|| format!("Invalid session cursor: {s}"))?
+                .with_timezone(&Utc),
+            id: Uuid::parse_str(id).with_context(|| format!("Invalid session cursor: {s}"))?,
+        })
+    }
+}
+
+impl TryFrom<String> for SessionCursor {
+    type Error = anyhow::Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<SessionCursor> for String {
+    fn from(cursor: SessionCursor) -> Self {
+        cursor.to_string()
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -121,6 +189,119 @@ pub struct SessionFilters {
     pub date_range: Option<DateRange>,
     pub min_messages: Option<i32>,
     pub max_messages: Option<i32>,
+    /// Include archived sessions (hidden by default)
+    #[serde(default)]
+    pub include_archived: bool,
+    /// Filter expression, e.g. `provider:claude tokens>50000 tool:Bash(failed)`
+    /// (see [`crate::utils::filter_expr`])
+    #[serde(default)]
+    pub expression: Option<String>,
+    /// Topic ID or label (see [`crate::services::TopicService`])
+    #[serde(default)]
+    pub topic: Option<String>,
+    /// `me`, `team` or a teammate's name (see [`ChatSession::matches_owner`])
+    #[serde(default)]
+    pub owner: Option<String>,
+    /// Sessions from any of these providers
+    #[serde(default)]
+    pub providers: Option<Vec<String>>,
+    /// Sessions in any of these projects
+    #[serde(default)]
+    pub projects: Option<Vec<String>>,
+    /// Sessions clustered into any of these topics (IDs or labels)
+    #[serde(default)]
+    pub topics: Option<Vec<String>>,
+}
+
+impl SessionFilters {
+    /// Whether a session passes the provider, project, owner, message count
+    /// and date range filters (the expression is applied separately)
+    fn matches(&self, session: &ChatSession) -> bool {
+        if let Some(ref provider_filter) = self.provider {
+            if session.provider.to_string() != *provider_filter {
+                return false;
+            }
+        }
+
+        if let Some(ref project_filter) = self.project {
+            if session.project_name.as_deref() != Some(project_filter) {
+                return false;
+            }
+        }
+
+        if let Some(ref providers) = self.providers {
+            let provider = session.provider.to_string();
+            if !providers.contains(&provider) {
+                return false;
+            }
+        }
+
+        if let Some(ref projects) = self.projects {
+            let in_projects = session
+                .project_name
+                .as_ref()
+                .is_some_and(|project| projects.contains(project));
+            if !in_projects {
+                return false;
+            }
+        }
+
+        if let Some(ref owner_filter) = self.owner {
+            if !session.matches_owner(owner_filter) {
+                return false;
+            }
+        }
+
+        if let Some(min_messages) = self.min_messages {
+            if (session.message_count as i32) < min_messages {
+                return false;
+            }
+        }
+
+        if let Some(max_messages) = self.max_messages {
+            if (session.message_count as i32) > max_messages {
+                return false;
+            }
+        }
+
+        if let Some(ref date_range) = self.date_range {
+            let session_start = session.start_time;
+            let start_date = date_range.start_date.parse::<DateTime<Utc>>().ok();
+            let end_date = date_range.end_date.parse::<DateTime<Utc>>().ok();
+
+            if let Some(start) = start_date {
+                if session_start < start {
+                    return false;
+                }
+            }
+
+            if let Some(end) = end_date {
+                if session_start > end {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+}
+
+/// A value offered by a filter picker, with the sessions that have it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FilterOption {
+    /// What goes into [`SessionFilters`]
+    pub value: String,
+    /// What the user sees
+    pub label: String,
+    pub session_count: i64,
+}
+
+/// Providers, projects and topics the session list can be filtered by
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FilterOptions {
+    pub providers: Vec<FilterOption>,
+    pub projects: Vec<FilterOption>,
+    pub topics: Vec<FilterOption>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -136,6 +317,9 @@ pub struct SessionsQueryResponse {
     pub page: i32,
     pub page_size: i32,
     pub total_pages: i32,
+    /// Cursor for the following page when sorted by start time (`None` on the last page)
+    #[serde(default)]
+    pub next_cursor: Option<SessionCursor>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -143,13 +327,19 @@ pub struct SessionSummary {
     pub session_id: String,
     pub provider: String,
     pub project: Option<String>,
+    pub title: Option<String>,
     pub start_time: String,
     pub end_time: String,
     pub message_count: i32,
     pub total_tokens: Option<i32>,
+    pub tool_op_count: i32,
+    pub duration_seconds: Option<i32>,
     pub first_message_preview: String,
     pub has_analytics: bool,
     pub analytics_status: Option<OperationStatus>,
+    /// Teammate the session was imported from (`None` for own sessions)
+    #[serde(default)]
+    pub owner: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -166,6 +356,21 @@ pub struct SessionDetailResponse {
     pub messages: Vec<Message>,
     pub total_message_count: i32,
     pub has_more_messages: bool,
+    /// Attachments of the returned messages
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
+}
+
+/// A session whose summary resembles another session's
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelatedSession {
+    pub session_id: String,
+    /// Cosine similarity of the two session summaries
+    pub score: f32,
+    pub provider: String,
+    pub project: Option<String>,
+    pub title: Option<String>,
+    pub start_time: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -186,6 +391,70 @@ pub struct SearchResponse {
     pub page: i32,
     pub page_size: i32,
     pub search_duration_ms: i32,
+    /// Hit counts before the provider and project selections are applied
+    #[serde(default)]
+    pub facets: SearchFacets,
+}
+
+/// Number of search hits with one facet value
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FacetCount {
+    pub value: String,
+    pub count: usize,
+}
+
+/// Search hits counted per provider, project and month, for narrowing a search
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SearchFacets {
+    /// Most hits first
+    pub providers: Vec<FacetCount>,
+    /// Most hits first
+    pub projects: Vec<FacetCount>,
+    /// `YYYY-MM` buckets, newest first
+    pub months: Vec<FacetCount>,
+}
+
+impl SearchFacets {
+    /// Count hits given as (provider, project, RFC 3339 timestamp)
+    pub fn count<'a>(hits: impl IntoIterator<Item = (&'a str, Option<&'a str>, &'a str)>) -> Self {
+        let mut providers: BTreeMap<&str, usize> = BTreeMap::new();
+        let mut projects: BTreeMap<&str, usize> = BTreeMap::new();
+        let mut months: BTreeMap<&str, usize> = BTreeMap::new();
+        for (provider, project, timestamp) in hits {
+            *providers.entry(provider).or_default() += 1;
+            if let Some(project) = project {
+                *projects.entry(project).or_default() += 1;
+            }
+            if let Some(month) = timestamp.get(..7) {
+                *months.entry(month).or_default() += 1;
+            }
+        }
+
+        let by_count = |counts: BTreeMap<&str, usize>| {
+            let mut facets: Vec<FacetCount> = counts
+                .into_iter()
+                .map(|(value, count)| FacetCount {
+                    value: value.to_string(),
+                    count,
+                })
+                .collect();
+            facets.sort_by_key(|facet| std::cmp::Reverse(facet.count));
+            facets
+        };
+
+        Self {
+            providers: by_count(providers),
+            projects: by_count(projects),
+            months: months
+                .into_iter()
+                .rev()
+                .map(|(value, count)| FacetCount {
+                    value: value.to_string(),
+                    count,
+                })
+                .collect(),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -198,6 +467,78 @@ pub struct SearchResult {
     pub content_snippet: String,
     pub message_role: String,
     pub relevance_score: f64,
+    /// Sequence number of the message within its session
+    #[serde(default)]
+    pub sequence_number: u32,
+    /// `retrochat://` link that opens the session at this message
+    /// (see [`crate::utils::deep_link`])
+    #[serde(default)]
+    pub link: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ToolOperationsQueryRequest {
+    pub tool_name: Option<String>,
+    pub success: Option<bool>,
+    pub file_path: Option<String>,
+    pub session_id: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub page: Option<i32>,
+    pub page_size: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ToolOperationsQueryResponse {
+    pub operations: Vec<ToolOperationRecord>,
+    pub total_count: i32,
+    pub page: i32,
+    pub page_size: i32,
+    pub total_pages: i32,
+}
+
+/// Compact view of a tool operation (raw input/result omitted)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolOperationRecord {
+    pub id: String,
+    pub session_id: String,
+    pub tool_name: String,
+    /// MCP server the tool belongs to; None for built-in tools
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub mcp_server: Option<String>,
+    pub timestamp: String,
+    pub success: Option<bool>,
+    pub file_path: Option<String>,
+    pub lines_added: Option<i32>,
+    pub lines_removed: Option<i32>,
+    pub command: Option<String>,
+    pub exit_code: Option<i32>,
+    pub result_summary: Option<String>,
+    /// Milliseconds from the call to its result, when the log times both
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub duration_ms: Option<i64>,
+}
+
+impl ToolOperationRecord {
+    fn from_operation(session_id: Uuid, operation: ToolOperation) -> Self {
+        let file = operation.file_metadata.as_ref();
+        let bash = operation.bash_metadata.as_ref();
+        Self {
+            id: operation.id.to_string(),
+            session_id: session_id.to_string(),
+            tool_name: operation.tool_name.clone(),
+            mcp_server: operation.tool_source.mcp_server().map(str::to_string),
+            timestamp: operation.timestamp.to_rfc3339(),
+            success: operation.success,
+            file_path: file.map(|f| f.file_path.clone()),
+            lines_added: file.and_then(|f| f.lines_added),
+            lines_removed: file.and_then(|f| f.lines_removed),
+            command: bash.map(|b| b.command.clone()),
+            exit_code: bash.and_then(|b| b.exit_code),
+            result_summary: operation.result_summary.clone(),
+            duration_ms: operation.duration_ms,
+        }
+    }
 }
 
 pub struct QueryService {
@@ -216,6 +557,98 @@ impl QueryService {
         Self { db_manager }
     }
 
+    /// Sessions matching a filter expression, newest first
+    ///
+    /// Archived sessions are only included when the expression asks for them.
+    pub async fn filter_sessions(&self, expression: &FilterExpr) -> Result<Vec<ChatSession>> {
+        let filter_context = self.resolve_filter_context(expression).await?;
+        let include_archived = expression.includes_archived();
+
+        let mut sessions: Vec<ChatSession> = ChatSessionRepository::new(&self.db_manager)
+            .get_all()
+            .await?
+            .into_iter()
+            .filter(|session| !session.is_importing())
+            .filter(|session| include_archived || !session.is_archived())
+            .filter(|session| expression.matches(session, &filter_context))
+            .collect();
+        sessions.sort_by_key(|session| std::cmp::Reverse(session.start_time));
+        Ok(sessions)
+    }
+
+    /// Providers and projects of unarchived sessions, and topics, each with
+    /// its session count
+    pub async fn get_filter_options(&self) -> Result<FilterOptions> {
+        let mut providers: BTreeMap<String, i64> = BTreeMap::new();
+        let mut projects: BTreeMap<String, i64> = BTreeMap::new();
+        for session in ChatSessionRepository::new(&self.db_manager)
+            .get_all()
+            .await?
+        {
+            if session.is_importing() || session.is_archived() {
+                continue;
+            }
+            *providers.entry(session.provider.to_string()).or_default() += 1;
+            if let Some(project) = session.project_name {
+                *projects.entry(project).or_default() += 1;
+            }
+        }
+        let counted = |values: BTreeMap<String, i64>| {
+            values
+                .into_iter()
+                .map(|(value, session_count)| FilterOption {
+                    label: value.clone(),
+                    value,
+                    session_count,
+                })
+                .collect()
+        };
+
+        let topics = TopicRepository::new(&self.db_manager)
+            .list()
+            .await?
+            .into_iter()
+            .map(|topic| FilterOption {
+                value: topic.id.to_string(),
+                label: topic.label,
+                session_count: topic.session_count,
+            })
+            .collect();
+
+        Ok(FilterOptions {
+            providers: counted(providers),
+            projects: counted(projects),
+            topics,
+        })
+    }
+
+    /// Load the tool usage and outcomes an expression refers to
+    async fn resolve_filter_context(&self, expression: &FilterExpr) -> Result<FilterContext> {
+        let tool_repo = ToolOperationRepository::new(&self.db_manager);
+        let mut context = FilterContext::default();
+        for tool in expression.tool_filters() {
+            let session_ids = tool_repo
+                .get_session_ids_using_tool(&tool.name, tool.success)
+                .await?;
+            context
+                .tools
+                .insert(tool.clone(), session_ids.into_iter().collect());
+        }
+        if expression.needs_outcomes() {
+            context.outcomes = SessionSummaryRepository::new(&self.db_manager)
+                .get_outcomes()
+                .await?
+                .into_iter()
+                .collect();
+        }
+        if expression.needs_topics() {
+            context.topics = TopicRepository::new(&self.db_manager)
+                .session_topics()
+                .await?;
+        }
+        Ok(context)
+    }
+
     pub async fn query_sessions(
         &self,
         request: SessionsQueryRequest,
@@ -227,75 +660,106 @@ impl QueryService {
 
         let session_repo = ChatSessionRepository::new(&self.db_manager);
 
-        // Get all sessions first (we'll implement pagination later)
-        let all_sessions = session_repo.get_all().await?;
-
-        // Apply filters if specified
-        let filtered_sessions: Vec<ChatSession> = if let Some(filters) = &request.filters {
-            all_sessions
-                .into_iter()
-                .filter(|session| {
-                    // Filter by provider
-                    if let Some(ref provider_filter) = filters.provider {
-                        if session.provider.to_string() != *provider_filter {
-                            return false;
-                        }
-                    }
-
-                    // Filter by project
-                    if let Some(ref project_filter) = filters.project {
-                        if session.project_name.as_deref() != Some(project_filter) {
-                            return false;
-                        }
-                    }
+        let mut expression = request
+            .filters
+            .as_ref()
+            .and_then(|f| f.expression.as_deref())
+            .map(FilterExpr::parse)
+            .transpose()?
+            .unwrap_or_default();
+        if let Some(topic) = request.filters.as_ref().and_then(|f| f.topic.clone()) {
+            expression.clauses.push(FilterClause {
+                negated: false,
+                term: FilterTerm::Topic(vec![topic]),
+            });
+        }
+        if let Some(topics) = request.filters.as_ref().and_then(|f| f.topics.clone()) {
+            expression.clauses.push(FilterClause {
+                negated: false,
+                term: FilterTerm::Topic(topics),
+            });
+        }
+        let filter_context = self.resolve_filter_context(&expression).await?;
+
+        let include_archived = request.filters.as_ref().is_some_and(|f| f.include_archived)
+            || expression.includes_archived();
+        let matches = |session: &ChatSession| {
+            !session.is_importing()
+                && (include_archived || !session.is_archived())
+                && expression.matches(session, &filter_context)
+                && request
+                    .filters
+                    .as_ref()
+                    .is_none_or(|filters| filters.matches(session))
+        };
 
-                    // Filter by message count
-                    if let Some(min_messages) = filters.min_messages {
-                        if (session.message_count as i32) < min_messages {
-                            return false;
-                        }
-                    }
+        if let Some(after) = request.after {
+            if sort_by != "start_time" {
+                anyhow::bail!("Cursor pagination requires sorting by start_time");
+            }
 
-                    if let Some(max_messages) = filters.max_messages {
-                        if (session.message_count as i32) > max_messages {
-                            return false;
+            // Walk the index in batches until the page is full, since filters
+            // are applied after loading
+            let descending = sort_order == "desc";
+            let batch_size = i64::from(page_size).max(100);
+            let mut position = after;
+            let mut page_sessions = Vec::new();
+            let mut exhausted = false;
+
+            while !exhausted && page_sessions.len() < page_size as usize {
+                let batch = session_repo
+                    .get_page_by_start_time(
+                        Some((position.start_time, position.id)),
+                        descending,
+                        batch_size,
+                    )
+                    .await?;
+                exhausted = (batch.len() as i64) < batch_size;
+
+                for session in batch {
+                    position = SessionCursor::for_session(&session);
+                    if matches(&session) {
+                        page_sessions.push(session);
+                        if page_sessions.len() == page_size as usize {
+                            break;
                         }
                     }
+                }
+            }
 
-                    // Implement date range filtering
-                    if let Some(ref date_range) = filters.date_range {
-                        let session_start = session.start_time;
-                        let start_date = date_range.start_date.parse::<DateTime<Utc>>().ok();
-                        let end_date = date_range.end_date.parse::<DateTime<Utc>>().ok();
-
-                        if let Some(start) = start_date {
-                            if session_start < start {
-                                return false;
-                            }
-                        }
+            let next_cursor = (!exhausted || page_sessions.len() == page_size as usize)
+                .then(|| page_sessions.last().map(SessionCursor::for_session))
+                .flatten();
 
-                        if let Some(end) = end_date {
-                            if session_start > end {
-                                return false;
-                            }
-                        }
-                    }
+            return Ok(SessionsQueryResponse {
+                sessions: self.summarize_sessions(page_sessions).await,
+                total_count: 0,
+                page: 0,
+                page_size,
+                total_pages: 0,
+                next_cursor,
+            });
+        }
 
-                    true
-                })
-                .collect()
-        } else {
-            all_sessions
-        };
+        // Get all sessions first (we'll implement pagination later)
+        let mut sorted_sessions: Vec<ChatSession> = session_repo
+            .get_all()
+            .await?
+            .into_iter()
+            .filter(|session| matches(session))
+            .collect();
 
         // Sort sessions
-        let mut sorted_sessions = filtered_sessions;
         sorted_sessions.sort_by(|a, b| {
             let ordering = match sort_by.as_str() {
                 "message_count" => a.message_count.cmp(&b.message_count),
                 "provider" => a.provider.to_string().cmp(&b.provider.to_string()),
                 "project" => a.project_name.cmp(&b.project_name),
-                _ => a.start_time.cmp(&b.start_time), // default to start_time
+                // default to start_time, in the same order cursors use
+                _ => a
+                    .start_time
+                    .cmp(&b.start_time)
+                    .then_with(|| a.id.cmp(&b.id)),
             };
 
             if sort_order == "desc" {
@@ -317,27 +781,41 @@ impl QueryService {
             .take(limit)
             .collect();
 
-        // Convert to SessionSummary format with actual first message preview
+        let next_cursor = (sort_by == "start_time" && page < total_pages)
+            .then(|| paginated_sessions.last().map(SessionCursor::for_session))
+            .flatten();
+
+        Ok(SessionsQueryResponse {
+            sessions: self.summarize_sessions(paginated_sessions).await,
+            total_count,
+            page,
+            page_size,
+            total_pages,
+            next_cursor,
+        })
+    }
+
+    /// Convert sessions to list summaries with a first message preview
+    async fn summarize_sessions(&self, sessions: Vec<ChatSession>) -> Vec<SessionSummary> {
         let message_repo = crate::database::MessageRepository::new(&self.db_manager);
         let analytics_request_repo = AnalyticsRequestRepository::new(self.db_manager.clone());
-        let mut sessions = Vec::new();
+        let mut summaries = Vec::new();
 
-        for session in paginated_sessions {
+        for session in sessions {
             // Get first message preview
             let first_message_preview = message_repo
-                .get_by_session(&session.id)
+                .get_first_by_session(&session.id)
                 .await
                 .ok()
-                .and_then(|messages| {
-                    messages.first().map(|msg| {
-                        let preview = if msg.content.chars().count() > 100 {
-                            let truncated: String = msg.content.chars().take(97).collect();
-                            format!("{truncated}...")
-                        } else {
-                            msg.content.clone()
-                        };
-                        preview
-                    })
+                .flatten()
+                .map(|msg| {
+                    let preview = if msg.content.chars().count() > 100 {
+                        let truncated: String = msg.content.chars().take(97).collect();
+                        format!("{truncated}...")
+                    } else {
+                        msg.content.clone()
+                    };
+                    preview
                 })
                 .unwrap_or_else(|| "No messages available".to_string());
 
@@ -357,10 +835,11 @@ impl QueryService {
                 })
                 .unwrap_or((false, None));
 
-            sessions.push(SessionSummary {
+            summaries.push(SessionSummary {
                 session_id: session.id.to_string(),
                 provider: session.provider.to_string(),
                 project: session.project_name,
+                title: session.title,
                 start_time: session.start_time.to_rfc3339(),
                 end_time: session
                     .end_time
@@ -368,19 +847,16 @@ impl QueryService {
                     .unwrap_or_else(|| session.start_time.to_rfc3339()),
                 message_count: session.message_count as i32,
                 total_tokens: session.token_count.map(|t| t as i32),
+                tool_op_count: session.tool_op_count as i32,
+                duration_seconds: session.duration_seconds.map(|d| d as i32),
                 first_message_preview,
                 has_analytics,
                 analytics_status,
+                owner: session.owner,
             });
         }
 
-        Ok(SessionsQueryResponse {
-            sessions,
-            total_count,
-            page,
-            page_size,
-            total_pages,
-        })
+        summaries
     }
 
     pub async fn get_session_detail(
@@ -400,16 +876,94 @@ impl QueryService {
 
         // Get messages for this session
         let message_repo = crate::database::MessageRepository::new(&self.db_manager);
-        let messages = message_repo.get_by_session(&session_id).await?;
+        let offset = request.message_offset.unwrap_or(0).max(0);
+        // Only a requested page is read from the database, so huge sessions
+        // can be loaded piece by piece
+        let (messages, total_message_count) = match request.message_limit {
+            Some(limit) => {
+                let messages = message_repo
+                    .get_page_by_session(&session_id, offset as i64, limit.max(0) as i64)
+                    .await?;
+                let total = message_repo.count_by_session(&session_id).await? as usize;
+                (messages, total)
+            }
+            None => {
+                let messages = message_repo.get_by_session(&session_id).await?;
+                let total = messages.len();
+                let messages: Vec<Message> = messages.into_iter().skip(offset as usize).collect();
+                (messages, total)
+            }
+        };
+        let offset = offset as usize;
+        let has_more_messages = offset + messages.len() < total_message_count;
+
+        let message_ids: HashSet<Uuid> = messages.iter().map(|m| m.id).collect();
+        let attachments = crate::database::AttachmentRepository::new(&self.db_manager)
+            .get_by_session(&session_id)
+            .await?
+            .into_iter()
+            .filter(|a| message_ids.contains(&a.message_id))
+            .collect();
 
         Ok(SessionDetailResponse {
             session,
-            total_message_count: messages.len() as i32,
             messages,
-            has_more_messages: false, // For now, we load all messages
+            total_message_count: total_message_count as i32,
+            has_more_messages,
+            attachments,
         })
     }
 
+    /// The `k` sessions whose summaries are most similar to this session's
+    ///
+    /// Uses the session embedding index, so only sessions with an indexed
+    /// summary take part; the list stays empty until they are indexed.
+    pub async fn get_related_sessions(
+        &self,
+        session_id: &str,
+        k: usize,
+    ) -> Result<Vec<RelatedSession>> {
+        let embeddings = EmbeddingRepository::new(&self.db_manager)
+            .list(EmbeddingKind::Session)
+            .await?;
+        let Some(target) = embeddings.iter().find(|e| e.session_id == session_id) else {
+            return Ok(Vec::new());
+        };
+
+        let candidates: Vec<_> = embeddings
+            .iter()
+            .filter(|e| e.session_id != session_id)
+            .collect();
+        let vectors: Vec<Vec<f32>> = candidates.iter().map(|e| e.vector.clone()).collect();
+
+        let session_repo = ChatSessionRepository::new(&self.db_manager);
+        let summary_repo = SessionSummaryRepository::new(&self.db_manager);
+        let mut related = Vec::new();
+        for (index, score) in rank_by_similarity(&target.vector, &vectors) {
+            if related.len() >= k {
+                break;
+            }
+            let Ok(uuid) = Uuid::parse_str(&candidates[index].session_id) else {
+                continue;
+            };
+            let Some(session) = session_repo.get_by_id(&uuid).await? else {
+                continue;
+            };
+            let summary_title = summary_repo.get_by_session(&uuid).await?.map(|s| s.title);
+
+            related.push(RelatedSession {
+                session_id: uuid.to_string(),
+                score,
+                provider: session.provider.to_string(),
+                project: session.project_name,
+                title: summary_title.or(session.title),
+                start_time: session.start_time.to_rfc3339(),
+            });
+        }
+
+        Ok(related)
+    }
+
     pub async fn search_messages(&self, request: SearchRequest) -> Result<SearchResponse> {
         let start_time = std::time::Instant::now();
 
@@ -430,15 +984,41 @@ impl QueryService {
             (None, None)
         };
 
+        // Filter terms in the query (e.g. "provider:claude tool:Bash") restrict
+        // which sessions match; the remaining text is the search query
+        let expression = FilterExpr::parse(&request.query)?;
+        let session_terms = expression.without_text();
+        let (query, allowed_sessions) = if session_terms.is_empty() {
+            (request.query.clone(), None)
+        } else {
+            let text = expression.text();
+            if text.is_empty() {
+                anyhow::bail!("Search query needs text besides filter terms");
+            }
+            let allowed: HashSet<Uuid> = self
+                .filter_sessions(&session_terms)
+                .await
+                .context("Failed to apply search filters")?
+                .into_iter()
+                .map(|session| session.id)
+                .collect();
+            (text, Some(allowed))
+        };
+
         // Search for messages using FTS with filters
         let messages = message_repo
             .search_content_with_time_filters(
-                &request.query,
+                &query,
                 None,           // session_id filter
                 None,           // role filter
                 start_datetime, // from timestamp
                 end_datetime,   // to timestamp
-                Some(100),      // limit
+                // Fetch more when filter terms will drop some of the hits
+                Some(if allowed_sessions.is_some() {
+                    1000
+                } else {
+                    100
+                }),
             )
             .await?;
 
@@ -446,6 +1026,13 @@ impl QueryService {
         let mut results = Vec::new();
 
         for message in messages {
+            if allowed_sessions
+                .as_ref()
+                .is_some_and(|allowed| !allowed.contains(&message.session_id))
+            {
+                continue;
+            }
+
             // Get session info for context
             let session = session_repo
                 .get_by_id(&message.session_id)
@@ -473,6 +1060,27 @@ impl QueryService {
                 content_snippet,
                 message_role: message.role.to_string(),
                 relevance_score: 0.8, // FTS doesn't provide relevance scores, use default
+                sequence_number: message.sequence_number,
+                link: DeepLink::message(message.session_id, message.sequence_number).to_string(),
+            });
+        }
+
+        let facets = SearchFacets::count(results.iter().map(|result| {
+            (
+                result.provider.as_str(),
+                result.project.as_deref(),
+                result.timestamp.as_str(),
+            )
+        }));
+        if let Some(ref providers) = request.providers {
+            results.retain(|result| providers.contains(&result.provider));
+        }
+        if let Some(ref projects) = request.projects {
+            results.retain(|result| {
+                result
+                    .project
+                    .as_ref()
+                    .is_some_and(|project| projects.contains(project))
             });
         }
 
@@ -505,6 +1113,49 @@ impl QueryService {
             page,
             page_size,
             search_duration_ms,
+            facets,
+        })
+    }
+
+    /// Query tool operations across sessions, newest first
+    pub async fn query_tool_operations(
+        &self,
+        request: ToolOperationsQueryRequest,
+    ) -> Result<ToolOperationsQueryResponse> {
+        let page = request.page.unwrap_or(1).max(1);
+        let page_size = request.page_size.unwrap_or(20).max(1);
+
+        let session_id = request
+            .session_id
+            .as_deref()
+            .map(Uuid::parse_str)
+            .transpose()
+            .map_err(|e| anyhow::anyhow!("Invalid session ID: {e}"))?;
+
+        let filter = ToolOperationFilter {
+            tool_name: request.tool_name,
+            success: request.success,
+            file_path: request.file_path,
+            session_id,
+            from: request.since,
+            to: request.until,
+        };
+
+        let tool_repo = ToolOperationRepository::new(&self.db_manager);
+        let total_count = tool_repo.count_matching(&filter).await? as i32;
+        let operations = tool_repo
+            .query(&filter, page_size as i64, ((page - 1) * page_size) as i64)
+            .await?
+            .into_iter()
+            .map(|(session_id, op)| ToolOperationRecord::from_operation(session_id, op))
+            .collect();
+
+        Ok(ToolOperationsQueryResponse {
+            operations,
+            total_count,
+            page,
+            page_size,
+            total_pages: (total_count + page_size - 1) / page_size,
         })
     }
 
@@ -576,3 +1227,198 @@ impl Default for QueryService {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Provider;
+    use chrono::TimeZone;
+
+    fn request(page: i32, after: Option<SessionCursor>) -> SessionsQueryRequest {
+        SessionsQueryRequest {
+            page: Some(page),
+            page_size: Some(2),
+            sort_by: None,
+            sort_order: None,
+            filters: None,
+            after,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cursor_pagination_matches_offset_pagination() {
+        let db = Arc::new(DatabaseManager::open_in_memory().await.unwrap());
+        let repo = ChatSessionRepository::new(&db);
+        // Two sessions share a start time to exercise the id tie-break
+        for (i, hour) in [1, 2, 2, 3, 4].into_iter().enumerate() {
+            let start = Utc.with_ymd_and_hms(2024, 1, 1, hour, 0, 0).unwrap();
+            let session = ChatSession::new(
+                Provider::ClaudeCode,
+                format!("/test{i}.jsonl"),
+                format!("hash{i}"),
+                start,
+            );
+            repo.create(&session).await.unwrap();
+        }
+        let service = QueryService::with_database(db);
+
+        let mut by_offset = Vec::new();
+        for page in 1..=3 {
+            let response = service.query_sessions(request(page, None)).await.unwrap();
+            assert_eq!(response.next_cursor.is_some(), page < 3);
+            by_offset.extend(response.sessions.into_iter().map(|s| s.session_id));
+        }
+
+        let first = service.query_sessions(request(1, None)).await.unwrap();
+        let mut by_cursor: Vec<String> = first.sessions.into_iter().map(|s| s.session_id).collect();
+        let mut cursor = first.next_cursor;
+        while let Some(after) = cursor {
+            // Cursors survive a round trip through their string form
+            let after: SessionCursor = after.to_string().parse().unwrap();
+            let response = service
+                .query_sessions(request(1, Some(after)))
+                .await
+                .unwrap();
+            by_cursor.extend(response.sessions.into_iter().map(|s| s.session_id));
+            cursor = response.next_cursor;
+        }
+
+        assert_eq!(by_offset.len(), 5);
+        assert_eq!(by_cursor, by_offset);
+
+        let mut sorted_by_project = request(
+            1,
+            Some(SessionCursor {
+                start_time: Utc::now(),
+                id: Uuid::new_v4(),
+            }),
+        );
+        sorted_by_project.sort_by = Some("project".to_string());
+        assert!(service.query_sessions(sorted_by_project).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_filter_options_and_multi_select() {
+        let db = Arc::new(DatabaseManager::open_in_memory().await.unwrap());
+        let repo = ChatSessionRepository::new(&db);
+        for (i, provider) in [Provider::ClaudeCode, Provider::ClaudeCode, Provider::Codex]
+            .into_iter()
+            .chain([Provider::GeminiCLI])
+            .enumerate()
+        {
+            let session = ChatSession::new(
+                provider,
+                format!("/options{i}.jsonl"),
+                format!("hash{i}"),
+                Utc::now(),
+            );
+            repo.create(&session).await.unwrap();
+        }
+        let service = QueryService::with_database(db);
+
+        let options = service.get_filter_options().await.unwrap();
+        let providers: Vec<(&str, i64)> = options
+            .providers
+            .iter()
+            .map(|option| (option.value.as_str(), option.session_count))
+            .collect();
+        assert_eq!(
+            providers,
+            vec![("Claude Code", 2), ("Codex", 1), ("Gemini CLI", 1)]
+        );
+        assert!(options.projects.is_empty());
+
+        let mut multi_select = request(1, None);
+        multi_select.page_size = Some(10);
+        multi_select.filters = Some(SessionFilters {
+            provider: None,
+            project: None,
+            date_range: None,
+            min_messages: None,
+            max_messages: None,
+            include_archived: false,
+            expression: None,
+            topic: None,
+            owner: None,
+            providers: Some(vec!["Codex".to_string(), "Gemini CLI".to_string()]),
+            projects: None,
+            topics: None,
+        });
+        let response = service.query_sessions(multi_select).await.unwrap();
+        assert_eq!(response.total_count, 2);
+    }
+
+    #[test]
+    fn test_search_facets_count() {
+        let facets = SearchFacets::count([
+            ("Codex", None, "2024-09-30T10:00:00+00:00"),
+            (
+                "Claude Code",
+                Some("retrochat"),
+                "2024-10-01T10:00:00+00:00",
+            ),
+            ("Claude Code", Some("sandbox"), "2024-10-02T10:00:00+00:00"),
+        ]);
+        let counts = |facets: &[FacetCount]| {
+            facets
+                .iter()
+                .map(|facet| (facet.value.clone(), facet.count))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            counts(&facets.providers),
+            vec![("Claude Code".to_string(), 2), ("Codex".to_string(), 1)]
+        );
+        assert_eq!(
+            counts(&facets.projects),
+            vec![("retrochat".to_string(), 1), ("sandbox".to_string(), 1)]
+        );
+        assert_eq!(
+            counts(&facets.months),
+            vec![("2024-10".to_string(), 2), ("2024-09".to_string(), 1)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_related_sessions_ranks_by_summary_embedding() {
+        let db = Arc::new(DatabaseManager::open_in_memory().await.unwrap());
+        let repo = ChatSessionRepository::new(&db);
+        let mut ids = Vec::new();
+        for i in 0..3 {
+            let session = ChatSession::new(
+                Provider::ClaudeCode,
+                format!("/related{i}.jsonl"),
+                format!("hash{i}"),
+                Utc.with_ymd_and_hms(2024, 1, 1, i, 0, 0).unwrap(),
+            );
+            repo.create(&session).await.unwrap();
+            ids.push(session.id.to_string());
+        }
+
+        let embeddings = EmbeddingRepository::new(&db);
+        for (id, vector) in ids.iter().zip([[1.0, 0.0], [0.9, 0.1], [0.0, 1.0]]) {
+            embeddings
+                .upsert(
+                    EmbeddingKind::Session,
+                    &format!("summary-{id}"),
+                    id,
+                    "h",
+                    &vector,
+                )
+                .await
+                .unwrap();
+        }
+        let service = QueryService::with_database(db);
+
+        let related = service.get_related_sessions(&ids[0], 5).await.unwrap();
+        assert_eq!(related.len(), 1);
+        assert_eq!(related[0].session_id, ids[1]);
+
+        let unindexed = Uuid::new_v4().to_string();
+        assert!(service
+            .get_related_sessions(&unindexed, 5)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,273 @@
+//! Usage quota alerts
+//!
+//! Thresholds from the `[quota]` config section are checked after imports and
+//! analyses. Daily token usage counts every imported message (reported tokens,
+//! or the import-time estimate); weekly analysis usage counts what RetroChat's
+//! own LLM analyses spent. A crossed threshold is logged once per day or week
+//! in `quota_events`, so repeated checks don't repeat the alert.
+
+use anyhow::Result as AnyhowResult;
+use chrono::{DateTime, Datelike, Duration, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::config::QuotaConfig;
+use crate::database::{DatabaseManager, EventRepository, QuotaRepository};
+use crate::models::{EventKind, QuotaEvent, QuotaKind};
+
+/// Usage over the current UTC day and week
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct QuotaUsage {
+    pub day_start: DateTime<Utc>,
+    /// Monday 00:00 UTC of the current week
+    pub week_start: DateTime<Utc>,
+    pub daily_tokens: u64,
+    pub weekly_analysis_tokens: u64,
+    pub weekly_analysis_usd: f64,
+}
+
+impl QuotaUsage {
+    /// Current usage and period start counted against `kind`
+    pub fn get(&self, kind: QuotaKind) -> (f64, DateTime<Utc>) {
+        match kind {
+            QuotaKind::DailyTokens => (self.daily_tokens as f64, self.day_start),
+            QuotaKind::WeeklyAnalysisTokens => {
+                (self.weekly_analysis_tokens as f64, self.week_start)
+            }
+            QuotaKind::WeeklyAnalysisUsd => (self.weekly_analysis_usd, self.week_start),
+        }
+    }
+}
+
+/// A configured threshold and how much of it is used
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct QuotaStatus {
+    pub kind: QuotaKind,
+    pub threshold: f64,
+    pub used: f64,
+}
+
+impl QuotaStatus {
+    pub fn exceeded(&self) -> bool {
+        self.used >= self.threshold
+    }
+
+    pub fn percentage(&self) -> f64 {
+        self.used / self.threshold * 100.0
+    }
+}
+
+/// Service checking usage against the configured quotas
+pub struct QuotaService {
+    repo: QuotaRepository,
+    event_repo: EventRepository,
+}
+
+impl QuotaService {
+    pub fn new(db: &DatabaseManager) -> Self {
+        Self {
+            repo: QuotaRepository::new(db),
+            event_repo: EventRepository::new(db),
+        }
+    }
+
+    /// Usage over the UTC day and week containing `now`
+    pub async fn usage(&self, now: DateTime<Utc>) -> AnyhowResult<QuotaUsage> {
+        let day_start = now.date_naive().and_time(NaiveTime::MIN).and_utc();
+        let week_start = day_start - Duration::days(now.weekday().num_days_from_monday() as i64);
+
+        let daily_tokens = self
+            .repo
+            .tokens_between(day_start, day_start + Duration::days(1))
+            .await?;
+        let (weekly_analysis_tokens, weekly_analysis_usd) = self
+            .repo
+            .analysis_usage_between(week_start, week_start + Duration::weeks(1))
+            .await?;
+
+        Ok(QuotaUsage {
+            day_start,
+            week_start,
+            daily_tokens,
+            weekly_analysis_tokens,
+            weekly_analysis_usd,
+        })
+    }
+
+    /// Every configured threshold with its current usage
+    pub async fn status(
+        &self,
+        config: &QuotaConfig,
+        now: DateTime<Utc>,
+    ) -> AnyhowResult<Vec<QuotaStatus>> {
+        let usage = self.usage(now).await?;
+        Ok(thresholds(config)
+            .into_iter()
+            .map(|(kind, threshold)| QuotaStatus {
+                kind,
+                threshold,
+                used: usage.get(kind).0,
+            })
+            .collect())
+    }
+
+    /// Check usage against `config`, logging and returning thresholds crossed
+    /// for the first time in their current period
+    pub async fn check(
+        &self,
+        config: &QuotaConfig,
+        now: DateTime<Utc>,
+    ) -> AnyhowResult<Vec<QuotaEvent>> {
+        if !config.has_thresholds() {
+            return Ok(Vec::new());
+        }
+
+        let usage = self.usage(now).await?;
+        let mut crossed = Vec::new();
+        for (kind, threshold) in thresholds(config) {
+            let (observed, period_start) = usage.get(kind);
+            if observed < threshold {
+                continue;
+            }
+            let event = QuotaEvent {
+                kind,
+                period_start,
+                threshold,
+                observed,
+                created_at: now,
+            };
+            if self.repo.record(&event).await? {
+                self.event_repo
+                    .record(
+                        EventKind::QuotaExceeded,
+                        None,
+                        serde_json::to_value(&event)?,
+                    )
+                    .await;
+                crossed.push(event);
+            }
+        }
+        Ok(crossed)
+    }
+
+    /// Logged events, most recent first
+    pub async fn events(&self, limit: i64) -> AnyhowResult<Vec<QuotaEvent>> {
+        self.repo.list(limit).await
+    }
+}
+
+fn thresholds(config: &QuotaConfig) -> Vec<(QuotaKind, f64)> {
+    [
+        (
+            QuotaKind::DailyTokens,
+            config.daily_tokens.map(|t| t as f64),
+        ),
+        (
+            QuotaKind::WeeklyAnalysisTokens,
+            config.weekly_analysis_tokens.map(|t| t as f64),
+        ),
+        (QuotaKind::WeeklyAnalysisUsd, config.weekly_analysis_usd),
+    ]
+    .into_iter()
+    .filter_map(|(kind, threshold)| threshold.map(|t| (kind, t)))
+    .collect()
+}
+
+/// Show a desktop notification with `notify-send` (Linux) or `osascript`
+/// (macOS); returns whether one was shown
+pub fn notify_desktop(title: &str, body: &str) -> bool {
+    use std::process::{Command, Stdio};
+
+    let mut command = if cfg!(target_os = "macos") {
+        let quote = |s: &str| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+        let mut command = Command::new("osascript");
+        command.arg("-e").arg(format!(
+            "display notification {} with title {}",
+            quote(body),
+            quote(title)
+        ));
+        command
+    } else if cfg!(unix) {
+        let mut command = Command::new("notify-send");
+        command.arg("--app-name=RetroChat").arg(title).arg(body);
+        command
+    } else {
+        return false;
+    };
+
+    command
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{ChatSessionRepository, MessageRepository};
+    use crate::models::{ChatSession, Message, MessageRole, Provider};
+    use chrono::TimeZone;
+
+    #[tokio::test]
+    async fn test_check_alerts_once_per_period() {
+        let db = DatabaseManager::open_in_memory().await.unwrap();
+        // A Wednesday
+        let now = Utc.with_ymd_and_hms(2026, 3, 4, 15, 0, 0).unwrap();
+        let session = ChatSession::new(
+            Provider::GeminiCLI,
+            "/quota.json".to_string(),
+            "hash".to_string(),
+            now,
+        );
+        ChatSessionRepository::new(&db)
+            .create(&session)
+            .await
+            .unwrap();
+        let message = Message::new(
+            session.id,
+            MessageRole::Assistant,
+            "done".to_string(),
+            now - Duration::hours(1),
+            1,
+        )
+        .with_token_count(5000);
+        MessageRepository::new(&db)
+            .bulk_create(&[message])
+            .await
+            .unwrap();
+
+        let service = QuotaService::new(&db);
+        let usage = service.usage(now).await.unwrap();
+        assert_eq!(usage.daily_tokens, 5000);
+        assert_eq!(
+            usage.week_start,
+            Utc.with_ymd_and_hms(2026, 3, 2, 0, 0, 0).unwrap()
+        );
+
+        assert!(service
+            .check(&QuotaConfig::default(), now)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let config = QuotaConfig {
+            daily_tokens: Some(4000),
+            weekly_analysis_usd: Some(1.0),
+            ..Default::default()
+        };
+        let status = service.status(&config, now).await.unwrap();
+        assert_eq!(status.len(), 2);
+        assert!(status[0].exceeded());
+        assert!(!status[1].exceeded());
+
+        let events = service.check(&config, now).await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, QuotaKind::DailyTokens);
+        assert_eq!(events[0].observed, 5000.0);
+
+        // Already alerted today
+        assert!(service.check(&config, now).await.unwrap().is_empty());
+        assert_eq!(service.events(10).await.unwrap().len(), 1);
+    }
+}
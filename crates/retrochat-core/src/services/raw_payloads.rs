@@ -0,0 +1,327 @@
+//! Keep, strip and restore the provider JSON behind imported rows
+//!
+//! With `import.raw_payloads = true`, each message's original log line and
+//! each tool call's `tool_use`/`tool_result` blocks are kept in the
+//! `raw_payloads` table next to the normalized rows. Stripping drops them to
+//! save space; rehydrating re-reads the source logs and restores them for
+//! the messages and tool calls already in the database.
+
+use anyhow::{Context, Result as AnyhowResult};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::database::{
+    ChatSessionRepository, DatabaseManager, MessageRepository, RawPayloadRepository,
+    ToolOperationRepository,
+};
+use crate::models::{ChatSession, Message, RawPayload, RawPayloadKind, RawPayloadStats};
+use crate::parsers::ParserRegistry;
+use crate::services::project_config::{ProjectConfigCache, Redactor};
+
+/// Result of restoring raw payloads from the source logs
+#[derive(Debug, Clone, Default)]
+pub struct RehydrateReport {
+    pub sessions: usize,
+    pub payloads: usize,
+    /// Source files that no longer exist or could not be parsed
+    pub missing_files: Vec<String>,
+}
+
+/// Take the raw JSON off parsed messages as payloads to store
+///
+/// Message raws are always cleared, so they are never carried further than
+/// this. Tool calls pair each `tool_use` with its `tool_result` by ID, even
+/// when the result arrives in a later message.
+pub fn collect_raw_payloads(
+    session_id: Uuid,
+    messages: &mut [Message],
+    redactor: Option<&Redactor>,
+) -> Vec<RawPayload> {
+    let redact = |text: String| match redactor {
+        Some(redactor) => redactor.redact(&text),
+        None => text,
+    };
+
+    let results: HashMap<&str, &Value> = messages
+        .iter()
+        .flat_map(|message| message.tool_results.iter().flatten())
+        .map(|result| (result.tool_use_id.as_str(), &result.raw))
+        .collect();
+
+    let mut payloads = Vec::new();
+    for message in messages.iter() {
+        for tool_use in message.tool_uses.iter().flatten() {
+            let payload = json!({
+                "tool_use": tool_use.raw,
+                "tool_result": results.get(tool_use.id.as_str()),
+            });
+            payloads.push(RawPayload::new(
+                session_id,
+                RawPayloadKind::ToolCall,
+                tool_use.id.clone(),
+                redact(payload.to_string()),
+            ));
+        }
+    }
+
+    for message in messages.iter_mut() {
+        if let Some(raw) = message.raw.take() {
+            payloads.push(RawPayload::new(
+                session_id,
+                RawPayloadKind::Message,
+                message.id.to_string(),
+                redact(raw),
+            ));
+        }
+    }
+
+    payloads
+}
+
+/// Service behind `retrochat raw`
+pub struct RawPayloadService {
+    db_manager: Arc<DatabaseManager>,
+}
+
+impl RawPayloadService {
+    pub fn new(db_manager: Arc<DatabaseManager>) -> Self {
+        Self { db_manager }
+    }
+
+    pub async fn status(&self) -> AnyhowResult<RawPayloadStats> {
+        RawPayloadRepository::new(&self.db_manager).stats().await
+    }
+
+    /// Delete the raw payloads of one session, or of all sessions
+    pub async fn strip(&self, session_id: Option<&Uuid>) -> AnyhowResult<u64> {
+        let _write_guard = self.db_manager.write_lock().await?;
+        RawPayloadRepository::new(&self.db_manager)
+            .delete(session_id)
+            .await
+    }
+
+    /// Re-read source logs and store raw payloads for rows already imported
+    ///
+    /// Normalized rows are left as they are; only payloads for message and
+    /// tool use IDs still in the database are written.
+    pub async fn rehydrate(&self, session_id: Option<&Uuid>) -> AnyhowResult<RehydrateReport> {
+        let session_repo = ChatSessionRepository::new(&self.db_manager);
+        let sessions = match session_id {
+            Some(id) => vec![session_repo
+                .get_by_id(id)
+                .await?
+                .with_context(|| format!("Session not found: {id}"))?],
+            None => session_repo.get_all().await?,
+        };
+
+        // Parse each log once, however many sessions it holds
+        let mut by_file: BTreeMap<String, Vec<ChatSession>> = BTreeMap::new();
+        for session in sessions {
+            by_file
+                .entry(session.file_path.clone())
+                .or_default()
+                .push(session);
+        }
+
+        let mut report = RehydrateReport::default();
+        let mut project_configs = ProjectConfigCache::default();
+        for (file_path, stored) in by_file {
+            let parsed = if Path::new(&file_path).exists() {
+                ParserRegistry::parse_file(&file_path).await
+            } else {
+                Err(anyhow::anyhow!("File not found"))
+            };
+            let mut parsed: HashMap<Uuid, Vec<Message>> = match parsed {
+                Ok(parsed) => parsed
+                    .into_iter()
+                    .map(|(session, messages)| (session.id, messages))
+                    .collect(),
+                Err(e) => {
+                    tracing::warn!(file = %file_path, error = %e, "Cannot rehydrate from file");
+                    report.missing_files.push(file_path);
+                    continue;
+                }
+            };
+
+            for session in stored {
+                let Some(mut messages) = parsed.remove(&session.id) else {
+                    continue;
+                };
+                let redactor = match project_configs.get(&self.db_manager, &session).await {
+                    Some(config) => config.redactor().unwrap_or_else(|e| {
+                        tracing::warn!(error = %e, "Ignoring project redaction rules");
+                        None
+                    }),
+                    None => None,
+                };
+
+                let payloads = collect_raw_payloads(session.id, &mut messages, redactor.as_ref());
+                let stored = self.restore(&session.id, payloads).await?;
+                if stored > 0 {
+                    report.sessions += 1;
+                    report.payloads += stored;
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Replace a session's payloads with those matching its stored rows
+    async fn restore(&self, session_id: &Uuid, payloads: Vec<RawPayload>) -> AnyhowResult<usize> {
+        let message_ids: HashSet<String> = MessageRepository::new(&self.db_manager)
+            .get_by_session(session_id)
+            .await?
+            .into_iter()
+            .map(|message| message.id.to_string())
+            .collect();
+        let tool_use_ids: HashSet<String> = ToolOperationRepository::new(&self.db_manager)
+            .get_by_session(session_id)
+            .await?
+            .into_iter()
+            .map(|operation| operation.tool_use_id)
+            .collect();
+
+        let payloads: Vec<RawPayload> = payloads
+            .into_iter()
+            .filter(|payload| match payload.kind {
+                RawPayloadKind::Message => message_ids.contains(&payload.owner_id),
+                RawPayloadKind::ToolCall => tool_use_ids.contains(&payload.owner_id),
+            })
+            .collect();
+
+        let repo = RawPayloadRepository::new(&self.db_manager);
+        let _write_guard = self.db_manager.write_lock().await?;
+        repo.delete(Some(session_id)).await?;
+        repo.bulk_create(&payloads).await?;
+        Ok(payloads.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::message::{MessageRole, ToolResult, ToolUse};
+    use crate::services::{ImportFileRequest, ImportService};
+    use chrono::Utc;
+
+    #[test]
+    fn test_collect_raw_payloads_pairs_tool_results() {
+        let session_id = Uuid::new_v4();
+        let mut call = Message::new(
+            session_id,
+            MessageRole::Assistant,
+            "Reading".to_string(),
+            Utc::now(),
+            1,
+        )
+        .with_raw(r#"{"type":"assistant","secret":"hunter2"}"#.to_string());
+        call.tool_uses = Some(vec![ToolUse {
+            id: "toolu_1".to_string(),
+            name: "Read".to_string(),
+            input: json!({ "file_path": "a.rs" }),
+            raw: json!({ "id": "toolu_1" }),
+        }]);
+        let mut result = Message::new(session_id, MessageRole::User, String::new(), Utc::now(), 2);
+        result.tool_results = Some(vec![ToolResult {
+            tool_use_id: "toolu_1".to_string(),
+            content: "fn main() {}".to_string(),
+            is_error: false,
+            details: None,
+            raw: json!({ "tool_use_id": "toolu_1" }),
+        }]);
+        let mut messages = vec![call, result];
+
+        let payloads = collect_raw_payloads(session_id, &mut messages, None);
+        assert!(messages.iter().all(|message| message.raw.is_none()));
+        assert_eq!(payloads.len(), 2);
+
+        let tool_call = &payloads[0];
+        assert_eq!(tool_call.kind, RawPayloadKind::ToolCall);
+        assert_eq!(tool_call.owner_id, "toolu_1");
+        let value: Value = serde_json::from_str(&tool_call.payload).unwrap();
+        assert_eq!(value["tool_result"]["tool_use_id"], "toolu_1");
+
+        let message = &payloads[1];
+        assert_eq!(message.kind, RawPayloadKind::Message);
+        assert_eq!(message.owner_id, messages[0].id.to_string());
+        assert_eq!(
+            message.payload,
+            r#"{"type":"assistant","secret":"hunter2"}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_import_strip_and_rehydrate() {
+        let db = Arc::new(DatabaseManager::open_in_memory().await.unwrap());
+        let service = RawPayloadService::new(db.clone());
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let session_id = "550e8400-e29b-41d4-a716-446655440010";
+        let file_path = temp_dir.path().join(format!("{session_id}.jsonl"));
+        let user_line = format!(
+            r#"{{"type":"user","sessionId":"{session_id}","uuid":"6f1c2a52-0d1e-4b8e-9a57-0c1d2e3f4a01","timestamp":"2024-01-01T00:00:00Z","message":{{"role":"user","content":"List files"}}}}"#
+        );
+        let content = [
+            user_line.clone(),
+            format!(
+                r#"{{"type":"assistant","sessionId":"{session_id}","uuid":"6f1c2a52-0d1e-4b8e-9a57-0c1d2e3f4a02","timestamp":"2024-01-01T00:01:00Z","message":{{"role":"assistant","content":[{{"type":"tool_use","id":"toolu_1","name":"Bash","input":{{"command":"ls"}}}}]}}}}"#
+            ),
+            format!(
+                r#"{{"type":"user","sessionId":"{session_id}","uuid":"6f1c2a52-0d1e-4b8e-9a57-0c1d2e3f4a03","timestamp":"2024-01-01T00:01:30Z","message":{{"role":"user","content":[{{"type":"tool_result","tool_use_id":"toolu_1","content":"src"}}]}}}}"#
+            ),
+        ]
+        .join("\n");
+        std::fs::write(&file_path, content).unwrap();
+
+        let import = |keep: bool| {
+            let service = ImportService::new(db.clone()).with_raw_payloads(keep);
+            let file_path = file_path.to_string_lossy().to_string();
+            async move {
+                service
+                    .import_file(ImportFileRequest {
+                        file_path,
+                        provider: None,
+                        project_name: None,
+                        overwrite_existing: Some(true),
+                    })
+                    .await
+                    .unwrap()
+            }
+        };
+
+        import(false).await;
+        assert_eq!(service.status().await.unwrap(), RawPayloadStats::default());
+
+        import(true).await;
+        let stats = service.status().await.unwrap();
+        assert_eq!(stats.sessions, 1);
+        assert_eq!(stats.messages, 3);
+        assert_eq!(stats.tool_calls, 1);
+
+        let session_id = Uuid::parse_str(session_id).unwrap();
+        let repo = RawPayloadRepository::new(&db);
+        let message = repo
+            .get(
+                &session_id,
+                RawPayloadKind::Message,
+                "6f1c2a52-0d1e-4b8e-9a57-0c1d2e3f4a01",
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(message.payload, user_line);
+
+        assert_eq!(service.strip(Some(&session_id)).await.unwrap(), 4);
+        assert_eq!(service.status().await.unwrap().sessions, 0);
+
+        let report = service.rehydrate(None).await.unwrap();
+        assert_eq!(report.sessions, 1);
+        assert_eq!(report.payloads, 4);
+        assert!(report.missing_files.is_empty());
+        assert_eq!(service.status().await.unwrap(), stats);
+    }
+}
@@ -0,0 +1,357 @@
+//! Multi-device sync through remote storage
+//!
+//! `push` packs the sessions a remote hasn't seen yet (or has seen with fewer
+//! messages) into a bundle: a SQLite snapshot made by
+//! [`export_sqlite_snapshot`], gzipped and encrypted with the sync passphrase.
+//! `pull` downloads the bundles it hasn't merged yet and merges them by
+//! session ID: unknown sessions are added, and a known session is replaced
+//! when the bundle's copy has more messages. Analyses from either side are
+//! kept. Bundles are never modified after upload, so machines can push and
+//! pull in any order without coordinating.
+
+pub mod storage;
+
+pub use storage::{open_storage, RemoteStorage};
+
+use anyhow::{Context, Result as AnyhowResult};
+use chrono::Utc;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde_json::json;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::database::{
+    BundleDirection, DatabaseManager, EventRepository, RemoteSyncRepository, RemoteSyncSummary,
+};
+use crate::models::EventKind;
+use crate::services::snapshot_export::export_sqlite_snapshot;
+use crate::services::snapshot_merge::{
+    merge_snapshot, prepare_snapshot, MergeAction, MergeStatement, TempDatabase,
+};
+use crate::utils::crypto;
+
+/// What a push uploaded
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PushReport {
+    /// Name of the uploaded bundle; `None` when there was nothing to push
+    pub bundle: Option<String>,
+    pub sessions: usize,
+    /// Size of the encrypted bundle
+    pub bytes: usize,
+}
+
+/// What a pull merged
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PullReport {
+    pub bundles: usize,
+    /// Sessions this database didn't have
+    pub added: usize,
+    /// Sessions replaced by a copy with more messages
+    pub replaced: usize,
+    /// Sessions whose local copy was kept because it was at least as long
+    pub kept: usize,
+}
+
+/// Pushes to and pulls from sync remotes
+pub struct RemoteSyncService {
+    db_manager: Arc<DatabaseManager>,
+    repo: RemoteSyncRepository,
+    event_repo: EventRepository,
+}
+
+impl RemoteSyncService {
+    pub fn new(db_manager: Arc<DatabaseManager>) -> Self {
+        Self {
+            repo: RemoteSyncRepository::new(&db_manager),
+            event_repo: EventRepository::new(&db_manager),
+            db_manager,
+        }
+    }
+
+    /// Sessions the next push to `remote` would upload
+    pub async fn pending_sessions(&self, remote: &str) -> AnyhowResult<usize> {
+        Ok(self.repo.unsynced_sessions(remote).await?.len())
+    }
+
+    pub async fn summary(&self, remote: &str) -> AnyhowResult<RemoteSyncSummary> {
+        self.repo.summary(remote).await
+    }
+
+    /// Drop the sync history of a removed remote
+    pub async fn forget(&self, remote: &str) -> AnyhowResult<()> {
+        self.repo.forget(remote).await
+    }
+
+    /// Upload one bundle with every session `remote` hasn't seen in its
+    /// current form
+    pub async fn push(
+        &self,
+        remote: &str,
+        storage: &dyn RemoteStorage,
+        passphrase: &str,
+    ) -> AnyhowResult<PushReport> {
+        let sessions = self.repo.unsynced_sessions(remote).await?;
+        if sessions.is_empty() {
+            return Ok(PushReport::default());
+        }
+
+        let snapshot = TempDatabase::new();
+        let ids: Vec<Uuid> = sessions.iter().map(|(id, _)| *id).collect();
+        export_sqlite_snapshot(&self.db_manager, &ids, snapshot.path()).await?;
+        let contents = std::fs::read(snapshot.path()).context("Failed to read bundle")?;
+        drop(snapshot);
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&contents)?;
+        let sealed = crypto::seal(passphrase, &encoder.finish()?)?;
+        let bytes = sealed.len();
+
+        let name = bundle_name();
+        storage
+            .put(&name, sealed)
+            .await
+            .with_context(|| format!("Failed to upload bundle to {remote}"))?;
+
+        // Counts were read before the export, so a session that grew in the
+        // meantime is pushed again next time rather than skipped
+        self.repo.mark_synced(remote, &sessions).await?;
+        self.repo
+            .record_bundle(remote, &name, BundleDirection::Push, sessions.len())
+            .await?;
+        tracing::info!(remote, bundle = %name, sessions = sessions.len(), "Pushed sync bundle");
+
+        Ok(PushReport {
+            bundle: Some(name),
+            sessions: sessions.len(),
+            bytes,
+        })
+    }
+
+    /// Merge every bundle on `remote` that this database hasn't seen, oldest
+    /// first
+    pub async fn pull(
+        &self,
+        remote: &str,
+        storage: &dyn RemoteStorage,
+        passphrase: &str,
+    ) -> AnyhowResult<PullReport> {
+        let known = self.repo.known_bundles(remote).await?;
+        let mut names: Vec<String> = storage
+            .list()
+            .await
+            .with_context(|| format!("Failed to list bundles on {remote}"))?
+            .into_iter()
+            .filter(|name| !known.contains(name))
+            .collect();
+        // Names start with the upload time
+        names.sort();
+
+        let mut report = PullReport::default();
+        for name in names {
+            let sealed = storage.get(&name).await?;
+            let compressed = crypto::open(passphrase, &sealed)
+                .with_context(|| format!("Failed to decrypt bundle {name}"))?;
+            let mut contents = Vec::new();
+            GzDecoder::new(compressed.as_slice())
+                .read_to_end(&mut contents)
+                .with_context(|| format!("Bundle {name} is corrupt"))?;
+
+            // Bring bundles from older versions up to this schema
+            let bundle = prepare_snapshot(&contents, &format!("Bundle {name}")).await?;
+
+            let merged = self.merge_bundle(remote, bundle.path()).await?;
+            let sessions = merged.len();
+            for (session_id, action) in merged {
+                let kind = match action {
+                    MergeAction::Add => {
+                        report.added += 1;
+                        EventKind::SessionImported
+                    }
+                    MergeAction::Replace => {
+                        report.replaced += 1;
+                        EventKind::SessionReplaced
+                    }
+                    MergeAction::Keep => {
+                        report.kept += 1;
+                        continue;
+                    }
+                };
+                self.event_repo
+                    .record(
+                        kind,
+                        Some(&session_id),
+                        json!({ "remote": remote, "bundle": name }),
+                    )
+                    .await;
+            }
+
+            self.repo
+                .record_bundle(remote, &name, BundleDirection::Pull, sessions)
+                .await?;
+            report.bundles += 1;
+            tracing::info!(remote, bundle = %name, sessions, "Merged sync bundle");
+        }
+
+        Ok(report)
+    }
+
+    /// Merge one migrated bundle database by session ID, keeping the
+    /// longer copy of sessions both sides have
+    async fn merge_bundle(
+        &self,
+        remote: &str,
+        bundle: &Path,
+    ) -> AnyhowResult<Vec<(String, MergeAction)>> {
+        let plan = MergeStatement {
+            sql: "SELECT s.id,
+                         CASE WHEN m.id IS NULL THEN 'add'
+                              WHEN s.message_count > m.message_count THEN 'replace'
+                              ELSE 'keep' END
+                  FROM incoming.chat_sessions s
+                  LEFT JOIN main.chat_sessions m ON m.id = s.id",
+            binds: Vec::new(),
+        };
+        // Everything in the bundle is on the remote; sessions kept locally
+        // are recorded with the remote's count so a longer local copy is
+        // pushed back
+        let record_synced = MergeStatement {
+            sql: "INSERT INTO main.remote_sessions (remote, session_id, message_count, synced_at)
+                  SELECT ?, id, message_count, ? FROM incoming.chat_sessions WHERE true
+                  ON CONFLICT(remote, session_id) DO UPDATE SET
+                      message_count = excluded.message_count,
+                      synced_at = excluded.synced_at",
+            binds: vec![remote.to_string(), Utc::now().to_rfc3339()],
+        };
+        merge_snapshot(&self.db_manager, bundle, plan, &[record_synced]).await
+    }
+}
+
+/// Unique, chronologically sortable bundle file name
+fn bundle_name() -> String {
+    format!(
+        "{}-{}{}",
+        Utc::now().format("%Y%m%dT%H%M%S%.3fZ"),
+        &Uuid::new_v4().simple().to_string()[..12],
+        storage::BUNDLE_EXTENSION
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{ChatSessionRepository, MessageRepository};
+    use crate::models::{ChatSession, Message, MessageRole, Provider};
+    use storage::LocalStorage;
+
+    const PASSPHRASE: &str = "correct horse battery staple";
+
+    async fn open(dir: &Path, name: &str) -> Arc<DatabaseManager> {
+        Arc::new(DatabaseManager::new(dir.join(name)).await.unwrap())
+    }
+
+    async fn add_messages(db: &DatabaseManager, session: &ChatSession, from: u32, to: u32) {
+        let messages: Vec<Message> = (from..=to)
+            .map(|n| {
+                Message::new(
+                    session.id,
+                    MessageRole::User,
+                    format!("message {n}"),
+                    Utc::now(),
+                    n,
+                )
+            })
+            .collect();
+        MessageRepository::new(db)
+            .bulk_create(&messages)
+            .await
+            .unwrap();
+        ChatSessionRepository::new(db)
+            .refresh_stats(&session.id)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_push_pull_and_merge_by_session() {
+        let dir = tempfile::tempdir().unwrap();
+        let laptop = open(dir.path(), "laptop.db").await;
+        let desktop = open(dir.path(), "desktop.db").await;
+        let storage = LocalStorage::new(dir.path().join("remote"));
+
+        let session = ChatSession::new(
+            Provider::ClaudeCode,
+            "/shared.jsonl".to_string(),
+            "hash".to_string(),
+            Utc::now(),
+        );
+        ChatSessionRepository::new(&laptop)
+            .create(&session)
+            .await
+            .unwrap();
+        add_messages(&laptop, &session, 1, 2).await;
+
+        let laptop_sync = RemoteSyncService::new(laptop.clone());
+        let desktop_sync = RemoteSyncService::new(desktop.clone());
+        let pushed = laptop_sync.push("nas", &storage, PASSPHRASE).await.unwrap();
+        assert_eq!(pushed.sessions, 1);
+        assert_eq!(
+            laptop_sync.push("nas", &storage, PASSPHRASE).await.unwrap(),
+            PushReport::default()
+        );
+
+        assert!(desktop_sync.pull("nas", &storage, "wrong").await.is_err());
+        let pulled = desktop_sync
+            .pull("nas", &storage, PASSPHRASE)
+            .await
+            .unwrap();
+        assert_eq!((pulled.bundles, pulled.added), (1, 1));
+        assert_eq!(
+            MessageRepository::new(&desktop)
+                .count_by_session(&session.id)
+                .await
+                .unwrap(),
+            2
+        );
+        // Pulled sessions aren't pushed back, and bundles aren't merged twice
+        assert_eq!(desktop_sync.pending_sessions("nas").await.unwrap(), 0);
+        assert_eq!(
+            desktop_sync
+                .pull("nas", &storage, PASSPHRASE)
+                .await
+                .unwrap(),
+            PullReport::default()
+        );
+        assert_eq!(
+            laptop_sync.pull("nas", &storage, PASSPHRASE).await.unwrap(),
+            PullReport::default()
+        );
+
+        // The session continues on the desktop; the longer copy wins
+        add_messages(&desktop, &session, 3, 4).await;
+        assert_eq!(desktop_sync.pending_sessions("nas").await.unwrap(), 1);
+        desktop_sync
+            .push("nas", &storage, PASSPHRASE)
+            .await
+            .unwrap();
+        let pulled = laptop_sync.pull("nas", &storage, PASSPHRASE).await.unwrap();
+        assert_eq!((pulled.added, pulled.replaced), (0, 1));
+        let merged = ChatSessionRepository::new(&laptop)
+            .get_by_id(&session.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(merged.message_count, 4);
+        assert_eq!(laptop_sync.pending_sessions("nas").await.unwrap(), 0);
+
+        let events = EventRepository::new(&laptop).recent(1).await.unwrap();
+        assert_eq!(events[0].kind, EventKind::SessionReplaced);
+        assert_eq!(events[0].payload["remote"], "nas");
+
+        let summary = laptop_sync.summary("nas").await.unwrap();
+        assert_eq!((summary.pushed_bundles, summary.pulled_bundles), (1, 1));
+    }
+}
@@ -0,0 +1,267 @@
+use anyhow::{Context, Result as AnyhowResult};
+use chrono::{DateTime, Duration, Utc};
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::config::{Config, RetentionConfig};
+use crate::database::{
+    ChatSessionRepository, DatabaseManager, EventRepository, ToolOperationRepository,
+};
+use crate::models::{ChatSession, EventKind, Provider, SessionState};
+
+/// Selects sessions for manual archiving
+///
+/// Every field that is set must match; an empty selector matches nothing.
+#[derive(Debug, Clone, Default)]
+pub struct SessionSelector {
+    pub session_ids: Vec<Uuid>,
+    pub provider: Option<String>,
+    pub project: Option<String>,
+    /// Sessions that ended before this time
+    pub before: Option<DateTime<Utc>>,
+}
+
+impl SessionSelector {
+    pub fn is_empty(&self) -> bool {
+        self.session_ids.is_empty()
+            && self.provider.is_none()
+            && self.project.is_none()
+            && self.before.is_none()
+    }
+
+    pub fn matches(&self, session: &ChatSession) -> bool {
+        if !self.session_ids.is_empty() && !self.session_ids.contains(&session.id) {
+            return false;
+        }
+        if let Some(provider) = &self.provider {
+            let matches_provider = session.provider.to_string() == *provider
+                || provider.parse::<Provider>().ok().as_ref() == Some(&session.provider);
+            if !matches_provider {
+                return false;
+            }
+        }
+        if let Some(project) = &self.project {
+            if session.project_name.as_deref() != Some(project) {
+                return false;
+            }
+        }
+        if let Some(before) = self.before {
+            if session.end_time.unwrap_or(session.start_time) >= before {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// What a retention run changed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RetentionReport {
+    pub archived_sessions: u64,
+    pub purged_tool_results: u64,
+}
+
+impl RetentionReport {
+    pub fn is_empty(&self) -> bool {
+        self.archived_sessions == 0 && self.purged_tool_results == 0
+    }
+}
+
+/// Service for archiving sessions and applying retention rules
+pub struct RetentionService {
+    db_manager: Arc<DatabaseManager>,
+}
+
+impl RetentionService {
+    pub fn new(db_manager: Arc<DatabaseManager>) -> Self {
+        Self { db_manager }
+    }
+
+    /// Archive the selected sessions, returning how many were archived
+    pub async fn archive(&self, selector: &SessionSelector) -> AnyhowResult<u64> {
+        let ids = self.select(selector, |s| !s.is_archived()).await?;
+        let archived = ChatSessionRepository::new(&self.db_manager)
+            .set_state_by_ids(&ids, &SessionState::Archived)
+            .await?;
+        self.record_event(EventKind::SessionsArchived, archived, &ids)
+            .await;
+        Ok(archived)
+    }
+
+    /// Bring archived sessions matching the selector back into session lists
+    pub async fn restore(&self, selector: &SessionSelector) -> AnyhowResult<u64> {
+        let ids = self.select(selector, ChatSession::is_archived).await?;
+        let restored = ChatSessionRepository::new(&self.db_manager)
+            .set_state_by_ids(&ids, &SessionState::Imported)
+            .await?;
+        self.record_event(EventKind::SessionsRestored, restored, &ids)
+            .await;
+        Ok(restored)
+    }
+
+    /// Apply the retention policy from the user config
+    pub async fn run_maintenance(&self) -> AnyhowResult<RetentionReport> {
+        let config = Config::load().context("Failed to load config")?;
+        self.apply_policy(&config.retention, Utc::now()).await
+    }
+
+    /// Apply a retention policy as of `now`
+    pub async fn apply_policy(
+        &self,
+        policy: &RetentionConfig,
+        now: DateTime<Utc>,
+    ) -> AnyhowResult<RetentionReport> {
+        let mut report = RetentionReport::default();
+        let _write_guard = self.db_manager.write_lock().await?;
+
+        if let Some(days) = policy.archive_after_days {
+            report.archived_sessions = ChatSessionRepository::new(&self.db_manager)
+                .archive_ended_before(now - Duration::days(days.into()))
+                .await?;
+            self.record_event(EventKind::SessionsArchived, report.archived_sessions, &[])
+                .await;
+        }
+
+        if let Some(days) = policy.purge_tool_results_after_days {
+            report.purged_tool_results = ToolOperationRepository::new(&self.db_manager)
+                .purge_raw_results_before(now - Duration::days(days.into()))
+                .await?;
+        }
+
+        Ok(report)
+    }
+
+    /// Log archived or restored sessions to the change feed; `ids` is empty
+    /// when the policy archived them
+    async fn record_event(&self, kind: EventKind, count: u64, ids: &[Uuid]) {
+        if count == 0 {
+            return;
+        }
+        EventRepository::new(&self.db_manager)
+            .record(kind, None, json!({ "count": count, "session_ids": ids }))
+            .await;
+    }
+
+    async fn select(
+        &self,
+        selector: &SessionSelector,
+        include: impl Fn(&ChatSession) -> bool,
+    ) -> AnyhowResult<Vec<Uuid>> {
+        if selector.is_empty() {
+            anyhow::bail!("Specify session IDs or at least one filter");
+        }
+
+        let sessions = ChatSessionRepository::new(&self.db_manager)
+            .get_all()
+            .await?;
+        Ok(sessions
+            .iter()
+            .filter(|s| selector.matches(s) && include(s))
+            .map(|s| s.id)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::MessageRepository;
+    use crate::models::{Message, MessageRole, ToolOperation};
+    use serde_json::json;
+
+    async fn insert_session(db: &DatabaseManager, index: usize, end_time: &str) -> Uuid {
+        let session_id = Uuid::new_v4();
+        sqlx::query(
+            r#"
+            INSERT INTO chat_sessions (id, provider, project_name, start_time, end_time, message_count, file_path, file_hash, state)
+            VALUES (?, 'Claude Code', NULL, ?, ?, 1, ?, ?, 'imported')
+            "#,
+        )
+        .bind(session_id.to_string())
+        .bind(end_time)
+        .bind(end_time)
+        .bind(format!("/test{index}.jsonl"))
+        .bind(format!("hash{index}"))
+        .execute(db.pool())
+        .await
+        .unwrap();
+        session_id
+    }
+
+    #[tokio::test]
+    async fn test_archive_and_restore_by_selector() {
+        let db = Arc::new(DatabaseManager::open_in_memory().await.unwrap());
+        let old = insert_session(&db, 0, "2023-01-01T00:00:00+00:00").await;
+        let recent = insert_session(&db, 1, "2024-06-01T00:00:00+00:00").await;
+        let service = RetentionService::new(db.clone());
+
+        assert!(service.archive(&SessionSelector::default()).await.is_err());
+
+        let selector = SessionSelector {
+            before: Some("2024-01-01T00:00:00Z".parse().unwrap()),
+            ..Default::default()
+        };
+        assert_eq!(service.archive(&selector).await.unwrap(), 1);
+        assert_eq!(service.archive(&selector).await.unwrap(), 0);
+
+        let repo = ChatSessionRepository::new(&db);
+        assert!(repo.get_by_id(&old).await.unwrap().unwrap().is_archived());
+        assert!(!repo
+            .get_by_id(&recent)
+            .await
+            .unwrap()
+            .unwrap()
+            .is_archived());
+
+        let selector = SessionSelector {
+            session_ids: vec![old],
+            ..Default::default()
+        };
+        assert_eq!(service.restore(&selector).await.unwrap(), 1);
+        assert!(!repo.get_by_id(&old).await.unwrap().unwrap().is_archived());
+    }
+
+    #[tokio::test]
+    async fn test_apply_policy_archives_and_purges_old_sessions() {
+        let db = Arc::new(DatabaseManager::open_in_memory().await.unwrap());
+        let old = insert_session(&db, 0, "2023-01-01T00:00:00+00:00").await;
+        insert_session(&db, 1, "2024-06-01T00:00:00+00:00").await;
+
+        let operation = ToolOperation::new("toolu_1".to_string(), "Bash".to_string(), Utc::now())
+            .with_raw_result(json!({"stdout": "lots of output"}))
+            .with_result_summary("Listed files".to_string());
+        ToolOperationRepository::new(&db)
+            .create(&operation)
+            .await
+            .unwrap();
+        let mut message = Message::new(old, MessageRole::User, "ls".to_string(), Utc::now(), 1);
+        message.tool_operation_id = Some(operation.id);
+        MessageRepository::new(&db).create(&message).await.unwrap();
+
+        let policy = RetentionConfig {
+            archive_after_days: Some(180),
+            purge_tool_results_after_days: Some(365),
+        };
+        let now = "2024-07-01T00:00:00Z".parse().unwrap();
+        let service = RetentionService::new(db.clone());
+
+        let report = service.apply_policy(&policy, now).await.unwrap();
+        assert_eq!(
+            report,
+            RetentionReport {
+                archived_sessions: 1,
+                purged_tool_results: 1,
+            }
+        );
+        assert!(service.apply_policy(&policy, now).await.unwrap().is_empty());
+
+        let stored = ToolOperationRepository::new(&db)
+            .get_by_id(&operation.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(stored.raw_result.is_none());
+        assert_eq!(stored.result_summary.as_deref(), Some("Listed files"));
+    }
+}
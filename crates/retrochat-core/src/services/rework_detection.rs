@@ -0,0 +1,323 @@
+use anyhow::{Context, Result as AnyhowResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use super::embedding::{cosine_similarity, Embedder, HashingEmbedder};
+use crate::database::{DatabaseManager, MessageRepository};
+use crate::models::message::MessageType;
+use crate::models::{ChatSession, Message};
+
+/// Cosine similarity above which two prompts are considered the same ask
+pub const DEFAULT_REWORK_THRESHOLD: f32 = 0.9;
+
+/// Upper bound on prompts loaded from history for a single detection pass
+const MAX_HISTORY_PROMPTS: i64 = 5000;
+
+/// Prompts shorter than this (e.g. "yes", "continue") are never treated as rework
+const MIN_PROMPT_WORDS: usize = 3;
+
+/// A user ask that was repeated in more than one session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepeatedPrompt {
+    /// Earliest occurrence of the prompt, used as the representative text
+    pub prompt: String,
+    /// Total number of near-duplicate occurrences
+    pub occurrences: usize,
+    /// Distinct sessions the prompt appeared in
+    pub session_ids: Vec<String>,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+}
+
+/// Service for detecting near-duplicate user prompts ("rework") across sessions
+pub struct ReworkDetector {
+    db_manager: Arc<DatabaseManager>,
+    embedder: Arc<dyn Embedder>,
+    threshold: f32,
+}
+
+impl ReworkDetector {
+    pub fn new(db_manager: Arc<DatabaseManager>) -> Self {
+        Self {
+            db_manager,
+            embedder: Arc::new(HashingEmbedder::new()),
+            threshold: DEFAULT_REWORK_THRESHOLD,
+        }
+    }
+
+    pub fn with_embedder(mut self, embedder: Arc<dyn Embedder>) -> Self {
+        self.embedder = embedder;
+        self
+    }
+
+    pub fn with_threshold(mut self, threshold: f32) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// List the most repeated asks across all sessions, most frequent first
+    pub async fn find_repeated_prompts(&self, limit: usize) -> AnyhowResult<Vec<RepeatedPrompt>> {
+        let message_repo = MessageRepository::new(&self.db_manager);
+        let mut prompts: Vec<Message> = message_repo
+            .get_user_prompts(None, MAX_HISTORY_PROMPTS)
+            .await
+            .context("Failed to load user prompts")?
+            .into_iter()
+            .filter(is_rework_candidate)
+            .collect();
+        prompts.sort_by_key(|m| m.timestamp);
+
+        let vectors = self.embed_messages(&prompts).await?;
+        let mut repeated = group_repeated_prompts(&prompts, &vectors, self.threshold);
+        repeated.truncate(limit);
+        Ok(repeated)
+    }
+
+    /// Fraction of a session's prompts that repeat a prompt from an earlier session
+    pub async fn rework_rate(
+        &self,
+        session: &ChatSession,
+        messages: &[Message],
+    ) -> AnyhowResult<f64> {
+        let session_prompts: Vec<Message> = messages
+            .iter()
+            .filter(|m| is_rework_candidate(m))
+            .cloned()
+            .collect();
+        if session_prompts.is_empty() {
+            return Ok(0.0);
+        }
+
+        let message_repo = MessageRepository::new(&self.db_manager);
+        let prior_prompts: Vec<Message> = message_repo
+            .get_user_prompts(Some(session.start_time), MAX_HISTORY_PROMPTS)
+            .await
+            .context("Failed to load prior user prompts")?
+            .into_iter()
+            .filter(|m| m.session_id != session.id && is_rework_candidate(m))
+            .collect();
+
+        let session_vectors = self.embed_messages(&session_prompts).await?;
+        let prior_vectors = self.embed_messages(&prior_prompts).await?;
+
+        Ok(calculate_rework_rate(
+            &session_vectors,
+            &prior_vectors,
+            self.threshold,
+        ))
+    }
+
+    async fn embed_messages(&self, messages: &[Message]) -> AnyhowResult<Vec<Vec<f32>>> {
+        let texts: Vec<String> = messages.iter().map(|m| m.content.clone()).collect();
+        self.embedder.embed_batch(&texts).await
+    }
+}
+
+/// Whether a message is a user-authored prompt long enough to compare
+pub fn is_rework_candidate(message: &Message) -> bool {
+    message.is_user_message()
+        && message.message_type == MessageType::SimpleMessage
+        && message.word_count() >= MIN_PROMPT_WORDS
+}
+
+/// Fraction of `session_vectors` with a near-duplicate in `prior_vectors`
+pub fn calculate_rework_rate(
+    session_vectors: &[Vec<f32>],
+    prior_vectors: &[Vec<f32>],
+    threshold: f32,
+) -> f64 {
+    if session_vectors.is_empty() {
+        return 0.0;
+    }
+
+    let reworked = session_vectors
+        .iter()
+        .filter(|v| {
+            prior_vectors
+                .iter()
+                .any(|p| cosine_similarity(v, p) >= threshold)
+        })
+        .count();
+
+    reworked as f64 / session_vectors.len() as f64
+}
+
+/// Greedily cluster chronologically ordered prompts by similarity to each
+/// cluster's first prompt, keeping only clusters that span multiple sessions.
+pub fn group_repeated_prompts(
+    prompts: &[Message],
+    vectors: &[Vec<f32>],
+    threshold: f32,
+) -> Vec<RepeatedPrompt> {
+    struct Cluster<'a> {
+        representative: usize,
+        members: Vec<&'a Message>,
+    }
+
+    let mut clusters: Vec<Cluster> = Vec::new();
+
+    for (i, prompt) in prompts.iter().enumerate() {
+        let existing = clusters
+            .iter_mut()
+            .find(|c| cosine_similarity(&vectors[c.representative], &vectors[i]) >= threshold);
+
+        match existing {
+            Some(cluster) => cluster.members.push(prompt),
+            None => clusters.push(Cluster {
+                representative: i,
+                members: vec![prompt],
+            }),
+        }
+    }
+
+    let mut repeated: Vec<RepeatedPrompt> = clusters
+        .into_iter()
+        .filter_map(|cluster| {
+            let mut seen = HashSet::new();
+            let session_ids: Vec<String> = cluster
+                .members
+                .iter()
+                .filter(|m| seen.insert(m.session_id))
+                .map(|m| m.session_id.to_string())
+                .collect();
+            if session_ids.len() < 2 {
+                return None;
+            }
+
+            let first = cluster.members.first()?;
+            let last = cluster.members.last()?;
+            Some(RepeatedPrompt {
+                prompt: prompts[cluster.representative].content.clone(),
+                occurrences: cluster.members.len(),
+                session_ids,
+                first_seen: first.timestamp,
+                last_seen: last.timestamp,
+            })
+        })
+        .collect();
+
+    repeated.sort_by(|a, b| {
+        b.occurrences
+            .cmp(&a.occurrences)
+            .then_with(|| b.last_seen.cmp(&a.last_seen))
+    });
+    repeated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::MessageRole;
+    use uuid::Uuid;
+
+    fn user_prompt(session_id: Uuid, content: &str, minutes: i64) -> Message {
+        let timestamp = DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+            + chrono::Duration::minutes(minutes);
+        Message::new(
+            session_id,
+            MessageRole::User,
+            content.to_string(),
+            timestamp,
+            1,
+        )
+    }
+
+    #[test]
+    fn test_is_rework_candidate_skips_short_and_non_user_messages() {
+        let session_id = Uuid::new_v4();
+        assert!(!is_rework_candidate(&user_prompt(session_id, "yes", 0)));
+        assert!(is_rework_candidate(&user_prompt(
+            session_id,
+            "run the test suite again",
+            0
+        )));
+
+        let mut assistant = user_prompt(session_id, "run the test suite again", 0);
+        assistant.role = MessageRole::Assistant;
+        assert!(!is_rework_candidate(&assistant));
+    }
+
+    #[test]
+    fn test_group_repeated_prompts_requires_multiple_sessions() {
+        let embedder = HashingEmbedder::new();
+        let session_a = Uuid::new_v4();
+        let session_b = Uuid::new_v4();
+        let prompts = vec![
+            user_prompt(session_a, "Fix the flaky login integration test", 0),
+            user_prompt(session_a, "Explain how the parser registry works", 1),
+            user_prompt(session_b, "fix the flaky login integration test", 60),
+            user_prompt(session_b, "Explain how the parser registry works", 61),
+            user_prompt(session_a, "Explain how the parser registry works", 62),
+        ];
+        let vectors: Vec<Vec<f32>> = prompts
+            .iter()
+            .map(|m| embedder.embed_text(&m.content))
+            .collect();
+
+        let repeated = group_repeated_prompts(&prompts, &vectors, DEFAULT_REWORK_THRESHOLD);
+
+        assert_eq!(repeated.len(), 2);
+        assert_eq!(repeated[0].prompt, "Explain how the parser registry works");
+        assert_eq!(repeated[0].occurrences, 3);
+        assert_eq!(repeated[0].session_ids.len(), 2);
+        assert_eq!(repeated[1].occurrences, 2);
+    }
+
+    #[test]
+    fn test_calculate_rework_rate() {
+        let embedder = HashingEmbedder::new();
+        let session = vec![
+            embedder.embed_text("Fix the flaky login integration test"),
+            embedder.embed_text("Add a dark mode toggle to settings"),
+        ];
+        let prior = vec![embedder.embed_text("fix the flaky login integration test")];
+
+        let rate = calculate_rework_rate(&session, &prior, DEFAULT_REWORK_THRESHOLD);
+        assert!((rate - 0.5).abs() < f64::EPSILON);
+        assert_eq!(
+            calculate_rework_rate(&[], &prior, DEFAULT_REWORK_THRESHOLD),
+            0.0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_find_repeated_prompts_from_database() {
+        let db = Arc::new(DatabaseManager::open_in_memory().await.unwrap());
+        let message_repo = MessageRepository::new(&db);
+
+        for (i, content) in ["Refactor the import service", "refactor the import service"]
+            .iter()
+            .enumerate()
+        {
+            let session_id = Uuid::new_v4();
+            sqlx::query(
+                r#"
+                INSERT INTO chat_sessions (id, provider, project_name, start_time, end_time, message_count, file_path, file_hash, state)
+                VALUES (?, 'Claude Code', NULL, '2024-01-01T00:00:00Z', '2024-01-01T01:00:00Z', 1, ?, ?, 'imported')
+                "#,
+            )
+            .bind(session_id.to_string())
+            .bind(format!("/test{i}.jsonl"))
+            .bind(format!("hash{i}"))
+            .execute(db.pool())
+            .await
+            .unwrap();
+
+            message_repo
+                .create(&user_prompt(session_id, content, i as i64))
+                .await
+                .unwrap();
+        }
+
+        let detector = ReworkDetector::new(db.clone());
+        let repeated = detector.find_repeated_prompts(10).await.unwrap();
+
+        assert_eq!(repeated.len(), 1);
+        assert_eq!(repeated[0].occurrences, 2);
+        assert_eq!(repeated[0].prompt, "Refactor the import service");
+    }
+}
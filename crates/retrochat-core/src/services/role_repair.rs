@@ -0,0 +1,408 @@
+//! Re-infer message roles for sessions imported with the wrong ones
+//!
+//! Some Cursor and older imports label every message as User. Roles are first
+//! guessed from the message type and typical phrasing ("I'll update...",
+//! "Can you..."); with an LLM client, messages the heuristics can't place are
+//! classified by the LLM. System messages are never touched, and every change
+//! is kept in the message's revision history.
+
+use anyhow::{Context, Result as AnyhowResult};
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::database::{DatabaseManager, MessageRepository};
+use crate::models::message::MessageType;
+use crate::models::{Message, MessageRole, RevisionSource};
+use crate::services::llm::{CachingClient, GenerateRequest, LlmClient};
+use crate::utils::session_title::truncate_title;
+
+/// Messages classified per LLM request
+const MESSAGES_PER_REQUEST: usize = 40;
+
+/// Characters of each message shown to the LLM
+const EXCERPT_CHARS: usize = 300;
+
+/// How assistants typically open a reply
+const ASSISTANT_OPENERS: &[&str] = &[
+    "i'll ",
+    "i will ",
+    "i've ",
+    "i have ",
+    "let me check",
+    "let me look",
+    "let me start",
+    "let me update",
+    "let me fix",
+    "sure",
+    "certainly",
+    "absolutely",
+    "here's ",
+    "here is ",
+    "now i",
+    "you're right",
+    "the issue",
+    "the problem",
+];
+
+/// How users typically phrase a request
+const USER_OPENERS: &[&str] = &[
+    "please",
+    "can you",
+    "could you",
+    "can we",
+    "would you",
+    "why ",
+    "how do",
+    "how can",
+    "what ",
+    "fix ",
+    "add ",
+    "make ",
+    "implement ",
+    "remove ",
+    "update ",
+    "write ",
+    "create ",
+    "change ",
+    "refactor ",
+];
+
+/// Requests are short; longer messages starting like one are left to the LLM
+const MAX_USER_CHARS: usize = 400;
+
+/// One role correction
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoleChange {
+    pub message_id: Uuid,
+    pub sequence_number: u32,
+    pub from: MessageRole,
+    pub to: MessageRole,
+    /// What gave the role away, e.g. `assistant phrasing` or `llm`
+    pub reason: String,
+    /// Start of the message, for review
+    pub excerpt: String,
+}
+
+/// Result of a role repair
+#[derive(Debug, Clone, Default)]
+pub struct RoleRepairReport {
+    pub changes: Vec<RoleChange>,
+    /// Messages neither the heuristics nor the LLM could place
+    pub undecided: usize,
+    /// Whether the changes were saved
+    pub applied: bool,
+}
+
+/// Service for re-inferring the roles of a session's messages
+pub struct RoleRepairService {
+    message_repo: MessageRepository,
+    llm_client: Option<Arc<dyn LlmClient>>,
+}
+
+impl RoleRepairService {
+    /// Without an LLM client, only the heuristics are used
+    pub fn new(db_manager: Arc<DatabaseManager>, llm_client: Option<Arc<dyn LlmClient>>) -> Self {
+        let llm_client = llm_client.map(|client| CachingClient::wrap(client, &db_manager));
+        Self {
+            message_repo: MessageRepository::new(&db_manager),
+            llm_client,
+        }
+    }
+
+    /// Work out role corrections for a session, saving them unless `dry_run`
+    pub async fn repair(&self, session_id: &Uuid, dry_run: bool) -> AnyhowResult<RoleRepairReport> {
+        let messages = self
+            .message_repo
+            .get_by_session_id(session_id)
+            .await
+            .context("Failed to fetch session messages")?;
+        if messages.is_empty() {
+            anyhow::bail!("Session {session_id} has no messages");
+        }
+
+        let mut report = RoleRepairReport::default();
+        let mut undecided = Vec::new();
+        for message in messages.iter().filter(|m| m.role != MessageRole::System) {
+            match infer_role(message) {
+                Some((role, reason)) => push_change(&mut report, message, role, reason),
+                None => undecided.push(message),
+            }
+        }
+
+        if let Some(client) = &self.llm_client {
+            for batch in undecided.chunks(MESSAGES_PER_REQUEST) {
+                let roles = classify_with_llm(client.as_ref(), batch).await?;
+                for message in batch {
+                    match roles.get(&message.sequence_number) {
+                        Some(role) => push_change(&mut report, message, role.clone(), "llm"),
+                        None => report.undecided += 1,
+                    }
+                }
+            }
+        } else {
+            report.undecided = undecided.len();
+        }
+
+        if !dry_run && !report.changes.is_empty() {
+            let roles: Vec<(Uuid, MessageRole, String)> = report
+                .changes
+                .iter()
+                .map(|c| (c.message_id, c.to.clone(), c.reason.clone()))
+                .collect();
+            self.message_repo
+                .set_roles(&roles, RevisionSource::Repair)
+                .await?;
+            report.applied = true;
+        }
+
+        Ok(report)
+    }
+}
+
+/// Record a change if the inferred role differs from the stored one
+fn push_change(report: &mut RoleRepairReport, message: &Message, role: MessageRole, reason: &str) {
+    if message.role == role {
+        return;
+    }
+    report.changes.push(RoleChange {
+        message_id: message.id,
+        sequence_number: message.sequence_number,
+        from: message.role.clone(),
+        to: role,
+        reason: reason.to_string(),
+        excerpt: truncate_title(message.content.lines().next().unwrap_or(""), 60),
+    });
+}
+
+/// Role given away by the message type or phrasing, with the reason
+fn infer_role(message: &Message) -> Option<(MessageRole, &'static str)> {
+    match message.message_type {
+        MessageType::Thinking => return Some((MessageRole::Assistant, "thinking")),
+        MessageType::ToolRequest => return Some((MessageRole::Assistant, "tool call")),
+        MessageType::SlashCommand => return Some((MessageRole::User, "slash command")),
+        MessageType::SimpleMessage => {}
+        // Providers disagree on who owns tool results and injected context
+        _ => return None,
+    }
+
+    let content = message.content.trim();
+    let lower = content.to_lowercase();
+    if ASSISTANT_OPENERS.iter().any(|p| lower.starts_with(p)) {
+        return Some((MessageRole::Assistant, "assistant phrasing"));
+    }
+    if content.chars().count() <= MAX_USER_CHARS {
+        if USER_OPENERS.iter().any(|p| lower.starts_with(p)) {
+            return Some((MessageRole::User, "request phrasing"));
+        }
+        if content.ends_with('?') && !content.contains("```") {
+            return Some((MessageRole::User, "question"));
+        }
+    }
+    if content.contains("```") && content.lines().filter(|l| l.starts_with('#')).count() >= 2 {
+        return Some((MessageRole::Assistant, "structured reply"));
+    }
+    None
+}
+
+/// Ask the LLM for the roles of `messages`, keyed by sequence number
+async fn classify_with_llm(
+    client: &dyn LlmClient,
+    messages: &[&Message],
+) -> AnyhowResult<HashMap<u32, MessageRole>> {
+    let mut transcript = String::new();
+    for message in messages {
+        let excerpt = truncate_title(&message.content.replace('\n', " "), EXCERPT_CHARS);
+        transcript.push_str(&format!("[{}] {excerpt}\n", message.sequence_number));
+    }
+
+    let prompt = format!(
+        r#"The messages below come from a conversation between a developer and an AI coding assistant, in order, but who wrote each one was lost. Decide for each message whether the developer (USER) or the assistant (ASSISTANT) wrote it.
+
+## Messages
+
+{transcript}
+## Required Output Format
+
+One line per message, e.g. "12: USER" or "13: ASSISTANT". Skip messages you can't decide."#
+    );
+    let request = GenerateRequest::new(prompt)
+        .with_max_tokens(16 * messages.len() as u32 + 64)
+        .with_temperature(0.0);
+    let response = client.generate(request).await?;
+    Ok(parse_llm_roles(&response.text))
+}
+
+fn parse_llm_roles(response: &str) -> HashMap<u32, MessageRole> {
+    let re = Regex::new(r"(?im)^\W*(\d+)\W+(USER|ASSISTANT)\b").expect("valid regex");
+    re.captures_iter(response)
+        .filter_map(|caps| {
+            let sequence = caps[1].parse().ok()?;
+            let role = if caps[2].eq_ignore_ascii_case("user") {
+                MessageRole::User
+            } else {
+                MessageRole::Assistant
+            };
+            Some((sequence, role))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{ChatSessionRepository, MessageRevisionRepository};
+    use crate::models::{ChatSession, Provider};
+    use crate::services::llm::{GenerateResponse, LlmError};
+    use async_trait::async_trait;
+    use chrono::Utc;
+
+    struct FixedLlm(&'static str);
+
+    #[async_trait]
+    impl LlmClient for FixedLlm {
+        async fn generate(&self, _request: GenerateRequest) -> Result<GenerateResponse, LlmError> {
+            Ok(GenerateResponse {
+                text: self.0.to_string(),
+                ..Default::default()
+            })
+        }
+
+        fn provider_name(&self) -> &'static str {
+            "fixed"
+        }
+
+        fn model_name(&self) -> &str {
+            "fixed"
+        }
+
+        async fn health_check(&self) -> Result<(), LlmError> {
+            Ok(())
+        }
+    }
+
+    fn user_message(content: &str) -> Message {
+        Message::new(
+            Uuid::new_v4(),
+            MessageRole::User,
+            content.to_string(),
+            Utc::now(),
+            1,
+        )
+    }
+
+    #[test]
+    fn test_infer_role() {
+        let role = |content: &str| infer_role(&user_message(content)).map(|(role, _)| role);
+        assert_eq!(
+            role("I'll add the migration first."),
+            Some(MessageRole::Assistant)
+        );
+        assert_eq!(role("Can you add a migration?"), Some(MessageRole::User));
+        assert_eq!(role("is this thread safe?"), Some(MessageRole::User));
+        assert_eq!(role("Let me know when it builds"), None);
+        assert_eq!(role("ok"), None);
+
+        let mut thinking = user_message("The user wants a migration");
+        thinking.message_type = MessageType::Thinking;
+        assert_eq!(
+            infer_role(&thinking).map(|(role, _)| role),
+            Some(MessageRole::Assistant)
+        );
+    }
+
+    #[test]
+    fn test_parse_llm_roles() {
+        let roles = parse_llm_roles("3: USER\n- 4: Assistant\n**5** - USER\nnot sure about 6");
+        assert_eq!(roles.len(), 3);
+        assert_eq!(roles[&4], MessageRole::Assistant);
+        assert_eq!(roles[&5], MessageRole::User);
+    }
+
+    #[tokio::test]
+    async fn test_repair_with_heuristics_and_llm() {
+        let db = Arc::new(DatabaseManager::open_in_memory().await.unwrap());
+        let session = ChatSession::new(
+            Provider::CursorClient,
+            "/cursor.db".to_string(),
+            "hash".to_string(),
+            Utc::now(),
+        );
+        ChatSessionRepository::new(&db)
+            .create(&session)
+            .await
+            .unwrap();
+        let contents = [
+            "Can you rename the config module?",
+            "Sure, I'll rename it to settings.",
+            "thanks, now the tests",
+            "Renamed and the tests pass again.",
+        ];
+        let messages: Vec<Message> = contents
+            .iter()
+            .enumerate()
+            .map(|(i, content)| {
+                let mut message = user_message(content);
+                message.session_id = session.id;
+                message.sequence_number = i as u32 + 1;
+                message
+            })
+            .collect();
+        let message_repo = MessageRepository::new(&db);
+        message_repo.bulk_create(&messages).await.unwrap();
+
+        let heuristic = RoleRepairService::new(db.clone(), None)
+            .repair(&session.id, true)
+            .await
+            .unwrap();
+        assert_eq!(heuristic.changes.len(), 1);
+        assert_eq!(heuristic.changes[0].sequence_number, 2);
+        assert_eq!(heuristic.undecided, 2);
+        assert!(!heuristic.applied);
+
+        let llm: Arc<dyn LlmClient> = Arc::new(FixedLlm("3: USER\n4: ASSISTANT"));
+        let report = RoleRepairService::new(db.clone(), Some(llm))
+            .repair(&session.id, false)
+            .await
+            .unwrap();
+        assert!(report.applied);
+        assert_eq!(report.undecided, 0);
+        assert_eq!(
+            report
+                .changes
+                .iter()
+                .map(|c| (c.sequence_number, c.reason.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(2, "assistant phrasing"), (4, "llm")]
+        );
+
+        let revisions = MessageRevisionRepository::new(&db)
+            .get_by_message(&messages[1].id)
+            .await
+            .unwrap();
+        assert_eq!(revisions.len(), 1);
+        assert_eq!(revisions[0].field, "role");
+        assert_eq!(revisions[0].before.as_deref(), Some("User"));
+        assert_eq!(revisions[0].after.as_deref(), Some("Assistant"));
+        assert_eq!(revisions[0].source, RevisionSource::Repair);
+        assert_eq!(revisions[0].reason.as_deref(), Some("assistant phrasing"));
+
+        let roles: Vec<MessageRole> = message_repo
+            .get_by_session_id(&session.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|m| m.role)
+            .collect();
+        assert_eq!(
+            roles,
+            vec![
+                MessageRole::User,
+                MessageRole::Assistant,
+                MessageRole::User,
+                MessageRole::Assistant
+            ]
+        );
+    }
+}
@@ -0,0 +1,730 @@
+//! Manual fixes for sessions a parser split or joined wrongly
+//!
+//! `retrochat session split` moves the tail of a session into a new one, and
+//! `retrochat session merge` appends one session to another. Messages, their
+//! usage, attachments and raw payloads move in one transaction and session
+//! tags carry over to the new or surviving session; summaries, embeddings and topic
+//! assignments of both sessions are dropped since their turn boundaries no
+//! longer hold, and each moved message gets a `position` revision.
+//! Importing the source file again with `--overwrite` restores the parser's
+//! version of a split session; merged sessions are remembered so re-imports
+//! skip them.
+
+use anyhow::{Context, Result as AnyhowResult};
+use chrono::Utc;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::database::{
+    ChatSessionRepository, DatabaseManager, MessageRepository, SessionQualityRepository,
+    SessionSegmentRepository, ToolOperationRepository,
+};
+use crate::models::{ChatSession, SessionQuality, SessionSegment, TitleSource};
+use crate::utils::session_title::title_from_messages;
+
+/// Per-message rows that follow their message into the other session
+pub(crate) const MESSAGE_TABLES: &[&str] = &["message_usage", "attachments"];
+
+/// Session tables moved or rebuilt by their own statements, or that only
+/// describe a merged-away session and go with it
+#[cfg(test)]
+pub(crate) const HANDLED_TABLES: &[&str] = &[
+    "messages",
+    "message_revisions",
+    "raw_payloads",
+    "session_tags",
+    "session_segments",
+    "session_quality",
+    "merged_sessions",
+    "analytics_requests",
+    "experiment_sessions",
+    "session_comparisons",
+];
+
+/// Per-session data that describes the old message layout
+pub(crate) const DERIVED_TABLES: &[&str] = &[
+    "turn_summaries",
+    "session_summaries",
+    "summary_embeddings",
+    "session_topics",
+];
+
+/// Result of splitting a session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SplitReport {
+    pub session_id: Uuid,
+    /// Session holding the messages from the split point on
+    pub new_session_id: Uuid,
+    pub messages_kept: usize,
+    pub messages_moved: usize,
+}
+
+/// Result of merging two sessions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeReport {
+    /// The earlier session, which now holds both conversations
+    pub session_id: Uuid,
+    /// The later session, which no longer exists
+    pub merged_session_id: Uuid,
+    pub messages_moved: usize,
+}
+
+/// Service for splitting and merging stored sessions
+pub struct SessionEditService {
+    db_manager: Arc<DatabaseManager>,
+}
+
+impl SessionEditService {
+    pub fn new(db_manager: Arc<DatabaseManager>) -> Self {
+        Self { db_manager }
+    }
+
+    /// Move the messages from sequence number `at_sequence` on into a new
+    /// session, numbered from 1
+    pub async fn split(&self, session_id: &Uuid, at_sequence: u32) -> AnyhowResult<SplitReport> {
+        let _write_guard = self.db_manager.write_lock().await?;
+        let session = self.editable_session(session_id).await?;
+        let messages = MessageRepository::new(&self.db_manager)
+            .get_by_session_id(session_id)
+            .await?;
+
+        let split_index = messages.partition_point(|m| m.sequence_number < at_sequence);
+        let (kept, moved) = messages.split_at(split_index);
+        let (Some(last_kept), Some(first_moved), Some(last_moved)) =
+            (kept.last(), moved.first(), moved.last())
+        else {
+            anyhow::bail!(
+                "Sequence {at_sequence} does not split session {session_id}: \
+                 both parts need at least one message"
+            );
+        };
+
+        let new_session_id = Uuid::new_v4();
+        let offset = i64::from(first_moved.sequence_number) - 1;
+        let title = title_from_messages(moved);
+        let mut tx = self
+            .db_manager
+            .pool()
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        // The copy shares the source file, so it gets its own hash to satisfy
+        // UNIQUE(file_hash, file_path)
+        sqlx::query(
+            r#"
+            INSERT INTO chat_sessions (
+                id, provider, project_name, title, title_source, start_time, end_time,
+                file_path, file_hash, state, owner
+            )
+            SELECT ?, provider, project_name, ?, ?, ?, ?, file_path, ?, state, owner
+            FROM chat_sessions WHERE id = ?
+            "#,
+        )
+        .bind(new_session_id.to_string())
+        .bind(&title)
+        .bind(title.as_ref().map(|_| TitleSource::Heuristic.to_string()))
+        .bind(first_moved.timestamp.to_rfc3339())
+        .bind(last_moved.timestamp.to_rfc3339())
+        .bind(format!("{}#{new_session_id}", session.file_hash))
+        .bind(session_id.to_string())
+        .execute(&mut *tx)
+        .await
+        .context("Failed to create split session")?;
+
+        sqlx::query(
+            "INSERT INTO session_tags (session_id, tag, created_at)
+             SELECT ?, tag, created_at FROM session_tags WHERE session_id = ?",
+        )
+        .bind(new_session_id.to_string())
+        .bind(session_id.to_string())
+        .execute(&mut *tx)
+        .await
+        .context("Failed to copy session tags")?;
+
+        sqlx::query(&record_positions("sequence_number >= ?4", "-"))
+            .bind(new_session_id.to_string())
+            .bind(offset)
+            .bind(session_id.to_string())
+            .bind(i64::from(at_sequence))
+            .bind("session split")
+            .bind(Utc::now().to_rfc3339())
+            .execute(&mut *tx)
+            .await
+            .context("Failed to record message revisions")?;
+
+        sqlx::query(
+            r#"
+            UPDATE messages SET session_id = ?, sequence_number = sequence_number - ?
+            WHERE session_id = ? AND sequence_number >= ?
+            "#,
+        )
+        .bind(new_session_id.to_string())
+        .bind(offset)
+        .bind(session_id.to_string())
+        .bind(i64::from(at_sequence))
+        .execute(&mut *tx)
+        .await
+        .context("Failed to move messages")?;
+
+        for table in MESSAGE_TABLES {
+            sqlx::query(&format!(
+                "UPDATE {table} SET session_id = ?1
+                 WHERE message_id IN (SELECT id FROM messages WHERE session_id = ?1)"
+            ))
+            .bind(new_session_id.to_string())
+            .execute(&mut *tx)
+            .await
+            .with_context(|| format!("Failed to move {table}"))?;
+        }
+
+        // Raw payloads are keyed by message ID or by the tool use ID of a
+        // moved message's tool call
+        sqlx::query(
+            r#"
+            UPDATE raw_payloads SET session_id = ?1
+            WHERE session_id = ?2 AND (
+                (owner_kind = 'message'
+                 AND owner_id IN (SELECT id FROM messages WHERE session_id = ?1))
+                OR (owner_kind = 'tool_call'
+                    AND owner_id IN (
+                        SELECT t.tool_use_id FROM tool_operations t
+                        JOIN messages m ON m.tool_operation_id = t.id
+                        WHERE m.session_id = ?1))
+            )
+            "#,
+        )
+        .bind(new_session_id.to_string())
+        .bind(session_id.to_string())
+        .execute(&mut *tx)
+        .await
+        .context("Failed to move raw_payloads")?;
+
+        // Provider-reported totals cover the whole file, so both halves fall
+        // back to their messages' counts
+        sqlx::query("UPDATE chat_sessions SET end_time = ?, token_count = NULL WHERE id = ?")
+            .bind(last_kept.timestamp.to_rfc3339())
+            .bind(session_id.to_string())
+            .execute(&mut *tx)
+            .await
+            .context("Failed to update split session")?;
+
+        delete_derived(&mut tx, &[session_id, &new_session_id]).await?;
+        tx.commit()
+            .await
+            .context("Failed to commit session split")?;
+
+        self.refresh(&[*session_id, new_session_id]).await?;
+        Ok(SplitReport {
+            session_id: *session_id,
+            new_session_id,
+            messages_kept: kept.len(),
+            messages_moved: moved.len(),
+        })
+    }
+
+    /// Append the later of two sessions to the earlier one and delete it
+    pub async fn merge(&self, first_id: &Uuid, second_id: &Uuid) -> AnyhowResult<MergeReport> {
+        if first_id == second_id {
+            anyhow::bail!("Cannot merge session {first_id} into itself");
+        }
+        let _write_guard = self.db_manager.write_lock().await?;
+        let mut target = self.editable_session(first_id).await?;
+        let mut other = self.editable_session(second_id).await?;
+        if target.provider != other.provider {
+            anyhow::bail!(
+                "Cannot merge a {} session with a {} session",
+                target.provider,
+                other.provider
+            );
+        }
+        if other.start_time < target.start_time {
+            std::mem::swap(&mut target, &mut other);
+        }
+
+        let message_repo = MessageRepository::new(&self.db_manager);
+        let target_messages = message_repo.get_by_session_id(&target.id).await?;
+        let other_messages = message_repo.get_by_session_id(&other.id).await?;
+        let last_sequence = target_messages.last().map_or(0, |m| m.sequence_number);
+        let first_sequence = other_messages.first().map_or(1, |m| m.sequence_number);
+        let offset = i64::from(last_sequence) - i64::from(first_sequence) + 1;
+
+        let end_time = target.end_time.max(other.end_time);
+        let token_count = target
+            .token_count
+            .zip(other.token_count)
+            .map(|(a, b)| a + b);
+        let mut tx = self
+            .db_manager
+            .pool()
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        sqlx::query(&record_positions("1", "+"))
+            .bind(target.id.to_string())
+            .bind(offset)
+            .bind(other.id.to_string())
+            .bind(None::<i64>)
+            .bind("session merge")
+            .bind(Utc::now().to_rfc3339())
+            .execute(&mut *tx)
+            .await
+            .context("Failed to record message revisions")?;
+
+        sqlx::query(
+            "UPDATE messages SET session_id = ?, sequence_number = sequence_number + ?
+             WHERE session_id = ?",
+        )
+        .bind(target.id.to_string())
+        .bind(offset)
+        .bind(other.id.to_string())
+        .execute(&mut *tx)
+        .await
+        .context("Failed to move messages")?;
+
+        for table in MESSAGE_TABLES {
+            sqlx::query(&format!(
+                "UPDATE {table} SET session_id = ? WHERE session_id = ?"
+            ))
+            .bind(target.id.to_string())
+            .bind(other.id.to_string())
+            .execute(&mut *tx)
+            .await
+            .with_context(|| format!("Failed to move {table}"))?;
+        }
+
+        sqlx::query("UPDATE chat_sessions SET end_time = ?, token_count = ? WHERE id = ?")
+            .bind(end_time.map(|t| t.to_rfc3339()))
+            .bind(token_count)
+            .bind(target.id.to_string())
+            .execute(&mut *tx)
+            .await
+            .context("Failed to update merged session")?;
+
+        delete_derived(&mut tx, &[&target.id, &other.id]).await?;
+
+        // Sessions merged into the deleted one follow its messages
+        sqlx::query("UPDATE merged_sessions SET merged_into = ? WHERE merged_into = ?")
+            .bind(target.id.to_string())
+            .bind(other.id.to_string())
+            .execute(&mut *tx)
+            .await
+            .context("Failed to update earlier merges")?;
+        sqlx::query(
+            "INSERT OR REPLACE INTO merged_sessions (session_id, merged_into, merged_at)
+             VALUES (?, ?, ?)",
+        )
+        .bind(other.id.to_string())
+        .bind(target.id.to_string())
+        .bind(Utc::now().to_rfc3339())
+        .execute(&mut *tx)
+        .await
+        .context("Failed to record merge")?;
+
+        sqlx::query("UPDATE OR IGNORE raw_payloads SET session_id = ? WHERE session_id = ?")
+            .bind(target.id.to_string())
+            .bind(other.id.to_string())
+            .execute(&mut *tx)
+            .await
+            .context("Failed to move raw_payloads")?;
+
+        sqlx::query(
+            "INSERT OR IGNORE INTO session_tags (session_id, tag, created_at)
+             SELECT ?, tag, created_at FROM session_tags WHERE session_id = ?",
+        )
+        .bind(target.id.to_string())
+        .bind(other.id.to_string())
+        .execute(&mut *tx)
+        .await
+        .context("Failed to copy session tags")?;
+
+        sqlx::query("DELETE FROM chat_sessions WHERE id = ?")
+            .bind(other.id.to_string())
+            .execute(&mut *tx)
+            .await
+            .context("Failed to delete merged session")?;
+
+        tx.commit()
+            .await
+            .context("Failed to commit session merge")?;
+
+        self.refresh(&[target.id]).await?;
+        Ok(MergeReport {
+            session_id: target.id,
+            merged_session_id: other.id,
+            messages_moved: other_messages.len(),
+        })
+    }
+
+    async fn editable_session(&self, id: &Uuid) -> AnyhowResult<ChatSession> {
+        let session = ChatSessionRepository::new(&self.db_manager)
+            .get_by_id(id)
+            .await?
+            .with_context(|| format!("Session not found: {id}"))?;
+        if let Some(owner) = &session.owner {
+            anyhow::bail!("Session {id} was imported from {owner}'s export and is read-only");
+        }
+        Ok(session)
+    }
+
+    /// Recompute cached statistics, segments and quality signals from the
+    /// moved messages
+    async fn refresh(&self, session_ids: &[Uuid]) -> AnyhowResult<()> {
+        let session_repo = ChatSessionRepository::new(&self.db_manager);
+        let message_repo = MessageRepository::new(&self.db_manager);
+        let segment_repo = SessionSegmentRepository::new(&self.db_manager);
+        let tool_repo = ToolOperationRepository::new(&self.db_manager);
+        let quality_repo = SessionQualityRepository::new(&self.db_manager);
+        for id in session_ids {
+            session_repo.refresh_stats(id).await?;
+            let messages = message_repo.get_by_session_id(id).await?;
+            segment_repo
+                .replace_for_session(id, &SessionSegment::from_messages(*id, &messages))
+                .await?;
+            let operations = tool_repo.get_by_session(id).await?;
+            quality_repo
+                .upsert(&SessionQuality::from_messages(*id, &messages, &operations))
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+/// Statement recording the new position of the messages of session `?3`
+/// matching `filter`, moved to session `?1` with their sequence numbers
+/// shifted by `op ?2`
+///
+/// The remaining parameters are `?4` for the filter, the reason `?5` and the
+/// time `?6`.
+fn record_positions(filter: &str, op: &str) -> String {
+    format!(
+        "INSERT INTO message_revisions (
+             message_id, field, before_value, after_value, source, reason, created_at
+         )
+         SELECT id, 'position', session_id || '#' || sequence_number,
+                ?1 || '#' || (sequence_number {op} ?2), 'manual', ?5, ?6
+         FROM messages WHERE session_id = ?3 AND {filter}"
+    )
+}
+
+async fn delete_derived(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    session_ids: &[&Uuid],
+) -> AnyhowResult<()> {
+    for table in DERIVED_TABLES {
+        for id in session_ids {
+            sqlx::query(&format!("DELETE FROM {table} WHERE session_id = ?"))
+                .bind(id.to_string())
+                .execute(&mut **tx)
+                .await
+                .with_context(|| format!("Failed to clear {table}"))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{
+        AttachmentRepository, MessageRevisionRepository, MessageUsageRepository,
+        RawPayloadRepository, SessionTagRepository,
+    };
+    use crate::models::{
+        Attachment, Message, MessageRole, MessageUsage, Provider, RawPayload, RawPayloadKind,
+        ToolOperation,
+    };
+    use chrono::{DateTime, Duration};
+
+    async fn insert_session(
+        db: &DatabaseManager,
+        file_path: &str,
+        start: DateTime<Utc>,
+        contents: &[&str],
+    ) -> (ChatSession, Vec<Message>) {
+        let mut session = ChatSession::new(
+            Provider::ClaudeCode,
+            file_path.to_string(),
+            format!("hash-{file_path}"),
+            start,
+        );
+        session.token_count = Some(1000);
+        ChatSessionRepository::new(db)
+            .create(&session)
+            .await
+            .unwrap();
+
+        let messages: Vec<Message> = contents
+            .iter()
+            .enumerate()
+            .map(|(i, content)| {
+                let role = if i % 2 == 0 {
+                    MessageRole::User
+                } else {
+                    MessageRole::Assistant
+                };
+                let time = start + Duration::minutes(i as i64);
+                Message::new(session.id, role, content.to_string(), time, i as u32 + 1)
+            })
+            .collect();
+        MessageRepository::new(db)
+            .bulk_create(&messages)
+            .await
+            .unwrap();
+        (session, messages)
+    }
+
+    fn contents(messages: &[Message]) -> Vec<(u32, &str)> {
+        messages
+            .iter()
+            .map(|m| (m.sequence_number, m.content.as_str()))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_split_moves_tail_into_new_session() {
+        let db = Arc::new(DatabaseManager::open_in_memory().await.unwrap());
+        let (session, messages) = insert_session(
+            &db,
+            "/a.jsonl",
+            Utc::now(),
+            &["fix the build", "done", "now write docs", "written"],
+        )
+        .await;
+        let usage = MessageUsage {
+            message_id: messages[3].id,
+            session_id: session.id,
+            output_tokens: 50,
+            ..Default::default()
+        };
+        MessageUsageRepository::new(&db)
+            .bulk_create(&[usage])
+            .await
+            .unwrap();
+        let mut attachment = Attachment::new("image", None, None);
+        attachment.message_id = messages[2].id;
+        attachment.session_id = session.id;
+        AttachmentRepository::new(&db)
+            .bulk_create(&[attachment])
+            .await
+            .unwrap();
+        let tag_repo = SessionTagRepository::new(&db);
+        tag_repo
+            .set_tags(&session.id, &["docs".to_string()])
+            .await
+            .unwrap();
+        let operation = ToolOperation::new("toolu_1".to_string(), "Write".to_string(), Utc::now());
+        ToolOperationRepository::new(&db)
+            .create(&operation)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE messages SET tool_operation_id = ? WHERE id = ?")
+            .bind(operation.id.to_string())
+            .bind(messages[3].id.to_string())
+            .execute(db.pool())
+            .await
+            .unwrap();
+        let payload_repo = RawPayloadRepository::new(&db);
+        payload_repo
+            .bulk_create(&[
+                RawPayload::new(
+                    session.id,
+                    RawPayloadKind::Message,
+                    messages[0].id.to_string(),
+                    "{}".to_string(),
+                ),
+                RawPayload::new(
+                    session.id,
+                    RawPayloadKind::Message,
+                    messages[2].id.to_string(),
+                    "{}".to_string(),
+                ),
+                RawPayload::new(
+                    session.id,
+                    RawPayloadKind::ToolCall,
+                    "toolu_1".to_string(),
+                    "{}".to_string(),
+                ),
+            ])
+            .await
+            .unwrap();
+
+        let service = SessionEditService::new(db.clone());
+        assert!(service.split(&session.id, 1).await.is_err());
+        assert!(service.split(&session.id, 5).await.is_err());
+
+        let report = service.split(&session.id, 3).await.unwrap();
+        assert_eq!((report.messages_kept, report.messages_moved), (2, 2));
+
+        let message_repo = MessageRepository::new(&db);
+        let kept = message_repo.get_by_session_id(&session.id).await.unwrap();
+        assert_eq!(contents(&kept), vec![(1, "fix the build"), (2, "done")]);
+        let moved = message_repo
+            .get_by_session_id(&report.new_session_id)
+            .await
+            .unwrap();
+        assert_eq!(
+            contents(&moved),
+            vec![(1, "now write docs"), (2, "written")]
+        );
+
+        let revisions = MessageRevisionRepository::new(&db)
+            .get_by_message(&messages[2].id)
+            .await
+            .unwrap();
+        assert_eq!(revisions.len(), 1);
+        assert_eq!(revisions[0].field, "position");
+        assert_eq!(revisions[0].before, Some(format!("{}#3", session.id)));
+        assert_eq!(
+            revisions[0].after,
+            Some(format!("{}#1", report.new_session_id))
+        );
+
+        let session_repo = ChatSessionRepository::new(&db);
+        let original = session_repo.get_by_id(&session.id).await.unwrap().unwrap();
+        assert_eq!(original.message_count, 2);
+        assert_eq!(original.end_time, Some(messages[1].timestamp));
+        let new = session_repo
+            .get_by_id(&report.new_session_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(new.message_count, 2);
+        assert_eq!(new.file_path, "/a.jsonl");
+        assert_eq!(new.start_time, messages[2].timestamp);
+        assert_eq!(new.title.as_deref(), Some("now write docs"));
+
+        let usage = MessageUsageRepository::new(&db)
+            .get_by_session(&report.new_session_id)
+            .await
+            .unwrap();
+        assert_eq!(usage.len(), 1);
+        let attachments = AttachmentRepository::new(&db)
+            .get_by_session(&report.new_session_id)
+            .await
+            .unwrap();
+        assert_eq!(attachments.len(), 1);
+        let owners = |payloads: Vec<RawPayload>| {
+            payloads.into_iter().map(|p| p.owner_id).collect::<Vec<_>>()
+        };
+        assert_eq!(
+            owners(payload_repo.get_by_session(&session.id).await.unwrap()),
+            vec![messages[0].id.to_string()]
+        );
+        let mut moved_owners = owners(
+            payload_repo
+                .get_by_session(&report.new_session_id)
+                .await
+                .unwrap(),
+        );
+        moved_owners.sort();
+        let mut expected = vec![messages[2].id.to_string(), "toolu_1".to_string()];
+        expected.sort();
+        assert_eq!(moved_owners, expected);
+        let segments = SessionSegmentRepository::new(&db)
+            .get_by_session(&report.new_session_id)
+            .await
+            .unwrap();
+        assert_eq!(
+            (segments[0].start_sequence, segments[0].end_sequence),
+            (1, 2)
+        );
+        for id in [session.id, report.new_session_id] {
+            assert_eq!(tag_repo.get_by_session(&id).await.unwrap(), vec!["docs"]);
+            let quality = SessionQualityRepository::new(&db)
+                .get_by_session(&id)
+                .await
+                .unwrap()
+                .expect("quality signals after split");
+            assert_eq!(quality.user_prompts, 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_merge_appends_later_session() {
+        let db = Arc::new(DatabaseManager::open_in_memory().await.unwrap());
+        let start = Utc::now();
+        let (earlier, _) = insert_session(&db, "/a.jsonl", start, &["start", "ok"]).await;
+        let (later, later_messages) = insert_session(
+            &db,
+            "/b.jsonl",
+            start + Duration::hours(1),
+            &["continue", "ok", "thanks"],
+        )
+        .await;
+        let payload_repo = RawPayloadRepository::new(&db);
+        payload_repo
+            .bulk_create(&[RawPayload::new(
+                later.id,
+                RawPayloadKind::Message,
+                later_messages[0].id.to_string(),
+                "{}".to_string(),
+            )])
+            .await
+            .unwrap();
+        let tag_repo = SessionTagRepository::new(&db);
+        tag_repo
+            .set_tags(&earlier.id, &["api".to_string()])
+            .await
+            .unwrap();
+        tag_repo
+            .set_tags(&later.id, &["api".to_string(), "docs".to_string()])
+            .await
+            .unwrap();
+
+        let service = SessionEditService::new(db.clone());
+        assert!(service.merge(&earlier.id, &earlier.id).await.is_err());
+
+        // Order of the arguments doesn't matter
+        let report = service.merge(&later.id, &earlier.id).await.unwrap();
+        assert_eq!(report.session_id, earlier.id);
+        assert_eq!(report.merged_session_id, later.id);
+        assert_eq!(report.messages_moved, 3);
+
+        let messages = MessageRepository::new(&db)
+            .get_by_session_id(&earlier.id)
+            .await
+            .unwrap();
+        assert_eq!(
+            contents(&messages),
+            vec![
+                (1, "start"),
+                (2, "ok"),
+                (3, "continue"),
+                (4, "ok"),
+                (5, "thanks")
+            ]
+        );
+
+        let revisions = MessageRevisionRepository::new(&db)
+            .get_by_session(&earlier.id)
+            .await
+            .unwrap();
+        assert_eq!(revisions.len(), 3);
+        assert_eq!(revisions[0].reason.as_deref(), Some("session merge"));
+
+        let session_repo = ChatSessionRepository::new(&db);
+        let merged = session_repo.get_by_id(&earlier.id).await.unwrap().unwrap();
+        assert_eq!(merged.message_count, 5);
+        assert_eq!(merged.token_count, Some(2000));
+        assert!(session_repo.get_by_id(&later.id).await.unwrap().is_none());
+        assert_eq!(
+            session_repo.get_merged_into(&later.id).await.unwrap(),
+            Some(earlier.id)
+        );
+        assert_eq!(
+            tag_repo.get_by_session(&earlier.id).await.unwrap(),
+            vec!["api", "docs"]
+        );
+        let quality = SessionQualityRepository::new(&db)
+            .get_by_session(&earlier.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(quality.user_prompts, 3);
+        let payloads = payload_repo.get_by_session(&earlier.id).await.unwrap();
+        assert_eq!(payloads.len(), 1);
+        assert_eq!(payloads[0].owner_id, later_messages[0].id.to_string());
+    }
+}
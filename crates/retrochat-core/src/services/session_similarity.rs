@@ -0,0 +1,266 @@
+use anyhow::{Context, Result as AnyhowResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::embedding::{cosine_similarity, Embedder, HashingEmbedder};
+use crate::database::{
+    ChatSessionRepository, DatabaseManager, MessageRepository, SessionSummaryRepository,
+};
+use crate::models::{GeneratedSessionSummary as SessionSummary, Message};
+
+/// Upper bound on session summaries considered for a single search
+const MAX_SUMMARIES: i64 = 5000;
+
+/// Upper bound on user prompts loaded for sessions without a summary
+const MAX_PROMPTS: i64 = 20000;
+
+/// Number of prompts used to describe a session that has no summary
+const PROMPTS_PER_SESSION: usize = 5;
+
+/// A past session ranked by similarity to a task description
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimilarSession {
+    pub session_id: String,
+    pub score: f32,
+    pub provider: String,
+    pub project: Option<String>,
+    pub start_time: DateTime<Utc>,
+    /// Title from the session summary, if one was generated
+    pub title: Option<String>,
+    pub summary: Option<String>,
+    pub outcome: Option<String>,
+    /// First user prompt, for sessions without a summary
+    pub first_prompt: Option<String>,
+}
+
+/// Service for finding past sessions similar to a free-text task description
+pub struct SessionSimilarityService {
+    db_manager: Arc<DatabaseManager>,
+    embedder: Arc<dyn Embedder>,
+}
+
+impl SessionSimilarityService {
+    pub fn new(db_manager: Arc<DatabaseManager>) -> Self {
+        Self {
+            db_manager,
+            embedder: Arc::new(HashingEmbedder::new()),
+        }
+    }
+
+    pub fn with_embedder(mut self, embedder: Arc<dyn Embedder>) -> Self {
+        self.embedder = embedder;
+        self
+    }
+
+    /// Return the `limit` sessions most similar to `query`, best match first
+    ///
+    /// Sessions are described by their summary when one exists, otherwise by
+    /// their first few user prompts.
+    pub async fn find_similar_sessions(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> AnyhowResult<Vec<SimilarSession>> {
+        if query.trim().is_empty() {
+            anyhow::bail!("Query cannot be empty");
+        }
+
+        let summary_repo = SessionSummaryRepository::new(&self.db_manager);
+        let message_repo = MessageRepository::new(&self.db_manager);
+        let session_repo = ChatSessionRepository::new(&self.db_manager);
+
+        let summaries: HashMap<String, SessionSummary> = summary_repo
+            .list_all(None, MAX_SUMMARIES, 0)
+            .await
+            .context("Failed to load session summaries")?
+            .into_iter()
+            .map(|s| (s.session_id.clone(), s))
+            .collect();
+
+        let mut prompts = message_repo
+            .get_user_prompts(None, MAX_PROMPTS)
+            .await
+            .context("Failed to load user prompts")?;
+        prompts.sort_by_key(|m| m.timestamp);
+        let prompts_by_session = group_prompts_by_session(prompts);
+
+        let mut documents: Vec<(String, String)> = summaries
+            .values()
+            .map(|s| (s.session_id.clone(), summary_document(s)))
+            .collect();
+        for (session_id, session_prompts) in &prompts_by_session {
+            let key = session_id.to_string();
+            if !summaries.contains_key(&key) {
+                documents.push((key, prompt_document(session_prompts)));
+            }
+        }
+        if documents.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let query_vector = self.embedder.embed(query).await?;
+        let texts: Vec<String> = documents.iter().map(|(_, text)| text.clone()).collect();
+        let vectors = self.embedder.embed_batch(&texts).await?;
+
+        let ranked = rank_by_similarity(&query_vector, &vectors);
+
+        let mut results = Vec::new();
+        for (index, score) in ranked {
+            if results.len() >= limit {
+                break;
+            }
+
+            let session_id = &documents[index].0;
+            let Ok(uuid) = Uuid::parse_str(session_id) else {
+                continue;
+            };
+            let Some(session) = session_repo.get_by_id(&uuid).await? else {
+                continue;
+            };
+
+            let summary = summaries.get(session_id);
+            results.push(SimilarSession {
+                session_id: session_id.clone(),
+                score,
+                provider: session.provider.to_string(),
+                project: session.project_name,
+                start_time: session.start_time,
+                title: summary.map(|s| s.title.clone()),
+                summary: summary.map(|s| s.summary.clone()),
+                outcome: summary.and_then(|s| s.outcome.as_ref().map(|o| o.to_string())),
+                first_prompt: prompts_by_session
+                    .get(&uuid)
+                    .and_then(|p| p.first())
+                    .map(|m| m.content.clone()),
+            });
+        }
+
+        Ok(results)
+    }
+}
+
+/// Indices of `vectors` with a positive similarity to `query`, best first
+pub fn rank_by_similarity(query: &[f32], vectors: &[Vec<f32>]) -> Vec<(usize, f32)> {
+    let mut ranked: Vec<(usize, f32)> = vectors
+        .iter()
+        .enumerate()
+        .map(|(i, v)| (i, cosine_similarity(query, v)))
+        .filter(|(_, score)| *score > 0.0)
+        .collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+    ranked
+}
+
+fn group_prompts_by_session(prompts: Vec<Message>) -> HashMap<Uuid, Vec<Message>> {
+    let mut grouped: HashMap<Uuid, Vec<Message>> = HashMap::new();
+    for prompt in prompts {
+        let entry = grouped.entry(prompt.session_id).or_default();
+        if entry.len() < PROMPTS_PER_SESSION {
+            entry.push(prompt);
+        }
+    }
+    grouped
+}
+
+pub(crate) fn summary_document(summary: &SessionSummary) -> String {
+    let mut parts = vec![summary.title.clone(), summary.summary.clone()];
+    if let Some(goal) = &summary.primary_goal {
+        parts.push(goal.clone());
+    }
+    if let Some(technologies) = &summary.technologies_used {
+        parts.push(technologies.join(" "));
+    }
+    parts.join("\n")
+}
+
+fn prompt_document(prompts: &[Message]) -> String {
+    prompts
+        .iter()
+        .map(|m| m.content.as_str())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::MessageRole;
+
+    async fn insert_session(db: &DatabaseManager, index: usize, prompt: &str) -> Uuid {
+        let session_id = Uuid::new_v4();
+        sqlx::query(
+            r#"
+            INSERT INTO chat_sessions (id, provider, project_name, start_time, end_time, message_count, file_path, file_hash, state)
+            VALUES (?, 'Claude Code', NULL, '2024-01-01T00:00:00Z', '2024-01-01T01:00:00Z', 1, ?, ?, 'imported')
+            "#,
+        )
+        .bind(session_id.to_string())
+        .bind(format!("/test{index}.jsonl"))
+        .bind(format!("hash{index}"))
+        .execute(db.pool())
+        .await
+        .unwrap();
+
+        MessageRepository::new(db)
+            .create(&Message::new(
+                session_id,
+                MessageRole::User,
+                prompt.to_string(),
+                Utc::now(),
+                1,
+            ))
+            .await
+            .unwrap();
+        session_id
+    }
+
+    #[test]
+    fn test_rank_by_similarity_orders_and_drops_unrelated() {
+        let embedder = HashingEmbedder::new();
+        let query = embedder.embed_text("fix the sqlite migration error");
+        let vectors = vec![
+            embedder.embed_text("add dark mode toggle"),
+            embedder.embed_text("sqlite migration fails with error on startup"),
+        ];
+
+        let ranked = rank_by_similarity(&query, &vectors);
+
+        assert_eq!(ranked[0].0, 1);
+        assert!(ranked.iter().all(|(_, score)| *score > 0.0));
+    }
+
+    #[tokio::test]
+    async fn test_find_similar_sessions_prefers_summary_match() {
+        let db = Arc::new(DatabaseManager::open_in_memory().await.unwrap());
+        let migration_session =
+            insert_session(&db, 0, "The app crashes when opening the database").await;
+        insert_session(&db, 1, "Add a dark mode toggle to the settings page").await;
+
+        SessionSummaryRepository::new(&db)
+            .create(&SessionSummary::new(
+                migration_session.to_string(),
+                "Fix SQLite migration failure".to_string(),
+                "Repaired a broken sqlite migration that failed on startup".to_string(),
+            ))
+            .await
+            .unwrap();
+
+        let service = SessionSimilarityService::new(db.clone());
+        let results = service
+            .find_similar_sessions("sqlite migration fails on startup", 1)
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].session_id, migration_session.to_string());
+        assert_eq!(
+            results[0].title.as_deref(),
+            Some("Fix SQLite migration failure")
+        );
+
+        assert!(service.find_similar_sessions("  ", 5).await.is_err());
+    }
+}
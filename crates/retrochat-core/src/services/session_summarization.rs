@@ -6,13 +6,18 @@ use uuid::Uuid;
 use crate::database::{DatabaseManager, SessionSummaryRepository, TurnSummaryRepository};
 use crate::models::session_summary::{SessionOutcome, SessionSummary};
 use crate::models::TurnSummary;
-use crate::services::llm::{GenerateRequest, LlmClient};
+use crate::services::embedding_index::EmbeddingIndexService;
+use crate::services::llm::{CachingClient, GenerateRequest, LlmClient};
+use crate::utils::locale::Language;
 
 /// Service for generating LLM-based session summaries from turn summaries
 pub struct SessionSummarizer {
     turn_summary_repo: TurnSummaryRepository,
     session_summary_repo: SessionSummaryRepository,
     llm_client: Arc<dyn LlmClient>,
+    /// None when the configured embedder could not be created
+    index: Option<EmbeddingIndexService>,
+    language: Language,
 }
 
 impl SessionSummarizer {
@@ -20,7 +25,11 @@ impl SessionSummarizer {
         Self {
             turn_summary_repo: TurnSummaryRepository::new(db),
             session_summary_repo: SessionSummaryRepository::new(db),
-            llm_client,
+            llm_client: CachingClient::wrap(llm_client, db),
+            index: EmbeddingIndexService::from_config(db)
+                .map_err(|e| tracing::warn!(error = %e, "Summary embeddings will not be indexed"))
+                .ok(),
+            language: Language::load(),
         }
     }
 
@@ -57,16 +66,30 @@ impl SessionSummarizer {
             .await
             .context("Failed to save session summary")?;
 
+        self.index_embeddings(session_id).await;
+
         Ok(summary)
     }
 
+    /// Embed the session's new summaries; a failure only costs search quality
+    async fn index_embeddings(&self, session_id: &Uuid) {
+        let Some(index) = &self.index else {
+            return;
+        };
+        if let Err(e) = index.index_session(session_id).await {
+            tracing::warn!(session_id = %session_id, error = %e, "Failed to index summary embeddings");
+        }
+    }
+
     /// Generate a session summary from turn summaries
     async fn generate_from_turns(
         &self,
         session_id: &Uuid,
         turns: &[TurnSummary],
     ) -> AnyhowResult<SessionSummary> {
-        let prompt = self.build_session_prompt(turns);
+        let prompt = self
+            .language
+            .localize_prompt(self.build_session_prompt(turns));
 
         let request = GenerateRequest::new(prompt)
             .with_max_tokens(1024)
@@ -0,0 +1,328 @@
+use anyhow::{Context, Result as AnyhowResult};
+use futures::future::join_all;
+use regex::Regex;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::database::{
+    ChatSessionRepository, DatabaseManager, MessageRepository, SessionSummaryRepository,
+};
+use crate::models::message::MessageType;
+use crate::models::{Message, TitleSource};
+use crate::services::llm::{GenerateRequest, LlmClient};
+use crate::utils::locale::Language;
+use crate::utils::session_title::truncate_title;
+
+/// Maximum characters of an LLM-generated title
+const MAX_LLM_TITLE_CHARS: usize = 60;
+
+/// Number of user prompts shown to the LLM
+const PROMPTS_IN_CONTEXT: usize = 5;
+
+/// Characters kept from each prompt shown to the LLM
+const PROMPT_CHARS: usize = 500;
+
+/// Options for a title backfill run
+#[derive(Debug, Clone)]
+pub struct TitleBackfillOptions {
+    /// Only title sessions that have no title at all (skip heuristic titles)
+    pub missing_only: bool,
+    /// Number of sessions sent to the LLM concurrently
+    pub batch_size: usize,
+    /// Pause between batches, to stay under provider rate limits
+    pub delay: Duration,
+    /// Stop after this many sessions
+    pub limit: Option<usize>,
+}
+
+impl Default for TitleBackfillOptions {
+    fn default() -> Self {
+        Self {
+            missing_only: false,
+            batch_size: 5,
+            delay: Duration::from_secs(1),
+            limit: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TitleBackfillReport {
+    pub candidates: usize,
+    pub titled: usize,
+    pub failed: usize,
+}
+
+/// Service for generating short session titles with an LLM
+///
+/// Titles are saved as soon as each one is generated and sessions that already
+/// have an LLM title are never candidates, so an interrupted backfill resumes
+/// where it left off when run again.
+pub struct SessionTitler {
+    session_repo: ChatSessionRepository,
+    message_repo: MessageRepository,
+    session_summary_repo: SessionSummaryRepository,
+    llm_client: Arc<dyn LlmClient>,
+    language: Language,
+}
+
+impl SessionTitler {
+    pub fn new(db: &DatabaseManager, llm_client: Arc<dyn LlmClient>) -> Self {
+        Self {
+            session_repo: ChatSessionRepository::new(db),
+            message_repo: MessageRepository::new(db),
+            session_summary_repo: SessionSummaryRepository::new(db),
+            llm_client,
+            language: Language::load(),
+        }
+    }
+
+    /// Sessions a backfill would title, newest first
+    pub async fn pending_sessions(&self, missing_only: bool) -> AnyhowResult<Vec<Uuid>> {
+        self.session_repo.get_ids_needing_title(missing_only).await
+    }
+
+    /// Generate and store an LLM title for one session
+    pub async fn title_session(&self, session_id: &Uuid) -> AnyhowResult<String> {
+        let messages = self
+            .message_repo
+            .get_by_session(session_id)
+            .await
+            .context("Failed to fetch session messages")?;
+        let summary = self
+            .session_summary_repo
+            .get_by_session(session_id)
+            .await
+            .context("Failed to fetch session summary")?
+            .map(|s| s.summary);
+
+        let prompt = Self::build_title_prompt(&messages, summary.as_deref())
+            .map(|prompt| self.language.localize_prompt(prompt))
+            .context("Session has no user prompts to title")?;
+
+        let request = GenerateRequest::new(prompt)
+            .with_max_tokens(64)
+            .with_temperature(0.2);
+        let response = self.llm_client.generate(request).await?;
+
+        let title = Self::parse_title_response(&response.text)
+            .context("LLM response did not contain a title")?;
+
+        self.session_repo
+            .update_title(session_id, &title, TitleSource::Llm)
+            .await?;
+
+        Ok(title)
+    }
+
+    /// Title every pending session in throttled batches
+    ///
+    /// `on_result` is called once per session as soon as its title is saved
+    /// (or has failed).
+    pub async fn backfill<F>(
+        &self,
+        options: &TitleBackfillOptions,
+        mut on_result: F,
+    ) -> AnyhowResult<TitleBackfillReport>
+    where
+        F: FnMut(&Uuid, &AnyhowResult<String>),
+    {
+        let mut pending = self.pending_sessions(options.missing_only).await?;
+        if let Some(limit) = options.limit {
+            pending.truncate(limit);
+        }
+
+        let mut report = TitleBackfillReport {
+            candidates: pending.len(),
+            ..Default::default()
+        };
+
+        for (index, batch) in pending.chunks(options.batch_size.max(1)).enumerate() {
+            if index > 0 && !options.delay.is_zero() {
+                tokio::time::sleep(options.delay).await;
+            }
+
+            let results = join_all(batch.iter().map(|id| self.title_session(id))).await;
+            for (id, result) in batch.iter().zip(results) {
+                match &result {
+                    Ok(_) => report.titled += 1,
+                    Err(_) => report.failed += 1,
+                }
+                on_result(id, &result);
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Build the title prompt from the first user prompts and any summary
+    fn build_title_prompt(messages: &[Message], summary: Option<&str>) -> Option<String> {
+        let prompts: Vec<String> = messages
+            .iter()
+            .filter(|m| m.is_user_message() && m.message_type == MessageType::SimpleMessage)
+            .map(|m| m.content.trim())
+            .filter(|content| !content.is_empty())
+            .take(PROMPTS_IN_CONTEXT)
+            .map(|content| truncate_title(content, PROMPT_CHARS))
+            .collect();
+
+        if prompts.is_empty() {
+            return None;
+        }
+
+        let mut context = String::new();
+        for (i, prompt) in prompts.iter().enumerate() {
+            context.push_str(&format!("Prompt {}: {prompt}\n\n", i + 1));
+        }
+        if let Some(summary) = summary {
+            context.push_str(&format!("Session summary: {summary}\n"));
+        }
+
+        Some(format!(
+            r#"Write a short, descriptive title for the coding session below, based on what the user asked for.
+
+## Session
+
+{context}
+
+## Required Output Format
+
+TITLE: [At most 8 words and {MAX_LLM_TITLE_CHARS} characters, no quotes or trailing punctuation, e.g., "Fix flaky login integration test"]"#,
+            context = context.trim()
+        ))
+    }
+
+    /// Extract the title from an LLM response
+    fn parse_title_response(response: &str) -> Option<String> {
+        let re = Regex::new(r"(?i)TITLE:\s*(.+)").ok()?;
+        let raw = match re.captures(response).and_then(|caps| caps.get(1)) {
+            Some(m) => m.as_str(),
+            None => response.lines().find(|line| !line.trim().is_empty())?,
+        };
+
+        let title = raw
+            .trim()
+            .trim_matches(|c| c == '"' || c == '\'' || c == '`' || c == '*')
+            .trim_end_matches('.')
+            .trim();
+        if title.is_empty() {
+            return None;
+        }
+
+        Some(truncate_title(title, MAX_LLM_TITLE_CHARS))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::MessageRole;
+    use crate::services::llm::{GenerateResponse, LlmError};
+    use async_trait::async_trait;
+    use chrono::Utc;
+
+    struct FixedLlm(&'static str);
+
+    #[async_trait]
+    impl LlmClient for FixedLlm {
+        async fn generate(&self, _request: GenerateRequest) -> Result<GenerateResponse, LlmError> {
+            Ok(GenerateResponse {
+                text: self.0.to_string(),
+                ..Default::default()
+            })
+        }
+
+        fn provider_name(&self) -> &'static str {
+            "fixed"
+        }
+
+        fn model_name(&self) -> &str {
+            "fixed"
+        }
+
+        async fn health_check(&self) -> Result<(), LlmError> {
+            Ok(())
+        }
+    }
+
+    async fn insert_session(db: &DatabaseManager, index: usize, prompt: Option<&str>) -> Uuid {
+        let session_id = Uuid::new_v4();
+        sqlx::query(
+            r#"
+            INSERT INTO chat_sessions (id, provider, project_name, start_time, end_time, message_count, file_path, file_hash, state)
+            VALUES (?, 'Claude Code', NULL, ?, '2024-01-02T00:00:00Z', 1, ?, ?, 'imported')
+            "#,
+        )
+        .bind(session_id.to_string())
+        .bind(format!("2024-01-01T0{index}:00:00Z"))
+        .bind(format!("/test{index}.jsonl"))
+        .bind(format!("hash{index}"))
+        .execute(db.pool())
+        .await
+        .unwrap();
+
+        if let Some(prompt) = prompt {
+            MessageRepository::new(db)
+                .create(&Message::new(
+                    session_id,
+                    MessageRole::User,
+                    prompt.to_string(),
+                    Utc::now(),
+                    1,
+                ))
+                .await
+                .unwrap();
+        }
+        session_id
+    }
+
+    #[test]
+    fn test_parse_title_response() {
+        assert_eq!(
+            SessionTitler::parse_title_response("TITLE: \"Fix flaky login test.\"").as_deref(),
+            Some("Fix flaky login test")
+        );
+        assert_eq!(
+            SessionTitler::parse_title_response("\nAdd dark mode\n").as_deref(),
+            Some("Add dark mode")
+        );
+        assert_eq!(SessionTitler::parse_title_response("TITLE: \"\""), None);
+    }
+
+    #[tokio::test]
+    async fn test_backfill_titles_sessions_and_resumes() {
+        let db = DatabaseManager::open_in_memory().await.unwrap();
+        let titled = insert_session(&db, 0, Some("The login test fails on CI")).await;
+        let empty = insert_session(&db, 1, None).await;
+
+        let titler = SessionTitler::new(&db, Arc::new(FixedLlm("TITLE: Fix flaky login test")));
+        let options = TitleBackfillOptions {
+            delay: Duration::ZERO,
+            ..Default::default()
+        };
+
+        let mut seen = Vec::new();
+        let report = titler
+            .backfill(&options, |id, result| seen.push((*id, result.is_ok())))
+            .await
+            .unwrap();
+
+        assert_eq!(report.candidates, 2);
+        assert_eq!(report.titled, 1);
+        assert_eq!(report.failed, 1);
+        assert!(seen.contains(&(titled, true)));
+        assert!(seen.contains(&(empty, false)));
+
+        let session = ChatSessionRepository::new(&db)
+            .get_by_id(&titled)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(session.title.as_deref(), Some("Fix flaky login test"));
+        assert_eq!(session.title_source, Some(TitleSource::Llm));
+
+        // Only the session that failed is left for the next run
+        assert_eq!(titler.pending_sessions(false).await.unwrap(), vec![empty]);
+    }
+}
@@ -0,0 +1,538 @@
+//! Standalone HTML transcripts for sharing a session
+//!
+//! [`render_share_html`] writes one self-contained page (inline CSS, no
+//! scripts) with the conversation, tool calls folded under `<details>`.
+//! Text is passed through an [`Anonymizer`] first. [`upload_gist`] posts
+//! the page as a secret GitHub gist. [`render_markdown_transcript`] gives
+//! the same conversation as markdown.
+
+use anyhow::{Context, Result};
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::models::message::MessageType;
+use crate::models::{ChatSession, Message, MessageRole, ToolOperation};
+use crate::utils::anonymize::Anonymizer;
+use crate::utils::locale::Language;
+
+const GITHUB_GISTS_URL: &str = "https://api.github.com/gists";
+
+const REQUEST_TIMEOUT_SECS: u64 = 60;
+
+/// Tool input and output beyond this many characters is cut in the middle
+const TOOL_TEXT_MAX_CHARS: usize = 4000;
+
+const STYLE: &str = "
+body { font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', sans-serif; max-width: 860px; margin: 2rem auto; padding: 0 1rem; color: #1f2328; background: #fff; }
+header { border-bottom: 1px solid #d0d7de; margin-bottom: 1.5rem; }
+h1 { font-size: 1.4rem; margin-bottom: .25rem; }
+.meta { color: #59636e; font-size: .85rem; margin-bottom: 1rem; }
+.message { border-radius: 8px; padding: .6rem .9rem; margin: .75rem 0; }
+.user { background: #ddf4ff; }
+.assistant { background: #f6f8fa; }
+.system { background: #fff8c5; }
+.role { font-size: .75rem; font-weight: 600; text-transform: uppercase; color: #59636e; }
+pre { white-space: pre-wrap; word-wrap: break-word; font-family: ui-monospace, SFMono-Regular, Menlo, monospace; font-size: .82rem; margin: .4rem 0 0; }
+.text { font-family: inherit; font-size: .95rem; }
+details { margin: .4rem 0; }
+summary { cursor: pointer; font-size: .85rem; color: #59636e; }
+.failed summary { color: #cf222e; }
+footer { color: #59636e; font-size: .75rem; border-top: 1px solid #d0d7de; margin-top: 2rem; padding-top: .5rem; }
+@media (prefers-color-scheme: dark) {
+  body { color: #e6edf3; background: #0d1117; }
+  .user { background: #0c2d48; } .assistant { background: #161b22; } .system { background: #3b2e00; }
+  .role, .meta, summary, footer { color: #9198a1; }
+}
+";
+
+/// What goes into a shared transcript
+#[derive(Debug, Clone)]
+pub struct ShareOptions {
+    /// `None` shares the text as stored
+    pub anonymizer: Option<Anonymizer>,
+    pub include_thinking: bool,
+}
+
+impl Default for ShareOptions {
+    fn default() -> Self {
+        Self {
+            anonymizer: Some(Anonymizer::for_current_user()),
+            include_thinking: false,
+        }
+    }
+}
+
+/// Render a session as a standalone HTML page
+pub fn render_share_html(
+    session: &ChatSession,
+    messages: &[Message],
+    tool_operations: &[ToolOperation],
+    options: &ShareOptions,
+) -> String {
+    let clean = |text: &str| -> String {
+        let text = match &options.anonymizer {
+            Some(anonymizer) => anonymizer.anonymize(text),
+            None => text.to_string(),
+        };
+        escape_html(&text)
+    };
+    let operations: HashMap<Uuid, &ToolOperation> =
+        tool_operations.iter().map(|op| (op.id, op)).collect();
+
+    let title = clean(session.title.as_deref().unwrap_or("Untitled session"));
+    let mut meta = vec![
+        session.provider.to_string(),
+        format!(
+            "{} UTC",
+            Language::current().format_datetime(&session.start_time)
+        ),
+        format!("{} messages", session.message_count),
+    ];
+    if let Some(project) = &session.project_name {
+        meta.insert(1, clean(project));
+    }
+    if let Some(tokens) = session.token_count {
+        meta.push(format!("{tokens} tokens"));
+    }
+
+    let mut body = String::new();
+    for message in messages {
+        let operation = message
+            .tool_operation_id
+            .and_then(|id| operations.get(&id).copied());
+        let role = match message.role {
+            MessageRole::User => "user",
+            MessageRole::Assistant => "assistant",
+            MessageRole::System => "system",
+        };
+        let content = match (&message.message_type, operation) {
+            (MessageType::Thinking, _) if !options.include_thinking => continue,
+            (MessageType::Thinking, _) => fold("Thinking", &clean(&message.content), false),
+            (MessageType::ToolRequest, Some(op)) => {
+                let input = tool_input_text(op, message);
+                fold(
+                    &format!("Tool call: {}", escape_html(&op.tool_name)),
+                    &clean(&truncate_middle(&input, TOOL_TEXT_MAX_CHARS)),
+                    false,
+                )
+            }
+            (MessageType::ToolResult, Some(op)) => {
+                let output = tool_output_text(op, message);
+                let failed = op.success == Some(false);
+                let label = if failed { "failed" } else { "result" };
+                fold(
+                    &format!("{} {label}", escape_html(&op.tool_name)),
+                    &clean(&truncate_middle(&output, TOOL_TEXT_MAX_CHARS)),
+                    failed,
+                )
+            }
+            (MessageType::HookEvent, _) => fold("Hook", &clean(&message.content), false),
+            (MessageType::SystemReminder, _) => {
+                fold("System reminder", &clean(&message.content), false)
+            }
+            (MessageType::ToolRequest | MessageType::ToolResult, None) => fold(
+                "Tool",
+                &clean(&truncate_middle(&message.content, TOOL_TEXT_MAX_CHARS)),
+                false,
+            ),
+            _ => format!("<pre class=\"text\">{}</pre>", clean(&message.content)),
+        };
+        body.push_str(&format!(
+            "<div class=\"message {role}\"><div class=\"role\">{role}</div>{content}</div>\n"
+        ));
+    }
+
+    format!(
+        "<!DOCTYPE html>
+<html lang=\"en\">
+<head>
+<meta charset=\"utf-8\">
+<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">
+<meta name=\"robots\" content=\"noindex\">
+<title>{title}</title>
+<style>{STYLE}</style>
+</head>
+<body>
+<header>
+<h1>{title}</h1>
+<div class=\"meta\">{meta}</div>
+</header>
+<main>
+{body}</main>
+<footer>Shared from RetroChat</footer>
+</body>
+</html>
+",
+        meta = meta.join(" · "),
+    )
+}
+
+/// Render a session as a markdown transcript, e.g. for the clipboard
+///
+/// Text is kept as stored. Thinking is left out and tool calls become fenced
+/// blocks under the assistant's turn.
+pub fn render_markdown_transcript(
+    session: &ChatSession,
+    messages: &[Message],
+    tool_operations: &[ToolOperation],
+) -> String {
+    let operations: HashMap<Uuid, &ToolOperation> =
+        tool_operations.iter().map(|op| (op.id, op)).collect();
+
+    let mut meta = vec![
+        session.provider.to_string(),
+        format!(
+            "{} UTC",
+            Language::current().format_datetime(&session.start_time)
+        ),
+        format!("{} messages", session.message_count),
+    ];
+    if let Some(project) = &session.project_name {
+        meta.insert(1, project.clone());
+    }
+    let mut out = format!(
+        "# {}
+
+{}
+",
+        session.title.as_deref().unwrap_or("Untitled session"),
+        meta.join(" · ")
+    );
+
+    let mut last_role = None;
+    for message in messages {
+        if message.message_type == MessageType::Thinking {
+            continue;
+        }
+        // Tool results are stored as user messages but belong to the call
+        let is_result = message.message_type == MessageType::ToolResult;
+        if last_role != Some(&message.role) && !(is_result && last_role.is_some()) {
+            out.push_str(&format!("\n## {}\n", message.role));
+            last_role = Some(&message.role);
+        }
+
+        let operation = message
+            .tool_operation_id
+            .and_then(|id| operations.get(&id).copied());
+        let block = match (&message.message_type, operation) {
+            (MessageType::ToolRequest, Some(op)) => fenced(
+                &format!("Tool call: {}", op.tool_name),
+                "json",
+                &truncate_middle(&tool_input_text(op, message), TOOL_TEXT_MAX_CHARS),
+            ),
+            (MessageType::ToolResult, Some(op)) => {
+                let label = if op.success == Some(false) {
+                    "failed"
+                } else {
+                    "result"
+                };
+                fenced(
+                    &format!("{} {label}", op.tool_name),
+                    "",
+                    &truncate_middle(&tool_output_text(op, message), TOOL_TEXT_MAX_CHARS),
+                )
+            }
+            (MessageType::ToolRequest | MessageType::ToolResult, None) => fenced(
+                "Tool",
+                "",
+                &truncate_middle(&message.content, TOOL_TEXT_MAX_CHARS),
+            ),
+            _ => message.content.trim().to_string(),
+        };
+        out.push('\n');
+        out.push_str(&block);
+        out.push('\n');
+    }
+
+    out
+}
+
+/// A labelled code block, fenced with more backticks than the text contains
+fn fenced(label: &str, language: &str, text: &str) -> String {
+    let mut longest = 0;
+    let mut run = 0;
+    for c in text.chars() {
+        run = if c == '`' { run + 1 } else { 0 };
+        longest = longest.max(run);
+    }
+    let fence = "`".repeat(longest.max(2) + 1);
+    format!(
+        "**{label}**\n\n{fence}{language}\n{}\n{fence}",
+        text.trim_end()
+    )
+}
+
+fn tool_input_text(op: &ToolOperation, message: &Message) -> String {
+    op.raw_input
+        .as_ref()
+        .and_then(|input| serde_json::to_string_pretty(input).ok())
+        .unwrap_or_else(|| message.content.clone())
+}
+
+fn tool_output_text(op: &ToolOperation, message: &Message) -> String {
+    op.raw_result
+        .as_ref()
+        .map(|result| match result.as_str() {
+            Some(text) => text.to_string(),
+            None => serde_json::to_string_pretty(result).unwrap_or_default(),
+        })
+        .or_else(|| op.result_summary.clone())
+        .unwrap_or_else(|| message.content.clone())
+}
+
+fn fold(summary: &str, content: &str, failed: bool) -> String {
+    let class = if failed { " class=\"failed\"" } else { "" };
+    format!("<details{class}><summary>{summary}</summary><pre>{content}</pre></details>")
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Keep the start and end of long text
+fn truncate_middle(text: &str, max_chars: usize) -> String {
+    let count = text.chars().count();
+    if count <= max_chars {
+        return text.to_string();
+    }
+    let half = max_chars / 2;
+    let head: String = text.chars().take(half).collect();
+    let tail: String = text.chars().skip(count - half).collect();
+    format!(
+        "{head}\n… {} characters omitted …\n{tail}",
+        count - 2 * half
+    )
+}
+
+/// A gist created by [`upload_gist`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GistUpload {
+    /// The gist's page on GitHub
+    pub html_url: String,
+    /// The uploaded file itself
+    pub raw_url: String,
+}
+
+#[derive(Deserialize)]
+struct GistResponse {
+    html_url: String,
+    files: HashMap<String, GistFile>,
+}
+
+#[derive(Deserialize)]
+struct GistFile {
+    raw_url: String,
+}
+
+/// Upload `content` as a secret gist with one file
+///
+/// Secret gists are unlisted, not private: anyone with the URL can read them.
+pub async fn upload_gist(
+    token: &str,
+    file_name: &str,
+    description: &str,
+    content: &str,
+) -> Result<GistUpload> {
+    let client = Client::builder()
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .user_agent(concat!("retrochat/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .context("Failed to create HTTP client")?;
+    let response = client
+        .post(GITHUB_GISTS_URL)
+        .bearer_auth(token)
+        .header("Accept", "application/vnd.github+json")
+        .json(&json!({
+            "description": description,
+            "public": false,
+            "files": { file_name: { "content": content } },
+        }))
+        .send()
+        .await
+        .context("Failed to reach GitHub")?;
+
+    let status = response.status();
+    if status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN {
+        anyhow::bail!("GitHub rejected the token ({status}); it needs the `gist` scope");
+    }
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        anyhow::bail!("GitHub returned {status}: {body}");
+    }
+
+    let gist: GistResponse = response
+        .json()
+        .await
+        .context("Unexpected response from GitHub")?;
+    let raw_url = gist
+        .files
+        .get(file_name)
+        .or_else(|| gist.files.values().next())
+        .map(|file| file.raw_url.clone())
+        .context("GitHub returned a gist without files")?;
+    Ok(GistUpload {
+        html_url: gist.html_url,
+        raw_url,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Provider;
+    use chrono::Utc;
+
+    #[test]
+    fn test_render_share_html() {
+        let mut session = ChatSession::new(
+            Provider::ClaudeCode,
+            "/home/jdoe/.claude/projects/app/1.jsonl".to_string(),
+            "hash".to_string(),
+            Utc::now(),
+        );
+        session.title = Some("Fix <login> bug".to_string());
+        session.project_name = Some("app".to_string());
+
+        let mut operation =
+            ToolOperation::new("toolu_1".to_string(), "Bash".to_string(), Utc::now());
+        operation.raw_input = Some(json!({ "command": "cat /home/jdoe/app/.env" }));
+        operation.raw_result = Some(json!("API_KEY=abcd1234efgh"));
+        operation.success = Some(false);
+
+        let messages = vec![
+            Message::new(
+                session.id,
+                MessageRole::User,
+                "Why does jdoe's login fail?".to_string(),
+                Utc::now(),
+                1,
+            ),
+            Message::new(
+                session.id,
+                MessageRole::Assistant,
+                "pondering".to_string(),
+                Utc::now(),
+                2,
+            )
+            .with_message_type(MessageType::Thinking),
+            Message::new(
+                session.id,
+                MessageRole::Assistant,
+                "[Tool Use: Bash]".to_string(),
+                Utc::now(),
+                3,
+            )
+            .with_message_type(MessageType::ToolRequest)
+            .with_tool_operation(operation.id),
+            Message::new(
+                session.id,
+                MessageRole::User,
+                "[Tool Result]".to_string(),
+                Utc::now(),
+                4,
+            )
+            .with_message_type(MessageType::ToolResult)
+            .with_tool_operation(operation.id),
+        ];
+
+        let options = ShareOptions {
+            anonymizer: Some(Anonymizer::new(
+                Some("/home/jdoe".to_string()),
+                Some("jdoe".to_string()),
+            )),
+            include_thinking: false,
+        };
+        let html = render_share_html(&session, &messages, &[operation], &options);
+
+        assert!(html.contains("<title>Fix &lt;login&gt; bug</title>"));
+        assert!(html.contains("Why does user&#39;s login fail?"));
+        assert!(html.contains("cat ~/app/.env"));
+        assert!(html.contains("API_KEY=&lt;redacted&gt;"));
+        assert!(html.contains("<details class=\"failed\"><summary>Bash failed</summary>"));
+        assert!(!html.contains("jdoe"));
+        assert!(!html.contains("pondering"));
+        assert!(!html.contains("<script"));
+    }
+
+    #[test]
+    fn test_render_markdown_transcript() {
+        let mut session = ChatSession::new(
+            Provider::ClaudeCode,
+            "/tmp/1.jsonl".to_string(),
+            "hash".to_string(),
+            Utc::now(),
+        );
+        session.title = Some("Fix the build".to_string());
+
+        let mut operation =
+            ToolOperation::new("toolu_1".to_string(), "Bash".to_string(), Utc::now());
+        operation.raw_input = Some(json!({ "command": "cargo build" }));
+        operation.raw_result = Some(json!("error: ```oops```"));
+        operation.success = Some(false);
+
+        let messages = vec![
+            Message::new(session.id, MessageRole::User, "Why?".into(), Utc::now(), 1),
+            Message::new(
+                session.id,
+                MessageRole::Assistant,
+                "hmm".into(),
+                Utc::now(),
+                2,
+            )
+            .with_message_type(MessageType::Thinking),
+            Message::new(
+                session.id,
+                MessageRole::Assistant,
+                "[Tool Use: Bash]".into(),
+                Utc::now(),
+                3,
+            )
+            .with_message_type(MessageType::ToolRequest)
+            .with_tool_operation(operation.id),
+            Message::new(
+                session.id,
+                MessageRole::User,
+                "[Tool Result]".into(),
+                Utc::now(),
+                4,
+            )
+            .with_message_type(MessageType::ToolResult)
+            .with_tool_operation(operation.id),
+        ];
+
+        let markdown = render_markdown_transcript(&session, &messages, &[operation]);
+
+        assert!(markdown.starts_with("# Fix the build\n"));
+        assert!(markdown.contains("\n## User\n\nWhy?\n"));
+        assert!(markdown
+            .contains("**Tool call: Bash**\n\n```json\n{\n  \"command\": \"cargo build\"\n}\n```"));
+        assert!(markdown.contains("**Bash failed**\n\n````\nerror: ```oops```\n````"));
+        assert_eq!(markdown.matches("## User").count(), 1);
+        assert!(!markdown.contains("hmm"));
+    }
+
+    #[test]
+    fn test_truncate_middle() {
+        assert_eq!(truncate_middle("short", 10), "short");
+        let text = "a".repeat(10) + &"b".repeat(10);
+        assert_eq!(
+            truncate_middle(&text, 10),
+            "aaaaa\n… 10 characters omitted …\nbbbbb"
+        );
+    }
+}
@@ -0,0 +1,268 @@
+//! Export a subset of sessions to a standalone SQLite database
+//!
+//! The snapshot is created with the full RetroChat schema, so it can be opened
+//! by another RetroChat instance (e.g. via `RETROCHAT_DB`) or queried with
+//! standard SQL tools.
+
+use anyhow::{Context, Result as AnyhowResult};
+use std::path::Path;
+use uuid::Uuid;
+
+use crate::database::DatabaseManager;
+
+/// Rows copied into a snapshot, per table
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnapshotReport {
+    pub sessions: u64,
+    pub messages: u64,
+    pub tool_operations: u64,
+    pub analytics: u64,
+    pub summaries: u64,
+}
+
+/// Copy statements in dependency order; each selects from `main` and
+/// inserts into the attached `snapshot` database.
+pub(crate) const COPY_STATEMENTS: &[(&str, &str)] = &[
+    (
+        "projects",
+        "INSERT OR IGNORE INTO snapshot.projects SELECT * FROM main.projects
+         WHERE name IN (SELECT project_name FROM main.chat_sessions
+                        WHERE id IN (SELECT id FROM temp.snapshot_sessions))",
+    ),
+    (
+        "chat_sessions",
+        "INSERT INTO snapshot.chat_sessions SELECT * FROM main.chat_sessions
+         WHERE id IN (SELECT id FROM temp.snapshot_sessions)",
+    ),
+    (
+        "tool_operations",
+        "INSERT OR IGNORE INTO snapshot.tool_operations SELECT * FROM main.tool_operations
+         WHERE id IN (SELECT tool_operation_id FROM main.messages
+                      WHERE session_id IN (SELECT id FROM temp.snapshot_sessions))",
+    ),
+    (
+        "messages",
+        "INSERT INTO snapshot.messages SELECT * FROM main.messages
+         WHERE session_id IN (SELECT id FROM temp.snapshot_sessions)",
+    ),
+    (
+        "message_usage",
+        "INSERT INTO snapshot.message_usage SELECT * FROM main.message_usage
+         WHERE session_id IN (SELECT id FROM temp.snapshot_sessions)",
+    ),
+    (
+        "attachments",
+        "INSERT INTO snapshot.attachments SELECT * FROM main.attachments
+         WHERE session_id IN (SELECT id FROM temp.snapshot_sessions)",
+    ),
+    (
+        "message_revisions",
+        "INSERT INTO snapshot.message_revisions SELECT * FROM main.message_revisions
+         WHERE message_id IN (SELECT id FROM snapshot.messages)",
+    ),
+    (
+        "session_segments",
+        "INSERT INTO snapshot.session_segments SELECT * FROM main.session_segments
+         WHERE session_id IN (SELECT id FROM temp.snapshot_sessions)",
+    ),
+    (
+        "session_tags",
+        "INSERT INTO snapshot.session_tags SELECT * FROM main.session_tags
+         WHERE session_id IN (SELECT id FROM temp.snapshot_sessions)",
+    ),
+    (
+        "session_quality",
+        "INSERT INTO snapshot.session_quality SELECT * FROM main.session_quality
+         WHERE session_id IN (SELECT id FROM temp.snapshot_sessions)",
+    ),
+    (
+        "raw_payloads",
+        "INSERT INTO snapshot.raw_payloads SELECT * FROM main.raw_payloads
+         WHERE session_id IN (SELECT id FROM temp.snapshot_sessions)",
+    ),
+    (
+        "analytics_requests",
+        "INSERT INTO snapshot.analytics_requests SELECT * FROM main.analytics_requests
+         WHERE session_id IN (SELECT id FROM temp.snapshot_sessions)",
+    ),
+    (
+        "analytics",
+        "INSERT INTO snapshot.analytics SELECT * FROM main.analytics
+         WHERE analytics_request_id IN (SELECT id FROM snapshot.analytics_requests)",
+    ),
+    (
+        "turn_summaries",
+        "INSERT INTO snapshot.turn_summaries SELECT * FROM main.turn_summaries
+         WHERE session_id IN (SELECT id FROM temp.snapshot_sessions)",
+    ),
+    (
+        "session_summaries",
+        "INSERT INTO snapshot.session_summaries SELECT * FROM main.session_summaries
+         WHERE session_id IN (SELECT id FROM temp.snapshot_sessions)",
+    ),
+];
+
+/// Write the given sessions and everything that belongs to them to a new
+/// SQLite database at `path`
+///
+/// Refuses to overwrite an existing file.
+pub async fn export_sqlite_snapshot(
+    source: &DatabaseManager,
+    session_ids: &[Uuid],
+    path: &Path,
+) -> AnyhowResult<SnapshotReport> {
+    if path.exists() {
+        anyhow::bail!("Snapshot file already exists: {}", path.display());
+    }
+
+    // Create the snapshot with the full, migrated schema
+    DatabaseManager::new(path)
+        .await
+        .with_context(|| format!("Failed to create snapshot database: {}", path.display()))?
+        .close()
+        .await?;
+
+    let mut conn = source.pool().acquire().await?;
+    // An empty key keeps the snapshot plaintext when the database is encrypted
+    sqlx::query("ATTACH DATABASE ? AS snapshot KEY ''")
+        .bind(path.to_string_lossy().to_string())
+        .execute(&mut *conn)
+        .await
+        .context("Failed to attach snapshot database")?;
+
+    let result = copy_sessions(&mut conn, session_ids).await;
+
+    sqlx::query("DETACH DATABASE snapshot")
+        .execute(&mut *conn)
+        .await
+        .context("Failed to detach snapshot database")?;
+
+    result
+}
+
+async fn copy_sessions(
+    conn: &mut sqlx::SqliteConnection,
+    session_ids: &[Uuid],
+) -> AnyhowResult<SnapshotReport> {
+    sqlx::query("CREATE TEMP TABLE IF NOT EXISTS snapshot_sessions (id TEXT PRIMARY KEY)")
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query("DELETE FROM temp.snapshot_sessions")
+        .execute(&mut *conn)
+        .await?;
+    for id in session_ids {
+        sqlx::query("INSERT OR IGNORE INTO temp.snapshot_sessions (id) VALUES (?)")
+            .bind(id.to_string())
+            .execute(&mut *conn)
+            .await?;
+    }
+
+    let mut report = SnapshotReport::default();
+    sqlx::query("BEGIN").execute(&mut *conn).await?;
+    for (table, statement) in COPY_STATEMENTS {
+        let copied = match sqlx::query(statement).execute(&mut *conn).await {
+            Ok(result) => result.rows_affected(),
+            Err(e) => {
+                sqlx::query("ROLLBACK").execute(&mut *conn).await.ok();
+                return Err(e).with_context(|| format!("Failed to copy {table} into snapshot"));
+            }
+        };
+        match *table {
+            "chat_sessions" => report.sessions = copied,
+            "messages" => report.messages = copied,
+            "tool_operations" => report.tool_operations = copied,
+            "analytics" => report.analytics = copied,
+            "turn_summaries" | "session_summaries" => report.summaries += copied,
+            _ => {}
+        }
+    }
+    sqlx::query("COMMIT").execute(&mut *conn).await?;
+
+    sqlx::query("DROP TABLE temp.snapshot_sessions")
+        .execute(&mut *conn)
+        .await?;
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{ChatSessionRepository, MessageRepository};
+    use crate::models::{Message, MessageRole};
+    use chrono::Utc;
+    use tempfile::TempDir;
+
+    async fn insert_session(db: &DatabaseManager, index: usize) -> Uuid {
+        let session_id = Uuid::new_v4();
+        sqlx::query("INSERT OR IGNORE INTO projects (id, name) VALUES (?, 'retrochat')")
+            .bind(Uuid::new_v4().to_string())
+            .execute(db.pool())
+            .await
+            .unwrap();
+        sqlx::query(
+            r#"
+            INSERT INTO chat_sessions (id, provider, project_name, start_time, end_time, message_count, file_path, file_hash, state)
+            VALUES (?, 'Claude Code', 'retrochat', '2024-01-01T00:00:00Z', '2024-01-01T01:00:00Z', 1, ?, ?, 'imported')
+            "#,
+        )
+        .bind(session_id.to_string())
+        .bind(format!("/test{index}.jsonl"))
+        .bind(format!("hash{index}"))
+        .execute(db.pool())
+        .await
+        .unwrap();
+
+        MessageRepository::new(db)
+            .create(&Message::new(
+                session_id,
+                MessageRole::User,
+                format!("prompt {index}"),
+                Utc::now(),
+                1,
+            ))
+            .await
+            .unwrap();
+        session_id
+    }
+
+    #[tokio::test]
+    async fn test_export_sqlite_snapshot_copies_selected_sessions() {
+        // ATTACH inherits the open flags of the main database, so the source
+        // has to be file-backed for the snapshot to land on disk
+        let temp_dir = TempDir::new().unwrap();
+        let db = DatabaseManager::new(temp_dir.path().join("source.db"))
+            .await
+            .unwrap();
+        let selected = insert_session(&db, 0).await;
+        insert_session(&db, 1).await;
+
+        let path = temp_dir.path().join("snapshot.db");
+
+        let report = export_sqlite_snapshot(&db, &[selected], &path)
+            .await
+            .unwrap();
+        assert_eq!(report.sessions, 1);
+        assert_eq!(report.messages, 1);
+
+        let snapshot = DatabaseManager::new(&path).await.unwrap();
+        let sessions = ChatSessionRepository::new(&snapshot)
+            .get_all()
+            .await
+            .unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].id, selected);
+        assert_eq!(sessions[0].project_name.as_deref(), Some("retrochat"));
+
+        // Full-text search works in the snapshot
+        let hits = MessageRepository::new(&snapshot)
+            .search_content("prompt", Some(10))
+            .await
+            .unwrap();
+        assert_eq!(hits.len(), 1);
+
+        assert!(export_sqlite_snapshot(&db, &[selected], &path)
+            .await
+            .is_err());
+    }
+}
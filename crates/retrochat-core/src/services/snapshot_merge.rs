@@ -0,0 +1,521 @@
+//! Merge a standalone snapshot database into this one
+//!
+//! Snapshots are made by [`export_sqlite_snapshot`](super::export_sqlite_snapshot)
+//! and arrive as sync bundles or teammates' exports. The caller decides per
+//! snapshot session whether to add it, replace the local copy, or keep the
+//! local copy; everything belonging to added and replaced sessions is then
+//! copied in one transaction.
+
+use anyhow::{Context, Result as AnyhowResult};
+use sqlx::Row;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+use crate::database::DatabaseManager;
+
+/// What a merge did with one snapshot session
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MergeAction {
+    /// The session was new
+    Add,
+    /// The local copy was replaced by the snapshot's
+    Replace,
+    /// The local copy was kept
+    Keep,
+}
+
+/// A statement with string parameters, run as part of a merge
+pub(crate) struct MergeStatement<'a> {
+    pub sql: &'a str,
+    pub binds: Vec<String>,
+}
+
+/// Statements merging the attached `incoming` database into `main`, run
+/// before replaced session rows are updated
+///
+/// `temp.merge_plan` holds each snapshot session with its action: `add`,
+/// `replace` or `keep`. Replaced sessions lose their messages and everything
+/// written with them at import, which is then copied from the snapshot by
+/// [`CHILD_STATEMENTS`]; the session row is updated in place so its analyses
+/// survive. Message revisions are set aside in `temp.merge_revisions` and
+/// restored afterwards, so changes made on this machine stay on record.
+const SESSION_STATEMENTS: &[(&str, &str)] = &[
+    (
+        "message_revisions",
+        "CREATE TEMP TABLE merge_revisions AS SELECT * FROM main.message_revisions
+         WHERE message_id IN (
+             SELECT id FROM main.messages
+             WHERE session_id IN (SELECT id FROM temp.merge_plan WHERE action = 'replace'))",
+    ),
+    (
+        "message_usage",
+        "DELETE FROM main.message_usage
+         WHERE session_id IN (SELECT id FROM temp.merge_plan WHERE action = 'replace')",
+    ),
+    (
+        "attachments",
+        "DELETE FROM main.attachments
+         WHERE session_id IN (SELECT id FROM temp.merge_plan WHERE action = 'replace')",
+    ),
+    (
+        "message_revisions",
+        "DELETE FROM main.message_revisions WHERE message_id IN (
+             SELECT id FROM main.messages
+             WHERE session_id IN (SELECT id FROM temp.merge_plan WHERE action = 'replace'))",
+    ),
+    (
+        "tool_operations",
+        "DELETE FROM main.tool_operations WHERE id IN (
+             SELECT tool_operation_id FROM main.messages
+             WHERE session_id IN (SELECT id FROM temp.merge_plan WHERE action = 'replace'))",
+    ),
+    (
+        "messages",
+        "DELETE FROM main.messages
+         WHERE session_id IN (SELECT id FROM temp.merge_plan WHERE action = 'replace')",
+    ),
+    (
+        "turn_summaries",
+        "DELETE FROM main.turn_summaries
+         WHERE session_id IN (SELECT id FROM temp.merge_plan WHERE action = 'replace')",
+    ),
+    (
+        "session_summaries",
+        "DELETE FROM main.session_summaries
+         WHERE session_id IN (SELECT id FROM temp.merge_plan WHERE action = 'replace')",
+    ),
+    (
+        "session_segments",
+        "DELETE FROM main.session_segments
+         WHERE session_id IN (SELECT id FROM temp.merge_plan WHERE action = 'replace')",
+    ),
+    (
+        "session_tags",
+        "DELETE FROM main.session_tags
+         WHERE session_id IN (SELECT id FROM temp.merge_plan WHERE action = 'replace')",
+    ),
+    (
+        "session_quality",
+        "DELETE FROM main.session_quality
+         WHERE session_id IN (SELECT id FROM temp.merge_plan WHERE action = 'replace')",
+    ),
+    (
+        "raw_payloads",
+        "DELETE FROM main.raw_payloads
+         WHERE session_id IN (SELECT id FROM temp.merge_plan WHERE action = 'replace')",
+    ),
+    (
+        "projects",
+        "INSERT OR IGNORE INTO main.projects SELECT * FROM incoming.projects",
+    ),
+    (
+        "chat_sessions",
+        "INSERT INTO main.chat_sessions SELECT * FROM incoming.chat_sessions
+         WHERE id IN (SELECT id FROM temp.merge_plan WHERE action = 'add')",
+    ),
+];
+
+/// Statements copying what belongs to added and replaced sessions, plus the
+/// analyses of every planned session
+const CHILD_STATEMENTS: &[(&str, &str)] = &[
+    (
+        "tool_operations",
+        "INSERT OR IGNORE INTO main.tool_operations SELECT * FROM incoming.tool_operations
+         WHERE id IN (SELECT tool_operation_id FROM incoming.messages
+                      WHERE session_id IN (SELECT id FROM temp.merge_plan WHERE action != 'keep'))",
+    ),
+    (
+        "messages",
+        "INSERT OR IGNORE INTO main.messages SELECT * FROM incoming.messages
+         WHERE session_id IN (SELECT id FROM temp.merge_plan WHERE action != 'keep')",
+    ),
+    (
+        "message_usage",
+        "INSERT OR IGNORE INTO main.message_usage SELECT * FROM incoming.message_usage
+         WHERE session_id IN (SELECT id FROM temp.merge_plan WHERE action != 'keep')",
+    ),
+    (
+        "attachments",
+        "INSERT OR IGNORE INTO main.attachments SELECT * FROM incoming.attachments
+         WHERE session_id IN (SELECT id FROM temp.merge_plan WHERE action != 'keep')",
+    ),
+    (
+        "message_revisions",
+        "INSERT INTO main.message_revisions (message_id, field, before_value, after_value, source, reason, created_at)
+         SELECT message_id, field, before_value, after_value, source, reason, created_at FROM temp.merge_revisions r
+         WHERE message_id IN (SELECT id FROM main.messages)",
+    ),
+    (
+        "message_revisions",
+        "INSERT INTO main.message_revisions (message_id, field, before_value, after_value, source, reason, created_at)
+         SELECT message_id, field, before_value, after_value, source, reason, created_at FROM incoming.message_revisions r
+         WHERE message_id IN (
+             SELECT id FROM incoming.messages
+             WHERE session_id IN (SELECT id FROM temp.merge_plan WHERE action != 'keep'))
+           AND NOT EXISTS (
+             SELECT 1 FROM main.message_revisions l
+             WHERE l.message_id = r.message_id AND l.field = r.field
+               AND l.created_at = r.created_at)",
+    ),
+    ("message_revisions", "DROP TABLE temp.merge_revisions"),
+    (
+        "session_segments",
+        "INSERT OR IGNORE INTO main.session_segments SELECT * FROM incoming.session_segments
+         WHERE session_id IN (SELECT id FROM temp.merge_plan WHERE action != 'keep')",
+    ),
+    (
+        "session_tags",
+        "INSERT OR IGNORE INTO main.session_tags SELECT * FROM incoming.session_tags
+         WHERE session_id IN (SELECT id FROM temp.merge_plan WHERE action != 'keep')",
+    ),
+    (
+        "session_quality",
+        "INSERT OR IGNORE INTO main.session_quality SELECT * FROM incoming.session_quality
+         WHERE session_id IN (SELECT id FROM temp.merge_plan WHERE action != 'keep')",
+    ),
+    (
+        "raw_payloads",
+        "INSERT OR IGNORE INTO main.raw_payloads SELECT * FROM incoming.raw_payloads
+         WHERE session_id IN (SELECT id FROM temp.merge_plan WHERE action != 'keep')",
+    ),
+    (
+        "turn_summaries",
+        "INSERT OR IGNORE INTO main.turn_summaries SELECT * FROM incoming.turn_summaries
+         WHERE session_id IN (SELECT id FROM temp.merge_plan WHERE action != 'keep')",
+    ),
+    (
+        "session_summaries",
+        "INSERT OR IGNORE INTO main.session_summaries SELECT * FROM incoming.session_summaries
+         WHERE session_id IN (SELECT id FROM temp.merge_plan WHERE action != 'keep')",
+    ),
+    (
+        "analytics_requests",
+        "INSERT OR IGNORE INTO main.analytics_requests SELECT * FROM incoming.analytics_requests
+         WHERE session_id IN (SELECT id FROM temp.merge_plan)",
+    ),
+    (
+        "analytics",
+        "INSERT OR IGNORE INTO main.analytics SELECT * FROM incoming.analytics
+         WHERE analytics_request_id IN (
+             SELECT id FROM incoming.analytics_requests
+             WHERE session_id IN (SELECT id FROM temp.merge_plan))",
+    ),
+];
+
+/// Write `contents` to a temporary database and bring it up to this schema
+pub(crate) async fn prepare_snapshot(contents: &[u8], name: &str) -> AnyhowResult<TempDatabase> {
+    let snapshot = TempDatabase::new();
+    std::fs::write(snapshot.path(), contents)
+        .with_context(|| format!("Failed to write {}", snapshot.path().display()))?;
+    DatabaseManager::new(snapshot.path())
+        .await
+        .with_context(|| {
+            format!("{name} was made by a newer RetroChat or is not a RetroChat database")
+        })?
+        .close()
+        .await?;
+    Ok(snapshot)
+}
+
+/// Merge the snapshot at `snapshot` into `db`
+///
+/// `plan` is a `SELECT` returning `(id, action)` for the snapshot sessions
+/// to merge, reading them from `incoming.chat_sessions`. `finish` runs in
+/// the same transaction after everything is copied. Returns each planned
+/// session with its action.
+pub(crate) async fn merge_snapshot(
+    db: &DatabaseManager,
+    snapshot: &Path,
+    plan: MergeStatement<'_>,
+    finish: &[MergeStatement<'_>],
+) -> AnyhowResult<Vec<(String, MergeAction)>> {
+    let _write_guard = db.write_lock().await?;
+    let mut conn = db.pool().acquire().await?;
+    // An empty key keeps the snapshot plaintext when the database is encrypted
+    sqlx::query("ATTACH DATABASE ? AS incoming KEY ''")
+        .bind(snapshot.to_string_lossy().to_string())
+        .execute(&mut *conn)
+        .await
+        .context("Failed to attach snapshot")?;
+
+    let result = merge_attached(&mut conn, plan, finish).await;
+
+    sqlx::query("DETACH DATABASE incoming")
+        .execute(&mut *conn)
+        .await
+        .context("Failed to detach snapshot")?;
+    result
+}
+
+async fn merge_attached(
+    conn: &mut sqlx::SqliteConnection,
+    plan: MergeStatement<'_>,
+    finish: &[MergeStatement<'_>],
+) -> AnyhowResult<Vec<(String, MergeAction)>> {
+    sqlx::query("DROP TABLE IF EXISTS temp.merge_plan")
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query("CREATE TEMP TABLE merge_plan (id TEXT PRIMARY KEY, action TEXT NOT NULL)")
+        .execute(&mut *conn)
+        .await?;
+    let fill_plan = format!("INSERT INTO temp.merge_plan (id, action) {}", plan.sql);
+    let mut query = sqlx::query(&fill_plan);
+    for value in &plan.binds {
+        query = query.bind(value);
+    }
+    query
+        .execute(&mut *conn)
+        .await
+        .context("Failed to compare snapshot sessions")?;
+
+    // Replaced sessions are updated column by column; INSERT OR REPLACE
+    // would delete the row and cascade to its analyses
+    let columns: Vec<String> = sqlx::query("PRAGMA main.table_info(chat_sessions)")
+        .fetch_all(&mut *conn)
+        .await?
+        .iter()
+        .map(|row| row.try_get::<String, _>("name"))
+        .collect::<Result<_, _>>()?;
+    let columns = columns.join(", ");
+    let update_sessions = format!(
+        "UPDATE main.chat_sessions SET ({columns}) =
+             (SELECT {columns} FROM incoming.chat_sessions s WHERE s.id = main.chat_sessions.id)
+         WHERE id IN (SELECT id FROM temp.merge_plan WHERE action = 'replace')"
+    );
+
+    sqlx::query("BEGIN").execute(&mut *conn).await?;
+    let result = async {
+        for (table, statement) in SESSION_STATEMENTS {
+            sqlx::query(statement)
+                .execute(&mut *conn)
+                .await
+                .with_context(|| format!("Failed to merge {table}"))?;
+        }
+        sqlx::query(&update_sessions)
+            .execute(&mut *conn)
+            .await
+            .context("Failed to merge chat_sessions")?;
+        for (table, statement) in CHILD_STATEMENTS {
+            sqlx::query(statement)
+                .execute(&mut *conn)
+                .await
+                .with_context(|| format!("Failed to merge {table}"))?;
+        }
+        for statement in finish {
+            let mut query = sqlx::query(statement.sql);
+            for value in &statement.binds {
+                query = query.bind(value);
+            }
+            query
+                .execute(&mut *conn)
+                .await
+                .context("Failed to finish merging")?;
+        }
+
+        sqlx::query("SELECT id, action FROM temp.merge_plan ORDER BY id")
+            .fetch_all(&mut *conn)
+            .await?
+            .iter()
+            .map(|row| {
+                let action = match row.try_get::<String, _>("action")?.as_str() {
+                    "add" => MergeAction::Add,
+                    "replace" => MergeAction::Replace,
+                    _ => MergeAction::Keep,
+                };
+                Ok((row.try_get("id")?, action))
+            })
+            .collect::<AnyhowResult<Vec<_>>>()
+    }
+    .await;
+
+    match result {
+        Ok(plan) => {
+            sqlx::query("COMMIT").execute(&mut *conn).await?;
+            sqlx::query("DROP TABLE temp.merge_plan")
+                .execute(&mut *conn)
+                .await?;
+            Ok(plan)
+        }
+        Err(e) => {
+            sqlx::query("ROLLBACK").execute(&mut *conn).await.ok();
+            Err(e)
+        }
+    }
+}
+
+/// A database file in the temp directory, removed with its WAL files on drop
+pub(crate) struct TempDatabase(PathBuf);
+
+impl TempDatabase {
+    pub(crate) fn new() -> Self {
+        Self(std::env::temp_dir().join(format!("retrochat-snapshot-{}.db", Uuid::new_v4())))
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDatabase {
+    fn drop(&mut self) {
+        for suffix in ["", "-wal", "-shm"] {
+            let mut path = self.0.clone().into_os_string();
+            path.push(suffix);
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{ChatSessionRepository, MessageRepository};
+    use crate::models::{ChatSession, Message, MessageRole, Provider};
+    use crate::services::session_edit::{DERIVED_TABLES, HANDLED_TABLES, MESSAGE_TABLES};
+    use crate::services::snapshot_export::{export_sqlite_snapshot, COPY_STATEMENTS};
+    use chrono::Utc;
+
+    /// Tables tied to sessions that stay on this machine: how sessions were
+    /// merged, grouped into experiments and topics, or compared locally
+    const LOCAL_TABLES: &[&str] = &[
+        "experiment_sessions",
+        "merged_sessions",
+        "session_comparisons",
+        "session_topics",
+    ];
+
+    async fn count(db: &DatabaseManager, table: &str) -> i64 {
+        sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {table}"))
+            .fetch_one(db.pool())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_statements_cover_every_session_table() {
+        let db = DatabaseManager::open_in_memory().await.unwrap();
+        let tables: Vec<String> = sqlx::query_scalar(
+            "SELECT DISTINCT m.name FROM sqlite_master m, pragma_foreign_key_list(m.name) f
+             WHERE m.type = 'table' AND f.\"table\" IN ('chat_sessions', 'messages')
+             ORDER BY m.name",
+        )
+        .fetch_all(db.pool())
+        .await
+        .unwrap();
+        assert!(tables.iter().any(|table| table == "message_usage"));
+
+        for table in tables
+            .iter()
+            .filter(|table| !LOCAL_TABLES.contains(&table.as_str()))
+        {
+            assert!(
+                COPY_STATEMENTS.iter().any(|(name, _)| name == table),
+                "{table} is not exported to snapshots"
+            );
+            assert!(
+                CHILD_STATEMENTS.iter().any(|(name, _)| name == table),
+                "{table} is not merged from snapshots"
+            );
+        }
+        for table in &tables {
+            assert!(
+                [MESSAGE_TABLES, DERIVED_TABLES, HANDLED_TABLES]
+                    .iter()
+                    .any(|list| list.contains(&table.as_str())),
+                "{table} is not handled by session split and merge"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replace_keeps_per_message_rows() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = DatabaseManager::new(dir.path().join("source.db"))
+            .await
+            .unwrap();
+        let local = DatabaseManager::new(dir.path().join("local.db"))
+            .await
+            .unwrap();
+
+        let session = ChatSession::new(
+            Provider::ClaudeCode,
+            "/shared.jsonl".to_string(),
+            "hash".to_string(),
+            Utc::now(),
+        );
+        ChatSessionRepository::new(&source)
+            .create(&session)
+            .await
+            .unwrap();
+        let message = Message::new(
+            session.id,
+            MessageRole::User,
+            "prompt".to_string(),
+            Utc::now(),
+            1,
+        );
+        MessageRepository::new(&source)
+            .create(&message)
+            .await
+            .unwrap();
+        for statement in [
+            "INSERT INTO message_usage (message_id, session_id, input_tokens, cache_read_input_tokens)
+             VALUES (?2, ?1, 10, 90)",
+            "INSERT INTO attachments (id, message_id, session_id, kind) VALUES ('a1', ?2, ?1, 'image')",
+            "INSERT INTO message_revisions (message_id, field, source, created_at)
+             VALUES (?2, 'role', 'repair', '2024-01-01T00:00:00Z')",
+            "INSERT INTO session_segments (session_id, segment_index, start_sequence, end_sequence, started_at, ended_at)
+             VALUES (?1, 0, 1, 1, '2024-01-01T00:00:00Z', '2024-01-01T00:00:00Z')",
+            "INSERT INTO session_tags (session_id, tag, created_at) VALUES (?1, 'work', '2024-01-01T00:00:00Z')",
+            "INSERT INTO session_quality (session_id, computed_at) VALUES (?1, '2024-01-01T00:00:00Z')",
+            "INSERT INTO raw_payloads (session_id, owner_kind, owner_id, payload, created_at)
+             VALUES (?1, 'message', ?2, '{}', '2024-01-01T00:00:00Z')",
+        ] {
+            sqlx::query(statement)
+                .bind(session.id.to_string())
+                .bind(message.id.to_string())
+                .execute(source.pool())
+                .await
+                .unwrap();
+        }
+
+        let snapshot = dir.path().join("snapshot.db");
+        export_sqlite_snapshot(&source, &[session.id], &snapshot)
+            .await
+            .unwrap();
+
+        let plan = |action: &'static str| MergeStatement {
+            sql: "SELECT id, ? FROM incoming.chat_sessions",
+            binds: vec![action.to_string()],
+        };
+        merge_snapshot(&local, &snapshot, plan("add"), &[])
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO message_revisions (message_id, field, source, created_at)
+             VALUES (?, 'content', 'redaction', '2024-01-02T00:00:00Z')",
+        )
+        .bind(message.id.to_string())
+        .execute(local.pool())
+        .await
+        .unwrap();
+
+        let merged = merge_snapshot(&local, &snapshot, plan("replace"), &[])
+            .await
+            .unwrap();
+        assert_eq!(merged, vec![(session.id.to_string(), MergeAction::Replace)]);
+
+        for table in [
+            "message_usage",
+            "attachments",
+            "session_segments",
+            "session_tags",
+            "session_quality",
+            "raw_payloads",
+        ] {
+            assert_eq!(count(&local, table).await, 1, "{table}");
+        }
+        // The snapshot's revision is not duplicated and the local one survives
+        assert_eq!(count(&local, "message_revisions").await, 2);
+    }
+}
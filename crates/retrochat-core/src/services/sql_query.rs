@@ -0,0 +1,341 @@
+//! Ad-hoc, read-only SQL against the RetroChat database
+//!
+//! Statements are checked against a whitelist before they run, and they run
+//! on a connection SQLite itself treats as read-only, so a statement that
+//! slips past the check still cannot modify anything.
+
+use anyhow::{Context, Result as AnyhowResult};
+use futures::TryStreamExt;
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Column, ConnectOptions, Row, SqliteConnection, TypeInfo, ValueRef};
+use std::sync::Arc;
+
+use crate::database::DatabaseManager;
+
+/// Statements a query may start with
+const ALLOWED_LEADING_KEYWORDS: &[&str] = &["SELECT", "WITH", "EXPLAIN", "VALUES"];
+
+/// Keywords rejected anywhere outside string literals
+const FORBIDDEN_KEYWORDS: &[&str] = &[
+    "ALTER", "ANALYZE", "ATTACH", "CREATE", "DELETE", "DETACH", "DROP", "INSERT", "PRAGMA",
+    "REINDEX", "UPDATE", "VACUUM",
+];
+
+/// Result of a read-only query
+#[derive(Debug, Clone, Serialize)]
+pub struct SqlQueryResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+    /// More rows were available than the limit allowed
+    pub truncated: bool,
+}
+
+/// A table or view and the statement that created it
+#[derive(Debug, Clone, Serialize)]
+pub struct SchemaEntry {
+    pub name: String,
+    pub kind: String,
+    pub sql: String,
+}
+
+/// Service for running read-only SQL
+pub struct SqlQueryService {
+    db_manager: Arc<DatabaseManager>,
+}
+
+impl SqlQueryService {
+    pub fn new(db_manager: Arc<DatabaseManager>) -> Self {
+        Self { db_manager }
+    }
+
+    /// Run a single read-only statement, returning at most `limit` rows
+    pub async fn run(&self, sql: &str, limit: usize) -> AnyhowResult<SqlQueryResult> {
+        let sql = validate_read_only_sql(sql)?;
+
+        self.with_read_only_connection(|conn| {
+            Box::pin(async move {
+                let mut stream = sqlx::query(&sql).fetch(&mut *conn);
+                let mut columns = Vec::new();
+                let mut rows = Vec::new();
+                let mut truncated = false;
+
+                while let Some(row) = stream.try_next().await? {
+                    if columns.is_empty() {
+                        columns = row.columns().iter().map(|c| c.name().to_string()).collect();
+                    }
+                    if rows.len() == limit {
+                        truncated = true;
+                        break;
+                    }
+                    rows.push(row_to_values(&row)?);
+                }
+
+                Ok(SqlQueryResult {
+                    columns,
+                    rows,
+                    truncated,
+                })
+            })
+        })
+        .await
+    }
+
+    /// Tables and views a query can read, with their definitions
+    pub async fn schema(&self) -> AnyhowResult<Vec<SchemaEntry>> {
+        self.with_read_only_connection(|conn| {
+            Box::pin(async move {
+                let rows = sqlx::query(
+                    r#"
+                    SELECT name, type, sql FROM sqlite_master
+                    WHERE type IN ('table', 'view')
+                      AND sql IS NOT NULL
+                      AND name NOT LIKE 'sqlite_%'
+                      AND name NOT LIKE '_sqlx_%'
+                      AND name NOT LIKE '%_fts_%'
+                    ORDER BY type, name
+                    "#,
+                )
+                .fetch_all(&mut *conn)
+                .await?;
+
+                rows.iter()
+                    .map(|row| {
+                        Ok(SchemaEntry {
+                            name: row.try_get("name")?,
+                            kind: row.try_get("type")?,
+                            sql: row.try_get("sql")?,
+                        })
+                    })
+                    .collect()
+            })
+        })
+        .await
+    }
+
+    /// Run `f` on a connection that cannot write
+    ///
+    /// File databases get a fresh connection opened read-only. In-memory
+    /// databases can only be reached through the pool, so the pooled
+    /// connection is switched to `query_only` for the duration instead.
+    async fn with_read_only_connection<T>(
+        &self,
+        f: impl for<'c> FnOnce(
+            &'c mut SqliteConnection,
+        ) -> futures::future::BoxFuture<'c, AnyhowResult<T>>,
+    ) -> AnyhowResult<T> {
+        let db_path = self.db_manager.db_path();
+
+        if db_path.as_os_str() == ":memory:" {
+            let mut conn = self.db_manager.pool().acquire().await?;
+            sqlx::query("PRAGMA query_only = ON")
+                .execute(&mut *conn)
+                .await?;
+            let result = f(&mut conn).await;
+            sqlx::query("PRAGMA query_only = OFF")
+                .execute(&mut *conn)
+                .await?;
+            return result;
+        }
+
+        let mut conn = self
+            .db_manager
+            .connect_options()
+            .read_only(true)
+            .connect()
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to open read-only connection to: {}",
+                    db_path.display()
+                )
+            })?;
+        f(&mut conn).await
+    }
+}
+
+/// Check that `sql` is a single read-only statement
+///
+/// Returns the statement with surrounding whitespace and a trailing
+/// semicolon removed.
+pub fn validate_read_only_sql(sql: &str) -> AnyhowResult<String> {
+    let sql = sql.trim();
+    let sql = sql.strip_suffix(';').unwrap_or(sql).trim_end();
+    if sql.is_empty() {
+        anyhow::bail!("Query is empty");
+    }
+
+    let words = scan_words(sql)?;
+    match words.first() {
+        Some(first) if ALLOWED_LEADING_KEYWORDS.contains(&first.as_str()) => {}
+        _ => anyhow::bail!(
+            "Only read-only queries are allowed (statements must start with {})",
+            ALLOWED_LEADING_KEYWORDS.join(", ")
+        ),
+    }
+    if let Some(word) = words
+        .iter()
+        .find(|w| FORBIDDEN_KEYWORDS.contains(&w.as_str()))
+    {
+        anyhow::bail!("{word} is not allowed in read-only queries");
+    }
+
+    Ok(sql.to_string())
+}
+
+/// Uppercased bare words outside literals, quoted identifiers and comments
+///
+/// Fails if the text contains more than one statement.
+fn scan_words(sql: &str) -> AnyhowResult<Vec<String>> {
+    let chars: Vec<char> = sql.chars().collect();
+    let mut words = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\'' | '"' | '`' => {
+                // Quoted text ends at the next unescaped (undoubled) quote
+                i += 1;
+                while i < chars.len() {
+                    if chars[i] == c {
+                        if chars.get(i + 1) == Some(&c) {
+                            i += 1;
+                        } else {
+                            break;
+                        }
+                    }
+                    i += 1;
+                }
+                i += 1;
+            }
+            '[' => {
+                while i < chars.len() && chars[i] != ']' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            '-' if chars.get(i + 1) == Some(&'-') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                i += 2;
+            }
+            ';' => anyhow::bail!("Only a single statement is allowed"),
+            c if c.is_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                words.push(chars[start..i].iter().collect::<String>().to_uppercase());
+            }
+            _ => i += 1,
+        }
+    }
+
+    Ok(words)
+}
+
+fn row_to_values(row: &SqliteRow) -> AnyhowResult<Vec<Value>> {
+    (0..row.columns().len())
+        .map(|i| {
+            let raw = row.try_get_raw(i)?;
+            if raw.is_null() {
+                return Ok(Value::Null);
+            }
+            let type_name = raw.type_info().name().to_string();
+            let value = match type_name.as_str() {
+                "INTEGER" | "BOOLEAN" => Value::from(row.try_get::<i64, _>(i)?),
+                "REAL" => Value::from(row.try_get::<f64, _>(i)?),
+                "BLOB" => Value::from(format!("<{} bytes>", row.try_get::<Vec<u8>, _>(i)?.len())),
+                _ => Value::from(row.try_get::<String, _>(i)?),
+            };
+            Ok(value)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_validate_read_only_sql() {
+        assert_eq!(validate_read_only_sql("  SELECT 1;  ").unwrap(), "SELECT 1");
+        assert!(validate_read_only_sql("with x as (select 1) select * from x").is_ok());
+        assert!(validate_read_only_sql("SELECT 'drop table; delete' AS s").is_ok());
+        assert!(validate_read_only_sql("-- note\nSELECT 1").is_ok());
+
+        assert!(validate_read_only_sql("").is_err());
+        assert!(validate_read_only_sql("DELETE FROM messages").is_err());
+        assert!(validate_read_only_sql("SELECT 1; DROP TABLE messages").is_err());
+        assert!(validate_read_only_sql("PRAGMA table_info(messages)").is_err());
+        assert!(validate_read_only_sql("WITH x AS (SELECT 1) DELETE FROM messages").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_run_returns_typed_rows_and_truncates() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Arc::new(
+            DatabaseManager::new(temp_dir.path().join("test.db"))
+                .await
+                .unwrap(),
+        );
+        let service = SqlQueryService::new(db);
+
+        let result = service
+            .run(
+                "SELECT 1 AS n, 'a' AS s, 2.5 AS r, NULL AS z UNION ALL SELECT 2, 'b', 3.5, NULL",
+                1,
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.columns, vec!["n", "s", "r", "z"]);
+        assert_eq!(
+            result.rows,
+            vec![vec![
+                Value::from(1),
+                Value::from("a"),
+                Value::from(2.5),
+                Value::Null
+            ]]
+        );
+        assert!(result.truncated);
+
+        let schema = service.schema().await.unwrap();
+        assert!(schema.iter().any(|entry| entry.name == "chat_sessions"));
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_connection_rejects_writes() {
+        let db = Arc::new(DatabaseManager::open_in_memory().await.unwrap());
+        let service = SqlQueryService::new(db.clone());
+
+        // Bypass the whitelist to check the connection itself refuses writes
+        let result = service
+            .with_read_only_connection(|conn| {
+                Box::pin(async move {
+                    sqlx::query("INSERT INTO projects (id, name) VALUES ('p', 'p')")
+                        .execute(&mut *conn)
+                        .await?;
+                    Ok(())
+                })
+            })
+            .await;
+        assert!(result.is_err());
+
+        // The pooled connection is writable again afterwards
+        sqlx::query("INSERT INTO projects (id, name) VALUES ('p', 'p')")
+            .execute(db.pool())
+            .await
+            .unwrap();
+    }
+}
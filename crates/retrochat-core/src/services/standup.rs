@@ -0,0 +1,418 @@
+//! Daily standup digest
+//!
+//! Collects one day of activity (sessions, file edits, commits, failed tools
+//! and abandoned sessions) and writes it up as a short markdown standup,
+//! with the LLM when one is given and as a plain list otherwise.
+
+use anyhow::{Context, Result as AnyhowResult};
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::activity_digest::{
+    collect_failures, summarize_file_changes, ActivityDigestService, ActivityFailure,
+    ActivityFileChange, RecentActivityRequest,
+};
+use crate::database::{
+    ChatSessionRepository, DatabaseManager, SessionSummaryRepository, ToolOperationRepository,
+};
+use crate::models::{SessionOutcome, ToolOperation};
+use crate::services::llm::{CachingClient, GenerateRequest, LlmClient};
+use crate::utils::locale::Language;
+
+/// Maximum entries in each standup section
+const STANDUP_SECTION_LIMIT: usize = 15;
+
+/// Characters kept from a commit message
+const COMMIT_MESSAGE_CHARS: usize = 100;
+
+/// A session worked on during the day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StandupSession {
+    pub session_id: String,
+    pub project: Option<String>,
+    /// Summary title, session title or first prompt, in that order
+    pub title: String,
+    pub outcome: Option<SessionOutcome>,
+    pub message_count: usize,
+}
+
+/// Everything a standup is written from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StandupFacts {
+    pub date: NaiveDate,
+    pub sessions: Vec<StandupSession>,
+    pub file_changes: Vec<ActivityFileChange>,
+    /// First line of each commit made through a shell tool
+    pub commits: Vec<String>,
+    pub failures: Vec<ActivityFailure>,
+}
+
+impl StandupFacts {
+    pub fn is_empty(&self) -> bool {
+        self.sessions.is_empty() && self.file_changes.is_empty() && self.commits.is_empty()
+    }
+
+    /// Sessions whose summary says they were abandoned
+    pub fn abandoned(&self) -> impl Iterator<Item = &StandupSession> {
+        self.sessions
+            .iter()
+            .filter(|s| s.outcome == Some(SessionOutcome::Abandoned))
+    }
+}
+
+/// A generated standup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Standup {
+    pub facts: StandupFacts,
+    pub markdown: String,
+    /// False when the plain list was used (no LLM, or the LLM call failed)
+    pub written_by_llm: bool,
+}
+
+/// Service writing daily standup digests
+pub struct StandupService {
+    db_manager: Arc<DatabaseManager>,
+    llm_client: Option<Arc<dyn LlmClient>>,
+    language: Language,
+}
+
+impl StandupService {
+    /// Without an LLM client, standups are rendered as plain lists
+    pub fn new(db_manager: Arc<DatabaseManager>, llm_client: Option<Arc<dyn LlmClient>>) -> Self {
+        let llm_client = llm_client.map(|client| CachingClient::wrap(client, &db_manager));
+        Self {
+            db_manager,
+            llm_client,
+            language: Language::load(),
+        }
+    }
+
+    /// Write the standup for `date` (a UTC day)
+    pub async fn generate(&self, date: NaiveDate) -> AnyhowResult<Standup> {
+        let facts = self.collect(date).await?;
+
+        if let Some(client) = self.llm_client.as_ref().filter(|_| !facts.is_empty()) {
+            match write_with_llm(client.as_ref(), &facts, self.language).await {
+                Ok(markdown) => {
+                    return Ok(Standup {
+                        facts,
+                        markdown,
+                        written_by_llm: true,
+                    });
+                }
+                Err(e) => tracing::warn!(error = %e, "Falling back to a plain standup"),
+            }
+        }
+
+        Ok(Standup {
+            markdown: render_markdown(&facts, self.language),
+            facts,
+            written_by_llm: false,
+        })
+    }
+
+    /// Gather the activity of `date`
+    pub async fn collect(&self, date: NaiveDate) -> AnyhowResult<StandupFacts> {
+        let (since, until) = day_window(date)?;
+
+        let digest = ActivityDigestService::with_database(self.db_manager.clone())
+            .get_recent_activity(RecentActivityRequest {
+                since,
+                until,
+                limit: Some(STANDUP_SECTION_LIMIT),
+            })
+            .await?;
+
+        let session_repo = ChatSessionRepository::new(&self.db_manager);
+        let summary_repo = SessionSummaryRepository::new(&self.db_manager);
+        let mut sessions = Vec::with_capacity(digest.sessions.len());
+        for activity in digest.sessions.into_iter().filter(|s| s.message_count > 0) {
+            let id = Uuid::parse_str(&activity.session_id)?;
+            let summary = summary_repo.get_by_session(&id).await?;
+            let session_title = session_repo.get_by_id(&id).await?.and_then(|s| s.title);
+            let title = summary
+                .as_ref()
+                .map(|s| s.title.clone())
+                .or(session_title)
+                .or(activity.first_prompt)
+                .unwrap_or_else(|| "Untitled session".to_string());
+
+            sessions.push(StandupSession {
+                session_id: activity.session_id,
+                project: activity.project,
+                title,
+                outcome: summary.and_then(|s| s.outcome),
+                message_count: activity.message_count,
+            });
+        }
+
+        let mut operations = ToolOperationRepository::new(&self.db_manager)
+            .get_by_time_range(&since, &until)
+            .await
+            .context("Failed to load tool operations for standup")?;
+        let mut seen = HashSet::new();
+        operations.retain(|(_, op)| seen.insert(op.id));
+
+        Ok(StandupFacts {
+            date,
+            sessions,
+            file_changes: summarize_file_changes(&operations, STANDUP_SECTION_LIMIT),
+            commits: collect_commits(&operations, STANDUP_SECTION_LIMIT),
+            failures: collect_failures(&operations, STANDUP_SECTION_LIMIT),
+        })
+    }
+}
+
+/// Start and end of a UTC day
+fn day_window(date: NaiveDate) -> AnyhowResult<(DateTime<Utc>, DateTime<Utc>)> {
+    let start = Utc
+        .from_local_datetime(&date.and_hms_opt(0, 0, 0).context("Invalid date")?)
+        .single()
+        .context("Ambiguous date")?;
+    Ok((start, start + Duration::days(1) - Duration::milliseconds(1)))
+}
+
+/// Commit messages of successful `git commit` commands, oldest first
+fn collect_commits(operations: &[(Uuid, ToolOperation)], limit: usize) -> Vec<String> {
+    let mut seen = HashSet::new();
+    operations
+        .iter()
+        .filter(|(_, op)| op.success != Some(false))
+        .filter_map(|(_, op)| op.bash_metadata.as_ref())
+        .filter_map(|bash| commit_message(&bash.command))
+        .filter(|message| seen.insert(message.clone()))
+        .take(limit)
+        .collect()
+}
+
+/// First line of the message of a `git commit` command, e.g.
+/// `git commit -m "Fix login"` or a `-m "$(cat <<'EOF' ... EOF)"` heredoc
+fn commit_message(command: &str) -> Option<String> {
+    let rest = &command[command.find("git commit")? + "git commit".len()..];
+
+    let message = if let Some(heredoc) = rest.find("<<") {
+        rest[heredoc..]
+            .lines()
+            .skip(1)
+            .map(str::trim)
+            .find(|line| !line.is_empty())?
+            .to_string()
+    } else {
+        let flag = rest
+            .split_whitespace()
+            .position(|arg| arg == "-m" || arg == "-am" || arg == "--message")?;
+        let after = rest
+            .split_whitespace()
+            .skip(flag + 1)
+            .collect::<Vec<_>>()
+            .join(" ");
+        let quote = after.chars().next()?;
+        if quote == '"' || quote == '\'' {
+            after[1..].split(quote).next()?.to_string()
+        } else {
+            after.split_whitespace().next()?.to_string()
+        }
+    };
+
+    let first_line = message.lines().next()?.trim();
+    if first_line.is_empty() {
+        return None;
+    }
+    Some(first_line.chars().take(COMMIT_MESSAGE_CHARS).collect())
+}
+
+/// Plain standup, also used as the fact sheet given to the LLM
+pub fn render_markdown(facts: &StandupFacts, language: Language) -> String {
+    let mut out = format!("# Standup for {}\n\n", language.format_date(facts.date));
+    if facts.is_empty() {
+        out.push_str("No sessions on this day.\n");
+        return out;
+    }
+
+    out.push_str("## Worked on\n\n");
+    for session in &facts.sessions {
+        let project = session
+            .project
+            .as_deref()
+            .map(|p| format!(" ({p})"))
+            .unwrap_or_default();
+        let outcome = session
+            .outcome
+            .as_ref()
+            .map(|o| format!(" [{o}]"))
+            .unwrap_or_default();
+        let _ = writeln!(out, "- {}{project}{outcome}", session.title);
+    }
+
+    out.push_str("\n## Shipped\n\n");
+    if facts.commits.is_empty() && facts.file_changes.is_empty() {
+        out.push_str("- Nothing committed or edited\n");
+    }
+    for commit in &facts.commits {
+        let _ = writeln!(out, "- Commit: {commit}");
+    }
+    for change in &facts.file_changes {
+        let _ = writeln!(
+            out,
+            "- Edited {} ({} edits, +{} -{})",
+            change.file_path, change.edit_count, change.lines_added, change.lines_removed
+        );
+    }
+
+    out.push_str("\n## Blockers\n\n");
+    let mut blockers = 0;
+    for session in facts.abandoned() {
+        let _ = writeln!(out, "- Abandoned: {}", session.title);
+        blockers += 1;
+    }
+    for failure in &facts.failures {
+        match &failure.detail {
+            Some(detail) => {
+                let _ = writeln!(out, "- {} failed: {detail}", failure.tool_name);
+            }
+            None => {
+                let _ = writeln!(out, "- {} failed", failure.tool_name);
+            }
+        }
+        blockers += 1;
+    }
+    if blockers == 0 {
+        out.push_str("- None\n");
+    }
+
+    out
+}
+
+async fn write_with_llm(
+    client: &dyn LlmClient,
+    facts: &StandupFacts,
+    language: Language,
+) -> AnyhowResult<String> {
+    let prompt = format!(
+        "Write a short daily standup from this activity log of coding assistant sessions.\n\n\
+         {}\n\
+         Use markdown with exactly three sections: \"## Yesterday\" (what was worked on, \
+         grouped by project), \"## Shipped\" (commits and notable file changes) and \
+         \"## Blockers\" (failures and abandoned work that still need attention, or \"None\"). \
+         Use at most five bullets per section, keep each bullet to one line, and do not \
+         invent anything that is not in the log. Output only the markdown.",
+        render_markdown(facts, language)
+    );
+    let request = GenerateRequest::new(language.localize_prompt(prompt))
+        .with_max_tokens(800)
+        .with_temperature(0.3);
+    let response = client.generate(request).await?;
+
+    let markdown = response.text.trim();
+    if markdown.is_empty() {
+        anyhow::bail!("LLM returned an empty standup");
+    }
+    Ok(format!(
+        "# Standup for {}\n\n{}\n",
+        language.format_date(facts.date),
+        markdown
+            .trim_start_matches("```markdown")
+            .trim_matches('`')
+            .trim()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::BashMetadata;
+
+    fn bash(command: &str, success: bool) -> (Uuid, ToolOperation) {
+        let operation =
+            ToolOperation::new(Uuid::new_v4().to_string(), "Bash".to_string(), Utc::now())
+                .with_bash_metadata(BashMetadata::new("Git".to_string(), command.to_string()))
+                .with_success(success);
+        (Uuid::new_v4(), operation)
+    }
+
+    #[test]
+    fn test_commit_message() {
+        assert_eq!(
+            commit_message(r#"git add -A && git commit -m "Fix login redirect""#).as_deref(),
+            Some("Fix login redirect")
+        );
+        assert_eq!(
+            commit_message("git commit -am 'Bump version'").as_deref(),
+            Some("Bump version")
+        );
+        let heredoc = "git commit -m \"$(cat <<'EOF'\nAdd standup command\n\nLonger body\nEOF\n)\"";
+        assert_eq!(
+            commit_message(heredoc).as_deref(),
+            Some("Add standup command")
+        );
+        assert_eq!(commit_message("git status"), None);
+        assert_eq!(commit_message("git commit --amend --no-edit"), None);
+    }
+
+    #[test]
+    fn test_collect_commits_skips_failed() {
+        let operations = vec![
+            bash("git commit -m 'First'", true),
+            bash("git commit -m 'Rejected by hook'", false),
+            bash("cargo test", true),
+        ];
+        assert_eq!(collect_commits(&operations, 10), vec!["First".to_string()]);
+    }
+
+    #[test]
+    fn test_collect_commits_dedupes_non_adjacent() {
+        let operations = vec![
+            bash("git commit -m 'First'", true),
+            bash("git commit -m 'Second'", true),
+            bash("git commit -m 'First'", true),
+        ];
+        assert_eq!(
+            collect_commits(&operations, 10),
+            vec!["First".to_string(), "Second".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_render_markdown_sections() {
+        let date = NaiveDate::from_ymd_opt(2024, 1, 10).unwrap();
+        let mut facts = StandupFacts {
+            date,
+            sessions: vec![],
+            file_changes: vec![],
+            commits: vec![],
+            failures: vec![],
+        };
+        assert!(render_markdown(&facts, Language::English).contains("No sessions on this day."));
+
+        facts.sessions.push(StandupSession {
+            session_id: Uuid::new_v4().to_string(),
+            project: Some("retrochat".to_string()),
+            title: "Flaky import test".to_string(),
+            outcome: Some(SessionOutcome::Abandoned),
+            message_count: 4,
+        });
+        facts.commits.push("Fix login redirect".to_string());
+
+        let markdown = render_markdown(&facts, Language::English);
+        assert!(markdown.starts_with("# Standup for 2024-01-10"));
+        assert!(markdown.contains("- Flaky import test (retrochat) [abandoned]"));
+        assert!(markdown.contains("- Commit: Fix login redirect"));
+        assert!(markdown.contains("- Abandoned: Flaky import test"));
+    }
+
+    #[tokio::test]
+    async fn test_generate_without_llm_for_empty_day() {
+        let db = Arc::new(DatabaseManager::open_in_memory().await.unwrap());
+        let date = NaiveDate::from_ymd_opt(2024, 1, 10).unwrap();
+
+        let standup = StandupService::new(db, None).generate(date).await.unwrap();
+
+        assert!(standup.facts.is_empty());
+        assert!(!standup.written_by_llm);
+        assert!(standup.markdown.contains("No sessions on this day."));
+    }
+}
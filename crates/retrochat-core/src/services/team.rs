@@ -0,0 +1,385 @@
+//! Team mode: read-only copies of teammates' sessions
+//!
+//! A teammate shares a snapshot made with `retrochat export --format sqlite`
+//! and it is imported here under their name. Imported sessions carry an
+//! `owner`, are never re-imported from provider files, archived or titled,
+//! and can be filtered apart from your own with `owner:me` / `owner:team`.
+//! Only the exporter's own sessions are taken from a snapshot, so passing
+//! exports around never launders someone else's sessions under a new name.
+
+use anyhow::{Context, Result as AnyhowResult};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::json;
+use sqlx::Row;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::database::{DatabaseManager, EventRepository};
+use crate::models::EventKind;
+use crate::services::snapshot_merge::{
+    merge_snapshot, prepare_snapshot, MergeAction, MergeStatement,
+};
+
+/// Owner names with a meaning in `owner:` filters
+const RESERVED_OWNERS: [&str; 3] = ["me", "mine", "team"];
+
+/// What a team import merged
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TeamImportReport {
+    /// Sessions this database didn't have
+    pub added: usize,
+    /// Sessions of the same teammate replaced by a copy with more messages
+    pub updated: usize,
+    /// Sessions left alone because the local copy was at least as long or
+    /// belongs to someone else
+    pub kept: usize,
+}
+
+/// Usage of one owner's sessions
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct OwnerStats {
+    /// `None` for your own sessions
+    pub owner: Option<String>,
+    pub sessions: i64,
+    pub messages: i64,
+    pub tokens: i64,
+    /// Sessions per provider, most used first
+    pub providers: Vec<(String, i64)>,
+    pub tool_uses: i64,
+    pub failed_tool_uses: i64,
+    pub last_session: Option<DateTime<Utc>>,
+}
+
+impl OwnerStats {
+    /// Share of tool uses that failed, `None` without tool uses
+    pub fn tool_failure_rate(&self) -> Option<f64> {
+        (self.tool_uses > 0).then(|| self.failed_tool_uses as f64 / self.tool_uses as f64)
+    }
+
+    /// Sum several owners' stats into one row with the given owner
+    pub fn combine<'a>(
+        owner: Option<String>,
+        stats: impl IntoIterator<Item = &'a OwnerStats>,
+    ) -> OwnerStats {
+        let mut total = OwnerStats {
+            owner,
+            ..Default::default()
+        };
+        let mut providers: BTreeMap<String, i64> = BTreeMap::new();
+        for row in stats {
+            total.sessions += row.sessions;
+            total.messages += row.messages;
+            total.tokens += row.tokens;
+            total.tool_uses += row.tool_uses;
+            total.failed_tool_uses += row.failed_tool_uses;
+            total.last_session = total.last_session.max(row.last_session);
+            for (provider, count) in &row.providers {
+                *providers.entry(provider.clone()).or_default() += count;
+            }
+        }
+        total.providers = sort_providers(providers.into_iter().collect());
+        total
+    }
+}
+
+fn sort_providers(mut providers: Vec<(String, i64)>) -> Vec<(String, i64)> {
+    providers.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    providers
+}
+
+/// Imports, removes and summarizes teammates' sessions
+pub struct TeamService {
+    db_manager: Arc<DatabaseManager>,
+    event_repo: EventRepository,
+}
+
+impl TeamService {
+    pub fn new(db_manager: Arc<DatabaseManager>) -> Self {
+        Self {
+            event_repo: EventRepository::new(&db_manager),
+            db_manager,
+        }
+    }
+
+    /// Import the sessions in a teammate's snapshot export as `owner`'s
+    ///
+    /// Importing a newer export of the same teammate updates sessions that
+    /// grew; sessions you already have, yours or another teammate's, are
+    /// never overwritten.
+    pub async fn import(&self, snapshot: &Path, owner: &str) -> AnyhowResult<TeamImportReport> {
+        let owner = validate_owner(owner)?;
+        let contents = std::fs::read(snapshot)
+            .with_context(|| format!("Failed to read {}", snapshot.display()))?;
+        let prepared = prepare_snapshot(&contents, &snapshot.display().to_string()).await?;
+
+        let plan = MergeStatement {
+            sql: "SELECT s.id,
+                         CASE WHEN m.id IS NULL THEN 'add'
+                              WHEN m.owner = ? AND s.message_count > m.message_count THEN 'replace'
+                              ELSE 'keep' END
+                  FROM incoming.chat_sessions s
+                  LEFT JOIN main.chat_sessions m ON m.id = s.id
+                  WHERE s.owner IS NULL",
+            binds: vec![owner.clone()],
+        };
+        let tag_owner = MergeStatement {
+            sql: "UPDATE main.chat_sessions SET owner = ?
+                  WHERE id IN (SELECT id FROM temp.merge_plan WHERE action != 'keep')",
+            binds: vec![owner.clone()],
+        };
+        let merged = merge_snapshot(&self.db_manager, prepared.path(), plan, &[tag_owner]).await?;
+
+        let mut report = TeamImportReport::default();
+        for (session_id, action) in merged {
+            let kind = match action {
+                MergeAction::Add => {
+                    report.added += 1;
+                    EventKind::SessionImported
+                }
+                MergeAction::Replace => {
+                    report.updated += 1;
+                    EventKind::SessionReplaced
+                }
+                MergeAction::Keep => {
+                    report.kept += 1;
+                    continue;
+                }
+            };
+            self.event_repo
+                .record(kind, Some(&session_id), json!({ "owner": owner }))
+                .await;
+        }
+        tracing::info!(
+            owner = %owner,
+            added = report.added,
+            updated = report.updated,
+            "Imported team export"
+        );
+        Ok(report)
+    }
+
+    /// Delete every session imported for `owner`, returning how many
+    pub async fn remove(&self, owner: &str) -> AnyhowResult<u64> {
+        let _write_guard = self.db_manager.write_lock().await?;
+        let mut tx = self.db_manager.pool().begin().await?;
+        // Tool operations hang off messages rather than sessions, so they
+        // don't cascade
+        sqlx::query(
+            "DELETE FROM tool_operations WHERE id IN (
+                 SELECT m.tool_operation_id FROM messages m
+                 JOIN chat_sessions s ON s.id = m.session_id
+                 WHERE s.owner = ?)",
+        )
+        .bind(owner)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to delete tool operations")?;
+        let removed = sqlx::query("DELETE FROM chat_sessions WHERE owner = ?")
+            .bind(owner)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to delete sessions")?
+            .rows_affected();
+        tx.commit().await?;
+        Ok(removed)
+    }
+
+    /// Usage per owner: your own sessions first, then teammates by name
+    pub async fn stats(&self) -> AnyhowResult<Vec<OwnerStats>> {
+        let pool = self.db_manager.pool();
+        let mut by_owner: BTreeMap<Option<String>, OwnerStats> = BTreeMap::new();
+
+        let rows = sqlx::query(
+            "SELECT owner, COUNT(*) AS sessions, SUM(message_count) AS messages,
+                    SUM(COALESCE(token_count, 0)) AS tokens, MAX(start_time) AS last_session
+             FROM chat_sessions GROUP BY owner",
+        )
+        .fetch_all(pool)
+        .await?;
+        for row in rows {
+            let owner: Option<String> = row.try_get("owner")?;
+            let last_session = row
+                .try_get::<Option<String>, _>("last_session")?
+                .and_then(|time| DateTime::parse_from_rfc3339(&time).ok())
+                .map(|time| time.with_timezone(&Utc));
+            by_owner.insert(
+                owner.clone(),
+                OwnerStats {
+                    owner,
+                    sessions: row.try_get("sessions")?,
+                    messages: row.try_get("messages")?,
+                    tokens: row.try_get("tokens")?,
+                    last_session,
+                    ..Default::default()
+                },
+            );
+        }
+
+        let rows = sqlx::query(
+            "SELECT owner, provider, COUNT(*) AS sessions
+             FROM chat_sessions GROUP BY owner, provider",
+        )
+        .fetch_all(pool)
+        .await?;
+        for row in rows {
+            let owner: Option<String> = row.try_get("owner")?;
+            if let Some(stats) = by_owner.get_mut(&owner) {
+                stats
+                    .providers
+                    .push((row.try_get("provider")?, row.try_get("sessions")?));
+            }
+        }
+
+        let rows = sqlx::query(
+            "SELECT s.owner AS owner, COUNT(DISTINCT t.id) AS tool_uses,
+                    COUNT(DISTINCT CASE WHEN t.success = 0 THEN t.id END) AS failed
+             FROM chat_sessions s
+             JOIN messages m ON m.session_id = s.id
+             JOIN tool_operations t ON t.id = m.tool_operation_id
+             GROUP BY s.owner",
+        )
+        .fetch_all(pool)
+        .await?;
+        for row in rows {
+            let owner: Option<String> = row.try_get("owner")?;
+            if let Some(stats) = by_owner.get_mut(&owner) {
+                stats.tool_uses = row.try_get("tool_uses")?;
+                stats.failed_tool_uses = row.try_get("failed")?;
+            }
+        }
+
+        // `None` sorts first, so your own sessions lead
+        Ok(by_owner
+            .into_values()
+            .map(|mut stats| {
+                stats.providers = sort_providers(std::mem::take(&mut stats.providers));
+                stats
+            })
+            .collect())
+    }
+}
+
+fn validate_owner(owner: &str) -> AnyhowResult<String> {
+    let owner = owner.trim();
+    if owner.is_empty() {
+        anyhow::bail!("Owner name must not be empty");
+    }
+    if RESERVED_OWNERS
+        .iter()
+        .any(|reserved| owner.eq_ignore_ascii_case(reserved))
+    {
+        anyhow::bail!("'{owner}' is reserved for owner filters; use the teammate's name");
+    }
+    Ok(owner.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{ChatSessionRepository, MessageRepository, ToolOperationRepository};
+    use crate::models::{ChatSession, Message, MessageRole, Provider, ToolOperation};
+    use crate::services::export_sqlite_snapshot;
+
+    async fn add_session(db: &DatabaseManager, messages: u32, failed_tool: bool) -> ChatSession {
+        let session = ChatSession::new(
+            Provider::ClaudeCode,
+            format!("/{}.jsonl", uuid::Uuid::new_v4()),
+            "hash".to_string(),
+            Utc::now(),
+        );
+        ChatSessionRepository::new(db)
+            .create(&session)
+            .await
+            .unwrap();
+        let mut operation =
+            ToolOperation::new("toolu_1".to_string(), "Bash".to_string(), Utc::now());
+        operation.success = Some(!failed_tool);
+        ToolOperationRepository::new(db)
+            .create(&operation)
+            .await
+            .unwrap();
+        let messages: Vec<Message> = (1..=messages)
+            .map(|n| {
+                let message = Message::new(
+                    session.id,
+                    MessageRole::Assistant,
+                    format!("message {n}"),
+                    Utc::now(),
+                    n,
+                );
+                if n == 1 {
+                    message.with_tool_operation(operation.id)
+                } else {
+                    message
+                }
+            })
+            .collect();
+        MessageRepository::new(db)
+            .bulk_create(&messages)
+            .await
+            .unwrap();
+        ChatSessionRepository::new(db)
+            .refresh_stats(&session.id)
+            .await
+            .unwrap();
+        session
+    }
+
+    #[tokio::test]
+    async fn test_import_stats_and_remove() {
+        let dir = tempfile::tempdir().unwrap();
+        let alice = DatabaseManager::new(dir.path().join("alice.db"))
+            .await
+            .unwrap();
+        let shared = add_session(&alice, 2, true).await;
+        let export = dir.path().join("alice-export.db");
+        export_sqlite_snapshot(&alice, &[shared.id], &export)
+            .await
+            .unwrap();
+
+        let mine = Arc::new(
+            DatabaseManager::new(dir.path().join("mine.db"))
+                .await
+                .unwrap(),
+        );
+        add_session(&mine, 3, false).await;
+        let team = TeamService::new(mine.clone());
+
+        assert!(team.import(&export, "team").await.is_err());
+        let report = team.import(&export, "alice").await.unwrap();
+        assert_eq!(report.added, 1);
+        let imported = ChatSessionRepository::new(&mine)
+            .get_by_id(&shared.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(imported.owner.as_deref(), Some("alice"));
+        assert_eq!(imported.message_count, 2);
+
+        // Re-importing the same export changes nothing, and another
+        // teammate can't claim alice's session
+        assert_eq!(team.import(&export, "alice").await.unwrap().kept, 1);
+        assert_eq!(team.import(&export, "bob").await.unwrap().kept, 1);
+
+        let stats = team.stats().await.unwrap();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].owner, None);
+        assert_eq!((stats[0].sessions, stats[0].messages), (1, 3));
+        assert_eq!(stats[0].tool_failure_rate(), Some(0.0));
+        assert_eq!(stats[1].owner.as_deref(), Some("alice"));
+        assert_eq!(stats[1].providers, vec![("Claude Code".to_string(), 1)]);
+        assert_eq!(stats[1].tool_failure_rate(), Some(1.0));
+        let total = OwnerStats::combine(None, &stats);
+        assert_eq!((total.sessions, total.tool_uses), (2, 2));
+
+        assert_eq!(team.remove("alice").await.unwrap(), 1);
+        assert_eq!(team.stats().await.unwrap().len(), 1);
+        let orphaned: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tool_operations")
+            .fetch_one(mine.pool())
+            .await
+            .unwrap();
+        assert_eq!(orphaned, 1);
+    }
+}
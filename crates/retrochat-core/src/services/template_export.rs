@@ -0,0 +1,235 @@
+//! Render exported messages through a user's Handlebars template
+//!
+//! The template sees this context (fields as in `--format jsonl`):
+//!
+//! ```text
+//! generated_at       time of the export
+//! session_count      number of sessions with exported messages
+//! message_count      number of exported messages
+//! sessions[]         each session, with
+//!   messages[]         its exported messages, each with `tool` (the tool
+//!                      operation it called or answered, if any)
+//!   tool_operations[]  every tool operation of the session
+//! messages[]         all exported messages in export order, each with
+//!                    `tool` and `session` (id, provider, project_name, title)
+//! ```
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::database::{ChatSessionRepository, DatabaseManager, ToolOperationRepository};
+use crate::models::{Message, ToolOperation};
+use crate::utils::template::Template;
+
+/// Build the template context for exported messages
+pub async fn build_template_context(
+    db_manager: &DatabaseManager,
+    messages: &[Message],
+) -> Result<Value> {
+    let session_repo = ChatSessionRepository::new(db_manager);
+    let tool_repo = ToolOperationRepository::new(db_manager);
+
+    // Sessions in the order their first message was exported
+    let mut session_ids: Vec<Uuid> = Vec::new();
+    for message in messages {
+        if !session_ids.contains(&message.session_id) {
+            session_ids.push(message.session_id);
+        }
+    }
+
+    let mut sessions = Vec::with_capacity(session_ids.len());
+    let mut session_refs = HashMap::new();
+    let mut tools: HashMap<Uuid, ToolOperation> = HashMap::new();
+    for session_id in &session_ids {
+        let Some(session) = session_repo.get_by_id(session_id).await? else {
+            continue;
+        };
+        let operations = tool_repo.get_by_session(session_id).await?;
+        tools.extend(operations.iter().map(|op| (op.id, op.clone())));
+
+        session_refs.insert(
+            *session_id,
+            json!({
+                "id": session.id,
+                "provider": session.provider.to_string(),
+                "project_name": session.project_name,
+                "title": session.title,
+            }),
+        );
+        let mut value = serde_json::to_value(&session)?;
+        value["tool_operations"] = serde_json::to_value(&operations)?;
+        sessions.push((*session_id, value));
+    }
+
+    let mut by_session: HashMap<Uuid, Vec<Value>> = HashMap::new();
+    let mut all_messages = Vec::with_capacity(messages.len());
+    for message in messages {
+        let mut value = serde_json::to_value(message)?;
+        value["tool"] = match message.tool_operation_id.and_then(|id| tools.get(&id)) {
+            Some(tool) => serde_json::to_value(tool)?,
+            None => Value::Null,
+        };
+        by_session
+            .entry(message.session_id)
+            .or_default()
+            .push(value.clone());
+
+        value["session"] = session_refs
+            .get(&message.session_id)
+            .cloned()
+            .unwrap_or(Value::Null);
+        all_messages.push(value);
+    }
+
+    let sessions: Vec<Value> = sessions
+        .into_iter()
+        .map(|(id, mut value)| {
+            value["messages"] = Value::Array(by_session.remove(&id).unwrap_or_default());
+            value
+        })
+        .collect();
+
+    Ok(json!({
+        "generated_at": Utc::now().to_rfc3339(),
+        "session_count": sessions.len(),
+        "message_count": all_messages.len(),
+        "sessions": sessions,
+        "messages": all_messages,
+    }))
+}
+
+/// Render exported messages with the template in `template_path`
+pub async fn render_template_export(
+    db_manager: &DatabaseManager,
+    template_path: &std::path::Path,
+    messages: &[Message],
+) -> Result<String> {
+    let source = std::fs::read_to_string(template_path)
+        .with_context(|| format!("Failed to read template {}", template_path.display()))?;
+    // Parse before querying, so a broken template fails fast
+    let template = Template::parse(&source)
+        .with_context(|| format!("Invalid template {}", template_path.display()))?;
+    let context = build_template_context(db_manager, messages).await?;
+    Ok(template.render(&context))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{MessageRepository, ProjectRepository};
+    use crate::models::message::MessageType;
+    use crate::models::{ChatSession, MessageRole, Project, Provider};
+
+    #[tokio::test]
+    async fn test_render_template_export() {
+        let db = DatabaseManager::open_in_memory().await.unwrap();
+        ProjectRepository::new(&db)
+            .create(&Project::new("retrochat".to_string()))
+            .await
+            .unwrap();
+        let mut session = ChatSession::new(
+            Provider::ClaudeCode,
+            "/tmp/session.jsonl".to_string(),
+            "hash".to_string(),
+            Utc::now(),
+        );
+        session.project_name = Some("retrochat".to_string());
+        session.message_count = 2;
+        ChatSessionRepository::new(&db)
+            .create(&session)
+            .await
+            .unwrap();
+        let messages = vec![
+            Message::new(
+                session.id,
+                MessageRole::User,
+                "Fix the build".to_string(),
+                Utc::now(),
+                1,
+            ),
+            Message::new(
+                session.id,
+                MessageRole::Assistant,
+                "Done <3".to_string(),
+                Utc::now(),
+                2,
+            ),
+        ];
+        let message_repo = MessageRepository::new(&db);
+        for message in &messages {
+            message_repo.create(message).await.unwrap();
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let template_path = dir.path().join("retro.hbs");
+        std::fs::write(
+            &template_path,
+            "{{#each sessions}}\n## {{project_name}} ({{message_count}} messages)\n{{#each messages}}\n- {{role}}: {{content}}\n{{/each}}\n{{/each}}\n",
+        )
+        .unwrap();
+
+        let output = render_template_export(&db, &template_path, &messages)
+            .await
+            .unwrap();
+        assert_eq!(
+            output,
+            "## retrochat (2 messages)\n- User: Fix the build\n- Assistant: Done &lt;3\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tool_operation_linked_twice_is_listed_once() {
+        let db = DatabaseManager::open_in_memory().await.unwrap();
+        let session = ChatSession::new(
+            Provider::ClaudeCode,
+            "/tmp/session.jsonl".to_string(),
+            "hash".to_string(),
+            Utc::now(),
+        );
+        ChatSessionRepository::new(&db)
+            .create(&session)
+            .await
+            .unwrap();
+        let operation = ToolOperation::new("toolu_1".to_string(), "Bash".to_string(), Utc::now());
+        ToolOperationRepository::new(&db)
+            .create(&operation)
+            .await
+            .unwrap();
+
+        // Claude Code links both the request and the result to the operation
+        let messages = vec![
+            Message::new(
+                session.id,
+                MessageRole::Assistant,
+                "[Tool Use: Bash]".to_string(),
+                Utc::now(),
+                1,
+            )
+            .with_message_type(MessageType::ToolRequest)
+            .with_tool_operation(operation.id),
+            Message::new(
+                session.id,
+                MessageRole::User,
+                "[Tool Result]".to_string(),
+                Utc::now(),
+                2,
+            )
+            .with_message_type(MessageType::ToolResult)
+            .with_tool_operation(operation.id),
+        ];
+        let message_repo = MessageRepository::new(&db);
+        for message in &messages {
+            message_repo.create(message).await.unwrap();
+        }
+
+        let context = build_template_context(&db, &messages).await.unwrap();
+        let tool_operations = context["sessions"][0]["tool_operations"]
+            .as_array()
+            .unwrap();
+        assert_eq!(tool_operations.len(), 1);
+        assert_eq!(tool_operations[0]["tool_name"], "Bash");
+    }
+}
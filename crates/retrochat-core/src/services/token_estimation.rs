@@ -0,0 +1,130 @@
+use anyhow::Result as AnyhowResult;
+use serde::{Deserialize, Serialize};
+
+use crate::database::{ChatSessionRepository, DatabaseManager, MessageRepository, TokenAccuracy};
+use crate::utils::token_estimate::estimate_tokens;
+
+/// What a token estimate backfill changed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenBackfillReport {
+    pub messages_estimated: usize,
+    /// Sessions whose cached statistics were refreshed
+    pub sessions_updated: usize,
+}
+
+/// Service maintaining per-message token estimates
+///
+/// New messages are estimated at import time; this fills in messages imported
+/// before estimates existed and re-estimates after the heuristic changes.
+pub struct TokenEstimationService {
+    session_repo: ChatSessionRepository,
+    message_repo: MessageRepository,
+}
+
+impl TokenEstimationService {
+    pub fn new(db: &DatabaseManager) -> Self {
+        Self {
+            session_repo: ChatSessionRepository::new(db),
+            message_repo: MessageRepository::new(db),
+        }
+    }
+
+    /// Estimate messages without an estimate, or every message with `force`
+    pub async fn backfill(&self, force: bool) -> AnyhowResult<TokenBackfillReport> {
+        let mut report = TokenBackfillReport::default();
+
+        for session in self.session_repo.get_all().await? {
+            let estimates: Vec<_> = self
+                .message_repo
+                .get_by_session(&session.id)
+                .await?
+                .into_iter()
+                .filter(|message| force || message.estimated_tokens.is_none())
+                .map(|message| {
+                    (
+                        message.id,
+                        estimate_tokens(&message.content, &session.provider),
+                    )
+                })
+                .collect();
+            if estimates.is_empty() {
+                continue;
+            }
+
+            self.message_repo.set_estimated_tokens(&estimates).await?;
+            self.session_repo.refresh_stats(&session.id).await?;
+            report.messages_estimated += estimates.len();
+            report.sessions_updated += 1;
+        }
+
+        Ok(report)
+    }
+
+    /// Estimates compared with reported usage, per provider
+    pub async fn accuracy(&self) -> AnyhowResult<Vec<TokenAccuracy>> {
+        self.message_repo.token_accuracy().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ChatSession, Message, MessageRole, Provider};
+    use chrono::Utc;
+
+    #[tokio::test]
+    async fn test_backfill_and_accuracy() {
+        let db = DatabaseManager::open_in_memory().await.unwrap();
+        let session = ChatSession::new(
+            Provider::GeminiCLI,
+            "/gemini.json".to_string(),
+            "hash".to_string(),
+            Utc::now(),
+        );
+        ChatSessionRepository::new(&db)
+            .create(&session)
+            .await
+            .unwrap();
+
+        let message_repo = MessageRepository::new(&db);
+        let reported = Message::new(
+            session.id,
+            MessageRole::User,
+            "Please fix the failing login test in the auth module.".to_string(),
+            Utc::now(),
+            1,
+        )
+        .with_token_count(12);
+        let unreported = Message::new(
+            session.id,
+            MessageRole::Assistant,
+            "Done.".to_string(),
+            Utc::now(),
+            2,
+        );
+        message_repo
+            .bulk_create(&[reported, unreported])
+            .await
+            .unwrap();
+
+        let service = TokenEstimationService::new(&db);
+        assert!(service.accuracy().await.unwrap().is_empty());
+
+        let report = service.backfill(false).await.unwrap();
+        assert_eq!(report.messages_estimated, 2);
+        assert_eq!(report.sessions_updated, 1);
+        assert_eq!(service.backfill(false).await.unwrap().messages_estimated, 0);
+        assert_eq!(service.backfill(true).await.unwrap().messages_estimated, 2);
+
+        let messages = message_repo.get_by_session(&session.id).await.unwrap();
+        assert!(messages.iter().all(|m| m.estimated_tokens.is_some()));
+        assert_eq!(messages[0].tokens(), Some(12));
+
+        let accuracy = service.accuracy().await.unwrap();
+        assert_eq!(accuracy.len(), 1);
+        assert_eq!(accuracy[0].provider, "Gemini CLI");
+        assert_eq!(accuracy[0].messages, 1);
+        assert_eq!(accuracy[0].reported_tokens, 12);
+        assert!((accuracy[0].ratio() - 1.0).abs() < 0.5);
+    }
+}
@@ -0,0 +1,549 @@
+//! Topic clustering of sessions
+//!
+//! Session summary embeddings (kept by [`super::embedding_index`]) are
+//! grouped with k-means over cosine similarity. Each cluster gets a short
+//! label, written by the LLM when one is given and made from the most common
+//! title words otherwise. A cluster whose members mostly match an existing
+//! topic keeps that topic's ID and label, so re-clustering does not rename
+//! topics or break `topic:` filters.
+
+use anyhow::{Context, Result as AnyhowResult};
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::embedding::cosine_similarity;
+use crate::database::{
+    ChatSessionRepository, DatabaseManager, EmbeddingKind, EmbeddingRepository, EventRepository,
+    SessionSummaryRepository, TopicAssignment, TopicRepository,
+};
+use crate::models::{ChatSession, EventKind, Topic, TopicLabelSource};
+use crate::services::llm::structured::parse_structured;
+use crate::services::llm::{CachingClient, GenerateRequest, LlmClient};
+use crate::utils::locale::Language;
+
+/// Upper bound on the number of topics picked automatically
+const MAX_TOPICS: usize = 20;
+
+/// K-means stops after this many rounds even if assignments still move
+const MAX_ITERATIONS: usize = 50;
+
+/// Sessions closest to the centroid shown to the LLM when labelling
+const LABEL_SAMPLE_SIZE: usize = 8;
+
+/// Characters of each session summary shown to the LLM
+const SUMMARY_CHARS: usize = 200;
+
+/// Member overlap (Jaccard) at which a cluster inherits an existing topic
+const CARRY_OVER_OVERLAP: f64 = 0.5;
+
+/// Words in a keyword label
+const LABEL_KEYWORDS: usize = 3;
+
+const STOPWORDS: &[&str] = &[
+    "the", "and", "for", "with", "from", "into", "that", "this", "add", "fix", "use", "using",
+    "make", "update", "how", "what", "why", "when", "are", "not", "new", "can", "you", "all",
+    "session", "code",
+];
+
+/// What a clustering run produced
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TopicReport {
+    pub topics: usize,
+    /// Sessions assigned to a topic
+    pub sessions: usize,
+    /// Topics that kept the ID and label of an earlier topic
+    pub carried_over: usize,
+    /// New topics labelled by the LLM (the rest used title keywords)
+    pub labeled_by_llm: usize,
+}
+
+/// Label the LLM is asked for
+#[derive(Debug, Deserialize)]
+struct TopicLabel {
+    label: String,
+    description: Option<String>,
+}
+
+/// One k-means cluster, members as indices into the clustered sessions
+struct Cluster {
+    members: Vec<(usize, f32)>,
+}
+
+/// Service grouping sessions into labelled topics
+pub struct TopicService {
+    embedding_repo: EmbeddingRepository,
+    topic_repo: TopicRepository,
+    session_repo: ChatSessionRepository,
+    summary_repo: SessionSummaryRepository,
+    event_repo: EventRepository,
+    llm_client: Option<Arc<dyn LlmClient>>,
+    language: Language,
+}
+
+impl TopicService {
+    /// Without an LLM client, topics are labelled with title keywords
+    pub fn new(db: &DatabaseManager, llm_client: Option<Arc<dyn LlmClient>>) -> Self {
+        Self {
+            embedding_repo: EmbeddingRepository::new(db),
+            topic_repo: TopicRepository::new(db),
+            session_repo: ChatSessionRepository::new(db),
+            summary_repo: SessionSummaryRepository::new(db),
+            event_repo: EventRepository::new(db),
+            llm_client: llm_client.map(|client| CachingClient::wrap(client, db)),
+            language: Language::load(),
+        }
+    }
+
+    /// Topics, largest first
+    pub async fn list(&self) -> AnyhowResult<Vec<Topic>> {
+        self.topic_repo.list().await
+    }
+
+    /// Find a topic by ID or label
+    pub async fn find(&self, key: &str) -> AnyhowResult<Option<Topic>> {
+        self.topic_repo.find(key).await
+    }
+
+    /// Re-cluster if the topics are older than `max_age` or were never built
+    pub async fn refresh_if_stale(&self, max_age: Duration) -> AnyhowResult<Option<TopicReport>> {
+        let stale = match self.topic_repo.last_clustered_at().await? {
+            Some(clustered_at) => Utc::now() - clustered_at > max_age,
+            None => true,
+        };
+        if !stale {
+            return Ok(None);
+        }
+        self.refresh(None).await.map(Some)
+    }
+
+    /// Cluster every session with an indexed summary into `clusters` topics
+    /// (picked from the number of sessions when `None`), replacing the
+    /// current topics
+    pub async fn refresh(&self, clusters: Option<usize>) -> AnyhowResult<TopicReport> {
+        let sessions: HashMap<Uuid, ChatSession> = self
+            .session_repo
+            .get_all()
+            .await?
+            .into_iter()
+            .map(|s| (s.id, s))
+            .collect();
+
+        let mut seen = HashSet::new();
+        let mut session_ids = Vec::new();
+        let mut vectors = Vec::new();
+        for embedding in self.embedding_repo.list(EmbeddingKind::Session).await? {
+            let Ok(id) = Uuid::parse_str(&embedding.session_id) else {
+                continue;
+            };
+            if sessions.contains_key(&id) && seen.insert(id) {
+                session_ids.push(id);
+                vectors.push(embedding.vector);
+            }
+        }
+
+        if vectors.len() < 2 {
+            self.topic_repo.replace_all(&[], &[]).await?;
+            return Ok(TopicReport::default());
+        }
+
+        let k = clusters
+            .unwrap_or_else(|| default_cluster_count(vectors.len()))
+            .clamp(1, vectors.len());
+        let (assigned, centroids) = kmeans(&vectors, k);
+
+        let mut clusters: Vec<Cluster> = (0..k).map(|_| Cluster { members: vec![] }).collect();
+        for (index, &cluster) in assigned.iter().enumerate() {
+            let similarity = cosine_similarity(&vectors[index], &centroids[cluster]);
+            clusters[cluster].members.push((index, similarity));
+        }
+        clusters.retain(|c| !c.members.is_empty());
+        clusters.sort_by_key(|c| std::cmp::Reverse(c.members.len()));
+
+        let previous = self.previous_topics().await?;
+        let mut next_id = previous.iter().map(|(t, _)| t.id).max().unwrap_or(0) + 1;
+        let mut taken = HashSet::new();
+        let clustered_at = Utc::now();
+        let mut report = TopicReport::default();
+        let mut topics = Vec::new();
+        let mut assignments = Vec::new();
+
+        for cluster in &mut clusters {
+            cluster.members.sort_by(|a, b| b.1.total_cmp(&a.1));
+            let members: HashSet<Uuid> = cluster
+                .members
+                .iter()
+                .map(|(i, _)| session_ids[*i])
+                .collect();
+
+            let inherited = previous
+                .iter()
+                .filter(|(topic, _)| !taken.contains(&topic.id))
+                .map(|(topic, old)| (topic, jaccard(&members, old)))
+                .filter(|(_, overlap)| *overlap >= CARRY_OVER_OVERLAP)
+                .max_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(topic, _)| topic);
+
+            let topic = match inherited {
+                Some(old) => {
+                    taken.insert(old.id);
+                    report.carried_over += 1;
+                    Topic {
+                        session_count: members.len() as i64,
+                        clustered_at,
+                        ..old.clone()
+                    }
+                }
+                None => {
+                    let id = next_id;
+                    next_id += 1;
+                    let sample: Vec<&ChatSession> = cluster
+                        .members
+                        .iter()
+                        .take(LABEL_SAMPLE_SIZE)
+                        .filter_map(|(i, _)| sessions.get(&session_ids[*i]))
+                        .collect();
+                    let (label, description, label_source) =
+                        self.label_cluster(id, &sample).await?;
+                    if label_source == TopicLabelSource::Llm {
+                        report.labeled_by_llm += 1;
+                    }
+                    Topic {
+                        id,
+                        label,
+                        description,
+                        label_source,
+                        session_count: members.len() as i64,
+                        clustered_at,
+                    }
+                }
+            };
+
+            assignments.extend(
+                cluster
+                    .members
+                    .iter()
+                    .map(|(i, similarity)| TopicAssignment {
+                        session_id: session_ids[*i],
+                        topic_id: topic.id,
+                        similarity: *similarity,
+                    }),
+            );
+            topics.push(topic);
+        }
+
+        self.topic_repo.replace_all(&topics, &assignments).await?;
+
+        report.topics = topics.len();
+        report.sessions = assignments.len();
+        self.event_repo
+            .record(
+                EventKind::TopicsRefreshed,
+                None,
+                serde_json::to_value(report)?,
+            )
+            .await;
+        Ok(report)
+    }
+
+    /// Current topics with their member sessions
+    async fn previous_topics(&self) -> AnyhowResult<Vec<(Topic, HashSet<Uuid>)>> {
+        let mut members: HashMap<i64, HashSet<Uuid>> = HashMap::new();
+        for assignment in self.topic_repo.assignments().await? {
+            members
+                .entry(assignment.topic_id)
+                .or_default()
+                .insert(assignment.session_id);
+        }
+
+        Ok(self
+            .topic_repo
+            .list()
+            .await?
+            .into_iter()
+            .map(|topic| {
+                let sessions = members.remove(&topic.id).unwrap_or_default();
+                (topic, sessions)
+            })
+            .collect())
+    }
+
+    /// Label a new cluster from its most central sessions
+    async fn label_cluster(
+        &self,
+        id: i64,
+        sample: &[&ChatSession],
+    ) -> AnyhowResult<(String, Option<String>, TopicLabelSource)> {
+        let mut documents = Vec::new();
+        for session in sample {
+            let summary = self.summary_repo.get_by_session(&session.id).await?;
+            let title = summary
+                .as_ref()
+                .map(|s| s.title.clone())
+                .or_else(|| session.title.clone())
+                .or_else(|| session.project_name.clone());
+            let Some(title) = title else {
+                continue;
+            };
+            let summary_text = summary.map(|s| s.summary.chars().take(SUMMARY_CHARS).collect());
+            documents.push((title, summary_text));
+        }
+
+        if let Some(client) = &self.llm_client {
+            match label_with_llm(client.as_ref(), &documents, self.language).await {
+                Ok(label) => {
+                    return Ok((label.label, label.description, TopicLabelSource::Llm));
+                }
+                Err(e) => {
+                    tracing::warn!(topic = id, error = %e, "Falling back to keyword topic label");
+                }
+            }
+        }
+
+        let titles: Vec<&str> = documents.iter().map(|(title, _)| title.as_str()).collect();
+        let label = keyword_label(&titles).unwrap_or_else(|| format!("Topic {id}"));
+        Ok((label, None, TopicLabelSource::Keywords))
+    }
+}
+
+async fn label_with_llm(
+    client: &dyn LlmClient,
+    documents: &[(String, Option<String>)],
+    language: Language,
+) -> AnyhowResult<TopicLabel> {
+    if documents.is_empty() {
+        anyhow::bail!("No session titles to label the topic with");
+    }
+
+    let mut prompt = String::from(
+        "These coding assistant sessions were grouped together because they are about the same subject:\n\n",
+    );
+    for (title, summary) in documents {
+        match summary {
+            Some(summary) => prompt.push_str(&format!("- {title}: {summary}\n")),
+            None => prompt.push_str(&format!("- {title}\n")),
+        }
+    }
+    prompt.push_str(
+        "\nName the subject with a short topic label (2 to 5 words, no trailing punctuation) \
+         and describe it in one sentence. Respond with JSON.",
+    );
+
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "label": { "type": "string" },
+            "description": { "type": "string" }
+        },
+        "required": ["label", "description"]
+    });
+    let request = GenerateRequest::new(language.localize_prompt(prompt))
+        .with_max_tokens(200)
+        .with_temperature(0.2)
+        .with_response_schema(schema);
+    let response = client.generate(request).await?;
+
+    let label: TopicLabel =
+        parse_structured(&response.text).context("LLM response did not contain a topic label")?;
+    if label.label.trim().is_empty() {
+        anyhow::bail!("LLM returned an empty topic label");
+    }
+    Ok(TopicLabel {
+        label: label.label.trim().trim_end_matches('.').to_string(),
+        description: label.description.filter(|d| !d.trim().is_empty()),
+    })
+}
+
+/// Roughly sqrt(n / 2) topics, which keeps clusters readable as n grows
+fn default_cluster_count(sessions: usize) -> usize {
+    ((sessions as f64 / 2.0).sqrt().round() as usize).clamp(2, MAX_TOPICS)
+}
+
+/// Group `vectors` into `k` clusters by cosine similarity, returning each
+/// vector's cluster and the cluster centroids
+///
+/// Centroids start at mutually distant vectors (farthest-point seeding), so
+/// the result is deterministic for a given input order.
+fn kmeans(vectors: &[Vec<f32>], k: usize) -> (Vec<usize>, Vec<Vec<f32>>) {
+    let mut centroids = vec![vectors[0].clone()];
+    while centroids.len() < k {
+        let farthest = vectors
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (i, nearest(v, &centroids).1))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i)
+            .unwrap_or(0);
+        centroids.push(vectors[farthest].clone());
+    }
+
+    let mut assigned = vec![usize::MAX; vectors.len()];
+    for _ in 0..MAX_ITERATIONS {
+        let mut changed = false;
+        for (i, vector) in vectors.iter().enumerate() {
+            let cluster = nearest(vector, &centroids).0;
+            if assigned[i] != cluster {
+                assigned[i] = cluster;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+
+        for (cluster, centroid) in centroids.iter_mut().enumerate() {
+            let mut sum = vec![0.0f32; centroid.len()];
+            let mut count = 0;
+            for (vector, _) in vectors.iter().zip(&assigned).filter(|(_, &a)| a == cluster) {
+                for (s, v) in sum.iter_mut().zip(vector) {
+                    *s += v;
+                }
+                count += 1;
+            }
+            // An empty cluster keeps its old centroid
+            if count > 0 {
+                *centroid = sum;
+            }
+        }
+    }
+
+    (assigned, centroids)
+}
+
+/// Index of the most similar centroid and the similarity
+fn nearest(vector: &[f32], centroids: &[Vec<f32>]) -> (usize, f32) {
+    centroids
+        .iter()
+        .enumerate()
+        .map(|(i, c)| (i, cosine_similarity(vector, c)))
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap_or((0, 0.0))
+}
+
+fn jaccard(a: &HashSet<Uuid>, b: &HashSet<Uuid>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+/// The words that appear in the most titles, e.g. "login, oauth, tokens"
+fn keyword_label(titles: &[&str]) -> Option<String> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for title in titles {
+        let words: HashSet<String> = title
+            .split(|c: char| !c.is_alphanumeric())
+            .map(|w| w.to_lowercase())
+            .filter(|w| w.chars().count() >= 3 && !STOPWORDS.contains(&w.as_str()))
+            .filter(|w| !w.chars().all(|c| c.is_ascii_digit()))
+            .collect();
+        for word in words {
+            *counts.entry(word).or_default() += 1;
+        }
+    }
+
+    let mut ranked: Vec<(String, usize)> = counts.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    let words: Vec<String> = ranked
+        .into_iter()
+        .take(LABEL_KEYWORDS)
+        .map(|(w, _)| w)
+        .collect();
+    (!words.is_empty()).then(|| words.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Provider;
+
+    async fn insert_session(db: &DatabaseManager, title: &str, vector: &[f32]) -> Uuid {
+        let mut session = ChatSession::new(
+            Provider::ClaudeCode,
+            format!("/{title}.jsonl"),
+            title.to_string(),
+            Utc::now(),
+        );
+        session.title = Some(title.to_string());
+        ChatSessionRepository::new(db)
+            .create(&session)
+            .await
+            .unwrap();
+        EmbeddingRepository::new(db)
+            .upsert(
+                EmbeddingKind::Session,
+                &format!("summary-{}", session.id),
+                &session.id.to_string(),
+                "h",
+                vector,
+            )
+            .await
+            .unwrap();
+        session.id
+    }
+
+    #[test]
+    fn test_kmeans_separates_groups() {
+        let vectors = vec![
+            vec![1.0, 0.0, 0.0],
+            vec![0.0, 1.0, 0.1],
+            vec![0.9, 0.1, 0.0],
+            vec![0.1, 0.9, 0.0],
+        ];
+        let (assigned, _) = kmeans(&vectors, 2);
+        assert_eq!(assigned[0], assigned[2]);
+        assert_eq!(assigned[1], assigned[3]);
+        assert_ne!(assigned[0], assigned[1]);
+    }
+
+    #[test]
+    fn test_keyword_label() {
+        let titles = [
+            "Fix OAuth login redirect",
+            "Login token refresh",
+            "OAuth scopes",
+        ];
+        assert_eq!(
+            keyword_label(&titles),
+            Some("login, oauth, redirect".to_string())
+        );
+        assert_eq!(keyword_label(&["Fix the code"]), None);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_clusters_and_keeps_topic_ids() {
+        let db = DatabaseManager::open_in_memory().await.unwrap();
+        let login = insert_session(&db, "Login token refresh", &[1.0, 0.0, 0.0]).await;
+        insert_session(&db, "OAuth login redirect", &[0.9, 0.1, 0.0]).await;
+        insert_session(&db, "Docker build cache", &[0.0, 1.0, 0.1]).await;
+        insert_session(&db, "Docker compose networking", &[0.1, 0.9, 0.0]).await;
+
+        let service = TopicService::new(&db, None);
+        let report = service.refresh(Some(2)).await.unwrap();
+        assert_eq!(report.topics, 2);
+        assert_eq!(report.sessions, 4);
+        assert_eq!(report.labeled_by_llm, 0);
+
+        let topics = service.list().await.unwrap();
+        let login_topic = TopicRepository::new(&db).session_topics().await.unwrap()[&login].clone();
+        assert!(login_topic.1.contains("login"));
+        assert!(topics.iter().any(|t| t.label.contains("docker")));
+
+        let again = service.refresh(Some(2)).await.unwrap();
+        assert_eq!(again.carried_over, 2);
+        let labels = |topics: Vec<Topic>| -> Vec<(i64, String)> {
+            topics.into_iter().map(|t| (t.id, t.label)).collect()
+        };
+        assert_eq!(labels(service.list().await.unwrap()), labels(topics));
+
+        assert!(service
+            .refresh_if_stale(Duration::days(7))
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
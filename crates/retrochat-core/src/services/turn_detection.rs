@@ -43,7 +43,7 @@ impl TurnDetector {
 
     /// Pure function to detect turns from a list of messages
     /// This is separated for easier testing
-    fn detect_turns_from_messages(messages: &[Message]) -> Vec<DetectedTurn> {
+    pub fn detect_turns_from_messages(messages: &[Message]) -> Vec<DetectedTurn> {
         if messages.is_empty() {
             return Vec::new();
         }
@@ -156,7 +156,7 @@ impl TurnMetrics {
                 _ => {}
             }
 
-            if let Some(tokens) = message.token_count {
+            if let Some(tokens) = message.tokens() {
                 metrics.total_tokens += tokens as i64;
             }
         }
@@ -6,8 +6,10 @@ use uuid::Uuid;
 use crate::database::{DatabaseManager, MessageRepository, TurnSummaryRepository};
 use crate::models::message::MessageType;
 use crate::models::{DetectedTurn, Message, MessageRole, TurnSummary, TurnType};
-use crate::services::llm::{GenerateRequest, LlmClient};
+use crate::services::embedding_index::EmbeddingIndexService;
+use crate::services::llm::{CachingClient, GenerateRequest, LlmClient};
 use crate::services::turn_detection::TurnDetector;
+use crate::utils::locale::Language;
 
 /// Service for generating LLM-based turn summaries
 pub struct TurnSummarizer {
@@ -15,6 +17,9 @@ pub struct TurnSummarizer {
     turn_summary_repo: TurnSummaryRepository,
     turn_detector: TurnDetector,
     llm_client: Arc<dyn LlmClient>,
+    /// None when the configured embedder could not be created
+    index: Option<EmbeddingIndexService>,
+    language: Language,
 }
 
 impl TurnSummarizer {
@@ -23,7 +28,11 @@ impl TurnSummarizer {
             message_repo: MessageRepository::new(db),
             turn_summary_repo: TurnSummaryRepository::new(db),
             turn_detector: TurnDetector::new(db),
-            llm_client,
+            llm_client: CachingClient::wrap(llm_client, db),
+            index: EmbeddingIndexService::from_config(db)
+                .map_err(|e| tracing::warn!(error = %e, "Summary embeddings will not be indexed"))
+                .ok(),
+            language: Language::load(),
         }
     }
 
@@ -90,9 +99,21 @@ impl TurnSummarizer {
             }
         }
 
+        self.index_embeddings(session_id).await;
+
         Ok(summarized_count)
     }
 
+    /// Embed the session's new summaries; a failure only costs search quality
+    async fn index_embeddings(&self, session_id: &Uuid) {
+        let Some(index) = &self.index else {
+            return;
+        };
+        if let Err(e) = index.index_session(session_id).await {
+            tracing::warn!(session_id = %session_id, error = %e, "Failed to index summary embeddings");
+        }
+    }
+
     /// Summarize a single turn
     async fn summarize_turn(
         &self,
@@ -100,7 +121,9 @@ impl TurnSummarizer {
         turn: &DetectedTurn,
         messages: &[&Message],
     ) -> AnyhowResult<TurnSummary> {
-        let prompt = self.build_turn_prompt(messages);
+        let prompt = self
+            .language
+            .localize_prompt(self.build_turn_prompt(messages));
 
         let request = GenerateRequest::new(prompt)
             .with_max_tokens(1024)
@@ -143,6 +166,8 @@ impl TurnSummarizer {
                 MessageType::ToolResult => " [Tool Result]",
                 MessageType::Thinking => " [Thinking]",
                 MessageType::SlashCommand => " [Command]",
+                MessageType::HookEvent => " [Hook]",
+                MessageType::SystemReminder => " [Reminder]",
                 MessageType::SimpleMessage => "",
             };
 
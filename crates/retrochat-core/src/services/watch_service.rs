@@ -6,10 +6,104 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
+use crate::config::WatchConfig;
 use crate::models::provider::registry::ProviderRegistry;
 use crate::models::Provider;
-use crate::services::ParserService;
+use crate::services::{ImportFileRequest, ImportService, ParserService};
+
+/// How long a file has to stay unchanged before the watcher imports it;
+/// agents append to their logs line by line while a turn runs
+const IMPORT_QUIET_PERIOD: Duration = Duration::from_secs(2);
+
+/// A root path to watch, optionally pinned to a provider
+#[derive(Debug, Clone, PartialEq)]
+pub struct WatchTarget {
+    pub path: PathBuf,
+    /// Provider used for files under this root instead of auto-detection
+    pub provider: Option<Provider>,
+}
+
+impl WatchTarget {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            provider: None,
+        }
+    }
+
+    pub fn with_provider(mut self, provider: Provider) -> Self {
+        self.provider = Some(provider);
+        self
+    }
+}
+
+/// Compiled ignore globs for watcher events
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreRules {
+    patterns: Vec<glob::Pattern>,
+}
+
+impl IgnoreRules {
+    pub fn new(globs: &[String]) -> Result<Self> {
+        let patterns = globs
+            .iter()
+            .map(|g| {
+                glob::Pattern::new(&expand_home(g))
+                    .with_context(|| format!("Invalid ignore glob: {g}"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { patterns })
+    }
+
+    /// Whether the path (or its file name) matches any ignore glob
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        let options = glob::MatchOptions {
+            require_literal_separator: true,
+            ..Default::default()
+        };
+        let file_name = path.file_name().and_then(|n| n.to_str());
+
+        self.patterns.iter().any(|pattern| {
+            pattern.matches_path_with(path, options)
+                || file_name.is_some_and(|name| pattern.matches_with(name, options))
+        })
+    }
+}
+
+/// Build watch targets from the user-configured `watch.paths`
+pub fn collect_configured_targets(config: &WatchConfig) -> Vec<WatchTarget> {
+    config
+        .paths
+        .iter()
+        .map(|entry| {
+            let target = WatchTarget::new(expand_home(&entry.path));
+            match entry.provider.as_deref().map(str::parse::<Provider>) {
+                Some(Ok(provider)) if provider.is_concrete() => target.with_provider(provider),
+                _ => target,
+            }
+        })
+        .collect()
+}
+
+/// Find the provider hint of the most specific target containing `path`
+fn provider_hint<'a>(targets: &'a [WatchTarget], path: &Path) -> Option<&'a Provider> {
+    targets
+        .iter()
+        .filter(|t| path.starts_with(&t.path))
+        .max_by_key(|t| t.path.components().count())
+        .and_then(|t| t.provider.as_ref())
+}
+
+fn expand_home(path: &str) -> String {
+    if let Some(rest) = path.strip_prefix('~') {
+        if let Some(home) = dirs::home_dir() {
+            return format!("{}{rest}", home.display());
+        }
+    }
+    path.to_string()
+}
 
 /// Result of provider detection
 #[derive(Debug, Clone)]
@@ -63,8 +157,18 @@ pub fn collect_provider_paths(providers: &[Provider]) -> Result<Vec<String>> {
 }
 
 /// Watch paths for file system changes and print events
-pub async fn watch_paths_for_changes(paths: Vec<String>, verbose: bool) -> Result<()> {
-    use std::sync::mpsc::channel;
+///
+/// Uses the platform's recommended backend (FSEvents, inotify,
+/// ReadDirectoryChangesW) and skips events for paths matching `ignore`.
+///
+/// With an `importer`, created and modified session files are imported once
+/// they have been quiet for a moment, which keeps the database in sync.
+pub async fn watch_paths_for_changes(
+    targets: Vec<WatchTarget>,
+    ignore: IgnoreRules,
+    verbose: bool,
+    importer: Option<Arc<ImportService>>,
+) -> Result<()> {
     use tokio::sync::mpsc as tokio_mpsc;
 
     println!(
@@ -83,11 +187,24 @@ pub async fn watch_paths_for_changes(paths: Vec<String>, verbose: bool) -> Resul
         "📂".with(Color::Yellow),
         "Watching".bold()
     );
-    for path in &paths {
+    for target in &targets {
+        let hint = target
+            .provider
+            .as_ref()
+            .map(|p| format!(" [{p}]"))
+            .unwrap_or_default();
         println!(
-            "  {} {}",
+            "  {} {}{}",
             "└─".with(Color::DarkGrey),
-            path.as_str().with(Color::Green)
+            target.path.display().to_string().with(Color::Green),
+            hint.with(Color::Magenta)
+        );
+    }
+    if importer.is_some() {
+        println!(
+            "{} {}",
+            "📥".with(Color::Cyan),
+            "Changed session files are imported automatically".with(Color::Cyan)
         );
     }
     println!(
@@ -104,27 +221,25 @@ pub async fn watch_paths_for_changes(paths: Vec<String>, verbose: bool) -> Resul
         Arc::new(Mutex::new(HashMap::new()));
 
     // Create channels for file system events and parse requests
-    let (tx, rx) = channel();
-    let (parse_tx, mut parse_rx) = tokio_mpsc::unbounded_channel::<PathBuf>();
+    let (tx, mut rx) = tokio_mpsc::unbounded_channel::<Result<Event, notify::Error>>();
+    let (parse_tx, mut parse_rx) = tokio_mpsc::unbounded_channel::<(PathBuf, Option<Provider>)>();
 
     let mut watcher: RecommendedWatcher = Watcher::new(
         move |res: Result<Event, notify::Error>| {
-            if let Ok(event) = res {
-                let _ = tx.send(event);
-            }
+            let _ = tx.send(res);
         },
         notify::Config::default(),
     )?;
 
     // Watch all paths
-    for path_str in &paths {
-        let path = PathBuf::from(path_str);
+    for target in &targets {
+        let path = &target.path;
         if !path.exists() {
             eprintln!(
                 "{} {} {}",
                 "⚠️".with(Color::Yellow),
                 "Warning:".with(Color::Yellow).bold(),
-                format!("Path does not exist: {path_str}").with(Color::DarkGrey)
+                format!("Path does not exist: {}", path.display()).with(Color::DarkGrey)
             );
             continue;
         }
@@ -137,27 +252,44 @@ pub async fn watch_paths_for_changes(paths: Vec<String>, verbose: bool) -> Resul
         };
 
         watcher
-            .watch(&path, mode)
-            .with_context(|| format!("Failed to watch path: {path_str}"))?;
+            .watch(path, mode)
+            .with_context(|| format!("Failed to watch path: {}", path.display()))?;
     }
 
     // Spawn a task to handle async parsing
     let parse_handle = tokio::spawn(async move {
-        while let Some(path) = parse_rx.recv().await {
-            parse_and_log_sessions_async(&path).await;
+        while let Some((path, provider)) = parse_rx.recv().await {
+            parse_and_log_sessions_async(&path, provider).await;
         }
     });
 
+    // Spawn a task importing changed files
+    let (import_tx, import_handle) = match importer {
+        Some(importer) => {
+            let (import_tx, import_rx) =
+                tokio_mpsc::unbounded_channel::<(PathBuf, Option<Provider>)>();
+            let handle = tokio::spawn(import_changed_files(importer, import_rx));
+            (Some(import_tx), Some(handle))
+        }
+        None => (None, None),
+    };
+
     // Process events
-    loop {
-        match rx.recv() {
-            Ok(event) => {
+    while let Some(res) = rx.recv().await {
+        match res {
+            Ok(mut event) => {
+                event.paths.retain(|p| !ignore.is_ignored(p));
+                if event.paths.is_empty() {
+                    continue;
+                }
                 print_event(
                     &event,
+                    &targets,
                     verbose,
                     &file_cache,
                     &debounce_map,
                     parse_tx.clone(),
+                    import_tx.as_ref(),
                 );
             }
             Err(e) => {
@@ -167,14 +299,18 @@ pub async fn watch_paths_for_changes(paths: Vec<String>, verbose: bool) -> Resul
                     "Watch error:".with(Color::Red).bold(),
                     e.to_string().with(Color::DarkGrey)
                 );
-                break;
             }
         }
     }
 
     // Clean up
+    drop(watcher);
     drop(parse_tx);
     let _ = parse_handle.await;
+    drop(import_tx);
+    if let Some(handle) = import_handle {
+        let _ = handle.await;
+    }
 
     Ok(())
 }
@@ -182,10 +318,12 @@ pub async fn watch_paths_for_changes(paths: Vec<String>, verbose: bool) -> Resul
 /// Print a filesystem event
 fn print_event(
     event: &Event,
+    targets: &[WatchTarget],
     verbose: bool,
     file_cache: &Arc<Mutex<HashMap<PathBuf, String>>>,
     debounce_map: &Arc<Mutex<HashMap<PathBuf, std::time::Instant>>>,
-    parse_tx: tokio::sync::mpsc::UnboundedSender<PathBuf>,
+    parse_tx: tokio::sync::mpsc::UnboundedSender<(PathBuf, Option<Provider>)>,
+    import_tx: Option<&tokio::sync::mpsc::UnboundedSender<(PathBuf, Option<Provider>)>>,
 ) {
     let (emoji, event_kind, color) = match &event.kind {
         EventKind::Create(_) => ("✨", "CREATE", Color::Green),
@@ -198,7 +336,15 @@ fn print_event(
 
     println!("{} {}", emoji, format!("[{event_kind}]").with(color).bold());
     for path in &event.paths {
-        let detection = detect_provider(path);
+        let hint = provider_hint(targets, path).cloned();
+        let detection = match &hint {
+            Some(provider) => ProviderDetection {
+                provider: provider.to_string(),
+                file_pattern_matched: true,
+                matched_pattern: Some("watch.paths hint".to_string()),
+            },
+            None => detect_provider(path),
+        };
 
         // Choose color based on match type
         let provider_color = if detection.provider == "Unknown format" {
@@ -229,8 +375,17 @@ fn print_event(
             && detection.file_pattern_matched
             && matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_));
 
+        if let Some(import_tx) = import_tx {
+            if detection.file_pattern_matched
+                && path.is_file()
+                && matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+            {
+                let _ = import_tx.send((path.to_path_buf(), hint.clone()));
+            }
+        }
+
         if should_show_diff {
-            show_file_diff(path, file_cache, debounce_map, parse_tx.clone());
+            show_file_diff(path, hint, file_cache, debounce_map, parse_tx.clone());
         }
     }
 }
@@ -238,9 +393,10 @@ fn print_event(
 /// Show diff for modified files
 fn show_file_diff(
     path: &Path,
+    provider: Option<Provider>,
     file_cache: &Arc<Mutex<HashMap<PathBuf, String>>>,
     debounce_map: &Arc<Mutex<HashMap<PathBuf, std::time::Instant>>>,
-    parse_tx: tokio::sync::mpsc::UnboundedSender<PathBuf>,
+    parse_tx: tokio::sync::mpsc::UnboundedSender<(PathBuf, Option<Provider>)>,
 ) {
     // Debounce: Check if file was visited too recently (within 100ms)
     const DEBOUNCE_DURATION_MS: u128 = 100;
@@ -334,13 +490,15 @@ fn show_file_diff(
     drop(cache); // Release the mutex before async operation
 
     // Send path to parse channel for async parsing
-    let _ = parse_tx.send(path.to_path_buf());
+    let _ = parse_tx.send((path.to_path_buf(), provider));
 }
 
 /// Parse file and log session information (async version)
-async fn parse_and_log_sessions_async(path: &Path) {
+async fn parse_and_log_sessions_async(path: &Path, provider: Option<Provider>) {
     let parser_service = ParserService::new();
-    let result = parser_service.parse_file(path).await;
+    let result = parser_service
+        .parse_file_with_provider(path, provider)
+        .await;
 
     match result {
         Ok(_sessions) => {
@@ -357,6 +515,77 @@ async fn parse_and_log_sessions_async(path: &Path) {
     }
 }
 
+/// Import files once they stop changing
+async fn import_changed_files(
+    importer: Arc<ImportService>,
+    mut rx: tokio::sync::mpsc::UnboundedReceiver<(PathBuf, Option<Provider>)>,
+) {
+    let mut pending: HashMap<PathBuf, (Instant, Option<Provider>)> = HashMap::new();
+    let mut tick = tokio::time::interval(Duration::from_millis(500));
+
+    loop {
+        tokio::select! {
+            received = rx.recv() => match received {
+                Some((path, provider)) => {
+                    pending.insert(path, (Instant::now(), provider));
+                }
+                None => break,
+            },
+            _ = tick.tick() => {
+                let due: Vec<PathBuf> = pending
+                    .iter()
+                    .filter(|(_, (changed_at, _))| changed_at.elapsed() >= IMPORT_QUIET_PERIOD)
+                    .map(|(path, _)| path.clone())
+                    .collect();
+                for path in due {
+                    if let Some((_, provider)) = pending.remove(&path) {
+                        import_changed_file(&importer, &path, provider).await;
+                    }
+                }
+            }
+        }
+    }
+
+    // The watcher stopped; import what is still pending
+    for (path, (_, provider)) in pending {
+        import_changed_file(&importer, &path, provider).await;
+    }
+}
+
+/// Import one changed file, replacing the sessions it held before
+async fn import_changed_file(importer: &ImportService, path: &Path, provider: Option<Provider>) {
+    let request = ImportFileRequest {
+        file_path: path.to_string_lossy().to_string(),
+        provider: provider.map(|p| p.to_string()),
+        project_name: None,
+        overwrite_existing: Some(true),
+    };
+
+    match importer.import_file(request).await {
+        Ok(response) if response.sessions_imported > 0 => {
+            println!(
+                "{} {} {}",
+                "📥".with(Color::Green),
+                format!(
+                    "Imported {} session(s), {} message(s) from",
+                    response.sessions_imported, response.messages_imported
+                )
+                .with(Color::Green),
+                path.display().to_string().with(Color::Cyan)
+            );
+        }
+        Ok(_) => {}
+        Err(e) => {
+            eprintln!(
+                "{} {} {}",
+                "⚠️".with(Color::Yellow),
+                format!("Failed to import {}:", path.display()).with(Color::Yellow),
+                e.to_string().with(Color::DarkGrey)
+            );
+        }
+    }
+}
+
 /// Print unified diff between two texts
 fn print_diff(old: &str, new: &str) {
     let diff = TextDiff::from_lines(old, new);
@@ -513,3 +742,51 @@ fn find_matching_pattern(patterns: &[String], file_name: &str) -> Option<String>
     }
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::WatchPathConfig;
+
+    #[test]
+    fn test_ignore_rules_match_paths_and_file_names() {
+        let rules =
+            IgnoreRules::new(&["*.tmp".to_string(), "/data/**/backup/*".to_string()]).unwrap();
+
+        assert!(rules.is_ignored(Path::new("/home/user/.claude/session.tmp")));
+        assert!(rules.is_ignored(Path::new("/data/x/backup/a.jsonl")));
+        assert!(!rules.is_ignored(Path::new("/data/x/a.jsonl")));
+        assert!(IgnoreRules::new(&["[".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_configured_targets_and_provider_hints() {
+        let config = WatchConfig {
+            paths: vec![
+                WatchPathConfig {
+                    path: "/logs".to_string(),
+                    provider: None,
+                },
+                WatchPathConfig {
+                    path: "/logs/claude".to_string(),
+                    provider: Some("claude".to_string()),
+                },
+                WatchPathConfig {
+                    path: "/other".to_string(),
+                    provider: Some("not-a-provider".to_string()),
+                },
+            ],
+            ignore: Vec::new(),
+        };
+
+        let targets = collect_configured_targets(&config);
+
+        assert_eq!(targets.len(), 3);
+        assert_eq!(targets[2].provider, None);
+        assert_eq!(
+            provider_hint(&targets, Path::new("/logs/claude/a.jsonl")),
+            Some(&Provider::ClaudeCode)
+        );
+        assert_eq!(provider_hint(&targets, Path::new("/logs/a.jsonl")), None);
+    }
+}
@@ -0,0 +1,273 @@
+//! User-defined parsing rules for MCP tools
+//!
+//! Declared as `[[parsers.mcp]]` tables in config.toml. MCP servers return
+//! whatever JSON they like, so RetroChat cannot tell on its own what a call
+//! acted on or whether it worked; a rule names the fields that say so:
+//!
+//! ```toml
+//! [[parsers.mcp]]
+//! tool = "mcp__github__create_issue"
+//! subject = "$.title"
+//! success = "$.number"
+//! summary = "$.html_url"
+//! ```
+
+use serde_json::Value;
+
+use crate::config::{Config, McpToolRuleConfig};
+use crate::models::message::{ToolResult, ToolUse};
+use crate::models::ToolOperation;
+
+/// Compiled `[[parsers.mcp]]` rules
+#[derive(Debug, Clone, Default)]
+pub struct McpToolRules {
+    rules: Vec<(glob::Pattern, McpToolRuleConfig)>,
+}
+
+impl McpToolRules {
+    /// Compile rules, skipping (with a warning) any whose tool glob is invalid
+    pub fn new(rules: &[McpToolRuleConfig]) -> Self {
+        let rules = rules
+            .iter()
+            .filter_map(|rule| match glob::Pattern::new(&rule.tool) {
+                Ok(pattern) => Some((pattern, rule.clone())),
+                Err(e) => {
+                    tracing::warn!(tool = %rule.tool, error = %e, "Invalid MCP tool glob");
+                    None
+                }
+            })
+            .collect();
+        Self { rules }
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(&config.parsers.mcp)
+    }
+
+    /// Rules from the config file; none (with a warning) if it cannot be read
+    pub fn load() -> Self {
+        match Config::load() {
+            Ok(config) => Self::from_config(&config),
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to load MCP tool rules");
+                Self::default()
+            }
+        }
+    }
+
+    /// First rule matching the tool name
+    pub fn find(&self, tool_name: &str) -> Option<&McpToolRuleConfig> {
+        self.rules
+            .iter()
+            .find(|(pattern, _)| pattern.matches(tool_name))
+            .map(|(_, rule)| rule)
+    }
+
+    /// Refine an operation's success and result summary with its tool's rule
+    pub fn apply(
+        &self,
+        mut operation: ToolOperation,
+        tool_use: &ToolUse,
+        tool_result: Option<&ToolResult>,
+    ) -> ToolOperation {
+        let Some(rule) = self.find(&tool_use.name) else {
+            return operation;
+        };
+
+        let subject = rule
+            .subject
+            .as_deref()
+            .and_then(|path| json_path(&tool_use.input, path))
+            .and_then(scalar_text);
+        let result_json = tool_result.and_then(result_value);
+
+        if let (Some(result), Some(path)) = (tool_result, rule.success.as_deref()) {
+            let succeeded = !result.is_error
+                && result_json
+                    .as_ref()
+                    .and_then(|json| json_path(json, path))
+                    .is_some_and(is_truthy);
+            operation = operation.with_success(succeeded);
+        }
+
+        let summary = rule
+            .summary
+            .as_deref()
+            .and_then(|path| json_path(result_json.as_ref()?, path))
+            .map(|value| scalar_text(value).unwrap_or_else(|| value.to_string()))
+            .or_else(|| operation.result_summary.clone());
+
+        let summary = match (subject, summary) {
+            (Some(subject), Some(summary)) => Some(format!("{subject}: {summary}")),
+            (subject, summary) => summary.or(subject),
+        };
+        if let Some(summary) = summary {
+            operation = operation.with_result_summary(summary);
+        }
+
+        operation
+    }
+}
+
+/// The structured result, or the result text when it is JSON
+fn result_value(result: &ToolResult) -> Option<Value> {
+    result
+        .details
+        .clone()
+        .or_else(|| serde_json::from_str(result.content.trim()).ok())
+}
+
+/// Strings, numbers and booleans as text; `None` for null, arrays and objects
+fn scalar_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// False for null, false, 0, "" and empty arrays and objects
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+        Value::String(s) => !s.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(fields) => !fields.is_empty(),
+    }
+}
+
+/// Resolve a simple JSONPath: `$`, then `.field`, `["field"]` or `[index]` steps
+pub fn json_path<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    let mut rest = path.trim().strip_prefix('$').unwrap_or(path.trim());
+    let mut current = value;
+
+    while !rest.is_empty() {
+        if let Some(after_dot) = rest.strip_prefix('.') {
+            let end = after_dot.find(['.', '[']).unwrap_or(after_dot.len());
+            current = current.get(&after_dot[..end])?;
+            rest = &after_dot[end..];
+        } else if let Some(after_bracket) = rest.strip_prefix('[') {
+            let end = after_bracket.find(']')?;
+            let step = after_bracket[..end].trim();
+            current = match step.parse::<usize>() {
+                Ok(index) => current.get(index)?,
+                Err(_) => current.get(step.trim_matches(|c| c == '"' || c == '\''))?,
+            };
+            rest = &after_bracket[end + 1..];
+        } else {
+            // A bare leading field, as in "title.text"
+            let end = rest.find(['.', '[']).unwrap_or(rest.len());
+            current = current.get(&rest[..end])?;
+            rest = &rest[end..];
+        }
+    }
+
+    Some(current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use serde_json::json;
+
+    fn rule(tool: &str, subject: &str, success: &str, summary: Option<&str>) -> McpToolRuleConfig {
+        McpToolRuleConfig {
+            tool: tool.to_string(),
+            subject: Some(subject.to_string()),
+            success: Some(success.to_string()),
+            summary: summary.map(String::from),
+        }
+    }
+
+    fn call(name: &str, input: Value, content: &str) -> (ToolUse, ToolResult) {
+        let tool_use = ToolUse {
+            id: "toolu_1".to_string(),
+            name: name.to_string(),
+            input,
+            raw: json!({}),
+        };
+        let result = ToolResult {
+            tool_use_id: "toolu_1".to_string(),
+            content: content.to_string(),
+            is_error: false,
+            details: None,
+            raw: json!({}),
+        };
+        (tool_use, result)
+    }
+
+    fn apply(rules: &McpToolRules, tool_use: &ToolUse, result: &ToolResult) -> ToolOperation {
+        let operation = ToolOperation::from_tool_use(tool_use, Some(result), Utc::now());
+        rules.apply(operation, tool_use, Some(result))
+    }
+
+    #[test]
+    fn test_json_path() {
+        let value = json!({ "issue": { "labels": [{ "name": "bug" }] }, "a.b": 1 });
+        assert_eq!(
+            json_path(&value, "$.issue.labels[0].name"),
+            Some(&json!("bug"))
+        );
+        assert_eq!(
+            json_path(&value, "issue.labels[0]['name']"),
+            Some(&json!("bug"))
+        );
+        assert_eq!(json_path(&value, "$[\"a.b\"]"), Some(&json!(1)));
+        assert_eq!(json_path(&value, "$"), Some(&value));
+        assert_eq!(json_path(&value, "$.issue.labels[3]"), None);
+        assert_eq!(json_path(&value, "$.issue[0"), None);
+    }
+
+    #[test]
+    fn test_rule_sets_subject_summary_and_success() {
+        let rules = McpToolRules::new(&[rule(
+            "mcp__github__*",
+            "$.title",
+            "$.number",
+            Some("$.html_url"),
+        )]);
+
+        let (tool_use, result) = call(
+            "mcp__github__create_issue",
+            json!({ "title": "Crash on import" }),
+            r#"{"number": 42, "html_url": "https://github.com/o/r/issues/42"}"#,
+        );
+        let operation = apply(&rules, &tool_use, &result);
+        assert_eq!(operation.success, Some(true));
+        assert_eq!(
+            operation.result_summary.as_deref(),
+            Some("Crash on import: https://github.com/o/r/issues/42")
+        );
+
+        // A reply without the success field is a failure even without is_error
+        let (tool_use, result) = call(
+            "mcp__github__create_issue",
+            json!({ "title": "Crash on import" }),
+            r#"{"message": "Validation Failed"}"#,
+        );
+        let operation = apply(&rules, &tool_use, &result);
+        assert_eq!(operation.success, Some(false));
+        assert_eq!(
+            operation.result_summary.as_deref(),
+            Some(r#"Crash on import: {"message": "Validation Failed"}"#)
+        );
+    }
+
+    #[test]
+    fn test_unmatched_tool_is_unchanged() {
+        let rules = McpToolRules::new(&[
+            rule("mcp__github__*", "$.title", "$.number", None),
+            rule("mcp__[", "$.x", "$.y", None),
+        ]);
+        assert!(rules.find("mcp__linear__create_issue").is_none());
+
+        let (tool_use, result) = call("mcp__linear__create_issue", json!({}), "created");
+        let operation = apply(&rules, &tool_use, &result);
+        assert_eq!(operation.success, Some(true));
+        assert_eq!(operation.result_summary.as_deref(), Some("created"));
+    }
+}
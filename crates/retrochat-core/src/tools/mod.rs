@@ -1,13 +1,16 @@
+pub mod mcp_rules;
 pub mod parsers;
 pub mod types;
 
+pub use mcp_rules::McpToolRules;
 pub use types::ToolType;
 
 use crate::models::message::ToolUse;
 use anyhow::Result;
 use parsers::{
-    bash::BashParser, edit::EditParser, read::ReadParser, write::WriteParser, ParsedTool,
-    ToolParser,
+    bash::BashParser, edit::EditParser, glob::GlobParser, grep::GrepParser,
+    notebook_edit::NotebookEditParser, read::ReadParser, todo_write::TodoWriteParser,
+    web_fetch::WebFetchParser, write::WriteParser, ParsedTool, ToolParser,
 };
 
 /// Main tool parsing facade
@@ -28,6 +31,11 @@ impl ToolParsingService {
             ToolType::Read => ReadParser.parse(tool_use),
             ToolType::Write => WriteParser.parse(tool_use),
             ToolType::Edit => EditParser.parse(tool_use),
+            ToolType::Grep => GrepParser.parse(tool_use),
+            ToolType::Glob => GlobParser.parse(tool_use),
+            ToolType::WebFetch => WebFetchParser.parse(tool_use),
+            ToolType::TodoWrite => TodoWriteParser.parse(tool_use),
+            ToolType::NotebookEdit => NotebookEditParser.parse(tool_use),
             _ => {
                 // For unsupported tool types, return unknown parsed tool
                 Ok(ParsedTool::new(
@@ -87,6 +95,34 @@ mod tests {
         assert_eq!(parsed.tool_name, "Bash");
     }
 
+    #[test]
+    fn test_parse_extended_tools() {
+        let service = ToolParsingService::new();
+        let tool_use = |name: &str, input: serde_json::Value| ToolUse {
+            id: "test_id".to_string(),
+            name: name.to_string(),
+            input,
+            raw: json!({}),
+        };
+
+        let parsed = [
+            tool_use("Grep", json!({ "pattern": "TODO" })),
+            tool_use("Glob", json!({ "pattern": "**/*.rs" })),
+            tool_use("WebFetch", json!({ "url": "https://example.com" })),
+            tool_use("TodoWrite", json!({ "todos": [] })),
+            tool_use("NotebookEdit", json!({ "notebook_path": "a.ipynb" })),
+        ]
+        .iter()
+        .map(|tool_use| service.parse_tool(tool_use).unwrap().data)
+        .collect::<Vec<_>>();
+
+        assert!(matches!(parsed[0], parsers::ToolData::Grep(_)));
+        assert!(matches!(parsed[1], parsers::ToolData::Glob(_)));
+        assert!(matches!(parsed[2], parsers::ToolData::WebFetch(_)));
+        assert!(matches!(parsed[3], parsers::ToolData::TodoWrite(_)));
+        assert!(matches!(parsed[4], parsers::ToolData::NotebookEdit(_)));
+    }
+
     #[test]
     fn test_get_tool_type() {
         let service = ToolParsingService::new();
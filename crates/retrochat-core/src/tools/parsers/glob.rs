@@ -0,0 +1,90 @@
+use super::{ParsedTool, ToolData, ToolParser};
+use crate::models::message::ToolUse;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+/// Glob tool parser
+pub struct GlobParser;
+
+/// Structured data from Glob tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlobData {
+    /// Glob pattern files are matched against
+    pub pattern: String,
+    /// Directory to search in (defaults to the working directory)
+    pub path: Option<String>,
+}
+
+impl ToolParser for GlobParser {
+    fn parse(&self, tool_use: &ToolUse) -> Result<ParsedTool> {
+        let pattern = tool_use
+            .input
+            .get("pattern")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow!("Glob tool missing 'pattern' field"))?
+            .to_string();
+
+        let path = tool_use
+            .input
+            .get("path")
+            .and_then(|v| v.as_str())
+            .map(String::from);
+
+        Ok(ParsedTool::new(
+            tool_use.name.clone(),
+            ToolData::Glob(GlobData { pattern, path }),
+            tool_use.input.clone(),
+        ))
+    }
+}
+
+impl GlobData {
+    /// Check if the pattern descends into subdirectories
+    pub fn is_recursive(&self) -> bool {
+        self.pattern.contains("**")
+    }
+
+    /// Extension the pattern is limited to, e.g. "rs" for `**/*.rs`
+    pub fn extension(&self) -> Option<&str> {
+        let (_, extension) = self.pattern.rsplit_once("*.")?;
+        (!extension.is_empty() && extension.chars().all(|c| c.is_ascii_alphanumeric()))
+            .then_some(extension)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_glob_parser() {
+        let tool_use = ToolUse {
+            id: "test_id".to_string(),
+            name: "Glob".to_string(),
+            input: json!({ "pattern": "src/**/*.rs" }),
+            raw: json!({}),
+        };
+
+        let parsed = GlobParser.parse(&tool_use).unwrap();
+        if let ToolData::Glob(data) = parsed.data {
+            assert_eq!(data.pattern, "src/**/*.rs");
+            assert_eq!(data.path, None);
+            assert!(data.is_recursive());
+            assert_eq!(data.extension(), Some("rs"));
+        } else {
+            panic!("Expected GlobData");
+        }
+    }
+
+    #[test]
+    fn test_glob_data_extension() {
+        let data = |pattern: &str| GlobData {
+            pattern: pattern.to_string(),
+            path: None,
+        };
+        assert_eq!(data("*.{ts,tsx}").extension(), None);
+        assert_eq!(data("Cargo.toml").extension(), None);
+        assert!(!data("*.md").is_recursive());
+    }
+}
@@ -0,0 +1,124 @@
+use super::{ParsedTool, ToolData, ToolParser};
+use crate::models::message::ToolUse;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+/// Grep tool parser
+pub struct GrepParser;
+
+/// Structured data from Grep tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrepData {
+    /// Pattern being searched for
+    pub pattern: String,
+    /// File or directory to search in (defaults to the working directory)
+    pub path: Option<String>,
+    /// Glob restricting which files are searched
+    pub glob: Option<String>,
+    /// File type restricting which files are searched (e.g. "rust")
+    pub file_type: Option<String>,
+    /// "content", "files_with_matches" or "count"
+    pub output_mode: Option<String>,
+    /// Whether the search ignores case
+    pub case_insensitive: bool,
+    /// Whether patterns may span lines
+    pub multiline: bool,
+}
+
+impl ToolParser for GrepParser {
+    fn parse(&self, tool_use: &ToolUse) -> Result<ParsedTool> {
+        let input = &tool_use.input;
+        let pattern = input
+            .get("pattern")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow!("Grep tool missing 'pattern' field"))?
+            .to_string();
+
+        let string_field = |key: &str| input.get(key).and_then(|v| v.as_str()).map(String::from);
+
+        let data = GrepData {
+            pattern,
+            path: string_field("path"),
+            glob: string_field("glob"),
+            file_type: string_field("type"),
+            output_mode: string_field("output_mode"),
+            case_insensitive: input.get("-i").and_then(|v| v.as_bool()).unwrap_or(false),
+            multiline: input
+                .get("multiline")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+        };
+
+        Ok(ParsedTool::new(
+            tool_use.name.clone(),
+            ToolData::Grep(data),
+            tool_use.input.clone(),
+        ))
+    }
+}
+
+impl GrepData {
+    /// Check if the pattern uses regex syntax rather than a plain string
+    pub fn is_regex(&self) -> bool {
+        self.pattern.chars().any(|c| {
+            matches!(
+                c,
+                '.' | '*' | '+' | '?' | '[' | '(' | '|' | '^' | '$' | '\\'
+            )
+        })
+    }
+
+    /// Check if only matching file names are returned
+    pub fn lists_files_only(&self) -> bool {
+        // Claude Code defaults to file names when no mode is given
+        matches!(
+            self.output_mode.as_deref(),
+            None | Some("files_with_matches")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_grep_parser() {
+        let tool_use = ToolUse {
+            id: "test_id".to_string(),
+            name: "Grep".to_string(),
+            input: json!({
+                "pattern": "fn\\s+parse",
+                "path": "src",
+                "type": "rust",
+                "output_mode": "content",
+                "-i": true
+            }),
+            raw: json!({}),
+        };
+
+        let parsed = GrepParser.parse(&tool_use).unwrap();
+        if let ToolData::Grep(data) = parsed.data {
+            assert_eq!(data.pattern, "fn\\s+parse");
+            assert_eq!(data.path.as_deref(), Some("src"));
+            assert_eq!(data.file_type.as_deref(), Some("rust"));
+            assert!(data.case_insensitive);
+            assert!(data.is_regex());
+            assert!(!data.lists_files_only());
+        } else {
+            panic!("Expected GrepData");
+        }
+    }
+
+    #[test]
+    fn test_grep_parser_missing_pattern() {
+        let tool_use = ToolUse {
+            id: "test_id".to_string(),
+            name: "Grep".to_string(),
+            input: json!({ "path": "src" }),
+            raw: json!({}),
+        };
+        assert!(GrepParser.parse(&tool_use).is_err());
+    }
+}
@@ -1,6 +1,11 @@
 pub mod bash;
 pub mod edit;
+pub mod glob;
+pub mod grep;
+pub mod notebook_edit;
 pub mod read;
+pub mod todo_write;
+pub mod web_fetch;
 pub mod write;
 
 use crate::models::message::ToolUse;
@@ -31,6 +36,11 @@ pub enum ToolData {
     Read(read::ReadData),
     Write(write::WriteData),
     Edit(edit::EditData),
+    Grep(grep::GrepData),
+    Glob(glob::GlobData),
+    WebFetch(web_fetch::WebFetchData),
+    TodoWrite(todo_write::TodoWriteData),
+    NotebookEdit(notebook_edit::NotebookEditData),
     Unknown,
 }
 
@@ -0,0 +1,100 @@
+use super::{ParsedTool, ToolData, ToolParser};
+use crate::models::message::ToolUse;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+/// NotebookEdit tool parser
+pub struct NotebookEditParser;
+
+/// Structured data from NotebookEdit tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotebookEditData {
+    /// Path to the notebook being edited
+    pub notebook_path: String,
+    /// Cell being replaced or deleted, or inserted after
+    pub cell_id: Option<String>,
+    /// "code" or "markdown"
+    pub cell_type: Option<String>,
+    /// "replace", "insert" or "delete"; replace when not given
+    pub edit_mode: Option<String>,
+    /// New cell source
+    pub new_source: Option<String>,
+}
+
+impl ToolParser for NotebookEditParser {
+    fn parse(&self, tool_use: &ToolUse) -> Result<ParsedTool> {
+        let input = &tool_use.input;
+        let notebook_path = input
+            .get("notebook_path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow!("NotebookEdit tool missing 'notebook_path' field"))?
+            .to_string();
+
+        let string_field = |key: &str| input.get(key).and_then(|v| v.as_str()).map(String::from);
+
+        let data = NotebookEditData {
+            notebook_path,
+            cell_id: string_field("cell_id"),
+            cell_type: string_field("cell_type"),
+            edit_mode: string_field("edit_mode"),
+            new_source: string_field("new_source"),
+        };
+
+        Ok(ParsedTool::new(
+            tool_use.name.clone(),
+            ToolData::NotebookEdit(data),
+            tool_use.input.clone(),
+        ))
+    }
+}
+
+impl NotebookEditData {
+    /// Edit mode, defaulting to "replace"
+    pub fn edit_mode(&self) -> &str {
+        self.edit_mode.as_deref().unwrap_or("replace")
+    }
+
+    /// Check if the edit changes a code cell rather than markdown
+    pub fn is_code_cell(&self) -> bool {
+        self.cell_type.as_deref() != Some("markdown")
+    }
+
+    /// Count lines in new_source
+    pub fn lines_after(&self) -> Option<i32> {
+        self.new_source
+            .as_ref()
+            .map(|s| crate::models::ToolOperation::count_lines(s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_notebook_edit_parser() {
+        let tool_use = ToolUse {
+            id: "test_id".to_string(),
+            name: "NotebookEdit".to_string(),
+            input: json!({
+                "notebook_path": "/work/analysis.ipynb",
+                "cell_id": "cell-3",
+                "new_source": "import pandas as pd\ndf = pd.read_csv('x.csv')",
+                "edit_mode": "insert",
+                "cell_type": "code"
+            }),
+            raw: json!({}),
+        };
+
+        let parsed = NotebookEditParser.parse(&tool_use).unwrap();
+        if let ToolData::NotebookEdit(data) = parsed.data {
+            assert_eq!(data.notebook_path, "/work/analysis.ipynb");
+            assert_eq!(data.edit_mode(), "insert");
+            assert!(data.is_code_cell());
+            assert_eq!(data.lines_after(), Some(2));
+        } else {
+            panic!("Expected NotebookEditData");
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use super::{ParsedTool, ToolData, ToolParser};
+use crate::models::message::ToolUse;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+/// TodoWrite tool parser
+pub struct TodoWriteParser;
+
+/// Structured data from TodoWrite tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TodoWriteData {
+    /// The full todo list after the call
+    pub todos: Vec<TodoItem>,
+}
+
+/// One entry of the agent's todo list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TodoItem {
+    pub content: String,
+    /// "pending", "in_progress" or "completed"
+    pub status: String,
+    /// Present-tense label shown while the item is in progress
+    pub active_form: Option<String>,
+}
+
+impl ToolParser for TodoWriteParser {
+    fn parse(&self, tool_use: &ToolUse) -> Result<ParsedTool> {
+        let todos = tool_use
+            .input
+            .get("todos")
+            .and_then(|v| v.as_array())
+            .ok_or_else(|| anyhow!("TodoWrite tool missing 'todos' field"))?
+            .iter()
+            .filter_map(|todo| {
+                Some(TodoItem {
+                    content: todo.get("content")?.as_str()?.to_string(),
+                    status: todo
+                        .get("status")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("pending")
+                        .to_string(),
+                    active_form: todo
+                        .get("activeForm")
+                        .and_then(|v| v.as_str())
+                        .map(String::from),
+                })
+            })
+            .collect();
+
+        Ok(ParsedTool::new(
+            tool_use.name.clone(),
+            ToolData::TodoWrite(TodoWriteData { todos }),
+            tool_use.input.clone(),
+        ))
+    }
+}
+
+impl TodoWriteData {
+    /// Number of todos with the given status
+    pub fn count_with_status(&self, status: &str) -> usize {
+        self.todos
+            .iter()
+            .filter(|todo| todo.status == status)
+            .count()
+    }
+
+    /// Number of completed todos
+    pub fn completed_count(&self) -> usize {
+        self.count_with_status("completed")
+    }
+
+    /// The item being worked on, if any
+    pub fn in_progress(&self) -> Option<&TodoItem> {
+        self.todos.iter().find(|todo| todo.status == "in_progress")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_todo_write_parser() {
+        let tool_use = ToolUse {
+            id: "test_id".to_string(),
+            name: "TodoWrite".to_string(),
+            input: json!({
+                "todos": [
+                    { "content": "Add parser", "status": "completed", "activeForm": "Adding parser" },
+                    { "content": "Write tests", "status": "in_progress", "activeForm": "Writing tests" },
+                    { "content": "Update docs", "status": "pending" },
+                    { "status": "pending" }
+                ]
+            }),
+            raw: json!({}),
+        };
+
+        let parsed = TodoWriteParser.parse(&tool_use).unwrap();
+        if let ToolData::TodoWrite(data) = parsed.data {
+            assert_eq!(data.todos.len(), 3);
+            assert_eq!(data.completed_count(), 1);
+            assert_eq!(data.count_with_status("pending"), 1);
+            assert_eq!(
+                data.in_progress()
+                    .and_then(|todo| todo.active_form.as_deref()),
+                Some("Writing tests")
+            );
+        } else {
+            panic!("Expected TodoWriteData");
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use super::{ParsedTool, ToolData, ToolParser};
+use crate::models::message::ToolUse;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+/// WebFetch tool parser
+pub struct WebFetchParser;
+
+/// Structured data from WebFetch tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebFetchData {
+    /// URL being fetched
+    pub url: String,
+    /// What the agent asked to extract from the page
+    pub prompt: Option<String>,
+}
+
+impl ToolParser for WebFetchParser {
+    fn parse(&self, tool_use: &ToolUse) -> Result<ParsedTool> {
+        let url = tool_use
+            .input
+            .get("url")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow!("WebFetch tool missing 'url' field"))?
+            .to_string();
+
+        let prompt = tool_use
+            .input
+            .get("prompt")
+            .and_then(|v| v.as_str())
+            .map(String::from);
+
+        Ok(ParsedTool::new(
+            tool_use.name.clone(),
+            ToolData::WebFetch(WebFetchData { url, prompt }),
+            tool_use.input.clone(),
+        ))
+    }
+}
+
+impl WebFetchData {
+    /// Host part of the URL, without scheme, credentials or port
+    pub fn host(&self) -> Option<&str> {
+        let (_, rest) = self.url.split_once("://")?;
+        let authority = rest.split(['/', '?', '#']).next()?;
+        let host = authority.rsplit('@').next()?;
+        let host = host.split(':').next()?;
+        (!host.is_empty()).then_some(host)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_web_fetch_parser() {
+        let tool_use = ToolUse {
+            id: "test_id".to_string(),
+            name: "WebFetch".to_string(),
+            input: json!({
+                "url": "https://docs.rs/sqlx/latest/sqlx/",
+                "prompt": "How do I run migrations?"
+            }),
+            raw: json!({}),
+        };
+
+        let parsed = WebFetchParser.parse(&tool_use).unwrap();
+        if let ToolData::WebFetch(data) = parsed.data {
+            assert_eq!(data.host(), Some("docs.rs"));
+            assert_eq!(data.prompt.as_deref(), Some("How do I run migrations?"));
+        } else {
+            panic!("Expected WebFetchData");
+        }
+    }
+
+    #[test]
+    fn test_web_fetch_host() {
+        let data = |url: &str| WebFetchData {
+            url: url.to_string(),
+            prompt: None,
+        };
+        assert_eq!(
+            data("http://user@localhost:8080?q=1").host(),
+            Some("localhost")
+        );
+        assert_eq!(data("not a url").host(), None);
+    }
+}
@@ -23,6 +23,8 @@ pub enum ToolType {
     Task,
     /// Notebook editing
     NotebookEdit,
+    /// Todo list updates
+    TodoWrite,
     /// Other/unknown tool type
     Other(String),
 }
@@ -41,6 +43,7 @@ impl ToolType {
             "WebSearch" | "web_search" => ToolType::WebSearch,
             "Task" | "task" | "agent" => ToolType::Task,
             "NotebookEdit" | "notebook_edit" => ToolType::NotebookEdit,
+            "TodoWrite" | "todo_write" => ToolType::TodoWrite,
             other => ToolType::Other(other.to_string()),
         }
     }
@@ -58,6 +61,7 @@ impl ToolType {
             ToolType::WebSearch => "WebSearch",
             ToolType::Task => "Task",
             ToolType::NotebookEdit => "NotebookEdit",
+            ToolType::TodoWrite => "TodoWrite",
             ToolType::Other(name) => name,
         }
     }
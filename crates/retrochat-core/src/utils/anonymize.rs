@@ -0,0 +1,138 @@
+//! Strip personal details from text before it leaves the machine
+//!
+//! Replaces the home directory with `~`, the login name with `user`, email
+//! addresses with `<email>`, and anything that looks like a credential with
+//! `<redacted>`. This is a best-effort filter for sharing transcripts, not a
+//! guarantee; callers should say so to the user.
+
+use lazy_static::lazy_static;
+use regex::{NoExpand, Regex};
+
+use crate::env::system as env_system;
+
+lazy_static! {
+    static ref EMAIL_RE: Regex =
+        Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}").unwrap();
+    /// Well-known token formats: OpenAI/Anthropic, GitHub, AWS, Google, Slack
+    static ref TOKEN_RE: Regex = Regex::new(
+        r"\b(?:sk-[A-Za-z0-9_-]{20,}|gh[pousr]_[A-Za-z0-9]{30,}|github_pat_[A-Za-z0-9_]{30,}|AKIA[0-9A-Z]{16}|AIza[0-9A-Za-z_-]{35}|xox[abprs]-[A-Za-z0-9-]{10,})"
+    )
+    .unwrap();
+    static ref BEARER_RE: Regex = Regex::new(r"(?i)\b(bearer\s+)[A-Za-z0-9._~+/=-]{8,}").unwrap();
+    /// `password=...`, `API_KEY: ...` and the like; the value is replaced
+    static ref ASSIGNMENT_RE: Regex = Regex::new(
+        r#"(?i)\b([A-Z0-9_]*(?:password|passwd|secret|token|api[_-]?key|access[_-]?key)[A-Z0-9_]*["']?\s*[:=]\s*["']?)[^\s"',;]{4,}"#
+    )
+    .unwrap();
+}
+
+const REDACTED: &str = "<redacted>";
+
+/// Replaces personal details in text
+#[derive(Debug, Clone, Default)]
+pub struct Anonymizer {
+    home: Option<String>,
+    username: Option<Regex>,
+    /// Extra terms replaced by `<redacted>`, e.g. a company or host name
+    terms: Vec<Regex>,
+}
+
+impl Anonymizer {
+    /// Anonymizer for the current user's home directory and login name
+    pub fn for_current_user() -> Self {
+        let home = dirs::home_dir().map(|home| home.to_string_lossy().to_string());
+        let username = std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .ok()
+            .or_else(|| {
+                std::env::var(env_system::HOME)
+                    .ok()
+                    .and_then(|home| home.rsplit(['/', '\\']).next().map(String::from))
+            });
+        Self::new(home, username)
+    }
+
+    /// Anonymizer for the given home directory and login name
+    pub fn new(home: Option<String>, username: Option<String>) -> Self {
+        // Very short names would match inside ordinary words
+        let username = username
+            .filter(|name| name.chars().count() >= 3 && name != "root")
+            .map(|name| Regex::new(&format!(r"(?i)\b{}\b", regex::escape(&name))).unwrap());
+        Self {
+            home: home.filter(|home| home.len() > 1),
+            username,
+            terms: Vec::new(),
+        }
+    }
+
+    /// Also redact `term`, case-insensitively
+    pub fn with_term(mut self, term: &str) -> Self {
+        let term = term.trim();
+        if !term.is_empty() {
+            self.terms
+                .push(Regex::new(&format!("(?i){}", regex::escape(term))).unwrap());
+        }
+        self
+    }
+
+    pub fn anonymize(&self, text: &str) -> String {
+        let mut text = text.to_string();
+        if let Some(home) = &self.home {
+            text = text.replace(home.as_str(), "~");
+        }
+        text = TOKEN_RE.replace_all(&text, REDACTED).into_owned();
+        text = BEARER_RE
+            .replace_all(&text, format!("${{1}}{REDACTED}"))
+            .into_owned();
+        text = ASSIGNMENT_RE
+            .replace_all(&text, format!("${{1}}{REDACTED}"))
+            .into_owned();
+        text = EMAIL_RE.replace_all(&text, "<email>").into_owned();
+        if let Some(username) = &self.username {
+            text = username.replace_all(&text, NoExpand("user")).into_owned();
+        }
+        for term in &self.terms {
+            text = term.replace_all(&text, NoExpand(REDACTED)).into_owned();
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anonymize() {
+        let anonymizer = Anonymizer::new(Some("/home/jdoe".to_string()), Some("jdoe".to_string()))
+            .with_term("Acme Corp");
+
+        assert_eq!(
+            anonymizer.anonymize("cd /home/jdoe/src/app && ls"),
+            "cd ~/src/app && ls"
+        );
+        assert_eq!(
+            anonymizer.anonymize("Committed as jdoe <jdoe@example.com>"),
+            "Committed as user <<email>>"
+        );
+        assert_eq!(
+            anonymizer.anonymize("export OPENAI_API_KEY=sk-abcdefghijklmnopqrstuvwx"),
+            "export OPENAI_API_KEY=<redacted>"
+        );
+        assert_eq!(
+            anonymizer.anonymize(r#"{"password": "hunter22"}"#),
+            r#"{"password": "<redacted>"}"#
+        );
+        assert_eq!(
+            anonymizer.anonymize("Authorization: Bearer abc.def.ghi123"),
+            "Authorization: Bearer <redacted>"
+        );
+        assert_eq!(
+            anonymizer.anonymize("Deploying for ACME corp"),
+            "Deploying for <redacted>"
+        );
+        // Short names are left alone rather than mangling words
+        let short = Anonymizer::new(None, Some("al".to_string()));
+        assert_eq!(short.anonymize("also al"), "also al");
+    }
+}
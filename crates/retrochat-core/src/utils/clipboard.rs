@@ -0,0 +1,61 @@
+//! Copying text to the system clipboard
+//!
+//! Like the keychain, this goes through the platform's own tool: `pbcopy`
+//! on macOS, `clip.exe` on Windows and WSL, and `wl-copy`, `xclip` or
+//! `xsel` on Linux, whichever is installed.
+
+use anyhow::{Context, Result as AnyhowResult};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Clipboard tools to try, in order, with their arguments
+fn candidates() -> Vec<(&'static str, &'static [&'static str])> {
+    if cfg!(target_os = "macos") {
+        vec![("pbcopy", &[])]
+    } else if cfg!(windows) {
+        vec![("clip.exe", &[])]
+    } else {
+        let mut tools: Vec<(&'static str, &'static [&'static str])> = Vec::new();
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            tools.push(("wl-copy", &[]));
+        }
+        tools.push(("xclip", &["-selection", "clipboard"]));
+        tools.push(("xsel", &["--clipboard", "--input"]));
+        // WSL has no X server by default but can reach the Windows clipboard
+        tools.push(("clip.exe", &[]));
+        tools
+    }
+}
+
+/// Put `text` on the clipboard, returning the name of the tool that did it
+pub fn copy(text: &str) -> AnyhowResult<&'static str> {
+    for (tool, args) in candidates() {
+        let mut child = match Command::new(tool)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        {
+            Ok(child) => child,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e).with_context(|| format!("Failed to run `{tool}`")),
+        };
+        child
+            .stdin
+            .take()
+            .with_context(|| format!("Failed to open {tool} input"))?
+            .write_all(text.as_bytes())
+            .with_context(|| format!("Failed to write to `{tool}`"))?;
+        if child.wait()?.success() {
+            return Ok(tool);
+        }
+        tracing::debug!(tool, "Clipboard tool failed, trying the next one");
+    }
+
+    let tools: Vec<&str> = candidates().into_iter().map(|(tool, _)| tool).collect();
+    anyhow::bail!(
+        "No working clipboard tool found (tried {}); install one of them",
+        tools.join(", ")
+    )
+}
@@ -0,0 +1,118 @@
+//! Passphrase-based encryption of blobs
+//!
+//! The key is derived from the passphrase with PBKDF2-HMAC-SHA256 and a random
+//! salt; the data is sealed with ChaCha20-Poly1305, so a wrong passphrase or a
+//! modified blob fails to open instead of producing garbage. Layout:
+//!
+//! ```text
+//! MAGIC (8) | PBKDF2 iterations (u32, big endian) | salt (16) | nonce (12) | ciphertext + tag
+//! ```
+
+use anyhow::{Context, Result};
+use ring::aead::{self, Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use std::num::NonZeroU32;
+
+const MAGIC: &[u8; 8] = b"RCSEAL01";
+const SALT_LEN: usize = 16;
+const HEADER_LEN: usize = MAGIC.len() + 4 + SALT_LEN + NONCE_LEN;
+
+/// PBKDF2 rounds for newly sealed blobs; opening uses the count in the header
+const PBKDF2_ITERATIONS: u32 = 600_000;
+
+/// Most PBKDF2 rounds accepted when opening, so a tampered header cannot stall
+/// the key derivation before the tag check gets to reject it
+const MAX_PBKDF2_ITERATIONS: u32 = PBKDF2_ITERATIONS * 10;
+
+/// Encrypt `plaintext` with a key derived from `passphrase`
+pub fn seal(passphrase: &str, plaintext: &[u8]) -> Result<Vec<u8>> {
+    let rng = SystemRandom::new();
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    rng.fill(&mut salt)
+        .map_err(|_| anyhow::anyhow!("Failed to generate a salt"))?;
+    rng.fill(&mut nonce)
+        .map_err(|_| anyhow::anyhow!("Failed to generate a nonce"))?;
+
+    let key = derive_key(passphrase, &salt, PBKDF2_ITERATIONS)?;
+    let mut sealed = Vec::with_capacity(HEADER_LEN + plaintext.len() + aead::MAX_TAG_LEN);
+    sealed.extend_from_slice(MAGIC);
+    sealed.extend_from_slice(&PBKDF2_ITERATIONS.to_be_bytes());
+    sealed.extend_from_slice(&salt);
+    sealed.extend_from_slice(&nonce);
+
+    let mut in_out = plaintext.to_vec();
+    key.seal_in_place_append_tag(
+        Nonce::assume_unique_for_key(nonce),
+        Aad::from(&sealed[..HEADER_LEN]),
+        &mut in_out,
+    )
+    .map_err(|_| anyhow::anyhow!("Failed to encrypt data"))?;
+    sealed.extend_from_slice(&in_out);
+    Ok(sealed)
+}
+
+/// Decrypt a blob made by [`seal`]
+pub fn open(passphrase: &str, sealed: &[u8]) -> Result<Vec<u8>> {
+    if sealed.len() < HEADER_LEN || &sealed[..MAGIC.len()] != MAGIC {
+        anyhow::bail!("Not an encrypted RetroChat file");
+    }
+    let (header, ciphertext) = sealed.split_at(HEADER_LEN);
+    let iterations = u32::from_be_bytes(header[8..12].try_into()?);
+    if iterations > MAX_PBKDF2_ITERATIONS {
+        anyhow::bail!("Unsupported key derivation parameters: {iterations} iterations");
+    }
+    let salt = &header[12..12 + SALT_LEN];
+    let nonce: [u8; NONCE_LEN] = header[12 + SALT_LEN..].try_into()?;
+
+    let key = derive_key(passphrase, salt, iterations)?;
+    let mut in_out = ciphertext.to_vec();
+    let plaintext = key
+        .open_in_place(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(header),
+            &mut in_out,
+        )
+        .map_err(|_| anyhow::anyhow!("Wrong passphrase, or the data was modified"))?;
+    Ok(plaintext.to_vec())
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], iterations: u32) -> Result<LessSafeKey> {
+    let iterations = NonZeroU32::new(iterations).context("Invalid key derivation parameters")?;
+    let mut key = [0u8; 32];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        salt,
+        passphrase.as_bytes(),
+        &mut key,
+    );
+    let key = UnboundKey::new(&CHACHA20_POLY1305, &key)
+        .map_err(|_| anyhow::anyhow!("Failed to create encryption key"))?;
+    Ok(LessSafeKey::new(key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_and_open() {
+        let sealed = seal("correct horse", b"session data").unwrap();
+        assert_ne!(&sealed[HEADER_LEN..HEADER_LEN + 12], b"session data");
+        assert_eq!(open("correct horse", &sealed).unwrap(), b"session data");
+
+        assert!(open("wrong horse", &sealed).is_err());
+
+        let mut tampered = sealed.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(open("correct horse", &tampered).is_err());
+
+        assert!(open("correct horse", b"plain text").is_err());
+
+        let mut expensive = sealed.clone();
+        expensive[8..12].copy_from_slice(&u32::MAX.to_be_bytes());
+        assert!(open("correct horse", &expensive).is_err());
+    }
+}
@@ -0,0 +1,107 @@
+//! Links that open a session, optionally at one message
+//!
+//! ```text
+//! retrochat://session/<session id>
+//! retrochat://session/<session id>?seq=<message sequence number>
+//! ```
+//!
+//! Search results carry these so a hit can be opened in context with
+//! `retrochat show`, the TUI or the desktop app.
+
+use anyhow::{Context, Result};
+use uuid::Uuid;
+
+/// URI scheme of RetroChat links
+pub const SCHEME: &str = "retrochat";
+
+/// A session, and the message to jump to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeepLink {
+    pub session_id: Uuid,
+    /// Sequence number of the message to show
+    pub sequence: Option<u32>,
+}
+
+impl DeepLink {
+    pub fn session(session_id: Uuid) -> Self {
+        Self {
+            session_id,
+            sequence: None,
+        }
+    }
+
+    pub fn message(session_id: Uuid, sequence: u32) -> Self {
+        Self {
+            session_id,
+            sequence: Some(sequence),
+        }
+    }
+
+    /// Whether `text` looks like a link rather than a bare session ID
+    pub fn is_link(text: &str) -> bool {
+        text.trim().starts_with(&format!("{SCHEME}://"))
+    }
+}
+
+impl std::fmt::Display for DeepLink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{SCHEME}://session/{}", self.session_id)?;
+        if let Some(sequence) = self.sequence {
+            write!(f, "?seq={sequence}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for DeepLink {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || format!("Invalid link {s}; expected {SCHEME}://session/<id>?seq=<n>");
+        let rest = s
+            .trim()
+            .strip_prefix(&format!("{SCHEME}://session/"))
+            .with_context(invalid)?;
+        let (id, query) = match rest.split_once('?') {
+            Some((id, query)) => (id, Some(query)),
+            None => (rest, None),
+        };
+        let session_id = Uuid::parse_str(id.trim_end_matches('/')).with_context(invalid)?;
+
+        let mut sequence = None;
+        for pair in query.into_iter().flat_map(|query| query.split('&')) {
+            if let Some(value) = pair.strip_prefix("seq=") {
+                sequence = Some(value.parse().with_context(invalid)?);
+            }
+        }
+
+        Ok(Self {
+            session_id,
+            sequence,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deep_link_round_trip() {
+        let id = Uuid::new_v4();
+        let link = DeepLink::message(id, 42);
+        assert_eq!(link.to_string(), format!("retrochat://session/{id}?seq=42"));
+        assert_eq!(link.to_string().parse::<DeepLink>().unwrap(), link);
+
+        let session: DeepLink = format!("retrochat://session/{id}/").parse().unwrap();
+        assert_eq!(session, DeepLink::session(id));
+        assert!(DeepLink::is_link(&session.to_string()));
+        assert!(!DeepLink::is_link(&id.to_string()));
+
+        assert!("retrochat://session/nope".parse::<DeepLink>().is_err());
+        assert!(format!("retrochat://session/{id}?seq=x")
+            .parse::<DeepLink>()
+            .is_err());
+        assert!(format!("https://session/{id}").parse::<DeepLink>().is_err());
+    }
+}
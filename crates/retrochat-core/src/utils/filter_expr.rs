@@ -0,0 +1,515 @@
+//! Session filter expressions
+//!
+//! A filter is a whitespace-separated list of terms that must all match:
+//!
+//! ```text
+//! provider:claude project:retrochat tokens>50000 since:"last week" tool:Bash(failed)
+//! ```
+//!
+//! | Term                          | Matches sessions                                  |
+//! |-------------------------------|---------------------------------------------------|
+//! | `provider:NAME[,NAME]`        | from any of the providers                         |
+//! | `project:NAME[,NAME]`         | in any of the projects (case-insensitive)         |
+//! | `since:TIME` / `until:TIME`   | started in the window (see [`time_parser`])       |
+//! | `tokens>N`, `messages<=N`, …  | with a token or message count in range            |
+//! | `tool:NAME[(failed\|ok)]`     | that used the tool, optionally only failed/ok uses |
+//! | `outcome:abandoned[,partial]` | whose generated summary has one of the outcomes   |
+//! | `topic:ID\|LABEL[,…]`         | clustered into one of the topics                  |
+//! | `archived:true`               | that are archived (hidden unless asked for)       |
+//! | `owner:me\|team\|NAME[,…]`     | of the user, of any teammate, or of the teammates |
+//! | anything else                 | free text, matched against title and project      |
+//!
+//! Prefix a term with `-` to negate it, and quote values containing spaces.
+//!
+//! [`time_parser`]: crate::utils::time_parser
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use uuid::Uuid;
+
+use crate::models::{ChatSession, Provider, SessionOutcome};
+use crate::utils::time_parser::{parse_time_spec, parse_until_spec};
+
+/// Comparison operator of a numeric term
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Eq,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl CompareOp {
+    fn apply(self, actual: u64, expected: u64) -> bool {
+        match self {
+            CompareOp::Eq => actual == expected,
+            CompareOp::Gt => actual > expected,
+            CompareOp::Ge => actual >= expected,
+            CompareOp::Lt => actual < expected,
+            CompareOp::Le => actual <= expected,
+        }
+    }
+}
+
+/// A tool the session must have used
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ToolFilter {
+    pub name: String,
+    /// `Some(false)` for failed uses only, `Some(true)` for successful ones
+    pub success: Option<bool>,
+}
+
+/// A single condition of a filter expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterTerm {
+    Provider(Vec<String>),
+    Project(Vec<String>),
+    Since(DateTime<Utc>),
+    Until(DateTime<Utc>),
+    Tokens(CompareOp, u64),
+    Messages(CompareOp, u64),
+    Tool(ToolFilter),
+    Outcome(Vec<SessionOutcome>),
+    /// Topic IDs or labels
+    Topic(Vec<String>),
+    Archived(bool),
+    /// `me`, `team` or teammate names (see [`ChatSession::matches_owner`])
+    Owner(Vec<String>),
+    Text(String),
+}
+
+/// A term and whether it was negated with `-`
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilterClause {
+    pub negated: bool,
+    pub term: FilterTerm,
+}
+
+/// A parsed filter expression; an empty expression matches everything
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FilterExpr {
+    pub clauses: Vec<FilterClause>,
+}
+
+/// Data a filter needs beyond [`ChatSession`], resolved from the database by
+/// the caller
+#[derive(Debug, Clone, Default)]
+pub struct FilterContext {
+    /// Sessions that used each tool in the expression
+    pub tools: HashMap<ToolFilter, HashSet<Uuid>>,
+    /// Outcome of each summarized session (only needed for `outcome:` terms)
+    pub outcomes: HashMap<Uuid, SessionOutcome>,
+    /// Topic ID and label of each clustered session (only needed for
+    /// `topic:` terms)
+    pub topics: HashMap<Uuid, (i64, String)>,
+}
+
+impl FilterExpr {
+    /// Parse a filter expression
+    pub fn parse(input: &str) -> Result<Self> {
+        let clauses = tokenize(input)?
+            .into_iter()
+            .map(|token| parse_clause(&token))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { clauses })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.clauses.is_empty()
+    }
+
+    /// Free-text terms joined with spaces (e.g. to use as a search query)
+    pub fn text(&self) -> String {
+        self.clauses
+            .iter()
+            .filter_map(|clause| match &clause.term {
+                FilterTerm::Text(text) if !clause.negated => Some(text.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// The expression without its free-text terms
+    pub fn without_text(&self) -> Self {
+        Self {
+            clauses: self
+                .clauses
+                .iter()
+                .filter(|clause| !matches!(clause.term, FilterTerm::Text(_)))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Tool conditions that need to be resolved into [`FilterContext::tools`]
+    pub fn tool_filters(&self) -> Vec<&ToolFilter> {
+        self.clauses
+            .iter()
+            .filter_map(|clause| match &clause.term {
+                FilterTerm::Tool(tool) => Some(tool),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Whether [`FilterContext::outcomes`] has to be loaded
+    pub fn needs_outcomes(&self) -> bool {
+        self.clauses
+            .iter()
+            .any(|clause| matches!(clause.term, FilterTerm::Outcome(_)))
+    }
+
+    /// Whether [`FilterContext::topics`] has to be loaded
+    pub fn needs_topics(&self) -> bool {
+        self.clauses
+            .iter()
+            .any(|clause| matches!(clause.term, FilterTerm::Topic(_)))
+    }
+
+    /// Whether the expression asks for archived sessions
+    pub fn includes_archived(&self) -> bool {
+        self.clauses.iter().any(|clause| {
+            matches!(clause.term, FilterTerm::Archived(archived) if archived != clause.negated)
+        })
+    }
+
+    /// Check a session against every clause
+    ///
+    /// Archived sessions are not excluded here; callers hide them unless
+    /// [`FilterExpr::includes_archived`] (or their own flag) says otherwise.
+    pub fn matches(&self, session: &ChatSession, context: &FilterContext) -> bool {
+        self.clauses
+            .iter()
+            .all(|clause| term_matches(&clause.term, session, context) != clause.negated)
+    }
+}
+
+impl fmt::Display for CompareOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let op = match self {
+            CompareOp::Eq => ":",
+            CompareOp::Gt => ">",
+            CompareOp::Ge => ">=",
+            CompareOp::Lt => "<",
+            CompareOp::Le => "<=",
+        };
+        write!(f, "{op}")
+    }
+}
+
+fn term_matches(term: &FilterTerm, session: &ChatSession, context: &FilterContext) -> bool {
+    match term {
+        FilterTerm::Provider(providers) => providers.iter().any(|provider| {
+            session.provider.to_string().eq_ignore_ascii_case(provider)
+                || provider.parse::<Provider>().ok().as_ref() == Some(&session.provider)
+        }),
+        FilterTerm::Project(projects) => session.project_name.as_deref().is_some_and(|name| {
+            projects
+                .iter()
+                .any(|project| name.eq_ignore_ascii_case(project))
+        }),
+        FilterTerm::Since(since) => session.start_time >= *since,
+        FilterTerm::Until(until) => session.start_time <= *until,
+        FilterTerm::Tokens(op, value) => session
+            .token_count
+            .is_some_and(|tokens| op.apply(tokens.into(), *value)),
+        FilterTerm::Messages(op, value) => op.apply(session.message_count.into(), *value),
+        FilterTerm::Tool(tool) => context
+            .tools
+            .get(tool)
+            .is_some_and(|sessions| sessions.contains(&session.id)),
+        FilterTerm::Outcome(outcomes) => context
+            .outcomes
+            .get(&session.id)
+            .is_some_and(|outcome| outcomes.contains(outcome)),
+        FilterTerm::Topic(topics) => context.topics.get(&session.id).is_some_and(|(id, label)| {
+            topics
+                .iter()
+                .any(|topic| topic.trim() == id.to_string() || topic.eq_ignore_ascii_case(label))
+        }),
+        FilterTerm::Archived(archived) => session.is_archived() == *archived,
+        FilterTerm::Owner(owners) => owners.iter().any(|owner| session.matches_owner(owner)),
+        FilterTerm::Text(text) => {
+            let text = text.to_lowercase();
+            [session.title.as_deref(), session.project_name.as_deref()]
+                .into_iter()
+                .flatten()
+                .any(|field| field.to_lowercase().contains(&text))
+        }
+    }
+}
+
+/// Split on whitespace, keeping double-quoted runs together (quotes removed)
+fn tokenize(input: &str) -> Result<Vec<String>> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+
+    for c in input.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            c if c.is_whitespace() && !in_quotes => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if in_quotes {
+        anyhow::bail!("Unterminated quote in filter: {input}");
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    Ok(tokens)
+}
+
+fn parse_clause(token: &str) -> Result<FilterClause> {
+    let (negated, body) = match token.strip_prefix('-') {
+        Some(rest) if !rest.is_empty() => (true, rest),
+        _ => (false, token),
+    };
+
+    let Some((key, op, value)) = split_term(body) else {
+        return Ok(FilterClause {
+            negated,
+            term: FilterTerm::Text(body.to_string()),
+        });
+    };
+
+    let term = match key.to_lowercase().as_str() {
+        "provider" => FilterTerm::Provider(parse_list(key, value)?),
+        "project" => FilterTerm::Project(parse_list(key, value)?),
+        "since" => FilterTerm::Since(parse_time(key, value, parse_time_spec)?),
+        "until" => FilterTerm::Until(parse_time(key, value, parse_until_spec)?),
+        "tokens" => FilterTerm::Tokens(op, parse_number(key, value)?),
+        "messages" => FilterTerm::Messages(op, parse_number(key, value)?),
+        "tool" => FilterTerm::Tool(parse_tool(value)?),
+        "outcome" => FilterTerm::Outcome(
+            parse_list(key, value)?
+                .iter()
+                .map(|outcome| outcome.to_lowercase().parse().map_err(anyhow::Error::msg))
+                .collect::<Result<_>>()?,
+        ),
+        "topic" => FilterTerm::Topic(parse_list(key, value)?),
+        "owner" => FilterTerm::Owner(parse_list(key, value)?),
+        "archived" => FilterTerm::Archived(
+            value
+                .parse()
+                .with_context(|| format!("archived expects true or false, got: {value}"))?,
+        ),
+        // Unknown keys are plain text, so searching for "foo:bar" still works
+        _ => FilterTerm::Text(body.to_string()),
+    };
+
+    let numeric = matches!(term, FilterTerm::Tokens(..) | FilterTerm::Messages(..));
+    if op != CompareOp::Eq && !numeric && !matches!(term, FilterTerm::Text(_)) {
+        anyhow::bail!("{key} does not support {op}, use {key}:VALUE");
+    }
+
+    Ok(FilterClause { negated, term })
+}
+
+/// Split `key<op>value`, returning `None` when the token has no operator
+fn split_term(body: &str) -> Option<(&str, CompareOp, &str)> {
+    let index = body.find([':', '>', '<', '='])?;
+    let (key, rest) = body.split_at(index);
+    if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return None;
+    }
+
+    let (op, value) = if let Some(value) = rest.strip_prefix(">=") {
+        (CompareOp::Ge, value)
+    } else if let Some(value) = rest.strip_prefix("<=") {
+        (CompareOp::Le, value)
+    } else if let Some(value) = rest.strip_prefix('>') {
+        (CompareOp::Gt, value)
+    } else if let Some(value) = rest.strip_prefix('<') {
+        (CompareOp::Lt, value)
+    } else {
+        (CompareOp::Eq, &rest[1..])
+    };
+    Some((key, op, value))
+}
+
+fn parse_list(key: &str, value: &str) -> Result<Vec<String>> {
+    let values: Vec<String> = value
+        .split(',')
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(String::from)
+        .collect();
+    if values.is_empty() {
+        anyhow::bail!("{key} needs a value");
+    }
+    Ok(values)
+}
+
+fn parse_time(
+    key: &str,
+    value: &str,
+    parse: fn(&str) -> Result<DateTime<Utc>>,
+) -> Result<DateTime<Utc>> {
+    parse(value).with_context(|| format!("Invalid {key} time: {value}"))
+}
+
+fn parse_number(key: &str, value: &str) -> Result<u64> {
+    let value = value.replace('_', "");
+    let (digits, multiplier) = match value.to_lowercase().strip_suffix('k') {
+        Some(digits) => (digits.to_string(), 1_000),
+        None => (value.clone(), 1),
+    };
+    let number: u64 = digits
+        .parse()
+        .with_context(|| format!("{key} expects a number, got: {value}"))?;
+    number
+        .checked_mul(multiplier)
+        .with_context(|| format!("{key} value is too large: {value}"))
+}
+
+fn parse_tool(value: &str) -> Result<ToolFilter> {
+    let (name, success) = match value.split_once('(') {
+        Some((name, outcome)) => {
+            let outcome = outcome
+                .strip_suffix(')')
+                .with_context(|| format!("Missing ')' in tool filter: {value}"))?;
+            let success = match outcome.to_lowercase().as_str() {
+                "failed" | "fail" | "error" => false,
+                "ok" | "success" | "succeeded" => true,
+                other => anyhow::bail!("Unknown tool outcome: {other} (use failed or ok)"),
+            };
+            (name, Some(success))
+        }
+        None => (value, None),
+    };
+    if name.is_empty() {
+        anyhow::bail!("tool needs a name");
+    }
+    Ok(ToolFilter {
+        name: name.to_string(),
+        success,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SessionState;
+
+    fn session(provider: Provider, project: &str, messages: u32, tokens: u32) -> ChatSession {
+        let mut session = ChatSession::new(
+            provider,
+            "/tmp/session.jsonl".to_string(),
+            "hash".to_string(),
+            "2024-06-01T00:00:00Z".parse().unwrap(),
+        );
+        session.project_name = Some(project.to_string());
+        session.message_count = messages;
+        session.token_count = Some(tokens);
+        session
+    }
+
+    #[test]
+    fn test_parse_expression() {
+        let expr = FilterExpr::parse(
+            r#"provider:claude,gemini tokens>50k since:"2024-01-01" -tool:Bash(failed) fix bug"#,
+        )
+        .unwrap();
+
+        assert_eq!(expr.clauses.len(), 6);
+        assert_eq!(
+            expr.clauses[0].term,
+            FilterTerm::Provider(vec!["claude".to_string(), "gemini".to_string()])
+        );
+        assert_eq!(
+            expr.clauses[1].term,
+            FilterTerm::Tokens(CompareOp::Gt, 50_000)
+        );
+        assert!(matches!(expr.clauses[2].term, FilterTerm::Since(_)));
+        assert!(expr.clauses[3].negated);
+        assert_eq!(
+            expr.clauses[3].term,
+            FilterTerm::Tool(ToolFilter {
+                name: "Bash".to_string(),
+                success: Some(false),
+            })
+        );
+        assert_eq!(expr.text(), "fix bug");
+
+        // Unknown keys are text; bad values are errors
+        assert_eq!(FilterExpr::parse("http://x").unwrap().text(), "http://x");
+        assert!(FilterExpr::parse("tokens>lots").is_err());
+        assert!(FilterExpr::parse("tokens>99999999999999999k").is_err());
+        assert!(FilterExpr::parse("provider>claude").is_err());
+        assert!(FilterExpr::parse("since:\"last week").is_err());
+    }
+
+    #[test]
+    fn test_matches_session() {
+        let claude = session(Provider::ClaudeCode, "retrochat", 40, 80_000);
+        let gemini = session(Provider::GeminiCLI, "other", 5, 1_000);
+        let mut context = FilterContext::default();
+
+        let expr = FilterExpr::parse("provider:claude project:RetroChat tokens>50000").unwrap();
+        assert!(expr.matches(&claude, &context));
+        assert!(!expr.matches(&gemini, &context));
+
+        let expr = FilterExpr::parse("-project:retrochat messages<=5").unwrap();
+        assert!(!expr.matches(&claude, &context));
+        assert!(expr.matches(&gemini, &context));
+
+        let expr = FilterExpr::parse("tool:Bash(failed)").unwrap();
+        assert!(!expr.matches(&claude, &context));
+        context
+            .tools
+            .insert(expr.tool_filters()[0].clone(), HashSet::from([claude.id]));
+        assert!(expr.matches(&claude, &context));
+
+        let expr = FilterExpr::parse("outcome:abandoned,partial").unwrap();
+        assert!(expr.needs_outcomes());
+        context
+            .outcomes
+            .insert(claude.id, SessionOutcome::Abandoned);
+        context
+            .outcomes
+            .insert(gemini.id, SessionOutcome::Completed);
+        assert!(expr.matches(&claude, &context));
+        assert!(!expr.matches(&gemini, &context));
+        assert!(FilterExpr::parse("outcome:unknown").is_err());
+
+        let expr = FilterExpr::parse(r#"topic:"Docker builds",4"#).unwrap();
+        assert!(expr.needs_topics());
+        assert!(!expr.matches(&claude, &context));
+        context
+            .topics
+            .insert(claude.id, (2, "docker BUILDS".to_string()));
+        context.topics.insert(gemini.id, (4, "Auth".to_string()));
+        assert!(expr.matches(&claude, &context));
+        assert!(expr.matches(&gemini, &context));
+        assert!(!FilterExpr::parse("topic:3")
+            .unwrap()
+            .matches(&gemini, &context));
+
+        let mut archived = claude.clone();
+        archived.state = SessionState::Archived;
+        assert!(!FilterExpr::default().includes_archived());
+        let expr = FilterExpr::parse("archived:true").unwrap();
+        assert!(expr.includes_archived());
+        assert!(expr.matches(&archived, &context));
+        assert!(!expr.matches(&claude, &context));
+
+        let mut shared = gemini.clone();
+        shared.owner = Some("alice".to_string());
+        let expr = FilterExpr::parse("owner:team").unwrap();
+        assert!(expr.matches(&shared, &context));
+        assert!(!expr.matches(&gemini, &context));
+        let expr = FilterExpr::parse("owner:me,alice").unwrap();
+        assert!(expr.matches(&shared, &context));
+        assert!(expr.matches(&claude, &context));
+    }
+}
@@ -0,0 +1,133 @@
+//! Programming language of a file, from its name
+//!
+//! Used to break file changes down by language; files with no known
+//! extension count as [`OTHER`].
+
+use std::path::Path;
+
+/// Language of files that match no mapping
+pub const OTHER: &str = "Other";
+
+/// Lowercase extension -> language
+const EXTENSIONS: &[(&str, &str)] = &[
+    ("rs", "Rust"),
+    ("py", "Python"),
+    ("pyi", "Python"),
+    ("ipynb", "Jupyter Notebook"),
+    ("js", "JavaScript"),
+    ("mjs", "JavaScript"),
+    ("cjs", "JavaScript"),
+    ("jsx", "JavaScript"),
+    ("ts", "TypeScript"),
+    ("mts", "TypeScript"),
+    ("cts", "TypeScript"),
+    ("tsx", "TypeScript"),
+    ("go", "Go"),
+    ("java", "Java"),
+    ("kt", "Kotlin"),
+    ("kts", "Kotlin"),
+    ("scala", "Scala"),
+    ("swift", "Swift"),
+    ("m", "Objective-C"),
+    ("mm", "Objective-C"),
+    ("c", "C"),
+    ("h", "C"),
+    ("cc", "C++"),
+    ("cpp", "C++"),
+    ("cxx", "C++"),
+    ("hh", "C++"),
+    ("hpp", "C++"),
+    ("hxx", "C++"),
+    ("cs", "C#"),
+    ("fs", "F#"),
+    ("rb", "Ruby"),
+    ("php", "PHP"),
+    ("dart", "Dart"),
+    ("lua", "Lua"),
+    ("ex", "Elixir"),
+    ("exs", "Elixir"),
+    ("erl", "Erlang"),
+    ("hs", "Haskell"),
+    ("ml", "OCaml"),
+    ("mli", "OCaml"),
+    ("clj", "Clojure"),
+    ("zig", "Zig"),
+    ("r", "R"),
+    ("jl", "Julia"),
+    ("sql", "SQL"),
+    ("sh", "Shell"),
+    ("bash", "Shell"),
+    ("zsh", "Shell"),
+    ("fish", "Shell"),
+    ("ps1", "PowerShell"),
+    ("html", "HTML"),
+    ("htm", "HTML"),
+    ("css", "CSS"),
+    ("scss", "SCSS"),
+    ("sass", "SCSS"),
+    ("less", "Less"),
+    ("vue", "Vue"),
+    ("svelte", "Svelte"),
+    ("md", "Markdown"),
+    ("mdx", "Markdown"),
+    ("json", "JSON"),
+    ("jsonc", "JSON"),
+    ("yaml", "YAML"),
+    ("yml", "YAML"),
+    ("toml", "TOML"),
+    ("xml", "XML"),
+    ("proto", "Protocol Buffers"),
+    ("graphql", "GraphQL"),
+    ("gql", "GraphQL"),
+    ("tf", "HCL"),
+    ("hcl", "HCL"),
+    ("nix", "Nix"),
+];
+
+/// Files recognized by their whole name
+const FILE_NAMES: &[(&str, &str)] = &[
+    ("Dockerfile", "Dockerfile"),
+    ("Containerfile", "Dockerfile"),
+    ("Makefile", "Makefile"),
+    ("GNUmakefile", "Makefile"),
+    ("CMakeLists.txt", "CMake"),
+    ("Gemfile", "Ruby"),
+    ("Rakefile", "Ruby"),
+    ("Justfile", "Just"),
+    ("justfile", "Just"),
+];
+
+/// Language of the file at `path`, or [`OTHER`]
+pub fn language_for_path(path: &str) -> &'static str {
+    let path = Path::new(path);
+    let file_name = path.file_name().and_then(|name| name.to_str());
+    if let Some((_, language)) =
+        file_name.and_then(|name| FILE_NAMES.iter().find(|(known, _)| *known == name))
+    {
+        return language;
+    }
+
+    let Some(extension) = path.extension().and_then(|ext| ext.to_str()) else {
+        return OTHER;
+    };
+    let extension = extension.to_ascii_lowercase();
+    EXTENSIONS
+        .iter()
+        .find(|(known, _)| *known == extension)
+        .map_or(OTHER, |(_, language)| language)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_for_path() {
+        assert_eq!(language_for_path("src/main.rs"), "Rust");
+        assert_eq!(language_for_path("/app/web/App.TSX"), "TypeScript");
+        assert_eq!(language_for_path("docker/Dockerfile"), "Dockerfile");
+        assert_eq!(language_for_path("include/vec.h"), "C");
+        assert_eq!(language_for_path("LICENSE"), OTHER);
+        assert_eq!(language_for_path("notes.unknown"), OTHER);
+    }
+}
@@ -0,0 +1,230 @@
+//! Output language for LLM-written text and locale-aware date formatting
+//!
+//! Set with `retrochat config set analysis.language ko`. English keeps the
+//! prompts and ISO dates RetroChat has always used, so its LLM cache
+//! entries stay valid.
+
+use chrono::{DateTime, NaiveDate, TimeZone};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use crate::config::Config;
+
+/// Language analyses and summaries are written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Language {
+    #[default]
+    English,
+    Korean,
+    Japanese,
+    Chinese,
+    Spanish,
+    French,
+    German,
+    Portuguese,
+}
+
+impl Language {
+    pub const ALL: [Language; 8] = [
+        Language::English,
+        Language::Korean,
+        Language::Japanese,
+        Language::Chinese,
+        Language::Spanish,
+        Language::French,
+        Language::German,
+        Language::Portuguese,
+    ];
+
+    /// ISO 639-1 code, as written in the config file
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Korean => "ko",
+            Language::Japanese => "ja",
+            Language::Chinese => "zh",
+            Language::Spanish => "es",
+            Language::French => "fr",
+            Language::German => "de",
+            Language::Portuguese => "pt",
+        }
+    }
+
+    /// English name, as given to the LLM
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Korean => "Korean",
+            Language::Japanese => "Japanese",
+            Language::Chinese => "Chinese",
+            Language::Spanish => "Spanish",
+            Language::French => "French",
+            Language::German => "German",
+            Language::Portuguese => "Portuguese",
+        }
+    }
+
+    /// Language from the config file, or English if it cannot be read
+    pub fn load() -> Self {
+        match Config::load() {
+            Ok(config) => Self::from_config(&config),
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to load analysis language, using English");
+                Self::default()
+            }
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        config
+            .analysis
+            .language
+            .as_deref()
+            .and_then(|code| code.parse().ok())
+            .unwrap_or_default()
+    }
+
+    /// Language loaded once per process, for formatting dates in output
+    pub fn current() -> Self {
+        static CURRENT: OnceLock<Language> = OnceLock::new();
+        *CURRENT.get_or_init(Self::load)
+    }
+
+    /// Append an instruction to write free text in this language
+    ///
+    /// Field labels and enum values are parsed by RetroChat, so they stay in
+    /// English. English prompts are returned unchanged.
+    pub fn localize_prompt(&self, prompt: String) -> String {
+        if *self == Language::English {
+            return prompt;
+        }
+        format!(
+            "{prompt}\n\n## Output Language\n\nWrite all free text (titles, summaries, observations, reasoning) in {name}. Keep field names, labels and fixed values (such as turn types, outcomes and scores) exactly as specified above, in English.",
+            name = self.name()
+        )
+    }
+
+    fn date_pattern(&self) -> &'static str {
+        match self {
+            Language::English => "%Y-%m-%d",
+            Language::Korean => "%Y. %m. %d.",
+            Language::Japanese | Language::Chinese => "%Y/%m/%d",
+            Language::German => "%d.%m.%Y",
+            Language::Spanish | Language::French | Language::Portuguese => "%d/%m/%Y",
+        }
+    }
+
+    fn month_day_pattern(&self) -> &'static str {
+        match self {
+            Language::English => "%m-%d",
+            Language::Korean => "%m. %d.",
+            Language::Japanese | Language::Chinese => "%m/%d",
+            Language::German => "%d.%m.",
+            Language::Spanish | Language::French | Language::Portuguese => "%d/%m",
+        }
+    }
+
+    pub fn format_date(&self, date: NaiveDate) -> String {
+        date.format(self.date_pattern()).to_string()
+    }
+
+    /// Date with hours and minutes
+    pub fn format_datetime<Tz: TimeZone>(&self, time: &DateTime<Tz>) -> String
+    where
+        Tz::Offset: fmt::Display,
+    {
+        time.format(&format!("{} %H:%M", self.date_pattern()))
+            .to_string()
+    }
+
+    /// Date with seconds, for logs and error listings
+    pub fn format_datetime_secs<Tz: TimeZone>(&self, time: &DateTime<Tz>) -> String
+    where
+        Tz::Offset: fmt::Display,
+    {
+        time.format(&format!("{} %H:%M:%S", self.date_pattern()))
+            .to_string()
+    }
+
+    /// Month, day and time without the year, for dense timelines
+    pub fn format_month_day_time<Tz: TimeZone>(&self, time: &DateTime<Tz>) -> String
+    where
+        Tz::Offset: fmt::Display,
+    {
+        time.format(&format!("{} %H:%M", self.month_day_pattern()))
+            .to_string()
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for Language {
+    type Err = String;
+
+    /// Accepts a code (`ko`), a locale (`ko-KR`, `ko_KR.UTF-8`) or an English name
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.trim().to_lowercase();
+        let code = value.split(['-', '_', '.']).next().unwrap_or_default();
+        Language::ALL
+            .into_iter()
+            .find(|language| {
+                language.code() == code || language.name().eq_ignore_ascii_case(&value)
+            })
+            .ok_or_else(|| {
+                let codes: Vec<&str> = Language::ALL.iter().map(|l| l.code()).collect();
+                format!(
+                    "Unsupported language: {s} (expected one of {})",
+                    codes.join(", ")
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn test_parse_language() {
+        assert_eq!("ko".parse::<Language>(), Ok(Language::Korean));
+        assert_eq!("ja_JP.UTF-8".parse::<Language>(), Ok(Language::Japanese));
+        assert_eq!("pt-BR".parse::<Language>(), Ok(Language::Portuguese));
+        assert_eq!("German".parse::<Language>(), Ok(Language::German));
+        assert!("tlh".parse::<Language>().is_err());
+    }
+
+    #[test]
+    fn test_localize_prompt() {
+        let prompt = "Summarize this.".to_string();
+        assert_eq!(Language::English.localize_prompt(prompt.clone()), prompt);
+
+        let localized = Language::Korean.localize_prompt(prompt);
+        assert!(localized.starts_with("Summarize this."));
+        assert!(localized.contains("in Korean"));
+    }
+
+    #[test]
+    fn test_format_dates() {
+        let time = Utc.with_ymd_and_hms(2026, 3, 7, 14, 5, 9).unwrap();
+        let date = time.date_naive();
+
+        assert_eq!(Language::English.format_date(date), "2026-03-07");
+        assert_eq!(Language::English.format_datetime(&time), "2026-03-07 14:05");
+        assert_eq!(Language::Korean.format_date(date), "2026. 03. 07.");
+        assert_eq!(
+            Language::Japanese.format_datetime(&time),
+            "2026/03/07 14:05"
+        );
+        assert_eq!(
+            Language::German.format_datetime_secs(&time),
+            "07.03.2026 14:05:09"
+        );
+        assert_eq!(Language::French.format_month_day_time(&time), "07/03 14:05");
+    }
+}
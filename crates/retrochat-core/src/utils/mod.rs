@@ -1,2 +1,13 @@
+pub mod anonymize;
 pub mod bash_utils;
+pub mod clipboard;
+pub mod crypto;
+pub mod deep_link;
+pub mod filter_expr;
+pub mod language;
+pub mod locale;
+pub mod session_title;
+pub mod template;
 pub mod time_parser;
+pub mod token_estimate;
+pub mod wsl;
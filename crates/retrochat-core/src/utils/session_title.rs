@@ -0,0 +1,69 @@
+//! Heuristic session titles derived from the conversation itself
+
+use crate::models::message::MessageType;
+use crate::models::Message;
+
+/// Maximum characters kept in a generated title
+pub const MAX_TITLE_CHARS: usize = 80;
+
+/// Title from the first line of the first user prompt
+///
+/// Lines that look like injected markup (e.g. `<command-name>` wrappers) are
+/// skipped so the title reflects what the user actually typed.
+pub fn title_from_messages(messages: &[Message]) -> Option<String> {
+    messages
+        .iter()
+        .filter(|m| m.is_user_message() && m.message_type == MessageType::SimpleMessage)
+        .flat_map(|m| m.content.lines())
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('<'))
+        .map(|line| truncate_title(line, MAX_TITLE_CHARS))
+}
+
+/// Shorten `title` to at most `max_chars` characters, ending with "..." if cut
+pub fn truncate_title(title: &str, max_chars: usize) -> String {
+    if title.chars().count() <= max_chars {
+        return title.to_string();
+    }
+    let truncated: String = title.chars().take(max_chars.saturating_sub(3)).collect();
+    format!("{}...", truncated.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::MessageRole;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn message(role: MessageRole, content: &str) -> Message {
+        Message::new(Uuid::new_v4(), role, content.to_string(), Utc::now(), 1)
+    }
+
+    #[test]
+    fn test_title_from_first_user_prompt() {
+        let messages = vec![
+            message(MessageRole::Assistant, "How can I help?"),
+            message(
+                MessageRole::User,
+                "<command-name>/clear</command-name>\n\nFix the flaky login test\nIt fails on CI",
+            ),
+        ];
+
+        assert_eq!(
+            title_from_messages(&messages).as_deref(),
+            Some("Fix the flaky login test")
+        );
+        assert_eq!(
+            title_from_messages(&[message(MessageRole::Assistant, "hi")]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_truncate_title() {
+        assert_eq!(truncate_title("short", 10), "short");
+        assert_eq!(truncate_title("a long title here", 10), "a long...");
+        assert_eq!(truncate_title("ééééééééééé", 5).chars().count(), 5);
+    }
+}
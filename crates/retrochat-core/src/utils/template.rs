@@ -0,0 +1,599 @@
+//! Handlebars templates for `retrochat export --format template`
+//!
+//! Supports the part of Handlebars that report layouts need:
+//!
+//! | Syntax                              | Meaning                                      |
+//! |-------------------------------------|----------------------------------------------|
+//! | `{{path.to.value}}`                 | the value, HTML-escaped                      |
+//! | `{{{path}}}`                        | the value as is                              |
+//! | `{{#each list}}…{{else}}…{{/each}}` | once per item, or the else part when empty   |
+//! | `{{#if path}}…{{else}}…{{/if}}`     | when the value is truthy                     |
+//! | `{{#unless path}}…{{/unless}}`      | when the value is falsy                      |
+//! | `{{#with path}}…{{/with}}`          | with the value as `this`                     |
+//! | `this`, `../path`, `@root.path`     | the current, enclosing or outermost value    |
+//! | `@index`, `@first`, `@last`, `@key` | the position inside `#each`                  |
+//! | `{{! note }}`, `{{!-- note --}}`    | nothing                                      |
+//! | `{{~ … ~}}`                         | strip the whitespace before or after the tag |
+//!
+//! As in Handlebars, block tags alone on their line leave no blank line
+//! behind, and `null`, `false`, `0`, `""` and `[]` are falsy.
+
+use anyhow::{anyhow, bail, Result};
+use serde_json::Value;
+
+/// A parsed template, ready to render any number of times
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Text(String),
+    Value { path: PathExpr, escape: bool },
+    Block(Block),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Block {
+    kind: BlockKind,
+    path: PathExpr,
+    body: Vec<Node>,
+    inverse: Vec<Node>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BlockKind {
+    Each,
+    If,
+    Unless,
+    With,
+}
+
+impl BlockKind {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "each" => Some(BlockKind::Each),
+            "if" => Some(BlockKind::If),
+            "unless" => Some(BlockKind::Unless),
+            "with" => Some(BlockKind::With),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            BlockKind::Each => "each",
+            BlockKind::If => "if",
+            BlockKind::Unless => "unless",
+            BlockKind::With => "with",
+        }
+    }
+}
+
+/// A value reference such as `../session.title` or `@index`
+#[derive(Debug, Clone, PartialEq)]
+struct PathExpr {
+    /// Scopes to go up (`../`), or `None` for `@root`
+    depth: Option<usize>,
+    /// `index`, `first`, `last` or `key`
+    variable: Option<String>,
+    segments: Vec<String>,
+}
+
+impl PathExpr {
+    fn parse(source: &str) -> Result<Self> {
+        let mut rest = source.trim();
+        if rest.is_empty() || rest.contains(char::is_whitespace) {
+            bail!("expected one value reference, found '{source}'");
+        }
+
+        if let Some(after) = rest.strip_prefix("@root") {
+            return Ok(PathExpr {
+                depth: None,
+                variable: None,
+                segments: split_segments(after),
+            });
+        }
+        if let Some(variable) = rest.strip_prefix('@') {
+            if !matches!(variable, "index" | "first" | "last" | "key") {
+                bail!("unknown variable '@{variable}'");
+            }
+            return Ok(PathExpr {
+                depth: Some(0),
+                variable: Some(variable.to_string()),
+                segments: Vec::new(),
+            });
+        }
+
+        let mut depth = 0;
+        while let Some(after) = rest.strip_prefix("../") {
+            depth += 1;
+            rest = after;
+        }
+        let rest = match rest.strip_prefix("this") {
+            Some(after) if after.is_empty() || after.starts_with('.') => after,
+            _ => rest,
+        };
+        Ok(PathExpr {
+            depth: Some(depth),
+            variable: None,
+            segments: split_segments(rest),
+        })
+    }
+}
+
+fn split_segments(path: &str) -> Vec<String> {
+    path.split('.')
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Text between tags, or one `{{…}}` tag with its surrounding markers removed
+#[derive(Debug)]
+enum Token {
+    Text(String),
+    Tag {
+        content: String,
+        raw: bool,
+        line: usize,
+    },
+}
+
+/// What ended a run of nodes
+#[derive(Debug, PartialEq, Eq)]
+enum End {
+    Else,
+    Close,
+    Eof,
+}
+
+impl Template {
+    pub fn parse(source: &str) -> Result<Self> {
+        let mut tokens = tokenize(source)?.into_iter();
+        let (nodes, _) = parse_nodes(&mut tokens, None)?;
+        Ok(Template { nodes })
+    }
+
+    /// Render against a context; missing values render as nothing
+    pub fn render(&self, context: &Value) -> String {
+        let mut output = String::new();
+        let mut scopes = vec![Scope {
+            value: context,
+            locals: None,
+        }];
+        render_nodes(&self.nodes, &mut scopes, &mut output);
+        output
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    // Token index of each tag, whether it strips whitespace before and after
+    // it, and whether it is a block tag that may stand alone on its line
+    let mut tags = Vec::new();
+    let mut rest = source;
+
+    while let Some(start) = rest.find("{{") {
+        tokens.push(Token::Text(rest[..start].to_string()));
+        let line = source.len() - rest.len() + start;
+        let line = source[..line].matches('\n').count() + 1;
+        let tag = &rest[start..];
+
+        let (open, close) = if tag.starts_with("{{{") {
+            (3, "}}}")
+        } else if tag.starts_with("{{!--") || tag.starts_with("{{~!--") {
+            (2, "--}}")
+        } else {
+            (2, "}}")
+        };
+        let end = tag[open..]
+            .find(close)
+            .ok_or_else(|| anyhow!("Unclosed tag at line {line}: expected '{close}'"))?;
+        let mut content = &tag[open..open + end];
+        rest = &tag[open + end + close.len()..];
+
+        let strip_before = content.starts_with('~');
+        if strip_before {
+            content = &content[1..];
+        }
+        let strip_after = content.ends_with('~');
+        if strip_after {
+            content = &content[..content.len() - 1];
+        }
+        let content = content.trim().to_string();
+        let raw = open == 3;
+        let block = !raw && (content.starts_with(['#', '/', '!']) || content == "else");
+
+        tags.push((tokens.len(), strip_before, strip_after, block));
+        tokens.push(Token::Tag { content, raw, line });
+    }
+    tokens.push(Token::Text(rest.to_string()));
+
+    // Tags removed as standalone; the text after them starts a line
+    let mut standalone = vec![false; tokens.len()];
+    for (index, strip_before, strip_after, block) in tags {
+        if strip_before {
+            if let Token::Text(text) = &mut tokens[index - 1] {
+                text.truncate(text.trim_end().len());
+            }
+        }
+        if strip_after {
+            if let Token::Text(text) = &mut tokens[index + 1] {
+                *text = text.trim_start().to_string();
+            }
+        }
+        if block && !strip_before && !strip_after {
+            let at_line_start = index == 1 || (index >= 3 && standalone[index - 2]);
+            standalone[index] = strip_standalone(&mut tokens, index, at_line_start);
+        }
+    }
+
+    Ok(tokens
+        .into_iter()
+        .filter(|token| !matches!(token, Token::Text(text) if text.is_empty()))
+        .collect())
+}
+
+/// Remove the indentation and line break around a block tag alone on its line
+///
+/// Tags alternate with (possibly empty) text tokens, so `index - 1` and
+/// `index + 1` are always text. `at_line_start` tells whether the text before
+/// the tag starts a line.
+fn strip_standalone(tokens: &mut [Token], index: usize, at_line_start: bool) -> bool {
+    let (Token::Text(before), Token::Text(after)) = (&tokens[index - 1], &tokens[index + 1]) else {
+        return false;
+    };
+    let line_start = match before.rfind('\n') {
+        Some(newline) => newline + 1,
+        None if at_line_start => 0,
+        None => return false,
+    };
+    let line_end = match after.find('\n') {
+        Some(newline) => newline + 1,
+        None if index + 2 == tokens.len() => after.len(),
+        None => return false,
+    };
+    if !before[line_start..].trim().is_empty() || !after[..line_end].trim().is_empty() {
+        return false;
+    }
+
+    if let Token::Text(before) = &mut tokens[index - 1] {
+        before.truncate(line_start);
+    }
+    if let Token::Text(after) = &mut tokens[index + 1] {
+        after.replace_range(..line_end, "");
+    }
+    true
+}
+
+/// Parse nodes up to the end of input, or up to the `{{else}}` or `{{/…}}`
+/// of `open` (the kind and line of the enclosing block)
+fn parse_nodes(
+    tokens: &mut impl Iterator<Item = Token>,
+    open: Option<(BlockKind, usize)>,
+) -> Result<(Vec<Node>, End)> {
+    let mut nodes = Vec::new();
+
+    while let Some(token) = tokens.next() {
+        let (content, raw, line) = match token {
+            Token::Text(text) => {
+                nodes.push(Node::Text(text));
+                continue;
+            }
+            Token::Tag { content, raw, line } => (content, raw, line),
+        };
+
+        if content.starts_with('!') && !raw {
+            continue;
+        }
+        if let Some(header) = content.strip_prefix('#').filter(|_| !raw) {
+            let (name, argument) = header
+                .split_once(char::is_whitespace)
+                .unwrap_or((header, ""));
+            let kind = BlockKind::from_name(name)
+                .ok_or_else(|| anyhow!("Unknown block helper '#{name}' at line {line}"))?;
+            let path = PathExpr::parse(argument)
+                .map_err(|e| anyhow!("Invalid '#{name}' at line {line}: {e}"))?;
+
+            let (body, end) = parse_nodes(tokens, Some((kind, line)))?;
+            let inverse = if end == End::Else {
+                match parse_nodes(tokens, Some((kind, line)))? {
+                    (inverse, End::Close) => inverse,
+                    _ => bail!("'#{name}' from line {line} has more than one else"),
+                }
+            } else {
+                Vec::new()
+            };
+            nodes.push(Node::Block(Block {
+                kind,
+                path,
+                body,
+                inverse,
+            }));
+            continue;
+        }
+        if let Some(name) = content.strip_prefix('/').filter(|_| !raw) {
+            let name = name.trim();
+            return match open {
+                Some((kind, _)) if kind.name() == name => Ok((nodes, End::Close)),
+                Some((kind, open_line)) => bail!(
+                    "'/{name}' at line {line} does not close '#{}' from line {open_line}",
+                    kind.name()
+                ),
+                None => bail!("'/{name}' at line {line} closes no block"),
+            };
+        }
+        if content == "else" && !raw {
+            if open.is_none() {
+                bail!("'else' outside of a block at line {line}");
+            }
+            return Ok((nodes, End::Else));
+        }
+
+        let path =
+            PathExpr::parse(&content).map_err(|e| anyhow!("Invalid tag at line {line}: {e}"))?;
+        nodes.push(Node::Value { path, escape: !raw });
+    }
+
+    match open {
+        Some((kind, line)) => bail!("'#{}' from line {line} is never closed", kind.name()),
+        None => Ok((nodes, End::Eof)),
+    }
+}
+
+struct Scope<'v> {
+    value: &'v Value,
+    locals: Option<Locals>,
+}
+
+/// Position of the current item of an `#each`
+#[derive(Clone)]
+struct Locals {
+    index: usize,
+    first: bool,
+    last: bool,
+    key: Option<String>,
+}
+
+/// A value of the context, or one computed from the position in a loop
+enum Resolved<'v> {
+    Borrowed(&'v Value),
+    Local(Value),
+    Missing,
+}
+
+impl Resolved<'_> {
+    fn value(&self) -> &Value {
+        match self {
+            Resolved::Borrowed(value) => value,
+            Resolved::Local(value) => value,
+            Resolved::Missing => &Value::Null,
+        }
+    }
+}
+
+fn resolve<'v>(path: &PathExpr, scopes: &[Scope<'v>]) -> Resolved<'v> {
+    let scope = match path.depth {
+        None => scopes.first(),
+        Some(depth) => scopes.len().checked_sub(depth + 1).map(|i| &scopes[i]),
+    };
+    let Some(scope) = scope else {
+        return Resolved::Missing;
+    };
+
+    if let Some(variable) = &path.variable {
+        let Some(locals) = &scope.locals else {
+            return Resolved::Missing;
+        };
+        return Resolved::Local(match variable.as_str() {
+            "index" => Value::from(locals.index),
+            "first" => Value::Bool(locals.first),
+            "last" => Value::Bool(locals.last),
+            _ => locals.key.clone().map(Value::String).unwrap_or(Value::Null),
+        });
+    }
+
+    let mut value = scope.value;
+    for segment in &path.segments {
+        let next = match value {
+            Value::Object(map) => map.get(segment),
+            Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+            _ => None,
+        };
+        match next {
+            Some(next) => value = next,
+            None => return Resolved::Missing,
+        }
+    }
+    Resolved::Borrowed(value)
+}
+
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+        Value::String(s) => !s.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(_) => true,
+    }
+}
+
+fn render_nodes<'v>(nodes: &[Node], scopes: &mut Vec<Scope<'v>>, output: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => output.push_str(text),
+            Node::Value { path, escape } => {
+                let text = match resolve(path, scopes).value() {
+                    Value::Null => String::new(),
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                if *escape {
+                    push_escaped(output, &text);
+                } else {
+                    output.push_str(&text);
+                }
+            }
+            Node::Block(block) => render_block(block, scopes, output),
+        }
+    }
+}
+
+fn render_block<'v>(block: &Block, scopes: &mut Vec<Scope<'v>>, output: &mut String) {
+    let resolved = resolve(&block.path, scopes);
+    if matches!(block.kind, BlockKind::If | BlockKind::Unless) {
+        let show_body = is_truthy(resolved.value()) == (block.kind == BlockKind::If);
+        let nodes = if show_body {
+            &block.body
+        } else {
+            &block.inverse
+        };
+        render_nodes(nodes, scopes, output);
+        return;
+    }
+
+    // Loop variables are not something to iterate over or step into
+    let value = match resolved {
+        Resolved::Borrowed(value) => value,
+        Resolved::Local(_) | Resolved::Missing => &Value::Null,
+    };
+    let items: Vec<(&Value, Option<Locals>)> = match (block.kind, value) {
+        (BlockKind::With, value) if is_truthy(value) => vec![(value, None)],
+        (BlockKind::Each, Value::Array(items)) => items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let locals = Locals {
+                    index,
+                    first: index == 0,
+                    last: index + 1 == items.len(),
+                    key: None,
+                };
+                (item, Some(locals))
+            })
+            .collect(),
+        (BlockKind::Each, Value::Object(map)) => map
+            .iter()
+            .enumerate()
+            .map(|(index, (key, item))| {
+                let locals = Locals {
+                    index,
+                    first: index == 0,
+                    last: index + 1 == map.len(),
+                    key: Some(key.clone()),
+                };
+                (item, Some(locals))
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    if items.is_empty() {
+        render_nodes(&block.inverse, scopes, output);
+        return;
+    }
+    for (item, locals) in items {
+        scopes.push(Scope {
+            value: item,
+            locals,
+        });
+        render_nodes(&block.body, scopes, output);
+        scopes.pop();
+    }
+}
+
+fn push_escaped(output: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            '\'' => output.push_str("&#x27;"),
+            '`' => output.push_str("&#x60;"),
+            '=' => output.push_str("&#x3D;"),
+            _ => output.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn render(source: &str, context: Value) -> String {
+        Template::parse(source).unwrap().render(&context)
+    }
+
+    #[test]
+    fn test_values_and_escaping() {
+        let context = json!({ "session": { "title": "Fix <build>", "tokens": 1200 } });
+        assert_eq!(
+            render(
+                "{{session.title}} / {{{session.title}}} / {{session.tokens}}",
+                context
+            ),
+            "Fix &lt;build&gt; / Fix <build> / 1200"
+        );
+        assert_eq!(render("[{{missing.value}}]", json!({})), "[]");
+    }
+
+    #[test]
+    fn test_each_with_locals_and_parent_scope() {
+        let context = json!({
+            "project": "retrochat",
+            "messages": [{ "role": "User" }, { "role": "Assistant" }],
+        });
+        let template = "{{#each messages}}{{@index}}:{{role}}@{{../project}}{{#unless @last}}, {{/unless}}{{/each}}";
+        assert_eq!(
+            render(template, context),
+            "0:User@retrochat, 1:Assistant@retrochat"
+        );
+
+        let template = "{{#each items}}{{this}}{{else}}none{{/each}}";
+        assert_eq!(render(template, json!({ "items": [] })), "none");
+        let template = "{{#each counts}}{{@key}}={{this}} {{/each}}";
+        assert_eq!(
+            render(template, json!({ "counts": { "a": 1, "b": 2 } })),
+            "a=1 b=2 "
+        );
+    }
+
+    #[test]
+    fn test_if_with_and_root() {
+        let context = json!({ "title": "Retro", "session": { "outcome": "", "end": 0 } });
+        let template =
+            "{{#with session}}{{#if outcome}}{{outcome}}{{else}}{{@root.title}}{{/if}}{{/with}}";
+        assert_eq!(render(template, context), "Retro");
+        assert_eq!(
+            render("{{#if list}}yes{{else}}no{{/if}}", json!({ "list": [] })),
+            "no"
+        );
+    }
+
+    #[test]
+    fn test_standalone_tags_and_whitespace_control() {
+        let template = "# Report\n{{#each items}}\n- {{this}}\n{{/each}}\n{{! done }}\nEnd\n";
+        assert_eq!(
+            render(template, json!({ "items": ["a", "b"] })),
+            "# Report\n- a\n- b\nEnd\n"
+        );
+        assert_eq!(render("a  {{~x~}}  b", json!({ "x": "-" })), "a-b");
+        assert_eq!(render("{{!-- {{ignored}} --}}ok", json!({})), "ok");
+    }
+
+    #[test]
+    fn test_parse_errors_name_the_line() {
+        let error = Template::parse("line\n{{#each items}}\n").unwrap_err();
+        assert!(error.to_string().contains("line 2"), "{error}");
+        let error = Template::parse("{{#if a}}{{/each}}").unwrap_err();
+        assert!(error.to_string().contains("does not close"), "{error}");
+        assert!(Template::parse("{{#loop a}}{{/loop}}").is_err());
+        assert!(Template::parse("{{title").is_err());
+        assert!(Template::parse("{{else}}").is_err());
+    }
+}
@@ -1,97 +1,322 @@
+//! Time specifications for `--since`, `--until`, `--before` and filter
+//! expressions
+//!
+//! A spec names either an instant ("now", "2 hours ago", "@1697328000") or a
+//! period ("2024-10-19", "yesterday", "last monday", "this week", "this
+//! sprint"). `--since` uses the start of a period and `--until` its end, so
+//! `--until today` includes today. All calendar arithmetic is in UTC.
+
 use anyhow::{Context, Result};
-use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+use chrono::{DateTime, Datelike, Duration, Months, NaiveDate, NaiveDateTime, Utc, Weekday};
+
+use crate::config::{Config, TimeSettings};
+
+/// Sprint length used when `time.sprint_length_days` is not set
+pub const DEFAULT_SPRINT_LENGTH_DAYS: u32 = 14;
+
+const EXPECTED_FORMATS: &str = "'now', '2024-10-19', '7 days ago', '2w', 'yesterday', \
+    'last monday', 'this week', 'this sprint', '@1234567890'";
+
+/// Sprint calendar behind "this sprint" and "last sprint"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SprintCalendar {
+    /// First day of any sprint; earlier and later sprints follow from it
+    pub anchor: NaiveDate,
+    pub length_days: u32,
+}
+
+impl SprintCalendar {
+    /// Sprint calendar from `[time]` settings, if a sprint start is configured
+    pub fn from_settings(settings: &TimeSettings) -> Result<Option<Self>> {
+        let Some(start) = settings.sprint_start.as_deref() else {
+            return Ok(None);
+        };
+        let anchor = NaiveDate::parse_from_str(start.trim(), "%Y-%m-%d")
+            .with_context(|| format!("Invalid time.sprint_start: {start}"))?;
+        Ok(Some(Self {
+            anchor,
+            length_days: settings
+                .sprint_length_days
+                .unwrap_or(DEFAULT_SPRINT_LENGTH_DAYS),
+        }))
+    }
+
+    /// Start of the sprint containing `date`, shifted by `offset` sprints
+    fn sprint_start(&self, date: NaiveDate, offset: i64) -> Option<NaiveDate> {
+        let length = i64::from(self.length_days.max(1));
+        let index = (date - self.anchor)
+            .num_days()
+            .div_euclid(length)
+            .checked_add(offset)?;
+        self.anchor
+            .checked_add_signed(Duration::try_days(index.checked_mul(length)?)?)
+    }
+}
+
+/// `--since`/`--until` bounds; `None` leaves that side open
+pub type TimeRange = (Option<DateTime<Utc>>, Option<DateTime<Utc>>);
+
+/// Time span a spec names; an instant starts and ends at the same time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Period {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl Period {
+    fn instant(at: DateTime<Utc>) -> Self {
+        Self { start: at, end: at }
+    }
+
+    fn days(first: NaiveDate, count: i64) -> Self {
+        Self {
+            start: midnight(first),
+            end: midnight(first + Duration::days(count)),
+        }
+    }
+}
 
-/// Parse a time specification into a DateTime<Utc>
+/// Parse a time specification into a DateTime<Utc>; for a period such as
+/// "yesterday" this is its start
 ///
 /// Supports:
 /// - "now" keyword
-/// - ISO8601: "2024-10-19T14:23:45Z"
+/// - ISO8601: "2024-10-19T14:23:45Z", "2024-10-19 14:23"
 /// - Short date: "2024-10-19" (defaults to 00:00:00 UTC)
 /// - Unix timestamp: "@1697328000"
-/// - Relative time: "7 days ago", "1 week ago", "yesterday"
+/// - Relative time: "7 days ago", "an hour ago", "2w", "last week", "yesterday"
+/// - Calendar: "last monday", "friday", "this week", "this month", "this year"
+/// - Sprints: "this sprint", "last sprint" (needs `time.sprint_start`)
 pub fn parse_time_spec(spec: &str) -> Result<DateTime<Utc>> {
+    Ok(parse_period(spec)?.start)
+}
+
+/// Parse a time specification for an upper bound; for a period such as
+/// "today" this is its end, so the whole period is included
+pub fn parse_until_spec(spec: &str) -> Result<DateTime<Utc>> {
+    Ok(parse_period(spec)?.end)
+}
+
+/// Parse `--since`/`--until` options together, rejecting an empty range
+pub fn parse_time_range(since: Option<&str>, until: Option<&str>) -> Result<TimeRange> {
+    let from = since
+        .map(|spec| parse_time_spec(spec).with_context(|| format!("Invalid --since: {spec}")))
+        .transpose()?;
+    let to = until
+        .map(|spec| parse_until_spec(spec).with_context(|| format!("Invalid --until: {spec}")))
+        .transpose()?;
+    if let (Some(from), Some(to)) = (from, to) {
+        if from > to {
+            anyhow::bail!(
+                "--since ({}) is after --until ({})",
+                from.to_rfc3339(),
+                to.to_rfc3339()
+            );
+        }
+    }
+    Ok((from, to))
+}
+
+/// Parse a spec into the period it names, relative to the current time
+pub fn parse_period(spec: &str) -> Result<Period> {
+    let sprint = if spec.to_lowercase().contains("sprint") {
+        let config = Config::load()?;
+        Some(SprintCalendar::from_settings(&config.time)?.context(
+            "Sprint expressions need a sprint calendar: retrochat config set time.sprint_start 2024-01-08",
+        )?)
+    } else {
+        None
+    };
+    resolve_period(spec, Utc::now(), sprint)
+}
+
+/// Resolve a spec relative to `now`
+pub fn resolve_period(
+    spec: &str,
+    now: DateTime<Utc>,
+    sprint: Option<SprintCalendar>,
+) -> Result<Period> {
     let spec = spec.trim();
 
     // 1. "now" keyword
     if spec.eq_ignore_ascii_case("now") {
-        return Ok(Utc::now());
+        return Ok(Period::instant(now));
     }
 
     // 2. Unix timestamp: @1234567890
     if let Some(ts_str) = spec.strip_prefix('@') {
         let timestamp: i64 = ts_str.parse().context("Invalid unix timestamp format")?;
-        return DateTime::from_timestamp(timestamp, 0).context("Unix timestamp out of valid range");
+        return DateTime::from_timestamp(timestamp, 0)
+            .map(Period::instant)
+            .context("Unix timestamp out of valid range");
     }
 
-    // 3. ISO8601: 2024-10-19T14:23:45Z
+    // 3. ISO8601: 2024-10-19T14:23:45Z, or without an offset (UTC)
     if let Ok(dt) = DateTime::parse_from_rfc3339(spec) {
-        return Ok(dt.with_timezone(&Utc));
+        return Ok(Period::instant(dt.with_timezone(&Utc)));
+    }
+    for format in ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"] {
+        if let Ok(dt) = NaiveDateTime::parse_from_str(spec, format) {
+            return Ok(Period::instant(dt.and_utc()));
+        }
     }
 
     // 4. Short date: 2024-10-19
     if let Ok(date) = NaiveDate::parse_from_str(spec, "%Y-%m-%d") {
-        return Utc
-            .from_local_datetime(&date.and_hms_opt(0, 0, 0).unwrap())
-            .single()
-            .context("Ambiguous date/time");
+        return Ok(Period::days(date, 1));
     }
 
-    // 5. Relative time: "7 days ago", "1 week ago", "yesterday"
-    parse_relative_time(spec)
+    // 5. Relative and calendar expressions
+    parse_relative_time(spec, now, sprint)
 }
 
-fn parse_relative_time(spec: &str) -> Result<DateTime<Utc>> {
-    let now = Utc::now();
+fn parse_relative_time(
+    spec: &str,
+    now: DateTime<Utc>,
+    sprint: Option<SprintCalendar>,
+) -> Result<Period> {
+    let today = now.date_naive();
     let spec_lower = spec.to_lowercase();
+    let parts: Vec<&str> = spec_lower.split_whitespace().collect();
 
     // Special keywords
-    match spec_lower.as_str() {
-        "yesterday" => {
-            let yesterday = now - Duration::days(1);
-            let date = yesterday.date_naive();
-            return Ok(Utc
-                .from_local_datetime(&date.and_hms_opt(0, 0, 0).unwrap())
-                .single()
-                .unwrap());
+    match parts.as_slice() {
+        ["today"] => return Ok(Period::days(today, 1)),
+        ["yesterday"] => return Ok(Period::days(today - Duration::days(1), 1)),
+        ["this", "week"] => {
+            let monday = today - Duration::days(today.weekday().num_days_from_monday().into());
+            return Ok(Period::days(monday, 7));
+        }
+        ["this", "month"] => {
+            let first = today.with_day(1).context("Invalid date")?;
+            return Ok(Period {
+                start: midnight(first),
+                end: midnight(first + Months::new(1)),
+            });
         }
-        "today" => {
-            let date = now.date_naive();
-            return Ok(Utc
-                .from_local_datetime(&date.and_hms_opt(0, 0, 0).unwrap())
-                .single()
-                .unwrap());
+        ["this", "year"] => {
+            let first = NaiveDate::from_ymd_opt(today.year(), 1, 1).context("Invalid date")?;
+            return Ok(Period {
+                start: midnight(first),
+                end: midnight(first + Months::new(12)),
+            });
         }
+        ["this" | "current", "sprint"] => return sprint_period(sprint, today, 0),
+        ["last" | "previous", "sprint"] => return sprint_period(sprint, today, -1),
         _ => {}
     }
 
-    // Pattern: "N units ago"
-    let parts: Vec<&str> = spec_lower.split_whitespace().collect();
-    if parts.len() == 3 && parts[2] == "ago" {
-        let num: i64 = parts[0]
-            .parse()
-            .context("Invalid number in relative time")?;
-
-        let duration = match parts[1].trim_end_matches('s') {
-            "second" => Duration::seconds(num),
-            "minute" => Duration::minutes(num),
-            "hour" => Duration::hours(num),
-            "day" => Duration::days(num),
-            "week" => Duration::weeks(num),
-            "month" => Duration::days(num * 30), // Approximate
-            "year" => Duration::days(num * 365), // Approximate
+    // Weekdays: "monday" is the latest Monday up to today, "last monday"
+    // the latest one before today
+    let weekday = match parts.as_slice() {
+        [day] | ["this", day] => parse_weekday(day).map(|d| (d, false)),
+        ["last", day] => parse_weekday(day).map(|d| (d, true)),
+        _ => None,
+    };
+    if let Some((weekday, strictly_before)) = weekday {
+        let mut back = (i64::from(today.weekday().num_days_from_monday())
+            - i64::from(weekday.num_days_from_monday()))
+        .rem_euclid(7);
+        if back == 0 && strictly_before {
+            back = 7;
+        }
+        return Ok(Period::days(today - Duration::days(back), 1));
+    }
+
+    // Pattern: "N units ago", "an hour ago", "2w" or "last unit" (same as "1 unit ago")
+    let relative = match parts.as_slice() {
+        ["a" | "an", unit, "ago"] => Some((1, (*unit).to_string())),
+        [num, unit, "ago"] => Some((
+            num.parse::<i64>()
+                .context("Invalid number in relative time")?,
+            (*unit).to_string(),
+        )),
+        ["last", unit] => Some((1, (*unit).to_string())),
+        [compact] | [compact, "ago"] => split_compact(compact),
+        _ => None,
+    };
+    if let Some((num, unit)) = relative {
+        // Large counts are parseable but reach past the supported dates
+        let ago = |delta: Option<Duration>| {
+            delta
+                .and_then(|delta| now.checked_sub_signed(delta))
+                .context("Date out of range")
+        };
+        let at = match unit.trim_end_matches('s') {
+            "second" | "sec" => ago(Duration::try_seconds(num))?,
+            "minute" | "min" => ago(Duration::try_minutes(num))?,
+            "hour" => ago(Duration::try_hours(num))?,
+            "day" => ago(Duration::try_days(num))?,
+            "week" => ago(Duration::try_weeks(num))?,
+            "month" => now
+                .checked_sub_months(Months::new(u32::try_from(num)?))
+                .context("Date out of range")?,
+            "year" => now
+                .checked_sub_months(Months::new(u32::try_from(
+                    num.checked_mul(12).context("Date out of range")?,
+                )?))
+                .context("Date out of range")?,
+            "sprint" => {
+                let offset = num.checked_neg().context("Date out of range")?;
+                let start = sprint_period(sprint, today, offset)?.start;
+                return Ok(Period::instant(start));
+            }
             unit => {
                 return Err(anyhow::anyhow!("Unknown time unit: {unit}"));
             }
         };
 
-        return Ok(now - duration);
+        return Ok(Period::instant(at));
     }
 
     Err(anyhow::anyhow!(
-        "Invalid time specification: '{spec}'. Expected formats: 'now', '2024-10-19', '7 days ago', '@1234567890'"
+        "Invalid time specification: '{spec}'. Expected formats: {EXPECTED_FORMATS}"
     ))
 }
 
+fn sprint_period(sprint: Option<SprintCalendar>, today: NaiveDate, offset: i64) -> Result<Period> {
+    let sprint = sprint.context("No sprint calendar configured (time.sprint_start)")?;
+    let start = sprint
+        .sprint_start(today, offset)
+        .context("Date out of range")?;
+    Ok(Period::days(start, sprint.length_days.max(1).into()))
+}
+
+/// "2w", "3d", "12h", "30m", "45s"
+fn split_compact(spec: &str) -> Option<(i64, String)> {
+    let split = spec.find(|c: char| !c.is_ascii_digit())?;
+    let (num, suffix) = spec.split_at(split);
+    let num = num.parse().ok()?;
+    let unit = match suffix {
+        "s" => "second",
+        "m" => "minute",
+        "h" => "hour",
+        "d" => "day",
+        "w" => "week",
+        "mo" => "month",
+        "y" => "year",
+        _ => return None,
+    };
+    Some((num, unit.to_string()))
+}
+
+fn parse_weekday(word: &str) -> Option<Weekday> {
+    match word {
+        "monday" | "mon" => Some(Weekday::Mon),
+        "tuesday" | "tue" | "tues" => Some(Weekday::Tue),
+        "wednesday" | "wed" => Some(Weekday::Wed),
+        "thursday" | "thu" | "thurs" => Some(Weekday::Thu),
+        "friday" | "fri" => Some(Weekday::Fri),
+        "saturday" | "sat" => Some(Weekday::Sat),
+        "sunday" | "sun" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+fn midnight(date: NaiveDate) -> DateTime<Utc> {
+    date.and_time(chrono::NaiveTime::MIN).and_utc()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -173,6 +398,15 @@ mod tests {
         assert!((dt.timestamp() - expected.timestamp()).abs() < 2);
     }
 
+    #[test]
+    fn test_parse_last_unit() {
+        let dt = parse_time_spec("last week").unwrap();
+        let expected = Utc::now() - Duration::weeks(1);
+        // Allow 2 second tolerance
+        assert!((dt.timestamp() - expected.timestamp()).abs() < 2);
+        assert!(parse_time_spec("last fortnight").is_err());
+    }
+
     #[test]
     fn test_parse_today() {
         let result = parse_time_spec("today");
@@ -184,6 +418,127 @@ mod tests {
         assert_eq!(dt.minute(), 0);
     }
 
+    fn at(spec: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(spec)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_resolve_calendar_periods() {
+        // A Wednesday
+        let now = at("2024-10-16T15:30:00Z");
+        let resolve = |spec: &str| resolve_period(spec, now, None).unwrap();
+
+        assert_eq!(
+            resolve("today"),
+            Period {
+                start: at("2024-10-16T00:00:00Z"),
+                end: at("2024-10-17T00:00:00Z")
+            }
+        );
+        assert_eq!(resolve("Yesterday").start, at("2024-10-15T00:00:00Z"));
+        assert_eq!(resolve("2024-10-01").end, at("2024-10-02T00:00:00Z"));
+        assert_eq!(
+            resolve("2024-10-01 09:15").start,
+            at("2024-10-01T09:15:00Z")
+        );
+
+        assert_eq!(resolve("last monday").start, at("2024-10-14T00:00:00Z"));
+        assert_eq!(resolve("monday").start, at("2024-10-14T00:00:00Z"));
+        assert_eq!(resolve("last wednesday").start, at("2024-10-09T00:00:00Z"));
+        assert_eq!(resolve("wed").start, at("2024-10-16T00:00:00Z"));
+        assert_eq!(resolve("last friday").start, at("2024-10-11T00:00:00Z"));
+
+        let week = resolve("this week");
+        assert_eq!(week.start, at("2024-10-14T00:00:00Z"));
+        assert_eq!(week.end, at("2024-10-21T00:00:00Z"));
+        let month = resolve("this month");
+        assert_eq!(month.start, at("2024-10-01T00:00:00Z"));
+        assert_eq!(month.end, at("2024-11-01T00:00:00Z"));
+        assert_eq!(resolve("this year").start, at("2024-01-01T00:00:00Z"));
+    }
+
+    #[test]
+    fn test_resolve_relative_instants() {
+        let now = at("2024-03-31T12:00:00Z");
+        let resolve = |spec: &str| resolve_period(spec, now, None).unwrap();
+
+        assert_eq!(
+            resolve("2 weeks ago"),
+            Period::instant(at("2024-03-17T12:00:00Z"))
+        );
+        assert_eq!(resolve("2w").start, at("2024-03-17T12:00:00Z"));
+        assert_eq!(resolve("3d ago").start, at("2024-03-28T12:00:00Z"));
+        assert_eq!(resolve("an hour ago").start, at("2024-03-31T11:00:00Z"));
+        assert_eq!(resolve("90m").start, at("2024-03-31T10:30:00Z"));
+        // Calendar months, clamped to the end of shorter months
+        assert_eq!(resolve("1 month ago").start, at("2024-02-29T12:00:00Z"));
+        assert_eq!(resolve("1y").start, at("2023-03-31T12:00:00Z"));
+
+        assert!(resolve_period("2 fortnights ago", now, None).is_err());
+        assert!(resolve_period("12x", now, None).is_err());
+        assert!(resolve_period("this sprint", now, None).is_err());
+    }
+
+    #[test]
+    fn test_resolve_overflowing_counts_fails() {
+        let now = at("2024-10-16T15:30:00Z");
+        for spec in [
+            "99999999999999 days ago",
+            "99999999999999 weeks ago",
+            "9223372036854775807 hours ago",
+            "9223372036854775807 seconds ago",
+            "999999999999999999y",
+            "99999999999 months ago",
+        ] {
+            assert!(resolve_period(spec, now, None).is_err(), "{spec}");
+        }
+
+        let sprint = SprintCalendar::from_settings(&TimeSettings {
+            sprint_start: Some("2024-01-08".to_string()),
+            sprint_length_days: None,
+        })
+        .unwrap();
+        assert!(resolve_period("99999999999999 sprints ago", now, sprint).is_err());
+    }
+
+    #[test]
+    fn test_resolve_sprints() {
+        let sprint = SprintCalendar::from_settings(&TimeSettings {
+            sprint_start: Some("2024-01-08".to_string()),
+            sprint_length_days: None,
+        })
+        .unwrap();
+        let now = at("2024-10-16T15:30:00Z");
+        let resolve = |spec: &str| resolve_period(spec, now, sprint).unwrap();
+
+        // Sprints start every other Monday from 2024-01-08
+        let this = resolve("this sprint");
+        assert_eq!(this.start, at("2024-10-14T00:00:00Z"));
+        assert_eq!(this.end, at("2024-10-28T00:00:00Z"));
+        assert_eq!(resolve("last sprint").start, at("2024-09-30T00:00:00Z"));
+        assert_eq!(resolve("2 sprints ago").start, at("2024-09-16T00:00:00Z"));
+
+        // Before the anchor, sprints continue backwards
+        let early = resolve_period("this sprint", at("2024-01-01T00:00:00Z"), sprint).unwrap();
+        assert_eq!(early.start, at("2023-12-25T00:00:00Z"));
+
+        assert!(SprintCalendar::from_settings(&TimeSettings::default())
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_parse_time_range() {
+        let (from, to) = parse_time_range(Some("2024-10-01"), Some("2024-10-01")).unwrap();
+        assert_eq!(from, Some(at("2024-10-01T00:00:00Z")));
+        assert_eq!(to, Some(at("2024-10-02T00:00:00Z")));
+
+        assert_eq!(parse_time_range(None, None).unwrap(), (None, None));
+        assert!(parse_time_range(Some("2024-10-03"), Some("2024-10-01")).is_err());
+    }
+
     #[test]
     fn test_parse_plural_units() {
         // Test plural forms
@@ -0,0 +1,148 @@
+//! Token count estimation for messages without provider-reported usage
+//!
+//! No tokenizer is bundled; instead each provider gets a profile of how its
+//! tokenizer tends to split text. ASCII words cost roughly one token per few
+//! characters, punctuation is usually its own token, and CJK characters cost
+//! about a token each. `retrochat tokens accuracy` compares the estimates with
+//! reported usage where a provider records it.
+
+use crate::models::Provider;
+
+/// How a provider's tokenizer splits text, on average
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct TokenizerProfile {
+    /// Characters of an ASCII word per token
+    word_chars: f64,
+    /// Tokens per ASCII punctuation character
+    punctuation: f64,
+    /// Tokens per CJK (or other wide) character
+    wide_char: f64,
+    /// Tokens per other non-ASCII character (accented letters, symbols)
+    other_char: f64,
+}
+
+const CLAUDE_PROFILE: TokenizerProfile = TokenizerProfile {
+    word_chars: 4.0,
+    punctuation: 0.8,
+    wide_char: 1.2,
+    other_char: 0.6,
+};
+
+const GEMINI_PROFILE: TokenizerProfile = TokenizerProfile {
+    word_chars: 4.5,
+    punctuation: 0.7,
+    wide_char: 0.8,
+    other_char: 0.5,
+};
+
+/// OpenAI-style BPE (Codex, Cursor and unknown providers)
+const OPENAI_PROFILE: TokenizerProfile = TokenizerProfile {
+    word_chars: 4.2,
+    punctuation: 0.7,
+    wide_char: 1.0,
+    other_char: 0.5,
+};
+
+fn profile(provider: &Provider) -> TokenizerProfile {
+    match provider {
+        Provider::ClaudeCode => CLAUDE_PROFILE,
+        Provider::GeminiCLI => GEMINI_PROFILE,
+        _ => OPENAI_PROFILE,
+    }
+}
+
+/// Whether a character is from a script tokenized about one token per
+/// character (CJK ideographs, kana, Hangul and their punctuation)
+fn is_wide(c: char) -> bool {
+    matches!(c as u32,
+        0x2E80..=0x9FFF | 0xA960..=0xA97F | 0xAC00..=0xD7FF | 0xF900..=0xFAFF | 0xFF00..=0xFFEF
+            | 0x20000..=0x3FFFF)
+}
+
+/// Estimated number of tokens `text` takes with `provider`'s tokenizer
+pub fn estimate_tokens(text: &str, provider: &Provider) -> u32 {
+    let profile = profile(provider);
+    let mut tokens = 0.0;
+    let mut word = 0usize;
+    let mut whitespace = 0usize;
+
+    let flush_word = |word: &mut usize, tokens: &mut f64| {
+        if *word > 0 {
+            *tokens += (*word as f64 / profile.word_chars).ceil();
+            *word = 0;
+        }
+    };
+    let flush_whitespace = |whitespace: &mut usize, tokens: &mut f64| {
+        // A single space is merged into the next word; indentation runs are not
+        if *whitespace > 1 {
+            *tokens += ((*whitespace - 1) as f64 / profile.word_chars).ceil();
+        }
+        *whitespace = 0;
+    };
+
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            flush_whitespace(&mut whitespace, &mut tokens);
+            word += 1;
+            continue;
+        }
+        flush_word(&mut word, &mut tokens);
+
+        if c.is_whitespace() {
+            whitespace += 1;
+            continue;
+        }
+        flush_whitespace(&mut whitespace, &mut tokens);
+
+        tokens += if c.is_ascii() {
+            profile.punctuation
+        } else if is_wide(c) {
+            profile.wide_char
+        } else {
+            profile.other_char
+        };
+    }
+    flush_word(&mut word, &mut tokens);
+    flush_whitespace(&mut whitespace, &mut tokens);
+
+    if tokens > 0.0 {
+        (tokens.round() as u32).max(1)
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_tokens_prose_and_code() {
+        assert_eq!(estimate_tokens("", &Provider::ClaudeCode), 0);
+        assert_eq!(estimate_tokens("   \n", &Provider::ClaudeCode), 1);
+        assert_eq!(estimate_tokens("ok", &Provider::Codex), 1);
+
+        let prose = "Please fix the failing login test in the auth module.";
+        let estimate = estimate_tokens(prose, &Provider::ClaudeCode);
+        assert!((10..=16).contains(&estimate), "got {estimate}");
+
+        let code = "fn main() {\n    println!(\"{}\", add(1, 2));\n}";
+        let estimate = estimate_tokens(code, &Provider::Codex);
+        assert!((15..=25).contains(&estimate), "got {estimate}");
+    }
+
+    #[test]
+    fn test_estimate_tokens_per_provider() {
+        // CJK text costs about a token per character, far more than chars / 4
+        let korean = "로그인 테스트가 실패합니다";
+        assert!(estimate_tokens(korean, &Provider::ClaudeCode) >= 12);
+        assert!(
+            estimate_tokens(korean, &Provider::GeminiCLI)
+                < estimate_tokens(korean, &Provider::ClaudeCode)
+        );
+        assert_eq!(
+            estimate_tokens("hello", &Provider::Other("aider".to_string())),
+            estimate_tokens("hello", &Provider::Codex)
+        );
+    }
+}
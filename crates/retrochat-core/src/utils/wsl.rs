@@ -0,0 +1,251 @@
+//! Path bridging between Windows and WSL
+//!
+//! Agents run inside WSL keep their history in the Linux home directory,
+//! which Windows sees as `\\wsl.localhost\<distro>\home\<user>`; agents run
+//! on Windows keep it in the user profile, which WSL sees under
+//! `/mnt/c/Users/<user>`. These helpers translate between the two and list
+//! the home directories on the other side, so detection finds both.
+
+use std::path::{Path, PathBuf};
+
+/// Roots Windows exposes WSL distributions under
+const WSL_SHARE_ROOTS: &[&str] = &[r"\\wsl.localhost", r"\\wsl$"];
+
+/// Where WSL mounts the Windows system drive
+const WINDOWS_USERS_DIR: &str = "/mnt/c/Users";
+
+/// Profile folders that are not a person's home
+const NON_USER_PROFILES: &[&str] = &["All Users", "Default", "Default User", "Public"];
+
+/// Whether this process runs inside WSL
+pub fn is_wsl() -> bool {
+    if !cfg!(target_os = "linux") {
+        return false;
+    }
+    if std::env::var_os("WSL_DISTRO_NAME").is_some() {
+        return true;
+    }
+    std::fs::read_to_string("/proc/sys/kernel/osrelease")
+        .map(|release| release.to_lowercase().contains("microsoft"))
+        .unwrap_or(false)
+}
+
+/// Home directories on the other side of the Windows/WSL boundary: Windows
+/// profiles when running in WSL, WSL homes when running on Windows
+pub fn bridged_home_dirs() -> Vec<PathBuf> {
+    if is_wsl() {
+        windows_user_homes(Path::new(WINDOWS_USERS_DIR))
+    } else if cfg!(windows) {
+        WSL_SHARE_ROOTS
+            .iter()
+            .map(|root| wsl_user_homes(Path::new(root)))
+            .find(|homes| !homes.is_empty())
+            .unwrap_or_default()
+    } else {
+        Vec::new()
+    }
+}
+
+/// User profiles under a Windows `Users` directory
+pub fn windows_user_homes(users_dir: &Path) -> Vec<PathBuf> {
+    let mut homes: Vec<PathBuf> = subdirectories(users_dir)
+        .into_iter()
+        .filter(|dir| {
+            dir.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|name| !NON_USER_PROFILES.contains(&name))
+        })
+        .collect();
+    homes.sort();
+    homes
+}
+
+/// Home directories (`home/*` and `root`) of every distribution under a WSL
+/// share root
+pub fn wsl_user_homes(share_root: &Path) -> Vec<PathBuf> {
+    let mut homes = Vec::new();
+    for distro in subdirectories(share_root) {
+        homes.extend(subdirectories(&distro.join("home")));
+        let root_home = distro.join("root");
+        if root_home.is_dir() {
+            homes.push(root_home);
+        }
+    }
+    homes.sort();
+    homes
+}
+
+/// Translate a Windows path (`C:\Users\me`, `\\wsl$\Ubuntu\home\me`) to the
+/// path WSL sees; `None` if it is not a Windows path
+pub fn windows_to_wsl_path(path: &str) -> Option<PathBuf> {
+    let normalized = path.replace('\\', "/");
+
+    for root in WSL_SHARE_ROOTS {
+        let root = root.replace('\\', "/");
+        let prefix = format!("{root}/");
+        let matches = normalized
+            .get(..prefix.len())
+            .is_some_and(|start| start.eq_ignore_ascii_case(&prefix));
+        if matches && normalized.len() > prefix.len() {
+            // Drop the distribution name
+            let rest = &normalized[prefix.len()..];
+            let inside = rest.find('/').map(|i| &rest[i..]).unwrap_or("/");
+            return Some(PathBuf::from(inside));
+        }
+    }
+
+    let mut chars = normalized.chars();
+    let drive = chars.next()?;
+    if !drive.is_ascii_alphabetic() || chars.next() != Some(':') {
+        return None;
+    }
+    let rest = chars.as_str().trim_start_matches('/');
+    let mut wsl = PathBuf::from(format!("/mnt/{}", drive.to_ascii_lowercase()));
+    if !rest.is_empty() {
+        wsl.push(rest);
+    }
+    Some(wsl)
+}
+
+/// Translate a WSL path to the path Windows sees: drive mounts become drive
+/// paths, everything else goes through the `\\wsl.localhost` share
+pub fn wsl_to_windows_path(path: &Path, distro: &str) -> String {
+    let path = path.to_string_lossy();
+    let mut parts = path.trim_start_matches('/').split('/');
+
+    if parts.next() == Some("mnt") {
+        if let Some(drive) = parts
+            .next()
+            .filter(|d| d.len() == 1 && d.chars().all(|c| c.is_ascii_alphabetic()))
+        {
+            let rest: Vec<&str> = parts.filter(|p| !p.is_empty()).collect();
+            return format!("{}:\\{}", drive.to_ascii_uppercase(), rest.join("\\"));
+        }
+    }
+
+    let rest: Vec<&str> = path.split('/').filter(|p| !p.is_empty()).collect();
+    format!(r"\\wsl.localhost\{distro}\{}", rest.join("\\"))
+}
+
+/// Path of a VS Code-style folder URI (`file:///c%3A/src/app`,
+/// `vscode-remote://wsl%2Bubuntu/home/me/app`) as seen from this machine
+pub fn folder_uri_to_path(uri: &str) -> Option<PathBuf> {
+    let decoded = percent_decode(uri);
+
+    if let Some(rest) = decoded.strip_prefix("file://") {
+        // `file:///c:/src` carries a Windows drive path after the slash
+        let path = rest.strip_prefix('/').filter(|r| r.get(1..2) == Some(":"));
+        return match path {
+            Some(windows) if is_wsl() => windows_to_wsl_path(windows),
+            Some(windows) => Some(PathBuf::from(windows)),
+            None => Some(PathBuf::from(rest)),
+        };
+    }
+
+    let rest = decoded.strip_prefix("vscode-remote://")?;
+    let (authority, path) = rest.split_at(rest.find('/')?);
+    let distro = authority.strip_prefix("wsl+")?;
+    if cfg!(windows) {
+        Some(PathBuf::from(wsl_to_windows_path(Path::new(path), distro)))
+    } else {
+        Some(PathBuf::from(path))
+    }
+}
+
+fn subdirectories(dir: &Path) -> Vec<PathBuf> {
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.is_dir())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+            if let Some(byte) = hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_translation() {
+        assert_eq!(
+            windows_to_wsl_path(r"C:\Users\me\.claude"),
+            Some(PathBuf::from("/mnt/c/Users/me/.claude"))
+        );
+        assert_eq!(
+            windows_to_wsl_path(r"\\wsl$\Ubuntu\home\me\.claude"),
+            Some(PathBuf::from("/home/me/.claude"))
+        );
+        assert_eq!(
+            windows_to_wsl_path(r"\\WSL.localhost\Ubuntu-22.04\home\me"),
+            Some(PathBuf::from("/home/me"))
+        );
+        assert_eq!(windows_to_wsl_path("/home/me"), None);
+
+        assert_eq!(
+            wsl_to_windows_path(Path::new("/mnt/d/work/app"), "Ubuntu"),
+            r"D:\work\app"
+        );
+        assert_eq!(
+            wsl_to_windows_path(Path::new("/home/me/.claude"), "Ubuntu"),
+            r"\\wsl.localhost\Ubuntu\home\me\.claude"
+        );
+
+        #[cfg(not(windows))]
+        assert_eq!(
+            folder_uri_to_path("vscode-remote://wsl%2Bubuntu/home/me/app"),
+            Some(PathBuf::from("/home/me/app"))
+        );
+        assert_eq!(
+            folder_uri_to_path("file:///home/me/app"),
+            Some(PathBuf::from("/home/me/app"))
+        );
+        assert_eq!(
+            folder_uri_to_path("vscode-remote://ssh-remote%2Bbox/srv"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_home_discovery() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let users = temp_dir.path().join("Users");
+        for name in ["me", "Public", "Default"] {
+            std::fs::create_dir_all(users.join(name)).unwrap();
+        }
+        assert_eq!(windows_user_homes(&users), vec![users.join("me")]);
+
+        let share = temp_dir.path().join("wsl");
+        std::fs::create_dir_all(share.join("Ubuntu").join("home").join("me")).unwrap();
+        std::fs::create_dir_all(share.join("Ubuntu").join("root")).unwrap();
+        std::fs::create_dir_all(share.join("Debian")).unwrap();
+        assert_eq!(
+            wsl_user_homes(&share),
+            vec![
+                share.join("Ubuntu").join("home").join("me"),
+                share.join("Ubuntu").join("root"),
+            ]
+        );
+    }
+}
@@ -33,6 +33,8 @@ async fn test_google_ai_api_request_response_structure() {
             top_k: None,
             candidate_count: None,
             stop_sequences: None,
+            response_mime_type: None,
+            response_schema: None,
         }),
         safety_settings: None,
     };
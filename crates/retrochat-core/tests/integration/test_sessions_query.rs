@@ -13,6 +13,7 @@ async fn test_list_sessions_default() {
         sort_by: None,
         sort_order: None,
         filters: None,
+        after: None,
     };
 
     let result = service.query_sessions(request).await;
@@ -37,7 +38,15 @@ async fn test_list_sessions_filter_by_provider() {
             date_range: None,
             min_messages: None,
             max_messages: None,
+            include_archived: false,
+            expression: None,
+            topic: None,
+            owner: None,
+            providers: None,
+            projects: None,
+            topics: None,
         }),
+        after: None,
     };
 
     let result = service.query_sessions(request).await;
@@ -61,7 +70,15 @@ async fn test_list_sessions_filter_by_project() {
             date_range: None,
             min_messages: None,
             max_messages: None,
+            include_archived: false,
+            expression: None,
+            topic: None,
+            owner: None,
+            providers: None,
+            projects: None,
+            topics: None,
         }),
+        after: None,
     };
 
     let result = service.query_sessions(request).await;
@@ -94,7 +111,15 @@ async fn test_list_sessions_date_range() {
             }),
             min_messages: None,
             max_messages: None,
+            include_archived: false,
+            expression: None,
+            topic: None,
+            owner: None,
+            providers: None,
+            projects: None,
+            topics: None,
         }),
+        after: None,
     };
 
     let result = service.query_sessions(request).await;
@@ -116,6 +141,7 @@ async fn test_list_sessions_pagination() {
         sort_by: None,
         sort_order: None,
         filters: None,
+        after: None,
     };
 
     let second_page = SessionsQueryRequest {
@@ -124,6 +150,7 @@ async fn test_list_sessions_pagination() {
         sort_by: None,
         sort_order: None,
         filters: None,
+        after: None,
     };
 
     let first_result = service.query_sessions(first_page).await;
@@ -150,6 +177,7 @@ async fn test_list_sessions_sorting() {
         sort_by: Some("start_time".to_string()),
         sort_order: Some("asc".to_string()),
         filters: None,
+        after: None,
     };
 
     let request_desc = SessionsQueryRequest {
@@ -158,6 +186,7 @@ async fn test_list_sessions_sorting() {
         sort_by: Some("start_time".to_string()),
         sort_order: Some("desc".to_string()),
         filters: None,
+        after: None,
     };
 
     let asc_result = service.query_sessions(request_asc).await;
@@ -179,6 +208,7 @@ async fn test_sessions_response_schema() {
         sort_by: None,
         sort_order: None,
         filters: None,
+        after: None,
     };
 
     let result = service.query_sessions(request).await;
@@ -41,18 +41,27 @@ fn test_total_sessions_calculation() {
             paths: vec![PathBuf::from("/test")],
             estimated_sessions: 10,
             is_valid: true,
+            files: vec![],
+            newest_modified: None,
+            unimported_sessions: None,
         },
         DetectedProvider {
             provider: retrochat::models::Provider::GeminiCLI,
             paths: vec![PathBuf::from("/test2")],
             estimated_sessions: 20,
             is_valid: true,
+            files: vec![],
+            newest_modified: None,
+            unimported_sessions: None,
         },
         DetectedProvider {
             provider: retrochat::models::Provider::Codex,
             paths: vec![],
             estimated_sessions: 0,
             is_valid: false,
+            files: vec![],
+            newest_modified: None,
+            unimported_sessions: None,
         },
     ];
 
@@ -102,6 +111,9 @@ fn test_detected_provider_structure() {
         paths: vec![PathBuf::from("/test/path")],
         estimated_sessions: 42,
         is_valid: true,
+        files: vec![],
+        newest_modified: None,
+        unimported_sessions: None,
     };
 
     assert_eq!(provider.provider.to_string(), "Claude Code");
@@ -118,12 +130,18 @@ fn test_empty_valid_providers() {
             paths: vec![],
             estimated_sessions: 0,
             is_valid: false,
+            files: vec![],
+            newest_modified: None,
+            unimported_sessions: None,
         },
         DetectedProvider {
             provider: retrochat::models::Provider::GeminiCLI,
             paths: vec![],
             estimated_sessions: 0,
             is_valid: false,
+            files: vec![],
+            newest_modified: None,
+            unimported_sessions: None,
         },
     ];
 
@@ -146,6 +164,9 @@ fn test_multiple_paths_per_provider() {
         ],
         estimated_sessions: 100,
         is_valid: true,
+        files: vec![],
+        newest_modified: None,
+        unimported_sessions: None,
     };
 
     assert_eq!(provider.paths.len(), 3);
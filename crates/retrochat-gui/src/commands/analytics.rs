@@ -19,13 +19,18 @@ pub async fn analyze_session(
             .map(|p| format!("{}...", &p.chars().take(50).collect::<String>()))
     );
 
-    let state_guard = state.lock().await;
-
-    let analytics_service = state_guard.analytics_service.as_ref().ok_or_else(|| {
-        log::error!("Analytics service not available");
-        "Analytics service not available. Please set GOOGLE_AI_API_KEY environment variable."
-            .to_string()
-    })?;
+    // Release the state lock before running the analysis so that
+    // cancel_analysis can reach the service while it is in flight
+    let analytics_service = state
+        .lock()
+        .await
+        .analytics_service
+        .clone()
+        .ok_or_else(|| {
+            log::error!("Analytics service not available");
+            "Analytics service not available. Please set GOOGLE_AI_API_KEY environment variable."
+                .to_string()
+        })?;
 
     // Create the request
     log::debug!("Creating analysis request");
@@ -128,12 +133,16 @@ pub async fn run_analysis(
 ) -> Result<String, String> {
     log::info!("run_analysis called - request_id: {}", request_id);
 
-    let state_guard = state.lock().await;
-
-    let analytics_service = state_guard.analytics_service.as_ref().ok_or_else(|| {
-        log::error!("Analytics service not available");
-        "Analytics service not available".to_string()
-    })?;
+    // Release the state lock so the run can be cancelled while in flight
+    let analytics_service = state
+        .lock()
+        .await
+        .analytics_service
+        .clone()
+        .ok_or_else(|| {
+            log::error!("Analytics service not available");
+            "Analytics service not available".to_string()
+        })?;
 
     log::debug!("Executing analysis");
     analytics_service
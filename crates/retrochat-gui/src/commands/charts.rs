@@ -0,0 +1,101 @@
+use crate::dto::{ChartRequest, DailyUsageItem, RubricTrendItem, TokenTrendItem, ToolUsageItem};
+use crate::AppState;
+use chrono::{DateTime, Utc};
+use retrochat_core::database::AnalyticsRepository;
+use retrochat_core::utils::time_parser;
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+type ChartWindow = (Option<DateTime<Utc>>, Option<DateTime<Utc>>);
+
+fn parse_window(request: &ChartRequest) -> Result<ChartWindow, String> {
+    time_parser::parse_time_range(request.since.as_deref(), request.until.as_deref()).map_err(|e| {
+        log::error!("Invalid chart window: {}", e);
+        e.to_string()
+    })
+}
+
+/// Sessions, messages and tokens per day
+#[tauri::command]
+pub async fn get_daily_usage(
+    state: State<'_, Arc<Mutex<AppState>>>,
+    request: ChartRequest,
+) -> Result<Vec<DailyUsageItem>, String> {
+    log::info!("get_daily_usage called - {:?}", request);
+    let (since, until) = parse_window(&request)?;
+
+    let state = state.lock().await;
+    let points = AnalyticsRepository::new(&state.db_manager)
+        .daily_usage(since, until)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to fetch daily usage: {}", e);
+            e.to_string()
+        })?;
+
+    Ok(points.into_iter().map(DailyUsageItem::from).collect())
+}
+
+/// Calls and failures per tool, most used first
+#[tauri::command]
+pub async fn get_tool_distribution(
+    state: State<'_, Arc<Mutex<AppState>>>,
+    request: ChartRequest,
+) -> Result<Vec<ToolUsageItem>, String> {
+    log::info!("get_tool_distribution called - {:?}", request);
+    let (since, until) = parse_window(&request)?;
+
+    let state = state.lock().await;
+    let points = AnalyticsRepository::new(&state.db_manager)
+        .tool_distribution(since, until)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to fetch tool distribution: {}", e);
+            e.to_string()
+        })?;
+
+    Ok(points.into_iter().map(ToolUsageItem::from).collect())
+}
+
+/// Tokens per day, one series per provider
+#[tauri::command]
+pub async fn get_token_trend(
+    state: State<'_, Arc<Mutex<AppState>>>,
+    request: ChartRequest,
+) -> Result<Vec<TokenTrendItem>, String> {
+    log::info!("get_token_trend called - {:?}", request);
+    let (since, until) = parse_window(&request)?;
+
+    let state = state.lock().await;
+    let points = AnalyticsRepository::new(&state.db_manager)
+        .token_trend(since, until)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to fetch token trend: {}", e);
+            e.to_string()
+        })?;
+
+    Ok(points.into_iter().map(TokenTrendItem::from).collect())
+}
+
+/// Average rubric scores per day, one series per rubric
+#[tauri::command]
+pub async fn get_rubric_trend(
+    state: State<'_, Arc<Mutex<AppState>>>,
+    request: ChartRequest,
+) -> Result<Vec<RubricTrendItem>, String> {
+    log::info!("get_rubric_trend called - {:?}", request);
+    let (since, until) = parse_window(&request)?;
+
+    let state = state.lock().await;
+    let points = AnalyticsRepository::new(&state.db_manager)
+        .rubric_trend(since, until)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to fetch rubric trend: {}", e);
+            e.to_string()
+        })?;
+
+    Ok(points.into_iter().map(RubricTrendItem::from).collect())
+}
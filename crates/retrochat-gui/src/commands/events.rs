@@ -0,0 +1,34 @@
+use crate::dto::EventItem;
+use crate::AppState;
+use retrochat_core::services::EventFeed;
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// Events returned when no limit is given
+const DEFAULT_LIMIT: i64 = 100;
+
+/// Events after `cursor` (oldest first), or the most recent ones without a
+/// cursor; new events arrive as `retrochat-event` emits
+#[tauri::command]
+pub async fn get_events(
+    state: State<'_, Arc<Mutex<AppState>>>,
+    cursor: Option<i64>,
+    limit: Option<i64>,
+) -> Result<Vec<EventItem>, String> {
+    log::debug!("get_events called - cursor: {:?}", cursor);
+
+    let state = state.lock().await;
+    let feed = EventFeed::new(&state.db_manager);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let events = match cursor {
+        Some(cursor) => feed.since(cursor, limit).await,
+        None => feed.recent(limit).await,
+    }
+    .map_err(|e| {
+        log::error!("Failed to read events: {}", e);
+        e.to_string()
+    })?;
+
+    Ok(events.into_iter().map(Into::into).collect())
+}
@@ -1,4 +1,4 @@
-use crate::dto::{ImportFileResult, ImportSessionsResponse};
+use crate::dto::{ImportErrorItem, ImportFileResult, ImportSessionsResponse};
 use crate::{AppState, OpenedFiles};
 use retrochat_core::models::provider::config::{ClaudeCodeConfig, CodexConfig, GeminiCliConfig};
 use retrochat_core::models::Provider;
@@ -375,3 +375,35 @@ async fn import_provider_directories(
 
     Ok(())
 }
+
+// Command to list files that failed to import
+#[tauri::command]
+pub async fn get_import_errors(
+    state: State<'_, Arc<Mutex<AppState>>>,
+    limit: Option<i64>,
+) -> Result<Vec<ImportErrorItem>, String> {
+    log::info!("get_import_errors called - limit: {:?}", limit);
+
+    let state_guard = state.lock().await;
+    let errors = state_guard
+        .import_service
+        .get_import_errors(limit)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to load import errors: {}", e);
+            e.to_string()
+        })?;
+
+    log::info!("Returning {} import errors", errors.len());
+    Ok(errors
+        .into_iter()
+        .map(|e| ImportErrorItem {
+            file_path: e.file_path,
+            provider: e.provider,
+            error_message: e.error_message,
+            attempt_count: e.attempt_count,
+            first_attempt_at: e.first_attempt_at.to_rfc3339(),
+            last_attempt_at: e.last_attempt_at.to_rfc3339(),
+        })
+        .collect())
+}
@@ -0,0 +1,43 @@
+use crate::dto::DeepLinkTarget;
+use retrochat_core::utils::deep_link::DeepLink;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+// Link the app was launched with, kept until the frontend asks for it
+#[derive(Default)]
+pub struct PendingDeepLink(pub Mutex<Option<DeepLinkTarget>>);
+
+// Handler for retrochat:// links from the command line or the OS
+pub fn handle_deep_link(app: AppHandle, link: &str) {
+    log::info!("handle_deep_link called with {}", link);
+
+    let link: DeepLink = match link.parse() {
+        Ok(link) => link,
+        Err(e) => {
+            log::warn!("Ignoring deep link: {}", e);
+            return;
+        }
+    };
+    let target = DeepLinkTarget {
+        session_id: link.session_id.to_string(),
+        sequence_number: link.sequence,
+    };
+
+    if let Some(pending) = app.try_state::<PendingDeepLink>() {
+        if let Ok(mut pending) = pending.0.lock() {
+            *pending = Some(target.clone());
+        }
+    }
+
+    match app.emit("open-deep-link", target) {
+        Ok(_) => log::info!("Successfully emitted open-deep-link event"),
+        Err(e) => log::error!("Failed to emit open-deep-link event: {}", e),
+    }
+}
+
+// Command to get, and forget, the link the app was launched with
+#[tauri::command]
+pub fn take_deep_link(state: State<PendingDeepLink>) -> Option<DeepLinkTarget> {
+    log::debug!("take_deep_link called");
+    state.0.lock().unwrap().take()
+}
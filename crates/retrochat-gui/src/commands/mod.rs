@@ -1,4 +1,9 @@
 pub mod analytics;
+pub mod charts;
+pub mod events;
 pub mod file;
 pub mod histogram;
+pub mod link;
+pub mod onboarding;
 pub mod session;
+pub mod views;
@@ -0,0 +1,154 @@
+use crate::dto::{DetectedProviderItem, InitialImportProgress, InitialImportResponse};
+use crate::AppState;
+use retrochat_core::config::{self, Config};
+use retrochat_core::services::{
+    google_ai::{GoogleAiClient, GoogleAiConfig},
+    AnalyticsRequestService, AutoDetectService, BatchImportRequest,
+};
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, State};
+use tokio::sync::Mutex;
+
+// Command to find chat histories in the default provider locations, with how
+// many of their files have not been imported yet
+#[tauri::command]
+pub async fn detect_providers(
+    state: State<'_, Arc<Mutex<AppState>>>,
+) -> Result<Vec<DetectedProviderItem>, String> {
+    log::info!("detect_providers called");
+    let db_manager = state.lock().await.db_manager.clone();
+    let detected = AutoDetectService::scan_with_import_status(&db_manager)
+        .await
+        .map_err(|e| format!("Failed to check for unimported sessions: {e}"))?;
+    log::info!(
+        "Detected {} sessions ({} new) across {} providers",
+        AutoDetectService::total_sessions(&detected),
+        AutoDetectService::total_unimported(&detected),
+        AutoDetectService::valid_providers(&detected).len()
+    );
+    Ok(detected
+        .into_iter()
+        .map(DetectedProviderItem::from)
+        .collect())
+}
+
+// Command to import the providers picked in the onboarding wizard, reporting
+// progress through `initial-import-progress` events
+#[tauri::command]
+pub async fn run_initial_import(
+    app: AppHandle,
+    state: State<'_, Arc<Mutex<AppState>>>,
+    providers: Vec<String>,
+) -> Result<InitialImportResponse, String> {
+    log::info!("run_initial_import called with providers: {:?}", providers);
+
+    let selected: Vec<_> = AutoDetectService::valid_providers(&AutoDetectService::scan_all())
+        .into_iter()
+        .filter(|detected| providers.contains(&detected.provider.to_string()))
+        .collect();
+    if selected.is_empty() {
+        return Err("None of the selected providers has chat history to import".to_string());
+    }
+
+    let import_service = state.lock().await.import_service.clone();
+    let mut response = InitialImportResponse::default();
+
+    for detected in selected {
+        let provider = detected.provider.to_string();
+        for path in detected.paths {
+            let path = path.to_string_lossy().to_string();
+            let request = BatchImportRequest {
+                directory_path: path.clone(),
+                providers: None,
+                project_name: None,
+                overwrite_existing: Some(false),
+                recursive: Some(true),
+            };
+
+            let progress_app = app.clone();
+            let progress = InitialImportProgress {
+                provider: provider.clone(),
+                path: path.clone(),
+                completed_files: 0,
+                total_files: 0,
+            };
+            let result = import_service
+                .import_batch_with_progress(request, move |completed_files, total_files| {
+                    let progress = InitialImportProgress {
+                        completed_files,
+                        total_files,
+                        ..progress.clone()
+                    };
+                    if let Err(e) = progress_app.emit("initial-import-progress", progress) {
+                        log::warn!("Failed to emit initial-import-progress event: {}", e);
+                    }
+                })
+                .await;
+
+            match result {
+                Ok(batch) => {
+                    response.total_sessions_imported += batch.total_sessions_imported;
+                    response.total_messages_imported += batch.total_messages_imported;
+                }
+                Err(e) => {
+                    log::error!("Failed to import {}: {}", path, e);
+                    response.errors.push(format!("{provider} ({path}): {e}"));
+                }
+            }
+        }
+        response.providers_imported.push(provider);
+    }
+
+    log::info!(
+        "Initial import finished - {} sessions, {} messages",
+        response.total_sessions_imported,
+        response.total_messages_imported
+    );
+    Ok(response)
+}
+
+// Command to choose the LLM used for analyses, saved to the config file
+#[tauri::command]
+pub async fn set_llm_provider(
+    state: State<'_, Arc<Mutex<AppState>>>,
+    provider: String,
+    model: Option<String>,
+    api_key: Option<String>,
+) -> Result<(), String> {
+    log::info!("set_llm_provider called - provider: {}", provider);
+
+    let mut config = Config::load().map_err(|e| e.to_string())?;
+    config
+        .set("llm.provider", provider)
+        .map_err(|e| e.to_string())?;
+    match model.filter(|model| !model.trim().is_empty()) {
+        Some(model) => config.set("llm.model", model),
+        None => config.unset("llm.model"),
+    }
+    .map_err(|e| e.to_string())?;
+    if let Some(api_key) = api_key.filter(|key| !key.trim().is_empty()) {
+        config
+            .set("google-ai-api-key", api_key.trim().to_string())
+            .map_err(|e| e.to_string())?;
+    }
+    config.save().map_err(|e| {
+        log::error!("Failed to save config: {}", e);
+        e.to_string()
+    })?;
+
+    // Analyses in the desktop app run on Google AI; start them once a key exists
+    let mut state = state.lock().await;
+    if state.analytics_service.is_none() {
+        if let Some(api_key) = config::get_google_ai_api_key().map_err(|e| e.to_string())? {
+            let client =
+                GoogleAiClient::new(GoogleAiConfig::new(api_key)).map_err(|e| e.to_string())?;
+            state.analytics_service = Some(Arc::new(AnalyticsRequestService::new(
+                state.db_manager.clone(),
+                client,
+            )));
+            log::info!("Analytics service initialized");
+        }
+    }
+
+    Ok(())
+}
@@ -1,40 +1,66 @@
 use crate::dto::{
-    FileMetadataItem, MessageItem, SearchResultItem, SessionDetail, SessionListItem,
-    ToolOperationItem,
+    AdvancedSearchResponse, FileMetadataItem, MessageItem, RelatedSessionItem, SearchResultItem,
+    SemanticSearchHitItem, SessionDetail, SessionListItem, ToolOperationItem,
 };
 use crate::AppState;
-use retrochat_core::database::ToolOperationRepository;
+use retrochat_core::database::{ChatSessionRepository, ToolOperationRepository};
 use retrochat_core::services::{
-    SearchRequest, SessionDetailRequest, SessionFilters, SessionsQueryRequest,
+    DateRange, SearchFacets, SearchRequest, SessionDetailRequest, SessionFilters,
+    SessionSimilarityService, SessionsQueryRequest,
 };
+use retrochat_core::utils::time_parser;
+use std::path::{Path, PathBuf};
+use std::process::Command;
 use std::sync::Arc;
+use std::time::Instant;
 use tauri::State;
 use tokio::sync::Mutex;
 
+/// Related sessions included in a session detail
+const RELATED_SESSION_LIMIT: usize = 5;
+
+/// Sessions ranked by a semantic search before facets and paging
+const SEMANTIC_CANDIDATE_LIMIT: usize = 200;
+
 #[tauri::command]
 pub async fn get_sessions(
     state: State<'_, Arc<Mutex<AppState>>>,
     page: Option<i32>,
     page_size: Option<i32>,
     provider: Option<String>,
+    filter: Option<String>,
+    owner: Option<String>,
 ) -> Result<Vec<SessionListItem>, String> {
     log::info!(
-        "get_sessions called - page: {:?}, page_size: {:?}, provider: {:?}",
+        "get_sessions called - page: {:?}, page_size: {:?}, provider: {:?}, filter: {:?}, owner: {:?}",
         page,
         page_size,
-        provider
+        provider,
+        filter,
+        owner
     );
 
     let state = state.lock().await;
 
-    let filters = provider.as_ref().map(|p| {
-        log::debug!("Applying provider filter: {}", p);
+    let filters = (provider.is_some() || filter.is_some() || owner.is_some()).then(|| {
+        log::debug!(
+            "Applying filters - provider: {:?}, filter: {:?}",
+            provider,
+            filter
+        );
         SessionFilters {
-            provider: Some(p.clone()),
+            provider,
             project: None,
             date_range: None,
             min_messages: None,
             max_messages: None,
+            include_archived: false,
+            expression: filter,
+            topic: None,
+            owner,
+            providers: None,
+            projects: None,
+            topics: None,
         }
     });
 
@@ -44,6 +70,7 @@ pub async fn get_sessions(
         sort_by: Some("start_time".to_string()),
         sort_order: Some("desc".to_string()),
         filters,
+        after: None,
     };
 
     let response = state
@@ -65,9 +92,11 @@ pub async fn get_sessions(
             id: s.session_id,
             provider: s.provider,
             project_name: s.project,
+            title: s.title,
             created_at: s.start_time,
             updated_at: s.end_time,
             message_count: s.message_count,
+            owner: s.owner,
         })
         .collect())
 }
@@ -136,6 +165,16 @@ pub async fn get_session_detail(
         }
     }
 
+    log::debug!("Fetching related sessions");
+    let related_sessions = state_guard
+        .query_service
+        .get_related_sessions(&session_id, RELATED_SESSION_LIMIT)
+        .await
+        .unwrap_or_else(|e| {
+            log::warn!("Failed to fetch related sessions: {}", e);
+            Vec::new()
+        });
+
     log::info!(
         "Successfully retrieved session detail with {} messages",
         response.messages.len()
@@ -145,6 +184,7 @@ pub async fn get_session_detail(
         id: response.session.id.to_string(),
         provider: response.session.provider.to_string(),
         project_name: response.session.project_name,
+        title: response.session.title,
         created_at: response.session.start_time.to_rfc3339(),
         updated_at: response
             .session
@@ -187,6 +227,17 @@ pub async fn get_session_detail(
                 }
             })
             .collect(),
+        related_sessions: related_sessions
+            .into_iter()
+            .map(|r| RelatedSessionItem {
+                id: r.session_id,
+                provider: r.provider,
+                project_name: r.project,
+                title: r.title,
+                created_at: r.start_time,
+                score: r.score,
+            })
+            .collect(),
     })
 }
 
@@ -230,17 +281,144 @@ pub async fn search_messages(
     Ok(response
         .results
         .into_iter()
-        .map(|r| SearchResultItem {
-            session_id: r.session_id,
-            message_id: r.message_id,
-            content: r.content_snippet,
-            role: "User".to_string(), // Default role since SearchResult doesn't include it
-            timestamp: r.timestamp,
-            provider: r.provider,
-        })
+        .map(SearchResultItem::from)
         .collect())
 }
 
+/// Search messages by their words (`full_text`, the default) or sessions by
+/// meaning (`semantic`), with hit counts per provider, project and month
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn search_sessions_advanced(
+    state: State<'_, Arc<Mutex<AppState>>>,
+    query: String,
+    search_type: Option<String>,
+    providers: Option<Vec<String>>,
+    projects: Option<Vec<String>>,
+    since: Option<String>,
+    until: Option<String>,
+    page: Option<i32>,
+    page_size: Option<i32>,
+) -> Result<AdvancedSearchResponse, String> {
+    log::info!(
+        "search_sessions_advanced called - query: '{}', search_type: {:?}",
+        query,
+        search_type
+    );
+
+    let search_type = search_type.unwrap_or_else(|| "full_text".to_string());
+    let (since, until) = time_parser::parse_time_range(since.as_deref(), until.as_deref())
+        .map_err(|e| e.to_string())?;
+    let page = page.unwrap_or(1).max(1);
+    let page_size = page_size.unwrap_or(20).max(1);
+
+    let state = state.lock().await;
+
+    match search_type.as_str() {
+        "full_text" => {
+            let date_range = (since.is_some() || until.is_some()).then(|| DateRange {
+                start_date: since.unwrap_or_default().to_rfc3339(),
+                end_date: until.unwrap_or_else(chrono::Utc::now).to_rfc3339(),
+            });
+            let request = SearchRequest {
+                query,
+                providers,
+                projects,
+                date_range,
+                search_type: Some(search_type.clone()),
+                page: Some(page),
+                page_size: Some(page_size),
+            };
+            let response = state
+                .query_service
+                .search_messages(request)
+                .await
+                .map_err(|e| {
+                    log::error!("Failed to search messages: {}", e);
+                    e.to_string()
+                })?;
+
+            log::info!("Search completed - found {} results", response.total_count);
+            Ok(AdvancedSearchResponse {
+                search_type,
+                messages: response
+                    .results
+                    .into_iter()
+                    .map(SearchResultItem::from)
+                    .collect(),
+                sessions: Vec::new(),
+                facets: response.facets.into(),
+                total_count: response.total_count,
+                page: response.page,
+                page_size: response.page_size,
+                search_duration_ms: response.search_duration_ms,
+            })
+        }
+        "semantic" => {
+            let start_time = Instant::now();
+            let mut hits = SessionSimilarityService::new(state.db_manager.clone())
+                .find_similar_sessions(&query, SEMANTIC_CANDIDATE_LIMIT)
+                .await
+                .map_err(|e| {
+                    log::error!("Failed to run semantic search: {}", e);
+                    e.to_string()
+                })?;
+            hits.retain(|hit| {
+                since.is_none_or(|since| hit.start_time >= since)
+                    && until.is_none_or(|until| hit.start_time <= until)
+            });
+
+            let start_times: Vec<String> =
+                hits.iter().map(|hit| hit.start_time.to_rfc3339()).collect();
+            let facets = SearchFacets::count(hits.iter().zip(&start_times).map(|(hit, start)| {
+                (
+                    hit.provider.as_str(),
+                    hit.project.as_deref(),
+                    start.as_str(),
+                )
+            }));
+
+            if let Some(ref providers) = providers {
+                hits.retain(|hit| providers.contains(&hit.provider));
+            }
+            if let Some(ref projects) = projects {
+                hits.retain(|hit| hit.project.as_ref().is_some_and(|p| projects.contains(p)));
+            }
+            let total_count = hits.len() as i32;
+            let sessions = hits
+                .into_iter()
+                .skip(((page - 1) * page_size) as usize)
+                .take(page_size as usize)
+                .map(|hit| SemanticSearchHitItem {
+                    session_id: hit.session_id,
+                    provider: hit.provider,
+                    project_name: hit.project,
+                    title: hit.title,
+                    summary: hit.summary,
+                    first_prompt: hit.first_prompt,
+                    created_at: hit.start_time.to_rfc3339(),
+                    score: hit.score,
+                })
+                .collect();
+
+            log::info!("Semantic search completed - found {} sessions", total_count);
+            Ok(AdvancedSearchResponse {
+                search_type,
+                messages: Vec::new(),
+                sessions,
+                facets: facets.into(),
+                total_count,
+                page,
+                page_size,
+                search_duration_ms: start_time.elapsed().as_millis() as i32,
+            })
+        }
+        other => Err(format!(
+            "Unknown search type {other}; use full_text or semantic"
+        )),
+    }
+}
+
 #[tauri::command]
 pub async fn get_providers(_state: State<'_, Arc<Mutex<AppState>>>) -> Result<Vec<String>, String> {
     log::debug!("get_providers called");
@@ -252,3 +430,76 @@ pub async fn get_providers(_state: State<'_, Arc<Mutex<AppState>>>) -> Result<Ve
         "Cursor Client".to_string(),
     ])
 }
+
+/// Show the file a session was imported from in the system file manager, or
+/// open it with its default app when `open_file` is set; returns the path
+#[tauri::command]
+pub async fn reveal_session_source(
+    state: State<'_, Arc<Mutex<AppState>>>,
+    session_id: String,
+    open_file: Option<bool>,
+) -> Result<String, String> {
+    log::info!(
+        "reveal_session_source called - session_id: {}, open_file: {:?}",
+        session_id,
+        open_file
+    );
+
+    let session_uuid = uuid::Uuid::parse_str(&session_id).map_err(|e| {
+        log::error!("Failed to parse session UUID: {}", e);
+        e.to_string()
+    })?;
+
+    let state = state.lock().await;
+    let session = ChatSessionRepository::new(&state.db_manager)
+        .get_by_id(&session_uuid)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Session not found: {session_id}"))?;
+
+    let path = PathBuf::from(&session.file_path);
+    if !path.exists() {
+        return Err(format!(
+            "The source file no longer exists: {}",
+            path.display()
+        ));
+    }
+
+    reveal_path(&path, open_file.unwrap_or(false)).map_err(|e| {
+        log::error!("Failed to reveal {}: {}", path.display(), e);
+        format!("Failed to open {}: {e}", path.display())
+    })?;
+
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Select `path` in Finder or Explorer (on Linux, open its folder), or open
+/// the file itself
+fn reveal_path(path: &Path, open_file: bool) -> std::io::Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        let mut command = Command::new("open");
+        if !open_file {
+            command.arg("-R");
+        }
+        command.arg(path);
+        command
+    } else if cfg!(target_os = "windows") {
+        let mut command = Command::new("explorer");
+        if open_file {
+            command.arg(path);
+        } else {
+            command.arg(format!("/select,{}", path.display()));
+        }
+        command
+    } else {
+        let target = match path.parent() {
+            Some(folder) if !open_file && path.is_file() => folder,
+            _ => path,
+        };
+        let mut command = Command::new("xdg-open");
+        command.arg(target);
+        command
+    };
+
+    command.spawn().map(|_| ())
+}
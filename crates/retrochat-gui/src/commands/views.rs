@@ -0,0 +1,76 @@
+use crate::dto::SavedViewItem;
+use crate::AppState;
+use retrochat_core::database::SavedViewRepository;
+use retrochat_core::utils::filter_expr::FilterExpr;
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+#[tauri::command]
+pub async fn get_saved_views(
+    state: State<'_, Arc<Mutex<AppState>>>,
+) -> Result<Vec<SavedViewItem>, String> {
+    log::debug!("get_saved_views called");
+
+    let state = state.lock().await;
+    let views = SavedViewRepository::new(&state.db_manager)
+        .list()
+        .await
+        .map_err(|e| {
+            log::error!("Failed to list saved views: {}", e);
+            e.to_string()
+        })?;
+
+    Ok(views.into_iter().map(Into::into).collect())
+}
+
+#[tauri::command]
+pub async fn save_view(
+    state: State<'_, Arc<Mutex<AppState>>>,
+    name: String,
+    expression: String,
+) -> Result<SavedViewItem, String> {
+    log::info!(
+        "save_view called - name: {}, expression: {}",
+        name,
+        expression
+    );
+
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("View name cannot be empty".to_string());
+    }
+    match FilterExpr::parse(&expression) {
+        Ok(expr) if expr.is_empty() => return Err("Filter expression cannot be empty".to_string()),
+        Ok(_) => {}
+        Err(e) => return Err(e.to_string()),
+    }
+
+    let state = state.lock().await;
+    let view = SavedViewRepository::new(&state.db_manager)
+        .save(name, expression.trim())
+        .await
+        .map_err(|e| {
+            log::error!("Failed to save view: {}", e);
+            e.to_string()
+        })?;
+
+    Ok(view.into())
+}
+
+#[tauri::command]
+pub async fn delete_view(
+    state: State<'_, Arc<Mutex<AppState>>>,
+    name: String,
+) -> Result<bool, String> {
+    log::info!("delete_view called - name: {}", name);
+
+    let state = state.lock().await;
+    SavedViewRepository::new(&state.db_manager)
+        .delete(&name)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to delete view: {}", e);
+            e.to_string()
+        })
+}
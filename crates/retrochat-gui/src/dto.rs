@@ -16,9 +16,12 @@ pub struct SessionListItem {
     pub id: String,
     pub provider: String,
     pub project_name: Option<String>,
+    pub title: Option<String>,
     pub created_at: String,
     pub updated_at: String,
     pub message_count: i32,
+    /// Teammate the session was imported from; `None` for own sessions
+    pub owner: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -26,9 +29,22 @@ pub struct SessionDetail {
     pub id: String,
     pub provider: String,
     pub project_name: Option<String>,
+    pub title: Option<String>,
     pub created_at: String,
     pub updated_at: String,
     pub messages: Vec<MessageItem>,
+    pub related_sessions: Vec<RelatedSessionItem>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RelatedSessionItem {
+    pub id: String,
+    pub provider: String,
+    pub project_name: Option<String>,
+    pub title: Option<String>,
+    pub created_at: String,
+    /// Cosine similarity of the two session summaries (0.0 - 1.0)
+    pub score: f32,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -76,6 +92,139 @@ pub struct SearchResultItem {
     pub role: String,
     pub timestamp: String,
     pub provider: String,
+    pub sequence_number: u32,
+    /// `retrochat://` link that opens the session at this message
+    pub link: String,
+}
+
+impl From<retrochat_core::services::SearchResult> for SearchResultItem {
+    fn from(result: retrochat_core::services::SearchResult) -> Self {
+        Self {
+            session_id: result.session_id,
+            message_id: result.message_id,
+            content: result.content_snippet,
+            role: result.message_role,
+            timestamp: result.timestamp,
+            provider: result.provider,
+            sequence_number: result.sequence_number,
+            link: result.link,
+        }
+    }
+}
+
+/// Session matched by meaning rather than by its words
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SemanticSearchHitItem {
+    pub session_id: String,
+    pub provider: String,
+    pub project_name: Option<String>,
+    pub title: Option<String>,
+    pub summary: Option<String>,
+    /// First user prompt, for sessions without a summary
+    pub first_prompt: Option<String>,
+    pub created_at: String,
+    /// Cosine similarity to the query (0.0 - 1.0)
+    pub score: f32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FacetCountItem {
+    pub value: String,
+    pub count: usize,
+}
+
+/// Hit counts for the search filter sidebar
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SearchFacetsItem {
+    pub providers: Vec<FacetCountItem>,
+    pub projects: Vec<FacetCountItem>,
+    /// `YYYY-MM` buckets, newest first
+    pub months: Vec<FacetCountItem>,
+}
+
+impl From<retrochat_core::services::SearchFacets> for SearchFacetsItem {
+    fn from(facets: retrochat_core::services::SearchFacets) -> Self {
+        let items = |counts: Vec<retrochat_core::services::FacetCount>| {
+            counts
+                .into_iter()
+                .map(|facet| FacetCountItem {
+                    value: facet.value,
+                    count: facet.count,
+                })
+                .collect()
+        };
+        Self {
+            providers: items(facets.providers),
+            projects: items(facets.projects),
+            months: items(facets.months),
+        }
+    }
+}
+
+/// Full-text or semantic search results with facet counts
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AdvancedSearchResponse {
+    /// `full_text` or `semantic`
+    pub search_type: String,
+    /// Message hits of a full-text search
+    pub messages: Vec<SearchResultItem>,
+    /// Session hits of a semantic search
+    pub sessions: Vec<SemanticSearchHitItem>,
+    /// Counts before the provider and project selections are applied
+    pub facets: SearchFacetsItem,
+    pub total_count: i32,
+    pub page: i32,
+    pub page_size: i32,
+    pub search_duration_ms: i32,
+}
+
+/// Message to open, from a `retrochat://` link the app was launched with
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeepLinkTarget {
+    pub session_id: String,
+    pub sequence_number: Option<u32>,
+}
+
+// =============================================================================
+// Saved View DTOs
+// =============================================================================
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SavedViewItem {
+    pub name: String,
+    pub expression: String,
+    pub updated_at: String,
+}
+
+impl From<retrochat_core::models::SavedView> for SavedViewItem {
+    fn from(view: retrochat_core::models::SavedView) -> Self {
+        Self {
+            name: view.name,
+            expression: view.expression,
+            updated_at: view.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventItem {
+    pub id: i64,
+    pub kind: String,
+    pub session_id: Option<String>,
+    pub payload: serde_json::Value,
+    pub created_at: String,
+}
+
+impl From<retrochat_core::models::Event> for EventItem {
+    fn from(event: retrochat_core::models::Event) -> Self {
+        Self {
+            id: event.id,
+            kind: event.kind.to_string(),
+            session_id: event.session_id,
+            payload: event.payload,
+            created_at: event.created_at.to_rfc3339(),
+        }
+    }
 }
 
 // =============================================================================
@@ -101,6 +250,16 @@ pub struct ImportSessionsResponse {
     pub results: Vec<ImportFileResult>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportErrorItem {
+    pub file_path: String,
+    pub provider: Option<String>,
+    pub error_message: String,
+    pub attempt_count: u32,
+    pub first_attempt_at: String,
+    pub last_attempt_at: String,
+}
+
 // =============================================================================
 // Analytics Request DTOs
 // =============================================================================
@@ -186,6 +345,18 @@ pub struct MetricQuantitativeOutputItem {
     pub time_metrics: TimeConsumptionMetricsItem,
     pub token_metrics: TokenConsumptionMetricsItem,
     pub tool_usage: ToolUsageMetricsItem,
+    pub rework_rate: f64,
+    pub turns: Vec<TurnUsageMetricsItem>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TurnUsageMetricsItem {
+    pub turn_number: u32,
+    pub prompt_preview: String,
+    pub total_tokens: u64,
+    pub tool_calls: u64,
+    pub duration_minutes: f64,
+    pub files_touched: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -195,12 +366,25 @@ pub struct FileChangeMetricsItem {
     pub lines_added: u64,
     pub lines_removed: u64,
     pub net_code_growth: i64,
+    pub languages: Vec<LanguageChangeMetricsItem>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LanguageChangeMetricsItem {
+    pub language: String,
+    pub files_touched: u64,
+    pub lines_added: u64,
+    pub lines_removed: u64,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TimeConsumptionMetricsItem {
     pub total_session_time_minutes: f64,
+    pub active_time_minutes: Option<f64>,
+    pub idle_gaps: u32,
     pub peak_hours: Vec<u32>,
+    pub compactions: u32,
+    pub segment_minutes: Vec<f64>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -209,6 +393,10 @@ pub struct TokenConsumptionMetricsItem {
     pub input_tokens: u64,
     pub output_tokens: u64,
     pub token_efficiency: f64,
+    pub peak_context_tokens: u64,
+    pub cache_creation_input_tokens: u64,
+    pub cache_read_input_tokens: u64,
+    pub cache_hit_ratio: f64,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -218,6 +406,21 @@ pub struct ToolUsageMetricsItem {
     pub failed_operations: u64,
     pub tool_distribution: HashMap<String, u64>,
     pub average_execution_time_ms: f64,
+    pub timed_operations: u64,
+    pub p50_execution_time_ms: f64,
+    pub p95_execution_time_ms: f64,
+    pub thrash_segments: Vec<ThrashSegmentItem>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ThrashSegmentItem {
+    /// `repeated_call` or `edit_revert`
+    pub kind: String,
+    pub tool_name: String,
+    pub target: Option<String>,
+    pub occurrences: u32,
+    pub started_at: String,
+    pub ended_at: String,
 }
 
 // =============================================================================
@@ -320,6 +523,21 @@ impl From<retrochat_core::services::analytics::MetricQuantitativeOutput>
             time_metrics: output.time_metrics.into(),
             token_metrics: output.token_metrics.into(),
             tool_usage: output.tool_usage.into(),
+            rework_rate: output.rework_rate,
+            turns: output.turns.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<retrochat_core::services::analytics::TurnUsageMetrics> for TurnUsageMetricsItem {
+    fn from(turn: retrochat_core::services::analytics::TurnUsageMetrics) -> Self {
+        Self {
+            turn_number: turn.turn_number,
+            prompt_preview: turn.prompt_preview,
+            total_tokens: turn.total_tokens,
+            tool_calls: turn.tool_calls,
+            duration_minutes: turn.duration_minutes,
+            files_touched: turn.files_touched,
         }
     }
 }
@@ -332,6 +550,20 @@ impl From<retrochat_core::services::analytics::FileChangeMetrics> for FileChange
             lines_added: metrics.lines_added,
             lines_removed: metrics.lines_removed,
             net_code_growth: metrics.net_code_growth,
+            languages: metrics.languages.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<retrochat_core::services::analytics::LanguageChangeMetrics>
+    for LanguageChangeMetricsItem
+{
+    fn from(language: retrochat_core::services::analytics::LanguageChangeMetrics) -> Self {
+        Self {
+            language: language.language,
+            files_touched: language.files_touched,
+            lines_added: language.lines_added,
+            lines_removed: language.lines_removed,
         }
     }
 }
@@ -342,7 +574,11 @@ impl From<retrochat_core::services::analytics::TimeConsumptionMetrics>
     fn from(metrics: retrochat_core::services::analytics::TimeConsumptionMetrics) -> Self {
         Self {
             total_session_time_minutes: metrics.total_session_time_minutes,
+            active_time_minutes: metrics.active_time_minutes,
+            idle_gaps: metrics.idle_gaps,
             peak_hours: metrics.peak_hours,
+            compactions: metrics.compactions,
+            segment_minutes: metrics.segment_minutes,
         }
     }
 }
@@ -356,6 +592,10 @@ impl From<retrochat_core::services::analytics::TokenConsumptionMetrics>
             input_tokens: metrics.input_tokens,
             output_tokens: metrics.output_tokens,
             token_efficiency: metrics.token_efficiency,
+            peak_context_tokens: metrics.peak_context_tokens,
+            cache_creation_input_tokens: metrics.cache_creation_input_tokens,
+            cache_read_input_tokens: metrics.cache_read_input_tokens,
+            cache_hit_ratio: metrics.cache_hit_ratio,
         }
     }
 }
@@ -368,6 +608,32 @@ impl From<retrochat_core::services::analytics::ToolUsageMetrics> for ToolUsageMe
             failed_operations: metrics.failed_operations,
             tool_distribution: metrics.tool_distribution,
             average_execution_time_ms: metrics.average_execution_time_ms,
+            timed_operations: metrics.timed_operations,
+            p50_execution_time_ms: metrics.p50_execution_time_ms,
+            p95_execution_time_ms: metrics.p95_execution_time_ms,
+            thrash_segments: metrics
+                .thrash_segments
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        }
+    }
+}
+
+impl From<retrochat_core::services::analytics::ThrashSegment> for ThrashSegmentItem {
+    fn from(segment: retrochat_core::services::analytics::ThrashSegment) -> Self {
+        use retrochat_core::services::analytics::ThrashKind;
+        Self {
+            kind: match segment.kind {
+                ThrashKind::RepeatedCall => "repeated_call",
+                ThrashKind::EditRevert => "edit_revert",
+            }
+            .to_string(),
+            tool_name: segment.tool_name,
+            target: segment.target,
+            occurrences: segment.occurrences,
+            started_at: segment.started_at.to_rfc3339(),
+            ended_at: segment.ended_at.to_rfc3339(),
         }
     }
 }
@@ -397,3 +663,139 @@ pub struct HistogramResponse {
     pub end_time: String,
     pub interval_minutes: i32,
 }
+
+// =============================================================================
+// Chart DTOs
+// =============================================================================
+
+/// Time window of a chart; either end may be open
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ChartRequest {
+    /// RFC3339 timestamp, date or relative time such as "30 days ago"
+    pub since: Option<String>,
+    pub until: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DailyUsageItem {
+    pub date: String, // YYYY-MM-DD (UTC)
+    pub sessions: i64,
+    pub messages: i64,
+    pub tokens: i64,
+}
+
+impl From<retrochat_core::database::DailyUsagePoint> for DailyUsageItem {
+    fn from(point: retrochat_core::database::DailyUsagePoint) -> Self {
+        Self {
+            date: point.date,
+            sessions: point.sessions,
+            messages: point.messages,
+            tokens: point.tokens,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ToolUsageItem {
+    pub tool_name: String,
+    pub calls: i64,
+    pub failures: i64,
+}
+
+impl From<retrochat_core::database::ToolUsagePoint> for ToolUsageItem {
+    fn from(point: retrochat_core::database::ToolUsagePoint) -> Self {
+        Self {
+            tool_name: point.tool_name,
+            calls: point.calls,
+            failures: point.failures,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TokenTrendItem {
+    pub date: String, // YYYY-MM-DD (UTC)
+    pub provider: String,
+    pub tokens: i64,
+}
+
+impl From<retrochat_core::database::TokenTrendPoint> for TokenTrendItem {
+    fn from(point: retrochat_core::database::TokenTrendPoint) -> Self {
+        Self {
+            date: point.date,
+            provider: point.provider,
+            tokens: point.tokens,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RubricTrendItem {
+    pub date: String, // YYYY-MM-DD (UTC)
+    pub rubric_name: String,
+    pub average_percentage: f64, // 0 - 100
+    pub analyses: i64,
+}
+
+impl From<retrochat_core::database::RubricTrendPoint> for RubricTrendItem {
+    fn from(point: retrochat_core::database::RubricTrendPoint) -> Self {
+        Self {
+            date: point.date,
+            rubric_name: point.rubric_name,
+            average_percentage: point.average_percentage,
+            analyses: point.analyses,
+        }
+    }
+}
+
+// =============================================================================
+// Onboarding DTOs
+// =============================================================================
+
+/// Chat history found on this machine for one provider
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DetectedProviderItem {
+    pub provider: String,
+    pub paths: Vec<String>,
+    pub estimated_sessions: usize,
+    /// Whether any of the paths holds chat history
+    pub found: bool,
+    /// RFC3339 modification time of the newest session file
+    pub newest_modified: Option<String>,
+    /// Session files not yet imported (or changed since import)
+    pub unimported_sessions: Option<usize>,
+}
+
+impl From<retrochat_core::services::DetectedProvider> for DetectedProviderItem {
+    fn from(detected: retrochat_core::services::DetectedProvider) -> Self {
+        Self {
+            provider: detected.provider.to_string(),
+            paths: detected
+                .paths
+                .iter()
+                .map(|path| path.to_string_lossy().to_string())
+                .collect(),
+            estimated_sessions: detected.estimated_sessions,
+            found: detected.is_valid,
+            newest_modified: detected.newest_modified.map(|time| time.to_rfc3339()),
+            unimported_sessions: detected.unimported_sessions,
+        }
+    }
+}
+
+/// Payload of the `initial-import-progress` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InitialImportProgress {
+    pub provider: String,
+    pub path: String,
+    pub completed_files: i32,
+    pub total_files: i32,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct InitialImportResponse {
+    pub providers_imported: Vec<String>,
+    pub total_sessions_imported: i32,
+    pub total_messages_imported: i32,
+    pub errors: Vec<String>,
+}
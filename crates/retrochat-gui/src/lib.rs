@@ -9,21 +9,31 @@ use commands::{
         analyze_session, cancel_analysis, create_analysis, get_analysis_result,
         get_analysis_status, list_analyses, run_analysis,
     },
+    charts::{get_daily_usage, get_rubric_trend, get_token_trend, get_tool_distribution},
+    events::get_events,
     file::{
-        clear_opened_files, get_opened_files, handle_file_drop, import_from_provider,
-        import_sessions,
+        clear_opened_files, get_import_errors, get_opened_files, handle_file_drop,
+        import_from_provider, import_sessions,
     },
     histogram::{get_session_activity_histogram, get_user_message_histogram},
-    session::{get_providers, get_session_detail, get_sessions, search_messages},
+    link::{handle_deep_link, take_deep_link, PendingDeepLink},
+    onboarding::{detect_providers, run_initial_import, set_llm_provider},
+    session::{
+        get_providers, get_session_detail, get_sessions, reveal_session_source, search_messages,
+        search_sessions_advanced,
+    },
+    views::{delete_view, get_saved_views, save_view},
 };
 use retrochat_core::database::{config, DatabaseManager};
 use retrochat_core::services::{
     google_ai::{GoogleAiClient, GoogleAiConfig},
-    AnalyticsRequestService, ImportService, QueryService,
+    AnalyticsRequestService, EventFeed, ImportService, QueryService,
 };
+use retrochat_core::utils::deep_link;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex as StdMutex};
-use tauri::Manager;
+use std::time::Duration;
+use tauri::{Emitter, Manager};
 use tokio::sync::Mutex;
 
 // Application state
@@ -54,9 +64,14 @@ pub async fn run() -> anyhow::Result<()> {
     let import_service = Arc::new(ImportService::new(db_manager.clone()));
     log::debug!("Query and import services initialized");
 
+    // Clean up after an import that was interrupted by a crash
+    if let Err(e) = import_service.resume_interrupted_imports().await {
+        log::warn!("Failed to resume interrupted imports: {}", e);
+    }
+
     // Initialize analytics service if Google AI API key is available
-    let analytics_service = match std::env::var(retrochat_core::env::apis::GOOGLE_AI_API_KEY) {
-        Ok(api_key) if !api_key.is_empty() => {
+    let analytics_service = match retrochat_core::config::get_google_ai_api_key() {
+        Ok(Some(api_key)) if !api_key.is_empty() => {
             log::info!("Google AI API key found, initializing analytics service");
             let google_ai_config = GoogleAiConfig::new(api_key);
             match GoogleAiClient::new(google_ai_config) {
@@ -79,6 +94,7 @@ pub async fn run() -> anyhow::Result<()> {
         }
     };
 
+    let watched_db = db_manager.clone();
     let app_state = Arc::new(Mutex::new(AppState {
         db_manager,
         query_service,
@@ -125,13 +141,20 @@ pub async fn run() -> anyhow::Result<()> {
                 window.close_devtools();
             }
 
-            // Handle file associations on Windows/Linux (command-line arguments)
+            // Handle file associations and retrochat:// links on Windows/Linux
+            // (command-line arguments)
             #[cfg(not(any(target_os = "macos", target_os = "ios")))]
             {
                 log::debug!("Checking for file associations from command line");
                 let args: Vec<String> = std::env::args().collect();
                 if args.len() > 1 {
                     log::debug!("Processing {} command line arguments", args.len() - 1);
+                    if let Some(link) = args[1..]
+                        .iter()
+                        .find(|arg| deep_link::DeepLink::is_link(arg))
+                    {
+                        handle_deep_link(app.handle().clone(), link);
+                    }
                     let file_paths: Vec<PathBuf> = args[1..]
                         .iter()
                         .filter(|arg| {
@@ -151,6 +174,46 @@ pub async fn run() -> anyhow::Result<()> {
                 }
             }
 
+            // Tell the frontend when another process writes to the database
+            let feed = EventFeed::new(&watched_db);
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                let mut changes = match watched_db.watch_changes(Duration::from_millis(500)).await {
+                    Ok(changes) => changes,
+                    Err(e) => {
+                        log::warn!("Database change notifications unavailable: {}", e);
+                        return;
+                    }
+                };
+                while changes.changed().await.is_ok() {
+                    if let Err(e) = handle.emit("database-changed", ()) {
+                        log::warn!("Failed to emit database-changed event: {}", e);
+                    }
+                }
+            });
+
+            // Forward new change feed events; the frontend catches up on
+            // older ones with get_events
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                let subscription = match feed.latest_cursor().await {
+                    Ok(cursor) => feed.subscribe(cursor, Duration::from_millis(500)).await,
+                    Err(e) => Err(e),
+                };
+                let mut events = match subscription {
+                    Ok(events) => events,
+                    Err(e) => {
+                        log::warn!("Event log bridging unavailable: {}", e);
+                        return;
+                    }
+                };
+                while let Some(event) = events.recv().await {
+                    if let Err(e) = handle.emit("retrochat-event", dto::EventItem::from(event)) {
+                        log::warn!("Failed to emit retrochat-event: {}", e);
+                    }
+                }
+            });
+
             log::info!("Tauri setup completed successfully");
             Ok(())
         })
@@ -158,11 +221,18 @@ pub async fn run() -> anyhow::Result<()> {
         .plugin(tauri_plugin_dialog::init())
         .manage(app_state)
         .manage(OpenedFiles(StdMutex::new(Vec::new())))
+        .manage(PendingDeepLink::default())
         .invoke_handler(tauri::generate_handler![
             get_sessions,
             get_session_detail,
             search_messages,
+            search_sessions_advanced,
+            reveal_session_source,
             get_providers,
+            get_saved_views,
+            save_view,
+            delete_view,
+            get_events,
             analyze_session,
             create_analysis,
             run_analysis,
@@ -174,17 +244,30 @@ pub async fn run() -> anyhow::Result<()> {
             clear_opened_files,
             import_sessions,
             import_from_provider,
+            get_import_errors,
             get_session_activity_histogram,
             get_user_message_histogram,
+            get_daily_usage,
+            get_tool_distribution,
+            get_token_trend,
+            get_rubric_trend,
+            take_deep_link,
+            detect_providers,
+            run_initial_import,
+            set_llm_provider,
         ])
         .build(tauri::generate_context!())
         .expect("error while building tauri application")
         .run(|app, event| {
             match event {
-                // Handle file associations on macOS/iOS (file open events)
+                // Handle file associations and retrochat:// links on macOS/iOS
+                // (file open events)
                 #[cfg(any(target_os = "macos", target_os = "ios"))]
                 tauri::RunEvent::Opened { urls } => {
                     log::debug!("Received file open event with {} URLs", urls.len());
+                    if let Some(link) = urls.iter().find(|url| url.scheme() == deep_link::SCHEME) {
+                        handle_deep_link(app.clone(), link.as_str());
+                    }
                     let file_paths: Vec<PathBuf> = urls
                         .iter()
                         .filter_map(|url| {
@@ -5,6 +5,7 @@
 
 pub mod error;
 pub mod server;
+pub mod truncation;
 
 // Re-exports for convenience
 pub use server::*;
@@ -1,11 +1,15 @@
 //! MCP Server implementation for RetroChat
 
 use crate::error::{not_found_error, to_mcp_error, validation_error};
+use crate::truncation::ResponseBudget;
 use retrochat_core::database::DatabaseManager;
 use retrochat_core::services::{
-    DateRange, QueryService, SearchRequest, SessionDetailRequest, SessionFilters,
-    SessionsQueryRequest,
+    ActivityDigestService, DateRange, QueryService, RecentActivityRequest, SearchRequest,
+    SessionCursor, SessionDetailRequest, SessionFilters, SessionSimilarityService,
+    SessionsQueryRequest, SqlQueryService, ToolOperationsQueryRequest,
 };
+use retrochat_core::utils::filter_expr::FilterExpr;
+use retrochat_core::utils::time_parser::{parse_time_spec, parse_until_spec};
 use rmcp::handler::server::{router::tool::ToolRouter, wrapper::Parameters};
 use rmcp::model::{CallToolResult, Content, ServerCapabilities, ServerInfo};
 use rmcp::{tool, tool_handler, tool_router, ErrorData as McpError, ServerHandler};
@@ -22,6 +26,7 @@ use uuid::Uuid;
 pub struct RetroChatMcpServer {
     pub(crate) db_manager: Arc<DatabaseManager>,
     pub(crate) tool_router: ToolRouter<Self>,
+    pub(crate) response_budget: ResponseBudget,
 }
 
 impl RetroChatMcpServer {
@@ -30,6 +35,21 @@ impl RetroChatMcpServer {
         QueryService::with_database(self.db_manager.clone())
     }
 
+    /// Get the activity digest service (creates fresh instance)
+    pub(crate) fn activity_digest_service(&self) -> ActivityDigestService {
+        ActivityDigestService::with_database(self.db_manager.clone())
+    }
+
+    /// Get the session similarity service (creates fresh instance)
+    pub(crate) fn session_similarity_service(&self) -> SessionSimilarityService {
+        SessionSimilarityService::new(self.db_manager.clone())
+    }
+
+    /// Get the read-only SQL service (creates fresh instance)
+    pub(crate) fn sql_query_service(&self) -> SqlQueryService {
+        SqlQueryService::new(self.db_manager.clone())
+    }
+
     /// Create a new MCP server with default database
     pub async fn new() -> anyhow::Result<Self> {
         let db_path = retrochat_core::database::config::get_default_db_path()?;
@@ -38,6 +58,7 @@ impl RetroChatMcpServer {
         Ok(Self {
             db_manager,
             tool_router: Self::tool_router(),
+            response_budget: ResponseBudget::from_env(),
         })
     }
 
@@ -46,8 +67,34 @@ impl RetroChatMcpServer {
         Self {
             db_manager,
             tool_router: Self::tool_router(),
+            response_budget: ResponseBudget::default(),
         }
     }
+
+    /// Override the response size budget applied to tool results
+    pub fn with_response_budget(mut self, budget: ResponseBudget) -> Self {
+        self.response_budget = budget;
+        self
+    }
+
+    /// Serialize a tool result as pretty JSON within the response budget
+    ///
+    /// `hint` is included in the truncation notice to tell the assistant how to
+    /// fetch the data that was cut.
+    pub(crate) fn json_result<T: Serialize>(
+        &self,
+        value: &T,
+        hint: &str,
+    ) -> Result<CallToolResult, McpError> {
+        let value = serde_json::to_value(value)
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        let json = self
+            .response_budget
+            .render(value, hint)
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        Ok(CallToolResult::success(vec![Content::text(json)]))
+    }
 }
 
 // Implement the ServerHandler trait
@@ -65,7 +112,11 @@ impl ServerHandler for RetroChatMcpServer {
             instructions: Some(
                 "RetroChat MCP Server - Query and analyze your AI chat history. \
                  Use list_sessions to browse sessions, get_session_detail for full session info, \
-                 search_messages for full-text search, and get_session_analytics for analytics data."
+                 search_messages for full-text search, get_session_analytics for analytics data, \
+                 get_recent_activity for a digest of recent sessions, file changes and failures, \
+                 find_similar_sessions to learn from past sessions that tackled a similar task, \
+                 query_tool_operations to inspect tool calls such as failed bash commands, \
+                 and run_sql for ad-hoc read-only SQL when no other tool fits."
                     .into(),
             ),
             capabilities: ServerCapabilities::builder().enable_tools().build(),
@@ -148,6 +199,16 @@ pub struct ListSessionsParams {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_messages: Option<i32>,
 
+    /// Filter expression combining terms such as provider:claude, project:NAME,
+    /// tokens>50000, messages<=10, since:"7 days ago", tool:Bash(failed) and free text
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+
+    /// Whose sessions to list: "me", "team" (teammates' imported sessions)
+    /// or a teammate's name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+
     /// Page number (default: 1)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub page: Option<i32>,
@@ -163,12 +224,25 @@ pub struct ListSessionsParams {
     /// Sort order: "asc" or "desc" (default: "desc")
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sort_order: Option<String>,
+
+    /// Continue after this cursor (the `next_cursor` of a previous response)
+    /// instead of using `page`; only valid when sorting by start_time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct GetSessionDetailParams {
     /// Session ID (UUID format)
     pub session_id: String,
+
+    /// Maximum number of messages to return (default: all)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_limit: Option<i32>,
+
+    /// Number of messages to skip (default: 0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_offset: Option<i32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -207,6 +281,77 @@ pub struct GetSessionAnalyticsParams {
     pub session_id: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GetRecentActivityParams {
+    /// Start of the window, e.g. "7 days ago", "yesterday", "2024-01-01" or RFC 3339 (default: "7 days ago")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<String>,
+
+    /// End of the window, same formats as `since` (default: now)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub until: Option<String>,
+
+    /// Maximum entries per digest section (default: 10)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FindSimilarSessionsParams {
+    /// Free-text description of the task or problem
+    pub query: String,
+
+    /// Number of sessions to return (default: 5, max: 50)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_k: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct QueryToolOperationsParams {
+    /// Filter by tool name, case-insensitive (e.g., "Bash", "Edit", "Write")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_name: Option<String>,
+
+    /// Filter by outcome: true for successful, false for failed operations
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub success: Option<bool>,
+
+    /// Filter by a substring of the affected file path
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_path: Option<String>,
+
+    /// Restrict to a single session (UUID format)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+
+    /// Operations from this time, e.g. "7 days ago", "2024-01-01" or RFC 3339
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<String>,
+
+    /// Operations until this time, same formats as `since`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub until: Option<String>,
+
+    /// Page number (default: 1)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<i32>,
+
+    /// Items per page (default: 20)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RunSqlParams {
+    /// A single read-only statement (SELECT, WITH, EXPLAIN or VALUES). Omit to get the schema
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sql: Option<String>,
+
+    /// Maximum rows to return (default: 100, max: 1000)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
 // ============================================================================
 // Tool Implementations
 // ============================================================================
@@ -215,7 +360,7 @@ pub struct GetSessionAnalyticsParams {
 impl RetroChatMcpServer {
     /// List chat sessions with optional filtering and pagination
     #[tool(
-        description = "List chat sessions with optional filtering by provider, project, date range, message count, and pagination support"
+        description = "List chat sessions with optional filtering by provider, project, date range, message count, a filter expression (e.g. 'tokens>50000 tool:Bash(failed)'), and page or cursor (next_cursor/after) pagination"
     )]
     pub async fn list_sessions(
         &self,
@@ -232,6 +377,16 @@ impl RetroChatMcpServer {
             chrono::DateTime::parse_from_rfc3339(end)
                 .map_err(|_| validation_error(&format!("Invalid end_date format: {}", end)))?;
         }
+        if let Some(ref filter) = params.filter {
+            FilterExpr::parse(filter)
+                .map_err(|e| validation_error(&format!("Invalid filter: {}", e)))?;
+        }
+        let after = params
+            .after
+            .as_deref()
+            .map(str::parse::<SessionCursor>)
+            .transpose()
+            .map_err(|e| validation_error(&e.to_string()))?;
         if let Some(ref order) = params.sort_order {
             if order != "asc" && order != "desc" {
                 return Err(validation_error(&format!(
@@ -255,6 +410,8 @@ impl RetroChatMcpServer {
             || date_range.is_some()
             || params.min_messages.is_some()
             || params.max_messages.is_some()
+            || params.filter.is_some()
+            || params.owner.is_some()
         {
             Some(SessionFilters {
                 provider: params.provider,
@@ -262,6 +419,13 @@ impl RetroChatMcpServer {
                 date_range,
                 min_messages: params.min_messages,
                 max_messages: params.max_messages,
+                include_archived: false,
+                expression: params.filter,
+                topic: None,
+                owner: params.owner,
+                providers: None,
+                projects: None,
+                topics: None,
             })
         } else {
             None
@@ -273,6 +437,7 @@ impl RetroChatMcpServer {
             sort_by: params.sort_by,
             sort_order: params.sort_order,
             filters,
+            after,
         };
 
         // Query sessions
@@ -282,11 +447,11 @@ impl RetroChatMcpServer {
             .await
             .map_err(to_mcp_error)?;
 
-        // Return pretty-printed JSON
-        let json = serde_json::to_string_pretty(&response)
-            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
-
-        Ok(CallToolResult::success(vec![Content::text(json)]))
+        // Return pretty-printed JSON within the response budget
+        self.json_result(
+            &response,
+            "Request fewer sessions with page/page_size or narrower filters",
+        )
     }
 
     /// Get detailed information about a specific chat session including all messages
@@ -311,8 +476,8 @@ impl RetroChatMcpServer {
         let request = SessionDetailRequest {
             session_id: params.session_id.clone(),
             include_content: Some(true),
-            message_limit: None,
-            message_offset: None,
+            message_limit: params.message_limit,
+            message_offset: params.message_offset,
         };
 
         // Get session detail
@@ -329,11 +494,11 @@ impl RetroChatMcpServer {
                 }
             })?;
 
-        // Return pretty-printed JSON
-        let json = serde_json::to_string_pretty(&response)
-            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
-
-        Ok(CallToolResult::success(vec![Content::text(json)]))
+        // Return pretty-printed JSON within the response budget
+        self.json_result(
+            &response,
+            "Page through messages with message_offset/message_limit, or use search_messages",
+        )
     }
 
     /// Full-text search across all messages in chat sessions
@@ -387,11 +552,11 @@ impl RetroChatMcpServer {
             .await
             .map_err(to_mcp_error)?;
 
-        // Return pretty-printed JSON
-        let json = serde_json::to_string_pretty(&response)
-            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
-
-        Ok(CallToolResult::success(vec![Content::text(json)]))
+        // Return pretty-printed JSON within the response budget
+        self.json_result(
+            &response,
+            "Request fewer results with page/page_size or narrower filters",
+        )
     }
 
     /// Get analytics information for a specific chat session
@@ -427,18 +592,176 @@ impl RetroChatMcpServer {
             })?;
 
         // Manually construct JSON since SessionAnalytics doesn't implement Serialize
-        let json = if let Some(analytics) = response {
-            let value = serde_json::json!({
+        let value = if let Some(analytics) = response {
+            serde_json::json!({
                 "latest_analytics": analytics.latest_analytics,
                 "latest_request": analytics.latest_request,
                 "active_request": analytics.active_request,
-            });
-            serde_json::to_string_pretty(&value)
-                .map_err(|e| McpError::internal_error(e.to_string(), None))?
+            })
         } else {
-            "null".to_string()
+            serde_json::Value::Null
         };
 
-        Ok(CallToolResult::success(vec![Content::text(json)]))
+        self.json_result(&value, "Analytics text fields were shortened")
+    }
+
+    /// Summarize recent activity across all sessions
+    #[tool(
+        description = "Get a compact digest of recent activity for a time window: active sessions, projects, most edited files, and notable tool failures. Accepts relative times like \"7 days ago\" or \"yesterday\""
+    )]
+    pub async fn get_recent_activity(
+        &self,
+        params: Parameters<GetRecentActivityParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+
+        // Parse the time window
+        let since = parse_time_spec(params.since.as_deref().unwrap_or("7 days ago"))
+            .map_err(|e| validation_error(&format!("Invalid since: {}", e)))?;
+        let until = parse_until_spec(params.until.as_deref().unwrap_or("now"))
+            .map_err(|e| validation_error(&format!("Invalid until: {}", e)))?;
+        if since > until {
+            return Err(validation_error("since must be earlier than until"));
+        }
+        if params.limit == Some(0) {
+            return Err(validation_error("limit must be greater than 0"));
+        }
+
+        let request = RecentActivityRequest {
+            since,
+            until,
+            limit: params.limit,
+        };
+
+        // Build digest
+        let digest = self
+            .activity_digest_service()
+            .get_recent_activity(request)
+            .await
+            .map_err(to_mcp_error)?;
+
+        // Return pretty-printed JSON within the response budget
+        self.json_result(
+            &digest,
+            "Use a shorter since/until window or a smaller limit",
+        )
+    }
+
+    /// Find past sessions similar to a task description
+    #[tool(
+        description = "Find past chat sessions most similar to a free-text task description, returning the top matches with their summaries so you can learn how a similar problem was solved before"
+    )]
+    pub async fn find_similar_sessions(
+        &self,
+        params: Parameters<FindSimilarSessionsParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+
+        // Validate query
+        if params.query.trim().is_empty() {
+            return Err(validation_error("Query cannot be empty"));
+        }
+        let top_k = params.top_k.unwrap_or(5);
+        if top_k == 0 || top_k > 50 {
+            return Err(validation_error("top_k must be between 1 and 50"));
+        }
+
+        // Rank sessions
+        let results = self
+            .session_similarity_service()
+            .find_similar_sessions(&params.query, top_k)
+            .await
+            .map_err(to_mcp_error)?;
+
+        // Return pretty-printed JSON within the response budget
+        self.json_result(&results, "Use a smaller top_k")
+    }
+
+    /// Query tool operations across all sessions
+    #[tool(
+        description = "Query tool operations (bash commands, file edits, reads, etc.) across all sessions, filtering by tool name, success, file path, session, and date range. Results are newest first with pagination"
+    )]
+    pub async fn query_tool_operations(
+        &self,
+        params: Parameters<QueryToolOperationsParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+
+        // Validate session ID and time window
+        if let Some(ref session_id) = params.session_id {
+            Uuid::parse_str(session_id).map_err(|_| {
+                validation_error(&format!(
+                    "Invalid session_id format: {}. Must be a valid UUID",
+                    session_id
+                ))
+            })?;
+        }
+        let since = params
+            .since
+            .as_deref()
+            .map(parse_time_spec)
+            .transpose()
+            .map_err(|e| validation_error(&format!("Invalid since: {}", e)))?;
+        let until = params
+            .until
+            .as_deref()
+            .map(parse_until_spec)
+            .transpose()
+            .map_err(|e| validation_error(&format!("Invalid until: {}", e)))?;
+
+        let request = ToolOperationsQueryRequest {
+            tool_name: params.tool_name,
+            success: params.success,
+            file_path: params.file_path,
+            session_id: params.session_id,
+            since,
+            until,
+            page: params.page,
+            page_size: params.page_size,
+        };
+
+        // Query tool operations
+        let response = self
+            .query_service()
+            .query_tool_operations(request)
+            .await
+            .map_err(to_mcp_error)?;
+
+        // Return pretty-printed JSON within the response budget
+        self.json_result(
+            &response,
+            "Request fewer operations with page/page_size or narrower filters",
+        )
+    }
+
+    /// Run a read-only SQL query
+    #[tool(
+        description = "Run a single read-only SQL statement (SELECT, WITH, EXPLAIN or VALUES) against the RetroChat SQLite database and get columns and rows back. Call without sql to get the CREATE statements of all tables and views first. Writes, PRAGMA and ATTACH are rejected"
+    )]
+    pub async fn run_sql(
+        &self,
+        params: Parameters<RunSqlParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let service = self.sql_query_service();
+
+        let Some(sql) = params.sql else {
+            let schema = service.schema().await.map_err(to_mcp_error)?;
+            return self.json_result(&schema, "Query sqlite_master for individual tables");
+        };
+
+        let limit = params.limit.unwrap_or(100);
+        if limit == 0 || limit > 1000 {
+            return Err(validation_error("limit must be between 1 and 1000"));
+        }
+        retrochat_core::services::sql_query::validate_read_only_sql(&sql)
+            .map_err(|e| validation_error(&e.to_string()))?;
+
+        let result = service.run(&sql, limit).await.map_err(to_mcp_error)?;
+
+        self.json_result(
+            &result,
+            "Select fewer columns, use LIMIT, or aggregate in SQL",
+        )
     }
 }
@@ -0,0 +1,296 @@
+//! Response size budget for MCP tool results
+//!
+//! Tool results are JSON documents that can grow very large (e.g. a session
+//! with thousands of messages). Before a result is returned, long string fields
+//! are cut down to a head and tail with a marker in between, and if the document
+//! still exceeds the byte budget the largest arrays are shortened. Whenever
+//! anything is dropped a `_truncation` object is attached describing what
+//! happened and how to fetch the rest.
+
+use retrochat_core::env::mcp as env_vars;
+use serde_json::{json, Map, Value};
+use std::collections::HashSet;
+
+/// Default maximum size of a serialized tool result (~64k tokens)
+pub const DEFAULT_MAX_RESPONSE_BYTES: usize = 256 * 1024;
+
+/// Default maximum length of a single string field before head/tail truncation
+pub const DEFAULT_MAX_FIELD_CHARS: usize = 8 * 1024;
+
+/// Rough bytes-per-token ratio used when the budget is given in tokens
+const BYTES_PER_TOKEN: usize = 4;
+
+/// Smallest field length the budget will shrink fields down to
+const MIN_FIELD_CHARS: usize = 256;
+
+/// Size budget applied to every tool result
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResponseBudget {
+    pub max_bytes: usize,
+    pub max_field_chars: usize,
+}
+
+impl Default for ResponseBudget {
+    fn default() -> Self {
+        Self {
+            max_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+            max_field_chars: DEFAULT_MAX_FIELD_CHARS,
+        }
+    }
+}
+
+impl ResponseBudget {
+    /// Build a budget from environment variables, falling back to defaults
+    ///
+    /// `RETROCHAT_MCP_MAX_RESPONSE_BYTES` takes precedence over
+    /// `RETROCHAT_MCP_MAX_RESPONSE_TOKENS`.
+    pub fn from_env() -> Self {
+        let read = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse::<usize>().ok())
+                .filter(|v| *v > 0)
+        };
+
+        let mut budget = Self::default();
+        if let Some(bytes) = read(env_vars::MAX_RESPONSE_BYTES) {
+            budget.max_bytes = bytes;
+        } else if let Some(tokens) = read(env_vars::MAX_RESPONSE_TOKENS) {
+            budget.max_bytes = tokens.saturating_mul(BYTES_PER_TOKEN);
+        }
+        if let Some(chars) = read(env_vars::MAX_FIELD_CHARS) {
+            budget.max_field_chars = chars;
+        }
+        budget
+    }
+
+    /// Serialize `value` as pretty JSON, truncating it to fit the budget
+    ///
+    /// `hint` tells the caller how to request the omitted data (e.g. which
+    /// pagination parameters the tool accepts).
+    pub fn render(&self, value: Value, hint: &str) -> serde_json::Result<String> {
+        let original_bytes = serde_json::to_string_pretty(&value)?.len();
+        if original_bytes <= self.max_bytes && !has_long_string(&value, self.max_field_chars) {
+            return serde_json::to_string_pretty(&value);
+        }
+
+        let mut value = value;
+        let mut field_limit = self.max_field_chars;
+        // Paths of cut fields, so a field cut again on a later pass counts once
+        let mut truncated_fields = HashSet::new();
+        truncate_strings(&mut value, field_limit, "", &mut truncated_fields);
+
+        // Shrink string fields further until the document fits
+        while serde_json::to_string_pretty(&value)?.len() > self.max_bytes
+            && field_limit > MIN_FIELD_CHARS
+        {
+            field_limit = (field_limit / 2).max(MIN_FIELD_CHARS);
+            truncate_strings(&mut value, field_limit, "", &mut truncated_fields);
+        }
+
+        // Then drop trailing items from the largest arrays
+        let mut omitted_items = 0;
+        while serde_json::to_string_pretty(&value)?.len() > self.max_bytes {
+            let dropped = shorten_largest_array(&mut value);
+            if dropped == 0 {
+                break;
+            }
+            omitted_items += dropped;
+        }
+
+        let notice = json!({
+            "truncated": true,
+            "original_bytes": original_bytes,
+            "max_bytes": self.max_bytes,
+            "truncated_fields": truncated_fields.len(),
+            "omitted_items": omitted_items,
+            "hint": hint,
+        });
+
+        let value = match value {
+            Value::Object(mut map) => {
+                map.insert("_truncation".to_string(), notice);
+                Value::Object(map)
+            }
+            other => {
+                let mut map = Map::new();
+                map.insert("result".to_string(), other);
+                map.insert("_truncation".to_string(), notice);
+                Value::Object(map)
+            }
+        };
+
+        serde_json::to_string_pretty(&value)
+    }
+}
+
+/// Keep the head and tail of `text`, replacing the middle with a marker
+pub fn truncate_middle(text: &str, max_chars: usize) -> String {
+    let total = text.chars().count();
+    if total <= max_chars {
+        return text.to_string();
+    }
+
+    let head_len = max_chars * 2 / 3;
+    let tail_len = max_chars - head_len;
+    let head: String = text.chars().take(head_len).collect();
+    let tail: String = text.chars().skip(total - tail_len).collect();
+    let omitted = total - head_len - tail_len;
+
+    format!("{head}\n…[truncated {omitted} chars]…\n{tail}")
+}
+
+fn has_long_string(value: &Value, max_chars: usize) -> bool {
+    match value {
+        Value::String(s) => s.len() > max_chars && s.chars().count() > max_chars,
+        Value::Array(items) => items.iter().any(|v| has_long_string(v, max_chars)),
+        Value::Object(map) => map.values().any(|v| has_long_string(v, max_chars)),
+        _ => false,
+    }
+}
+
+/// Truncate every string longer than `max_chars`, adding the path of each
+/// cut field under `path` to `truncated`
+fn truncate_strings(
+    value: &mut Value,
+    max_chars: usize,
+    path: &str,
+    truncated: &mut HashSet<String>,
+) {
+    match value {
+        Value::String(s) if s.len() > max_chars && s.chars().count() > max_chars => {
+            *s = truncate_middle(s, max_chars);
+            truncated.insert(path.to_string());
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter_mut().enumerate() {
+                truncate_strings(item, max_chars, &format!("{path}[{index}]"), truncated);
+            }
+        }
+        Value::Object(map) => {
+            for (key, item) in map.iter_mut() {
+                truncate_strings(item, max_chars, &format!("{path}.{key}"), truncated);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Halve the longest array in the document, returning the number of dropped items
+fn shorten_largest_array(value: &mut Value) -> usize {
+    fn longest(value: &Value) -> usize {
+        match value {
+            Value::Array(items) => items
+                .iter()
+                .map(longest)
+                .max()
+                .unwrap_or(0)
+                .max(items.len()),
+            Value::Object(map) => map.values().map(longest).max().unwrap_or(0),
+            _ => 0,
+        }
+    }
+
+    fn halve(value: &mut Value, target: usize) -> usize {
+        match value {
+            Value::Array(items) if items.len() == target => {
+                let keep = items.len() / 2;
+                items.truncate(keep);
+                target - keep
+            }
+            Value::Array(items) => items
+                .iter_mut()
+                .map(|v| halve(v, target))
+                .find(|d| *d > 0)
+                .unwrap_or(0),
+            Value::Object(map) => map
+                .values_mut()
+                .map(|v| halve(v, target))
+                .find(|d| *d > 0)
+                .unwrap_or(0),
+            _ => 0,
+        }
+    }
+
+    let target = longest(value);
+    if target == 0 {
+        return 0;
+    }
+    halve(value, target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_small_response_is_unchanged() {
+        let budget = ResponseBudget::default();
+        let value = json!({ "sessions": [{ "id": "a" }] });
+
+        let rendered = budget.render(value.clone(), "use page").unwrap();
+
+        assert_eq!(rendered, serde_json::to_string_pretty(&value).unwrap());
+        assert!(!rendered.contains("_truncation"));
+    }
+
+    #[test]
+    fn test_truncate_middle_keeps_head_and_tail() {
+        let text = format!("{}{}", "a".repeat(100), "z".repeat(100));
+        let truncated = truncate_middle(&text, 30);
+
+        assert!(truncated.starts_with(&"a".repeat(20)));
+        assert!(truncated.ends_with(&"z".repeat(10)));
+        assert!(truncated.contains("[truncated 170 chars]"));
+        assert_eq!(truncate_middle("short", 30), "short");
+    }
+
+    #[test]
+    fn test_long_fields_are_truncated_with_notice() {
+        let budget = ResponseBudget {
+            max_bytes: 1024 * 1024,
+            max_field_chars: 300,
+        };
+        let value = json!({ "content": "x".repeat(5000) });
+
+        let rendered: Value =
+            serde_json::from_str(&budget.render(value, "use page").unwrap()).unwrap();
+
+        assert!(rendered["content"].as_str().unwrap().len() < 400);
+        assert_eq!(rendered["_truncation"]["truncated_fields"], 1);
+        assert_eq!(rendered["_truncation"]["hint"], "use page");
+    }
+
+    #[test]
+    fn test_fields_cut_on_several_passes_count_once() {
+        let budget = ResponseBudget {
+            max_bytes: 3000,
+            max_field_chars: 4000,
+        };
+        // `content` is cut on every pass, `summary` only once the limit drops below it
+        let value = json!({ "content": "x".repeat(10_000), "summary": "y".repeat(1500) });
+
+        let rendered: Value =
+            serde_json::from_str(&budget.render(value, "use page").unwrap()).unwrap();
+
+        assert!(rendered["content"].as_str().unwrap().len() < 1100);
+        assert!(rendered["summary"].as_str().unwrap().len() < 1100);
+        assert_eq!(rendered["_truncation"]["truncated_fields"], 2);
+    }
+
+    #[test]
+    fn test_large_arrays_are_shortened_to_fit() {
+        let budget = ResponseBudget {
+            max_bytes: 2000,
+            max_field_chars: 1000,
+        };
+        let items: Vec<Value> = (0..200).map(|i| json!({ "id": i })).collect();
+
+        let rendered = budget.render(Value::Array(items), "use page").unwrap();
+        let parsed: Value = serde_json::from_str(&rendered).unwrap();
+
+        assert!(rendered.len() <= 2000 + 400);
+        assert!(parsed["result"].as_array().unwrap().len() < 200);
+        assert!(parsed["_truncation"]["omitted_items"].as_u64().unwrap() > 0);
+    }
+}
@@ -0,0 +1,287 @@
+//! Python bindings for RetroChat
+//!
+//! Built as the `retrochat` Python module with maturin. Rows come back as
+//! lists of plain dicts, so they go straight into pandas:
+//!
+//! ```python
+//! import pandas as pd
+//! import retrochat
+//!
+//! db = retrochat.open()
+//! sessions = pd.DataFrame(db.sessions(filter='since:"last month"'))
+//! tools = pd.DataFrame(db.tool_operations(tool_name="Bash", success=False))
+//! ```
+//!
+//! Timestamps are RFC 3339 strings (`pd.to_datetime` parses them).
+
+use pyo3::exceptions::PyRuntimeError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use serde::Serialize;
+use serde_json::Value;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+use retrochat_client::{ListOptions, RetrochatClient, SearchOptions, SessionSummary};
+use retrochat_core::services::{export_sqlite_snapshot, QueryService, ToolOperationsQueryRequest};
+
+/// Rows read per query when collecting a whole listing
+const PAGE_SIZE: u32 = 500;
+
+/// An open retrochat database
+#[pyclass(module = "retrochat", frozen)]
+struct Database {
+    client: RetrochatClient,
+    runtime: tokio::runtime::Runtime,
+}
+
+/// Open the database the `retrochat` CLI uses, or the one at `path`
+#[pyfunction]
+#[pyo3(signature = (path=None))]
+fn open(py: Python<'_>, path: Option<PathBuf>) -> PyResult<Database> {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()?;
+    let client = py
+        .allow_threads(|| {
+            runtime.block_on(async {
+                match path {
+                    Some(path) => RetrochatClient::open(path).await,
+                    None => RetrochatClient::open_default().await,
+                }
+            })
+        })
+        .map_err(to_py_err)?;
+    Ok(Database { client, runtime })
+}
+
+#[pymethods]
+impl Database {
+    /// Sessions, newest first. `filter` takes a `retrochat list --filter`
+    /// expression; all matching sessions are returned unless `limit` is set
+    #[pyo3(signature = (filter=None, provider=None, project=None, limit=None, include_archived=false))]
+    fn sessions(
+        &self,
+        py: Python<'_>,
+        filter: Option<String>,
+        provider: Option<String>,
+        project: Option<String>,
+        limit: Option<u32>,
+        include_archived: bool,
+    ) -> PyResult<PyObject> {
+        let options = ListOptions {
+            filter,
+            provider,
+            project,
+            include_archived,
+            ..Default::default()
+        };
+        let sessions = self.block_on(py, collect_sessions(&self.client, options, limit))?;
+        to_python(py, &sessions)
+    }
+
+    /// Messages of a session in conversation order
+    fn messages(&self, py: Python<'_>, session_id: &str) -> PyResult<PyObject> {
+        let detail = self.block_on(py, self.client.get(session_id))?;
+        match detail {
+            Some(detail) => to_python(py, &detail.messages),
+            None => Err(PyRuntimeError::new_err(format!(
+                "Session not found: {session_id}"
+            ))),
+        }
+    }
+
+    /// Tool calls, newest first, across all sessions unless `session_id` is
+    /// given
+    #[pyo3(signature = (session_id=None, tool_name=None, success=None, file_path=None, limit=None))]
+    fn tool_operations(
+        &self,
+        py: Python<'_>,
+        session_id: Option<String>,
+        tool_name: Option<String>,
+        success: Option<bool>,
+        file_path: Option<String>,
+        limit: Option<u32>,
+    ) -> PyResult<PyObject> {
+        let query_service = QueryService::with_database(self.client.database().clone());
+        let operations = self.block_on(py, async {
+            let mut operations = Vec::new();
+            let mut page = 1;
+            loop {
+                let response = query_service
+                    .query_tool_operations(ToolOperationsQueryRequest {
+                        tool_name: tool_name.clone(),
+                        success,
+                        file_path: file_path.clone(),
+                        session_id: session_id.clone(),
+                        since: None,
+                        until: None,
+                        page: Some(page),
+                        page_size: Some(PAGE_SIZE as i32),
+                    })
+                    .await?;
+                operations.extend(response.operations);
+                if page >= response.total_pages
+                    || limit.is_some_and(|limit| operations.len() >= limit as usize)
+                {
+                    break;
+                }
+                page += 1;
+            }
+            if let Some(limit) = limit {
+                operations.truncate(limit as usize);
+            }
+            Ok(operations)
+        })?;
+        to_python(py, &operations)
+    }
+
+    /// Messages matching a full-text query, best matches first
+    #[pyo3(signature = (query, limit=50, providers=None, projects=None))]
+    fn search(
+        &self,
+        py: Python<'_>,
+        query: &str,
+        limit: u32,
+        providers: Option<Vec<String>>,
+        projects: Option<Vec<String>>,
+    ) -> PyResult<PyObject> {
+        let results = self.block_on(
+            py,
+            self.client.search(
+                query,
+                SearchOptions {
+                    providers: providers.unwrap_or_default(),
+                    projects: projects.unwrap_or_default(),
+                    limit: Some(limit),
+                },
+            ),
+        )?;
+        to_python(py, &results)
+    }
+
+    /// Latest completed analysis of a session, or None
+    fn analytics(&self, py: Python<'_>, session_id: &str) -> PyResult<PyObject> {
+        let analytics = self.block_on(py, self.client.analytics(session_id))?;
+        to_python(py, &analytics)
+    }
+
+    /// Copy sessions (all of them by default) with their messages, tool
+    /// calls and analyses into a new SQLite file; returns the row counts
+    #[pyo3(signature = (path, session_ids=None))]
+    fn export_sqlite(
+        &self,
+        py: Python<'_>,
+        path: PathBuf,
+        session_ids: Option<Vec<String>>,
+    ) -> PyResult<PyObject> {
+        let report = self.block_on(py, async {
+            let ids = match session_ids {
+                Some(ids) => ids
+                    .iter()
+                    .map(|id| Uuid::parse_str(id))
+                    .collect::<Result<Vec<_>, _>>()?,
+                None => {
+                    let options = ListOptions {
+                        include_archived: true,
+                        ..Default::default()
+                    };
+                    collect_sessions(&self.client, options, None)
+                        .await?
+                        .iter()
+                        .map(|session| Uuid::parse_str(&session.session_id))
+                        .collect::<Result<Vec<_>, _>>()?
+                }
+            };
+            export_sqlite_snapshot(self.client.database(), &ids, &path).await
+        })?;
+        let dict = PyDict::new(py);
+        dict.set_item("sessions", report.sessions)?;
+        dict.set_item("messages", report.messages)?;
+        dict.set_item("tool_operations", report.tool_operations)?;
+        dict.set_item("analytics", report.analytics)?;
+        dict.set_item("summaries", report.summaries)?;
+        Ok(dict.into_any().unbind())
+    }
+}
+
+impl Database {
+    /// Run `future` on the database runtime with the GIL released
+    fn block_on<T: Send>(
+        &self,
+        py: Python<'_>,
+        future: impl std::future::Future<Output = anyhow::Result<T>> + Send,
+    ) -> PyResult<T> {
+        py.allow_threads(|| self.runtime.block_on(future))
+            .map_err(to_py_err)
+    }
+}
+
+/// Every session matching `options` (up to `limit`), following the cursor
+/// from page to page
+async fn collect_sessions(
+    client: &RetrochatClient,
+    mut options: ListOptions,
+    limit: Option<u32>,
+) -> anyhow::Result<Vec<SessionSummary>> {
+    let mut sessions = Vec::new();
+    loop {
+        let wanted = limit.map_or(PAGE_SIZE, |limit| {
+            limit.saturating_sub(sessions.len() as u32).min(PAGE_SIZE)
+        });
+        if wanted == 0 {
+            break;
+        }
+        options.limit = Some(wanted);
+        let page = client.list(options.clone()).await?;
+        sessions.extend(page.sessions);
+        match page.next_cursor {
+            Some(cursor) => options.after = Some(cursor),
+            None => break,
+        }
+    }
+    Ok(sessions)
+}
+
+fn to_py_err(e: anyhow::Error) -> PyErr {
+    PyRuntimeError::new_err(format!("{e:#}"))
+}
+
+/// `value` as nested Python dicts, lists and scalars
+fn to_python<T: Serialize>(py: Python<'_>, value: &T) -> PyResult<PyObject> {
+    let value = serde_json::to_value(value).map_err(|e| to_py_err(e.into()))?;
+    json_to_python(py, &value)
+}
+
+fn json_to_python(py: Python<'_>, value: &Value) -> PyResult<PyObject> {
+    Ok(match value {
+        Value::Null => py.None(),
+        Value::Bool(b) => b.into_pyobject(py)?.to_owned().into_any().unbind(),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => i.into_pyobject(py)?.into_any().unbind(),
+            None => n.as_f64().into_pyobject(py)?.into_any().unbind(),
+        },
+        Value::String(s) => s.into_pyobject(py)?.into_any().unbind(),
+        Value::Array(items) => {
+            let items = items
+                .iter()
+                .map(|item| json_to_python(py, item))
+                .collect::<PyResult<Vec<_>>>()?;
+            PyList::new(py, items)?.into_any().unbind()
+        }
+        Value::Object(map) => {
+            let dict = PyDict::new(py);
+            for (key, item) in map {
+                dict.set_item(key, json_to_python(py, item)?)?;
+            }
+            dict.into_any().unbind()
+        }
+    })
+}
+
+#[pymodule]
+fn retrochat(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_function(wrap_pyfunction!(open, module)?)?;
+    module.add_class::<Database>()?;
+    Ok(())
+}
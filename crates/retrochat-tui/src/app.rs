@@ -1,5 +1,5 @@
 use anyhow::Result;
-use crossterm::event::{self, Event, KeyEvent};
+use crossterm::event::{self, Event, KeyEvent, MouseEvent};
 use ratatui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
@@ -16,7 +16,9 @@ use tokio::time::timeout;
 use retrochat_core::database::DatabaseManager;
 use retrochat_core::env::apis as env_vars;
 use retrochat_core::services::google_ai::{GoogleAiClient, GoogleAiConfig};
-use retrochat_core::services::{AnalyticsRequestService, AnalyticsService, QueryService};
+use retrochat_core::services::{
+    AnalyticsRequestService, AnalyticsService, AutoDetectService, QueryService,
+};
 
 use super::{
     components::dialog::{Dialog, DialogType},
@@ -136,6 +138,9 @@ impl Default for AppState {
     }
 }
 
+/// How often the database is checked for writes by other processes
+const CHANGE_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
 pub struct App {
     pub state: AppState,
     pub session_list: SessionListWidget,
@@ -144,6 +149,7 @@ pub struct App {
     pub analytics_service: AnalyticsService,
     pub analytics_request_service: Option<Arc<AnalyticsRequestService>>,
     pub event_handler: EventHandler,
+    db_manager: Arc<DatabaseManager>,
 }
 
 impl App {
@@ -173,6 +179,7 @@ impl App {
             analytics_service,
             analytics_request_service,
             event_handler: EventHandler::new(),
+            db_manager,
         })
     }
 
@@ -180,6 +187,25 @@ impl App {
         // Initial data load
         self.session_list.refresh().await?;
 
+        // Point out chat history that has not been imported yet
+        match AutoDetectService::scan_with_import_status(&self.db_manager).await {
+            Ok(detected) => {
+                self.session_list.state.new_sessions_notice =
+                    AutoDetectService::new_sessions_message(&detected);
+            }
+            Err(e) => tracing::warn!(error = %e, "Failed to check for unimported sessions"),
+        }
+
+        // Refresh when anything (an import, the desktop app, an analysis)
+        // writes to the database; fall back to polling if that fails
+        let mut changes = match self.db_manager.watch_changes(CHANGE_POLL_INTERVAL).await {
+            Ok(changes) => Some(changes),
+            Err(e) => {
+                tracing::warn!(error = %e, "Database change notifications unavailable");
+                None
+            }
+        };
+
         loop {
             // Render UI
             terminal.draw(|f| self.render(f))?;
@@ -198,16 +224,31 @@ impl App {
                 break;
             }
 
+            if self.state.mode == AppMode::SessionDetail {
+                self.session_detail.tick().await;
+            }
+
             // Processing status updates removed
 
+            let changed = match changes.as_mut() {
+                Some(changes) => {
+                    let changed = changes.has_changed().unwrap_or(false);
+                    changes.mark_unchanged();
+                    changed
+                }
+                None => false,
+            };
+
             // Auto-refresh data periodically - frequent refresh for active views
-            let refresh_interval = match self.state.mode {
-                AppMode::SessionList => Duration::from_secs(3), // Refresh every 3 seconds for session list to catch status changes
-                AppMode::SessionDetail => Duration::from_secs(3), // Refresh every 3 seconds for session detail
+            // when change notifications are unavailable
+            let refresh_interval = match (&self.state.mode, &changes) {
+                (_, Some(_)) => Duration::from_secs(30), // Writes trigger a refresh; this is a safety net
+                (AppMode::SessionList, None) => Duration::from_secs(3), // Refresh every 3 seconds for session list to catch status changes
+                (AppMode::SessionDetail, None) => Duration::from_secs(3), // Refresh every 3 seconds for session detail
                 _ => Duration::from_secs(30), // Normal 30 second refresh for other views
             };
 
-            if self.state.last_updated.elapsed() > refresh_interval {
+            if changed || self.state.last_updated.elapsed() > refresh_interval {
                 self.refresh_current_view().await?;
             }
         }
@@ -228,9 +269,26 @@ impl App {
         let app_event = match event {
             Event::Key(key) => AppEvent::Input(key),
             Event::Resize(w, h) => AppEvent::Resize(w, h),
+            Event::Mouse(mouse) => AppEvent::Mouse(mouse),
             _ => return Ok(true),
         };
 
+        if let AppEvent::Mouse(mouse) = app_event {
+            self.handle_mouse(mouse).await?;
+            return Ok(true);
+        }
+
+        // An open filter bar or panel takes all key presses, including global shortcuts
+        if let AppEvent::Input(key) = &app_event {
+            if self.state.mode == AppMode::SessionList
+                && (self.session_list.is_editing_filter()
+                    || self.session_list.is_filter_panel_open())
+            {
+                self.session_list.handle_key(*key).await?;
+                return Ok(true);
+            }
+        }
+
         // Get user actions from event handler
         let mut actions = self.event_handler.handle_event(
             &app_event,
@@ -256,6 +314,23 @@ impl App {
         Ok(true)
     }
 
+    async fn handle_mouse(&mut self, mouse: MouseEvent) -> Result<()> {
+        if self.state.show_help || self.state.error_dialog.is_some() {
+            return Ok(());
+        }
+        match self.state.mode {
+            AppMode::SessionList => {
+                if let Some(session_id) = self.session_list.handle_mouse(mouse).await? {
+                    self.dispatch_action(UserAction::SelectSession(session_id))
+                        .await?;
+                }
+            }
+            AppMode::SessionDetail => self.session_detail.handle_mouse(mouse),
+            AppMode::Help => {}
+        }
+        Ok(())
+    }
+
     async fn handle_widget_specific_keys(&mut self, key: KeyEvent) -> Result<Vec<UserAction>> {
         // Handle widget-specific keys that require context (e.g., selected session)
         if self.state.mode == AppMode::SessionList {
@@ -507,7 +582,8 @@ impl App {
     fn render_footer(&self, f: &mut Frame, area: Rect) {
         let key_hints = match self.state.mode {
             AppMode::SessionList => {
-                "↑/↓: Navigate | Enter: View | a: Analytics | ?: Help | q: Quit".to_string()
+                "↑/↓: Navigate | Enter: View | /: Filter | f: Filter Panel | v: Views | a: Analytics | ?: Help | q: Quit"
+                    .to_string()
             }
             AppMode::SessionDetail => {
                 if self.session_detail.state.show_analytics
@@ -515,8 +591,14 @@ impl App {
                 {
                     "↑/↓: Scroll | ←/→: Switch Panel | a: Toggle Analytics | Esc: Back | q: Quit"
                         .to_string()
+                } else if self.session_detail.state.replay.is_some() {
+                    "Space: Next | Backspace: Previous | Enter: Play/Pause | +/-: Speed | p: Stop Replay | Esc: Back"
+                        .to_string()
+                } else if !self.session_detail.state.related_sessions.is_empty() {
+                    "↑/↓: Scroll | Enter: Fold | c: Collapse Tools | d: Tool Details | t: Thinking | y/Y: Copy | L: Link | a: Analytics | 1-3: Related | Esc: Back"
+                        .to_string()
                 } else {
-                    "↑/↓: Scroll | d: Tool Details | t: Thinking | a: Analytics | Esc: Back | q: Quit"
+                    "↑/↓: Scroll | Enter: Fold | c: Collapse Tools | d: Tool Details | t: Thinking | y/Y: Copy | L: Link | a: Analytics | Esc: Back | q: Quit"
                         .to_string()
                 }
             }
@@ -554,20 +636,42 @@ impl App {
             Line::from("  Enter          - View session details"),
             Line::from("  s              - Change sort field"),
             Line::from("  o              - Toggle sort order"),
+            Line::from("  /              - Filter (e.g. provider:claude tool:Bash(failed))"),
+            Line::from("  f              - Tick providers, projects and topics to show"),
+            Line::from("  v              - Switch saved view (retrochat views save ...)"),
             Line::from("  a              - Start analytics analysis"),
             Line::from(""),
             Line::from("Session Detail:"),
             Line::from("  ↑/↓            - Scroll messages"),
             Line::from("  Page Up/Down   - Fast scroll"),
             Line::from("  Home/End       - Jump to start/end"),
+            Line::from("  Enter          - Fold/unfold the message at the top"),
+            Line::from("  c              - Collapse/expand all tool results"),
             Line::from("  d              - Toggle tool details"),
             Line::from("  a              - Toggle analytics view"),
             Line::from("  t              - Toggle thinking messages"),
+            Line::from("  p              - Replay the session message by message"),
+            Line::from("  y              - Copy the message at the top to the clipboard"),
+            Line::from("  Y              - Copy the whole session as markdown"),
+            Line::from("  L              - Copy a retrochat:// link to the message at the top"),
+            Line::from("  1-3            - Open a related session"),
+            Line::from(""),
+            Line::from("Replay:"),
+            Line::from("  Space          - Show the next message"),
+            Line::from("  Backspace      - Hide the last message"),
+            Line::from("  Enter          - Play/pause at the session's own pace"),
+            Line::from("  + / -          - Faster / slower"),
+            Line::from("  p              - Stop replaying"),
             Line::from(""),
             Line::from("Analytics View:"),
             Line::from("  ←/→            - Switch between panels"),
             Line::from("  ↑/↓            - Scroll current panel"),
             Line::from("  a              - Return to messages"),
+            Line::from(""),
+            Line::from("Mouse (unless tui.mouse is false):"),
+            Line::from("  Click          - Select a session; click again to open it"),
+            Line::from("  Click          - Fold/unfold a message by its first line"),
+            Line::from("  Wheel          - Scroll the list, messages or analytics"),
         ];
 
         let dialog = Dialog::new(DialogType::Help, content).size(80, 70);
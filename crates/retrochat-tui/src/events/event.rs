@@ -1,10 +1,12 @@
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyEvent, MouseEvent};
 
 /// Low-level events from the terminal
 #[derive(Debug, Clone)]
 pub enum AppEvent {
     /// Keyboard input
     Input(KeyEvent),
+    /// Clicks and wheel scrolling, when mouse capture is on
+    Mouse(MouseEvent),
     /// Periodic tick for updates
     Tick,
     /// Terminal resize
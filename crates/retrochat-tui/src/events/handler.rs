@@ -24,7 +24,8 @@ impl EventHandler {
     ) -> Vec<UserAction> {
         match event {
             AppEvent::Input(key) => self.handle_key_event(*key, mode, show_help, has_error_dialog),
-            AppEvent::Tick => vec![],
+            // Clicks depend on what was drawn where, so the app hands them to the widgets
+            AppEvent::Tick | AppEvent::Mouse(_) => vec![],
             AppEvent::Resize(_, _) => vec![UserAction::RefreshCurrentView],
         }
     }
@@ -13,6 +13,7 @@ pub use session_list::SessionListWidget;
 
 use anyhow::{Context, Result};
 use retrochat_core::database::DatabaseManager;
+use retrochat_core::services::ImportService;
 
 /// Main entry point for TUI mode
 /// This function handles all the terminal setup, TUI execution, and teardown
@@ -42,6 +43,14 @@ pub async fn run_tui() -> Result<()> {
             .with_context(|| "Failed to initialize database")?,
     );
 
+    // Clean up after a sync that was interrupted, so no half-written sessions show
+    if let Err(e) = ImportService::new(db_manager.clone())
+        .resume_interrupted_imports()
+        .await
+    {
+        tracing::warn!(error = %e, "Failed to resume interrupted imports");
+    }
+
     // Check if we're in a proper terminal environment
     if !atty::is(atty::Stream::Stdout) {
         return Err(anyhow::anyhow!(
@@ -59,12 +68,25 @@ pub async fn run_tui() -> Result<()> {
         ));
     }
 
+    // Mouse capture can be turned off to keep the terminal's own text selection
+    let mouse = match retrochat_core::config::Config::load() {
+        Ok(config) => config.tui.mouse_enabled(),
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to load config, capturing the mouse");
+            true
+        }
+    };
+
     // Setup terminal with proper error handling
     enable_raw_mode().map_err(|e| anyhow::anyhow!("Failed to enable raw mode: {e}"))?;
 
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)
+    execute!(stdout, EnterAlternateScreen)
         .map_err(|e| anyhow::anyhow!("Failed to setup terminal: {e}"))?;
+    if mouse {
+        execute!(stdout, EnableMouseCapture)
+            .map_err(|e| anyhow::anyhow!("Failed to enable mouse capture: {e}"))?;
+    }
 
     let backend = CrosstermBackend::new(stdout);
     let mut terminal =
@@ -77,11 +99,10 @@ pub async fn run_tui() -> Result<()> {
 
     // Restore terminal with proper error handling
     let _ = disable_raw_mode();
-    let _ = execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    );
+    let _ = execute!(terminal.backend_mut(), LeaveAlternateScreen);
+    if mouse {
+        let _ = execute!(terminal.backend_mut(), DisableMouseCapture);
+    }
     let _ = terminal.show_cursor();
 
     result.map_err(|e| anyhow::anyhow!("TUI runtime error: {e}"))
@@ -1,5 +1,5 @@
 use anyhow::Result;
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
@@ -7,24 +7,71 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, Wrap},
     Frame,
 };
+use std::cell::RefCell;
 use std::sync::Arc;
+use std::time::Instant;
+use tokio::task::JoinHandle;
 
-use retrochat_core::database::DatabaseManager;
+use retrochat_core::database::{DatabaseManager, MessageRepository, ToolOperationRepository};
 use retrochat_core::models::{Message, MessageRole};
-use retrochat_core::services::{MessageGroup, QueryService, SessionDetailRequest};
+use retrochat_core::services::{
+    render_markdown_transcript, MessageGroup, QueryService, SessionDetailRequest,
+    SessionDetailResponse,
+};
+use retrochat_core::utils::clipboard;
+use retrochat_core::utils::deep_link::DeepLink;
+use retrochat_core::utils::locale::Language;
 
 use super::state::session_detail_state::AnalyticsPanelFocus;
-use super::state::SessionDetailState;
+use super::state::{ReplayState, SessionDetailState};
 use super::tool_display::{ToolDisplayConfig, ToolDisplayFormatter};
+use super::utils::layout::inner_row;
 use super::utils::text::wrap_text;
 
+/// Related sessions shown in the footer, each opened with its number key
+const RELATED_SESSION_LIMIT: usize = 3;
+
 /// Reserved width for labels and values next to bar charts (e.g., "  Tokens", " 12345 total")
 const BAR_CHART_LABEL_WIDTH: u16 = 20;
 
+/// Languages listed under the file changes in the analytics panel
+const MAX_LANGUAGES_SHOWN: usize = 5;
+
+/// Messages loaded per page; later pages load as the view nears their end
+const MESSAGE_PAGE_SIZE: usize = 500;
+
+/// Start fetching the next page when fewer lines than this are left below the view
+const PREFETCH_MARGIN_LINES: usize = 300;
+
+/// Lines scrolled per mouse wheel notch
+const WHEEL_SCROLL_LINES: usize = 3;
+
+/// What the message lines were laid out for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct LineCacheKey {
+    width: usize,
+    layout_generation: u64,
+    replay_shown: Option<usize>,
+}
+
+/// Message lines wrapped once per layout, so a frame only copies out the
+/// lines on screen
+struct LineCache {
+    key: LineCacheKey,
+    lines: Vec<Line<'static>>,
+    /// First line of each visible message group
+    group_starts: Vec<usize>,
+}
+
 pub struct SessionDetailWidget {
     pub state: SessionDetailState,
+    db_manager: Arc<DatabaseManager>,
     query_service: QueryService,
     tool_formatter: ToolDisplayFormatter,
+    line_cache: RefCell<Option<LineCache>>,
+    next_page: Option<JoinHandle<Result<SessionDetailResponse>>>,
+    /// Where the messages were last drawn, for mouse clicks
+    messages_area: Rect,
 }
 
 impl SessionDetailWidget {
@@ -32,7 +79,11 @@ impl SessionDetailWidget {
         Self {
             state: SessionDetailState::new(),
             query_service: QueryService::with_database(db_manager.clone()),
+            db_manager,
             tool_formatter: ToolDisplayFormatter::new(),
+            line_cache: RefCell::new(None),
+            next_page: None,
+            messages_area: Rect::default(),
         }
     }
 
@@ -47,11 +98,15 @@ impl SessionDetailWidget {
     pub async fn refresh(&mut self) -> Result<()> {
         if let Some(session_id) = &self.state.session_id.clone() {
             self.state.loading = true;
+            if let Some(next_page) = self.next_page.take() {
+                next_page.abort();
+            }
 
+            // Reload the pages already loaded, or the first one
             let request = SessionDetailRequest {
                 session_id: session_id.clone(),
                 include_content: Some(true),
-                message_limit: None,
+                message_limit: Some(self.state.messages.len().max(MESSAGE_PAGE_SIZE) as i32),
                 message_offset: None,
             };
 
@@ -59,6 +114,8 @@ impl SessionDetailWidget {
                 Ok(response) => {
                     self.state
                         .update_session(response.session, response.messages);
+                    self.state.total_messages = response.total_message_count as usize;
+                    self.state.attachments = response.attachments;
                     self.update_scroll_state();
                 }
                 Err(e) => {
@@ -77,6 +134,18 @@ impl SessionDetailWidget {
                 }
             }
 
+            match self
+                .query_service
+                .get_related_sessions(session_id, RELATED_SESSION_LIMIT)
+                .await
+            {
+                Ok(related) => self.state.related_sessions = related,
+                Err(e) => {
+                    tracing::debug!(error = %e, "Failed to load related sessions");
+                    self.state.related_sessions.clear();
+                }
+            }
+
             self.state.loading = false;
         }
         Ok(())
@@ -85,6 +154,7 @@ impl SessionDetailWidget {
     pub async fn handle_key(&mut self, key: KeyEvent) -> Result<()> {
         // Check if we should scroll analytics instead of messages
         let scroll_analytics = self.state.show_analytics && self.state.analytics.is_some();
+        self.state.notice = None;
 
         match key.code {
             KeyCode::Up => {
@@ -151,10 +221,15 @@ impl SessionDetailWidget {
                     self.update_scroll_state();
                 }
             }
-            KeyCode::Left | KeyCode::Right => {
+            KeyCode::Left | KeyCode::Right if scroll_analytics => {
                 // Left/Right: Switch focus between quantitative and qualitative panels
-                if scroll_analytics {
-                    self.state.toggle_analytics_panel_focus();
+                self.state.toggle_analytics_panel_focus();
+            }
+            KeyCode::Char(c @ '1'..='3') => {
+                // 1-3: Open a related session
+                let index = c as usize - '1' as usize;
+                if let Some(session_id) = self.state.related_session_id(index) {
+                    self.set_session_id(Some(session_id)).await?;
                 }
             }
             KeyCode::Char('d') => {
@@ -165,6 +240,57 @@ impl SessionDetailWidget {
                 // A: Toggle analytics panel
                 self.state.toggle_analytics();
             }
+            KeyCode::Char('p') => {
+                // P: Start or stop replaying the session
+                self.toggle_replay();
+            }
+            KeyCode::Char(' ') if self.state.replay.is_some() => {
+                self.replay_forward();
+            }
+            KeyCode::Backspace if self.state.replay.is_some() => {
+                if let Some(replay) = self.state.replay.as_mut() {
+                    replay.step_back(Instant::now());
+                }
+                self.follow_replay();
+            }
+            KeyCode::Enter => {
+                if let Some(replay) = self.state.replay.as_mut() {
+                    // Enter: Pause or resume autoplay
+                    replay.autoplay = !replay.autoplay;
+                    replay.last_step = Instant::now();
+                } else if let Some(group) = self.group_at_line(self.state.current_scroll) {
+                    // Enter: Fold or unfold the message at the top of the view
+                    self.state.toggle_fold(group_key(&group).0);
+                    self.follow_replay();
+                }
+            }
+            KeyCode::Char('c') => {
+                // C: Collapse or expand all tool results
+                self.state.toggle_collapse_tool_results();
+                self.follow_replay();
+            }
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                if let Some(replay) = self.state.replay.as_mut() {
+                    replay.faster();
+                }
+            }
+            KeyCode::Char('-') => {
+                if let Some(replay) = self.state.replay.as_mut() {
+                    replay.slower();
+                }
+            }
+            KeyCode::Char('y') if !scroll_analytics => {
+                // y: Copy the message at the top of the view
+                self.yank_message();
+            }
+            KeyCode::Char('Y') => {
+                // Y: Copy the whole session as markdown
+                self.yank_session().await;
+            }
+            KeyCode::Char('L') => {
+                // L: Copy a link to the message at the top of the view
+                self.yank_link();
+            }
             KeyCode::Char('t') => {
                 // T: Toggle thinking messages visibility
                 self.state.toggle_thinking();
@@ -180,12 +306,264 @@ impl SessionDetailWidget {
         Ok(())
     }
 
+    /// Scroll with the wheel, and fold or unfold a message by clicking its
+    /// first line
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) {
+        let scroll_analytics = self.state.show_analytics && self.state.analytics.is_some();
+        self.state.notice = None;
+
+        match mouse.kind {
+            MouseEventKind::ScrollUp if scroll_analytics => {
+                self.state.focused_panel_page_up(WHEEL_SCROLL_LINES);
+                self.update_dual_panel_scroll_state();
+            }
+            MouseEventKind::ScrollDown if scroll_analytics => {
+                let (quant_max, qual_max) = self.get_dual_panel_max_scroll();
+                self.state
+                    .focused_panel_page_down(WHEEL_SCROLL_LINES, quant_max, qual_max);
+                self.update_dual_panel_scroll_state();
+            }
+            MouseEventKind::ScrollUp => {
+                self.state.scroll_page_up(WHEEL_SCROLL_LINES);
+                self.update_scroll_state();
+            }
+            MouseEventKind::ScrollDown => {
+                let max_scroll = self.get_max_scroll();
+                self.state.scroll_page_down(WHEEL_SCROLL_LINES, max_scroll);
+                self.update_scroll_state();
+            }
+            MouseEventKind::Down(MouseButton::Left) if !scroll_analytics => {
+                let Some(line) = inner_row(self.messages_area, mouse.column, mouse.row) else {
+                    return;
+                };
+                let line = self.state.current_scroll + line;
+                let clicked =
+                    self.with_line_cache(|cache| cache.group_starts.binary_search(&line).ok());
+                if let Some(group) =
+                    clicked.and_then(|index| self.visible_message_groups().into_iter().nth(index))
+                {
+                    self.state.toggle_fold(group_key(&group).0);
+                    self.follow_replay();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn toggle_replay(&mut self) {
+        if self.state.replay.take().is_none() && !self.state.messages.is_empty() {
+            self.state.replay = Some(ReplayState::new(Instant::now()));
+            self.state.show_analytics = false;
+        }
+        self.follow_replay();
+    }
+
+    fn replay_forward(&mut self) {
+        let total = self.message_groups().len();
+        if let Some(replay) = self.state.replay.as_mut() {
+            replay.step_forward(total, Instant::now());
+        }
+        self.follow_replay();
+    }
+
+    /// Keep the newest replayed message in view
+    fn follow_replay(&mut self) {
+        let max_scroll = self.get_max_scroll();
+        if self.state.replay.is_some() {
+            self.state.scroll_to_bottom(max_scroll);
+        } else {
+            self.state.current_scroll = self.state.current_scroll.min(max_scroll);
+        }
+        self.update_scroll_state();
+    }
+
+    /// Take in prefetched messages and advance an autoplaying replay once
+    /// the next message is due
+    pub async fn tick(&mut self) {
+        self.load_next_page().await;
+
+        let Some(replay) = &self.state.replay else {
+            return;
+        };
+        if !replay.autoplay {
+            return;
+        }
+        let groups = self.message_groups();
+        let (Some(previous), Some(next)) = (groups.get(replay.shown - 1), groups.get(replay.shown))
+        else {
+            // Wait for the next page rather than stopping at the end of this one
+            if !self.state.has_more_messages() {
+                if let Some(replay) = self.state.replay.as_mut() {
+                    replay.autoplay = false;
+                }
+            }
+            return;
+        };
+        let gap = group_timestamp(next) - group_timestamp(previous);
+        if replay.last_step.elapsed() >= replay.delay_for(gap) {
+            self.replay_forward();
+        }
+    }
+
+    /// Fetch the next page of messages in the background once the view nears
+    /// the end of those loaded, and append it when it arrives
+    async fn load_next_page(&mut self) {
+        if self
+            .next_page
+            .as_ref()
+            .is_some_and(|page| page.is_finished())
+        {
+            let Some(page) = self.next_page.take() else {
+                return;
+            };
+            match page.await {
+                Ok(Ok(response))
+                    if self.state.session_id.as_deref()
+                        == Some(response.session.id.to_string().as_str()) =>
+                {
+                    self.state.total_messages = response.total_message_count as usize;
+                    self.state
+                        .append_messages(response.messages, response.attachments);
+                    self.update_scroll_state();
+                }
+                // The user moved to another session meanwhile
+                Ok(Ok(_)) => {}
+                Ok(Err(e)) => tracing::error!(error = %e, "Failed to load more messages"),
+                Err(e) => tracing::error!(error = %e, "Message page task failed"),
+            }
+            return;
+        }
+        if self.next_page.is_some() || !self.state.has_more_messages() {
+            return;
+        }
+        let Some(session_id) = self.state.session_id.clone() else {
+            return;
+        };
+        let lines_below = self
+            .get_total_lines()
+            .saturating_sub(self.state.current_scroll + self.state.viewport_height);
+        if lines_below > PREFETCH_MARGIN_LINES {
+            return;
+        }
+
+        let query_service = QueryService::with_database(self.db_manager.clone());
+        let request = SessionDetailRequest {
+            session_id,
+            include_content: Some(true),
+            message_limit: Some(MESSAGE_PAGE_SIZE as i32),
+            message_offset: Some(self.state.messages.len() as i32),
+        };
+        self.next_page = Some(tokio::spawn(async move {
+            query_service.get_session_detail(request).await
+        }));
+    }
+
+    /// Replay position, session time elapsed so far, speed and play state
+    fn replay_status(&self, replay: &ReplayState) -> String {
+        let groups = self.message_groups();
+        let elapsed = match (groups.first(), groups.get(replay.shown.saturating_sub(1))) {
+            (Some(first), Some(current)) => {
+                (group_timestamp(current) - group_timestamp(first)).num_seconds()
+            }
+            _ => 0,
+        };
+        format!(
+            "Replay {}/{} · +{:02}:{:02}:{:02} · {}x · {}",
+            replay.shown.min(groups.len()),
+            groups.len(),
+            elapsed / 3600,
+            elapsed / 60 % 60,
+            elapsed % 60,
+            replay.speed(),
+            if replay.autoplay { "playing" } else { "paused" }
+        )
+    }
+
+    fn yank_message(&mut self) {
+        let Some(group) = self.group_at_line(self.state.current_scroll) else {
+            return;
+        };
+        let text = match group {
+            MessageGroup::Single(message) => message.content,
+            MessageGroup::ToolPair {
+                tool_use_message,
+                tool_result_message,
+            } => format!(
+                "{}\n\n{}",
+                tool_use_message.content, tool_result_message.content
+            ),
+        };
+        self.copy_to_clipboard(&text, "Message");
+    }
+
+    fn yank_link(&mut self) {
+        let Some(group) = self.group_at_line(self.state.current_scroll) else {
+            return;
+        };
+        let message = match group {
+            MessageGroup::Single(message) => message,
+            MessageGroup::ToolPair {
+                tool_use_message, ..
+            } => tool_use_message,
+        };
+        let link = DeepLink::message(message.session_id, message.sequence_number).to_string();
+        self.copy_to_clipboard(&link, "Link");
+    }
+
+    async fn yank_session(&mut self) {
+        let Some(session) = self.state.session.clone() else {
+            return;
+        };
+        let tool_operations = match ToolOperationRepository::new(&self.db_manager)
+            .get_by_session(&session.id)
+            .await
+        {
+            Ok(operations) => operations,
+            Err(e) => {
+                self.state.notice = Some(format!("Copy failed: {e}"));
+                return;
+            }
+        };
+        // Copy the whole session, including pages not loaded yet
+        let all_messages;
+        let messages = if self.state.has_more_messages() {
+            all_messages = match MessageRepository::new(&self.db_manager)
+                .get_by_session(&session.id)
+                .await
+            {
+                Ok(messages) => messages,
+                Err(e) => {
+                    self.state.notice = Some(format!("Copy failed: {e}"));
+                    return;
+                }
+            };
+            &all_messages
+        } else {
+            &self.state.messages
+        };
+        let markdown = render_markdown_transcript(&session, messages, &tool_operations);
+        self.copy_to_clipboard(&markdown, "Session");
+    }
+
+    fn copy_to_clipboard(&mut self, text: &str, what: &str) {
+        self.state.notice = Some(match clipboard::copy(text) {
+            Ok(_) => format!("{what} copied to the clipboard"),
+            Err(e) => format!("Copy failed: {e}"),
+        });
+    }
+
     pub fn render(&mut self, f: &mut Frame, area: Rect) {
+        let related_height = if self.state.related_sessions.is_empty() {
+            0
+        } else {
+            self.state.related_sessions.len() as u16 + 2
+        };
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(4), // Session header
-                Constraint::Min(0),    // Main content
+                Constraint::Length(4),              // Session header
+                Constraint::Min(0),                 // Main content
+                Constraint::Length(related_height), // Related sessions
             ])
             .split(area);
 
@@ -200,6 +578,47 @@ impl SessionDetailWidget {
             // Show messages by default
             self.render_messages(f, chunks[1]);
         }
+
+        if related_height > 0 {
+            self.render_related_sessions(f, chunks[2]);
+        }
+    }
+
+    fn render_related_sessions(&self, f: &mut Frame, area: Rect) {
+        let lines: Vec<Line> = self
+            .state
+            .related_sessions
+            .iter()
+            .enumerate()
+            .map(|(i, related)| {
+                Line::from(vec![
+                    Span::styled(
+                        format!("{}: ", i + 1),
+                        Style::default()
+                            .fg(Color::Yellow)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(related.title.as_deref().unwrap_or("Untitled").to_string()),
+                    Span::styled(
+                        format!(
+                            "  {} | {} | {:.0}% similar",
+                            related.project.as_deref().unwrap_or("No Project"),
+                            related.start_time.get(..10).unwrap_or(&related.start_time),
+                            related.score * 100.0
+                        ),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ])
+            })
+            .collect();
+
+        let panel = Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Related Sessions"),
+        );
+
+        f.render_widget(panel, area);
     }
 
     fn render_session_header(&self, f: &mut Frame, area: Rect) {
@@ -227,7 +646,7 @@ impl SessionDetailWidget {
                 project_str,
                 session.message_count,
                 session.token_count.unwrap_or(0),
-                &session.start_time.format("%Y-%m-%d %H:%M").to_string(),
+                Language::current().format_datetime(&session.start_time),
                 session.state,
                 analytics_str,
             )
@@ -268,17 +687,30 @@ impl SessionDetailWidget {
 
         // Store viewport height for scroll calculations
         self.state.viewport_height = available_height;
-
-        let message_lines = self.calculate_message_lines(area.width.saturating_sub(4) as usize);
-
-        let visible_lines: Vec<Line> = message_lines
-            .into_iter()
-            .skip(self.state.current_scroll)
-            .take(available_height)
-            .collect();
-
+        self.state.message_width = area.width.saturating_sub(4) as usize;
+        self.messages_area = area;
+
+        // Only the lines on screen are copied out of the layout
+        let visible_lines: Vec<Line> = self.with_line_cache(|cache| {
+            let start = self.state.current_scroll.min(cache.lines.len());
+            let end = (start + available_height).min(cache.lines.len());
+            cache.lines[start..end].to_vec()
+        });
+
+        let mut title = match &self.state.replay {
+            Some(replay) => self.replay_status(replay),
+            None if self.state.has_more_messages() => format!(
+                "Messages ({} of {} loaded)",
+                self.state.messages.len(),
+                self.state.total_messages
+            ),
+            None => "Messages".to_string(),
+        };
+        if let Some(notice) = &self.state.notice {
+            title.push_str(&format!(" — {notice}"));
+        }
         let messages_block = Paragraph::new(visible_lines)
-            .block(Block::default().borders(Borders::ALL).title("Messages"))
+            .block(Block::default().borders(Borders::ALL).title(title))
             .wrap(Wrap { trim: true })
             .scroll((0, 0));
 
@@ -302,9 +734,8 @@ impl SessionDetailWidget {
         }
     }
 
-    fn calculate_message_lines(&self, width: usize) -> Vec<Line<'_>> {
-        let mut lines = Vec::new();
-
+    /// Visible messages, with tool uses paired with their results
+    fn message_groups(&self) -> Vec<MessageGroup> {
         // Filter out thinking messages if hidden
         let messages = if self.state.show_thinking {
             self.state.messages.clone()
@@ -318,40 +749,141 @@ impl SessionDetailWidget {
         };
 
         // Pair tool_use and tool_result messages
-        let message_groups = MessageGroup::pair_tool_messages(messages);
+        MessageGroup::pair_tool_messages(messages)
+    }
+
+    /// Message groups on screen; a replay shows only those reached so far
+    fn visible_message_groups(&self) -> Vec<MessageGroup> {
+        let mut groups = self.message_groups();
+        if let Some(replay) = &self.state.replay {
+            groups.truncate(replay.shown);
+        }
+        groups
+    }
+
+    /// Run `f` on the message lines for the current layout, laying them out
+    /// again only when the width or what is shown has changed
+    fn with_line_cache<R>(&self, f: impl FnOnce(&LineCache) -> R) -> R {
+        let key = LineCacheKey {
+            width: self.state.message_width,
+            layout_generation: self.state.layout_generation,
+            replay_shown: self.state.replay.as_ref().map(|replay| replay.shown),
+        };
+        let mut cache = self.line_cache.borrow_mut();
+        if cache.as_ref().is_some_and(|cache| cache.key != key) {
+            *cache = None;
+        }
+        f(cache.get_or_insert_with(|| self.layout_message_lines(key)))
+    }
 
-        for (group_idx, group) in message_groups.iter().enumerate() {
+    fn layout_message_lines(&self, key: LineCacheKey) -> LineCache {
+        let mut lines = Vec::new();
+        let mut group_starts = Vec::new();
+
+        for (group_idx, group) in self.visible_message_groups().iter().enumerate() {
             // Add separator between groups (except for first)
             if group_idx > 0 {
                 lines.push(Line::from(vec![Span::styled(
-                    "─".repeat(width.min(80)),
+                    "─".repeat(key.width.min(80)),
                     Style::default().fg(Color::DarkGray),
                 )]));
             }
+            group_starts.push(lines.len());
+            self.render_group(group, key.width, &mut lines);
+        }
 
-            match group {
-                MessageGroup::Single(message) => {
-                    self.render_message_block(message, width, &mut lines);
-                }
-                MessageGroup::ToolPair {
-                    tool_use_message,
-                    tool_result_message,
-                } => {
-                    self.render_tool_pair_block(
-                        tool_use_message,
-                        tool_result_message,
-                        width,
-                        &mut lines,
-                    );
-                }
+        LineCache {
+            key,
+            lines,
+            group_starts,
+        }
+    }
+
+    fn render_group(&self, group: &MessageGroup, width: usize, lines: &mut Vec<Line<'static>>) {
+        let (id, is_tool) = group_key(group);
+        if self.state.is_folded(id, is_tool) {
+            self.render_folded_group(group, width, lines);
+            return;
+        }
+
+        match group {
+            MessageGroup::Single(message) => {
+                self.render_message_block(message, width, lines);
+            }
+            MessageGroup::ToolPair {
+                tool_use_message,
+                tool_result_message,
+            } => {
+                self.render_tool_pair_block(tool_use_message, tool_result_message, width, lines);
             }
         }
+    }
+
+    /// One line per folded group: time, role, first line and how much is hidden
+    fn render_folded_group(
+        &self,
+        group: &MessageGroup,
+        width: usize,
+        lines: &mut Vec<Line<'static>>,
+    ) {
+        let (message, extra_lines, label) = match group {
+            MessageGroup::Single(message) => (message, 0, format!("{:?}", message.role)),
+            MessageGroup::ToolPair {
+                tool_use_message,
+                tool_result_message,
+            } => (
+                tool_use_message,
+                tool_result_message.content.lines().count(),
+                format!("{:?} → Tool Execution", tool_use_message.role),
+            ),
+        };
+        let line_count = message.content.lines().count() + extra_lines;
+        let first_line: String = message
+            .content
+            .lines()
+            .find(|line| !line.trim().is_empty())
+            .unwrap_or("")
+            .chars()
+            .take(width.saturating_sub(40))
+            .collect();
+
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("[{}] {label} ▸ ", message.timestamp.format("%H:%M:%S")),
+                Style::default()
+                    .fg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(first_line, Style::default().fg(Color::Gray)),
+            Span::styled(
+                format!("  ({line_count} lines folded)"),
+                Style::default()
+                    .fg(Color::DarkGray)
+                    .add_modifier(Modifier::ITALIC),
+            ),
+        ]));
+        lines.push(Line::from(""));
+    }
 
-        lines
+    /// The message group shown on `line` of the message view
+    fn group_at_line(&self, line: usize) -> Option<MessageGroup> {
+        // A separator line belongs to the group above it
+        let index = self.with_line_cache(|cache| {
+            cache
+                .group_starts
+                .partition_point(|&start| start <= line)
+                .checked_sub(1)
+        })?;
+        self.visible_message_groups().into_iter().nth(index)
     }
 
     /// Renders a single message block
-    fn render_message_block(&self, message: &Message, width: usize, lines: &mut Vec<Line<'_>>) {
+    fn render_message_block(
+        &self,
+        message: &Message,
+        width: usize,
+        lines: &mut Vec<Line<'static>>,
+    ) {
         // Check message types
         let is_thinking = message.is_thinking();
         let is_slash_command = message.is_slash_command();
@@ -443,8 +975,32 @@ impl SessionDetailWidget {
             }
         }
 
-        // Token count if available
-        if let Some(tokens) = message.token_count {
+        let attachments: Vec<String> = self
+            .state
+            .attachments
+            .iter()
+            .filter(|a| a.message_id == message.id)
+            .map(|a| match a.file_path {
+                Some(_) => format!("{} (saved)", a.label()),
+                None => a.label(),
+            })
+            .collect();
+        if !attachments.is_empty() {
+            lines.push(Line::from(vec![Span::styled(
+                format!("  [Attachments: {}]", attachments.join("; ")),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::ITALIC),
+            )]));
+        }
+
+        // Token count if available; estimates are marked with ~
+        let tokens = match (message.token_count, message.estimated_tokens) {
+            (Some(tokens), _) => Some(tokens.to_string()),
+            (None, Some(estimate)) => Some(format!("~{estimate}")),
+            (None, None) => None,
+        };
+        if let Some(tokens) = tokens {
             lines.push(Line::from(vec![Span::styled(
                 format!("  [Tokens: {tokens}]"),
                 Style::default().fg(Color::Cyan).add_modifier(Modifier::DIM),
@@ -461,7 +1017,7 @@ impl SessionDetailWidget {
         tool_use_msg: &Message,
         tool_result_msg: &Message,
         width: usize,
-        lines: &mut Vec<Line<'_>>,
+        lines: &mut Vec<Line<'static>>,
     ) {
         // Render the tool use message header
         let role_style = Style::default()
@@ -497,6 +1053,7 @@ impl SessionDetailWidget {
                 width: width.saturating_sub(4),
                 show_details: self.state.show_tool_details,
                 max_output_lines: 10,
+                collapse_results: self.state.collapse_tool_results,
             };
 
             // Format and add tool display lines
@@ -515,7 +1072,7 @@ impl SessionDetailWidget {
             // Indent tool lines
             for tool_line in tool_lines {
                 let indented_spans: Vec<Span> = std::iter::once(Span::raw("  │  "))
-                    .chain(tool_line.spans.into_iter())
+                    .chain(tool_line.spans)
                     .collect();
                 lines.push(Line::from(indented_spans));
             }
@@ -523,14 +1080,14 @@ impl SessionDetailWidget {
 
         // Token counts from both messages
         if let (Some(use_tokens), Some(result_tokens)) =
-            (tool_use_msg.token_count, tool_result_msg.token_count)
+            (tool_use_msg.tokens(), tool_result_msg.tokens())
         {
             let total = use_tokens + result_tokens;
             lines.push(Line::from(vec![Span::styled(
                 format!("  [Tokens: {total} (use: {use_tokens}, result: {result_tokens})]"),
                 Style::default().fg(Color::Cyan).add_modifier(Modifier::DIM),
             )]));
-        } else if let Some(tokens) = tool_use_msg.token_count.or(tool_result_msg.token_count) {
+        } else if let Some(tokens) = tool_use_msg.tokens().or(tool_result_msg.tokens()) {
             lines.push(Line::from(vec![Span::styled(
                 format!("  [Tokens: {tokens}]"),
                 Style::default().fg(Color::Cyan).add_modifier(Modifier::DIM),
@@ -542,7 +1099,7 @@ impl SessionDetailWidget {
     }
 
     fn get_total_lines(&self) -> usize {
-        self.calculate_message_lines(80).len() // Use standard width for calculation
+        self.with_line_cache(|cache| cache.lines.len())
     }
 
     pub fn get_max_scroll(&self) -> usize {
@@ -771,6 +1328,26 @@ impl SessionDetailWidget {
                     Style::default().fg(Color::White),
                 ),
             ]));
+            for language in file_changes.languages.iter().take(MAX_LANGUAGES_SHOWN) {
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        format!("    {:<14}", language.language),
+                        Style::default().fg(Color::White),
+                    ),
+                    Span::styled(
+                        format!("+{} ", language.lines_added),
+                        Style::default().fg(Color::Green),
+                    ),
+                    Span::styled(
+                        format!("-{} ", language.lines_removed),
+                        Style::default().fg(Color::Red),
+                    ),
+                    Span::styled(
+                        format!("({} files)", language.files_touched),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ]));
+            }
             lines.push(Line::from(""));
 
             // Tool usage
@@ -813,14 +1390,46 @@ impl SessionDetailWidget {
             lines.push(Line::from(""));
 
             // Time metrics
-            lines.push(Line::from(vec![Span::styled(
-                format!(
+            let duration = match metrics.time_metrics.active_time_minutes {
+                Some(active) => format!(
+                    "  Duration: {:.1} min active / {:.1} min wall",
+                    active, metrics.time_metrics.total_session_time_minutes
+                ),
+                None => format!(
                     "  Duration: {:.1} min",
                     metrics.time_metrics.total_session_time_minutes
                 ),
+            };
+            lines.push(Line::from(vec![Span::styled(
+                duration,
                 Style::default().fg(Color::White),
             )]));
 
+            let thrash = &metrics.tool_usage.thrash_segments;
+            if !thrash.is_empty() {
+                let occurrences: u32 = thrash.iter().map(|s| s.occurrences).sum();
+                lines.push(Line::from(vec![
+                    Span::styled("  Thrashing: ", Style::default().fg(Color::DarkGray)),
+                    Span::styled(
+                        format!("{} segments ({occurrences} repeats)", thrash.len()),
+                        Style::default().fg(Color::Yellow),
+                    ),
+                ]));
+            }
+
+            if let Some(turn) = metrics.turns.iter().max_by_key(|t| t.total_tokens) {
+                lines.push(Line::from(vec![
+                    Span::styled("  Costliest turn: ", Style::default().fg(Color::DarkGray)),
+                    Span::styled(
+                        format!(
+                            "#{} ({} tokens) {}",
+                            turn.turn_number, turn.total_tokens, turn.prompt_preview
+                        ),
+                        Style::default().fg(Color::White),
+                    ),
+                ]));
+            }
+
             // Model used
             if let Some(model) = &analytics.model_used {
                 lines.push(Line::from(""));
@@ -1081,3 +1690,26 @@ impl SessionDetailWidget {
         self.state.update_qualitative_scroll_state(qual_total);
     }
 }
+
+/// ID a group is folded by, and whether "collapse tool results" folds it
+///
+/// Paired tool calls stay open when collapsed; their results shrink to a
+/// summary line instead.
+fn group_key(group: &MessageGroup) -> (uuid::Uuid, bool) {
+    match group {
+        MessageGroup::Single(message) => (message.id, message.has_tool_operation()),
+        MessageGroup::ToolPair {
+            tool_use_message, ..
+        } => (tool_use_message.id, false),
+    }
+}
+
+/// When a message group started
+fn group_timestamp(group: &MessageGroup) -> chrono::DateTime<chrono::Utc> {
+    match group {
+        MessageGroup::Single(message) => message.timestamp,
+        MessageGroup::ToolPair {
+            tool_use_message, ..
+        } => tool_use_message.timestamp,
+    }
+}
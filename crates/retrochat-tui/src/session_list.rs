@@ -1,20 +1,24 @@
 use anyhow::Result;
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
     Frame,
 };
+use std::collections::BTreeSet;
 use std::sync::Arc;
 
-use retrochat_core::database::DatabaseManager;
+use retrochat_core::database::{DatabaseManager, SavedViewRepository};
 use retrochat_core::models::OperationStatus;
-use retrochat_core::services::{QueryService, SessionSummary, SessionsQueryRequest};
+use retrochat_core::services::{
+    QueryService, SessionCursor, SessionFilters, SessionSummary, SessionsQueryRequest,
+};
 
 use super::{
-    state::{SessionListState, SortOrder},
+    state::{FilterFacet, FilterPanel, SessionListState, SortOrder},
+    utils::layout::inner_row,
     utils::text::{get_spinner_char, truncate_text},
 };
 
@@ -24,32 +28,46 @@ pub use super::state::{SortBy, SortOrder as SessionListSortOrder};
 pub struct SessionListWidget {
     pub state: SessionListState,
     query_service: QueryService,
+    saved_view_repo: SavedViewRepository,
+    /// Where the session list and filter panel were last drawn, for mouse clicks
+    list_area: Rect,
+    filter_panel_area: Rect,
+    /// Filter panel row shown on each line of the panel (`None` for headings)
+    filter_panel_lines: Vec<Option<usize>>,
 }
 
 impl SessionListWidget {
     pub fn new(db_manager: Arc<DatabaseManager>) -> Self {
         Self {
             state: SessionListState::new(),
+            saved_view_repo: SavedViewRepository::new(&db_manager),
             query_service: QueryService::with_database(db_manager),
+            list_area: Rect::default(),
+            filter_panel_area: Rect::default(),
+            filter_panel_lines: Vec::new(),
         }
     }
 
     pub async fn refresh(&mut self) -> Result<()> {
         self.state.loading = true;
 
-        let request = SessionsQueryRequest {
-            page: Some(self.state.page),
-            page_size: Some(self.state.page_size),
-            sort_by: Some(self.state.sort_by.as_str().to_string()),
-            sort_order: Some(self.state.sort_order.as_str().to_string()),
-            filters: None,
-        };
+        match self.saved_view_repo.list().await {
+            Ok(views) => self.state.set_views(views),
+            Err(e) => tracing::error!(error = %e, "Failed to load saved views"),
+        }
+
+        let request = self.request(None);
 
         match self.query_service.query_sessions(request).await {
             Ok(response) => {
+                self.state.filter_error = None;
+                self.state.next_cursor = response.next_cursor;
                 self.state
                     .update_sessions(response.sessions, response.total_count);
             }
+            Err(e) if self.state.filter.is_some() || !self.state.selection.is_empty() => {
+                self.state.filter_error = Some(e.to_string());
+            }
             Err(e) => {
                 tracing::error!(error = %e, "Failed to load sessions");
             }
@@ -59,12 +77,199 @@ impl SessionListWidget {
         Ok(())
     }
 
+    /// Load the sessions following the last one in the list
+    async fn load_more(&mut self) -> Result<()> {
+        let request = self.request(self.state.next_cursor.clone());
+        match self.query_service.query_sessions(request).await {
+            Ok(response) => self
+                .state
+                .append_sessions(response.sessions, response.next_cursor),
+            Err(e) => {
+                self.state.next_cursor = None;
+                tracing::error!(error = %e, "Failed to load more sessions");
+            }
+        }
+        Ok(())
+    }
+
+    fn request(&self, after: Option<SessionCursor>) -> SessionsQueryRequest {
+        let selection = &self.state.selection;
+        let ticked = |values: &BTreeSet<String>| {
+            (!values.is_empty()).then(|| values.iter().cloned().collect())
+        };
+        let filtered = self.state.filter.is_some() || !selection.is_empty();
+
+        SessionsQueryRequest {
+            page: Some(self.state.page),
+            page_size: Some(self.state.page_size),
+            sort_by: Some(self.state.sort_by.as_str().to_string()),
+            sort_order: Some(self.state.sort_order.as_str().to_string()),
+            filters: filtered.then(|| SessionFilters {
+                provider: None,
+                project: None,
+                date_range: None,
+                min_messages: None,
+                max_messages: None,
+                include_archived: false,
+                expression: self.state.filter.clone(),
+                topic: None,
+                owner: None,
+                providers: ticked(&selection.providers),
+                projects: ticked(&selection.projects),
+                topics: ticked(&selection.topics),
+            }),
+            after,
+        }
+    }
+
+    /// Whether the filter panel is open and should receive all key presses
+    pub fn is_filter_panel_open(&self) -> bool {
+        self.state.filter_panel.is_some()
+    }
+
+    async fn open_filter_panel(&mut self) {
+        match self.query_service.get_filter_options().await {
+            Ok(options) => self.state.filter_panel = Some(FilterPanel::new(options)),
+            Err(e) => tracing::error!(error = %e, "Failed to load filter options"),
+        }
+    }
+
+    async fn handle_filter_panel_key(&mut self, key: KeyEvent) -> Result<()> {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('f') => self.state.filter_panel = None,
+            KeyCode::Up => {
+                if let Some(panel) = self.state.filter_panel.as_mut() {
+                    panel.move_up();
+                }
+            }
+            KeyCode::Down => {
+                if let Some(panel) = self.state.filter_panel.as_mut() {
+                    panel.move_down();
+                }
+            }
+            KeyCode::Char(' ') | KeyCode::Enter if self.state.toggle_filter_option() => {
+                self.refresh().await?;
+            }
+            KeyCode::Char('c') => {
+                self.state.clear_selection();
+                self.refresh().await?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Whether the filter bar is open and should receive all key presses
+    pub fn is_editing_filter(&self) -> bool {
+        self.state.filter_input.is_some()
+    }
+
+    async fn handle_filter_key(&mut self, key: KeyEvent) -> Result<()> {
+        match key.code {
+            KeyCode::Enter => {
+                self.state.apply_filter_input();
+                self.refresh().await?;
+            }
+            KeyCode::Esc => self.state.cancel_filter_input(),
+            KeyCode::Backspace => {
+                if let Some(input) = self.state.filter_input.as_mut() {
+                    input.pop();
+                }
+            }
+            KeyCode::Char(c) => {
+                if let Some(input) = self.state.filter_input.as_mut() {
+                    input.push(c);
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Select with a click, open with a click on the selected session, and
+    /// scroll with the wheel; returns the session to open
+    pub async fn handle_mouse(&mut self, mouse: MouseEvent) -> Result<Option<String>> {
+        let (column, row) = (mouse.column, mouse.row);
+        if self.state.filter_panel.is_some()
+            && self
+                .filter_panel_area
+                .intersects(Rect::new(column, row, 1, 1))
+        {
+            return self.handle_filter_panel_mouse(mouse).await.map(|_| None);
+        }
+        if self.is_editing_filter() {
+            return Ok(None);
+        }
+
+        match mouse.kind {
+            MouseEventKind::ScrollUp => {
+                let selected = self.state.list_state.selected().unwrap_or(0);
+                self.state.select_session(selected.saturating_sub(1));
+            }
+            MouseEventKind::ScrollDown => {
+                if self.state.should_load_more() {
+                    self.load_more().await?;
+                }
+                let selected = self.state.list_state.selected().unwrap_or(0);
+                self.state.select_session(selected + 1);
+            }
+            MouseEventKind::Down(MouseButton::Left) => {
+                let Some(line) = inner_row(self.list_area, column, row) else {
+                    return Ok(None);
+                };
+                let index = self.state.list_state.offset() + line;
+                if self.state.list_state.selected() == Some(index) {
+                    if let Some(session) = self.state.selected_session() {
+                        return Ok(Some(session.session_id.clone()));
+                    }
+                }
+                self.state.select_session(index);
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    async fn handle_filter_panel_mouse(&mut self, mouse: MouseEvent) -> Result<()> {
+        let Some(panel) = self.state.filter_panel.as_mut() else {
+            return Ok(());
+        };
+        match mouse.kind {
+            MouseEventKind::ScrollUp => panel.move_up(),
+            MouseEventKind::ScrollDown => panel.move_down(),
+            MouseEventKind::Down(MouseButton::Left) => {
+                let clicked = inner_row(self.filter_panel_area, mouse.column, mouse.row)
+                    .and_then(|line| self.filter_panel_lines.get(line).copied().flatten());
+                if let Some(row) = clicked {
+                    panel.cursor = row;
+                    if self.state.toggle_filter_option() {
+                        self.refresh().await?;
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
     pub async fn handle_key(&mut self, key: KeyEvent) -> Result<Option<String>> {
+        if self.is_editing_filter() {
+            self.handle_filter_key(key).await?;
+            return Ok(None);
+        }
+        if self.is_filter_panel_open() {
+            self.handle_filter_panel_key(key).await?;
+            return Ok(None);
+        }
+
         match key.code {
             KeyCode::Up => {
                 self.state.previous_session();
             }
             KeyCode::Down => {
+                if self.state.should_load_more() {
+                    self.load_more().await?;
+                }
                 self.state.next_session();
             }
             KeyCode::Enter => {
@@ -72,15 +277,11 @@ impl SessionListWidget {
                     return Ok(Some(session.session_id.clone()));
                 }
             }
-            KeyCode::PageUp => {
-                if self.state.previous_page() {
-                    self.refresh().await?;
-                }
+            KeyCode::PageUp if self.state.previous_page() => {
+                self.refresh().await?;
             }
-            KeyCode::PageDown => {
-                if self.state.next_page() {
-                    self.refresh().await?;
-                }
+            KeyCode::PageDown if self.state.next_page() => {
+                self.refresh().await?;
             }
             KeyCode::Home => {
                 self.state.first_session();
@@ -96,6 +297,16 @@ impl SessionListWidget {
                 self.state.toggle_sort_order();
                 self.refresh().await?;
             }
+            KeyCode::Char('/') => {
+                self.state.start_filter_input();
+            }
+            KeyCode::Char('f') => {
+                self.open_filter_panel().await;
+            }
+            KeyCode::Char('v') => {
+                self.state.cycle_view();
+                self.refresh().await?;
+            }
             KeyCode::Char('a') => {
                 // Start analytics for selected session
                 if let Some(session) = self.state.selected_session() {
@@ -121,21 +332,105 @@ impl SessionListWidget {
         // Render header with stats and controls
         self.render_header(f, chunks[0]);
 
-        // Render session list
-        self.render_session_list(f, chunks[1]);
+        // Render session list, with the filter panel beside it when open
+        if self.state.filter_panel.is_some() {
+            let columns = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Min(0), Constraint::Length(36)])
+                .split(chunks[1]);
+            self.render_session_list(f, columns[0]);
+            self.render_filter_panel(f, columns[1]);
+        } else {
+            self.render_session_list(f, chunks[1]);
+        }
+    }
+
+    fn render_filter_panel(&mut self, f: &mut Frame, area: Rect) {
+        let Some(panel) = &self.state.filter_panel else {
+            return;
+        };
+
+        let mut items = Vec::new();
+        let mut item_rows = Vec::new();
+        let mut selected_item = None;
+        let mut row = 0;
+        for facet in FilterFacet::ALL {
+            let options: Vec<_> = panel
+                .rows()
+                .into_iter()
+                .filter(|(option_facet, _)| *option_facet == facet)
+                .map(|(_, option)| option)
+                .collect();
+            if options.is_empty() {
+                continue;
+            }
+            item_rows.push(None);
+            items.push(ListItem::new(Line::from(Span::styled(
+                facet.title().to_string(),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ))));
+            for option in options {
+                let ticked = self.state.selection.values(facet).contains(&option.value);
+                if row == panel.cursor {
+                    selected_item = Some(items.len());
+                }
+                item_rows.push(Some(row));
+                items.push(ListItem::new(Line::from(vec![
+                    Span::styled(
+                        if ticked { " [x] " } else { " [ ] " },
+                        Style::default().fg(if ticked { Color::Green } else { Color::Gray }),
+                    ),
+                    Span::raw(truncate_text(&option.label, 22)),
+                    Span::styled(
+                        format!(" ({})", option.session_count),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ])));
+                row += 1;
+            }
+        }
+        if items.is_empty() {
+            items.push(ListItem::new("No sessions to filter"));
+        }
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Filters (Space: toggle, c: clear, Esc: close)"),
+            )
+            .highlight_style(
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD),
+            );
+        let mut list_state = ListState::default();
+        list_state.select(selected_item);
+
+        f.render_stateful_widget(list, area, &mut list_state);
+        self.filter_panel_area = area;
+        self.filter_panel_lines = item_rows.split_off(list_state.offset().min(item_rows.len()));
     }
 
     fn render_header(&self, f: &mut Frame, area: Rect) {
         let total_pages = self.state.total_pages();
 
-        let header_text = if self.state.loading {
+        // Scrolling past the end loads more sessions onto the current page
+        let position = if self.state.sessions.len() > self.state.page_size as usize {
+            format!("Loaded: {}", self.state.sessions.len())
+        } else {
+            format!("Page: {}/{}", self.state.page, total_pages.max(1))
+        };
+
+        let mut header_text = if self.state.loading {
             "Loading sessions...".to_string()
         } else {
             format!(
-                "Sessions: {} | Page: {}/{} | Sort: {} {}",
+                "Sessions: {} | {} | Sort: {} {}",
                 self.state.total_count,
-                self.state.page,
-                total_pages.max(1),
+                position,
                 self.state.sort_by.as_str(),
                 if matches!(self.state.sort_order, SortOrder::Ascending) {
                     "↑"
@@ -145,9 +440,41 @@ impl SessionListWidget {
             )
         };
 
+        if !self.state.selection.is_empty() {
+            header_text.push_str(&format!(" | {}", self.state.selection.describe()));
+        }
+
+        if let Some(notice) = &self.state.new_sessions_notice {
+            header_text.push_str(&format!(" | {notice} (run `retrochat sync`)"));
+        }
+
+        let (header_text, style) = if let Some(input) = &self.state.filter_input {
+            (
+                format!("Filter: {input}█  (Enter: apply, Esc: cancel)"),
+                Style::default().fg(Color::Yellow),
+            )
+        } else if let Some(error) = &self.state.filter_error {
+            (
+                format!("Invalid filter: {error}  (/ to edit)"),
+                Style::default().fg(Color::LightRed),
+            )
+        } else if let Some(view) = self.state.active_view() {
+            (
+                format!("{header_text} | View: {}", view.name),
+                Style::default().fg(Color::Cyan),
+            )
+        } else if let Some(filter) = &self.state.filter {
+            (
+                format!("{header_text} | Filter: {filter}"),
+                Style::default().fg(Color::Cyan),
+            )
+        } else {
+            (header_text, Style::default().fg(Color::Cyan))
+        };
+
         let header = Paragraph::new(header_text)
             .block(Block::default().borders(Borders::ALL).title("Session List"))
-            .style(Style::default().fg(Color::Cyan));
+            .style(style);
 
         f.render_widget(header, area);
     }
@@ -190,6 +517,7 @@ impl SessionListWidget {
             .highlight_symbol("▶ ");
 
         f.render_stateful_widget(list, area, &mut self.state.list_state);
+        self.list_area = area;
     }
 
     fn format_session_line_with_spinner(
@@ -226,7 +554,13 @@ impl SessionListWidget {
         let project_text = Self::truncate_and_pad(project_text, 20);
         let start_time_text = Self::truncate_and_pad(&session.start_time, 16);
         let msg_count_text = format!("{:4} msgs", session.message_count);
-        let preview_text = Self::truncate_and_pad(&session.first_message_preview, 40);
+        let preview_text = Self::truncate_and_pad(
+            session
+                .title
+                .as_deref()
+                .unwrap_or(&session.first_message_preview),
+            40,
+        );
 
         // Add analytics status indicator
         let analytics_indicator = match &session.analytics_status {
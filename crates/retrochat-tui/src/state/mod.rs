@@ -1,5 +1,5 @@
 pub mod session_detail_state;
 pub mod session_list_state;
 
-pub use session_detail_state::SessionDetailState;
-pub use session_list_state::{SessionListState, SortBy, SortOrder};
+pub use session_detail_state::{ReplayState, SessionDetailState};
+pub use session_list_state::{FilterFacet, FilterPanel, SessionListState, SortBy, SortOrder};
@@ -1,7 +1,10 @@
 use ratatui::widgets::ScrollbarState;
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
 
-use retrochat_core::models::{ChatSession, Message};
-use retrochat_core::services::SessionAnalytics;
+use retrochat_core::models::{Attachment, ChatSession, Message};
+use retrochat_core::services::{RelatedSession, SessionAnalytics};
 
 /// Which analytics panel is currently focused for scrolling
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -11,6 +14,78 @@ pub enum AnalyticsPanelFocus {
     Qualitative,
 }
 
+/// Autoplay speeds, as multiples of the session's real pace
+pub const REPLAY_SPEEDS: [f64; 6] = [0.5, 1.0, 2.0, 4.0, 8.0, 16.0];
+
+/// Autoplay waits at least this long between messages at 1x
+const REPLAY_MIN_GAP: Duration = Duration::from_millis(300);
+
+/// Autoplay never waits longer than this at 1x, so idle stretches pass quickly
+const REPLAY_MAX_GAP: Duration = Duration::from_secs(5);
+
+/// Playback of a session one message (or tool call) at a time
+#[derive(Debug, Clone)]
+pub struct ReplayState {
+    /// Message groups shown so far; always at least one
+    pub shown: usize,
+    /// Whether messages advance on their own
+    pub autoplay: bool,
+    /// Index into [`REPLAY_SPEEDS`]
+    pub speed_index: usize,
+    /// When the last message was revealed
+    pub last_step: Instant,
+}
+
+impl ReplayState {
+    pub fn new(now: Instant) -> Self {
+        Self {
+            shown: 1,
+            autoplay: false,
+            speed_index: 1,
+            last_step: now,
+        }
+    }
+
+    pub fn speed(&self) -> f64 {
+        REPLAY_SPEEDS[self.speed_index]
+    }
+
+    pub fn faster(&mut self) {
+        self.speed_index = (self.speed_index + 1).min(REPLAY_SPEEDS.len() - 1);
+    }
+
+    pub fn slower(&mut self) {
+        self.speed_index = self.speed_index.saturating_sub(1);
+    }
+
+    /// Reveal the next message; returns false at the end of the session
+    pub fn step_forward(&mut self, total: usize, now: Instant) -> bool {
+        self.last_step = now;
+        if self.shown < total {
+            self.shown += 1;
+            true
+        } else {
+            self.autoplay = false;
+            false
+        }
+    }
+
+    /// Hide the last revealed message
+    pub fn step_back(&mut self, now: Instant) {
+        self.last_step = now;
+        self.shown = self.shown.saturating_sub(1).max(1);
+    }
+
+    /// How long autoplay waits for a message that came `gap` after the previous one
+    pub fn delay_for(&self, gap: chrono::Duration) -> Duration {
+        let gap = gap
+            .to_std()
+            .unwrap_or_default()
+            .clamp(REPLAY_MIN_GAP, REPLAY_MAX_GAP);
+        gap.div_f64(self.speed())
+    }
+}
+
 /// State for the session detail view
 #[derive(Debug)]
 pub struct SessionDetailState {
@@ -18,10 +93,16 @@ pub struct SessionDetailState {
     pub session: Option<ChatSession>,
     /// Messages in this session
     pub messages: Vec<Message>,
+    /// Messages in the whole session; `messages` holds the pages loaded so far
+    pub total_messages: usize,
+    /// Images and documents attached to those messages
+    pub attachments: Vec<Attachment>,
     /// Currently selected session ID
     pub session_id: Option<String>,
     /// Analytics data for this session
     pub analytics: Option<SessionAnalytics>,
+    /// Sessions with similar summaries, best match first
+    pub related_sessions: Vec<RelatedSession>,
     /// Scrollbar state for messages
     pub scroll_state: ScrollbarState,
     /// Current scroll position (line number) for messages
@@ -36,6 +117,19 @@ pub struct SessionDetailState {
     pub show_thinking: bool,
     /// Last known viewport height for messages (used for scroll calculations)
     pub viewport_height: usize,
+    /// Last known text width for messages (used to find the message at the top)
+    pub message_width: usize,
+    /// Short feedback for the last action, e.g. a copy to the clipboard
+    pub notice: Option<String>,
+    /// Replay mode, when the session is being played back
+    pub replay: Option<ReplayState>,
+    /// Show tool results as one-line summaries
+    pub collapse_tool_results: bool,
+    /// Messages (by ID of a group's first message) folded or unfolded with Enter,
+    /// against the default set by `collapse_tool_results`
+    pub fold_toggled: HashSet<Uuid>,
+    /// Bumped whenever the message lines need laying out again
+    pub layout_generation: u64,
 
     // Analytics panel state
     /// Which analytics panel is focused for scrolling
@@ -60,8 +154,11 @@ impl SessionDetailState {
         Self {
             session: None,
             messages: Vec::new(),
+            total_messages: 0,
+            attachments: Vec::new(),
             session_id: None,
             analytics: None,
+            related_sessions: Vec::new(),
             scroll_state: ScrollbarState::default(),
             current_scroll: 0,
             loading: false,
@@ -69,6 +166,12 @@ impl SessionDetailState {
             show_analytics: false,
             show_thinking: true, // Show thinking messages by default
             viewport_height: 20, // Default fallback
+            message_width: 80,
+            notice: None,
+            replay: None,
+            collapse_tool_results: false,
+            fold_toggled: HashSet::new(),
+            layout_generation: 0,
 
             // Analytics panel state
             analytics_panel_focus: AnalyticsPanelFocus::default(),
@@ -88,10 +191,23 @@ impl SessionDetailState {
             // Clear old data when switching sessions
             self.session = None;
             self.messages.clear();
+            self.total_messages = 0;
+            self.attachments.clear();
+            self.related_sessions.clear();
             self.current_scroll = 0;
+            self.replay = None;
+            self.fold_toggled.clear();
+            self.layout_generation += 1;
         }
     }
 
+    /// ID of the `index`th related session, if there is one
+    pub fn related_session_id(&self, index: usize) -> Option<String> {
+        self.related_sessions
+            .get(index)
+            .map(|related| related.session_id.clone())
+    }
+
     /// Update the session data from query result
     pub fn update_session(&mut self, session: ChatSession, messages: Vec<Message>) {
         // Only reset scroll if we're switching to a different session
@@ -102,7 +218,9 @@ impl SessionDetailState {
             .unwrap_or(false);
 
         self.session = Some(session);
+        self.total_messages = self.total_messages.max(messages.len());
         self.messages = messages;
+        self.layout_generation += 1;
 
         // Only reset scroll position when switching to a different session
         if !is_same_session {
@@ -110,6 +228,18 @@ impl SessionDetailState {
         }
     }
 
+    /// Add the next page of messages after those already loaded
+    pub fn append_messages(&mut self, messages: Vec<Message>, attachments: Vec<Attachment>) {
+        self.messages.extend(messages);
+        self.attachments.extend(attachments);
+        self.layout_generation += 1;
+    }
+
+    /// Whether some of the session's messages are not loaded yet
+    pub fn has_more_messages(&self) -> bool {
+        self.messages.len() < self.total_messages
+    }
+
     /// Scroll up one line
     pub fn scroll_up(&mut self) {
         if self.current_scroll > 0 {
@@ -147,6 +277,7 @@ impl SessionDetailState {
     /// Toggle tool details visibility
     pub fn toggle_tool_details(&mut self) {
         self.show_tool_details = !self.show_tool_details;
+        self.layout_generation += 1;
     }
 
     /// Toggle analytics panel visibility
@@ -157,6 +288,28 @@ impl SessionDetailState {
     /// Toggle thinking messages visibility
     pub fn toggle_thinking(&mut self) {
         self.show_thinking = !self.show_thinking;
+        self.layout_generation += 1;
+    }
+
+    /// Fold or unfold one message
+    pub fn toggle_fold(&mut self, message_id: Uuid) {
+        if !self.fold_toggled.remove(&message_id) {
+            self.fold_toggled.insert(message_id);
+        }
+        self.layout_generation += 1;
+    }
+
+    /// Collapse or expand all tool results, forgetting per-message folds
+    pub fn toggle_collapse_tool_results(&mut self) {
+        self.collapse_tool_results = !self.collapse_tool_results;
+        self.fold_toggled.clear();
+        self.layout_generation += 1;
+    }
+
+    /// Whether a message is shown folded
+    pub fn is_folded(&self, message_id: Uuid, is_tool: bool) -> bool {
+        let folded_by_default = is_tool && self.collapse_tool_results;
+        folded_by_default != self.fold_toggled.contains(&message_id)
     }
 
     /// Update analytics data
@@ -299,6 +452,86 @@ mod tests {
         assert!(state.show_thinking); // Thinking messages shown by default
     }
 
+    #[test]
+    fn test_folding() {
+        let mut state = SessionDetailState::new();
+        let (text, tool) = (Uuid::new_v4(), Uuid::new_v4());
+        assert!(!state.is_folded(text, false));
+
+        state.toggle_fold(text);
+        assert!(state.is_folded(text, false));
+
+        state.toggle_collapse_tool_results();
+        assert!(state.is_folded(tool, true));
+        assert!(!state.is_folded(text, false));
+
+        // Enter on a collapsed tool call expands just that one
+        state.toggle_fold(tool);
+        assert!(!state.is_folded(tool, true));
+        state.toggle_fold(tool);
+        assert!(state.is_folded(tool, true));
+    }
+
+    #[test]
+    fn test_append_messages_pages() {
+        use chrono::Utc;
+        use retrochat_core::models::MessageRole;
+
+        let mut state = SessionDetailState::new();
+        let session_id = Uuid::new_v4();
+        let page = |from: u32, to: u32| -> Vec<Message> {
+            (from..to)
+                .map(|i| Message::new(session_id, MessageRole::User, i.to_string(), Utc::now(), i))
+                .collect()
+        };
+        state.messages = page(0, 2);
+        state.total_messages = 3;
+        assert!(state.has_more_messages());
+
+        let generation = state.layout_generation;
+        state.append_messages(page(2, 3), vec![]);
+        assert_eq!(state.messages.len(), 3);
+        assert!(!state.has_more_messages());
+        assert!(state.layout_generation > generation);
+    }
+
+    #[test]
+    fn test_replay_steps_and_delays() {
+        let start = Instant::now();
+        let mut replay = ReplayState::new(start);
+
+        assert!(replay.step_forward(3, start));
+        assert!(replay.step_forward(3, start));
+        assert_eq!(replay.shown, 3);
+        replay.autoplay = true;
+        assert!(!replay.step_forward(3, start));
+        assert!(!replay.autoplay);
+
+        replay.step_back(start);
+        replay.step_back(start);
+        replay.step_back(start);
+        assert_eq!(replay.shown, 1);
+
+        // Real gaps are clamped, then scaled by the speed
+        assert_eq!(
+            replay.delay_for(chrono::Duration::seconds(2)),
+            Duration::from_secs(2)
+        );
+        assert_eq!(
+            replay.delay_for(chrono::Duration::hours(1)),
+            Duration::from_secs(5)
+        );
+        replay.faster();
+        assert_eq!(
+            replay.delay_for(chrono::Duration::seconds(2)),
+            Duration::from_secs(1)
+        );
+        for _ in 0..10 {
+            replay.faster();
+        }
+        assert_eq!(replay.speed(), 16.0);
+    }
+
     #[test]
     fn test_set_session_id_clears_data() {
         let mut state = SessionDetailState::new();
@@ -310,6 +543,24 @@ mod tests {
         assert_eq!(state.current_scroll, 0);
     }
 
+    #[test]
+    fn test_related_sessions_cleared_on_session_switch() {
+        let mut state = SessionDetailState::new();
+        state.related_sessions.push(RelatedSession {
+            session_id: "related".to_string(),
+            score: 0.8,
+            provider: "Claude Code".to_string(),
+            project: None,
+            title: Some("Fix the build".to_string()),
+            start_time: "2024-01-01T00:00:00Z".to_string(),
+        });
+        assert_eq!(state.related_session_id(0), Some("related".to_string()));
+        assert_eq!(state.related_session_id(1), None);
+
+        state.set_session_id(Some("related".to_string()));
+        assert!(state.related_sessions.is_empty());
+    }
+
     #[test]
     fn test_scrolling() {
         let mut state = SessionDetailState::new();
@@ -380,15 +631,20 @@ mod tests {
             id: uuid::Uuid::new_v4(),
             provider: Provider::ClaudeCode,
             project_name: None,
+            title: None,
+            title_source: None,
             start_time: Utc::now(),
             end_time: None,
             message_count: 5,
             token_count: Some(100),
+            tool_op_count: 0,
+            duration_seconds: None,
             file_path: "/test/path.jsonl".to_string(),
             file_hash: "test_hash".to_string(),
             state: ModelSessionState::Imported,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            owner: None,
         };
 
         state.update_session(session1.clone(), vec![]);
@@ -414,15 +670,20 @@ mod tests {
             id: uuid::Uuid::new_v4(),
             provider: Provider::ClaudeCode,
             project_name: None,
+            title: None,
+            title_source: None,
             start_time: Utc::now(),
             end_time: None,
             message_count: 5,
             token_count: Some(100),
+            tool_op_count: 0,
+            duration_seconds: None,
             file_path: "/test/path1.jsonl".to_string(),
             file_hash: "test_hash_1".to_string(),
             state: ModelSessionState::Imported,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            owner: None,
         };
 
         state.update_session(session1, vec![]);
@@ -433,15 +694,20 @@ mod tests {
             id: uuid::Uuid::new_v4(),
             provider: Provider::ClaudeCode,
             project_name: None,
+            title: None,
+            title_source: None,
             start_time: Utc::now(),
             end_time: None,
             message_count: 3,
             token_count: Some(50),
+            tool_op_count: 0,
+            duration_seconds: None,
             file_path: "/test/path2.jsonl".to_string(),
             file_hash: "test_hash_2".to_string(),
             state: ModelSessionState::Imported,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            owner: None,
         };
 
         state.update_session(session2, vec![]);
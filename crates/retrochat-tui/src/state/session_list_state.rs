@@ -1,6 +1,8 @@
 use ratatui::widgets::ListState;
+use std::collections::BTreeSet;
 
-use retrochat_core::services::SessionSummary;
+use retrochat_core::models::SavedView;
+use retrochat_core::services::{FilterOption, FilterOptions, SessionCursor, SessionSummary};
 
 /// Sorting options for the session list
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -58,6 +60,133 @@ impl SortOrder {
     }
 }
 
+/// Group of checkboxes in the filter panel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterFacet {
+    Provider,
+    Project,
+    Topic,
+}
+
+impl FilterFacet {
+    /// Facets in the order the panel lists them
+    pub const ALL: [FilterFacet; 3] = [
+        FilterFacet::Provider,
+        FilterFacet::Project,
+        FilterFacet::Topic,
+    ];
+
+    /// Section heading in the filter panel
+    pub fn title(&self) -> &str {
+        match self {
+            FilterFacet::Provider => "Providers",
+            FilterFacet::Project => "Projects",
+            FilterFacet::Topic => "Topics",
+        }
+    }
+
+    fn options<'a>(&self, options: &'a FilterOptions) -> &'a [FilterOption] {
+        match self {
+            FilterFacet::Provider => &options.providers,
+            FilterFacet::Project => &options.projects,
+            FilterFacet::Topic => &options.topics,
+        }
+    }
+}
+
+/// Values ticked in the filter panel; sessions must match one ticked value
+/// of each facet, and the filter expression
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FilterSelection {
+    pub providers: BTreeSet<String>,
+    pub projects: BTreeSet<String>,
+    pub topics: BTreeSet<String>,
+}
+
+impl FilterSelection {
+    pub fn values(&self, facet: FilterFacet) -> &BTreeSet<String> {
+        match facet {
+            FilterFacet::Provider => &self.providers,
+            FilterFacet::Project => &self.projects,
+            FilterFacet::Topic => &self.topics,
+        }
+    }
+
+    fn values_mut(&mut self, facet: FilterFacet) -> &mut BTreeSet<String> {
+        match facet {
+            FilterFacet::Provider => &mut self.providers,
+            FilterFacet::Project => &mut self.projects,
+            FilterFacet::Topic => &mut self.topics,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        FilterFacet::ALL
+            .iter()
+            .all(|facet| self.values(*facet).is_empty())
+    }
+
+    /// Tick or untick one value
+    pub fn toggle(&mut self, facet: FilterFacet, value: &str) {
+        let values = self.values_mut(facet);
+        if !values.remove(value) {
+            values.insert(value.to_string());
+        }
+    }
+
+    /// Summary for the header, e.g. "Providers: 2, Topics: 1"
+    pub fn describe(&self) -> String {
+        FilterFacet::ALL
+            .iter()
+            .filter(|facet| !self.values(**facet).is_empty())
+            .map(|facet| format!("{}: {}", facet.title(), self.values(*facet).len()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// The filter side panel, while it is open
+#[derive(Debug, Clone, Default)]
+pub struct FilterPanel {
+    pub options: FilterOptions,
+    /// Highlighted row, counting the options of all facets in order
+    pub cursor: usize,
+}
+
+impl FilterPanel {
+    pub fn new(options: FilterOptions) -> Self {
+        Self { options, cursor: 0 }
+    }
+
+    /// Every option with its facet, in panel order
+    pub fn rows(&self) -> Vec<(FilterFacet, &FilterOption)> {
+        FilterFacet::ALL
+            .iter()
+            .flat_map(|facet| {
+                facet
+                    .options(&self.options)
+                    .iter()
+                    .map(move |option| (*facet, option))
+            })
+            .collect()
+    }
+
+    /// The highlighted option
+    pub fn current(&self) -> Option<(FilterFacet, &FilterOption)> {
+        self.rows().get(self.cursor).copied()
+    }
+
+    pub fn move_up(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    pub fn move_down(&mut self) {
+        if self.cursor + 1 < self.rows().len() {
+            self.cursor += 1;
+        }
+    }
+}
+
 /// State for the session list view
 #[derive(Debug)]
 pub struct SessionListState {
@@ -77,6 +206,24 @@ pub struct SessionListState {
     pub total_count: i32,
     /// Loading indicator
     pub loading: bool,
+    /// Applied filter expression
+    pub filter: Option<String>,
+    /// Filter being typed in the filter bar, if it is open
+    pub filter_input: Option<String>,
+    /// Why the applied filter could not be used
+    pub filter_error: Option<String>,
+    /// Saved views available in the view switcher
+    pub views: Vec<SavedView>,
+    /// Index into `views` of the view whose filter is applied
+    pub active_view: Option<usize>,
+    /// Where to continue loading when scrolling past the last session
+    pub next_cursor: Option<SessionCursor>,
+    /// Providers, projects and topics ticked in the filter panel
+    pub selection: FilterSelection,
+    /// Filter panel, if it is open
+    pub filter_panel: Option<FilterPanel>,
+    /// Prompt about chat history files that have not been imported yet
+    pub new_sessions_notice: Option<String>,
 }
 
 impl SessionListState {
@@ -94,6 +241,15 @@ impl SessionListState {
             page_size: 50,
             total_count: 0,
             loading: false,
+            filter: None,
+            filter_input: None,
+            filter_error: None,
+            views: Vec::new(),
+            active_view: None,
+            next_cursor: None,
+            selection: FilterSelection::default(),
+            filter_panel: None,
+            new_sessions_notice: None,
         }
     }
 
@@ -139,6 +295,16 @@ impl SessionListState {
         self.list_state.select(Some(previous));
     }
 
+    /// Select the session at `index`, if there is one
+    pub fn select_session(&mut self, index: usize) -> bool {
+        if index < self.sessions.len() {
+            self.list_state.select(Some(index));
+            true
+        } else {
+            false
+        }
+    }
+
     /// Move selection to the first session
     pub fn first_session(&mut self) {
         if !self.sessions.is_empty() {
@@ -153,6 +319,25 @@ impl SessionListState {
         }
     }
 
+    /// Whether moving down should first load the sessions after the last one
+    ///
+    /// Only start time ordering can continue from a cursor.
+    pub fn should_load_more(&self) -> bool {
+        self.next_cursor.is_some()
+            && self.sort_by == SortBy::StartTime
+            && self.list_state.selected() == Some(self.sessions.len().saturating_sub(1))
+    }
+
+    /// Add sessions loaded by scrolling past the end of the list
+    pub fn append_sessions(
+        &mut self,
+        sessions: Vec<SessionSummary>,
+        next_cursor: Option<SessionCursor>,
+    ) {
+        self.sessions.extend(sessions);
+        self.next_cursor = next_cursor;
+    }
+
     /// Move to the next page if possible
     pub fn next_page(&mut self) -> bool {
         let total_pages = self.total_pages();
@@ -188,6 +373,75 @@ impl SessionListState {
         self.page = 1;
     }
 
+    /// Open the filter bar, starting from the applied filter
+    pub fn start_filter_input(&mut self) {
+        self.filter_input = Some(self.filter.clone().unwrap_or_default());
+    }
+
+    /// Close the filter bar without changing the applied filter
+    pub fn cancel_filter_input(&mut self) {
+        self.filter_input = None;
+    }
+
+    /// Apply the typed filter (an empty one clears it) and reset to first page
+    pub fn apply_filter_input(&mut self) {
+        if let Some(input) = self.filter_input.take() {
+            let input = input.trim();
+            self.filter = (!input.is_empty()).then(|| input.to_string());
+            self.filter_error = None;
+            self.active_view = None;
+            self.page = 1;
+            self.list_state.select(Some(0));
+        }
+    }
+
+    /// Tick or untick the highlighted option of the filter panel and reset
+    /// to first page; false when the panel is closed or empty
+    pub fn toggle_filter_option(&mut self) -> bool {
+        let Some((facet, option)) = self.filter_panel.as_ref().and_then(|panel| panel.current())
+        else {
+            return false;
+        };
+        let value = option.value.clone();
+        self.selection.toggle(facet, &value);
+        self.page = 1;
+        self.list_state.select(Some(0));
+        true
+    }
+
+    /// Untick everything in the filter panel and reset to first page
+    pub fn clear_selection(&mut self) {
+        self.selection = FilterSelection::default();
+        self.page = 1;
+        self.list_state.select(Some(0));
+    }
+
+    /// Currently applied saved view
+    pub fn active_view(&self) -> Option<&SavedView> {
+        self.active_view.and_then(|idx| self.views.get(idx))
+    }
+
+    /// Switch to the next saved view, going back to all sessions after the last
+    pub fn cycle_view(&mut self) {
+        self.active_view = match self.active_view {
+            None if !self.views.is_empty() => Some(0),
+            Some(idx) if idx + 1 < self.views.len() => Some(idx + 1),
+            _ => None,
+        };
+        self.filter = self.active_view().map(|view| view.expression.clone());
+        self.filter_error = None;
+        self.page = 1;
+        self.list_state.select(Some(0));
+    }
+
+    /// Replace the saved views, keeping the active one selected if it still exists
+    pub fn set_views(&mut self, views: Vec<SavedView>) {
+        let active_name = self.active_view().map(|view| view.name.clone());
+        self.active_view =
+            active_name.and_then(|name| views.iter().position(|view| view.name == name));
+        self.views = views;
+    }
+
     /// Update sessions from query result
     pub fn update_sessions(&mut self, sessions: Vec<SessionSummary>, total_count: i32) {
         self.sessions = sessions;
@@ -261,6 +515,150 @@ mod tests {
         assert_eq!(state.total_pages(), 3);
     }
 
+    #[test]
+    fn test_filter_panel_selection() {
+        let option = |value: &str| FilterOption {
+            value: value.to_string(),
+            label: value.to_string(),
+            session_count: 1,
+        };
+        let mut state = SessionListState::new();
+        assert!(!state.toggle_filter_option());
+
+        state.filter_panel = Some(FilterPanel::new(FilterOptions {
+            providers: vec![option("Claude Code"), option("Codex")],
+            projects: vec![option("retrochat")],
+            topics: Vec::new(),
+        }));
+        state.page = 3;
+        assert!(state.toggle_filter_option());
+        assert_eq!(state.page, 1);
+
+        let panel = state.filter_panel.as_mut().unwrap();
+        panel.move_down();
+        panel.move_down();
+        panel.move_down(); // Stops at the last row
+        assert_eq!(panel.current().unwrap().0, FilterFacet::Project);
+        state.toggle_filter_option();
+        assert_eq!(
+            state.selection.describe(),
+            "Providers: 1, Projects: 1".to_string()
+        );
+
+        state.clear_selection();
+        assert!(state.selection.is_empty());
+    }
+
+    #[test]
+    fn test_filter_input() {
+        let mut state = SessionListState::new();
+        state.page = 3;
+
+        state.start_filter_input();
+        state.filter_input = Some(" provider:claude ".to_string());
+        state.apply_filter_input();
+        assert_eq!(state.filter.as_deref(), Some("provider:claude"));
+        assert!(state.filter_input.is_none());
+        assert_eq!(state.page, 1);
+
+        state.start_filter_input();
+        assert_eq!(state.filter_input.as_deref(), Some("provider:claude"));
+        state.cancel_filter_input();
+        assert_eq!(state.filter.as_deref(), Some("provider:claude"));
+
+        state.start_filter_input();
+        state.filter_input = Some(String::new());
+        state.apply_filter_input();
+        assert!(state.filter.is_none());
+    }
+
+    #[test]
+    fn test_cycle_view() {
+        let mut state = SessionListState::new();
+        state.cycle_view();
+        assert!(state.active_view.is_none());
+
+        state.set_views(vec![
+            SavedView::new("big".to_string(), "tokens>100k".to_string()),
+            SavedView::new("claude".to_string(), "provider:claude".to_string()),
+        ]);
+        state.page = 2;
+
+        state.cycle_view();
+        assert_eq!(state.active_view().unwrap().name, "big");
+        assert_eq!(state.filter.as_deref(), Some("tokens>100k"));
+        assert_eq!(state.page, 1);
+
+        state.cycle_view();
+        assert_eq!(state.filter.as_deref(), Some("provider:claude"));
+
+        // Reloading keeps the active view by name
+        state.set_views(vec![SavedView::new(
+            "claude".to_string(),
+            "provider:claude".to_string(),
+        )]);
+        assert_eq!(state.active_view, Some(0));
+
+        state.cycle_view();
+        assert!(state.active_view.is_none());
+        assert!(state.filter.is_none());
+
+        // Typing a filter leaves the view
+        state.cycle_view();
+        state.start_filter_input();
+        state.filter_input = Some("provider:gemini".to_string());
+        state.apply_filter_input();
+        assert!(state.active_view.is_none());
+    }
+
+    fn summary(id: &str) -> SessionSummary {
+        SessionSummary {
+            session_id: id.to_string(),
+            provider: "Claude Code".to_string(),
+            project: None,
+            title: None,
+            start_time: "2024-01-01T00:00:00+00:00".to_string(),
+            end_time: "2024-01-01T00:00:00+00:00".to_string(),
+            message_count: 1,
+            total_tokens: None,
+            tool_op_count: 0,
+            duration_seconds: None,
+            first_message_preview: String::new(),
+            has_analytics: false,
+            analytics_status: None,
+            owner: None,
+        }
+    }
+
+    #[test]
+    fn test_infinite_scroll() {
+        let mut state = SessionListState::new();
+        let cursor: SessionCursor =
+            "2024-01-01T00:00:00+00:00/550e8400-e29b-41d4-a716-446655440000"
+                .parse()
+                .unwrap();
+        state.update_sessions(vec![summary("a"), summary("b")], 3);
+        state.next_cursor = Some(cursor);
+        assert!(!state.should_load_more());
+
+        state.last_session();
+        assert!(state.should_load_more());
+
+        state.sort_by = SortBy::Provider;
+        assert!(!state.should_load_more());
+        state.sort_by = SortBy::StartTime;
+
+        state.append_sessions(vec![summary("c")], None);
+        state.next_session();
+        assert_eq!(state.selected_session().unwrap().session_id, "c");
+        assert!(!state.should_load_more());
+
+        // Clicks below the last session select nothing
+        assert!(state.select_session(0));
+        assert!(!state.select_session(3));
+        assert_eq!(state.selected_session().unwrap().session_id, "a");
+    }
+
     #[test]
     fn test_pagination() {
         let mut state = SessionListState::new();
@@ -4,7 +4,8 @@ use ratatui::{
 };
 use retrochat_core::models::message::{ToolResult, ToolUse};
 use retrochat_core::tools::parsers::{
-    bash::BashData, edit::EditData, read::ReadData, write::WriteData, ToolData,
+    bash::BashData, edit::EditData, read::ReadData, todo_write::TodoWriteData, write::WriteData,
+    ToolData,
 };
 use retrochat_core::tools::ToolParsingService;
 
@@ -16,6 +17,8 @@ pub struct ToolDisplayConfig {
     pub show_details: bool,
     /// Maximum lines to show for tool output before truncation
     pub max_output_lines: usize,
+    /// Show each result as a one-line summary, whether or not details are shown
+    pub collapse_results: bool,
 }
 
 impl Default for ToolDisplayConfig {
@@ -24,6 +27,7 @@ impl Default for ToolDisplayConfig {
             width: 80,
             show_details: false,
             max_output_lines: 10,
+            collapse_results: false,
         }
     }
 }
@@ -68,6 +72,52 @@ impl ToolDisplayFormatter {
                     ToolData::Edit(data) => {
                         self.format_edit_tool(&data, result, config, &tool_use.name)
                     }
+                    ToolData::Grep(data) => {
+                        let scope = data.path.as_deref().unwrap_or(".");
+                        let info = match data.glob.as_deref().or(data.file_type.as_deref()) {
+                            Some(filter) => format!("🔍 /{}/ in {scope} ({filter})", data.pattern),
+                            None => format!("🔍 /{}/ in {scope}", data.pattern),
+                        };
+                        self.format_summarized_tool(tool_use, "[Grep] Search", info, result, config)
+                    }
+                    ToolData::Glob(data) => {
+                        let info = format!(
+                            "📂 {} in {}",
+                            data.pattern,
+                            data.path.as_deref().unwrap_or(".")
+                        );
+                        self.format_summarized_tool(
+                            tool_use,
+                            "[Glob] Find files",
+                            info,
+                            result,
+                            config,
+                        )
+                    }
+                    ToolData::WebFetch(data) => {
+                        let title = format!("[WebFetch] {}", data.host().unwrap_or("Fetch"));
+                        let info = format!("🌐 {}", data.url);
+                        self.format_summarized_tool(tool_use, &title, info, result, config)
+                    }
+                    ToolData::TodoWrite(data) => {
+                        let info = todo_progress(&data);
+                        self.format_summarized_tool(
+                            tool_use,
+                            "[TodoWrite] Todos",
+                            info,
+                            result,
+                            config,
+                        )
+                    }
+                    ToolData::NotebookEdit(data) => {
+                        let title = format!("[NotebookEdit] {}", data.notebook_path);
+                        let info = format!(
+                            "📓 {} {} cell",
+                            data.edit_mode(),
+                            data.cell_type.as_deref().unwrap_or("code")
+                        );
+                        self.format_summarized_tool(tool_use, &title, info, result, config)
+                    }
                     ToolData::Unknown => self.format_unknown_tool(tool_use, result, config),
                 },
                 Err(_) => self.format_unknown_tool(tool_use, result, config),
@@ -113,65 +163,48 @@ impl ToolDisplayFormatter {
         ]);
         lines.push(command_line);
 
-        // Show result if available and details enabled
-        if config.show_details {
-            if let Some(result) = result {
+        // Show the result as a summary when collapsed, in full with details enabled
+        if let Some(result) = result {
+            if config.collapse_results {
+                lines.push(Line::from("┃"));
+                lines.push(result_summary_line(result));
+            } else if config.show_details {
                 lines.push(Line::from("┃"));
 
                 // Parse result details for stdout/stderr
                 let (stdout, stderr) = self.extract_bash_output(result);
+                let (mark, mark_color) = if result.is_error {
+                    ("✗", Color::Red)
+                } else {
+                    ("✓", Color::Green)
+                };
 
-                if let Some(stdout_text) = stdout {
-                    if !stdout_text.trim().is_empty() {
-                        let output_lines: Vec<&str> = stdout_text.lines().collect();
-                        let display_lines = if output_lines.len() > config.max_output_lines {
-                            &output_lines[..config.max_output_lines]
-                        } else {
-                            &output_lines[..]
-                        };
-
-                        for line in display_lines {
-                            lines.push(Line::from(vec![
-                                Span::raw("┃ "),
-                                Span::styled(
-                                    if result.is_error { "✗" } else { "✓" },
-                                    Style::default().fg(if result.is_error {
-                                        Color::Red
-                                    } else {
-                                        Color::Green
-                                    }),
-                                ),
-                                Span::raw(" "),
-                                Span::styled(line.to_string(), Style::default().fg(Color::Gray)),
-                            ]));
-                        }
-
-                        if output_lines.len() > config.max_output_lines {
-                            lines.push(Line::from(vec![
-                                Span::raw("┃ "),
-                                Span::styled(
-                                    format!(
-                                        "... ({} more lines)",
-                                        output_lines.len() - config.max_output_lines
-                                    ),
-                                    Style::default()
-                                        .fg(Color::DarkGray)
-                                        .add_modifier(Modifier::ITALIC),
-                                ),
-                            ]));
-                        }
+                if let Some(stdout_text) = stdout.filter(|text| !text.trim().is_empty()) {
+                    let (shown, hidden) = preview_lines(&stdout_text, config.max_output_lines);
+                    for line in shown {
+                        lines.push(Line::from(vec![
+                            Span::raw("┃ "),
+                            Span::styled(mark, Style::default().fg(mark_color)),
+                            Span::raw(" "),
+                            Span::styled(line.to_string(), Style::default().fg(Color::Gray)),
+                        ]));
+                    }
+                    if hidden > 0 {
+                        lines.push(more_lines_line("", hidden, &stdout_text));
                     }
                 }
 
-                if let Some(stderr_text) = stderr {
-                    if !stderr_text.trim().is_empty() {
-                        for line in stderr_text.lines().take(config.max_output_lines) {
-                            lines.push(Line::from(vec![
-                                Span::raw("┃ "),
-                                Span::styled("✗ ", Style::default().fg(Color::Red)),
-                                Span::styled(line.to_string(), Style::default().fg(Color::Red)),
-                            ]));
-                        }
+                if let Some(stderr_text) = stderr.filter(|text| !text.trim().is_empty()) {
+                    let (shown, hidden) = preview_lines(&stderr_text, config.max_output_lines);
+                    for line in shown {
+                        lines.push(Line::from(vec![
+                            Span::raw("┃ "),
+                            Span::styled("✗ ", Style::default().fg(Color::Red)),
+                            Span::styled(line.to_string(), Style::default().fg(Color::Red)),
+                        ]));
+                    }
+                    if hidden > 0 {
+                        lines.push(more_lines_line("", hidden, &stderr_text));
                     }
                 }
             }
@@ -268,13 +301,7 @@ impl ToolDisplayFormatter {
             if let Some(content) = &data.content {
                 lines.push(Line::from("┃"));
 
-                let content_lines: Vec<&str> = content.lines().collect();
-                let max_lines = config.max_output_lines;
-                let display_lines = if content_lines.len() > max_lines {
-                    &content_lines[..max_lines]
-                } else {
-                    &content_lines[..]
-                };
+                let (display_lines, hidden) = preview_lines(content, config.max_output_lines);
 
                 // Show content with line numbers
                 for (idx, line) in display_lines.iter().enumerate() {
@@ -290,16 +317,8 @@ impl ToolDisplayFormatter {
                 }
 
                 // Show truncation indicator if needed
-                if content_lines.len() > max_lines {
-                    lines.push(Line::from(vec![
-                        Span::raw("┃ "),
-                        Span::styled(
-                            format!("    ... ({} more lines)", content_lines.len() - max_lines),
-                            Style::default()
-                                .fg(Color::DarkGray)
-                                .add_modifier(Modifier::ITALIC),
-                        ),
-                    ]));
+                if hidden > 0 {
+                    lines.push(more_lines_line("    ", hidden, content));
                 }
             }
         } else {
@@ -513,23 +532,66 @@ impl ToolDisplayFormatter {
     fn format_unknown_tool(
         &self,
         tool_use: &ToolUse,
-        _result: Option<&ToolResult>,
+        result: Option<&ToolResult>,
+        config: &ToolDisplayConfig,
+    ) -> Vec<Line<'static>> {
+        let title = format!("[{}] Unknown Tool", tool_use.name);
+        let info = Span::styled(
+            format!("❓ Tool type: {}", tool_use.name),
+            Style::default().fg(Color::DarkGray),
+        );
+        self.format_tool_with_info(tool_use, &title, info, result, config)
+    }
+
+    /// Format a tool whose call is described by a single info line
+    fn format_summarized_tool(
+        &self,
+        tool_use: &ToolUse,
+        title: &str,
+        info: String,
+        result: Option<&ToolResult>,
+        config: &ToolDisplayConfig,
+    ) -> Vec<Line<'static>> {
+        let info = Span::styled(info, Style::default().fg(Color::Cyan));
+        self.format_tool_with_info(tool_use, title, info, result, config)
+    }
+
+    /// Bordered box with an info line, then the result per the display config
+    fn format_tool_with_info(
+        &self,
+        tool_use: &ToolUse,
+        title: &str,
+        info: Span<'static>,
+        result: Option<&ToolResult>,
         config: &ToolDisplayConfig,
     ) -> Vec<Line<'static>> {
         let mut lines = Vec::new();
 
         // Top border
-        let title = format!(" [{}] Unknown Tool ", tool_use.name);
+        let title = format!(" {title} ");
         let border_width = config.width.saturating_sub(4);
         lines.push(self.create_tool_border(&title, border_width, true));
 
-        lines.push(Line::from(vec![
-            Span::raw("┃ "),
-            Span::styled(
-                format!("❓ Tool type: {}", tool_use.name),
-                Style::default().fg(Color::DarkGray),
-            ),
-        ]));
+        lines.push(Line::from(vec![Span::raw("┃ "), info]));
+
+        if let Some(result) = result {
+            if config.collapse_results {
+                lines.push(Line::from("┃"));
+                lines.push(result_summary_line(result));
+            } else if config.show_details {
+                lines.push(Line::from("┃"));
+                let (shown, hidden) = preview_lines(&result.content, config.max_output_lines);
+                for line in shown {
+                    lines.push(Line::from(vec![
+                        Span::raw("┃ "),
+                        Span::styled(line.to_string(), Style::default().fg(Color::Gray)),
+                    ]));
+                }
+                if hidden > 0 {
+                    lines.push(more_lines_line("", hidden, &result.content));
+                }
+            }
+        }
 
         // Bottom border
         lines.push(self.create_tool_border(&format!(" {} ", tool_use.name), border_width, false));
@@ -570,6 +632,73 @@ impl ToolDisplayFormatter {
     }
 }
 
+/// "☑ 2/5 done · Writing tests" for a todo list update
+fn todo_progress(data: &TodoWriteData) -> String {
+    let done = format!("☑ {}/{} done", data.completed_count(), data.todos.len());
+    match data.in_progress() {
+        Some(todo) => format!(
+            "{done} · {}",
+            todo.active_form.as_deref().unwrap_or(&todo.content)
+        ),
+        None => done,
+    }
+}
+
+/// The first `max_lines` lines of `text`, and how many lines were left out
+pub fn preview_lines(text: &str, max_lines: usize) -> (Vec<&str>, usize) {
+    let total = text.lines().count();
+    (
+        text.lines().take(max_lines).collect(),
+        total.saturating_sub(max_lines),
+    )
+}
+
+/// "... (N more lines of M)" under a truncated preview
+fn more_lines_line(indent: &str, hidden: usize, text: &str) -> Line<'static> {
+    Line::from(vec![
+        Span::raw("┃ "),
+        Span::styled(
+            format!(
+                "{indent}... ({hidden} more lines of {})",
+                text.lines().count()
+            ),
+            Style::default()
+                .fg(Color::DarkGray)
+                .add_modifier(Modifier::ITALIC),
+        ),
+    ])
+}
+
+/// One line standing in for a collapsed result: outcome, size and first line
+fn result_summary_line(result: &ToolResult) -> Line<'static> {
+    let (mark, color) = if result.is_error {
+        ("✗", Color::Red)
+    } else {
+        ("✓", Color::Green)
+    };
+    let first_line = result
+        .content
+        .lines()
+        .find(|line| !line.trim().is_empty())
+        .unwrap_or("")
+        .chars()
+        .take(60)
+        .collect::<String>();
+    Line::from(vec![
+        Span::raw("┃ "),
+        Span::styled(mark, Style::default().fg(color)),
+        Span::styled(
+            format!(
+                " {} lines collapsed  {first_line}",
+                result.content.lines().count()
+            ),
+            Style::default()
+                .fg(Color::DarkGray)
+                .add_modifier(Modifier::ITALIC),
+        ),
+    ])
+}
+
 impl Default for ToolDisplayFormatter {
     fn default() -> Self {
         Self::new()
@@ -606,6 +735,58 @@ mod tests {
         assert!(lines.len() >= 3);
     }
 
+    #[test]
+    fn test_preview_lines_and_collapsed_results() {
+        let output = (1..=25)
+            .map(|i| format!("line {i}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let (shown, hidden) = preview_lines(&output, 10);
+        assert_eq!(shown.len(), 10);
+        assert_eq!(hidden, 15);
+        assert_eq!(preview_lines("one\ntwo", 10), (vec!["one", "two"], 0));
+
+        let formatter = ToolDisplayFormatter::new();
+        let result = ToolResult {
+            tool_use_id: "test_id".to_string(),
+            content: output.clone(),
+            is_error: false,
+            details: Some(json!({ "stdout": output })),
+            raw: json!({}),
+        };
+        let mut config = ToolDisplayConfig {
+            show_details: true,
+            ..ToolDisplayConfig::default()
+        };
+        let text = |lines: Vec<Line>| {
+            lines
+                .iter()
+                .map(|line| {
+                    line.spans
+                        .iter()
+                        .map(|span| span.content.as_ref())
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        let expanded = text(formatter.format_tools(
+            &[create_bash_tool_use()],
+            std::slice::from_ref(&result),
+            &config,
+        ));
+        assert!(expanded.contains("line 10"));
+        assert!(!expanded.contains("line 11"));
+        assert!(expanded.contains("... (15 more lines of 25)"));
+
+        config.show_details = false;
+        config.collapse_results = true;
+        let collapsed = text(formatter.format_tools(&[create_bash_tool_use()], &[result], &config));
+        assert!(collapsed.contains("25 lines collapsed  line 1"));
+        assert!(!collapsed.contains("line 2\n"));
+    }
+
     #[test]
     fn test_format_multiple_tools() {
         let formatter = ToolDisplayFormatter::new();
@@ -619,4 +800,24 @@ mod tests {
         // Should have lines for both tools plus empty line separator
         assert!(lines.len() >= 6);
     }
+
+    #[test]
+    fn test_format_todo_write_tool() {
+        let formatter = ToolDisplayFormatter::new();
+        let tool_use = ToolUse {
+            id: "todo_id".to_string(),
+            name: "TodoWrite".to_string(),
+            input: json!({
+                "todos": [
+                    { "content": "Add parser", "status": "completed" },
+                    { "content": "Write tests", "status": "in_progress", "activeForm": "Writing tests" }
+                ]
+            }),
+            raw: json!({}),
+        };
+
+        let lines = formatter.format_tools(&[tool_use], &[], &ToolDisplayConfig::default());
+        let text: String = lines[1].spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(text, "┃ ☑ 1/2 done · Writing tests");
+    }
 }
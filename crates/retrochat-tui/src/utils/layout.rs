@@ -39,3 +39,27 @@ pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         ])
         .split(popup_layout[1])[1]
 }
+
+/// Line of a bordered block under a mouse position, counting from the first
+/// line inside the borders
+///
+/// # Returns
+/// `None` when the position is on the borders or outside the block
+///
+/// # Example
+/// ```
+/// use retrochat_tui::utils::layout::inner_row;
+/// use ratatui::layout::Rect;
+///
+/// let block = Rect::new(0, 5, 40, 10);
+/// assert_eq!(inner_row(block, 3, 6), Some(0));
+/// assert_eq!(inner_row(block, 3, 5), None);
+/// assert_eq!(inner_row(block, 39, 8), None);
+/// ```
+pub fn inner_row(area: Rect, column: u16, row: u16) -> Option<usize> {
+    let inside = column > area.x
+        && column + 1 < area.x + area.width
+        && row > area.y
+        && row + 1 < area.y + area.height;
+    inside.then(|| (row - area.y - 1) as usize)
+}
@@ -9,6 +9,7 @@ import {
   FileCode,
   ListChecks,
   Loader2,
+  MessageSquare,
   Target,
   Zap,
 } from 'lucide-react'
@@ -353,6 +354,23 @@ export function AnalyticsPanel({ sessionId }: AnalyticsPanelProps) {
                   {analytics.metric_quantitative_output.file_changes.net_code_growth}
                 </span>
               </div>
+              {analytics.metric_quantitative_output.file_changes.languages.length > 0 && (
+                <div className="space-y-1 border-t pt-3 mt-3">
+                  {analytics.metric_quantitative_output.file_changes.languages
+                    .slice(0, 5)
+                    .map((language) => (
+                      <div key={language.language} className="flex justify-between text-xs">
+                        <span className="text-muted-foreground">
+                          {language.language} ({language.files_touched})
+                        </span>
+                        <span>
+                          <span className="text-green-500">+{language.lines_added}</span>{' '}
+                          <span className="text-red-500">-{language.lines_removed}</span>
+                        </span>
+                      </div>
+                    ))}
+                </div>
+              )}
             </CardContent>
           </Card>
 
@@ -370,12 +388,30 @@ export function AnalyticsPanel({ sessionId }: AnalyticsPanelProps) {
                   {analytics.metric_quantitative_output.time_metrics.total_session_time_minutes} min
                 </span>
               </div>
+              {analytics.metric_quantitative_output.time_metrics.active_time_minutes != null && (
+                <div className="flex justify-between text-sm">
+                  <span className="text-muted-foreground">Active Time</span>
+                  <span className="font-semibold">
+                    {analytics.metric_quantitative_output.time_metrics.active_time_minutes.toFixed(1)} min
+                    {analytics.metric_quantitative_output.time_metrics.idle_gaps > 0 &&
+                      ` (${analytics.metric_quantitative_output.time_metrics.idle_gaps} idle)`}
+                  </span>
+                </div>
+              )}
               <div className="flex justify-between text-sm">
                 <span className="text-muted-foreground">Peak Hours</span>
                 <span className="font-semibold">
                   {analytics.metric_quantitative_output.time_metrics.peak_hours.join(', ')}h
                 </span>
               </div>
+              {analytics.metric_quantitative_output.time_metrics.compactions > 0 && (
+                <div className="flex justify-between text-sm">
+                  <span className="text-muted-foreground">Compactions</span>
+                  <span className="font-semibold">
+                    {analytics.metric_quantitative_output.time_metrics.compactions}
+                  </span>
+                </div>
+              )}
             </CardContent>
           </Card>
 
@@ -414,6 +450,17 @@ export function AnalyticsPanel({ sessionId }: AnalyticsPanelProps) {
                   %
                 </span>
               </div>
+              {analytics.metric_quantitative_output.token_metrics.cache_read_input_tokens > 0 && (
+                <div className="flex justify-between text-sm">
+                  <span className="text-muted-foreground">Cache Hit Ratio</span>
+                  <span className="font-semibold">
+                    {(
+                      analytics.metric_quantitative_output.token_metrics.cache_hit_ratio * 100
+                    ).toFixed(0)}
+                    %
+                  </span>
+                </div>
+              )}
             </CardContent>
           </Card>
 
@@ -443,13 +490,17 @@ export function AnalyticsPanel({ sessionId }: AnalyticsPanelProps) {
                   {analytics.metric_quantitative_output.tool_usage.failed_operations}
                 </span>
               </div>
-              <div className="flex justify-between text-sm border-t pt-3 mt-3">
-                <span className="text-muted-foreground">Avg Exec Time</span>
-                <span className="font-semibold">
-                  {analytics.metric_quantitative_output.tool_usage.average_execution_time_ms}
-                  ms
-                </span>
-              </div>
+              {analytics.metric_quantitative_output.tool_usage.timed_operations > 0 && (
+                <div className="flex justify-between text-sm border-t pt-3 mt-3">
+                  <span className="text-muted-foreground">Exec Time (avg / p50 / p95)</span>
+                  <span className="font-semibold">
+                    {Math.round(analytics.metric_quantitative_output.tool_usage.average_execution_time_ms)}{' '}
+                    / {Math.round(analytics.metric_quantitative_output.tool_usage.p50_execution_time_ms)}{' '}
+                    / {Math.round(analytics.metric_quantitative_output.tool_usage.p95_execution_time_ms)}{' '}
+                    ms
+                  </span>
+                </div>
+              )}
             </CardContent>
           </Card>
         </div>
@@ -483,6 +534,71 @@ export function AnalyticsPanel({ sessionId }: AnalyticsPanelProps) {
           </CardContent>
         </Card>
 
+        {/* Loops and Thrashing */}
+        {analytics.metric_quantitative_output.tool_usage.thrash_segments?.length > 0 && (
+          <Card>
+            <CardHeader>
+              <CardTitle className="flex items-center gap-2">
+                <AlertCircle className="w-5 h-5" />
+                Loops and Thrashing
+              </CardTitle>
+            </CardHeader>
+            <CardContent className="space-y-3">
+              {analytics.metric_quantitative_output.tool_usage.thrash_segments.map((segment) => (
+                <div key={`${segment.kind}-${segment.started_at}`} className="space-y-1">
+                  <div className="flex justify-between gap-4 text-sm">
+                    <span className="truncate">
+                      {segment.tool_name} {segment.target ?? ''}
+                    </span>
+                    <Badge variant="outline">
+                      {segment.kind === 'edit_revert'
+                        ? `${segment.occurrences} reverts`
+                        : `${segment.occurrences}× repeated`}
+                    </Badge>
+                  </div>
+                  <div className="text-xs text-muted-foreground">
+                    {new Date(segment.started_at).toLocaleTimeString()} –{' '}
+                    {new Date(segment.ended_at).toLocaleTimeString()}
+                  </div>
+                </div>
+              ))}
+            </CardContent>
+          </Card>
+        )}
+
+        {/* Costliest Turns */}
+        {analytics.metric_quantitative_output.turns?.length > 0 && (
+          <Card>
+            <CardHeader>
+              <CardTitle className="flex items-center gap-2">
+                <MessageSquare className="w-5 h-5" />
+                Turns by Tokens
+              </CardTitle>
+            </CardHeader>
+            <CardContent className="space-y-3">
+              {[...analytics.metric_quantitative_output.turns]
+                .sort((a, b) => b.total_tokens - a.total_tokens)
+                .slice(0, 5)
+                .map((turn) => (
+                  <div key={turn.turn_number} className="space-y-1">
+                    <div className="flex justify-between gap-4 text-sm">
+                      <span className="truncate">
+                        #{turn.turn_number} {turn.prompt_preview}
+                      </span>
+                      <span className="font-semibold whitespace-nowrap">
+                        {turn.total_tokens.toLocaleString()} tokens
+                      </span>
+                    </div>
+                    <div className="text-xs text-muted-foreground">
+                      {turn.tool_calls} tool calls · {turn.duration_minutes.toFixed(1)} min ·{' '}
+                      {turn.files_touched.length} files
+                    </div>
+                  </div>
+                ))}
+            </CardContent>
+          </Card>
+        )}
+
         {/* AI Qualitative Output - Dynamic Entries */}
         {qualitativeEntries.length > 0 && (
           <Card>
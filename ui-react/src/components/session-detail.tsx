@@ -32,9 +32,10 @@ import { AnalyticsPanel } from './analytics-panel'
 interface SessionDetailProps {
   sessionId: string
   onClose: () => void
+  onSessionSelect?: (sessionId: string) => void
 }
 
-export function SessionDetail({ sessionId, onClose }: SessionDetailProps) {
+export function SessionDetail({ sessionId, onClose, onSessionSelect }: SessionDetailProps) {
   const [session, setSession] = useState<SessionWithMessages | null>(null)
   const [loading, setLoading] = useState(false)
   const [showAnalytics, setShowAnalytics] = useState(false)
@@ -82,7 +83,7 @@ export function SessionDetail({ sessionId, onClose }: SessionDetailProps) {
           <div className="flex items-start justify-between">
             <div>
               <h2 className="text-xl font-semibold text-foreground mb-2">
-                {session.project_name || 'Untitled Session'}
+                {session.title || session.project_name || 'Untitled Session'}
               </h2>
               <div className="flex items-center gap-4 text-sm text-muted-foreground">
                 <span className="flex items-center gap-1">
@@ -135,6 +136,30 @@ export function SessionDetail({ sessionId, onClose }: SessionDetailProps) {
               {session.messages.map((message) => (
                 <MessageRenderer key={message.id} message={message} />
               ))}
+              {session.related_sessions && session.related_sessions.length > 0 && (
+                <div className="border-t pt-4">
+                  <h3 className="text-sm font-medium text-muted-foreground mb-2">
+                    Related sessions
+                  </h3>
+                  <div className="space-y-1">
+                    {session.related_sessions.map((related) => (
+                      <button
+                        type="button"
+                        key={related.id}
+                        className="w-full flex items-center justify-between gap-4 rounded-md px-3 py-2 text-left text-sm hover:bg-accent"
+                        onClick={() => onSessionSelect?.(related.id)}
+                      >
+                        <span className="truncate">
+                          {related.title || related.project_name || 'Untitled Session'}
+                        </span>
+                        <span className="shrink-0 text-xs text-muted-foreground">
+                          {Math.round(related.score * 100)}% similar
+                        </span>
+                      </button>
+                    ))}
+                  </div>
+                </div>
+              )}
             </div>
           </div>
         )}
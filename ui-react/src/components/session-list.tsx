@@ -2,9 +2,10 @@
 
 import { ChevronLeftIcon, ChevronRightIcon } from '@radix-ui/react-icons'
 import { formatDistanceToNow } from 'date-fns'
-import { Filter, Upload } from 'lucide-react'
+import { Bookmark, Filter, Upload } from 'lucide-react'
 import { useCallback, useEffect, useState } from 'react'
 import { Button } from '@/components/ui/button'
+import { Input } from '@/components/ui/input'
 import { Kbd, KbdGroup } from '@/components/ui/kbd'
 import {
   Select,
@@ -14,8 +15,8 @@ import {
   SelectValue,
 } from '@/components/ui/select'
 import { Tooltip, TooltipContent, TooltipTrigger } from '@/components/ui/tooltip'
-import { getProviders, getSessions } from '@/lib/api'
-import type { Session } from '@/types'
+import { getProviders, getSavedViews, getSessions, saveView } from '@/lib/api'
+import type { SavedView, Session } from '@/types'
 
 interface SessionListProps {
   provider: string | null
@@ -39,6 +40,9 @@ export function SessionList({
   const [sessions, setSessions] = useState<Session[]>([])
   const [providers, setProviders] = useState<string[]>([])
   const [page, setPage] = useState(1)
+  const [filterInput, setFilterInput] = useState('')
+  const [filter, setFilter] = useState<string | null>(null)
+  const [views, setViews] = useState<SavedView[]>([])
   const [loading, setLoading] = useState(false)
   const pageSize = 20
 
@@ -51,10 +55,39 @@ export function SessionList({
     }
   }, [])
 
+  const loadViews = useCallback(async () => {
+    try {
+      setViews(await getSavedViews())
+    } catch (error) {
+      console.error('Failed to load saved views:', error)
+    }
+  }, [])
+
+  const applyFilter = (expression: string | null) => {
+    setFilterInput(expression ?? '')
+    setFilter(expression)
+    setPage(1)
+  }
+
+  const handleSaveView = async () => {
+    const expression = filterInput.trim()
+    if (!expression) return
+    const name = window.prompt('Save filter as view named:')?.trim()
+    if (!name) return
+    try {
+      await saveView(name, expression)
+      await loadViews()
+    } catch (error) {
+      console.error('Failed to save view:', error)
+    }
+  }
+
+  const activeView = views.find((v) => v.expression === filter)
+
   const loadSessions = useCallback(async () => {
     setLoading(true)
     try {
-      const data = await getSessions(page, pageSize, provider)
+      const data = await getSessions(page, pageSize, provider, filter)
       setSessions(data)
       onSessionsLoaded?.(data.length > 0)
     } catch (error) {
@@ -63,11 +96,12 @@ export function SessionList({
     } finally {
       setLoading(false)
     }
-  }, [page, provider, onSessionsLoaded])
+  }, [page, provider, filter, onSessionsLoaded])
 
   useEffect(() => {
     loadProviders()
-  }, [loadProviders])
+    loadViews()
+  }, [loadProviders, loadViews])
 
   useEffect(() => {
     loadSessions()
@@ -106,6 +140,57 @@ export function SessionList({
             ))}
           </SelectContent>
         </Select>
+        {views.length > 0 && (
+          <Select
+            value={activeView?.name ?? 'none'}
+            onValueChange={(value) =>
+              applyFilter(views.find((v) => v.name === value)?.expression ?? null)
+            }
+          >
+            <SelectTrigger className="mt-2">
+              <div className="flex items-center gap-2">
+                <Bookmark className="w-4 h-4" />
+                <SelectValue placeholder="Saved Views" />
+              </div>
+            </SelectTrigger>
+            <SelectContent>
+              <SelectItem value="none">No View</SelectItem>
+              {views.map((v) => (
+                <SelectItem key={v.name} value={v.name}>
+                  {v.name}
+                </SelectItem>
+              ))}
+            </SelectContent>
+          </Select>
+        )}
+        <div className="mt-2 flex items-center gap-2">
+          <Input
+            type="text"
+            placeholder='Filter, e.g. tokens>50000 tool:Bash(failed)'
+            value={filterInput}
+            onChange={(e) => setFilterInput(e.target.value)}
+            onKeyDown={(e) => {
+              if (e.key === 'Enter') {
+                applyFilter(filterInput.trim() || null)
+              }
+            }}
+          />
+          <Tooltip>
+            <TooltipTrigger asChild>
+              <Button
+                variant="outline"
+                size="icon"
+                onClick={handleSaveView}
+                disabled={!filterInput.trim()}
+              >
+                <Bookmark className="w-4 h-4" />
+              </Button>
+            </TooltipTrigger>
+            <TooltipContent>
+              <p>Save filter as a view</p>
+            </TooltipContent>
+          </Tooltip>
+        </div>
       </div>
 
       <div className="flex-1 overflow-y-auto min-h-0">
@@ -150,7 +235,7 @@ export function SessionList({
                 >
                   <div className="flex items-start justify-between gap-2 mb-1">
                     <span className="text-sm font-medium text-foreground truncate">
-                      {session.project_name || 'Untitled Session'}
+                      {session.title || session.project_name || 'Untitled Session'}
                     </span>
                     <span className="text-xs text-muted-foreground shrink-0">
                       {formatDistanceToNow(new Date(session.created_at), {
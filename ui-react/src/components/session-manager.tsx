@@ -106,6 +106,17 @@ export function SessionManager() {
     }
   }, [handleFilesImport])
 
+  // Reload the session list when another process (CLI sync, TUI, MCP) writes
+  useEffect(() => {
+    const unlistenChanges = listen('database-changed', () => {
+      setRefreshTrigger((prev) => prev + 1)
+    })
+
+    return () => {
+      unlistenChanges.then((fn) => fn())
+    }
+  }, [])
+
   const handleImport = async () => {
     // Show import method selection dialog
     setImportMethodDialogOpen(true)
@@ -375,7 +386,11 @@ export function SessionManager() {
         {/* Main Content */}
         <div className="flex-1 flex flex-col h-full min-w-0">
           {selectedSession ? (
-            <SessionDetail sessionId={selectedSession} onClose={() => setSelectedSession(null)} />
+            <SessionDetail
+              sessionId={selectedSession}
+              onClose={() => setSelectedSession(null)}
+              onSessionSelect={setSelectedSession}
+            />
           ) : hasAnySessions ? (
             <SessionStatistics provider={provider} />
           ) : (
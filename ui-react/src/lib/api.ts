@@ -4,6 +4,9 @@ import type {
   AnalyticsRequest,
   HistogramRequest,
   HistogramResponse,
+  ImportErrorItem,
+  RetrochatEvent,
+  SavedView,
   SearchResult,
   Session,
   SessionWithMessages,
@@ -14,6 +17,7 @@ const MOCK_SESSIONS: Session[] = [
     id: '550e8400-e29b-41d4-a716-446655440001',
     provider: 'claude',
     project_name: 'Customer Support Dashboard',
+    title: 'Customer Support Dashboard',
     message_count: 24,
     created_at: '2024-01-15T09:30:00Z',
     updated_at: '2024-01-15T11:45:00Z',
@@ -22,6 +26,7 @@ const MOCK_SESSIONS: Session[] = [
     id: '550e8400-e29b-41d4-a716-446655440002',
     provider: 'gpt4',
     project_name: 'Code Review Session',
+    title: 'Code Review Session',
     message_count: 18,
     created_at: '2024-01-14T14:20:00Z',
     updated_at: '2024-01-14T16:10:00Z',
@@ -30,6 +35,7 @@ const MOCK_SESSIONS: Session[] = [
     id: '550e8400-e29b-41d4-a716-446655440003',
     provider: 'claude',
     project_name: 'Blog Post Generation',
+    title: 'Blog Post Generation',
     message_count: 12,
     created_at: '2024-01-13T10:00:00Z',
     updated_at: '2024-01-13T11:30:00Z',
@@ -38,6 +44,7 @@ const MOCK_SESSIONS: Session[] = [
     id: '550e8400-e29b-41d4-a716-446655440004',
     provider: 'gpt4',
     project_name: 'Learning TypeScript',
+    title: 'Learning TypeScript',
     message_count: 35,
     created_at: '2024-01-12T08:15:00Z',
     updated_at: '2024-01-12T12:00:00Z',
@@ -46,6 +53,7 @@ const MOCK_SESSIONS: Session[] = [
     id: '550e8400-e29b-41d4-a716-446655440005',
     provider: 'gemini',
     project_name: 'Data Analysis Task',
+    title: 'Data Analysis Task',
     message_count: 16,
     created_at: '2024-01-11T15:45:00Z',
     updated_at: '2024-01-11T17:20:00Z',
@@ -54,6 +62,7 @@ const MOCK_SESSIONS: Session[] = [
     id: '550e8400-e29b-41d4-a716-446655440006',
     provider: 'claude',
     project_name: 'Email Draft Assistant',
+    title: 'Email Draft Assistant',
     message_count: 8,
     created_at: '2024-01-10T13:30:00Z',
     updated_at: '2024-01-10T14:15:00Z',
@@ -103,6 +112,7 @@ const MOCK_SESSION_DETAILS: Record<string, SessionWithMessages> = {
           timestamp: '2024-01-15T09:31:00Z',
           success: null,
           result_summary: null,
+          duration_ms: null,
           file_metadata: {
             file_path: 'components/dashboard-layout.tsx',
             file_extension: 'tsx',
@@ -126,6 +136,7 @@ const MOCK_SESSION_DETAILS: Record<string, SessionWithMessages> = {
           timestamp: '2024-01-15T09:31:05Z',
           success: true,
           result_summary: 'Created dashboard layout component with responsive design',
+          duration_ms: null,
           file_metadata: {
             file_path: 'components/dashboard-layout.tsx',
             file_extension: 'tsx',
@@ -166,6 +177,7 @@ const MOCK_SESSION_DETAILS: Record<string, SessionWithMessages> = {
           timestamp: '2024-01-15T09:32:30Z',
           success: null,
           result_summary: null,
+          duration_ms: null,
           file_metadata: {
             file_path: 'components/metrics-section.tsx',
             file_extension: 'tsx',
@@ -189,6 +201,7 @@ const MOCK_SESSION_DETAILS: Record<string, SessionWithMessages> = {
           timestamp: '2024-01-15T09:32:45Z',
           success: true,
           result_summary: 'Created metrics component with recharts integration',
+          duration_ms: null,
           file_metadata: {
             file_path: 'components/metrics-section.tsx',
             file_extension: 'tsx',
@@ -229,6 +242,7 @@ const MOCK_SESSION_DETAILS: Record<string, SessionWithMessages> = {
           timestamp: '2024-01-15T09:35:30Z',
           success: null,
           result_summary: null,
+          duration_ms: null,
           file_metadata: {
             file_path: 'hooks/use-realtime-data.ts',
             file_extension: 'ts',
@@ -252,6 +266,7 @@ const MOCK_SESSION_DETAILS: Record<string, SessionWithMessages> = {
           timestamp: '2024-01-15T09:35:50Z',
           success: true,
           result_summary: 'Implemented custom hook with SWR for real-time data updates',
+          duration_ms: null,
           file_metadata: {
             file_path: 'hooks/use-realtime-data.ts',
             file_extension: 'ts',
@@ -275,6 +290,7 @@ const MOCK_SESSION_DETAILS: Record<string, SessionWithMessages> = {
           timestamp: '2024-01-15T09:36:00Z',
           success: null,
           result_summary: null,
+          duration_ms: null,
           file_metadata: {
             file_path: 'components/metrics-section.tsx',
             file_extension: 'tsx',
@@ -298,6 +314,7 @@ const MOCK_SESSION_DETAILS: Record<string, SessionWithMessages> = {
           timestamp: '2024-01-15T09:36:20Z',
           success: true,
           result_summary: 'Integrated real-time hook with automatic data refresh every 5 seconds',
+          duration_ms: null,
           file_metadata: {
             file_path: 'components/metrics-section.tsx',
             file_extension: 'tsx',
@@ -347,6 +364,7 @@ const MOCK_SESSION_DETAILS: Record<string, SessionWithMessages> = {
           timestamp: '2024-01-15T09:40:30Z',
           success: null,
           result_summary: null,
+          duration_ms: null,
           file_metadata: {
             file_path: 'components/ticket-search.tsx',
             file_extension: 'tsx',
@@ -370,6 +388,7 @@ const MOCK_SESSION_DETAILS: Record<string, SessionWithMessages> = {
           timestamp: '2024-01-15T09:40:45Z',
           success: false,
           result_summary: "Import error: Cannot find module 'use-debounce'",
+          duration_ms: null,
           file_metadata: {
             file_path: 'components/ticket-search.tsx',
             file_extension: 'tsx',
@@ -402,6 +421,7 @@ const MOCK_SESSION_DETAILS: Record<string, SessionWithMessages> = {
           timestamp: '2024-01-15T09:41:15Z',
           success: null,
           result_summary: null,
+          duration_ms: null,
           file_metadata: {
             file_path: 'hooks/use-debounce.ts',
             file_extension: 'ts',
@@ -425,6 +445,7 @@ const MOCK_SESSION_DETAILS: Record<string, SessionWithMessages> = {
           timestamp: '2024-01-15T09:41:25Z',
           success: true,
           result_summary: 'Custom debounce hook with configurable delay',
+          duration_ms: null,
           file_metadata: {
             file_path: 'hooks/use-debounce.ts',
             file_extension: 'ts',
@@ -448,6 +469,7 @@ const MOCK_SESSION_DETAILS: Record<string, SessionWithMessages> = {
           timestamp: '2024-01-15T09:41:35Z',
           success: null,
           result_summary: null,
+          duration_ms: null,
           file_metadata: {
             file_path: 'components/ticket-search.tsx',
             file_extension: 'tsx',
@@ -471,6 +493,7 @@ const MOCK_SESSION_DETAILS: Record<string, SessionWithMessages> = {
           timestamp: '2024-01-15T09:41:50Z',
           success: true,
           result_summary: 'Search component with debouncing and keyboard shortcuts',
+          duration_ms: null,
           file_metadata: {
             file_path: 'components/ticket-search.tsx',
             file_extension: 'tsx',
@@ -639,16 +662,28 @@ const MOCK_ANALYTICS: Record<string, Analytics> = {
         lines_added: 543,
         lines_removed: 127,
         net_code_growth: 416,
+        languages: [
+          { language: 'TypeScript', files_touched: 6, lines_added: 421, lines_removed: 98 },
+          { language: 'CSS', files_touched: 2, lines_added: 122, lines_removed: 29 },
+        ],
       },
       time_metrics: {
         total_session_time_minutes: 135,
+        active_time_minutes: 95,
+        idle_gaps: 1,
         peak_hours: [14, 15, 16],
+        compactions: 1,
+        segment_minutes: [95, 40],
       },
       token_metrics: {
         total_tokens_used: 45320,
         input_tokens: 28500,
         output_tokens: 16820,
         token_efficiency: 0.89,
+        peak_context_tokens: 31200,
+        cache_creation_input_tokens: 12400,
+        cache_read_input_tokens: 184300,
+        cache_hit_ratio: 0.82,
       },
       tool_usage: {
         total_operations: 42,
@@ -661,7 +696,38 @@ const MOCK_ANALYTICS: Record<string, Analytics> = {
           list_files: 3,
         },
         average_execution_time_ms: 245,
+        timed_operations: 24,
+        p50_execution_time_ms: 120,
+        p95_execution_time_ms: 980,
+        thrash_segments: [
+          {
+            kind: 'repeated_call',
+            tool_name: 'Bash',
+            target: 'npm test',
+            occurrences: 4,
+            started_at: '2024-01-15T15:12:00Z',
+            ended_at: '2024-01-15T15:19:00Z',
+          },
+        ],
       },
+      turns: [
+        {
+          turn_number: 1,
+          prompt_preview: 'Add a dark mode toggle to the settings page',
+          total_tokens: 31200,
+          tool_calls: 30,
+          duration_minutes: 62,
+          files_touched: ['src/settings.tsx', 'src/theme.ts'],
+        },
+        {
+          turn_number: 2,
+          prompt_preview: 'Fix the failing snapshot tests',
+          total_tokens: 14120,
+          tool_calls: 12,
+          duration_minutes: 33,
+          files_touched: ['src/__tests__/settings.test.tsx'],
+        },
+      ],
     },
     model_used: 'gemini-2.0-flash',
     analysis_duration_ms: 3456,
@@ -765,16 +831,28 @@ const MOCK_ANALYTICS: Record<string, Analytics> = {
         lines_added: 324,
         lines_removed: 89,
         net_code_growth: 235,
+        languages: [
+          { language: 'Rust', files_touched: 4, lines_added: 301, lines_removed: 80 },
+          { language: 'TOML', files_touched: 1, lines_added: 23, lines_removed: 9 },
+        ],
       },
       time_metrics: {
         total_session_time_minutes: 110,
+        active_time_minutes: 110,
+        idle_gaps: 0,
         peak_hours: [10, 11],
+        compactions: 0,
+        segment_minutes: [110],
       },
       token_metrics: {
         total_tokens_used: 32100,
         input_tokens: 20000,
         output_tokens: 12100,
         token_efficiency: 0.85,
+        peak_context_tokens: 32100,
+        cache_creation_input_tokens: 0,
+        cache_read_input_tokens: 0,
+        cache_hit_ratio: 0,
       },
       tool_usage: {
         total_operations: 28,
@@ -787,7 +865,12 @@ const MOCK_ANALYTICS: Record<string, Analytics> = {
           list_files: 1,
         },
         average_execution_time_ms: 220,
+        timed_operations: 16,
+        p50_execution_time_ms: 110,
+        p95_execution_time_ms: 870,
+        thrash_segments: [],
       },
+      turns: [],
     },
     model_used: 'gemini-2.0-flash',
     analysis_duration_ms: 2890,
@@ -817,10 +900,12 @@ const MOCK_SEARCH_RESULTS: SearchResult[] = [
 export async function getSessions(
   page = 1,
   pageSize = 20,
-  provider: string | null = null
+  provider: string | null = null,
+  filter: string | null = null,
+  owner: string | null = null
 ): Promise<Session[]> {
   try {
-    return await invoke('get_sessions', { page, pageSize, provider })
+    return await invoke('get_sessions', { page, pageSize, provider, filter, owner })
   } catch (_error) {
     console.log('[v0] Using mock data for getSessions')
     let filtered = MOCK_SESSIONS
@@ -1019,3 +1104,44 @@ export async function getUserMessageHistogram(
 ): Promise<HistogramResponse> {
   return await invoke('get_user_message_histogram', { request })
 }
+
+/**
+ * Get files whose most recent import attempt failed, newest first
+ */
+export async function getImportErrors(limit?: number): Promise<ImportErrorItem[]> {
+  return await invoke('get_import_errors', { limit })
+}
+
+/**
+ * Get saved filter expressions, by name
+ */
+export async function getSavedViews(): Promise<SavedView[]> {
+  try {
+    return await invoke('get_saved_views')
+  } catch (_error) {
+    console.log('[v0] Using mock data for getSavedViews')
+    return []
+  }
+}
+
+/**
+ * Save a filter expression under a name, replacing any view with that name
+ */
+export async function saveView(name: string, expression: string): Promise<SavedView> {
+  return await invoke('save_view', { name, expression })
+}
+
+/**
+ * Delete a saved view
+ */
+export async function deleteView(name: string): Promise<boolean> {
+  return await invoke('delete_view', { name })
+}
+
+/**
+ * Get change feed events after a cursor (oldest first), or the most recent
+ * ones without a cursor
+ */
+export async function getEvents(cursor?: number, limit?: number): Promise<RetrochatEvent[]> {
+  return await invoke('get_events', { cursor, limit })
+}
@@ -2,9 +2,12 @@ export interface Session {
   id: string
   provider: string
   project_name: string | null
+  title: string | null
   message_count: number
   created_at: string
   updated_at: string
+  /** Teammate the session was imported from; null for own sessions */
+  owner?: string | null
 }
 
 export interface FileMetadata {
@@ -22,6 +25,7 @@ export interface ToolOperation {
   timestamp: string
   success: boolean | null
   result_summary: string | null
+  duration_ms: number | null
   file_metadata: FileMetadata | null
   bash_metadata: Record<string, unknown> | null
   raw_input: Record<string, unknown> | null
@@ -37,8 +41,18 @@ export interface Message {
   tool_operation: ToolOperation | null
 }
 
+export interface RelatedSession {
+  id: string
+  provider: string
+  project_name: string | null
+  title: string | null
+  created_at: string
+  score: number
+}
+
 export interface SessionWithMessages extends Session {
   messages: Message[]
+  related_sessions?: RelatedSession[]
 }
 
 export interface SearchResult {
@@ -68,11 +82,23 @@ export interface FileChangeMetrics {
   lines_added: number
   lines_removed: number
   net_code_growth: number
+  languages: LanguageChangeMetrics[]
+}
+
+export interface LanguageChangeMetrics {
+  language: string
+  files_touched: number
+  lines_added: number
+  lines_removed: number
 }
 
 export interface TimeConsumptionMetrics {
   total_session_time_minutes: number
+  active_time_minutes: number | null
+  idle_gaps: number
   peak_hours: number[]
+  compactions: number
+  segment_minutes: number[]
 }
 
 export interface TokenConsumptionMetrics {
@@ -80,6 +106,10 @@ export interface TokenConsumptionMetrics {
   input_tokens: number
   output_tokens: number
   token_efficiency: number
+  peak_context_tokens: number
+  cache_creation_input_tokens: number
+  cache_read_input_tokens: number
+  cache_hit_ratio: number
 }
 
 export interface ToolUsageMetrics {
@@ -88,6 +118,19 @@ export interface ToolUsageMetrics {
   failed_operations: number
   tool_distribution: Record<string, number>
   average_execution_time_ms: number
+  timed_operations: number
+  p50_execution_time_ms: number
+  p95_execution_time_ms: number
+  thrash_segments: ThrashSegment[]
+}
+
+export interface ThrashSegment {
+  kind: 'repeated_call' | 'edit_revert'
+  tool_name: string
+  target: string | null
+  occurrences: number
+  started_at: string
+  ended_at: string
 }
 
 export interface MetricQuantitativeOutput {
@@ -95,6 +138,17 @@ export interface MetricQuantitativeOutput {
   time_metrics: TimeConsumptionMetrics
   token_metrics: TokenConsumptionMetrics
   tool_usage: ToolUsageMetrics
+  rework_rate: number
+  turns: TurnUsageMetrics[]
+}
+
+export interface TurnUsageMetrics {
+  turn_number: number
+  prompt_preview: string
+  total_tokens: number
+  tool_calls: number
+  duration_minutes: number
+  files_touched: string[]
 }
 
 // Summary of qualitative evaluation
@@ -185,3 +239,27 @@ export interface HistogramResponse {
   end_time: string
   interval_minutes: number
 }
+
+export interface SavedView {
+  name: string
+  expression: string
+  updated_at: string // ISO 8601
+}
+
+// One entry of the change feed; new ones arrive as 'retrochat-event' emits
+export interface RetrochatEvent {
+  id: number // Increasing; pass the last one seen as the cursor to getEvents
+  kind: string // e.g. session_imported, analysis_completed, view_saved
+  session_id: string | null
+  payload: Record<string, unknown>
+  created_at: string // ISO 8601
+}
+
+export interface ImportErrorItem {
+  file_path: string
+  provider: string | null
+  error_message: string
+  attempt_count: number
+  first_attempt_at: string // ISO 8601
+  last_attempt_at: string // ISO 8601
+}